@@ -9,6 +9,7 @@ use omicron_common::api::external::Error;
 use openssl::asn1::Asn1Time;
 use openssl::pkey::PKey;
 use openssl::x509::X509;
+use openssl::x509::X509Ref;
 use std::borrow::Borrow;
 use std::ffi::CString;
 
@@ -197,6 +198,39 @@ impl CertificateValidator {
     }
 }
 
+/// Returns when the first of the certificates in `certs` expires, in seconds
+/// since the Unix epoch.
+///
+/// `certs` is expected to be one or more certificates in PEM format, such as
+/// a certificate chain or a bundle of trusted CA certificates.
+pub fn first_expiry(certs: &[u8]) -> Result<i64, CertificateError> {
+    let certs = X509::stack_from_pem(certs)
+        .map_err(CertificateError::BadCertificate)?;
+    let mut earliest = None;
+    for cert in &certs {
+        let expiry = expiry(cert)?;
+        earliest = Some(earliest.map_or(expiry, |e: i64| e.min(expiry)));
+    }
+    earliest.ok_or(CertificateError::CertificateEmpty)
+}
+
+/// Returns when the DER-encoded certificate `cert` expires, in seconds since
+/// the Unix epoch.
+///
+/// This is the form in which TLS peers' certificates are reported.
+pub fn der_expiry(cert: &[u8]) -> Result<i64, CertificateError> {
+    let cert =
+        X509::from_der(cert).map_err(CertificateError::BadCertificate)?;
+    expiry(&cert)
+}
+
+fn expiry(cert: &X509Ref) -> Result<i64, CertificateError> {
+    let epoch = Asn1Time::from_unix(0).map_err(CertificateError::Unexpected)?;
+    let diff =
+        epoch.diff(cert.not_after()).map_err(CertificateError::Unexpected)?;
+    Ok(i64::from(diff.days) * 86_400 + i64::from(diff.secs))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -444,4 +478,45 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_first_expiry() {
+        // rcgen's default validity ends in the year 4096, so the leaf is the
+        // first certificate in each of these chains to expire.
+        let mut params = CertificateParams::new(vec![]);
+        params.not_after = rcgen::date_time_ymd(2000, 1, 1);
+        let chain = CertificateChain::with_params(params);
+        assert_eq!(
+            first_expiry(chain.cert_chain_as_pem().as_bytes()).unwrap(),
+            946_684_800,
+        );
+
+        let unlimited = CertificateChain::new("oxide.computer");
+        assert!(
+            first_expiry(unlimited.cert_chain_as_pem().as_bytes()).unwrap()
+                > 946_684_800
+        );
+
+        assert!(matches!(
+            first_expiry(b""),
+            Err(CertificateError::CertificateEmpty)
+        ));
+    }
+
+    #[test]
+    fn test_der_expiry() {
+        let mut params = CertificateParams::new(vec![]);
+        params.not_after = rcgen::date_time_ymd(2000, 1, 1);
+        let chain = CertificateChain::with_params(params);
+        let der = X509::from_pem(chain.cert_chain_as_pem().as_bytes())
+            .unwrap()
+            .to_der()
+            .unwrap();
+        assert_eq!(der_expiry(&der).unwrap(), 946_684_800);
+
+        assert!(matches!(
+            der_expiry(b"not a certificate"),
+            Err(CertificateError::BadCertificate(_))
+        ));
+    }
 }
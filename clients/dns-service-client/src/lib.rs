@@ -22,7 +22,20 @@ progenitor::generate_api!(
         DnsConfigParams = internal_dns_types_versions::latest::config::DnsConfigParams,
         DnsConfigZone = internal_dns_types_versions::latest::config::DnsConfigZone,
         DnsRecord = internal_dns_types_versions::latest::config::DnsRecord,
+        DnssecAlgorithm = internal_dns_types_versions::latest::config::DnssecAlgorithm,
+        DnssecConfig = internal_dns_types_versions::latest::config::DnssecConfig,
+        DnssecConfigParams = internal_dns_types_versions::latest::config::DnssecConfigParams,
+        DnssecKey = internal_dns_types_versions::latest::config::DnssecKey,
+        DnssecKeyRole = internal_dns_types_versions::latest::config::DnssecKeyRole,
+        DnssecKeyState = internal_dns_types_versions::latest::config::DnssecKeyState,
+        DnssecPublicKey = internal_dns_types_versions::latest::config::DnssecPublicKey,
+        DnssecZone = internal_dns_types_versions::latest::config::DnssecZone,
+        DnssecZoneStatus = internal_dns_types_versions::latest::config::DnssecZoneStatus,
         Srv = internal_dns_types_versions::latest::config::Srv,
+        VpcDnsClient = internal_dns_types_versions::latest::config::VpcDnsClient,
+        VpcDnsConfig = internal_dns_types_versions::latest::config::VpcDnsConfig,
+        VpcDnsConfigParams = internal_dns_types_versions::latest::config::VpcDnsConfigParams,
+        VpcDnsZone = internal_dns_types_versions::latest::config::VpcDnsZone,
     }
 );
 
@@ -71,6 +71,10 @@ progenitor::generate_api!(
         Inventory = sled_agent_types_versions::latest::inventory::Inventory,
         InventoryDisk = sled_agent_types_versions::latest::inventory::InventoryDisk,
         InventoryZpool = sled_agent_types_versions::latest::inventory::InventoryZpool,
+        LedgerCopyReport = sled_agent_types_versions::latest::inventory::LedgerCopyReport,
+        LedgerCopyStatus = sled_agent_types_versions::latest::inventory::LedgerCopyStatus,
+        LedgerRepairOutcome = sled_agent_types_versions::latest::inventory::LedgerRepairOutcome,
+        LedgerReport = sled_agent_types_versions::latest::inventory::LedgerReport,
         LinkFec = sled_agent_types_versions::latest::early_networking::LinkFec,
         LinkSpeed = sled_agent_types_versions::latest::early_networking::LinkSpeed,
         LldpAdminStatus = sled_agent_types_versions::latest::early_networking::LldpAdminStatus,
@@ -1009,6 +1009,7 @@ pub enum ResourceType {
     AntiAffinityGroup,
     AntiAffinityGroupMember,
     AuditLogEntry,
+    AuditLogSink,
     BackgroundTask,
     BgpAnnounceSet,
     BgpConfig,
@@ -1032,6 +1033,8 @@ pub enum ResourceType {
     IpPool,
     IpPoolResource,
     LldpLinkConfig,
+    LoadBalancer,
+    LoadBalancerBackend,
     LoopbackAddress,
     MetricProducer,
     MulticastGroup,
@@ -1050,6 +1053,8 @@ pub enum ResourceType {
     SamlIdentityProvider,
     ScimClientBearerToken,
     Service,
+    ServiceAccount,
+    ServiceAccountToken,
     ServiceNetworkInterface,
     Silo,
     SiloAuthSettings,
@@ -1776,6 +1781,10 @@ pub struct VpcFirewallRule {
 #[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
 pub struct VpcFirewallRules {
     pub rules: Vec<VpcFirewallRule>,
+    /// Generation of the VPC's rule set, which changes whenever any of its
+    /// rules change. Requests that edit individual rules must supply this
+    /// value and fail if it is stale.
+    pub generation: Generation,
 }
 
 /// A single rule in a VPC firewall
@@ -1800,6 +1809,21 @@ pub struct VpcFirewallRuleUpdate {
     pub priority: VpcFirewallRulePriority,
 }
 
+impl From<VpcFirewallRule> for VpcFirewallRuleUpdate {
+    fn from(rule: VpcFirewallRule) -> Self {
+        Self {
+            name: rule.identity.name,
+            description: rule.identity.description,
+            status: rule.status,
+            direction: rule.direction,
+            targets: rule.targets,
+            filters: rule.filters,
+            action: rule.action,
+            priority: rule.priority,
+        }
+    }
+}
+
 /// Updated list of firewall rules. Will replace all existing rules.
 #[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
 pub struct VpcFirewallRuleUpdateParams {
@@ -2012,9 +2036,9 @@ pub enum VpcFirewallRuleAction {
 
 /// A `VpcFirewallRuleTarget` is used to specify the set of instances to which
 /// a firewall rule applies. You can target instances directly by name, or
-/// specify a VPC, VPC subnet, IP, or IP subnet, which will apply the rule to
-/// traffic going to all matching instances. Targets are additive: the rule
-/// applies to instances matching ANY target.
+/// specify a VPC, VPC subnet, IP, IP subnet, or network tag, which will apply
+/// the rule to traffic going to all matching instances. Targets are additive:
+/// the rule applies to instances matching ANY target.
 #[derive(
     Clone,
     Debug,
@@ -2038,8 +2062,8 @@ pub enum VpcFirewallRuleTarget {
     Ip(IpAddr),
     /// The rule applies to a specific IP subnet
     IpNet(oxnet::IpNet),
-    // Tags not yet implemented
-    // Tag(Name),
+    /// The rule applies to all instances carrying this network tag
+    Tag(Name),
 }
 
 /// The `VpcFirewallRuleHostFilter` is used to filter traffic on the basis of
@@ -2063,12 +2087,13 @@ pub enum VpcFirewallRuleHostFilter {
     Subnet(Name),
     /// The rule applies to traffic from/to this specific instance
     Instance(Name),
-    // Tags not yet implemented
-    // Tag(Name),
     /// The rule applies to traffic from/to a specific IP address
     Ip(IpAddr),
     /// The rule applies to traffic from/to a specific IP subnet
     IpNet(oxnet::IpNet),
+    /// The rule applies to traffic from/to all instances carrying this
+    /// network tag
+    Tag(Name),
     // TODO: Internet gateways not yet implemented
     // #[display("inetgw:{0}")]
     // InternetGateway(Name),
@@ -3658,9 +3683,13 @@ mod test {
             "subnet:foo".parse().unwrap()
         );
         assert_eq!(
-            VpcFirewallRuleTarget::Instance(name),
+            VpcFirewallRuleTarget::Instance(name.clone()),
             "instance:foo".parse().unwrap()
         );
+        assert_eq!(
+            VpcFirewallRuleTarget::Tag(name),
+            "tag:foo".parse().unwrap()
+        );
         assert_eq!(
             VpcFirewallRuleTarget::Ip(address),
             "ip:192.168.0.10".parse().unwrap()
@@ -3687,9 +3716,13 @@ mod test {
             "subnet:foo".parse().unwrap()
         );
         assert_eq!(
-            VpcFirewallRuleHostFilter::Instance(name),
+            VpcFirewallRuleHostFilter::Instance(name.clone()),
             "instance:foo".parse().unwrap()
         );
+        assert_eq!(
+            VpcFirewallRuleHostFilter::Tag(name),
+            "tag:foo".parse().unwrap()
+        );
         assert_eq!(
             VpcFirewallRuleHostFilter::Ip(address),
             "ip:192.168.0.10".parse().unwrap()
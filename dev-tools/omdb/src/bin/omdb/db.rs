@@ -7276,7 +7276,12 @@ fn print_name(
     if records.len() == 1 {
         match &records[0] {
             DnsRecord::Srv(_) => (),
-            DnsRecord::Aaaa(_) | DnsRecord::A(_) | DnsRecord::Ns(_) => {
+            DnsRecord::Aaaa(_)
+            | DnsRecord::A(_)
+            | DnsRecord::Ns(_)
+            | DnsRecord::Txt(_)
+            | DnsRecord::Cname(_)
+            | DnsRecord::Ptr(_) => {
                 println!(
                     "{}  {:50} {}",
                     prefix,
@@ -7302,6 +7307,9 @@ fn format_record(record: &DnsRecord) -> impl Display + use<> {
             format!("SRV  port {:5} {}", port, target)
         }
         DnsRecord::Ns(ns) => format!("NS   {}", ns),
+        DnsRecord::Txt(text) => format!("TXT  {:?}", text),
+        DnsRecord::Cname(target) => format!("CNAME {}", target),
+        DnsRecord::Ptr(ptrdname) => format!("PTR  {}", ptrdname),
     }
 }
 
@@ -852,6 +852,7 @@ async fn cmd_db_webhook_delivery_info(
                  response_duration,
                  time_created,
                  deliverator_id,
+                 time_peer_cert_expires: _,
              }| DeliveryAttemptRow {
                 id: id.into_untyped_uuid(),
                 attempt: attempt.0,
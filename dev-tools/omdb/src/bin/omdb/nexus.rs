@@ -44,6 +44,7 @@ use nexus_lockstep_client::types::PhysicalDiskPath;
 use nexus_lockstep_client::types::SagaState;
 use nexus_lockstep_client::types::SledSelector;
 use nexus_saga_recovery::LastPass;
+use nexus_types::alert::certificate::CertificateKind;
 use nexus_types::deployment::Blueprint;
 use nexus_types::deployment::ClickhouseMode;
 use nexus_types::deployment::ClickhousePolicy;
@@ -51,19 +52,24 @@ use nexus_types::deployment::OximeterReadMode;
 use nexus_types::deployment::OximeterReadPolicy;
 use nexus_types::fm;
 use nexus_types::internal_api::background::AbandonedVmmReaperStatus;
+use nexus_types::internal_api::background::AcmeCertificatesStatus;
 use nexus_types::internal_api::background::AttachedSubnetManagerStatus;
 use nexus_types::internal_api::background::AuditLogCleanupStatus;
+use nexus_types::internal_api::background::AuditLogExportStatus;
 use nexus_types::internal_api::background::AuditLogTimeoutIncompleteStatus;
 use nexus_types::internal_api::background::BlueprintPlannerStatus;
 use nexus_types::internal_api::background::BlueprintRendezvousStats;
 use nexus_types::internal_api::background::BlueprintRendezvousStatus;
+use nexus_types::internal_api::background::CertificateExpiryStatus;
 use nexus_types::internal_api::background::DatasetsRendezvousStats;
+use nexus_types::internal_api::background::DnssecKeysStatus;
 use nexus_types::internal_api::background::EreporterStatus;
 use nexus_types::internal_api::background::FmAnalysisStatus;
 use nexus_types::internal_api::background::FmRendezvousStatus;
 use nexus_types::internal_api::background::InstanceReincarnationStatus;
 use nexus_types::internal_api::background::InstanceUpdaterStatus;
 use nexus_types::internal_api::background::InventoryLoadStatus;
+use nexus_types::internal_api::background::LoadBalancerManagerStatus;
 use nexus_types::internal_api::background::LookupRegionPortStatus;
 use nexus_types::internal_api::background::PhysicalDiskAdoptionStatus;
 use nexus_types::internal_api::background::ProbeDistributorStatus;
@@ -78,6 +84,7 @@ use nexus_types::internal_api::background::ServiceFirewallRuleStatus;
 use nexus_types::internal_api::background::SessionCleanupStatus;
 use nexus_types::internal_api::background::SitrepGcStatus;
 use nexus_types::internal_api::background::SitrepLoadStatus;
+use nexus_types::internal_api::background::SledEvacuatorStatus;
 use nexus_types::internal_api::background::SupportBundleCleanupReport;
 use nexus_types::internal_api::background::SupportBundleCollectionReport;
 use nexus_types::internal_api::background::SupportBundleCollectionStepStatus;
@@ -89,6 +96,7 @@ use nexus_types::internal_api::background::TufArtifactReplicationCounters;
 use nexus_types::internal_api::background::TufArtifactReplicationRequest;
 use nexus_types::internal_api::background::TufArtifactReplicationStatus;
 use nexus_types::internal_api::background::TufRepoPrunerStatus;
+use nexus_types::internal_api::background::VpcDnsStatus;
 use nexus_types::internal_api::background::fm_rendezvous;
 use omicron_uuid_kinds::BlueprintUuid;
 use omicron_uuid_kinds::CollectionUuid;
@@ -160,6 +168,8 @@ enum NexusCommands {
     BackgroundTasks(BackgroundTasksArgs),
     /// interact with blueprints
     Blueprints(BlueprintsArgs),
+    /// print when stored certificates expire
+    Certificates,
     /// interact with clickhouse policy
     ClickhousePolicy(ClickhousePolicyArgs),
     /// fetch an omdb binary associated with an active Nexus
@@ -806,6 +816,10 @@ impl NexusArgs {
                 cmd_nexus_blueprints_import(&client, token, args).await
             }
 
+            NexusCommands::Certificates => {
+                cmd_nexus_certificates(&client).await
+            }
+
             NexusCommands::ClickhousePolicy(ClickhousePolicyArgs {
                 command,
             }) => match command {
@@ -1249,12 +1263,18 @@ fn print_task_details(bgtask: &BackgroundTask, details: &serde_json::Value) {
         "abandoned_vmm_reaper" => {
             print_task_abandoned_vmm_reaper(details);
         }
+        "acme_certificates" => {
+            print_task_acme_certificates(details);
+        }
         "attached_subnet_manager" => {
             print_task_attached_subnet_manager_status(details);
         }
         "audit_log_cleanup" => {
             print_task_audit_log_cleanup(details);
         }
+        "audit_log_export" => {
+            print_task_audit_log_export(details);
+        }
         "audit_log_timeout_incomplete" => {
             print_task_audit_log_timeout_incomplete(details);
         }
@@ -1270,6 +1290,9 @@ fn print_task_details(bgtask: &BackgroundTask, details: &serde_json::Value) {
         "blueprint_rendezvous" => {
             print_task_blueprint_rendezvous(details);
         }
+        "certificate_expiry" => {
+            print_task_certificate_expiry(details);
+        }
         "dns_config_external" | "dns_config_internal" => {
             print_task_dns_config(details);
         }
@@ -1279,6 +1302,9 @@ fn print_task_details(bgtask: &BackgroundTask, details: &serde_json::Value) {
         "dns_propagation_internal" | "dns_propagation_external" => {
             print_task_dns_propagation(details);
         }
+        "dnssec_keys" => {
+            print_task_dnssec_keys(details);
+        }
         "external_endpoints" => {
             print_task_external_endpoints(details);
         }
@@ -1297,6 +1323,9 @@ fn print_task_details(bgtask: &BackgroundTask, details: &serde_json::Value) {
         "inventory_loader" => {
             print_task_inventory_load(details);
         }
+        "load_balancer_manager" => {
+            print_task_load_balancer_manager(details);
+        }
         "lookup_region_port" => {
             print_task_lookup_region_port(details);
         }
@@ -1342,6 +1371,9 @@ fn print_task_details(bgtask: &BackgroundTask, details: &serde_json::Value) {
         "session_cleanup" => {
             print_task_session_cleanup(details);
         }
+        "sled_evacuator" => {
+            print_task_sled_evacuator(details);
+        }
         "sp_ereport_ingester" => {
             print_task_sp_ereport_ingester(details);
         }
@@ -1354,6 +1386,9 @@ fn print_task_details(bgtask: &BackgroundTask, details: &serde_json::Value) {
         "tuf_repo_pruner" => {
             print_task_tuf_repo_pruner(details);
         }
+        "vpc_dns" => {
+            print_task_vpc_dns(details);
+        }
         "alert_dispatcher" => {
             print_task_alert_dispatcher(details);
         }
@@ -1448,6 +1483,44 @@ fn print_task_abandoned_vmm_reaper(details: &serde_json::Value) {
     };
 }
 
+fn print_task_acme_certificates(details: &serde_json::Value) {
+    match serde_json::from_value::<AcmeCertificatesStatus>(details.clone()) {
+        Err(error) => eprintln!(
+            "warning: failed to interpret task details: {:?}: {:?}",
+            error, details
+        ),
+        Ok(status) => {
+            if !status.enabled {
+                println!("    no ACME server configured");
+                return;
+            }
+
+            const CURRENT: &str = "silos with current certificates:";
+            const ISSUED: &str = "certificates obtained:";
+            const SUPERSEDED: &str = "superseded certificates deleted:";
+            const ERRORS: &str = "silos with errors:";
+            const WIDTH: usize =
+                const_max_len(&[CURRENT, ISSUED, SUPERSEDED, ERRORS]) + 1;
+
+            println!("    {CURRENT:<WIDTH$}{}", status.silos_current);
+            println!("    {ISSUED:<WIDTH$}{}", status.issued.len());
+            for issued in &status.issued {
+                println!(
+                    "        silo {}: {} ({})",
+                    issued.silo_name,
+                    issued.certificate_name,
+                    issued.certificate_id,
+                );
+            }
+            println!("    {SUPERSEDED:<WIDTH$}{}", status.superseded_deleted);
+            println!("    {ERRORS:<WIDTH$}{}", status.silo_errors.len());
+            for (silo_name, error) in &status.silo_errors {
+                println!("    {ERRICON} silo {silo_name}: {error}");
+            }
+        }
+    };
+}
+
 fn print_task_blueprint_planner(details: &serde_json::Value) {
     let status =
         match serde_json::from_value::<BlueprintPlannerStatus>(details.clone())
@@ -1691,6 +1764,52 @@ fn print_task_blueprint_rendezvous(details: &serde_json::Value) {
     }
 }
 
+fn print_task_certificate_expiry(details: &serde_json::Value) {
+    match serde_json::from_value::<CertificateExpiryStatus>(details.clone()) {
+        Err(error) => eprintln!(
+            "warning: failed to interpret task details: {:?}: {:?}",
+            error, details
+        ),
+        Ok(status) => {
+            const INSPECTED: &str = "certificates inspected:";
+            const EXPIRED: &str = "certificates already expired:";
+            const UNPARSEABLE: &str = "certificates not parsed:";
+            const ALERTS: &str = "alerts published:";
+            const ERRORS: &str = "errors:";
+            const WIDTH: usize = const_max_len(&[
+                INSPECTED,
+                EXPIRED,
+                UNPARSEABLE,
+                ALERTS,
+                ERRORS,
+            ]) + 1;
+
+            let expired = status
+                .certificates
+                .iter()
+                .filter(|c| c.days_remaining < 0)
+                .count();
+            println!("    {INSPECTED:<WIDTH$}{}", status.certificates.len());
+            println!("    {EXPIRED:<WIDTH$}{expired}");
+            println!("    {UNPARSEABLE:<WIDTH$}{}", status.parse_errors.len());
+            for e in &status.parse_errors {
+                println!(
+                    "    {ERRICON} {} {} ({}): {}",
+                    e.kind, e.name, e.id, e.error
+                );
+            }
+            println!("    {ALERTS:<WIDTH$}{}", status.alerts_published);
+            println!("    {ERRORS:<WIDTH$}{}", status.errors.len());
+            for error in &status.errors {
+                println!("    {ERRICON} {error}");
+            }
+            println!(
+                "    (use `omdb nexus certificates` to see when each expires)"
+            );
+        }
+    }
+}
+
 fn print_task_dns_config(details: &serde_json::Value) {
     // The "dns_config" tasks emit the generation number of the config that
     // they read.
@@ -1751,6 +1870,64 @@ fn print_task_dns_servers(details: &serde_json::Value) {
     }
 }
 
+fn print_task_dnssec_keys(details: &serde_json::Value) {
+    match serde_json::from_value::<DnssecKeysStatus>(details.clone()) {
+        Err(error) => eprintln!(
+            "warning: failed to interpret task details: {:?}: {:?}",
+            error, details
+        ),
+        Ok(status) => {
+            const ENABLED: &str = "signing enabled:";
+            const GENERATION: &str = "generation:";
+            const CREATED: &str = "keys created:";
+            const ACTIVATED: &str = "keys activated:";
+            const RETIRED: &str = "keys retired:";
+            const DELETED: &str = "keys deleted:";
+            const ERROR: &str = "error:";
+            const WIDTH: usize = const_max_len(&[
+                ENABLED, GENERATION, CREATED, ACTIVATED, RETIRED, DELETED,
+                ERROR,
+            ]) + 1;
+
+            println!("    {ENABLED:<WIDTH$}{}", status.enabled);
+            match status.generation {
+                Some(generation) => {
+                    println!("    {GENERATION:<WIDTH$}{generation}")
+                }
+                None => println!("    {GENERATION:<WIDTH$}unknown"),
+            }
+            println!("    {CREATED:<WIDTH$}{}", status.keys_created);
+            println!("    {ACTIVATED:<WIDTH$}{}", status.keys_activated);
+            println!("    {RETIRED:<WIDTH$}{}", status.keys_retired);
+            println!("    {DELETED:<WIDTH$}{}", status.keys_deleted);
+            if let Some(error) = &status.error {
+                println!("    {ERROR:<WIDTH$}{error}");
+            }
+            for key in &status.keys {
+                println!(
+                    "    {} {} {} (tag {}, created {})",
+                    key.zone_name,
+                    key.role,
+                    key.state,
+                    key.key_tag,
+                    key.time_created,
+                );
+                if let Some(ds) = &key.ds {
+                    println!("        {ds}");
+                }
+            }
+            for (server, result) in &status.server_results {
+                match result {
+                    Ok(()) => println!("    DNS server {server}: success"),
+                    Err(error) => {
+                        println!("    {ERRICON} DNS server {server}: {error}")
+                    }
+                }
+            }
+        }
+    };
+}
+
 fn print_task_dns_propagation(details: &serde_json::Value) {
     // The "dns_propagation" tasks emit a mapping of (dns server address) to
     // (result of propagation attempt).  There's no data in the success
@@ -2225,6 +2402,64 @@ fn print_task_inventory_load(details: &serde_json::Value) {
     };
 }
 
+fn print_task_load_balancer_manager(details: &serde_json::Value) {
+    match serde_json::from_value::<LoadBalancerManagerStatus>(details.clone()) {
+        Err(error) => eprintln!(
+            "warning: failed to interpret task details: {:?}: {:?}",
+            error, details
+        ),
+        Ok(status) => {
+            const LOAD_BALANCERS: &str = "load balancers:";
+            const ERROR: &str = "error:";
+            const WIDTH: usize = const_max_len(&[LOAD_BALANCERS, ERROR]) + 1;
+
+            println!(
+                "    {LOAD_BALANCERS:<WIDTH$}{}",
+                status.load_balancers.len()
+            );
+            if let Some(error) = &status.error {
+                println!("    {ERROR:<WIDTH$}{error}");
+            }
+
+            if !status.load_balancers.is_empty() {
+                #[derive(Tabled)]
+                #[tabled(rename_all = "SCREAMING_SNAKE_CASE")]
+                struct LoadBalancerRow {
+                    load_balancer_id: String,
+                    ip: String,
+                    backends: usize,
+                    serving: usize,
+                    nat_changes: usize,
+                    errors: usize,
+                }
+                let table_rows =
+                    status.load_balancers.iter().map(|lb| LoadBalancerRow {
+                        load_balancer_id: lb.load_balancer_id.to_string(),
+                        ip: lb.ip.to_string(),
+                        backends: lb.backends,
+                        serving: lb.serving,
+                        nat_changes: lb.nat_changes,
+                        errors: lb.errors.len(),
+                    });
+                let table = tabled::Table::new(table_rows)
+                    .with(tabled::settings::Style::empty())
+                    .with(tabled::settings::Padding::new(0, 1, 0, 0))
+                    .to_string();
+                println!("{}", textwrap::indent(&table, "        "));
+
+                for lb in &status.load_balancers {
+                    for error in &lb.errors {
+                        println!(
+                            "    {ERRICON} load balancer {}: {error}",
+                            lb.load_balancer_id
+                        );
+                    }
+                }
+            }
+        }
+    };
+}
+
 fn print_task_lookup_region_port(details: &serde_json::Value) {
     match serde_json::from_value::<LookupRegionPortStatus>(details.clone()) {
         Ok(LookupRegionPortStatus { found_port_ok, errors }) => {
@@ -2759,6 +2994,52 @@ fn print_task_audit_log_cleanup(details: &serde_json::Value) {
     };
 }
 
+fn print_task_audit_log_export(details: &serde_json::Value) {
+    match serde_json::from_value::<AuditLogExportStatus>(details.clone()) {
+        Err(error) => eprintln!(
+            "warning: failed to interpret task details: {:?}: {:?}",
+            error, details
+        ),
+        Ok(status) => {
+            const CUTOFF: &str = "cutoff:";
+            const SINKS: &str = "sinks due for delivery:";
+            const ERROR: &str = "error:";
+            const WIDTH: usize = const_max_len(&[CUTOFF, SINKS, ERROR]) + 1;
+
+            println!(
+                "    {CUTOFF:<WIDTH$}{}",
+                status.cutoff.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+            );
+            println!("    {SINKS:<WIDTH$}{}", status.sinks.len());
+            if let Some(error) = &status.error {
+                println!("    {ERROR:<WIDTH$}{error}");
+            }
+
+            if !status.sinks.is_empty() {
+                #[derive(Tabled)]
+                #[tabled(rename_all = "SCREAMING_SNAKE_CASE")]
+                struct SinkRow<'a> {
+                    sink_id: String,
+                    name: &'a str,
+                    delivered: usize,
+                    error: &'a str,
+                }
+                let table_rows = status.sinks.iter().map(|s| SinkRow {
+                    sink_id: s.sink_id.to_string(),
+                    name: &s.sink_name,
+                    delivered: s.entries_delivered,
+                    error: s.error.as_deref().unwrap_or("-"),
+                });
+                let table = tabled::Table::new(table_rows)
+                    .with(tabled::settings::Style::empty())
+                    .with(tabled::settings::Padding::new(0, 1, 0, 0))
+                    .to_string();
+                println!("{}", textwrap::indent(&table, "        "));
+            }
+        }
+    };
+}
+
 fn print_task_audit_log_timeout_incomplete(details: &serde_json::Value) {
     match serde_json::from_value::<AuditLogTimeoutIncompleteStatus>(
         details.clone(),
@@ -2818,6 +3099,61 @@ fn print_task_session_cleanup(details: &serde_json::Value) {
     };
 }
 
+fn print_task_sled_evacuator(details: &serde_json::Value) {
+    match serde_json::from_value::<SledEvacuatorStatus>(details.clone()) {
+        Err(error) => eprintln!(
+            "warning: failed to interpret task details: {:?}: {:?}",
+            error, details
+        ),
+        Ok(status) => {
+            const EVACUATIONS: &str = "evacuations in progress:";
+            const ERROR: &str = "error:";
+            const WIDTH: usize = const_max_len(&[EVACUATIONS, ERROR]) + 1;
+
+            println!("    {EVACUATIONS:<WIDTH$}{}", status.evacuations.len());
+            if let Some(error) = &status.error {
+                println!("    {ERROR:<WIDTH$}{error}");
+            }
+
+            if !status.evacuations.is_empty() {
+                #[derive(Tabled)]
+                #[tabled(rename_all = "SCREAMING_SNAKE_CASE")]
+                struct EvacuationRow {
+                    sled_id: String,
+                    remaining: usize,
+                    migrating: usize,
+                    migrated: usize,
+                    completed: bool,
+                    errors: usize,
+                }
+                let table_rows =
+                    status.evacuations.iter().map(|e| EvacuationRow {
+                        sled_id: e.sled_id.to_string(),
+                        remaining: e.instances_remaining,
+                        migrating: e.instances_migrating,
+                        migrated: e.instances_migrated.len(),
+                        completed: e.completed,
+                        errors: e.errors.len(),
+                    });
+                let table = tabled::Table::new(table_rows)
+                    .with(tabled::settings::Style::empty())
+                    .with(tabled::settings::Padding::new(0, 1, 0, 0))
+                    .to_string();
+                println!("{}", textwrap::indent(&table, "        "));
+
+                for evacuation in &status.evacuations {
+                    for error in &evacuation.errors {
+                        println!(
+                            "    {ERRICON} sled {}: {error}",
+                            evacuation.sled_id
+                        );
+                    }
+                }
+            }
+        }
+    };
+}
+
 fn print_task_service_firewall_rule_propagation(details: &serde_json::Value) {
     match serde_json::from_value::<ServiceFirewallRuleStatus>(details.clone()) {
         Err(error) => eprintln!(
@@ -3183,6 +3519,40 @@ fn print_task_alert_dispatcher(details: &serde_json::Value) {
         );
     }
 }
+fn print_task_vpc_dns(details: &serde_json::Value) {
+    match serde_json::from_value::<VpcDnsStatus>(details.clone()) {
+        Err(error) => eprintln!(
+            "warning: failed to interpret task details: {:?}: {:?}",
+            error, details
+        ),
+        Ok(status) => {
+            const GENERATION: &str = "generation:";
+            const ZONES: &str = "zones:";
+            const ERROR: &str = "error:";
+            const WIDTH: usize = const_max_len(&[GENERATION, ZONES, ERROR]) + 1;
+
+            match status.generation {
+                Some(generation) => {
+                    println!("    {GENERATION:<WIDTH$}{generation}")
+                }
+                None => println!("    {GENERATION:<WIDTH$}unknown"),
+            }
+            println!("    {ZONES:<WIDTH$}{}", status.zones);
+            if let Some(error) = &status.error {
+                println!("    {ERROR:<WIDTH$}{error}");
+            }
+            for (server, result) in &status.server_results {
+                match result {
+                    Ok(()) => println!("    DNS server {server}: success"),
+                    Err(error) => {
+                        println!("    {ERRICON} DNS server {server}: {error}")
+                    }
+                }
+            }
+        }
+    };
+}
+
 fn print_task_webhook_deliverator(details: &serde_json::Value) {
     use nexus_types::external_api::alert::WebhookDeliveryAttemptResult;
     use nexus_types::internal_api::background::WebhookDeliveratorStatus;
@@ -4650,6 +5020,70 @@ async fn cmd_nexus_mgs_updates(
     Ok(())
 }
 
+/// Runs `omdb nexus certificates`
+async fn cmd_nexus_certificates(
+    client: &nexus_lockstep_client::Client,
+) -> Result<(), anyhow::Error> {
+    let task = client
+        .bgtask_view("certificate_expiry")
+        .await
+        .context("fetching background task")?
+        .into_inner();
+    let LastResult::Completed(last) = task.last else {
+        bail!("task \"certificate_expiry\" has never completed");
+    };
+    let status =
+        serde_json::from_value::<CertificateExpiryStatus>(last.details)
+            .context("interpreting task details")?;
+
+    println!(
+        "as of the last check at {}:",
+        last.start_time.to_rfc3339_opts(SecondsFormat::Secs, true)
+    );
+
+    #[derive(Tabled)]
+    #[tabled(rename_all = "SCREAMING_SNAKE_CASE")]
+    struct CertificateRow {
+        kind: CertificateKind,
+        id: Uuid,
+        name: String,
+        #[tabled(display_with = "display_option_blank")]
+        silo: Option<Uuid>,
+        expires: String,
+        days: i64,
+    }
+
+    // The task reports certificates soonest to expire first.
+    let rows = status.certificates.into_iter().map(|c| CertificateRow {
+        kind: c.kind,
+        id: c.id,
+        name: c.name,
+        silo: c.silo_id,
+        expires: c.time_expires.to_rfc3339_opts(SecondsFormat::Secs, true),
+        days: c.days_remaining,
+    });
+    let table = tabled::Table::new(rows)
+        .with(tabled::settings::Style::empty())
+        .with(tabled::settings::Padding::new(0, 1, 0, 0))
+        .to_string();
+    println!("{}", textwrap::indent(&table, "    "));
+
+    if !status.parse_errors.is_empty() {
+        println!("\ncertificates that could not be parsed:");
+        for e in &status.parse_errors {
+            println!(
+                "    {ERRICON} {} {} ({}): {}",
+                e.kind, e.name, e.id, e.error
+            );
+        }
+    }
+    for error in &status.errors {
+        println!("{ERRICON} {error}");
+    }
+
+    Ok(())
+}
+
 async fn cmd_nexus_clickhouse_policy_set(
     client: &nexus_lockstep_client::Client,
     args: &ClickhousePolicySetArgs,
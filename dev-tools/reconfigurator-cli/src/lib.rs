@@ -13,6 +13,7 @@ use daft::Diffable;
 use gateway_types::rot::RotSlot;
 use iddqd::IdOrdMap;
 use indent_write::fmt::IndentWriter;
+use internal_dns_types::config::sole_forward_zone;
 use internal_dns_types::diff::DnsDiff;
 use itertools::Itertools;
 pub use log_capture::LogCapture;
@@ -3007,7 +3008,7 @@ fn cmd_blueprint_diff_dns(
         ),
     };
 
-    let existing_dns_zone = existing_dns_config.sole_zone()?;
+    let existing_dns_zone = sole_forward_zone(&existing_dns_config)?;
     let dns_diff = DnsDiff::new(&existing_dns_zone, &blueprint_dns_zone)
         .context("failed to assemble DNS diff")?;
     Ok(Some(dns_diff.to_string()))
@@ -88,12 +88,36 @@
 //! What else could we do?  We could queue the incoming request behind the
 //! in-progress one.  How large do we allow that queue to grow?  At some point
 //! we'll need to stop queueing them.  So why bother at all?
+//!
+//! ## VPC zones
+//!
+//! External DNS servers also serve zones private to each VPC, which let
+//! instances resolve each other by name.  These are configured with a
+//! separate pair of endpoints (`/vpc-config`) and their own generation number,
+//! but otherwise follow the same rules as above.  They change far more often
+//! than the rest of the DNS data, and folding them into the main
+//! configuration would mean every new network interface bumped the generation
+//! of the silos' DNS names as well.
+//!
+//! Instances use these servers as their resolver, so the VPC configuration
+//! also lists recursive resolvers to forward the instances' other queries to.
+//! Without them, a query from an instance for any name outside its zone would
+//! fail with SERVFAIL, and not every resolver moves on to the next server
+//! when that happens.
+//!
+//! ## DNSSEC
+//!
+//! The keys used to sign zones are configured in the same way, with their own
+//! pair of endpoints (`/dnssec-config`) and generation number.  Keys are rolled
+//! over on a schedule of their own, and a rollover should not have to wait on
+//! (or be undone by) a change to the records.  The GET endpoint reports only
+//! the public halves of the keys.
 
 use dropshot::{HttpError, HttpResponseOk, RequestContext};
 use dropshot_api_manager_types::api_versions;
 use internal_dns_types_versions::{
     latest::{self, config::ERROR_CODE_INCOMPATIBLE_RECORD},
-    v1, v2,
+    v1, v2, v3, v4,
 };
 
 api_versions!([
@@ -108,6 +132,10 @@ api_versions!([
     // |  example for the next person.
     // v
     // (next_int, IDENT),
+    (6, VPC_FORWARDERS),
+    (5, DNSSEC),
+    (4, RECORD_TYPES),
+    (3, VPC_ZONES),
     (2, SOA_AND_NS),
     (1, INITIAL),
 ]);
@@ -131,12 +159,33 @@ pub trait DnsServerApi {
     #[endpoint(
         method = GET,
         path = "/config",
-        versions = VERSION_SOA_AND_NS..
+        versions = VERSION_RECORD_TYPES..
     )]
     async fn dns_config_get(
         rqctx: RequestContext<Self::Context>,
     ) -> Result<HttpResponseOk<latest::config::DnsConfig>, HttpError>;
 
+    #[endpoint(
+        method = GET,
+        path = "/config",
+        operation_id = "dns_config_get",
+        versions = VERSION_SOA_AND_NS..VERSION_RECORD_TYPES
+    )]
+    async fn dns_config_get_v2(
+        rqctx: RequestContext<Self::Context>,
+    ) -> Result<HttpResponseOk<v2::config::DnsConfig>, HttpError> {
+        Self::dns_config_get(rqctx).await?.try_map(|config| {
+            config.try_into().map_err(
+                |v4::config::V4ToV3TranslationError::IncompatibleRecord| {
+                    HttpError::for_bad_request(
+                        None,
+                        ERROR_CODE_INCOMPATIBLE_RECORD.to_string(),
+                    )
+                },
+            )
+        })
+    }
+
     #[endpoint(
         method = GET,
         path = "/config",
@@ -146,7 +195,7 @@ pub trait DnsServerApi {
     async fn dns_config_get_v1(
         rqctx: RequestContext<Self::Context>,
     ) -> Result<HttpResponseOk<v1::config::DnsConfig>, HttpError> {
-        Self::dns_config_get(rqctx).await?.try_map(|config| {
+        Self::dns_config_get_v2(rqctx).await?.try_map(|config| {
             config.try_into().map_err(
                 |v2::config::V2ToV1TranslationError::IncompatibleRecord| {
                     HttpError::for_bad_request(
@@ -161,13 +210,27 @@ pub trait DnsServerApi {
     #[endpoint(
         method = PUT,
         path = "/config",
-        versions = VERSION_SOA_AND_NS..
+        versions = VERSION_RECORD_TYPES..
     )]
     async fn dns_config_put(
         rqctx: RequestContext<Self::Context>,
         rq: dropshot::TypedBody<latest::config::DnsConfigParams>,
     ) -> Result<dropshot::HttpResponseUpdatedNoContent, dropshot::HttpError>;
 
+    #[endpoint(
+        method = PUT,
+        path = "/config",
+        operation_id = "dns_config_put",
+        versions = VERSION_SOA_AND_NS..VERSION_RECORD_TYPES,
+    )]
+    async fn dns_config_put_v2(
+        rqctx: RequestContext<Self::Context>,
+        rq: dropshot::TypedBody<v2::config::DnsConfigParams>,
+    ) -> Result<dropshot::HttpResponseUpdatedNoContent, dropshot::HttpError>
+    {
+        Self::dns_config_put(rqctx, rq.map(Into::into)).await
+    }
+
     #[endpoint(
         method = PUT,
         path = "/config",
@@ -189,6 +252,106 @@ pub trait DnsServerApi {
                 },
             )
         })?;
-        Self::dns_config_put(rqctx, rq).await
+        Self::dns_config_put_v2(rqctx, rq).await
     }
+
+    #[endpoint(
+        method = GET,
+        path = "/vpc-config",
+        versions = VERSION_VPC_FORWARDERS..
+    )]
+    async fn vpc_dns_config_get(
+        rqctx: RequestContext<Self::Context>,
+    ) -> Result<HttpResponseOk<latest::config::VpcDnsConfig>, HttpError>;
+
+    #[endpoint(
+        method = GET,
+        path = "/vpc-config",
+        operation_id = "vpc_dns_config_get",
+        versions = VERSION_RECORD_TYPES..VERSION_VPC_FORWARDERS
+    )]
+    async fn vpc_dns_config_get_v4(
+        rqctx: RequestContext<Self::Context>,
+    ) -> Result<HttpResponseOk<v4::config::VpcDnsConfig>, HttpError> {
+        let HttpResponseOk(config) = Self::vpc_dns_config_get(rqctx).await?;
+        Ok(HttpResponseOk(config.into()))
+    }
+
+    #[endpoint(
+        method = GET,
+        path = "/vpc-config",
+        operation_id = "vpc_dns_config_get",
+        versions = VERSION_VPC_ZONES..VERSION_RECORD_TYPES
+    )]
+    async fn vpc_dns_config_get_v3(
+        rqctx: RequestContext<Self::Context>,
+    ) -> Result<HttpResponseOk<v3::config::VpcDnsConfig>, HttpError> {
+        Self::vpc_dns_config_get_v4(rqctx).await?.try_map(|config| {
+            config.try_into().map_err(
+                |v4::config::V4ToV3TranslationError::IncompatibleRecord| {
+                    HttpError::for_bad_request(
+                        None,
+                        ERROR_CODE_INCOMPATIBLE_RECORD.to_string(),
+                    )
+                },
+            )
+        })
+    }
+
+    #[endpoint(
+        method = PUT,
+        path = "/vpc-config",
+        versions = VERSION_VPC_FORWARDERS..
+    )]
+    async fn vpc_dns_config_put(
+        rqctx: RequestContext<Self::Context>,
+        rq: dropshot::TypedBody<latest::config::VpcDnsConfigParams>,
+    ) -> Result<dropshot::HttpResponseUpdatedNoContent, dropshot::HttpError>;
+
+    #[endpoint(
+        method = PUT,
+        path = "/vpc-config",
+        operation_id = "vpc_dns_config_put",
+        versions = VERSION_RECORD_TYPES..VERSION_VPC_FORWARDERS,
+    )]
+    async fn vpc_dns_config_put_v4(
+        rqctx: RequestContext<Self::Context>,
+        rq: dropshot::TypedBody<v4::config::VpcDnsConfigParams>,
+    ) -> Result<dropshot::HttpResponseUpdatedNoContent, dropshot::HttpError>
+    {
+        Self::vpc_dns_config_put(rqctx, rq.map(Into::into)).await
+    }
+
+    #[endpoint(
+        method = PUT,
+        path = "/vpc-config",
+        operation_id = "vpc_dns_config_put",
+        versions = VERSION_VPC_ZONES..VERSION_RECORD_TYPES,
+    )]
+    async fn vpc_dns_config_put_v3(
+        rqctx: RequestContext<Self::Context>,
+        rq: dropshot::TypedBody<v3::config::VpcDnsConfigParams>,
+    ) -> Result<dropshot::HttpResponseUpdatedNoContent, dropshot::HttpError>
+    {
+        Self::vpc_dns_config_put_v4(rqctx, rq.map(Into::into)).await
+    }
+
+    #[endpoint(
+        method = GET,
+        path = "/dnssec-config",
+        versions = VERSION_DNSSEC..
+    )]
+    async fn dnssec_config_get(
+        rqctx: RequestContext<Self::Context>,
+    ) -> Result<HttpResponseOk<latest::config::DnssecConfig>, HttpError>;
+
+    #[endpoint(
+        method = PUT,
+        path = "/dnssec-config",
+        versions = VERSION_DNSSEC..
+    )]
+    async fn dnssec_config_put(
+        rqctx: RequestContext<Self::Context>,
+        rq: dropshot::TypedBody<latest::config::DnssecConfigParams>,
+    ) -> Result<dropshot::HttpResponseUpdatedNoContent, dropshot::HttpError>;
 }
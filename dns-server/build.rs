@@ -12,4 +12,7 @@ fn main() {
     materializer
         .materialize("openapi/dns-server/dns-server-1.0.0-49359e.json.gitstub")
         .expect("materialized dns-server v1 git stub");
+    materializer
+        .materialize("openapi/dns-server/dns-server-3.0.0-4a2531.json.gitstub")
+        .expect("materialized dns-server v3 git stub");
 }
@@ -11,6 +11,12 @@
 //!   ".oxide.test" to avoid ever conflicting with a deployed server
 //! - All writes involve a read-modify-write with no ability to avoid clobbering
 //!   a concurrent write.
+//!
+//! Zones can also be exported to and imported from RFC 1035 zone files (see
+//! [`zone_file`]).  An import replaces all of a zone's records with those in
+//! the file, in a single new generation.
+
+mod zone_file;
 
 use anyhow::Context;
 use anyhow::Result;
@@ -23,11 +29,13 @@ use internal_dns_types::config::DnsConfigParams;
 use internal_dns_types::config::DnsConfigZone;
 use internal_dns_types::config::DnsRecord;
 use internal_dns_types::config::Srv;
+use internal_dns_types::diff::DnsDiff;
 use slog::{Drain, Logger};
 use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::iter::once;
 use std::net::{Ipv4Addr, Ipv6Addr};
+use std::path::PathBuf;
 
 #[derive(Debug, Parser)]
 #[clap(name = "dnsadm", about = "Administer DNS records (for testing only)")]
@@ -49,8 +57,19 @@ enum SubCommand {
     AddAAAA(AddAAAACommand),
     /// Add a SRV record (non-transactionally) to the DNS server
     AddSRV(AddSRVCommand),
+    /// Add a TXT record (non-transactionally) to the DNS server
+    AddTXT(AddTXTCommand),
+    /// Add a CNAME record (non-transactionally) to the DNS server
+    AddCNAME(AddCNAMECommand),
+    /// Add a PTR record (non-transactionally) to the DNS server
+    AddPTR(AddPTRCommand),
     /// Delete all records for a name (non-transactionally) in the DNS server
     DeleteRecord(DeleteRecordCommand),
+    /// Print DNS zones in RFC 1035 zone file format
+    Export(ExportCommand),
+    /// Replace all records in a zone (non-transactionally) with those in an
+    /// RFC 1035 zone file
+    Import(ImportCommand),
 }
 
 #[derive(Debug, Args)]
@@ -101,6 +120,45 @@ struct AddSRVCommand {
     target: String,
 }
 
+#[derive(Debug, Args)]
+struct AddTXTCommand {
+    /// name of one of the server's DNS zones (under ".oxide.test")
+    #[clap(action)]
+    zone_name: String,
+    /// name under which the new record should be added
+    #[clap(action)]
+    name: String,
+    /// text for the new TXT record
+    #[clap(action)]
+    text: String,
+}
+
+#[derive(Debug, Args)]
+struct AddCNAMECommand {
+    /// name of one of the server's DNS zones (under ".oxide.test")
+    #[clap(action)]
+    zone_name: String,
+    /// name under which the new record should be added
+    #[clap(action)]
+    name: String,
+    /// name that the new CNAME record is an alias for
+    #[clap(action)]
+    target: String,
+}
+
+#[derive(Debug, Args)]
+struct AddPTRCommand {
+    /// name of one of the server's DNS zones (under ".oxide.test")
+    #[clap(action)]
+    zone_name: String,
+    /// name under which the new record should be added
+    #[clap(action)]
+    name: String,
+    /// name that the new PTR record points to
+    #[clap(action)]
+    ptrdname: String,
+}
+
 #[derive(Debug, Args)]
 struct DeleteRecordCommand {
     /// name of one of the server's DNS zones (under ".oxide.test")
@@ -111,6 +169,34 @@ struct DeleteRecordCommand {
     name: String,
 }
 
+#[derive(Debug, Args)]
+struct ExportCommand {
+    /// name of the DNS zone to export (default: all zones)
+    #[clap(action)]
+    zone_name: Option<String>,
+    /// read the DNS configuration from this JSON file instead of from the
+    /// server
+    ///
+    /// This may be the DNS configuration of any generation, in the form
+    /// returned by the server's `GET /config` endpoint or sent to it by
+    /// Nexus.
+    #[clap(long, action)]
+    input: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+struct ImportCommand {
+    /// name of one of the server's DNS zones (under ".oxide.test")
+    #[clap(action)]
+    zone_name: String,
+    /// zone file to read the zone's new records from
+    #[clap(action)]
+    file: PathBuf,
+    /// show the changes that would be made without making them
+    #[clap(long, action)]
+    dry_run: bool,
+}
+
 fn main() -> Result<()> {
     oxide_tokio_rt::run(main_impl())
 }
@@ -160,6 +246,15 @@ async fn main_impl() -> Result<()> {
                             DnsRecord::Ns(name) => {
                                 println!("        NS: {:?}", name);
                             }
+                            DnsRecord::Txt(text) => {
+                                println!("        TXT:  {:?}", text);
+                            }
+                            DnsRecord::Cname(name) => {
+                                println!("        CNAME: {:?}", name);
+                            }
+                            DnsRecord::Ptr(name) => {
+                                println!("        PTR:  {:?}", name);
+                            }
                         }
                     }
                 }
@@ -204,6 +299,39 @@ async fn main_impl() -> Result<()> {
             client.dns_config_put(&new_config).await.context("updating DNS")?;
         }
 
+        SubCommand::AddTXT(cmd) => {
+            let old_config = client.dns_config_get().await?.into_inner();
+            let new_config = add_record(
+                old_config,
+                &cmd.zone_name,
+                &cmd.name,
+                DnsRecord::Txt(cmd.text),
+            )?;
+            client.dns_config_put(&new_config).await.context("updating DNS")?;
+        }
+
+        SubCommand::AddCNAME(cmd) => {
+            let old_config = client.dns_config_get().await?.into_inner();
+            let new_config = add_record(
+                old_config,
+                &cmd.zone_name,
+                &cmd.name,
+                DnsRecord::Cname(cmd.target),
+            )?;
+            client.dns_config_put(&new_config).await.context("updating DNS")?;
+        }
+
+        SubCommand::AddPTR(cmd) => {
+            let old_config = client.dns_config_get().await?.into_inner();
+            let new_config = add_record(
+                old_config,
+                &cmd.zone_name,
+                &cmd.name,
+                DnsRecord::Ptr(cmd.ptrdname),
+            )?;
+            client.dns_config_put(&new_config).await.context("updating DNS")?;
+        }
+
         SubCommand::DeleteRecord(cmd) => {
             let old_config = client.dns_config_get().await?.into_inner();
             verify_zone_name(&cmd.zone_name)?;
@@ -234,6 +362,86 @@ async fn main_impl() -> Result<()> {
             };
             client.dns_config_put(&new_config).await.context("updating DNS")?;
         }
+
+        SubCommand::Export(cmd) => {
+            let config = match &cmd.input {
+                Some(path) => {
+                    let contents = std::fs::read_to_string(path)
+                        .with_context(|| format!("reading {:?}", path))?;
+                    serde_json::from_str::<DnsConfigParams>(&contents)
+                        .with_context(|| format!("parsing {:?}", path))?
+                }
+                None => {
+                    let config = client.dns_config_get().await?.into_inner();
+                    DnsConfigParams {
+                        generation: config.generation,
+                        serial: config.serial,
+                        time_created: config.time_created,
+                        zones: config.zones,
+                    }
+                }
+            };
+
+            let mut zones: Vec<_> = config
+                .zones
+                .iter()
+                .filter(|zone| {
+                    cmd.zone_name.as_ref().is_none_or(|zone_name| {
+                        zone.zone_name == zone_name.trim_end_matches('.')
+                    })
+                })
+                .collect();
+            if let Some(zone_name) = &cmd.zone_name {
+                ensure!(!zones.is_empty(), "no such zone: {:?}", zone_name);
+            }
+            zones.sort_by(|a, b| a.zone_name.cmp(&b.zone_name));
+
+            for (i, zone) in zones.into_iter().enumerate() {
+                if i > 0 {
+                    println!();
+                }
+                let header = format!(
+                    "zone {:?}\nDNS generation {} (serial {})",
+                    zone.zone_name, config.generation, config.serial,
+                );
+                print!("{}", zone_file::write_zone(zone, &header));
+            }
+        }
+
+        SubCommand::Import(cmd) => {
+            verify_zone_name(&cmd.zone_name)?;
+            let zone_name = cmd.zone_name.trim_end_matches('.');
+            let contents = std::fs::read_to_string(&cmd.file)
+                .with_context(|| format!("reading {:?}", cmd.file))?;
+            let new_zone = zone_file::parse_zone(zone_name, &contents)
+                .with_context(|| format!("parsing {:?}", cmd.file))?;
+
+            let old_config = client.dns_config_get().await?.into_inner();
+            let old_zone = old_config
+                .zones
+                .iter()
+                .find(|zone| zone.zone_name == zone_name)
+                .cloned()
+                .unwrap_or_else(|| DnsConfigZone {
+                    zone_name: zone_name.to_owned(),
+                    records: HashMap::new(),
+                });
+            let diff = DnsDiff::new(&old_zone, &new_zone)?;
+            println!("changes from generation {}:", old_config.generation);
+            print!("{}", diff);
+            if diff.is_empty() {
+                println!("no changes to apply");
+                return Ok(());
+            }
+            if cmd.dry_run {
+                println!("not applying changes (dry run)");
+                return Ok(());
+            }
+
+            let new_config = replace_zone(old_config, new_zone)?;
+            client.dns_config_put(&new_config).await.context("updating DNS")?;
+            println!("applied as generation {}", new_config.generation);
+        }
     }
 
     Ok(())
@@ -258,6 +466,28 @@ fn init_logger() -> Logger {
     slog::Logger::root(drain, slog::o!())
 }
 
+/// Returns a new generation of `config` in which the zone named like
+/// `new_zone` is replaced with `new_zone` (or added, if it doesn't exist)
+fn replace_zone(
+    config: DnsConfig,
+    new_zone: DnsConfigZone,
+) -> Result<DnsConfigParams> {
+    let serial = config.serial.checked_add(1).ok_or_else(|| {
+        anyhow!("Cannot produce new serial for {}", config.serial)
+    })?;
+    Ok(DnsConfigParams {
+        generation: config.generation.next(),
+        serial,
+        time_created: chrono::Utc::now(),
+        zones: config
+            .zones
+            .into_iter()
+            .filter(|z| z.zone_name != new_zone.zone_name)
+            .chain(once(new_zone))
+            .collect(),
+    })
+}
+
 fn add_record(
     config: DnsConfig,
     zone_name: &str,
@@ -0,0 +1,587 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Reading and writing DNS zones in the master file format of RFC 1035
+//! (section 5)
+//!
+//! Only the record types that the DNS server stores are supported.  The DNS
+//! server synthesizes SOA records itself and serves every record with a TTL of
+//! zero, so SOA records and TTLs in zone files are accepted but ignored.
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::anyhow;
+use anyhow::bail;
+use anyhow::ensure;
+use internal_dns_types::config::DnsConfigZone;
+use internal_dns_types::config::DnsRecord;
+use internal_dns_types::config::Srv;
+use internal_dns_types::names::ZONE_APEX_NAME;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Longest character-string allowed in a record (RFC 1035 section 3.3)
+const MAX_CHARACTER_STRING: usize = 255;
+
+/// Returns the zone file representation of `zone`
+///
+/// `header` is written at the top of the file as a comment.
+pub fn write_zone(zone: &DnsConfigZone, header: &str) -> String {
+    let mut out = String::new();
+    for line in header.lines() {
+        writeln!(out, "; {}", line).unwrap();
+    }
+    writeln!(out, "$ORIGIN {}", absolute(&zone.zone_name)).unwrap();
+    writeln!(out, "$TTL 0").unwrap();
+
+    // Sort the names so that we get consistent ordering, with the apex first.
+    let names: BTreeMap<_, _> = zone
+        .records
+        .iter()
+        .map(|(name, records)| ((name != ZONE_APEX_NAME, name), records))
+        .collect();
+    for ((_, name), records) in names {
+        for record in records {
+            writeln!(out, "{}", record_line(name, record)).unwrap();
+        }
+    }
+    out
+}
+
+/// Returns the zone file line for `record`, whose owner is `name` (relative
+/// to the zone)
+pub fn record_line(name: &str, record: &DnsRecord) -> String {
+    let (rtype, rdata) = match record {
+        DnsRecord::A(addr) => ("A", addr.to_string()),
+        DnsRecord::Aaaa(addr) => ("AAAA", addr.to_string()),
+        DnsRecord::Srv(Srv { prio, weight, port, target }) => (
+            "SRV",
+            format!("{} {} {} {}", prio, weight, port, absolute(target)),
+        ),
+        DnsRecord::Ns(nsdname) => ("NS", absolute(nsdname)),
+        DnsRecord::Txt(text) => ("TXT", txt_rdata(text)),
+        DnsRecord::Cname(target) => ("CNAME", absolute(target)),
+        DnsRecord::Ptr(ptrdname) => ("PTR", absolute(ptrdname)),
+    };
+    format!("{:<24} IN {:<5} {}", name, rtype, rdata)
+}
+
+/// Returns `name` as an absolute domain name (with a trailing dot)
+fn absolute(name: &str) -> String {
+    if name.ends_with('.') { name.to_string() } else { format!("{}.", name) }
+}
+
+/// Returns the RDATA of a TXT record holding `text`, as a sequence of quoted
+/// character-strings
+///
+/// Like the DNS server, we split text that's too long for one
+/// character-string into several.
+fn txt_rdata(text: &str) -> String {
+    if text.is_empty() {
+        return String::from("\"\"");
+    }
+    text.as_bytes()
+        .chunks(MAX_CHARACTER_STRING)
+        .map(|chunk| {
+            let mut quoted = String::from("\"");
+            for byte in chunk {
+                match byte {
+                    b'"' | b'\\' => {
+                        quoted.push('\\');
+                        quoted.push(char::from(*byte));
+                    }
+                    0x20..=0x7e => quoted.push(char::from(*byte)),
+                    _ => write!(quoted, "\\{:03}", byte).unwrap(),
+                }
+            }
+            quoted.push('"');
+            quoted
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Parses the zone file `contents` describing the zone `zone_name`
+///
+/// Names that aren't absolute are relative to the current origin, which is
+/// `zone_name` until a `$ORIGIN` directive changes it.  Every record must
+/// belong to the zone.
+pub fn parse_zone(zone_name: &str, contents: &str) -> Result<DnsConfigZone> {
+    let zone = zone_name.trim_end_matches('.').to_ascii_lowercase();
+    let mut parser = Parser {
+        origin: zone.clone(),
+        zone,
+        owner: None,
+        records: HashMap::new(),
+    };
+    for entry in entries(contents)? {
+        parser
+            .entry(entry.continues_owner, entry.tokens)
+            .with_context(|| format!("line {}", entry.line))?;
+    }
+    Ok(DnsConfigZone {
+        zone_name: zone_name.to_string(),
+        records: parser.records,
+    })
+}
+
+/// State accumulated while parsing a zone file
+struct Parser {
+    /// name of the zone being parsed (lowercase, without a trailing dot)
+    zone: String,
+    /// name that relative names are relative to (lowercase, without a
+    /// trailing dot)
+    origin: String,
+    /// owner of the previous record, relative to the zone
+    owner: Option<String>,
+    records: HashMap<String, Vec<DnsRecord>>,
+}
+
+impl Parser {
+    /// Parses one entry (a directive or a record)
+    fn entry(
+        &mut self,
+        continues_owner: bool,
+        tokens: Vec<Token>,
+    ) -> Result<()> {
+        let mut tokens = tokens.into_iter();
+        if !continues_owner {
+            let first = tokens.next().expect("entries are not empty");
+            match first.word() {
+                Some(directive) if directive.starts_with('$') => {
+                    return self.directive(directive, tokens);
+                }
+                Some(name) => {
+                    self.owner = Some(relative_to_zone(
+                        &resolve(name, &self.origin),
+                        &self.zone,
+                    )?);
+                }
+                None => bail!("expected owner name"),
+            }
+        }
+        let Some(owner) = self.owner.clone() else {
+            bail!("record has no owner name");
+        };
+
+        // The TTL and class may appear in either order before the type.
+        let rtype = loop {
+            let word = next_word(&mut tokens, "record type")?;
+            if word.eq_ignore_ascii_case("IN")
+                || word.bytes().next().is_some_and(|b| b.is_ascii_digit())
+            {
+                continue;
+            }
+            if matches!(word.to_ascii_uppercase().as_str(), "CS" | "CH" | "HS")
+            {
+                bail!("unsupported class {}", word);
+            }
+            break word.to_ascii_uppercase();
+        };
+
+        let origin = &self.origin;
+        let record = match rtype.as_str() {
+            "A" => DnsRecord::A(
+                next_word(&mut tokens, "address")?
+                    .parse()
+                    .context("parsing IPv4 address")?,
+            ),
+            "AAAA" => DnsRecord::Aaaa(
+                next_word(&mut tokens, "address")?
+                    .parse()
+                    .context("parsing IPv6 address")?,
+            ),
+            "SRV" => {
+                let prio = next_word(&mut tokens, "priority")?
+                    .parse()
+                    .context("parsing SRV priority")?;
+                let weight = next_word(&mut tokens, "weight")?
+                    .parse()
+                    .context("parsing SRV weight")?;
+                let port = next_word(&mut tokens, "port")?
+                    .parse()
+                    .context("parsing SRV port")?;
+                let target =
+                    resolve(&next_word(&mut tokens, "target")?, origin);
+                DnsRecord::Srv(Srv { prio, weight, port, target })
+            }
+            "NS" => DnsRecord::Ns(resolve(
+                &next_word(&mut tokens, "name server")?,
+                origin,
+            )),
+            "CNAME" => DnsRecord::Cname(resolve(
+                &next_word(&mut tokens, "target")?,
+                origin,
+            )),
+            "PTR" => DnsRecord::Ptr(resolve(
+                &next_word(&mut tokens, "name")?,
+                origin,
+            )),
+            "TXT" => {
+                let text =
+                    tokens.by_ref().flat_map(Token::into_bytes).collect();
+                DnsRecord::Txt(
+                    String::from_utf8(text)
+                        .context("TXT record is not valid UTF-8")?,
+                )
+            }
+            // The DNS server builds the SOA record itself.
+            "SOA" => return Ok(()),
+            other => bail!("unsupported record type {}", other),
+        };
+        ensure!(
+            tokens.next().is_none(),
+            "unexpected data after {} record",
+            rtype
+        );
+        self.records.entry(owner).or_default().push(record);
+        Ok(())
+    }
+
+    /// Parses a directive (an entry starting with `$`)
+    fn directive(
+        &mut self,
+        directive: &str,
+        mut tokens: impl Iterator<Item = Token>,
+    ) -> Result<()> {
+        match directive.to_ascii_uppercase().as_str() {
+            "$ORIGIN" => {
+                let name = next_word(&mut tokens, "origin")?;
+                ensure!(
+                    name.ends_with('.'),
+                    "$ORIGIN must be an absolute name"
+                );
+                self.origin = name.trim_end_matches('.').to_ascii_lowercase();
+            }
+            // Every record is served with a TTL of zero.
+            "$TTL" => {
+                next_word(&mut tokens, "TTL")?;
+            }
+            other => bail!("unsupported directive {}", other),
+        }
+        ensure!(tokens.next().is_none(), "unexpected data after {}", directive);
+        Ok(())
+    }
+}
+
+/// Returns the next token as a word (that is, not a quoted string)
+fn next_word(
+    tokens: &mut impl Iterator<Item = Token>,
+    what: &str,
+) -> Result<String> {
+    match tokens.next() {
+        Some(Token::Word(word)) => Ok(word),
+        Some(Token::Quoted(_)) => {
+            bail!("expected {}, found quoted string", what)
+        }
+        None => bail!("expected {}", what),
+    }
+}
+
+/// Returns the absolute form (without a trailing dot) of `name`, which may be
+/// relative to `origin`
+fn resolve(name: &str, origin: &str) -> String {
+    if name == "@" {
+        origin.to_string()
+    } else if let Some(name) = name.strip_suffix('.') {
+        name.to_string()
+    } else {
+        format!("{}.{}", name, origin)
+    }
+}
+
+/// Returns the name of the absolute name `name` relative to `zone`, as the DNS
+/// server stores it
+fn relative_to_zone(name: &str, zone: &str) -> Result<String> {
+    let lower = name.to_ascii_lowercase();
+    if lower == zone {
+        return Ok(String::from(ZONE_APEX_NAME));
+    }
+    lower
+        .strip_suffix(zone)
+        .and_then(|prefix| prefix.strip_suffix('.'))
+        .map(|prefix| name[..prefix.len()].to_string())
+        .ok_or_else(|| anyhow!("name {:?} is not in zone {:?}", name, zone))
+}
+
+/// A single token of a zone file entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Word(String),
+    Quoted(Vec<u8>),
+}
+
+impl Token {
+    fn word(&self) -> Option<&str> {
+        match self {
+            Token::Word(word) => Some(word),
+            Token::Quoted(_) => None,
+        }
+    }
+
+    fn into_bytes(self) -> Vec<u8> {
+        match self {
+            Token::Word(word) => word.into_bytes(),
+            Token::Quoted(bytes) => bytes,
+        }
+    }
+}
+
+/// A zone file entry: a directive or a record, which may span several lines
+/// if it's parenthesized
+#[derive(Debug)]
+struct Entry {
+    /// line on which the entry starts, for error messages
+    line: usize,
+    /// whether the entry starts with whitespace, meaning the record has the
+    /// same owner as the previous one
+    continues_owner: bool,
+    tokens: Vec<Token>,
+}
+
+/// Splits `contents` into entries, removing comments and handling quoting,
+/// escapes and parentheses
+fn entries(contents: &str) -> Result<Vec<Entry>> {
+    let mut entries = Vec::new();
+    let mut current: Option<Entry> = None;
+    let mut depth = 0usize;
+
+    for (index, text) in contents.lines().enumerate() {
+        let line = index + 1;
+        if depth == 0 {
+            current = Some(Entry {
+                line,
+                continues_owner: text.starts_with([' ', '\t']),
+                tokens: Vec::new(),
+            });
+        }
+        let entry = current.as_mut().expect("an entry is in progress");
+
+        let mut bytes = text.bytes().peekable();
+        while let Some(byte) = bytes.next() {
+            match byte {
+                b' ' | b'\t' | b'\r' => (),
+                b';' => break,
+                b'(' => depth += 1,
+                b')' => {
+                    depth = depth.checked_sub(1).ok_or_else(|| {
+                        anyhow!("line {}: unbalanced parentheses", line)
+                    })?;
+                }
+                b'"' => {
+                    let mut quoted = Vec::new();
+                    loop {
+                        match bytes.next() {
+                            Some(b'"') => break,
+                            Some(b'\\') => {
+                                quoted.push(unescape(&mut bytes).with_context(
+                                    || format!("line {}", line),
+                                )?)
+                            }
+                            Some(byte) => quoted.push(byte),
+                            None => bail!(
+                                "line {}: unterminated quoted string",
+                                line
+                            ),
+                        }
+                    }
+                    entry.tokens.push(Token::Quoted(quoted));
+                }
+                _ => {
+                    let mut word = vec![byte];
+                    if byte == b'\\' {
+                        word = vec![
+                            unescape(&mut bytes)
+                                .with_context(|| format!("line {}", line))?,
+                        ];
+                    }
+                    while let Some(&next) = bytes.peek() {
+                        if matches!(
+                            next,
+                            b' ' | b'\t' | b'\r' | b';' | b'(' | b')' | b'"'
+                        ) {
+                            break;
+                        }
+                        bytes.next();
+                        if next == b'\\' {
+                            word.push(
+                                unescape(&mut bytes).with_context(|| {
+                                    format!("line {}", line)
+                                })?,
+                            );
+                        } else {
+                            word.push(next);
+                        }
+                    }
+                    let word = String::from_utf8(word)
+                        .with_context(|| format!("line {}", line))?;
+                    entry.tokens.push(Token::Word(word));
+                }
+            }
+        }
+
+        if depth == 0 {
+            let entry = current.take().expect("an entry is in progress");
+            if !entry.tokens.is_empty() {
+                entries.push(entry);
+            }
+        }
+    }
+
+    if let Some(entry) = current {
+        bail!("line {}: unbalanced parentheses", entry.line);
+    }
+    Ok(entries)
+}
+
+/// Decodes the escape sequence following a backslash: either `\DDD` (a byte
+/// value in decimal) or `\X` (the character X, literally)
+fn unescape(bytes: &mut impl Iterator<Item = u8>) -> Result<u8> {
+    let first = bytes.next().ok_or_else(|| anyhow!("incomplete escape"))?;
+    if !first.is_ascii_digit() {
+        return Ok(first);
+    }
+    let mut value = u32::from(first - b'0');
+    for _ in 0..2 {
+        match bytes.next() {
+            Some(digit) if digit.is_ascii_digit() => {
+                value = value * 10 + u32::from(digit - b'0');
+            }
+            _ => bail!("incomplete \\DDD escape"),
+        }
+    }
+    u8::try_from(value).map_err(|_| anyhow!("escape \\{} out of range", value))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::net::Ipv6Addr;
+
+    #[test]
+    fn test_round_trip() {
+        let zone = DnsConfigZone {
+            zone_name: String::from("z1.oxide.test"),
+            records: HashMap::from([
+                (
+                    String::from(ZONE_APEX_NAME),
+                    vec![DnsRecord::Ns(String::from("ns1.z1.oxide.test"))],
+                ),
+                (
+                    String::from("host1"),
+                    vec![
+                        DnsRecord::Aaaa(Ipv6Addr::LOCALHOST),
+                        DnsRecord::A("192.0.2.1".parse().unwrap()),
+                    ],
+                ),
+                (
+                    String::from("_nexus._tcp"),
+                    vec![DnsRecord::Srv(Srv {
+                        prio: 0,
+                        weight: 10,
+                        port: 12221,
+                        target: String::from("host1.z1.oxide.test"),
+                    })],
+                ),
+                (
+                    String::from("www"),
+                    vec![DnsRecord::Cname(String::from("host1.z1.oxide.test"))],
+                ),
+                (
+                    String::from("1.2"),
+                    vec![DnsRecord::Ptr(String::from("host1.z1.oxide.test"))],
+                ),
+                (
+                    String::from("text"),
+                    vec![
+                        DnsRecord::Txt(String::from("say \"hi\" \\ bye; ok")),
+                        DnsRecord::Txt(String::new()),
+                        DnsRecord::Txt(String::from("caf\u{e9}")),
+                        DnsRecord::Txt("x".repeat(600)),
+                    ],
+                ),
+            ]),
+        };
+
+        let contents = write_zone(&zone, "test zone");
+        let parsed = parse_zone(&zone.zone_name, &contents).unwrap();
+        assert_eq!(parsed, zone, "zone file:\n{}", contents);
+    }
+
+    #[test]
+    fn test_parse() {
+        let contents = r#"
+$ORIGIN z1.oxide.test.
+$TTL 3600
+@   IN  SOA ns1 admin (
+            1       ; serial
+            3600 600 86400 0 )
+    IN  NS  ns1
+ns1 300 IN AAAA ::1   ; a comment
+    AAAA ::2
+host.z1.oxide.test. IN 60 A 192.0.2.1
+$ORIGIN sub.z1.oxide.test.
+deep CNAME @
+txt TXT split "quoted words" \065\"
+"#;
+        let zone = parse_zone("z1.oxide.test", contents).unwrap();
+        let sorted: BTreeMap<_, _> = zone.records.into_iter().collect();
+        assert_eq!(
+            sorted,
+            BTreeMap::from([
+                (
+                    String::from("@"),
+                    vec![DnsRecord::Ns(String::from("ns1.z1.oxide.test"))]
+                ),
+                (
+                    String::from("deep.sub"),
+                    vec![DnsRecord::Cname(String::from("sub.z1.oxide.test"))]
+                ),
+                (
+                    String::from("host"),
+                    vec![DnsRecord::A("192.0.2.1".parse().unwrap())]
+                ),
+                (
+                    String::from("ns1"),
+                    vec![
+                        DnsRecord::Aaaa("::1".parse().unwrap()),
+                        DnsRecord::Aaaa("::2".parse().unwrap()),
+                    ]
+                ),
+                (
+                    String::from("txt.sub"),
+                    vec![DnsRecord::Txt(String::from("splitquoted wordsA\""))]
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let cases = [
+            ("www.example.com. IN A 192.0.2.1\n", "is not in zone"),
+            ("www IN MX 10 mail\n", "unsupported record type MX"),
+            ("www CH A 192.0.2.1\n", "unsupported class CH"),
+            ("www IN A 192.0.2.1 extra\n", "unexpected data"),
+            ("www IN A not-an-address\n", "parsing IPv4 address"),
+            ("    IN A 192.0.2.1\n", "no owner name"),
+            ("@ IN SOA ( ns1 admin\n", "unbalanced parentheses"),
+            ("www TXT \"unterminated\n", "unterminated quoted string"),
+            ("$INCLUDE other.zone\n", "unsupported directive"),
+        ];
+        for (contents, expected) in cases {
+            let error = parse_zone("z1.oxide.test", contents)
+                .expect_err("unexpectedly parsed zone file");
+            let message = format!("{:#}", error);
+            assert!(
+                message.contains(expected),
+                "parsing {:?}: expected error containing {:?}, got {:?}",
+                contents,
+                expected,
+                message
+            );
+        }
+    }
+}
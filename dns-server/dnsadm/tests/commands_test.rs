@@ -2,6 +2,7 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+use camino::Utf8Path;
 use camino_tempfile::Utf8TempDir;
 use dns_server::storage::Store;
 use omicron_test_utils::dev::test_cmds::EXIT_SUCCESS;
@@ -33,6 +34,10 @@ async fn test_dnsadm() {
         store,
         &dns_server::dns_server::Config {
             bind_address: "[::1]:0".parse().unwrap(),
+            transfer: Default::default(),
+            rate_limit: Default::default(),
+            query_log: Default::default(),
+            tcp: Default::default(),
         },
         &dropshot::ConfigDropshot {
             bind_address: "[::1]:0".parse().unwrap(),
@@ -43,6 +48,7 @@ async fn test_dnsadm() {
     .expect("starting servers");
 
     let config_addr = dropshot_server.local_addr();
+    let zone_dir = tmpdir.path().to_path_buf();
 
     let h1 = tokio::task::spawn_blocking(move || {
         // Now run a sequence of `dnsadm` commands against that server, put the
@@ -113,6 +119,57 @@ async fn test_dnsadm() {
             &["delete-record", "z1.oxide.test", "host1"],
         );
         run(&mut buffer, config_addr, &["list-records"]);
+        run(
+            &mut buffer,
+            config_addr,
+            &["add-txt", "z1.oxide.test", "_acme-challenge", "some digest"],
+        );
+        run(
+            &mut buffer,
+            config_addr,
+            &["add-cname", "z1.oxide.test", "www", "host2.z1.oxide.test"],
+        );
+        run(
+            &mut buffer,
+            config_addr,
+            &["add-ptr", "z1.oxide.test", "3.0.0.0", "host2.z1.oxide.test"],
+        );
+        run(&mut buffer, config_addr, &["list-records"]);
+
+        // Export a zone, then replace its records with those from a zone
+        // file: change host2's address, drop the PTR record, and add host3.
+        run(&mut buffer, config_addr, &["export", "z1.oxide.test"]);
+        std::fs::write(
+            zone_dir.join("z1.zone"),
+            "$ORIGIN z1.oxide.test.\n\
+             host2           IN AAAA  fe80::2:4\n\
+             host3           IN A     192.0.2.3\n\
+             s1.services     IN SRV   0 0 12345 host1\n\
+             \x20               IN SRV   0 0 12345 host2.z1.oxide.test.\n\
+             _acme-challenge IN TXT   \"some digest\"\n\
+             www             IN CNAME host2 ; an alias\n",
+        )
+        .expect("failed to write zone file");
+        run_in(
+            &mut buffer,
+            config_addr,
+            &zone_dir,
+            &["import", "--dry-run", "z1.oxide.test", "z1.zone"],
+        );
+        run_in(
+            &mut buffer,
+            config_addr,
+            &zone_dir,
+            &["import", "z1.oxide.test", "z1.zone"],
+        );
+        // Importing the same file again changes nothing.
+        run_in(
+            &mut buffer,
+            config_addr,
+            &zone_dir,
+            &["import", "z1.oxide.test", "z1.zone"],
+        );
+        run(&mut buffer, config_addr, &["export"]);
         buffer
     });
 
@@ -125,9 +182,30 @@ async fn test_dnsadm() {
 
 fn run(s: &mut String, config_addr: SocketAddr, args: &[&str]) {
     let path = path_to_executable(CMD_DNSADM);
-    let mut exec = subprocess::Exec::cmd(&path)
-        .arg("--address")
-        .arg(config_addr.to_string());
+    let exec = subprocess::Exec::cmd(&path);
+    run_exec(s, exec, config_addr, args);
+}
+
+/// Like `run()`, but runs the command in directory `cwd` (so that the paths
+/// of files it reads don't vary between runs)
+fn run_in(
+    s: &mut String,
+    config_addr: SocketAddr,
+    cwd: &Utf8Path,
+    args: &[&str],
+) {
+    let path = path_to_executable(CMD_DNSADM);
+    let exec = subprocess::Exec::cmd(&path).cwd(cwd);
+    run_exec(s, exec, config_addr, args);
+}
+
+fn run_exec(
+    s: &mut String,
+    exec: subprocess::Exec,
+    config_addr: SocketAddr,
+    args: &[&str],
+) {
+    let mut exec = exec.arg("--address").arg(config_addr.to_string());
 
     // Redact the TCP port number because it changes with each invocation.
     let mut cmdstr_redacted = "dnsadm --address REDACTED".to_string();
@@ -8,11 +8,21 @@
 use anyhow::Context;
 use anyhow::anyhow;
 use clap::Parser;
+use omicron_common::api::internal::nexus::{ProducerEndpoint, ProducerKind};
+use oximeter::types::ProducerRegistry;
 use serde::Deserialize;
 use slog::info;
 use slog::o;
 use std::net::{SocketAddr, SocketAddrV6};
 use std::path::PathBuf;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// How often Oximeter collects the DNS server's query metrics
+const METRIC_COLLECTION_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Largest request body the metric producer server accepts
+const METRIC_REQUEST_MAX_SIZE: usize = 1024 * 1024;
 
 #[derive(Parser, Debug)]
 struct Args {
@@ -24,6 +34,13 @@ struct Args {
 
     #[clap(long, action)]
     dns_address: SocketAddr,
+
+    /// ID of the zone this server runs in
+    ///
+    /// If given, the server's query metrics are reported to Oximeter under
+    /// this ID.
+    #[clap(long, action)]
+    id: Option<Uuid>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -31,6 +48,14 @@ pub struct Config {
     pub log: dropshot::ConfigLogging,
     pub dropshot: dropshot::ConfigDropshot,
     pub storage: dns_server::storage::Config,
+    #[serde(default)]
+    pub transfer: dns_server::dns_server::TransferConfig,
+    #[serde(default)]
+    pub rate_limit: dns_server::rate_limit::RateLimitConfig,
+    #[serde(default)]
+    pub query_log: dns_server::query_log::QueryLogConfig,
+    #[serde(default)]
+    pub tcp: dns_server::dns_server::TcpConfig,
 }
 
 fn main() -> Result<(), anyhow::Error> {
@@ -53,8 +78,13 @@ async fn main_impl() -> Result<(), anyhow::Error> {
         .to_logger("dns-server")
         .context("failed to create logger")?;
 
-    let dns_server_config =
-        dns_server::dns_server::Config { bind_address: args.dns_address };
+    let dns_server_config = dns_server::dns_server::Config {
+        bind_address: args.dns_address,
+        transfer: config.transfer.clone(),
+        rate_limit: config.rate_limit.clone(),
+        query_log: config.query_log.clone(),
+        tcp: config.tcp.clone(),
+    };
 
     info!(&log, "config";
         "config" => ?config,
@@ -67,14 +97,53 @@ async fn main_impl() -> Result<(), anyhow::Error> {
     )
     .context("initializing persistent storage")?;
 
-    let (_dns_server, dropshot_server) = dns_server::start_servers(
-        log,
+    let (dns_server, dropshot_server) = dns_server::start_servers(
+        log.clone(),
         store,
         &dns_server_config,
         &config.dropshot,
     )
     .await?;
 
+    // Report query metrics to Oximeter if we know which zone we are.  The
+    // producer server listens on any available port on our HTTP address, and
+    // finds Nexus to register with through internal DNS.
+    let _producer_server = match args.id {
+        Some(id) => {
+            let registry = ProducerRegistry::with_id(id);
+            registry
+                .register_producer(dns_server::metrics::Producer::new(
+                    id,
+                    dns_server.metrics().clone(),
+                ))
+                .context("registering metrics producer")?;
+            let producer_config = oximeter_producer::Config {
+                server_info: ProducerEndpoint {
+                    id,
+                    kind: ProducerKind::Service,
+                    address: SocketAddr::new(args.http_address.ip().into(), 0),
+                    interval: METRIC_COLLECTION_INTERVAL,
+                },
+                registration_address: None,
+                default_request_body_max_bytes: METRIC_REQUEST_MAX_SIZE,
+                log: oximeter_producer::LogConfig::Logger(
+                    log.new(o!("component" => "producer-server")),
+                ),
+            };
+            Some(
+                oximeter_producer::Server::with_registry(
+                    registry,
+                    &producer_config,
+                )
+                .context("starting metrics producer server")?,
+            )
+        }
+        None => {
+            info!(&log, "no zone ID given; not reporting metrics");
+            None
+        }
+    };
+
     dropshot_server
         .await
         .map_err(|error_message| anyhow!("server exiting: {}", error_message))
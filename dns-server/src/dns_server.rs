@@ -4,23 +4,77 @@
 
 //! Guts of the DNS (protocol) server within our DNS server program
 //!
-//! The facilities here handle binding a UDP socket, receiving DNS messages on
-//! that socket, and replying to them.
+//! The facilities here handle binding a UDP socket and a TCP listener on the
+//! same address, receiving DNS messages over either transport, and replying to
+//! them.
+//!
+//! UDP responses are limited to 512 bytes, or to the buffer size a client
+//! advertises with EDNS(0) (up to [`MAX_UDP_PAYLOAD`]).  Responses that don't
+//! fit have the TC (truncated) bit set so that clients retry over TCP, where
+//! responses can be as large as the protocol allows.
+//!
+//! Secondary servers may copy our zones with full (AXFR, RFC 5936) or
+//! incremental (IXFR, RFC 1995) zone transfers, if they're allowed to by
+//! [`TransferConfig`].  We can also tell them when there's a new version of
+//! our zones to transfer with NOTIFY messages (RFC 1996).
+//!
+//! Zones that have DNSSEC keys are signed online, for clients that ask for
+//! signatures (see [`crate::dnssec`]).  Zone transfers are not signed:
+//! secondary servers are expected to sign the zones they serve themselves,
+//! if at all.
+//!
+//! Queries from the instances served by VPC zones, for names outside of those
+//! zones and ours, are forwarded to the VPC configuration's recursive
+//! resolvers, and their answers relayed back, so that instances can use us as
+//! their only resolver.  VPC clients are identified only by their source
+//! address, which is easily spoofed over UDP, so we only forward queries that
+//! arrive over TCP.  The same queries over UDP get a truncated response (which
+//! is subject to rate limiting like any other), telling the client to retry
+//! over TCP.  That way, nobody can use us to send queries to the forwarders on
+//! another client's behalf, and the forwarding we do is bounded by the TCP
+//! connection limit (see [`TcpConfig`]).
+//!
+//! Every query answered is counted in the server's [`QueryMetrics`] and may be
+//! written to a sampled query log (see [`crate::query_log`]).  UDP responses
+//! are subject to response rate limiting (see [`crate::rate_limit`]).
 
+use crate::dnssec;
+use crate::dnssec::SignedZone;
+use crate::metrics::QueryMetrics;
+use crate::metrics::response_code_name;
+use crate::query_log::QueryLog;
+use crate::query_log::QueryLogConfig;
+use crate::query_log::QueryLogEntry;
+use crate::rate_limit::RateLimitConfig;
+use crate::rate_limit::RateLimiter;
+use crate::rate_limit::ResponseKind;
+use crate::rate_limit::Verdict;
 use crate::storage;
+use crate::storage::Answer;
 use crate::storage::QueryError;
 use crate::storage::Store;
 use anyhow::Context;
 use anyhow::anyhow;
+use hickory_proto::op::Edns;
 use hickory_proto::op::Header;
+use hickory_proto::op::LowerQuery;
+use hickory_proto::op::Message;
+use hickory_proto::op::MessageType;
+use hickory_proto::op::OpCode;
+use hickory_proto::op::Query;
 use hickory_proto::op::ResponseCode;
 use hickory_proto::rr::RData;
 use hickory_proto::rr::Record;
 use hickory_proto::rr::RecordType;
+use hickory_proto::rr::rdata::CNAME;
 use hickory_proto::rr::rdata::NS;
+use hickory_proto::rr::rdata::PTR;
+use hickory_proto::rr::rdata::SOA;
 use hickory_proto::rr::rdata::SRV;
+use hickory_proto::rr::rdata::TXT;
 use hickory_proto::serialize::binary::BinDecodable;
 use hickory_proto::serialize::binary::BinDecoder;
+use hickory_proto::serialize::binary::BinEncodable;
 use hickory_proto::serialize::binary::BinEncoder;
 use hickory_resolver::Name;
 use hickory_server::authority::MessageRequest;
@@ -28,22 +82,140 @@ use hickory_server::authority::MessageResponse;
 use hickory_server::authority::MessageResponseBuilder;
 use internal_dns_types::config::DnsRecord;
 use internal_dns_types::config::Srv;
+use internal_dns_types::names::ZONE_APEX_NAME;
+use omicron_common::api::external::Generation;
+use oxnet::IpNet;
 use pretty_hex::*;
 use serde::Deserialize;
-use slog::{Logger, debug, error, info, o, trace};
+use slog::{Logger, debug, error, info, o, trace, warn};
 use slog_error_chain::InlineErrorChain;
+use std::collections::BTreeSet;
 use std::net::SocketAddr;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
 use thiserror::Error;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpListener;
+use tokio::net::TcpStream;
 use tokio::net::UdpSocket;
+use tokio::sync::Semaphore;
+use tokio::sync::watch;
+use tokio::task::JoinSet;
 use uuid::Uuid;
 
+/// Largest UDP response we'll send, regardless of what a client advertises
+///
+/// This is the value recommended by DNS Flag Day 2020 to avoid IP
+/// fragmentation on common paths.  It's also the buffer size we advertise in
+/// our own EDNS(0) OPT records.
+pub const MAX_UDP_PAYLOAD: u16 = 1232;
+
+/// Largest UDP response we'll send to a client that doesn't use EDNS(0)
+/// (RFC 1035 section 4.2.1)
+const MIN_UDP_PAYLOAD: u16 = 512;
+
+/// How many times to try to find a port that's free for both UDP and TCP when
+/// asked to bind to any available port
+const MAX_BIND_ATTEMPTS: usize = 16;
+
+/// How many CNAME records we'll follow when answering a single query
+const MAX_CNAME_CHAIN: usize = 8;
+
+/// Largest total size of the records in one message of a zone transfer
+///
+/// This leaves room within the largest TCP message for the header, the
+/// question, and an OPT record.
+const MAX_TRANSFER_RECORDS_SIZE: usize = u16::MAX as usize - 1024;
+
+/// How long to wait for a secondary server to acknowledge a NOTIFY message
+/// before sending it again
+const NOTIFY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// How many times to send a NOTIFY message to a secondary server that doesn't
+/// acknowledge it
+const NOTIFY_ATTEMPTS: usize = 5;
+
+/// How long to wait for a forwarder to answer a query before trying the next
+/// one
+const FORWARD_TIMEOUT: Duration = Duration::from_secs(2);
+
 /// Configuration related to the DNS server
 #[derive(Deserialize, Debug, Clone)]
 pub struct Config {
-    /// The address to listen for DNS requests on
+    /// The address to listen for DNS requests on, over both UDP and TCP
     pub bind_address: SocketAddr,
+    /// Configuration related to transferring our zones to secondary servers
+    #[serde(default)]
+    pub transfer: TransferConfig,
+    /// Configuration related to rate limiting UDP responses
+    #[serde(default)]
+    pub rate_limit: RateLimitConfig,
+    /// Configuration related to logging the queries we answer
+    #[serde(default)]
+    pub query_log: QueryLogConfig,
+    /// Configuration related to serving DNS over TCP
+    #[serde(default)]
+    pub tcp: TcpConfig,
+}
+
+/// Configuration related to serving DNS over TCP
+#[derive(Deserialize, Debug, Clone)]
+pub struct TcpConfig {
+    /// The most TCP connections we'll serve at once
+    ///
+    /// Connections beyond this are closed as soon as they're accepted, so
+    /// that idle clients can't exhaust our file descriptors.
+    #[serde(default = "default_max_tcp_connections")]
+    pub max_connections: usize,
+    /// How long (in seconds) a TCP connection may sit idle, or take to accept
+    /// a response, before we close it
+    ///
+    /// RFC 7766 recommends that servers time out idle connections on the order
+    /// of seconds.
+    #[serde(default = "default_tcp_idle_timeout_secs")]
+    pub idle_timeout_secs: u64,
+}
+
+fn default_max_tcp_connections() -> usize {
+    1024
+}
+
+fn default_tcp_idle_timeout_secs() -> u64 {
+    10
+}
+
+impl Default for TcpConfig {
+    fn default() -> Self {
+        TcpConfig {
+            max_connections: default_max_tcp_connections(),
+            idle_timeout_secs: default_tcp_idle_timeout_secs(),
+        }
+    }
+}
+
+/// Configuration related to transferring our zones to secondary servers
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct TransferConfig {
+    /// Networks whose hosts may transfer our zones (with AXFR or IXFR)
+    ///
+    /// Zone transfer requests from anywhere else are refused.  By default,
+    /// that's everywhere.
+    #[serde(default)]
+    pub allowed_clients: Vec<IpNet>,
+    /// Secondary servers to send NOTIFY messages to whenever a new generation
+    /// of DNS data is applied
+    #[serde(default)]
+    pub notify: Vec<SocketAddr>,
+}
+
+impl TransferConfig {
+    /// Returns whether the client at `client_addr` may transfer our zones
+    fn allows(&self, client_addr: SocketAddr) -> bool {
+        self.allowed_clients.iter().any(|net| net.contains(client_addr.ip()))
+    }
 }
 
 /// Handle to the DNS server
@@ -51,6 +223,7 @@ pub struct Config {
 /// Dropping this handle shuts down the DNS server.
 pub struct ServerHandle {
     local_address: SocketAddr,
+    metrics: QueryMetrics,
     handle: tokio::task::JoinHandle<anyhow::Result<()>>,
 }
 
@@ -61,9 +234,15 @@ impl Drop for ServerHandle {
 }
 
 impl ServerHandle {
+    /// Returns the address the server is listening on (for both UDP and TCP)
     pub fn local_address(&self) -> SocketAddr {
         self.local_address
     }
+
+    /// Returns the metrics describing the queries the server has answered
+    pub fn metrics(&self) -> &QueryMetrics {
+        &self.metrics
+    }
 }
 
 /// DNS (protocol) server
@@ -73,7 +252,18 @@ impl ServerHandle {
 pub struct Server {
     log: Logger,
     store: storage::Store,
+    shared: Arc<Shared>,
     server_socket: Arc<UdpSocket>,
+    tcp_listener: TcpListener,
+    tcp_config: TcpConfig,
+}
+
+/// State shared by all the requests handled by a server
+struct Shared {
+    transfer: TransferConfig,
+    rate_limiter: RateLimiter,
+    query_log: QueryLog,
+    metrics: QueryMetrics,
 }
 
 impl Server {
@@ -83,14 +273,9 @@ impl Server {
         store: storage::Store,
         config: &Config,
     ) -> anyhow::Result<ServerHandle> {
-        let server_socket = Arc::new(
-            UdpSocket::bind(config.bind_address).await.with_context(|| {
-                format!(
-                    "DNS server start: UDP bind to {:?}",
-                    config.bind_address
-                )
-            })?,
-        );
+        let (server_socket, tcp_listener) =
+            bind_sockets(config.bind_address).await?;
+        let server_socket = Arc::new(server_socket);
 
         let local_address = server_socket.local_addr().context(
             "DNS server start: failed to get local address of bound socket",
@@ -100,41 +285,499 @@ impl Server {
             "local_address" => ?local_address
         );
 
-        let server = Server { log, store, server_socket };
+        let metrics = QueryMetrics::default();
+        let shared = Arc::new(Shared {
+            transfer: config.transfer.clone(),
+            rate_limiter: RateLimiter::new(config.rate_limit.clone()),
+            query_log: QueryLog::new(&config.query_log),
+            metrics: metrics.clone(),
+        });
+        let server = Server {
+            log,
+            store,
+            shared,
+            server_socket,
+            tcp_listener,
+            tcp_config: config.tcp.clone(),
+        };
         let handle = tokio::task::spawn(server.run());
-        Ok(ServerHandle { local_address, handle })
+        Ok(ServerHandle { local_address, metrics, handle })
     }
 
     async fn run(self) -> anyhow::Result<()> {
-        // The guts of the DNS server: read packets from the bound socket and
-        // handle them.
-        loop {
-            let mut buf = vec![0u8; 16384];
-            let (n, client_addr) = self
-                .server_socket
-                .recv_from(&mut buf)
+        let Server {
+            log,
+            store,
+            shared,
+            server_socket,
+            tcp_listener,
+            tcp_config,
+        } = self;
+        let applied = store.subscribe_applied();
+        tokio::try_join!(
+            run_udp(log.clone(), store.clone(), shared.clone(), server_socket),
+            run_tcp(
+                log.clone(),
+                store.clone(),
+                shared.clone(),
+                tcp_listener,
+                tcp_config,
+            ),
+            run_notify(log, store, shared, applied),
+        )?;
+        Ok(())
+    }
+}
+
+/// Binds a UDP socket and a TCP listener to the same address
+///
+/// If `bind_address` asks for any available port, we take whichever port the
+/// UDP socket gets and try to bind the TCP listener to the same one.  That
+/// port may already be in use for TCP, so we retry a few times.
+async fn bind_sockets(
+    bind_address: SocketAddr,
+) -> anyhow::Result<(UdpSocket, TcpListener)> {
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        let udp_socket =
+            UdpSocket::bind(bind_address).await.with_context(|| {
+                format!("DNS server start: UDP bind to {:?}", bind_address)
+            })?;
+        let udp_address = udp_socket.local_addr().context(
+            "DNS server start: failed to get local address of bound socket",
+        )?;
+        match TcpListener::bind(udp_address).await {
+            Ok(tcp_listener) => return Ok((udp_socket, tcp_listener)),
+            Err(error)
+                if bind_address.port() == 0
+                    && error.kind() == std::io::ErrorKind::AddrInUse
+                    && attempts < MAX_BIND_ATTEMPTS =>
+            {
+                continue;
+            }
+            Err(error) => {
+                return Err(error).with_context(|| {
+                    format!("DNS server start: TCP bind to {:?}", udp_address)
+                });
+            }
+        }
+    }
+}
+
+async fn run_udp(
+    log: Logger,
+    store: Store,
+    shared: Arc<Shared>,
+    server_socket: Arc<UdpSocket>,
+) -> anyhow::Result<()> {
+    // The guts of the DNS server: read packets from the bound socket and
+    // handle them.
+    loop {
+        let mut buf = vec![0u8; 16384];
+        let (n, client_addr) = server_socket
+            .recv_from(&mut buf)
+            .await
+            .context("receiving packet from UDP listen socket")?;
+        let received = Instant::now();
+        buf.resize(n, 0);
+
+        let req_id = Uuid::new_v4();
+        let log = log.new(o!(
+            "req_id" => req_id.to_string(),
+            "peer_addr" => client_addr.to_string(),
+            "transport" => "udp",
+        ));
+
+        let request = Request {
+            log,
+            store: store.clone(),
+            shared: shared.clone(),
+            transport: Transport::Udp,
+            client_addr,
+            packet: buf,
+            received,
+            req_id,
+        };
+
+        // TODO-robustness We should cap the number of tokio tasks that
+        // we're willing to spawn if we receive a flood of requests.
+        let socket = server_socket.clone();
+        tokio::spawn(async move {
+            // If we get this far and fail to send the data, there's nothing
+            // else to do but log the problem.
+            for response in handle_request(&request).await {
+                if let Err(error) = socket.send_to(&response, client_addr).await
+                {
+                    error!(
+                        &request.log,
+                        "failed to send response";
+                        InlineErrorChain::new(&error),
+                    );
+                    return;
+                }
+            }
+        });
+    }
+}
+
+async fn run_tcp(
+    log: Logger,
+    store: Store,
+    shared: Arc<Shared>,
+    tcp_listener: TcpListener,
+    config: TcpConfig,
+) -> anyhow::Result<()> {
+    // Connections are tracked here so that they're torn down along with the
+    // server.
+    let mut connections = JoinSet::new();
+    let permits = Arc::new(Semaphore::new(config.max_connections));
+    let idle_timeout = Duration::from_secs(config.idle_timeout_secs);
+    loop {
+        tokio::select! {
+            accepted = tcp_listener.accept() => {
+                let (stream, client_addr) = match accepted {
+                    Ok(accepted) => accepted,
+                    Err(error) => {
+                        // Failures here are generally specific to the
+                        // connection (e.g., it was reset before we accepted
+                        // it), so keep going.
+                        error!(
+                            &log,
+                            "failed to accept TCP connection";
+                            InlineErrorChain::new(&error),
+                        );
+                        continue;
+                    }
+                };
+                let log = log.new(o!(
+                    "peer_addr" => client_addr.to_string(),
+                    "transport" => "tcp",
+                ));
+                // Dropping the stream closes the connection.
+                let Ok(permit) = permits.clone().try_acquire_owned() else {
+                    warn!(
+                        &log,
+                        "closing TCP connection: too many connections";
+                        "max_connections" => config.max_connections,
+                    );
+                    continue;
+                };
+                let handler = handle_tcp_connection(
+                    log,
+                    store.clone(),
+                    shared.clone(),
+                    stream,
+                    client_addr,
+                    idle_timeout,
+                );
+                connections.spawn(async move {
+                    handler.await;
+                    drop(permit);
+                });
+            }
+            Some(_) = connections.join_next() => {}
+        }
+    }
+}
+
+/// Serves DNS messages received on a TCP connection until the client closes
+/// it, it's idle (or doesn't read our responses) for longer than
+/// `idle_timeout`, or it sends something we can't make sense of
+///
+/// Each message in either direction is preceded by its length as a two-byte
+/// integer (RFC 1035 section 4.2.2).
+async fn handle_tcp_connection(
+    log: Logger,
+    store: Store,
+    shared: Arc<Shared>,
+    mut stream: TcpStream,
+    client_addr: SocketAddr,
+    idle_timeout: Duration,
+) {
+    trace!(&log, "accepted TCP connection");
+    loop {
+        let length =
+            match tokio::time::timeout(idle_timeout, stream.read_u16()).await {
+                Ok(Ok(length)) => length,
+                Ok(Err(error))
+                    if error.kind() == std::io::ErrorKind::UnexpectedEof =>
+                {
+                    trace!(&log, "client closed TCP connection");
+                    return;
+                }
+                Ok(Err(error)) => {
+                    error!(
+                        &log,
+                        "failed to read from TCP connection";
+                        InlineErrorChain::new(&error),
+                    );
+                    return;
+                }
+                Err(_) => {
+                    debug!(&log, "closing idle TCP connection");
+                    return;
+                }
+            };
+
+        let mut packet = vec![0u8; usize::from(length)];
+        match tokio::time::timeout(idle_timeout, stream.read_exact(&mut packet))
+            .await
+        {
+            Ok(Ok(_)) => (),
+            Ok(Err(error)) => {
+                error!(
+                    &log,
+                    "failed to read message from TCP connection";
+                    InlineErrorChain::new(&error),
+                );
+                return;
+            }
+            Err(_) => {
+                debug!(&log, "timed out reading message from TCP connection");
+                return;
+            }
+        }
+
+        let req_id = Uuid::new_v4();
+        let request = Request {
+            log: log.new(o!("req_id" => req_id.to_string())),
+            store: store.clone(),
+            shared: shared.clone(),
+            transport: Transport::Tcp,
+            client_addr,
+            packet,
+            received: Instant::now(),
+            req_id,
+        };
+
+        // Most responses are a single message, but zone transfers may take
+        // several.
+        for response in handle_request(&request).await {
+            // Responses are encoded with a maximum size of `u16::MAX`, so this
+            // can't fail.
+            let length = u16::try_from(response.len())
+                .expect("TCP response length fits in a u16");
+            let mut framed = Vec::with_capacity(response.len() + 2);
+            framed.extend_from_slice(&length.to_be_bytes());
+            framed.extend_from_slice(&response);
+            match tokio::time::timeout(idle_timeout, stream.write_all(&framed))
                 .await
-                .context("receiving packet from UDP listen socket")?;
-            buf.resize(n, 0);
+            {
+                Ok(Ok(())) => (),
+                Ok(Err(error)) => {
+                    error!(
+                        &request.log,
+                        "failed to send response";
+                        InlineErrorChain::new(&error),
+                    );
+                    return;
+                }
+                Err(_) => {
+                    debug!(&request.log, "timed out sending TCP response");
+                    return;
+                }
+            }
+        }
+    }
+}
 
-            let req_id = Uuid::new_v4();
-            let log = self.log.new(o!(
-                "req_id" => req_id.to_string(),
-                "peer_addr" => client_addr.to_string(),
-            ));
+/// Sends NOTIFY messages for each of our zones to the configured secondary
+/// servers whenever a new generation of DNS data is applied
+async fn run_notify(
+    log: Logger,
+    store: Store,
+    shared: Arc<Shared>,
+    mut applied: watch::Receiver<Generation>,
+) -> anyhow::Result<()> {
+    let transfer = &shared.transfer;
+    if transfer.notify.is_empty() {
+        return Ok(());
+    }
 
-            let request = Request {
-                log,
-                store: self.store.clone(),
-                socket: self.server_socket.clone(),
-                client_addr,
-                packet: buf,
-                req_id,
+    // Several generations may be applied while we're busy.  We only need to
+    // tell secondaries about the latest one, which the watch channel gives us.
+    while applied.changed().await.is_ok() {
+        let generation = *applied.borrow_and_update();
+        let log = log.new(o!("generation" => u64::from(generation)));
+        let zones = match store.zone_names() {
+            Ok(zones) => zones,
+            Err(error) => {
+                error!(
+                    &log,
+                    "failed to list zones to send NOTIFY for";
+                    InlineErrorChain::new(error.as_ref()),
+                );
+                continue;
+            }
+        };
+
+        for zone in zones {
+            let soa = match Name::from_str(&zone)
+                .context("parsing zone name")
+                .and_then(|zone_name| {
+                    let answer = store.query_name(&zone_name)?;
+                    Ok(store.soa_for(&answer)?)
+                }) {
+                Ok(soa) => soa,
+                Err(error) => {
+                    error!(
+                        &log,
+                        "failed to build SOA record for NOTIFY";
+                        "zone" => &zone,
+                        InlineErrorChain::new(error.as_ref()),
+                    );
+                    continue;
+                }
             };
 
-            // TODO-robustness We should cap the number of tokio tasks that
-            // we're willing to spawn if we receive a flood of requests.
-            tokio::spawn(handle_dns_packet(request));
+            // Secondaries that are down shouldn't hold up the others (or the
+            // next generation), so each gets its own task.
+            for secondary in &transfer.notify {
+                tokio::spawn(send_notify(
+                    log.new(o!(
+                        "zone" => zone.clone(),
+                        "secondary" => secondary.to_string(),
+                    )),
+                    *secondary,
+                    soa.clone(),
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Tells the secondary server at `secondary` that there's a new version of the
+/// zone whose SOA record is `soa` (RFC 1996)
+///
+/// The message is sent again until the secondary acknowledges it or we run out
+/// of attempts.
+async fn send_notify(log: Logger, secondary: SocketAddr, soa: Record) {
+    let bind_address: SocketAddr = if secondary.is_ipv6() {
+        "[::]:0".parse().unwrap()
+    } else {
+        "0.0.0.0:0".parse().unwrap()
+    };
+    let socket = match UdpSocket::bind(bind_address).await {
+        Ok(socket) => socket,
+        Err(error) => {
+            error!(
+                &log,
+                "failed to bind socket to send NOTIFY";
+                InlineErrorChain::new(&error),
+            );
+            return;
+        }
+    };
+
+    let id =
+        u16::from_be_bytes(Uuid::new_v4().as_bytes()[..2].try_into().unwrap());
+    let mut message = Message::new();
+    message
+        .set_id(id)
+        .set_message_type(MessageType::Query)
+        .set_op_code(OpCode::Notify)
+        .set_authoritative(true)
+        .add_query(Query::query(soa.name().clone(), RecordType::SOA))
+        .add_answer(soa);
+    let packet = match message.to_vec() {
+        Ok(packet) => packet,
+        Err(error) => {
+            error!(
+                &log,
+                "failed to encode NOTIFY";
+                InlineErrorChain::new(&error),
+            );
+            return;
+        }
+    };
+
+    for attempt in 1..=NOTIFY_ATTEMPTS {
+        debug!(&log, "sending NOTIFY"; "attempt" => attempt);
+        if let Err(error) = socket.send_to(&packet, secondary).await {
+            error!(
+                &log,
+                "failed to send NOTIFY";
+                InlineErrorChain::new(&error),
+            );
+            return;
+        }
+
+        let acknowledged = tokio::time::timeout(NOTIFY_TIMEOUT, async {
+            let mut buf = vec![0u8; usize::from(MAX_UDP_PAYLOAD)];
+            loop {
+                let (n, from) = socket.recv_from(&mut buf).await?;
+                // Ignore anything that isn't the response to our message.
+                if from != secondary {
+                    continue;
+                }
+                match Message::from_vec(&buf[..n]) {
+                    Ok(response)
+                        if response.id() == id
+                            && response.message_type()
+                                == MessageType::Response =>
+                    {
+                        return Ok::<_, std::io::Error>(());
+                    }
+                    _ => continue,
+                }
+            }
+        })
+        .await;
+
+        match acknowledged {
+            Ok(Ok(())) => {
+                debug!(&log, "NOTIFY acknowledged");
+                return;
+            }
+            Ok(Err(error)) => {
+                error!(
+                    &log,
+                    "failed to receive NOTIFY response";
+                    InlineErrorChain::new(&error),
+                );
+                return;
+            }
+            Err(_) => continue,
+        }
+    }
+
+    error!(
+        &log,
+        "secondary did not acknowledge NOTIFY";
+        "attempts" => NOTIFY_ATTEMPTS,
+    );
+}
+
+/// The transport over which a DNS request arrived (and its response will be
+/// sent)
+#[derive(Clone, Copy, Debug)]
+enum Transport {
+    Udp,
+    Tcp,
+}
+
+impl Transport {
+    /// Returns a short name for the transport, for logs and metrics
+    fn label(&self) -> &'static str {
+        match self {
+            Transport::Udp => "udp",
+            Transport::Tcp => "tcp",
+        }
+    }
+
+    /// Returns the largest response we may send for the given request
+    fn max_response_size(&self, mr: &MessageRequest) -> u16 {
+        match self {
+            Transport::Tcp => u16::MAX,
+            Transport::Udp => match mr.edns() {
+                Some(edns) => {
+                    edns.max_payload().clamp(MIN_UDP_PAYLOAD, MAX_UDP_PAYLOAD)
+                }
+                None => MIN_UDP_PAYLOAD,
+            },
         }
     }
 }
@@ -143,14 +786,122 @@ impl Server {
 struct Request {
     log: Logger,
     store: Store,
-    socket: Arc<UdpSocket>,
+    shared: Arc<Shared>,
+    transport: Transport,
     client_addr: SocketAddr,
     packet: Vec<u8>,
+    /// when the request was received, for measuring how long it took to
+    /// answer
+    received: Instant,
     #[allow(dead_code)]
     req_id: Uuid,
 }
 
-async fn handle_dns_packet(request: Request) {
+/// Handles a DNS request, returning the encoded messages to send in response
+/// (if any)
+///
+/// Queries from VPC clients for names outside of our zones are forwarded (see
+/// [`forward_dns_packet`]).  Everything else is answered from our own data.
+async fn handle_request(request: &Request) -> Vec<Vec<u8>> {
+    match forward_dns_packet(request).await {
+        Some(responses) => responses,
+        None => handle_dns_packet(request),
+    }
+}
+
+/// Forwards a query from a VPC client for a name outside of our zones (and
+/// outside of the client's VPC zones) to the VPC forwarders, returning the
+/// messages to send in response
+///
+/// Only queries that arrived over TCP are forwarded; the same queries over UDP
+/// are answered with a truncated response so that the client retries over TCP
+/// (see the module docs).  Forwarders are tried in order, and the first
+/// response is relayed as is.  Returns `None` if the request isn't one to
+/// forward, or if no forwarder answered it, in which case it's handled like
+/// any other.
+async fn forward_dns_packet(request: &Request) -> Option<Vec<Vec<u8>>> {
+    let forwarders = request.store.vpc_forwarders(request.client_addr);
+    if forwarders.is_empty() {
+        return None;
+    }
+
+    let mut dec = BinDecoder::new(&request.packet);
+    let mr = MessageRequest::read(&mut dec).ok()?;
+    let [query] = mr.queries() else {
+        return None;
+    };
+    if mr.header().op_code() != OpCode::Query
+        || matches!(query.query_type(), RecordType::AXFR | RecordType::IXFR)
+    {
+        return None;
+    }
+    match request.store.query_from(query, request.client_addr) {
+        Err(QueryError::NoZone(_)) => (),
+        _ => return None,
+    }
+
+    if let Transport::Udp = request.transport {
+        let responses = respond_truncated(request, &mr).into_iter().collect();
+        return Some(finish_request(request, &mr, None, responses));
+    }
+
+    let log = &request.log;
+    for forwarder in forwarders {
+        let forwarded = tokio::time::timeout(
+            FORWARD_TIMEOUT,
+            forward_tcp(&request.packet, forwarder),
+        )
+        .await;
+        match forwarded {
+            Ok(Ok(response)) => {
+                return Some(finish_request(
+                    request,
+                    &mr,
+                    None,
+                    vec![response],
+                ));
+            }
+            Ok(Err(error)) => {
+                warn!(
+                    log,
+                    "failed to forward query";
+                    "forwarder" => %forwarder,
+                    InlineErrorChain::new(error.as_ref()),
+                );
+            }
+            Err(_) => {
+                warn!(
+                    log,
+                    "timed out forwarding query";
+                    "forwarder" => %forwarder,
+                );
+            }
+        }
+    }
+    None
+}
+
+/// Sends the query `packet` to `forwarder` over TCP, returning the response
+async fn forward_tcp(
+    packet: &[u8],
+    forwarder: SocketAddr,
+) -> anyhow::Result<Vec<u8>> {
+    let mut stream = TcpStream::connect(forwarder)
+        .await
+        .context("connecting to forwarder")?;
+    // The query arrived over TCP, so its length fits in a u16.
+    let length = u16::try_from(packet.len()).context("query too large")?;
+    stream.write_u16(length).await.context("sending query")?;
+    stream.write_all(packet).await.context("sending query")?;
+    let length = stream.read_u16().await.context("receiving response")?;
+    let mut response = vec![0u8; usize::from(length)];
+    stream.read_exact(&mut response).await.context("receiving response")?;
+    Ok(response)
+}
+
+/// Handles a DNS message, returning the encoded messages to send in response
+/// (if any)
+fn handle_dns_packet(request: &Request) -> Vec<Vec<u8>> {
     let log = &request.log;
     let buf = &request.packet;
 
@@ -162,40 +913,176 @@ async fn handle_dns_packet(request: Request) {
         Ok(mr) => mr,
         Err(error) => {
             error!(log, "failed to parse incoming DNS message: {:#}", error);
-            return;
+            return Vec::new();
         }
     };
 
     // Handle the message.
-    match handle_dns_message(&request, &mr).await {
-        Ok(_) => (),
+    let mut zone = None;
+    let result = match mr.queries() {
+        [query]
+            if matches!(
+                query.query_type(),
+                RecordType::AXFR | RecordType::IXFR
+            ) =>
+        {
+            handle_zone_transfer(request, &mr, query, &mut zone)
+        }
+        _ => handle_dns_message(request, &mr, &mut zone)
+            .map(|response| vec![response]),
+    };
+    let responses = match result {
+        Ok(responses) => responses,
         Err(error) => {
             let header = Header::response_from_request(mr.header());
-            let rb_servfail = MessageResponseBuilder::from_message_request(&mr);
+            let max_size = request.transport.max_response_size(&mr);
+            let rb_servfail = response_builder(&mr);
             error!(
                 log,
                 "failed to handle incoming DNS message: {:#?} {:#}", mr, error
             );
-            match error {
+            let response = match error {
                 RequestError::NxDomain(_) => {
-                    let rb_nxdomain =
-                        MessageResponseBuilder::from_message_request(&mr);
+                    let rb_nxdomain = response_builder(&mr);
                     respond_nxdomain(
-                        &request,
+                        request,
                         rb_nxdomain,
                         rb_servfail,
                         &header,
+                        max_size,
+                    )
+                }
+                RequestError::Refused(_) => {
+                    let rb_refused = response_builder(&mr);
+                    respond_refused(
+                        request,
+                        rb_refused,
+                        rb_servfail,
+                        &header,
+                        max_size,
                     )
-                    .await
                 }
                 RequestError::ServFail(_) => {
-                    let rb_servfail =
-                        MessageResponseBuilder::from_message_request(&mr);
-                    respond_servfail(&request, rb_servfail, &header).await
+                    respond_servfail(request, rb_servfail, &header, max_size)
+                }
+            };
+            response.into_iter().collect()
+        }
+    };
+    finish_request(request, &mr, zone.as_deref(), responses)
+}
+
+/// Records metrics for the response to a request, applies response rate
+/// limiting to it, and logs it to the query log
+///
+/// `zone` is the zone the request was answered from, if it was one of ours.
+/// Returns the messages to actually send.
+fn finish_request(
+    request: &Request,
+    mr: &MessageRequest,
+    zone: Option<&str>,
+    mut responses: Vec<Vec<u8>>,
+) -> Vec<Vec<u8>> {
+    let shared = &request.shared;
+    let latency = request.received.elapsed();
+    let header = match responses.first().map(|r| Header::from_bytes(r)) {
+        Some(Ok(header)) => header,
+        Some(Err(error)) => {
+            error!(
+                &request.log,
+                "failed to decode our own response";
+                InlineErrorChain::new(&error),
+            );
+            return responses;
+        }
+        // There's nothing to record if we're not responding at all.
+        None => return responses,
+    };
+    let query = mr.queries().first();
+    shared.metrics.record_query(
+        zone,
+        query.map(|q| q.query_type()),
+        header.response_code(),
+        request.transport.label(),
+        latency,
+    );
+
+    // Only UDP responses can be sent to a spoofed address.
+    let verdict = match request.transport {
+        Transport::Tcp => Verdict::Send,
+        Transport::Udp => {
+            let kind = match (header.response_code(), query) {
+                (ResponseCode::NoError, Some(query))
+                    if header.answer_count() > 0 =>
+                {
+                    ResponseKind::Answer {
+                        name: query.original().name(),
+                        qtype: query.query_type(),
+                    }
                 }
+                (ResponseCode::NoError | ResponseCode::NXDomain, _) => {
+                    ResponseKind::Empty { zone }
+                }
+                _ => ResponseKind::Error,
             };
+            shared.rate_limiter.check(
+                request.client_addr.ip(),
+                kind,
+                Instant::now(),
+            )
+        }
+    };
+    match verdict {
+        Verdict::Send => (),
+        Verdict::Drop => {
+            shared.metrics.record_rate_limited(zone, verdict.action());
+            responses.clear();
         }
+        Verdict::Truncate => {
+            shared.metrics.record_rate_limited(zone, verdict.action());
+            responses = respond_truncated(request, mr).into_iter().collect();
+        }
+    }
+
+    shared.query_log.log(
+        &request.log,
+        &QueryLogEntry {
+            name: query.map(|q| q.original().name().to_string()),
+            query_type: query.map(|q| q.query_type().to_string()),
+            zone,
+            response_code: response_code_name(header.response_code()),
+            answers: header.answer_count(),
+            latency,
+            action: verdict.action(),
+        },
+        Instant::now(),
+    );
+    responses
+}
+
+/// Returns a builder for a response to `mr`
+///
+/// If the request includes an EDNS(0) OPT record, the response will include
+/// one too, advertising the UDP payload size we're willing to send
+/// (RFC 6891 section 6.1.1).
+fn response_builder(mr: &MessageRequest) -> MessageResponseBuilder<'_> {
+    let mut rb = MessageResponseBuilder::from_message_request(mr);
+    if mr.edns().is_some() {
+        let mut edns = Edns::new();
+        edns.set_max_payload(MAX_UDP_PAYLOAD);
+        edns.set_version(0);
+        // The "DNSSEC OK" bit is copied from the request (RFC 3225 section
+        // 3).
+        edns.flags_mut().dnssec_ok = dnssec_ok(mr);
+        rb.edns(edns);
     }
+    rb
+}
+
+/// Returns whether the client asked for DNSSEC records to be included in the
+/// response, by setting the EDNS "DNSSEC OK" bit (RFC 3225)
+fn dnssec_ok(mr: &MessageRequest) -> bool {
+    mr.edns().is_some_and(|edns| edns.flags().dnssec_ok)
 }
 
 /// Describes how to respond to a particular request failure
@@ -203,6 +1090,8 @@ async fn handle_dns_packet(request: Request) {
 enum RequestError {
     #[error("NXDOMAIN: {0:#}")]
     NxDomain(String),
+    #[error("REFUSED: {0:#}")]
+    Refused(String),
     #[error("SERVFAIL: {0:#}")]
     ServFail(#[source] anyhow::Error),
 }
@@ -258,14 +1147,63 @@ fn dns_record_to_record(
             })?;
             Ok(Record::from_rdata(name.clone(), 0, RData::NS(NS(nsdname))))
         }
+
+        DnsRecord::Txt(text) => {
+            // A TXT record is a sequence of character-strings of at most 255
+            // bytes each, and must have at least one (possibly empty).
+            let strings = if text.is_empty() {
+                vec![&b""[..]]
+            } else {
+                text.as_bytes().chunks(255).collect()
+            };
+            Ok(Record::from_rdata(
+                name.clone(),
+                0,
+                RData::TXT(TXT::from_bytes(strings)),
+            ))
+        }
+
+        DnsRecord::Cname(target) => {
+            let target = Name::from_str(&target).map_err(|error| {
+                RequestError::ServFail(anyhow!(
+                    "serialization failed due to bad CNAME target {:?}: {:#}",
+                    &target,
+                    error
+                ))
+            })?;
+            Ok(Record::from_rdata(name.clone(), 0, RData::CNAME(CNAME(target))))
+        }
+
+        DnsRecord::Ptr(ptrdname) => {
+            let ptrdname = Name::from_str(&ptrdname).map_err(|error| {
+                RequestError::ServFail(anyhow!(
+                    "serialization failed due to bad PTR dname {:?}: {:#}",
+                    &ptrdname,
+                    error
+                ))
+            })?;
+            Ok(Record::from_rdata(name.clone(), 0, RData::PTR(PTR(ptrdname))))
+        }
     }
 }
 
+/// Returns the target of the CNAME record among `records`, if there is one
+fn cname_target(records: &[Record]) -> Option<Name> {
+    records.iter().find_map(|record| match record.data() {
+        RData::CNAME(cname) => Some(cname.0.clone()),
+        _ => None,
+    })
+}
+
 /// Handle a well-formed, decoded DNS query
-async fn handle_dns_message(
+///
+/// Once the zone the query is for has been found, its name is stored in
+/// `answered_zone`.
+fn handle_dns_message(
     request: &Request,
     mr: &MessageRequest,
-) -> Result<(), RequestError> {
+    answered_zone: &mut Option<String>,
+) -> Result<Vec<u8>, RequestError> {
     let log = &request.log;
     let store = &request.store;
     debug!(&log, "message_request"; "mr" => #?mr);
@@ -296,8 +1234,11 @@ async fn handle_dns_message(
         }
     };
     let name = query.original().name().clone();
-    let answer = store.query(query)?;
-    let rb = MessageResponseBuilder::from_message_request(mr);
+    let answer = store.query_from(query, request.client_addr)?;
+    *answered_zone = Some(answer.zone().to_string());
+    let signed_zone = store.signed_zone(answer.zone());
+    let signer = signed_zone.as_deref().filter(|_| dnssec_ok(mr));
+    let rb = response_builder(mr);
     let mut additional_records = vec![];
 
     let mut name_records = answer
@@ -314,14 +1255,86 @@ async fn handle_dns_message(
         name_records.push(store.soa_for(&answer)?);
     }
 
+    if answer.name.is_none()
+        && matches!(query.query_type(), RecordType::DNSKEY | RecordType::ANY)
+    {
+        // Similarly, the DNSKEY records of a signed zone come from its keys.
+        if let Some(zone) = &signed_zone {
+            name_records.extend(zone.dnskey_records(&name));
+        }
+    }
+
     // If there were no records for the name at all, the name simply is not
-    // known to us. Bail now to return NXDomain.
+    // known to us. Bail now to return NXDomain, or its signed equivalent.
     //
     // If there are no records after filtering, the name is known, just not with
     // any records.  Returning NXDomain later on would be incorrect.
     if name_records.is_empty() {
+        if let Some(zone) = signer {
+            return respond_denial(
+                request,
+                mr,
+                header,
+                zone,
+                &answer,
+                &name,
+                BTreeSet::from([dnssec::NXNAME]),
+            );
+        }
         return Err(RequestError::NxDomain(answer.queried_fqdn()));
     }
+
+    // If the name turns out not to have records of the requested type, a
+    // signed response has to prove which types it does have.
+    let mut name_types = name_records
+        .iter()
+        .map(|record| record.record_type())
+        .collect::<BTreeSet<_>>();
+    if answer.name.is_none() {
+        name_types.insert(RecordType::SOA);
+        if signed_zone.is_some() {
+            name_types.insert(RecordType::DNSKEY);
+        }
+    }
+
+    // If the name is an alias, the answer is the CNAME record followed by
+    // the answer for the name it points to, which may itself be an alias (RFC
+    // 1034, section 4.3.2).  Queries for the CNAME record itself (or for any
+    // record) get just the CNAME record.  We stop chasing when the target is
+    // in a zone we don't serve, and leave it to the client to look up the
+    // rest.  If the target is in one of our zones but doesn't exist, we
+    // still return the aliases we found, rather than NXDOMAIN.
+    let mut alias_records = Vec::new();
+    if !matches!(query.query_type(), RecordType::CNAME | RecordType::ANY) {
+        while let Some(target) = cname_target(&name_records) {
+            if alias_records.len() >= MAX_CNAME_CHAIN
+                || alias_records.iter().any(|r: &Record| *r.name() == target)
+            {
+                return Err(RequestError::ServFail(anyhow!(
+                    "CNAME chain from {} is too long or has a loop",
+                    name
+                )));
+            }
+            alias_records.extend(
+                name_records
+                    .drain(..)
+                    .filter(|r| r.record_type() == RecordType::CNAME),
+            );
+            let target_answer =
+                match store.query_name_from(&target, request.client_addr) {
+                    Ok(target_answer) => target_answer,
+                    Err(QueryError::NoZone(_)) => break,
+                    Err(error) => return Err(error.into()),
+                };
+            name_records = target_answer
+                .records
+                .unwrap_or_default()
+                .iter()
+                .map(|record| dns_record_to_record(&target, record))
+                .collect::<Result<Vec<_>, _>>()?;
+        }
+    }
+
     let response_records = name_records
         .into_iter()
         .filter(|record| match (query.query_type(), record.data()) {
@@ -331,6 +1344,12 @@ async fn handle_dns_message(
             (RecordType::SRV, RData::SRV(_)) => true,
             (RecordType::NS, RData::NS(_)) => true,
             (RecordType::SOA, RData::SOA(_)) => true,
+            (RecordType::TXT, RData::TXT(_)) => true,
+            (RecordType::CNAME, RData::CNAME(_)) => true,
+            (RecordType::PTR, RData::PTR(_)) => true,
+            (RecordType::DNSKEY, _) => {
+                record.record_type() == RecordType::DNSKEY
+            }
             _ => false,
         })
         .map(|record| {
@@ -391,6 +1410,24 @@ async fn handle_dns_message(
             Ok(record)
         })
         .collect::<Result<Vec<_>, RequestError>>()?;
+    let mut response_records =
+        alias_records.into_iter().chain(response_records).collect::<Vec<_>>();
+
+    if let Some(zone) = signer {
+        if response_records.is_empty() {
+            return respond_denial(
+                request, mr, header, zone, &answer, &name, name_types,
+            );
+        }
+        let rrsigs = zone
+            .sign_records(&response_records)
+            .map_err(RequestError::ServFail)?;
+        response_records.extend(rrsigs);
+        let rrsigs = zone
+            .sign_records(&additional_records)
+            .map_err(RequestError::ServFail)?;
+        additional_records.extend(rrsigs);
+    }
 
     debug!(
         &log,
@@ -399,41 +1436,307 @@ async fn handle_dns_message(
         "records" => ?&response_records,
         "additional_records" => ?&additional_records,
     );
-    respond_records(request, rb, header, &response_records, &additional_records)
-        .await
+    respond_records(
+        rb,
+        header,
+        &response_records,
+        &[],
+        &additional_records,
+        request.transport.max_response_size(mr),
+    )
 }
 
-/// Respond to a DNS query with the given set of DNS records
-async fn respond_records(
+/// Handle a well-formed, decoded zone transfer (AXFR or IXFR) request
+///
+/// On success, returns the messages that make up the response.  Once the zone
+/// has been found, its name is stored in `answered_zone`.
+fn handle_zone_transfer(
     request: &Request,
+    mr: &MessageRequest,
+    query: &LowerQuery,
+    answered_zone: &mut Option<String>,
+) -> Result<Vec<Vec<u8>>, RequestError> {
+    let log = &request.log;
+    let store = &request.store;
+    let zone = query.original().name();
+    debug!(&log, "zone transfer request"; "mr" => #?mr);
+
+    if !request.shared.transfer.allows(request.client_addr) {
+        return Err(RequestError::Refused(format!(
+            "zone transfers are not allowed for client {}",
+            request.client_addr
+        )));
+    }
+
+    // An IXFR request includes the SOA record of the version of the zone that
+    // the client already has (RFC 1995 section 3).  If it's missing, we can
+    // still send the whole zone.
+    let since_serial = match query.query_type() {
+        RecordType::IXFR => {
+            mr.name_servers().iter().find_map(|record| match record.data() {
+                RData::SOA(soa) => Some(soa.serial()),
+                _ => None,
+            })
+        }
+        _ => None,
+    };
+    if query.query_type() == RecordType::AXFR
+        && matches!(request.transport, Transport::Udp)
+    {
+        return Err(RequestError::Refused(String::from(
+            "AXFR is only supported over TCP",
+        )));
+    }
+
+    let transfer = store.zone_transfer(zone, since_serial).map_err(
+        |error| match error {
+            QueryError::NoZone(name) => {
+                RequestError::Refused(format!("not one of our zones: {}", name))
+            }
+            error => error.into(),
+        },
+    )?;
+    let apex = store.query_name(zone)?;
+    *answered_zone = Some(apex.zone().to_string());
+    let soa = store.soa_for(&apex)?;
+    let current_soa = soa_with_serial(&soa, transfer.current.serial)?;
+
+    let mut header = Header::response_from_request(mr.header());
+    header.set_authoritative(true);
+
+    let response_records = if matches!(request.transport, Transport::Udp)
+        || since_serial == Some(transfer.current.serial)
+    {
+        // The response to an IXFR request that we can't fit in a UDP message,
+        // or from a client that's already up to date, is just the current SOA
+        // record.  In the first case, the client retries over TCP (RFC 1995
+        // section 4).
+        vec![current_soa]
+    } else if let Some(previous) = &transfer.previous {
+        // We only have the two versions of the zone, not the changes that
+        // took one to the other, so we send the differences between them as
+        // a single (condensed) set of changes (RFC 1995 section 4): the new
+        // SOA, the old SOA and the records deleted, the new SOA and the
+        // records added, and the new SOA again.
+        let names = previous
+            .names
+            .keys()
+            .chain(transfer.current.names.keys())
+            .collect::<BTreeSet<_>>();
+        let mut deleted = Vec::new();
+        let mut added = Vec::new();
+        for key in names {
+            let old = previous.names.get(key).map(Vec::as_slice).unwrap_or(&[]);
+            let new = transfer
+                .current
+                .names
+                .get(key)
+                .map(Vec::as_slice)
+                .unwrap_or(&[]);
+            let name = name_in_zone(zone, key)?;
+            for record in old.iter().filter(|r| !new.contains(*r)) {
+                deleted.push(dns_record_to_record(&name, record)?);
+            }
+            for record in new.iter().filter(|r| !old.contains(*r)) {
+                added.push(dns_record_to_record(&name, record)?);
+            }
+        }
+
+        std::iter::once(current_soa.clone())
+            .chain(std::iter::once(soa_with_serial(&soa, previous.serial)?))
+            .chain(deleted)
+            .chain(std::iter::once(current_soa.clone()))
+            .chain(added)
+            .chain(std::iter::once(current_soa))
+            .collect()
+    } else {
+        // Otherwise, we send the whole zone, bracketed by its SOA record
+        // (RFC 5936 section 2.2).  This is also how we respond to an IXFR
+        // request for a version of the zone that we no longer have (RFC 1995
+        // section 2).
+        let mut keys = transfer.current.names.keys().collect::<Vec<_>>();
+        keys.sort();
+        let mut records = vec![current_soa.clone()];
+        for key in keys {
+            let name = name_in_zone(zone, key)?;
+            for record in &transfer.current.names[key] {
+                records.push(dns_record_to_record(&name, record)?);
+            }
+        }
+        records.push(current_soa);
+        records
+    };
+
+    debug!(
+        &log,
+        "zone transfer response";
+        "zone" => &transfer.zone,
+        "serial" => transfer.current.serial,
+        "since_serial" => ?since_serial,
+        "incremental" => transfer.previous.is_some(),
+        "nrecords" => response_records.len(),
+    );
+
+    // A large zone may not fit in one message.  Over TCP, we may send as many
+    // as we need (RFC 5936 section 2.2).
+    let mut responses = Vec::new();
+    let mut chunk: Vec<Record> = Vec::new();
+    let mut chunk_size = 0;
+    for record in response_records {
+        let record_size = record
+            .to_bytes()
+            .map_err(|error| {
+                RequestError::ServFail(anyhow!(
+                    "failed to encode record: {:#}",
+                    error
+                ))
+            })?
+            .len();
+        if !chunk.is_empty()
+            && chunk_size + record_size > MAX_TRANSFER_RECORDS_SIZE
+        {
+            responses.push(respond_records(
+                response_builder(mr),
+                header,
+                &chunk,
+                &[],
+                &[],
+                request.transport.max_response_size(mr),
+            )?);
+            chunk.clear();
+            chunk_size = 0;
+        }
+        chunk.push(record);
+        chunk_size += record_size;
+    }
+    responses.push(respond_records(
+        response_builder(mr),
+        header,
+        &chunk,
+        &[],
+        &[],
+        request.transport.max_response_size(mr),
+    )?);
+    Ok(responses)
+}
+
+/// Returns the fully-qualified name for the name `key` in `zone`, where `key`
+/// is as stored in a [`storage::ZoneVersion`]
+fn name_in_zone(zone: &Name, key: &str) -> Result<Name, RequestError> {
+    let mut zone = zone.clone();
+    zone.set_fqdn(true);
+    if key == ZONE_APEX_NAME {
+        return Ok(zone);
+    }
+    Name::from_str(key).and_then(|name| name.append_domain(&zone)).map_err(
+        |error| {
+            RequestError::ServFail(anyhow!(
+                "bad name {:?} in zone {}: {:#}",
+                key,
+                zone,
+                error
+            ))
+        },
+    )
+}
+
+/// Returns a copy of the SOA record `soa` with the serial `serial`
+fn soa_with_serial(soa: &Record, serial: u32) -> Result<Record, RequestError> {
+    let RData::SOA(rdata) = soa.data() else {
+        return Err(RequestError::ServFail(anyhow!(
+            "expected an SOA record, found {:?}",
+            soa
+        )));
+    };
+    Ok(Record::from_rdata(
+        soa.name().clone(),
+        soa.ttl(),
+        RData::SOA(SOA::new(
+            rdata.mname().clone(),
+            rdata.rname().clone(),
+            serial,
+            rdata.refresh(),
+            rdata.retry(),
+            rdata.expire(),
+            rdata.minimum(),
+        )),
+    ))
+}
+
+/// Respond to a DNS query with the given set of DNS records
+fn respond_records(
     rb: MessageResponseBuilder<'_>,
     header: Header,
     response_records: &[Record],
+    authority_records: &[Record],
     additional_records: &[Record],
-) -> Result<(), RequestError> {
+    max_size: u16,
+) -> Result<Vec<u8>, RequestError> {
     let mresp = rb.build(
         header,
         response_records.iter().collect::<Vec<&Record>>(),
-        vec![],
+        authority_records,
         vec![],
         additional_records,
     );
 
-    encode_and_send(&request, mresp, "records").await.map_err(|error| {
+    encode(mresp, "records", max_size).map_err(|error| {
         RequestError::ServFail(anyhow!("failed to emit response: {:#}", error))
     })
 }
 
+/// Respond to a query for `name`, in the signed zone `zone`, that has no
+/// records of the requested type, with signed proof of that
+///
+/// `types` are the types of records that `name` does have, or `NXNAME` if it
+/// doesn't exist at all.  Either way, the response is NOERROR with no
+/// answers: with compact denial of existence, clients can only tell a
+/// nonexistent name by the NXNAME type in the NSEC record (RFC 9824).
+fn respond_denial(
+    request: &Request,
+    mr: &MessageRequest,
+    header: Header,
+    zone: &SignedZone,
+    answer: &Answer,
+    name: &Name,
+    types: BTreeSet<RecordType>,
+) -> Result<Vec<u8>, RequestError> {
+    let soa = request.store.soa_for(&answer.for_apex())?;
+    let nsec =
+        zone.denial_nsec(name, &types).map_err(RequestError::ServFail)?;
+    let mut authority_records = vec![soa, nsec];
+    let rrsigs = zone
+        .sign_records(&authority_records)
+        .map_err(RequestError::ServFail)?;
+    authority_records.extend(rrsigs);
+
+    debug!(
+        &request.log,
+        "dns denial response";
+        "name" => %name,
+        "types" => ?types,
+    );
+    respond_records(
+        response_builder(mr),
+        header,
+        &[],
+        &authority_records,
+        &[],
+        request.transport.max_response_size(mr),
+    )
+}
+
 /// Respond to a DNS query with an NXDOMAIN error
 ///
 /// This means that we are authoritative for the parent domain and the requested
 /// name definitely does not exist.
-async fn respond_nxdomain(
+fn respond_nxdomain(
     request: &Request,
     rb_nxdomain: MessageResponseBuilder<'_>,
     rb_servfail: MessageResponseBuilder<'_>,
     header: &Header,
-) {
+    max_size: u16,
+) -> Option<Vec<u8>> {
     let log = &request.log;
     let mut mresp = rb_nxdomain.error_msg(&header, ResponseCode::NXDomain);
 
@@ -454,13 +1757,41 @@ async fn respond_nxdomain(
     // doesn't cary any RFC meaning anyway.
     mresp.header_mut().set_authoritative(true);
 
-    if let Err(error) = encode_and_send(request, mresp, "NXDOMAIN").await {
-        error!(
-            log,
-            "switching to SERVFAIL after failure to encode NXDOMAIN ({:#})",
-            error
-        );
-        respond_servfail(request, rb_servfail, header).await;
+    match encode(mresp, "NXDOMAIN", max_size) {
+        Ok(response) => Some(response),
+        Err(error) => {
+            error!(
+                log,
+                "switching to SERVFAIL after failure to encode NXDOMAIN ({:#})",
+                error
+            );
+            respond_servfail(request, rb_servfail, header, max_size)
+        }
+    }
+}
+
+/// Respond to a DNS query with a REFUSED error
+///
+/// This means that we won't answer this query for policy reasons, as when a
+/// client that isn't allowed to transfer zones asks to.
+fn respond_refused(
+    request: &Request,
+    rb_refused: MessageResponseBuilder<'_>,
+    rb_servfail: MessageResponseBuilder<'_>,
+    header: &Header,
+    max_size: u16,
+) -> Option<Vec<u8>> {
+    let mresp = rb_refused.error_msg(header, ResponseCode::Refused);
+    match encode(mresp, "REFUSED", max_size) {
+        Ok(response) => Some(response),
+        Err(error) => {
+            error!(
+                &request.log,
+                "switching to SERVFAIL after failure to encode REFUSED ({:#})",
+                error
+            );
+            respond_servfail(request, rb_servfail, header, max_size)
+        }
     }
 }
 
@@ -469,52 +1800,77 @@ async fn respond_nxdomain(
 /// This can be a catch-all for any kind of server-side failure.  We also use it
 /// when we're not authoritative for a domain because this generally causes
 /// clients to try another nameserver (which is usually what's wanted).
-async fn respond_servfail(
+fn respond_servfail(
     request: &Request,
     rb: MessageResponseBuilder<'_>,
     header: &Header,
-) {
+    max_size: u16,
+) -> Option<Vec<u8>> {
     let mresp = rb.error_msg(header, ResponseCode::ServFail);
-    if let Err(error) = encode_and_send(request, mresp, "SERVFAIL").await {
-        error!(&request.log, "failed to send SERVFAIL: {:#}", error);
+    match encode(mresp, "SERVFAIL", max_size) {
+        Ok(response) => Some(response),
+        Err(error) => {
+            error!(&request.log, "failed to encode SERVFAIL: {:#}", error);
+            None
+        }
+    }
+}
+
+/// Builds an empty response to `mr` with the TC (truncated) bit set, asking
+/// the client to retry over TCP
+///
+/// This is what response rate limiting sends in place of some of the
+/// responses it withholds.
+fn respond_truncated(
+    request: &Request,
+    mr: &MessageRequest,
+) -> Option<Vec<u8>> {
+    let mut header = Header::response_from_request(mr.header());
+    header.set_authoritative(true);
+    header.set_truncated(true);
+    let mut message = Message::new();
+    message.set_header(header);
+    message.add_queries(mr.queries().iter().map(|q| q.original().clone()));
+    if mr.edns().is_some() {
+        let mut edns = Edns::new();
+        edns.set_max_payload(MAX_UDP_PAYLOAD);
+        edns.set_version(0);
+        message.set_edns(edns);
+    }
+    match message.to_vec() {
+        Ok(response) => Some(response),
+        Err(error) => {
+            error!(
+                &request.log,
+                "failed to encode truncated response";
+                InlineErrorChain::new(&error),
+            );
+            None
+        }
     }
 }
 
 /// Encode the given message (which might describe an error or a collection of
 /// records) as a reply to a request
-fn encode_and_send<'a, Answers, NameServers, Soa, Additionals>(
-    request: &'a Request,
+///
+/// If the message doesn't fit in `max_size` bytes, as many records as fit are
+/// included and the TC (truncated) bit is set in the header.
+fn encode<'a, Answers, NameServers, Soa, Additionals>(
     mresp: MessageResponse<'a, 'a, Answers, NameServers, Soa, Additionals>,
     label: &'static str,
-) -> impl std::future::Future<Output = anyhow::Result<()>> + 'a
+    max_size: u16,
+) -> anyhow::Result<Vec<u8>>
 where
     Answers: Iterator<Item = &'a Record> + Send + 'a,
     NameServers: Iterator<Item = &'a Record> + Send + 'a,
     Soa: Iterator<Item = &'a Record> + Send + 'a,
     Additionals: Iterator<Item = &'a Record> + Send + 'a,
 {
-    async move {
-        let mut resp_data = Vec::new();
-        let mut enc = BinEncoder::new(&mut resp_data);
-        let _ = mresp
-            .destructive_emit(&mut enc)
-            .with_context(|| format!("encoding {}", label))?;
-
-        // If we get this far and fail to send the data, there's nothing else to
-        // do.  Log the problem and treat this as a success as far as the caller
-        // is concerned.
-        if let Err(error) =
-            request.socket.send_to(&resp_data, &request.client_addr).await
-        {
-            error!(
-                &request.log,
-                "failed to send {} to {:?}",
-                label,
-                request.client_addr.to_string();
-                InlineErrorChain::new(&error),
-            );
-        }
-
-        Ok(())
-    }
+    let mut resp_data = Vec::new();
+    let mut enc = BinEncoder::new(&mut resp_data);
+    enc.set_max_size(max_size);
+    let _ = mresp
+        .destructive_emit(&mut enc)
+        .with_context(|| format!("encoding {}", label))?;
+    Ok(resp_data)
 }
@@ -0,0 +1,460 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Online DNSSEC signing
+//!
+//! Zones configured with DNSSEC keys are signed as answers are built, rather
+//! than ahead of time: the records themselves come from Nexus unsigned, and
+//! the keys roll over on their own schedule.  Each RRset in an answer gets an
+//! RRSIG record from each of the zone's active zone-signing keys.  The
+//! zone's DNSKEY RRset is instead signed by all of its key-signing keys, so
+//! that resolvers can validate it with whichever key the parent zone's DS
+//! records currently refer to while a key-signing key is being rolled over.
+//!
+//! Names that don't exist (or don't have the requested type) are denied with
+//! "compact denial of existence" (RFC 9824): rather than proving that no
+//! names lie between two existing ones, which would require the server to
+//! know every name's neighbours, the answer is a NOERROR response with an
+//! NSEC record that covers just the queried name.  Nonexistent names are
+//! marked with the NXNAME pseudo-type in the NSEC record's type bitmap.
+//!
+//! hickory only knows how to sign and encode DNSSEC records with features we
+//! don't build it with, so we encode the records we need here, and hand them
+//! to hickory as opaque RDATA.
+
+use anyhow::{Context, anyhow, bail};
+use hickory_proto::rr::RData;
+use hickory_proto::rr::Record;
+use hickory_proto::rr::RecordType;
+use hickory_proto::rr::rdata::NULL;
+use hickory_resolver::Name;
+use internal_dns_types::config::{DnssecKeyRole, DnssecKeyState, DnssecZone};
+use internal_dns_types::dnssec::SigningKey;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::str::FromStr;
+
+/// The NXNAME pseudo-type, which marks a name in an NSEC record as
+/// nonexistent (RFC 9824 section 2)
+pub(crate) const NXNAME: RecordType = RecordType::Unknown(128);
+
+/// DNS class IN, the only one we serve
+const CLASS_IN: u16 = 1;
+
+/// How far before the time of signing an RRSIG record's validity period
+/// begins, to allow for clock skew between us and resolvers
+const SIGNATURE_INCEPTION_OFFSET: chrono::TimeDelta =
+    chrono::TimeDelta::hours(1);
+
+/// How long after the time of signing an RRSIG record remains valid
+///
+/// Our answers have a TTL of zero, so this mostly matters to resolvers that
+/// cache anyway, and to secondary servers.
+const SIGNATURE_VALIDITY: chrono::TimeDelta = chrono::TimeDelta::days(7);
+
+/// A zone that we sign, along with its keys
+#[derive(Debug)]
+pub(crate) struct SignedZone {
+    zone_name: String,
+    name: Name,
+    keys: Vec<SigningKey>,
+}
+
+impl SignedZone {
+    pub(crate) fn new(zone: &DnssecZone) -> anyhow::Result<SignedZone> {
+        let name = Name::from_str(&zone.zone_name)
+            .with_context(|| format!("bad zone name {:?}", zone.zone_name))?;
+        let keys = zone
+            .keys
+            .iter()
+            .map(SigningKey::new)
+            .collect::<Result<Vec<_>, _>>()
+            .with_context(|| format!("keys for zone {:?}", zone.zone_name))?;
+        if !keys.iter().any(|key| {
+            key.role() == DnssecKeyRole::Ksk
+                && key.state() == DnssecKeyState::Active
+        }) {
+            bail!("zone {:?} has no active key-signing key", zone.zone_name);
+        }
+        if !keys.iter().any(|key| {
+            key.role() == DnssecKeyRole::Zsk
+                && key.state() == DnssecKeyState::Active
+        }) {
+            bail!("zone {:?} has no active zone-signing key", zone.zone_name);
+        }
+        Ok(SignedZone { zone_name: zone.zone_name.clone(), name, keys })
+    }
+
+    pub(crate) fn zone_name(&self) -> &str {
+        &self.zone_name
+    }
+
+    pub(crate) fn keys(&self) -> &[SigningKey] {
+        &self.keys
+    }
+
+    /// Returns whether `name` is in this zone
+    pub(crate) fn contains(&self, name: &Name) -> bool {
+        self.name.zone_of(name)
+    }
+
+    /// Returns the zone's DNSKEY records, owned by `apex` (which should be
+    /// the zone's name as it appeared in the query)
+    pub(crate) fn dnskey_records(&self, apex: &Name) -> Vec<Record> {
+        self.keys
+            .iter()
+            .map(|key| {
+                opaque_record(apex, RecordType::DNSKEY, key.dnskey_rdata())
+            })
+            .collect()
+    }
+
+    /// Returns RRSIG records for each RRset among `records` that is in this
+    /// zone
+    ///
+    /// `records` may contain records from more than one RRset, in any order.
+    pub(crate) fn sign_records(
+        &self,
+        records: &[Record],
+    ) -> anyhow::Result<Vec<Record>> {
+        let mut rrsets: BTreeMap<(Name, RecordType), Vec<&Record>> =
+            BTreeMap::new();
+        for record in records {
+            let rtype = record.record_type();
+            if !self.contains(record.name()) || rtype == RecordType::RRSIG {
+                continue;
+            }
+            rrsets
+                .entry((record.name().to_lowercase(), rtype))
+                .or_default()
+                .push(record);
+        }
+
+        let now = chrono::Utc::now();
+        let mut signatures = Vec::new();
+        for ((_, rtype), rrset) in rrsets {
+            let signers = self.keys.iter().filter(|key| {
+                if rtype == RecordType::DNSKEY {
+                    key.role() == DnssecKeyRole::Ksk
+                } else {
+                    key.role() == DnssecKeyRole::Zsk
+                        && key.state() == DnssecKeyState::Active
+                }
+            });
+            for key in signers {
+                signatures.push(self.sign_rrset(key, rtype, &rrset, now)?);
+            }
+        }
+        Ok(signatures)
+    }
+
+    /// Returns an RRSIG record made by `key` for `rrset`, which must all have
+    /// the same owner name and type (RFC 4034 section 3.1.8.1)
+    fn sign_rrset(
+        &self,
+        key: &SigningKey,
+        rtype: RecordType,
+        rrset: &[&Record],
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> anyhow::Result<Record> {
+        let owner = rrset[0].name();
+        let ttl = rrset[0].ttl();
+        let inception = serial_time(now - SIGNATURE_INCEPTION_OFFSET);
+        let expiration = serial_time(now + SIGNATURE_VALIDITY);
+        let labels = u8::try_from(owner.num_labels())
+            .context("owner name has too many labels")?;
+
+        let mut rrsig = Vec::new();
+        rrsig.extend_from_slice(&u16::from(rtype).to_be_bytes());
+        rrsig.push(key.algorithm().number());
+        rrsig.push(labels);
+        rrsig.extend_from_slice(&ttl.to_be_bytes());
+        rrsig.extend_from_slice(&expiration.to_be_bytes());
+        rrsig.extend_from_slice(&inception.to_be_bytes());
+        rrsig.extend_from_slice(&key.key_tag().to_be_bytes());
+        rrsig.extend_from_slice(&canonical_name(&self.name));
+
+        // The records are signed in canonical form, sorted by their RDATA
+        // and without duplicates (RFC 4034 section 6.3).
+        let owner_wire = canonical_name(owner);
+        let rdatas = rrset
+            .iter()
+            .map(|record| canonical_rdata(record.data()))
+            .collect::<Result<BTreeSet<_>, _>>()?;
+        let mut data = rrsig.clone();
+        for rdata in rdatas {
+            let rdlength = u16::try_from(rdata.len())
+                .map_err(|_| anyhow!("RDATA is too long"))?;
+            data.extend_from_slice(&owner_wire);
+            data.extend_from_slice(&u16::from(rtype).to_be_bytes());
+            data.extend_from_slice(&CLASS_IN.to_be_bytes());
+            data.extend_from_slice(&ttl.to_be_bytes());
+            data.extend_from_slice(&rdlength.to_be_bytes());
+            data.extend_from_slice(&rdata);
+        }
+
+        rrsig.extend_from_slice(&key.sign(&data)?);
+        Ok(opaque_record(owner, RecordType::RRSIG, rrsig))
+    }
+
+    /// Returns an NSEC record proving that `name` has no records other than
+    /// those of the given types, which should include `NXNAME` if the name
+    /// doesn't exist at all (RFC 9824 section 3)
+    pub(crate) fn denial_nsec(
+        &self,
+        name: &Name,
+        types: &BTreeSet<RecordType>,
+    ) -> anyhow::Result<Record> {
+        // The NSEC record covers only the queried name: the next name is
+        // the queried name's immediate successor in canonical order.
+        let mut next =
+            Name::from_labels(std::iter::once(&b"\0"[..]).chain(name.iter()))
+                .context("building next name")?;
+        next.set_fqdn(true);
+
+        let mut types = types.clone();
+        types.insert(RecordType::RRSIG);
+        types.insert(RecordType::NSEC);
+
+        // Unlike most names in RDATA, the next name in an NSEC record is not
+        // lowercased (RFC 6840 section 5.1).
+        let mut rdata = name_to_wire(&next, false);
+        rdata.extend_from_slice(&type_bitmap(&types));
+        Ok(opaque_record(name, RecordType::NSEC, rdata))
+    }
+}
+
+/// Returns a record with the given type and already-encoded RDATA
+fn opaque_record(name: &Name, rtype: RecordType, rdata: Vec<u8>) -> Record {
+    Record::from_rdata(
+        name.clone(),
+        0,
+        RData::Unknown { code: rtype, rdata: NULL::with(rdata) },
+    )
+}
+
+/// Returns `time` as an RRSIG record represents it: seconds since the Unix
+/// epoch, modulo 2^32 (RFC 4034 section 3.1.5)
+fn serial_time(time: chrono::DateTime<chrono::Utc>) -> u32 {
+    time.timestamp() as u32
+}
+
+/// Encodes `name` uncompressed, optionally lowercasing it
+fn name_to_wire(name: &Name, lowercase: bool) -> Vec<u8> {
+    let mut wire = Vec::new();
+    for label in name.iter() {
+        wire.push(label.len() as u8);
+        if lowercase {
+            wire.extend(label.iter().map(|b| b.to_ascii_lowercase()));
+        } else {
+            wire.extend_from_slice(label);
+        }
+    }
+    wire.push(0);
+    wire
+}
+
+/// Encodes `name` in canonical form (RFC 4034 section 6.2)
+fn canonical_name(name: &Name) -> Vec<u8> {
+    name_to_wire(name, true)
+}
+
+/// Encodes `rdata` in canonical form (RFC 4034 section 6.2)
+///
+/// This supports the types of records we serve.
+fn canonical_rdata(rdata: &RData) -> anyhow::Result<Vec<u8>> {
+    let mut wire = Vec::new();
+    match rdata {
+        RData::A(a) => wire.extend_from_slice(&a.0.octets()),
+        RData::AAAA(aaaa) => wire.extend_from_slice(&aaaa.0.octets()),
+        RData::NS(ns) => wire.extend(canonical_name(&ns.0)),
+        RData::CNAME(cname) => wire.extend(canonical_name(&cname.0)),
+        RData::PTR(ptr) => wire.extend(canonical_name(&ptr.0)),
+        RData::SRV(srv) => {
+            wire.extend_from_slice(&srv.priority().to_be_bytes());
+            wire.extend_from_slice(&srv.weight().to_be_bytes());
+            wire.extend_from_slice(&srv.port().to_be_bytes());
+            wire.extend(canonical_name(srv.target()));
+        }
+        RData::TXT(txt) => {
+            for string in txt.txt_data() {
+                let len = u8::try_from(string.len())
+                    .map_err(|_| anyhow!("TXT character-string is too long"))?;
+                wire.push(len);
+                wire.extend_from_slice(string);
+            }
+        }
+        RData::SOA(soa) => {
+            wire.extend(canonical_name(soa.mname()));
+            wire.extend(canonical_name(soa.rname()));
+            wire.extend_from_slice(&soa.serial().to_be_bytes());
+            wire.extend_from_slice(&soa.refresh().to_be_bytes());
+            wire.extend_from_slice(&soa.retry().to_be_bytes());
+            wire.extend_from_slice(&soa.expire().to_be_bytes());
+            wire.extend_from_slice(&soa.minimum().to_be_bytes());
+        }
+        RData::Unknown { rdata, .. } => {
+            wire.extend_from_slice(rdata.anything())
+        }
+        other => bail!("cannot sign records of type {}", other.record_type()),
+    }
+    Ok(wire)
+}
+
+/// Encodes the type bitmap of an NSEC record (RFC 4034 section 4.1.2)
+fn type_bitmap(types: &BTreeSet<RecordType>) -> Vec<u8> {
+    let mut windows: BTreeMap<u8, [u8; 32]> = BTreeMap::new();
+    for rtype in types {
+        let [window, low] = u16::from(*rtype).to_be_bytes();
+        let bitmap = windows.entry(window).or_insert([0; 32]);
+        bitmap[usize::from(low / 8)] |= 0x80 >> (low % 8);
+    }
+
+    let mut wire = Vec::new();
+    for (window, bitmap) in windows {
+        let len = bitmap.iter().rposition(|b| *b != 0).map_or(0, |i| i + 1);
+        wire.push(window);
+        wire.push(len as u8);
+        wire.extend_from_slice(&bitmap[..len]);
+    }
+    wire
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use internal_dns_types::config::{DnssecAlgorithm, DnssecKey};
+    use internal_dns_types::dnssec::generate_private_key;
+    use ring::signature::{ECDSA_P256_SHA256_FIXED, UnparsedPublicKey};
+    use std::net::Ipv6Addr;
+
+    fn key(role: DnssecKeyRole, state: DnssecKeyState) -> DnssecKey {
+        let algorithm = DnssecAlgorithm::EcdsaP256Sha256;
+        DnssecKey {
+            role,
+            state,
+            algorithm,
+            private_key: generate_private_key(algorithm).unwrap(),
+        }
+    }
+
+    fn test_zone() -> SignedZone {
+        SignedZone::new(&DnssecZone {
+            zone_name: String::from("oxide.example"),
+            keys: vec![
+                key(DnssecKeyRole::Ksk, DnssecKeyState::Active),
+                key(DnssecKeyRole::Zsk, DnssecKeyState::Active),
+                key(DnssecKeyRole::Zsk, DnssecKeyState::Published),
+            ],
+        })
+        .unwrap()
+    }
+
+    /// Checks `rrsig` against `rrset` the way a validator would, using the
+    /// key in `zone` that it names
+    fn verify(zone: &SignedZone, rrsig: &Record, rrset: &[Record]) {
+        let RData::Unknown { code, rdata } = rrsig.data() else {
+            panic!("RRSIG record has unexpected data: {:?}", rrsig);
+        };
+        assert_eq!(*code, RecordType::RRSIG);
+        let rdata = rdata.anything();
+
+        let key_tag = u16::from_be_bytes([rdata[16], rdata[17]]);
+        let key = zone
+            .keys()
+            .iter()
+            .find(|key| key.key_tag() == key_tag)
+            .expect("RRSIG names a key in the zone");
+        let signer = canonical_name(&zone.name);
+        let signature_start = 18 + signer.len();
+        assert_eq!(&rdata[18..signature_start], signer);
+        let (signed_rdata, signature) = rdata.split_at(signature_start);
+
+        let mut data = signed_rdata.to_vec();
+        let mut rdatas = rrset
+            .iter()
+            .map(|r| canonical_rdata(r.data()).unwrap())
+            .collect::<Vec<_>>();
+        rdatas.sort();
+        for rr in rdatas {
+            data.extend(canonical_name(rrsig.name()));
+            data.extend_from_slice(
+                &u16::from(rrset[0].record_type()).to_be_bytes(),
+            );
+            data.extend_from_slice(&CLASS_IN.to_be_bytes());
+            data.extend_from_slice(&0u32.to_be_bytes());
+            data.extend_from_slice(&(rr.len() as u16).to_be_bytes());
+            data.extend_from_slice(&rr);
+        }
+
+        let mut point = vec![0x04];
+        point.extend_from_slice(key.public_key());
+        UnparsedPublicKey::new(&ECDSA_P256_SHA256_FIXED, &point)
+            .verify(&data, signature)
+            .expect("signature verifies");
+    }
+
+    #[test]
+    fn test_sign_records() {
+        let zone = test_zone();
+        let name = Name::from_str("Host.Oxide.Example.").unwrap();
+        let other = Name::from_str("host.other.example.").unwrap();
+        let aaaa = |name: &Name, ip: Ipv6Addr| {
+            Record::from_rdata(name.clone(), 0, RData::AAAA(ip.into()))
+        };
+        let rrset = vec![
+            aaaa(&name, Ipv6Addr::LOCALHOST),
+            aaaa(&name, Ipv6Addr::UNSPECIFIED),
+        ];
+        let mut records = rrset.clone();
+        records.push(aaaa(&other, Ipv6Addr::LOCALHOST));
+
+        // Only the active ZSK signs, and only records in the zone.
+        let rrsigs = zone.sign_records(&records).unwrap();
+        assert_eq!(rrsigs.len(), 1);
+        assert_eq!(rrsigs[0].name(), &name);
+        verify(&zone, &rrsigs[0], &rrset);
+
+        // The DNSKEY RRset is signed by the KSK.
+        let apex = Name::from_str("oxide.example.").unwrap();
+        let dnskeys = zone.dnskey_records(&apex);
+        assert_eq!(dnskeys.len(), 3);
+        let rrsigs = zone.sign_records(&dnskeys).unwrap();
+        assert_eq!(rrsigs.len(), 1);
+        verify(&zone, &rrsigs[0], &dnskeys);
+    }
+
+    #[test]
+    fn test_denial_nsec() {
+        let zone = test_zone();
+        let name = Name::from_str("nope.oxide.example.").unwrap();
+        let nsec = zone.denial_nsec(&name, &BTreeSet::from([NXNAME])).unwrap();
+        let RData::Unknown { code, rdata } = nsec.data() else {
+            panic!("NSEC record has unexpected data: {:?}", nsec);
+        };
+        assert_eq!(*code, RecordType::NSEC);
+        let mut expected = b"\x01\x00\x04nope\x05oxide\x07example\x00".to_vec();
+        // RRSIG (46) and NSEC (47) in window 0, NXNAME (128) in window 0.
+        expected.extend_from_slice(&[0, 17]);
+        let mut bitmap = [0u8; 17];
+        bitmap[5] = 0x03;
+        bitmap[16] = 0x80;
+        expected.extend_from_slice(&bitmap);
+        assert_eq!(rdata.anything(), expected);
+    }
+
+    #[test]
+    fn test_zone_needs_active_keys() {
+        let error = SignedZone::new(&DnssecZone {
+            zone_name: String::from("oxide.example"),
+            keys: vec![key(DnssecKeyRole::Ksk, DnssecKeyState::Active)],
+        })
+        .unwrap_err();
+        assert!(
+            error.to_string().contains("no active zone-signing key"),
+            "{:#}",
+            error
+        );
+    }
+}
@@ -8,8 +8,9 @@ use crate::storage::{self, UpdateError};
 use dns_server_api::DnsServerApi;
 use dropshot::RequestContext;
 use internal_dns_types::config::{
-    DnsConfig, DnsConfigParams, ERROR_CODE_BAD_UPDATE_GENERATION,
-    ERROR_CODE_UPDATE_IN_PROGRESS,
+    DnsConfig, DnsConfigParams, DnssecConfig, DnssecConfigParams,
+    ERROR_CODE_BAD_UPDATE_GENERATION, ERROR_CODE_UPDATE_IN_PROGRESS,
+    VpcDnsConfig, VpcDnsConfigParams,
 };
 
 pub struct Context {
@@ -57,6 +58,54 @@ impl DnsServerApi for DnsServerApiImpl {
             .await?;
         Ok(dropshot::HttpResponseUpdatedNoContent())
     }
+
+    async fn vpc_dns_config_get(
+        rqctx: RequestContext<Context>,
+    ) -> Result<dropshot::HttpResponseOk<VpcDnsConfig>, dropshot::HttpError>
+    {
+        let apictx = rqctx.context();
+        Ok(dropshot::HttpResponseOk(apictx.store.vpc_dns_config()))
+    }
+
+    async fn vpc_dns_config_put(
+        rqctx: RequestContext<Context>,
+        rq: dropshot::TypedBody<VpcDnsConfigParams>,
+    ) -> Result<dropshot::HttpResponseUpdatedNoContent, dropshot::HttpError>
+    {
+        let apictx = rqctx.context();
+        apictx
+            .store
+            .vpc_dns_config_update(&rq.into_inner(), &rqctx.request_id)
+            .await?;
+        Ok(dropshot::HttpResponseUpdatedNoContent())
+    }
+
+    async fn dnssec_config_get(
+        rqctx: RequestContext<Context>,
+    ) -> Result<dropshot::HttpResponseOk<DnssecConfig>, dropshot::HttpError>
+    {
+        let apictx = rqctx.context();
+        let config = apictx.store.dnssec_config().map_err(|e| {
+            dropshot::HttpError::for_internal_error(format!(
+                "internal error: {:?}",
+                e
+            ))
+        })?;
+        Ok(dropshot::HttpResponseOk(config))
+    }
+
+    async fn dnssec_config_put(
+        rqctx: RequestContext<Context>,
+        rq: dropshot::TypedBody<DnssecConfigParams>,
+    ) -> Result<dropshot::HttpResponseUpdatedNoContent, dropshot::HttpError>
+    {
+        let apictx = rqctx.context();
+        apictx
+            .store
+            .dnssec_config_update(&rq.into_inner(), &rqctx.request_id)
+            .await?;
+        Ok(dropshot::HttpResponseUpdatedNoContent())
+    }
 }
 
 impl From<UpdateError> for dropshot::HttpError {
@@ -81,6 +130,10 @@ impl From<UpdateError> for dropshot::HttpError {
                 headers: None,
             },
 
+            UpdateError::InvalidConfig(_) => {
+                dropshot::HttpError::for_bad_request(None, message)
+            }
+
             UpdateError::InternalError(_) => {
                 dropshot::HttpError::for_internal_error(message)
             }
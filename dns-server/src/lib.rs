@@ -4,10 +4,10 @@
 
 //! Dropshot-configurable DNS server
 //!
-//! This crate provides a standalone program that runs a UDP-based DNS server
-//! along with a Dropshot server for configuring the records served over DNS.
-//! The following RFDs describe the overall design of this server and how it's
-//! used:
+//! This crate provides a standalone program that runs a DNS server (over both
+//! UDP and TCP) along with a Dropshot server for configuring the records served
+//! over DNS.  The following RFDs describe the overall design of this server and
+//! how it's used:
 //!
 //!   RFD 248 Omicron service discovery: server side
 //!   RFD 357 External DNS in the MVP
@@ -52,7 +52,11 @@
 //!    the persistent DNS data
 
 pub mod dns_server;
+mod dnssec;
 pub mod http_server;
+pub mod metrics;
+pub mod query_log;
+pub mod rate_limit;
 pub mod storage;
 
 use anyhow::{Context, anyhow};
@@ -91,7 +95,7 @@ pub async fn start_servers(
         .version_policy(dropshot::VersionPolicy::Dynamic(Box::new(
             dropshot::ClientSpecifiesVersionInHeader::new(
                 omicron_common::api::VERSION_HEADER,
-                dns_server_api::VERSION_SOA_AND_NS,
+                dns_server_api::VERSION_VPC_FORWARDERS,
             ),
         )))
         .start()
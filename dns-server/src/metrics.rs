@@ -0,0 +1,186 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Oximeter metrics describing the queries answered by the DNS server
+//!
+//! The DNS server records every query it answers into a [`QueryMetrics`].
+//! When the server is run with a zone ID, a [`Producer`] wrapping those
+//! metrics is registered with Oximeter so they end up in the timeseries
+//! database.
+
+use hickory_proto::op::ResponseCode;
+use hickory_proto::rr::RecordType;
+use oximeter::MetricsError;
+use oximeter::histogram::Histogram;
+use oximeter::types::{Cumulative, Sample};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use uuid::Uuid;
+
+oximeter::use_timeseries!("dns-server.toml");
+use dns_server::{DnsServer, Queries, QueryLatency, RateLimitedResponses};
+
+/// Query counters and latency histograms accumulated by a running DNS server
+///
+/// Cloning a `QueryMetrics` produces another handle onto the same counters.
+#[derive(Clone, Debug, Default)]
+pub struct QueryMetrics {
+    inner: Arc<Mutex<Inner>>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    queries: BTreeMap<QueryKey, Queries>,
+    latency: BTreeMap<(String, &'static str), QueryLatency>,
+    rate_limited: BTreeMap<(String, &'static str), RateLimitedResponses>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct QueryKey {
+    zone: String,
+    query_type: String,
+    response_code: String,
+    transport: &'static str,
+}
+
+impl QueryMetrics {
+    /// Records one answered query
+    ///
+    /// `zone` is the zone the query was answered from, or `None` if it was not
+    /// for any zone that this server serves.
+    pub(crate) fn record_query(
+        &self,
+        zone: Option<&str>,
+        query_type: Option<RecordType>,
+        response_code: ResponseCode,
+        transport: &'static str,
+        latency: Duration,
+    ) {
+        let zone = zone.unwrap_or("").to_string();
+        let key = QueryKey {
+            zone: zone.clone(),
+            query_type: query_type_name(query_type),
+            response_code: response_code_name(response_code),
+            transport,
+        };
+
+        let mut inner = self.inner.lock().unwrap();
+        inner
+            .queries
+            .entry(key.clone())
+            .or_insert_with(|| Queries {
+                zone: key.zone.into(),
+                query_type: key.query_type.into(),
+                response_code: key.response_code.into(),
+                transport: key.transport.into(),
+                datum: Cumulative::new(0),
+            })
+            .datum
+            .increment();
+
+        let latency_ns = u64::try_from(latency.as_nanos()).unwrap_or(u64::MAX);
+        let entry = inner
+            .latency
+            .entry((zone.clone(), transport))
+            .or_insert_with(|| QueryLatency {
+                zone: zone.into(),
+                transport: transport.into(),
+                datum: new_latency_histogram(),
+            });
+        // Samples beyond the last bin land in the overflow bin, so this can't
+        // fail for any non-negative value.
+        let _ = entry.datum.sample(latency_ns);
+    }
+
+    /// Records that a response was withheld by response rate limiting
+    ///
+    /// `action` describes what was sent instead ("dropped" or "truncated").
+    pub(crate) fn record_rate_limited(
+        &self,
+        zone: Option<&str>,
+        action: &'static str,
+    ) {
+        let zone = zone.unwrap_or("").to_string();
+        let mut inner = self.inner.lock().unwrap();
+        inner
+            .rate_limited
+            .entry((zone.clone(), action))
+            .or_insert_with(|| RateLimitedResponses {
+                zone: zone.into(),
+                action: action.into(),
+                datum: Cumulative::new(0),
+            })
+            .datum
+            .increment();
+    }
+
+    fn samples(&self, target: &DnsServer) -> Result<Vec<Sample>, MetricsError> {
+        let inner = self.inner.lock().unwrap();
+        let mut samples = Vec::with_capacity(
+            inner.queries.len()
+                + inner.latency.len()
+                + inner.rate_limited.len(),
+        );
+        for metric in inner.queries.values() {
+            samples.push(Sample::new(target, metric)?);
+        }
+        for metric in inner.latency.values() {
+            samples.push(Sample::new(target, metric)?);
+        }
+        for metric in inner.rate_limited.values() {
+            samples.push(Sample::new(target, metric)?);
+        }
+        Ok(samples)
+    }
+}
+
+/// Latency histogram covering 1us through 10s
+fn new_latency_histogram() -> Histogram<u64> {
+    Histogram::span_decades(3, 10).expect("statically valid histogram bounds")
+}
+
+fn query_type_name(query_type: Option<RecordType>) -> String {
+    match query_type {
+        Some(RecordType::Unknown(_)) | None => String::from("unknown"),
+        Some(t) => t.to_string(),
+    }
+}
+
+/// Returns the conventional mnemonic for a response code
+pub(crate) fn response_code_name(rcode: ResponseCode) -> String {
+    match rcode {
+        ResponseCode::NoError => String::from("NOERROR"),
+        ResponseCode::FormErr => String::from("FORMERR"),
+        ResponseCode::ServFail => String::from("SERVFAIL"),
+        ResponseCode::NXDomain => String::from("NXDOMAIN"),
+        ResponseCode::NotImp => String::from("NOTIMP"),
+        ResponseCode::Refused => String::from("REFUSED"),
+        other => format!("RCODE{}", u16::from(other)),
+    }
+}
+
+/// Oximeter producer that reports the [`QueryMetrics`] of one DNS server
+#[derive(Debug, Clone)]
+pub struct Producer {
+    target: DnsServer,
+    metrics: QueryMetrics,
+}
+
+impl Producer {
+    /// Returns a producer reporting `metrics` for the DNS server running in
+    /// the zone with ID `id`
+    pub fn new(id: Uuid, metrics: QueryMetrics) -> Self {
+        Self { target: DnsServer { id }, metrics }
+    }
+}
+
+impl oximeter::Producer for Producer {
+    fn produce(
+        &mut self,
+    ) -> Result<Box<dyn Iterator<Item = Sample> + 'static>, MetricsError> {
+        let samples = self.metrics.samples(&self.target)?;
+        Ok(Box::new(samples.into_iter()))
+    }
+}
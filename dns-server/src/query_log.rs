@@ -0,0 +1,108 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Sampled log of the queries answered by the DNS server
+//!
+//! Logging every query would let a busy (or attacked) server fill its log, so
+//! entries are limited to a configured number per second.  Entries beyond that
+//! are counted and the count is reported with the next entry that's logged.
+
+use serde::Deserialize;
+use slog::{Logger, info};
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+/// Configuration related to logging queries
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct QueryLogConfig {
+    /// The most queries to log per second
+    ///
+    /// Zero (the default) disables the query log.
+    #[serde(default)]
+    pub max_per_second: u32,
+}
+
+/// Describes one answered query
+#[derive(Debug)]
+pub(crate) struct QueryLogEntry<'a> {
+    pub name: Option<String>,
+    pub query_type: Option<String>,
+    pub zone: Option<&'a str>,
+    pub response_code: String,
+    pub answers: u16,
+    pub latency: Duration,
+    /// what happened to the response (see
+    /// [`crate::rate_limit::Verdict::action()`])
+    pub action: &'static str,
+}
+
+/// Logs a limited number of queries per second
+#[derive(Debug)]
+pub(crate) struct QueryLog {
+    max_per_second: u32,
+    window: Mutex<Window>,
+}
+
+#[derive(Debug)]
+struct Window {
+    start: Instant,
+    logged: u32,
+    suppressed: u64,
+}
+
+impl QueryLog {
+    pub(crate) fn new(config: &QueryLogConfig) -> Self {
+        QueryLog {
+            max_per_second: config.max_per_second,
+            window: Mutex::new(Window {
+                start: Instant::now(),
+                logged: 0,
+                suppressed: 0,
+            }),
+        }
+    }
+
+    /// Logs `entry` to `log` (which identifies the client and transport),
+    /// unless we've already logged as many entries as we may this second
+    pub(crate) fn log(
+        &self,
+        log: &Logger,
+        entry: &QueryLogEntry<'_>,
+        now: Instant,
+    ) {
+        if self.max_per_second == 0 {
+            return;
+        }
+
+        let suppressed = {
+            let mut window = self.window.lock().unwrap();
+            if now.saturating_duration_since(window.start)
+                >= Duration::from_secs(1)
+            {
+                window.start = now;
+                window.logged = 0;
+            }
+            if window.logged >= self.max_per_second {
+                window.suppressed += 1;
+                return;
+            }
+            window.logged += 1;
+            std::mem::take(&mut window.suppressed)
+        };
+
+        info!(
+            log,
+            "query";
+            "name" => entry.name.as_deref().unwrap_or(""),
+            "type" => entry.query_type.as_deref().unwrap_or(""),
+            "zone" => entry.zone.unwrap_or(""),
+            "rcode" => &entry.response_code,
+            "answers" => entry.answers,
+            "latency" => ?entry.latency,
+            "action" => entry.action,
+            "suppressed" => suppressed,
+        );
+    }
+}
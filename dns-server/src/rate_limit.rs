@@ -0,0 +1,369 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Response rate limiting (RRL) for UDP responses
+//!
+//! DNS over UDP makes it easy to spoof the source address of a query, which
+//! lets an attacker use an authoritative server to flood a victim with
+//! responses it never asked for.  Response rate limiting blunts this by
+//! limiting how often we send "the same" response to "the same" network:
+//!
+//! * Clients are grouped by network (by default, a /24 for IPv4 and a /56 for
+//!   IPv6), since an attacker can spoof any address in a victim's network.
+//! * Responses are grouped by what they say: answers by the name and type
+//!   queried, NXDOMAIN and empty (NODATA) responses by zone (so that queries
+//!   for made-up names all count against the same limit), and errors all
+//!   together.
+//!
+//! Each such group gets a token bucket refilled at the configured rate.  Once
+//! it's empty, responses are withheld.  Every `slip`th withheld response is
+//! sent as an empty, truncated response instead of being dropped altogether,
+//! so that a real client caught up in an attack can still get its answer by
+//! retrying over TCP.  TCP responses are never limited, since TCP requires a
+//! handshake that can't be spoofed.
+//!
+//! The state here is a best-effort defense: if it grows too large to track
+//! every client network, we'd rather answer than drop legitimate queries.
+
+use hickory_proto::rr::Name;
+use hickory_proto::rr::RecordType;
+use oxnet::IpNet;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::net::Ipv4Addr;
+use std::net::Ipv6Addr;
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Number of (network, response) pairs we'll track before discarding the ones
+/// that aren't currently being limited
+const MAX_TRACKED_RESPONSES: usize = 100_000;
+
+/// Configuration related to response rate limiting
+#[derive(Deserialize, Debug, Clone)]
+pub struct RateLimitConfig {
+    /// How many identical responses per second we'll send to one client
+    /// network
+    ///
+    /// Zero (the default) disables response rate limiting.
+    #[serde(default)]
+    pub responses_per_second: u32,
+    /// Of the responses withheld because of rate limiting, one in this many is
+    /// sent as a truncated response instead of being dropped
+    ///
+    /// Zero means that withheld responses are always dropped.  One means that
+    /// they're always truncated.
+    #[serde(default = "default_slip")]
+    pub slip: u32,
+    /// Length of the prefix used to group IPv4 clients into networks
+    #[serde(default = "default_ipv4_prefix_len")]
+    pub ipv4_prefix_len: u8,
+    /// Length of the prefix used to group IPv6 clients into networks
+    #[serde(default = "default_ipv6_prefix_len")]
+    pub ipv6_prefix_len: u8,
+    /// Networks whose hosts are never rate limited
+    #[serde(default)]
+    pub exempt_clients: Vec<IpNet>,
+}
+
+fn default_slip() -> u32 {
+    2
+}
+
+fn default_ipv4_prefix_len() -> u8 {
+    24
+}
+
+fn default_ipv6_prefix_len() -> u8 {
+    56
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        RateLimitConfig {
+            responses_per_second: 0,
+            slip: default_slip(),
+            ipv4_prefix_len: default_ipv4_prefix_len(),
+            ipv6_prefix_len: default_ipv6_prefix_len(),
+            exempt_clients: Vec::new(),
+        }
+    }
+}
+
+/// Describes a response for the purpose of deciding whether it's "the same"
+/// as others sent to the same network
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum ResponseKind<'a> {
+    /// a response with answers for `name` and `qtype`
+    Answer { name: &'a Name, qtype: RecordType },
+    /// an NXDOMAIN or NODATA response from `zone`
+    Empty { zone: Option<&'a str> },
+    /// any error response
+    Error,
+}
+
+/// What to do with a response
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Verdict {
+    /// send the response as usual
+    Send,
+    /// send nothing
+    Drop,
+    /// send an empty response with the TC bit set in place of the real one
+    Truncate,
+}
+
+impl Verdict {
+    /// Describes what was sent in place of a withheld response, for metrics
+    /// and logs
+    pub(crate) fn action(&self) -> &'static str {
+        match self {
+            Verdict::Send => "sent",
+            Verdict::Drop => "dropped",
+            Verdict::Truncate => "truncated",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum ResponseKey {
+    Answer { name: Name, qtype: u16 },
+    Empty { zone: String },
+    Error,
+}
+
+impl From<ResponseKind<'_>> for ResponseKey {
+    fn from(kind: ResponseKind<'_>) -> Self {
+        match kind {
+            ResponseKind::Answer { name, qtype } => ResponseKey::Answer {
+                name: name.to_lowercase(),
+                qtype: u16::from(qtype),
+            },
+            ResponseKind::Empty { zone } => {
+                ResponseKey::Empty { zone: zone.unwrap_or("").to_string() }
+            }
+            ResponseKind::Error => ResponseKey::Error,
+        }
+    }
+}
+
+/// Token bucket holding up to one second's worth of responses
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+    /// number of responses withheld since the bucket last ran dry, used to
+    /// pick which ones slip through truncated
+    withheld: u64,
+}
+
+impl TokenBucket {
+    fn new(rate: f64, now: Instant) -> Self {
+        TokenBucket { tokens: rate, last_refill: now, withheld: 0 }
+    }
+
+    /// Refills the bucket for the time elapsed since the last refill and
+    /// takes a token from it, if there is one
+    fn take(&mut self, rate: f64, now: Instant) -> bool {
+        let elapsed =
+            now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * rate).min(rate);
+        self.last_refill = now;
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            self.withheld = 0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns whether the bucket would be full at time `now`
+    fn is_full(&self, rate: f64, now: Instant) -> bool {
+        let elapsed =
+            now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens + elapsed * rate >= rate
+    }
+}
+
+/// Tracks responses sent to each client network and decides which ones to
+/// withhold
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    config: RateLimitConfig,
+    buckets: Mutex<HashMap<(IpAddr, ResponseKey), TokenBucket>>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(config: RateLimitConfig) -> Self {
+        RateLimiter { config, buckets: Mutex::new(HashMap::new()) }
+    }
+
+    /// Decides what to do with a response of kind `kind` to `client` at time
+    /// `now`
+    pub(crate) fn check(
+        &self,
+        client: IpAddr,
+        kind: ResponseKind<'_>,
+        now: Instant,
+    ) -> Verdict {
+        if self.config.responses_per_second == 0
+            || self.config.exempt_clients.iter().any(|net| net.contains(client))
+        {
+            return Verdict::Send;
+        }
+
+        let rate = f64::from(self.config.responses_per_second);
+        let key = (self.client_network(client), ResponseKey::from(kind));
+        let mut buckets = self.buckets.lock().unwrap();
+        if buckets.len() >= MAX_TRACKED_RESPONSES && !buckets.contains_key(&key)
+        {
+            // Buckets that have refilled completely hold no information that
+            // a new bucket wouldn't, so they can go.
+            buckets.retain(|_, bucket| !bucket.is_full(rate, now));
+            if buckets.len() >= MAX_TRACKED_RESPONSES {
+                // We're tracking too many networks that are actively being
+                // limited to tell who's who.  Fail open.
+                return Verdict::Send;
+            }
+        }
+
+        let bucket =
+            buckets.entry(key).or_insert_with(|| TokenBucket::new(rate, now));
+        if bucket.take(rate, now) {
+            return Verdict::Send;
+        }
+
+        bucket.withheld += 1;
+        match self.config.slip {
+            0 => Verdict::Drop,
+            slip if bucket.withheld % u64::from(slip) == 0 => Verdict::Truncate,
+            _ => Verdict::Drop,
+        }
+    }
+
+    /// Returns the network `client` belongs to for rate limiting purposes
+    fn client_network(&self, client: IpAddr) -> IpAddr {
+        match client {
+            IpAddr::V4(addr) => {
+                let len = u32::from(self.config.ipv4_prefix_len.min(32));
+                let mask = u32::MAX.checked_shl(32 - len).unwrap_or(0);
+                IpAddr::V4(Ipv4Addr::from(u32::from(addr) & mask))
+            }
+            IpAddr::V6(addr) => {
+                let len = u32::from(self.config.ipv6_prefix_len.min(128));
+                let mask = u128::MAX.checked_shl(128 - len).unwrap_or(0);
+                IpAddr::V6(Ipv6Addr::from(u128::from(addr) & mask))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    fn limiter(rate: u32, slip: u32) -> RateLimiter {
+        RateLimiter::new(RateLimitConfig {
+            responses_per_second: rate,
+            slip,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_disabled() {
+        let limiter = limiter(0, 2);
+        let client = IpAddr::from_str("192.0.2.1").unwrap();
+        let now = Instant::now();
+        for _ in 0..100 {
+            assert_eq!(
+                limiter.check(client, ResponseKind::Error, now),
+                Verdict::Send
+            );
+        }
+    }
+
+    #[test]
+    fn test_limit_and_slip() {
+        let limiter = limiter(3, 2);
+        let client = IpAddr::from_str("192.0.2.1").unwrap();
+        let name = Name::from_str("www.example.com.").unwrap();
+        let kind = ResponseKind::Answer { name: &name, qtype: RecordType::A };
+        let now = Instant::now();
+
+        let verdicts: Vec<_> =
+            (0..7).map(|_| limiter.check(client, kind, now)).collect();
+        assert_eq!(
+            verdicts,
+            [
+                Verdict::Send,
+                Verdict::Send,
+                Verdict::Send,
+                Verdict::Drop,
+                Verdict::Truncate,
+                Verdict::Drop,
+                Verdict::Truncate,
+            ]
+        );
+
+        // Another client on the same /24 shares the limit, but one on a
+        // different network doesn't.
+        let neighbor = IpAddr::from_str("192.0.2.200").unwrap();
+        assert_ne!(limiter.check(neighbor, kind, now), Verdict::Send);
+        let stranger = IpAddr::from_str("198.51.100.1").unwrap();
+        assert_eq!(limiter.check(stranger, kind, now), Verdict::Send);
+
+        // Different questions from the same client have their own limits.
+        let other_kind =
+            ResponseKind::Answer { name: &name, qtype: RecordType::AAAA };
+        assert_eq!(limiter.check(client, other_kind, now), Verdict::Send);
+
+        // The bucket refills over time.
+        let later = now + Duration::from_secs(1);
+        for _ in 0..3 {
+            assert_eq!(limiter.check(client, kind, later), Verdict::Send);
+        }
+        assert_ne!(limiter.check(client, kind, later), Verdict::Send);
+    }
+
+    #[test]
+    fn test_empty_responses_grouped_by_zone() {
+        let limiter = limiter(1, 0);
+        let client = IpAddr::from_str("fd00:1122:3344:101::1").unwrap();
+        let now = Instant::now();
+
+        let zone = ResponseKind::Empty { zone: Some("example.com") };
+        assert_eq!(limiter.check(client, zone, now), Verdict::Send);
+        assert_eq!(limiter.check(client, zone, now), Verdict::Drop);
+        assert_eq!(limiter.check(client, zone, now), Verdict::Drop);
+
+        // A client in the same /56 is limited too.
+        let neighbor = IpAddr::from_str("fd00:1122:3344:1ff::1").unwrap();
+        assert_eq!(limiter.check(neighbor, zone, now), Verdict::Drop);
+
+        let other_zone = ResponseKind::Empty { zone: Some("example.org") };
+        assert_eq!(limiter.check(client, other_zone, now), Verdict::Send);
+    }
+
+    #[test]
+    fn test_exempt_clients() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            responses_per_second: 1,
+            exempt_clients: vec![IpNet::from_str("10.0.0.0/8").unwrap()],
+            ..Default::default()
+        });
+        let client = IpAddr::from_str("10.1.2.3").unwrap();
+        let now = Instant::now();
+        for _ in 0..10 {
+            assert_eq!(
+                limiter.check(client, ResponseKind::Error, now),
+                Verdict::Send
+            );
+        }
+    }
+}
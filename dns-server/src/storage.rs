@@ -28,6 +28,10 @@
 //
 // - "config": describes the current generation and the list of DNS zones
 //   associated with that generation
+// - "vpc_config": describes the current generation of the zones private to
+//   VPCs, including all of their records (see below)
+// - "dnssec_config": describes the current generation of the DNSSEC keys for
+//   the zones that we sign (see below)
 //
 // Then we have one tree for each generation for each zone.  This tree describes
 // all the DNS names that appear in that zone and what records are associated
@@ -37,6 +41,10 @@
 //   zone.  Keys in this tree represent DNS names (excluding the zone's DNS name
 //   itself, which needs to be appended to each key to get the fully-qualified
 //   domain name).  Each value is a Vec of DNS records.
+// - "serials": maps each generation whose trees we still have (as a big-endian
+//   u64) to the SOA serial it was applied with.  Secondary servers identify the
+//   version of a zone they have by its serial, so this is what lets us find
+//   the data to compute an incremental zone transfer from (see below).
 //
 // For all values in the sled database, we store JSON-serialized Rust
 // structures.  We don't have to worry about versioning or compatibility of any
@@ -69,6 +77,38 @@
 // desired.
 //
 //
+// ZONE TRANSFERS
+//
+// Secondary servers may copy our zones with a full (AXFR) or incremental
+// (IXFR) zone transfer.  A full transfer is just the current generation's data.
+// An incremental transfer is the difference between the current generation's
+// data and that of the generation the secondary already has, which we can only
+// compute if we've kept that generation's trees.  Otherwise, the secondary gets
+// a full transfer instead.  Secondaries may also ask to be told when a new
+// generation is applied; callers can find out about that with
+// `Store::subscribe_applied()`.
+//
+//
+// VPC ZONES
+//
+// Zones private to VPCs are versioned separately from the zones above, with
+// their own generation number.  Each is only served to queries from the
+// addresses listed as the zone's clients, and several VPCs may have zones of
+// the same name, so they don't fit the one-tree-per-zone layout.  Instead, the
+// whole VPC configuration is stored as a single value, which is replaced
+// atomically by an update.  We keep a parsed copy in memory so that queries
+// don't have to deserialize it.
+//
+//
+// DNSSEC KEYS
+//
+// The keys that we sign zones with are likewise versioned separately, and
+// stored as a single value that's replaced atomically by an update.  We keep
+// the parsed keys in memory, ready to sign with.  Nothing here depends on
+// which zones the keys are for: a zone that we don't (or no longer) serve
+// just never has anything signed.
+//
+//
 // INTERFACE
 //
 // This module exposes just one noteworthy type: the `Store`.  You can think of
@@ -92,26 +132,40 @@
 // backwards-compatible way (but obviously one wouldn't get the scaling benefits
 // while continuing to use the old API).
 
+use crate::dnssec::SignedZone;
 use anyhow::{Context, anyhow};
 use camino::Utf8PathBuf;
 use hickory_proto::{op::LowerQuery, rr::LowerName};
 use hickory_resolver::Name;
 use internal_dns_types::{
-    config::{DnsConfig, DnsConfigParams, DnsConfigZone, DnsRecord},
+    config::{
+        DnsConfig, DnsConfigParams, DnsConfigZone, DnsRecord, DnssecConfig,
+        DnssecConfigParams, DnssecZone, DnssecZoneStatus, VpcDnsConfig,
+        VpcDnsConfigParams, VpcDnsZone,
+    },
     names::ZONE_APEX_NAME,
 };
+use internal_dns_types_versions::v4;
 use omicron_common::api::external::Generation;
 use serde::{Deserialize, Serialize};
 use sled::transaction::ConflictableTransactionError;
 use slog::{debug, error, info, o, warn};
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::net::SocketAddr;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::sync::RwLock;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 use thiserror::Error;
 use tokio::sync::Mutex;
+use tokio::sync::watch;
 
 const KEY_CONFIG: &'static str = "config";
+const KEY_VPC_CONFIG: &'static str = "vpc_config";
+const KEY_DNSSEC_CONFIG: &'static str = "dnssec_config";
+const TREE_SERIALS: &'static str = "serials";
 
 /// Configuration for persistent storage of DNS data
 #[derive(Deserialize, Debug)]
@@ -130,6 +184,39 @@ pub struct Store {
     keep: usize,
     updating: Arc<Mutex<Option<UpdateInfo>>>,
     poisoned: Arc<AtomicBool>,
+    vpc_config: Arc<RwLock<Arc<VpcDnsConfig>>>,
+    vpc_updating: Arc<Mutex<()>>,
+    dnssec: Arc<RwLock<Arc<DnssecState>>>,
+    dnssec_updating: Arc<Mutex<()>>,
+    applied: Arc<watch::Sender<Generation>>,
+}
+
+/// The DNSSEC keys we store, including their private halves
+#[derive(Debug, Deserialize, Serialize)]
+struct CurrentDnssecConfig {
+    generation: Generation,
+    time_created: chrono::DateTime<chrono::Utc>,
+    time_applied: chrono::DateTime<chrono::Utc>,
+    zones: Vec<DnssecZone>,
+}
+
+/// The current DNSSEC configuration, along with the parsed keys for each zone
+/// that we sign
+#[derive(Debug)]
+struct DnssecState {
+    config: CurrentDnssecConfig,
+    zones: Vec<Arc<SignedZone>>,
+}
+
+impl DnssecState {
+    fn new(config: CurrentDnssecConfig) -> anyhow::Result<DnssecState> {
+        let zones = config
+            .zones
+            .iter()
+            .map(|zone| SignedZone::new(zone).map(Arc::new))
+            .collect::<anyhow::Result<_>>()?;
+        Ok(DnssecState { config, zones })
+    }
 }
 
 /// A temporary schema for DNS configurations from before the presence of the
@@ -228,6 +315,24 @@ impl CurrentConfig {
     }
 }
 
+/// Parses the stored configuration of the VPC zones
+///
+/// Configurations stored before VPC zones had forwarders are in the format of
+/// the earlier API version, and are translated forward (with no forwarders).
+fn parse_vpc_config_with_fallback(
+    bytes: &[u8],
+) -> anyhow::Result<VpcDnsConfig> {
+    let current_result = serde_json::from_slice::<VpcDnsConfig>(bytes)
+        .context("parsing current VPC config");
+    if current_result.is_err()
+        && let Ok(config) =
+            serde_json::from_slice::<v4::config::VpcDnsConfig>(bytes)
+    {
+        return Ok(VpcDnsConfig::from(config));
+    }
+    current_result
+}
+
 #[derive(Debug, Error)]
 pub enum UpdateError {
     #[error(
@@ -250,6 +355,9 @@ pub enum UpdateError {
         req_id: String,
     },
 
+    #[error("invalid configuration: {0:#}")]
+    InvalidConfig(#[source] anyhow::Error),
+
     #[error("internal error")]
     InternalError(#[from] anyhow::Error),
 }
@@ -276,12 +384,51 @@ impl Store {
         db: Arc<sled::Db>,
         config: &Config,
     ) -> Result<Self, anyhow::Error> {
+        let vpc_config = match db
+            .get(KEY_VPC_CONFIG)
+            .context("fetching current VPC config")?
+        {
+            Some(bytes) => parse_vpc_config_with_fallback(&bytes)?,
+            None => {
+                let now = chrono::Utc::now();
+                VpcDnsConfig {
+                    generation: Generation::from_u32(0),
+                    time_created: now,
+                    time_applied: now,
+                    forwarders: vec![],
+                    zones: vec![],
+                }
+            }
+        };
+        let dnssec_config = match db
+            .get(KEY_DNSSEC_CONFIG)
+            .context("fetching current DNSSEC config")?
+        {
+            Some(bytes) => serde_json::from_slice(&bytes)
+                .context("parsing current DNSSEC config")?,
+            None => {
+                let now = chrono::Utc::now();
+                CurrentDnssecConfig {
+                    generation: Generation::from_u32(0),
+                    time_created: now,
+                    time_applied: now,
+                    zones: vec![],
+                }
+            }
+        };
+        let dnssec = DnssecState::new(dnssec_config)
+            .context("loading current DNSSEC keys")?;
         let store = Store {
             log,
             db,
             keep: config.keep_old_generations,
             updating: Arc::new(Mutex::new(None)),
             poisoned: Arc::new(AtomicBool::new(false)),
+            vpc_config: Arc::new(RwLock::new(Arc::new(vpc_config))),
+            vpc_updating: Arc::new(Mutex::new(())),
+            dnssec: Arc::new(RwLock::new(Arc::new(dnssec))),
+            dnssec_updating: Arc::new(Mutex::new(())),
+            applied: Arc::new(watch::Sender::new(Generation::from_u32(0))),
         };
         if store.read_config_optional()?.is_none() {
             let now = chrono::Utc::now();
@@ -302,6 +449,7 @@ impl Store {
         let config = store.read_config()?;
         store.prune_newer(&config);
         store.prune_older(&config);
+        store.applied.send_replace(config.generation);
         Ok(store)
     }
 
@@ -351,35 +499,7 @@ impl Store {
                 // those reads finish.  (That creates a new problem: what if the
                 // read gets stuck for some reason?  We don't want to leave
                 // these trees hanging around forever.)
-                let tree_name =
-                    Self::tree_name_for_zone(zone_name, config.generation);
-                let tree = self
-                    .db
-                    .open_tree(&tree_name)
-                    .with_context(|| format!("opening tree {:?}", tree_name))?;
-
-                let records = tree
-                    .iter()
-                    .map(|entry| {
-                        let (name_bytes, records_bytes) =
-                            entry.context("loading entry")?;
-                        let name = std::str::from_utf8(&name_bytes)
-                            .with_context(|| {
-                                format!("parsing {:?} key name", tree_name)
-                            })?;
-                        let records: Vec<DnsRecord> =
-                            serde_json::from_slice(&records_bytes)
-                                .with_context(|| {
-                                    format!(
-                                        "parsing {:?} key {:?}",
-                                        tree_name, name
-                                    )
-                                })?;
-                        Ok((name.to_owned(), records))
-                    })
-                    .collect::<anyhow::Result<_>>()
-                    .context("assembling records")?;
-
+                let records = self.read_zone(zone_name, config.generation)?;
                 Ok(DnsConfigZone { zone_name: zone_name.to_owned(), records })
             })
             .collect::<anyhow::Result<_>>()?;
@@ -393,6 +513,191 @@ impl Store {
         })
     }
 
+    /// Reads all of the names and records in zone `zone_name` as of generation
+    /// `generation`
+    fn read_zone(
+        &self,
+        zone_name: &str,
+        generation: Generation,
+    ) -> anyhow::Result<HashMap<String, Vec<DnsRecord>>> {
+        let tree_name = Self::tree_name_for_zone(zone_name, generation);
+        let tree = self
+            .db
+            .open_tree(&tree_name)
+            .with_context(|| format!("opening tree {:?}", tree_name))?;
+
+        tree.iter()
+            .map(|entry| {
+                let (name_bytes, records_bytes) =
+                    entry.context("loading entry")?;
+                let name =
+                    std::str::from_utf8(&name_bytes).with_context(|| {
+                        format!("parsing {:?} key name", tree_name)
+                    })?;
+                let records: Vec<DnsRecord> =
+                    serde_json::from_slice(&records_bytes).with_context(
+                        || format!("parsing {:?} key {:?}", tree_name, name),
+                    )?;
+                Ok((name.to_owned(), records))
+            })
+            .collect::<anyhow::Result<_>>()
+            .context("assembling records")
+    }
+
+    /// Returns the names of the zones in the current generation (not
+    /// including zones private to VPCs)
+    pub(crate) fn zone_names(&self) -> anyhow::Result<Vec<String>> {
+        Ok(self.read_config()?.zones)
+    }
+
+    /// Returns a receiver that's notified each time a new generation of DNS
+    /// data is applied
+    ///
+    /// The value is the generation most recently applied.  VPC zones are not
+    /// included: they're not versioned with the other zones and they're never
+    /// transferred to secondary servers.
+    pub(crate) fn subscribe_applied(&self) -> watch::Receiver<Generation> {
+        self.applied.subscribe()
+    }
+
+    /// Returns the data needed to transfer the zone named `zone` to a
+    /// secondary server
+    ///
+    /// If `since_serial` is provided, it's the serial of the version of the
+    /// zone that the secondary already has.  If we still have that version's
+    /// data (and it differs from the current version), it's returned too so
+    /// that the caller can send just the differences.
+    ///
+    /// If `zone` is not the apex of one of our zones, returns
+    /// `QueryError::NoZone`.
+    pub(crate) fn zone_transfer(
+        &self,
+        zone: &Name,
+        since_serial: Option<u32>,
+    ) -> Result<ZoneTransfer, QueryError> {
+        let config = self.read_config().map_err(QueryError::QueryFail)?;
+        let zone_lower = LowerName::new(zone);
+        let zone_name = config
+            .zones
+            .iter()
+            .find(|z| {
+                Name::from_str(z)
+                    .is_ok_and(|z| LowerName::new(&z) == zone_lower)
+            })
+            .ok_or_else(|| QueryError::NoZone(zone.to_string()))?;
+
+        let current = ZoneVersion {
+            serial: config.serial,
+            names: self
+                .read_zone(zone_name, config.generation)
+                .map_err(QueryError::QueryFail)?,
+        };
+
+        let previous = match since_serial {
+            Some(serial) if serial != config.serial => self
+                .generation_for_serial(serial, &config)
+                .map_err(QueryError::QueryFail)?
+                .filter(|generation| {
+                    // The zone may not have existed in that generation, in
+                    // which case there's nothing to compute differences from.
+                    let tree_name =
+                        Self::tree_name_for_zone(zone_name, *generation);
+                    self.db
+                        .tree_names()
+                        .iter()
+                        .any(|t| t.as_ref() == tree_name.as_bytes())
+                })
+                .map(|generation| {
+                    Ok(ZoneVersion {
+                        serial,
+                        names: self.read_zone(zone_name, generation)?,
+                    })
+                })
+                .transpose()
+                .map_err(QueryError::QueryFail)?,
+            _ => None,
+        };
+
+        Ok(ZoneTransfer { zone: zone_name.clone(), current, previous })
+    }
+
+    /// Returns the most recent generation older than the current one that was
+    /// applied with serial `serial`, if we still have its data
+    fn generation_for_serial(
+        &self,
+        serial: u32,
+        config: &CurrentConfig,
+    ) -> anyhow::Result<Option<Generation>> {
+        let tree = self
+            .db
+            .open_tree(TREE_SERIALS)
+            .with_context(|| format!("opening tree {:?}", TREE_SERIALS))?;
+        let mut found = None;
+        for entry in tree.iter() {
+            let (gen_bytes, serial_bytes) = entry.context("loading entry")?;
+            let gen_num = <[u8; 8]>::try_from(gen_bytes.as_ref())
+                .map(u64::from_be_bytes)
+                .map_err(|_| anyhow!("bad key in {:?} tree", TREE_SERIALS))?;
+            let generation = Generation::try_from(gen_num)
+                .with_context(|| format!("bad generation {}", gen_num))?;
+            let entry_serial: u32 = serde_json::from_slice(&serial_bytes)
+                .with_context(|| {
+                    format!("parsing serial for generation {}", generation)
+                })?;
+            if entry_serial == serial && generation < config.generation {
+                found = found.max(Some(generation));
+            }
+        }
+        Ok(found)
+    }
+
+    /// Fetches the current configuration of the zones private to VPCs
+    pub(crate) fn vpc_dns_config(&self) -> VpcDnsConfig {
+        VpcDnsConfig::clone(&self.vpc_config.read().unwrap())
+    }
+
+    /// Fetches the current DNSSEC configuration, without the keys' private
+    /// halves
+    pub(crate) fn dnssec_config(&self) -> anyhow::Result<DnssecConfig> {
+        let dnssec = Arc::clone(&self.dnssec.read().unwrap());
+        let zones = dnssec
+            .zones
+            .iter()
+            .map(|zone| {
+                let keys = zone
+                    .keys()
+                    .iter()
+                    .map(|key| key.public_key_status(zone.zone_name()))
+                    .collect::<anyhow::Result<_>>()?;
+                Ok(DnssecZoneStatus {
+                    zone_name: zone.zone_name().to_owned(),
+                    keys,
+                })
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(DnssecConfig {
+            generation: dnssec.config.generation,
+            time_created: dnssec.config.time_created,
+            time_applied: dnssec.config.time_applied,
+            zones,
+        })
+    }
+
+    /// Returns the keys for the zone named `zone_name` (as in an [`Answer`]),
+    /// if we sign it
+    pub(crate) fn signed_zone(
+        &self,
+        zone_name: &str,
+    ) -> Option<Arc<SignedZone>> {
+        self.dnssec
+            .read()
+            .unwrap()
+            .zones
+            .iter()
+            .find(|zone| zone.zone_name().eq_ignore_ascii_case(zone_name))
+            .cloned()
+    }
+
     pub(crate) fn soa_for(
         &self,
         answer: &Answer,
@@ -610,6 +915,25 @@ impl Store {
                 .with_context(|| format!("flush tree {:?}", tree_name))?;
         }
 
+        // Record the serial that this generation is applied with so that we can
+        // find its data if a secondary server asks for changes since then.
+        // Like the trees above, this must be written before the config.
+        let serials = self
+            .db
+            .open_tree(TREE_SERIALS)
+            .with_context(|| format!("opening tree {:?}", TREE_SERIALS))?;
+        serials
+            .insert(
+                u64::from(generation).to_be_bytes(),
+                serde_json::to_vec(&config.serial)
+                    .context("serializing serial")?,
+            )
+            .context("inserting serial")?;
+        serials
+            .flush_async()
+            .await
+            .with_context(|| format!("flush tree {:?}", TREE_SERIALS))?;
+
         let new_config = CurrentConfig {
             generation,
             serial: config.serial,
@@ -655,11 +979,128 @@ impl Store {
 
         debug!(&log, "flushing default tree");
         self.db.flush_async().await.context("flush")?;
+        self.applied.send_replace(generation);
 
         self.prune_older(&new_config);
         Ok(())
     }
 
+    /// Updates to a new generation of the zones private to VPCs
+    ///
+    /// Unlike [`Store::dns_config_update`], concurrent updates wait for each
+    /// other rather than failing: an update is a single write.
+    pub(crate) async fn vpc_dns_config_update(
+        &self,
+        config: &VpcDnsConfigParams,
+        req_id: &str,
+    ) -> Result<(), UpdateError> {
+        let log = &self.log.new(o!(
+            "req_id" => req_id.to_owned(),
+            "new_vpc_generation" => u64::from(config.generation),
+        ));
+
+        let _guard = self.vpc_updating.lock().await;
+        let current_generation = self.vpc_config.read().unwrap().generation;
+        if current_generation > config.generation {
+            return Err(UpdateError::BadUpdateGeneration {
+                current_generation,
+                attempted_generation: config.generation,
+            });
+        }
+        if current_generation == config.generation {
+            return Ok(());
+        }
+
+        // Names are matched case-insensitively, so store them lowercased, as
+        // `do_update` does for the other zones.
+        let zones = config
+            .zones
+            .iter()
+            .map(|zone| VpcDnsZone {
+                zone_name: zone.zone_name.to_lowercase(),
+                clients: zone.clients.clone(),
+                records: zone
+                    .records
+                    .iter()
+                    .filter(|(_, records)| !records.is_empty())
+                    .map(|(name, records)| {
+                        (name.to_lowercase(), records.clone())
+                    })
+                    .collect(),
+            })
+            .collect();
+        let new_config = VpcDnsConfig {
+            generation: config.generation,
+            time_created: config.time_created,
+            time_applied: chrono::Utc::now(),
+            forwarders: config.forwarders.clone(),
+            zones,
+        };
+        let new_config_bytes = serde_json::to_vec(&new_config)
+            .context("serializing VPC config")?;
+        self.db
+            .insert(KEY_VPC_CONFIG, new_config_bytes)
+            .context("updating VPC config")?;
+        self.db.flush_async().await.context("flush")?;
+
+        *self.vpc_config.write().unwrap() = Arc::new(new_config);
+        info!(log, "updated VPC zones generation");
+        Ok(())
+    }
+
+    /// Updates to a new generation of DNSSEC keys
+    ///
+    /// As with [`Store::vpc_dns_config_update`], concurrent updates wait for
+    /// each other rather than failing.
+    pub(crate) async fn dnssec_config_update(
+        &self,
+        config: &DnssecConfigParams,
+        req_id: &str,
+    ) -> Result<(), UpdateError> {
+        let log = &self.log.new(o!(
+            "req_id" => req_id.to_owned(),
+            "new_dnssec_generation" => u64::from(config.generation),
+        ));
+
+        let _guard = self.dnssec_updating.lock().await;
+        let current_generation = self.dnssec.read().unwrap().config.generation;
+        if current_generation > config.generation {
+            return Err(UpdateError::BadUpdateGeneration {
+                current_generation,
+                attempted_generation: config.generation,
+            });
+        }
+        if current_generation == config.generation {
+            return Ok(());
+        }
+
+        let new_config = CurrentDnssecConfig {
+            generation: config.generation,
+            time_created: config.time_created,
+            time_applied: chrono::Utc::now(),
+            zones: config
+                .zones
+                .iter()
+                .map(|zone| DnssecZone {
+                    zone_name: zone.zone_name.to_lowercase(),
+                    keys: zone.keys.clone(),
+                })
+                .collect(),
+        };
+        let new_config_bytes = serde_json::to_vec(&new_config)
+            .context("serializing DNSSEC config")?;
+        let new_state =
+            DnssecState::new(new_config).map_err(UpdateError::InvalidConfig)?;
+        self.db
+            .insert(KEY_DNSSEC_CONFIG, new_config_bytes)
+            .context("updating DNSSEC config")?;
+        self.db.flush_async().await.context("flush")?;
+
+        *self.dnssec.write().unwrap() = Arc::new(new_state);
+        info!(log, "updated DNSSEC keys generation");
+        Ok(())
+    }
+
     fn prune_newer(&self, config: &CurrentConfig) {
         let log = &self.log;
         let current_generation = config.generation;
@@ -679,6 +1120,7 @@ impl Store {
             });
 
         self.prune_trees(trees_to_prune, "too new");
+        self.prune_serials();
     }
 
     fn all_name_trees(
@@ -742,30 +1184,137 @@ impl Store {
             .collect::<Vec<_>>();
 
         // Now remove all but the last "keep" items.
-        if trees_older.len() < keep {
-            return;
+        if trees_older.len() >= keep {
+            // Sort by each tree's generation number and take the first "keep".
+            trees_older.sort_by_key(|(k, _)| *k);
+            let ntake = trees_older.len() - keep;
+            let trees_to_prune =
+                trees_older.into_iter().take(ntake).map(|(_, n)| n);
+            self.prune_trees(trees_to_prune, "too old");
         }
 
-        // Sort by each tree's generation number and take the first "keep".
-        trees_older.sort_by_key(|(k, _)| *k);
-        let ntake = trees_older.len() - keep;
-        let trees_to_prune =
-            trees_older.into_iter().take(ntake).map(|(_, n)| n);
-        self.prune_trees(trees_to_prune, "too old");
+        self.prune_serials();
+    }
+
+    /// Removes the recorded serials of generations whose trees are all gone
+    fn prune_serials(&self) {
+        let log = &self.log;
+        let generations =
+            self.all_name_trees().map(|(g, _)| g).collect::<BTreeSet<_>>();
+        let serials = match self.db.open_tree(TREE_SERIALS) {
+            Ok(serials) => serials,
+            Err(error) => {
+                warn!(
+                    log,
+                    "failed to open serials tree";
+                    "error_message" => #%error,
+                );
+                return;
+            }
+        };
+
+        for entry in serials.iter().keys() {
+            let gen_bytes = match entry {
+                Ok(gen_bytes) => gen_bytes,
+                Err(error) => {
+                    warn!(
+                        log,
+                        "failed to read serials tree";
+                        "error_message" => #%error,
+                    );
+                    return;
+                }
+            };
+            let keep = <[u8; 8]>::try_from(gen_bytes.as_ref())
+                .ok()
+                .map(u64::from_be_bytes)
+                .and_then(|g| Generation::try_from(g).ok())
+                .is_some_and(|g| generations.contains(&g));
+            if keep {
+                continue;
+            }
+            if let Err(error) = serials.remove(&gen_bytes) {
+                warn!(
+                    log,
+                    "failed to remove serial";
+                    "error_message" => #%error,
+                );
+            }
+        }
+    }
+
+    /// Returns the resolvers to forward queries from `client` to, for names
+    /// outside of its VPC zones
+    ///
+    /// This is empty unless `client` is one of the VPC zones' clients: we
+    /// don't recurse for anyone else.
+    pub(crate) fn vpc_forwarders(&self, client: SocketAddr) -> Vec<SocketAddr> {
+        let vpc_config = self.vpc_config.read().unwrap();
+        let is_vpc_client = vpc_config
+            .zones
+            .iter()
+            .any(|zone| zone.clients.iter().any(|c| c.contains(client)));
+        if is_vpc_client { vpc_config.forwarders.clone() } else { Vec::new() }
     }
 
     /// Returns an [`Answer`] describing the records associated with the name in
     /// the given DNS request, as well as the zone containing the name and the
     /// name prefix in that zone that the query is for.
     ///
+    /// The query was sent from `client`, and is first answered from the VPC
+    /// zones served to that client.
+    ///
     /// If the name does not match any zone, returns `QueryError::NoZone`.
-    pub(crate) fn query(
+    pub(crate) fn query_from(
         &self,
         query: &LowerQuery,
+        client: SocketAddr,
+    ) -> Result<Answer, QueryError> {
+        self.query_raw_from(query.name(), query.original().name(), client)
+    }
+
+    /// Like [`Store::query_name`], but for a query sent from `client`, which
+    /// is first answered from the VPC zones served to that client (as in
+    /// [`Store::query_from`])
+    pub(crate) fn query_name_from(
+        &self,
+        name: &Name,
+        client: SocketAddr,
     ) -> Result<Answer, QueryError> {
-        let name = query.name();
-        let orig_name = query.original().name();
-        self.query_raw(name, orig_name)
+        self.query_raw_from(&LowerName::new(name), name, client)
+    }
+
+    fn query_raw_from(
+        &self,
+        name: &LowerName,
+        orig_name: &Name,
+        client: SocketAddr,
+    ) -> Result<Answer, QueryError> {
+        let vpc_config = Arc::clone(&self.vpc_config.read().unwrap());
+        let zone = vpc_config.zones.iter().find_map(|zone| {
+            if !zone.clients.iter().any(|c| c.contains(client)) {
+                return None;
+            }
+            let zone_name = Name::from_str(&zone.zone_name).ok()?;
+            LowerName::new(&zone_name)
+                .zone_of(name)
+                .then_some((zone, zone_name))
+        });
+        let Some((zone, zone_name)) = zone else {
+            return self.query_raw(name, orig_name);
+        };
+
+        let key = Self::key_in_zone(&zone_name, orig_name);
+        debug!(&self.log, "VPC query key"; "key" => &key);
+        Ok(Answer {
+            zone: zone.zone_name.clone(),
+            name: if key == ZONE_APEX_NAME { None } else { Some(key.clone()) },
+            // The serial is only used in SOA records; VPC generations are not
+            // expected to outgrow it any sooner than the main generation.
+            serial: u32::try_from(u64::from(vpc_config.generation))
+                .unwrap_or(u32::MAX),
+            records: zone.records.get(&key).cloned(),
+        })
     }
 
     /// Returns an [`Answer`] describing the records associated with the given
@@ -800,23 +1349,8 @@ impl Store {
             .with_context(|| format!("open tree {:?}", tree_name))
             .map_err(QueryError::QueryFail)?;
 
-        // The name tree stores just the part of each name that doesn't include
-        // the zone.  So we need to trim the zone part from the name provided in
-        // the request.  (This basically duplicates work in `zone_of` above.)
-        let key = {
-            let zone_name = Name::from_str(zone_name).unwrap();
-            // This is implied by passing the `zone_of()` check above.
-            assert!(zone_name.num_labels() <= orig_name.num_labels());
-            let name_only_labels =
-                usize::from(orig_name.num_labels() - zone_name.num_labels());
-            let mut name_only =
-                Name::from_labels(orig_name.iter().take(name_only_labels))
-                    .unwrap();
-            name_only.set_fqdn(false);
-            let key = name_only.to_string().to_lowercase();
-            assert!(!key.ends_with('.'));
-            if key.is_empty() { ZONE_APEX_NAME.to_string() } else { key }
-        };
+        let key =
+            Self::key_in_zone(&Name::from_str(zone_name).unwrap(), orig_name);
 
         debug!(&self.log, "query key"; "key" => &key);
 
@@ -856,6 +1390,25 @@ impl Store {
 
         Ok(answer)
     }
+
+    /// Returns the key under which records for `orig_name` are stored in the
+    /// zone `zone_name`, which must contain it
+    fn key_in_zone(zone_name: &Name, orig_name: &Name) -> String {
+        // Zones store just the part of each name that doesn't include the
+        // zone.  So we need to trim the zone part from the name provided in
+        // the request.  (This basically duplicates work in `zone_of`.)
+        //
+        // This is implied by passing the `zone_of()` check.
+        assert!(zone_name.num_labels() <= orig_name.num_labels());
+        let name_only_labels =
+            usize::from(orig_name.num_labels() - zone_name.num_labels());
+        let mut name_only =
+            Name::from_labels(orig_name.iter().take(name_only_labels)).unwrap();
+        name_only.set_fqdn(false);
+        let key = name_only.to_string().to_lowercase();
+        assert!(!key.ends_with('.'));
+        if key.is_empty() { ZONE_APEX_NAME.to_string() } else { key }
+    }
 }
 
 #[derive(Debug, Error)]
@@ -886,6 +1439,21 @@ pub(crate) struct Answer {
 }
 
 impl Answer {
+    /// Returns an answer (without records) for the apex of the zone that
+    /// provided this answer
+    pub fn for_apex(&self) -> Answer {
+        Answer {
+            zone: self.zone.clone(),
+            name: None,
+            serial: self.serial,
+            records: None,
+        }
+    }
+
+    pub fn zone(&self) -> &str {
+        &self.zone
+    }
+
     pub fn queried_fqdn(&self) -> String {
         if let Some(name) = self.name.as_ref() {
             format!("{}.{}", name, self.zone)
@@ -895,6 +1463,28 @@ impl Answer {
     }
 }
 
+/// The data needed to transfer one of our zones to a secondary server
+#[derive(Debug)]
+pub(crate) struct ZoneTransfer {
+    /// The name of the zone, as configured
+    pub zone: String,
+    /// The zone's current contents
+    pub current: ZoneVersion,
+    /// The zone's contents as of the serial that the secondary already has,
+    /// if it asked for an incremental transfer and we still have them
+    pub previous: Option<ZoneVersion>,
+}
+
+/// The contents of one of our zones as of a particular generation
+#[derive(Debug)]
+pub(crate) struct ZoneVersion {
+    /// The serial of the zone's SOA record in this generation
+    pub serial: u32,
+    /// The records for each name in the zone, keyed as in [`Answer::name`]
+    /// (except that the zone apex is [`ZONE_APEX_NAME`])
+    pub names: HashMap<String, Vec<DnsRecord>>,
+}
+
 /// Describes an ongoing update, if any
 struct UpdateInfo {
     start_time: chrono::DateTime<chrono::Utc>,
@@ -988,17 +1578,26 @@ mod test {
     use anyhow::Context;
     use camino::Utf8PathBuf;
     use camino_tempfile::Utf8TempDir;
+    use hickory_proto::op::LowerQuery;
+    use hickory_proto::op::Query;
     use hickory_proto::rr::LowerName;
+    use hickory_proto::rr::RecordType;
     use hickory_resolver::Name;
     use internal_dns_types::config::DnsConfigParams;
     use internal_dns_types::config::DnsConfigZone;
     use internal_dns_types::config::DnsRecord;
+    use internal_dns_types::config::VpcDnsClient;
+    use internal_dns_types::config::VpcDnsConfigParams;
+    use internal_dns_types::config::VpcDnsZone;
     use internal_dns_types::names::ZONE_APEX_NAME;
     use omicron_common::api::external::Generation;
     use omicron_test_utils::dev::test_setup_log;
     use std::collections::BTreeSet;
     use std::collections::HashMap;
+    use std::net::IpAddr;
+    use std::net::Ipv4Addr;
     use std::net::Ipv6Addr;
+    use std::net::SocketAddr;
     use std::str::FromStr;
     use std::sync::Arc;
 
@@ -1609,4 +2208,186 @@ mod test {
 
         tc.cleanup_successful();
     }
+
+    #[tokio::test]
+    async fn test_zone_transfer() {
+        let tc = TestContext::new("test_zone_transfer");
+        let zone = Name::from_str("zone1.internal").unwrap();
+        let mut applied = tc.store.subscribe_applied();
+
+        // Apply several generations, each with its own serial and a different
+        // address for one name.
+        for g in 1..=6 {
+            let update = DnsConfigParams {
+                time_created: chrono::Utc::now(),
+                generation: Generation::from_u32(g),
+                serial: g * 10,
+                zones: vec![DnsConfigZone {
+                    zone_name: "zone1.internal".to_string(),
+                    records: HashMap::from([(
+                        "host".to_string(),
+                        vec![DnsRecord::A(Ipv4Addr::new(10, 0, 0, g as u8))],
+                    )]),
+                }],
+            };
+            tc.store
+                .dns_config_update(&update, "my request id")
+                .await
+                .expect("can apply update");
+        }
+        assert!(applied.has_changed().unwrap());
+        assert_eq!(*applied.borrow_and_update(), Generation::from_u32(6));
+
+        // A full transfer is just the current generation's data.
+        let transfer = tc.store.zone_transfer(&zone, None).unwrap();
+        assert_eq!(transfer.zone, "zone1.internal");
+        assert_eq!(transfer.current.serial, 60);
+        assert_eq!(
+            transfer.current.names,
+            HashMap::from([(
+                "host".to_string(),
+                vec![DnsRecord::A(Ipv4Addr::new(10, 0, 0, 6))]
+            )])
+        );
+        assert!(transfer.previous.is_none());
+
+        // An incremental transfer includes the data the secondary already
+        // has, if we've kept it.
+        let transfer = tc.store.zone_transfer(&zone, Some(30)).unwrap();
+        let previous = transfer.previous.expect("have previous version");
+        assert_eq!(previous.serial, 30);
+        assert_eq!(
+            previous.names,
+            HashMap::from([(
+                "host".to_string(),
+                vec![DnsRecord::A(Ipv4Addr::new(10, 0, 0, 3))]
+            )])
+        );
+        let transfer = tc.store.zone_transfer(&zone, Some(60)).unwrap();
+        assert!(transfer.previous.is_none());
+        let transfer = tc.store.zone_transfer(&zone, Some(20)).unwrap();
+        assert!(transfer.previous.is_none());
+        let transfer = tc.store.zone_transfer(&zone, Some(25)).unwrap();
+        assert!(transfer.previous.is_none());
+
+        // We only remember the serials of the generations we've kept.
+        let serials = tc
+            .db
+            .open_tree(super::TREE_SERIALS)
+            .unwrap()
+            .iter()
+            .keys()
+            .map(|k| {
+                u64::from_be_bytes(k.unwrap().as_ref().try_into().unwrap())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(serials, [3, 4, 5, 6]);
+
+        // Only zones can be transferred.
+        let error = tc
+            .store
+            .zone_transfer(
+                &Name::from_str("host.zone1.internal").unwrap(),
+                None,
+            )
+            .unwrap_err();
+        assert!(matches!(error, QueryError::NoZone(_)));
+
+        tc.cleanup_successful();
+    }
+
+    #[tokio::test]
+    async fn test_vpc_zones() {
+        let tc = TestContext::new("test_vpc_zones");
+
+        // Two VPCs have zones of the same name, each served to its own
+        // clients.
+        let client1: SocketAddr = "[::ffff:192.0.2.1]:4000".parse().unwrap();
+        let client2: SocketAddr = "192.0.2.2:53".parse().unwrap();
+        let client3: SocketAddr = "192.0.2.1:20000".parse().unwrap();
+        let record1 = DnsRecord::A(Ipv4Addr::new(172, 30, 0, 5));
+        let record2 = DnsRecord::A(Ipv4Addr::new(172, 30, 0, 6));
+        let zone = |ip: Ipv4Addr, first_port, last_port, record: &DnsRecord| {
+            VpcDnsZone {
+                zone_name: "default.internal".to_string(),
+                clients: vec![VpcDnsClient {
+                    ip: IpAddr::V4(ip),
+                    first_port,
+                    last_port,
+                }],
+                records: HashMap::from([(
+                    "Web".to_string(),
+                    vec![record.clone()],
+                )]),
+            }
+        };
+        let forwarder: SocketAddr = "198.51.100.53:53".parse().unwrap();
+        let update = VpcDnsConfigParams {
+            generation: Generation::from_u32(1),
+            time_created: chrono::Utc::now(),
+            forwarders: vec![forwarder],
+            zones: vec![
+                zone(Ipv4Addr::new(192, 0, 2, 1), 0, 16383, &record1),
+                zone(Ipv4Addr::new(192, 0, 2, 2), 0, u16::MAX, &record2),
+            ],
+        };
+        tc.store
+            .vpc_dns_config_update(&update, "my request id")
+            .await
+            .expect("can apply update");
+
+        let query = |client| {
+            let name = Name::from_str("web.default.internal").unwrap();
+            tc.store
+                .query_from(
+                    &LowerQuery::query(Query::query(name, RecordType::A)),
+                    client,
+                )
+                .map(|answer| answer.records)
+        };
+        assert_eq!(query(client1).unwrap(), Some(vec![record1.clone()]));
+        assert_eq!(query(client2).unwrap(), Some(vec![record2.clone()]));
+
+        // Other clients, including other ports of the same address, don't see
+        // either zone.
+        assert!(matches!(query(client3), Err(QueryError::NoZone(_))));
+
+        // Only the zones' clients get their other queries forwarded.
+        assert_eq!(tc.store.vpc_forwarders(client1), vec![forwarder]);
+        assert_eq!(tc.store.vpc_forwarders(client2), vec![forwarder]);
+        assert!(tc.store.vpc_forwarders(client3).is_empty());
+
+        // The configuration can't move backwards.
+        let error = tc
+            .store
+            .vpc_dns_config_update(
+                &VpcDnsConfigParams {
+                    generation: Generation::from_u32(0),
+                    ..update.clone()
+                },
+                "my request id",
+            )
+            .await
+            .expect_err("update should have failed");
+        assert!(matches!(error, UpdateError::BadUpdateGeneration { .. }));
+
+        // The configuration survives a restart.
+        let store = Store::new_with_db(
+            tc.logctx.log.clone(),
+            Arc::clone(&tc.db),
+            &Config {
+                storage_path: tc.tmpdir.path().to_path_buf(),
+                keep_old_generations: 3,
+            },
+        )
+        .unwrap();
+        let config = store.vpc_dns_config();
+        assert_eq!(config.generation, Generation::from_u32(1));
+        assert_eq!(config.zones.len(), 2);
+        assert!(config.zones[0].records.contains_key("web"));
+        assert_eq!(config.forwarders, vec![forwarder]);
+        drop(store);
+
+        tc.cleanup_successful();
+    }
 }
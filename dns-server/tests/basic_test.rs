@@ -4,12 +4,19 @@
 
 use anyhow::{Context, Result};
 use camino_tempfile::Utf8TempDir;
+use dns_server::dns_server::TcpConfig;
+use dns_server::dns_server::TransferConfig;
+use dns_server::query_log::QueryLogConfig;
+use dns_server::rate_limit::RateLimitConfig;
 use dns_service_client::Client;
 use dropshot::{HandlerTaskMode, test_util::LogContext};
 use hickory_client::client::Client as HickoryClient;
 use hickory_client::{ClientError, client::ClientHandle};
-use hickory_proto::rr::RData;
+use hickory_proto::op::{Edns, Message, MessageType, OpCode, Query};
+use hickory_proto::rr::rdata::SOA;
+use hickory_proto::rr::{RData, Record};
 use hickory_proto::runtime::TokioRuntimeProvider;
+use hickory_proto::serialize::binary::{BinDecodable, BinEncodable};
 use hickory_proto::udp::UdpClientStream;
 use hickory_proto::xfer::Protocol;
 use hickory_resolver::ResolveErrorKind;
@@ -20,21 +27,33 @@ use hickory_resolver::{
     config::{NameServerConfig, ResolverConfig, ResolverOpts},
     proto::{
         op::ResponseCode,
-        rr::{DNSClass, Name, RecordType, rdata::AAAA},
+        rr::{
+            DNSClass, Name, RecordType,
+            rdata::{AAAA, CNAME},
+        },
         xfer::DnsResponse,
     },
 };
 use internal_dns_types::{
-    config::{DnsConfigParams, DnsConfigZone, DnsRecord, Srv},
+    config::{
+        DnsConfigParams, DnsConfigZone, DnsRecord, DnssecAlgorithm,
+        DnssecConfigParams, DnssecKey, DnssecKeyRole, DnssecKeyState,
+        DnssecZone, Srv, VpcDnsClient, VpcDnsConfigParams, VpcDnsZone,
+    },
     names::ZONE_APEX_NAME,
 };
+use omicron_common::api::external::Generation;
 use omicron_test_utils::dev::test_setup_log;
+use oximeter::types::Sample;
+use oximeter::{Datum, Field, FieldValue};
 use slog::o;
 use std::{
     collections::HashMap,
     net::Ipv6Addr,
-    net::{IpAddr, Ipv4Addr},
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    time::Duration,
 };
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
 const TEST_ZONE: &'static str = "oxide.internal";
 
@@ -637,6 +656,1128 @@ pub async fn servfail() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[tokio::test]
+pub async fn vpc_zone() -> Result<(), anyhow::Error> {
+    let test_ctx = init_client_server("vpc_zone").await?;
+    let client = &test_ctx.client;
+    let resolver = &test_ctx.resolver;
+
+    // Serve a VPC zone to queries from the loopback address, which is where
+    // the resolver sends them from, and another zone to some other client.
+    let addr = Ipv4Addr::new(172, 30, 0, 5);
+    let vpc_zone = |zone_name: &str, ip| VpcDnsZone {
+        zone_name: zone_name.to_string(),
+        clients: vec![VpcDnsClient { ip, first_port: 0, last_port: u16::MAX }],
+        records: HashMap::from([("web".to_string(), vec![DnsRecord::A(addr)])]),
+    };
+    let params = VpcDnsConfigParams {
+        generation: Generation::from_u32(1),
+        time_created: chrono::Utc::now(),
+        forwarders: vec![],
+        zones: vec![
+            vpc_zone("default.internal", IpAddr::V6(Ipv6Addr::LOCALHOST)),
+            vpc_zone("other.internal", IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1))),
+        ],
+    };
+    client.vpc_dns_config_put(&params).await?;
+    let config = client.vpc_dns_config_get().await?.into_inner();
+    assert_eq!(config.generation, params.generation);
+    assert_eq!(config.zones, params.zones);
+
+    let response = resolver.lookup_ip("web.default.internal.").await?;
+    let address = response.iter().next().expect("no addresses returned!");
+    assert_eq!(address, addr);
+
+    // The other zone is not ours to see.
+    lookup_ip_expect_error_code(
+        test_ctx.dns_server.local_address(),
+        resolver,
+        "web.other.internal.",
+        ResponseCode::ServFail,
+    )
+    .await;
+
+    test_ctx.cleanup().await;
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn vpc_forwarding() -> Result<(), anyhow::Error> {
+    // A second server stands in for the recursive resolver that VPC clients'
+    // other queries are forwarded to.
+    let upstream_ctx = init_client_server("vpc_forwarding_upstream").await?;
+    let upstream_addr = Ipv6Addr::new(0xfd00, 0, 0, 0, 0, 0, 0, 0x53);
+    dns_records_create(
+        &upstream_ctx.client,
+        TEST_ZONE,
+        HashMap::from([(
+            "www".to_string(),
+            vec![DnsRecord::Aaaa(upstream_addr)],
+        )]),
+    )
+    .await?;
+
+    let test_ctx = init_client_server("vpc_forwarding").await?;
+    let vpc_addr = Ipv4Addr::new(172, 30, 0, 5);
+    let params = VpcDnsConfigParams {
+        generation: Generation::from_u32(1),
+        time_created: chrono::Utc::now(),
+        forwarders: vec![upstream_ctx.dns_server.local_address()],
+        zones: vec![VpcDnsZone {
+            zone_name: "default.internal".to_string(),
+            clients: vec![VpcDnsClient {
+                ip: IpAddr::V6(Ipv6Addr::LOCALHOST),
+                first_port: 0,
+                last_port: u16::MAX,
+            }],
+            records: HashMap::from([(
+                "web".to_string(),
+                vec![DnsRecord::A(vpc_addr)],
+            )]),
+        }],
+    };
+    test_ctx.client.vpc_dns_config_put(&params).await?;
+    let config = test_ctx.client.vpc_dns_config_get().await?.into_inner();
+    assert_eq!(config.forwarders, params.forwarders);
+
+    // Queries to forward that arrive over UDP are only answered with a
+    // truncated response, since the client's address might be spoofed.
+    let name = Name::from_ascii(format!("www.{TEST_ZONE}."))?;
+    let query = query_message(1, name, RecordType::AAAA, None);
+    let (_, response) =
+        udp_exchange(test_ctx.dns_server.local_address(), &query).await?;
+    assert_eq!(response.id(), 1);
+    assert!(response.truncated());
+    assert!(response.answers().is_empty());
+
+    // The VPC zone is still answered locally, and names outside of it are
+    // answered by the forwarder once the client retries over TCP.
+    let tcp_resolver =
+        resolver_for(test_ctx.dns_server.local_address(), &[Protocol::Tcp]);
+    for resolver in [&test_ctx.resolver, &tcp_resolver] {
+        let response = resolver.lookup_ip("web.default.internal.").await?;
+        let address = response.iter().next().expect("no addresses returned!");
+        assert_eq!(address, vpc_addr);
+
+        let name = format!("www.{TEST_ZONE}.");
+        let response = resolver.lookup_ip(name.as_str()).await?;
+        let address = response.iter().next().expect("no addresses returned!");
+        assert_eq!(address, upstream_addr);
+    }
+
+    // Without a forwarder that answers, the query fails as it would have
+    // without any forwarders.
+    upstream_ctx.cleanup().await;
+    lookup_ip_expect_error_code(
+        test_ctx.dns_server.local_address(),
+        &test_ctx.resolver,
+        "unicorn.oxide.internal.",
+        ResponseCode::ServFail,
+    )
+    .await;
+
+    test_ctx.cleanup().await;
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn txt_lookup() -> Result<(), anyhow::Error> {
+    let test_ctx = init_client_server("txt_lookup").await?;
+    let client = &test_ctx.client;
+    let resolver = &test_ctx.resolver;
+
+    // Text longer than 255 bytes is split across several character-strings.
+    let short = "challenge-digest".to_string();
+    let long = "x".repeat(300);
+    let input_records = HashMap::from([
+        ("_acme-challenge".to_string(), vec![DnsRecord::Txt(short.clone())]),
+        ("long".to_string(), vec![DnsRecord::Txt(long.clone())]),
+    ]);
+    dns_records_create(client, TEST_ZONE, input_records.clone()).await?;
+    let records = dns_records_list(client, TEST_ZONE).await?;
+    assert_eq!(input_records, records);
+
+    let response =
+        resolver.txt_lookup(format!("_acme-challenge.{TEST_ZONE}.")).await?;
+    let txt = response.iter().next().expect("no TXT records returned!");
+    assert_eq!(txt.txt_data(), &[short.as_bytes().into()]);
+
+    let response = resolver.txt_lookup(format!("long.{TEST_ZONE}.")).await?;
+    let txt = response.iter().next().expect("no TXT records returned!");
+    assert_eq!(
+        txt.txt_data().iter().map(|s| s.len()).collect::<Vec<_>>(),
+        [255, 45]
+    );
+    assert_eq!(txt.txt_data().concat(), long.as_bytes());
+
+    test_ctx.cleanup().await;
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn cname_chasing() -> Result<(), anyhow::Error> {
+    let test_ctx = init_client_server("cname_chasing").await?;
+    let client = &test_ctx.client;
+    let resolver = &test_ctx.resolver;
+    let server_addr = test_ctx.dns_server.local_address();
+
+    let addr = Ipv6Addr::new(0xfd, 0, 0, 0, 0, 0, 0, 0x1);
+    let cname = |target: &str| vec![DnsRecord::Cname(target.to_string())];
+    let input_records = HashMap::from([
+        ("web".to_string(), vec![DnsRecord::Aaaa(addr)]),
+        ("www".to_string(), cname(&format!("web.{TEST_ZONE}"))),
+        ("alias".to_string(), cname(&format!("www.{TEST_ZONE}"))),
+        ("outside".to_string(), cname("example.com")),
+        ("dangling".to_string(), cname(&format!("unicorn.{TEST_ZONE}"))),
+        ("loop1".to_string(), cname(&format!("loop2.{TEST_ZONE}"))),
+        ("loop2".to_string(), cname(&format!("loop1.{TEST_ZONE}"))),
+    ]);
+    dns_records_create(client, TEST_ZONE, input_records.clone()).await?;
+    let records = dns_records_list(client, TEST_ZONE).await?;
+    assert_eq!(input_records, records);
+
+    let name = |label: &str| {
+        Name::from_ascii(format!("{label}.{TEST_ZONE}."))
+            .expect("can construct name for query")
+    };
+
+    // A query for an alias is answered with each alias in the chain, followed
+    // by the records of the name it ends at.
+    let response =
+        raw_dns_client_query(server_addr, name("alias"), RecordType::AAAA)
+            .await?;
+    assert_eq!(response.response_code(), ResponseCode::NoError);
+    let answers = response
+        .answers()
+        .iter()
+        .map(|r| (r.name().clone(), r.data().clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        answers,
+        [
+            (name("alias"), RData::CNAME(CNAME(name("www")))),
+            (name("www"), RData::CNAME(CNAME(name("web")))),
+            (name("web"), RData::AAAA(AAAA(addr))),
+        ]
+    );
+
+    // So the resolver finds the address the alias ends up at.
+    let response = resolver.lookup_ip(name("alias")).await?;
+    let address = response.iter().next().expect("no addresses returned!");
+    assert_eq!(address, addr);
+
+    // A query for the CNAME record itself isn't chased.
+    let response =
+        raw_dns_client_query(server_addr, name("alias"), RecordType::CNAME)
+            .await?;
+    assert_eq!(response.answers().len(), 1);
+    assert_eq!(response.answers()[0].data(), &RData::CNAME(CNAME(name("www"))));
+
+    // Aliases for names outside our zones, or names that don't exist, are
+    // returned on their own.
+    for label in ["outside", "dangling"] {
+        let response =
+            raw_dns_client_query(server_addr, name(label), RecordType::AAAA)
+                .await?;
+        assert_eq!(response.response_code(), ResponseCode::NoError);
+        assert_eq!(response.answers().len(), 1);
+        assert_eq!(response.answers()[0].record_type(), RecordType::CNAME);
+    }
+
+    // Loops are an error.
+    let response = raw_query_expect_err(
+        server_addr,
+        "loop1.oxide.internal.",
+        RecordType::A,
+    )
+    .await;
+    assert_eq!(response.response_code(), ResponseCode::ServFail);
+
+    test_ctx.cleanup().await;
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn ptr_lookup() -> Result<(), anyhow::Error> {
+    let test_ctx = init_client_server("ptr_lookup").await?;
+    let client = &test_ctx.client;
+    let resolver = &test_ctx.resolver;
+
+    // Serve a reverse zone alongside the forward one.
+    let addr = Ipv6Addr::new(0xfd00, 0x1122, 0x3344, 0x101, 0, 0, 0, 0x1);
+    let reverse_zone = "1.0.1.0.4.4.3.3.2.2.1.1.0.0.d.f.ip6.arpa";
+    let target = format!("web.{TEST_ZONE}");
+    dns_records_create(
+        client,
+        TEST_ZONE,
+        HashMap::from([("web".to_string(), vec![DnsRecord::Aaaa(addr)])]),
+    )
+    .await?;
+    let input_records = HashMap::from([(
+        "1.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0".to_string(),
+        vec![DnsRecord::Ptr(target.clone())],
+    )]);
+    dns_records_create(client, reverse_zone, input_records.clone()).await?;
+    let records = dns_records_list(client, reverse_zone).await?;
+    assert_eq!(input_records, records);
+
+    let response = resolver.reverse_lookup(IpAddr::V6(addr)).await?;
+    let names = response.iter().map(|ptr| ptr.0.clone()).collect::<Vec<_>>();
+    assert_eq!(names, [Name::from_ascii(format!("{target}."))?]);
+
+    test_ctx.cleanup().await;
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn tcp_query() -> Result<(), anyhow::Error> {
+    let test_ctx = init_client_server("tcp_query").await?;
+    let client = &test_ctx.client;
+
+    let name = "devron".to_string();
+    let addr1 = Ipv6Addr::new(0xfd, 0, 0, 0, 0, 0, 0, 0x1);
+    let addr2 = Ipv6Addr::new(0xfd, 0, 0, 0, 0, 0, 0, 0x2);
+    let input_records = HashMap::from([(
+        name.clone(),
+        vec![DnsRecord::Aaaa(addr1), DnsRecord::Aaaa(addr2)],
+    )]);
+    dns_records_create(client, TEST_ZONE, input_records).await?;
+
+    // Resolve the name using a resolver that only speaks TCP.
+    let resolver =
+        resolver_for(test_ctx.dns_server.local_address(), &[Protocol::Tcp]);
+    let response = resolver.lookup_ip(name + "." + TEST_ZONE + ".").await?;
+    let mut addresses = response.iter().collect::<Vec<_>>();
+    addresses.sort();
+    assert_eq!(addresses, [IpAddr::from(addr1), IpAddr::from(addr2)]);
+
+    // Errors come back over TCP too.
+    lookup_ip_expect_error_code(
+        test_ctx.dns_server.local_address(),
+        &resolver,
+        &format!("unicorn.{}.", TEST_ZONE),
+        ResponseCode::NXDomain,
+    )
+    .await;
+
+    // Several queries can be made over the same connection.
+    let fqdn = Name::from_ascii(format!("devron.{TEST_ZONE}."))?;
+    let mut stream =
+        tokio::net::TcpStream::connect(test_ctx.dns_server.local_address())
+            .await?;
+    for id in 0..3 {
+        let query = query_message(id, fqdn.clone(), RecordType::AAAA, None);
+        let response = tcp_exchange(&mut stream, &query).await?;
+        assert_eq!(response.id(), id);
+        assert_eq!(response.response_code(), ResponseCode::NoError);
+        assert_eq!(response.answers().len(), 2);
+    }
+
+    test_ctx.cleanup().await;
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn tcp_connection_limits() -> Result<(), anyhow::Error> {
+    let test_ctx = init_client_server_with_config(
+        "tcp_connection_limits",
+        dns_server::dns_server::Config {
+            bind_address: "[::1]:0".parse().unwrap(),
+            transfer: Default::default(),
+            rate_limit: Default::default(),
+            query_log: Default::default(),
+            tcp: TcpConfig { max_connections: 1, idle_timeout_secs: 1 },
+        },
+    )
+    .await?;
+    let client = &test_ctx.client;
+    let server_addr = test_ctx.dns_server.local_address();
+
+    let addr = Ipv6Addr::new(0xfd, 0, 0, 0, 0, 0, 0, 0x1);
+    let records =
+        HashMap::from([(String::from("devron"), vec![DnsRecord::Aaaa(addr)])]);
+    dns_records_create(client, TEST_ZONE, records).await?;
+
+    // The first connection is served as usual.
+    let fqdn = Name::from_ascii(format!("devron.{TEST_ZONE}."))?;
+    let mut stream = tokio::net::TcpStream::connect(server_addr).await?;
+    let query = query_message(1, fqdn.clone(), RecordType::AAAA, None);
+    let response = tcp_exchange(&mut stream, &query).await?;
+    assert_eq!(response.answers().len(), 1);
+
+    // Any more are closed right away.
+    let mut extra = tokio::net::TcpStream::connect(server_addr).await?;
+    let mut buf = [0u8; 1];
+    let read =
+        tokio::time::timeout(Duration::from_secs(10), extra.read(&mut buf))
+            .await
+            .context("extra connection was left open")?;
+    assert_eq!(read?, 0);
+
+    // Once the first connection has been idle for long enough, it's closed
+    // too.
+    let read =
+        tokio::time::timeout(Duration::from_secs(10), stream.read(&mut buf))
+            .await
+            .context("idle connection was left open")?;
+    assert_eq!(read?, 0);
+
+    test_ctx.cleanup().await;
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn udp_truncation() -> Result<(), anyhow::Error> {
+    let test_ctx = init_client_server("udp_truncation").await?;
+    let client = &test_ctx.client;
+    let server_addr = test_ctx.dns_server.local_address();
+
+    // A service with this many backends needs more than 512 bytes to describe
+    // (with the backends' addresses as additional records), but fits in our
+    // largest UDP response.
+    const NBACKENDS: usize = 10;
+    let service = "big-service";
+    dns_records_create(client, TEST_ZONE, service_records(service, NBACKENDS))
+        .await?;
+    let fqdn = Name::from_ascii(format!("{service}.{TEST_ZONE}."))?;
+
+    // Without EDNS(0), the response must fit in 512 bytes, so it's truncated.
+    let query = query_message(1, fqdn.clone(), RecordType::SRV, None);
+    let (size, response) = udp_exchange(server_addr, &query).await?;
+    assert!(size <= 512, "UDP response was {size} bytes");
+    assert!(response.truncated());
+    assert!(response.answers().len() < NBACKENDS);
+    assert!(response.extensions().is_none());
+
+    // With EDNS(0), the client can ask for a larger response.  We advertise
+    // the largest we're willing to send.
+    let query = query_message(2, fqdn.clone(), RecordType::SRV, Some(4096));
+    let (size, response) = udp_exchange(server_addr, &query).await?;
+    assert!(size > 512);
+    assert!(size <= usize::from(dns_server::dns_server::MAX_UDP_PAYLOAD));
+    assert!(!response.truncated());
+    assert_eq!(response.answers().len(), NBACKENDS);
+    assert_eq!(response.additionals().len(), NBACKENDS);
+    let edns = response.extensions().as_ref().expect("response has EDNS");
+    assert_eq!(edns.max_payload(), dns_server::dns_server::MAX_UDP_PAYLOAD);
+
+    // A buffer size smaller than 512 is treated as 512.
+    let query = query_message(3, fqdn.clone(), RecordType::SRV, Some(256));
+    let (size, response) = udp_exchange(server_addr, &query).await?;
+    assert!(size > 256 && size <= 512, "UDP response was {size} bytes");
+    assert!(response.truncated());
+
+    // Over TCP, the whole response comes back regardless.
+    let mut stream = tokio::net::TcpStream::connect(server_addr).await?;
+    let query = query_message(4, fqdn, RecordType::SRV, None);
+    let response = tcp_exchange(&mut stream, &query).await?;
+    assert!(!response.truncated());
+    assert_eq!(response.answers().len(), NBACKENDS);
+    assert_eq!(response.additionals().len(), NBACKENDS);
+
+    test_ctx.cleanup().await;
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn large_srv_lookup() -> Result<(), anyhow::Error> {
+    let test_ctx = init_client_server("large_srv_lookup").await?;
+    let client = &test_ctx.client;
+    let resolver = &test_ctx.resolver;
+
+    // This many backends won't fit in any UDP response, so the resolver has
+    // to retry over TCP to find them all.
+    const NBACKENDS: usize = 64;
+    let service = "huge-service";
+    dns_records_create(client, TEST_ZONE, service_records(service, NBACKENDS))
+        .await?;
+
+    let response =
+        resolver.srv_lookup(format!("{service}.{TEST_ZONE}.")).await?;
+    assert_eq!(response.iter().count(), NBACKENDS);
+    assert_eq!(response.ip_iter().count(), NBACKENDS);
+
+    test_ctx.cleanup().await;
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn zone_transfer_axfr() -> Result<(), anyhow::Error> {
+    let test_ctx = init_client_server_with_transfer(
+        "zone_transfer_axfr",
+        TransferConfig {
+            allowed_clients: vec!["::1/128".parse().unwrap()],
+            notify: vec![],
+        },
+    )
+    .await?;
+    let client = &test_ctx.client;
+    let server_addr = test_ctx.dns_server.local_address();
+
+    dns_records_create(client, TEST_ZONE, transfer_zone_records()).await?;
+    let serial = client.dns_config_get().await?.into_inner().serial;
+    let zone = Name::from_ascii(format!("{TEST_ZONE}."))?;
+
+    // The whole zone comes back, bracketed by its SOA record.
+    let mut stream = tokio::net::TcpStream::connect(server_addr).await?;
+    let query = query_message(1, zone.clone(), RecordType::AXFR, None);
+    let responses = tcp_transfer(&mut stream, &query).await?;
+    assert_eq!(responses.len(), 1);
+    let response = &responses[0];
+    assert_eq!(response.response_code(), ResponseCode::NoError);
+    assert!(response.authoritative());
+    let answers = response.answers();
+    assert_eq!(answers.len(), 5);
+    assert_eq!(soa_serial(&answers[0]), Some(serial));
+    assert_eq!(*answers[0].name(), zone);
+    assert_eq!(answers[1].record_type(), RecordType::NS);
+    assert_eq!(*answers[1].name(), zone);
+    assert_eq!(answers[2].record_type(), RecordType::A);
+    assert_eq!(answers[2].name().to_string(), format!("devron.{TEST_ZONE}."));
+    assert_eq!(answers[3].record_type(), RecordType::AAAA);
+    assert_eq!(answers[3].name().to_string(), format!("ns1.{TEST_ZONE}."));
+    assert_eq!(soa_serial(&answers[4]), Some(serial));
+
+    // AXFR isn't supported over UDP.
+    let query = query_message(2, zone.clone(), RecordType::AXFR, None);
+    let (_, response) = udp_exchange(server_addr, &query).await?;
+    assert_eq!(response.response_code(), ResponseCode::Refused);
+
+    // We don't transfer names that aren't zones.
+    let query = query_message(
+        3,
+        Name::from_ascii(format!("devron.{TEST_ZONE}."))?,
+        RecordType::AXFR,
+        None,
+    );
+    let response = tcp_exchange(&mut stream, &query).await?;
+    assert_eq!(response.response_code(), ResponseCode::Refused);
+
+    // A zone too large for one message is split across several.
+    let big_text = "x".repeat(40000);
+    let big_records = HashMap::from([
+        (String::from("big1"), vec![DnsRecord::Txt(big_text.clone())]),
+        (String::from("big2"), vec![DnsRecord::Txt(big_text)]),
+    ]);
+    dns_records_create(client, TEST_ZONE, big_records).await?;
+    let query = query_message(4, zone, RecordType::AXFR, None);
+    let responses = tcp_transfer(&mut stream, &query).await?;
+    assert!(responses.len() > 1);
+    let answers =
+        responses.iter().flat_map(|r| r.answers()).collect::<Vec<_>>();
+    assert_eq!(answers.len(), 7);
+    assert_eq!(
+        answers.iter().filter(|r| r.record_type() == RecordType::TXT).count(),
+        2
+    );
+
+    test_ctx.cleanup().await;
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn zone_transfer_refused() -> Result<(), anyhow::Error> {
+    // By default, no clients may transfer zones.
+    let test_ctx = init_client_server("zone_transfer_refused").await?;
+    let client = &test_ctx.client;
+    let server_addr = test_ctx.dns_server.local_address();
+
+    dns_records_create(client, TEST_ZONE, transfer_zone_records()).await?;
+    let zone = Name::from_ascii(format!("{TEST_ZONE}."))?;
+
+    let mut stream = tokio::net::TcpStream::connect(server_addr).await?;
+    for (id, record_ty) in [(1, RecordType::AXFR), (2, RecordType::IXFR)] {
+        let query = query_message(id, zone.clone(), record_ty, None);
+        let response = tcp_exchange(&mut stream, &query).await?;
+        assert_eq!(response.response_code(), ResponseCode::Refused);
+        assert!(response.answers().is_empty());
+    }
+
+    test_ctx.cleanup().await;
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn zone_transfer_ixfr() -> Result<(), anyhow::Error> {
+    let test_ctx = init_client_server_with_transfer(
+        "zone_transfer_ixfr",
+        TransferConfig {
+            allowed_clients: vec!["::/0".parse().unwrap()],
+            notify: vec![],
+        },
+    )
+    .await?;
+    let client = &test_ctx.client;
+    let server_addr = test_ctx.dns_server.local_address();
+    let zone = Name::from_ascii(format!("{TEST_ZONE}."))?;
+
+    dns_records_create(client, TEST_ZONE, transfer_zone_records()).await?;
+    let old_serial = client.dns_config_get().await?.into_inner().serial;
+
+    // Change one name's records and add another.
+    let new_addr = Ipv4Addr::new(10, 1, 2, 4);
+    let new_records = HashMap::from([
+        (String::from("devron"), vec![DnsRecord::A(new_addr)]),
+        (String::from("ferrari"), vec![DnsRecord::A(new_addr)]),
+    ]);
+    dns_records_create(client, TEST_ZONE, new_records).await?;
+    let new_serial = client.dns_config_get().await?.into_inner().serial;
+    assert_ne!(old_serial, new_serial);
+
+    // A client with the old version gets just the differences.
+    let mut stream = tokio::net::TcpStream::connect(server_addr).await?;
+    let query = ixfr_query_message(1, zone.clone(), old_serial);
+    let responses = tcp_transfer(&mut stream, &query).await?;
+    assert_eq!(responses.len(), 1);
+    let answers = responses[0].answers();
+    let serials = answers.iter().map(soa_serial).collect::<Vec<_>>();
+    assert_eq!(
+        serials,
+        [
+            Some(new_serial),
+            Some(old_serial),
+            None,
+            Some(new_serial),
+            None,
+            None,
+            Some(new_serial),
+        ]
+    );
+    let devron = format!("devron.{TEST_ZONE}.");
+    let ferrari = format!("ferrari.{TEST_ZONE}.");
+    assert_eq!(answers[2].name().to_string(), devron);
+    assert!(matches!(
+        answers[2].data(),
+        RData::A(a) if a.0 == Ipv4Addr::new(10, 1, 2, 3)
+    ));
+    assert_eq!(answers[4].name().to_string(), devron);
+    assert!(matches!(answers[4].data(), RData::A(a) if a.0 == new_addr));
+    assert_eq!(answers[5].name().to_string(), ferrari);
+    assert!(matches!(answers[5].data(), RData::A(a) if a.0 == new_addr));
+
+    // A client that's up to date gets just the current SOA record.
+    let query = ixfr_query_message(2, zone.clone(), new_serial);
+    let responses = tcp_transfer(&mut stream, &query).await?;
+    assert_eq!(responses.len(), 1);
+    let serials =
+        responses[0].answers().iter().map(soa_serial).collect::<Vec<_>>();
+    assert_eq!(serials, [Some(new_serial)]);
+
+    // A client with a version we don't have gets the whole zone.
+    let query = ixfr_query_message(3, zone.clone(), new_serial + 1000);
+    let responses = tcp_transfer(&mut stream, &query).await?;
+    assert_eq!(responses.len(), 1);
+    let answers = responses[0].answers();
+    assert_eq!(answers.len(), 6);
+    assert_eq!(soa_serial(&answers[0]), Some(new_serial));
+    assert!(answers[1..5].iter().all(|r| soa_serial(r).is_none()));
+    assert_eq!(soa_serial(&answers[5]), Some(new_serial));
+
+    // Over UDP, clients are told the current serial, and must use TCP to get
+    // the changes.
+    let query = ixfr_query_message(4, zone, old_serial);
+    let (_, response) = udp_exchange(server_addr, &query).await?;
+    let serials = response.answers().iter().map(soa_serial).collect::<Vec<_>>();
+    assert_eq!(serials, [Some(new_serial)]);
+
+    test_ctx.cleanup().await;
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn zone_transfer_notify() -> Result<(), anyhow::Error> {
+    // Stand in for a secondary server.
+    let secondary = tokio::net::UdpSocket::bind("[::1]:0").await?;
+    let test_ctx = init_client_server_with_transfer(
+        "zone_transfer_notify",
+        TransferConfig {
+            allowed_clients: vec![],
+            notify: vec![secondary.local_addr()?],
+        },
+    )
+    .await?;
+    let client = &test_ctx.client;
+
+    dns_records_create(client, TEST_ZONE, transfer_zone_records()).await?;
+    let serial = client.dns_config_get().await?.into_inner().serial;
+
+    // We're told about the new version of the zone.
+    let mut buf = vec![0u8; 65535];
+    let (n, server_addr) = tokio::time::timeout(
+        std::time::Duration::from_secs(30),
+        secondary.recv_from(&mut buf),
+    )
+    .await??;
+    let notify = Message::from_vec(&buf[..n])?;
+    assert_eq!(notify.op_code(), OpCode::Notify);
+    assert!(notify.authoritative());
+    assert_eq!(notify.queries().len(), 1);
+    assert_eq!(
+        *notify.queries()[0].name(),
+        Name::from_ascii(format!("{TEST_ZONE}."))?
+    );
+    assert_eq!(notify.queries()[0].query_type(), RecordType::SOA);
+    assert_eq!(notify.answers().len(), 1);
+    assert_eq!(soa_serial(&notify.answers()[0]), Some(serial));
+
+    // Acknowledge it, as a secondary would.
+    let mut ack = Message::new();
+    ack.set_id(notify.id())
+        .set_message_type(MessageType::Response)
+        .set_op_code(OpCode::Notify)
+        .add_queries(notify.queries().to_vec());
+    secondary.send_to(&ack.to_vec()?, server_addr).await?;
+
+    test_ctx.cleanup().await;
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn dnssec_signing() -> Result<(), anyhow::Error> {
+    let test_ctx = init_client_server("dnssec_signing").await?;
+    let client = &test_ctx.client;
+    let server_addr = test_ctx.dns_server.local_address();
+
+    dns_records_create(client, TEST_ZONE, transfer_zone_records()).await?;
+    let apex = Name::from_ascii(format!("{TEST_ZONE}."))?;
+    let devron = Name::from_ascii(format!("devron.{TEST_ZONE}."))?;
+    let nope = Name::from_ascii(format!("nope.{TEST_ZONE}."))?;
+
+    // Until the zone has keys, answers are unsigned, even if the client asks
+    // for signatures.
+    let query = dnssec_query_message(1, devron.clone(), RecordType::A);
+    let (_, response) = udp_exchange(server_addr, &query).await?;
+    assert_eq!(record_types(response.answers()), vec![RecordType::A]);
+
+    // A zone needs active keys of both roles to be signed.
+    let ksk = dnssec_key(DnssecKeyRole::Ksk, DnssecKeyState::Active)?;
+    let zsk = dnssec_key(DnssecKeyRole::Zsk, DnssecKeyState::Active)?;
+    let next_zsk = dnssec_key(DnssecKeyRole::Zsk, DnssecKeyState::Published)?;
+    let mut config = DnssecConfigParams {
+        generation: Generation::from_u32(1),
+        time_created: chrono::Utc::now(),
+        zones: vec![DnssecZone {
+            zone_name: TEST_ZONE.to_string(),
+            keys: vec![ksk.clone()],
+        }],
+    };
+    let error = client
+        .dnssec_config_put(&config)
+        .await
+        .expect_err("update without a ZSK should fail");
+    assert_eq!(error.status(), Some(reqwest::StatusCode::BAD_REQUEST));
+    config.zones[0].keys = vec![ksk, zsk, next_zsk];
+    client.dnssec_config_put(&config).await?;
+
+    // Only public keys are reported, along with the DS record for the KSK.
+    let reported = client.dnssec_config_get().await?.into_inner();
+    assert_eq!(reported.generation, Generation::from_u32(1));
+    assert_eq!(reported.zones.len(), 1);
+    let keys = &reported.zones[0].keys;
+    assert_eq!(keys.len(), 3);
+    let ksk_status =
+        keys.iter().find(|k| k.role == DnssecKeyRole::Ksk).unwrap();
+    let ds = ksk_status.ds.as_ref().expect("KSK has a DS record");
+    assert!(ds.starts_with(&format!(
+        "{TEST_ZONE}. IN DS {} 13 2 ",
+        ksk_status.key_tag
+    )));
+    assert!(
+        keys.iter()
+            .filter(|k| k.role == DnssecKeyRole::Zsk)
+            .all(|k| k.ds.is_none())
+    );
+
+    // Answers are signed by the active ZSK, and the "DNSSEC OK" bit is
+    // echoed back.
+    let query = dnssec_query_message(2, devron.clone(), RecordType::A);
+    let (_, response) = udp_exchange(server_addr, &query).await?;
+    assert_eq!(response.response_code(), ResponseCode::NoError);
+    assert_eq!(
+        record_types(response.answers()),
+        vec![RecordType::A, RecordType::RRSIG]
+    );
+    let edns = response.extensions().as_ref().expect("response has EDNS");
+    assert!(edns.flags().dnssec_ok);
+
+    // Clients that don't ask for signatures don't get them.
+    let query = query_message(3, devron.clone(), RecordType::A, Some(4096));
+    let (_, response) = udp_exchange(server_addr, &query).await?;
+    assert_eq!(record_types(response.answers()), vec![RecordType::A]);
+
+    // The zone's keys are published at its apex, signed by the KSK.  They're
+    // available to anyone.
+    let query = dnssec_query_message(4, apex.clone(), RecordType::DNSKEY);
+    let (_, response) = udp_exchange(server_addr, &query).await?;
+    assert_eq!(
+        record_types(response.answers()),
+        vec![
+            RecordType::DNSKEY,
+            RecordType::DNSKEY,
+            RecordType::DNSKEY,
+            RecordType::RRSIG
+        ]
+    );
+    let query = query_message(5, apex.clone(), RecordType::DNSKEY, None);
+    let (_, response) = udp_exchange(server_addr, &query).await?;
+    assert_eq!(response.answers().len(), 3);
+
+    // Names that don't exist are denied with a signed NSEC record rather than
+    // NXDOMAIN.
+    let query = dnssec_query_message(6, nope.clone(), RecordType::A);
+    let (_, response) = udp_exchange(server_addr, &query).await?;
+    assert_eq!(response.response_code(), ResponseCode::NoError);
+    assert!(response.answers().is_empty());
+    assert_eq!(
+        record_types(response.name_servers()),
+        vec![
+            RecordType::SOA,
+            RecordType::NSEC,
+            RecordType::RRSIG,
+            RecordType::RRSIG
+        ]
+    );
+    let nsec = &response.name_servers()[1];
+    assert_eq!(*nsec.name(), nope);
+    let query = query_message(7, nope.clone(), RecordType::A, Some(4096));
+    let (_, response) = udp_exchange(server_addr, &query).await?;
+    assert_eq!(response.response_code(), ResponseCode::NXDomain);
+
+    // So are types that a name doesn't have.
+    let query = dnssec_query_message(8, devron.clone(), RecordType::AAAA);
+    let (_, response) = udp_exchange(server_addr, &query).await?;
+    assert_eq!(response.response_code(), ResponseCode::NoError);
+    assert!(response.answers().is_empty());
+    assert_eq!(
+        record_types(response.name_servers()),
+        vec![
+            RecordType::SOA,
+            RecordType::NSEC,
+            RecordType::RRSIG,
+            RecordType::RRSIG
+        ]
+    );
+
+    // Removing the keys stops signing.
+    client
+        .dnssec_config_put(&DnssecConfigParams {
+            generation: Generation::from_u32(2),
+            time_created: chrono::Utc::now(),
+            zones: vec![],
+        })
+        .await?;
+    let query = dnssec_query_message(9, devron, RecordType::A);
+    let (_, response) = udp_exchange(server_addr, &query).await?;
+    assert_eq!(record_types(response.answers()), vec![RecordType::A]);
+    let query = dnssec_query_message(10, nope, RecordType::A);
+    let (_, response) = udp_exchange(server_addr, &query).await?;
+    assert_eq!(response.response_code(), ResponseCode::NXDomain);
+
+    test_ctx.cleanup().await;
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn rate_limit_and_metrics() -> Result<(), anyhow::Error> {
+    let test_ctx = init_client_server_with_config(
+        "rate_limit_and_metrics",
+        dns_server::dns_server::Config {
+            bind_address: "[::1]:0".parse().unwrap(),
+            transfer: Default::default(),
+            // Every response withheld is sent truncated, so that we always
+            // get a response to wait for.
+            rate_limit: RateLimitConfig {
+                responses_per_second: 2,
+                slip: 1,
+                ..Default::default()
+            },
+            query_log: QueryLogConfig { max_per_second: 10 },
+            tcp: Default::default(),
+        },
+    )
+    .await?;
+    let client = &test_ctx.client;
+    let server_addr = test_ctx.dns_server.local_address();
+
+    let addr = Ipv6Addr::new(0xfd, 0, 0, 0, 0, 0, 0, 0x1);
+    let records =
+        HashMap::from([(String::from("nova"), vec![DnsRecord::Aaaa(addr)])]);
+    dns_records_create(client, TEST_ZONE, records).await?;
+
+    // The first responses within the limit are answered as usual.  (The
+    // limit may have been refilled a little by the time the third query
+    // arrives, but not by a whole response.)
+    let name = Name::from_ascii(format!("nova.{TEST_ZONE}."))?;
+    for id in 0..2 {
+        let query = query_message(id, name.clone(), RecordType::AAAA, None);
+        let (_, response) = udp_exchange(server_addr, &query).await?;
+        assert!(!response.truncated());
+        assert_eq!(response.answers().len(), 1);
+    }
+    let query = query_message(2, name.clone(), RecordType::AAAA, None);
+    let (_, response) = udp_exchange(server_addr, &query).await?;
+    assert_eq!(response.id(), 2);
+    assert!(response.truncated());
+    assert!(response.answers().is_empty());
+
+    // TCP responses aren't limited.
+    let mut stream = tokio::net::TcpStream::connect(server_addr).await?;
+    let query = query_message(3, name.clone(), RecordType::AAAA, None);
+    let response = tcp_exchange(&mut stream, &query).await?;
+    assert_eq!(response.answers().len(), 1);
+
+    // Neither are different questions from the same client.
+    let missing = Name::from_ascii(format!("missing.{TEST_ZONE}."))?;
+    let query = query_message(4, missing, RecordType::AAAA, None);
+    let (_, response) = udp_exchange(server_addr, &query).await?;
+    assert_eq!(response.response_code(), ResponseCode::NXDomain);
+
+    // All of this shows up in the server's metrics.
+    let mut producer = dns_server::metrics::Producer::new(
+        uuid::Uuid::new_v4(),
+        test_ctx.dns_server.metrics().clone(),
+    );
+    let samples =
+        oximeter::Producer::produce(&mut producer)?.collect::<Vec<_>>();
+    let queries = |fields: &[(&str, &str)]| {
+        counter_value(&samples, "dns_server:queries", fields)
+    };
+    assert_eq!(
+        queries(&[
+            ("zone", TEST_ZONE),
+            ("query_type", "AAAA"),
+            ("response_code", "NOERROR"),
+            ("transport", "udp"),
+        ]),
+        3
+    );
+    assert_eq!(queries(&[("transport", "tcp")]), 1);
+    assert_eq!(queries(&[("response_code", "NXDOMAIN")]), 1);
+    assert_eq!(
+        counter_value(
+            &samples,
+            "dns_server:rate_limited_responses",
+            &[("zone", TEST_ZONE), ("action", "truncated")],
+        ),
+        1
+    );
+    assert!(
+        samples
+            .iter()
+            .any(|s| &*s.timeseries_name == "dns_server:query_latency")
+    );
+
+    test_ctx.cleanup().await;
+    Ok(())
+}
+
+/// Returns a new DNSSEC key with the given role and state
+fn dnssec_key(
+    role: DnssecKeyRole,
+    state: DnssecKeyState,
+) -> anyhow::Result<DnssecKey> {
+    let algorithm = DnssecAlgorithm::EcdsaP256Sha256;
+    Ok(DnssecKey {
+        role,
+        state,
+        algorithm,
+        private_key: internal_dns_types::dnssec::generate_private_key(
+            algorithm,
+        )?,
+    })
+}
+
+/// Returns the types of `records`, in order
+fn record_types(records: &[Record]) -> Vec<RecordType> {
+    records.iter().map(|record| record.record_type()).collect()
+}
+
+/// Returns the records for a small zone that has the nameserver records needed
+/// to produce an SOA record (and so be transferred)
+fn transfer_zone_records() -> HashMap<String, Vec<DnsRecord>> {
+    HashMap::from([
+        (
+            String::from(ZONE_APEX_NAME),
+            vec![DnsRecord::Ns(format!("ns1.{TEST_ZONE}"))],
+        ),
+        (String::from("ns1"), vec![DnsRecord::Aaaa(Ipv6Addr::LOCALHOST)]),
+        (
+            String::from("devron"),
+            vec![DnsRecord::A(Ipv4Addr::new(10, 1, 2, 3))],
+        ),
+    ])
+}
+
+/// Returns the serial of `record` if it's an SOA record
+fn soa_serial(record: &Record) -> Option<u32> {
+    match record.data() {
+        RData::SOA(soa) => Some(soa.serial()),
+        _ => None,
+    }
+}
+
+/// Builds an IXFR query for `zone` from a client that has the version of it
+/// with serial `serial`
+fn ixfr_query_message(id: u16, zone: Name, serial: u32) -> Message {
+    let mut message = query_message(id, zone.clone(), RecordType::IXFR, None);
+    let soa = SOA::new(
+        Name::from_ascii(format!("ns1.{TEST_ZONE}.")).unwrap(),
+        Name::from_ascii(format!("admin.{TEST_ZONE}.")).unwrap(),
+        serial,
+        0,
+        0,
+        0,
+        0,
+    );
+    message.add_name_server(Record::from_rdata(zone, 0, RData::SOA(soa)));
+    message
+}
+
+/// Returns records describing a service called `service` with `nbackends`
+/// backends, each with an SRV record pointing at its own AAAA record
+fn service_records(
+    service: &str,
+    nbackends: usize,
+) -> HashMap<String, Vec<DnsRecord>> {
+    let mut records = HashMap::new();
+    let mut srvs = Vec::new();
+    for i in 0..nbackends {
+        let backend = format!("{service}-backend-{i}");
+        srvs.push(DnsRecord::Srv(Srv {
+            prio: 0,
+            weight: 0,
+            port: 12345,
+            target: format!("{backend}.{TEST_ZONE}"),
+        }));
+        let addr =
+            Ipv6Addr::new(0xfd, 0, 0, 0, 0, 0, 0, u16::try_from(i).unwrap());
+        records.insert(backend, vec![DnsRecord::Aaaa(addr)]);
+    }
+    records.insert(service.to_string(), srvs);
+    records
+}
+
+/// Returns the sum of the cumulative counters among `samples` from the
+/// timeseries `timeseries_name` whose string fields have the given values
+fn counter_value(
+    samples: &[Sample],
+    timeseries_name: &str,
+    fields: &[(&str, &str)],
+) -> u64 {
+    samples
+        .iter()
+        .filter(|sample| &*sample.timeseries_name == timeseries_name)
+        .filter(|sample| {
+            let sample_fields = sample.sorted_metric_fields();
+            fields.iter().all(|(name, value)| {
+                matches!(
+                    sample_fields.get(*name),
+                    Some(Field { value: FieldValue::String(v), .. })
+                        if v == value
+                )
+            })
+        })
+        .map(|sample| match sample.measurement.datum() {
+            Datum::CumulativeU64(counter) => counter.value(),
+            datum => panic!("unexpected datum: {datum:?}"),
+        })
+        .sum()
+}
+
+/// Builds a query for `name`, optionally with an EDNS(0) OPT record
+/// advertising `edns_payload` bytes
+fn query_message(
+    id: u16,
+    name: Name,
+    record_ty: RecordType,
+    edns_payload: Option<u16>,
+) -> Message {
+    let mut message = Message::new();
+    message
+        .set_id(id)
+        .set_message_type(MessageType::Query)
+        .set_op_code(OpCode::Query)
+        .add_query(Query::query(name, record_ty));
+    if let Some(max_payload) = edns_payload {
+        let mut edns = Edns::new();
+        edns.set_max_payload(max_payload);
+        edns.set_version(0);
+        message.set_edns(edns);
+    }
+    message
+}
+
+/// Builds a query for `name` that asks for DNSSEC records
+fn dnssec_query_message(id: u16, name: Name, record_ty: RecordType) -> Message {
+    let mut message = query_message(id, name, record_ty, Some(4096));
+    message
+        .extensions_mut()
+        .as_mut()
+        .expect("query has EDNS")
+        .flags_mut()
+        .dnssec_ok = true;
+    message
+}
+
+/// Sends `query` over UDP, returning the size of the response as well as the
+/// response itself
+async fn udp_exchange(
+    server_addr: SocketAddr,
+    query: &Message,
+) -> anyhow::Result<(usize, Message)> {
+    let socket = tokio::net::UdpSocket::bind("[::1]:0").await?;
+    socket.send_to(&query.to_vec()?, server_addr).await?;
+    let mut buf = vec![0u8; 65535];
+    let n = socket.recv(&mut buf).await?;
+    Ok((n, Message::from_vec(&buf[..n])?))
+}
+
+/// Sends the zone transfer request `query` over an established TCP
+/// connection, returning all of the messages that make up the response
+///
+/// We take the response to be complete when a message ends with the zone's
+/// current SOA record (the first record of the response).  That's not quite
+/// right for an incremental transfer split across several messages, but it's
+/// good enough for the small ones in these tests.
+async fn tcp_transfer(
+    stream: &mut tokio::net::TcpStream,
+    query: &Message,
+) -> anyhow::Result<Vec<Message>> {
+    let mut responses = vec![tcp_exchange(stream, query).await?];
+    let Some(serial) = responses[0].answers().first().and_then(soa_serial)
+    else {
+        // This is an error.
+        return Ok(responses);
+    };
+    if responses[0].answers().len() == 1 {
+        // The client is up to date.
+        return Ok(responses);
+    }
+    while responses.last().unwrap().answers().last().and_then(soa_serial)
+        != Some(serial)
+    {
+        let length = stream.read_u16().await?;
+        let mut buf = vec![0u8; usize::from(length)];
+        stream.read_exact(&mut buf).await?;
+        responses.push(Message::from_vec(&buf)?);
+    }
+    Ok(responses)
+}
+
+/// Sends `query` over an established TCP connection, returning the response
+async fn tcp_exchange(
+    stream: &mut tokio::net::TcpStream,
+    query: &Message,
+) -> anyhow::Result<Message> {
+    let query = query.to_vec()?;
+    stream.write_u16(u16::try_from(query.len())?).await?;
+    stream.write_all(&query).await?;
+    let length = stream.read_u16().await?;
+    let mut buf = vec![0u8; usize::from(length)];
+    stream.read_exact(&mut buf).await?;
+    Ok(Message::from_vec(&buf)?)
+}
+
 struct TestContext {
     client: Client,
     resolver: TokioResolver,
@@ -657,6 +1798,30 @@ impl TestContext {
 
 async fn init_client_server(
     test_name: &str,
+) -> Result<TestContext, anyhow::Error> {
+    init_client_server_with_transfer(test_name, TransferConfig::default()).await
+}
+
+async fn init_client_server_with_transfer(
+    test_name: &str,
+    transfer: TransferConfig,
+) -> Result<TestContext, anyhow::Error> {
+    init_client_server_with_config(
+        test_name,
+        dns_server::dns_server::Config {
+            bind_address: "[::1]:0".parse().unwrap(),
+            transfer,
+            rate_limit: Default::default(),
+            query_log: Default::default(),
+            tcp: Default::default(),
+        },
+    )
+    .await
+}
+
+async fn init_client_server_with_config(
+    test_name: &str,
+    dns_server_config: dns_server::dns_server::Config,
 ) -> Result<TestContext, anyhow::Error> {
     // initialize dns server config
     let (tmp, config_storage, config_dropshot, logctx) =
@@ -672,9 +1837,6 @@ async fn init_client_server(
     assert!(store.is_new());
 
     // launch a dns server
-    let dns_server_config = dns_server::dns_server::Config {
-        bind_address: "[::1]:0".parse().unwrap(),
-    };
     let (dns_server, dropshot_server) = dns_server::start_servers(
         log.clone(),
         store,
@@ -683,21 +1845,12 @@ async fn init_client_server(
     )
     .await?;
 
-    let mut resolver_config = ResolverConfig::new();
-    resolver_config.add_name_server(NameServerConfig::new(
+    // Like most resolvers, this one uses UDP, retrying over TCP if a response
+    // is truncated.
+    let resolver = resolver_for(
         dns_server.local_address(),
-        Protocol::Udp,
-    ));
-    let mut resolver_opts = ResolverOpts::default();
-    // Enable edns for potentially larger records
-    resolver_opts.edns0 = true;
-
-    let resolver = TokioResolver::builder_with_config(
-        resolver_config,
-        TokioConnectionProvider::default(),
-    )
-    .with_options(resolver_opts)
-    .build();
+        &[Protocol::Udp, Protocol::Tcp],
+    );
     let client =
         Client::new(&format!("http://{}", dropshot_server.local_addr()), log);
 
@@ -711,6 +1864,29 @@ async fn init_client_server(
     })
 }
 
+/// Returns a resolver that queries the DNS server at `server_addr` using the
+/// given protocols
+fn resolver_for(
+    server_addr: SocketAddr,
+    protocols: &[Protocol],
+) -> TokioResolver {
+    let mut resolver_config = ResolverConfig::new();
+    for protocol in protocols {
+        resolver_config
+            .add_name_server(NameServerConfig::new(server_addr, *protocol));
+    }
+    let mut resolver_opts = ResolverOpts::default();
+    // Enable edns for potentially larger records
+    resolver_opts.edns0 = true;
+
+    TokioResolver::builder_with_config(
+        resolver_config,
+        TokioConnectionProvider::default(),
+    )
+    .with_options(resolver_opts)
+    .build()
+}
+
 fn test_config(
     test_name: &str,
 ) -> Result<
@@ -730,7 +1906,8 @@ fn test_config(
         dns_server::storage::Config { storage_path, keep_old_generations: 3 };
     let config_dropshot = dropshot::ConfigDropshot {
         bind_address: "[::1]:0".to_string().parse().unwrap(),
-        default_request_body_max_bytes: 1024,
+        // Some tests configure many (or large) records at once.
+        default_request_body_max_bytes: 1024 * 1024,
         default_handler_task_mode: HandlerTaskMode::Detached,
         log_headers: vec![],
         compression: dropshot::CompressionConfig::None,
@@ -111,6 +111,43 @@ mod v1_client {
     }
 }
 
+// A client of the `VPC_ZONES` version, the last before TXT, CNAME and PTR
+// records were added.
+mod v3_client {
+    use internal_dns_types_versions::{v2, v3};
+
+    progenitor::generate_api!(
+        spec = {
+            path = "git-stub-vcs/openapi/dns-server/dns-server-3.0.0-4a2531.json",
+            relative_to = OutDir,
+        },
+        interface = Positional,
+        inner_type = slog::Logger,
+        derives = [schemars::JsonSchema, Clone, Eq, PartialEq],
+        pre_hook = (|log: &slog::Logger, request: &reqwest::Request| {
+            slog::debug!(log, "client request";
+                "method" => %request.method(),
+                "uri" => %request.url(),
+                "body" => ?&request.body(),
+            );
+        }),
+        post_hook = (|log: &slog::Logger, result: &Result<_, _>| {
+            slog::debug!(log, "client response"; "result" => ?result);
+        }),
+        replace = {
+            DnsConfig = v2::config::DnsConfig,
+            DnsConfigParams = v2::config::DnsConfigParams,
+            DnsConfigZone = v2::config::DnsConfigZone,
+            DnsRecord = v2::config::DnsRecord,
+            Srv = v2::config::Srv,
+            VpcDnsClient = v3::config::VpcDnsClient,
+            VpcDnsConfig = v3::config::VpcDnsConfig,
+            VpcDnsConfigParams = v3::config::VpcDnsConfigParams,
+            VpcDnsZone = v3::config::VpcDnsZone,
+        }
+    );
+}
+
 // A V2 server can productively handle requests from a V1 client, and a V1
 // client *can* provide records to a V2 server (though this really shouldn't
 // ever happen). A V1 client will get an error trying to list records that its
@@ -121,6 +158,7 @@ pub async fn cross_version_works() -> Result<(), anyhow::Error> {
 
     use internal_dns_types_versions::v1::config::DnsRecord as V1DnsRecord;
     use internal_dns_types_versions::v2::config::DnsRecord as V2DnsRecord;
+    use internal_dns_types_versions::v4::config::DnsRecord as V4DnsRecord;
 
     let ns1_addr = Ipv6Addr::new(0xfd, 0, 0, 0, 0, 0, 0, 0x1);
     let ns1_name = format!("ns1.{TEST_ZONE}.");
@@ -143,20 +181,27 @@ pub async fn cross_version_works() -> Result<(), anyhow::Error> {
         .expect("zone exists");
 
     // V1 and V2 APIs return the same content, when both get content.
-    let v1_as_v2: HashMap<String, Vec<V2DnsRecord>> = v1_records
+    let v1_as_v2: HashMap<String, Vec<V4DnsRecord>> = v1_records
         .into_iter()
-        .map(|(k, v)| (k, v.into_iter().map(Into::into).collect()))
+        .map(|(k, v)| {
+            (
+                k,
+                v.into_iter()
+                    .map(|r| V4DnsRecord::from(V2DnsRecord::from(r)))
+                    .collect(),
+            )
+        })
         .collect();
     assert_eq!(v2_records, v1_as_v2);
 
     // A V2 client can create records including the new NS type.
     let mut records = HashMap::new();
     records
-        .insert("service".to_string(), vec![V2DnsRecord::Aaaa(service_addr)]);
-    records.insert("ns1".to_string(), vec![V2DnsRecord::Aaaa(ns1_addr)]);
+        .insert("service".to_string(), vec![V4DnsRecord::Aaaa(service_addr)]);
+    records.insert("ns1".to_string(), vec![V4DnsRecord::Aaaa(ns1_addr)]);
     records.insert(
         ZONE_APEX_NAME.to_string(),
-        vec![V2DnsRecord::Ns(ns1_name.clone())],
+        vec![V4DnsRecord::Ns(ns1_name.clone())],
     );
     dns_records_create(&test_ctx.latest_client, TEST_ZONE, records.clone())
         .await
@@ -183,8 +228,80 @@ pub async fn cross_version_works() -> Result<(), anyhow::Error> {
 
     // The V2 records are what we PUT.
     assert_eq!(records.len(), 3);
-    assert_eq!(records["service"], vec![V2DnsRecord::Aaaa(service_addr)]);
-    assert_eq!(records[ZONE_APEX_NAME], vec![V2DnsRecord::Ns(ns1_name)]);
+    assert_eq!(records["service"], vec![V4DnsRecord::Aaaa(service_addr)]);
+    assert_eq!(records[ZONE_APEX_NAME], vec![V4DnsRecord::Ns(ns1_name)]);
+
+    test_ctx.cleanup().await;
+
+    Ok(())
+}
+
+// A V3 client can read configurations made of the record types it knows
+// about, but gets an error once the configuration includes TXT, CNAME or PTR
+// records.
+#[tokio::test]
+pub async fn cross_version_record_types() -> Result<(), anyhow::Error> {
+    let test_ctx = init_client_server("cross_version_record_types").await?;
+
+    use internal_dns_types_versions::v2::config::DnsRecord as V2DnsRecord;
+
+    let service_addr = Ipv6Addr::new(0xfd, 0, 0, 0, 0, 0, 0, 0x2);
+
+    let mut records = HashMap::new();
+    records.insert("service".to_string(), vec![DnsRecord::Aaaa(service_addr)]);
+    dns_records_create(&test_ctx.latest_client, TEST_ZONE, records)
+        .await
+        .expect("can create zone");
+
+    let config = test_ctx
+        .v3_client
+        .dns_config_get()
+        .await
+        .expect("V3 client can get config")
+        .into_inner();
+    let zone = config
+        .zones
+        .iter()
+        .find(|z| z.zone_name == TEST_ZONE)
+        .expect("zone exists");
+    assert_eq!(zone.records["service"], vec![V2DnsRecord::Aaaa(service_addr)]);
+
+    let mut records = HashMap::new();
+    records.insert(
+        "_acme-challenge".to_string(),
+        vec![DnsRecord::Txt("challenge-digest".to_string())],
+    );
+    records.insert(
+        "alias".to_string(),
+        vec![DnsRecord::Cname(format!("service.{TEST_ZONE}"))],
+    );
+    dns_records_create(&test_ctx.latest_client, TEST_ZONE, records)
+        .await
+        .expect("can add new record types");
+
+    match test_ctx.v3_client.dns_config_get().await {
+        Err(dns_service_client::Error::ErrorResponse(rv)) => {
+            assert_eq!(
+                rv.message,
+                internal_dns_types::config::ERROR_CODE_INCOMPATIBLE_RECORD
+            );
+        }
+        o => {
+            panic!(
+                "expected V3 config get to fail with an ErrorResponse, got {:?}",
+                o
+            );
+        }
+    }
+
+    let records = dns_records_list(&test_ctx.latest_client, TEST_ZONE)
+        .await
+        .expect("zone exists");
+    assert_eq!(records.len(), 3);
+    assert_eq!(
+        records["_acme-challenge"],
+        vec![DnsRecord::Txt("challenge-digest".to_string())]
+    );
 
     test_ctx.cleanup().await;
 
@@ -193,6 +310,7 @@ pub async fn cross_version_works() -> Result<(), anyhow::Error> {
 
 struct TestContext {
     v1_client: v1_client::Client,
+    v3_client: v3_client::Client,
     latest_client: Client,
     dns_server: dns_server::dns_server::ServerHandle,
     dropshot_server: dropshot::HttpServer<dns_server::http_server::Context>,
@@ -228,6 +346,10 @@ async fn init_client_server(
     // launch a dns server
     let dns_server_config = dns_server::dns_server::Config {
         bind_address: "[::1]:0".parse().unwrap(),
+        transfer: Default::default(),
+        rate_limit: Default::default(),
+        query_log: Default::default(),
+        tcp: Default::default(),
     };
     let (dns_server, dropshot_server) = dns_server::start_servers(
         log.clone(),
@@ -241,11 +363,16 @@ async fn init_client_server(
         &format!("http://{}", dropshot_server.local_addr()),
         log.clone(),
     );
+    let v3_client = v3_client::Client::new(
+        &format!("http://{}", dropshot_server.local_addr()),
+        log.clone(),
+    );
     let latest_client =
         Client::new(&format!("http://{}", dropshot_server.local_addr()), log);
 
     Ok(TestContext {
         v1_client,
+        v3_client,
         latest_client,
         dns_server,
         dropshot_server,
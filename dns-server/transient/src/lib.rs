@@ -58,7 +58,13 @@ impl TransientDnsServer {
         let (dns_server, dropshot_server) = dns_server::start_servers(
             dns_log,
             store,
-            &dns_server::dns_server::Config { bind_address: dns_bind_address },
+            &dns_server::dns_server::Config {
+                bind_address: dns_bind_address,
+                transfer: Default::default(),
+                rate_limit: Default::default(),
+                query_log: Default::default(),
+                tcp: Default::default(),
+            },
             &dropshot::ConfigDropshot {
                 bind_address: "[::1]:0".parse().unwrap(),
                 default_request_body_max_bytes: 4 * 1024 * 1024,
@@ -22,6 +22,7 @@ use oxide_vpc::api::Ports;
 use oxide_vpc::api::ProtoFilter;
 use oxnet::IpNet;
 use sled_agent_types::instance::ResolvedVpcFirewallRule;
+use uuid::Uuid;
 
 trait FromVpcFirewallRule {
     fn action(&self) -> FirewallAction;
@@ -134,6 +135,19 @@ pub fn opte_firewall_rules(
     vni: &Vni,
     mac: &MacAddr6,
 ) -> Vec<FirewallRule> {
+    opte_firewall_rules_with_ids(rules, vni, mac)
+        .into_iter()
+        .map(|(_, rule)| rule)
+        .collect()
+}
+
+/// Like [`opte_firewall_rules`], but pair each OPTE rule with the ID of the
+/// VPC firewall rule it was unrolled from, if any.
+pub(crate) fn opte_firewall_rules_with_ids(
+    rules: &[ResolvedVpcFirewallRule],
+    vni: &Vni,
+    mac: &MacAddr6,
+) -> Vec<(Option<Uuid>, FirewallRule)> {
     #[allow(clippy::map_flatten)]
     rules
         .iter()
@@ -146,6 +160,7 @@ pub fn opte_firewall_rules(
                 })
         })
         .map(|rule| {
+            let id = rule.id;
             let priority = rule.priority();
             let action = rule.action();
             let direction = rule.direction();
@@ -157,33 +172,38 @@ pub fn opte_firewall_rules(
                 .map(|proto| {
                     hosts
                         .iter()
-                        .map(|hosts| FirewallRule {
-                            priority,
-                            action,
-                            direction,
-                            filters: {
-                                let mut filters = Filters::new();
+                        .map(|hosts| {
+                            (
+                                id,
+                                FirewallRule {
+                                    priority,
+                                    action,
+                                    direction,
+                                    filters: {
+                                        let mut filters = Filters::new();
 
-                                // Port assignments are incompatible with non
-                                // TCP/UDP protocols.
-                                if matches!(
-                                    proto,
-                                    ProtoFilter::Tcp | ProtoFilter::Udp
-                                ) {
-                                    filters.set_ports(ports.clone());
-                                }
+                                        // Port assignments are incompatible
+                                        // with non TCP/UDP protocols.
+                                        if matches!(
+                                            proto,
+                                            ProtoFilter::Tcp | ProtoFilter::Udp
+                                        ) {
+                                            filters.set_ports(ports.clone());
+                                        }
 
-                                filters
-                                    .set_hosts(*hosts)
-                                    .set_protocol(proto.clone());
-                                filters
-                            },
+                                        filters
+                                            .set_hosts(*hosts)
+                                            .set_protocol(proto.clone());
+                                        filters
+                                    },
+                                },
+                            )
                         })
-                        .collect::<Vec<FirewallRule>>()
+                        .collect::<Vec<(Option<Uuid>, FirewallRule)>>()
                 })
-                .collect::<Vec<Vec<FirewallRule>>>()
+                .collect::<Vec<Vec<(Option<Uuid>, FirewallRule)>>>()
         })
         .flatten()
         .flatten()
-        .collect::<Vec<FirewallRule>>()
+        .collect::<Vec<(Option<Uuid>, FirewallRule)>>()
 }
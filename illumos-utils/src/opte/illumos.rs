@@ -6,9 +6,12 @@
 
 use crate::addrobj::AddrObject;
 use crate::dladm;
+use crate::opte::FirewallLayerHits;
+use crate::opte::RuleHits;
 use camino::Utf8Path;
 use opte_ioctl::Error as OpteError;
 use opte_ioctl::OpteHdl;
+use oxide_vpc::api::RuleDump;
 use sled_agent_types::inventory::NetworkInterfaceKind;
 use slog::Logger;
 use slog::info;
@@ -143,6 +146,9 @@ pub fn initialize_xde_driver(
     }
 }
 
+/// The name of the OPTE layer implementing VPC firewall rules.
+const FIREWALL_LAYER_NAME: &str = "firewall";
+
 /// Handle to the OPTE kernel driver.
 pub struct Handle {
     inner: OpteHdl,
@@ -153,6 +159,24 @@ impl Handle {
     pub fn new() -> Result<Self, OpteError> {
         OpteHdl::open().map(|inner| Self { inner })
     }
+
+    /// Return the hit counters of the rules in the named port's firewall
+    /// layer.
+    pub fn firewall_rule_hits(
+        &self,
+        port_name: &str,
+    ) -> Result<FirewallLayerHits, OpteError> {
+        let layer =
+            self.inner.get_layer_by_name(port_name, FIREWALL_LAYER_NAME)?;
+        let to_hits = |(_, rule): &(_, RuleDump)| RuleHits {
+            priority: rule.priority,
+            hits: rule.hits,
+        };
+        Ok(FirewallLayerHits {
+            inbound: layer.rules_in.iter().map(to_hits).collect(),
+            outbound: layer.rules_out.iter().map(to_hits).collect(),
+        })
+    }
 }
 
 // This deref impl lets us use the actual API of `OpteHdl`.
@@ -33,6 +33,7 @@ use oxnet::IpNet;
 use oxnet::Ipv4Net;
 use oxnet::Ipv6Net;
 pub use port::Port;
+pub use port_manager::FirewallRuleHits;
 pub use port_manager::MulticastGroupCfg;
 pub use port_manager::PortCreateParams;
 pub use port_manager::PortManager;
@@ -253,6 +254,23 @@ pub struct EnsureAttachedSubnetResult {
     pub error: Option<Error>,
 }
 
+/// The hit counter of a single rule in an OPTE layer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RuleHits {
+    pub priority: u16,
+    pub hits: u64,
+}
+
+/// The hit counters of the rules in a port's firewall layer.
+///
+/// Rules are listed in the order OPTE evaluates them, which is by priority and
+/// then by the order in which they were set.
+#[derive(Clone, Debug, Default)]
+pub struct FirewallLayerHits {
+    pub inbound: Vec<RuleHits>,
+    pub outbound: Vec<RuleHits>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::Gateway;
@@ -5,6 +5,8 @@
 //! Mock / dummy versions of the OPTE module, for non-illumos platforms
 
 use crate::addrobj::AddrObject;
+use crate::opte::FirewallLayerHits;
+use crate::opte::RuleHits;
 use oxide_vpc::api::AddRouterEntryReq;
 use oxide_vpc::api::ClearVirt2PhysReq;
 use oxide_vpc::api::DelRouterEntryReq;
@@ -172,6 +174,9 @@ pub(crate) struct PortData {
     pub port: PortInfo,
     /// The routes for this port. This simulates the router layer.
     pub routes: Vec<RouteInfo>,
+    /// The firewall rules for this port, with their hit counters. This
+    /// simulates the firewall layer.
+    pub firewall_rules: FirewallLayerHits,
 }
 
 #[derive(Debug)]
@@ -237,7 +242,11 @@ impl Handle {
                 return Err(OpteError::DuplicatePort(entry.key().to_string()));
             }
             Entry::Vacant(entry) => {
-                entry.insert(PortData { port, routes: Vec::new() });
+                entry.insert(PortData {
+                    port,
+                    routes: Vec::new(),
+                    firewall_rules: FirewallLayerHits::default(),
+                });
             }
         }
         Ok(NO_RESPONSE)
@@ -249,13 +258,47 @@ impl Handle {
     }
 
     /// Set new firewall rules.
+    ///
+    /// Like OPTE, this resets the hit counters of all the port's rules.
     pub fn set_firewall_rules(
         &self,
-        _: &SetFwRulesReq,
+        req: &SetFwRulesReq,
     ) -> Result<NoResp, OpteError> {
+        let mut state = opte_state().lock().unwrap();
+        let Some(port) = state.ports.get_mut(&req.port_name) else {
+            return Err(OpteError::NoPort(req.port_name.clone()));
+        };
+        let mut firewall_rules = FirewallLayerHits::default();
+        for rule in &req.rules {
+            let hits = RuleHits { priority: rule.priority, hits: 0 };
+            match rule.direction {
+                Direction::In => firewall_rules.inbound.push(hits),
+                Direction::Out => firewall_rules.outbound.push(hits),
+            }
+        }
+        // OPTE evaluates rules by priority, keeping the order in which they
+        // were set among equal priorities.
+        firewall_rules.inbound.sort_by_key(|rule| rule.priority);
+        firewall_rules.outbound.sort_by_key(|rule| rule.priority);
+        port.firewall_rules = firewall_rules;
         Ok(NO_RESPONSE)
     }
 
+    /// Return the hit counters of the rules in the named port's firewall
+    /// layer.
+    pub fn firewall_rule_hits(
+        &self,
+        port_name: &str,
+    ) -> Result<FirewallLayerHits, OpteError> {
+        opte_state()
+            .lock()
+            .unwrap()
+            .ports
+            .get(port_name)
+            .map(|port| port.firewall_rules.clone())
+            .ok_or_else(|| OpteError::NoPort(port_name.to_string()))
+    }
+
     /// Add a new router entry to OPTE.
     pub fn add_router_entry(
         &self,
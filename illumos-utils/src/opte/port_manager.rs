@@ -8,12 +8,15 @@ use crate::dladm::OPTE_LINK_PREFIX;
 use crate::opte::AttachedSubnet;
 use crate::opte::EnsureAttachedSubnetResult;
 use crate::opte::Error;
+use crate::opte::FirewallLayerHits;
 use crate::opte::Gateway;
 use crate::opte::Handle;
 use crate::opte::Port;
 use crate::opte::Vni;
-use crate::opte::opte_firewall_rules;
+use crate::opte::firewall_rules::opte_firewall_rules_with_ids;
 use crate::opte::port::PortData;
+use chrono::DateTime;
+use chrono::Utc;
 use ipnetwork::Ipv4Network;
 use ipnetwork::Ipv6Network;
 use macaddr::MacAddr6;
@@ -38,7 +41,9 @@ use oxide_vpc::api::AttachedSubnetConfig;
 use oxide_vpc::api::DelRouterEntryReq;
 use oxide_vpc::api::DetachSubnetResp;
 use oxide_vpc::api::DhcpCfg;
+use oxide_vpc::api::Direction;
 use oxide_vpc::api::ExternalIpCfg;
+use oxide_vpc::api::FirewallRule;
 use oxide_vpc::api::IpCfg;
 use oxide_vpc::api::IpCidr;
 use oxide_vpc::api::Ipv4Cfg;
@@ -127,6 +132,90 @@ struct PortManagerInner {
     ///
     /// IGW IDs are specific to the VPC of each NIC.
     eip_gateways: Mutex<HashMap<Uuid, HashMap<IpAddr, HashSet<Uuid>>>>,
+
+    /// The firewall rules last set on each port, keyed like `ports`.
+    ///
+    /// This is used to attribute OPTE's per-rule hit counters back to the VPC
+    /// firewall rules they were derived from.
+    firewall_rules:
+        Mutex<BTreeMap<(Uuid, NetworkInterfaceKind), PortFirewallRules>>,
+}
+
+/// The OPTE firewall rules set on a port, recording which VPC firewall rule
+/// each was derived from.
+#[derive(Debug)]
+struct PortFirewallRules {
+    /// When the rules were set, which also resets their hit counters.
+    set_at: DateTime<Utc>,
+    /// The priority and VPC firewall rule ID of each inbound rule, in the
+    /// order OPTE evaluates them.
+    inbound: Vec<(u16, Option<Uuid>)>,
+    /// The same, for outbound rules.
+    outbound: Vec<(u16, Option<Uuid>)>,
+}
+
+impl PortFirewallRules {
+    fn new(rules: &[(Option<Uuid>, FirewallRule)]) -> Self {
+        let mut inbound = Vec::new();
+        let mut outbound = Vec::new();
+        for (id, rule) in rules {
+            match rule.direction {
+                Direction::In => inbound.push((rule.priority, *id)),
+                Direction::Out => outbound.push((rule.priority, *id)),
+            }
+        }
+        // OPTE orders each direction's rules by priority, keeping the order in
+        // which they were set among equal priorities. Our sort is stable, so
+        // this matches.
+        inbound.sort_by_key(|(priority, _)| *priority);
+        outbound.sort_by_key(|(priority, _)| *priority);
+        Self { set_at: Utc::now(), inbound, outbound }
+    }
+
+    /// Sum the hit counters reported by OPTE for each VPC firewall rule.
+    ///
+    /// Returns `None` if the rules in OPTE don't line up with the ones we set,
+    /// in which case we can't reliably attribute the counters.
+    fn attribute_hits(
+        &self,
+        layer: &FirewallLayerHits,
+    ) -> Option<BTreeMap<Uuid, u64>> {
+        let mut hits = BTreeMap::new();
+        for (ours, theirs) in
+            [(&self.inbound, &layer.inbound), (&self.outbound, &layer.outbound)]
+        {
+            if ours.len() != theirs.len() {
+                return None;
+            }
+            for ((priority, id), rule) in ours.iter().zip(theirs) {
+                if *priority != rule.priority {
+                    return None;
+                }
+                if let Some(id) = id {
+                    *hits.entry(*id).or_default() += rule.hits;
+                }
+            }
+        }
+        Some(hits)
+    }
+}
+
+/// The hit counter of a VPC firewall rule on one OPTE port.
+///
+/// OPTE counts the flows matched by a rule, rather than individual packets,
+/// since later packets in a flow bypass the rule table.
+#[derive(Clone, Debug)]
+pub struct FirewallRuleHits {
+    /// The ID of the network interface the port belongs to.
+    pub interface_id: Uuid,
+    /// The kind of network interface, including its parent's ID.
+    pub kind: NetworkInterfaceKind,
+    /// The ID of the VPC firewall rule.
+    pub rule_id: Uuid,
+    /// When the counter was last reset.
+    pub start_time: DateTime<Utc>,
+    /// The number of flows matched by the rule since `start_time`.
+    pub hits: u64,
 }
 
 impl PortManagerInner {
@@ -353,6 +442,7 @@ impl PortManager {
             ports: Mutex::new(BTreeMap::new()),
             routes: Mutex::new(Default::default()),
             eip_gateways: Mutex::new(Default::default()),
+            firewall_rules: Mutex::new(BTreeMap::new()),
         });
 
         Self { inner }
@@ -463,17 +553,14 @@ impl PortManager {
         };
 
         // Initialize firewall rules for the new port.
-        let rules = opte_firewall_rules(firewall_rules, &vni, &mac);
+        let rules = opte_firewall_rules_with_ids(firewall_rules, &vni, &mac);
         debug!(
             self.inner.log,
             "Setting firewall rules";
             "port_name" => &port_name,
             "rules" => ?&rules,
         );
-        hdl.set_firewall_rules(&oxide_vpc::api::SetFwRulesReq {
-            port_name: port_name.clone(),
-            rules,
-        })?;
+        self.set_port_firewall_rules(&hdl, nic.id, nic.kind, &port, rules)?;
 
         // Create the default set of routes for a new port.
         //
@@ -863,23 +950,85 @@ impl PortManager {
         let vpc_ports = ports
             .iter()
             .filter(|((_, _), port)| u32::from(vni) == u32::from(*port.vni()));
-        for ((_, _), port) in vpc_ports {
-            let rules = opte_firewall_rules(rules, port.vni(), port.mac());
-            let port_name = port.name().to_string();
+        for ((id, kind), port) in vpc_ports {
+            let rules =
+                opte_firewall_rules_with_ids(rules, port.vni(), port.mac());
             info!(
                 self.inner.log,
                 "Setting OPTE firewall rules";
-                "port" => ?&port_name,
+                "port" => port.name(),
                 "rules" => ?&rules,
             );
-            hdl.set_firewall_rules(&oxide_vpc::api::SetFwRulesReq {
-                port_name,
-                rules,
-            })?;
+            self.set_port_firewall_rules(&hdl, *id, *kind, port, rules)?;
         }
         Ok(())
     }
 
+    /// Set the firewall rules on a single port, remembering which VPC
+    /// firewall rule each was derived from.
+    fn set_port_firewall_rules(
+        &self,
+        hdl: &Handle,
+        id: Uuid,
+        kind: NetworkInterfaceKind,
+        port: &Port,
+        rules: Vec<(Option<Uuid>, FirewallRule)>,
+    ) -> Result<(), Error> {
+        let installed = PortFirewallRules::new(&rules);
+        hdl.set_firewall_rules(&oxide_vpc::api::SetFwRulesReq {
+            port_name: port.name().to_string(),
+            rules: rules.into_iter().map(|(_, rule)| rule).collect(),
+        })?;
+        self.inner.firewall_rules.lock().unwrap().insert((id, kind), installed);
+        Ok(())
+    }
+
+    /// Return the hit counter of each VPC firewall rule on each port.
+    ///
+    /// Ports whose counters can't be read or attributed are skipped.
+    pub fn firewall_rule_hits(&self) -> Result<Vec<FirewallRuleHits>, Error> {
+        let hdl = Handle::new()?;
+        let ports = self.inner.ports.lock().unwrap();
+        let firewall_rules = self.inner.firewall_rules.lock().unwrap();
+        let mut out = Vec::new();
+        for ((id, kind), port) in ports.iter() {
+            let Some(installed) = firewall_rules.get(&(*id, *kind)) else {
+                continue;
+            };
+            let layer = match hdl.firewall_rule_hits(port.name()) {
+                Ok(layer) => layer,
+                Err(e) => {
+                    warn!(
+                        self.inner.log,
+                        "failed to read OPTE firewall rule hits";
+                        "port" => port.name(),
+                        "error" => InlineErrorChain::new(&e),
+                    );
+                    continue;
+                }
+            };
+            let Some(hits) = installed.attribute_hits(&layer) else {
+                warn!(
+                    self.inner.log,
+                    "OPTE firewall rules do not match the rules we set, \
+                    not reporting hits";
+                    "port" => port.name(),
+                );
+                continue;
+            };
+            out.extend(hits.into_iter().map(|(rule_id, hits)| {
+                FirewallRuleHits {
+                    interface_id: *id,
+                    kind: *kind,
+                    rule_id,
+                    start_time: installed.set_at,
+                    hits,
+                }
+            }));
+        }
+        Ok(out)
+    }
+
     pub fn list_virtual_nics(
         &self,
     ) -> Result<Vec<VirtualNetworkInterfaceHost>, Error> {
@@ -1168,6 +1317,11 @@ impl PortTicket {
             return Err(Error::ReleaseMissingPort(self.id, self.kind));
         };
         drop(ports);
+        self.manager
+            .firewall_rules
+            .lock()
+            .unwrap()
+            .remove(&(self.id, self.kind));
 
         // Cleanup the set of subnets we want to receive routes for.
         let remove_key = |routes: &mut HashMap<RouterId, RouteSet>,
@@ -1235,6 +1389,11 @@ mod tests {
     use super::PortManager;
     use crate::opte::Handle;
     use macaddr::MacAddr6;
+    use omicron_common::api::external::VpcFirewallRuleAction;
+    use omicron_common::api::external::VpcFirewallRuleDirection;
+    use omicron_common::api::external::VpcFirewallRulePriority;
+    use omicron_common::api::external::VpcFirewallRuleProtocol;
+    use omicron_common::api::external::VpcFirewallRuleStatus;
     use omicron_common::api::external::{MacAddr, Vni};
     use omicron_common::api::internal::shared::InternetGatewayRouterTarget;
     use omicron_common::api::internal::shared::PrivateIpConfig;
@@ -1255,10 +1414,12 @@ mod tests {
     use sled_agent_types::instance::ExternalIpConfig;
     use sled_agent_types::instance::ExternalIpv4Config;
     use sled_agent_types::instance::ExternalIpv6Config;
+    use sled_agent_types::instance::ResolvedVpcFirewallRule;
     use sled_agent_types::inventory::NetworkInterface;
     use sled_agent_types::inventory::NetworkInterfaceKind;
     use sled_agent_types::inventory::SourceNatConfigV4;
     use sled_agent_types::inventory::SourceNatConfigV6;
+    use std::collections::BTreeMap;
     use std::collections::HashSet;
     use std::net::Ipv4Addr;
     use std::net::Ipv6Addr;
@@ -1652,6 +1813,155 @@ mod tests {
         logctx.cleanup_successful();
     }
 
+    #[test]
+    fn firewall_rule_hits_are_attributed_to_vpc_rules() {
+        let logctx =
+            test_setup_log("firewall_rule_hits_are_attributed_to_vpc_rules");
+        let manager = PortManager::new(logctx.log.clone(), Ipv6Addr::LOCALHOST);
+        let handle = Handle::new().unwrap();
+        handle.set_xde_underlay("foo0", "foo1").unwrap();
+
+        // The allow rule covers two protocols, so it's unrolled into two OPTE
+        // rules. The outbound rule has no ID, so it's not reported.
+        let allow_id = Uuid::new_v4();
+        let deny_id = Uuid::new_v4();
+        let rule = |id, direction, action, priority, protocols| {
+            ResolvedVpcFirewallRule {
+                id,
+                status: VpcFirewallRuleStatus::Enabled,
+                direction,
+                targets: vec![],
+                filter_hosts: None,
+                filter_ports: None,
+                filter_protocols: protocols,
+                action,
+                priority: VpcFirewallRulePriority(priority),
+            }
+        };
+        let rules = [
+            rule(
+                Some(allow_id),
+                VpcFirewallRuleDirection::Inbound,
+                VpcFirewallRuleAction::Allow,
+                100,
+                Some(vec![
+                    VpcFirewallRuleProtocol::Tcp,
+                    VpcFirewallRuleProtocol::Udp,
+                ]),
+            ),
+            rule(
+                Some(deny_id),
+                VpcFirewallRuleDirection::Inbound,
+                VpcFirewallRuleAction::Deny,
+                50,
+                None,
+            ),
+            rule(
+                None,
+                VpcFirewallRuleDirection::Outbound,
+                VpcFirewallRuleAction::Allow,
+                65534,
+                None,
+            ),
+        ];
+
+        let private_subnet =
+            Ipv4Net::new(Ipv4Addr::new(172, 20, 0, 0), 24).unwrap();
+        let ip_config = PrivateIpConfig::new_ipv4(
+            Ipv4Addr::new(172, 20, 0, 4),
+            private_subnet,
+        )
+        .unwrap();
+        let external_ips = ExternalIpConfig {
+            v4: Some(ExternalIpv4Config {
+                source_nat: Some(
+                    SourceNatConfigV4::new(
+                        Ipv4Addr::new(10, 0, 0, 4),
+                        0,
+                        16383,
+                    )
+                    .unwrap(),
+                ),
+                ..Default::default()
+            }),
+            v6: None,
+        };
+        let vni = Vni::SERVICES_VNI;
+        let (port, _ticket) = manager
+            .create_port(PortCreateParams {
+                nic: &NetworkInterface {
+                    id: Uuid::new_v4(),
+                    kind: NetworkInterfaceKind::Service { id: Uuid::new_v4() },
+                    name: "opte0".parse().unwrap(),
+                    ip_config,
+                    mac: MacAddr(MacAddr6::new(
+                        0xa8, 0x40, 0x25, 0x00, 0x00, 0x01,
+                    )),
+                    vni,
+                    primary: true,
+                    slot: 0,
+                },
+                external_ips: &external_ips,
+                firewall_rules: &rules,
+                dhcp_config: DhcpCfg {
+                    hostname: None,
+                    host_domain: None,
+                    domain_search_list: Vec::new(),
+                    dns4_servers: Vec::new(),
+                    dns6_servers: Vec::new(),
+                },
+                attached_subnets: vec![],
+                mtu: None,
+            })
+            .unwrap();
+
+        // OPTE orders the inbound rules as deny, allow/TCP, allow/UDP.
+        let set_hits = |hits: &[u64]| {
+            let mut state = handle.state().lock().unwrap();
+            let layer =
+                &mut state.ports.get_mut(port.name()).unwrap().firewall_rules;
+            for (rule, hits) in layer.inbound.iter_mut().zip(hits) {
+                rule.hits = *hits;
+            }
+        };
+        let hits_by_rule = |manager: &PortManager| {
+            manager
+                .firewall_rule_hits()
+                .unwrap()
+                .into_iter()
+                .map(|hits| (hits.rule_id, hits.hits))
+                .collect::<BTreeMap<_, _>>()
+        };
+        set_hits(&[4, 1, 2]);
+        assert_eq!(
+            hits_by_rule(&manager),
+            BTreeMap::from([(allow_id, 3), (deny_id, 4)]),
+        );
+
+        // Setting the rules again resets the counters.
+        manager.firewall_rules_ensure(vni, &rules).unwrap();
+        assert_eq!(
+            hits_by_rule(&manager),
+            BTreeMap::from([(allow_id, 0), (deny_id, 0)]),
+        );
+
+        // If the rules in OPTE don't match what we set, we can't attribute
+        // the hits, so the port is skipped.
+        handle
+            .state()
+            .lock()
+            .unwrap()
+            .ports
+            .get_mut(port.name())
+            .unwrap()
+            .firewall_rules
+            .inbound
+            .pop();
+        assert!(hits_by_rule(&manager).is_empty());
+
+        logctx.cleanup_successful();
+    }
+
     #[test]
     fn ip_cfg_from_ipv4_params() {
         let priv_ip = Ipv4Addr::new(172, 30, 2, 5);
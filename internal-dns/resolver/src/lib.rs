@@ -5,5 +5,6 @@
 //! A resolver for internal DNS names (see RFD 248).
 
 mod resolver;
+pub mod selection;
 
 pub use resolver::*;
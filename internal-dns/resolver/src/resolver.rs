@@ -2,6 +2,7 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+use crate::selection::{SrvTarget, TargetHealth, order_srv_targets};
 use hickory_resolver::TokioResolver;
 use hickory_resolver::config::{
     LookupIpStrategy, NameServerConfig, ResolveHosts, ResolverConfig,
@@ -9,11 +10,12 @@ use hickory_resolver::config::{
 };
 use hickory_resolver::lookup::SrvLookup;
 use hickory_resolver::name_server::TokioConnectionProvider;
+use hickory_resolver::proto::rr::rdata::SRV;
 use internal_dns_types::names::ServiceName;
 use omicron_common::address::{
     AZ_PREFIX, DNS_PORT, Ipv6Subnet, get_internal_dns_server_addresses,
 };
-use slog::{debug, error, info, trace};
+use slog::{debug, error, info, trace, warn};
 use std::net::{Ipv6Addr, SocketAddr, SocketAddrV6};
 
 #[derive(Debug, Clone, thiserror::Error)]
@@ -59,6 +61,21 @@ impl ResolveError {
     }
 }
 
+/// Error returned by [`Resolver::try_targets`]
+#[derive(Debug, thiserror::Error)]
+pub enum TryTargetsError<E> {
+    /// The service's targets could not be looked up
+    #[error(transparent)]
+    Resolve(#[from] ResolveError),
+
+    /// The last target tried failed
+    ///
+    /// This is either a target that could be reached but failed the request,
+    /// or, if no target could be reached, the last one tried.
+    #[error("request to {addr} failed: {error}")]
+    Target { addr: SocketAddrV6, error: E },
+}
+
 /// A wrapper around a set of bootstrap DNS addresses, providing a convenient
 /// way to construct a [`qorb::resolvers::dns::DnsResolver`] for specific
 /// services.
@@ -105,6 +122,7 @@ impl QorbResolver {
 pub struct Resolver {
     log: slog::Logger,
     resolver: TokioResolver,
+    health: TargetHealth,
 }
 
 type BoxError = Box<dyn std::error::Error + Send + Sync>;
@@ -122,6 +140,34 @@ impl reqwest::dns::Resolve for Resolver {
     }
 }
 
+/// A [`Resolver`] mode for use with [`reqwest::ClientBuilder::dns_resolver`]
+/// that fails over between the targets of an SRV record
+///
+/// Addresses are handed to reqwest in the order described in
+/// [`Resolver::lookup_all_socket_v6`]: by SRV priority and weight, with
+/// targets that have recently failed moved to the end.  reqwest tries each
+/// address in turn until it's able to connect, so a backend that's down is
+/// skipped without the caller having to do anything.  reqwest doesn't say
+/// which addresses it failed to connect to, though, so this alone never marks
+/// a target unhealthy.  Callers that want failures remembered across requests
+/// should use [`Resolver::try_targets`], or report failures they observe
+/// through [`Resolver::target_health`] themselves.
+#[derive(Clone)]
+pub struct FailoverResolver {
+    resolver: Resolver,
+}
+
+impl reqwest::dns::Resolve for FailoverResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        let this = self.resolver.clone();
+        Box::pin(async move {
+            this.lookup_ordered_sockets_v6_raw(name.as_str())
+                .await
+                .map_err(|err| -> BoxError { Box::new(err) })
+        })
+    }
+}
+
 impl Resolver {
     /// Construct a new DNS resolver from the system configuration.
     pub fn new_from_system_conf(
@@ -133,7 +179,7 @@ impl Resolver {
 
         let resolver = builder.build();
 
-        Ok(Self { log, resolver })
+        Ok(Self { log, resolver, health: TargetHealth::default() })
     }
 
     /// Construct a new DNS resolver from specific DNS server addresses.
@@ -146,19 +192,26 @@ impl Resolver {
         let mut rc = ResolverConfig::new();
         let dns_server_count = dns_addrs.len();
         for &socket_addr in dns_addrs.into_iter() {
-            let mut ns_config = NameServerConfig::new(
-                socket_addr,
+            // Queries go over UDP.  The resolver retries over TCP if a
+            // response is truncated, as happens for services with many
+            // backends.
+            for protocol in [
                 hickory_resolver::proto::xfer::Protocol::Udp,
-            );
-            // Intentionally continue trying other DNS servers if we get an
-            // NXDOMAIN or NOERROR with no answer.  This should be a rare
-            // circumstance.  If it occurs and the name is genuinely not
-            // present, we'll be slower to error.  If the name is unevenly
-            // distributed it is in the process of going away, or a DNS server
-            // is serving stale records and may not be getting updated anymore.
-            // In this last case, we may be avoiding service disruption.
-            ns_config.trust_negative_responses = false;
-            rc.add_name_server(ns_config);
+                hickory_resolver::proto::xfer::Protocol::Tcp,
+            ] {
+                let mut ns_config =
+                    NameServerConfig::new(socket_addr, protocol);
+                // Intentionally continue trying other DNS servers if we get an
+                // NXDOMAIN or NOERROR with no answer.  This should be a rare
+                // circumstance.  If it occurs and the name is genuinely not
+                // present, we'll be slower to error.  If the name is unevenly
+                // distributed it is in the process of going away, or a DNS
+                // server is serving stale records and may not be getting
+                // updated anymore.  In this last case, we may be avoiding
+                // service disruption.
+                ns_config.trust_negative_responses = false;
+                rc.add_name_server(ns_config);
+            }
         }
         let mut opts = ResolverOpts::default();
         // Enable edns for potentially larger records
@@ -176,7 +229,7 @@ impl Resolver {
         .with_options(opts)
         .build();
 
-        Ok(Self { log, resolver })
+        Ok(Self { log, resolver, health: TargetHealth::default() })
     }
 
     /// Convenience wrapper for [`Resolver::new_from_subnet`] that determines
@@ -196,7 +249,35 @@ impl Resolver {
         log: slog::Logger,
         resolver: TokioResolver,
     ) -> Self {
-        Self { log, resolver }
+        Self { log, resolver, health: TargetHealth::default() }
+    }
+
+    /// Use `health` to track failures of the targets returned by this
+    /// resolver
+    ///
+    /// This allows several resolvers to share what they know about which
+    /// targets are working.
+    pub fn with_target_health(mut self, health: TargetHealth) -> Self {
+        self.health = health;
+        self
+    }
+
+    /// Returns the record of recent failures of the targets returned by this
+    /// resolver
+    ///
+    /// Callers should report failures (and successes) connecting to targets
+    /// here so that targets that aren't working are tried last.
+    /// [`Resolver::try_targets`] does this for its callers.
+    pub fn target_health(&self) -> &TargetHealth {
+        &self.health
+    }
+
+    /// Returns a version of this resolver for use with reqwest that prefers
+    /// healthy targets and honors SRV priority and weight
+    ///
+    /// See [`FailoverResolver`].
+    pub fn failover(&self) -> FailoverResolver {
+        FailoverResolver { resolver: self.clone() }
     }
 
     // TODO-correctness This function and its callers make assumptions about how
@@ -262,6 +343,30 @@ impl Resolver {
             .collect())
     }
 
+    /// Returns the targets of the SRV records for a DNS name, in the order
+    /// that RFC 2782 says they should be tried
+    ///
+    /// Targets are sorted by priority, lowest first.  Targets with the same
+    /// priority are shuffled, with targets with a higher weight more likely
+    /// to come first.  Each call may return a different order.
+    pub async fn lookup_srv_targets(
+        &self,
+        srv: ServiceName,
+    ) -> Result<Vec<SrvTarget>, ResolveError> {
+        let name = srv.srv_name();
+        trace!(self.log, "lookup_srv_targets"; "dns_name" => &name);
+        let response = self.resolver.srv_lookup(&name).await?;
+        debug!(
+            self.log,
+            "lookup_srv_targets";
+            "dns_name" => &name,
+            "response" => ?response
+        );
+
+        let targets = response.iter().map(SrvTarget::from).collect();
+        Ok(order_srv_targets(targets, &mut rand::rng()))
+    }
+
     pub async fn lookup_all_ipv6(
         &self,
         srv: ServiceName,
@@ -276,7 +381,7 @@ impl Resolver {
             "response" => ?response
         );
         let addrs = self
-            .lookup_service_targets(response)
+            .lookup_service_targets(response.iter().cloned().collect())
             .await
             .map(|addrv6| *addrv6.ip())
             .collect::<Vec<_>>();
@@ -289,12 +394,16 @@ impl Resolver {
 
     /// Looks up a single [`SocketAddrV6`] based on the SRV name
     /// Returns an error if the record does not exist.
+    ///
+    /// The address returned is the first one that
+    /// [`Resolver::lookup_all_socket_v6`] would return: it honors SRV priority
+    /// and weight and avoids targets that have recently failed, if possible.
     // TODO-robustness: any callers of this should probably be using
     // all the targets for a given SRV and not just the first one
     // we get, see [`Resolver::lookup_all_socket_v6`].
     //
     // TODO: There are lots of ways this API can expand: Caching,
-    // actually respecting TTL, looking up ports, etc.
+    // actually respecting TTL, etc.
     //
     // For now, however, it serves as a very simple "get everyone using DNS"
     // API that can be improved upon later.
@@ -312,8 +421,9 @@ impl Resolver {
             "response" => ?response
         );
 
-        self.lookup_service_targets(response)
+        self.lookup_ordered_service_targets(response)
             .await
+            .into_iter()
             .next()
             .ok_or_else(|| ResolveError::NotFound(service))
     }
@@ -322,6 +432,10 @@ impl Resolver {
     ///
     /// Unlike [`Resolver::lookup_srv`], this will further lookup the returned
     /// targets and return a list of [`SocketAddrV6`].
+    ///
+    /// The addresses are returned in the order they should be tried: in the
+    /// order described in [`Resolver::lookup_srv_targets`], except that
+    /// targets that [`Resolver::target_health`] considers unhealthy come last.
     pub async fn lookup_all_socket_v6(
         &self,
         service: ServiceName,
@@ -336,8 +450,7 @@ impl Resolver {
             "response" => ?response
         );
 
-        let results =
-            self.lookup_service_targets(response).await.collect::<Vec<_>>();
+        let results = self.lookup_ordered_service_targets(response).await;
         if !results.is_empty() {
             Ok(results)
         } else {
@@ -345,6 +458,62 @@ impl Resolver {
         }
     }
 
+    /// Makes a request of the targets of an SRV record, failing over between
+    /// them and recording which ones can't be reached
+    ///
+    /// `attempt` is invoked with each address from
+    /// [`Resolver::lookup_all_socket_v6`] in turn until it returns something
+    /// other than an error for which `is_unreachable` returns true.  That
+    /// result is returned, with errors wrapped in [`TryTargetsError::Target`].
+    /// If no target can be reached, the error from the last one is returned.
+    ///
+    /// Each unreachable target is reported to [`Resolver::target_health`] as
+    /// a failure, and the target that handled the request (successfully or
+    /// not) as a success, so that later requests try working targets first.
+    /// `is_unreachable` should only return true for errors that mean the
+    /// target itself isn't working, like failing to connect (see
+    /// `reqwest::Error::is_connect()`), not for errors the target returned.
+    pub async fn try_targets<T, E, F, Fut>(
+        &self,
+        service: ServiceName,
+        is_unreachable: impl Fn(&E) -> bool,
+        mut attempt: F,
+    ) -> Result<T, TryTargetsError<E>>
+    where
+        F: FnMut(SocketAddrV6) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let addrs = self.lookup_all_socket_v6(service).await?;
+        let mut last_error = None;
+        for addr in addrs {
+            match attempt(addr).await {
+                Err(error) if is_unreachable(&error) => {
+                    warn!(
+                        self.log,
+                        "try_targets: target unreachable";
+                        "service" => ?service,
+                        "addr" => %addr,
+                    );
+                    self.health.report_failure(*addr.ip());
+                    last_error = Some((addr, error));
+                }
+                result => {
+                    self.health.report_success(*addr.ip());
+                    return result.map_err(|error| TryTargetsError::Target {
+                        addr,
+                        error,
+                    });
+                }
+            }
+        }
+
+        // `lookup_all_socket_v6()` never returns an empty list, so we've tried
+        // at least one target.
+        let (addr, error) =
+            last_error.expect("try_targets: tried at least one target");
+        Err(TryTargetsError::Target { addr, error })
+    }
+
     // Returns an iterator of SocketAddrs for the specified SRV name.
     //
     // Acts on a raw string for compatibility with the reqwest::dns::Resolve
@@ -356,7 +525,7 @@ impl Resolver {
         debug!(self.log, "lookup_sockets_v6_raw srv"; "dns_name" => &name);
         let response = self.resolver.srv_lookup(name).await?;
         let mut results = self
-            .lookup_service_targets(response)
+            .lookup_service_targets(response.iter().cloned().collect())
             .await
             .map(|addrv6| SocketAddr::V6(addrv6))
             .peekable();
@@ -367,8 +536,43 @@ impl Resolver {
         }
     }
 
+    // Like `lookup_sockets_v6_raw`, but returns addresses in the order
+    // described in `lookup_all_socket_v6`.
+    async fn lookup_ordered_sockets_v6_raw(
+        &self,
+        name: &str,
+    ) -> Result<Box<dyn Iterator<Item = SocketAddr> + Send>, ResolveError> {
+        debug!(
+            self.log,
+            "lookup_ordered_sockets_v6_raw srv";
+            "dns_name" => &name
+        );
+        let response = self.resolver.srv_lookup(name).await?;
+        let results = self.lookup_ordered_service_targets(response).await;
+        if !results.is_empty() {
+            Ok(Box::new(results.into_iter().map(SocketAddr::V6)))
+        } else {
+            Err(ResolveError::NotFoundByString(name.to_string()))
+        }
+    }
+
+    /// Returns the [`SocketAddrV6`]'s for the targets of the given SRV lookup
+    /// response, ordered by SRV priority and weight and then by the health of
+    /// each target.
+    async fn lookup_ordered_service_targets(
+        &self,
+        service_lookup: SrvLookup,
+    ) -> Vec<SocketAddrV6> {
+        let srvs = order_srv_targets(
+            service_lookup.iter().cloned().collect(),
+            &mut rand::rng(),
+        );
+        let addrs = self.lookup_service_targets(srvs).await.collect();
+        self.health.prefer_healthy(addrs, |addr| *addr.ip())
+    }
+
     /// Returns an iterator of [`SocketAddrV6`]'s for the targets of the given
-    /// SRV lookup response.
+    /// SRV records, in the same order as the records.
     // SRV records have a target, which is itself another DNS name that needs
     // to be looked up in order to get to the actual IP addresses. Many DNS
     // servers (including ours) return these IP addresses directly in the
@@ -384,11 +588,11 @@ impl Resolver {
     // the lookups explicitly.
     async fn lookup_service_targets(
         &self,
-        service_lookup: SrvLookup,
+        srvs: Vec<SRV>,
     ) -> impl Iterator<Item = SocketAddrV6> + Send + use<> {
         let futures =
             std::iter::repeat((self.log.clone(), self.resolver.clone()))
-                .zip(service_lookup.into_iter())
+                .zip(srvs.into_iter())
                 .map(|((log, resolver), srv)| async move {
                     let target = srv.target();
                     let port = srv.port();
@@ -456,7 +660,9 @@ impl Resolver {
 mod test {
     use super::ResolveError;
     use super::Resolver;
+    use super::TryTargetsError;
     use anyhow::Context;
+    use assert_matches::assert_matches;
     use dropshot::{
         ApiDescription, HandlerTaskMode, HttpError, HttpResponseOk,
         RequestContext, endpoint,
@@ -464,6 +670,7 @@ mod test {
     use hickory_resolver::ResolveErrorKind;
     use internal_dns_types::config::DnsConfigBuilder;
     use internal_dns_types::config::DnsConfigParams;
+    use internal_dns_types::config::DnsRecord;
     use internal_dns_types::names::DNS_ZONE;
     use internal_dns_types::names::ServiceName;
     use omicron_test_utils::dev::test_setup_log;
@@ -515,6 +722,10 @@ mod test {
                 store,
                 &dns_server::dns_server::Config {
                     bind_address: "[::1]:0".parse().unwrap(),
+                    transfer: Default::default(),
+                    rate_limit: Default::default(),
+                    query_log: Default::default(),
+                    tcp: Default::default(),
                 },
                 &dropshot::ConfigDropshot {
                     bind_address: "[::1]:0".parse().unwrap(),
@@ -817,6 +1028,229 @@ mod test {
     // What follows is a "test endpoint" to validate that the integration of
     // the DNS server, resolver, and progenitor all work together correctly.
 
+    // SRV priorities are honored, and targets that have failed are tried last.
+    #[tokio::test]
+    async fn lookup_honors_priority_and_health() {
+        let logctx = test_setup_log("lookup_honors_priority_and_health");
+        let dns_server = DnsServer::create(&logctx.log).await;
+        let resolver = dns_server.resolver().unwrap();
+
+        let mut dns_config = DnsConfigBuilder::new();
+        let ip1 = Ipv6Addr::new(0xfd, 0, 0, 0, 0, 0, 0, 0x1);
+        let addr1 = SocketAddrV6::new(ip1, 15001, 0, 0);
+        let zone1 =
+            dns_config.host_zone(OmicronZoneUuid::new_v4(), ip1).unwrap();
+        dns_config
+            .service_backend_zone(ServiceName::Cockroach, &zone1, addr1.port())
+            .unwrap();
+        let ip2 = Ipv6Addr::new(0xfd, 0, 0, 0, 0, 0, 0, 0x2);
+        let addr2 = SocketAddrV6::new(ip2, 15002, 0, 0);
+        let zone2 =
+            dns_config.host_zone(OmicronZoneUuid::new_v4(), ip2).unwrap();
+        dns_config
+            .service_backend_zone(ServiceName::Cockroach, &zone2, addr2.port())
+            .unwrap();
+        let mut dns_config =
+            dns_config.build_full_config_for_initial_generation();
+
+        // Make the first target less preferred than the second.
+        let root = dns_config
+            .zones
+            .iter_mut()
+            .find(|zone| zone.zone_name == DNS_ZONE)
+            .expect("root dns zone missing?");
+        let srv_records = root
+            .records
+            .get_mut(&ServiceName::Cockroach.dns_name())
+            .expect("Cockroach SRV records missing?");
+        for record in srv_records.iter_mut() {
+            let DnsRecord::Srv(srv) = record else {
+                panic!("unexpected record: {record:?}");
+            };
+            if srv.port == addr1.port() {
+                srv.prio = 1;
+            }
+        }
+        dns_server.update(&dns_config).await.unwrap();
+
+        for _ in 0..10 {
+            let addr = resolver
+                .lookup_socket_v6(ServiceName::Cockroach)
+                .await
+                .expect("Should have been able to look up IP address");
+            assert_eq!(addr, addr2);
+        }
+        let targets =
+            resolver.lookup_srv_targets(ServiceName::Cockroach).await.unwrap();
+        let ports: Vec<_> = targets.iter().map(|t| t.port).collect();
+        assert_eq!(ports, [addr2.port(), addr1.port()]);
+        let addrs = resolver
+            .lookup_all_socket_v6(ServiceName::Cockroach)
+            .await
+            .unwrap();
+        assert_eq!(addrs, [addr2, addr1]);
+
+        // After the preferred target fails, the other one is tried first,
+        // including by the failover resolver used with reqwest.
+        resolver.target_health().report_failure(ip2);
+        let addr =
+            resolver.lookup_socket_v6(ServiceName::Cockroach).await.unwrap();
+        assert_eq!(addr, addr1);
+        let addrs = resolver
+            .lookup_all_socket_v6(ServiceName::Cockroach)
+            .await
+            .unwrap();
+        assert_eq!(addrs, [addr1, addr2]);
+        let name =
+            reqwest::dns::Name::from_str(&ServiceName::Cockroach.srv_name())
+                .unwrap();
+        let resolved: Vec<_> =
+            reqwest::dns::Resolve::resolve(&resolver.failover(), name)
+                .await
+                .unwrap()
+                .collect();
+        assert_eq!(resolved, [SocketAddr::V6(addr1), SocketAddr::V6(addr2)]);
+
+        // Once it's reported working again, it's preferred again.
+        resolver.target_health().report_success(ip2);
+        let addr =
+            resolver.lookup_socket_v6(ServiceName::Cockroach).await.unwrap();
+        assert_eq!(addr, addr2);
+
+        dns_server.cleanup_successful();
+        logctx.cleanup_successful();
+    }
+
+    // Targets that `try_targets()` can't reach are reported as failures and
+    // tried last from then on.
+    #[tokio::test]
+    async fn try_targets_reports_unreachable_targets() {
+        let logctx = test_setup_log("try_targets_reports_unreachable_targets");
+        let dns_server = DnsServer::create(&logctx.log).await;
+        let resolver = dns_server.resolver().unwrap();
+
+        let mut dns_config = DnsConfigBuilder::new();
+        let ip1 = Ipv6Addr::new(0xfd, 0, 0, 0, 0, 0, 0, 0x1);
+        let addr1 = SocketAddrV6::new(ip1, 15001, 0, 0);
+        let zone1 =
+            dns_config.host_zone(OmicronZoneUuid::new_v4(), ip1).unwrap();
+        dns_config
+            .service_backend_zone(ServiceName::Cockroach, &zone1, addr1.port())
+            .unwrap();
+        let ip2 = Ipv6Addr::new(0xfd, 0, 0, 0, 0, 0, 0, 0x2);
+        let addr2 = SocketAddrV6::new(ip2, 15002, 0, 0);
+        let zone2 =
+            dns_config.host_zone(OmicronZoneUuid::new_v4(), ip2).unwrap();
+        dns_config
+            .service_backend_zone(ServiceName::Cockroach, &zone2, addr2.port())
+            .unwrap();
+        let mut dns_config =
+            dns_config.build_full_config_for_initial_generation();
+
+        // Make the second target less preferred than the first, which is
+        // the one that's down.
+        let root = dns_config
+            .zones
+            .iter_mut()
+            .find(|zone| zone.zone_name == DNS_ZONE)
+            .expect("root dns zone missing?");
+        let srv_records = root
+            .records
+            .get_mut(&ServiceName::Cockroach.dns_name())
+            .expect("Cockroach SRV records missing?");
+        for record in srv_records.iter_mut() {
+            let DnsRecord::Srv(srv) = record else {
+                panic!("unexpected record: {record:?}");
+            };
+            if srv.port == addr2.port() {
+                srv.prio = 1;
+            }
+        }
+        dns_server.update(&dns_config).await.unwrap();
+        assert_eq!(
+            resolver
+                .lookup_all_socket_v6(ServiceName::Cockroach)
+                .await
+                .unwrap(),
+            [addr1, addr2]
+        );
+
+        // Requests to the first target fail to connect; the second target
+        // answers the request, or rejects it if asked to.
+        let is_unreachable = |error: &&str| *error == "connection refused";
+        let request = |addr: SocketAddrV6, reject: bool| async move {
+            if addr == addr1 {
+                Err("connection refused")
+            } else if reject {
+                Err("bad request")
+            } else {
+                Ok(addr.port())
+            }
+        };
+
+        // The first request fails over to the second target, and the first
+        // target is tried last from then on.
+        let mut tried = Vec::new();
+        let port = resolver
+            .try_targets(ServiceName::Cockroach, is_unreachable, |addr| {
+                tried.push(addr);
+                request(addr, false)
+            })
+            .await
+            .unwrap();
+        assert_eq!(port, addr2.port());
+        assert_eq!(tried, [addr1, addr2]);
+        assert!(!resolver.target_health().is_healthy(ip1));
+        assert!(resolver.target_health().is_healthy(ip2));
+        assert_eq!(
+            resolver
+                .lookup_all_socket_v6(ServiceName::Cockroach)
+                .await
+                .unwrap(),
+            [addr2, addr1]
+        );
+
+        // An error from a target that could be reached is returned as-is
+        // without trying other targets, and doesn't count against it.
+        let mut tried = Vec::new();
+        let error = resolver
+            .try_targets(ServiceName::Cockroach, is_unreachable, |addr| {
+                tried.push(addr);
+                request(addr, true)
+            })
+            .await
+            .unwrap_err();
+        assert_matches!(
+            error,
+            TryTargetsError::Target { addr, error: "bad request" }
+                if addr == addr2
+        );
+        assert_eq!(tried, [addr2]);
+        assert!(resolver.target_health().is_healthy(ip2));
+
+        // If no target can be reached, the last one's error is returned and
+        // both are marked unhealthy.
+        let mut tried = Vec::new();
+        let error = resolver
+            .try_targets(ServiceName::Cockroach, is_unreachable, |addr| {
+                tried.push(addr);
+                async { Err::<(), _>("connection refused") }
+            })
+            .await
+            .unwrap_err();
+        assert_matches!(
+            error,
+            TryTargetsError::Target { addr, error: "connection refused" }
+                if addr == addr1
+        );
+        assert_eq!(tried, [addr2, addr1]);
+        assert!(!resolver.target_health().is_healthy(ip1));
+        assert!(!resolver.target_health().is_healthy(ip2));
+
+        dns_server.cleanup_successful();
+        logctx.cleanup_successful();
+    }
+
     #[endpoint {
         method = GET,
         path = "/test",
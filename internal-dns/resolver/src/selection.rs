@@ -0,0 +1,399 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Choosing among the targets of an SRV record
+//!
+//! SRV records carry a priority and a weight.  [`order_srv_targets()`] orders
+//! a set of targets the way RFC 2782 says clients should try them: lowest
+//! priority first, and within a priority, in a random order biased by weight.
+//!
+//! [`TargetHealth`] remembers recent connection failures for each target so
+//! that callers can prefer targets that have been working over ones that
+//! haven't.  Failures decay over time, so a target that failed a while ago is
+//! eventually tried first again even if nobody reports that it's recovered.
+
+use rand::Rng;
+use std::collections::BTreeMap;
+use std::net::Ipv6Addr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// One target of an SRV record
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SrvTarget {
+    /// DNS name of the target (which itself needs to be resolved to find an
+    /// address)
+    pub target: String,
+    pub port: u16,
+    pub priority: u16,
+    pub weight: u16,
+}
+
+impl From<&hickory_resolver::proto::rr::rdata::SRV> for SrvTarget {
+    fn from(srv: &hickory_resolver::proto::rr::rdata::SRV) -> Self {
+        SrvTarget {
+            target: srv.target().to_string(),
+            port: srv.port(),
+            priority: srv.priority(),
+            weight: srv.weight(),
+        }
+    }
+}
+
+/// Trait for anything that looks like an SRV target for the purpose of
+/// ordering
+pub trait SrvOrdering {
+    fn priority(&self) -> u16;
+    fn weight(&self) -> u16;
+}
+
+impl SrvOrdering for SrvTarget {
+    fn priority(&self) -> u16 {
+        self.priority
+    }
+
+    fn weight(&self) -> u16 {
+        self.weight
+    }
+}
+
+impl SrvOrdering for hickory_resolver::proto::rr::rdata::SRV {
+    fn priority(&self) -> u16 {
+        hickory_resolver::proto::rr::rdata::SRV::priority(self)
+    }
+
+    fn weight(&self) -> u16 {
+        hickory_resolver::proto::rr::rdata::SRV::weight(self)
+    }
+}
+
+/// Orders `targets` in the order that RFC 2782 says clients should try them
+///
+/// Targets are grouped by priority, lowest first.  Within each group, targets
+/// are ordered by repeatedly picking one of the remaining targets at random
+/// with probability proportional to its weight.  Targets with weight 0 are
+/// only very rarely picked ahead of targets with a non-zero weight, and are
+/// otherwise ordered uniformly at random among themselves.
+pub fn order_srv_targets<T, R>(mut targets: Vec<T>, rng: &mut R) -> Vec<T>
+where
+    T: SrvOrdering,
+    R: Rng + ?Sized,
+{
+    targets.sort_by_key(|t| t.priority());
+
+    let mut ordered = Vec::with_capacity(targets.len());
+    let mut remaining = targets.into_iter().peekable();
+    while let Some(first) = remaining.next() {
+        let priority = first.priority();
+        let mut group = vec![first];
+        while let Some(next) = remaining.next_if(|t| t.priority() == priority) {
+            group.push(next);
+        }
+        order_priority_group(group, rng, &mut ordered);
+    }
+    ordered
+}
+
+/// Appends the targets in `group` (which all have the same priority) to `out`
+/// in the weighted random order described in RFC 2782
+fn order_priority_group<T, R>(mut group: Vec<T>, rng: &mut R, out: &mut Vec<T>)
+where
+    T: SrvOrdering,
+    R: Rng + ?Sized,
+{
+    // RFC 2782 says to place the zero-weight entries at the beginning of the
+    // list before making the weighted selection.  Combined with the inclusive
+    // range below, this gives them a small chance of being picked first.  We
+    // shuffle them first so that they aren't always tried in the order the
+    // server returned them when all weights are zero (as is the case for most
+    // of our records).
+    group.sort_by_key(|t| t.weight() != 0);
+    let nzero = group.iter().take_while(|t| t.weight() == 0).count();
+    shuffle(&mut group[..nzero], rng);
+
+    while !group.is_empty() {
+        let total: u32 = group.iter().map(|t| u32::from(t.weight())).sum();
+        let pick = rng.random_range(0..=total);
+        let mut running = 0;
+        let index = group
+            .iter()
+            .position(|t| {
+                running += u32::from(t.weight());
+                running >= pick
+            })
+            .expect("running sum reaches the total");
+        out.push(group.remove(index));
+    }
+}
+
+/// Fisher-Yates shuffle
+fn shuffle<T, R: Rng + ?Sized>(items: &mut [T], rng: &mut R) {
+    for i in (1..items.len()).rev() {
+        let j = rng.random_range(0..=i);
+        items.swap(i, j);
+    }
+}
+
+/// Configuration for [`TargetHealth`]
+#[derive(Debug, Clone)]
+pub struct TargetHealthConfig {
+    /// How long it takes for the effect of a failure to decay by half
+    pub half_life: Duration,
+    /// A target whose (decayed) failure score is at least this much is
+    /// considered unhealthy
+    ///
+    /// Each failure adds 1 to the score.  With the default of 0.5, a single
+    /// failure makes a target unhealthy for one half-life, and each further
+    /// failure extends that.
+    pub unhealthy_threshold: f64,
+}
+
+impl Default for TargetHealthConfig {
+    fn default() -> Self {
+        TargetHealthConfig {
+            half_life: Duration::from_secs(30),
+            unhealthy_threshold: 0.5,
+        }
+    }
+}
+
+/// Failure scores below this are forgotten entirely
+const FORGET_THRESHOLD: f64 = 0.01;
+
+/// Remembers recent connection failures for SRV targets
+///
+/// Targets are identified by IPv6 address.  Every control plane zone has its
+/// own address, so this identifies a particular instance of a service
+/// regardless of which port a client happened to use to reach it.
+///
+/// Cloning a `TargetHealth` produces another handle onto the same state.
+#[derive(Debug, Clone, Default)]
+pub struct TargetHealth {
+    config: TargetHealthConfig,
+    failures: Arc<Mutex<BTreeMap<Ipv6Addr, FailureScore>>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct FailureScore {
+    score: f64,
+    as_of: Instant,
+}
+
+impl FailureScore {
+    fn at(&self, now: Instant, half_life: Duration) -> f64 {
+        let elapsed = now.saturating_duration_since(self.as_of);
+        let half_lives = elapsed.as_secs_f64() / half_life.as_secs_f64();
+        self.score * 0.5f64.powf(half_lives)
+    }
+}
+
+impl TargetHealth {
+    pub fn new(config: TargetHealthConfig) -> Self {
+        TargetHealth { config, failures: Arc::new(Mutex::new(BTreeMap::new())) }
+    }
+
+    /// Records that a connection to `addr` failed
+    pub fn report_failure(&self, addr: Ipv6Addr) {
+        self.report_failure_at(addr, Instant::now())
+    }
+
+    /// Records that a connection to `addr` succeeded, forgetting any earlier
+    /// failures
+    pub fn report_success(&self, addr: Ipv6Addr) {
+        self.failures.lock().unwrap().remove(&addr);
+    }
+
+    /// Returns the current failure score for `addr`
+    ///
+    /// This is 0 for targets with no recent failures.
+    pub fn failure_score(&self, addr: Ipv6Addr) -> f64 {
+        self.failure_score_at(addr, Instant::now())
+    }
+
+    /// Returns whether `addr` has few enough recent failures to be tried ahead
+    /// of other targets
+    pub fn is_healthy(&self, addr: Ipv6Addr) -> bool {
+        self.is_healthy_at(addr, Instant::now())
+    }
+
+    /// Reorders `addrs` so that healthy targets come first
+    ///
+    /// The relative order of the healthy targets is preserved, so this can be
+    /// applied to the output of [`order_srv_targets()`].  Unhealthy targets
+    /// are kept (so that callers still have something to try if everything
+    /// is failing) but moved to the end, least-recently-failing first.
+    pub fn prefer_healthy<T>(
+        &self,
+        addrs: Vec<T>,
+        addr_of: impl Fn(&T) -> Ipv6Addr,
+    ) -> Vec<T> {
+        self.prefer_healthy_at(addrs, addr_of, Instant::now())
+    }
+
+    pub(crate) fn report_failure_at(&self, addr: Ipv6Addr, now: Instant) {
+        let half_life = self.config.half_life;
+        let mut failures = self.failures.lock().unwrap();
+        let previous =
+            failures.get(&addr).map(|f| f.at(now, half_life)).unwrap_or(0.0);
+        failures
+            .insert(addr, FailureScore { score: previous + 1.0, as_of: now });
+        // Take the opportunity to forget targets that haven't failed in a
+        // long time so that this doesn't grow without bound as backends come
+        // and go.
+        failures.retain(|_, f| f.at(now, half_life) >= FORGET_THRESHOLD);
+    }
+
+    pub(crate) fn failure_score_at(&self, addr: Ipv6Addr, now: Instant) -> f64 {
+        self.failures
+            .lock()
+            .unwrap()
+            .get(&addr)
+            .map(|f| f.at(now, self.config.half_life))
+            .unwrap_or(0.0)
+    }
+
+    pub(crate) fn is_healthy_at(&self, addr: Ipv6Addr, now: Instant) -> bool {
+        self.failure_score_at(addr, now) < self.config.unhealthy_threshold
+    }
+
+    pub(crate) fn prefer_healthy_at<T>(
+        &self,
+        addrs: Vec<T>,
+        addr_of: impl Fn(&T) -> Ipv6Addr,
+        now: Instant,
+    ) -> Vec<T> {
+        let (healthy, mut unhealthy): (Vec<_>, Vec<_>) = addrs
+            .into_iter()
+            .map(|t| {
+                let score = self.failure_score_at(addr_of(&t), now);
+                (t, score)
+            })
+            .partition(|(_, score)| *score < self.config.unhealthy_threshold);
+        unhealthy.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+        healthy.into_iter().chain(unhealthy).map(|(t, _)| t).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use std::collections::BTreeMap;
+
+    fn target(name: &str, priority: u16, weight: u16) -> SrvTarget {
+        SrvTarget { target: name.to_string(), port: 123, priority, weight }
+    }
+
+    #[test]
+    fn test_priority_ordering() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let targets = vec![
+            target("c", 20, 5),
+            target("a", 10, 0),
+            target("d", 30, 100),
+            target("b", 10, 0),
+        ];
+        for _ in 0..100 {
+            let ordered = order_srv_targets(targets.clone(), &mut rng);
+            let priorities: Vec<_> =
+                ordered.iter().map(|t| t.priority).collect();
+            assert_eq!(priorities, [10, 10, 20, 30]);
+        }
+    }
+
+    #[test]
+    fn test_weighted_ordering() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let targets = vec![
+            target("heavy", 0, 90),
+            target("light", 0, 10),
+            target("zero", 0, 0),
+        ];
+        let mut first_counts = BTreeMap::new();
+        let trials = 10_000;
+        for _ in 0..trials {
+            let ordered = order_srv_targets(targets.clone(), &mut rng);
+            assert_eq!(ordered.len(), 3);
+            *first_counts.entry(ordered[0].target.clone()).or_insert(0) += 1;
+        }
+
+        // "heavy" should be picked first about 90% of the time and "light"
+        // about 10% of the time.  "zero" is only picked first when the random
+        // pick is exactly 0 (about 1% of the time).
+        let heavy = first_counts.get("heavy").copied().unwrap_or(0);
+        let light = first_counts.get("light").copied().unwrap_or(0);
+        let zero = first_counts.get("zero").copied().unwrap_or(0);
+        assert!((8_500..9_300).contains(&heavy), "heavy: {heavy}");
+        assert!((700..1_300).contains(&light), "light: {light}");
+        assert!(zero < 300, "zero: {zero}");
+    }
+
+    #[test]
+    fn test_all_zero_weights_are_shuffled() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let targets: Vec<_> =
+            ["a", "b", "c"].into_iter().map(|n| target(n, 0, 0)).collect();
+        let mut first_counts = BTreeMap::new();
+        for _ in 0..3_000 {
+            let ordered = order_srv_targets(targets.clone(), &mut rng);
+            *first_counts.entry(ordered[0].target.clone()).or_insert(0) += 1;
+        }
+        assert_eq!(first_counts.len(), 3);
+        for (name, count) in first_counts {
+            assert!((800..1_200).contains(&count), "{name}: {count}");
+        }
+    }
+
+    #[test]
+    fn test_failure_decay() {
+        let health = TargetHealth::new(TargetHealthConfig {
+            half_life: Duration::from_secs(10),
+            unhealthy_threshold: 0.5,
+        });
+        let addr: Ipv6Addr = "fd00::1".parse().unwrap();
+        let other: Ipv6Addr = "fd00::2".parse().unwrap();
+        let start = Instant::now();
+
+        assert!(health.is_healthy_at(addr, start));
+        health.report_failure_at(addr, start);
+        assert!(!health.is_healthy_at(addr, start));
+        assert!(health.is_healthy_at(other, start));
+
+        // After one half-life, the single failure has decayed to exactly the
+        // threshold.  Just after that, the target is healthy again.
+        let later = start + Duration::from_secs(10);
+        assert!((health.failure_score_at(addr, later) - 0.5).abs() < 1e-9);
+        assert!(!health.is_healthy_at(addr, later));
+        let later = start + Duration::from_secs(11);
+        assert!(health.is_healthy_at(addr, later));
+
+        // Repeated failures keep the target unhealthy for longer.
+        health.report_failure_at(addr, later);
+        health.report_failure_at(addr, later);
+        assert!(!health.is_healthy_at(addr, later + Duration::from_secs(11)));
+        assert!(health.is_healthy_at(addr, later + Duration::from_secs(25)));
+
+        // A success forgets the failures altogether.
+        health.report_failure_at(addr, later);
+        health.report_success(addr);
+        assert_eq!(health.failure_score_at(addr, later), 0.0);
+    }
+
+    #[test]
+    fn test_prefer_healthy() {
+        let health = TargetHealth::default();
+        let addrs: Vec<Ipv6Addr> = ["fd00::1", "fd00::2", "fd00::3", "fd00::4"]
+            .into_iter()
+            .map(|a| a.parse().unwrap())
+            .collect();
+        let now = Instant::now();
+        health.report_failure_at(addrs[0], now);
+        health.report_failure_at(addrs[0], now);
+        health.report_failure_at(addrs[2], now);
+
+        let ordered = health.prefer_healthy_at(addrs.clone(), |a| *a, now);
+        assert_eq!(ordered, [addrs[1], addrs[3], addrs[2], addrs[0]]);
+    }
+}
@@ -60,19 +60,48 @@
 //!
 //! This module provides types used to assemble that configuration.
 
-use crate::names::{BOUNDARY_NTP_DNS_NAME, DNS_ZONE, ServiceName};
+use crate::names::{
+    BOUNDARY_NTP_DNS_NAME, DNS_ZONE, ServiceName, ZONE_APEX_NAME,
+    ipv6_reverse_name, ipv6_reverse_zone, is_reverse_zone,
+};
 use anyhow::{anyhow, ensure};
 use core::fmt;
 use omicron_common::address::{CLICKHOUSE_ADMIN_PORT, CLICKHOUSE_TCP_PORT};
 use omicron_common::api::external::Generation;
 use omicron_uuid_kinds::{OmicronZoneUuid, SledUuid};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::net::{Ipv6Addr, SocketAddrV6};
 
 // Re-export the latest versions from the versions crate for dependents that
 // just want "latest".
 pub use internal_dns_types_versions::latest::config::*;
 
+/// Returns the sole forward DNS zone in `config`, ignoring any reverse zones
+/// alongside it
+///
+/// This is like [`DnsConfigParams::sole_zone()`] for DNS groups that may also
+/// have reverse zones, like internal DNS.
+///
+/// # Errors
+///
+/// Returns an error if there are 0 or more than one forward zones in this
+/// configuration.
+pub fn sole_forward_zone(
+    config: &DnsConfigParams,
+) -> anyhow::Result<&DnsConfigZone> {
+    let zones = config
+        .zones
+        .iter()
+        .filter(|zone| !is_reverse_zone(&zone.zone_name))
+        .collect::<Vec<_>>();
+    ensure!(
+        zones.len() == 1,
+        "expected exactly one forward DNS zone, but found {}",
+        zones.len()
+    );
+    Ok(zones[0])
+}
+
 /// Used to construct the DNS name for a control plane host
 #[derive(Clone, Debug, PartialEq, PartialOrd)]
 pub enum Host {
@@ -730,11 +759,71 @@ impl DnsConfigBuilder {
         DnsConfigZone { zone_name: DNS_ZONE.to_owned(), records: all_records }
     }
 
+    /// Construct the reverse DNS zones for the hosts described up to this
+    /// point
+    ///
+    /// Each sled and zone gets a PTR record mapping its underlay address back
+    /// to its name in the control plane DNS zone. There is one reverse zone
+    /// for each availability zone subnet containing any of these addresses
+    /// (in practice, just one), served by the same nameservers as the
+    /// control plane DNS zone. The zones are sorted by name.
+    pub fn build_reverse_zones(&self) -> Vec<DnsConfigZone> {
+        let sled_hosts = self
+            .sleds
+            .iter()
+            .map(|(sled, sled_ip)| (*sled_ip, Host::Sled(sled.0).fqdn()));
+        let zone_hosts = self
+            .zones
+            .iter()
+            .map(|(zone, zone_ip)| (*zone_ip, zone.to_host().fqdn()));
+
+        let mut zones: BTreeMap<String, BTreeMap<String, Vec<DnsRecord>>> =
+            BTreeMap::new();
+        for (ip, fqdn) in sled_hosts.chain(zone_hosts) {
+            zones
+                .entry(ipv6_reverse_zone(ip))
+                .or_default()
+                .entry(ipv6_reverse_name(ip))
+                .or_default()
+                .push(DnsRecord::Ptr(fqdn));
+        }
+
+        // These are the same nameserver names that `build_zone()` uses.
+        let nameservers = (1..=self.internal_dns_addresses.len())
+            .map(|n| DnsRecord::Ns(format!("ns{n}.{DNS_ZONE}")))
+            .collect::<Vec<_>>();
+
+        zones
+            .into_iter()
+            .map(|(zone_name, names)| {
+                let mut records = names
+                    .into_iter()
+                    .map(|(name, mut records)| {
+                        records.sort();
+                        (name, records)
+                    })
+                    .collect::<HashMap<_, _>>();
+                if !nameservers.is_empty() {
+                    records.insert(
+                        ZONE_APEX_NAME.to_string(),
+                        nameservers.clone(),
+                    );
+                }
+                DnsConfigZone { zone_name, records }
+            })
+            .collect()
+    }
+
     /// Construct a complete [`DnsConfigParams`] (suitable for propagating to
     /// our DNS servers) for the control plane DNS zone described up to this
     /// point
+    ///
+    /// The control plane DNS zone comes first, followed by the reverse zones
+    /// from [`Self::build_reverse_zones()`].
     pub fn build_full_config_for_initial_generation(self) -> DnsConfigParams {
-        let zone = self.build_zone();
+        let reverse_zones = self.build_reverse_zones();
+        let zones =
+            std::iter::once(self.build_zone()).chain(reverse_zones).collect();
         let generation = Generation::new();
         DnsConfigParams {
             generation,
@@ -743,7 +832,7 @@ impl DnsConfigBuilder {
                 .try_into()
                 .expect("initial generation fits into u32"),
             time_created: chrono::Utc::now(),
-            zones: vec![zone],
+            zones,
         }
     }
 }
@@ -751,9 +840,14 @@ impl DnsConfigBuilder {
 #[cfg(test)]
 mod test {
     use super::{
-        DnsConfigBuilder, DnsRecord, Host, HostSwitchZonePorts, ServiceName,
+        DnsConfigBuilder, DnsConfigZone, DnsRecord, Host, HostSwitchZonePorts,
+        ServiceName,
+    };
+    use crate::config::Zone;
+    use crate::names::{
+        DNS_ZONE, ZONE_APEX_NAME, ipv6_reverse_name, ipv6_reverse_zone,
+        is_reverse_zone,
     };
-    use crate::{config::Zone, names::DNS_ZONE};
     use omicron_common::api::external::Generation;
     use omicron_uuid_kinds::{OmicronZoneUuid, SledUuid};
     use std::{
@@ -1018,8 +1112,14 @@ mod test {
         ] {
             let config = builder.build_full_config_for_initial_generation();
             assert_eq!(config.generation, Generation::from(1));
-            assert_eq!(config.zones.len(), 1);
             assert_eq!(config.zones[0].zone_name, DNS_ZONE);
+            // Any other zones are the reverse zones, which are covered by
+            // `test_builder_reverse_zones()`.
+            assert!(
+                config.zones[1..]
+                    .iter()
+                    .all(|zone| is_reverse_zone(&zone.zone_name))
+            );
             write!(&mut output, "builder: {:?}\n", label).unwrap();
             // Sort the records for stability.
             let records: BTreeMap<_, _> =
@@ -1034,6 +1134,92 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_reverse_names() {
+        let addr: Ipv6Addr = "fd00:1122:3344:101::a".parse().unwrap();
+        assert_eq!(ipv6_reverse_zone(addr), "4.4.3.3.2.2.1.1.0.0.d.f.ip6.arpa");
+        assert_eq!(
+            ipv6_reverse_name(addr),
+            "a.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.1.0.1.0"
+        );
+
+        assert!(is_reverse_zone("4.4.3.3.2.2.1.1.0.0.d.f.ip6.arpa"));
+        assert!(is_reverse_zone("10.in-addr.arpa."));
+        assert!(is_reverse_zone("IP6.ARPA"));
+        assert!(!is_reverse_zone(DNS_ZONE));
+        assert!(!is_reverse_zone("notip6.arpa"));
+    }
+
+    #[test]
+    fn test_builder_reverse_zones() {
+        let sled1_uuid: SledUuid = SLED1_UUID.parse().unwrap();
+        let zone1_uuid: OmicronZoneUuid = ZONE1_UUID.parse().unwrap();
+        let sled_ip: Ipv6Addr = "fd00:1122:3344:101::1".parse().unwrap();
+        let zone_ip: Ipv6Addr = "fd00:1122:3344:101::a".parse().unwrap();
+        let dns_ip: Ipv6Addr = "fd00:1122:3344:1::1".parse().unwrap();
+        let other_az_ip: Ipv6Addr = "fd00:1122:3355:101::a".parse().unwrap();
+
+        // Without any hosts, there are no reverse zones.
+        assert!(DnsConfigBuilder::new().build_reverse_zones().is_empty());
+
+        let mut b = DnsConfigBuilder::new();
+        b.host_sled(sled1_uuid, sled_ip).unwrap();
+        b.host_zone(zone1_uuid, zone_ip).unwrap();
+        b.host_dendrite(sled1_uuid, other_az_ip).unwrap();
+        b.host_zone_internal_dns(
+            OmicronZoneUuid::nil(),
+            ServiceName::InternalDns,
+            SocketAddrV6::new(dns_ip, 5353, 0, 0),
+            SocketAddrV6::new(dns_ip, 53, 0, 0),
+        )
+        .unwrap();
+
+        let zones = b.build_reverse_zones();
+        let zone_names =
+            zones.iter().map(|z| z.zone_name.as_str()).collect::<Vec<_>>();
+        assert_eq!(
+            zone_names,
+            [
+                "4.4.3.3.2.2.1.1.0.0.d.f.ip6.arpa",
+                "5.5.3.3.2.2.1.1.0.0.d.f.ip6.arpa",
+            ]
+        );
+
+        let ns = vec![DnsRecord::Ns(format!("ns1.{DNS_ZONE}"))];
+        for zone in &zones {
+            assert_eq!(zone.records.get(ZONE_APEX_NAME), Some(&ns));
+        }
+
+        let ptr = |zone: &DnsConfigZone, ip: Ipv6Addr| {
+            assert_eq!(zone.zone_name, ipv6_reverse_zone(ip));
+            zone.records.get(&ipv6_reverse_name(ip)).cloned()
+        };
+        assert_eq!(
+            ptr(&zones[0], sled_ip),
+            Some(vec![DnsRecord::Ptr(Host::Sled(sled1_uuid).fqdn())])
+        );
+        assert_eq!(
+            ptr(&zones[0], zone_ip),
+            Some(vec![DnsRecord::Ptr(
+                Host::Zone(Zone::Other(zone1_uuid)).fqdn()
+            )])
+        );
+        assert_eq!(
+            ptr(&zones[0], dns_ip),
+            Some(vec![DnsRecord::Ptr(
+                Host::Zone(Zone::Other(OmicronZoneUuid::nil())).fqdn()
+            )])
+        );
+        assert_eq!(
+            ptr(&zones[1], other_az_ip),
+            Some(vec![DnsRecord::Ptr(
+                Host::Zone(Zone::Dendrite(sled1_uuid)).fqdn()
+            )])
+        );
+        assert_eq!(zones[0].records.len(), 4);
+        assert_eq!(zones[1].records.len(), 2);
+    }
+
     #[test]
     fn test_builder_errors() {
         let sled1_uuid: SledUuid = SLED1_UUID.parse().unwrap();
@@ -153,6 +153,9 @@ impl std::fmt::Display for DnsDiff<'_> {
                             format!("SRV  port {:5} {}", port, target)
                         }
                         DnsRecord::Ns(name) => format!("NS   {}", name),
+                        DnsRecord::Txt(text) => format!("TXT  {:?}", text),
+                        DnsRecord::Cname(name) => format!("CNAME {}", name),
+                        DnsRecord::Ptr(name) => format!("PTR  {}", name),
                     }
                 )?;
             }
@@ -0,0 +1,343 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! DNSSEC key material
+//!
+//! Nexus generates the keys that DNS servers sign zones with, and both need
+//! to derive the same public data from them: the DNSKEY record that the
+//! server publishes, its key tag, and the DS record that an operator gives to
+//! the parent zone.  That's all here.
+//!
+//! We only support algorithm 13 (ECDSA P-256 with SHA-256, RFC 6605), which
+//! is what RFC 8624 recommends for signing.  Its keys and signatures are
+//! small, which matters for answers that have to fit in a UDP datagram.
+
+use crate::config::{
+    DnssecAlgorithm, DnssecKey, DnssecKeyRole, DnssecKeyState, DnssecPublicKey,
+};
+use anyhow::{Context, anyhow, bail};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use ring::rand::SystemRandom;
+use ring::signature::{ECDSA_P256_SHA256_FIXED_SIGNING, EcdsaKeyPair, KeyPair};
+
+/// DNSKEY protocol field, which must always be 3 (RFC 4034 section 2.1.2)
+const DNSKEY_PROTOCOL: u8 = 3;
+
+/// DNSKEY flags for a zone-signing key (the "Zone Key" bit)
+const DNSKEY_FLAGS_ZSK: u16 = 0x0100;
+
+/// DNSKEY flags for a key-signing key (the "Zone Key" and "Secure Entry
+/// Point" bits)
+const DNSKEY_FLAGS_KSK: u16 = 0x0101;
+
+/// DS digest type for SHA-256 (RFC 4509)
+const DS_DIGEST_SHA256: u8 = 2;
+
+impl DnssecAlgorithm {
+    /// Returns the algorithm's number in DNSKEY, DS, and RRSIG records
+    pub fn number(&self) -> u8 {
+        match self {
+            DnssecAlgorithm::EcdsaP256Sha256 => 13,
+        }
+    }
+}
+
+/// Generates a new private key for the given algorithm, encoded as in
+/// [`DnssecKey::private_key`]
+pub fn generate_private_key(
+    algorithm: DnssecAlgorithm,
+) -> anyhow::Result<String> {
+    match algorithm {
+        DnssecAlgorithm::EcdsaP256Sha256 => {
+            let pkcs8 = EcdsaKeyPair::generate_pkcs8(
+                &ECDSA_P256_SHA256_FIXED_SIGNING,
+                &SystemRandom::new(),
+            )
+            .map_err(|_| anyhow!("failed to generate ECDSA key"))?;
+            Ok(STANDARD.encode(pkcs8.as_ref()))
+        }
+    }
+}
+
+/// A parsed DNSSEC key, ready to sign with
+pub struct SigningKey {
+    role: DnssecKeyRole,
+    state: DnssecKeyState,
+    algorithm: DnssecAlgorithm,
+    key_pair: EcdsaKeyPair,
+    rng: SystemRandom,
+}
+
+impl std::fmt::Debug for SigningKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Leave out the private key.
+        f.debug_struct("SigningKey")
+            .field("role", &self.role)
+            .field("state", &self.state)
+            .field("algorithm", &self.algorithm)
+            .field("key_tag", &self.key_tag())
+            .finish_non_exhaustive()
+    }
+}
+
+impl SigningKey {
+    pub fn new(key: &DnssecKey) -> anyhow::Result<SigningKey> {
+        let pkcs8 = STANDARD
+            .decode(&key.private_key)
+            .context("private key is not valid base64")?;
+        let rng = SystemRandom::new();
+        let key_pair = match key.algorithm {
+            DnssecAlgorithm::EcdsaP256Sha256 => EcdsaKeyPair::from_pkcs8(
+                &ECDSA_P256_SHA256_FIXED_SIGNING,
+                &pkcs8,
+                &rng,
+            )
+            .map_err(|error| anyhow!("bad ECDSA private key: {}", error))?,
+        };
+        Ok(SigningKey {
+            role: key.role,
+            state: key.state,
+            algorithm: key.algorithm,
+            key_pair,
+            rng,
+        })
+    }
+
+    pub fn role(&self) -> DnssecKeyRole {
+        self.role
+    }
+
+    pub fn state(&self) -> DnssecKeyState {
+        self.state
+    }
+
+    pub fn algorithm(&self) -> DnssecAlgorithm {
+        self.algorithm
+    }
+
+    /// Returns the key's public half, as it appears in a DNSKEY record
+    ///
+    /// For ECDSA this is the curve point's X and Y coordinates, without the
+    /// leading byte that marks the point as uncompressed (RFC 6605 section
+    /// 4).
+    pub fn public_key(&self) -> &[u8] {
+        &self.key_pair.public_key().as_ref()[1..]
+    }
+
+    /// Returns the flags field of the key's DNSKEY record
+    pub fn flags(&self) -> u16 {
+        match self.role {
+            DnssecKeyRole::Ksk => DNSKEY_FLAGS_KSK,
+            DnssecKeyRole::Zsk => DNSKEY_FLAGS_ZSK,
+        }
+    }
+
+    /// Returns the RDATA of the key's DNSKEY record
+    pub fn dnskey_rdata(&self) -> Vec<u8> {
+        let mut rdata = Vec::with_capacity(4 + self.public_key().len());
+        rdata.extend_from_slice(&self.flags().to_be_bytes());
+        rdata.push(DNSKEY_PROTOCOL);
+        rdata.push(self.algorithm.number());
+        rdata.extend_from_slice(self.public_key());
+        rdata
+    }
+
+    /// Returns the key's tag, which identifies it (though not uniquely) in
+    /// RRSIG and DS records (RFC 4034 appendix B)
+    pub fn key_tag(&self) -> u16 {
+        key_tag(&self.dnskey_rdata())
+    }
+
+    /// Returns the key's DNSKEY record for the zone `zone_name`, in zone file
+    /// presentation format
+    pub fn dnskey_text(&self, zone_name: &str) -> String {
+        format!(
+            "{} IN DNSKEY {} {} {} {}",
+            fqdn(zone_name),
+            self.flags(),
+            DNSKEY_PROTOCOL,
+            self.algorithm.number(),
+            STANDARD.encode(self.public_key()),
+        )
+    }
+
+    /// Returns the DS record that the parent of zone `zone_name` should
+    /// publish for this key, in zone file presentation format
+    pub fn ds_text(&self, zone_name: &str) -> anyhow::Result<String> {
+        let digest = ds_digest(zone_name, &self.dnskey_rdata())?;
+        Ok(format!(
+            "{} IN DS {} {} {} {}",
+            fqdn(zone_name),
+            self.key_tag(),
+            self.algorithm.number(),
+            DS_DIGEST_SHA256,
+            hex::encode_upper(digest),
+        ))
+    }
+
+    /// Returns a description of the key's public half, as used by the zone
+    /// `zone_name`
+    pub fn public_key_status(
+        &self,
+        zone_name: &str,
+    ) -> anyhow::Result<DnssecPublicKey> {
+        Ok(DnssecPublicKey {
+            role: self.role,
+            state: self.state,
+            algorithm: self.algorithm,
+            key_tag: self.key_tag(),
+            dnskey: self.dnskey_text(zone_name),
+            ds: match self.role {
+                DnssecKeyRole::Ksk => Some(self.ds_text(zone_name)?),
+                DnssecKeyRole::Zsk => None,
+            },
+        })
+    }
+
+    /// Signs `data`, returning the signature as it appears in an RRSIG
+    /// record
+    pub fn sign(&self, data: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let signature = self
+            .key_pair
+            .sign(&self.rng, data)
+            .map_err(|_| anyhow!("failed to sign"))?;
+        Ok(signature.as_ref().to_vec())
+    }
+}
+
+/// Computes the key tag of a key with the given DNSKEY RDATA (RFC 4034
+/// appendix B)
+pub fn key_tag(dnskey_rdata: &[u8]) -> u16 {
+    let mut ac: u32 = 0;
+    for (i, byte) in dnskey_rdata.iter().enumerate() {
+        if i & 1 == 0 {
+            ac += u32::from(*byte) << 8;
+        } else {
+            ac += u32::from(*byte);
+        }
+    }
+    ac += (ac >> 16) & 0xffff;
+    (ac & 0xffff) as u16
+}
+
+/// Computes the SHA-256 digest of a DS record for the key of zone
+/// `zone_name` with the given DNSKEY RDATA (RFC 4509 section 2.1)
+pub fn ds_digest(
+    zone_name: &str,
+    dnskey_rdata: &[u8],
+) -> anyhow::Result<Vec<u8>> {
+    let mut data = name_to_wire(zone_name)?;
+    data.extend_from_slice(dnskey_rdata);
+    Ok(ring::digest::digest(&ring::digest::SHA256, &data).as_ref().to_vec())
+}
+
+/// Encodes `name` in the canonical wire format used in signatures and DS
+/// digests: uncompressed, with ASCII letters lowercased (RFC 4034 section
+/// 6.2)
+pub fn name_to_wire(name: &str) -> anyhow::Result<Vec<u8>> {
+    let mut wire = Vec::with_capacity(name.len() + 2);
+    for label in name.split('.').filter(|label| !label.is_empty()) {
+        if label.len() > 63 {
+            bail!("label {:?} in name {:?} is too long", label, name);
+        }
+        wire.push(label.len() as u8);
+        wire.extend(label.bytes().map(|b| b.to_ascii_lowercase()));
+    }
+    wire.push(0);
+    if wire.len() > 255 {
+        bail!("name {:?} is too long", name);
+    }
+    Ok(wire)
+}
+
+/// Returns `name` with a trailing dot
+fn fqdn(name: &str) -> String {
+    if name.ends_with('.') { name.to_owned() } else { format!("{}.", name) }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ring::signature::{ECDSA_P256_SHA256_FIXED, UnparsedPublicKey};
+
+    fn test_key(role: DnssecKeyRole) -> SigningKey {
+        let algorithm = DnssecAlgorithm::EcdsaP256Sha256;
+        SigningKey::new(&DnssecKey {
+            role,
+            state: DnssecKeyState::Active,
+            algorithm,
+            private_key: generate_private_key(algorithm).unwrap(),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_rfc6605_example() {
+        // The KSK for example.net. from RFC 6605 section 6.1, and its key tag
+        // and DS digest from the same example.
+        let public_key = STANDARD
+            .decode(
+                "GojIhhXUN/u4v54ZQqGSnyhWJwaubCvTmeexv7bR6edb\
+                 krSqQpF64cYbcB7wNcP+e+MAnLr+Wi9xMWyQLc8NAA==",
+            )
+            .unwrap();
+        let mut rdata = vec![0x01, 0x01, 3, 13];
+        rdata.extend_from_slice(&public_key);
+        assert_eq!(key_tag(&rdata), 55648);
+        assert_eq!(
+            hex::encode(ds_digest("example.net.", &rdata).unwrap()),
+            "b4c8c1fe2e7477127b27115656ad6256f424625bf5c1\
+             e2770ce6d6e37df61d17"
+        );
+    }
+
+    #[test]
+    fn test_name_to_wire() {
+        assert_eq!(
+            name_to_wire("Oxide.Example.").unwrap(),
+            b"\x05oxide\x07example\x00"
+        );
+        assert_eq!(
+            name_to_wire("oxide.example").unwrap(),
+            b"\x05oxide\x07example\x00"
+        );
+        assert_eq!(name_to_wire(".").unwrap(), b"\x00");
+        assert!(name_to_wire(&format!("{}.example", "a".repeat(64))).is_err());
+    }
+
+    #[test]
+    fn test_signing_key() {
+        let ksk = test_key(DnssecKeyRole::Ksk);
+        let zsk = test_key(DnssecKeyRole::Zsk);
+        assert_eq!(ksk.public_key().len(), 64);
+        assert_eq!(ksk.flags(), 257);
+        assert_eq!(zsk.flags(), 256);
+        assert_eq!(ksk.dnskey_rdata().len(), 68);
+
+        let status = ksk.public_key_status("oxide.example").unwrap();
+        assert_eq!(status.key_tag, ksk.key_tag());
+        assert!(
+            status.dnskey.starts_with("oxide.example. IN DNSKEY 257 3 13 ")
+        );
+        let ds = status.ds.expect("KSKs have a DS record");
+        let prefix = format!("oxide.example. IN DS {} 13 2 ", ksk.key_tag());
+        assert!(ds.starts_with(&prefix));
+        assert_eq!(ds.len(), prefix.len() + 64);
+        assert!(zsk.public_key_status("oxide.example").unwrap().ds.is_none());
+
+        // Signatures verify with the public key published in the DNSKEY
+        // record.
+        let signature = zsk.sign(b"some data").unwrap();
+        assert_eq!(signature.len(), 64);
+        let mut point = vec![0x04];
+        point.extend_from_slice(zsk.public_key());
+        UnparsedPublicKey::new(&ECDSA_P256_SHA256_FIXED, &point)
+            .verify(b"some data", &signature)
+            .unwrap();
+        UnparsedPublicKey::new(&ECDSA_P256_SHA256_FIXED, &point)
+            .verify(b"other data", &signature)
+            .unwrap_err();
+    }
+}
@@ -7,4 +7,5 @@
 
 pub mod config;
 pub mod diff;
+pub mod dnssec;
 pub mod names;
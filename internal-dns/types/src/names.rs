@@ -4,7 +4,9 @@
 
 //! Well-known DNS names and related types for internal DNS (see RFD 248)
 
+use omicron_common::address::AZ_PREFIX;
 use omicron_uuid_kinds::{OmicronZoneUuid, SledUuid};
+use std::net::Ipv6Addr;
 use strum::{EnumIter, IntoEnumIterator};
 
 /// Name for the special boundary NTP DNS name
@@ -32,6 +34,56 @@ pub const DNS_ZONE_EXTERNAL_TESTING: &str = "oxide-dev.test";
 /// typically described with the name "@".
 pub const ZONE_APEX_NAME: &str = "@";
 
+/// Suffix of the DNS zones used for reverse lookups of IPv6 addresses
+pub const IPV6_REVERSE_DOMAIN: &str = "ip6.arpa";
+
+/// Suffix of the DNS zones used for reverse lookups of IPv4 addresses
+pub const IPV4_REVERSE_DOMAIN: &str = "in-addr.arpa";
+
+/// Number of nibbles (hex digits) of an underlay address that make up the
+/// name of its reverse zone
+///
+/// The control plane's underlay addresses all fall within the availability
+/// zone's subnet, so one reverse zone per AZ subnet covers all of them.
+const IPV6_REVERSE_ZONE_NIBBLES: usize = (AZ_PREFIX / 4) as usize;
+
+/// Returns the nibbles of `addr`, least significant first, as used in names
+/// under [`IPV6_REVERSE_DOMAIN`]
+fn ipv6_reverse_nibbles(addr: Ipv6Addr) -> Vec<String> {
+    format!("{:032x}", u128::from(addr))
+        .chars()
+        .rev()
+        .map(String::from)
+        .collect()
+}
+
+/// Returns the name of the reverse DNS zone that covers the underlay address
+/// `addr`
+///
+/// For example, an address in `fd00:1122:3344::/48` is covered by the zone
+/// `4.4.3.3.2.2.1.1.0.0.d.f.ip6.arpa`.
+pub fn ipv6_reverse_zone(addr: Ipv6Addr) -> String {
+    let nibbles = ipv6_reverse_nibbles(addr);
+    let zone_nibbles = &nibbles[nibbles.len() - IPV6_REVERSE_ZONE_NIBBLES..];
+    format!("{}.{IPV6_REVERSE_DOMAIN}", zone_nibbles.join("."))
+}
+
+/// Returns the name of the PTR record for `addr`, relative to
+/// [`ipv6_reverse_zone()`] for the same address
+pub fn ipv6_reverse_name(addr: Ipv6Addr) -> String {
+    let nibbles = ipv6_reverse_nibbles(addr);
+    nibbles[..nibbles.len() - IPV6_REVERSE_ZONE_NIBBLES].join(".")
+}
+
+/// Returns whether `zone_name` names a reverse DNS zone (one under
+/// [`IPV6_REVERSE_DOMAIN`] or [`IPV4_REVERSE_DOMAIN`])
+pub fn is_reverse_zone(zone_name: &str) -> bool {
+    let zone_name = zone_name.trim_end_matches('.').to_ascii_lowercase();
+    [IPV6_REVERSE_DOMAIN, IPV4_REVERSE_DOMAIN].iter().any(|domain| {
+        zone_name == *domain || zone_name.ends_with(&format!(".{domain}"))
+    })
+}
+
 /// Names of services within the control plane
 #[derive(
     Clone, Copy, Debug, Hash, Eq, Ord, PartialEq, PartialOrd, EnumIter,
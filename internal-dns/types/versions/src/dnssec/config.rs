@@ -0,0 +1,141 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use omicron_common::api::external::Generation;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The DNSSEC signing keys for the zones that a DNS server signs
+///
+/// Zones that appear here are signed online: the server adds signatures to
+/// its answers for names in these zones when the query asks for them (by
+/// setting the EDNS "DNSSEC OK" bit), and publishes the zone's public keys as
+/// DNSKEY records at the zone's apex. Zones that do not appear here are served
+/// unsigned.
+///
+/// This has its own generation, separate from that of the server's records:
+/// keys are rolled over on their own schedule.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct DnssecConfigParams {
+    pub generation: Generation,
+    pub time_created: chrono::DateTime<chrono::Utc>,
+    pub zones: Vec<DnssecZone>,
+}
+
+/// The signing keys for one zone
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct DnssecZone {
+    pub zone_name: String,
+    pub keys: Vec<DnssecKey>,
+}
+
+/// A DNSSEC signing key, including its private half
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct DnssecKey {
+    pub role: DnssecKeyRole,
+    pub state: DnssecKeyState,
+    pub algorithm: DnssecAlgorithm,
+    /// The private key, as a base64-encoded PKCS#8 document
+    pub private_key: String,
+}
+
+/// What a DNSSEC key signs
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum DnssecKeyRole {
+    /// A key-signing key, which signs the zone's DNSKEY records and is
+    /// referred to by the DS records in the parent zone
+    Ksk,
+    /// A zone-signing key, which signs all of the zone's other records
+    Zsk,
+}
+
+/// Where a DNSSEC key is in its lifecycle
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum DnssecKeyState {
+    /// The key is published as a DNSKEY record, but is not used to sign
+    /// anything other than (for a key-signing key) the DNSKEY records
+    ///
+    /// Keys are published ahead of being used, and kept published for a
+    /// while after they stop being used, so that resolvers that have cached
+    /// the zone's DNSKEY records can validate signatures made with either
+    /// the old or the new key.
+    Published,
+    /// The key is published and is used to sign records
+    Active,
+}
+
+/// The DNSSEC algorithm of a key
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+)]
+pub enum DnssecAlgorithm {
+    /// ECDSA using curve P-256 and SHA-256 (algorithm 13, RFC 6605)
+    #[serde(rename = "ECDSAP256SHA256")]
+    EcdsaP256Sha256,
+}
+
+/// The DNSSEC configuration of a DNS server, as reported by the server
+///
+/// This does not include the keys' private halves.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DnssecConfig {
+    pub generation: Generation,
+    pub time_created: chrono::DateTime<chrono::Utc>,
+    pub time_applied: chrono::DateTime<chrono::Utc>,
+    pub zones: Vec<DnssecZoneStatus>,
+}
+
+/// The public keys of a zone that the server signs
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DnssecZoneStatus {
+    pub zone_name: String,
+    pub keys: Vec<DnssecPublicKey>,
+}
+
+/// The public half of a DNSSEC key
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DnssecPublicKey {
+    pub role: DnssecKeyRole,
+    pub state: DnssecKeyState,
+    pub algorithm: DnssecAlgorithm,
+    pub key_tag: u16,
+    /// The key's DNSKEY record, in zone file presentation format
+    pub dnskey: String,
+    /// For key-signing keys, the DS record that the parent zone should
+    /// publish for this key, in zone file presentation format
+    pub ds: Option<String>,
+}
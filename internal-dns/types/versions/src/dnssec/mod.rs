@@ -0,0 +1,13 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Version `DNSSEC` of the DNS server API.
+//!
+//! This version adds:
+//!
+//! - [`config::DnssecConfigParams`] and [`config::DnssecConfig`], the signing
+//!   keys for the zones that the server signs. Like the VPC zones, these have
+//!   their own generation, separate from that of the server's records.
+
+pub mod config;
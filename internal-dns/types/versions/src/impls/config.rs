@@ -2,9 +2,11 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use crate::latest::config::{DnsConfigParams, DnsConfigZone, DnsRecord, Srv};
+use crate::latest::config::{
+    DnsConfigParams, DnsConfigZone, DnsRecord, Srv, VpcDnsClient,
+};
 use anyhow::ensure;
-use std::net::{Ipv4Addr, Ipv6Addr};
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
 
 /// Error code used when a record type cannot be represented in an older API
 /// version (e.g., NS records in v1).
@@ -38,8 +40,9 @@ impl DnsConfigParams {
 // dubious, because a v4 or v6 address could also theoretically map to a DNS
 // PTR record
 // (https://www.cloudflare.com/learning/dns/dns-records/dns-ptr-record/).
-// However, we don't support PTR records at the moment, so this is fine. Would
-// certainly be worth revisiting if we do in the future, though.
+// PTR records hold a name rather than an address, though (the address is
+// encoded in the name the record is stored under), so an address on its own
+// still always means a forward record.
 
 impl From<Ipv4Addr> for DnsRecord {
     fn from(ip: Ipv4Addr) -> Self {
@@ -58,3 +61,13 @@ impl From<Srv> for DnsRecord {
         DnsRecord::Srv(srv)
     }
 }
+
+impl VpcDnsClient {
+    /// Returns true if a query from `addr` was sent by this client
+    pub fn contains(&self, addr: SocketAddr) -> bool {
+        // Queries over IPv4 arrive on a dual-stack socket as IPv4-mapped IPv6
+        // addresses.
+        addr.ip().to_canonical() == self.ip
+            && (self.first_port..=self.last_port).contains(&addr.port())
+    }
+}
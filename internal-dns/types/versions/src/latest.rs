@@ -5,11 +5,24 @@
 //! Re-exports of the latest versions of each type.
 
 pub mod config {
-    pub use crate::v2::config::DnsConfig;
-    pub use crate::v2::config::DnsConfigParams;
-    pub use crate::v2::config::DnsConfigZone;
-    pub use crate::v2::config::DnsRecord;
     pub use crate::v2::config::Srv;
+    pub use crate::v3::config::VpcDnsClient;
+    pub use crate::v4::config::DnsConfig;
+    pub use crate::v4::config::DnsConfigParams;
+    pub use crate::v4::config::DnsConfigZone;
+    pub use crate::v4::config::DnsRecord;
+    pub use crate::v4::config::VpcDnsZone;
+    pub use crate::v5::config::DnssecAlgorithm;
+    pub use crate::v5::config::DnssecConfig;
+    pub use crate::v5::config::DnssecConfigParams;
+    pub use crate::v5::config::DnssecKey;
+    pub use crate::v5::config::DnssecKeyRole;
+    pub use crate::v5::config::DnssecKeyState;
+    pub use crate::v5::config::DnssecPublicKey;
+    pub use crate::v5::config::DnssecZone;
+    pub use crate::v5::config::DnssecZoneStatus;
+    pub use crate::v6::config::VpcDnsConfig;
+    pub use crate::v6::config::VpcDnsConfigParams;
 
     pub use crate::impls::config::ERROR_CODE_BAD_UPDATE_GENERATION;
     pub use crate::impls::config::ERROR_CODE_INCOMPATIBLE_RECORD;
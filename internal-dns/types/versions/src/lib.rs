@@ -35,3 +35,11 @@ pub mod latest;
 pub mod v1;
 #[path = "soa_and_ns/mod.rs"]
 pub mod v2;
+#[path = "vpc_zones/mod.rs"]
+pub mod v3;
+#[path = "record_types/mod.rs"]
+pub mod v4;
+#[path = "dnssec/mod.rs"]
+pub mod v5;
+#[path = "vpc_forwarders/mod.rs"]
+pub mod v6;
@@ -0,0 +1,294 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::v2;
+use crate::v2::config::Srv;
+use crate::v3;
+use crate::v3::config::VpcDnsClient;
+use omicron_common::api::external::Generation;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct DnsConfigParams {
+    pub generation: Generation,
+    /// See [`DnsConfig`]'s `serial` field for how this is different from `generation`
+    pub serial: u32,
+    pub time_created: chrono::DateTime<chrono::Utc>,
+    pub zones: Vec<DnsConfigZone>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DnsConfig {
+    pub generation: Generation,
+    /// A serial number for this DNS configuration, as should be used in SOA
+    /// records describing the configuration's zones. This is a property of the
+    /// overall DNS configuration for convenience: Nexus versions DNS
+    /// configurations at this granularity, and we expect Nexus will derive
+    /// serial numbers from that version.
+    pub serial: u32,
+    pub time_created: chrono::DateTime<chrono::Utc>,
+    pub time_applied: chrono::DateTime<chrono::Utc>,
+    pub zones: Vec<DnsConfigZone>,
+}
+
+/// Error type for conversions from v4 to v2 and v3.
+pub enum V4ToV3TranslationError {
+    /// The configuration contains records (such as TXT, CNAME, or PTR
+    /// records) that cannot be represented in v2 or v3.
+    IncompatibleRecord,
+}
+
+/// Configuration for a specific DNS zone, as opposed to illumos zones in which
+/// the services described by these records run.
+///
+/// The name `@` is special: it describes records that should be provided for
+/// queries about `zone_name`. This is used in favor of the empty string as `@`
+/// is the name used for this purpose in zone files for most DNS configurations.
+/// It also avoids potentially-confusing debug output from naively printing out
+/// records and their names - if you've seen an `@` record and tools are unclear
+/// about what that means, hopefully you've arrived here!
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct DnsConfigZone {
+    pub zone_name: String,
+    pub records: HashMap<String, Vec<DnsRecord>>,
+}
+
+#[derive(
+    Clone,
+    Debug,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+)]
+#[serde(tag = "type", content = "data")]
+pub enum DnsRecord {
+    A(Ipv4Addr),
+    // The renames are because openapi-lint complains about `Aaaa` and `Srv`
+    // not being in screaming snake case. `Aaaa` and `Srv` are the idiomatic
+    // Rust casings, though.
+    #[serde(rename = "AAAA")]
+    Aaaa(Ipv6Addr),
+    #[serde(rename = "SRV")]
+    Srv(Srv),
+    #[serde(rename = "NS")]
+    Ns(String),
+    // The text is served as a single TXT record, split into as many
+    // 255-byte character-strings as it needs.
+    #[serde(rename = "TXT")]
+    Txt(String),
+    // A name may have a CNAME record only if it has no other records.
+    #[serde(rename = "CNAME")]
+    Cname(String),
+    // PTR records are only useful in reverse zones (`in-addr.arpa` and
+    // `ip6.arpa`), but nothing here requires that.
+    #[serde(rename = "PTR")]
+    Ptr(String),
+}
+
+/// The DNS zones private to VPCs
+///
+/// These have their own generation, separate from that of the server's other
+/// zones: they change whenever instances' network interfaces do, and are
+/// propagated by a different part of the control plane.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct VpcDnsConfigParams {
+    pub generation: Generation,
+    pub time_created: chrono::DateTime<chrono::Utc>,
+    pub zones: Vec<VpcDnsZone>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct VpcDnsConfig {
+    pub generation: Generation,
+    pub time_created: chrono::DateTime<chrono::Utc>,
+    pub time_applied: chrono::DateTime<chrono::Utc>,
+    pub zones: Vec<VpcDnsZone>,
+}
+
+/// A DNS zone that is served only to the instances of one VPC
+///
+/// Zones of different VPCs may have the same name. A query is answered from
+/// the zone whose clients include the query's source address, and queries
+/// from any other address are treated as being for a zone the server does not
+/// have.
+///
+/// Names in `records` are relative to `zone_name`, as in `DnsConfigZone`.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct VpcDnsZone {
+    pub zone_name: String,
+    pub clients: Vec<VpcDnsClient>,
+    pub records: HashMap<String, Vec<DnsRecord>>,
+}
+
+impl From<v2::config::DnsConfigParams> for DnsConfigParams {
+    fn from(v2: v2::config::DnsConfigParams) -> Self {
+        let v2::config::DnsConfigParams {
+            generation,
+            serial,
+            time_created,
+            zones,
+        } = v2;
+        DnsConfigParams {
+            generation,
+            serial,
+            time_created,
+            zones: zones.into_iter().map(DnsConfigZone::from).collect(),
+        }
+    }
+}
+
+impl From<v2::config::DnsConfigZone> for DnsConfigZone {
+    fn from(v2: v2::config::DnsConfigZone) -> Self {
+        DnsConfigZone {
+            zone_name: v2.zone_name,
+            records: records_from_v2(v2.records),
+        }
+    }
+}
+
+impl From<v2::config::DnsRecord> for DnsRecord {
+    fn from(v2: v2::config::DnsRecord) -> Self {
+        match v2 {
+            v2::config::DnsRecord::A(ip) => DnsRecord::A(ip),
+            v2::config::DnsRecord::Aaaa(ip) => DnsRecord::Aaaa(ip),
+            v2::config::DnsRecord::Srv(srv) => DnsRecord::Srv(srv),
+            v2::config::DnsRecord::Ns(nsdname) => DnsRecord::Ns(nsdname),
+        }
+    }
+}
+
+impl From<v3::config::VpcDnsConfigParams> for VpcDnsConfigParams {
+    fn from(v3: v3::config::VpcDnsConfigParams) -> Self {
+        let v3::config::VpcDnsConfigParams { generation, time_created, zones } =
+            v3;
+        VpcDnsConfigParams {
+            generation,
+            time_created,
+            zones: zones.into_iter().map(VpcDnsZone::from).collect(),
+        }
+    }
+}
+
+impl From<v3::config::VpcDnsZone> for VpcDnsZone {
+    fn from(v3: v3::config::VpcDnsZone) -> Self {
+        VpcDnsZone {
+            zone_name: v3.zone_name,
+            clients: v3.clients,
+            records: records_from_v2(v3.records),
+        }
+    }
+}
+
+fn records_from_v2(
+    records: HashMap<String, Vec<v2::config::DnsRecord>>,
+) -> HashMap<String, Vec<DnsRecord>> {
+    records
+        .into_iter()
+        .map(|(name, records)| {
+            (name, records.into_iter().map(DnsRecord::from).collect())
+        })
+        .collect()
+}
+
+impl TryFrom<DnsConfig> for v2::config::DnsConfig {
+    type Error = V4ToV3TranslationError;
+
+    fn try_from(v4: DnsConfig) -> Result<Self, Self::Error> {
+        let DnsConfig { generation, serial, time_created, time_applied, zones } =
+            v4;
+
+        Ok(v2::config::DnsConfig {
+            generation,
+            serial,
+            time_created,
+            time_applied,
+            zones: zones
+                .into_iter()
+                .map(|zone| zone.try_into())
+                .collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+}
+
+impl TryFrom<DnsConfigZone> for v2::config::DnsConfigZone {
+    type Error = V4ToV3TranslationError;
+
+    fn try_from(v4: DnsConfigZone) -> Result<Self, Self::Error> {
+        let DnsConfigZone { zone_name, records } = v4;
+        Ok(v2::config::DnsConfigZone {
+            zone_name,
+            records: records_to_v2(records)?,
+        })
+    }
+}
+
+impl TryFrom<DnsRecord> for v2::config::DnsRecord {
+    type Error = V4ToV3TranslationError;
+
+    fn try_from(v4: DnsRecord) -> Result<Self, Self::Error> {
+        match v4 {
+            DnsRecord::A(ip) => Ok(v2::config::DnsRecord::A(ip)),
+            DnsRecord::Aaaa(ip) => Ok(v2::config::DnsRecord::Aaaa(ip)),
+            DnsRecord::Srv(srv) => Ok(v2::config::DnsRecord::Srv(srv)),
+            DnsRecord::Ns(nsdname) => Ok(v2::config::DnsRecord::Ns(nsdname)),
+            DnsRecord::Txt(_) | DnsRecord::Cname(_) | DnsRecord::Ptr(_) => {
+                Err(V4ToV3TranslationError::IncompatibleRecord)
+            }
+        }
+    }
+}
+
+impl TryFrom<VpcDnsConfig> for v3::config::VpcDnsConfig {
+    type Error = V4ToV3TranslationError;
+
+    fn try_from(v4: VpcDnsConfig) -> Result<Self, Self::Error> {
+        let VpcDnsConfig { generation, time_created, time_applied, zones } = v4;
+
+        Ok(v3::config::VpcDnsConfig {
+            generation,
+            time_created,
+            time_applied,
+            zones: zones
+                .into_iter()
+                .map(|zone| zone.try_into())
+                .collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+}
+
+impl TryFrom<VpcDnsZone> for v3::config::VpcDnsZone {
+    type Error = V4ToV3TranslationError;
+
+    fn try_from(v4: VpcDnsZone) -> Result<Self, Self::Error> {
+        let VpcDnsZone { zone_name, clients, records } = v4;
+        Ok(v3::config::VpcDnsZone {
+            zone_name,
+            clients,
+            records: records_to_v2(records)?,
+        })
+    }
+}
+
+fn records_to_v2(
+    records: HashMap<String, Vec<DnsRecord>>,
+) -> Result<HashMap<String, Vec<v2::config::DnsRecord>>, V4ToV3TranslationError>
+{
+    records
+        .into_iter()
+        .map(|(name, records)| {
+            let converted_records = records
+                .into_iter()
+                .map(|v| v.try_into())
+                .collect::<Result<_, _>>();
+            converted_records.map(|records| (name, records))
+        })
+        .collect()
+}
@@ -0,0 +1,17 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Version `RECORD_TYPES` of the DNS server API.
+//!
+//! This version adds:
+//!
+//! - The [`config::DnsRecord::Txt`], [`config::DnsRecord::Cname`], and
+//!   [`config::DnsRecord::Ptr`] variants, for text, alias, and reverse-lookup
+//!   records.
+//!
+//! All types that contain a [`config::DnsRecord`] are redefined here so that
+//! they use the new variants, including the VPC zone types added in
+//! `VPC_ZONES`.
+
+pub mod config;
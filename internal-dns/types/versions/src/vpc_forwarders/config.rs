@@ -0,0 +1,87 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::v4;
+use crate::v4::config::VpcDnsZone;
+use omicron_common::api::external::Generation;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+
+/// The DNS zones private to VPCs
+///
+/// These have their own generation, separate from that of the server's other
+/// zones: they change whenever instances' network interfaces do, and are
+/// propagated by a different part of the control plane.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct VpcDnsConfigParams {
+    pub generation: Generation,
+    pub time_created: chrono::DateTime<chrono::Utc>,
+    /// Recursive resolvers to forward queries to
+    ///
+    /// A query from one of the zones' clients for a name outside of that
+    /// client's zones is forwarded to these, in order, and the first answer
+    /// is relayed back. This lets instances use the server as their only
+    /// resolver.
+    pub forwarders: Vec<SocketAddr>,
+    pub zones: Vec<VpcDnsZone>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct VpcDnsConfig {
+    pub generation: Generation,
+    pub time_created: chrono::DateTime<chrono::Utc>,
+    pub time_applied: chrono::DateTime<chrono::Utc>,
+    pub forwarders: Vec<SocketAddr>,
+    pub zones: Vec<VpcDnsZone>,
+}
+
+impl From<v4::config::VpcDnsConfigParams> for VpcDnsConfigParams {
+    fn from(v4: v4::config::VpcDnsConfigParams) -> Self {
+        let v4::config::VpcDnsConfigParams { generation, time_created, zones } =
+            v4;
+        VpcDnsConfigParams {
+            generation,
+            time_created,
+            forwarders: Vec::new(),
+            zones,
+        }
+    }
+}
+
+impl From<v4::config::VpcDnsConfig> for VpcDnsConfig {
+    fn from(v4: v4::config::VpcDnsConfig) -> Self {
+        let v4::config::VpcDnsConfig {
+            generation,
+            time_created,
+            time_applied,
+            zones,
+        } = v4;
+        VpcDnsConfig {
+            generation,
+            time_created,
+            time_applied,
+            forwarders: Vec::new(),
+            zones,
+        }
+    }
+}
+
+impl From<VpcDnsConfig> for v4::config::VpcDnsConfig {
+    fn from(v6: VpcDnsConfig) -> Self {
+        let VpcDnsConfig {
+            generation,
+            time_created,
+            time_applied,
+            forwarders: _,
+            zones,
+        } = v6;
+        v4::config::VpcDnsConfig {
+            generation,
+            time_created,
+            time_applied,
+            zones,
+        }
+    }
+}
@@ -0,0 +1,13 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Version `VPC_FORWARDERS` of the DNS server API.
+//!
+//! This version adds:
+//!
+//! - The `forwarders` field of [`config::VpcDnsConfigParams`] and
+//!   [`config::VpcDnsConfig`]: recursive resolvers that queries from the VPC
+//!   zones' clients for names outside those zones are forwarded to.
+
+pub mod config;
@@ -0,0 +1,65 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::v2::config::DnsRecord;
+use omicron_common::api::external::Generation;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+/// The DNS zones private to VPCs
+///
+/// These have their own generation, separate from that of the server's other
+/// zones: they change whenever instances' network interfaces do, and are
+/// propagated by a different part of the control plane.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct VpcDnsConfigParams {
+    pub generation: Generation,
+    pub time_created: chrono::DateTime<chrono::Utc>,
+    pub zones: Vec<VpcDnsZone>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct VpcDnsConfig {
+    pub generation: Generation,
+    pub time_created: chrono::DateTime<chrono::Utc>,
+    pub time_applied: chrono::DateTime<chrono::Utc>,
+    pub zones: Vec<VpcDnsZone>,
+}
+
+/// A DNS zone that is served only to the instances of one VPC
+///
+/// Zones of different VPCs may have the same name. A query is answered from
+/// the zone whose clients include the query's source address, and queries
+/// from any other address are treated as being for a zone the server does not
+/// have.
+///
+/// Names in `records` are relative to `zone_name`, as in `DnsConfigZone`.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct VpcDnsZone {
+    pub zone_name: String,
+    pub clients: Vec<VpcDnsClient>,
+    pub records: HashMap<String, Vec<DnsRecord>>,
+}
+
+/// A source address, and range of source ports, from which a VPC's instances
+/// send DNS queries
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+)]
+pub struct VpcDnsClient {
+    pub ip: IpAddr,
+    pub first_port: u16,
+    pub last_port: u16,
+}
@@ -0,0 +1,13 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Version `VPC_ZONES` of the DNS server API.
+//!
+//! This version adds:
+//!
+//! - [`config::VpcDnsConfigParams`] and [`config::VpcDnsConfig`], describing
+//!   DNS zones that are private to a VPC. These are configured separately
+//!   from the server's other zones.
+
+pub mod config;
@@ -0,0 +1,172 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Integrity checking of the copies of a ledger.
+//!
+//! Each ledger file carries a SHA-256 checksum of its contents in a top-level
+//! `ledger_checksum` field.  The checksum covers the rest of the file's JSON
+//! (as serialized by `serde_json`), so bit rot that still leaves valid JSON is
+//! detected rather than silently accepted.  Software that predates checksums
+//! ignores the field, and files that predate checksums are accepted without
+//! one.
+//!
+//! When a ledger is loaded, each copy is classified (see [`LedgerCopyStatus`])
+//! and the results are collected into a [`LedgerReport`].
+
+use camino::Utf8PathBuf;
+use schemars::JsonSchema;
+use schemars::r#gen::SchemaGenerator;
+use schemars::schema::{Schema, SchemaObject};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+
+/// Name of the field holding the checksum of a ledger file
+const CHECKSUM_FIELD: &str = "ledger_checksum";
+
+const CHECKSUM_PREFIX: &str = "sha256:";
+
+#[derive(thiserror::Error, Debug)]
+#[error("checksum mismatch (recorded {recorded}, computed {computed})")]
+pub struct ChecksumMismatch {
+    recorded: String,
+    computed: String,
+}
+
+fn checksum(value: &serde_json::Value) -> Result<String, serde_json::Error> {
+    let bytes = serde_json::to_vec(value)?;
+    Ok(format!("{CHECKSUM_PREFIX}{}", hex::encode(Sha256::digest(&bytes))))
+}
+
+/// Serializes `value`, adding a checksum if it's a JSON object.
+pub(crate) fn seal(
+    mut value: serde_json::Value,
+) -> Result<Vec<u8>, serde_json::Error> {
+    // Ledgers are almost always structs, but there's nowhere to put a checksum
+    // in anything else, so those are written without one.
+    if value.is_object() {
+        let sum = checksum(&value)?;
+        value
+            .as_object_mut()
+            .unwrap()
+            .insert(CHECKSUM_FIELD.to_string(), serde_json::Value::String(sum));
+    }
+    serde_json::to_vec(&value)
+}
+
+/// Verifies the checksum in `s`, returning the contents without it.
+///
+/// Contents that aren't valid JSON or have no checksum are returned as-is;
+/// it's up to the caller to decide whether they're otherwise valid.
+pub(crate) fn verify(s: &str) -> Result<Cow<'_, str>, ChecksumMismatch> {
+    let Ok(mut value) = serde_json::from_str::<serde_json::Value>(s) else {
+        return Ok(Cow::Borrowed(s));
+    };
+    let Some(recorded) =
+        value.as_object_mut().and_then(|obj| obj.shift_remove(CHECKSUM_FIELD))
+    else {
+        return Ok(Cow::Borrowed(s));
+    };
+
+    // `Value` can always be serialized.
+    let computed = checksum(&value).expect("serialized JSON value");
+    match recorded {
+        serde_json::Value::String(recorded) if recorded == computed => {
+            Ok(Cow::Owned(value.to_string()))
+        }
+        recorded => {
+            Err(ChecksumMismatch { recorded: recorded.to_string(), computed })
+        }
+    }
+}
+
+/// The condition of one copy of a ledger, as found when it was loaded
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, JsonSchema, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum LedgerCopyStatus {
+    /// The copy is valid and as new as any other copy.
+    Current,
+    /// There is no copy at this path.
+    Missing,
+    /// The copy could not be read, failed its integrity check, or is not
+    /// valid JSON.
+    Corrupt { reason: String },
+    /// The copy is intact but doesn't match the ledger's type.  It may have
+    /// been written in a format that the caller knows how to convert (e.g.,
+    /// an older, unversioned type), so it's never overwritten.
+    Incompatible { reason: String },
+    /// The copy is valid but older than another copy.
+    Stale,
+    /// The copy was written by newer software using a schema version that
+    /// this software doesn't understand.  Such copies are never overwritten.
+    Unsupported { schema_version: u32 },
+}
+
+impl LedgerCopyStatus {
+    /// Returns true if this copy should be replaced with the best copy.
+    pub(crate) fn needs_repair(&self) -> bool {
+        match self {
+            LedgerCopyStatus::Missing
+            | LedgerCopyStatus::Corrupt { .. }
+            | LedgerCopyStatus::Stale => true,
+            LedgerCopyStatus::Current
+            | LedgerCopyStatus::Incompatible { .. }
+            | LedgerCopyStatus::Unsupported { .. } => false,
+        }
+    }
+}
+
+/// The result of trying to repair a copy of a ledger
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, JsonSchema, Serialize)]
+#[serde(tag = "result", rename_all = "snake_case")]
+pub enum LedgerRepairOutcome {
+    /// The copy was overwritten with the best copy.
+    Repaired,
+    /// Writing the best copy failed.
+    Failed { message: String },
+}
+
+/// What was found at one of the paths of a ledger
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, JsonSchema, Serialize)]
+pub struct LedgerCopyReport {
+    #[schemars(schema_with = "path_schema")]
+    pub path: Utf8PathBuf,
+    pub status: LedgerCopyStatus,
+    /// The outcome of repairing this copy, if it needed repair and there was
+    /// a good copy to repair it from.
+    pub repair: Option<LedgerRepairOutcome>,
+}
+
+/// The condition of every copy of a ledger, as found when it was loaded
+///
+/// This is intended to be reported (e.g., in sled-agent inventory) so that
+/// damage to one copy is noticed before the others are damaged too.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, JsonSchema, Serialize)]
+pub struct LedgerReport {
+    pub copies: Vec<LedgerCopyReport>,
+}
+
+impl LedgerReport {
+    /// Returns true if every copy was current when the ledger was loaded.
+    pub fn all_current(&self) -> bool {
+        self.copies.iter().all(|c| c.status == LedgerCopyStatus::Current)
+    }
+
+    /// Returns the copies that were not current when the ledger was loaded
+    /// and are still not current (because they couldn't be repaired).
+    pub fn unresolved(&self) -> impl Iterator<Item = &LedgerCopyReport> {
+        self.copies.iter().filter(|c| {
+            c.status != LedgerCopyStatus::Current
+                && c.repair != Some(LedgerRepairOutcome::Repaired)
+        })
+    }
+}
+
+// Used for schemars to be able to be used with camino:
+// See https://github.com/camino-rs/camino/issues/91#issuecomment-2027908513
+fn path_schema(generator: &mut SchemaGenerator) -> Schema {
+    let mut schema: SchemaObject = <String>::json_schema(generator).into();
+    schema.format = Some("Utf8PathBuf".to_owned());
+    schema.into()
+}
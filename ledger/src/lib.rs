@@ -9,15 +9,30 @@
 //! devices). It tracks generation numbers to determine which copy is
 //! newest, and uses atomic writes (write-to-temp then rename) to avoid
 //! corruption.
+//!
+//! Each copy carries a checksum, and loading a ledger reports on (and
+//! repairs) copies that are missing, corrupt, or stale; see [`integrity`].
+//!
+//! Ledgered types may opt into schema versioning, which allows their format to
+//! change over time; see [`versioning`].
 
 use async_trait::async_trait;
 use atomicwrites::{AtomicFile, OverwriteBehavior};
 use camino::{Utf8Path, Utf8PathBuf};
 use serde::{Serialize, de::DeserializeOwned};
-use slog::{Logger, debug, error, info, warn};
-use slog_error_chain::SlogInlineError;
+use slog::{Logger, error, info, warn};
+use slog_error_chain::{InlineErrorChain, SlogInlineError};
 use std::io::Write;
 
+pub mod integrity;
+pub mod versioning;
+
+pub use integrity::{
+    ChecksumMismatch, LedgerCopyReport, LedgerCopyStatus, LedgerRepairOutcome,
+    LedgerReport,
+};
+pub use versioning::{MigrationError, Migrations};
+
 #[derive(thiserror::Error, Debug, SlogInlineError)]
 pub enum Error {
     #[error("Cannot serialize JSON to file {path}")]
@@ -51,6 +66,33 @@ pub enum Error {
     #[error("Not found in storage")]
     NotFound,
 
+    #[error("Ledger at {path} failed its integrity check")]
+    Checksum {
+        path: Utf8PathBuf,
+        #[source]
+        err: ChecksumMismatch,
+    },
+
+    #[error(
+        "Ledger at {path} has schema version {found}, but the newest \
+         supported version is {supported}"
+    )]
+    NewerSchemaVersion { path: Utf8PathBuf, found: u32, supported: u32 },
+
+    #[error("Cannot migrate ledger at {path} from schema version {from}")]
+    Migration {
+        path: Utf8PathBuf,
+        from: u32,
+        #[source]
+        err: MigrationError,
+    },
+
+    #[error(
+        "Refusing to overwrite ledger at {path} with schema version {found} \
+         using older schema version {ours}"
+    )]
+    DowngradeWrite { path: Utf8PathBuf, found: u32, ours: u32 },
+
     #[error(
         "Failed to write the ledger to storage (tried to access: {failed_paths:?})"
     )]
@@ -63,6 +105,18 @@ impl Error {
     }
 }
 
+/// Returns true if `err` means a copy is intact JSON that doesn't describe the
+/// type being read, rather than that the copy is damaged.
+fn is_type_mismatch(err: &Error) -> bool {
+    match err {
+        Error::JsonDeserialize { err, .. } => {
+            err.classify() == serde_json::error::Category::Data
+        }
+        Error::Migration { .. } => true,
+        _ => false,
+    }
+}
+
 // TODO: .json EXPECTORATE test?
 //
 // ... yes, but maybe not here? Seems like we gotta know the type of "T" to pull
@@ -90,32 +144,140 @@ impl<T: Ledgerable> Ledger<T> {
     ///
     /// Returns the ledger with the highest generation number if it
     /// exists, otherwise returns `None`.
+    ///
+    /// Note that this may write to `paths`: if a ledger is found, copies that
+    /// are missing, corrupt, or stale are overwritten with it.  Copies that
+    /// are intact but can't be parsed as `T` are left alone.  See
+    /// [`Ledger::new_with_report`].
     pub async fn new(log: &Logger, paths: Vec<Utf8PathBuf>) -> Option<Self> {
-        // Read the ledgers from storage
-        if let Some(ledger) = Self::read(log, &paths).await {
-            Some(Self { log: log.clone(), ledger, paths })
-        } else {
-            None
-        }
+        Self::new_with_report(log, paths).await.0
     }
 
-    async fn read(log: &Logger, paths: &Vec<Utf8PathBuf>) -> Option<T> {
+    /// Reads the ledger from any of the provided `paths`, reporting on the
+    /// condition of each copy.
+    ///
+    /// Like [`Ledger::new`], this returns the ledger with the highest
+    /// generation number if any copy could be read.  In that case, every copy
+    /// that's missing, corrupt, or older is overwritten with it.  Copies that
+    /// pass their integrity check but don't parse as `T` are reported as
+    /// [`LedgerCopyStatus::Incompatible`] and never written: callers such as
+    /// sled-agent read the same paths as several types in turn while
+    /// converting older formats.  The report describes what was found at
+    /// each path and the outcome of any repair.
+    pub async fn new_with_report(
+        log: &Logger,
+        paths: Vec<Utf8PathBuf>,
+    ) -> (Option<Self>, LedgerReport) {
         // Read all the ledgers that we can.
-        let mut ledgers = vec![];
+        let mut copies = Vec::with_capacity(paths.len());
         for path in paths.iter() {
-            match T::read_from(log, &path).await {
-                Ok(ledger) => ledgers.push(ledger),
-                Err(err) => {
-                    debug!(log, "Failed to read ledger"; "path" => %path, err)
-                }
-            }
+            copies.push(T::read_from(log, &path).await);
         }
 
-        // Return the ledger with the highest generation number.
-        let ledger = ledgers.into_iter().reduce(|prior, ledger| {
-            if ledger.is_newer_than(&prior) { ledger } else { prior }
+        // Find the ledger with the highest generation number.
+        let best = copies
+            .iter()
+            .enumerate()
+            .filter_map(|(i, copy)| {
+                copy.as_ref().ok().map(|ledger| (i, ledger))
+            })
+            .reduce(|prior, (i, ledger)| {
+                if ledger.is_newer_than(prior.1) { (i, ledger) } else { prior }
+            });
+
+        let statuses = copies
+            .iter()
+            .zip(paths.iter())
+            .map(|(copy, path)| {
+                let status = match copy {
+                    Ok(ledger) => match best {
+                        Some((_, best)) if best.is_newer_than(ledger) => {
+                            LedgerCopyStatus::Stale
+                        }
+                        _ => LedgerCopyStatus::Current,
+                    },
+                    Err(Error::NotFound) => LedgerCopyStatus::Missing,
+                    Err(Error::NewerSchemaVersion { found, .. }) => {
+                        LedgerCopyStatus::Unsupported { schema_version: *found }
+                    }
+                    Err(err) if is_type_mismatch(err) => {
+                        LedgerCopyStatus::Incompatible {
+                            reason: InlineErrorChain::new(err).to_string(),
+                        }
+                    }
+                    Err(err) => LedgerCopyStatus::Corrupt {
+                        reason: InlineErrorChain::new(err).to_string(),
+                    },
+                };
+                match copy {
+                    Ok(_) | Err(Error::NotFound) => {}
+                    Err(err @ Error::NewerSchemaVersion { .. }) => {
+                        warn!(log, "Ignoring ledger from newer software"; err)
+                    }
+                    Err(err) => {
+                        warn!(log, "Failed to read ledger"; "path" => %path, err)
+                    }
+                }
+                status
+            })
+            .collect::<Vec<_>>();
+        let best = best.map(|(i, _)| i);
+
+        let Some(best) = best else {
+            let report = LedgerReport {
+                copies: paths
+                    .into_iter()
+                    .zip(statuses)
+                    .map(|(path, status)| LedgerCopyReport {
+                        path,
+                        status,
+                        repair: None,
+                    })
+                    .collect(),
+            };
+            return (None, report);
+        };
+
+        let ledger = copies.swap_remove(best).unwrap_or_else(|_| {
+            unreachable!("best copy was read successfully")
         });
-        ledger
+        let this = Self { log: log.clone(), ledger, paths };
+
+        // Repair the copies that need it from the best one.
+        let mut report = LedgerReport { copies: Vec::new() };
+        for (path, status) in this.paths.iter().zip(statuses) {
+            let repair = if status.needs_repair() {
+                info!(
+                    log,
+                    "Repairing ledger copy";
+                    "path" => %path,
+                    "status" => ?status,
+                );
+                Some(match this.atomic_write(path).await {
+                    Ok(()) => LedgerRepairOutcome::Repaired,
+                    Err(err) => {
+                        warn!(
+                            log,
+                            "Failed to repair ledger copy";
+                            "path" => %path,
+                            &err,
+                        );
+                        LedgerRepairOutcome::Failed {
+                            message: InlineErrorChain::new(&err).to_string(),
+                        }
+                    }
+                })
+            } else {
+                None
+            };
+            report.copies.push(LedgerCopyReport {
+                path: path.clone(),
+                status,
+                repair,
+            });
+        }
+
+        (Some(this), report)
     }
 
     pub fn data(&self) -> &T {
@@ -162,9 +324,37 @@ impl<T: Ledgerable> Ledger<T> {
         // Serialize the content prior to `spawn_blocking()`; this is bad if
         // `self.ledger` is very large, but it shouldn't be! And it makes
         // ownership of the closure below simple.
-        let content = serde_json::to_vec(&self.ledger).map_err(|err| {
-            Error::JsonSerialize { path: path.to_path_buf(), err }
-        })?;
+        let content = versioning::encode(&self.ledger)
+            .and_then(integrity::seal)
+            .map_err(|err| Error::JsonSerialize {
+                path: path.to_path_buf(),
+                err,
+            })?;
+
+        // Never replace a ledger written by newer software (e.g., before a
+        // rollback): we'd lose whatever it recorded that we can't represent.
+        if let Some(ours) = T::SCHEMA_VERSION {
+            // If the existing copy fails its integrity check, we can't trust
+            // the version it claims; overwriting it is the best we can do.
+            let found = match tokio::fs::read_to_string(path).await {
+                Ok(existing) => {
+                    integrity::verify(&existing).ok().and_then(|existing| {
+                        versioning::schema_version_of(&existing)
+                    })
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+                Err(err) => return Err(Error::io_path(path, err)),
+            };
+            if let Some(found) = found
+                && found > ours
+            {
+                return Err(Error::DowngradeWrite {
+                    path: path.to_path_buf(),
+                    found,
+                    ours,
+                });
+            }
+        }
 
         let result = {
             let path = path.to_path_buf();
@@ -195,18 +385,40 @@ pub trait Ledgerable: DeserializeOwned + Serialize + Send + Sync {
     /// Increments the gneration number.
     fn generation_bump(&mut self);
 
+    /// The schema version of the current format of `Self`.
+    ///
+    /// Ledgers are unversioned by default.  If this is set, ledgers are
+    /// written along with this version, and ledgers written with older
+    /// versions are migrated using [`Ledgerable::migrations`] when read.
+    const SCHEMA_VERSION: Option<u32> = None;
+
+    /// Returns the migrations from each older schema version of `Self`.
+    ///
+    /// Only used if [`Ledgerable::SCHEMA_VERSION`] is set.
+    fn migrations() -> Migrations {
+        Migrations::new()
+    }
+
     /// Reads from `path` as a json-serialized version of `Self`.
     async fn read_from(log: &Logger, path: &Utf8Path) -> Result<Self, Error> {
         if path.exists() {
             info!(log, "Reading ledger from {}", path);
-            <Self as Ledgerable>::deserialize(
-                &tokio::fs::read_to_string(&path)
-                    .await
-                    .map_err(|err| Error::io_path(&path, err))?,
-            )
-            .map_err(|err| Error::JsonDeserialize {
-                path: path.to_path_buf(),
-                err,
+            let contents = tokio::fs::read_to_string(&path)
+                .await
+                .map_err(|err| Error::io_path(&path, err))?;
+            let path = path.to_path_buf();
+            let contents = integrity::verify(&contents)
+                .map_err(|err| Error::Checksum { path: path.clone(), err })?;
+            versioning::decode(&contents).map_err(|err| match err {
+                versioning::DecodeError::Json(err) => {
+                    Error::JsonDeserialize { path, err }
+                }
+                versioning::DecodeError::NewerVersion { found, supported } => {
+                    Error::NewerSchemaVersion { path, found, supported }
+                }
+                versioning::DecodeError::Migration { from, err } => {
+                    Error::Migration { path, from, err }
+                }
             })
         } else {
             info!(log, "No ledger in {path}");
@@ -263,6 +475,55 @@ mod test {
         }
     }
 
+    // A ledger type that has been through two schema changes: version 1 added
+    // `labels`, and version 2 renamed `contents` to `description`.
+    #[derive(Serialize, serde::Deserialize, Default, Eq, PartialEq, Debug)]
+    struct VersionedData {
+        generation: u64,
+        description: String,
+        labels: Vec<String>,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct VersionedDataV1 {
+        generation: u64,
+        contents: String,
+        labels: Vec<String>,
+    }
+
+    impl From<VersionedDataV1> for VersionedData {
+        fn from(v1: VersionedDataV1) -> Self {
+            Self {
+                generation: v1.generation,
+                description: v1.contents,
+                labels: v1.labels,
+            }
+        }
+    }
+
+    impl Ledgerable for VersionedData {
+        const SCHEMA_VERSION: Option<u32> = Some(2);
+
+        fn is_newer_than(&self, other: &Self) -> bool {
+            self.generation > other.generation
+        }
+
+        fn generation_bump(&mut self) {
+            self.generation = self.generation + 1;
+        }
+
+        fn migrations() -> Migrations {
+            Migrations::new()
+                .register(versioning::UNVERSIONED, |mut value| {
+                    if let Some(obj) = value.as_object_mut() {
+                        obj.insert("labels".to_string(), serde_json::json!([]));
+                    }
+                    Ok(value)
+                })
+                .register_typed::<VersionedDataV1, VersionedData>(1)
+        }
+    }
+
     #[tokio::test]
     async fn test_create_default_ledger() {
         let logctx = test_setup_log("create_default_ledger");
@@ -433,4 +694,312 @@ mod test {
 
         logctx.cleanup_successful();
     }
+
+    #[tokio::test]
+    async fn test_versioned_ledger_migrates_unversioned() {
+        let logctx = test_setup_log("versioned_ledger_migrates_unversioned");
+        let log = &logctx.log;
+
+        let config_dir = camino_tempfile::Utf8TempDir::new().unwrap();
+        let config_path = config_dir.path().join("ledger.json");
+
+        // Write a ledger from before the type was versioned.
+        let mut ledger =
+            Ledger::new_with(&log, vec![config_path.clone()], Data::default());
+        ledger.data_mut().contents = "old contents".to_string();
+        ledger.commit().await.expect("Failed to write ledger");
+        drop(ledger);
+
+        // Read it as the versioned type, which migrates it.
+        let mut ledger =
+            Ledger::<VersionedData>::new(&log, vec![config_path.clone()])
+                .await
+                .expect("Failed to read ledger");
+        assert_eq!(
+            ledger.data(),
+            &VersionedData {
+                generation: 1,
+                description: "old contents".to_string(),
+                labels: vec![],
+            }
+        );
+
+        // Writing it back records the current schema version.
+        ledger.commit().await.expect("Failed to write ledger");
+        drop(ledger);
+        let contents = std::fs::read_to_string(&config_path).unwrap();
+        assert_eq!(versioning::schema_version_of(&contents), Some(2));
+
+        let ledger = Ledger::<VersionedData>::new(&log, vec![config_path])
+            .await
+            .expect("Failed to read ledger");
+        assert_eq!(ledger.data().generation, 2);
+        assert_eq!(ledger.data().description, "old contents");
+
+        logctx.cleanup_successful();
+    }
+
+    #[tokio::test]
+    async fn test_versioned_ledger_refuses_downgrade_write() {
+        let logctx = test_setup_log("versioned_ledger_refuses_downgrade_write");
+        let log = &logctx.log;
+
+        let config_dir = camino_tempfile::Utf8TempDir::new().unwrap();
+        let config_path = config_dir.path().join("ledger.json");
+
+        // Write a ledger as though by newer software.
+        let newer = r#"{"schema_version":3,"data":{"generation":7}}"#;
+        std::fs::write(&config_path, newer).unwrap();
+
+        // We can't read it...
+        let err = VersionedData::read_from(&log, &config_path)
+            .await
+            .expect_err("read ledger from newer software");
+        assert!(
+            matches!(
+                err,
+                Error::NewerSchemaVersion { found: 3, supported: 2, .. }
+            ),
+            "Unexpected error: {}",
+            InlineErrorChain::new(&err)
+        );
+        assert!(
+            Ledger::<VersionedData>::new(&log, vec![config_path.clone()])
+                .await
+                .is_none()
+        );
+
+        // ... and we must not overwrite it.
+        let mut ledger = Ledger::new_with(
+            &log,
+            vec![config_path.clone()],
+            VersionedData::default(),
+        );
+        let err = ledger.commit().await.unwrap_err();
+        let Error::FailedToWrite { failed_paths } = &err else {
+            panic!("Unexpected error: {}", InlineErrorChain::new(&err));
+        };
+        assert!(
+            matches!(
+                failed_paths.as_slice(),
+                [(_, Error::DowngradeWrite { found: 3, ours: 2, .. })]
+            ),
+            "Unexpected error: {}",
+            InlineErrorChain::new(&err)
+        );
+        assert_eq!(std::fs::read_to_string(&config_path).unwrap(), newer);
+
+        logctx.cleanup_successful();
+    }
+
+    #[test]
+    fn test_versioned_ledger_fixtures_migrate() {
+        let fixtures = versioning::assert_fixtures_migrate::<VersionedData>(
+            "test-data/versioned-data".into(),
+        );
+        assert_eq!(fixtures.len(), 3);
+        for (name, ledger) in &fixtures {
+            assert_eq!(ledger.generation, 4, "{name}");
+            assert_eq!(ledger.description, "hello", "{name}");
+        }
+        assert_eq!(fixtures["v0.json"].labels, Vec::<String>::new());
+        assert_eq!(fixtures["v1.json"].labels, ["a"]);
+        assert_eq!(fixtures["v2.json"].labels, ["a", "b"]);
+    }
+
+    fn statuses(report: &LedgerReport) -> Vec<LedgerCopyStatus> {
+        report.copies.iter().map(|c| c.status.clone()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_corrupt_copy_is_detected_and_repaired() {
+        let logctx = test_setup_log("corrupt_copy_is_detected_and_repaired");
+        let log = &logctx.log;
+
+        let config_dirs = [
+            camino_tempfile::Utf8TempDir::new().unwrap(),
+            camino_tempfile::Utf8TempDir::new().unwrap(),
+        ];
+        let config_paths = config_dirs
+            .iter()
+            .map(|d| d.path().join("ledger.json"))
+            .collect::<Vec<_>>();
+
+        let mut ledger =
+            Ledger::new_with(&log, config_paths.clone(), Data::default());
+        ledger.data_mut().contents = "good contents".to_string();
+        ledger.commit().await.expect("Failed to write ledger");
+        drop(ledger);
+
+        // Flip some bits in one copy without breaking the JSON.
+        let good = std::fs::read_to_string(&config_paths[1]).unwrap();
+        let bad = good.replace("good contents", "good cOntents");
+        assert_ne!(good, bad);
+        std::fs::write(&config_paths[1], &bad).unwrap();
+        let err = Data::read_from(&log, &config_paths[1])
+            .await
+            .expect_err("read corrupt ledger");
+        assert!(
+            matches!(err, Error::Checksum { .. }),
+            "Unexpected error: {}",
+            InlineErrorChain::new(&err)
+        );
+
+        // Loading the ledger uses the good copy and repairs the bad one.
+        let (ledger, report) =
+            Ledger::<Data>::new_with_report(&log, config_paths.clone()).await;
+        let ledger = ledger.expect("Failed to read ledger");
+        assert_eq!(ledger.data().contents, "good contents");
+        assert!(matches!(
+            statuses(&report).as_slice(),
+            [LedgerCopyStatus::Current, LedgerCopyStatus::Corrupt { .. }]
+        ));
+        assert_eq!(report.copies[0].repair, None);
+        assert_eq!(
+            report.copies[1].repair,
+            Some(LedgerRepairOutcome::Repaired)
+        );
+        assert!(!report.all_current());
+        assert_eq!(report.unresolved().count(), 0);
+        assert_eq!(std::fs::read_to_string(&config_paths[1]).unwrap(), good);
+
+        let (_, report) =
+            Ledger::<Data>::new_with_report(&log, config_paths).await;
+        assert!(report.all_current());
+
+        logctx.cleanup_successful();
+    }
+
+    #[tokio::test]
+    async fn test_stale_and_missing_copies_are_repaired() {
+        let logctx = test_setup_log("stale_and_missing_copies_are_repaired");
+        let log = &logctx.log;
+
+        let config_dir = camino_tempfile::Utf8TempDir::new().unwrap();
+        let config_paths = ["a.json", "b.json", "c.json"]
+            .into_iter()
+            .map(|name| config_dir.path().join(name))
+            .collect::<Vec<_>>();
+
+        // Write generation 1 to the first two paths and generation 2 to only
+        // the first.
+        let mut ledger =
+            Ledger::new_with(&log, config_paths[..2].to_vec(), Data::default());
+        ledger.commit().await.expect("Failed to write ledger");
+        drop(ledger);
+        let mut ledger = Ledger::<Data>::new(&log, config_paths[..1].to_vec())
+            .await
+            .expect("Failed to read ledger");
+        ledger.data_mut().contents = "newest".to_string();
+        ledger.commit().await.expect("Failed to write ledger");
+        drop(ledger);
+
+        let (ledger, report) =
+            Ledger::<Data>::new_with_report(&log, config_paths.clone()).await;
+        let ledger = ledger.expect("Failed to read ledger");
+        assert_eq!(ledger.data().generation, 2);
+        assert_eq!(
+            statuses(&report),
+            [
+                LedgerCopyStatus::Current,
+                LedgerCopyStatus::Stale,
+                LedgerCopyStatus::Missing,
+            ]
+        );
+        assert_eq!(
+            report.copies.iter().map(|c| c.repair.clone()).collect::<Vec<_>>(),
+            [
+                None,
+                Some(LedgerRepairOutcome::Repaired),
+                Some(LedgerRepairOutcome::Repaired),
+            ]
+        );
+
+        // Every copy now has the newest contents.
+        for path in &config_paths {
+            let copy = Data::read_from(&log, path).await.unwrap();
+            assert_eq!(copy, Data { generation: 2, contents: "newest".into() });
+        }
+
+        logctx.cleanup_successful();
+    }
+
+    #[tokio::test]
+    async fn test_report_without_good_copy() {
+        let logctx = test_setup_log("report_without_good_copy");
+        let log = &logctx.log;
+
+        let config_dir = camino_tempfile::Utf8TempDir::new().unwrap();
+        let config_paths = vec![
+            config_dir.path().join("garbage.json"),
+            config_dir.path().join("missing.json"),
+            config_dir.path().join("legacy.json"),
+        ];
+        std::fs::write(&config_paths[0], "not json").unwrap();
+
+        let (ledger, report) =
+            Ledger::<Data>::new_with_report(&log, config_paths[..2].to_vec())
+                .await;
+        assert!(ledger.is_none());
+        assert!(matches!(
+            statuses(&report).as_slice(),
+            [LedgerCopyStatus::Corrupt { .. }, LedgerCopyStatus::Missing]
+        ));
+        assert_eq!(report.unresolved().count(), 2);
+        assert!(report.copies.iter().all(|c| c.repair.is_none()));
+        assert!(!config_paths[1].exists());
+
+        // A copy written before checksums existed is still accepted, and the
+        // bad copies are repaired from it.
+        std::fs::write(
+            &config_paths[2],
+            r#"{"generation":3,"contents":"legacy"}"#,
+        )
+        .unwrap();
+        let (ledger, report) =
+            Ledger::<Data>::new_with_report(&log, config_paths.clone()).await;
+        assert_eq!(ledger.expect("Failed to read ledger").data().generation, 3);
+        assert_eq!(report.unresolved().count(), 0);
+        for path in &config_paths {
+            let copy = Data::read_from(&log, path).await.unwrap();
+            assert_eq!(copy.contents, "legacy");
+        }
+
+        logctx.cleanup_successful();
+    }
+
+    #[tokio::test]
+    async fn test_incompatible_copy_is_not_overwritten() {
+        let logctx = test_setup_log("incompatible_copy_is_not_overwritten");
+        let log = &logctx.log;
+
+        let config_dir = camino_tempfile::Utf8TempDir::new().unwrap();
+        let config_paths = vec![
+            config_dir.path().join("a.json"),
+            config_dir.path().join("b.json"),
+        ];
+
+        let mut ledger =
+            Ledger::new_with(&log, config_paths[..1].to_vec(), Data::default());
+        ledger.commit().await.expect("Failed to write ledger");
+        drop(ledger);
+
+        // Valid JSON that isn't a `Data`, e.g., an older format that our
+        // caller would try to read as a different type.
+        let other = r#"{"generation":7,"description":"other format"}"#;
+        std::fs::write(&config_paths[1], other).unwrap();
+
+        let (ledger, report) =
+            Ledger::<Data>::new_with_report(&log, config_paths.clone()).await;
+        assert_eq!(ledger.expect("Failed to read ledger").data().generation, 1);
+        assert!(matches!(
+            statuses(&report).as_slice(),
+            [LedgerCopyStatus::Current, LedgerCopyStatus::Incompatible { .. }]
+        ));
+        assert!(report.copies.iter().all(|c| c.repair.is_none()));
+        assert_eq!(report.unresolved().count(), 1);
+        assert_eq!(std::fs::read_to_string(&config_paths[1]).unwrap(), other);
+
+        logctx.cleanup_successful();
+    }
 }
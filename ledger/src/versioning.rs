@@ -0,0 +1,292 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Schema versioning for ledgers.
+//!
+//! A [`Ledgerable`] type that sets [`Ledgerable::SCHEMA_VERSION`] is stored
+//! on disk wrapped in an envelope recording the version it was written with:
+//!
+//! ```json
+//! { "schema_version": 2, "data": { ... } }
+//! ```
+//!
+//! When a ledger written with an older version is read, the migrations
+//! returned by [`Ledgerable::migrations`] are applied one at a time to bring
+//! it up to the current version.  Ledgers written before a type adopted
+//! versioning have no envelope; these are treated as version
+//! [`UNVERSIONED`], so adopting versioning only requires registering a
+//! migration from that version.
+//!
+//! Ledgers written with a *newer* version than the running software knows
+//! about can't be read, and [`crate::Ledger::commit`] refuses to overwrite
+//! them (see [`crate::Error::DowngradeWrite`]).
+
+use crate::Ledgerable;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// The schema version assigned to ledgers without a version envelope
+pub const UNVERSIONED: u32 = 0;
+
+#[derive(thiserror::Error, Debug)]
+pub enum MigrationError {
+    #[error("Cannot interpret ledger as schema version {version}")]
+    Deserialize {
+        version: u32,
+        #[source]
+        err: serde_json::Error,
+    },
+
+    #[error("Cannot serialize ledger as schema version {version}")]
+    Serialize {
+        version: u32,
+        #[source]
+        err: serde_json::Error,
+    },
+
+    #[error("No migration registered from schema version {version}")]
+    MissingMigration { version: u32 },
+
+    #[error("Cannot migrate ledger from schema version {version}: {message}")]
+    Failed { version: u32, message: String },
+}
+
+type MigrationFn = Box<
+    dyn Fn(serde_json::Value) -> Result<serde_json::Value, MigrationError>
+        + Send
+        + Sync,
+>;
+
+/// The set of migrations that bring older versions of a ledger up to date.
+///
+/// Each migration converts the JSON representation of the ledger's data from
+/// one schema version to the next.
+#[derive(Default)]
+pub struct Migrations {
+    steps: BTreeMap<u32, MigrationFn>,
+}
+
+impl fmt::Debug for Migrations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Migrations")
+            .field("from_versions", &self.steps.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl Migrations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `migration` to convert the JSON representation of the data
+    /// from schema version `from` to version `from + 1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a migration from `from` has already been registered.
+    pub fn register<F>(mut self, from: u32, migration: F) -> Self
+    where
+        F: Fn(serde_json::Value) -> Result<serde_json::Value, MigrationError>
+            + Send
+            + Sync
+            + 'static,
+    {
+        let prev = self.steps.insert(from, Box::new(migration));
+        assert!(
+            prev.is_none(),
+            "registered two migrations from schema version {from}"
+        );
+        self
+    }
+
+    /// Registers a migration from schema version `from` to `from + 1` that
+    /// deserializes the data as `Old` and converts it to `New`.
+    ///
+    /// This is convenient when the older version's type is still around
+    /// (e.g., in a `versions` crate).
+    pub fn register_typed<Old, New>(self, from: u32) -> Self
+    where
+        Old: DeserializeOwned,
+        New: Serialize + TryFrom<Old>,
+        <New as TryFrom<Old>>::Error: fmt::Display,
+    {
+        self.register(from, move |value| {
+            let old: Old = serde_json::from_value(value).map_err(|err| {
+                MigrationError::Deserialize { version: from, err }
+            })?;
+            let new =
+                New::try_from(old).map_err(|err| MigrationError::Failed {
+                    version: from,
+                    message: err.to_string(),
+                })?;
+            serde_json::to_value(new).map_err(|err| MigrationError::Serialize {
+                version: from + 1,
+                err,
+            })
+        })
+    }
+
+    /// Returns the schema versions that migrations have been registered from,
+    /// oldest first.
+    pub fn from_versions(&self) -> impl Iterator<Item = u32> + '_ {
+        self.steps.keys().copied()
+    }
+
+    /// Applies migrations to `value` until it's at schema version `to`.
+    fn migrate(
+        &self,
+        mut value: serde_json::Value,
+        from: u32,
+        to: u32,
+    ) -> Result<serde_json::Value, MigrationError> {
+        for version in from..to {
+            let step = self
+                .steps
+                .get(&version)
+                .ok_or(MigrationError::MissingMigration { version })?;
+            value = step(value)?;
+        }
+        Ok(value)
+    }
+}
+
+#[derive(Serialize)]
+struct EnvelopeRef<'a, T> {
+    schema_version: u32,
+    data: &'a T,
+}
+
+/// Why a ledger couldn't be decoded.
+pub(crate) enum DecodeError {
+    Json(serde_json::Error),
+    NewerVersion { found: u32, supported: u32 },
+    Migration { from: u32, err: MigrationError },
+}
+
+/// Returns the schema version of `value` and the data within it.
+fn unwrap_envelope(mut value: serde_json::Value) -> (u32, serde_json::Value) {
+    // Anything that isn't exactly an envelope is the unversioned data itself.
+    if let serde_json::Value::Object(obj) = &mut value
+        && obj.len() == 2
+        && let Some(version) = obj
+            .get("schema_version")
+            .and_then(|v| v.as_u64())
+            .and_then(|v| u32::try_from(v).ok())
+        && let Some(data) = obj.remove("data")
+    {
+        return (version, data);
+    }
+    (UNVERSIONED, value)
+}
+
+/// Deserializes `s` as written by [`encode`], migrating it if necessary.
+pub(crate) fn decode<T: Ledgerable>(s: &str) -> Result<T, DecodeError> {
+    let Some(current) = T::SCHEMA_VERSION else {
+        return <T as Ledgerable>::deserialize(s).map_err(DecodeError::Json);
+    };
+
+    let value: serde_json::Value =
+        serde_json::from_str(s).map_err(DecodeError::Json)?;
+    let (version, data) = unwrap_envelope(value);
+    if version > current {
+        return Err(DecodeError::NewerVersion {
+            found: version,
+            supported: current,
+        });
+    }
+    let data = T::migrations()
+        .migrate(data, version, current)
+        .map_err(|err| DecodeError::Migration { from: version, err })?;
+    serde_json::from_value(data).map_err(DecodeError::Json)
+}
+
+/// Converts `ledger` to JSON, wrapping it in an envelope if `T` is versioned.
+pub(crate) fn encode<T: Ledgerable>(
+    ledger: &T,
+) -> Result<serde_json::Value, serde_json::Error> {
+    match T::SCHEMA_VERSION {
+        Some(schema_version) => {
+            serde_json::to_value(EnvelopeRef { schema_version, data: ledger })
+        }
+        None => serde_json::to_value(ledger),
+    }
+}
+
+/// Returns the schema version of the ledger serialized in `s`, if it can be
+/// determined.
+pub(crate) fn schema_version_of(s: &str) -> Option<u32> {
+    serde_json::from_str::<serde_json::Value>(s)
+        .ok()
+        .map(|value| unwrap_envelope(value).0)
+}
+
+/// Checks that every ledger in a directory of fixtures can still be read.
+///
+/// Each `*.json` file in `dir` should be a ledger of `T` as it was written by
+/// some earlier (or the current) version of the software.  This panics if any
+/// of them fail to decode, or if there's no fixture for the current schema
+/// version or for any version that a migration is registered from.  Adding a
+/// fixture each time the schema changes ensures that every historical version
+/// keeps migrating as the chain of migrations grows.
+///
+/// Returns the decoded fixtures, keyed by file name, so that callers can
+/// check their contents.
+#[cfg(any(test, feature = "testing"))]
+pub fn assert_fixtures_migrate<T: Ledgerable>(
+    dir: &camino::Utf8Path,
+) -> BTreeMap<String, T> {
+    let current = T::SCHEMA_VERSION.expect("ledger type has no schema version");
+
+    let mut decoded = BTreeMap::new();
+    let mut versions_seen = std::collections::BTreeSet::new();
+    let entries = dir
+        .read_dir_utf8()
+        .unwrap_or_else(|err| panic!("failed to read {dir}: {err}"));
+    for entry in entries {
+        let entry =
+            entry.unwrap_or_else(|err| panic!("failed to read {dir}: {err}"));
+        let path = entry.path();
+        if path.extension() != Some("json") {
+            continue;
+        }
+        let contents = std::fs::read_to_string(path)
+            .unwrap_or_else(|err| panic!("failed to read {path}: {err}"));
+        let contents = crate::integrity::verify(&contents)
+            .unwrap_or_else(|err| panic!("fixture {path} is corrupt: {err}"));
+        let version = schema_version_of(&contents)
+            .unwrap_or_else(|| panic!("fixture {path} is not valid JSON"));
+        let ledger = match decode::<T>(&contents) {
+            Ok(ledger) => ledger,
+            Err(DecodeError::Json(err)) => {
+                panic!("failed to deserialize fixture {path}: {err}")
+            }
+            Err(DecodeError::NewerVersion { found, supported }) => panic!(
+                "fixture {path} has schema version {found}, \
+                 but the newest supported version is {supported}"
+            ),
+            Err(DecodeError::Migration { from, err }) => panic!(
+                "failed to migrate fixture {path} from schema version \
+                 {from}: {}",
+                slog_error_chain::InlineErrorChain::new(&err)
+            ),
+        };
+        versions_seen.insert(version);
+        decoded.insert(entry.file_name().to_string(), ledger);
+    }
+
+    let missing: Vec<_> = T::migrations()
+        .from_versions()
+        .chain(std::iter::once(current))
+        .filter(|v| !versions_seen.contains(v))
+        .collect();
+    assert!(
+        missing.is_empty(),
+        "{dir} has no fixtures for schema version(s) {missing:?}"
+    );
+
+    decoded
+}
@@ -442,6 +442,20 @@ pub struct BackgroundTaskConfig {
     pub audit_log_timeout_incomplete: AuditLogTimeoutIncompleteConfig,
     /// configuration for audit log cleanup (retention) task
     pub audit_log_cleanup: AuditLogCleanupConfig,
+    /// configuration for audit log export (sink delivery) task
+    pub audit_log_export: AuditLogExportConfig,
+    /// configuration for sled evacuator task
+    pub sled_evacuator: SledEvacuatorConfig,
+    /// configuration for load balancer manager task
+    pub load_balancer_manager: LoadBalancerManagerConfig,
+    /// configuration for VPC private DNS task
+    pub vpc_dns: VpcDnsConfig,
+    /// configuration for ACME certificate issuance task
+    pub acme_certificates: AcmeCertificatesConfig,
+    /// configuration for certificate expiry monitoring task
+    pub certificate_expiry: CertificateExpiryConfig,
+    /// configuration for DNSSEC key rollover task
+    pub dnssec_keys: DnssecKeysConfig,
     /// configuration for populate switch ports task
     pub populate_switch_ports: PopulateSwitchPortsConfig,
 }
@@ -490,6 +504,190 @@ pub struct AuditLogCleanupConfig {
     pub max_deleted_per_activation: u32,
 }
 
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct AuditLogExportConfig {
+    /// period (in seconds) for periodic activations of this task
+    #[serde_as(as = "DurationSeconds<u64>")]
+    pub period_secs: Duration,
+
+    /// how long after an entry's completion time to wait before delivering it
+    ///
+    /// Entries are delivered in order of completion time, but an entry may
+    /// become visible some time after the completion time it records (e.g.,
+    /// due to clock skew between Nexus instances). Entries that show up
+    /// behind a sink's cursor would never be delivered, so we stay this far
+    /// behind the present.
+    #[serde_as(as = "DurationSeconds<u64>")]
+    pub settle_time_secs: Duration,
+
+    /// maximum number of entries delivered to a sink in one batch
+    pub max_entries_per_batch: NonZeroU32,
+}
+
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SledEvacuatorConfig {
+    /// period (in seconds) for periodic activations of this task
+    #[serde_as(as = "DurationSeconds<u64>")]
+    pub period_secs: Duration,
+
+    /// maximum number of instances migrating off of each evacuated sled at
+    /// once
+    pub max_concurrent_migrations: NonZeroU32,
+}
+
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct LoadBalancerManagerConfig {
+    /// period (in seconds) for periodic activations of this task, which is
+    /// also the interval between health checks of each backend
+    #[serde_as(as = "DurationSeconds<u64>")]
+    pub period_secs: Duration,
+}
+
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct VpcDnsConfig {
+    /// period (in seconds) for periodic activations of the background task
+    /// that computes VPCs' private DNS zones and propagates them
+    #[serde_as(as = "DurationSeconds<u64>")]
+    pub period_secs: Duration,
+}
+
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct AcmeCertificatesConfig {
+    /// period (in seconds) for periodic activations of the background task
+    /// that obtains and renews Silos' TLS certificates from an ACME server
+    #[serde_as(as = "DurationSeconds<u64>")]
+    pub period_secs: Duration,
+
+    /// URL of the ACME server's directory
+    ///
+    /// If this is not set, Nexus does not obtain any certificates itself, and
+    /// operators must upload them.
+    #[serde(default)]
+    pub directory_url: Option<String>,
+
+    /// contact URLs (e.g., `mailto:` URLs) provided to the ACME server when
+    /// Nexus registers its account
+    #[serde(default)]
+    pub contact: Vec<String>,
+
+    /// how many days before a certificate expires Nexus obtains a new one
+    #[serde(default = "AcmeCertificatesConfig::default_renew_before_days")]
+    pub renew_before_days: u32,
+
+    /// how Nexus proves to the ACME server that it controls Silos' DNS names
+    #[serde(default)]
+    pub challenge: AcmeChallengeType,
+}
+
+impl AcmeCertificatesConfig {
+    const fn default_renew_before_days() -> u32 {
+        30
+    }
+}
+
+/// Kind of ACME challenge with which Nexus proves control of a DNS name
+#[derive(
+    Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize,
+)]
+pub enum AcmeChallengeType {
+    /// serve the challenge from the external API over plain HTTP on port 80
+    #[default]
+    #[serde(rename = "http-01")]
+    Http01,
+    /// publish the challenge as a TXT record in the external DNS zone
+    ///
+    /// This only works if the zone is delegated to the rack's external DNS
+    /// servers, but doesn't need the external API to be reachable on port 80.
+    #[serde(rename = "dns-01")]
+    Dns01,
+}
+
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct CertificateExpiryConfig {
+    /// period (in seconds) for periodic activations of the background task
+    /// that checks when stored certificates expire
+    #[serde_as(as = "DurationSeconds<u64>")]
+    pub period_secs: Duration,
+
+    /// how many days before a certificate expires to publish a
+    /// `certificate.expiring` alert about it
+    ///
+    /// An alert is published as each of these thresholds is crossed.
+    #[serde(default = "CertificateExpiryConfig::default_alert_before_days")]
+    pub alert_before_days: Vec<u32>,
+}
+
+impl CertificateExpiryConfig {
+    fn default_alert_before_days() -> Vec<u32> {
+        vec![30, 7, 1]
+    }
+}
+
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct DnssecKeysConfig {
+    /// period (in seconds) for periodic activations of the background task
+    /// that rolls over the external DNS zones' DNSSEC keys and propagates them
+    #[serde_as(as = "DurationSeconds<u64>")]
+    pub period_secs: Duration,
+
+    /// whether the external DNS zones are signed
+    ///
+    /// Before enabling this, operators must be prepared to register each
+    /// zone's DS record with its parent zone.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// how many days a zone-signing key signs its zone before it is replaced
+    #[serde(default = "DnssecKeysConfig::default_zsk_lifetime_days")]
+    pub zsk_lifetime_days: u32,
+
+    /// how many days a zone-signing key is published before it signs the
+    /// zone, and after it stops
+    ///
+    /// This must exceed the TTL of the zone's DNSKEY records, so that
+    /// resolvers have the new key before seeing its signatures.
+    #[serde(default = "DnssecKeysConfig::default_zsk_overlap_days")]
+    pub zsk_overlap_days: u32,
+
+    /// how many days a key-signing key signs its zone's keys before it is
+    /// replaced
+    #[serde(default = "DnssecKeysConfig::default_ksk_lifetime_days")]
+    pub ksk_lifetime_days: u32,
+
+    /// how many days a key-signing key is published before it replaces the
+    /// previous one, and after it does
+    ///
+    /// Operators must register the new key's DS record with the parent zone
+    /// within this time.
+    #[serde(default = "DnssecKeysConfig::default_ksk_overlap_days")]
+    pub ksk_overlap_days: u32,
+}
+
+impl DnssecKeysConfig {
+    const fn default_zsk_lifetime_days() -> u32 {
+        90
+    }
+
+    const fn default_zsk_overlap_days() -> u32 {
+        7
+    }
+
+    const fn default_ksk_lifetime_days() -> u32 {
+        365
+    }
+
+    const fn default_ksk_overlap_days() -> u32 {
+        30
+    }
+}
+
 #[serde_as]
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct PopulateSwitchPortsConfig {
@@ -1044,6 +1242,42 @@ pub struct MulticastConfig {
     pub enabled: bool,
 }
 
+/// Configuration for rate limiting of the external API
+///
+/// Each authenticated principal (user, service account, or SCIM client) gets
+/// its own token bucket that holds up to `burst` requests and refills at
+/// `requests_per_second`.  Silos may override both values.  Unauthenticated
+/// requests, and requests whose credentials fail to authenticate, are limited
+/// by client IP address using the separate `unauthenticated_*` values.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct RateLimitConfig {
+    /// Whether requests are rate limited at all
+    pub enabled: bool,
+    /// Default sustained request rate for each authenticated principal
+    pub requests_per_second: NonZeroU32,
+    /// Default number of requests an authenticated principal may make in a
+    /// burst
+    pub burst: NonZeroU32,
+    /// Sustained request rate for each client IP making unauthenticated
+    /// requests
+    pub unauthenticated_requests_per_second: NonZeroU32,
+    /// Number of unauthenticated requests a client IP may make in a burst
+    pub unauthenticated_burst: NonZeroU32,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            requests_per_second: NonZeroU32::new(100).unwrap(),
+            burst: NonZeroU32::new(200).unwrap(),
+            unauthenticated_requests_per_second: NonZeroU32::new(10).unwrap(),
+            unauthenticated_burst: NonZeroU32::new(20).unwrap(),
+        }
+    }
+}
+
 #[serde_as]
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct ProbeDistributorConfig {
@@ -1101,6 +1335,9 @@ pub struct PackageConfig {
     /// Multicast feature configuration
     #[serde(default)]
     pub multicast: MulticastConfig,
+    /// External API rate limiting configuration
+    #[serde(default)]
+    pub rate_limit: RateLimitConfig,
     /// Default Crucible region allocation strategy
     pub default_region_allocation_strategy: RegionAllocationStrategy,
 }
@@ -1350,6 +1587,23 @@ mod test {
             audit_log_cleanup.period_secs = 600
             audit_log_cleanup.retention_days = 90
             audit_log_cleanup.max_deleted_per_activation = 10000
+            audit_log_export.period_secs = 30
+            audit_log_export.settle_time_secs = 10
+            audit_log_export.max_entries_per_batch = 100
+            sled_evacuator.period_secs = 30
+            sled_evacuator.max_concurrent_migrations = 4
+            load_balancer_manager.period_secs = 10
+            vpc_dns.period_secs = 30
+            acme_certificates.period_secs = 3600
+            acme_certificates.directory_url = "https://acme.example.com/dir"
+            acme_certificates.contact = [ "mailto:ops@example.com" ]
+            acme_certificates.renew_before_days = 20
+            acme_certificates.challenge = "dns-01"
+            certificate_expiry.period_secs = 3600
+            certificate_expiry.alert_before_days = [ 14, 2 ]
+            dnssec_keys.period_secs = 3600
+            dnssec_keys.enabled = true
+            dnssec_keys.zsk_lifetime_days = 30
             populate_switch_ports.period_secs = 31
             [default_region_allocation_strategy]
             type = "random"
@@ -1631,11 +1885,52 @@ mod test {
                             retention_days: NonZeroU32::new(90).unwrap(),
                             max_deleted_per_activation: 10_000,
                         },
+                        audit_log_export: AuditLogExportConfig {
+                            period_secs: Duration::from_secs(30),
+                            settle_time_secs: Duration::from_secs(10),
+                            max_entries_per_batch: NonZeroU32::new(100)
+                                .unwrap(),
+                        },
+                        sled_evacuator: SledEvacuatorConfig {
+                            period_secs: Duration::from_secs(30),
+                            max_concurrent_migrations: NonZeroU32::new(4)
+                                .unwrap(),
+                        },
+                        load_balancer_manager: LoadBalancerManagerConfig {
+                            period_secs: Duration::from_secs(10),
+                        },
+                        vpc_dns: VpcDnsConfig {
+                            period_secs: Duration::from_secs(30),
+                        },
+                        acme_certificates: AcmeCertificatesConfig {
+                            period_secs: Duration::from_secs(3600),
+                            directory_url: Some(String::from(
+                                "https://acme.example.com/dir"
+                            )),
+                            contact: vec![String::from(
+                                "mailto:ops@example.com"
+                            )],
+                            renew_before_days: 20,
+                            challenge: AcmeChallengeType::Dns01,
+                        },
+                        certificate_expiry: CertificateExpiryConfig {
+                            period_secs: Duration::from_secs(3600),
+                            alert_before_days: vec![14, 2],
+                        },
+                        dnssec_keys: DnssecKeysConfig {
+                            period_secs: Duration::from_secs(3600),
+                            enabled: true,
+                            zsk_lifetime_days: 30,
+                            zsk_overlap_days: 7,
+                            ksk_lifetime_days: 365,
+                            ksk_overlap_days: 30,
+                        },
                         populate_switch_ports: PopulateSwitchPortsConfig {
                             period_secs: Duration::from_secs(31),
                         },
                     },
                     multicast: MulticastConfig { enabled: false },
+                    rate_limit: RateLimitConfig::default(),
                     default_region_allocation_strategy:
                         crate::nexus_config::RegionAllocationStrategy::Random {
                             seed: Some(0)
@@ -1749,6 +2044,16 @@ mod test {
             audit_log_cleanup.period_secs = 600
             audit_log_cleanup.retention_days = 90
             audit_log_cleanup.max_deleted_per_activation = 10000
+            audit_log_export.period_secs = 30
+            audit_log_export.settle_time_secs = 10
+            audit_log_export.max_entries_per_batch = 100
+            sled_evacuator.period_secs = 30
+            sled_evacuator.max_concurrent_migrations = 4
+            load_balancer_manager.period_secs = 10
+            vpc_dns.period_secs = 30
+            acme_certificates.period_secs = 3600
+            certificate_expiry.period_secs = 3600
+            dnssec_keys.period_secs = 3600
             populate_switch_ports.period_secs = 31
 
             [default_region_allocation_strategy]
@@ -238,6 +238,7 @@ mod test {
                     actor: self.actor,
                     device_token_expiration: None,
                     credential_id: None,
+                    scope: None,
                 }),
                 FAIL => SchemeResult::Failed(Reason::BadCredentials {
                     actor: self.actor,
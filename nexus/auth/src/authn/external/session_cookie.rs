@@ -185,6 +185,7 @@ where
             actor,
             device_token_expiration: None,
             credential_id: Some(session.id().into_untyped_uuid()),
+            scope: None,
         })
     }
 }
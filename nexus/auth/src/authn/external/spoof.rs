@@ -113,6 +113,7 @@ where
                             actor,
                             device_token_expiration: None,
                             credential_id: None, // spoof auth has no real credential
+                            scope: None,
                         })
                     }
                 }
@@ -11,10 +11,8 @@ use super::SchemeResult;
 use super::SiloUserSilo;
 use crate::authn;
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
 use headers::HeaderMapExt;
 use headers::authorization::{Authorization, Bearer};
-use uuid::Uuid;
 
 // This scheme is intended for clients such as the API, CLI, etc.
 //
@@ -67,13 +65,7 @@ where
             Ok(None) => SchemeResult::NotRequested,
             Ok(Some(token)) => match ctx.authenticate_token(token).await {
                 Err(error) => SchemeResult::Failed(error),
-                Ok((actor, device_token_expiration, token_id)) => {
-                    SchemeResult::Authenticated(Details {
-                        actor,
-                        device_token_expiration,
-                        credential_id: Some(token_id),
-                    })
-                }
+                Ok(details) => SchemeResult::Authenticated(details),
             },
         }
     }
@@ -96,15 +88,19 @@ fn parse_token(
     Ok(Some(token[TOKEN_PREFIX.len()..].to_string()))
 }
 
-/// A context that can look up a Silo user and client ID from a token.
+/// A context that can look up the actor for a token.
+///
+/// Tokens may have been issued to a Silo user (via the device authorization
+/// flow) or to a service account.
 #[async_trait]
 pub trait TokenContext {
-    /// Returns the actor authenticated by the token, the token's expiration
-    /// time (if any), and the token's ID.
+    /// Returns the details of the actor authenticated by the token, including
+    /// the token's expiration time (for device tokens), the token's ID, and any
+    /// restrictions attached to it.
     async fn authenticate_token(
         &self,
         token: String,
-    ) -> Result<(authn::Actor, Option<DateTime<Utc>>, Uuid), Reason>;
+    ) -> Result<Details, Reason>;
 }
 
 #[cfg(test)]
@@ -47,6 +47,7 @@ use nexus_types::identity::Asset;
 use omicron_common::api::external::LookupType;
 use omicron_uuid_kinds::BuiltInUserUuid;
 use omicron_uuid_kinds::GenericUuid;
+use omicron_uuid_kinds::ServiceAccountUuid;
 use omicron_uuid_kinds::SiloUserUuid;
 use serde::Deserialize;
 use serde::Serialize;
@@ -123,6 +124,17 @@ impl Context {
         }
     }
 
+    /// Returns the restrictions attached to the credential used to
+    /// authenticate, if any.
+    ///
+    /// Only service account tokens currently carry a scope.
+    pub fn credential_scope(&self) -> Option<&CredentialScope> {
+        match &self.kind {
+            Kind::Authenticated(Details { scope, .. }, ..) => scope.as_ref(),
+            Kind::Unauthenticated => None,
+        }
+    }
+
     /// Returns the current actor's Silo if they have one or an appropriate
     /// error otherwise
     ///
@@ -168,11 +180,13 @@ impl Context {
                 LookupType::ById(*silo_id),
             )),
             Actor::UserBuiltin { .. } => None,
-            Actor::Scim { silo_id } => Some(authz::Silo::new(
-                authz::FLEET,
-                *silo_id,
-                LookupType::ById(*silo_id),
-            )),
+            Actor::Scim { silo_id } | Actor::ServiceAccount { silo_id, .. } => {
+                Some(authz::Silo::new(
+                    authz::FLEET,
+                    *silo_id,
+                    LookupType::ById(*silo_id),
+                ))
+            }
         })
     }
 
@@ -255,6 +269,7 @@ impl Context {
                     actor: Actor::UserBuiltin { user_builtin_id },
                     device_token_expiration: None,
                     credential_id: None,
+                    scope: None,
                 },
                 None,
             ),
@@ -275,6 +290,7 @@ impl Context {
                     },
                     device_token_expiration: None,
                     credential_id: None,
+                    scope: None,
                 },
                 Some(SiloAuthnPolicy::try_from(&*DEFAULT_SILO).unwrap()),
             ),
@@ -306,6 +322,7 @@ impl Context {
                     actor: Actor::SiloUser { silo_user_id, silo_id },
                     device_token_expiration: None,
                     credential_id: None,
+                    scope: None,
                 },
                 Some(silo_authn_policy),
             ),
@@ -322,6 +339,7 @@ impl Context {
                     actor: Actor::Scim { silo_id },
                     device_token_expiration: None,
                     credential_id: None,
+                    scope: None,
                 },
                 // This should never be non-empty, we don't want the SCIM user
                 // to ever have associated roles.
@@ -453,14 +471,30 @@ pub struct Details {
     /// ID of the credential used to authenticate (session ID, access token ID,
     /// or SCIM token ID). Not set for spoof auth or built-in users.
     pub credential_id: Option<Uuid>,
+    /// Restrictions attached to the credential used to authenticate. These
+    /// only ever narrow what the actor's roles would otherwise allow.
+    #[serde(default)]
+    pub scope: Option<CredentialScope>,
+}
+
+/// Restrictions attached to a credential (currently, a service account token)
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct CredentialScope {
+    /// If true, the credential may only be used for operations that do not
+    /// modify anything
+    pub read_only: bool,
+    /// If set, only role assignments on these projects are considered when
+    /// authorizing requests made with this credential
+    pub project_ids: Option<BTreeSet<Uuid>>,
 }
 
 /// Who is performing an operation
-#[derive(Clone, Copy, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Copy, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub enum Actor {
     UserBuiltin { user_builtin_id: BuiltInUserUuid },
     SiloUser { silo_user_id: SiloUserUuid, silo_id: Uuid },
     Scim { silo_id: Uuid },
+    ServiceAccount { service_account_id: ServiceAccountUuid, silo_id: Uuid },
 }
 
 impl Actor {
@@ -469,6 +503,7 @@ impl Actor {
             Actor::UserBuiltin { .. } => None,
             Actor::SiloUser { silo_id, .. } => Some(*silo_id),
             Actor::Scim { silo_id } => Some(*silo_id),
+            Actor::ServiceAccount { silo_id, .. } => Some(*silo_id),
         }
     }
 
@@ -477,6 +512,18 @@ impl Actor {
             Actor::UserBuiltin { .. } => None,
             Actor::SiloUser { silo_user_id, .. } => Some(*silo_user_id),
             Actor::Scim { .. } => None,
+            Actor::ServiceAccount { .. } => None,
+        }
+    }
+
+    pub fn service_account_id(&self) -> Option<ServiceAccountUuid> {
+        match self {
+            Actor::UserBuiltin { .. } => None,
+            Actor::SiloUser { .. } => None,
+            Actor::Scim { .. } => None,
+            Actor::ServiceAccount { service_account_id, .. } => {
+                Some(*service_account_id)
+            }
         }
     }
 
@@ -485,6 +532,7 @@ impl Actor {
             Actor::UserBuiltin { user_builtin_id } => Some(*user_builtin_id),
             Actor::SiloUser { .. } => None,
             Actor::Scim { .. } => None,
+            Actor::ServiceAccount { .. } => None,
         }
     }
 
@@ -503,6 +551,10 @@ impl Actor {
                 silo_user_id.into_untyped_uuid(),
                 nexus_db_model::IdentityType::SiloUser,
             )),
+            Actor::ServiceAccount { service_account_id, .. } => Some((
+                service_account_id.into_untyped_uuid(),
+                nexus_db_model::IdentityType::ServiceAccount,
+            )),
             // a role assignment for this Actor is invalid, they have a fixed
             // policy.
             Actor::Scim { .. } => None,
@@ -533,6 +585,11 @@ impl std::fmt::Debug for Actor {
                 .debug_struct("Actor::Scim")
                 .field("silo_id", &silo_id)
                 .finish_non_exhaustive(),
+            Actor::ServiceAccount { service_account_id, silo_id } => f
+                .debug_struct("Actor::ServiceAccount")
+                .field("service_account_id", &service_account_id)
+                .field("silo_id", &silo_id)
+                .finish_non_exhaustive(),
         }
     }
 }
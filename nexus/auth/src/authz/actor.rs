@@ -131,6 +131,8 @@ impl oso::PolarClass for AuthenticatedActor {
 
                     authn::Actor::UserBuiltin { .. } => true,
 
+                    authn::Actor::ServiceAccount { .. } => true,
+
                     authn::Actor::Scim { .. } => false,
                 }
             })
@@ -143,12 +145,15 @@ impl oso::PolarClass for AuthenticatedActor {
 
                     authn::Actor::UserBuiltin { .. } => false,
 
+                    authn::Actor::ServiceAccount { .. } => false,
+
                     authn::Actor::Scim { .. } => true,
                 }
             })
             .add_attribute_getter("silo", |a: &AuthenticatedActor| {
                 match a.actor {
                     authn::Actor::SiloUser { silo_id, .. }
+                    | authn::Actor::ServiceAccount { silo_id, .. }
                     | authn::Actor::Scim { silo_id } => Some(super::Silo::new(
                         super::FLEET,
                         silo_id,
@@ -173,6 +178,8 @@ impl oso::PolarClass for AuthenticatedActor {
 
                     authn::Actor::UserBuiltin { .. } => false,
 
+                    authn::Actor::ServiceAccount { .. } => false,
+
                     authn::Actor::Scim { .. } => false,
                 },
             )
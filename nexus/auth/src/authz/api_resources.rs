@@ -592,6 +592,12 @@ impl AuthorizedResource for AuditLog {
     fn polar_class(&self) -> oso::Class {
         Self::get_polar_class()
     }
+
+    fn allowed_for_read_only_credentials(&self) -> bool {
+        // Every request writes an audit log entry, including requests made
+        // with read-only credentials.
+        true
+    }
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -800,6 +806,58 @@ impl AuthorizedResource for SiloCertificateList {
     }
 }
 
+/// Synthetic resource describing the list of service accounts associated with
+/// a Silo
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SiloServiceAccountList(Silo);
+
+impl SiloServiceAccountList {
+    pub fn new(silo: Silo) -> SiloServiceAccountList {
+        SiloServiceAccountList(silo)
+    }
+
+    pub fn silo(&self) -> &Silo {
+        &self.0
+    }
+}
+
+impl oso::PolarClass for SiloServiceAccountList {
+    fn get_polar_class_builder() -> oso::ClassBuilder<Self> {
+        oso::Class::builder()
+            .with_equality_check()
+            .add_attribute_getter("silo", |list: &SiloServiceAccountList| {
+                list.0.clone()
+            })
+    }
+}
+
+impl AuthorizedResource for SiloServiceAccountList {
+    fn load_roles<'fut>(
+        &'fut self,
+        opctx: &'fut OpContext,
+        authn: &'fut authn::Context,
+        roleset: &'fut mut RoleSet,
+    ) -> futures::future::BoxFuture<'fut, Result<(), Error>> {
+        // There are no roles on this resource, but we still need to load the
+        // Silo-related roles.
+        self.silo().load_roles(opctx, authn, roleset)
+    }
+
+    fn on_unauthorized(
+        &self,
+        _: &Authz,
+        error: Error,
+        _: AnyActor,
+        _: Action,
+    ) -> Error {
+        error
+    }
+
+    fn polar_class(&self) -> oso::Class {
+        Self::get_polar_class()
+    }
+}
+
 /// Synthetic resource describing the list of Identity Providers associated with
 /// a Silo
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -1500,6 +1558,14 @@ authz_resource! {
     polar_snippet = InProjectLimited,
 }
 
+authz_resource! {
+    name = "LoadBalancer",
+    parent = "Project",
+    primary_key = Uuid,
+    roles_allowed = false,
+    polar_snippet = InProjectLimited,
+}
+
 authz_resource! {
     name = "ExternalSubnet",
     parent = "Project",
@@ -1784,6 +1850,22 @@ authz_resource! {
     polar_snippet = Custom,
 }
 
+authz_resource! {
+    name = "ServiceAccount",
+    parent = "Silo",
+    primary_key = { uuid_kind = ServiceAccountKind },
+    roles_allowed = false,
+    polar_snippet = Custom,
+}
+
+authz_resource! {
+    name = "ServiceAccountToken",
+    parent = "ServiceAccount",
+    primary_key = { uuid_kind = ServiceAccountTokenKind },
+    roles_allowed = false,
+    polar_snippet = Custom,
+}
+
 authz_resource! {
     name = "IpPool",
     parent = "Fleet",
@@ -1808,6 +1890,14 @@ authz_resource! {
     polar_snippet = FleetChild,
 }
 
+authz_resource! {
+    name = "AuditLogSink",
+    parent = "Fleet",
+    primary_key = { uuid_kind = AuditLogSinkKind },
+    roles_allowed = false,
+    polar_snippet = FleetChild,
+}
+
 authz_resource! {
     name = "WebhookSecret",
     parent = "AlertReceiver",
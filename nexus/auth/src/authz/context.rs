@@ -120,7 +120,23 @@ impl Context {
         let actor = AnyActor::new(&self.authn, roles);
         let is_authn = self.authn.actor().is_some();
         match self.authz.is_allowed(&actor, action, &resource) {
-            Ok(true) => Ok(()),
+            Ok(true) => {
+                // Credentials may be scoped more narrowly than the actor's
+                // roles.  Project restrictions are applied when loading roles
+                // (see `load_directly_attached_roles()`).  Read-only
+                // restrictions are applied here.
+                let read_only = self
+                    .authn
+                    .credential_scope()
+                    .is_some_and(|scope| scope.read_only);
+                if read_only
+                    && !action.is_read_only()
+                    && !resource.allowed_for_read_only_credentials()
+                {
+                    return Err(Error::Forbidden);
+                }
+                Ok(())
+            }
             Err(error) => Err(Error::internal_error(&format!(
                 "failed to compute authorization: {:#}",
                 error
@@ -185,6 +201,15 @@ pub trait AuthorizedResource: oso::ToPolar + Send + Sync + 'static {
 
     /// Returns the Polar class that implements this resource
     fn polar_class(&self) -> oso::Class;
+
+    /// Returns whether actors authenticated with a read-only credential may
+    /// still be granted non-read actions on this resource
+    ///
+    /// This is only true for resources that get modified as a side effect of
+    /// every request, like the audit log.
+    fn allowed_for_read_only_credentials(&self) -> bool {
+        false
+    }
 }
 
 #[cfg(test)]
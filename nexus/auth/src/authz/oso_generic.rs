@@ -115,6 +115,7 @@ pub fn make_omicron_oso(log: &slog::Logger) -> Result<OsoInit, anyhow::Error> {
         DeviceAuthRequestList::get_polar_class(),
         QuiesceState::get_polar_class(),
         SiloCertificateList::get_polar_class(),
+        SiloServiceAccountList::get_polar_class(),
         SiloGroupList::get_polar_class(),
         SiloIdentityProviderList::get_polar_class(),
         SiloUserList::get_polar_class(),
@@ -151,6 +152,7 @@ pub fn make_omicron_oso(log: &slog::Logger) -> Result<OsoInit, anyhow::Error> {
         RouterRoute::init(),
         VpcSubnet::init(),
         FloatingIp::init(),
+        LoadBalancer::init(),
         ExternalSubnet::init(),
         // Silo-level resources
         Image::init(),
@@ -160,6 +162,8 @@ pub fn make_omicron_oso(log: &slog::Logger) -> Result<OsoInit, anyhow::Error> {
         Blueprint::init(),
         LoopbackAddress::init(),
         Certificate::init(),
+        ServiceAccount::init(),
+        ServiceAccountToken::init(),
         ConsoleSession::init(),
         DeviceAuthRequest::init(),
         DeviceAccessToken::init(),
@@ -179,6 +183,7 @@ pub fn make_omicron_oso(log: &slog::Logger) -> Result<OsoInit, anyhow::Error> {
         TufTrustRoot::init(),
         Alert::init(),
         AlertReceiver::init(),
+        AuditLogSink::init(),
         WebhookSecret::init(),
         Zpool::init(),
         Service::init(),
@@ -210,6 +215,23 @@ pub enum Action {
     Delete,
 }
 
+impl Action {
+    /// Returns whether this action only ever observes state (as opposed to
+    /// changing it)
+    pub fn is_read_only(&self) -> bool {
+        match self {
+            Action::Query
+            | Action::Read
+            | Action::ListChildren
+            | Action::ReadPolicy => true,
+            Action::Modify
+            | Action::ModifyPolicy
+            | Action::CreateChild
+            | Action::Delete => false,
+        }
+    }
+}
+
 impl oso::PolarClass for Action {
     fn get_polar_class_builder() -> oso::ClassBuilder<Self> {
         oso::Class::builder()
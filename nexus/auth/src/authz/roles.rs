@@ -168,6 +168,26 @@ async fn load_directly_attached_roles(
             return Ok(());
         };
 
+        // If the credential used to authenticate is restricted to particular
+        // projects, role assignments anywhere else do not apply.
+        if let Some(project_ids) = authn
+            .credential_scope()
+            .and_then(|scope| scope.project_ids.as_ref())
+        {
+            if resource_type != ResourceType::Project
+                || !project_ids.contains(&resource_id)
+            {
+                trace!(
+                    opctx.log,
+                    "credential scope excludes roles on resource";
+                    "actor" => ?actor,
+                    "resource_type" => ?resource_type,
+                    "resource_id" => resource_id.to_string(),
+                );
+                return Ok(());
+            }
+        }
+
         let roles = opctx
             .datastore()
             .role_asgn_list_for(
@@ -12,6 +12,7 @@ use crate::probes;
 use crate::storage::Storage;
 use chrono::{DateTime, Utc};
 use omicron_common::api::external::Error;
+use omicron_common::api::external::ResourceType;
 use omicron_uuid_kinds::ConsoleSessionUuid;
 use omicron_uuid_kinds::SiloUserUuid;
 use slog::debug;
@@ -20,6 +21,7 @@ use slog::trace;
 use std::collections::BTreeMap;
 use std::fmt::Debug;
 use std::sync::Arc;
+use std::sync::Mutex;
 use std::time::Instant;
 use std::time::SystemTime;
 use uuid::Uuid;
@@ -50,6 +52,14 @@ pub struct OpContext {
     created_walltime: SystemTime,
     metadata: BTreeMap<String, String>,
     kind: OpKind,
+    audit_target: Mutex<Option<AuditTarget>>,
+}
+
+/// Identifies the resource that an operation acted on, for the audit log
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AuditTarget {
+    pub resource_type: ResourceType,
+    pub id: Uuid,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -88,6 +98,7 @@ impl OpContext {
             created_walltime,
             metadata,
             kind,
+            audit_target: Mutex::new(None),
         })
     }
 
@@ -114,6 +125,7 @@ impl OpContext {
             created_walltime,
             metadata,
             kind,
+            audit_target: Mutex::new(None),
         })
     }
 
@@ -153,6 +165,16 @@ impl OpContext {
                     "type" => "scim",
                     "silo_id" => silo_id.to_string(),
                 )),
+
+                authn::Actor::ServiceAccount {
+                    service_account_id,
+                    silo_id,
+                } => log.new(o!(
+                    "authenticated" => true,
+                    "type" => "service_account",
+                    "service_account_id" => service_account_id.to_string(),
+                    "silo_id" => silo_id.to_string(),
+                )),
             }
         } else {
             metadata
@@ -197,6 +219,7 @@ impl OpContext {
             created_walltime,
             metadata: BTreeMap::new(),
             kind: OpKind::Background,
+            audit_target: Mutex::new(None),
         }
     }
 
@@ -226,6 +249,7 @@ impl OpContext {
             created_walltime,
             metadata: BTreeMap::new(),
             kind: OpKind::Test,
+            audit_target: Mutex::new(None),
         }
     }
 
@@ -250,6 +274,7 @@ impl OpContext {
             created_walltime,
             metadata: BTreeMap::new(),
             kind: OpKind::Test,
+            audit_target: Mutex::new(None),
         }
     }
 
@@ -278,6 +303,7 @@ impl OpContext {
             created_walltime,
             metadata,
             kind: self.kind,
+            audit_target: Mutex::new(None),
         }
     }
 
@@ -297,6 +323,7 @@ impl OpContext {
             created_walltime,
             metadata: self.metadata.clone(),
             kind: self.kind,
+            audit_target: Mutex::new(None),
         }
     }
 
@@ -345,6 +372,23 @@ impl OpContext {
         result
     }
 
+    /// Records that this operation acts on the resource of type
+    /// `resource_type` with id `id`
+    ///
+    /// For operations that are audited, this is saved in the audit log entry
+    /// when the operation completes. If this is called more than once, the
+    /// last call wins.
+    pub fn set_audit_target(&self, resource_type: ResourceType, id: Uuid) {
+        *self.audit_target.lock().unwrap() =
+            Some(AuditTarget { resource_type, id });
+    }
+
+    /// Returns the resource recorded with [`OpContext::set_audit_target()`],
+    /// if any
+    pub fn audit_target(&self) -> Option<AuditTarget> {
+        *self.audit_target.lock().unwrap()
+    }
+
     /// Returns an error if we're currently in a context where expensive or
     /// complex operations should not be allowed
     ///
@@ -38,6 +38,13 @@ pub struct BackgroundTasks {
     pub task_service_firewall_propagation: Activator,
     pub task_abandoned_vmm_reaper: Activator,
     pub task_audit_log_cleanup: Activator,
+    pub task_audit_log_export: Activator,
+    pub task_sled_evacuator: Activator,
+    pub task_load_balancer_manager: Activator,
+    pub task_vpc_dns: Activator,
+    pub task_acme_certificates: Activator,
+    pub task_certificate_expiry: Activator,
+    pub task_dnssec_keys: Activator,
     pub task_audit_log_timeout_incomplete: Activator,
     pub task_vpc_route_manager: Activator,
     pub task_saga_recovery: Activator,
@@ -24,7 +24,12 @@ pub static DNS_VPC_FW_RULE: LazyLock<VpcFirewallRuleUpdate> =
         )],
         filters: VpcFirewallRuleFilter {
             hosts: None,
-            protocols: Some(vec![VpcFirewallRuleProtocol::Udp]),
+            // DNS is served over TCP as well as UDP so that clients can
+            // retry there when a UDP response is truncated.
+            protocols: Some(vec![
+                VpcFirewallRuleProtocol::Udp,
+                VpcFirewallRuleProtocol::Tcp,
+            ]),
             ports: Some(vec![L4PortRange {
                 first: 53.try_into().unwrap(),
                 last: 53.try_into().unwrap(),
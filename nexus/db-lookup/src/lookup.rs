@@ -188,6 +188,11 @@ impl<'a> LookupPath<'a> {
         FloatingIp::PrimaryKey(Root { lookup_root: self }, id)
     }
 
+    /// Select a resource of type LoadBalancer, identified by its id
+    pub fn load_balancer_id(self, id: Uuid) -> LoadBalancer<'a> {
+        LoadBalancer::PrimaryKey(Root { lookup_root: self }, id)
+    }
+
     // Fleet-level resources
 
     /// Select a resource of type ConsoleSession, identified by its `id`
@@ -248,11 +253,11 @@ impl<'a> LookupPath<'a> {
                 SiloUser::PrimaryKey(Root { lookup_root: self }, *silo_user_id),
             ),
 
-            authn::Actor::UserBuiltin { .. } | authn::Actor::Scim { .. } => {
-                Err(Error::non_resourcetype_not_found(
-                    "could not find silo user",
-                ))
-            }
+            authn::Actor::UserBuiltin { .. }
+            | authn::Actor::Scim { .. }
+            | authn::Actor::ServiceAccount { .. } => Err(
+                Error::non_resourcetype_not_found("could not find silo user"),
+            ),
         }
     }
 
@@ -443,6 +448,69 @@ impl<'a> LookupPath<'a> {
         }
     }
 
+    /// Select a resource of type ServiceAccount, identified by its id
+    pub fn service_account_id(
+        self,
+        id: ServiceAccountUuid,
+    ) -> ServiceAccount<'a> {
+        ServiceAccount::PrimaryKey(Root { lookup_root: self }, id)
+    }
+
+    /// Select a resource of type ServiceAccount, identified by its name
+    pub fn service_account_name<'b, 'c>(
+        self,
+        name: &'b Name,
+    ) -> ServiceAccount<'c>
+    where
+        'a: 'c,
+        'b: 'c,
+    {
+        match self
+            .opctx
+            .authn
+            .silo_required()
+            .internal_context("looking up ServiceAccount by name")
+        {
+            Ok(authz_silo) => {
+                let root = Root { lookup_root: self };
+                let silo_key = Silo::PrimaryKey(root, authz_silo.id());
+                ServiceAccount::Name(silo_key, name)
+            }
+            Err(error) => {
+                let root = Root { lookup_root: self };
+                ServiceAccount::Error(root, error)
+            }
+        }
+    }
+
+    /// Select a resource of type ServiceAccount, identified by its owned name
+    pub fn service_account_name_owned(self, name: Name) -> ServiceAccount<'a> {
+        match self
+            .opctx
+            .authn
+            .silo_required()
+            .internal_context("looking up ServiceAccount by name")
+        {
+            Ok(authz_silo) => {
+                let root = Root { lookup_root: self };
+                let silo_key = Silo::PrimaryKey(root, authz_silo.id());
+                ServiceAccount::OwnedName(silo_key, name)
+            }
+            Err(error) => {
+                let root = Root { lookup_root: self };
+                ServiceAccount::Error(root, error)
+            }
+        }
+    }
+
+    /// Select a resource of type ServiceAccountToken, identified by its id
+    pub fn service_account_token_id(
+        self,
+        id: ServiceAccountTokenUuid,
+    ) -> ServiceAccountToken<'a> {
+        ServiceAccountToken::PrimaryKey(Root { lookup_root: self }, id)
+    }
+
     /// Select a resource of type SamlIdentityProvider, identified by its id
     pub fn saml_identity_provider_id(
         self,
@@ -472,6 +540,20 @@ impl<'a> LookupPath<'a> {
         AlertReceiver::OwnedName(Root { lookup_root: self }, name)
     }
 
+    /// Select a resource of type [`AuditLogSink`], identified by its UUID
+    pub fn audit_log_sink_id(self, id: AuditLogSinkUuid) -> AuditLogSink<'a> {
+        AuditLogSink::PrimaryKey(Root { lookup_root: self }, id)
+    }
+
+    /// Select a resource of type [`AuditLogSink`], identified by its name
+    pub fn audit_log_sink_name<'b, 'c>(self, name: &'b Name) -> AuditLogSink<'c>
+    where
+        'a: 'c,
+        'b: 'c,
+    {
+        AuditLogSink::Name(Root { lookup_root: self }, name)
+    }
+
     /// Select a resource of type [`WebhookSecret`], identified by its UUID.
     pub fn webhook_secret_id(self, id: WebhookSecretUuid) -> WebhookSecret<'a> {
         WebhookSecret::PrimaryKey(Root { lookup_root: self }, id)
@@ -730,6 +812,14 @@ lookup_resource! {
     primary_key_columns = [ { column_name = "id", rust_type = Uuid } ]
 }
 
+lookup_resource! {
+    name = "LoadBalancer",
+    ancestors = [ "Silo", "Project" ],
+    lookup_by_name = true,
+    soft_deletes = true,
+    primary_key_columns = [ { column_name = "id", rust_type = Uuid } ]
+}
+
 // Miscellaneous resources nested directly below "Fleet"
 
 lookup_resource! {
@@ -864,6 +954,22 @@ lookup_resource! {
     primary_key_columns = [ { column_name = "id", rust_type = Uuid } ]
 }
 
+lookup_resource! {
+    name = "ServiceAccount",
+    ancestors = [ "Silo" ],
+    lookup_by_name = true,
+    soft_deletes = true,
+    primary_key_columns = [ { column_name = "id", uuid_kind = ServiceAccountKind } ]
+}
+
+lookup_resource! {
+    name = "ServiceAccountToken",
+    ancestors = [ "Silo", "ServiceAccount" ],
+    lookup_by_name = true,
+    soft_deletes = true,
+    primary_key_columns = [ { column_name = "id", uuid_kind = ServiceAccountTokenKind } ]
+}
+
 lookup_resource! {
     name = "AddressLot",
     ancestors = [], // TODO: Should this include AddressLotBlock?
@@ -894,6 +1000,16 @@ lookup_resource! {
     ]
 }
 
+lookup_resource! {
+    name = "AuditLogSink",
+    ancestors = [],
+    lookup_by_name = true,
+    soft_deletes = true,
+    primary_key_columns = [
+        { column_name = "id", uuid_kind = AuditLogSinkKind }
+    ]
+}
+
 lookup_resource! {
     name = "WebhookSecret",
     ancestors = ["AlertReceiver"],
@@ -0,0 +1,52 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use chrono::{DateTime, Utc};
+use nexus_db_schema::schema::{acme_account, acme_http01_challenge};
+
+/// The account Nexus uses with an ACME server
+#[derive(Clone, Queryable, Selectable, Insertable)]
+#[diesel(table_name = acme_account)]
+pub struct AcmeAccount {
+    pub directory_url: String,
+    pub time_created: DateTime<Utc>,
+    pub account_url: String,
+    pub key_pem: String,
+}
+
+impl AcmeAccount {
+    pub fn new(
+        directory_url: String,
+        account_url: String,
+        key_pem: String,
+    ) -> Self {
+        Self { directory_url, time_created: Utc::now(), account_url, key_pem }
+    }
+}
+
+impl std::fmt::Debug for AcmeAccount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AcmeAccount")
+            .field("directory_url", &self.directory_url)
+            .field("time_created", &self.time_created)
+            .field("account_url", &self.account_url)
+            .field("key_pem", &"<redacted>")
+            .finish()
+    }
+}
+
+/// An outstanding ACME HTTP-01 challenge
+#[derive(Clone, Debug, Queryable, Selectable, Insertable)]
+#[diesel(table_name = acme_http01_challenge)]
+pub struct AcmeHttp01Challenge {
+    pub token: String,
+    pub time_created: DateTime<Utc>,
+    pub key_authorization: String,
+}
+
+impl AcmeHttp01Challenge {
+    pub fn new(token: String, key_authorization: String) -> Self {
+        Self { token, time_created: Utc::now(), key_authorization }
+    }
+}
@@ -29,6 +29,17 @@ impl_enum_type!(
     TestFooBaz => b"test.foo.baz"
     TestQuuxBar => b"test.quux.bar"
     TestQuuxBarBaz => b"test.quux.bar.baz"
+    InstanceFailed => b"instance.failed"
+    InstanceAutoRestarted => b"instance.auto_restarted"
+    InstanceMigrationCompleted => b"instance.migration.completed"
+    InstanceMigrationFailed => b"instance.migration.failed"
+    RegionReplacementStarted => b"storage.region_replacement.started"
+    RegionReplacementFinished => b"storage.region_replacement.finished"
+    PhysicalDiskExpunged => b"hardware.physical_disk.expunged"
+    SledPolicyChanged => b"hardware.sled.policy_changed"
+    SupportBundleReady => b"support_bundle.ready"
+    UpdateStatusChanged => b"update.status_changed"
+    CertificateExpiring => b"certificate.expiring"
 );
 
 impl AlertClass {
@@ -62,6 +73,17 @@ impl From<nexus_types::alert::AlertClass> for AlertClass {
             In::TestFooBaz => Self::TestFooBaz,
             In::TestQuuxBar => Self::TestQuuxBar,
             In::TestQuuxBarBaz => Self::TestQuuxBarBaz,
+            In::InstanceFailed => Self::InstanceFailed,
+            In::InstanceAutoRestarted => Self::InstanceAutoRestarted,
+            In::InstanceMigrationCompleted => Self::InstanceMigrationCompleted,
+            In::InstanceMigrationFailed => Self::InstanceMigrationFailed,
+            In::RegionReplacementStarted => Self::RegionReplacementStarted,
+            In::RegionReplacementFinished => Self::RegionReplacementFinished,
+            In::PhysicalDiskExpunged => Self::PhysicalDiskExpunged,
+            In::SledPolicyChanged => Self::SledPolicyChanged,
+            In::SupportBundleReady => Self::SupportBundleReady,
+            In::UpdateStatusChanged => Self::UpdateStatusChanged,
+            In::CertificateExpiring => Self::CertificateExpiring,
         }
     }
 }
@@ -75,6 +97,25 @@ impl From<AlertClass> for nexus_types::alert::AlertClass {
             AlertClass::TestFooBaz => Self::TestFooBaz,
             AlertClass::TestQuuxBar => Self::TestQuuxBar,
             AlertClass::TestQuuxBarBaz => Self::TestQuuxBarBaz,
+            AlertClass::InstanceFailed => Self::InstanceFailed,
+            AlertClass::InstanceAutoRestarted => Self::InstanceAutoRestarted,
+            AlertClass::InstanceMigrationCompleted => {
+                Self::InstanceMigrationCompleted
+            }
+            AlertClass::InstanceMigrationFailed => {
+                Self::InstanceMigrationFailed
+            }
+            AlertClass::RegionReplacementStarted => {
+                Self::RegionReplacementStarted
+            }
+            AlertClass::RegionReplacementFinished => {
+                Self::RegionReplacementFinished
+            }
+            AlertClass::PhysicalDiskExpunged => Self::PhysicalDiskExpunged,
+            AlertClass::SledPolicyChanged => Self::SledPolicyChanged,
+            AlertClass::SupportBundleReady => Self::SupportBundleReady,
+            AlertClass::UpdateStatusChanged => Self::UpdateStatusChanged,
+            AlertClass::CertificateExpiring => Self::CertificateExpiring,
         }
     }
 }
@@ -0,0 +1,48 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use super::impl_enum_type;
+use serde::Deserialize;
+use serde::Serialize;
+use std::fmt;
+
+impl_enum_type!(
+    AlertReceiverKindEnum:
+
+    /// The mechanism by which alerts are delivered to an alert receiver.
+    #[derive(
+        Copy,
+        Clone,
+        Debug,
+        PartialEq,
+        Eq,
+        Serialize,
+        Deserialize,
+        AsExpression,
+        FromSqlRow,
+        strum::VariantArray,
+    )]
+    #[serde(rename_all = "snake_case")]
+    pub enum AlertRxKind;
+
+    Webhook => b"webhook"
+    Smtp => b"smtp"
+    Syslog => b"syslog"
+);
+
+impl AlertRxKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Webhook => "webhook",
+            Self::Smtp => "smtp",
+            Self::Syslog => "syslog",
+        }
+    }
+}
+
+impl fmt::Display for AlertRxKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -15,6 +15,7 @@ use nexus_types::external_api::audit;
 use omicron_common::api::external::Error;
 use omicron_uuid_kinds::BuiltInUserUuid;
 use omicron_uuid_kinds::GenericUuid;
+use omicron_uuid_kinds::ServiceAccountUuid;
 use omicron_uuid_kinds::SiloUserUuid;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -25,6 +26,7 @@ pub enum AuditLogActor {
     UserBuiltin { user_builtin_id: BuiltInUserUuid },
     SiloUser { silo_user_id: SiloUserUuid, silo_id: Uuid },
     Scim { silo_id: Uuid },
+    ServiceAccount { service_account_id: ServiceAccountUuid, silo_id: Uuid },
     Unauthenticated,
 }
 
@@ -42,6 +44,8 @@ pub struct AuditLogEntryInitParams {
     /// ID of the credential used to authenticate (session ID, access token ID,
     /// or SCIM token ID). Not set for unauthenticated requests or spoof auth.
     pub credential_id: Option<Uuid>,
+    /// Request body, already redacted
+    pub request_body: Option<serde_json::Value>,
 }
 
 impl_enum_type!(
@@ -65,6 +69,7 @@ impl_enum_type!(
     SiloUser => b"silo_user"
     Unauthenticated => b"unauthenticated"
     Scim => b"scim"
+    ServiceAccount => b"service_account"
 );
 
 impl_enum_type!(
@@ -159,7 +164,8 @@ pub struct AuditLogEntryInit {
     // login.
 
     // see AuditLogActor for the allowed combinations
-    /// Actor kind indicating builtin user, silo user, or unauthenticated
+    /// Actor kind indicating builtin user, silo user, SCIM client, service
+    /// account, or unauthenticated
     pub actor_kind: AuditLogActorKind,
     pub actor_id: Option<Uuid>,
     pub actor_silo_id: Option<Uuid>,
@@ -171,6 +177,11 @@ pub struct AuditLogEntryInit {
     /// ID of the credential used to authenticate (session ID, access token ID,
     /// or SCIM token ID). Not set for unauthenticated requests or spoof auth.
     pub credential_id: Option<Uuid>,
+
+    /// Request body with sensitive values redacted. The resource the operation
+    /// acted on is not known until it completes; see
+    /// `AuditLogCompletionUpdate`.
+    pub request_body: Option<serde_json::Value>,
 }
 
 impl From<AuditLogEntryInitParams> for AuditLogEntryInit {
@@ -184,6 +195,7 @@ impl From<AuditLogEntryInitParams> for AuditLogEntryInit {
             actor,
             auth_method,
             credential_id,
+            request_body,
         } = params;
 
         let (actor_id, actor_silo_id, actor_kind) = match actor {
@@ -197,6 +209,11 @@ impl From<AuditLogEntryInitParams> for AuditLogEntryInit {
                 Some(silo_id),
                 AuditLogActorKind::SiloUser,
             ),
+            AuditLogActor::ServiceAccount { service_account_id, silo_id } => (
+                Some(service_account_id.into_untyped_uuid()),
+                Some(silo_id),
+                AuditLogActorKind::ServiceAccount,
+            ),
             AuditLogActor::Scim { silo_id } => {
                 (None, Some(silo_id), AuditLogActorKind::Scim)
             }
@@ -218,6 +235,7 @@ impl From<AuditLogEntryInitParams> for AuditLogEntryInit {
             user_agent,
             auth_method,
             credential_id,
+            request_body,
         }
     }
 }
@@ -257,6 +275,13 @@ pub struct AuditLogEntry {
     /// ID of the credential used to authenticate (session ID, access token ID,
     /// or SCIM token ID). Not set for unauthenticated requests or spoof auth.
     pub credential_id: Option<Uuid>,
+
+    /// Kebab-case type of the resource the operation acted on, if known
+    pub resource_type: Option<String>,
+    /// ID of the resource the operation acted on, if known
+    pub resource_id: Option<Uuid>,
+    /// Request body with sensitive values redacted
+    pub request_body: Option<serde_json::Value>,
 }
 
 /// Struct that we can use as a kind of constructor arg for our actual audit
@@ -291,6 +316,17 @@ pub struct AuditLogCompletionUpdate {
     pub http_status_code: Option<SqlU16>,
     pub error_code: Option<String>,
     pub error_message: Option<String>,
+    pub resource_type: Option<String>,
+    pub resource_id: Option<Uuid>,
+}
+
+impl AuditLogCompletionUpdate {
+    /// Records the resource that the operation acted on
+    pub fn with_resource(mut self, resource_type: String, id: Uuid) -> Self {
+        self.resource_type = Some(resource_type);
+        self.resource_id = Some(id);
+        self
+    }
 }
 
 impl From<AuditLogCompletion> for AuditLogCompletionUpdate {
@@ -303,6 +339,8 @@ impl From<AuditLogCompletion> for AuditLogCompletionUpdate {
                 http_status_code: Some(SqlU16(http_status_code)),
                 error_code: None,
                 error_message: None,
+                resource_type: None,
+                resource_id: None,
             },
             AuditLogCompletion::Error {
                 http_status_code,
@@ -314,6 +352,8 @@ impl From<AuditLogCompletion> for AuditLogCompletionUpdate {
                 http_status_code: Some(SqlU16(http_status_code)),
                 error_code,
                 error_message: Some(error_message),
+                resource_type: None,
+                resource_id: None,
             },
             AuditLogCompletion::Timeout => Self {
                 time_completed,
@@ -321,6 +361,8 @@ impl From<AuditLogCompletion> for AuditLogCompletionUpdate {
                 http_status_code: None,
                 error_code: None,
                 error_message: None,
+                resource_type: None,
+                resource_id: None,
             },
         }
     }
@@ -377,6 +419,26 @@ impl TryFrom<AuditLogEntry> for audit::AuditLogEntry {
                     })?;
                     audit::AuditLogEntryActor::Scim { silo_id }
                 }
+                AuditLogActorKind::ServiceAccount => {
+                    let service_account_id =
+                        entry.actor_id.ok_or_else(|| {
+                            Error::internal_error(
+                                "ServiceAccount actor missing actor_id",
+                            )
+                        })?;
+                    let silo_id = entry.actor_silo_id.ok_or_else(|| {
+                        Error::internal_error(
+                            "ServiceAccount actor missing actor_silo_id",
+                        )
+                    })?;
+                    audit::AuditLogEntryActor::ServiceAccount {
+                        service_account_id:
+                            ServiceAccountUuid::from_untyped_uuid(
+                                service_account_id,
+                            ),
+                        silo_id,
+                    }
+                }
                 AuditLogActorKind::Unauthenticated => {
                     audit::AuditLogEntryActor::Unauthenticated
                 }
@@ -415,6 +477,9 @@ impl TryFrom<AuditLogEntry> for audit::AuditLogEntry {
                 }
             },
             credential_id: entry.credential_id,
+            resource_type: entry.resource_type,
+            resource_id: entry.resource_id,
+            request_body: entry.request_body,
         })
     }
 }
@@ -0,0 +1,131 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::impl_enum_type;
+use chrono::{DateTime, Utc};
+use db_macros::Resource;
+use nexus_db_schema::schema::audit_log_sink;
+use nexus_types::external_api::audit;
+use nexus_types::identity::Resource;
+use omicron_uuid_kinds::AuditLogSinkUuid;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+impl_enum_type!(
+    AuditLogSinkKindEnum:
+
+    #[derive(
+        Clone,
+        Copy,
+        Debug,
+        AsExpression,
+        FromSqlRow,
+        Serialize,
+        Deserialize,
+        PartialEq,
+        Eq,
+    )]
+    pub enum AuditLogSinkKind;
+
+    // Enum values
+    Syslog => b"syslog"
+    Http => b"http"
+);
+
+impl From<AuditLogSinkKind> for audit::AuditLogSinkKind {
+    fn from(kind: AuditLogSinkKind) -> Self {
+        match kind {
+            AuditLogSinkKind::Syslog => audit::AuditLogSinkKind::Syslog,
+            AuditLogSinkKind::Http => audit::AuditLogSinkKind::Http,
+        }
+    }
+}
+
+/// A row in the `audit_log_sink` table
+///
+/// Besides the sink's configuration, this records how far delivery to the
+/// sink has progressed: every audit log entry sorting at or before
+/// `(cursor_time_completed, cursor_id)` has been delivered.
+#[derive(
+    Clone,
+    Debug,
+    Queryable,
+    Selectable,
+    Resource,
+    Insertable,
+    Serialize,
+    Deserialize,
+)]
+#[resource(uuid_kind = AuditLogSinkKind)]
+#[diesel(table_name = audit_log_sink)]
+pub struct AuditLogSink {
+    #[diesel(embed)]
+    pub identity: AuditLogSinkIdentity,
+    pub kind: AuditLogSinkKind,
+    pub endpoint: String,
+    pub tls_root_cert: Option<String>,
+    pub secret: Option<String>,
+
+    pub cursor_time_completed: DateTime<Utc>,
+    pub cursor_id: Uuid,
+    pub time_last_delivered: Option<DateTime<Utc>>,
+
+    pub consecutive_failures: i32,
+    pub time_next_attempt: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+}
+
+impl AuditLogSink {
+    /// Makes a new sink from validated create-time parameters
+    pub fn new(params: audit::AuditLogSinkCreate) -> Self {
+        let identity = AuditLogSinkIdentity::new(
+            AuditLogSinkUuid::new_v4(),
+            params.identity,
+        );
+        let (kind, endpoint, tls_root_cert, secret) = match params.config {
+            audit::AuditLogSinkConfig::Syslog { address, tls_root_cert } => {
+                (AuditLogSinkKind::Syslog, address, tls_root_cert, None)
+            }
+            audit::AuditLogSinkConfig::Http { endpoint, secret } => (
+                AuditLogSinkKind::Http,
+                endpoint.to_string(),
+                None,
+                Some(secret),
+            ),
+        };
+        // The nil UUID sorts before any other, so that entries completed at
+        // exactly `start_time` are delivered too.
+        let cursor_time_completed =
+            params.start_time.unwrap_or(identity.time_created);
+        Self {
+            identity,
+            kind,
+            endpoint,
+            tls_root_cert,
+            secret,
+            cursor_time_completed,
+            cursor_id: Uuid::nil(),
+            time_last_delivered: None,
+            consecutive_failures: 0,
+            time_next_attempt: None,
+            last_error: None,
+        }
+    }
+}
+
+impl From<AuditLogSink> for audit::AuditLogSink {
+    fn from(sink: AuditLogSink) -> Self {
+        Self {
+            identity: sink.identity(),
+            kind: sink.kind.into(),
+            endpoint: sink.endpoint,
+            tls: sink.tls_root_cert.is_some(),
+            cursor: sink.cursor_time_completed,
+            time_last_delivered: sink.time_last_delivered,
+            consecutive_failures: u32::try_from(sink.consecutive_failures)
+                .unwrap_or(0),
+            last_error: sink.last_error,
+        }
+    }
+}
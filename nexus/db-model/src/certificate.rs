@@ -25,6 +25,9 @@ pub struct Certificate {
 
     pub cert: Vec<u8>,
     pub key: Vec<u8>,
+
+    /// Whether Nexus obtained this certificate from an ACME server
+    pub acme_issued: bool,
 }
 
 impl std::fmt::Debug for Certificate {
@@ -34,6 +37,7 @@ impl std::fmt::Debug for Certificate {
             .field("service", &self.service)
             .field("cert", &self.cert)
             .field("key", &"<redacted>")
+            .field("acme_issued", &self.acme_issued)
             .finish()
     }
 }
@@ -71,6 +75,7 @@ impl Certificate {
             service,
             cert: params.cert.into_bytes(),
             key: params.key.into_bytes(),
+            acme_issued: false,
         }
     }
 }
@@ -125,6 +125,9 @@ pub enum DnsRecord {
     AAAA(Ipv6Addr),
     SRV(SRV),
     NS(String),
+    TXT(String),
+    CNAME(String),
+    PTR(String),
 }
 
 impl From<params::DnsRecord> for DnsRecord {
@@ -134,6 +137,9 @@ impl From<params::DnsRecord> for DnsRecord {
             params::DnsRecord::Aaaa(addr) => DnsRecord::AAAA(addr),
             params::DnsRecord::Srv(srv) => DnsRecord::SRV(SRV::from(srv)),
             params::DnsRecord::Ns(ns) => DnsRecord::NS(ns),
+            params::DnsRecord::Txt(text) => DnsRecord::TXT(text),
+            params::DnsRecord::Cname(target) => DnsRecord::CNAME(target),
+            params::DnsRecord::Ptr(ptrdname) => DnsRecord::PTR(ptrdname),
         }
     }
 }
@@ -147,6 +153,9 @@ impl From<DnsRecord> for params::DnsRecord {
                 params::DnsRecord::Srv(params::Srv::from(srv))
             }
             DnsRecord::NS(ns) => params::DnsRecord::Ns(ns),
+            DnsRecord::TXT(text) => params::DnsRecord::Txt(text),
+            DnsRecord::CNAME(target) => params::DnsRecord::Cname(target),
+            DnsRecord::PTR(ptrdname) => params::DnsRecord::Ptr(ptrdname),
         }
     }
 }
@@ -193,12 +202,14 @@ impl From<SRV> for params::Srv {
 /// Provides helpers for constructing the database rows to describe that initial
 /// configuration
 ///
-/// We assume that there will be exactly one DNS zone in this group.
+/// We assume that there will be exactly one forward DNS zone in this group,
+/// possibly alongside some reverse zones (see [`Self::add_reverse_zone()`]).
 #[derive(Debug, Clone)]
 pub struct InitialDnsGroup {
     dns_group: DnsGroup,
     zone_name: String,
     records: HashMap<String, Vec<params::DnsRecord>>,
+    reverse_zones: Vec<InitialReverseZone>,
     version: Generation,
     dns_zone_id: Uuid,
     time_created: DateTime<Utc>,
@@ -218,6 +229,7 @@ impl InitialDnsGroup {
             dns_group,
             zone_name: zone_name.to_owned(),
             records,
+            reverse_zones: Vec::new(),
             dns_zone_id: Uuid::new_v4(),
             time_created: Utc::now(),
             version: Generation::new(),
@@ -226,13 +238,34 @@ impl InitialDnsGroup {
         }
     }
 
-    pub fn row_for_zone(&self) -> DnsZone {
-        DnsZone {
-            id: self.dns_zone_id,
-            time_created: self.time_created,
-            dns_group: self.dns_group,
-            zone_name: self.zone_name.clone(),
-        }
+    /// Adds a reverse DNS zone, with the given records, to the initial
+    /// configuration of this group
+    pub fn add_reverse_zone(
+        &mut self,
+        zone_name: &str,
+        records: HashMap<String, Vec<params::DnsRecord>>,
+    ) {
+        self.reverse_zones.push(InitialReverseZone {
+            dns_zone_id: Uuid::new_v4(),
+            zone_name: zone_name.to_owned(),
+            records,
+        });
+    }
+
+    pub fn rows_for_zones(&self) -> Vec<DnsZone> {
+        std::iter::once((self.dns_zone_id, &self.zone_name))
+            .chain(
+                self.reverse_zones
+                    .iter()
+                    .map(|zone| (zone.dns_zone_id, &zone.zone_name)),
+            )
+            .map(|(id, zone_name)| DnsZone {
+                id,
+                time_created: self.time_created,
+                dns_group: self.dns_group,
+                zone_name: zone_name.clone(),
+            })
+            .collect()
     }
 
     pub fn row_for_version(&self) -> DnsVersion {
@@ -246,17 +279,31 @@ impl InitialDnsGroup {
     }
 
     pub fn rows_for_names(&self) -> Result<Vec<DnsName>, Error> {
-        self.records
-            .iter()
-            .map(|(name, records)| {
-                DnsName::new(
-                    self.dns_zone_id,
-                    name.clone(),
-                    self.version,
-                    None,
-                    records.clone(),
-                )
+        std::iter::once((self.dns_zone_id, &self.records))
+            .chain(
+                self.reverse_zones
+                    .iter()
+                    .map(|zone| (zone.dns_zone_id, &zone.records)),
+            )
+            .flat_map(|(dns_zone_id, records)| {
+                records.iter().map(move |(name, records)| {
+                    DnsName::new(
+                        dns_zone_id,
+                        name.clone(),
+                        self.version,
+                        None,
+                        records.clone(),
+                    )
+                })
             })
             .collect::<Result<Vec<_>, _>>()
     }
 }
+
+/// A reverse DNS zone in an [`InitialDnsGroup`]
+#[derive(Debug, Clone)]
+struct InitialReverseZone {
+    dns_zone_id: Uuid,
+    zone_name: String,
+    records: HashMap<String, Vec<params::DnsRecord>>,
+}
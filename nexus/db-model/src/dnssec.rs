@@ -0,0 +1,168 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use super::impl_enum_type;
+use crate::Generation;
+use chrono::{DateTime, Utc};
+use nexus_db_schema::schema::{dnssec_config, dnssec_key};
+use nexus_types::internal_api::params;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use uuid::Uuid;
+
+impl_enum_type!(
+    DnssecKeyRoleEnum:
+
+    #[derive(
+        Copy,
+        Clone,
+        Debug,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        AsExpression,
+        FromSqlRow,
+        Serialize,
+        Deserialize,
+    )]
+    pub enum DnssecKeyRole;
+
+    // Signs the zone's DNSKEY records, and is referred to by DS records in
+    // the parent zone
+    Ksk => b"ksk"
+    // Signs the rest of the zone
+    Zsk => b"zsk"
+);
+
+impl From<DnssecKeyRole> for params::DnssecKeyRole {
+    fn from(role: DnssecKeyRole) -> Self {
+        match role {
+            DnssecKeyRole::Ksk => Self::Ksk,
+            DnssecKeyRole::Zsk => Self::Zsk,
+        }
+    }
+}
+
+impl fmt::Display for DnssecKeyRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DnssecKeyRole::Ksk => "KSK",
+            DnssecKeyRole::Zsk => "ZSK",
+        })
+    }
+}
+
+impl_enum_type!(
+    DnssecKeyStateEnum:
+
+    #[derive(
+        Copy,
+        Clone,
+        Debug,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        AsExpression,
+        FromSqlRow,
+        Serialize,
+        Deserialize,
+    )]
+    pub enum DnssecKeyState;
+
+    // Published in the zone ahead of being used to sign it
+    Published => b"published"
+    // Published and used to sign the zone
+    Active => b"active"
+    // Still published, but no longer used to sign the zone
+    Retired => b"retired"
+);
+
+impl From<DnssecKeyState> for params::DnssecKeyState {
+    fn from(state: DnssecKeyState) -> Self {
+        match state {
+            // DNS servers treat keys that are about to be used and keys that
+            // have just stopped being used the same way: both are published,
+            // and neither signs anything.
+            DnssecKeyState::Published | DnssecKeyState::Retired => {
+                Self::Published
+            }
+            DnssecKeyState::Active => Self::Active,
+        }
+    }
+}
+
+impl fmt::Display for DnssecKeyState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DnssecKeyState::Published => "published",
+            DnssecKeyState::Active => "active",
+            DnssecKeyState::Retired => "retired",
+        })
+    }
+}
+
+/// A DNSSEC key for one of the external DNS zones
+#[derive(Clone, Debug, Queryable, Selectable, Insertable)]
+#[diesel(table_name = dnssec_key)]
+pub struct DnssecKey {
+    pub id: Uuid,
+    pub time_created: DateTime<Utc>,
+    pub time_activated: Option<DateTime<Utc>>,
+    pub time_retired: Option<DateTime<Utc>>,
+    pub time_deleted: Option<DateTime<Utc>>,
+    pub zone_name: String,
+    pub role: DnssecKeyRole,
+    pub state: DnssecKeyState,
+    /// The private key, encoded as in
+    /// [`params::DnssecKey::private_key`]
+    pub private_key: String,
+}
+
+impl DnssecKey {
+    /// Returns a new key for the zone `zone_name`, in the given state
+    pub fn new(
+        zone_name: String,
+        role: DnssecKeyRole,
+        state: DnssecKeyState,
+        private_key: String,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            time_created: now,
+            time_activated: (state == DnssecKeyState::Active).then_some(now),
+            time_retired: None,
+            time_deleted: None,
+            zone_name,
+            role,
+            state,
+            private_key,
+        }
+    }
+}
+
+impl From<DnssecKey> for params::DnssecKey {
+    fn from(key: DnssecKey) -> Self {
+        Self {
+            role: key.role.into(),
+            state: key.state.into(),
+            algorithm: params::DnssecAlgorithm::EcdsaP256Sha256,
+            private_key: key.private_key,
+        }
+    }
+}
+
+/// The generation of the DNSSEC keys as a whole
+///
+/// There is only ever one row, whose generation advances each time any key
+/// changes.
+#[derive(Clone, Debug, Queryable, Selectable, Insertable)]
+#[diesel(table_name = dnssec_config)]
+pub struct DnssecConfig {
+    pub singleton: bool,
+    pub generation: Generation,
+    pub time_modified: DateTime<Utc>,
+}
@@ -49,6 +49,7 @@ impl_enum_type!(
     SNat => b"snat"
     Ephemeral => b"ephemeral"
     Floating => b"floating"
+    LoadBalancer => b"load_balancer"
 );
 
 impl_enum_type!(
@@ -80,6 +81,7 @@ impl std::fmt::Display for IpKind {
             IpKind::Floating => "floating",
             IpKind::Ephemeral => "ephemeral",
             IpKind::SNat => "SNAT",
+            IpKind::LoadBalancer => "load balancer",
         })
     }
 }
@@ -148,6 +150,8 @@ pub enum OmicronZoneExternalIpError {
         "database IP is ephemeral; currently unsupported for Omicron zones"
     )]
     EphemeralIp,
+    #[error("database IP is the frontend of a load balancer")]
+    LoadBalancerIp,
 }
 
 impl TryFrom<&'_ ExternalIp> for OmicronZoneExternalIp {
@@ -181,6 +185,9 @@ impl TryFrom<&'_ ExternalIp> for OmicronZoneExternalIp {
                 }))
             }
             IpKind::Ephemeral => Err(OmicronZoneExternalIpError::EphemeralIp),
+            IpKind::LoadBalancer => {
+                Err(OmicronZoneExternalIpError::LoadBalancerIp)
+            }
         }
     }
 }
@@ -201,7 +208,11 @@ impl From<IpKind> for ProbeExternalIpKind {
         match value {
             IpKind::SNat => ProbeExternalIpKind::Snat,
             IpKind::Ephemeral => ProbeExternalIpKind::Ephemeral,
-            IpKind::Floating => ProbeExternalIpKind::Floating,
+            // Load balancer frontends are never attached to probes, but
+            // are otherwise treated like floating IPs in the data plane.
+            IpKind::Floating | IpKind::LoadBalancer => {
+                ProbeExternalIpKind::Floating
+            }
         }
     }
 }
@@ -374,6 +385,30 @@ impl IncompleteExternalIp {
         }
     }
 
+    pub fn for_load_balancer(
+        id: Uuid,
+        load_balancer_id: Uuid,
+        pool_id: Uuid,
+        explicit_ip: Option<IpAddr>,
+    ) -> Self {
+        let kind = IpKind::LoadBalancer;
+        Self {
+            id,
+            name: None,
+            description: None,
+            time_created: Utc::now(),
+            kind,
+            is_service: false,
+            is_probe: false,
+            parent_id: Some(load_balancer_id),
+            pool_id,
+            project_id: None,
+            explicit_ip: explicit_ip.map(Into::into),
+            explicit_port_range: explicit_ip.map(|_| (0, u16::MAX.into())),
+            state: kind.initial_state(),
+        }
+    }
+
     pub fn for_omicron_zone(
         pool_id: Uuid,
         external_ip: OmicronZoneExternalIp,
@@ -496,6 +531,9 @@ impl IpKind {
             IpKind::SNat => IpAttachState::Attached,
             IpKind::Ephemeral => IpAttachState::Detached,
             IpKind::Floating => IpAttachState::Detached,
+            // The frontend of a load balancer is attached to it for its
+            // whole lifetime.
+            IpKind::LoadBalancer => IpAttachState::Attached,
         }
     }
 }
@@ -539,6 +577,9 @@ impl TryFrom<ExternalIp> for external_ip_types::ExternalIp {
                     ip_pool_id: ip.ip_pool_id,
                 },
             )),
+            IpKind::LoadBalancer => Err(Error::internal_error(
+                "load balancer frontends are not instance external IPs",
+            )),
         }
     }
 }
@@ -644,7 +685,11 @@ impl TryFrom<ExternalIp> for InstanceExternalIpBody {
         let ip = value.ip.ip();
         match value.kind {
             IpKind::Ephemeral => Ok(InstanceExternalIpBody::Ephemeral(ip)),
-            IpKind::Floating => Ok(InstanceExternalIpBody::Floating(ip)),
+            // OPTE accepts traffic for a load balancer's frontend on each
+            // backend serving it as it would for a floating IP.
+            IpKind::Floating | IpKind::LoadBalancer => {
+                Ok(InstanceExternalIpBody::Floating(ip))
+            }
             IpKind::SNat => Err(Error::invalid_request(
                 "cannot dynamically add/remove SNAT allocation",
             )),
@@ -0,0 +1,24 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::Name;
+use chrono::{DateTime, Utc};
+use nexus_db_schema::schema::instance_network_tag;
+use omicron_common::api::external;
+use uuid::Uuid;
+
+/// A network tag carried by an instance
+#[derive(Clone, Debug, Insertable, Queryable, Selectable)]
+#[diesel(table_name = instance_network_tag)]
+pub struct InstanceNetworkTag {
+    pub instance_id: Uuid,
+    pub tag: Name,
+    pub time_created: DateTime<Utc>,
+}
+
+impl InstanceNetworkTag {
+    pub fn new(instance_id: Uuid, tag: external::Name) -> Self {
+        Self { instance_id, tag: tag.into(), time_created: Utc::now() }
+    }
+}
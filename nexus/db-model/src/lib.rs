@@ -117,15 +117,18 @@ extern crate diesel;
 #[macro_use]
 extern crate newtype_derive;
 
+mod acme;
 mod address_lot;
 mod affinity;
 mod alert;
 mod alert_class;
 mod alert_delivery_state;
 mod alert_delivery_trigger;
+mod alert_rx_kind;
 mod alert_subscription;
 mod allow_list;
 mod audit_log;
+mod audit_log_sink;
 mod bfd;
 mod bgp;
 mod block_size;
@@ -146,6 +149,7 @@ mod disk_state;
 mod disk_type_crucible;
 mod disk_type_local_storage;
 mod dns;
+mod dnssec;
 mod downstairs;
 pub mod ereport;
 mod ereporter_type;
@@ -160,6 +164,7 @@ mod instance_auto_restart_policy;
 mod instance_cpu_count;
 mod instance_cpu_platform;
 mod instance_intended_state;
+mod instance_network_tag;
 mod instance_state;
 mod internet_gateway;
 mod inventory;
@@ -170,6 +175,7 @@ mod ipv4net;
 pub mod ipv6;
 mod ipv6net;
 mod l4_port_range;
+mod load_balancer;
 mod local_storage;
 mod local_storage_dataset_allocation;
 mod macaddr;
@@ -193,6 +199,7 @@ mod scim_client_bearer_token;
 mod semver_version;
 mod serde_time_delta;
 mod silo_auth_settings;
+mod silo_rate_limit;
 mod switch_interface;
 mod switch_port;
 mod system_networking_settings;
@@ -220,6 +227,7 @@ mod region_snapshot_replacement_step;
 mod role_assignment;
 pub mod saga_types;
 mod schema_versions;
+mod service_account;
 mod service_kind;
 mod silo;
 mod silo_group;
@@ -227,6 +235,7 @@ mod silo_user;
 mod silo_user_password_hash;
 mod sled;
 mod sled_cpu_family;
+mod sled_evacuation;
 mod sled_instance;
 mod sled_policy;
 mod sled_resource_vmm;
@@ -253,6 +262,7 @@ mod volume;
 mod volume_repair;
 mod volume_resource_usage;
 mod vpc;
+mod vpc_dns;
 mod vpc_firewall_rule;
 mod vpc_route;
 mod vpc_router;
@@ -269,15 +279,18 @@ mod db {
 
 pub use self::macaddr::*;
 pub use self::unsigned::*;
+pub use acme::*;
 pub use address_lot::*;
 pub use affinity::*;
 pub use alert::*;
 pub use alert_class::*;
 pub use alert_delivery_state::*;
 pub use alert_delivery_trigger::*;
+pub use alert_rx_kind::*;
 pub use alert_subscription::*;
 pub use allow_list::*;
 pub use audit_log::*;
+pub use audit_log_sink::*;
 pub use bfd::*;
 pub use bgp::*;
 pub use block_size::*;
@@ -299,6 +312,7 @@ pub use disk_state::*;
 pub use disk_type_crucible::*;
 pub use disk_type_local_storage::*;
 pub use dns::*;
+pub use dnssec::*;
 pub use downstairs::*;
 pub use ereport::Ereport;
 pub use ereporter_type::*;
@@ -313,6 +327,7 @@ pub use instance_auto_restart_policy::*;
 pub use instance_cpu_count::*;
 pub use instance_cpu_platform::*;
 pub use instance_intended_state::*;
+pub use instance_network_tag::*;
 pub use instance_state::*;
 pub use internet_gateway::*;
 pub use inventory::*;
@@ -323,6 +338,7 @@ pub use ipv4net::*;
 pub use ipv6::*;
 pub use ipv6net::*;
 pub use l4_port_range::*;
+pub use load_balancer::*;
 pub use local_storage::*;
 pub use local_storage_dataset_allocation::*;
 pub use migration::*;
@@ -355,14 +371,17 @@ pub use saga_types::*;
 pub use schema_versions::*;
 pub use scim_client_bearer_token::*;
 pub use semver_version::*;
+pub use service_account::*;
 pub use service_kind::*;
 pub use silo::*;
 pub use silo_auth_settings::*;
 pub use silo_group::*;
+pub use silo_rate_limit::*;
 pub use silo_user::*;
 pub use silo_user_password_hash::*;
 pub use sled::*;
 pub use sled_cpu_family::*;
+pub use sled_evacuation::*;
 pub use sled_instance::*;
 pub use sled_policy::to_db_sled_policy; // Do not expose DbSledPolicy
 pub use sled_resource_vmm::*;
@@ -396,6 +415,7 @@ pub use volume::*;
 pub use volume_repair::*;
 pub use volume_resource_usage::*;
 pub use vpc::*;
+pub use vpc_dns::*;
 pub use vpc_firewall_rule::*;
 pub use vpc_route::*;
 pub use vpc_router::*;
@@ -0,0 +1,205 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::ExternalIp;
+use crate::Name;
+use crate::SqlU16;
+use crate::impl_enum_type;
+use chrono::{DateTime, Utc};
+use db_macros::Resource;
+use nexus_db_schema::schema::{load_balancer, load_balancer_backend};
+use nexus_types::external_api::load_balancer as load_balancer_types;
+use nexus_types::identity::Resource;
+use omicron_common::api::external;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A per-port load balancer
+#[derive(
+    Queryable,
+    Insertable,
+    Clone,
+    Debug,
+    Selectable,
+    Resource,
+    Serialize,
+    Deserialize,
+)]
+#[diesel(table_name = load_balancer)]
+pub struct LoadBalancer {
+    #[diesel(embed)]
+    identity: LoadBalancerIdentity,
+
+    pub project_id: Uuid,
+    /// The `external_ip` row holding the frontend address
+    pub external_ip_id: Uuid,
+    pub ports: Vec<SqlU16>,
+    pub backend_tag: Option<Name>,
+    pub health_check_port: Option<SqlU16>,
+    pub healthy_threshold: Option<SqlU16>,
+    pub unhealthy_threshold: Option<SqlU16>,
+}
+
+impl LoadBalancer {
+    pub fn new(
+        id: Uuid,
+        project_id: Uuid,
+        external_ip_id: Uuid,
+        params: load_balancer_types::LoadBalancerCreate,
+    ) -> Self {
+        let identity = LoadBalancerIdentity::new(id, params.identity);
+        let health_check = params.health_check;
+        Self {
+            identity,
+            project_id,
+            external_ip_id,
+            ports: params.ports.into_iter().map(SqlU16::from).collect(),
+            backend_tag: params.backend_tag.map(Name::from),
+            health_check_port: health_check
+                .as_ref()
+                .map(|check| SqlU16::from(check.port)),
+            healthy_threshold: health_check
+                .as_ref()
+                .map(|check| SqlU16::from(u16::from(check.healthy_threshold))),
+            unhealthy_threshold: health_check.as_ref().map(|check| {
+                SqlU16::from(u16::from(check.unhealthy_threshold))
+            }),
+        }
+    }
+
+    /// The ports on which the load balancer accepts traffic
+    pub fn ports(&self) -> impl Iterator<Item = u16> + '_ {
+        self.ports.iter().map(|port| port.0)
+    }
+
+    /// The health check applied to backends, if any
+    pub fn health_check(
+        &self,
+    ) -> Option<load_balancer_types::LoadBalancerHealthCheck> {
+        // The thresholds were created from `u8`s, and are set along with the
+        // port.
+        let threshold = |t: Option<SqlU16>| {
+            u8::try_from(t.map_or(1, |t| t.0)).unwrap_or(u8::MAX)
+        };
+        Some(load_balancer_types::LoadBalancerHealthCheck {
+            port: self.health_check_port?.0,
+            healthy_threshold: threshold(self.healthy_threshold),
+            unhealthy_threshold: threshold(self.unhealthy_threshold),
+        })
+    }
+
+    /// Convert to the external view, given the load balancer's frontend
+    /// address
+    pub fn into_view(
+        self,
+        frontend: &ExternalIp,
+    ) -> load_balancer_types::LoadBalancer {
+        let health_check = self.health_check();
+        load_balancer_types::LoadBalancer {
+            ip: frontend.ip.ip(),
+            ip_pool_id: frontend.ip_pool_id,
+            project_id: self.project_id,
+            ports: self.ports().collect(),
+            backend_tag: self.backend_tag.map(external::Name::from),
+            health_check,
+            identity: self.identity(),
+        }
+    }
+}
+
+impl_enum_type!(
+    LoadBalancerBackendHealthEnum:
+
+    #[derive(
+        Clone,
+        Copy,
+        Debug,
+        AsExpression,
+        FromSqlRow,
+        Serialize,
+        Deserialize,
+        PartialEq,
+        Eq,
+    )]
+    pub enum LoadBalancerBackendHealth;
+
+    // Enum values
+    Unknown => b"unknown"
+    Healthy => b"healthy"
+    Unhealthy => b"unhealthy"
+);
+
+impl From<LoadBalancerBackendHealth>
+    for load_balancer_types::LoadBalancerBackendHealth
+{
+    fn from(health: LoadBalancerBackendHealth) -> Self {
+        match health {
+            LoadBalancerBackendHealth::Unknown => Self::Unknown,
+            LoadBalancerBackendHealth::Healthy => Self::Healthy,
+            LoadBalancerBackendHealth::Unhealthy => Self::Unhealthy,
+        }
+    }
+}
+
+/// An instance behind a load balancer
+#[derive(Clone, Debug, Insertable, Queryable, Selectable)]
+#[diesel(table_name = load_balancer_backend)]
+pub struct LoadBalancerBackend {
+    pub load_balancer_id: Uuid,
+    pub instance_id: Uuid,
+    pub time_created: DateTime<Utc>,
+    pub time_modified: DateTime<Utc>,
+    /// True if added through the API, false if selected by the load
+    /// balancer's backend tag
+    pub explicit: bool,
+    pub health: LoadBalancerBackendHealth,
+    pub consecutive_successes: i32,
+    pub consecutive_failures: i32,
+    /// The VMM whose OPTE port currently carries the frontend address, if the
+    /// backend is serving traffic
+    pub frontend_vmm_id: Option<Uuid>,
+    /// The load balancer ports the switches currently forward to this
+    /// backend
+    pub ports: Vec<SqlU16>,
+}
+
+impl LoadBalancerBackend {
+    pub fn new(
+        load_balancer_id: Uuid,
+        instance_id: Uuid,
+        explicit: bool,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            load_balancer_id,
+            instance_id,
+            time_created: now,
+            time_modified: now,
+            explicit,
+            health: LoadBalancerBackendHealth::Unknown,
+            consecutive_successes: 0,
+            consecutive_failures: 0,
+            frontend_vmm_id: None,
+            ports: Vec::new(),
+        }
+    }
+}
+
+impl From<LoadBalancerBackend> for load_balancer_types::LoadBalancerBackend {
+    fn from(backend: LoadBalancerBackend) -> Self {
+        Self {
+            load_balancer_id: backend.load_balancer_id,
+            instance_id: backend.instance_id,
+            explicit: backend.explicit,
+            health: backend.health.into(),
+            // The frontend address stays on healthy backends that aren't
+            // assigned any ports, so that ports can move to them quickly, but
+            // they receive no traffic.
+            serving: backend.frontend_vmm_id.is_some()
+                && !backend.ports.is_empty(),
+            ports: backend.ports.iter().map(|port| port.0).collect(),
+            time_created: backend.time_created,
+        }
+    }
+}
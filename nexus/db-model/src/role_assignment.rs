@@ -9,6 +9,7 @@ use nexus_types::external_api::policy;
 use omicron_common::api::external::Error;
 use omicron_uuid_kinds::BuiltInUserUuid;
 use omicron_uuid_kinds::GenericUuid;
+use omicron_uuid_kinds::ServiceAccountUuid;
 use omicron_uuid_kinds::SiloUserUuid;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -31,6 +32,7 @@ impl_enum_type!(
     UserBuiltin => b"user_builtin"
     SiloUser => b"silo_user"
     SiloGroup => b"silo_group"
+    ServiceAccount => b"service_account"
 );
 
 impl From<policy::IdentityType> for IdentityType {
@@ -47,7 +49,7 @@ impl TryFrom<IdentityType> for policy::IdentityType {
 
     fn try_from(other: IdentityType) -> Result<Self, Self::Error> {
         match other {
-            IdentityType::UserBuiltin => {
+            IdentityType::UserBuiltin | IdentityType::ServiceAccount => {
                 Err(anyhow!("unsupported db identity type: {:?}", other))
             }
             IdentityType::SiloUser => Ok(policy::IdentityType::SiloUser),
@@ -116,6 +118,22 @@ impl RoleAssignment {
             role_name,
         )
     }
+
+    /// Creates a new database RoleAssignment object for a service account
+    pub fn new_for_service_account(
+        service_account_id: ServiceAccountUuid,
+        resource_type: omicron_common::api::external::ResourceType,
+        resource_id: Uuid,
+        role_name: &str,
+    ) -> Self {
+        Self::new(
+            IdentityType::ServiceAccount,
+            service_account_id.into_untyped_uuid(),
+            resource_type,
+            resource_id,
+            role_name,
+        )
+    }
 }
 
 impl<AllowedRoles> TryFrom<RoleAssignment>
@@ -16,7 +16,7 @@ use std::{collections::BTreeMap, sync::LazyLock};
 ///
 /// This must be updated when you change the database schema.  Refer to
 /// schema/crdb/README.adoc in the root of this repository for details.
-pub const SCHEMA_VERSION: Version = Version::new(268, 0, 0);
+pub const SCHEMA_VERSION: Version = Version::new(285, 0, 0);
 
 /// List of all past database schema versions, in *reverse* order
 ///
@@ -28,6 +28,23 @@ pub static KNOWN_VERSIONS: LazyLock<Vec<KnownVersion>> = LazyLock::new(|| {
         // |  leaving the first copy as an example for the next person.
         // v
         // KnownVersion::new(next_int, "unique-dirname-with-the-sql-files"),
+        KnownVersion::new(285, "webhook-peer-certificate-expiry"),
+        KnownVersion::new(284, "vpc-dns-forwarders"),
+        KnownVersion::new(283, "load-balancer-backend-ports"),
+        KnownVersion::new(282, "dnssec-keys"),
+        KnownVersion::new(281, "external-dns-tcp"),
+        KnownVersion::new(280, "certificate-expiring-alert"),
+        KnownVersion::new(279, "acme-certificates"),
+        KnownVersion::new(278, "vpc-dns"),
+        KnownVersion::new(277, "load-balancers"),
+        KnownVersion::new(276, "instance-network-tags"),
+        KnownVersion::new(275, "sled-evacuation"),
+        KnownVersion::new(274, "real-alert-classes"),
+        KnownVersion::new(273, "alert-receiver-kinds"),
+        KnownVersion::new(272, "audit-log-resource"),
+        KnownVersion::new(271, "audit-log-sinks"),
+        KnownVersion::new(270, "silo-rate-limit"),
+        KnownVersion::new(269, "service-accounts"),
         KnownVersion::new(268, "fm-sitrep-analysis-report"),
         KnownVersion::new(267, "add-disruption-policy"),
         KnownVersion::new(266, "alert-version"),
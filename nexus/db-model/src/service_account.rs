@@ -0,0 +1,143 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::DbTypedUuid;
+use crate::device_auth::generate_token;
+use crate::to_db_typed_uuid;
+use chrono::{DateTime, Utc};
+use db_macros::Resource;
+use nexus_db_schema::schema::{service_account, service_account_token};
+use nexus_types::external_api::service_account as service_account_types;
+use nexus_types::identity::Resource;
+use omicron_uuid_kinds::ServiceAccountKind;
+use omicron_uuid_kinds::ServiceAccountTokenUuid;
+use omicron_uuid_kinds::ServiceAccountUuid;
+use uuid::Uuid;
+
+/// A non-human principal within a Silo, as stored in the database
+#[derive(Queryable, Insertable, Clone, Debug, Resource, Selectable)]
+#[diesel(table_name = service_account)]
+#[resource(uuid_kind = ServiceAccountKind)]
+pub struct ServiceAccount {
+    #[diesel(embed)]
+    pub identity: ServiceAccountIdentity,
+
+    pub silo_id: Uuid,
+}
+
+impl ServiceAccount {
+    pub fn new(
+        silo_id: Uuid,
+        params: service_account_types::ServiceAccountCreate,
+    ) -> Self {
+        Self {
+            identity: ServiceAccountIdentity::new(
+                ServiceAccountUuid::new_v4(),
+                params.identity,
+            ),
+            silo_id,
+        }
+    }
+}
+
+impl From<ServiceAccount> for service_account_types::ServiceAccount {
+    fn from(sa: ServiceAccount) -> Self {
+        Self { identity: sa.identity(), silo_id: sa.silo_id }
+    }
+}
+
+/// An API token with which a service account authenticates
+#[derive(Queryable, Insertable, Clone, Resource, Selectable)]
+#[diesel(table_name = service_account_token)]
+#[resource(uuid_kind = ServiceAccountTokenKind)]
+pub struct ServiceAccountToken {
+    #[diesel(embed)]
+    pub identity: ServiceAccountTokenIdentity,
+
+    service_account_id: DbTypedUuid<ServiceAccountKind>,
+    pub token: String,
+    pub time_expires: Option<DateTime<Utc>>,
+    pub read_only: bool,
+    pub project_ids: Option<Vec<Uuid>>,
+}
+
+impl std::fmt::Debug for ServiceAccountToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ServiceAccountToken")
+            .field("identity", &self.identity)
+            .field("service_account_id", &self.service_account_id)
+            .field("token", &"<redacted>")
+            .field("time_expires", &self.time_expires)
+            .field("read_only", &self.read_only)
+            .field("project_ids", &self.project_ids)
+            .finish()
+    }
+}
+
+impl ServiceAccountToken {
+    pub fn new(
+        service_account_id: ServiceAccountUuid,
+        params: service_account_types::ServiceAccountTokenCreate,
+    ) -> Self {
+        Self {
+            identity: ServiceAccountTokenIdentity::new(
+                ServiceAccountTokenUuid::new_v4(),
+                params.identity,
+            ),
+            service_account_id: to_db_typed_uuid(service_account_id),
+            token: generate_token(),
+            time_expires: params.time_expires,
+            read_only: params.read_only,
+            project_ids: params.project_ids,
+        }
+    }
+
+    /// Returns a new token that carries over everything about this one except
+    /// its ID and secret value. Used for rotation.
+    pub fn rotated(&self) -> Self {
+        let mut identity = self.identity.clone();
+        let now = Utc::now();
+        identity.id = to_db_typed_uuid(ServiceAccountTokenUuid::new_v4());
+        identity.time_created = now;
+        identity.time_modified = now;
+        Self {
+            identity,
+            service_account_id: self.service_account_id,
+            token: generate_token(),
+            time_expires: self.time_expires,
+            read_only: self.read_only,
+            project_ids: self.project_ids.clone(),
+        }
+    }
+
+    pub fn service_account_id(&self) -> ServiceAccountUuid {
+        self.service_account_id.into()
+    }
+
+    /// The value a client presents in the `Authorization` header
+    pub fn bearer_token(&self) -> String {
+        format!("oxide-token-{}", self.token)
+    }
+}
+
+impl From<ServiceAccountToken> for service_account_types::ServiceAccountToken {
+    fn from(t: ServiceAccountToken) -> Self {
+        Self {
+            identity: t.identity(),
+            service_account_id: t.service_account_id(),
+            time_expires: t.time_expires,
+            read_only: t.read_only,
+            project_ids: t.project_ids,
+        }
+    }
+}
+
+impl From<ServiceAccountToken>
+    for service_account_types::ServiceAccountTokenValue
+{
+    fn from(t: ServiceAccountToken) -> Self {
+        let bearer_token = t.bearer_token();
+        Self { token: t.into(), bearer_token }
+    }
+}
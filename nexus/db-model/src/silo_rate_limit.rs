@@ -0,0 +1,84 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::SqlU32;
+use chrono::{DateTime, Utc};
+use nexus_db_schema::schema::silo_rate_limit;
+use nexus_types::external_api::silo;
+use serde::{Deserialize, Serialize};
+use std::num::NonZeroU32;
+use uuid::Uuid;
+
+/// Per-silo overrides of the external API rate limit
+///
+/// Silos without a row use the fleet-wide defaults from Nexus configuration,
+/// as do any columns here that are null.
+#[derive(
+    Queryable,
+    Insertable,
+    Debug,
+    Clone,
+    Selectable,
+    Serialize,
+    Deserialize,
+    AsChangeset,
+)]
+#[diesel(table_name = silo_rate_limit)]
+pub struct SiloRateLimit {
+    pub silo_id: Uuid,
+    pub time_created: DateTime<Utc>,
+    pub time_modified: DateTime<Utc>,
+
+    pub requests_per_second: Option<SqlU32>,
+    pub burst: Option<SqlU32>,
+}
+
+impl SiloRateLimit {
+    /// Returns a rate limit for `silo_id` that overrides nothing
+    pub fn new(silo_id: Uuid) -> Self {
+        let now = Utc::now();
+        Self {
+            silo_id,
+            time_created: now,
+            time_modified: now,
+            requests_per_second: None,
+            burst: None,
+        }
+    }
+
+    pub fn requests_per_second(&self) -> Option<NonZeroU32> {
+        self.requests_per_second.and_then(|r| NonZeroU32::new(r.0))
+    }
+
+    pub fn burst(&self) -> Option<NonZeroU32> {
+        self.burst.and_then(|b| NonZeroU32::new(b.0))
+    }
+}
+
+impl From<SiloRateLimit> for silo::SiloRateLimit {
+    fn from(rate_limit: SiloRateLimit) -> Self {
+        Self {
+            silo_id: rate_limit.silo_id,
+            requests_per_second: rate_limit.requests_per_second(),
+            burst: rate_limit.burst(),
+        }
+    }
+}
+
+impl SiloRateLimit {
+    /// Builds the row that results from applying `params` to `silo_id`
+    pub fn from_update(
+        silo_id: Uuid,
+        params: silo::SiloRateLimitUpdate,
+    ) -> Self {
+        Self {
+            requests_per_second: params
+                .requests_per_second
+                .0
+                .map(|r| SqlU32::new(r.get())),
+            burst: params.burst.0.map(|b| SqlU32::new(b.get())),
+            ..Self::new(silo_id)
+        }
+    }
+}
@@ -351,13 +351,18 @@ impl SledUpdate {
 #[derive(Clone, Debug)]
 pub struct SledReservationConstraints {
     must_select_from: Vec<SledUuid>,
+    must_not_select_from: Vec<SledUuid>,
     cpu_families: Vec<SledCpuFamily>,
 }
 
 impl SledReservationConstraints {
     /// Creates a constraint set with no constraints in it.
     pub fn none() -> Self {
-        Self { must_select_from: Vec::new(), cpu_families: Vec::new() }
+        Self {
+            must_select_from: Vec::new(),
+            must_not_select_from: Vec::new(),
+            cpu_families: Vec::new(),
+        }
     }
 
     /// If the constraints include a set of sleds that the caller must select
@@ -372,6 +377,12 @@ impl SledReservationConstraints {
         }
     }
 
+    /// Returns the set of sleds that the caller must not select. This is empty
+    /// if no "must not select from these" constraint exists.
+    pub fn must_not_select_from(&self) -> &[SledUuid] {
+        &self.must_not_select_from
+    }
+
     /// If the constraints include a list of acceptable sled CPU families,
     /// returns `Some` and a slice containing the members of that set.
     ///
@@ -406,6 +417,14 @@ impl SledReservationConstraintBuilder {
         self
     }
 
+    /// Adds a "must not select from the following sled IDs" constraint. If such
+    /// a constraint already exists, appends the supplied sled IDs to the "must
+    /// not select from" list.
+    pub fn must_not_select_from(mut self, sled_ids: &[SledUuid]) -> Self {
+        self.constraints.must_not_select_from.extend(sled_ids);
+        self
+    }
+
     pub fn cpu_families(mut self, families: &[SledCpuFamily]) -> Self {
         self.constraints.cpu_families.extend(families);
         self
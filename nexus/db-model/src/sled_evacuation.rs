@@ -0,0 +1,112 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::impl_enum_type;
+use crate::typed_uuid::DbTypedUuid;
+use chrono::{DateTime, Utc};
+use nexus_db_schema::schema::sled_evacuation;
+use nexus_types::external_api::sled;
+use omicron_uuid_kinds::SledKind;
+use omicron_uuid_kinds::SledUuid;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+impl_enum_type!(
+    SledEvacuationStateEnum:
+
+    #[derive(
+        Clone,
+        Copy,
+        Debug,
+        AsExpression,
+        FromSqlRow,
+        Serialize,
+        Deserialize,
+        PartialEq,
+        Eq,
+    )]
+    pub enum SledEvacuationState;
+
+    // Enum values
+    InProgress => b"in_progress"
+    Completed => b"completed"
+    Cancelled => b"cancelled"
+);
+
+impl From<SledEvacuationState> for sled::SledEvacuationState {
+    fn from(state: SledEvacuationState) -> Self {
+        match state {
+            SledEvacuationState::InProgress => Self::InProgress,
+            SledEvacuationState::Completed => Self::Completed,
+            SledEvacuationState::Cancelled => Self::Cancelled,
+        }
+    }
+}
+
+/// A row in the `sled_evacuation` table
+#[derive(
+    Clone, Debug, Queryable, Selectable, Insertable, Serialize, Deserialize,
+)]
+#[diesel(table_name = sled_evacuation)]
+pub struct SledEvacuation {
+    pub id: Uuid,
+    pub sled_id: DbTypedUuid<SledKind>,
+    pub time_started: DateTime<Utc>,
+    pub time_finished: Option<DateTime<Utc>>,
+    pub state: SledEvacuationState,
+    pub instances_total: i64,
+    pub migrations_started: i64,
+    pub last_error: Option<String>,
+}
+
+impl SledEvacuation {
+    pub fn new(sled_id: SledUuid, instances_total: u64) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            sled_id: sled_id.into(),
+            time_started: Utc::now(),
+            time_finished: None,
+            state: SledEvacuationState::InProgress,
+            instances_total: i64::try_from(instances_total).unwrap_or(i64::MAX),
+            migrations_started: 0,
+            last_error: None,
+        }
+    }
+
+    pub fn sled_id(&self) -> SledUuid {
+        self.sled_id.into()
+    }
+
+    /// Converts this record into its external view, given the instances
+    /// currently remaining on the sled.
+    pub fn into_view(
+        self,
+        progress: SledEvacuationProgress,
+    ) -> sled::SledEvacuation {
+        sled::SledEvacuation {
+            id: self.id,
+            sled_id: self.sled_id.into(),
+            state: self.state.into(),
+            time_started: self.time_started,
+            time_finished: self.time_finished,
+            instances_total: self.instances_total.try_into().unwrap_or(0),
+            instances_remaining: progress.instances_remaining,
+            instances_migrating: progress.instances_migrating,
+            migrations_started: self.migrations_started.try_into().unwrap_or(0),
+            last_error: self.last_error,
+        }
+    }
+}
+
+/// The instances remaining on a sled that is being evacuated
+///
+/// Unlike the rest of an evacuation's status, this is computed from the
+/// instances currently running on the sled rather than being stored.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SledEvacuationProgress {
+    /// Instances whose active VMM is on the sled
+    pub instances_remaining: u64,
+    /// The subset of `instances_remaining` that are currently migrating
+    pub instances_migrating: u64,
+}
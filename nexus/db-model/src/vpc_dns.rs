@@ -0,0 +1,72 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::Generation;
+use crate::Name;
+use chrono::{DateTime, Utc};
+use ipnetwork::IpNetwork;
+use nexus_db_schema::schema::{vpc_dns_config, vpc_dns_record};
+use nexus_types::external_api::vpc;
+use omicron_common::api::external;
+use uuid::Uuid;
+
+/// A user-defined record in a VPC's private DNS zone
+#[derive(Clone, Debug, Queryable, Selectable, Insertable)]
+#[diesel(table_name = vpc_dns_record)]
+pub struct VpcDnsRecord {
+    pub id: Uuid,
+    pub time_created: DateTime<Utc>,
+    pub time_modified: DateTime<Utc>,
+    pub time_deleted: Option<DateTime<Utc>>,
+    pub vpc_id: Uuid,
+    pub name: Name,
+    pub address: IpNetwork,
+}
+
+impl VpcDnsRecord {
+    pub fn new(vpc_id: Uuid, record: vpc::VpcDnsRecord) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            time_created: now,
+            time_modified: now,
+            time_deleted: None,
+            vpc_id,
+            name: Name::from(record.name),
+            address: IpNetwork::from(record.address),
+        }
+    }
+}
+
+impl From<VpcDnsRecord> for vpc::VpcDnsRecord {
+    fn from(record: VpcDnsRecord) -> Self {
+        Self {
+            name: external::Name::from(record.name),
+            address: record.address.ip(),
+        }
+    }
+}
+
+/// The DNS zones private to VPCs, as last computed
+///
+/// There is only ever one row, whose generation advances each time the zones
+/// or forwarders change. The zones are stored as the JSON serialization of
+/// `internal_dns_types::config::VpcDnsZone`s, since they are only ever
+/// propagated to DNS servers in full.
+#[derive(Clone, Debug, Queryable, Selectable, Insertable)]
+#[diesel(table_name = vpc_dns_config)]
+pub struct VpcDnsConfig {
+    pub singleton: bool,
+    pub generation: Generation,
+    pub time_created: DateTime<Utc>,
+    pub zones: serde_json::Value,
+    /// Recursive resolvers that the DNS servers forward instances' queries
+    /// for names outside of their zones to
+    pub forwarders: Vec<IpNetwork>,
+}
+
+/// The name of the private DNS zone of the VPC with the given DNS name
+pub fn vpc_dns_zone_name(dns_name: &Name) -> String {
+    format!("{dns_name}.internal")
+}
@@ -311,6 +311,28 @@ impl VpcFirewallRule {
             .map(|rule| VpcFirewallRule::new(Uuid::new_v4(), vpc_id, &rule))
             .collect()
     }
+
+    /// Checks that a VPC may hold `count` firewall rules
+    pub fn ensure_rule_count(count: usize) -> Result<(), external::Error> {
+        if count > MAX_FW_RULES_PER_VPC {
+            let msg = format!("max length {}", MAX_FW_RULES_PER_VPC);
+            return Err(external::Error::invalid_value("rules", msg));
+        }
+        Ok(())
+    }
+
+    /// Returns true if any of this rule's targets or host filters is a
+    /// network tag
+    ///
+    /// The resolved form of such a rule changes whenever the set of instances
+    /// carrying the tag changes.
+    pub fn references_tags(&self) -> bool {
+        self.targets.iter().any(|target| {
+            matches!(target.0, external::VpcFirewallRuleTarget::Tag(_))
+        }) || self.filter_hosts.iter().flatten().any(|host| {
+            matches!(host.0, external::VpcFirewallRuleHostFilter::Tag(_))
+        })
+    }
 }
 
 fn ensure_no_duplicates(
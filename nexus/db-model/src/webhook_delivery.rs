@@ -6,6 +6,7 @@ use crate::Alert;
 use crate::AlertClass;
 use crate::AlertDeliveryState;
 use crate::AlertDeliveryTrigger;
+use crate::AlertRxKind;
 use crate::SqlU8;
 use crate::SqlU16;
 use crate::WebhookDeliveryAttemptResult;
@@ -114,15 +115,26 @@ impl WebhookDelivery {
     pub fn to_api_delivery(
         &self,
         alert_class: impl Into<AlertClass>,
+        rx_kind: AlertRxKind,
         attempts: &[WebhookDeliveryAttempt],
     ) -> alert::AlertDelivery {
-        let mut attempts: Vec<_> =
-            attempts.iter().map(alert::WebhookDeliveryAttempt::from).collect();
         // Make sure attempts are in order; each attempt entry also includes an
         // attempt number, which should be used authoritatively to determine the
         // ordering of attempts, but it seems nice to also sort the list,
         // because we can...
+        let mut attempts = attempts.iter().collect::<Vec<_>>();
         attempts.sort_by_key(|a| a.attempt);
+        let attempts = match rx_kind {
+            AlertRxKind::Webhook => alert::AlertDeliveryAttempts::Webhook(
+                attempts.into_iter().map(Into::into).collect(),
+            ),
+            AlertRxKind::Smtp => alert::AlertDeliveryAttempts::Smtp(
+                attempts.into_iter().map(Into::into).collect(),
+            ),
+            AlertRxKind::Syslog => alert::AlertDeliveryAttempts::Syslog(
+                attempts.into_iter().map(Into::into).collect(),
+            ),
+        };
         alert::AlertDelivery {
             id: self.id.into_untyped_uuid(),
             receiver_id: self.rx_id.into(),
@@ -130,7 +142,7 @@ impl WebhookDelivery {
             alert_id: self.alert_id.into(),
             state: self.state.into(),
             trigger: self.triggered_by.into(),
-            attempts: alert::AlertDeliveryAttempts::Webhook(attempts),
+            attempts,
             time_started: self.time_created,
         }
     }
@@ -171,6 +183,10 @@ pub struct WebhookDeliveryAttempt {
     pub time_created: DateTime<Utc>,
 
     pub deliverator_id: DbTypedUuid<OmicronZoneKind>,
+
+    /// When the certificate the receiver's endpoint presented expires, if the
+    /// attempt was made over TLS and the handshake completed.
+    pub time_peer_cert_expires: Option<DateTime<Utc>>,
 }
 
 impl WebhookDeliveryAttempt {
@@ -193,3 +209,55 @@ impl From<&'_ WebhookDeliveryAttempt> for alert::WebhookDeliveryAttempt {
         }
     }
 }
+
+/// SMTP delivery attempts are recorded in the `webhook_delivery_attempt`
+/// table. A rejection by the mail server is recorded as an HTTP error, with
+/// the server's reply code stored in place of the HTTP status.
+impl From<&'_ WebhookDeliveryAttempt> for alert::SmtpDeliveryAttempt {
+    fn from(attempt: &WebhookDeliveryAttempt) -> Self {
+        let result = match attempt.result {
+            WebhookDeliveryAttemptResult::Succeeded => {
+                alert::SmtpDeliveryAttemptResult::Succeeded
+            }
+            WebhookDeliveryAttemptResult::FailedHttpError => {
+                alert::SmtpDeliveryAttemptResult::FailedRejected
+            }
+            WebhookDeliveryAttemptResult::FailedUnreachable => {
+                alert::SmtpDeliveryAttemptResult::FailedUnreachable
+            }
+            WebhookDeliveryAttemptResult::FailedTimeout => {
+                alert::SmtpDeliveryAttemptResult::FailedTimeout
+            }
+        };
+        Self {
+            attempt: attempt.attempt.0 as usize,
+            result,
+            time_sent: attempt.time_created,
+            reply_code: attempt.response_status.map(Into::into),
+        }
+    }
+}
+
+impl From<&'_ WebhookDeliveryAttempt> for alert::SyslogDeliveryAttempt {
+    fn from(attempt: &WebhookDeliveryAttempt) -> Self {
+        let result = match attempt.result {
+            WebhookDeliveryAttemptResult::Succeeded => {
+                alert::SyslogDeliveryAttemptResult::Succeeded
+            }
+            WebhookDeliveryAttemptResult::FailedTimeout => {
+                alert::SyslogDeliveryAttemptResult::FailedTimeout
+            }
+            // Syslog has no notion of a response, so the only other way a
+            // delivery can fail is if the message couldn't be sent.
+            WebhookDeliveryAttemptResult::FailedHttpError
+            | WebhookDeliveryAttemptResult::FailedUnreachable => {
+                alert::SyslogDeliveryAttemptResult::FailedUnreachable
+            }
+        };
+        Self {
+            attempt: attempt.attempt.0 as usize,
+            result,
+            time_sent: attempt.time_created,
+        }
+    }
+}
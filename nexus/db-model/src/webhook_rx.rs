@@ -3,10 +3,12 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use crate::AlertRxGlob;
+use crate::AlertRxKind;
 use crate::AlertRxSubscription;
 use crate::AlertSubscriptionKind;
 use crate::Generation;
 use crate::Name;
+use crate::SqlU8;
 use crate::collection::DatastoreCollectionConfig;
 use crate::typed_uuid::DbTypedUuid;
 use chrono::{DateTime, Utc};
@@ -17,10 +19,13 @@ use nexus_db_schema::schema::{
 use nexus_types::external_api::alert;
 use nexus_types::identity::Resource;
 use omicron_common::api::external::Error;
+use omicron_common::api::external::IdentityMetadataCreateParams;
 use omicron_uuid_kinds::{
     AlertReceiverKind, AlertReceiverUuid, GenericUuid, WebhookSecretUuid,
 };
 use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use url::Url;
 use uuid::Uuid;
 
 /// The full configuration of a webhook alert receiver, including the
@@ -32,11 +37,29 @@ pub struct WebhookReceiverConfig {
     pub subscriptions: Vec<AlertSubscriptionKind>,
 }
 
+impl TryFrom<WebhookReceiverConfig> for alert::AlertReceiver {
+    type Error = Error;
+    fn try_from(config: WebhookReceiverConfig) -> Result<Self, Self::Error> {
+        match config.rx.kind {
+            AlertRxKind::Webhook => {
+                alert::WebhookReceiver::try_from(config).map(Into::into)
+            }
+            AlertRxKind::Smtp => {
+                alert::SmtpReceiver::try_from(config).map(Into::into)
+            }
+            AlertRxKind::Syslog => {
+                alert::SyslogReceiver::try_from(config).map(Into::into)
+            }
+        }
+    }
+}
+
 impl TryFrom<WebhookReceiverConfig> for alert::WebhookReceiver {
     type Error = Error;
     fn try_from(
         WebhookReceiverConfig { rx, secrets, subscriptions }: WebhookReceiverConfig,
     ) -> Result<alert::WebhookReceiver, Self::Error> {
+        rx.ensure_kind(AlertRxKind::Webhook)?;
         let secrets = secrets.iter().map(alert::WebhookSecret::from).collect();
         let subscriptions = subscriptions
             .into_iter()
@@ -59,12 +82,46 @@ impl TryFrom<WebhookReceiverConfig> for alert::WebhookReceiver {
     }
 }
 
+impl TryFrom<WebhookReceiverConfig> for alert::SmtpReceiver {
+    type Error = Error;
+    fn try_from(
+        WebhookReceiverConfig { rx, subscriptions, .. }: WebhookReceiverConfig,
+    ) -> Result<alert::SmtpReceiver, Self::Error> {
+        let subscriptions = subscriptions
+            .into_iter()
+            .map(alert::AlertSubscription::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(alert::SmtpReceiver {
+            identity: rx.identity(),
+            subscriptions,
+            config: rx.smtp_config()?,
+        })
+    }
+}
+
+impl TryFrom<WebhookReceiverConfig> for alert::SyslogReceiver {
+    type Error = Error;
+    fn try_from(
+        WebhookReceiverConfig { rx, subscriptions, .. }: WebhookReceiverConfig,
+    ) -> Result<alert::SyslogReceiver, Self::Error> {
+        let subscriptions = subscriptions
+            .into_iter()
+            .map(alert::AlertSubscription::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(alert::SyslogReceiver {
+            identity: rx.identity(),
+            subscriptions,
+            config: rx.syslog_config()?,
+        })
+    }
+}
+
 /// A row in the `alert_receiver` table.
-// XXX(eliza): Note that this presently contains both generic "alert receiver
-// stuff" (i.e. the identity and subscription rcgen) *and*
-// webhook-receiver-specific stuff (endpoint, secret rcgen). If/when we
-// introduce other kinds of alert receivers, we will want to split that out into
-// a webhook-specific table.
+///
+/// All kinds of receiver share this table, as they share identity,
+/// subscriptions, and the delivery machinery. Configuration which only applies
+/// to one kind of receiver is stored in nullable columns, which are set if and
+/// only if the receiver is of that kind.
 #[derive(
     Clone,
     Debug,
@@ -86,6 +143,217 @@ pub struct AlertReceiver {
     pub secret_gen: Generation,
     /// child resource generation number for event subscriptions, per RFD 192
     pub subscription_gen: Generation,
+
+    /// The mechanism by which alerts are delivered to this receiver.
+    pub kind: AlertRxKind,
+    /// For SMTP receivers, the address alert messages are sent from.
+    pub smtp_from: Option<String>,
+    /// For SMTP receivers, the addresses alert messages are sent to.
+    pub smtp_to: Option<Vec<String>>,
+    /// For syslog receivers, the numeric syslog facility code.
+    pub syslog_facility: Option<SqlU8>,
+}
+
+impl AlertReceiver {
+    /// Constructs a new SMTP receiver, validating its configuration.
+    pub fn new_smtp(
+        id: AlertReceiverUuid,
+        identity: IdentityMetadataCreateParams,
+        host: &str,
+        port: u16,
+        from: String,
+        to: Vec<String>,
+    ) -> Result<Self, Error> {
+        validate_email_addresses(&from, &to)?;
+        Ok(Self {
+            identity: AlertReceiverIdentity::new(id, identity),
+            endpoint: alert_rx_endpoint(SMTP_SCHEME, host, port)?,
+            secret_gen: Generation::new(),
+            subscription_gen: Generation::new(),
+            kind: AlertRxKind::Smtp,
+            smtp_from: Some(from),
+            smtp_to: Some(to),
+            syslog_facility: None,
+        })
+    }
+
+    /// Constructs a new syslog receiver, validating its configuration.
+    pub fn new_syslog(
+        id: AlertReceiverUuid,
+        identity: IdentityMetadataCreateParams,
+        host: &str,
+        port: u16,
+        transport: alert::SyslogTransport,
+        facility: alert::SyslogFacility,
+    ) -> Result<Self, Error> {
+        Ok(Self {
+            identity: AlertReceiverIdentity::new(id, identity),
+            endpoint: alert_rx_endpoint(transport.scheme(), host, port)?,
+            secret_gen: Generation::new(),
+            subscription_gen: Generation::new(),
+            kind: AlertRxKind::Syslog,
+            smtp_from: None,
+            smtp_to: None,
+            syslog_facility: Some(SqlU8::new(facility.code())),
+        })
+    }
+
+    /// Returns an error if this receiver is not of the `expected` kind.
+    pub fn ensure_kind(&self, expected: AlertRxKind) -> Result<(), Error> {
+        if self.kind != expected {
+            return Err(Error::invalid_request(format!(
+                "alert receiver {:?} is a {} receiver, not a {expected} \
+                 receiver",
+                self.identity.name.as_str(),
+                self.kind,
+            )));
+        }
+        Ok(())
+    }
+
+    /// Returns the host and port of a non-HTTP receiver's endpoint, along with
+    /// the endpoint's URL scheme.
+    pub fn endpoint_host_port(&self) -> Result<(String, u16, String), Error> {
+        let invalid = |reason: &str| Error::InternalError {
+            // As with webhook URLs, we should never have inserted an endpoint
+            // we can't parse back out of the database.
+            internal_message: format!(
+                "invalid alert receiver endpoint {:?}: {reason}",
+                self.endpoint,
+            ),
+        };
+        let url =
+            Url::parse(&self.endpoint).map_err(|e| invalid(&e.to_string()))?;
+        let host = match url.host() {
+            Some(url::Host::Domain(host)) => host.to_string(),
+            Some(url::Host::Ipv4(addr)) => addr.to_string(),
+            Some(url::Host::Ipv6(addr)) => addr.to_string(),
+            None => return Err(invalid("missing host")),
+        };
+        let port = url.port().ok_or_else(|| invalid("missing port"))?;
+        Ok((host, port, url.scheme().to_string()))
+    }
+
+    /// Returns the API view of an SMTP receiver's configuration.
+    pub fn smtp_config(&self) -> Result<alert::SmtpReceiverConfig, Error> {
+        self.ensure_kind(AlertRxKind::Smtp)?;
+        let (host, port, _) = self.endpoint_host_port()?;
+        let (Some(from), Some(to)) = (&self.smtp_from, &self.smtp_to) else {
+            return Err(Error::internal_error(
+                "SMTP receiver is missing its sender or recipients",
+            ));
+        };
+        Ok(alert::SmtpReceiverConfig {
+            host,
+            port,
+            from: from.clone(),
+            to: to.clone(),
+        })
+    }
+
+    /// Returns the API view of a syslog receiver's configuration.
+    pub fn syslog_config(&self) -> Result<alert::SyslogReceiverConfig, Error> {
+        self.ensure_kind(AlertRxKind::Syslog)?;
+        let (host, port, scheme) = self.endpoint_host_port()?;
+        let transport = alert::SyslogTransport::from_scheme(&scheme)
+            .ok_or_else(|| {
+                Error::internal_error(&format!(
+                    "invalid syslog transport {scheme:?}"
+                ))
+            })?;
+        let facility = self
+            .syslog_facility
+            .and_then(|code| alert::SyslogFacility::from_code(*code))
+            .ok_or_else(|| {
+                Error::internal_error(&format!(
+                    "invalid syslog facility {:?}",
+                    self.syslog_facility
+                ))
+            })?;
+        Ok(alert::SyslogReceiverConfig { host, port, transport, facility })
+    }
+}
+
+/// The URL scheme used for SMTP receiver endpoints.
+pub const SMTP_SCHEME: &str = "smtp";
+
+/// Formats the `endpoint` of a receiver that delivers alerts to `host:port`,
+/// after checking that `host` is an IP address or a valid DNS name.
+pub fn alert_rx_endpoint(
+    scheme: &str,
+    host: &str,
+    port: u16,
+) -> Result<String, Error> {
+    if port == 0 {
+        return Err(Error::invalid_value("port", "port must be nonzero"));
+    }
+    match host.parse::<IpAddr>() {
+        Ok(IpAddr::V4(addr)) => Ok(format!("{scheme}://{addr}:{port}")),
+        Ok(IpAddr::V6(addr)) => Ok(format!("{scheme}://[{addr}]:{port}")),
+        Err(_) => {
+            let valid_label = |label: &str| {
+                !label.is_empty()
+                    && label.len() <= 63
+                    && !label.starts_with('-')
+                    && !label.ends_with('-')
+                    && label
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '-')
+            };
+            let name = host.strip_suffix('.').unwrap_or(host);
+            if name.is_empty()
+                || name.len() > 253
+                || !name.split('.').all(valid_label)
+            {
+                return Err(Error::invalid_value(
+                    "host",
+                    format!("{host:?} is not an IP address or DNS name"),
+                ));
+            }
+            Ok(format!("{scheme}://{}:{port}", name.to_ascii_lowercase()))
+        }
+    }
+}
+
+/// Checks the sender and recipient addresses of an SMTP receiver.
+///
+/// This isn't a full RFC 5321 address parser; it only rejects addresses
+/// which are obviously malformed, or which could be used to inject additional
+/// SMTP commands or message headers.
+pub fn validate_email_addresses(
+    from: &str,
+    to: &[String],
+) -> Result<(), Error> {
+    if to.is_empty() {
+        return Err(Error::invalid_value(
+            "to",
+            "at least one recipient address is required",
+        ));
+    }
+    for (field, addr) in std::iter::once(("from", from))
+        .chain(to.iter().map(|a| ("to", a.as_str())))
+    {
+        let valid = addr.len() <= 254
+            && matches!(
+                addr.split_once('@'),
+                Some((local, domain))
+                    if !local.is_empty()
+                        && !domain.is_empty()
+                        && !domain.contains('@')
+            )
+            && !addr.chars().any(|c| {
+                c.is_whitespace()
+                    || c.is_control()
+                    || matches!(c, '<' | '>' | ',' | ';')
+            });
+        if !valid {
+            return Err(Error::invalid_value(
+                field,
+                format!("{addr:?} is not a valid email address"),
+            ));
+        }
+    }
+    Ok(())
 }
 
 impl DatastoreCollectionConfig<WebhookSecret> for AlertReceiver {
@@ -120,6 +388,90 @@ pub struct WebhookReceiverUpdate {
     pub time_modified: DateTime<Utc>,
 }
 
+/// Describes a set of updates for the [`alert_receiver`] table to update an
+/// SMTP receiver configuration.
+#[derive(Clone, AsChangeset)]
+#[diesel(table_name = alert_receiver)]
+pub struct SmtpReceiverUpdate {
+    pub name: Option<Name>,
+    pub description: Option<String>,
+    pub endpoint: Option<String>,
+    pub smtp_from: Option<String>,
+    pub smtp_to: Option<Vec<String>>,
+    pub time_modified: DateTime<Utc>,
+}
+
+impl SmtpReceiverUpdate {
+    /// Validates `params` against the receiver's current configuration,
+    /// `rx`, and returns the corresponding update.
+    pub fn new(
+        rx: &AlertReceiver,
+        params: alert::SmtpReceiverUpdate,
+    ) -> Result<Self, Error> {
+        let current = rx.smtp_config()?;
+        let endpoint = if params.host.is_some() || params.port.is_some() {
+            let host = params.host.as_deref().unwrap_or(&current.host);
+            let port = params.port.unwrap_or(current.port);
+            Some(alert_rx_endpoint(SMTP_SCHEME, host, port)?)
+        } else {
+            None
+        };
+        validate_email_addresses(
+            params.from.as_deref().unwrap_or(&current.from),
+            params.to.as_deref().unwrap_or(&current.to),
+        )?;
+        Ok(Self {
+            name: params.identity.name.map(Name),
+            description: params.identity.description,
+            endpoint,
+            smtp_from: params.from,
+            smtp_to: params.to,
+            time_modified: Utc::now(),
+        })
+    }
+}
+
+/// Describes a set of updates for the [`alert_receiver`] table to update a
+/// syslog receiver configuration.
+#[derive(Clone, AsChangeset)]
+#[diesel(table_name = alert_receiver)]
+pub struct SyslogReceiverUpdate {
+    pub name: Option<Name>,
+    pub description: Option<String>,
+    pub endpoint: Option<String>,
+    pub syslog_facility: Option<SqlU8>,
+    pub time_modified: DateTime<Utc>,
+}
+
+impl SyslogReceiverUpdate {
+    /// Validates `params` against the receiver's current configuration,
+    /// `rx`, and returns the corresponding update.
+    pub fn new(
+        rx: &AlertReceiver,
+        params: alert::SyslogReceiverUpdate,
+    ) -> Result<Self, Error> {
+        let current = rx.syslog_config()?;
+        let endpoint = if params.host.is_some()
+            || params.port.is_some()
+            || params.transport.is_some()
+        {
+            let host = params.host.as_deref().unwrap_or(&current.host);
+            let port = params.port.unwrap_or(current.port);
+            let transport = params.transport.unwrap_or(current.transport);
+            Some(alert_rx_endpoint(transport.scheme(), host, port)?)
+        } else {
+            None
+        };
+        Ok(Self {
+            name: params.identity.name.map(Name),
+            description: params.identity.description,
+            endpoint,
+            syslog_facility: params.facility.map(|f| SqlU8::new(f.code())),
+            time_modified: Utc::now(),
+        })
+    }
+}
+
 #[derive(
     Clone,
     Debug,
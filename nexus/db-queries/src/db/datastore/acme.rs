@@ -0,0 +1,623 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! [`DataStore`] methods used to obtain Silos' TLS certificates from an ACME
+//! server.
+//!
+//! Certificates obtained this way are stored alongside those uploaded by
+//! operators, marked `acme_issued` so that Nexus knows it's responsible for
+//! renewing and removing them.
+//!
+//! Challenges are answered either over HTTP (HTTP-01), from challenges stored
+//! in their own table, or with TXT records in the external DNS zones (DNS-01).
+
+use super::DataStore;
+use super::DnsVersionUpdateBuilder;
+use super::SQL_BATCH_SIZE;
+use crate::authz;
+use crate::context::OpContext;
+use crate::db::model::AcmeAccount;
+use crate::db::model::AcmeHttp01Challenge;
+use crate::db::model::Certificate;
+use crate::db::model::DnsGroup;
+use crate::db::model::DnsName;
+use crate::db::model::ServiceKind;
+use crate::db::pagination::Paginator;
+use crate::db::pagination::paginated;
+use async_bb8_diesel::AsyncRunQueryDsl;
+use chrono::Utc;
+use diesel::prelude::*;
+use internal_dns_types::names::is_reverse_zone;
+use nexus_db_errors::ErrorHandler;
+use nexus_db_errors::TransactionError;
+use nexus_db_errors::public_error_from_diesel;
+use nexus_types::identity::Resource;
+use nexus_types::internal_api::params::DnsRecord;
+use omicron_common::api::external::CreateResult;
+use omicron_common::api::external::DeleteResult;
+use omicron_common::api::external::Error;
+use omicron_common::api::external::ListResultVec;
+use omicron_common::api::external::ResourceType;
+use uuid::Uuid;
+
+impl DataStore {
+    /// Fetch the account Nexus uses with the ACME server at `directory_url`,
+    /// if one has been registered
+    pub async fn acme_account_fetch(
+        &self,
+        opctx: &OpContext,
+        directory_url: &str,
+    ) -> Result<Option<AcmeAccount>, Error> {
+        use nexus_db_schema::schema::acme_account::dsl;
+
+        opctx.authorize(authz::Action::Read, &authz::FLEET).await?;
+        dsl::acme_account
+            .filter(dsl::directory_url.eq(directory_url.to_string()))
+            .select(AcmeAccount::as_select())
+            .first_async(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .optional()
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))
+    }
+
+    /// Record the account Nexus uses with an ACME server
+    ///
+    /// If another Nexus has already recorded an account for the same server,
+    /// that account is kept and returned instead, so that every Nexus uses the
+    /// same one.
+    pub async fn acme_account_create(
+        &self,
+        opctx: &OpContext,
+        account: AcmeAccount,
+    ) -> CreateResult<AcmeAccount> {
+        use nexus_db_schema::schema::acme_account::dsl;
+
+        opctx.authorize(authz::Action::Modify, &authz::FLEET).await?;
+        let conn = self.pool_connection_authorized(opctx).await?;
+        let directory_url = account.directory_url.clone();
+        diesel::insert_into(dsl::acme_account)
+            .values(account)
+            .on_conflict(dsl::directory_url)
+            .do_nothing()
+            .execute_async(&*conn)
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))?;
+        dsl::acme_account
+            .filter(dsl::directory_url.eq(directory_url))
+            .select(AcmeAccount::as_select())
+            .get_result_async(&*conn)
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))
+    }
+
+    /// Record an outstanding HTTP-01 challenge, so that any Nexus can answer
+    /// the ACME server's request for it
+    pub async fn acme_http01_challenge_create(
+        &self,
+        opctx: &OpContext,
+        challenge: AcmeHttp01Challenge,
+    ) -> CreateResult<AcmeHttp01Challenge> {
+        use nexus_db_schema::schema::acme_http01_challenge::dsl;
+
+        opctx.authorize(authz::Action::Modify, &authz::FLEET).await?;
+        diesel::insert_into(dsl::acme_http01_challenge)
+            .values(challenge)
+            .on_conflict(dsl::token)
+            .do_update()
+            .set(dsl::time_created.eq(dsl::time_created))
+            .returning(AcmeHttp01Challenge::as_returning())
+            .get_result_async(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))
+    }
+
+    /// Look up an outstanding HTTP-01 challenge by its token
+    ///
+    /// This performs no authorization check.  It's used to answer
+    /// unauthenticated requests from the ACME server, and the key
+    /// authorization it returns is not secret: it's only useful to whoever
+    /// holds the account key.
+    pub async fn acme_http01_challenge_fetch(
+        &self,
+        opctx: &OpContext,
+        token: &str,
+    ) -> Result<Option<AcmeHttp01Challenge>, Error> {
+        use nexus_db_schema::schema::acme_http01_challenge::dsl;
+
+        dsl::acme_http01_challenge
+            .filter(dsl::token.eq(token.to_string()))
+            .select(AcmeHttp01Challenge::as_select())
+            .first_async(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .optional()
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))
+    }
+
+    /// Remove an HTTP-01 challenge once the ACME server has decided it
+    pub async fn acme_http01_challenge_delete(
+        &self,
+        opctx: &OpContext,
+        token: &str,
+    ) -> DeleteResult {
+        use nexus_db_schema::schema::acme_http01_challenge::dsl;
+
+        opctx.authorize(authz::Action::Modify, &authz::FLEET).await?;
+        diesel::delete(dsl::acme_http01_challenge)
+            .filter(dsl::token.eq(token.to_string()))
+            .execute_async(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))?;
+        Ok(())
+    }
+
+    /// Publish `value` as a TXT record of `name` in the external DNS zones, to
+    /// answer a DNS-01 challenge
+    ///
+    /// `name` is relative to the zones (e.g., `_acme-challenge.my-silo.sys`).
+    /// Values already published for `name` are kept: several Nexus instances
+    /// may be answering challenges for the same name at once.
+    ///
+    /// Like other DNS changes, this takes effect once the external DNS
+    /// propagation task has sent it to the DNS servers.
+    pub async fn acme_dns01_challenge_create(
+        &self,
+        opctx: &OpContext,
+        creator: &str,
+        name: &str,
+        value: &str,
+    ) -> Result<(), Error> {
+        self.acme_dns01_challenge_update(opctx, creator, name, value, true)
+            .await
+    }
+
+    /// Withdraw a TXT record published by
+    /// [`DataStore::acme_dns01_challenge_create()`]
+    pub async fn acme_dns01_challenge_delete(
+        &self,
+        opctx: &OpContext,
+        creator: &str,
+        name: &str,
+        value: &str,
+    ) -> DeleteResult {
+        self.acme_dns01_challenge_update(opctx, creator, name, value, false)
+            .await
+    }
+
+    async fn acme_dns01_challenge_update(
+        &self,
+        opctx: &OpContext,
+        creator: &str,
+        name: &str,
+        value: &str,
+        publish: bool,
+    ) -> Result<(), Error> {
+        use nexus_db_schema::schema::dns_name::dsl;
+
+        let zone_ids: Vec<Uuid> = self
+            .dns_zones_list_all(opctx, DnsGroup::External)
+            .await?
+            .into_iter()
+            .filter(|zone| !is_reverse_zone(&zone.zone_name))
+            .map(|zone| zone.id)
+            .collect();
+        let conn = self.pool_connection_authorized(opctx).await?;
+
+        // This method uses nested transactions, which are not supported
+        // with retryable transactions.
+        self.transaction_non_retry_wrapper("acme_dns01_challenge_update")
+            .transaction(&conn, |conn| async move {
+                // Names are the same in every forward zone of a DNS group, so
+                // any one of them tells us what's published now.
+                let existing = dsl::dns_name
+                    .filter(dsl::dns_zone_id.eq_any(zone_ids))
+                    .filter(dsl::name.eq(name.to_string()))
+                    .filter(dsl::version_removed.is_null())
+                    .select(DnsName::as_select())
+                    .first_async(&conn)
+                    .await
+                    .optional()?;
+                let mut values: Vec<String> = match &existing {
+                    Some(dns_name) => dns_name
+                        .records()?
+                        .into_iter()
+                        .filter_map(|record| match record {
+                            DnsRecord::Txt(text) => Some(text),
+                            _ => None,
+                        })
+                        .collect(),
+                    None => Vec::new(),
+                };
+
+                let present = values.iter().any(|v| v == value);
+                if present == publish {
+                    return Ok(());
+                }
+                let comment = if publish {
+                    values.push(value.to_string());
+                    format!("ACME DNS-01 challenge: publish {name}")
+                } else {
+                    values.retain(|v| v != value);
+                    format!("ACME DNS-01 challenge: withdraw {name}")
+                };
+
+                let mut update = DnsVersionUpdateBuilder::new(
+                    DnsGroup::External,
+                    comment,
+                    creator.to_string(),
+                );
+                if existing.is_some() {
+                    update.remove_name(name.to_string())?;
+                }
+                if !values.is_empty() {
+                    update.add_name(
+                        name.to_string(),
+                        values.into_iter().map(DnsRecord::Txt).collect(),
+                    )?;
+                }
+                self.dns_update_incremental(opctx, &conn, update).await
+            })
+            .await
+            .map_err(|e| match e {
+                TransactionError::CustomError(e) => e,
+                TransactionError::Database(e) => {
+                    public_error_from_diesel(e, ErrorHandler::Server)
+                }
+            })
+    }
+
+    /// List all certificates obtained from an ACME server, making as many
+    /// queries as needed to get them all
+    pub async fn acme_certificate_list_all_batched(
+        &self,
+        opctx: &OpContext,
+    ) -> ListResultVec<Certificate> {
+        use nexus_db_schema::schema::certificate::dsl;
+
+        opctx.authorize(authz::Action::ListChildren, &authz::FLEET).await?;
+        opctx.check_complex_operations_allowed()?;
+        let conn = self.pool_connection_authorized(opctx).await?;
+        let mut certificates = Vec::new();
+        let mut paginator = Paginator::new(
+            SQL_BATCH_SIZE,
+            dropshot::PaginationOrder::Ascending,
+        );
+        while let Some(p) = paginator.next() {
+            let batch =
+                paginated(dsl::certificate, dsl::id, &p.current_pagparams())
+                    .filter(dsl::time_deleted.is_null())
+                    .filter(dsl::acme_issued.eq(true))
+                    .select(Certificate::as_select())
+                    .load_async(&*conn)
+                    .await
+                    .map_err(|e| {
+                        public_error_from_diesel(e, ErrorHandler::Server)
+                    })?;
+            paginator = p.found_batch(&batch, &|c: &Certificate| c.id());
+            certificates.extend(batch);
+        }
+        Ok(certificates)
+    }
+
+    /// Store a certificate obtained from an ACME server for a Silo's external
+    /// API endpoint
+    pub async fn acme_certificate_create(
+        &self,
+        opctx: &OpContext,
+        mut certificate: Certificate,
+    ) -> CreateResult<Certificate> {
+        use nexus_db_schema::schema::certificate::dsl;
+
+        opctx.authorize(authz::Action::Modify, &authz::FLEET).await?;
+        certificate.acme_issued = true;
+        certificate.service = ServiceKind::Nexus;
+        let name = certificate.name().clone();
+        diesel::insert_into(dsl::certificate)
+            .values(certificate)
+            .returning(Certificate::as_returning())
+            .get_result_async(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .map_err(|e| {
+                public_error_from_diesel(
+                    e,
+                    ErrorHandler::Conflict(
+                        ResourceType::Certificate,
+                        name.as_str(),
+                    ),
+                )
+            })
+    }
+
+    /// Delete the certificates obtained from an ACME server for `silo_id` that
+    /// were stored before `replacement`
+    ///
+    /// Several Nexus instances may each renew a Silo's certificate at about
+    /// the same time.  Each deletes only the certificates older than its own,
+    /// so the newest always survives.
+    ///
+    /// Returns the number of certificates deleted.
+    pub async fn acme_certificate_delete_superseded(
+        &self,
+        opctx: &OpContext,
+        silo_id: Uuid,
+        replacement: &Certificate,
+    ) -> Result<usize, Error> {
+        use nexus_db_schema::schema::certificate::dsl;
+
+        opctx.authorize(authz::Action::Modify, &authz::FLEET).await?;
+        diesel::update(dsl::certificate)
+            .filter(dsl::silo_id.eq(silo_id))
+            .filter(dsl::acme_issued.eq(true))
+            .filter(dsl::time_deleted.is_null())
+            .filter(dsl::time_created.lt(replacement.time_created()))
+            .set(dsl::time_deleted.eq(Utc::now()))
+            .execute_async(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::model::DnsVersion;
+    use crate::db::model::DnsZone;
+    use crate::db::model::Generation;
+    use crate::db::pub_test_utils::TestDatabase;
+    use nexus_types::external_api::certificate::CertificateCreate;
+    use nexus_types::external_api::certificate::ServiceUsingCertificate;
+    use nexus_types::silo::DEFAULT_SILO_ID;
+    use omicron_common::api::external::IdentityMetadataCreateParams;
+    use omicron_test_utils::dev;
+
+    #[tokio::test]
+    async fn test_acme_account_create_keeps_first() {
+        let logctx =
+            dev::test_setup_log("test_acme_account_create_keeps_first");
+        let db = TestDatabase::new_with_datastore(&logctx.log).await;
+        let (opctx, datastore) = (db.opctx(), db.datastore());
+        let directory_url = "https://acme.example.com/directory";
+
+        assert!(
+            datastore
+                .acme_account_fetch(opctx, directory_url)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let first = datastore
+            .acme_account_create(
+                opctx,
+                AcmeAccount::new(
+                    directory_url.to_string(),
+                    String::from("https://acme.example.com/account/1"),
+                    String::from("first key"),
+                ),
+            )
+            .await
+            .unwrap();
+        assert_eq!(first.account_url, "https://acme.example.com/account/1");
+
+        // A second Nexus registering concurrently gets the first account.
+        let second = datastore
+            .acme_account_create(
+                opctx,
+                AcmeAccount::new(
+                    directory_url.to_string(),
+                    String::from("https://acme.example.com/account/2"),
+                    String::from("second key"),
+                ),
+            )
+            .await
+            .unwrap();
+        assert_eq!(second.account_url, first.account_url);
+        assert_eq!(second.key_pem, "first key");
+
+        let fetched = datastore
+            .acme_account_fetch(opctx, directory_url)
+            .await
+            .unwrap()
+            .expect("account was recorded");
+        assert_eq!(fetched.account_url, first.account_url);
+
+        db.terminate().await;
+        logctx.cleanup_successful();
+    }
+
+    #[tokio::test]
+    async fn test_acme_http01_challenges() {
+        let logctx = dev::test_setup_log("test_acme_http01_challenges");
+        let db = TestDatabase::new_with_datastore(&logctx.log).await;
+        let (opctx, datastore) = (db.opctx(), db.datastore());
+
+        datastore
+            .acme_http01_challenge_create(
+                opctx,
+                AcmeHttp01Challenge::new(
+                    String::from("token"),
+                    String::from("token.thumbprint"),
+                ),
+            )
+            .await
+            .unwrap();
+        let challenge = datastore
+            .acme_http01_challenge_fetch(opctx, "token")
+            .await
+            .unwrap()
+            .expect("challenge was recorded");
+        assert_eq!(challenge.key_authorization, "token.thumbprint");
+        assert!(
+            datastore
+                .acme_http01_challenge_fetch(opctx, "other")
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        datastore.acme_http01_challenge_delete(opctx, "token").await.unwrap();
+        assert!(
+            datastore
+                .acme_http01_challenge_fetch(opctx, "token")
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        db.terminate().await;
+        logctx.cleanup_successful();
+    }
+
+    #[tokio::test]
+    async fn test_acme_dns01_challenges() {
+        let logctx = dev::test_setup_log("test_acme_dns01_challenges");
+        let db = TestDatabase::new_with_datastore(&logctx.log).await;
+        let (opctx, datastore) = (db.opctx(), db.datastore());
+
+        // Set up an empty external DNS zone.
+        let conn = datastore.pool_connection_for_tests().await.unwrap();
+        {
+            use nexus_db_schema::schema::dns_zone::dsl;
+            diesel::insert_into(dsl::dns_zone)
+                .values(DnsZone {
+                    id: Uuid::new_v4(),
+                    time_created: Utc::now(),
+                    dns_group: DnsGroup::External,
+                    zone_name: String::from("oxide.test"),
+                })
+                .execute_async(&*conn)
+                .await
+                .unwrap();
+        }
+        {
+            use nexus_db_schema::schema::dns_version::dsl;
+            diesel::insert_into(dsl::dns_version)
+                .values(DnsVersion {
+                    dns_group: DnsGroup::External,
+                    version: Generation::new(),
+                    time_created: Utc::now(),
+                    creator: String::from("test suite"),
+                    comment: String::from("initial version"),
+                })
+                .execute_async(&*conn)
+                .await
+                .unwrap();
+        }
+
+        let name = "_acme-challenge.my-silo.sys";
+        let txt_records = || async {
+            let config = datastore
+                .dns_config_read(opctx, DnsGroup::External)
+                .await
+                .unwrap();
+            let records = config
+                .zones
+                .first()
+                .and_then(|zone| zone.records.get(name).cloned())
+                .unwrap_or_default();
+            (u64::from(config.generation), records)
+        };
+
+        // Challenges for the same name are published side by side, and
+        // publishing one again changes nothing.
+        for value in ["first", "second", "first"] {
+            datastore
+                .acme_dns01_challenge_create(opctx, "test suite", name, value)
+                .await
+                .unwrap();
+        }
+        assert_eq!(
+            txt_records().await,
+            (
+                3,
+                vec![
+                    DnsRecord::Txt(String::from("first")),
+                    DnsRecord::Txt(String::from("second")),
+                ]
+            )
+        );
+
+        // Withdrawing the last one removes the name.
+        datastore
+            .acme_dns01_challenge_delete(opctx, "test suite", name, "first")
+            .await
+            .unwrap();
+        assert_eq!(
+            txt_records().await,
+            (4, vec![DnsRecord::Txt(String::from("second"))])
+        );
+        for _ in 0..2 {
+            datastore
+                .acme_dns01_challenge_delete(
+                    opctx,
+                    "test suite",
+                    name,
+                    "second",
+                )
+                .await
+                .unwrap();
+        }
+        assert_eq!(txt_records().await, (5, vec![]));
+
+        db.terminate().await;
+        logctx.cleanup_successful();
+    }
+
+    #[tokio::test]
+    async fn test_acme_certificate_delete_superseded() {
+        let logctx =
+            dev::test_setup_log("test_acme_certificate_delete_superseded");
+        let db = TestDatabase::new_with_datastore(&logctx.log).await;
+        let (opctx, datastore) = (db.opctx(), db.datastore());
+
+        let mut certificates = Vec::new();
+        for name in ["acme-1", "acme-2"] {
+            let certificate = Certificate::new_unvalidated(
+                DEFAULT_SILO_ID,
+                Uuid::new_v4(),
+                ServiceKind::Nexus,
+                CertificateCreate {
+                    identity: IdentityMetadataCreateParams {
+                        name: name.parse().unwrap(),
+                        description: String::new(),
+                    },
+                    cert: String::from("cert"),
+                    key: String::from("key"),
+                    service: ServiceUsingCertificate::ExternalApi,
+                },
+            );
+            certificates.push(
+                datastore
+                    .acme_certificate_create(opctx, certificate)
+                    .await
+                    .unwrap(),
+            );
+        }
+        assert!(certificates.iter().all(|c| c.acme_issued));
+        assert_eq!(
+            datastore
+                .acme_certificate_list_all_batched(opctx)
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+
+        let deleted = datastore
+            .acme_certificate_delete_superseded(
+                opctx,
+                DEFAULT_SILO_ID,
+                &certificates[1],
+            )
+            .await
+            .unwrap();
+        assert_eq!(deleted, 1);
+        let remaining =
+            datastore.acme_certificate_list_all_batched(opctx).await.unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id(), certificates[1].id());
+
+        db.terminate().await;
+        logctx.cleanup_successful();
+    }
+}
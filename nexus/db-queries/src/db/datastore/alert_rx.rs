@@ -17,6 +17,7 @@ use crate::db::model::AlertGlob;
 use crate::db::model::AlertReceiver;
 use crate::db::model::AlertReceiverIdentity;
 use crate::db::model::AlertRxGlob;
+use crate::db::model::AlertRxKind;
 use crate::db::model::AlertRxSubscription;
 use crate::db::model::AlertSubscriptionKind;
 use crate::db::model::Generation;
@@ -92,6 +93,10 @@ impl DataStore {
                     endpoint: endpoint.to_string(),
                     secret_gen: Generation::new(),
                     subscription_gen: Generation::new(),
+                    kind: AlertRxKind::Webhook,
+                    smtp_from: None,
+                    smtp_to: None,
+                    syslog_facility: None,
                 };
                 let subscriptions = subscriptions.clone();
                 let secret_keys = secrets.clone();
@@ -166,6 +171,128 @@ impl DataStore {
         Ok(WebhookReceiverConfig { rx, secrets, subscriptions })
     }
 
+    pub async fn smtp_rx_create(
+        &self,
+        opctx: &OpContext,
+        params: alert::SmtpCreate,
+    ) -> CreateResult<WebhookReceiverConfig> {
+        let alert::SmtpCreate { identity, host, port, from, to, subscriptions } =
+            params;
+        let rx = AlertReceiver::new_smtp(
+            AlertReceiverUuid::new_v4(),
+            identity,
+            &host,
+            port,
+            from,
+            to,
+        )?;
+        self.secretless_rx_create(opctx, "smtp_rx_create", rx, subscriptions)
+            .await
+    }
+
+    pub async fn syslog_rx_create(
+        &self,
+        opctx: &OpContext,
+        params: alert::SyslogCreate,
+    ) -> CreateResult<WebhookReceiverConfig> {
+        let alert::SyslogCreate {
+            identity,
+            host,
+            port,
+            transport,
+            facility,
+            subscriptions,
+        } = params;
+        let rx = AlertReceiver::new_syslog(
+            AlertReceiverUuid::new_v4(),
+            identity,
+            &host,
+            port,
+            transport,
+            facility,
+        )?;
+        self.secretless_rx_create(opctx, "syslog_rx_create", rx, subscriptions)
+            .await
+    }
+
+    /// Inserts `receiver`, a receiver of a kind which has no secrets, along
+    /// with its initial subscriptions.
+    async fn secretless_rx_create(
+        &self,
+        opctx: &OpContext,
+        txn_name: &'static str,
+        receiver: AlertReceiver,
+        subscriptions: Vec<alert::AlertSubscription>,
+    ) -> CreateResult<WebhookReceiverConfig> {
+        opctx.authorize(authz::Action::CreateChild, &authz::FLEET).await?;
+
+        let conn = self.pool_connection_authorized(opctx).await?;
+        let subscriptions = subscriptions
+            .into_iter()
+            .map(AlertSubscriptionKind::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        let name = receiver.identity.name.clone();
+        let err = OptionalError::new();
+        let rx = self
+            .transaction_retry_wrapper(txn_name)
+            .transaction(&conn, |conn| {
+                // As in `webhook_rx_create`, make a fresh UUID for each
+                // transaction, in case of a UUID collision.
+                let mut receiver = receiver.clone();
+                receiver.identity.id = AlertReceiverUuid::new_v4().into();
+                let subscriptions = subscriptions.clone();
+                let err = err.clone();
+                let name = name.clone();
+                async move {
+                    let rx = diesel::insert_into(rx_dsl::alert_receiver)
+                        .values(receiver)
+                        .returning(AlertReceiver::as_returning())
+                        .get_result_async(&conn)
+                        .await
+                        .map_err(|e| {
+                            err.bail_retryable_or_else(e, |e| {
+                                public_error_from_diesel(
+                                    e,
+                                    ErrorHandler::Conflict(
+                                        ResourceType::AlertReceiver,
+                                        name.as_str(),
+                                    ),
+                                )
+                            })
+                        })?;
+                    let rx_id = rx.identity.id.into();
+                    for subscription in subscriptions {
+                        self.rx_add_subscription_on_conn(
+                            opctx,
+                            rx_id,
+                            subscription,
+                            &conn,
+                        )
+                        .await
+                        .map_err(|e| match e {
+                            TransactionError::CustomError(e) => err.bail(e),
+                            TransactionError::Database(e) => e,
+                        })?;
+                    }
+                    Ok(rx)
+                }
+            })
+            .await
+            .map_err(|e| {
+                if let Some(err) = err.take() {
+                    return err;
+                }
+                public_error_from_diesel(
+                    e,
+                    ErrorHandler::Conflict(
+                        ResourceType::AlertReceiver,
+                        name.as_str(),
+                    ),
+                )
+            })?;
+        Ok(WebhookReceiverConfig { rx, secrets: Vec::new(), subscriptions })
+    }
+
     pub async fn webhook_rx_config_fetch(
         &self,
         opctx: &OpContext,
@@ -366,6 +493,64 @@ impl DataStore {
         Ok(updated.found)
     }
 
+    pub async fn smtp_rx_update(
+        &self,
+        opctx: &OpContext,
+        authz_rx: &authz::AlertReceiver,
+        db_rx: &AlertReceiver,
+        params: alert::SmtpReceiverUpdate,
+    ) -> UpdateResult<AlertReceiver> {
+        opctx.authorize(authz::Action::Modify, authz_rx).await?;
+        let update = db::model::SmtpReceiverUpdate::new(db_rx, params)?;
+        let conn = self.pool_connection_authorized(opctx).await?;
+
+        let rx_id = authz_rx.id().into_untyped_uuid();
+        let updated = diesel::update(rx_dsl::alert_receiver)
+            .filter(rx_dsl::id.eq(rx_id))
+            .filter(rx_dsl::time_deleted.is_null())
+            .filter(rx_dsl::kind.eq(AlertRxKind::Smtp))
+            .set(update)
+            .check_if_exists(rx_id)
+            .execute_and_check(&conn)
+            .await
+            .map_err(|e| {
+                public_error_from_diesel(
+                    e,
+                    ErrorHandler::NotFoundByResource(authz_rx),
+                )
+            })?;
+        Ok(updated.found)
+    }
+
+    pub async fn syslog_rx_update(
+        &self,
+        opctx: &OpContext,
+        authz_rx: &authz::AlertReceiver,
+        db_rx: &AlertReceiver,
+        params: alert::SyslogReceiverUpdate,
+    ) -> UpdateResult<AlertReceiver> {
+        opctx.authorize(authz::Action::Modify, authz_rx).await?;
+        let update = db::model::SyslogReceiverUpdate::new(db_rx, params)?;
+        let conn = self.pool_connection_authorized(opctx).await?;
+
+        let rx_id = authz_rx.id().into_untyped_uuid();
+        let updated = diesel::update(rx_dsl::alert_receiver)
+            .filter(rx_dsl::id.eq(rx_id))
+            .filter(rx_dsl::time_deleted.is_null())
+            .filter(rx_dsl::kind.eq(AlertRxKind::Syslog))
+            .set(update)
+            .check_if_exists(rx_id)
+            .execute_and_check(&conn)
+            .await
+            .map_err(|e| {
+                public_error_from_diesel(
+                    e,
+                    ErrorHandler::NotFoundByResource(authz_rx),
+                )
+            })?;
+        Ok(updated.found)
+    }
+
     pub async fn alert_rx_list(
         &self,
         opctx: &OpContext,
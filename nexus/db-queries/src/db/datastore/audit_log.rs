@@ -53,12 +53,16 @@ impl DataStore {
     /// So instead, we sort by `time_completed`, so that the log for `t0 <= t <
     /// t3` when fetched at time t3 includes entry B only and the results will
     /// not change on future requests for the same time range.
+    ///
+    /// If `resource_id` is provided, only entries for operations that acted
+    /// on that resource are listed.
     pub async fn audit_log_list(
         &self,
         opctx: &OpContext,
         pagparams: &DataPageParams<'_, (DateTime<Utc>, Uuid)>,
         start_time: DateTime<Utc>,
         end_time: Option<DateTime<Utc>>,
+        resource_id: Option<Uuid>,
     ) -> ListResultVec<db::model::AuditLogEntry> {
         opctx.authorize(authz::Action::ListChildren, &authz::AUDIT_LOG).await?;
 
@@ -74,6 +78,11 @@ impl DataStore {
             query = query.filter(audit_log_complete::time_completed.lt(end));
         }
 
+        if let Some(resource_id) = resource_id {
+            query =
+                query.filter(audit_log_complete::resource_id.eq(resource_id));
+        }
+
         query
             .select(AuditLogEntry::as_select())
             .load_async(&*self.pool_connection_authorized(opctx).await?)
@@ -251,13 +260,13 @@ mod tests {
         let t_future: DateTime<Utc> = "2099-01-01T00:00:00Z".parse().unwrap();
 
         let audit_log = datastore
-            .audit_log_list(opctx, &pagparams, t0, None)
+            .audit_log_list(opctx, &pagparams, t0, None, None)
             .await
             .expect("retrieve empty audit log");
         assert_eq!(audit_log.len(), 0);
 
         let audit_log = datastore
-            .audit_log_list(opctx, &pagparams, t_future, None)
+            .audit_log_list(opctx, &pagparams, t_future, None, None)
             .await
             .expect("retrieve empty audit log");
         assert_eq!(audit_log.len(), 0);
@@ -271,6 +280,7 @@ mod tests {
             actor: AuditLogActor::Unauthenticated,
             auth_method: None,
             credential_id: None,
+            request_body: None,
         };
         let entry1 = datastore
             .audit_log_entry_init(opctx, entry1_params.clone().into())
@@ -305,6 +315,7 @@ mod tests {
             actor: AuditLogActor::Unauthenticated,
             auth_method: None,
             credential_id: None,
+            request_body: None,
         };
         let entry2 = datastore
             .audit_log_entry_init(opctx, entry2_params.clone().into())
@@ -315,7 +326,7 @@ mod tests {
 
         // before entry2 is completed, it doesn't come back in the list
         let audit_log = datastore
-            .audit_log_list(opctx, &pagparams, t0, None)
+            .audit_log_list(opctx, &pagparams, t0, None, None)
             .await
             .expect("retrieve audit log");
         assert_eq!(audit_log.len(), 1);
@@ -336,7 +347,7 @@ mod tests {
 
         // get both entries
         let audit_log = datastore
-            .audit_log_list(opctx, &pagparams, t0, None)
+            .audit_log_list(opctx, &pagparams, t0, None, None)
             .await
             .expect("retrieve audit log");
         assert_eq!(audit_log.len(), 2);
@@ -352,7 +363,7 @@ mod tests {
 
         // Only get first entry
         let audit_log = datastore
-            .audit_log_list(opctx, &pagparams, t1, Some(t2))
+            .audit_log_list(opctx, &pagparams, t1, Some(t2), None)
             .await
             .expect("retrieve first audit log entry");
         assert_eq!(audit_log.len(), 1);
@@ -366,7 +377,7 @@ mod tests {
 
         // Only get second entry
         let audit_log = datastore
-            .audit_log_list(opctx, &pagparams, t2, None)
+            .audit_log_list(opctx, &pagparams, t2, None, None)
             .await
             .expect("retrieve second audit log entry");
         assert_eq!(audit_log.len(), 1);
@@ -404,6 +415,7 @@ mod tests {
             actor: AuditLogActor::Unauthenticated,
             auth_method: None,
             credential_id: None,
+            request_body: None,
         };
         // we have to do the from() out here because that's what sets
         // time_completed, and we need them to all have the same time
@@ -441,7 +453,7 @@ mod tests {
         // retrieve both and check the order -- the one with the lower ID
         // should always be first
         let audit_log = datastore
-            .audit_log_list(opctx, &pagparams, t0, None)
+            .audit_log_list(opctx, &pagparams, t0, None, None)
             .await
             .expect("retrieve audit log");
         assert_eq!(audit_log.len(), 4);
@@ -457,7 +469,7 @@ mod tests {
             direction: dropshot::PaginationOrder::Descending,
         };
         let audit_log = datastore
-            .audit_log_list(opctx, &pagparams_desc, t0, None)
+            .audit_log_list(opctx, &pagparams_desc, t0, None, None)
             .await
             .expect("retrieve audit log");
         assert_eq!(audit_log.len(), 4);
@@ -480,6 +492,7 @@ mod tests {
             actor: AuditLogActor::Unauthenticated,
             auth_method: None,
             credential_id: None,
+            request_body: None,
         }
     }
 
@@ -537,7 +550,7 @@ mod tests {
             direction: dropshot::PaginationOrder::Ascending,
         };
         let entries = datastore
-            .audit_log_list(opctx, &pagparams, two_hours_ago, None)
+            .audit_log_list(opctx, &pagparams, two_hours_ago, None, None)
             .await
             .unwrap();
         let timed_out_entry =
@@ -558,7 +571,7 @@ mod tests {
 
         // The entry should still show as timeout, unchanged
         let entries = datastore
-            .audit_log_list(opctx, &pagparams, two_hours_ago, None)
+            .audit_log_list(opctx, &pagparams, two_hours_ago, None, None)
             .await
             .unwrap();
         let found = entries.iter().find(|e| e.id == entry.id).unwrap();
@@ -569,4 +582,74 @@ mod tests {
         db.terminate().await;
         logctx.cleanup_successful();
     }
+
+    #[tokio::test]
+    async fn test_audit_log_filter_by_resource() {
+        let logctx = dev::test_setup_log("test_audit_log_filter_by_resource");
+        let db = TestDatabase::new_with_datastore(&logctx.log).await;
+        let (opctx, datastore) = (db.opctx(), db.datastore());
+
+        let t0 = now_db_precision();
+        let project_id = Uuid::new_v4();
+        let body = serde_json::json!({ "name": "a-project" });
+
+        // One entry records the project and the request body; the other
+        // acts on no particular resource.
+        let mut params = make_entry_params("req-project");
+        params.request_body = Some(body.clone());
+        let entry =
+            datastore.audit_log_entry_init(opctx, params.into()).await.unwrap();
+        let completion =
+            AuditLogCompletionUpdate::from(AuditLogCompletion::Success {
+                http_status_code: 201,
+            })
+            .with_resource(String::from("project"), project_id);
+        datastore
+            .audit_log_entry_complete(opctx, &entry, completion)
+            .await
+            .unwrap();
+
+        let other = datastore
+            .audit_log_entry_init(opctx, make_entry_params("req-other").into())
+            .await
+            .unwrap();
+        datastore
+            .audit_log_entry_complete(
+                opctx,
+                &other,
+                AuditLogCompletion::Success { http_status_code: 200 }.into(),
+            )
+            .await
+            .unwrap();
+
+        let pagparams = DataPageParams {
+            marker: None,
+            limit: NonZeroU32::new(100).unwrap(),
+            direction: dropshot::PaginationOrder::Ascending,
+        };
+        let all = datastore
+            .audit_log_list(opctx, &pagparams, t0, None, None)
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 2);
+
+        let filtered = datastore
+            .audit_log_list(opctx, &pagparams, t0, None, Some(project_id))
+            .await
+            .unwrap();
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].id, entry.id);
+        assert_eq!(filtered[0].resource_type.as_deref(), Some("project"));
+        assert_eq!(filtered[0].resource_id, Some(project_id));
+        assert_eq!(filtered[0].request_body, Some(body));
+
+        let none = datastore
+            .audit_log_list(opctx, &pagparams, t0, None, Some(Uuid::new_v4()))
+            .await
+            .unwrap();
+        assert!(none.is_empty());
+
+        db.terminate().await;
+        logctx.cleanup_successful();
+    }
 }
@@ -0,0 +1,229 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! [`DataStore`] methods related to [`AuditLogSink`]s.
+
+use super::DataStore;
+use crate::authz;
+use crate::context::OpContext;
+use crate::db::model::AuditLogSink;
+use crate::db::model::Name;
+use crate::db::model::to_db_typed_uuid;
+use crate::db::pagination::paginated;
+use crate::db::update_and_check::UpdateAndCheck;
+use async_bb8_diesel::AsyncRunQueryDsl;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use nexus_db_errors::ErrorHandler;
+use nexus_db_errors::public_error_from_diesel;
+use nexus_types::identity::Resource;
+use omicron_common::api::external::CreateResult;
+use omicron_common::api::external::DeleteResult;
+use omicron_common::api::external::ListResultVec;
+use omicron_common::api::external::LookupType;
+use omicron_common::api::external::ResourceType;
+use omicron_common::api::external::UpdateResult;
+use omicron_common::api::external::http_pagination::PaginatedBy;
+use omicron_uuid_kinds::GenericUuid;
+use ref_cast::RefCast;
+use uuid::Uuid;
+
+impl DataStore {
+    pub async fn audit_log_sink_list(
+        &self,
+        opctx: &OpContext,
+        pagparams: &PaginatedBy<'_>,
+    ) -> ListResultVec<AuditLogSink> {
+        opctx.authorize(authz::Action::ListChildren, &authz::FLEET).await?;
+
+        use nexus_db_schema::schema::audit_log_sink::dsl;
+        match pagparams {
+            PaginatedBy::Id(pagparams) => {
+                paginated(dsl::audit_log_sink, dsl::id, &pagparams)
+            }
+            PaginatedBy::Name(pagparams) => paginated(
+                dsl::audit_log_sink,
+                dsl::name,
+                &pagparams.map_name(|n| Name::ref_cast(n)),
+            ),
+        }
+        .filter(dsl::time_deleted.is_null())
+        .select(AuditLogSink::as_select())
+        .load_async(&*self.pool_connection_authorized(opctx).await?)
+        .await
+        .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))
+    }
+
+    pub async fn audit_log_sink_create(
+        &self,
+        opctx: &OpContext,
+        sink: AuditLogSink,
+    ) -> CreateResult<AuditLogSink> {
+        opctx.authorize(authz::Action::CreateChild, &authz::FLEET).await?;
+        let name = sink.name().to_string();
+
+        use nexus_db_schema::schema::audit_log_sink::dsl;
+        diesel::insert_into(dsl::audit_log_sink)
+            .values(sink)
+            .returning(AuditLogSink::as_returning())
+            .get_result_async(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .map_err(|e| {
+                public_error_from_diesel(
+                    e,
+                    ErrorHandler::Conflict(ResourceType::AuditLogSink, &name),
+                )
+            })
+    }
+
+    pub async fn audit_log_sink_delete(
+        &self,
+        opctx: &OpContext,
+        authz_sink: &authz::AuditLogSink,
+    ) -> DeleteResult {
+        opctx.authorize(authz::Action::Delete, authz_sink).await?;
+
+        use nexus_db_schema::schema::audit_log_sink::dsl;
+        diesel::update(dsl::audit_log_sink)
+            .filter(dsl::id.eq(to_db_typed_uuid(authz_sink.id())))
+            .filter(dsl::time_deleted.is_null())
+            .set(dsl::time_deleted.eq(Utc::now()))
+            .check_if_exists::<AuditLogSink>(
+                authz_sink.id().into_untyped_uuid(),
+            )
+            .execute_and_check(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .map_err(|e| {
+                public_error_from_diesel(
+                    e,
+                    ErrorHandler::NotFoundByResource(authz_sink),
+                )
+            })?;
+        Ok(())
+    }
+
+    /// List all sinks
+    ///
+    /// There are expected to be only a handful of sinks, so this does not
+    /// paginate.
+    pub async fn audit_log_sink_list_all(
+        &self,
+        opctx: &OpContext,
+    ) -> ListResultVec<AuditLogSink> {
+        opctx.authorize(authz::Action::ListChildren, &authz::FLEET).await?;
+
+        use nexus_db_schema::schema::audit_log_sink::dsl;
+        dsl::audit_log_sink
+            .filter(dsl::time_deleted.is_null())
+            .order(dsl::id.asc())
+            .select(AuditLogSink::as_select())
+            .load_async(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))
+    }
+
+    /// List sinks that are due for a delivery attempt at `now`, i.e., those
+    /// that are not backing off after a failure
+    ///
+    /// There are expected to be only a handful of sinks, so this does not
+    /// paginate.
+    pub async fn audit_log_sink_list_due(
+        &self,
+        opctx: &OpContext,
+        now: DateTime<Utc>,
+    ) -> ListResultVec<AuditLogSink> {
+        opctx.authorize(authz::Action::ListChildren, &authz::FLEET).await?;
+
+        use nexus_db_schema::schema::audit_log_sink::dsl;
+        dsl::audit_log_sink
+            .filter(dsl::time_deleted.is_null())
+            .filter(
+                dsl::time_next_attempt
+                    .is_null()
+                    .or(dsl::time_next_attempt.le(now)),
+            )
+            .order(dsl::id.asc())
+            .select(AuditLogSink::as_select())
+            .load_async(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))
+    }
+
+    /// Record that all entries up to and including `delivered` have been
+    /// delivered to `sink`
+    ///
+    /// The update only applies if the sink's cursor hasn't moved since `sink`
+    /// was read, so that if multiple Nexus instances deliver to the same
+    /// sink concurrently, the cursor never moves backwards. Returns whether
+    /// the cursor was advanced.
+    pub async fn audit_log_sink_advance(
+        &self,
+        opctx: &OpContext,
+        sink: &AuditLogSink,
+        delivered: (DateTime<Utc>, Uuid),
+    ) -> UpdateResult<bool> {
+        let authz_sink = authz_sink_for(sink);
+        opctx.authorize(authz::Action::Modify, &authz_sink).await?;
+
+        use nexus_db_schema::schema::audit_log_sink::dsl;
+        let (time_completed, id) = delivered;
+        let updated = diesel::update(dsl::audit_log_sink)
+            .filter(dsl::id.eq(to_db_typed_uuid(sink.id())))
+            .filter(dsl::time_deleted.is_null())
+            .filter(dsl::cursor_time_completed.eq(sink.cursor_time_completed))
+            .filter(dsl::cursor_id.eq(sink.cursor_id))
+            .set((
+                dsl::cursor_time_completed.eq(time_completed),
+                dsl::cursor_id.eq(id),
+                dsl::time_last_delivered.eq(Utc::now()),
+                dsl::consecutive_failures.eq(0),
+                dsl::time_next_attempt.eq(None::<DateTime<Utc>>),
+                dsl::last_error.eq(None::<String>),
+            ))
+            .execute_async(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))?;
+        Ok(updated != 0)
+    }
+
+    /// Record a failed attempt to deliver entries to `sink`, which should not
+    /// be retried before `next_attempt`
+    ///
+    /// As with [`DataStore::audit_log_sink_advance`], this has no effect if
+    /// the sink's cursor has moved since `sink` was read.
+    pub async fn audit_log_sink_delivery_failed(
+        &self,
+        opctx: &OpContext,
+        sink: &AuditLogSink,
+        error: String,
+        next_attempt: DateTime<Utc>,
+    ) -> UpdateResult<bool> {
+        let authz_sink = authz_sink_for(sink);
+        opctx.authorize(authz::Action::Modify, &authz_sink).await?;
+
+        use nexus_db_schema::schema::audit_log_sink::dsl;
+        let updated = diesel::update(dsl::audit_log_sink)
+            .filter(dsl::id.eq(to_db_typed_uuid(sink.id())))
+            .filter(dsl::time_deleted.is_null())
+            .filter(dsl::cursor_time_completed.eq(sink.cursor_time_completed))
+            .filter(dsl::cursor_id.eq(sink.cursor_id))
+            .set((
+                dsl::consecutive_failures.eq(dsl::consecutive_failures + 1),
+                dsl::time_next_attempt.eq(next_attempt),
+                dsl::last_error.eq(error),
+            ))
+            .execute_async(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))?;
+        Ok(updated != 0)
+    }
+}
+
+fn authz_sink_for(sink: &AuditLogSink) -> authz::AuditLogSink {
+    authz::AuditLogSink::new(
+        authz::FLEET,
+        sink.id(),
+        LookupType::ById(sink.id().into_untyped_uuid()),
+    )
+}
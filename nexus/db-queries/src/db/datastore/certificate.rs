@@ -5,11 +5,13 @@
 //! [`DataStore`] methods on [`Certificate`]s.
 
 use super::DataStore;
+use super::SQL_BATCH_SIZE;
 use crate::authz;
 use crate::context::OpContext;
 use crate::db::model::Certificate;
 use crate::db::model::Name;
 use crate::db::model::ServiceKind;
+use crate::db::pagination::Paginator;
 use crate::db::pagination::paginated;
 use async_bb8_diesel::AsyncRunQueryDsl;
 use chrono::Utc;
@@ -123,6 +125,38 @@ impl DataStore {
             .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))
     }
 
+    /// List the certificates of all Silos, making as many queries as needed
+    /// to get them all
+    pub async fn certificate_list_all_batched(
+        &self,
+        opctx: &OpContext,
+    ) -> ListResultVec<Certificate> {
+        use nexus_db_schema::schema::certificate::dsl;
+
+        opctx.authorize(authz::Action::ListChildren, &authz::FLEET).await?;
+        opctx.check_complex_operations_allowed()?;
+        let conn = self.pool_connection_authorized(opctx).await?;
+        let mut certificates = Vec::new();
+        let mut paginator = Paginator::new(
+            SQL_BATCH_SIZE,
+            dropshot::PaginationOrder::Ascending,
+        );
+        while let Some(p) = paginator.next() {
+            let batch =
+                paginated(dsl::certificate, dsl::id, &p.current_pagparams())
+                    .filter(dsl::time_deleted.is_null())
+                    .select(Certificate::as_select())
+                    .load_async(&*conn)
+                    .await
+                    .map_err(|e| {
+                        public_error_from_diesel(e, ErrorHandler::Server)
+                    })?;
+            paginator = p.found_batch(&batch, &|c: &Certificate| c.id());
+            certificates.extend(batch);
+        }
+        Ok(certificates)
+    }
+
     pub async fn certificate_delete(
         &self,
         opctx: &OpContext,
@@ -18,6 +18,7 @@ use async_bb8_diesel::AsyncRunQueryDsl;
 use diesel::prelude::*;
 use futures::FutureExt;
 use futures::future::BoxFuture;
+use internal_dns_types::names::is_reverse_zone;
 use nexus_db_errors::ErrorHandler;
 use nexus_db_errors::OptionalError;
 use nexus_db_errors::TransactionError;
@@ -33,6 +34,8 @@ use omicron_common::api::external::ListResultVec;
 use omicron_common::api::external::LookupResult;
 use omicron_common::bail_unless;
 use slog::debug;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::hash_map::Entry;
@@ -311,7 +314,7 @@ impl DataStore {
         {
             use nexus_db_schema::schema::dns_zone::dsl;
             diesel::insert_into(dsl::dns_zone)
-                .values(dns.row_for_zone())
+                .values(dns.rows_for_zones())
                 .on_conflict((dsl::dns_group, dsl::zone_name))
                 .do_nothing()
                 .execute_async(conn)
@@ -511,12 +514,56 @@ impl DataStore {
             comment: update.comment,
         };
 
-        let dns_zone_ids: Vec<_> = zones.iter().map(|z| z.id).collect();
-        let new_names = update
+        // Create any reverse zones that don't exist yet.
+        let new_zones = update
+            .zones_added
+            .iter()
+            .filter(|zone_name| {
+                !zones.iter().any(|z| &z.zone_name == *zone_name)
+            })
+            .map(|zone_name| DnsZone {
+                id: Uuid::new_v4(),
+                time_created: chrono::Utc::now(),
+                dns_group: update.dns_group,
+                zone_name: zone_name.clone(),
+            })
+            .collect::<Vec<_>>();
+        let mut zones = zones;
+        if !new_zones.is_empty() {
+            use nexus_db_schema::schema::dns_zone::dsl;
+            diesel::insert_into(dsl::dns_zone)
+                .values(new_zones.clone())
+                .execute_async(conn)
+                .await?;
+            zones.extend(new_zones);
+        }
+
+        // Changes made with `add_name()` and `remove_name()` apply to all of
+        // the group's forward zones.  The rest apply to one reverse zone each.
+        let forward_zone_ids: Vec<_> = zones
+            .iter()
+            .filter(|z| !is_reverse_zone(&z.zone_name))
+            .map(|z| z.id)
+            .collect();
+        let zone_id_for = |zone_name: &str| -> Result<Uuid, Error> {
+            zones
+                .iter()
+                .find(|z| z.zone_name == zone_name)
+                .map(|z| z.id)
+                .ok_or_else(|| {
+                    Error::internal_error(&format!(
+                        "DNS update changes zone {:?}, which does not exist \
+                        in DNS group {}",
+                        zone_name, update.dns_group,
+                    ))
+                })
+        };
+
+        let mut new_names = update
             .names_added
             .into_iter()
             .flat_map(|(name, records)| {
-                dns_zone_ids.iter().map(move |dns_zone_id| {
+                forward_zone_ids.iter().map(move |dns_zone_id| {
                     DnsName::new(
                         *dns_zone_id,
                         name.clone(),
@@ -527,8 +574,28 @@ impl DataStore {
                 })
             })
             .collect::<Result<Vec<_>, _>>()?;
+        for (zone_name, names) in update.zone_names_added {
+            let dns_zone_id = zone_id_for(&zone_name)?;
+            for (name, records) in names {
+                new_names.push(DnsName::new(
+                    dns_zone_id,
+                    name,
+                    new_version_num,
+                    None,
+                    records,
+                )?);
+            }
+        }
         let ntoadd = new_names.len();
 
+        let mut to_remove = Vec::new();
+        if !update.names_removed.is_empty() {
+            to_remove.push((forward_zone_ids, update.names_removed));
+        }
+        for (zone_name, names) in update.zone_names_removed {
+            to_remove.push((vec![zone_id_for(&zone_name)?], names));
+        }
+
         {
             use nexus_db_schema::schema::dns_version::dsl;
             diesel::insert_into(dsl::dns_version)
@@ -546,25 +613,26 @@ impl DataStore {
             // versions.  If someone is adding *and* removing a name in this
             // update, we would (temporarily) violate that constraint if we did
             // this in the other order.
-            let to_remove = update.names_removed;
-            let ntoremove = to_remove.len() * dns_zone_ids.len();
-            let nremoved = diesel::update(
-                dsl::dns_name
-                    .filter(dsl::dns_zone_id.eq_any(dns_zone_ids))
-                    .filter(dsl::name.eq_any(to_remove))
-                    .filter(dsl::version_removed.is_null()),
-            )
-            .set(dsl::version_removed.eq(new_version_num))
-            .execute_async(conn)
-            .await?;
+            for (dns_zone_ids, names) in to_remove {
+                let ntoremove = names.len() * dns_zone_ids.len();
+                let nremoved = diesel::update(
+                    dsl::dns_name
+                        .filter(dsl::dns_zone_id.eq_any(dns_zone_ids))
+                        .filter(dsl::name.eq_any(names))
+                        .filter(dsl::version_removed.is_null()),
+                )
+                .set(dsl::version_removed.eq(new_version_num))
+                .execute_async(conn)
+                .await?;
 
-            bail_unless!(
-                nremoved == ntoremove,
-                "updated wrong number of dns_name records: expected {}, \
-                actually marked {} for removal",
-                ntoremove,
-                nremoved
-            );
+                bail_unless!(
+                    nremoved == ntoremove,
+                    "updated wrong number of dns_name records: expected {}, \
+                    actually marked {} for removal",
+                    ntoremove,
+                    nremoved
+                );
+            }
 
             // Now add any names being added.
             let nadded = diesel::insert_into(dsl::dns_name)
@@ -601,9 +669,13 @@ impl DataStore {
 /// asynchronously to the DNS servers.  No changes are made (to either the
 /// database or the DNS servers) while you modify this object.
 ///
-/// This object changes all of the zones associated with a particular DNS group
-/// because the assumption right now is that they're equivalent.  (In practice,
-/// we should only ever have one zone in each group right now.)
+/// Names added or removed with `add_name()` and `remove_name()` apply to all of
+/// the forward zones associated with a particular DNS group because the
+/// assumption right now is that they're equivalent.  (In practice, we should
+/// only ever have one forward zone in each group right now.)  Reverse zones
+/// (see [`is_reverse_zone()`]) are different for each group of addresses they
+/// cover, so changes to them are made one zone at a time with
+/// `add_name_in_zone()` and `remove_name_in_zone()`.
 #[derive(Clone, Debug)]
 pub struct DnsVersionUpdateBuilder {
     dns_group: DnsGroup,
@@ -611,6 +683,9 @@ pub struct DnsVersionUpdateBuilder {
     creator: String,
     names_added: HashMap<String, Vec<DnsRecord>>,
     names_removed: HashSet<String>,
+    zones_added: BTreeSet<String>,
+    zone_names_added: BTreeMap<String, HashMap<String, Vec<DnsRecord>>>,
+    zone_names_removed: BTreeMap<String, HashSet<String>>,
 }
 
 impl DnsVersionUpdateBuilder {
@@ -634,6 +709,9 @@ impl DnsVersionUpdateBuilder {
             creator,
             names_added: HashMap::new(),
             names_removed: HashSet::new(),
+            zones_added: BTreeSet::new(),
+            zone_names_added: BTreeMap::new(),
+            zone_names_removed: BTreeMap::new(),
         }
     }
 
@@ -695,6 +773,74 @@ impl DnsVersionUpdateBuilder {
         }
     }
 
+    /// Record that the DNS zone `zone_name` should be created in this group,
+    /// if it does not already exist
+    ///
+    /// This is only supported for reverse zones (see [`is_reverse_zone()`]).
+    /// An existing zone is left alone, so callers need not know whether the
+    /// zone exists already.  (Zones without any names are not reported by
+    /// [`DataStore::dns_config_read()`].)
+    pub fn add_zone(&mut self, zone_name: String) -> Result<(), Error> {
+        self.check_reverse_zone(&zone_name)?;
+        self.zones_added.insert(zone_name);
+        Ok(())
+    }
+
+    /// Like `add_name()`, but for the single reverse zone `zone_name`
+    ///
+    /// `zone_name` must either already exist or be added with `add_zone()`.
+    pub fn add_name_in_zone(
+        &mut self,
+        zone_name: String,
+        name: String,
+        records: Vec<DnsRecord>,
+    ) -> Result<(), Error> {
+        self.check_reverse_zone(&zone_name)?;
+        match self.zone_names_added.entry(zone_name).or_default().entry(name) {
+            Entry::Vacant(entry) => {
+                entry.insert(records);
+                Ok(())
+            }
+            Entry::Occupied(entry) => Err(Error::internal_error(&format!(
+                "DNS update ({:?}) attempted to add name {:?} multiple times",
+                self.comment,
+                entry.key()
+            ))),
+        }
+    }
+
+    /// Like `remove_name()`, but for the single reverse zone `zone_name`
+    pub fn remove_name_in_zone(
+        &mut self,
+        zone_name: String,
+        name: String,
+    ) -> Result<(), Error> {
+        self.check_reverse_zone(&zone_name)?;
+        let names = self.zone_names_removed.entry(zone_name).or_default();
+        if names.contains(&name) {
+            Err(Error::internal_error(&format!(
+                "DNS update ({:?}) attempted to remove name {:?} \
+                multiple times",
+                self.comment, &name,
+            )))
+        } else {
+            assert!(names.insert(name));
+            Ok(())
+        }
+    }
+
+    fn check_reverse_zone(&self, zone_name: &str) -> Result<(), Error> {
+        if is_reverse_zone(zone_name) {
+            Ok(())
+        } else {
+            Err(Error::internal_error(&format!(
+                "DNS update ({:?}) attempted to change zone {:?} on its own, \
+                but only reverse zones can be changed this way",
+                self.comment, zone_name,
+            )))
+        }
+    }
+
     pub fn names_removed(&self) -> impl Iterator<Item = &str> {
         self.names_removed.iter().map(AsRef::as_ref)
     }
@@ -773,6 +919,7 @@ mod test {
     use nexus_types::internal_api::params::Srv;
     use omicron_common::api::external::Error;
     use omicron_test_utils::dev;
+    use std::collections::BTreeSet;
     use std::collections::HashMap;
     use std::net::Ipv6Addr;
     use std::num::NonZeroU32;
@@ -2011,4 +2158,136 @@ mod test {
         db.terminate().await;
         logctx.cleanup_successful();
     }
+
+    #[tokio::test]
+    async fn test_dns_update_reverse_zones() {
+        let logctx = dev::test_setup_log("test_dns_update_reverse_zones");
+        let db = TestDatabase::new_with_datastore(&logctx.log).await;
+        let (opctx, datastore) = (db.opctx(), db.datastore());
+
+        let conn = datastore.pool_connection_for_tests().await.unwrap();
+        let initial_data = InitialDnsGroup::new(
+            DnsGroup::Internal,
+            "my-zone",
+            "test-suite",
+            "test-suite",
+            HashMap::from([(
+                "wendell".to_string(),
+                vec![DnsRecord::Aaaa(Ipv6Addr::LOCALHOST)],
+            )]),
+        );
+        DataStore::load_dns_data(&conn, initial_data)
+            .await
+            .expect("failed to insert initial data");
+
+        // Only reverse zones can be changed on their own.
+        let mut update = DnsVersionUpdateBuilder::new(
+            DnsGroup::Internal,
+            String::from("test-suite-1"),
+            String::from("test-suite-1"),
+        );
+        update.add_zone(String::from("my-zone")).unwrap_err();
+        update
+            .remove_name_in_zone(String::from("my-zone"), "wendell".to_string())
+            .unwrap_err();
+
+        // Changes to a zone that doesn't exist fail.
+        let reverse_zone = String::from("1.0.0.0.ip6.arpa");
+        update
+            .add_name_in_zone(
+                reverse_zone.clone(),
+                String::from("1.0"),
+                vec![DnsRecord::Ptr(String::from("wendell.my-zone"))],
+            )
+            .unwrap();
+        let gen1 = Generation::new();
+        let error = datastore
+            .dns_update_from_version(&opctx, update.clone(), gen1)
+            .await
+            .expect_err("update unexpectedly succeeded");
+        assert!(
+            error.to_string().contains("which does not exist"),
+            "unexpected error: {error:#}"
+        );
+
+        // Adding the zone in the same update makes it work.  Names added with
+        // `add_name()` only go to the forward zone.
+        update.add_zone(reverse_zone.clone()).unwrap();
+        update
+            .add_name(
+                String::from("nelson"),
+                vec![DnsRecord::Aaaa(Ipv6Addr::LOCALHOST)],
+            )
+            .unwrap();
+        datastore
+            .dns_update_from_version(&opctx, update, gen1)
+            .await
+            .expect("failed to update from first generation");
+        let config = datastore
+            .dns_config_read(&opctx, DnsGroup::Internal)
+            .await
+            .expect("failed to read config");
+        // Zones are sorted by name, so the reverse zone comes first.
+        assert_eq!(2, config.zones.len());
+        let forward = &config.zones[1];
+        assert_eq!(forward.zone_name, "my-zone");
+        assert_eq!(
+            forward.records.keys().collect::<BTreeSet<_>>(),
+            BTreeSet::from([&String::from("nelson"), &String::from("wendell")]),
+        );
+        let reverse = &config.zones[0];
+        assert_eq!(reverse.zone_name, reverse_zone);
+        assert_eq!(
+            reverse.records,
+            HashMap::from([(
+                String::from("1.0"),
+                vec![DnsRecord::Ptr(String::from("wendell.my-zone"))],
+            )]),
+        );
+
+        // Adding an existing zone again is fine.  Names removed with
+        // `remove_name()` are only removed from the forward zone, which would
+        // fail if they were looked for in the reverse zone too.
+        let gen2 = Generation(config.generation);
+        let mut update = DnsVersionUpdateBuilder::new(
+            DnsGroup::Internal,
+            String::from("test-suite-2"),
+            String::from("test-suite-2"),
+        );
+        update.add_zone(reverse_zone.clone()).unwrap();
+        update.remove_name(String::from("wendell")).unwrap();
+        update
+            .remove_name_in_zone(reverse_zone.clone(), String::from("1.0"))
+            .unwrap();
+        update
+            .add_name_in_zone(
+                reverse_zone.clone(),
+                String::from("2.0"),
+                vec![DnsRecord::Ptr(String::from("nelson.my-zone"))],
+            )
+            .unwrap();
+        datastore
+            .dns_update_from_version(&opctx, update, gen2)
+            .await
+            .expect("failed to update from second generation");
+        let config = datastore
+            .dns_config_read(&opctx, DnsGroup::Internal)
+            .await
+            .expect("failed to read config");
+        assert_eq!(2, config.zones.len());
+        assert_eq!(
+            config.zones[1].records.keys().collect::<Vec<_>>(),
+            [&String::from("nelson")],
+        );
+        assert_eq!(
+            config.zones[0].records,
+            HashMap::from([(
+                String::from("2.0"),
+                vec![DnsRecord::Ptr(String::from("nelson.my-zone"))],
+            )]),
+        );
+
+        db.terminate().await;
+        logctx.cleanup_successful();
+    }
 }
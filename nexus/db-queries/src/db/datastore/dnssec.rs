@@ -0,0 +1,391 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! [`DataStore`] methods on the DNSSEC keys of the external DNS zones.
+//!
+//! Each signed zone has a key-signing key (KSK) and a zone-signing key (ZSK),
+//! which are rolled over by the `dnssec_keys` background task. All of the
+//! keys share a single generation, which advances whenever any key changes,
+//! so that DNS servers can tell whether they have the latest keys.
+
+use super::DataStore;
+use crate::authz;
+use crate::context::OpContext;
+use crate::db::model::DnssecConfig;
+use crate::db::model::DnssecKey;
+use crate::db::model::DnssecKeyState;
+use crate::db::model::Generation;
+use async_bb8_diesel::AsyncRunQueryDsl;
+use chrono::Utc;
+use diesel::prelude::*;
+use nexus_db_errors::ErrorHandler;
+use nexus_db_errors::public_error_from_diesel;
+use nexus_types::internal_api::params::DnssecConfigParams;
+use nexus_types::internal_api::params::DnssecZone;
+use omicron_common::api::external;
+use omicron_common::api::external::Error;
+use omicron_common::api::external::ListResultVec;
+use omicron_common::api::external::UpdateResult;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+/// Changes to the DNSSEC keys, applied together by
+/// [`DataStore::dnssec_keys_update`]
+#[derive(Clone, Debug, Default)]
+pub struct DnssecKeyChanges {
+    /// Keys to create
+    pub create: Vec<DnssecKey>,
+    /// Published keys to start signing with
+    pub activate: Vec<Uuid>,
+    /// Active keys to stop signing with, while still publishing them
+    pub retire: Vec<Uuid>,
+    /// Keys to stop publishing altogether
+    pub delete: Vec<Uuid>,
+}
+
+impl DnssecKeyChanges {
+    pub fn is_empty(&self) -> bool {
+        self.create.is_empty()
+            && self.activate.is_empty()
+            && self.retire.is_empty()
+            && self.delete.is_empty()
+    }
+}
+
+impl DataStore {
+    /// List the DNSSEC keys of all zones, along with their generation
+    pub async fn dnssec_key_list(
+        &self,
+        opctx: &OpContext,
+    ) -> Result<(external::Generation, Vec<DnssecKey>), Error> {
+        opctx.authorize(authz::Action::Read, &authz::DNS_CONFIG).await?;
+        let conn = self.pool_connection_authorized(opctx).await?;
+        let (config, keys) = self
+            .transaction_retry_wrapper("dnssec_key_list")
+            .transaction(&conn, |conn| async move {
+                use nexus_db_schema::schema::dnssec_config::dsl as config_dsl;
+                use nexus_db_schema::schema::dnssec_key::dsl;
+                let config = config_dsl::dnssec_config
+                    .select(DnssecConfig::as_select())
+                    .get_result_async(&conn)
+                    .await
+                    .optional()?
+                    .unwrap_or_else(initial_dnssec_config);
+                let keys = dsl::dnssec_key
+                    .filter(dsl::time_deleted.is_null())
+                    .order((
+                        dsl::zone_name.asc(),
+                        dsl::role.asc(),
+                        dsl::time_created.asc(),
+                    ))
+                    .select(DnssecKey::as_select())
+                    .load_async(&conn)
+                    .await?;
+                Ok((config, keys))
+            })
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))?;
+        Ok((*config.generation, keys))
+    }
+
+    /// Fetch the DNSSEC keys of all zones, as they are sent to DNS servers
+    pub async fn dnssec_config_read(
+        &self,
+        opctx: &OpContext,
+    ) -> Result<DnssecConfigParams, Error> {
+        let (generation, keys) = self.dnssec_key_list(opctx).await?;
+        Ok(dnssec_config_params(generation, keys))
+    }
+
+    /// Apply `changes` to the DNSSEC keys, advancing their generation
+    ///
+    /// Returns `false` without changing anything if the keys' generation is
+    /// no longer `generation`, as happens when another Nexus changed them
+    /// concurrently.
+    pub async fn dnssec_keys_update(
+        &self,
+        opctx: &OpContext,
+        generation: external::Generation,
+        changes: DnssecKeyChanges,
+    ) -> UpdateResult<bool> {
+        opctx.authorize(authz::Action::Modify, &authz::DNS_CONFIG).await?;
+        if changes.is_empty() {
+            return Ok(true);
+        }
+
+        let conn = self.pool_connection_authorized(opctx).await?;
+        self.transaction_retry_wrapper("dnssec_keys_update")
+            .transaction(&conn, |conn| {
+                let changes = changes.clone();
+                async move {
+                    use nexus_db_schema::schema::dnssec_config::dsl as config_dsl;
+                    use nexus_db_schema::schema::dnssec_key::dsl;
+                    let current = config_dsl::dnssec_config
+                        .select(DnssecConfig::as_select())
+                        .get_result_async(&conn)
+                        .await
+                        .optional()?
+                        .unwrap_or_else(initial_dnssec_config);
+                    if *current.generation != generation {
+                        return Ok(false);
+                    }
+
+                    let now = Utc::now();
+                    if !changes.create.is_empty() {
+                        diesel::insert_into(dsl::dnssec_key)
+                            .values(changes.create)
+                            .execute_async(&conn)
+                            .await?;
+                    }
+                    if !changes.activate.is_empty() {
+                        diesel::update(dsl::dnssec_key)
+                            .filter(dsl::time_deleted.is_null())
+                            .filter(dsl::id.eq_any(changes.activate))
+                            .set((
+                                dsl::state.eq(DnssecKeyState::Active),
+                                dsl::time_activated.eq(now),
+                            ))
+                            .execute_async(&conn)
+                            .await?;
+                    }
+                    if !changes.retire.is_empty() {
+                        diesel::update(dsl::dnssec_key)
+                            .filter(dsl::time_deleted.is_null())
+                            .filter(dsl::id.eq_any(changes.retire))
+                            .set((
+                                dsl::state.eq(DnssecKeyState::Retired),
+                                dsl::time_retired.eq(now),
+                            ))
+                            .execute_async(&conn)
+                            .await?;
+                    }
+                    if !changes.delete.is_empty() {
+                        diesel::update(dsl::dnssec_key)
+                            .filter(dsl::time_deleted.is_null())
+                            .filter(dsl::id.eq_any(changes.delete))
+                            .set(dsl::time_deleted.eq(now))
+                            .execute_async(&conn)
+                            .await?;
+                    }
+
+                    let config = DnssecConfig {
+                        singleton: true,
+                        generation: Generation::from(current.generation.next()),
+                        time_modified: now,
+                    };
+                    diesel::insert_into(config_dsl::dnssec_config)
+                        .values(config.clone())
+                        .on_conflict(config_dsl::singleton)
+                        .do_update()
+                        .set((
+                            config_dsl::generation.eq(config.generation),
+                            config_dsl::time_modified.eq(config.time_modified),
+                        ))
+                        .execute_async(&conn)
+                        .await?;
+                    Ok(true)
+                }
+            })
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))
+    }
+}
+
+/// The keys' generation before any have been created
+fn initial_dnssec_config() -> DnssecConfig {
+    DnssecConfig {
+        singleton: true,
+        generation: Generation::new(),
+        time_modified: Utc::now(),
+    }
+}
+
+/// Returns the keys `keys` grouped by zone, as they are sent to DNS servers
+pub fn dnssec_config_params(
+    generation: external::Generation,
+    keys: Vec<DnssecKey>,
+) -> DnssecConfigParams {
+    let mut zones: BTreeMap<String, DnssecZone> = BTreeMap::new();
+    for key in keys {
+        zones
+            .entry(key.zone_name.clone())
+            .or_insert_with(|| DnssecZone {
+                zone_name: key.zone_name.clone(),
+                keys: Vec::new(),
+            })
+            .keys
+            .push(key.into());
+    }
+    DnssecConfigParams {
+        generation,
+        time_created: Utc::now(),
+        zones: zones.into_values().collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::model::DnssecKeyRole;
+    use crate::db::pub_test_utils::TestDatabase;
+    use nexus_types::internal_api::params;
+    use omicron_test_utils::dev;
+
+    fn key(
+        zone_name: &str,
+        role: DnssecKeyRole,
+        state: DnssecKeyState,
+    ) -> DnssecKey {
+        DnssecKey::new(
+            zone_name.to_string(),
+            role,
+            state,
+            format!("{role} key for {zone_name}"),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_dnssec_keys() {
+        let logctx = dev::test_setup_log("test_dnssec_keys");
+        let db = TestDatabase::new_with_datastore(&logctx.log).await;
+        let (opctx, datastore) = (db.opctx(), db.datastore());
+
+        let initial = datastore
+            .dnssec_config_read(opctx)
+            .await
+            .expect("failed to read config");
+        assert_eq!(u64::from(initial.generation), 1);
+        assert!(initial.zones.is_empty());
+
+        // Creating keys advances the generation.
+        let ksk = key("oxide.test", DnssecKeyRole::Ksk, DnssecKeyState::Active);
+        let zsk = key("oxide.test", DnssecKeyRole::Zsk, DnssecKeyState::Active);
+        let applied = datastore
+            .dnssec_keys_update(
+                opctx,
+                initial.generation,
+                DnssecKeyChanges {
+                    create: vec![ksk.clone(), zsk.clone()],
+                    ..Default::default()
+                },
+            )
+            .await
+            .expect("failed to update keys");
+        assert!(applied);
+        let config = datastore
+            .dnssec_config_read(opctx)
+            .await
+            .expect("failed to read config");
+        assert_eq!(u64::from(config.generation), 2);
+        assert_eq!(config.zones.len(), 1);
+        assert_eq!(config.zones[0].zone_name, "oxide.test");
+        assert_eq!(
+            config.zones[0]
+                .keys
+                .iter()
+                .map(|k| (k.role, k.state))
+                .collect::<Vec<_>>(),
+            [
+                (params::DnssecKeyRole::Ksk, params::DnssecKeyState::Active),
+                (params::DnssecKeyRole::Zsk, params::DnssecKeyState::Active),
+            ]
+        );
+
+        // Changes based on an old generation are not applied.
+        let zsk2 =
+            key("oxide.test", DnssecKeyRole::Zsk, DnssecKeyState::Published);
+        let applied = datastore
+            .dnssec_keys_update(
+                opctx,
+                initial.generation,
+                DnssecKeyChanges {
+                    create: vec![zsk2.clone()],
+                    ..Default::default()
+                },
+            )
+            .await
+            .expect("failed to update keys");
+        assert!(!applied);
+        let (generation, keys) =
+            datastore.dnssec_key_list(opctx).await.expect("failed to list");
+        assert_eq!(generation, config.generation);
+        assert_eq!(keys.len(), 2);
+
+        // Roll the ZSK over: publish its successor, then activate that while
+        // retiring the old one, and finally delete the old one.
+        assert!(
+            datastore
+                .dnssec_keys_update(
+                    opctx,
+                    generation,
+                    DnssecKeyChanges {
+                        create: vec![zsk2.clone()],
+                        ..Default::default()
+                    },
+                )
+                .await
+                .expect("failed to update keys")
+        );
+        assert!(
+            datastore
+                .dnssec_keys_update(
+                    opctx,
+                    generation.next(),
+                    DnssecKeyChanges {
+                        activate: vec![zsk2.id],
+                        retire: vec![zsk.id],
+                        ..Default::default()
+                    },
+                )
+                .await
+                .expect("failed to update keys")
+        );
+        let (generation, keys) =
+            datastore.dnssec_key_list(opctx).await.expect("failed to list");
+        assert_eq!(u64::from(generation), 4);
+        let states = keys
+            .iter()
+            .map(|k| (k.id, k.state, k.time_retired.is_some()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            states,
+            [
+                (ksk.id, DnssecKeyState::Active, false),
+                (zsk.id, DnssecKeyState::Retired, true),
+                (zsk2.id, DnssecKeyState::Active, false),
+            ]
+        );
+
+        // Retired keys are still published, but no longer sign the zone.
+        let config = dnssec_config_params(generation, keys);
+        assert_eq!(
+            config.zones[0].keys[1].state,
+            params::DnssecKeyState::Published
+        );
+
+        assert!(
+            datastore
+                .dnssec_keys_update(
+                    opctx,
+                    generation,
+                    DnssecKeyChanges {
+                        delete: vec![zsk.id],
+                        ..Default::default()
+                    },
+                )
+                .await
+                .expect("failed to update keys")
+        );
+        let (generation, keys) =
+            datastore.dnssec_key_list(opctx).await.expect("failed to list");
+        assert_eq!(u64::from(generation), 5);
+        assert_eq!(
+            keys.iter().map(|k| k.id).collect::<Vec<_>>(),
+            [ksk.id, zsk2.id]
+        );
+
+        db.terminate().await;
+        logctx.cleanup_successful();
+    }
+}
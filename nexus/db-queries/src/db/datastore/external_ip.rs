@@ -287,37 +287,8 @@ impl DataStore {
     ) -> CreateResult<ExternalIp> {
         let ip_id = Uuid::new_v4();
 
-        let (authz_pool, explicit_ip) = match &allocation {
-            FloatingIpAllocation::Explicit { ip } => {
-                let pool = self
-                    .ip_pool_fetch_containing_address(
-                        opctx,
-                        *ip,
-                        IpPoolType::Unicast,
-                    )
-                    .await
-                    .map_err(|e| match e {
-                        Error::ObjectNotFound { .. } => {
-                            Error::invalid_request(format!(
-                                "IP address {ip} is not in any configured pool"
-                            ))
-                        }
-                        other => other,
-                    })?;
-                (pool, Some(*ip))
-            }
-            FloatingIpAllocation::Auto { pool, ip_version } => {
-                let (pool, _pool_version) = self
-                    .resolve_pool_for_allocation(
-                        opctx,
-                        pool.clone(),
-                        IpPoolType::Unicast,
-                        *ip_version,
-                    )
-                    .await?;
-                (pool, None)
-            }
-        };
+        let (authz_pool, explicit_ip) =
+            self.resolve_floating_ip_allocation(opctx, &allocation).await?;
 
         debug!(
             opctx.log,
@@ -349,6 +320,46 @@ impl DataStore {
         self.allocate_external_ip(opctx, data).await
     }
 
+    /// Resolve `allocation` into the pool to allocate an address from, and
+    /// the specific address requested, if any.
+    pub(super) async fn resolve_floating_ip_allocation(
+        &self,
+        opctx: &OpContext,
+        allocation: &FloatingIpAllocation,
+    ) -> Result<(authz::IpPool, Option<IpAddr>), Error> {
+        match allocation {
+            FloatingIpAllocation::Explicit { ip } => {
+                let pool = self
+                    .ip_pool_fetch_containing_address(
+                        opctx,
+                        *ip,
+                        IpPoolType::Unicast,
+                    )
+                    .await
+                    .map_err(|e| match e {
+                        Error::ObjectNotFound { .. } => {
+                            Error::invalid_request(format!(
+                                "IP address {ip} is not in any configured pool"
+                            ))
+                        }
+                        other => other,
+                    })?;
+                Ok((pool, Some(*ip)))
+            }
+            FloatingIpAllocation::Auto { pool, ip_version } => {
+                let (pool, _pool_version) = self
+                    .resolve_pool_for_allocation(
+                        opctx,
+                        pool.clone(),
+                        IpPoolType::Unicast,
+                        *ip_version,
+                    )
+                    .await?;
+                Ok((pool, None))
+            }
+        }
+    }
+
     async fn allocate_external_ip(
         &self,
         opctx: &OpContext,
@@ -5,6 +5,7 @@
 //! [`DataStore`] methods related to [`IdentityProvider`]s.
 
 use super::DataStore;
+use super::SQL_BATCH_SIZE;
 use crate::authz;
 use crate::context::OpContext;
 use crate::db;
@@ -12,6 +13,7 @@ use crate::db::identity::Resource;
 use crate::db::model;
 use crate::db::model::IdentityProvider;
 use crate::db::model::Name;
+use crate::db::pagination::Paginator;
 use crate::db::pagination::paginated;
 use async_bb8_diesel::AsyncRunQueryDsl;
 use diesel::prelude::*;
@@ -167,4 +169,40 @@ impl DataStore {
                 )
             })
     }
+
+    /// List the SAML identity providers of all Silos, making as many queries
+    /// as needed to get them all
+    pub async fn saml_identity_provider_list_all_batched(
+        &self,
+        opctx: &OpContext,
+    ) -> ListResultVec<model::SamlIdentityProvider> {
+        use nexus_db_schema::schema::saml_identity_provider::dsl;
+
+        opctx.authorize(authz::Action::ListChildren, &authz::FLEET).await?;
+        opctx.check_complex_operations_allowed()?;
+        let conn = self.pool_connection_authorized(opctx).await?;
+        let mut providers = Vec::new();
+        let mut paginator = Paginator::new(
+            SQL_BATCH_SIZE,
+            dropshot::PaginationOrder::Ascending,
+        );
+        while let Some(p) = paginator.next() {
+            let batch = paginated(
+                dsl::saml_identity_provider,
+                dsl::id,
+                &p.current_pagparams(),
+            )
+            .filter(dsl::time_deleted.is_null())
+            .select(model::SamlIdentityProvider::as_select())
+            .load_async(&*conn)
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))?;
+            paginator = p
+                .found_batch(&batch, &|idp: &model::SamlIdentityProvider| {
+                    idp.id()
+                });
+            providers.extend(batch);
+        }
+        Ok(providers)
+    }
 }
@@ -1414,6 +1414,7 @@ impl DataStore {
         )
         .await?;
         self.instance_ssh_keys_delete(opctx, instance_id).await?;
+        self.instance_network_tags_delete(opctx, instance_id).await?;
         self.instance_mark_migrations_deleted(opctx, instance_id).await?;
 
         Ok(())
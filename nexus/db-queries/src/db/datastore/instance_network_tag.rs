@@ -0,0 +1,182 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! [`DataStore`] methods related to [`InstanceNetworkTag`]s.
+
+use super::DataStore;
+use crate::authz;
+use crate::context::OpContext;
+use crate::db::model::InstanceNetworkTag;
+use async_bb8_diesel::AsyncRunQueryDsl;
+use diesel::prelude::*;
+use nexus_db_errors::ErrorHandler;
+use nexus_db_errors::public_error_from_diesel;
+use omicron_common::api::external::DeleteResult;
+use omicron_common::api::external::ListResultVec;
+use omicron_common::api::external::Name;
+use omicron_common::api::external::UpdateResult;
+use omicron_uuid_kinds::GenericUuid;
+use omicron_uuid_kinds::InstanceUuid;
+use std::collections::BTreeSet;
+
+impl DataStore {
+    /// List the network tags carried by `authz_instance`, ordered by tag
+    pub async fn instance_network_tags_list(
+        &self,
+        opctx: &OpContext,
+        authz_instance: &authz::Instance,
+    ) -> ListResultVec<InstanceNetworkTag> {
+        opctx.authorize(authz::Action::Read, authz_instance).await?;
+
+        use nexus_db_schema::schema::instance_network_tag::dsl;
+        dsl::instance_network_tag
+            .filter(dsl::instance_id.eq(authz_instance.id()))
+            .order(dsl::tag.asc())
+            .select(InstanceNetworkTag::as_select())
+            .load_async(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))
+    }
+
+    /// Replace the network tags carried by `authz_instance` with `tags`
+    ///
+    /// Duplicate tags are ignored. Returns the new set of tags, ordered by
+    /// tag.
+    pub async fn instance_network_tags_replace(
+        &self,
+        opctx: &OpContext,
+        authz_instance: &authz::Instance,
+        tags: Vec<Name>,
+    ) -> UpdateResult<Vec<InstanceNetworkTag>> {
+        opctx.authorize(authz::Action::Modify, authz_instance).await?;
+
+        let instance_id = authz_instance.id();
+        let rows = tags
+            .into_iter()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(|tag| InstanceNetworkTag::new(instance_id, tag))
+            .collect::<Vec<_>>();
+
+        let conn = self.pool_connection_authorized(opctx).await?;
+        self.transaction_retry_wrapper("instance_network_tags_replace")
+            .transaction(&conn, |conn| {
+                let rows = rows.clone();
+                async move {
+                    use nexus_db_schema::schema::instance_network_tag::dsl;
+                    diesel::delete(dsl::instance_network_tag)
+                        .filter(dsl::instance_id.eq(instance_id))
+                        .execute_async(&conn)
+                        .await?;
+                    diesel::insert_into(dsl::instance_network_tag)
+                        .values(rows)
+                        .execute_async(&conn)
+                        .await?;
+                    Ok(())
+                }
+            })
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))?;
+
+        Ok(rows)
+    }
+
+    /// Delete all network tags carried by the instance `instance_id`
+    ///
+    /// This is used when the instance is destroyed.
+    pub async fn instance_network_tags_delete(
+        &self,
+        opctx: &OpContext,
+        instance_id: InstanceUuid,
+    ) -> DeleteResult {
+        use nexus_db_schema::schema::instance_network_tag::dsl;
+        diesel::delete(dsl::instance_network_tag)
+            .filter(dsl::instance_id.eq(instance_id.into_untyped_uuid()))
+            .execute_async(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::db::pub_test_utils::TestDatabase;
+    use crate::db::pub_test_utils::helpers::create_project;
+    use crate::db::pub_test_utils::helpers::create_stopped_instance_record;
+    use omicron_common::api::external::LookupType;
+    use omicron_test_utils::dev;
+
+    #[tokio::test]
+    async fn test_instance_network_tags_replace() {
+        let logctx = dev::test_setup_log("test_instance_network_tags_replace");
+        let db = TestDatabase::new_with_datastore(&logctx.log).await;
+        let (opctx, datastore) = (db.opctx(), db.datastore());
+
+        let (authz_project, _) =
+            create_project(&opctx, &datastore, "my-project").await;
+        let instance_id = create_stopped_instance_record(
+            &opctx,
+            &datastore,
+            &authz_project,
+            "my-instance",
+        )
+        .await;
+        let authz_instance = authz::Instance::new(
+            authz_project,
+            instance_id.into_untyped_uuid(),
+            LookupType::ById(instance_id.into_untyped_uuid()),
+        );
+        let tag = |s: &str| s.parse::<Name>().unwrap();
+        let tag_names = |tags: Vec<InstanceNetworkTag>| {
+            tags.into_iter().map(|t| t.tag.0).collect::<Vec<_>>()
+        };
+
+        let tags = datastore
+            .instance_network_tags_list(opctx, &authz_instance)
+            .await
+            .expect("should list tags");
+        assert!(tags.is_empty());
+
+        // Duplicates are collapsed, and tags come back in order.
+        let tags = datastore
+            .instance_network_tags_replace(
+                opctx,
+                &authz_instance,
+                vec![tag("web"), tag("db"), tag("web")],
+            )
+            .await
+            .expect("should set tags");
+        assert_eq!(tag_names(tags), vec![tag("db"), tag("web")]);
+
+        let tags = datastore
+            .instance_network_tags_replace(
+                opctx,
+                &authz_instance,
+                vec![tag("frontend")],
+            )
+            .await
+            .expect("should replace tags");
+        assert_eq!(tag_names(tags), vec![tag("frontend")]);
+        let tags = datastore
+            .instance_network_tags_list(opctx, &authz_instance)
+            .await
+            .expect("should list tags");
+        assert_eq!(tag_names(tags), vec![tag("frontend")]);
+
+        datastore
+            .instance_network_tags_delete(opctx, instance_id)
+            .await
+            .expect("should delete tags");
+        let tags = datastore
+            .instance_network_tags_list(opctx, &authz_instance)
+            .await
+            .expect("should list tags");
+        assert!(tags.is_empty());
+
+        db.terminate().await;
+        logctx.cleanup_successful();
+    }
+}
@@ -0,0 +1,692 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! [`DataStore`] methods on [`LoadBalancer`]s and their backends.
+
+use super::DataStore;
+use super::FloatingIpAllocation;
+use super::SQL_BATCH_SIZE;
+use crate::authz;
+use crate::authz::ApiResource;
+use crate::context::OpContext;
+use crate::db::model::ExternalIp;
+use crate::db::model::IncompleteExternalIp;
+use crate::db::model::Ipv4Addr;
+use crate::db::model::Ipv6Addr;
+use crate::db::model::LoadBalancer;
+use crate::db::model::LoadBalancerBackend;
+use crate::db::model::MacAddr;
+use crate::db::model::Name;
+use crate::db::model::NetworkInterfaceKind;
+use crate::db::model::SqlU16;
+use crate::db::model::VmmState;
+use crate::db::model::Vni;
+use crate::db::pagination::Paginator;
+use crate::db::pagination::paginated;
+use async_bb8_diesel::AsyncRunQueryDsl;
+use chrono::Utc;
+use diesel::prelude::*;
+use dropshot::PaginationOrder;
+use nexus_db_errors::ErrorHandler;
+use nexus_db_errors::OptionalError;
+use nexus_db_errors::TransactionError;
+use nexus_db_errors::public_error_from_diesel;
+use nexus_types::external_api::load_balancer::LoadBalancerCreate;
+use nexus_types::identity::Resource;
+use omicron_common::api::external::CreateResult;
+use omicron_common::api::external::DataPageParams;
+use omicron_common::api::external::Error;
+use omicron_common::api::external::ListResultVec;
+use omicron_common::api::external::LookupResult;
+use omicron_common::api::external::LookupType;
+use omicron_common::api::external::ResourceType;
+use omicron_common::api::external::UpdateResult;
+use omicron_common::api::external::http_pagination::PaginatedBy;
+use omicron_uuid_kinds::GenericUuid;
+use omicron_uuid_kinds::PropolisUuid;
+use omicron_uuid_kinds::SledUuid;
+use ref_cast::RefCast;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::net::IpAddr;
+use std::net::SocketAddrV6;
+use uuid::Uuid;
+
+/// A running backend of a load balancer, along with what is needed to route
+/// traffic to it
+#[derive(Clone, Debug)]
+pub struct LoadBalancerBackendTarget {
+    pub instance_id: Uuid,
+    /// The instance's active VMM
+    pub propolis_id: PropolisUuid,
+    /// The sled hosting the active VMM
+    pub sled_id: SledUuid,
+    /// The address of the sled agent on that sled
+    pub sled_agent_address: SocketAddrV6,
+    /// The private address of the instance's primary network interface,
+    /// used for health checks
+    pub private_ip: IpAddr,
+    /// The MAC address of the instance's primary network interface
+    pub mac: MacAddr,
+    /// The VNI of the instance's VPC
+    pub vni: Vni,
+}
+
+// Helper containing database records across the tables we need to join for
+// a backend target.
+#[derive(diesel::Queryable, diesel::Selectable)]
+struct LoadBalancerBackendTargetDetails {
+    #[diesel(select_expression = nexus_db_schema::schema::instance::id)]
+    instance_id: Uuid,
+    #[diesel(select_expression = nexus_db_schema::schema::vmm::id)]
+    vmm_id: Uuid,
+    #[diesel(select_expression = nexus_db_schema::schema::sled::id)]
+    sled_id: Uuid,
+    #[diesel(select_expression = nexus_db_schema::schema::sled::ip)]
+    sled_ip: Ipv6Addr,
+    #[diesel(select_expression = nexus_db_schema::schema::sled::port)]
+    sled_port: SqlU16,
+    #[diesel(select_expression = nexus_db_schema::schema::network_interface::ip)]
+    ipv4: Option<Ipv4Addr>,
+    #[diesel(select_expression = nexus_db_schema::schema::network_interface::ipv6)]
+    ipv6: Option<Ipv6Addr>,
+    #[diesel(select_expression = nexus_db_schema::schema::network_interface::mac)]
+    mac: MacAddr,
+    #[diesel(select_expression = nexus_db_schema::schema::vpc::vni)]
+    vni: Vni,
+}
+
+impl LoadBalancerBackendTargetDetails {
+    fn into_target(self) -> Option<LoadBalancerBackendTarget> {
+        // Prefer the IPv4 address for health checks, since every NIC has at
+        // least one of the two.
+        let private_ip = self
+            .ipv4
+            .map(|ip| IpAddr::from(*ip))
+            .or_else(|| self.ipv6.map(|ip| IpAddr::from(*ip)))?;
+        Some(LoadBalancerBackendTarget {
+            instance_id: self.instance_id,
+            propolis_id: PropolisUuid::from_untyped_uuid(self.vmm_id),
+            sled_id: SledUuid::from_untyped_uuid(self.sled_id),
+            sled_agent_address: SocketAddrV6::new(
+                *self.sled_ip,
+                self.sled_port.into(),
+                0,
+                0,
+            ),
+            private_ip,
+            mac: self.mac,
+            vni: self.vni,
+        })
+    }
+}
+
+impl DataStore {
+    /// Create a load balancer in `authz_project`, allocating its frontend
+    /// address according to `allocation`
+    pub async fn load_balancer_create(
+        &self,
+        opctx: &OpContext,
+        authz_project: &authz::Project,
+        params: LoadBalancerCreate,
+        allocation: FloatingIpAllocation,
+    ) -> CreateResult<(LoadBalancer, ExternalIp)> {
+        opctx.authorize(authz::Action::CreateChild, authz_project).await?;
+
+        let (authz_pool, explicit_ip) =
+            self.resolve_floating_ip_allocation(opctx, &allocation).await?;
+
+        let name = params.identity.name.clone();
+        let load_balancer = LoadBalancer::new(
+            Uuid::new_v4(),
+            authz_project.id(),
+            Uuid::new_v4(),
+            params,
+        );
+        let frontend = IncompleteExternalIp::for_load_balancer(
+            load_balancer.external_ip_id,
+            load_balancer.id(),
+            authz_pool.id(),
+            explicit_ip,
+        );
+
+        let err = OptionalError::new();
+        let conn = self.pool_connection_authorized(opctx).await?;
+        self.transaction_retry_wrapper("load_balancer_create")
+            .transaction(&conn, |conn| {
+                let err = err.clone();
+                let load_balancer = load_balancer.clone();
+                let frontend = frontend.clone();
+                let name = name.clone();
+                async move {
+                    use nexus_db_schema::schema::load_balancer::dsl;
+
+                    let load_balancer = diesel::insert_into(dsl::load_balancer)
+                        .values(load_balancer)
+                        .returning(LoadBalancer::as_returning())
+                        .get_result_async(&conn)
+                        .await
+                        .map_err(|e| {
+                            err.bail_retryable_or_else(e, |e| {
+                                public_error_from_diesel(
+                                    e,
+                                    ErrorHandler::Conflict(
+                                        ResourceType::LoadBalancer,
+                                        name.as_str(),
+                                    ),
+                                )
+                            })
+                        })?;
+
+                    let frontend = Self::allocate_external_ip_on_connection(
+                        &conn, frontend,
+                    )
+                    .await
+                    .map_err(|e| match e {
+                        TransactionError::Database(e) => e,
+                        TransactionError::CustomError(e) => err.bail(e),
+                    })?;
+
+                    Ok((load_balancer, frontend))
+                }
+            })
+            .await
+            .map_err(|e| {
+                if let Some(err) = err.take() {
+                    return err;
+                }
+                public_error_from_diesel(e, ErrorHandler::Server)
+            })
+    }
+
+    /// List the load balancers in `authz_project`, along with their frontend
+    /// addresses
+    pub async fn load_balancers_list(
+        &self,
+        opctx: &OpContext,
+        authz_project: &authz::Project,
+        pagparams: &PaginatedBy<'_>,
+    ) -> ListResultVec<(LoadBalancer, ExternalIp)> {
+        use nexus_db_schema::schema::load_balancer::dsl;
+
+        opctx.authorize(authz::Action::ListChildren, authz_project).await?;
+
+        let load_balancers = match pagparams {
+            PaginatedBy::Id(pagparams) => {
+                paginated(dsl::load_balancer, dsl::id, &pagparams)
+            }
+            PaginatedBy::Name(pagparams) => paginated(
+                dsl::load_balancer,
+                dsl::name,
+                &pagparams.map_name(|n| Name::ref_cast(n)),
+            ),
+        }
+        .filter(dsl::project_id.eq(authz_project.id()))
+        .filter(dsl::time_deleted.is_null())
+        .select(LoadBalancer::as_select())
+        .get_results_async(&*self.pool_connection_authorized(opctx).await?)
+        .await
+        .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))?;
+
+        self.load_balancers_with_frontends(opctx, load_balancers).await
+    }
+
+    /// List all load balancers in the fleet, along with their frontend
+    /// addresses
+    ///
+    /// This is used by the load balancer background task.
+    pub async fn load_balancers_list_all_batched(
+        &self,
+        opctx: &OpContext,
+    ) -> ListResultVec<(LoadBalancer, ExternalIp)> {
+        use nexus_db_schema::schema::load_balancer::dsl;
+
+        opctx.check_complex_operations_allowed()?;
+
+        let mut load_balancers = Vec::new();
+        let mut paginator =
+            Paginator::new(SQL_BATCH_SIZE, PaginationOrder::Ascending);
+        let conn = self.pool_connection_authorized(opctx).await?;
+        while let Some(p) = paginator.next() {
+            let batch =
+                paginated(dsl::load_balancer, dsl::id, &p.current_pagparams())
+                    .filter(dsl::time_deleted.is_null())
+                    .select(LoadBalancer::as_select())
+                    .get_results_async(&*conn)
+                    .await
+                    .map_err(|e| {
+                        public_error_from_diesel(e, ErrorHandler::Server)
+                    })?;
+            paginator = p.found_batch(&batch, &|lb| lb.id());
+            load_balancers.extend(batch);
+        }
+
+        self.load_balancers_with_frontends(opctx, load_balancers).await
+    }
+
+    /// Fetch the frontend address of each of `load_balancers`
+    async fn load_balancers_with_frontends(
+        &self,
+        opctx: &OpContext,
+        load_balancers: Vec<LoadBalancer>,
+    ) -> ListResultVec<(LoadBalancer, ExternalIp)> {
+        use nexus_db_schema::schema::external_ip::dsl;
+
+        let mut frontends = dsl::external_ip
+            .filter(
+                dsl::id
+                    .eq_any(load_balancers.iter().map(|lb| lb.external_ip_id)),
+            )
+            .filter(dsl::time_deleted.is_null())
+            .select(ExternalIp::as_select())
+            .get_results_async(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))?
+            .into_iter()
+            .map(|ip| (ip.id, ip))
+            .collect::<BTreeMap<_, _>>();
+
+        load_balancers
+            .into_iter()
+            .map(|lb| {
+                let frontend =
+                    frontends.remove(&lb.external_ip_id).ok_or_else(|| {
+                        Error::internal_error(&format!(
+                            "load balancer {} has no frontend address",
+                            lb.id()
+                        ))
+                    })?;
+                Ok((lb, frontend))
+            })
+            .collect()
+    }
+
+    /// Fetch the frontend address of `load_balancer`
+    pub async fn load_balancer_frontend_fetch(
+        &self,
+        opctx: &OpContext,
+        load_balancer: &LoadBalancer,
+    ) -> LookupResult<ExternalIp> {
+        use nexus_db_schema::schema::external_ip::dsl;
+
+        dsl::external_ip
+            .filter(dsl::id.eq(load_balancer.external_ip_id))
+            .filter(dsl::time_deleted.is_null())
+            .select(ExternalIp::as_select())
+            .get_result_async(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))
+    }
+
+    /// Delete a load balancer, along with its frontend address and backends
+    ///
+    /// Returns the frontend address and the backends that were removed, so
+    /// that the caller can withdraw the address from the data plane.
+    pub async fn load_balancer_delete(
+        &self,
+        opctx: &OpContext,
+        authz_load_balancer: &authz::LoadBalancer,
+    ) -> Result<(ExternalIp, Vec<LoadBalancerBackend>), Error> {
+        opctx.authorize(authz::Action::Delete, authz_load_balancer).await?;
+
+        let load_balancer_id = authz_load_balancer.id();
+        let err = OptionalError::new();
+        let conn = self.pool_connection_authorized(opctx).await?;
+        self.transaction_retry_wrapper("load_balancer_delete")
+            .transaction(&conn, |conn| {
+                let err = err.clone();
+                async move {
+                    use nexus_db_schema::schema::external_ip::dsl as ip_dsl;
+                    use nexus_db_schema::schema::load_balancer::dsl;
+                    use nexus_db_schema::schema::load_balancer_backend::dsl as backend_dsl;
+
+                    let now = Utc::now();
+                    let external_ip_id = diesel::update(dsl::load_balancer)
+                        .filter(dsl::id.eq(load_balancer_id))
+                        .filter(dsl::time_deleted.is_null())
+                        .set(dsl::time_deleted.eq(now))
+                        .returning(dsl::external_ip_id)
+                        .get_result_async::<Uuid>(&conn)
+                        .await
+                        .optional()?
+                        .ok_or_else(|| {
+                            err.bail(authz_load_balancer.not_found())
+                        })?;
+
+                    let frontend = diesel::update(ip_dsl::external_ip)
+                        .filter(ip_dsl::id.eq(external_ip_id))
+                        .filter(ip_dsl::time_deleted.is_null())
+                        .set(ip_dsl::time_deleted.eq(now))
+                        .returning(ExternalIp::as_returning())
+                        .get_result_async(&conn)
+                        .await?;
+
+                    let backends =
+                        diesel::delete(backend_dsl::load_balancer_backend)
+                            .filter(
+                                backend_dsl::load_balancer_id
+                                    .eq(load_balancer_id),
+                            )
+                            .returning(LoadBalancerBackend::as_returning())
+                            .get_results_async(&conn)
+                            .await?;
+
+                    Ok((frontend, backends))
+                }
+            })
+            .await
+            .map_err(|e| {
+                if let Some(err) = err.take() {
+                    return err;
+                }
+                public_error_from_diesel(e, ErrorHandler::Server)
+            })
+    }
+
+    /// List the backends of a load balancer, ordered by instance ID
+    pub async fn load_balancer_backends_list(
+        &self,
+        opctx: &OpContext,
+        authz_load_balancer: &authz::LoadBalancer,
+        pagparams: &DataPageParams<'_, Uuid>,
+    ) -> ListResultVec<LoadBalancerBackend> {
+        use nexus_db_schema::schema::load_balancer_backend::dsl;
+
+        opctx.authorize(authz::Action::Read, authz_load_balancer).await?;
+
+        paginated(dsl::load_balancer_backend, dsl::instance_id, pagparams)
+            .filter(dsl::load_balancer_id.eq(authz_load_balancer.id()))
+            .select(LoadBalancerBackend::as_select())
+            .get_results_async(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))
+    }
+
+    /// Add `authz_instance` as an explicit backend of a load balancer
+    ///
+    /// The caller is responsible for checking that the instance is in the
+    /// load balancer's project. Adding an instance already selected by the
+    /// load balancer's backend tag makes it an explicit backend, so that it
+    /// remains one if its tags change.
+    pub async fn load_balancer_backend_add(
+        &self,
+        opctx: &OpContext,
+        authz_load_balancer: &authz::LoadBalancer,
+        authz_instance: &authz::Instance,
+    ) -> CreateResult<LoadBalancerBackend> {
+        use nexus_db_schema::schema::load_balancer_backend::dsl;
+
+        opctx.authorize(authz::Action::Modify, authz_load_balancer).await?;
+        opctx.authorize(authz::Action::Read, authz_instance).await?;
+
+        let backend = LoadBalancerBackend::new(
+            authz_load_balancer.id(),
+            authz_instance.id(),
+            true,
+        );
+        diesel::insert_into(dsl::load_balancer_backend)
+            .values(backend)
+            .on_conflict((dsl::load_balancer_id, dsl::instance_id))
+            .do_update()
+            .set((dsl::explicit.eq(true), dsl::time_modified.eq(Utc::now())))
+            .returning(LoadBalancerBackend::as_returning())
+            .get_result_async(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))
+    }
+
+    /// Remove the explicit backend `instance_id` from a load balancer
+    ///
+    /// Returns the removed backend, so that the caller can withdraw the
+    /// frontend address from it if it was serving.
+    pub async fn load_balancer_backend_remove(
+        &self,
+        opctx: &OpContext,
+        authz_load_balancer: &authz::LoadBalancer,
+        instance_id: Uuid,
+    ) -> Result<LoadBalancerBackend, Error> {
+        use nexus_db_schema::schema::load_balancer_backend::dsl;
+
+        opctx.authorize(authz::Action::Modify, authz_load_balancer).await?;
+
+        let conn = self.pool_connection_authorized(opctx).await?;
+        let removed = diesel::delete(dsl::load_balancer_backend)
+            .filter(dsl::load_balancer_id.eq(authz_load_balancer.id()))
+            .filter(dsl::instance_id.eq(instance_id))
+            .filter(dsl::explicit.eq(true))
+            .returning(LoadBalancerBackend::as_returning())
+            .get_result_async(&*conn)
+            .await
+            .optional()
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))?;
+        if let Some(removed) = removed {
+            return Ok(removed);
+        }
+
+        // Distinguish a backend selected by the tag from a missing one.
+        let tagged = dsl::load_balancer_backend
+            .filter(dsl::load_balancer_id.eq(authz_load_balancer.id()))
+            .filter(dsl::instance_id.eq(instance_id))
+            .select(dsl::instance_id)
+            .first_async::<Uuid>(&*conn)
+            .await
+            .optional()
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))?;
+        match tagged {
+            Some(_) => Err(Error::invalid_request(
+                "backend is selected by the load balancer's backend tag, and \
+                cannot be removed explicitly",
+            )),
+            None => Err(Error::ObjectNotFound {
+                type_name: ResourceType::LoadBalancerBackend,
+                lookup_type: LookupType::ById(instance_id),
+            }),
+        }
+    }
+
+    /// Bring the backends of `load_balancer` up to date with the instances
+    /// of its project
+    ///
+    /// Adds the instances carrying the load balancer's backend tag, and
+    /// removes backends that were selected by the tag but no longer carry it,
+    /// as well as backends whose instance has been deleted. Returns the
+    /// backends that were removed, so that the caller can withdraw the
+    /// frontend address from them.
+    pub async fn load_balancer_backends_sync(
+        &self,
+        opctx: &OpContext,
+        load_balancer: &LoadBalancer,
+    ) -> ListResultVec<LoadBalancerBackend> {
+        use nexus_db_schema::schema::instance::dsl as instance_dsl;
+        use nexus_db_schema::schema::instance_network_tag::dsl as tag_dsl;
+        use nexus_db_schema::schema::load_balancer_backend::dsl;
+
+        let conn = self.pool_connection_authorized(opctx).await?;
+        let load_balancer_id = load_balancer.id();
+
+        let backends = dsl::load_balancer_backend
+            .filter(dsl::load_balancer_id.eq(load_balancer_id))
+            .select(LoadBalancerBackend::as_select())
+            .get_results_async(&*conn)
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))?;
+
+        let tagged = match &load_balancer.backend_tag {
+            Some(tag) => tag_dsl::instance_network_tag
+                .filter(tag_dsl::tag.eq(tag.clone()))
+                .select(tag_dsl::instance_id)
+                .get_results_async::<Uuid>(&*conn)
+                .await
+                .map_err(|e| {
+                    public_error_from_diesel(e, ErrorHandler::Server)
+                })?,
+            None => Vec::new(),
+        };
+
+        // Only live instances in the load balancer's project may be backends.
+        let live = instance_dsl::instance
+            .filter(instance_dsl::id.eq_any(
+                backends.iter().map(|b| b.instance_id).chain(tagged.clone()),
+            ))
+            .filter(instance_dsl::project_id.eq(load_balancer.project_id))
+            .filter(instance_dsl::time_deleted.is_null())
+            .select(instance_dsl::id)
+            .get_results_async::<Uuid>(&*conn)
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))?
+            .into_iter()
+            .collect::<BTreeSet<_>>();
+        let tagged = tagged
+            .into_iter()
+            .filter(|id| live.contains(id))
+            .collect::<BTreeSet<_>>();
+
+        let (keep, remove): (Vec<_>, Vec<_>) =
+            backends.into_iter().partition(|b| {
+                live.contains(&b.instance_id)
+                    && (b.explicit || tagged.contains(&b.instance_id))
+            });
+        let existing =
+            keep.iter().map(|b| b.instance_id).collect::<BTreeSet<_>>();
+        let add = tagged
+            .difference(&existing)
+            .map(|id| LoadBalancerBackend::new(load_balancer_id, *id, false))
+            .collect::<Vec<_>>();
+
+        if !remove.is_empty() {
+            diesel::delete(dsl::load_balancer_backend)
+                .filter(dsl::load_balancer_id.eq(load_balancer_id))
+                .filter(
+                    dsl::instance_id
+                        .eq_any(remove.iter().map(|b| b.instance_id)),
+                )
+                .execute_async(&*conn)
+                .await
+                .map_err(|e| {
+                    public_error_from_diesel(e, ErrorHandler::Server)
+                })?;
+        }
+        if !add.is_empty() {
+            diesel::insert_into(dsl::load_balancer_backend)
+                .values(add)
+                .on_conflict((dsl::load_balancer_id, dsl::instance_id))
+                .do_nothing()
+                .execute_async(&*conn)
+                .await
+                .map_err(|e| {
+                    public_error_from_diesel(e, ErrorHandler::Server)
+                })?;
+        }
+
+        Ok(remove)
+    }
+
+    /// List the backends of a load balancer that are currently running,
+    /// along with what is needed to route traffic to them
+    pub async fn load_balancer_backend_targets(
+        &self,
+        opctx: &OpContext,
+        load_balancer_id: Uuid,
+    ) -> ListResultVec<LoadBalancerBackendTarget> {
+        use nexus_db_schema::schema::instance;
+        use nexus_db_schema::schema::load_balancer_backend;
+        use nexus_db_schema::schema::network_interface;
+        use nexus_db_schema::schema::sled;
+        use nexus_db_schema::schema::vmm;
+        use nexus_db_schema::schema::vpc;
+        use nexus_db_schema::schema::vpc_subnet;
+
+        let details = load_balancer_backend::dsl::load_balancer_backend
+            .inner_join(instance::dsl::instance.on(
+                instance::dsl::id.eq(load_balancer_backend::dsl::instance_id),
+            ))
+            .inner_join(vmm::dsl::vmm.on(
+                vmm::dsl::id.nullable().eq(instance::dsl::active_propolis_id),
+            ))
+            .inner_join(sled::dsl::sled.on(vmm::dsl::sled_id.eq(sled::dsl::id)))
+            .inner_join(
+                network_interface::dsl::network_interface.on(
+                    network_interface::dsl::kind
+                        .eq(NetworkInterfaceKind::Instance)
+                        .and(
+                            network_interface::dsl::parent_id
+                                .eq(instance::dsl::id),
+                        )
+                        .and(network_interface::dsl::is_primary.eq(true)),
+                ),
+            )
+            .inner_join(
+                vpc_subnet::dsl::vpc_subnet
+                    .on(vpc_subnet::dsl::id
+                        .eq(network_interface::dsl::subnet_id)),
+            )
+            .inner_join(
+                vpc::dsl::vpc.on(vpc::dsl::id.eq(vpc_subnet::dsl::vpc_id)),
+            )
+            .filter(
+                load_balancer_backend::dsl::load_balancer_id
+                    .eq(load_balancer_id),
+            )
+            .filter(instance::dsl::time_deleted.is_null())
+            .filter(vmm::dsl::state.eq(VmmState::Running))
+            .filter(network_interface::dsl::time_deleted.is_null())
+            .filter(vpc_subnet::dsl::time_deleted.is_null())
+            .filter(vpc::dsl::time_deleted.is_null())
+            .select(LoadBalancerBackendTargetDetails::as_select())
+            .get_results_async::<LoadBalancerBackendTargetDetails>(
+                &*self.pool_connection_authorized(opctx).await?,
+            )
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))?;
+
+        Ok(details.into_iter().filter_map(|d| d.into_target()).collect())
+    }
+
+    /// Record the health and serving state of a load balancer backend
+    ///
+    /// This is used by the load balancer background task, and does nothing
+    /// if the backend has since been removed.
+    pub async fn load_balancer_backend_update_state(
+        &self,
+        opctx: &OpContext,
+        backend: &LoadBalancerBackend,
+    ) -> UpdateResult<()> {
+        use nexus_db_schema::schema::load_balancer_backend::dsl;
+
+        diesel::update(dsl::load_balancer_backend)
+            .filter(dsl::load_balancer_id.eq(backend.load_balancer_id))
+            .filter(dsl::instance_id.eq(backend.instance_id))
+            .set((
+                dsl::health.eq(backend.health),
+                dsl::consecutive_successes.eq(backend.consecutive_successes),
+                dsl::consecutive_failures.eq(backend.consecutive_failures),
+                dsl::frontend_vmm_id.eq(backend.frontend_vmm_id),
+                dsl::ports.eq(backend.ports.clone()),
+                dsl::time_modified.eq(Utc::now()),
+            ))
+            .execute_async(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))?;
+        Ok(())
+    }
+
+    /// List all backends of the load balancer `load_balancer_id`
+    ///
+    /// This is used by the load balancer background task.
+    pub async fn load_balancer_backends_list_all(
+        &self,
+        opctx: &OpContext,
+        load_balancer_id: Uuid,
+    ) -> ListResultVec<LoadBalancerBackend> {
+        use nexus_db_schema::schema::load_balancer_backend::dsl;
+
+        dsl::load_balancer_backend
+            .filter(dsl::load_balancer_id.eq(load_balancer_id))
+            .order(dsl::instance_id.asc())
+            .select(LoadBalancerBackend::as_select())
+            .get_results_async(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))
+    }
+}
@@ -71,12 +71,14 @@ use std::net::Ipv6Addr;
 use std::num::NonZeroU32;
 use std::sync::Arc;
 
+mod acme;
 mod address_lot;
 mod affinity;
 mod alert;
 mod alert_rx;
 mod allow_list;
 mod audit_log;
+mod audit_log_sink;
 mod auth;
 mod bfd;
 mod bgp;
@@ -92,6 +94,7 @@ mod deployment;
 mod device_auth;
 mod disk;
 mod dns;
+mod dnssec;
 mod ereport;
 mod external_ip;
 mod external_subnet;
@@ -99,9 +102,11 @@ pub mod fm;
 mod identity_provider;
 mod image;
 pub mod instance;
+mod instance_network_tag;
 mod inventory;
 mod ip_pool;
 mod lldp;
+mod load_balancer;
 mod local_storage;
 mod lookup_interface;
 mod migration;
@@ -125,11 +130,14 @@ mod role;
 mod saga;
 mod scim;
 mod scim_provider_store;
+mod service_account;
 mod silo;
 mod silo_auth_settings;
 mod silo_group;
+mod silo_rate_limit;
 mod silo_user;
 pub mod sled;
+mod sled_evacuation;
 mod sled_instance;
 mod snapshot;
 mod ssh_key;
@@ -151,6 +159,7 @@ mod vmm;
 mod volume;
 mod volume_repair;
 mod vpc;
+mod vpc_dns;
 pub mod webhook_delivery;
 mod zpool;
 
@@ -164,11 +173,14 @@ pub use disk::LocalStorageAllocation;
 pub use disk::LocalStorageDisk;
 pub use dns::DataStoreDnsTest;
 pub use dns::DnsVersionUpdateBuilder;
+pub use dnssec::DnssecKeyChanges;
+pub use dnssec::dnssec_config_params;
 pub use external_ip::FloatingIpAllocation;
 pub use external_subnet::ExternalSubnetBeginOpResult;
 pub use external_subnet::ExternalSubnetCompleteOpResult;
 pub use instance::{InstanceAndActiveVmm, InstanceGestalt};
 pub use inventory::DataStoreInventoryTest;
+pub use load_balancer::LoadBalancerBackendTarget;
 use nexus_db_model::AllSchemaVersions;
 use nexus_types::internal_api::views::HeldDbClaimInfo;
 pub use oximeter::CollectorReassignment;
@@ -199,6 +211,7 @@ pub use user_data_export::*;
 pub use virtual_provisioning_collection::StorageType;
 pub use vmm::VmmStateUpdateResult;
 pub use volume::*;
+pub use vpc::VpcFirewallRuleEdit;
 
 // Number of unique datasets required to back a region.
 // TODO: This should likely turn into a configuration option.
@@ -17,6 +17,7 @@ use diesel::sql_types::BigInt;
 use nexus_db_errors::ErrorHandler;
 use nexus_db_errors::public_error_from_diesel;
 use nexus_db_model::ExternalIp;
+use nexus_db_model::IpNet;
 use nexus_db_model::NatChange;
 use nexus_types::internal_api::views::NatEntryView;
 use omicron_common::api::external::CreateResult;
@@ -183,6 +184,59 @@ impl DataStore {
         Ok(count)
     }
 
+    /// Synchronize the NAT entries for the external address
+    /// `external_address` with `nat_entries`, called by the load balancer
+    /// background task.
+    ///
+    /// Expects the complete set of entries for the address. Soft-deletes
+    /// entries for the address that are not present in `nat_entries`, and
+    /// creates missing entries idempotently.
+    ///
+    /// Returns the number of records added or removed.
+    pub async fn nat_sync_external_address(
+        &self,
+        opctx: &OpContext,
+        external_address: IpNet,
+        nat_entries: &[NatEntryValues],
+    ) -> CreateResult<usize> {
+        use nexus_db_schema::schema::nat_entry::dsl;
+
+        let result: Vec<NatEntry> = dsl::nat_entry
+            .filter(dsl::external_address.eq(external_address))
+            .filter(dsl::version_removed.is_null())
+            .select(NatEntry::as_select())
+            .load_async(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))?;
+
+        let mut keep: Vec<_> = vec![];
+        let mut count = 0;
+        for db_entry in result.iter() {
+            let values = NatEntryValues {
+                external_address: db_entry.external_address,
+                first_port: db_entry.first_port,
+                last_port: db_entry.last_port,
+                sled_address: db_entry.sled_address,
+                vni: db_entry.vni,
+                mac: db_entry.mac,
+            };
+
+            if nat_entries.contains(&values) {
+                keep.push(values);
+            } else {
+                self.nat_delete(opctx, db_entry).await?;
+                count += 1;
+            }
+        }
+
+        for entry in nat_entries.iter().filter(|entry| !keep.contains(entry)) {
+            self.ensure_nat_entry(opctx, entry.clone()).await?;
+            count += 1;
+        }
+
+        Ok(count)
+    }
+
     /// Mark the provided NAT entry as removed in the database.
     ///
     /// This soft-deletes the entry and sets the `version_removed` column. The
@@ -915,4 +969,83 @@ mod test {
         db.terminate().await;
         logctx.cleanup_successful();
     }
+
+    // Test that syncing the entries of one external address only adds and
+    // removes what changed, and leaves other addresses alone.
+    #[tokio::test]
+    async fn nat_sync_external_address() {
+        let logctx = dev::test_setup_log("nat_sync_external_address");
+        let db = TestDatabase::new_with_datastore(&logctx.log).await;
+        let (opctx, datastore) = (db.opctx(), db.datastore());
+
+        let external_address =
+            oxnet::Ipv4Net::host_net("10.0.0.100".parse().unwrap());
+        let other_address =
+            oxnet::Ipv4Net::host_net("10.0.0.101".parse().unwrap());
+        let sled_address =
+            oxnet::Ipv6Net::host_net("fd00:1122:3344:104::1".parse().unwrap());
+        let vni = Vni(external::Vni::random());
+        let mac =
+            MacAddr(external::MacAddr::from_str("A8:40:25:F5:EB:2A").unwrap());
+        let entry = |address: oxnet::Ipv4Net, port: u16| NatEntryValues {
+            external_address: address.into(),
+            first_port: port.into(),
+            last_port: port.into(),
+            sled_address: sled_address.into(),
+            vni,
+            mac,
+        };
+
+        let other = entry(other_address, 80);
+        datastore.ensure_nat_entry(&opctx, other.clone()).await.unwrap();
+
+        let http = entry(external_address, 80);
+        let https = entry(external_address, 443);
+        let changes = datastore
+            .nat_sync_external_address(
+                &opctx,
+                external_address.into(),
+                &[http.clone(), https.clone()],
+            )
+            .await
+            .unwrap();
+        assert_eq!(changes, 2);
+
+        // Syncing the same entries again changes nothing.
+        let changes = datastore
+            .nat_sync_external_address(
+                &opctx,
+                external_address.into(),
+                &[http.clone(), https.clone()],
+            )
+            .await
+            .unwrap();
+        assert_eq!(changes, 0);
+
+        // Replacing one entry removes it and adds its replacement.
+        let ssh = entry(external_address, 22);
+        let changes = datastore
+            .nat_sync_external_address(
+                &opctx,
+                external_address.into(),
+                &[http.clone(), ssh.clone()],
+            )
+            .await
+            .unwrap();
+        assert_eq!(changes, 2);
+        assert!(datastore.nat_find_by_values(&opctx, https).await.is_err());
+
+        // Syncing an empty set removes every entry for the address only.
+        let changes = datastore
+            .nat_sync_external_address(&opctx, external_address.into(), &[])
+            .await
+            .unwrap();
+        assert_eq!(changes, 2);
+        assert!(datastore.nat_find_by_values(&opctx, http).await.is_err());
+        assert!(datastore.nat_find_by_values(&opctx, ssh).await.is_err());
+        assert!(datastore.nat_find_by_values(&opctx, other).await.is_ok());
+
+        db.terminate().await;
+        logctx.cleanup_successful();
+    }
 }
@@ -40,6 +40,7 @@ use nexus_db_model::Ipv4Addr;
 use nexus_db_model::Ipv6Addr;
 use nexus_db_model::ServiceNetworkInterface;
 use nexus_types::identity::Resource;
+use omicron_common::api::external;
 use omicron_common::api::external::DataPageParams;
 use omicron_common::api::external::DeleteResult;
 use omicron_common::api::external::Error;
@@ -688,6 +689,35 @@ impl DataStore {
         .await
     }
 
+    /// Return information about all VNICs in a VPC belonging to instances
+    /// carrying the network tag `tag`, required for the sled agent to
+    /// instantiate firewall rules via OPTE.
+    pub async fn derive_tag_network_interface_info(
+        &self,
+        opctx: &OpContext,
+        authz_vpc: &authz::Vpc,
+        tag: &external::Name,
+    ) -> ListResultVec<sled_agent_types::inventory::NetworkInterface> {
+        opctx.authorize(authz::Action::ListChildren, authz_vpc).await?;
+
+        use nexus_db_schema::schema::instance_network_tag;
+        use nexus_db_schema::schema::network_interface;
+        let tagged_instances = instance_network_tag::table
+            .filter(instance_network_tag::tag.eq(Name::ref_cast(tag)))
+            .select(instance_network_tag::instance_id);
+        self.derive_network_interface_info(
+            opctx,
+            network_interface::table
+                .filter(network_interface::vpc_id.eq(authz_vpc.id()))
+                .filter(
+                    network_interface::kind.eq(NetworkInterfaceKind::Instance),
+                )
+                .filter(network_interface::parent_id.eq_any(tagged_instances))
+                .into_boxed(),
+        )
+        .await
+    }
+
     /// List network interfaces associated with a given instance.
     pub async fn instance_list_network_interfaces(
         &self,
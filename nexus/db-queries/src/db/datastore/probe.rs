@@ -477,7 +477,8 @@ const fn db_ip_kind_to_sled(
         nexus_db_model::IpKind::Ephemeral => {
             sled_agent_client::types::IpKind::Ephemeral
         }
-        nexus_db_model::IpKind::Floating => {
+        nexus_db_model::IpKind::Floating
+        | nexus_db_model::IpKind::LoadBalancer => {
             sled_agent_client::types::IpKind::Floating
         }
     }
@@ -222,6 +222,7 @@ impl DataStore {
     generate_fn_to_ensure_none_in_project!(instance, name, String);
     generate_fn_to_ensure_none_in_project!(disk, name, String);
     generate_fn_to_ensure_none_in_project!(floating_ip, name, String);
+    generate_fn_to_ensure_none_in_project!(load_balancer, name, String);
     generate_fn_to_ensure_none_in_project!(project_image, name, String);
     generate_fn_to_ensure_none_in_project!(snapshot, name, String);
     generate_fn_to_ensure_none_in_project!(vpc, name, String);
@@ -241,6 +242,7 @@ impl DataStore {
         self.ensure_no_instances_in_project(opctx, authz_project).await?;
         self.ensure_no_disks_in_project(opctx, authz_project).await?;
         self.ensure_no_floating_ips_in_project(opctx, authz_project).await?;
+        self.ensure_no_load_balancers_in_project(opctx, authz_project).await?;
         self.ensure_no_project_images_in_project(opctx, authz_project).await?;
         self.ensure_no_snapshots_in_project(opctx, authz_project).await?;
         self.ensure_no_vpcs_in_project(opctx, authz_project).await?;
@@ -59,8 +59,9 @@ impl DataStore {
     /// Fetches all of the externally-visible role assignments for the specified
     /// resource
     ///
-    /// Role assignments for internal identities (e.g., built-in users) are not
-    /// included in this list.
+    /// Role assignments for internal identities (e.g., built-in users) and for
+    /// service accounts are not included in this list.  Service account roles
+    /// are managed through the service account's own policy.
     ///
     /// This function is generic over all resources that can accept roles (e.g.,
     /// Fleet, Silo, etc.).
@@ -94,7 +95,10 @@ impl DataStore {
         dsl::role_assignment
             .filter(dsl::resource_type.eq(resource_type.to_string()))
             .filter(dsl::resource_id.eq(resource_id))
-            .filter(dsl::identity_type.ne(IdentityType::UserBuiltin))
+            .filter(dsl::identity_type.ne_all([
+                IdentityType::UserBuiltin,
+                IdentityType::ServiceAccount,
+            ]))
             .order(dsl::role_name.asc())
             .then_order_by(dsl::identity_id.asc())
             .select(RoleAssignment::as_select())
@@ -106,8 +110,8 @@ impl DataStore {
     /// Removes all existing externally-visble role assignments on
     /// `authz_resource` and adds those specified by `new_assignments`
     ///
-    /// Role assignments for internal identities (e.g., built-in users) are not
-    /// affected.
+    /// Role assignments for internal identities (e.g., built-in users) and for
+    /// service accounts are not affected.
     ///
     /// The expectation is that the caller will have just fetched the role
     /// assignments, modified them, and is giving us the complete new list.
@@ -218,7 +222,10 @@ impl DataStore {
         let delete_old_query = diesel::delete(dsl::role_assignment)
             .filter(dsl::resource_id.eq(resource_id))
             .filter(dsl::resource_type.eq(resource_type.to_string()))
-            .filter(dsl::identity_type.ne(IdentityType::UserBuiltin));
+            .filter(dsl::identity_type.ne_all([
+                IdentityType::UserBuiltin,
+                IdentityType::ServiceAccount,
+            ]));
 
         let insert_new_query = diesel::insert_into(dsl::role_assignment)
             .values(new_assignments)
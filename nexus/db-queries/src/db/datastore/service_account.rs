@@ -0,0 +1,404 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! [`DataStore`] methods related to [`ServiceAccount`]s and their tokens.
+
+use super::DataStore;
+use crate::authz;
+use crate::context::OpContext;
+use crate::db::model::IdentityType;
+use crate::db::model::Name;
+use crate::db::model::RoleAssignment;
+use crate::db::model::ServiceAccount;
+use crate::db::model::ServiceAccountToken;
+use crate::db::model::to_db_typed_uuid;
+use crate::db::pagination::paginated;
+use crate::db::update_and_check::UpdateAndCheck;
+use async_bb8_diesel::AsyncRunQueryDsl;
+use chrono::Utc;
+use diesel::prelude::*;
+use nexus_db_errors::ErrorHandler;
+use nexus_db_errors::OptionalError;
+use nexus_db_errors::public_error_from_diesel;
+use nexus_types::identity::Resource;
+use omicron_common::api::external::CreateResult;
+use omicron_common::api::external::DeleteResult;
+use omicron_common::api::external::Error;
+use omicron_common::api::external::ListResultVec;
+use omicron_common::api::external::LookupResult;
+use omicron_common::api::external::LookupType;
+use omicron_common::api::external::ResourceType;
+use omicron_common::api::external::UpdateResult;
+use omicron_common::api::external::http_pagination::PaginatedBy;
+use omicron_uuid_kinds::GenericUuid;
+use ref_cast::RefCast;
+
+impl DataStore {
+    pub async fn service_account_list(
+        &self,
+        opctx: &OpContext,
+        authz_sa_list: &authz::SiloServiceAccountList,
+        pagparams: &PaginatedBy<'_>,
+    ) -> ListResultVec<ServiceAccount> {
+        opctx.authorize(authz::Action::ListChildren, authz_sa_list).await?;
+
+        use nexus_db_schema::schema::service_account::dsl;
+        match pagparams {
+            PaginatedBy::Id(pagparams) => {
+                paginated(dsl::service_account, dsl::id, &pagparams)
+            }
+            PaginatedBy::Name(pagparams) => paginated(
+                dsl::service_account,
+                dsl::name,
+                &pagparams.map_name(|n| Name::ref_cast(n)),
+            ),
+        }
+        .filter(dsl::silo_id.eq(authz_sa_list.silo().id()))
+        .filter(dsl::time_deleted.is_null())
+        .select(ServiceAccount::as_select())
+        .load_async(&*self.pool_connection_authorized(opctx).await?)
+        .await
+        .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))
+    }
+
+    pub async fn service_account_create(
+        &self,
+        opctx: &OpContext,
+        authz_sa_list: &authz::SiloServiceAccountList,
+        service_account: ServiceAccount,
+    ) -> CreateResult<ServiceAccount> {
+        assert_eq!(authz_sa_list.silo().id(), service_account.silo_id);
+        opctx.authorize(authz::Action::CreateChild, authz_sa_list).await?;
+        let name = service_account.name().to_string();
+
+        use nexus_db_schema::schema::service_account::dsl;
+        diesel::insert_into(dsl::service_account)
+            .values(service_account)
+            .returning(ServiceAccount::as_returning())
+            .get_result_async(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .map_err(|e| {
+                public_error_from_diesel(
+                    e,
+                    ErrorHandler::Conflict(ResourceType::ServiceAccount, &name),
+                )
+            })
+    }
+
+    /// Delete a service account, along with its tokens and role assignments
+    pub async fn service_account_delete(
+        &self,
+        opctx: &OpContext,
+        authz_sa: &authz::ServiceAccount,
+    ) -> DeleteResult {
+        opctx.authorize(authz::Action::Delete, authz_sa).await?;
+
+        let sa_id = authz_sa.id();
+        let conn = self.pool_connection_authorized(opctx).await?;
+        self.transaction_retry_wrapper("service_account_delete")
+            .transaction(&conn, |conn| async move {
+                let now = Utc::now();
+
+                {
+                    use nexus_db_schema::schema::service_account::dsl;
+                    diesel::update(dsl::service_account)
+                        .filter(dsl::id.eq(to_db_typed_uuid(sa_id)))
+                        .filter(dsl::time_deleted.is_null())
+                        .set(dsl::time_deleted.eq(now))
+                        .check_if_exists::<ServiceAccount>(
+                            sa_id.into_untyped_uuid(),
+                        )
+                        .execute_and_check(&conn)
+                        .await?;
+                }
+
+                {
+                    use nexus_db_schema::schema::service_account_token::dsl;
+                    diesel::update(dsl::service_account_token)
+                        .filter(
+                            dsl::service_account_id.eq(to_db_typed_uuid(sa_id)),
+                        )
+                        .filter(dsl::time_deleted.is_null())
+                        .set(dsl::time_deleted.eq(now))
+                        .execute_async(&conn)
+                        .await?;
+                }
+
+                {
+                    use nexus_db_schema::schema::role_assignment::dsl;
+                    diesel::delete(dsl::role_assignment)
+                        .filter(
+                            dsl::identity_type.eq(IdentityType::ServiceAccount),
+                        )
+                        .filter(dsl::identity_id.eq(sa_id.into_untyped_uuid()))
+                        .execute_async(&conn)
+                        .await?;
+                }
+
+                Ok(())
+            })
+            .await
+            .map_err(|e| {
+                public_error_from_diesel(
+                    e,
+                    ErrorHandler::NotFoundByResource(authz_sa),
+                )
+                .internal_context("deleting service account")
+            })
+    }
+
+    /// List all role assignments held by a service account, on any resource
+    pub async fn service_account_role_assignments_list(
+        &self,
+        opctx: &OpContext,
+        authz_sa: &authz::ServiceAccount,
+    ) -> ListResultVec<RoleAssignment> {
+        opctx.authorize(authz::Action::Read, authz_sa).await?;
+
+        use nexus_db_schema::schema::role_assignment::dsl;
+        dsl::role_assignment
+            .filter(dsl::identity_type.eq(IdentityType::ServiceAccount))
+            .filter(dsl::identity_id.eq(authz_sa.id().into_untyped_uuid()))
+            .order(dsl::resource_type.asc())
+            .then_order_by(dsl::resource_id.asc())
+            .then_order_by(dsl::role_name.asc())
+            .select(RoleAssignment::as_select())
+            .load_async(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))
+    }
+
+    /// Replace all role assignments held by a service account
+    ///
+    /// The caller is responsible for checking that the actor may modify the
+    /// policy of every resource on which roles are being granted or revoked.
+    pub async fn service_account_role_assignments_replace(
+        &self,
+        opctx: &OpContext,
+        authz_sa: &authz::ServiceAccount,
+        new_assignments: Vec<RoleAssignment>,
+    ) -> ListResultVec<RoleAssignment> {
+        opctx.authorize(authz::Action::Modify, authz_sa).await?;
+
+        let sa_id = authz_sa.id().into_untyped_uuid();
+        for asgn in &new_assignments {
+            if asgn.identity_type != IdentityType::ServiceAccount
+                || asgn.identity_id != sa_id
+            {
+                return Err(Error::internal_error(
+                    "role assignment is not for this service account",
+                ));
+            }
+        }
+
+        let conn = self.pool_connection_authorized(opctx).await?;
+        self.transaction_retry_wrapper(
+            "service_account_role_assignments_replace",
+        )
+        .transaction(&conn, |conn| {
+            let new_assignments = new_assignments.clone();
+            async move {
+                use nexus_db_schema::schema::role_assignment::dsl;
+                diesel::delete(dsl::role_assignment)
+                    .filter(dsl::identity_type.eq(IdentityType::ServiceAccount))
+                    .filter(dsl::identity_id.eq(sa_id))
+                    .execute_async(&conn)
+                    .await?;
+                diesel::insert_into(dsl::role_assignment)
+                    .values(new_assignments)
+                    .returning(RoleAssignment::as_returning())
+                    .get_results_async(&conn)
+                    .await
+            }
+        })
+        .await
+        .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))
+    }
+
+    pub async fn service_account_token_list(
+        &self,
+        opctx: &OpContext,
+        authz_sa: &authz::ServiceAccount,
+        pagparams: &PaginatedBy<'_>,
+    ) -> ListResultVec<ServiceAccountToken> {
+        opctx.authorize(authz::Action::ListChildren, authz_sa).await?;
+
+        use nexus_db_schema::schema::service_account_token::dsl;
+        match pagparams {
+            PaginatedBy::Id(pagparams) => {
+                paginated(dsl::service_account_token, dsl::id, &pagparams)
+            }
+            PaginatedBy::Name(pagparams) => paginated(
+                dsl::service_account_token,
+                dsl::name,
+                &pagparams.map_name(|n| Name::ref_cast(n)),
+            ),
+        }
+        .filter(dsl::service_account_id.eq(to_db_typed_uuid(authz_sa.id())))
+        .filter(dsl::time_deleted.is_null())
+        .select(ServiceAccountToken::as_select())
+        .load_async(&*self.pool_connection_authorized(opctx).await?)
+        .await
+        .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))
+    }
+
+    pub async fn service_account_token_create(
+        &self,
+        opctx: &OpContext,
+        authz_sa: &authz::ServiceAccount,
+        token: ServiceAccountToken,
+    ) -> CreateResult<ServiceAccountToken> {
+        assert_eq!(authz_sa.id(), token.service_account_id());
+        opctx.authorize(authz::Action::CreateChild, authz_sa).await?;
+        let name = token.name().to_string();
+
+        use nexus_db_schema::schema::service_account_token::dsl;
+        diesel::insert_into(dsl::service_account_token)
+            .values(token)
+            .returning(ServiceAccountToken::as_returning())
+            .get_result_async(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .map_err(|e| {
+                public_error_from_diesel(
+                    e,
+                    ErrorHandler::Conflict(
+                        ResourceType::ServiceAccountToken,
+                        &name,
+                    ),
+                )
+            })
+    }
+
+    pub async fn service_account_token_delete(
+        &self,
+        opctx: &OpContext,
+        authz_token: &authz::ServiceAccountToken,
+    ) -> DeleteResult {
+        opctx.authorize(authz::Action::Delete, authz_token).await?;
+
+        use nexus_db_schema::schema::service_account_token::dsl;
+        diesel::update(dsl::service_account_token)
+            .filter(dsl::id.eq(to_db_typed_uuid(authz_token.id())))
+            .filter(dsl::time_deleted.is_null())
+            .set(dsl::time_deleted.eq(Utc::now()))
+            .check_if_exists::<ServiceAccountToken>(
+                authz_token.id().into_untyped_uuid(),
+            )
+            .execute_and_check(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .map_err(|e| {
+                public_error_from_diesel(
+                    e,
+                    ErrorHandler::NotFoundByResource(authz_token),
+                )
+            })?;
+        Ok(())
+    }
+
+    /// Atomically revoke `db_token` and issue a replacement with the same
+    /// name, expiration, and restrictions but a new secret value
+    pub async fn service_account_token_rotate(
+        &self,
+        opctx: &OpContext,
+        authz_token: &authz::ServiceAccountToken,
+        db_token: &ServiceAccountToken,
+    ) -> UpdateResult<ServiceAccountToken> {
+        opctx.authorize(authz::Action::Modify, authz_token).await?;
+
+        let old_id = authz_token.id();
+        let new_token = db_token.rotated();
+        let err = OptionalError::new();
+        let conn = self.pool_connection_authorized(opctx).await?;
+        self.transaction_retry_wrapper("service_account_token_rotate")
+            .transaction(&conn, |conn| {
+                let new_token = new_token.clone();
+                let err = err.clone();
+                async move {
+                    use nexus_db_schema::schema::service_account_token::dsl;
+                    let updated = diesel::update(dsl::service_account_token)
+                        .filter(dsl::id.eq(to_db_typed_uuid(old_id)))
+                        .filter(dsl::time_deleted.is_null())
+                        .set(dsl::time_deleted.eq(Utc::now()))
+                        .execute_async(&conn)
+                        .await?;
+                    if updated == 0 {
+                        return Err(err.bail(
+                            LookupType::by_id(old_id).into_not_found(
+                                ResourceType::ServiceAccountToken,
+                            ),
+                        ));
+                    }
+                    diesel::insert_into(dsl::service_account_token)
+                        .values(new_token)
+                        .returning(ServiceAccountToken::as_returning())
+                        .get_result_async(&conn)
+                        .await
+                }
+            })
+            .await
+            .map_err(|e| match err.take() {
+                Some(err) => err,
+                None => public_error_from_diesel(e, ErrorHandler::Server),
+            })
+    }
+
+    /// Look up a service account token (and the service account it belongs
+    /// to) by its secret value, for authentication
+    pub async fn service_account_token_lookup_by_token(
+        &self,
+        opctx: &OpContext,
+        token: String,
+    ) -> LookupResult<(
+        authz::ServiceAccountToken,
+        ServiceAccountToken,
+        ServiceAccount,
+    )> {
+        use nexus_db_schema::schema::service_account::dsl as sa_dsl;
+        use nexus_db_schema::schema::service_account_token::dsl;
+        let (db_token, db_sa) = dsl::service_account_token
+            .inner_join(
+                sa_dsl::service_account
+                    .on(sa_dsl::id.eq(dsl::service_account_id)),
+            )
+            .filter(dsl::token.eq(token))
+            .filter(dsl::time_deleted.is_null())
+            .filter(sa_dsl::time_deleted.is_null())
+            .select((
+                ServiceAccountToken::as_select(),
+                ServiceAccount::as_select(),
+            ))
+            .get_result_async(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .map_err(|_e| Error::ObjectNotFound {
+                type_name: ResourceType::ServiceAccountToken,
+                lookup_type: LookupType::ByOther(
+                    "service account token".to_string(),
+                ),
+            })?;
+
+        // we have to construct the authz resource after the lookup because we
+        // don't have its ID on hand until then
+        let authz_silo = authz::Silo::new(
+            authz::FLEET,
+            db_sa.silo_id,
+            LookupType::by_id(db_sa.silo_id),
+        );
+        let authz_sa = authz::ServiceAccount::new(
+            authz_silo,
+            db_sa.id(),
+            LookupType::by_id(db_sa.id()),
+        );
+        let authz_token = authz::ServiceAccountToken::new(
+            authz_sa,
+            db_token.id(),
+            LookupType::by_id(db_token.id()),
+        );
+
+        // As with device access tokens, only the external authenticator is
+        // expected to be able to read tokens this way.
+        opctx.authorize(authz::Action::Read, &authz_token).await?;
+
+        Ok((authz_token, db_token, db_sa))
+    }
+}
@@ -499,6 +499,7 @@ impl DataStore {
                 self.silo_quotas_delete(opctx, &conn, &authz_silo).await?;
                 self.silo_auth_settings_delete(opctx, &conn, &authz_silo)
                     .await?;
+                self.silo_rate_limit_delete(opctx, &conn, &authz_silo).await?;
 
                 self.virtual_provisioning_collection_delete_on_connection(
                     &opctx.log, &conn, id,
@@ -650,6 +651,43 @@ impl DataStore {
 
         debug!(opctx.log, "deleted {} silo IdPs for silo {}", updated_rows, id);
 
+        // delete service accounts and their tokens
+        use nexus_db_schema::schema::service_account::dsl as sa_dsl;
+        use nexus_db_schema::schema::service_account_token::dsl as sa_token_dsl;
+
+        let updated_rows = diesel::update(sa_token_dsl::service_account_token)
+            .filter(
+                sa_token_dsl::service_account_id.eq_any(
+                    sa_dsl::service_account
+                        .filter(sa_dsl::silo_id.eq(id))
+                        .filter(sa_dsl::time_deleted.is_null())
+                        .select(sa_dsl::id),
+                ),
+            )
+            .filter(sa_token_dsl::time_deleted.is_null())
+            .set(sa_token_dsl::time_deleted.eq(now))
+            .execute_async(&*conn)
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))?;
+
+        debug!(
+            opctx.log,
+            "deleted {} service account tokens for silo {}", updated_rows, id
+        );
+
+        let updated_rows = diesel::update(sa_dsl::service_account)
+            .filter(sa_dsl::silo_id.eq(id))
+            .filter(sa_dsl::time_deleted.is_null())
+            .set(sa_dsl::time_deleted.eq(now))
+            .execute_async(&*conn)
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))?;
+
+        debug!(
+            opctx.log,
+            "deleted {} service accounts for silo {}", updated_rows, id
+        );
+
         // delete IP pool links (not IP pools, just the links)
         use nexus_db_schema::schema::ip_pool_resource;
 
@@ -0,0 +1,91 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! [`DataStore`] methods on per-silo rate limits
+
+use super::DataStore;
+use crate::authz;
+use crate::context::OpContext;
+use async_bb8_diesel::AsyncRunQueryDsl;
+use diesel::prelude::*;
+use nexus_db_errors::ErrorHandler;
+use nexus_db_errors::public_error_from_diesel;
+use nexus_db_lookup::DbConnection;
+use nexus_db_model::SiloRateLimit;
+use omicron_common::api::external::DeleteResult;
+use omicron_common::api::external::LookupResult;
+use omicron_common::api::external::UpdateResult;
+
+impl DataStore {
+    /// Fetches the rate limit overrides for a silo
+    ///
+    /// Silos that have never had their rate limit set have no row, in which
+    /// case this returns a limit that overrides nothing.
+    pub async fn silo_rate_limit_view(
+        &self,
+        opctx: &OpContext,
+        authz_silo: &authz::Silo,
+    ) -> LookupResult<SiloRateLimit> {
+        opctx.authorize(authz::Action::Read, authz_silo).await?;
+
+        use nexus_db_schema::schema::silo_rate_limit::dsl;
+        let rate_limit = dsl::silo_rate_limit
+            .filter(dsl::silo_id.eq(authz_silo.id()))
+            .select(SiloRateLimit::as_select())
+            .first_async(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .optional()
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))?;
+        Ok(rate_limit.unwrap_or_else(|| SiloRateLimit::new(authz_silo.id())))
+    }
+
+    /// Replaces the rate limit overrides for a silo
+    ///
+    /// Rate limits protect the fleet as a whole, so only fleet operators may
+    /// change them, even though they're stored per silo.
+    pub async fn silo_rate_limit_update(
+        &self,
+        opctx: &OpContext,
+        authz_silo: &authz::Silo,
+        rate_limit: SiloRateLimit,
+    ) -> UpdateResult<SiloRateLimit> {
+        opctx.authorize(authz::Action::Modify, &authz::FLEET).await?;
+        assert_eq!(rate_limit.silo_id, authz_silo.id());
+
+        use nexus_db_schema::schema::silo_rate_limit::dsl;
+        diesel::insert_into(dsl::silo_rate_limit)
+            .values(rate_limit.clone())
+            .on_conflict(dsl::silo_id)
+            .do_update()
+            .set((
+                dsl::time_modified.eq(rate_limit.time_modified),
+                dsl::requests_per_second.eq(rate_limit.requests_per_second),
+                dsl::burst.eq(rate_limit.burst),
+            ))
+            .returning(SiloRateLimit::as_returning())
+            .get_result_async(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))
+    }
+
+    pub(crate) async fn silo_rate_limit_delete(
+        &self,
+        opctx: &OpContext,
+        conn: &async_bb8_diesel::Connection<DbConnection>,
+        authz_silo: &authz::Silo,
+    ) -> DeleteResult {
+        // As with the Silo's auth settings, the rate limit is an extension of
+        // the Silo itself.
+        opctx.authorize(authz::Action::Delete, authz_silo).await?;
+
+        use nexus_db_schema::schema::silo_rate_limit;
+        diesel::delete(silo_rate_limit::table)
+            .filter(silo_rate_limit::silo_id.eq(authz_silo.id()))
+            .execute_async(conn)
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))?;
+
+        Ok(())
+    }
+}
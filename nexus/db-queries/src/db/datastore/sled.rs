@@ -1018,6 +1018,12 @@ impl DataStore {
                 None
             };
 
+        let must_not_use_sleds: HashSet<SledUuid> =
+            constraints.must_not_select_from().iter().cloned().collect();
+        if !must_not_use_sleds.is_empty() {
+            info!(&log, "reservation excludes sleds {must_not_use_sleds:?}");
+        }
+
         // If any local storage disks have been allocated already, then this
         // constrains VMM placement and where other unallocated local storage
         // must be.
@@ -1153,7 +1159,7 @@ impl DataStore {
             // queries like this.
             let sled_id = SledUuid::from_untyped_uuid(sled_id);
 
-            if fits {
+            if fits && !must_not_use_sleds.contains(&sled_id) {
                 // If there is a Some list of sleds to select from, only add
                 // this target if it is in that list. A None list means that any
                 // sled could be a target.
@@ -3744,6 +3750,62 @@ pub(in crate::db::datastore) mod test {
         logctx.cleanup_successful();
     }
 
+    #[tokio::test]
+    async fn sled_reservation_must_not_select_from() {
+        let logctx =
+            dev::test_setup_log("sled_reservation_must_not_select_from");
+        let db = TestDatabase::new_with_datastore(&logctx.log).await;
+        let (opctx, datastore) = (db.opctx(), db.datastore());
+        let (_authz_project, _project) =
+            create_project(&opctx, &datastore, "project").await;
+
+        const SLED_COUNT: usize = 3;
+        let sleds = create_sleds(&datastore, SLED_COUNT).await;
+
+        // Excluding all but one sled should always select the remaining one.
+        for _ in 0..SLED_COUNT * 2 {
+            let instance = Instance::new();
+            let constraints =
+                db::model::SledReservationConstraintBuilder::new()
+                    .must_not_select_from(&[sleds[0].id(), sleds[1].id()])
+                    .build();
+            let resource = datastore
+                .sled_reservation_create(
+                    &opctx,
+                    instance.id,
+                    PropolisUuid::new_v4(),
+                    instance.resources(),
+                    constraints,
+                    SledReservationReason::MigrationTarget,
+                )
+                .await
+                .expect("reservation should succeed");
+            assert_eq!(SledUuid::from(resource.sled_id), sleds[2].id());
+        }
+
+        // Excluding every sled leaves nowhere to go.
+        let instance = Instance::new();
+        let constraints = db::model::SledReservationConstraintBuilder::new()
+            .must_not_select_from(
+                &sleds.iter().map(|sled| sled.id()).collect::<Vec<_>>(),
+            )
+            .build();
+        datastore
+            .sled_reservation_create(
+                &opctx,
+                instance.id,
+                PropolisUuid::new_v4(),
+                instance.resources(),
+                constraints,
+                SledReservationReason::MigrationTarget,
+            )
+            .await
+            .expect_err("reservation should fail with every sled excluded");
+
+        db.terminate().await;
+        logctx.cleanup_successful();
+    }
+
     async fn lookup_physical_disk(
         datastore: &DataStore,
         id: PhysicalDiskUuid,
@@ -0,0 +1,345 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! [`DataStore`] methods related to [`SledEvacuation`]s.
+
+use super::DataStore;
+use crate::authz;
+use crate::context::OpContext;
+use crate::db::model::SledEvacuation;
+use crate::db::model::SledEvacuationProgress;
+use crate::db::model::SledEvacuationState;
+use crate::db::model::to_db_typed_uuid;
+use async_bb8_diesel::AsyncRunQueryDsl;
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::result::DatabaseErrorKind;
+use diesel::result::Error as DieselError;
+use nexus_db_errors::ErrorHandler;
+use nexus_db_errors::public_error_from_diesel;
+use omicron_common::api::external::CreateResult;
+use omicron_common::api::external::Error;
+use omicron_common::api::external::ListResultVec;
+use omicron_common::api::external::LookupResult;
+use omicron_common::api::external::UpdateResult;
+use omicron_uuid_kinds::SledUuid;
+use uuid::Uuid;
+
+impl DataStore {
+    /// Record the start of an evacuation of `authz_sled`
+    ///
+    /// Fails with a conflict if an evacuation of the sled is already in
+    /// progress.
+    pub async fn sled_evacuation_create(
+        &self,
+        opctx: &OpContext,
+        authz_sled: &authz::Sled,
+        instances_total: u64,
+    ) -> CreateResult<SledEvacuation> {
+        opctx.authorize(authz::Action::Modify, authz_sled).await?;
+
+        use nexus_db_schema::schema::sled_evacuation::dsl;
+        diesel::insert_into(dsl::sled_evacuation)
+            .values(SledEvacuation::new(authz_sled.id(), instances_total))
+            .returning(SledEvacuation::as_returning())
+            .get_result_async(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .map_err(|e| match e {
+                DieselError::DatabaseError(
+                    DatabaseErrorKind::UniqueViolation,
+                    _,
+                ) => Error::conflict(format!(
+                    "an evacuation of sled {} is already in progress",
+                    authz_sled.id()
+                )),
+                e => public_error_from_diesel(e, ErrorHandler::Server),
+            })
+    }
+
+    /// Fetch the most recently started evacuation of `authz_sled`
+    pub async fn sled_evacuation_fetch_latest(
+        &self,
+        opctx: &OpContext,
+        authz_sled: &authz::Sled,
+    ) -> LookupResult<SledEvacuation> {
+        opctx.authorize(authz::Action::Read, authz_sled).await?;
+
+        use nexus_db_schema::schema::sled_evacuation::dsl;
+        dsl::sled_evacuation
+            .filter(dsl::sled_id.eq(to_db_typed_uuid(authz_sled.id())))
+            .order(dsl::time_started.desc())
+            .select(SledEvacuation::as_select())
+            .first_async(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .optional()
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))?
+            .ok_or_else(|| {
+                Error::non_resourcetype_not_found(format!(
+                    "sled {} has never been evacuated",
+                    authz_sled.id()
+                ))
+            })
+    }
+
+    /// Cancel the in-progress evacuation of `authz_sled`
+    pub async fn sled_evacuation_cancel(
+        &self,
+        opctx: &OpContext,
+        authz_sled: &authz::Sled,
+    ) -> UpdateResult<SledEvacuation> {
+        opctx.authorize(authz::Action::Modify, authz_sled).await?;
+
+        use nexus_db_schema::schema::sled_evacuation::dsl;
+        diesel::update(dsl::sled_evacuation)
+            .filter(dsl::sled_id.eq(to_db_typed_uuid(authz_sled.id())))
+            .filter(dsl::state.eq(SledEvacuationState::InProgress))
+            .set((
+                dsl::state.eq(SledEvacuationState::Cancelled),
+                dsl::time_finished.eq(Utc::now()),
+            ))
+            .returning(SledEvacuation::as_returning())
+            .get_result_async(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .optional()
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))?
+            .ok_or_else(|| {
+                Error::invalid_request(format!(
+                    "sled {} has no evacuation in progress",
+                    authz_sled.id()
+                ))
+            })
+    }
+
+    /// List all evacuations that are in progress
+    ///
+    /// There can be at most one in-progress evacuation per sled, so this does
+    /// not paginate.
+    pub async fn sled_evacuation_list_in_progress(
+        &self,
+        opctx: &OpContext,
+    ) -> ListResultVec<SledEvacuation> {
+        opctx.authorize(authz::Action::Read, &authz::FLEET).await?;
+
+        use nexus_db_schema::schema::sled_evacuation::dsl;
+        dsl::sled_evacuation
+            .filter(dsl::state.eq(SledEvacuationState::InProgress))
+            .order(dsl::time_started.asc())
+            .select(SledEvacuation::as_select())
+            .load_async(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))
+    }
+
+    /// Record that the evacuator started `migrations_started` more
+    /// migrations for the evacuation `evacuation_id`, and the most recent
+    /// error it encountered, if any
+    ///
+    /// If `last_error` is `None`, any previously recorded error is left in
+    /// place.
+    pub async fn sled_evacuation_record_progress(
+        &self,
+        opctx: &OpContext,
+        evacuation_id: Uuid,
+        migrations_started: u64,
+        last_error: Option<String>,
+    ) -> Result<(), Error> {
+        opctx.authorize(authz::Action::Modify, &authz::FLEET).await?;
+
+        let migrations_started =
+            i64::try_from(migrations_started).unwrap_or(i64::MAX);
+        let conn = self.pool_connection_authorized(opctx).await?;
+
+        use nexus_db_schema::schema::sled_evacuation::dsl;
+        let query = diesel::update(dsl::sled_evacuation)
+            .filter(dsl::id.eq(evacuation_id))
+            .filter(dsl::state.eq(SledEvacuationState::InProgress));
+        match last_error {
+            Some(last_error) => {
+                query
+                    .set((
+                        dsl::migrations_started
+                            .eq(dsl::migrations_started + migrations_started),
+                        dsl::last_error.eq(last_error),
+                    ))
+                    .execute_async(&*conn)
+                    .await
+            }
+            None => {
+                query
+                    .set(
+                        dsl::migrations_started
+                            .eq(dsl::migrations_started + migrations_started),
+                    )
+                    .execute_async(&*conn)
+                    .await
+            }
+        }
+        .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))?;
+        Ok(())
+    }
+
+    /// Mark the evacuation `evacuation_id` as completed, if it is still in
+    /// progress
+    ///
+    /// Returns whether the evacuation was marked completed.
+    pub async fn sled_evacuation_complete(
+        &self,
+        opctx: &OpContext,
+        evacuation_id: Uuid,
+    ) -> Result<bool, Error> {
+        opctx.authorize(authz::Action::Modify, &authz::FLEET).await?;
+
+        use nexus_db_schema::schema::sled_evacuation::dsl;
+        let updated = diesel::update(dsl::sled_evacuation)
+            .filter(dsl::id.eq(evacuation_id))
+            .filter(dsl::state.eq(SledEvacuationState::InProgress))
+            .set((
+                dsl::state.eq(SledEvacuationState::Completed),
+                dsl::time_finished.eq(Utc::now()),
+            ))
+            .execute_async(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))?;
+        Ok(updated > 0)
+    }
+
+    /// Count the instances whose active VMM is on `sled_id`, and how many of
+    /// those are migrating
+    pub async fn sled_evacuation_progress(
+        &self,
+        opctx: &OpContext,
+        sled_id: SledUuid,
+    ) -> LookupResult<SledEvacuationProgress> {
+        opctx.authorize(authz::Action::Read, &authz::FLEET).await?;
+
+        use nexus_db_schema::schema::sled_instance::dsl;
+        let (instances_remaining, instances_migrating) = dsl::sled_instance
+            .filter(dsl::active_sled_id.eq(to_db_typed_uuid(sled_id)))
+            .select((
+                diesel::dsl::count_star(),
+                diesel::dsl::count(dsl::migration_id),
+            ))
+            .get_result_async::<(i64, i64)>(
+                &*self.pool_connection_authorized(opctx).await?,
+            )
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))?;
+        Ok(SledEvacuationProgress {
+            instances_remaining: instances_remaining.try_into().unwrap_or(0),
+            instances_migrating: instances_migrating.try_into().unwrap_or(0),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::db::pub_test_utils::TestDatabase;
+    use omicron_common::api::external::LookupType;
+    use omicron_test_utils::dev;
+
+    #[tokio::test]
+    async fn test_sled_evacuation_lifecycle() {
+        let logctx = dev::test_setup_log("test_sled_evacuation_lifecycle");
+        let db = TestDatabase::new_with_datastore(&logctx.log).await;
+        let (opctx, datastore) = (db.opctx(), db.datastore());
+
+        let sled_id = SledUuid::new_v4();
+        let authz_sled =
+            authz::Sled::new(authz::FLEET, sled_id, LookupType::by_id(sled_id));
+
+        // A sled that was never evacuated has no evacuation to show.
+        datastore
+            .sled_evacuation_fetch_latest(opctx, &authz_sled)
+            .await
+            .expect_err("sled should not have been evacuated yet");
+        datastore
+            .sled_evacuation_cancel(opctx, &authz_sled)
+            .await
+            .expect_err("nothing to cancel");
+
+        let first = datastore
+            .sled_evacuation_create(opctx, &authz_sled, 3)
+            .await
+            .expect("should start evacuation");
+        assert_eq!(first.state, SledEvacuationState::InProgress);
+        assert_eq!(first.sled_id(), sled_id);
+
+        // Only one evacuation may be in progress at a time.
+        let error = datastore
+            .sled_evacuation_create(opctx, &authz_sled, 3)
+            .await
+            .expect_err("second evacuation should conflict");
+        assert!(matches!(error, Error::Conflict { .. }), "{error:?}");
+
+        let in_progress = datastore
+            .sled_evacuation_list_in_progress(opctx)
+            .await
+            .expect("should list evacuations");
+        assert_eq!(in_progress.len(), 1);
+        assert_eq!(in_progress[0].id, first.id);
+
+        datastore
+            .sled_evacuation_record_progress(
+                opctx,
+                first.id,
+                2,
+                Some(String::from("no capacity")),
+            )
+            .await
+            .expect("should record progress");
+        datastore
+            .sled_evacuation_record_progress(opctx, first.id, 1, None)
+            .await
+            .expect("should record progress");
+        let latest = datastore
+            .sled_evacuation_fetch_latest(opctx, &authz_sled)
+            .await
+            .expect("should fetch evacuation");
+        assert_eq!(latest.id, first.id);
+        assert_eq!(latest.migrations_started, 3);
+        assert_eq!(latest.last_error.as_deref(), Some("no capacity"));
+
+        // Once cancelled, a new evacuation can be started, which completes.
+        let cancelled = datastore
+            .sled_evacuation_cancel(opctx, &authz_sled)
+            .await
+            .expect("should cancel evacuation");
+        assert_eq!(cancelled.state, SledEvacuationState::Cancelled);
+        assert!(cancelled.time_finished.is_some());
+
+        let second = datastore
+            .sled_evacuation_create(opctx, &authz_sled, 0)
+            .await
+            .expect("should start another evacuation");
+        assert!(
+            datastore
+                .sled_evacuation_complete(opctx, second.id)
+                .await
+                .expect("should complete evacuation")
+        );
+        assert!(
+            !datastore
+                .sled_evacuation_complete(opctx, second.id)
+                .await
+                .expect("completing twice should succeed")
+        );
+        let latest = datastore
+            .sled_evacuation_fetch_latest(opctx, &authz_sled)
+            .await
+            .expect("should fetch evacuation");
+        assert_eq!(latest.id, second.id);
+        assert_eq!(latest.state, SledEvacuationState::Completed);
+
+        // No instances are running on this (imaginary) sled.
+        let progress = datastore
+            .sled_evacuation_progress(opctx, sled_id)
+            .await
+            .expect("should count instances");
+        assert_eq!(progress, SledEvacuationProgress::default());
+
+        db.terminate().await;
+        logctx.cleanup_successful();
+    }
+}
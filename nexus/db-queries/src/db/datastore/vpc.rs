@@ -16,6 +16,7 @@ use crate::db::collection_insert::DatastoreCollection;
 use crate::db::identity::Resource;
 use crate::db::model::ApplySledFilterExt;
 use crate::db::model::DbTypedUuid;
+use crate::db::model::Generation;
 use crate::db::model::IncompleteVpc;
 use crate::db::model::InstanceNetworkInterface;
 use crate::db::model::Name;
@@ -96,6 +97,28 @@ use std::collections::HashSet;
 use std::net::IpAddr;
 use uuid::Uuid;
 
+/// A change to a single firewall rule of a VPC
+///
+/// See [`DataStore::vpc_edit_firewall_rule_at_generation`].
+#[derive(Clone, Debug)]
+pub enum VpcFirewallRuleEdit {
+    /// Add a new rule
+    Create(VpcFirewallRule),
+    /// Overwrite the existing rule with the same ID, keeping its ID and
+    /// creation time
+    Replace(VpcFirewallRule),
+    /// Remove the rule with this ID
+    Delete(Uuid),
+}
+
+/// How [`DataStore::vpc_write_firewall_rules_at_generation`] changes a VPC's
+/// firewall rules
+#[derive(Clone, Debug)]
+enum FirewallRulesWrite {
+    ReplaceAll(Vec<VpcFirewallRule>),
+    Edit(VpcFirewallRuleEdit),
+}
+
 impl DataStore {
     /// Load built-in VPCs into the database.
     pub async fn load_builtin_vpcs(
@@ -663,34 +686,97 @@ impl DataStore {
         &self,
         opctx: &OpContext,
         authz_vpc: &authz::Vpc,
-        mut rules: Vec<VpcFirewallRule>,
+        rules: Vec<VpcFirewallRule>,
     ) -> UpdateResult<Vec<VpcFirewallRule>> {
-        opctx.authorize(authz::Action::Modify, authz_vpc).await?;
-        for r in &rules {
-            assert_eq!(r.vpc_id, authz_vpc.id());
-        }
+        self.vpc_update_firewall_rules_at_generation(
+            opctx, authz_vpc, None, rules,
+        )
+        .await
+        .map(|(_, rules)| rules)
+    }
 
+    /// Replace all firewall rules with the given rules, provided the VPC's
+    /// rule set is still at generation `expected_gen`
+    ///
+    /// If `expected_gen` is `None`, the rules are replaced whatever the current
+    /// generation is. Either way, a successful replacement advances the
+    /// generation, and the new generation is returned alongside the rules.
+    pub async fn vpc_update_firewall_rules_at_generation(
+        &self,
+        opctx: &OpContext,
+        authz_vpc: &authz::Vpc,
+        expected_gen: Option<Generation>,
+        mut rules: Vec<VpcFirewallRule>,
+    ) -> UpdateResult<(Generation, Vec<VpcFirewallRule>)> {
         // Sort the rules in the same order that we would return them when
         // listing them.  This is because we're going to use RETURNING to return
         // the inserted rows from the database and we want them to come back in
         // the same order that we would normally list them.
         rules.sort_by_key(|r| r.name().to_string());
+        self.vpc_write_firewall_rules_at_generation(
+            opctx,
+            authz_vpc,
+            expected_gen,
+            FirewallRulesWrite::ReplaceAll(rules),
+        )
+        .await
+    }
 
-        use nexus_db_schema::schema::vpc_firewall_rule::dsl;
+    /// Create, replace, or delete a single firewall rule, provided the VPC's
+    /// rule set is still at generation `expected_gen`
+    ///
+    /// Unlike [`Self::vpc_update_firewall_rules_at_generation`], the other
+    /// rules of the VPC are left untouched, so they keep their IDs. A
+    /// successful edit advances the generation, and the new generation is
+    /// returned alongside the resulting set of rules.
+    pub async fn vpc_edit_firewall_rule_at_generation(
+        &self,
+        opctx: &OpContext,
+        authz_vpc: &authz::Vpc,
+        expected_gen: Generation,
+        edit: VpcFirewallRuleEdit,
+    ) -> UpdateResult<(Generation, Vec<VpcFirewallRule>)> {
+        self.vpc_write_firewall_rules_at_generation(
+            opctx,
+            authz_vpc,
+            Some(expected_gen),
+            FirewallRulesWrite::Edit(edit),
+        )
+        .await
+    }
 
-        let now = Utc::now();
-        let delete_old_query = diesel::update(dsl::vpc_firewall_rule)
-            .filter(dsl::time_deleted.is_null())
-            .filter(dsl::vpc_id.eq(authz_vpc.id()))
-            .set(dsl::time_deleted.eq(now));
+    async fn vpc_write_firewall_rules_at_generation(
+        &self,
+        opctx: &OpContext,
+        authz_vpc: &authz::Vpc,
+        expected_gen: Option<Generation>,
+        write: FirewallRulesWrite,
+    ) -> UpdateResult<(Generation, Vec<VpcFirewallRule>)> {
+        opctx.authorize(authz::Action::Modify, authz_vpc).await?;
+        match &write {
+            FirewallRulesWrite::ReplaceAll(rules) => {
+                for r in rules {
+                    assert_eq!(r.vpc_id, authz_vpc.id());
+                }
+            }
+            FirewallRulesWrite::Edit(
+                VpcFirewallRuleEdit::Create(r)
+                | VpcFirewallRuleEdit::Replace(r),
+            ) => {
+                assert_eq!(r.vpc_id, authz_vpc.id());
+            }
+            FirewallRulesWrite::Edit(VpcFirewallRuleEdit::Delete(_)) => (),
+        }
 
-        let rules_is_empty = rules.is_empty();
         #[derive(Debug)]
         enum FirewallUpdateError {
             CollectionNotFound,
+            StaleGeneration { expected: Generation, current: Generation },
+            RuleNotFound(Uuid),
         }
 
         let err = OptionalError::new();
+        let vpc_id = authz_vpc.id();
 
         // TODO-scalability: Ideally this would be a CTE so we don't need to
         // hold a transaction open across multiple roundtrips from the database,
@@ -701,30 +787,124 @@ impl DataStore {
         self.transaction_retry_wrapper("vpc_update_firewall_rules")
             .transaction(&conn, |conn| {
                 let err = err.clone();
-                let delete_old_query = delete_old_query.clone();
-                let rules = rules.clone();
+                let write = write.clone();
                 async move {
-                    delete_old_query.execute_async(&conn).await?;
-
-                    // The generation count update on the vpc table row will take a
-                    // write lock on the row, ensuring that the vpc was not deleted
-                    // concurently.
-                    if rules_is_empty {
-                        return Ok(vec![]);
-                    }
-                    Vpc::insert_resource(
-                        authz_vpc.id(),
-                        diesel::insert_into(dsl::vpc_firewall_rule)
-                            .values(rules),
-                    )
-                    .insert_and_get_results_async(&conn)
-                    .await
-                    .map_err(|e| match e {
-                        AsyncInsertError::CollectionNotFound => {
+                    use nexus_db_schema::schema::vpc::dsl as vpc_dsl;
+                    use nexus_db_schema::schema::vpc_firewall_rule::dsl;
+
+                    let current = vpc_dsl::vpc
+                        .filter(vpc_dsl::id.eq(vpc_id))
+                        .filter(vpc_dsl::time_deleted.is_null())
+                        .select(vpc_dsl::firewall_gen)
+                        .get_result_async::<Generation>(&conn)
+                        .await
+                        .optional()?
+                        .ok_or_else(|| {
                             err.bail(FirewallUpdateError::CollectionNotFound)
+                        })?;
+                    if let Some(expected) = expected_gen {
+                        if expected != current {
+                            return Err(err.bail(
+                                FirewallUpdateError::StaleGeneration {
+                                    expected,
+                                    current,
+                                },
+                            ));
                         }
-                        AsyncInsertError::DatabaseError(e) => e,
-                    })
+                    }
+
+                    // Advancing the generation writes the vpc row, so a
+                    // concurrent edit of the rules or deletion of the vpc
+                    // conflicts with this transaction rather than racing it.
+                    let next = Generation::from(current.next());
+                    diesel::update(vpc_dsl::vpc)
+                        .filter(vpc_dsl::id.eq(vpc_id))
+                        .filter(vpc_dsl::firewall_gen.eq(current))
+                        .set(vpc_dsl::firewall_gen.eq(next))
+                        .execute_async(&conn)
+                        .await?;
+
+                    let edit = match write {
+                        FirewallRulesWrite::ReplaceAll(rules) => {
+                            diesel::update(dsl::vpc_firewall_rule)
+                                .filter(dsl::time_deleted.is_null())
+                                .filter(dsl::vpc_id.eq(vpc_id))
+                                .set(dsl::time_deleted.eq(Utc::now()))
+                                .execute_async(&conn)
+                                .await?;
+
+                            if rules.is_empty() {
+                                return Ok((next, vec![]));
+                            }
+                            let rules =
+                                diesel::insert_into(dsl::vpc_firewall_rule)
+                                    .values(rules)
+                                    .returning(VpcFirewallRule::as_returning())
+                                    .get_results_async(&conn)
+                                    .await?;
+                            return Ok((next, rules));
+                        }
+                        FirewallRulesWrite::Edit(edit) => edit,
+                    };
+
+                    let (updated, rule_id) = match edit {
+                        VpcFirewallRuleEdit::Create(rule) => {
+                            let rule_id = rule.id();
+                            let n = diesel::insert_into(dsl::vpc_firewall_rule)
+                                .values(rule)
+                                .execute_async(&conn)
+                                .await?;
+                            (n, rule_id)
+                        }
+                        VpcFirewallRuleEdit::Replace(rule) => {
+                            let rule_id = rule.id();
+                            let n = diesel::update(dsl::vpc_firewall_rule)
+                                .filter(dsl::id.eq(rule_id))
+                                .filter(dsl::vpc_id.eq(vpc_id))
+                                .filter(dsl::time_deleted.is_null())
+                                .set((
+                                    dsl::name.eq(rule.identity.name),
+                                    dsl::description
+                                        .eq(rule.identity.description),
+                                    dsl::time_modified.eq(Utc::now()),
+                                    dsl::status.eq(rule.status),
+                                    dsl::direction.eq(rule.direction),
+                                    dsl::targets.eq(rule.targets),
+                                    dsl::filter_hosts.eq(rule.filter_hosts),
+                                    dsl::filter_ports.eq(rule.filter_ports),
+                                    dsl::filter_protocols
+                                        .eq(rule.filter_protocols),
+                                    dsl::action.eq(rule.action),
+                                    dsl::priority.eq(rule.priority),
+                                ))
+                                .execute_async(&conn)
+                                .await?;
+                            (n, rule_id)
+                        }
+                        VpcFirewallRuleEdit::Delete(rule_id) => {
+                            let n = diesel::update(dsl::vpc_firewall_rule)
+                                .filter(dsl::id.eq(rule_id))
+                                .filter(dsl::vpc_id.eq(vpc_id))
+                                .filter(dsl::time_deleted.is_null())
+                                .set(dsl::time_deleted.eq(Utc::now()))
+                                .execute_async(&conn)
+                                .await?;
+                            (n, rule_id)
+                        }
+                    };
+                    if updated == 0 {
+                        return Err(err
+                            .bail(FirewallUpdateError::RuleNotFound(rule_id)));
+                    }
+
+                    let rules = dsl::vpc_firewall_rule
+                        .filter(dsl::time_deleted.is_null())
+                        .filter(dsl::vpc_id.eq(vpc_id))
+                        .order(dsl::name.asc())
+                        .select(VpcFirewallRule::as_select())
+                        .load_async(&conn)
+                        .await?;
+                    Ok((next, rules))
                 }
             })
             .await
@@ -732,9 +912,20 @@ impl DataStore {
                 if let Some(err) = err.take() {
                     match err {
                         FirewallUpdateError::CollectionNotFound => {
+                            Error::not_found_by_id(ResourceType::Vpc, &vpc_id)
+                        }
+                        FirewallUpdateError::StaleGeneration {
+                            expected,
+                            current,
+                        } => Error::conflict(format!(
+                            "firewall rules of VPC {vpc_id} are at generation \
+                             {}, not {}",
+                            *current, *expected,
+                        )),
+                        FirewallUpdateError::RuleNotFound(rule_id) => {
                             Error::not_found_by_id(
-                                ResourceType::Vpc,
-                                &authz_vpc.id(),
+                                ResourceType::VpcFirewallRule,
+                                &rule_id,
                             )
                         }
                     }
@@ -4085,4 +4276,114 @@ mod tests {
         db.terminate().await;
         logctx.cleanup_successful();
     }
+
+    #[tokio::test]
+    async fn test_vpc_update_firewall_rules_at_generation() {
+        let logctx =
+            dev::test_setup_log("test_vpc_update_firewall_rules_at_generation");
+        let db = TestDatabase::new_with_datastore(&logctx.log).await;
+        let (opctx, datastore) = (db.opctx(), db.datastore());
+
+        let project_params = project::ProjectCreate {
+            identity: IdentityMetadataCreateParams {
+                name: "project".parse().unwrap(),
+                description: String::from("test project"),
+            },
+        };
+        let project = Project::new(Uuid::new_v4(), project_params);
+        let (authz_project, _) = datastore
+            .project_create(&opctx, project)
+            .await
+            .expect("failed to create project");
+        let name: external::Name = "my-vpc".parse().unwrap();
+        let incomplete_vpc = IncompleteVpc::new(
+            Uuid::new_v4(),
+            authz_project.id(),
+            Uuid::new_v4(),
+            vpc::VpcCreate {
+                identity: IdentityMetadataCreateParams {
+                    name: name.clone(),
+                    description: String::from("test vpc"),
+                },
+                ipv6_prefix: None,
+                dns_name: name,
+            },
+        )
+        .expect("failed to create incomplete VPC");
+        let (authz_vpc, db_vpc) = datastore
+            .project_create_vpc(&opctx, &authz_project, incomplete_vpc)
+            .await
+            .expect("failed to create VPC");
+        assert_eq!(*db_vpc.firewall_gen, Generation::new());
+
+        let rule = VpcFirewallRule::new(
+            Uuid::new_v4(),
+            authz_vpc.id(),
+            &external::VpcFirewallRuleUpdate {
+                name: "allow-icmp".parse().unwrap(),
+                description: String::from("allow icmp"),
+                status: external::VpcFirewallRuleStatus::Enabled,
+                direction: external::VpcFirewallRuleDirection::Inbound,
+                targets: vec![external::VpcFirewallRuleTarget::Vpc(
+                    "my-vpc".parse().unwrap(),
+                )],
+                filters: external::VpcFirewallRuleFilter {
+                    hosts: None,
+                    protocols: None,
+                    ports: None,
+                },
+                action: external::VpcFirewallRuleAction::Allow,
+                priority: external::VpcFirewallRulePriority(100),
+            },
+        )
+        .unwrap();
+
+        // An unconditional replacement still advances the generation.
+        let (generation, rules) = datastore
+            .vpc_update_firewall_rules_at_generation(
+                &opctx,
+                &authz_vpc,
+                None,
+                vec![rule],
+            )
+            .await
+            .expect("failed to replace rules");
+        assert_eq!(*generation, Generation::new().next());
+        assert_eq!(rules.len(), 1);
+
+        // Replacing against the original generation is a conflict, and leaves
+        // the rules alone.
+        let err = datastore
+            .vpc_update_firewall_rules_at_generation(
+                &opctx,
+                &authz_vpc,
+                Some(Generation::new().into()),
+                vec![],
+            )
+            .await
+            .expect_err("stale generation should fail");
+        assert!(matches!(err, Error::Conflict { .. }), "{err:?}");
+        let rules = datastore
+            .vpc_list_firewall_rules(&opctx, &authz_vpc)
+            .await
+            .expect("failed to list rules");
+        assert_eq!(rules.len(), 1);
+
+        // Replacing against the current generation succeeds, even when
+        // removing every rule.
+        let (next, rules) = datastore
+            .vpc_update_firewall_rules_at_generation(
+                &opctx,
+                &authz_vpc,
+                Some(generation),
+                vec![],
+            )
+            .await
+            .expect("failed to replace rules");
+        assert_eq!(*next, generation.next());
+        assert!(rules.is_empty());
+
+        db.terminate().await;
+        logctx.cleanup_successful();
+    }
 }
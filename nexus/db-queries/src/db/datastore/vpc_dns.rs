@@ -0,0 +1,674 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! [`DataStore`] methods on VPCs' private DNS zones.
+//!
+//! Each VPC has a zone, `<vpc dns_name>.internal`, in which its instances can
+//! resolve each other by name. The zones' records are generated from the
+//! instances' network interfaces, along with any user-defined records, and
+//! are served only to queries from the instances' external addresses.
+
+use super::DataStore;
+use super::SQL_BATCH_SIZE;
+use crate::authz;
+use crate::context::OpContext;
+use crate::db::model::ExternalIp;
+use crate::db::model::Generation;
+use crate::db::model::IpKind;
+use crate::db::model::Name;
+use crate::db::model::VpcDnsConfig;
+use crate::db::model::VpcDnsRecord;
+use crate::db::model::vpc_dns_zone_name;
+use crate::db::pagination::Paginator;
+use crate::db::pagination::paginated;
+use async_bb8_diesel::AsyncRunQueryDsl;
+use chrono::Utc;
+use diesel::prelude::*;
+use ipnetwork::IpNetwork;
+use nexus_db_errors::ErrorHandler;
+use nexus_db_errors::OptionalError;
+use nexus_db_errors::public_error_from_diesel;
+use nexus_types::identity::Resource;
+use nexus_types::internal_api::params::DnsRecord;
+use nexus_types::internal_api::params::VpcDnsClient;
+use nexus_types::internal_api::params::VpcDnsConfigParams;
+use nexus_types::internal_api::params::VpcDnsZone;
+use omicron_common::address::DNS_PORT;
+use omicron_common::api::external::Error;
+use omicron_common::api::external::ListResultVec;
+use omicron_common::api::external::ResourceType;
+use omicron_common::api::external::UpdateResult;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::net::SocketAddr;
+use uuid::Uuid;
+
+impl DataStore {
+    /// List the user-defined records in a VPC's private DNS zone
+    pub async fn vpc_dns_record_list(
+        &self,
+        opctx: &OpContext,
+        authz_vpc: &authz::Vpc,
+    ) -> ListResultVec<VpcDnsRecord> {
+        opctx.authorize(authz::Action::Read, authz_vpc).await?;
+        use nexus_db_schema::schema::vpc_dns_record::dsl;
+        dsl::vpc_dns_record
+            .filter(dsl::time_deleted.is_null())
+            .filter(dsl::vpc_id.eq(authz_vpc.id()))
+            .order(dsl::name.asc())
+            .select(VpcDnsRecord::as_select())
+            .load_async(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))
+    }
+
+    /// Replace the user-defined records in a VPC's private DNS zone
+    pub async fn vpc_dns_record_replace(
+        &self,
+        opctx: &OpContext,
+        authz_vpc: &authz::Vpc,
+        mut records: Vec<VpcDnsRecord>,
+    ) -> UpdateResult<Vec<VpcDnsRecord>> {
+        opctx.authorize(authz::Action::Modify, authz_vpc).await?;
+        for r in &records {
+            assert_eq!(r.vpc_id, authz_vpc.id());
+        }
+
+        // Return the records in the order they would be listed.
+        records.sort_by(|a, b| a.name.as_str().cmp(b.name.as_str()));
+
+        let err = OptionalError::new();
+        let vpc_id = authz_vpc.id();
+        let conn = self.pool_connection_authorized(opctx).await?;
+        self.transaction_retry_wrapper("vpc_dns_record_replace")
+            .transaction(&conn, |conn| {
+                let err = err.clone();
+                let records = records.clone();
+                async move {
+                    use nexus_db_schema::schema::vpc::dsl as vpc_dsl;
+                    use nexus_db_schema::schema::vpc_dns_record::dsl;
+
+                    // Reading the vpc row makes a concurrent deletion of the
+                    // vpc conflict with this transaction.
+                    vpc_dsl::vpc
+                        .filter(vpc_dsl::id.eq(vpc_id))
+                        .filter(vpc_dsl::time_deleted.is_null())
+                        .select(vpc_dsl::id)
+                        .get_result_async::<Uuid>(&conn)
+                        .await
+                        .optional()?
+                        .ok_or_else(|| {
+                            err.bail(Error::not_found_by_id(
+                                ResourceType::Vpc,
+                                &vpc_id,
+                            ))
+                        })?;
+
+                    diesel::update(dsl::vpc_dns_record)
+                        .filter(dsl::time_deleted.is_null())
+                        .filter(dsl::vpc_id.eq(vpc_id))
+                        .set(dsl::time_deleted.eq(Utc::now()))
+                        .execute_async(&conn)
+                        .await?;
+
+                    if records.is_empty() {
+                        return Ok(vec![]);
+                    }
+                    diesel::insert_into(dsl::vpc_dns_record)
+                        .values(records)
+                        .returning(VpcDnsRecord::as_returning())
+                        .get_results_async(&conn)
+                        .await
+                }
+            })
+            .await
+            .map_err(|e| {
+                if let Some(err) = err.take() {
+                    err
+                } else {
+                    public_error_from_diesel(
+                        e,
+                        ErrorHandler::NotFoundByResource(authz_vpc),
+                    )
+                }
+            })
+    }
+
+    /// Delete the user-defined records in a VPC's private DNS zone, as part
+    /// of deleting the VPC
+    pub async fn vpc_dns_record_delete_all(
+        &self,
+        opctx: &OpContext,
+        authz_vpc: &authz::Vpc,
+    ) -> Result<(), Error> {
+        opctx.authorize(authz::Action::Delete, authz_vpc).await?;
+        use nexus_db_schema::schema::vpc_dns_record::dsl;
+        diesel::update(dsl::vpc_dns_record)
+            .filter(dsl::time_deleted.is_null())
+            .filter(dsl::vpc_id.eq(authz_vpc.id()))
+            .set(dsl::time_deleted.eq(Utc::now()))
+            .execute_async(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .map(|_| ())
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))
+    }
+
+    /// Compute the private DNS zones of all VPCs
+    ///
+    /// Each instance's name resolves to the addresses of its primary network
+    /// interface, and `<interface>.<instance>` to the addresses of that
+    /// interface. A zone's clients are the external addresses of its VPC's
+    /// instances, from which their DNS queries arrive; VPCs without any
+    /// instances having external addresses have no zone.
+    pub async fn vpc_dns_zones(
+        &self,
+        opctx: &OpContext,
+    ) -> ListResultVec<VpcDnsZone> {
+        opctx.authorize(authz::Action::Read, &authz::DNS_CONFIG).await?;
+        opctx.check_complex_operations_allowed()?;
+        let conn = self.pool_connection_authorized(opctx).await?;
+
+        let mut vpc_names = BTreeMap::new();
+        let mut paginator = Paginator::new(
+            SQL_BATCH_SIZE,
+            dropshot::PaginationOrder::Ascending,
+        );
+        while let Some(p) = paginator.next() {
+            use nexus_db_schema::schema::vpc::dsl;
+            let batch = paginated(dsl::vpc, dsl::id, &p.current_pagparams())
+                .filter(dsl::time_deleted.is_null())
+                .select((dsl::id, dsl::dns_name))
+                .load_async::<(Uuid, Name)>(&*conn)
+                .await
+                .map_err(|e| {
+                    public_error_from_diesel(e, ErrorHandler::Server)
+                })?;
+            paginator = p.found_batch(&batch, &|(id, _)| *id);
+            vpc_names.extend(batch);
+        }
+
+        let mut instance_names = BTreeMap::new();
+        let mut paginator = Paginator::new(
+            SQL_BATCH_SIZE,
+            dropshot::PaginationOrder::Ascending,
+        );
+        while let Some(p) = paginator.next() {
+            use nexus_db_schema::schema::instance::dsl;
+            let batch =
+                paginated(dsl::instance, dsl::id, &p.current_pagparams())
+                    .filter(dsl::time_deleted.is_null())
+                    .select((dsl::id, dsl::name))
+                    .load_async::<(Uuid, Name)>(&*conn)
+                    .await
+                    .map_err(|e| {
+                        public_error_from_diesel(e, ErrorHandler::Server)
+                    })?;
+            paginator = p.found_batch(&batch, &|(id, _)| *id);
+            instance_names.extend(batch);
+        }
+
+        // Instances send their DNS queries from the same external addresses
+        // as the rest of their traffic.
+        let mut instance_clients: BTreeMap<Uuid, Vec<VpcDnsClient>> =
+            BTreeMap::new();
+        let mut paginator = Paginator::new(
+            SQL_BATCH_SIZE,
+            dropshot::PaginationOrder::Ascending,
+        );
+        while let Some(p) = paginator.next() {
+            use nexus_db_schema::schema::external_ip::dsl;
+            let batch =
+                paginated(dsl::external_ip, dsl::id, &p.current_pagparams())
+                    .filter(dsl::time_deleted.is_null())
+                    .filter(dsl::is_service.eq(false))
+                    .filter(dsl::is_probe.eq(false))
+                    .filter(dsl::parent_id.is_not_null())
+                    .filter(dsl::kind.eq_any([
+                        IpKind::SNat,
+                        IpKind::Ephemeral,
+                        IpKind::Floating,
+                    ]))
+                    .select(ExternalIp::as_select())
+                    .load_async(&*conn)
+                    .await
+                    .map_err(|e| {
+                        public_error_from_diesel(e, ErrorHandler::Server)
+                    })?;
+            paginator = p.found_batch(&batch, &|ip: &ExternalIp| ip.id);
+            for ip in batch {
+                let Some(instance_id) = ip.parent_id else { continue };
+                instance_clients.entry(instance_id).or_default().push(
+                    VpcDnsClient {
+                        ip: ip.ip.ip(),
+                        first_port: ip.first_port.0,
+                        last_port: ip.last_port.0,
+                    },
+                );
+            }
+        }
+
+        let mut zones: BTreeMap<Uuid, VpcDnsZone> = BTreeMap::new();
+
+        for nic in
+            self.instance_network_interfaces_all_list_batched(opctx).await?
+        {
+            let Some(instance_name) = instance_names.get(&nic.instance_id)
+            else {
+                continue;
+            };
+            let Some(zone) = zone_for(&mut zones, &vpc_names, nic.vpc_id)
+            else {
+                continue;
+            };
+            let addresses: Vec<_> = nic
+                .ipv4
+                .map(|ip| DnsRecord::A(*ip))
+                .into_iter()
+                .chain(nic.ipv6.map(|ip| DnsRecord::Aaaa(*ip)))
+                .collect();
+            if nic.primary {
+                zone.records
+                    .entry(instance_name.to_string())
+                    .or_default()
+                    .extend(addresses.iter().cloned());
+                if let Some(clients) = instance_clients.get(&nic.instance_id) {
+                    zone.clients.extend(clients.iter().copied());
+                }
+            }
+            zone.records
+                .entry(format!("{}.{}", nic.name(), instance_name))
+                .or_default()
+                .extend(addresses);
+        }
+
+        let mut paginator = Paginator::new(
+            SQL_BATCH_SIZE,
+            dropshot::PaginationOrder::Ascending,
+        );
+        while let Some(p) = paginator.next() {
+            use nexus_db_schema::schema::vpc_dns_record::dsl;
+            let batch =
+                paginated(dsl::vpc_dns_record, dsl::id, &p.current_pagparams())
+                    .filter(dsl::time_deleted.is_null())
+                    .select(VpcDnsRecord::as_select())
+                    .load_async(&*conn)
+                    .await
+                    .map_err(|e| {
+                        public_error_from_diesel(e, ErrorHandler::Server)
+                    })?;
+            paginator = p.found_batch(&batch, &|r: &VpcDnsRecord| r.id);
+            for record in batch {
+                let Some(zone) =
+                    zone_for(&mut zones, &vpc_names, record.vpc_id)
+                else {
+                    continue;
+                };
+                let address = match record.address.ip() {
+                    std::net::IpAddr::V4(ip) => DnsRecord::A(ip),
+                    std::net::IpAddr::V6(ip) => DnsRecord::Aaaa(ip),
+                };
+                zone.records
+                    .entry(record.name.to_string())
+                    .or_default()
+                    .push(address);
+            }
+        }
+
+        // Sort everything, so that unchanged zones compare equal to those
+        // last stored.
+        Ok(zones
+            .into_values()
+            .filter_map(|mut zone| {
+                zone.clients.sort();
+                zone.clients.dedup();
+                if zone.clients.is_empty() {
+                    return None;
+                }
+                for records in zone.records.values_mut() {
+                    records.sort();
+                    records.dedup();
+                }
+                Some(zone)
+            })
+            .collect())
+    }
+
+    /// Fetch the private DNS zones of all VPCs, as last stored
+    pub async fn vpc_dns_config_read(
+        &self,
+        opctx: &OpContext,
+    ) -> Result<VpcDnsConfigParams, Error> {
+        opctx.authorize(authz::Action::Read, &authz::DNS_CONFIG).await?;
+        use nexus_db_schema::schema::vpc_dns_config::dsl;
+        let config = dsl::vpc_dns_config
+            .select(VpcDnsConfig::as_select())
+            .get_result_async(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .optional()
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))?;
+        vpc_dns_config_params(config.unwrap_or_else(initial_vpc_dns_config))
+    }
+
+    /// Store the private DNS zones of all VPCs, and the resolvers that
+    /// instances' queries for other names are forwarded to, advancing their
+    /// generation if they differ from those last stored
+    ///
+    /// Returns the configuration as stored, which is the one given unless
+    /// another Nexus stored a different one concurrently.
+    pub async fn vpc_dns_config_update(
+        &self,
+        opctx: &OpContext,
+        zones: Vec<VpcDnsZone>,
+        forwarders: &[IpAddr],
+    ) -> Result<VpcDnsConfigParams, Error> {
+        opctx.authorize(authz::Action::Modify, &authz::DNS_CONFIG).await?;
+        let zones_json = serde_json::to_value(&zones).map_err(|e| {
+            Error::internal_error(&format!(
+                "failed to serialize VPC DNS zones: {e}"
+            ))
+        })?;
+        let forwarders = forwarders
+            .iter()
+            .map(|ip| IpNetwork::from(*ip))
+            .collect::<Vec<_>>();
+
+        let conn = self.pool_connection_authorized(opctx).await?;
+        let config = self
+            .transaction_retry_wrapper("vpc_dns_config_update")
+            .transaction(&conn, |conn| {
+                let zones_json = zones_json.clone();
+                let forwarders = forwarders.clone();
+                async move {
+                    use nexus_db_schema::schema::vpc_dns_config::dsl;
+                    let current = dsl::vpc_dns_config
+                        .select(VpcDnsConfig::as_select())
+                        .get_result_async(&conn)
+                        .await
+                        .optional()?
+                        .unwrap_or_else(initial_vpc_dns_config);
+                    if current.zones == zones_json
+                        && current.forwarders == forwarders
+                    {
+                        return Ok(current);
+                    }
+                    let config = VpcDnsConfig {
+                        singleton: true,
+                        generation: Generation::from(current.generation.next()),
+                        time_created: Utc::now(),
+                        zones: zones_json,
+                        forwarders,
+                    };
+                    diesel::insert_into(dsl::vpc_dns_config)
+                        .values(config.clone())
+                        .on_conflict(dsl::singleton)
+                        .do_update()
+                        .set((
+                            dsl::generation.eq(config.generation),
+                            dsl::time_created.eq(config.time_created),
+                            dsl::zones.eq(config.zones.clone()),
+                            dsl::forwarders.eq(config.forwarders.clone()),
+                        ))
+                        .execute_async(&conn)
+                        .await?;
+                    Ok(config)
+                }
+            })
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))?;
+        vpc_dns_config_params(config)
+    }
+}
+
+/// The zones before any have been stored: there are none, at the first
+/// generation
+fn initial_vpc_dns_config() -> VpcDnsConfig {
+    VpcDnsConfig {
+        singleton: true,
+        generation: Generation::new(),
+        time_created: Utc::now(),
+        zones: serde_json::Value::Array(Vec::new()),
+        forwarders: Vec::new(),
+    }
+}
+
+/// Returns the zone of the VPC `vpc_id`, creating it if need be, or `None` if
+/// the VPC does not exist
+fn zone_for<'a>(
+    zones: &'a mut BTreeMap<Uuid, VpcDnsZone>,
+    vpc_names: &BTreeMap<Uuid, Name>,
+    vpc_id: Uuid,
+) -> Option<&'a mut VpcDnsZone> {
+    let dns_name = vpc_names.get(&vpc_id)?;
+    Some(zones.entry(vpc_id).or_insert_with(|| VpcDnsZone {
+        zone_name: vpc_dns_zone_name(dns_name),
+        clients: Vec::new(),
+        records: HashMap::new(),
+    }))
+}
+
+fn vpc_dns_config_params(
+    config: VpcDnsConfig,
+) -> Result<VpcDnsConfigParams, Error> {
+    let zones = serde_json::from_value(config.zones).map_err(|e| {
+        Error::internal_error(&format!(
+            "failed to deserialize VPC DNS zones: {e}"
+        ))
+    })?;
+    Ok(VpcDnsConfigParams {
+        generation: *config.generation,
+        time_created: config.time_created,
+        // DNS servers are always reached on the standard port.
+        forwarders: config
+            .forwarders
+            .iter()
+            .map(|ip| SocketAddr::new(ip.ip(), DNS_PORT))
+            .collect(),
+        zones,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::model::IncompleteVpc;
+    use crate::db::model::Project;
+    use crate::db::pub_test_utils::TestDatabase;
+    use nexus_db_fixed_data::silo::DEFAULT_SILO;
+    use nexus_types::external_api::project;
+    use nexus_types::external_api::vpc;
+    use omicron_common::api::external::IdentityMetadataCreateParams;
+    use omicron_test_utils::dev;
+    use std::net::IpAddr;
+    use std::net::Ipv4Addr;
+
+    #[tokio::test]
+    async fn test_vpc_dns_records() {
+        let logctx = dev::test_setup_log("test_vpc_dns_records");
+        let db = TestDatabase::new_with_datastore(&logctx.log).await;
+        let (opctx, datastore) = (db.opctx(), db.datastore());
+
+        let project = Project::new(
+            DEFAULT_SILO.id(),
+            project::ProjectCreate {
+                identity: IdentityMetadataCreateParams {
+                    name: "project".parse().unwrap(),
+                    description: String::from("test project"),
+                },
+            },
+        );
+        let (authz_project, _) = datastore
+            .project_create(opctx, project)
+            .await
+            .expect("failed to create project");
+        let incomplete_vpc = IncompleteVpc::new(
+            Uuid::new_v4(),
+            authz_project.id(),
+            Uuid::new_v4(),
+            vpc::VpcCreate {
+                identity: IdentityMetadataCreateParams {
+                    name: "vpc".parse().unwrap(),
+                    description: String::from("test vpc"),
+                },
+                ipv6_prefix: None,
+                dns_name: "vpc".parse().unwrap(),
+            },
+        )
+        .expect("failed to create incomplete VPC");
+        let (authz_vpc, db_vpc) = datastore
+            .project_create_vpc(opctx, &authz_project, incomplete_vpc)
+            .await
+            .expect("failed to create VPC");
+
+        let record = |name: &str, last_octet: u8| {
+            VpcDnsRecord::new(
+                db_vpc.id(),
+                vpc::VpcDnsRecord {
+                    name: name.parse().unwrap(),
+                    address: IpAddr::from(Ipv4Addr::new(10, 0, 0, last_octet)),
+                },
+            )
+        };
+        let names = |records: &[VpcDnsRecord]| {
+            records.iter().map(|r| r.name.to_string()).collect::<Vec<_>>()
+        };
+
+        // Records are returned sorted by name, and replaced wholesale.
+        let records = datastore
+            .vpc_dns_record_replace(
+                opctx,
+                &authz_vpc,
+                vec![record("web", 1), record("db", 2)],
+            )
+            .await
+            .expect("failed to replace records");
+        assert_eq!(names(&records), ["db", "web"]);
+        let records = datastore
+            .vpc_dns_record_list(opctx, &authz_vpc)
+            .await
+            .expect("failed to list records");
+        assert_eq!(names(&records), ["db", "web"]);
+
+        datastore
+            .vpc_dns_record_replace(opctx, &authz_vpc, vec![record("cache", 3)])
+            .await
+            .expect("failed to replace records");
+        let records = datastore
+            .vpc_dns_record_list(opctx, &authz_vpc)
+            .await
+            .expect("failed to list records");
+        assert_eq!(names(&records), ["cache"]);
+
+        // Without any instances, there is no one to serve the zone to.
+        let zones =
+            datastore.vpc_dns_zones(opctx).await.expect("failed to get zones");
+        assert!(zones.is_empty());
+
+        datastore
+            .vpc_dns_record_delete_all(opctx, &authz_vpc)
+            .await
+            .expect("failed to delete records");
+        let records = datastore
+            .vpc_dns_record_list(opctx, &authz_vpc)
+            .await
+            .expect("failed to list records");
+        assert!(records.is_empty());
+
+        db.terminate().await;
+        logctx.cleanup_successful();
+    }
+
+    #[tokio::test]
+    async fn test_vpc_dns_config_generation() {
+        let logctx = dev::test_setup_log("test_vpc_dns_config_generation");
+        let db = TestDatabase::new_with_datastore(&logctx.log).await;
+        let (opctx, datastore) = (db.opctx(), db.datastore());
+
+        let initial = datastore
+            .vpc_dns_config_read(opctx)
+            .await
+            .expect("failed to read config");
+        assert_eq!(u64::from(initial.generation), 1);
+        assert!(initial.zones.is_empty());
+
+        let zone = |ip: Ipv4Addr| VpcDnsZone {
+            zone_name: String::from("vpc.internal"),
+            clients: vec![VpcDnsClient {
+                ip: IpAddr::from(ip),
+                first_port: 0,
+                last_port: 16383,
+            }],
+            records: HashMap::from([(
+                String::from("web"),
+                vec![DnsRecord::A(Ipv4Addr::new(172, 30, 0, 5))],
+            )]),
+        };
+
+        // Storing new zones advances the generation, but storing the same
+        // zones again does not.
+        let forwarders = [IpAddr::from(Ipv4Addr::new(192, 0, 2, 53))];
+        let config = datastore
+            .vpc_dns_config_update(
+                opctx,
+                vec![zone(Ipv4Addr::new(10, 0, 0, 1))],
+                &forwarders,
+            )
+            .await
+            .expect("failed to update config");
+        assert_eq!(u64::from(config.generation), 2);
+        let config = datastore
+            .vpc_dns_config_update(
+                opctx,
+                vec![zone(Ipv4Addr::new(10, 0, 0, 1))],
+                &forwarders,
+            )
+            .await
+            .expect("failed to update config");
+        assert_eq!(u64::from(config.generation), 2);
+        let config = datastore
+            .vpc_dns_config_update(
+                opctx,
+                vec![zone(Ipv4Addr::new(10, 0, 0, 2))],
+                &forwarders,
+            )
+            .await
+            .expect("failed to update config");
+        assert_eq!(u64::from(config.generation), 3);
+        assert_eq!(config.zones, vec![zone(Ipv4Addr::new(10, 0, 0, 2))]);
+
+        // So does changing the forwarders, which are reached on the DNS port.
+        let config = datastore
+            .vpc_dns_config_update(
+                opctx,
+                vec![zone(Ipv4Addr::new(10, 0, 0, 2))],
+                &[],
+            )
+            .await
+            .expect("failed to update config");
+        assert_eq!(u64::from(config.generation), 4);
+        assert!(config.forwarders.is_empty());
+        let config = datastore
+            .vpc_dns_config_update(
+                opctx,
+                vec![zone(Ipv4Addr::new(10, 0, 0, 2))],
+                &forwarders,
+            )
+            .await
+            .expect("failed to update config");
+        assert_eq!(u64::from(config.generation), 5);
+        assert_eq!(
+            config.forwarders,
+            vec![SocketAddr::new(forwarders[0], DNS_PORT)]
+        );
+
+        let read = datastore
+            .vpc_dns_config_read(opctx)
+            .await
+            .expect("failed to read config");
+        assert_eq!(read, config);
+
+        db.terminate().await;
+        logctx.cleanup_successful();
+    }
+}
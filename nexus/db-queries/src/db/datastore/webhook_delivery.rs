@@ -12,6 +12,7 @@ use crate::db::model::Alert;
 use crate::db::model::AlertClass;
 use crate::db::model::AlertDeliveryState;
 use crate::db::model::AlertDeliveryTrigger;
+use crate::db::model::AlertReceiver;
 use crate::db::model::SqlU32;
 use crate::db::model::WebhookDelivery;
 use crate::db::model::WebhookDeliveryAttempt;
@@ -337,6 +338,36 @@ impl DataStore {
         }
     }
 
+    /// Lists live receivers whose endpoints have presented a TLS certificate,
+    /// along with when the certificate each presented most recently expires
+    ///
+    /// There are expected to be only a handful of receivers, so this does not
+    /// paginate.
+    pub async fn webhook_rx_peer_cert_expiry_list(
+        &self,
+        opctx: &OpContext,
+    ) -> ListResultVec<(AlertReceiver, DateTime<Utc>)> {
+        use nexus_db_schema::schema::alert_receiver::dsl as rx_dsl;
+
+        attempt_dsl::webhook_delivery_attempt
+            .inner_join(
+                rx_dsl::alert_receiver.on(rx_dsl::id.eq(attempt_dsl::rx_id)),
+            )
+            .filter(rx_dsl::time_deleted.is_null())
+            .filter(attempt_dsl::time_peer_cert_expires.is_not_null())
+            // Only the most recent attempt to each receiver matters: the
+            // receiver may have replaced its certificate since earlier ones.
+            .distinct_on(attempt_dsl::rx_id)
+            .order((attempt_dsl::rx_id, attempt_dsl::time_created.desc()))
+            .select((
+                AlertReceiver::as_select(),
+                attempt_dsl::time_peer_cert_expires.assume_not_null(),
+            ))
+            .load_async(&*self.pool_connection_authorized(opctx).await?)
+            .await
+            .map_err(|e| public_error_from_diesel(e, ErrorHandler::Server))
+    }
+
     pub async fn webhook_delivery_finish_attempt(
         &self,
         opctx: &OpContext,
@@ -465,6 +496,8 @@ mod test {
     use omicron_common::api::external::IdentityMetadataCreateParams;
     use omicron_test_utils::dev;
     use omicron_uuid_kinds::AlertUuid;
+    use omicron_uuid_kinds::WebhookDeliveryAttemptUuid;
+    use omicron_uuid_kinds::WebhookDeliveryUuid;
 
     #[tokio::test]
     async fn test_dispatched_deliveries_are_unique_per_rx() {
@@ -592,6 +625,91 @@ mod test {
         logctx.cleanup_successful();
     }
 
+    #[tokio::test]
+    async fn test_webhook_rx_peer_cert_expiry_list() {
+        let logctx =
+            dev::test_setup_log("test_webhook_rx_peer_cert_expiry_list");
+        let db = TestDatabase::new_with_datastore(&logctx.log).await;
+        let (opctx, datastore) = (db.opctx(), db.datastore());
+        let rx = datastore
+            .webhook_rx_create(
+                opctx,
+                alert::WebhookCreate {
+                    identity: IdentityMetadataCreateParams {
+                        name: "test-webhook".parse().unwrap(),
+                        description: String::new(),
+                    },
+                    endpoint: "https://webhooks.example.com".parse().unwrap(),
+                    secrets: vec!["my cool secret".to_string()],
+                    subscriptions: vec![],
+                },
+            )
+            .await
+            .unwrap();
+        let rx_id: AlertReceiverUuid = rx.rx.identity.id.into();
+
+        // Nothing is reported until an attempt records a certificate.
+        assert!(
+            datastore
+                .webhook_rx_peer_cert_expiry_list(opctx)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        // The receiver's certificate is renewed, and then an attempt fails
+        // before the handshake completes.
+        let now = Utc::now();
+        let renewed = now + TimeDelta::days(90);
+        let attempts = [
+            (now - TimeDelta::hours(2), Some(now + TimeDelta::days(1))),
+            (now - TimeDelta::hours(1), Some(renewed)),
+            (now, None),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, (time_created, time_peer_cert_expires))| {
+            let succeeded = time_peer_cert_expires.is_some();
+            WebhookDeliveryAttempt {
+                id: WebhookDeliveryAttemptUuid::new_v4().into(),
+                delivery_id: WebhookDeliveryUuid::new_v4().into(),
+                attempt: model::SqlU8::new(u8::try_from(i).unwrap() + 1),
+                rx_id: rx_id.into(),
+                result: if succeeded {
+                    WebhookDeliveryAttemptResult::Succeeded
+                } else {
+                    WebhookDeliveryAttemptResult::FailedUnreachable
+                },
+                response_status: succeeded.then(|| model::SqlU16::new(200)),
+                response_duration: succeeded
+                    .then(|| TimeDelta::milliseconds(10)),
+                time_created,
+                deliverator_id: OmicronZoneUuid::new_v4().into(),
+                time_peer_cert_expires,
+            }
+        })
+        .collect::<Vec<_>>();
+        let conn = datastore.pool_connection_for_tests().await.unwrap();
+        diesel::insert_into(attempt_dsl::webhook_delivery_attempt)
+            .values(attempts)
+            .execute_async(&*conn)
+            .await
+            .unwrap();
+
+        // Only the most recently presented certificate is reported.
+        let expiries =
+            datastore.webhook_rx_peer_cert_expiry_list(opctx).await.unwrap();
+        assert_eq!(expiries.len(), 1);
+        assert_eq!(expiries[0].0.identity.id, rx.rx.identity.id);
+        assert_eq!(
+            expiries[0].1.timestamp_micros(),
+            renewed.timestamp_micros()
+        );
+
+        db.terminate().await;
+        logctx.cleanup_successful();
+    }
+
     #[tokio::test]
     async fn expectorate_rx_list_resendable() {
         let query = DataStore::rx_list_resendable_events_query(
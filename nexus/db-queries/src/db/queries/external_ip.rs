@@ -798,14 +798,17 @@ impl QueryFragment<Pg> for NextExternalIp {
             }
 
             (IpKind::Ephemeral, None, None)
-            | (IpKind::Floating, None, None) => {
+            | (IpKind::Floating, None, None)
+            | (IpKind::LoadBalancer, None, None) => {
                 self.push_automatic_full_ip_subquery(out.reborrow())?;
                 out.push_sql("), ");
             }
 
             (IpKind::SNat, Some(ip), Some((first_port, last_port)))
             | (IpKind::Ephemeral, Some(ip), Some((first_port, last_port)))
-            | (IpKind::Floating, Some(ip), Some((first_port, last_port))) => {
+            | (IpKind::Floating, Some(ip), Some((first_port, last_port)))
+            | (IpKind::LoadBalancer, Some(ip), Some((first_port, last_port))) =>
+            {
                 self.push_explicit_ip_subquery(
                     out.reborrow(),
                     ip,
@@ -825,7 +828,9 @@ impl QueryFragment<Pg> for NextExternalIp {
             | (IpKind::Ephemeral, None, Some(_))
             | (IpKind::Ephemeral, Some(_), None)
             | (IpKind::Floating, None, Some(_))
-            | (IpKind::Floating, Some(_), None) => unreachable!(
+            | (IpKind::Floating, Some(_), None)
+            | (IpKind::LoadBalancer, None, Some(_))
+            | (IpKind::LoadBalancer, Some(_), None) => unreachable!(
                 "IP and port ranges should always be either both \
                 Some(_) or both None"
             ),
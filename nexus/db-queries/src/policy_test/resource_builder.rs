@@ -263,6 +263,7 @@ impl_dyn_authorized_resource_for_resource!(authz::Disk);
 impl_dyn_authorized_resource_for_resource!(authz::ExternalSubnet);
 impl_dyn_authorized_resource_for_resource!(authz::Fleet);
 impl_dyn_authorized_resource_for_resource!(authz::FloatingIp);
+impl_dyn_authorized_resource_for_resource!(authz::LoadBalancer);
 impl_dyn_authorized_resource_for_resource!(authz::IdentityProvider);
 impl_dyn_authorized_resource_for_resource!(authz::Image);
 impl_dyn_authorized_resource_for_resource!(authz::Instance);
@@ -280,6 +281,8 @@ impl_dyn_authorized_resource_for_resource!(authz::RouterRoute);
 impl_dyn_authorized_resource_for_resource!(authz::SamlIdentityProvider);
 impl_dyn_authorized_resource_for_resource!(authz::ScimClientBearerToken);
 impl_dyn_authorized_resource_for_resource!(authz::Service);
+impl_dyn_authorized_resource_for_resource!(authz::ServiceAccount);
+impl_dyn_authorized_resource_for_resource!(authz::ServiceAccountToken);
 impl_dyn_authorized_resource_for_resource!(authz::Silo);
 impl_dyn_authorized_resource_for_resource!(authz::SiloGroup);
 impl_dyn_authorized_resource_for_resource!(authz::SiloImage);
@@ -298,6 +301,7 @@ impl_dyn_authorized_resource_for_resource!(authz::VpcRouter);
 impl_dyn_authorized_resource_for_resource!(authz::VpcSubnet);
 impl_dyn_authorized_resource_for_resource!(authz::Alert);
 impl_dyn_authorized_resource_for_resource!(authz::AlertReceiver);
+impl_dyn_authorized_resource_for_resource!(authz::AuditLogSink);
 impl_dyn_authorized_resource_for_resource!(authz::WebhookSecret);
 impl_dyn_authorized_resource_for_resource!(authz::Zpool);
 impl_dyn_authorized_resource_for_resource!(authz::MulticastGroup);
@@ -351,6 +355,23 @@ impl DynAuthorizedResource for authz::SiloCertificateList {
     }
 }
 
+impl DynAuthorizedResource for authz::SiloServiceAccountList {
+    fn do_authorize<'a, 'b>(
+        &'a self,
+        opctx: &'b OpContext,
+        action: authz::Action,
+    ) -> BoxFuture<'a, Result<(), Error>>
+    where
+        'b: 'a,
+    {
+        opctx.authorize(action, self).boxed()
+    }
+
+    fn resource_name(&self) -> String {
+        format!("{}: service account list", self.silo().resource_name())
+    }
+}
+
 impl DynAuthorizedResource for authz::SiloIdentityProviderList {
     fn do_authorize<'a, 'b>(
         &'a self,
@@ -14,6 +14,8 @@ use omicron_uuid_kinds::ConsoleSessionKind;
 use omicron_uuid_kinds::GenericUuid;
 use omicron_uuid_kinds::PhysicalDiskUuid;
 use omicron_uuid_kinds::RackUuid;
+use omicron_uuid_kinds::ServiceAccountTokenUuid;
+use omicron_uuid_kinds::ServiceAccountUuid;
 use omicron_uuid_kinds::SiloGroupUuid;
 use omicron_uuid_kinds::SiloUserUuid;
 use omicron_uuid_kinds::SupportBundleUuid;
@@ -199,6 +201,14 @@ pub async fn make_resources(
 
     make_webhook_rx(&mut builder).await;
 
+    let audit_log_sink_id =
+        "5a3a4bb4-2ad5-4a1c-9e49-5a4a1d0e6c49".parse().unwrap();
+    builder.new_resource(authz::AuditLogSink::new(
+        authz::FLEET,
+        audit_log_sink_id,
+        LookupType::by_id(audit_log_sink_id),
+    ));
+
     let subnet_pool_id =
         "e3a6e04e-ad41-483c-8ee9-3958c3ffb4e5".parse().unwrap();
     builder.new_resource(authz::SubnetPool::new(
@@ -302,6 +312,19 @@ async fn make_silo(
         LookupType::ByName(format!("{}-saml-identity-provider", silo_name)),
     ));
 
+    builder.new_resource(authz::SiloServiceAccountList::new(silo.clone()));
+    let service_account = authz::ServiceAccount::new(
+        silo.clone(),
+        ServiceAccountUuid::new_v4(),
+        LookupType::ByName(format!("{}-service-account", silo_name)),
+    );
+    builder.new_resource(service_account.clone());
+    builder.new_resource(authz::ServiceAccountToken::new(
+        service_account,
+        ServiceAccountTokenUuid::new_v4(),
+        LookupType::ByName(format!("{}-service-account-token", silo_name)),
+    ));
+
     builder.new_resource(authz::SiloUserList::new(silo.clone()));
     builder.new_resource(authz::SiloGroupList::new(silo.clone()));
     let silo_user_id = SiloUserUuid::new_v4();
@@ -477,6 +500,13 @@ async fn make_project(
         LookupType::ByName(floating_ip_name),
     ));
 
+    let load_balancer_name = format!("{project_name}-lb1");
+    builder.new_resource(authz::LoadBalancer::new(
+        project.clone(),
+        Uuid::new_v4(),
+        LookupType::ByName(load_balancer_name),
+    ));
+
     let igw_name = format!("{project_name}-igw1");
     let igw = authz::InternetGateway::new(
         vpc1.clone(),
@@ -33,9 +33,11 @@ define_enums! {
     AddressLotKindEnum => "address_lot_kind",
     AffinityPolicyEnum => "affinity_policy",
     AlertClassEnum => "alert_class",
+    AlertReceiverKindEnum => "alert_receiver_kind",
     AuditLogActorKindEnum => "audit_log_actor_kind",
     AuditLogAuthMethodEnum => "audit_log_auth_method",
     AuditLogResultKindEnum => "audit_log_result_kind",
+    AuditLogSinkKindEnum => "audit_log_sink_kind",
     AlertDeliveryTriggerEnum => "alert_delivery_trigger",
     AlertDeliveryStateEnum => "alert_delivery_state",
     AuthenticationModeEnum => "authentication_mode",
@@ -54,6 +56,8 @@ define_enums! {
     DiagnosisEngineEnum => "diagnosis_engine",
     DiskTypeEnum => "disk_type",
     DnsGroupEnum => "dns_group",
+    DnssecKeyRoleEnum => "dnssec_key_role",
+    DnssecKeyStateEnum => "dnssec_key_state",
     DownstairsClientStopRequestReasonEnum => "downstairs_client_stop_request_reason_type",
     DownstairsClientStoppedReasonEnum => "downstairs_client_stopped_reason_type",
     EreporterTypeEnum => "ereporter_type",
@@ -78,6 +82,7 @@ define_enums! {
     IpPoolResourceTypeEnum => "ip_pool_resource_type",
     IpPoolTypeEnum => "ip_pool_type",
     IpVersionEnum => "ip_version",
+    LoadBalancerBackendHealthEnum => "load_balancer_backend_health",
     MigrationStateEnum => "migration_state",
     MulticastGroupStateEnum => "multicast_group_state",
     MulticastGroupMemberStateEnum => "multicast_group_member_state",
@@ -103,6 +108,7 @@ define_enums! {
     SagaStateEnum => "saga_state",
     ServiceKindEnum => "service_kind",
     SledCpuFamilyEnum => "sled_cpu_family",
+    SledEvacuationStateEnum => "sled_evacuation_state",
     SledPolicyEnum => "sled_policy",
     SledResourceVmmStateEnum => "sled_resource_vmm_state",
     SledRoleEnum => "sled_role",
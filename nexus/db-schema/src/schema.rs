@@ -596,6 +596,16 @@ table! {
     }
 }
 
+table! {
+    silo_rate_limit(silo_id) {
+        silo_id -> Uuid,
+        time_created -> Timestamptz,
+        time_modified -> Timestamptz,
+        requests_per_second -> Nullable<Int8>,
+        burst -> Nullable<Int8>,
+    }
+}
+
 table! {
     system_networking_settings(singleton) {
         singleton -> Bool,
@@ -791,6 +801,47 @@ table! {
     }
 }
 
+table! {
+    load_balancer (id) {
+        id -> Uuid,
+        name -> Text,
+        description -> Text,
+        time_created -> Timestamptz,
+        time_modified -> Timestamptz,
+        time_deleted -> Nullable<Timestamptz>,
+        project_id -> Uuid,
+        external_ip_id -> Uuid,
+        ports -> Array<Int4>,
+        backend_tag -> Nullable<Text>,
+        health_check_port -> Nullable<Int4>,
+        healthy_threshold -> Nullable<Int4>,
+        unhealthy_threshold -> Nullable<Int4>,
+    }
+}
+
+table! {
+    load_balancer_backend (load_balancer_id, instance_id) {
+        load_balancer_id -> Uuid,
+        instance_id -> Uuid,
+        time_created -> Timestamptz,
+        time_modified -> Timestamptz,
+        explicit -> Bool,
+        health -> crate::enums::LoadBalancerBackendHealthEnum,
+        consecutive_successes -> Int4,
+        consecutive_failures -> Int4,
+        frontend_vmm_id -> Nullable<Uuid>,
+        ports -> Array<Int4>,
+    }
+}
+
+allow_tables_to_appear_in_same_query!(load_balancer, load_balancer_backend);
+allow_tables_to_appear_in_same_query!(load_balancer_backend, instance);
+allow_tables_to_appear_in_same_query!(load_balancer_backend, vmm);
+allow_tables_to_appear_in_same_query!(load_balancer_backend, sled);
+allow_tables_to_appear_in_same_query!(load_balancer_backend, network_interface);
+allow_tables_to_appear_in_same_query!(load_balancer_backend, vpc);
+allow_tables_to_appear_in_same_query!(load_balancer_backend, vpc_subnet);
+
 table! {
     subnet_pool (id) {
         id -> Uuid,
@@ -987,6 +1038,14 @@ table! {
     }
 }
 
+table! {
+    instance_network_tag (instance_id, tag) {
+        instance_id -> Uuid,
+        tag -> Text,
+        time_created -> Timestamptz,
+    }
+}
+
 table! {
     oximeter (id) {
         id -> Uuid,
@@ -1115,6 +1174,19 @@ table! {
     }
 }
 
+table! {
+    sled_evacuation (id) {
+        id -> Uuid,
+        sled_id -> Uuid,
+        time_started -> Timestamptz,
+        time_finished -> Nullable<Timestamptz>,
+        state -> crate::enums::SledEvacuationStateEnum,
+        instances_total -> Int8,
+        migrations_started -> Int8,
+        last_error -> Nullable<Text>,
+    }
+}
+
 table! {
     sled_underlay_subnet_allocation (hw_baseboard_id, sled_id) {
         hw_baseboard_id -> Uuid,
@@ -1183,6 +1255,24 @@ table! {
         service -> crate::enums::ServiceKindEnum,
         cert -> Binary,
         key -> Binary,
+        acme_issued -> Bool,
+    }
+}
+
+table! {
+    acme_account (directory_url) {
+        directory_url -> Text,
+        time_created -> Timestamptz,
+        account_url -> Text,
+        key_pem -> Text,
+    }
+}
+
+table! {
+    acme_http01_challenge (token) {
+        token -> Text,
+        time_created -> Timestamptz,
+        key_authorization -> Text,
     }
 }
 
@@ -1453,6 +1543,18 @@ table! {
     }
 }
 
+table! {
+    vpc_dns_record (id) {
+        id -> Uuid,
+        time_created -> Timestamptz,
+        time_modified -> Timestamptz,
+        time_deleted -> Nullable<Timestamptz>,
+        vpc_id -> Uuid,
+        name -> Text,
+        address -> Inet,
+    }
+}
+
 table! {
     dns_zone (id) {
         id -> Uuid,
@@ -1482,6 +1584,38 @@ table! {
     }
 }
 
+table! {
+    vpc_dns_config (singleton) {
+        singleton -> Bool,
+        generation -> Int8,
+        time_created -> Timestamptz,
+        zones -> Jsonb,
+        forwarders -> Array<Inet>,
+    }
+}
+
+table! {
+    dnssec_key (id) {
+        id -> Uuid,
+        time_created -> Timestamptz,
+        time_activated -> Nullable<Timestamptz>,
+        time_retired -> Nullable<Timestamptz>,
+        time_deleted -> Nullable<Timestamptz>,
+        zone_name -> Text,
+        role -> crate::enums::DnssecKeyRoleEnum,
+        state -> crate::enums::DnssecKeyStateEnum,
+        private_key -> Text,
+    }
+}
+
+table! {
+    dnssec_config (singleton) {
+        singleton -> Bool,
+        generation -> Int8,
+        time_modified -> Timestamptz,
+    }
+}
+
 table! {
     user_builtin (id) {
         id -> Uuid,
@@ -1517,6 +1651,36 @@ table! {
     }
 }
 
+table! {
+    service_account (id) {
+        id -> Uuid,
+        name -> Text,
+        description -> Text,
+        time_created -> Timestamptz,
+        time_modified -> Timestamptz,
+        time_deleted -> Nullable<Timestamptz>,
+        silo_id -> Uuid,
+    }
+}
+
+table! {
+    service_account_token (id) {
+        id -> Uuid,
+        name -> Text,
+        description -> Text,
+        time_created -> Timestamptz,
+        time_modified -> Timestamptz,
+        time_deleted -> Nullable<Timestamptz>,
+        service_account_id -> Uuid,
+        token -> Text,
+        time_expires -> Nullable<Timestamptz>,
+        read_only -> Bool,
+        project_ids -> Nullable<Array<Uuid>>,
+    }
+}
+
+allow_tables_to_appear_in_same_query!(service_account, service_account_token);
+
 table! {
     role_assignment (
         resource_id,
@@ -2716,6 +2880,7 @@ allow_tables_to_appear_in_same_query!(
     metric_producer,
     network_interface,
     instance_network_interface,
+    instance_network_tag,
     inv_physical_disk,
     inv_nvme_disk_firmware,
     physical_disk_adoption_request,
@@ -2850,6 +3015,10 @@ table! {
         secret_gen -> Int8,
         subscription_gen -> Int8,
         endpoint -> Text,
+        kind -> crate::enums::AlertReceiverKindEnum,
+        smtp_from -> Nullable<Text>,
+        smtp_to -> Nullable<Array<Text>>,
+        syslog_facility -> Nullable<Int2>,
     }
 }
 
@@ -2927,6 +3096,7 @@ allow_tables_to_appear_in_same_query!(alert_receiver, webhook_delivery);
 joinable!(webhook_delivery -> alert_receiver (rx_id));
 allow_tables_to_appear_in_same_query!(webhook_delivery, alert);
 allow_tables_to_appear_in_same_query!(webhook_delivery_attempt, alert);
+allow_tables_to_appear_in_same_query!(webhook_delivery_attempt, alert_receiver);
 joinable!(webhook_delivery -> alert (alert_id));
 
 table! {
@@ -2940,6 +3110,7 @@ table! {
         response_duration -> Nullable<Interval>,
         time_created -> Timestamptz,
         deliverator_id -> Uuid,
+        time_peer_cert_expires -> Nullable<Timestamptz>,
     }
 }
 
@@ -3065,6 +3236,9 @@ table! {
         result_kind -> Nullable<crate::enums::AuditLogResultKindEnum>,
         auth_method -> Nullable<crate::enums::AuditLogAuthMethodEnum>,
         credential_id -> Nullable<Uuid>,
+        resource_type -> Nullable<Text>,
+        resource_id -> Nullable<Uuid>,
+        request_body -> Nullable<Jsonb>,
     }
 }
 
@@ -3087,6 +3261,30 @@ table! {
         result_kind -> crate::enums::AuditLogResultKindEnum,
         auth_method -> Nullable<crate::enums::AuditLogAuthMethodEnum>,
         credential_id -> Nullable<Uuid>,
+        resource_type -> Nullable<Text>,
+        resource_id -> Nullable<Uuid>,
+        request_body -> Nullable<Jsonb>,
+    }
+}
+
+table! {
+    audit_log_sink (id) {
+        id -> Uuid,
+        name -> Text,
+        description -> Text,
+        time_created -> Timestamptz,
+        time_modified -> Timestamptz,
+        time_deleted -> Nullable<Timestamptz>,
+        kind -> crate::enums::AuditLogSinkKindEnum,
+        endpoint -> Text,
+        tls_root_cert -> Nullable<Text>,
+        secret -> Nullable<Text>,
+        cursor_time_completed -> Timestamptz,
+        cursor_id -> Uuid,
+        time_last_delivered -> Nullable<Timestamptz>,
+        consecutive_failures -> Int4,
+        time_next_attempt -> Nullable<Timestamptz>,
+        last_error -> Nullable<Text>,
     }
 }
 
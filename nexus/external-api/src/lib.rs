@@ -29,6 +29,7 @@ use nexus_types_versions::v2026_01_03_00;
 use nexus_types_versions::v2026_01_05_00;
 use nexus_types_versions::v2026_01_08_00;
 use nexus_types_versions::v2026_01_15_00;
+use nexus_types_versions::v2026_01_15_01;
 use nexus_types_versions::v2026_01_16_00;
 use nexus_types_versions::v2026_01_16_01;
 use nexus_types_versions::v2026_01_22_00;
@@ -37,6 +38,8 @@ use nexus_types_versions::v2026_01_31_00;
 use nexus_types_versions::v2026_02_13_01;
 use nexus_types_versions::v2026_04_16_00;
 use nexus_types_versions::v2026_06_05_00;
+use nexus_types_versions::v2026_10_19_00;
+use nexus_types_versions::v2026_10_19_08;
 use omicron_common::address::IpRange;
 use omicron_common::api::external::{
     http_pagination::{
@@ -56,6 +59,8 @@ mod v2026_01_01_00_local;
 mod v2026_01_30_00_local;
 mod v2026_03_24_00_local;
 mod v2026_05_20_00_local;
+mod v2026_10_19_05_local;
+mod v2026_10_19_06_local;
 
 api_versions!([
     // API versions are in the format YYYY_MM_DD_NN.0.0, defined below as
@@ -86,6 +91,17 @@ api_versions!([
     // |  date-based version should be at the top of the list.
     // v
     // (next_yyyy_mm_dd_nn, IDENT),
+    (2026_10_19_10, LOAD_BALANCER_PORT_ASSIGNMENTS),
+    (2026_10_19_09, VPC_DNS),
+    (2026_10_19_08, LOAD_BALANCERS),
+    (2026_10_19_07, FIREWALL_RULE_GENERATION),
+    (2026_10_19_06, FIREWALL_TAGS),
+    (2026_10_19_05, SLED_EVACUATION),
+    (2026_10_19_04, ALERT_RECEIVER_KINDS),
+    (2026_10_19_03, AUDIT_LOG_RESOURCE),
+    (2026_10_19_02, AUDIT_LOG_SINKS),
+    (2026_10_19_01, SILO_RATE_LIMIT),
+    (2026_10_19_00, SERVICE_ACCOUNTS),
     (2026_06_08_00, INSTANCE_CPU_TYPE_TURIN_V2),
     (2026_06_05_00, EXTERNAL_JUMBO_FRAMES),
     (2026_06_04_00, IMAGE_BLOCK_SIZE_TYPE),
@@ -261,6 +277,13 @@ const PUT_UPDATE_REPOSITORY_MAX_BYTES: usize = 4 * GIB;
                     url = "http://docs.oxide.computer/api/ip-pools"
                 }
             },
+            "load-balancers" = {
+                description = "Load balancers distribute traffic sent to an \
+                    external address across a set of instances.",
+                external_docs = {
+                    url = "http://docs.oxide.computer/api/load-balancers"
+                }
+            },
             "login" = {
                 description = "Authentication endpoints",
                 external_docs = {
@@ -595,6 +618,34 @@ pub trait NexusExternalApi {
         new_quota: TypedBody<latest::silo::SiloQuotasUpdate>,
     ) -> Result<HttpResponseOk<latest::silo::SiloQuotas>, HttpError>;
 
+    /// Fetch rate limit for silo
+    #[endpoint {
+        method = GET,
+        path = "/v1/system/silos/{silo}/rate-limit",
+        tags = ["system/silos"],
+        versions = VERSION_SILO_RATE_LIMIT..,
+    }]
+    async fn silo_rate_limit_view(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<latest::path_params::SiloPath>,
+    ) -> Result<HttpResponseOk<latest::silo::SiloRateLimit>, HttpError>;
+
+    /// Update rate limit for silo
+    ///
+    /// Both values are replaced. Setting a value to null makes the silo use the
+    /// fleet-wide default.
+    #[endpoint {
+        method = PUT,
+        path = "/v1/system/silos/{silo}/rate-limit",
+        tags = ["system/silos"],
+        versions = VERSION_SILO_RATE_LIMIT..,
+    }]
+    async fn silo_rate_limit_update(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<latest::path_params::SiloPath>,
+        new_rate_limit: TypedBody<latest::silo::SiloRateLimitUpdate>,
+    ) -> Result<HttpResponseOk<latest::silo::SiloRateLimit>, HttpError>;
+
     /// List silos
     ///
     /// Lists silos that are discoverable based on the current permissions.
@@ -2993,6 +3044,270 @@ pub trait NexusExternalApi {
         query_params: Query<latest::project::OptionalProjectSelector>,
     ) -> Result<HttpResponseAccepted<latest::floating_ip::FloatingIp>, HttpError>;
 
+    // Load Balancers
+
+    /// List load balancers
+    #[endpoint {
+        method = GET,
+        path = "/v1/load-balancers",
+        tags = ["load-balancers"],
+        versions = VERSION_LOAD_BALANCER_PORT_ASSIGNMENTS..,
+    }]
+    async fn load_balancer_list(
+        rqctx: RequestContext<Self::Context>,
+        query_params: Query<
+            PaginatedByNameOrId<latest::project::ProjectSelector>,
+        >,
+    ) -> Result<
+        HttpResponseOk<ResultsPage<latest::load_balancer::LoadBalancer>>,
+        HttpError,
+    >;
+
+    /// List load balancers
+    #[endpoint {
+        operation_id = "load_balancer_list",
+        method = GET,
+        path = "/v1/load-balancers",
+        tags = ["load-balancers"],
+        versions = VERSION_LOAD_BALANCERS..VERSION_LOAD_BALANCER_PORT_ASSIGNMENTS,
+    }]
+    async fn load_balancer_list_v2026_10_19_08(
+        rqctx: RequestContext<Self::Context>,
+        query_params: Query<
+            PaginatedByNameOrId<latest::project::ProjectSelector>,
+        >,
+    ) -> Result<
+        HttpResponseOk<
+            ResultsPage<v2026_10_19_08::load_balancer::LoadBalancer>,
+        >,
+        HttpError,
+    > {
+        Self::load_balancer_list(rqctx, query_params).await.map(
+            |HttpResponseOk(page)| {
+                HttpResponseOk(ResultsPage {
+                    items: page.items.into_iter().map(Into::into).collect(),
+                    next_page: page.next_page,
+                })
+            },
+        )
+    }
+
+    /// Create load balancer
+    ///
+    /// Allocates an external address for the load balancer, in the same way
+    /// as for a floating IP. Traffic to that address on each of the listener
+    /// ports is forwarded to one of the load balancer's healthy, running
+    /// backends. Each port is served by a single backend at a time, so a load
+    /// balancer spreads ports, not connections, across its backends.
+    #[endpoint {
+        method = POST,
+        path = "/v1/load-balancers",
+        tags = ["load-balancers"],
+        versions = VERSION_LOAD_BALANCER_PORT_ASSIGNMENTS..,
+    }]
+    async fn load_balancer_create(
+        rqctx: RequestContext<Self::Context>,
+        query_params: Query<latest::project::ProjectSelector>,
+        new_load_balancer: TypedBody<latest::load_balancer::LoadBalancerCreate>,
+    ) -> Result<
+        HttpResponseCreated<latest::load_balancer::LoadBalancer>,
+        HttpError,
+    >;
+
+    /// Create load balancer
+    ///
+    /// Allocates an external address for the load balancer, in the same way
+    /// as for a floating IP. Traffic to that address on each of the listener
+    /// ports is forwarded to one of the load balancer's healthy, running
+    /// backends.
+    #[endpoint {
+        operation_id = "load_balancer_create",
+        method = POST,
+        path = "/v1/load-balancers",
+        tags = ["load-balancers"],
+        versions = VERSION_LOAD_BALANCERS..VERSION_LOAD_BALANCER_PORT_ASSIGNMENTS,
+    }]
+    async fn load_balancer_create_v2026_10_19_08(
+        rqctx: RequestContext<Self::Context>,
+        query_params: Query<latest::project::ProjectSelector>,
+        new_load_balancer: TypedBody<latest::load_balancer::LoadBalancerCreate>,
+    ) -> Result<
+        HttpResponseCreated<v2026_10_19_08::load_balancer::LoadBalancer>,
+        HttpError,
+    > {
+        Self::load_balancer_create(rqctx, query_params, new_load_balancer)
+            .await
+            .map(|HttpResponseCreated(lb)| HttpResponseCreated(lb.into()))
+    }
+
+    /// Fetch load balancer
+    #[endpoint {
+        method = GET,
+        path = "/v1/load-balancers/{load_balancer}",
+        tags = ["load-balancers"],
+        versions = VERSION_LOAD_BALANCER_PORT_ASSIGNMENTS..,
+    }]
+    async fn load_balancer_view(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<latest::load_balancer::LoadBalancerPath>,
+        query_params: Query<latest::project::OptionalProjectSelector>,
+    ) -> Result<HttpResponseOk<latest::load_balancer::LoadBalancer>, HttpError>;
+
+    /// Fetch load balancer
+    #[endpoint {
+        operation_id = "load_balancer_view",
+        method = GET,
+        path = "/v1/load-balancers/{load_balancer}",
+        tags = ["load-balancers"],
+        versions = VERSION_LOAD_BALANCERS..VERSION_LOAD_BALANCER_PORT_ASSIGNMENTS,
+    }]
+    async fn load_balancer_view_v2026_10_19_08(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<latest::load_balancer::LoadBalancerPath>,
+        query_params: Query<latest::project::OptionalProjectSelector>,
+    ) -> Result<
+        HttpResponseOk<v2026_10_19_08::load_balancer::LoadBalancer>,
+        HttpError,
+    > {
+        Self::load_balancer_view(rqctx, path_params, query_params)
+            .await
+            .map(|HttpResponseOk(lb)| HttpResponseOk(lb.into()))
+    }
+
+    /// Delete load balancer
+    ///
+    /// Stops forwarding traffic to the backends and releases the load
+    /// balancer's external address.
+    #[endpoint {
+        method = DELETE,
+        path = "/v1/load-balancers/{load_balancer}",
+        tags = ["load-balancers"],
+        versions = VERSION_LOAD_BALANCERS..,
+    }]
+    async fn load_balancer_delete(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<latest::load_balancer::LoadBalancerPath>,
+        query_params: Query<latest::project::OptionalProjectSelector>,
+    ) -> Result<HttpResponseDeleted, HttpError>;
+
+    /// List load balancer backends
+    ///
+    /// Lists both the instances added explicitly and those selected by the
+    /// load balancer's backend tag, along with their health.
+    #[endpoint {
+        method = GET,
+        path = "/v1/load-balancers/{load_balancer}/backends",
+        tags = ["load-balancers"],
+        versions = VERSION_LOAD_BALANCER_PORT_ASSIGNMENTS..,
+    }]
+    async fn load_balancer_backend_list(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<latest::load_balancer::LoadBalancerPath>,
+        query_params: Query<
+            PaginatedById<latest::project::OptionalProjectSelector>,
+        >,
+    ) -> Result<
+        HttpResponseOk<ResultsPage<latest::load_balancer::LoadBalancerBackend>>,
+        HttpError,
+    >;
+
+    /// List load balancer backends
+    ///
+    /// Lists both the instances added explicitly and those selected by the
+    /// load balancer's backend tag, along with their health.
+    #[endpoint {
+        operation_id = "load_balancer_backend_list",
+        method = GET,
+        path = "/v1/load-balancers/{load_balancer}/backends",
+        tags = ["load-balancers"],
+        versions = VERSION_LOAD_BALANCERS..VERSION_LOAD_BALANCER_PORT_ASSIGNMENTS,
+    }]
+    async fn load_balancer_backend_list_v2026_10_19_08(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<latest::load_balancer::LoadBalancerPath>,
+        query_params: Query<
+            PaginatedById<latest::project::OptionalProjectSelector>,
+        >,
+    ) -> Result<
+        HttpResponseOk<
+            ResultsPage<v2026_10_19_08::load_balancer::LoadBalancerBackend>,
+        >,
+        HttpError,
+    > {
+        Self::load_balancer_backend_list(rqctx, path_params, query_params)
+            .await
+            .map(|HttpResponseOk(page)| {
+                HttpResponseOk(ResultsPage {
+                    items: page.items.into_iter().map(Into::into).collect(),
+                    next_page: page.next_page,
+                })
+            })
+    }
+
+    /// Add load balancer backend
+    ///
+    /// The instance must be in the same project as the load balancer.
+    #[endpoint {
+        method = POST,
+        path = "/v1/load-balancers/{load_balancer}/backends",
+        tags = ["load-balancers"],
+        versions = VERSION_LOAD_BALANCER_PORT_ASSIGNMENTS..,
+    }]
+    async fn load_balancer_backend_add(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<latest::load_balancer::LoadBalancerPath>,
+        query_params: Query<latest::project::OptionalProjectSelector>,
+        backend: TypedBody<latest::load_balancer::LoadBalancerBackendCreate>,
+    ) -> Result<
+        HttpResponseCreated<latest::load_balancer::LoadBalancerBackend>,
+        HttpError,
+    >;
+
+    /// Add load balancer backend
+    ///
+    /// The instance must be in the same project as the load balancer.
+    #[endpoint {
+        operation_id = "load_balancer_backend_add",
+        method = POST,
+        path = "/v1/load-balancers/{load_balancer}/backends",
+        tags = ["load-balancers"],
+        versions = VERSION_LOAD_BALANCERS..VERSION_LOAD_BALANCER_PORT_ASSIGNMENTS,
+    }]
+    async fn load_balancer_backend_add_v2026_10_19_08(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<latest::load_balancer::LoadBalancerPath>,
+        query_params: Query<latest::project::OptionalProjectSelector>,
+        backend: TypedBody<latest::load_balancer::LoadBalancerBackendCreate>,
+    ) -> Result<
+        HttpResponseCreated<v2026_10_19_08::load_balancer::LoadBalancerBackend>,
+        HttpError,
+    > {
+        Self::load_balancer_backend_add(
+            rqctx,
+            path_params,
+            query_params,
+            backend,
+        )
+        .await
+        .map(|HttpResponseCreated(backend)| HttpResponseCreated(backend.into()))
+    }
+
+    /// Remove load balancer backend
+    ///
+    /// Only backends added explicitly can be removed. Backends selected by
+    /// the backend tag are removed by removing the tag from the instance.
+    #[endpoint {
+        method = DELETE,
+        path = "/v1/load-balancers/{load_balancer}/backends/{instance}",
+        tags = ["load-balancers"],
+        versions = VERSION_LOAD_BALANCERS..,
+    }]
+    async fn load_balancer_backend_remove(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<latest::load_balancer::LoadBalancerBackendPath>,
+        query_params: Query<latest::project::OptionalProjectSelector>,
+    ) -> Result<HttpResponseDeleted, HttpError>;
+
     // Multicast Groups
     //
     // TODO: Consider adding `.map()` to dropshot's `Path<T>` (like `TypedBody`)
@@ -4175,6 +4490,24 @@ pub trait NexusExternalApi {
         Ok(HttpResponseAccepted(resp.0.into()))
     }
 
+    /// Migrate instance
+    ///
+    /// Live-migrates a running instance to another sled. This operation is
+    /// only available to fleet operators. The response indicates that the
+    /// migration has started; the instance's state reflects its progress.
+    #[endpoint {
+        method = POST,
+        path = "/v1/instances/{instance}/migrate",
+        tags = ["instances"],
+        versions = VERSION_SLED_EVACUATION..,
+    }]
+    async fn instance_migrate(
+        rqctx: RequestContext<Self::Context>,
+        query_params: Query<latest::project::OptionalProjectSelector>,
+        path_params: Path<latest::path_params::InstancePath>,
+        migrate_params: TypedBody<latest::instance::InstanceMigrate>,
+    ) -> Result<HttpResponseAccepted<latest::instance::Instance>, HttpError>;
+
     /// Boot instance
     #[endpoint {
         method = POST,
@@ -4337,6 +4670,37 @@ pub trait NexusExternalApi {
         >,
     ) -> Result<HttpResponseOk<ResultsPage<latest::ssh_key::SshKey>>, HttpError>;
 
+    /// View network tags for instance
+    #[endpoint {
+        method = GET,
+        path = "/v1/instances/{instance}/network-tags",
+        tags = ["instances"],
+        versions = VERSION_FIREWALL_TAGS..,
+    }]
+    async fn instance_network_tags_view(
+        rqctx: RequestContext<Self::Context>,
+        query_params: Query<latest::project::OptionalProjectSelector>,
+        path_params: Path<latest::path_params::InstancePath>,
+    ) -> Result<HttpResponseOk<latest::instance::InstanceNetworkTags>, HttpError>;
+
+    /// Replace network tags for instance
+    ///
+    /// VPC firewall rules that target, or filter traffic by, the instance's
+    /// old or new tags are updated accordingly. The maximum number of tags per
+    /// instance is 32.
+    #[endpoint {
+        method = PUT,
+        path = "/v1/instances/{instance}/network-tags",
+        tags = ["instances"],
+        versions = VERSION_FIREWALL_TAGS..,
+    }]
+    async fn instance_network_tags_update(
+        rqctx: RequestContext<Self::Context>,
+        query_params: Query<latest::project::OptionalProjectSelector>,
+        path_params: Path<latest::path_params::InstancePath>,
+        tags: TypedBody<latest::instance::InstanceNetworkTagsUpdate>,
+    ) -> Result<HttpResponseOk<latest::instance::InstanceNetworkTags>, HttpError>;
+
     /// List disks for instance
     #[endpoint {
         method = GET,
@@ -6914,13 +7278,49 @@ pub trait NexusExternalApi {
         method = GET,
         path = "/v1/vpc-firewall-rules",
         tags = ["vpcs"],
-        versions = VERSION_ADD_ICMPV6_FIREWALL_SUPPORT..,
+        versions = VERSION_FIREWALL_RULE_GENERATION..,
     }]
     async fn vpc_firewall_rules_view(
         rqctx: RequestContext<Self::Context>,
         query_params: Query<latest::vpc::VpcSelector>,
     ) -> Result<HttpResponseOk<VpcFirewallRules>, HttpError>;
 
+    /// List firewall rules
+    #[endpoint {
+        operation_id = "vpc_firewall_rules_view",
+        method = GET,
+        path = "/v1/vpc-firewall-rules",
+        tags = ["vpcs"],
+        versions = VERSION_FIREWALL_TAGS..VERSION_FIREWALL_RULE_GENERATION,
+    }]
+    async fn vpc_firewall_rules_view_v2026_10_19_06(
+        rqctx: RequestContext<Self::Context>,
+        query_params: Query<latest::vpc::VpcSelector>,
+    ) -> Result<HttpResponseOk<v2026_10_19_06_local::VpcFirewallRules>, HttpError>
+    {
+        Self::vpc_firewall_rules_view(rqctx, query_params)
+            .await
+            .map(|resp| resp.map(Into::into))
+    }
+
+    /// List firewall rules
+    #[endpoint {
+        operation_id = "vpc_firewall_rules_view",
+        method = GET,
+        path = "/v1/vpc-firewall-rules",
+        tags = ["vpcs"],
+        versions = VERSION_ADD_ICMPV6_FIREWALL_SUPPORT..VERSION_FIREWALL_TAGS,
+    }]
+    async fn vpc_firewall_rules_view_v2026_10_19_05(
+        rqctx: RequestContext<Self::Context>,
+        query_params: Query<latest::vpc::VpcSelector>,
+    ) -> Result<HttpResponseOk<v2026_10_19_05_local::VpcFirewallRules>, HttpError>
+    {
+        Self::vpc_firewall_rules_view(rqctx, query_params).await.and_then(
+            |resp| resp.try_map(TryInto::try_into).map_err(HttpError::from),
+        )
+    }
+
     /// List firewall rules
     #[endpoint {
         operation_id = "vpc_firewall_rules_view",
@@ -6948,9 +7348,10 @@ pub trait NexusExternalApi {
     ///
     /// Targets are used to specify the set of instances to which a firewall rule
     /// applies. You can target instances directly by name, or specify a VPC, VPC
-    /// subnet, IP, or IP subnet, which will apply the rule to traffic going to
-    /// all matching instances. Targets are additive: the rule applies to instances
-    /// matching ANY target. The maximum number of targets is 256.
+    /// subnet, IP, IP subnet, or network tag, which will apply the rule to
+    /// traffic going to all matching instances. Targets are additive: the rule
+    /// applies to instances matching ANY target. The maximum number of targets
+    /// is 256.
     ///
     /// Filters reduce the scope of a firewall rule. Without filters, the rule
     /// applies to all packets to the targets (or from the targets, if it's an
@@ -6960,7 +7361,7 @@ pub trait NexusExternalApi {
         method = PUT,
         path = "/v1/vpc-firewall-rules",
         tags = ["vpcs"],
-        versions = VERSION_ADD_ICMPV6_FIREWALL_SUPPORT..,
+        versions = VERSION_FIREWALL_RULE_GENERATION..,
     }]
     async fn vpc_firewall_rules_update(
         rqctx: RequestContext<Self::Context>,
@@ -6969,37 +7370,191 @@ pub trait NexusExternalApi {
     ) -> Result<HttpResponseOk<VpcFirewallRules>, HttpError>;
 
     /// Replace firewall rules
+    ///
+    /// The maximum number of rules per VPC is 1024.
+    ///
+    /// Targets are used to specify the set of instances to which a firewall rule
+    /// applies. You can target instances directly by name, or specify a VPC, VPC
+    /// subnet, IP, IP subnet, or network tag, which will apply the rule to
+    /// traffic going to all matching instances. Targets are additive: the rule
+    /// applies to instances matching ANY target. The maximum number of targets
+    /// is 256.
+    ///
+    /// Filters reduce the scope of a firewall rule. Without filters, the rule
+    /// applies to all packets to the targets (or from the targets, if it's an
+    /// outbound rule). With multiple filters, the rule applies only to packets
+    /// matching ALL filters. The maximum number of each type of filter is 256.
     #[endpoint {
         operation_id = "vpc_firewall_rules_update",
         method = PUT,
         path = "/v1/vpc-firewall-rules",
         tags = ["vpcs"],
-        versions = ..VERSION_ADD_ICMPV6_FIREWALL_SUPPORT,
+        versions = VERSION_FIREWALL_TAGS..VERSION_FIREWALL_RULE_GENERATION,
     }]
-    async fn vpc_firewall_rules_update_v2026_03_24_00(
+    async fn vpc_firewall_rules_update_v2026_10_19_06(
         rqctx: RequestContext<Self::Context>,
         query_params: Query<latest::vpc::VpcSelector>,
-        update: TypedBody<v2026_03_24_00_local::VpcFirewallRuleUpdateParams>,
-    ) -> Result<HttpResponseOk<v2026_03_24_00_local::VpcFirewallRules>, HttpError>
+        update: TypedBody<VpcFirewallRuleUpdateParams>,
+    ) -> Result<HttpResponseOk<v2026_10_19_06_local::VpcFirewallRules>, HttpError>
     {
-        let body = update.map(Into::into);
-        Self::vpc_firewall_rules_update(rqctx, query_params, body)
+        Self::vpc_firewall_rules_update(rqctx, query_params, update)
             .await
-            .and_then(|resp| {
-                resp.try_map(TryInto::try_into).map_err(HttpError::from)
-            })
+            .map(|resp| resp.map(Into::into))
     }
 
-    // VPC Routers
-
-    /// List routers
-    #[endpoint {
-        method = GET,
-        path = "/v1/vpc-routers",
-        tags = ["vpcs"],
-    }]
-    async fn vpc_router_list(
-        rqctx: RequestContext<Self::Context>,
+    /// Replace firewall rules
+    ///
+    /// The maximum number of rules per VPC is 1024.
+    ///
+    /// Targets are used to specify the set of instances to which a firewall rule
+    /// applies. You can target instances directly by name, or specify a VPC, VPC
+    /// subnet, IP, or IP subnet, which will apply the rule to traffic going to
+    /// all matching instances. Targets are additive: the rule applies to instances
+    /// matching ANY target. The maximum number of targets is 256.
+    ///
+    /// Filters reduce the scope of a firewall rule. Without filters, the rule
+    /// applies to all packets to the targets (or from the targets, if it's an
+    /// outbound rule). With multiple filters, the rule applies only to packets
+    /// matching ALL filters. The maximum number of each type of filter is 256.
+    #[endpoint {
+        operation_id = "vpc_firewall_rules_update",
+        method = PUT,
+        path = "/v1/vpc-firewall-rules",
+        tags = ["vpcs"],
+        versions = VERSION_ADD_ICMPV6_FIREWALL_SUPPORT..VERSION_FIREWALL_TAGS,
+    }]
+    async fn vpc_firewall_rules_update_v2026_10_19_05(
+        rqctx: RequestContext<Self::Context>,
+        query_params: Query<latest::vpc::VpcSelector>,
+        update: TypedBody<v2026_10_19_05_local::VpcFirewallRuleUpdateParams>,
+    ) -> Result<HttpResponseOk<v2026_10_19_05_local::VpcFirewallRules>, HttpError>
+    {
+        let body = update.map(Into::into);
+        Self::vpc_firewall_rules_update(rqctx, query_params, body)
+            .await
+            .and_then(|resp| {
+                resp.try_map(TryInto::try_into).map_err(HttpError::from)
+            })
+    }
+
+    /// Replace firewall rules
+    #[endpoint {
+        operation_id = "vpc_firewall_rules_update",
+        method = PUT,
+        path = "/v1/vpc-firewall-rules",
+        tags = ["vpcs"],
+        versions = ..VERSION_ADD_ICMPV6_FIREWALL_SUPPORT,
+    }]
+    async fn vpc_firewall_rules_update_v2026_03_24_00(
+        rqctx: RequestContext<Self::Context>,
+        query_params: Query<latest::vpc::VpcSelector>,
+        update: TypedBody<v2026_03_24_00_local::VpcFirewallRuleUpdateParams>,
+    ) -> Result<HttpResponseOk<v2026_03_24_00_local::VpcFirewallRules>, HttpError>
+    {
+        let body = update.map(Into::into);
+        Self::vpc_firewall_rules_update(rqctx, query_params, body)
+            .await
+            .and_then(|resp| {
+                resp.try_map(TryInto::try_into).map_err(HttpError::from)
+            })
+    }
+
+    /// Create firewall rule
+    ///
+    /// Adds a single rule to the VPC's firewall rules. The request must supply
+    /// the current generation of the VPC's rule set, as returned when listing
+    /// the rules, and fails with a conflict if the rule set has changed since.
+    #[endpoint {
+        method = POST,
+        path = "/v1/vpc-firewall-rules",
+        tags = ["vpcs"],
+        versions = VERSION_FIREWALL_RULE_GENERATION..,
+    }]
+    async fn vpc_firewall_rule_create(
+        rqctx: RequestContext<Self::Context>,
+        query_params: Query<latest::vpc::VpcSelector>,
+        create: TypedBody<latest::vpc::VpcFirewallRuleCreate>,
+    ) -> Result<HttpResponseCreated<VpcFirewallRules>, HttpError>;
+
+    /// Replace firewall rule
+    ///
+    /// Replaces a single rule of the VPC's firewall rules, leaving the other
+    /// rules as they are. The request must supply the current generation of
+    /// the VPC's rule set, and fails with a conflict if the rule set has
+    /// changed since.
+    #[endpoint {
+        method = PUT,
+        path = "/v1/vpc-firewall-rules/{rule}",
+        tags = ["vpcs"],
+        versions = VERSION_FIREWALL_RULE_GENERATION..,
+    }]
+    async fn vpc_firewall_rule_update(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<latest::vpc::VpcFirewallRulePath>,
+        query_params: Query<latest::vpc::VpcSelector>,
+        update: TypedBody<latest::vpc::VpcFirewallRuleReplace>,
+    ) -> Result<HttpResponseOk<VpcFirewallRules>, HttpError>;
+
+    /// Delete firewall rule
+    ///
+    /// Removes a single rule from the VPC's firewall rules. The request must
+    /// supply the current generation of the VPC's rule set, and fails with a
+    /// conflict if the rule set has changed since.
+    #[endpoint {
+        method = DELETE,
+        path = "/v1/vpc-firewall-rules/{rule}",
+        tags = ["vpcs"],
+        versions = VERSION_FIREWALL_RULE_GENERATION..,
+    }]
+    async fn vpc_firewall_rule_delete(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<latest::vpc::VpcFirewallRulePath>,
+        query_params: Query<latest::vpc::VpcFirewallRuleDeleteSelector>,
+    ) -> Result<HttpResponseDeleted, HttpError>;
+
+    // VPC DNS
+
+    /// List VPC DNS records
+    ///
+    /// Lists the user-defined records in a VPC's private DNS zone. Instances in
+    /// the VPC can also resolve each other by name in the zone.
+    #[endpoint {
+        method = GET,
+        path = "/v1/vpc-dns-records",
+        tags = ["vpcs"],
+        versions = VERSION_VPC_DNS..,
+    }]
+    async fn vpc_dns_records_view(
+        rqctx: RequestContext<Self::Context>,
+        query_params: Query<latest::vpc::VpcSelector>,
+    ) -> Result<HttpResponseOk<latest::vpc::VpcDnsRecords>, HttpError>;
+
+    /// Replace VPC DNS records
+    ///
+    /// Replaces the user-defined records in a VPC's private DNS zone. The
+    /// maximum number of records per VPC is 1024.
+    #[endpoint {
+        method = PUT,
+        path = "/v1/vpc-dns-records",
+        tags = ["vpcs"],
+        versions = VERSION_VPC_DNS..,
+    }]
+    async fn vpc_dns_records_update(
+        rqctx: RequestContext<Self::Context>,
+        query_params: Query<latest::vpc::VpcSelector>,
+        update: TypedBody<latest::vpc::VpcDnsRecordsUpdate>,
+    ) -> Result<HttpResponseOk<latest::vpc::VpcDnsRecords>, HttpError>;
+
+    // VPC Routers
+
+    /// List routers
+    #[endpoint {
+        method = GET,
+        path = "/v1/vpc-routers",
+        tags = ["vpcs"],
+    }]
+    async fn vpc_router_list(
+        rqctx: RequestContext<Self::Context>,
         query_params: Query<PaginatedByNameOrId<latest::vpc::VpcSelector>>,
     ) -> Result<HttpResponseOk<ResultsPage<latest::vpc::VpcRouter>>, HttpError>;
 
@@ -7433,6 +7988,52 @@ pub trait NexusExternalApi {
         HttpError,
     >;
 
+    /// Start sled evacuation
+    ///
+    /// Marks the sled as non-provisionable and begins live-migrating every
+    /// running instance off of it. Only one evacuation may be in progress for
+    /// a sled at a time.
+    #[endpoint {
+        method = POST,
+        path = "/v1/system/hardware/sleds/{sled_id}/evacuation",
+        tags = ["system/hardware"],
+        versions = VERSION_SLED_EVACUATION..,
+    }]
+    async fn sled_evacuation_start(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<latest::path_params::SledPath>,
+    ) -> Result<HttpResponseCreated<latest::sled::SledEvacuation>, HttpError>;
+
+    /// Fetch sled evacuation
+    ///
+    /// Returns the sled's most recent evacuation, including its progress.
+    #[endpoint {
+        method = GET,
+        path = "/v1/system/hardware/sleds/{sled_id}/evacuation",
+        tags = ["system/hardware"],
+        versions = VERSION_SLED_EVACUATION..,
+    }]
+    async fn sled_evacuation_view(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<latest::path_params::SledPath>,
+    ) -> Result<HttpResponseOk<latest::sled::SledEvacuation>, HttpError>;
+
+    /// Cancel sled evacuation
+    ///
+    /// Stops starting new migrations off of the sled. Migrations that are
+    /// already underway run to completion. The sled remains
+    /// non-provisionable.
+    #[endpoint {
+        method = POST,
+        path = "/v1/system/hardware/sleds/{sled_id}/evacuation/cancel",
+        tags = ["system/hardware"],
+        versions = VERSION_SLED_EVACUATION..,
+    }]
+    async fn sled_evacuation_cancel(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<latest::path_params::SledPath>,
+    ) -> Result<HttpResponseOk<latest::sled::SledEvacuation>, HttpError>;
+
     // Physical disks
 
     /// List physical disks
@@ -8248,6 +8849,191 @@ pub trait NexusExternalApi {
         path_params: Path<latest::path_params::TokenPath>,
     ) -> Result<HttpResponseDeleted, HttpError>;
 
+    // Service accounts
+
+    /// List service accounts
+    ///
+    /// List service accounts in the current Silo.
+    #[endpoint {
+        method = GET,
+        path = "/v1/service-accounts",
+        tags = ["silos"],
+        versions = VERSION_SERVICE_ACCOUNTS..,
+    }]
+    async fn service_account_list(
+        rqctx: RequestContext<Self::Context>,
+        query_params: Query<PaginatedByNameOrId>,
+    ) -> Result<
+        HttpResponseOk<ResultsPage<latest::service_account::ServiceAccount>>,
+        HttpError,
+    >;
+
+    /// Create service account
+    #[endpoint {
+        method = POST,
+        path = "/v1/service-accounts",
+        tags = ["silos"],
+        versions = VERSION_SERVICE_ACCOUNTS..,
+    }]
+    async fn service_account_create(
+        rqctx: RequestContext<Self::Context>,
+        new_service_account: TypedBody<
+            latest::service_account::ServiceAccountCreate,
+        >,
+    ) -> Result<
+        HttpResponseCreated<latest::service_account::ServiceAccount>,
+        HttpError,
+    >;
+
+    /// Fetch service account
+    #[endpoint {
+        method = GET,
+        path = "/v1/service-accounts/{service_account}",
+        tags = ["silos"],
+        versions = VERSION_SERVICE_ACCOUNTS..,
+    }]
+    async fn service_account_view(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<latest::service_account::ServiceAccountPath>,
+    ) -> Result<
+        HttpResponseOk<latest::service_account::ServiceAccount>,
+        HttpError,
+    >;
+
+    /// Delete service account
+    ///
+    /// Deletes the service account, all of its tokens, and all of its role
+    /// assignments.
+    #[endpoint {
+        method = DELETE,
+        path = "/v1/service-accounts/{service_account}",
+        tags = ["silos"],
+        versions = VERSION_SERVICE_ACCOUNTS..,
+    }]
+    async fn service_account_delete(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<latest::service_account::ServiceAccountPath>,
+    ) -> Result<HttpResponseDeleted, HttpError>;
+
+    /// Fetch service account roles
+    #[endpoint {
+        method = GET,
+        path = "/v1/service-accounts/{service_account}/policy",
+        tags = ["silos"],
+        versions = VERSION_SERVICE_ACCOUNTS..,
+    }]
+    async fn service_account_policy_view(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<latest::service_account::ServiceAccountPath>,
+    ) -> Result<
+        HttpResponseOk<latest::service_account::ServiceAccountPolicy>,
+        HttpError,
+    >;
+
+    /// Update service account roles
+    ///
+    /// Replaces the complete set of roles granted to the service account. The
+    /// caller must be allowed to change the policy of every Silo or project
+    /// whose roles are added or removed.
+    #[endpoint {
+        method = PUT,
+        path = "/v1/service-accounts/{service_account}/policy",
+        tags = ["silos"],
+        versions = VERSION_SERVICE_ACCOUNTS..,
+    }]
+    async fn service_account_policy_update(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<latest::service_account::ServiceAccountPath>,
+        new_policy: TypedBody<latest::service_account::ServiceAccountPolicy>,
+    ) -> Result<
+        HttpResponseOk<latest::service_account::ServiceAccountPolicy>,
+        HttpError,
+    >;
+
+    /// List service account tokens
+    #[endpoint {
+        method = GET,
+        path = "/v1/service-accounts/{service_account}/tokens",
+        tags = ["silos"],
+        versions = VERSION_SERVICE_ACCOUNTS..,
+    }]
+    async fn service_account_token_list(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<latest::service_account::ServiceAccountPath>,
+        query_params: Query<PaginatedByNameOrId>,
+    ) -> Result<
+        HttpResponseOk<
+            ResultsPage<latest::service_account::ServiceAccountToken>,
+        >,
+        HttpError,
+    >;
+
+    /// Create service account token
+    ///
+    /// The token value is included in the response and cannot be retrieved
+    /// again.
+    #[endpoint {
+        method = POST,
+        path = "/v1/service-accounts/{service_account}/tokens",
+        tags = ["silos"],
+        versions = VERSION_SERVICE_ACCOUNTS..,
+    }]
+    async fn service_account_token_create(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<latest::service_account::ServiceAccountPath>,
+        new_token: TypedBody<
+            latest::service_account::ServiceAccountTokenCreate,
+        >,
+    ) -> Result<
+        HttpResponseCreated<latest::service_account::ServiceAccountTokenValue>,
+        HttpError,
+    >;
+
+    /// Fetch service account token
+    #[endpoint {
+        method = GET,
+        path = "/v1/service-accounts/{service_account}/tokens/{token}",
+        tags = ["silos"],
+        versions = VERSION_SERVICE_ACCOUNTS..,
+    }]
+    async fn service_account_token_view(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<latest::service_account::ServiceAccountTokenPath>,
+    ) -> Result<
+        HttpResponseOk<latest::service_account::ServiceAccountToken>,
+        HttpError,
+    >;
+
+    /// Delete service account token
+    #[endpoint {
+        method = DELETE,
+        path = "/v1/service-accounts/{service_account}/tokens/{token}",
+        tags = ["silos"],
+        versions = VERSION_SERVICE_ACCOUNTS..,
+    }]
+    async fn service_account_token_delete(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<latest::service_account::ServiceAccountTokenPath>,
+    ) -> Result<HttpResponseDeleted, HttpError>;
+
+    /// Rotate service account token
+    ///
+    /// Replaces the token with a new one that has the same name, expiration,
+    /// and restrictions. The old token stops working immediately.
+    #[endpoint {
+        method = POST,
+        path = "/v1/service-accounts/{service_account}/tokens/{token}/rotate",
+        tags = ["silos"],
+        versions = VERSION_SERVICE_ACCOUNTS..,
+    }]
+    async fn service_account_token_rotate(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<latest::service_account::ServiceAccountTokenPath>,
+    ) -> Result<
+        HttpResponseOk<latest::service_account::ServiceAccountTokenValue>,
+        HttpError,
+    >;
+
     // Support bundles (experimental)
 
     /// List all support bundles
@@ -8518,11 +9304,14 @@ pub trait NexusExternalApi {
     /// log for a time range that is fully in the past, the resulting list is
     /// guaranteed to be complete, i.e., fetching the same timespan again later
     /// will always produce the same set of entries.
+    ///
+    /// Use `resource_id` to list only the operations that acted on a
+    /// particular resource, e.g., to find out who changed it.
     #[endpoint {
         method = GET,
         path = "/v1/system/audit-log",
         tags = ["system/audit-log"],
-        versions = VERSION_AUDIT_LOG_CREDENTIAL_ID..,
+        versions = VERSION_AUDIT_LOG_RESOURCE..,
     }]
     async fn audit_log_list(
         rqctx: RequestContext<Self::Context>,
@@ -8534,6 +9323,74 @@ pub trait NexusExternalApi {
         HttpError,
     >;
 
+    /// View audit log
+    ///
+    /// A single item in the audit log represents both the beginning and
+    /// end of the logged operation (represented by `time_started` and
+    /// `time_completed`) so that clients do not have to find multiple entries
+    /// and match them up by request ID to get the full picture of an operation.
+    /// Because timestamps may not be unique, entries have also have a unique
+    /// `id` that can be used to deduplicate items fetched from overlapping
+    /// time intervals.
+    ///
+    /// Audit log entries are designed to be immutable: once you see an entry,
+    /// fetching it again will never get you a different result. The list is
+    /// ordered by `time_completed`, not `time_started`. If you fetch the audit
+    /// log for a time range that is fully in the past, the resulting list is
+    /// guaranteed to be complete, i.e., fetching the same timespan again later
+    /// will always produce the same set of entries.
+    // This is implemented in the server rather than by delegating to
+    // `audit_log_list`, because there's no way to convert the pagination query
+    // parameters into those of the newer version.
+    #[endpoint {
+        operation_id = "audit_log_list",
+        method = GET,
+        path = "/v1/system/audit-log",
+        tags = ["system/audit-log"],
+        versions = VERSION_SERVICE_ACCOUNTS..VERSION_AUDIT_LOG_RESOURCE,
+    }]
+    async fn audit_log_list_v2026_10_19_00(
+        rqctx: RequestContext<Self::Context>,
+        query_params: Query<
+            PaginatedByTimeAndId<v2025_11_20_00::audit::AuditLogParams>,
+        >,
+    ) -> Result<
+        HttpResponseOk<ResultsPage<v2026_10_19_00::audit::AuditLogEntry>>,
+        HttpError,
+    >;
+
+    /// View audit log
+    ///
+    /// Entries for actions taken by service accounts are omitted, since this
+    /// version of the API cannot represent them.
+    #[endpoint {
+        operation_id = "audit_log_list",
+        method = GET,
+        path = "/v1/system/audit-log",
+        tags = ["system/audit-log"],
+        versions = VERSION_AUDIT_LOG_CREDENTIAL_ID..VERSION_SERVICE_ACCOUNTS,
+    }]
+    async fn audit_log_list_v2026_01_15_01(
+        rqctx: RequestContext<Self::Context>,
+        query_params: Query<
+            PaginatedByTimeAndId<v2025_11_20_00::audit::AuditLogParams>,
+        >,
+    ) -> Result<
+        HttpResponseOk<ResultsPage<v2026_01_15_01::audit::AuditLogEntry>>,
+        HttpError,
+    > {
+        let page =
+            Self::audit_log_list_v2026_10_19_00(rqctx, query_params).await?.0;
+        Ok(HttpResponseOk(ResultsPage {
+            items: page
+                .items
+                .into_iter()
+                .filter_map(|e| e.try_into().ok())
+                .collect(),
+            next_page: page.next_page,
+        }))
+    }
+
     /// View audit log
     #[endpoint {
         operation_id = "audit_log_list",
@@ -8545,13 +9402,14 @@ pub trait NexusExternalApi {
     async fn audit_log_list_v2026_01_15_00(
         rqctx: RequestContext<Self::Context>,
         query_params: Query<
-            PaginatedByTimeAndId<latest::audit::AuditLogParams>,
+            PaginatedByTimeAndId<v2025_11_20_00::audit::AuditLogParams>,
         >,
     ) -> Result<
         HttpResponseOk<ResultsPage<v2026_01_15_00::audit::AuditLogEntry>>,
         HttpError,
     > {
-        let page = Self::audit_log_list(rqctx, query_params).await?.0;
+        let page =
+            Self::audit_log_list_v2026_01_15_01(rqctx, query_params).await?.0;
         Ok(HttpResponseOk(ResultsPage {
             items: page.items.into_iter().map(Into::into).collect(),
             next_page: page.next_page,
@@ -8569,13 +9427,14 @@ pub trait NexusExternalApi {
     async fn audit_log_list_v2025_11_20_00(
         rqctx: RequestContext<Self::Context>,
         query_params: Query<
-            PaginatedByTimeAndId<latest::audit::AuditLogParams>,
+            PaginatedByTimeAndId<v2025_11_20_00::audit::AuditLogParams>,
         >,
     ) -> Result<
         HttpResponseOk<ResultsPage<v2025_11_20_00::audit::AuditLogEntry>>,
         HttpError,
     > {
-        let page = Self::audit_log_list(rqctx, query_params).await?.0;
+        let page =
+            Self::audit_log_list_v2026_01_15_01(rqctx, query_params).await?.0;
         Ok(HttpResponseOk(ResultsPage {
             items: page
                 .items
@@ -8586,6 +9445,63 @@ pub trait NexusExternalApi {
         }))
     }
 
+    /// List audit log sinks
+    #[endpoint {
+        method = GET,
+        path = "/v1/system/audit-log/sinks",
+        tags = ["system/audit-log"],
+        versions = VERSION_AUDIT_LOG_SINKS..,
+    }]
+    async fn audit_log_sink_list(
+        rqctx: RequestContext<Self::Context>,
+        query_params: Query<PaginatedByNameOrId>,
+    ) -> Result<
+        HttpResponseOk<ResultsPage<latest::audit::AuditLogSink>>,
+        HttpError,
+    >;
+
+    /// Create audit log sink
+    ///
+    /// Nexus delivers each completed audit log entry to every sink, in the
+    /// order that the audit log is listed, starting from the sink's
+    /// `start_time`. Syslog sinks receive one RFC 5424 message per entry.
+    /// HTTP sinks receive batches of entries as JSON, signed with the sink's
+    /// secret in the same way as webhook payloads.
+    #[endpoint {
+        method = POST,
+        path = "/v1/system/audit-log/sinks",
+        tags = ["system/audit-log"],
+        versions = VERSION_AUDIT_LOG_SINKS..,
+    }]
+    async fn audit_log_sink_create(
+        rqctx: RequestContext<Self::Context>,
+        new_sink: TypedBody<latest::audit::AuditLogSinkCreate>,
+    ) -> Result<HttpResponseCreated<latest::audit::AuditLogSink>, HttpError>;
+
+    /// Fetch audit log sink
+    #[endpoint {
+        method = GET,
+        path = "/v1/system/audit-log/sinks/{sink}",
+        tags = ["system/audit-log"],
+        versions = VERSION_AUDIT_LOG_SINKS..,
+    }]
+    async fn audit_log_sink_view(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<latest::audit::AuditLogSinkPath>,
+    ) -> Result<HttpResponseOk<latest::audit::AuditLogSink>, HttpError>;
+
+    /// Delete audit log sink
+    #[endpoint {
+        method = DELETE,
+        path = "/v1/system/audit-log/sinks/{sink}",
+        tags = ["system/audit-log"],
+        versions = VERSION_AUDIT_LOG_SINKS..,
+    }]
+    async fn audit_log_sink_delete(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<latest::audit::AuditLogSinkPath>,
+    ) -> Result<HttpResponseDeleted, HttpError>;
+
     // Console API: logins
 
     /// SAML login console page (just a link to the IdP)
@@ -8779,6 +9695,20 @@ pub trait NexusExternalApi {
         path_params: Path<latest::console::RestPathParam>,
     ) -> Result<Response<Body>, HttpError>;
 
+    /// Answer an ACME HTTP-01 challenge
+    ///
+    /// This is fetched by the ACME server from which Nexus obtains Silos' TLS
+    /// certificates, to validate that Nexus controls the Silos' DNS names.
+    #[endpoint {
+        method = GET,
+        path = "/.well-known/acme-challenge/{path:.*}",
+        unpublished = true,
+    }]
+    async fn acme_http01_challenge(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<latest::console::RestPathParam>,
+    ) -> Result<Response<Body>, HttpError>;
+
     /// Start an OAuth 2.0 Device Authorization Grant
     ///
     /// This endpoint is designed to be accessed from an *unauthenticated*
@@ -8879,6 +9809,7 @@ pub trait NexusExternalApi {
         method = GET,
         path = "/v1/alert-receivers",
         tags = ["system/alerts"],
+        versions = VERSION_ALERT_RECEIVER_KINDS..,
     }]
     async fn alert_receiver_list(
         rqctx: RequestContext<Self::Context>,
@@ -8888,17 +9819,65 @@ pub trait NexusExternalApi {
         HttpError,
     >;
 
+    /// List alert receivers
+    ///
+    /// Only webhook receivers are included, as other kinds of receiver cannot
+    /// be represented in this version of the API.
+    #[endpoint {
+        operation_id = "alert_receiver_list",
+        method = GET,
+        path = "/v1/alert-receivers",
+        tags = ["system/alerts"],
+        versions = ..VERSION_ALERT_RECEIVER_KINDS,
+    }]
+    async fn alert_receiver_list_v2025_11_20_00(
+        rqctx: RequestContext<Self::Context>,
+        query_params: Query<PaginatedByNameOrId>,
+    ) -> Result<
+        HttpResponseOk<ResultsPage<v2025_11_20_00::alert::AlertReceiver>>,
+        HttpError,
+    > {
+        let page = Self::alert_receiver_list(rqctx, query_params).await?.0;
+        Ok(HttpResponseOk(ResultsPage {
+            items: page
+                .items
+                .into_iter()
+                .filter_map(|rx| rx.try_into().ok())
+                .collect(),
+            next_page: page.next_page,
+        }))
+    }
+
     /// Fetch alert receiver
     #[endpoint {
         method = GET,
         path = "/v1/alert-receivers/{receiver}",
         tags = ["system/alerts"],
+        versions = VERSION_ALERT_RECEIVER_KINDS..,
     }]
     async fn alert_receiver_view(
         rqctx: RequestContext<Self::Context>,
         path_params: Path<latest::alert::AlertReceiverSelector>,
     ) -> Result<HttpResponseOk<latest::alert::AlertReceiver>, HttpError>;
 
+    /// Fetch alert receiver
+    #[endpoint {
+        operation_id = "alert_receiver_view",
+        method = GET,
+        path = "/v1/alert-receivers/{receiver}",
+        tags = ["system/alerts"],
+        versions = ..VERSION_ALERT_RECEIVER_KINDS,
+    }]
+    async fn alert_receiver_view_v2025_11_20_00(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<latest::alert::AlertReceiverSelector>,
+    ) -> Result<HttpResponseOk<v2025_11_20_00::alert::AlertReceiver>, HttpError>
+    {
+        let HttpResponseOk(rx) =
+            Self::alert_receiver_view(rqctx, path_params).await?;
+        Ok(HttpResponseOk(rx.try_into()?))
+    }
+
     /// Delete alert receiver
     #[endpoint {
         method = DELETE,
@@ -8947,6 +9926,7 @@ pub trait NexusExternalApi {
         method = GET,
         path = "/v1/alert-receivers/{receiver}/deliveries",
         tags = ["system/alerts"],
+        versions = VERSION_ALERT_RECEIVER_KINDS..,
     }]
     async fn alert_delivery_list(
         rqctx: RequestContext<Self::Context>,
@@ -8958,6 +9938,47 @@ pub trait NexusExternalApi {
         HttpError,
     >;
 
+    /// List delivery attempts to alert receiver
+    ///
+    /// Optional query parameters to this endpoint may be used to filter
+    /// deliveries by state. If none of the `failed`, `pending` or `delivered`
+    /// query parameters are present, all deliveries are returned. If one or
+    /// more of these parameters are provided, only those which are set to
+    /// "true" are included in the response.
+    #[endpoint {
+        operation_id = "alert_delivery_list",
+        method = GET,
+        path = "/v1/alert-receivers/{receiver}/deliveries",
+        tags = ["system/alerts"],
+        versions = ..VERSION_ALERT_RECEIVER_KINDS,
+    }]
+    async fn alert_delivery_list_v2025_11_20_00(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<latest::alert::AlertReceiverSelector>,
+        state_filter: Query<latest::alert::AlertDeliveryStateFilter>,
+        pagination: Query<PaginatedByTimeAndId>,
+    ) -> Result<
+        HttpResponseOk<ResultsPage<v2025_11_20_00::alert::AlertDelivery>>,
+        HttpError,
+    > {
+        let page = Self::alert_delivery_list(
+            rqctx,
+            path_params,
+            state_filter,
+            pagination,
+        )
+        .await?
+        .0;
+        Ok(HttpResponseOk(ResultsPage {
+            items: page
+                .items
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<Vec<_>, _>>()?,
+            next_page: page.next_page,
+        }))
+    }
+
     /// Send liveness probe to alert receiver
     ///
     /// This endpoint synchronously sends a liveness probe to the selected alert
@@ -8985,6 +10006,7 @@ pub trait NexusExternalApi {
         method = POST,
         path = "/v1/alert-receivers/{receiver}/probe",
         tags = ["system/alerts"],
+        versions = VERSION_ALERT_RECEIVER_KINDS..,
     }]
     async fn alert_receiver_probe(
         rqctx: RequestContext<Self::Context>,
@@ -8992,6 +10014,50 @@ pub trait NexusExternalApi {
         query_params: Query<latest::alert::AlertReceiverProbe>,
     ) -> Result<HttpResponseOk<latest::alert::AlertProbeResult>, HttpError>;
 
+    /// Send liveness probe to alert receiver
+    ///
+    /// This endpoint synchronously sends a liveness probe to the selected alert
+    /// receiver. The response message describes the outcome of the probe:
+    /// either the successful response (as appropriate), or indication of why
+    /// the probe failed.
+    ///
+    /// The result of the probe is represented as an `AlertDelivery` model.
+    /// Details relating to the status of the probe depend on the alert delivery
+    /// mechanism, and are included in the `AlertDeliveryAttempts` model. For
+    /// example, webhook receiver liveness probes include the HTTP status code
+    /// returned by the receiver endpoint.
+    ///
+    /// Note that the response status is `200 OK` as long as a probe request was
+    /// able to be sent to the receiver endpoint. If an HTTP-based receiver,
+    /// such as a webhook, responds to the another status code, including an
+    /// error, this will be indicated by the response body, *not* the status of
+    /// the response.
+    ///
+    /// The `resend` query parameter can be used to request re-delivery of
+    /// failed events if the liveness probe succeeds. If it is set to true and
+    /// the liveness probe succeeds, any alerts for which delivery to this
+    /// receiver has failed will be queued for re-delivery.
+    #[endpoint {
+        operation_id = "alert_receiver_probe",
+        method = POST,
+        path = "/v1/alert-receivers/{receiver}/probe",
+        tags = ["system/alerts"],
+        versions = ..VERSION_ALERT_RECEIVER_KINDS,
+    }]
+    async fn alert_receiver_probe_v2025_11_20_00(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<latest::alert::AlertReceiverSelector>,
+        query_params: Query<latest::alert::AlertReceiverProbe>,
+    ) -> Result<
+        HttpResponseOk<v2025_11_20_00::alert::AlertProbeResult>,
+        HttpError,
+    > {
+        let HttpResponseOk(result) =
+            Self::alert_receiver_probe(rqctx, path_params, query_params)
+                .await?;
+        Ok(HttpResponseOk(result.try_into()?))
+    }
+
     /// Request re-delivery of alert
     #[endpoint {
         method = POST,
@@ -9066,6 +10132,67 @@ pub trait NexusExternalApi {
         rqctx: RequestContext<Self::Context>,
         path_params: Path<latest::alert::WebhookSecretSelector>,
     ) -> Result<HttpResponseDeleted, HttpError>;
+
+    // ALERTS: SMTP
+
+    /// Create SMTP receiver
+    ///
+    /// SMTP receivers deliver alerts as email messages, relayed through the
+    /// configured mail server. The mail server must accept unauthenticated
+    /// mail from the rack over plain SMTP.
+    #[endpoint {
+        method = POST,
+        path = "/v1/smtp-receivers",
+        tags = ["system/alerts"],
+        versions = VERSION_ALERT_RECEIVER_KINDS..,
+    }]
+    async fn smtp_receiver_create(
+        rqctx: RequestContext<Self::Context>,
+        params: TypedBody<latest::alert::SmtpCreate>,
+    ) -> Result<HttpResponseCreated<latest::alert::SmtpReceiver>, HttpError>;
+
+    /// Update SMTP receiver
+    #[endpoint {
+        method = PUT,
+        path = "/v1/smtp-receivers/{receiver}",
+        tags = ["system/alerts"],
+        versions = VERSION_ALERT_RECEIVER_KINDS..,
+    }]
+    async fn smtp_receiver_update(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<latest::alert::AlertReceiverSelector>,
+        params: TypedBody<latest::alert::SmtpReceiverUpdate>,
+    ) -> Result<HttpResponseUpdatedNoContent, HttpError>;
+
+    // ALERTS: SYSLOG
+
+    /// Create syslog receiver
+    ///
+    /// Syslog receivers deliver alerts as RFC 5424 syslog messages, sent to
+    /// the configured collector over UDP or TCP.
+    #[endpoint {
+        method = POST,
+        path = "/v1/syslog-receivers",
+        tags = ["system/alerts"],
+        versions = VERSION_ALERT_RECEIVER_KINDS..,
+    }]
+    async fn syslog_receiver_create(
+        rqctx: RequestContext<Self::Context>,
+        params: TypedBody<latest::alert::SyslogCreate>,
+    ) -> Result<HttpResponseCreated<latest::alert::SyslogReceiver>, HttpError>;
+
+    /// Update syslog receiver
+    #[endpoint {
+        method = PUT,
+        path = "/v1/syslog-receivers/{receiver}",
+        tags = ["system/alerts"],
+        versions = VERSION_ALERT_RECEIVER_KINDS..,
+    }]
+    async fn syslog_receiver_update(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<latest::alert::AlertReceiverSelector>,
+        params: TypedBody<latest::alert::SyslogReceiverUpdate>,
+    ) -> Result<HttpResponseUpdatedNoContent, HttpError>;
 }
 
 /// Perform extra validations on the OpenAPI document, and generate the
@@ -5,6 +5,10 @@
 //! Types from API version 2026_03_14_00 that cannot live in `nexus-types-versions`
 //! because they convert to/from `omicron-common` types (orphan rule).
 
+use crate::v2026_10_19_05_local::VpcFirewallRuleHostFilter;
+use crate::v2026_10_19_05_local::VpcFirewallRuleTarget;
+use crate::v2026_10_19_05_local::try_hosts_from_external;
+use crate::v2026_10_19_05_local::try_targets_from_external;
 use api_identity::ObjectIdentity;
 use omicron_common::api::external;
 use omicron_common::api::external::Error;
@@ -15,10 +19,8 @@ use omicron_common::api::external::ObjectIdentity;
 use omicron_common::api::external::VpcFirewallIcmpFilter;
 use omicron_common::api::external::VpcFirewallRuleAction;
 use omicron_common::api::external::VpcFirewallRuleDirection;
-use omicron_common::api::external::VpcFirewallRuleHostFilter;
 use omicron_common::api::external::VpcFirewallRulePriority;
 use omicron_common::api::external::VpcFirewallRuleStatus;
-use omicron_common::api::external::VpcFirewallRuleTarget;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
@@ -99,14 +101,18 @@ impl TryFrom<external::VpcFirewallRuleFilter> for VpcFirewallRuleFilter {
                     .collect::<Result<Vec<_>, _>>()
             })
             .transpose()?;
-        Ok(Self { hosts: f.hosts, protocols, ports: f.ports })
+        Ok(Self {
+            hosts: try_hosts_from_external(f.hosts)?,
+            protocols,
+            ports: f.ports,
+        })
     }
 }
 
 impl From<VpcFirewallRuleFilter> for external::VpcFirewallRuleFilter {
     fn from(f: VpcFirewallRuleFilter) -> Self {
         Self {
-            hosts: f.hosts,
+            hosts: f.hosts.map(|hs| hs.into_iter().map(Into::into).collect()),
             protocols: f
                 .protocols
                 .map(|ps| ps.into_iter().map(Into::into).collect()),
@@ -148,7 +154,7 @@ impl TryFrom<external::VpcFirewallRule> for VpcFirewallRule {
             identity: r.identity,
             status: r.status,
             direction: r.direction,
-            targets: r.targets,
+            targets: try_targets_from_external(r.targets)?,
             filters: r.filters.try_into()?,
             action: r.action,
             priority: r.priority,
@@ -211,7 +217,7 @@ impl From<VpcFirewallRuleUpdate> for external::VpcFirewallRuleUpdate {
             description: u.description,
             status: u.status,
             direction: u.direction,
-            targets: u.targets,
+            targets: u.targets.into_iter().map(Into::into).collect(),
             filters: u.filters.into(),
             action: u.action,
             priority: u.priority,
@@ -0,0 +1,319 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Types from API version 2026_10_19_05 that cannot live in `nexus-types-versions`
+//! because they convert to/from `omicron-common` types (orphan rule).
+//!
+//! This version pre-dates `FIREWALL_TAGS`, which added the `tag` variant to
+//! firewall rule targets and host filters. Rules using tags cannot be
+//! represented in these versions, so listing them fails.
+
+use api_identity::ObjectIdentity;
+use omicron_common::api::external;
+use omicron_common::api::external::Error;
+use omicron_common::api::external::IdentityMetadata;
+use omicron_common::api::external::L4PortRange;
+use omicron_common::api::external::Name;
+use omicron_common::api::external::ObjectIdentity;
+use omicron_common::api::external::VpcFirewallRuleAction;
+use omicron_common::api::external::VpcFirewallRuleDirection;
+use omicron_common::api::external::VpcFirewallRulePriority;
+use omicron_common::api::external::VpcFirewallRuleProtocol;
+use omicron_common::api::external::VpcFirewallRuleStatus;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+use std::net::IpAddr;
+use uuid::Uuid;
+
+/// A `VpcFirewallRuleTarget` is used to specify the set of instances to which
+/// a firewall rule applies. You can target instances directly by name, or
+/// specify a VPC, VPC subnet, IP, or IP subnet, which will apply the rule to
+/// traffic going to all matching instances. Targets are additive: the rule
+/// applies to instances matching ANY target.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, JsonSchema)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum VpcFirewallRuleTarget {
+    /// The rule applies to all instances in the VPC
+    Vpc(Name),
+    /// The rule applies to all instances in the VPC Subnet
+    Subnet(Name),
+    /// The rule applies to this specific instance
+    Instance(Name),
+    /// The rule applies to a specific IP address
+    Ip(IpAddr),
+    /// The rule applies to a specific IP subnet
+    IpNet(oxnet::IpNet),
+}
+
+impl From<VpcFirewallRuleTarget> for external::VpcFirewallRuleTarget {
+    fn from(t: VpcFirewallRuleTarget) -> Self {
+        match t {
+            VpcFirewallRuleTarget::Vpc(name) => Self::Vpc(name),
+            VpcFirewallRuleTarget::Subnet(name) => Self::Subnet(name),
+            VpcFirewallRuleTarget::Instance(name) => Self::Instance(name),
+            VpcFirewallRuleTarget::Ip(addr) => Self::Ip(addr),
+            VpcFirewallRuleTarget::IpNet(net) => Self::IpNet(net),
+        }
+    }
+}
+
+impl TryFrom<external::VpcFirewallRuleTarget> for VpcFirewallRuleTarget {
+    type Error = Error;
+
+    fn try_from(t: external::VpcFirewallRuleTarget) -> Result<Self, Error> {
+        match t {
+            external::VpcFirewallRuleTarget::Vpc(name) => Ok(Self::Vpc(name)),
+            external::VpcFirewallRuleTarget::Subnet(name) => {
+                Ok(Self::Subnet(name))
+            }
+            external::VpcFirewallRuleTarget::Instance(name) => {
+                Ok(Self::Instance(name))
+            }
+            external::VpcFirewallRuleTarget::Ip(addr) => Ok(Self::Ip(addr)),
+            external::VpcFirewallRuleTarget::IpNet(net) => Ok(Self::IpNet(net)),
+            external::VpcFirewallRuleTarget::Tag(_) => {
+                Err(Error::invalid_value(
+                    "vpc_firewall_rule_target",
+                    format!("unrecognized target: {t}"),
+                ))
+            }
+        }
+    }
+}
+
+/// The `VpcFirewallRuleHostFilter` is used to filter traffic on the basis of
+/// its source or destination host.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, JsonSchema)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum VpcFirewallRuleHostFilter {
+    /// The rule applies to traffic from/to all instances in the VPC
+    Vpc(Name),
+    /// The rule applies to traffic from/to all instances in the VPC Subnet
+    Subnet(Name),
+    /// The rule applies to traffic from/to this specific instance
+    Instance(Name),
+    /// The rule applies to traffic from/to a specific IP address
+    Ip(IpAddr),
+    /// The rule applies to traffic from/to a specific IP subnet
+    IpNet(oxnet::IpNet),
+}
+
+impl From<VpcFirewallRuleHostFilter> for external::VpcFirewallRuleHostFilter {
+    fn from(h: VpcFirewallRuleHostFilter) -> Self {
+        match h {
+            VpcFirewallRuleHostFilter::Vpc(name) => Self::Vpc(name),
+            VpcFirewallRuleHostFilter::Subnet(name) => Self::Subnet(name),
+            VpcFirewallRuleHostFilter::Instance(name) => Self::Instance(name),
+            VpcFirewallRuleHostFilter::Ip(addr) => Self::Ip(addr),
+            VpcFirewallRuleHostFilter::IpNet(net) => Self::IpNet(net),
+        }
+    }
+}
+
+impl TryFrom<external::VpcFirewallRuleHostFilter>
+    for VpcFirewallRuleHostFilter
+{
+    type Error = Error;
+
+    fn try_from(h: external::VpcFirewallRuleHostFilter) -> Result<Self, Error> {
+        match h {
+            external::VpcFirewallRuleHostFilter::Vpc(name) => {
+                Ok(Self::Vpc(name))
+            }
+            external::VpcFirewallRuleHostFilter::Subnet(name) => {
+                Ok(Self::Subnet(name))
+            }
+            external::VpcFirewallRuleHostFilter::Instance(name) => {
+                Ok(Self::Instance(name))
+            }
+            external::VpcFirewallRuleHostFilter::Ip(addr) => Ok(Self::Ip(addr)),
+            external::VpcFirewallRuleHostFilter::IpNet(net) => {
+                Ok(Self::IpNet(net))
+            }
+            external::VpcFirewallRuleHostFilter::Tag(_) => {
+                Err(Error::invalid_value(
+                    "vpc_firewall_rule_host_filter",
+                    format!("unrecognized host filter: {h}"),
+                ))
+            }
+        }
+    }
+}
+
+/// Convert a list of host filters from `omicron-common`, failing if any of
+/// them cannot be represented in this version.
+pub(crate) fn try_hosts_from_external(
+    hosts: Option<Vec<external::VpcFirewallRuleHostFilter>>,
+) -> Result<Option<Vec<VpcFirewallRuleHostFilter>>, Error> {
+    hosts
+        .map(|hs| {
+            hs.into_iter()
+                .map(VpcFirewallRuleHostFilter::try_from)
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .transpose()
+}
+
+/// Convert a list of targets from `omicron-common`, failing if any of them
+/// cannot be represented in this version.
+pub(crate) fn try_targets_from_external(
+    targets: Vec<external::VpcFirewallRuleTarget>,
+) -> Result<Vec<VpcFirewallRuleTarget>, Error> {
+    targets.into_iter().map(VpcFirewallRuleTarget::try_from).collect()
+}
+
+/// Filters reduce the scope of a firewall rule. Without filters, the rule
+/// applies to all packets to the targets (or from the targets, if it's an
+/// outbound rule). With multiple filters, the rule applies only to packets
+/// matching ALL filters. The maximum number of each type of filter is 256.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct VpcFirewallRuleFilter {
+    /// If present, host filters match the "other end" of traffic from the
+    /// target’s perspective: for an inbound rule, they match the source of
+    /// traffic. For an outbound rule, they match the destination.
+    #[schemars(length(max = 256))]
+    pub hosts: Option<Vec<VpcFirewallRuleHostFilter>>,
+
+    /// If present, the networking protocols this rule applies to.
+    #[schemars(length(max = 256))]
+    pub protocols: Option<Vec<VpcFirewallRuleProtocol>>,
+
+    /// If present, the destination ports or port ranges this rule applies to.
+    #[schemars(length(max = 256))]
+    pub ports: Option<Vec<L4PortRange>>,
+}
+
+impl TryFrom<external::VpcFirewallRuleFilter> for VpcFirewallRuleFilter {
+    type Error = Error;
+
+    fn try_from(f: external::VpcFirewallRuleFilter) -> Result<Self, Error> {
+        Ok(Self {
+            hosts: try_hosts_from_external(f.hosts)?,
+            protocols: f.protocols,
+            ports: f.ports,
+        })
+    }
+}
+
+impl From<VpcFirewallRuleFilter> for external::VpcFirewallRuleFilter {
+    fn from(f: VpcFirewallRuleFilter) -> Self {
+        Self {
+            hosts: f.hosts.map(|hs| hs.into_iter().map(Into::into).collect()),
+            protocols: f.protocols,
+            ports: f.ports,
+        }
+    }
+}
+
+/// A single rule in a VPC firewall
+#[derive(ObjectIdentity, Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct VpcFirewallRule {
+    /// Common identifying metadata
+    #[serde(flatten)]
+    pub identity: IdentityMetadata,
+    /// Whether this rule is in effect
+    pub status: VpcFirewallRuleStatus,
+    /// Whether this rule is for incoming or outgoing traffic
+    pub direction: VpcFirewallRuleDirection,
+    /// Determine the set of instances that the rule applies to
+    pub targets: Vec<VpcFirewallRuleTarget>,
+    /// Reductions on the scope of the rule
+    pub filters: VpcFirewallRuleFilter,
+    /// Whether traffic matching the rule should be allowed or dropped
+    pub action: VpcFirewallRuleAction,
+    /// The relative priority of this rule
+    pub priority: VpcFirewallRulePriority,
+    /// The VPC to which this rule belongs
+    pub vpc_id: Uuid,
+}
+
+impl TryFrom<external::VpcFirewallRule> for VpcFirewallRule {
+    type Error = Error;
+
+    fn try_from(r: external::VpcFirewallRule) -> Result<Self, Error> {
+        Ok(Self {
+            identity: r.identity,
+            status: r.status,
+            direction: r.direction,
+            targets: try_targets_from_external(r.targets)?,
+            filters: r.filters.try_into()?,
+            action: r.action,
+            priority: r.priority,
+            vpc_id: r.vpc_id,
+        })
+    }
+}
+
+/// Collection of a Vpc's firewall rules
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct VpcFirewallRules {
+    pub rules: Vec<VpcFirewallRule>,
+}
+
+impl TryFrom<external::VpcFirewallRules> for VpcFirewallRules {
+    type Error = Error;
+
+    fn try_from(r: external::VpcFirewallRules) -> Result<Self, Error> {
+        let rules = r
+            .rules
+            .into_iter()
+            .map(VpcFirewallRule::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { rules })
+    }
+}
+
+/// A single rule in a VPC firewall
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize, JsonSchema)]
+pub struct VpcFirewallRuleUpdate {
+    /// Name of the rule, unique to this VPC
+    pub name: Name,
+    /// Human-readable free-form text about a resource
+    pub description: String,
+    /// Whether this rule is in effect
+    pub status: VpcFirewallRuleStatus,
+    /// Whether this rule is for incoming or outgoing traffic
+    pub direction: VpcFirewallRuleDirection,
+    /// Determine the set of instances that the rule applies to
+    #[schemars(length(max = 256))]
+    pub targets: Vec<VpcFirewallRuleTarget>,
+    /// Reductions on the scope of the rule
+    pub filters: VpcFirewallRuleFilter,
+    /// Whether traffic matching the rule should be allowed or dropped
+    pub action: VpcFirewallRuleAction,
+    /// The relative priority of this rule
+    pub priority: VpcFirewallRulePriority,
+}
+
+impl From<VpcFirewallRuleUpdate> for external::VpcFirewallRuleUpdate {
+    fn from(u: VpcFirewallRuleUpdate) -> Self {
+        Self {
+            name: u.name,
+            description: u.description,
+            status: u.status,
+            direction: u.direction,
+            targets: u.targets.into_iter().map(Into::into).collect(),
+            filters: u.filters.into(),
+            action: u.action,
+            priority: u.priority,
+        }
+    }
+}
+
+/// Updated list of firewall rules. Will replace all existing rules.
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct VpcFirewallRuleUpdateParams {
+    #[schemars(length(max = 1024))]
+    #[serde(default)]
+    pub rules: Vec<VpcFirewallRuleUpdate>,
+}
+
+impl From<VpcFirewallRuleUpdateParams>
+    for external::VpcFirewallRuleUpdateParams
+{
+    fn from(p: VpcFirewallRuleUpdateParams) -> Self {
+        Self { rules: p.rules.into_iter().map(Into::into).collect() }
+    }
+}
@@ -0,0 +1,26 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Types from API version 2026_10_19_06 that cannot live in `nexus-types-versions`
+//! because they convert to/from `omicron-common` types (orphan rule).
+//!
+//! This version pre-dates `FIREWALL_RULE_GENERATION`, which added the rule set
+//! generation to the list of a VPC's firewall rules.
+
+use omicron_common::api::external;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Collection of a Vpc's firewall rules
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct VpcFirewallRules {
+    pub rules: Vec<external::VpcFirewallRule>,
+}
+
+impl From<external::VpcFirewallRules> for VpcFirewallRules {
+    fn from(r: external::VpcFirewallRules) -> Self {
+        Self { rules: r.rules }
+    }
+}
@@ -1111,6 +1111,7 @@ pub fn sled_agent(
         zpools,
         datasets,
         ledgered_sled_config,
+        sled_config_ledger_report: None,
         reconciler_status,
         last_reconciliation,
         file_source_resolver,
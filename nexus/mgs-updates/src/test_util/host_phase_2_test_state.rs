@@ -365,6 +365,7 @@ mod api_impl {
                 zpools: Vec::new(),
                 datasets: Vec::new(),
                 ledgered_sled_config: Some(config.clone()),
+                sled_config_ledger_report: None,
                 reconciler_status: ConfigReconcilerInventoryStatus::Idle {
                     completed_at: Utc::now(),
                     ran_for: Duration::from_secs(5),
@@ -134,13 +134,14 @@ pub async fn resolve_firewall_rules_for_sled_agent(
     ensure_no_cross_vpc_references(vpc, rules)
         .map_err(FirewallRulesError::Lookup)?;
 
-    // Collect the names of instances, subnets, and VPCs that are either
+    // Collect the names of instances, subnets, VPCs, and tags that are either
     // targets or host filters. We have to find the sleds for all the
     // targets, and we'll need information about the IP addresses or
     // subnets for things that are specified as host filters as well.
     let mut instances: HashSet<Name> = HashSet::new();
     let mut subnets: HashSet<Name> = HashSet::new();
     let mut vpcs: HashSet<Name> = HashSet::new();
+    let mut tags: HashSet<external::Name> = HashSet::new();
     for rule in rules {
         for target in &rule.targets {
             match &target.0 {
@@ -157,6 +158,9 @@ pub async fn resolve_firewall_rules_for_sled_agent(
                 | external::VpcFirewallRuleTarget::IpNet(_) => {
                     vpcs.insert(vpc.name().clone().into());
                 }
+                external::VpcFirewallRuleTarget::Tag(name) => {
+                    tags.insert(name.clone());
+                }
             }
         }
 
@@ -171,6 +175,9 @@ pub async fn resolve_firewall_rules_for_sled_agent(
                 external::VpcFirewallRuleHostFilter::Vpc(name) => {
                     vpcs.insert(name.clone().into());
                 }
+                external::VpcFirewallRuleHostFilter::Tag(name) => {
+                    tags.insert(name.clone());
+                }
                 // We don't need to resolve anything for Ip(Net)s.
                 external::VpcFirewallRuleHostFilter::Ip(_) => (),
                 external::VpcFirewallRuleHostFilter::IpNet(_) => (),
@@ -253,6 +260,26 @@ pub async fn resolve_firewall_rules_for_sled_agent(
         }
     }
 
+    // Tags are resolved to the interfaces in this VPC of the instances
+    // currently carrying them.
+    let mut tag_interfaces: NicMap = HashMap::new();
+    if !tags.is_empty() {
+        if let Ok((.., authz_vpc)) = LookupPath::new(opctx, datastore)
+            .project_id(vpc.project_id)
+            .vpc_name(&Name::from(vpc.name().clone()))
+            .lookup_for(authz::Action::ListChildren)
+            .await
+        {
+            for tag in &tags {
+                let ifaces = datastore
+                    .derive_tag_network_interface_info(opctx, &authz_vpc, tag)
+                    .await
+                    .map_err(FirewallRulesError::Lookup)?;
+                tag_interfaces.insert(tag.clone(), ifaces);
+            }
+        }
+    }
+
     let subnet_networks: NetMap = datastore
         .resolve_vpc_subnets_to_ip_networks(vpc, subnets)
         .await
@@ -267,13 +294,22 @@ pub async fn resolve_firewall_rules_for_sled_agent(
         "instance_interfaces" => ?instance_interfaces,
         "vpc_interfaces" => ?vpc_interfaces,
         "subnet_interfaces" => ?subnet_interfaces,
+        "tag_interfaces" => ?tag_interfaces,
         "subnet_networks" => ?subnet_networks,
     );
 
     // Compile resolved rules for the sled agents.
     let mut sled_agent_rules =
         Vec::with_capacity(rules.len() + allowlist_rules.len());
-    for rule in rules.iter().chain(allowlist_rules.iter()) {
+    //
+    // The allowlist rules are synthesized with fresh IDs on each call, so
+    // their IDs are not passed along; the sled agents use rule IDs to attribute
+    // hit counters, and those should stay stable.
+    let rules_with_ids = rules
+        .iter()
+        .map(|rule| (rule, Some(rule.id())))
+        .chain(allowlist_rules.iter().map(|rule| (rule, None)));
+    for (rule, id) in rules_with_ids {
         // TODO: what is the correct behavior when a name is not found?
         // Options:
         // (1) Fail update request (though note this can still arise
@@ -347,6 +383,13 @@ pub async fn resolve_firewall_rules_for_sled_agent(
                         })
                         .for_each(&mut push_target_nic);
                 }
+                external::VpcFirewallRuleTarget::Tag(name) => {
+                    tag_interfaces
+                        .get(&name)
+                        .unwrap_or(&no_interfaces)
+                        .iter()
+                        .for_each(&mut push_target_nic);
+                }
             }
         }
         if !rule.targets.is_empty() && targets.is_empty() {
@@ -366,21 +409,21 @@ pub async fn resolve_firewall_rules_for_sled_agent(
                                 .get(&name)
                                 .unwrap_or(&no_interfaces)
                             {
-                                // Insert both IPv4 and / or IPv6 addresses.
-                                if let Some(ipv4) =
-                                    interface.ip_config.ipv4_addr()
-                                {
-                                    host_addrs.insert(HostIdentifier::Ip(
-                                        IpNet::host_net(IpAddr::V4(*ipv4)),
-                                    ));
-                                }
-                                if let Some(ipv6) =
-                                    interface.ip_config.ipv6_addr()
-                                {
-                                    host_addrs.insert(HostIdentifier::Ip(
-                                        IpNet::host_net(IpAddr::V6(*ipv6)),
-                                    ));
-                                }
+                                insert_interface_host_addrs(
+                                    &mut host_addrs,
+                                    interface,
+                                );
+                            }
+                        }
+                        external::VpcFirewallRuleHostFilter::Tag(name) => {
+                            for interface in tag_interfaces
+                                .get(&name)
+                                .unwrap_or(&no_interfaces)
+                            {
+                                insert_interface_host_addrs(
+                                    &mut host_addrs,
+                                    interface,
+                                );
                             }
                         }
                         external::VpcFirewallRuleHostFilter::Subnet(name) => {
@@ -427,6 +470,7 @@ pub async fn resolve_firewall_rules_for_sled_agent(
             .map(|protocols| protocols.iter().map(|v| v.0).collect());
 
         sled_agent_rules.push(ResolvedVpcFirewallRule {
+            id,
             status: rule.status.0,
             direction: rule.direction.0,
             targets,
@@ -446,6 +490,21 @@ pub async fn resolve_firewall_rules_for_sled_agent(
     Ok(sled_agent_rules)
 }
 
+/// Insert the IPv4 and / or IPv6 addresses of `interface` into `host_addrs`
+fn insert_interface_host_addrs(
+    host_addrs: &mut HashSet<HostIdentifier>,
+    interface: &NetworkInterface,
+) {
+    if let Some(ipv4) = interface.ip_config.ipv4_addr() {
+        host_addrs
+            .insert(HostIdentifier::Ip(IpNet::host_net(IpAddr::V4(*ipv4))));
+    }
+    if let Some(ipv6) = interface.ip_config.ipv6_addr() {
+        host_addrs
+            .insert(HostIdentifier::Ip(IpNet::host_net(IpAddr::V6(*ipv6))));
+    }
+}
+
 pub async fn send_sled_agents_firewall_rules(
     datastore: &DataStore,
     opctx: &OpContext,
@@ -6,7 +6,9 @@
 
 use crate::Sled;
 use iddqd::IdOrdMap;
+use internal_dns_types::config::sole_forward_zone;
 use internal_dns_types::diff::DnsDiff;
+use internal_dns_types::names::is_reverse_zone;
 use nexus_db_model::DnsGroup;
 use nexus_db_queries::context::OpContext;
 use nexus_db_queries::db::DataStore;
@@ -16,14 +18,19 @@ use nexus_types::deployment::Blueprint;
 use nexus_types::deployment::execution::Overridables;
 use nexus_types::deployment::execution::blueprint_external_dns_config;
 use nexus_types::deployment::execution::blueprint_internal_dns_config;
+use nexus_types::deployment::execution::blueprint_internal_reverse_dns_config;
 use nexus_types::identity::Resource;
 use nexus_types::internal_api::params::DnsConfigParams;
 use nexus_types::internal_api::params::DnsConfigZone;
+use nexus_types::silo::ACME_CHALLENGE_LABEL;
 use omicron_common::api::external::Error;
 use omicron_common::api::external::InternalContext;
 use omicron_common::bail_unless;
 use omicron_uuid_kinds::OmicronZoneUuid;
 use slog::{debug, info, o};
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
 
 pub(crate) async fn deploy_dns(
     opctx: &OpContext,
@@ -57,6 +64,16 @@ pub(crate) async fn deploy_dns(
         overrides,
     )
     .map_err(|e| Error::InternalError { internal_message: e.to_string() })?;
+    let internal_dns_reverse_zones_blueprint =
+        blueprint_internal_reverse_dns_config(
+            blueprint,
+            sleds_by_id,
+            active_nexus_generation,
+            overrides,
+        )
+        .map_err(|e| Error::InternalError {
+            internal_message: e.to_string(),
+        })?;
     let silos = datastore
         .silo_list_all_batched(opctx, Discoverability::All)
         .await
@@ -99,6 +116,7 @@ pub(crate) async fn deploy_dns(
         blueprint,
         &internal_dns_config_current,
         internal_dns_zone_blueprint,
+        &internal_dns_reverse_zones_blueprint,
         DnsGroup::Internal,
     )
     .await?;
@@ -109,6 +127,7 @@ pub(crate) async fn deploy_dns(
         blueprint,
         &external_dns_config_current,
         external_dns_zone_blueprint,
+        &[],
         DnsGroup::External,
     )
     .await?;
@@ -122,6 +141,7 @@ pub(crate) async fn deploy_dns_one(
     blueprint: &Blueprint,
     dns_config_current: &DnsConfigParams,
     dns_zone_blueprint: DnsConfigZone,
+    dns_reverse_zones_blueprint: &[DnsConfigZone],
     dns_group: DnsGroup,
 ) -> Result<(), Error> {
     let log = opctx
@@ -129,26 +149,43 @@ pub(crate) async fn deploy_dns_one(
         .new(o!("blueprint_execution" => format!("dns {:?}", dns_group)));
 
     // Other parts of the system support multiple external DNS zones.  We do not
-    // do so here.
-    let dns_zone_current = dns_config_current
-        .sole_zone()
+    // do so here.  Reverse zones are handled separately below.
+    let dns_zone_current = sole_forward_zone(dns_config_current)
         .map_err(|e| Error::internal_error(&format!("{:#}", e)))?;
 
+    // Nexus publishes ACME challenges in the external DNS zone itself, outside
+    // of any blueprint.  Leave them out of the comparison so that they aren't
+    // removed while the ACME server is validating them.
+    let dns_zone_current = match dns_group {
+        DnsGroup::External => &without_acme_challenges(dns_zone_current),
+        DnsGroup::Internal => dns_zone_current,
+    };
+
     // Looking at the current contents of DNS, prepare an update that will make
     // it match what it should be.
     let comment = format!("blueprint {} ({})", blueprint.id, blueprint.comment);
     let maybe_update = dns_compute_update(
         &log,
         dns_group,
-        comment,
-        creator,
+        comment.clone(),
+        creator.clone(),
         dns_zone_current,
         &dns_zone_blueprint,
     )?;
-    let Some(update) = maybe_update else {
+    let forward_changed = maybe_update.is_some();
+    let mut update = maybe_update.unwrap_or_else(|| {
+        DnsVersionUpdateBuilder::new(dns_group, comment, creator)
+    });
+    let reverse_changed = dns_compute_reverse_update(
+        &log,
+        &mut update,
+        dns_config_current,
+        dns_reverse_zones_blueprint,
+    )?;
+    if !forward_changed && !reverse_changed {
         // Nothing to do.
         return Ok(());
-    };
+    }
 
     // Our goal here is to update the DNS configuration stored in the database
     // to match the blueprint.  But it's always possible that we're executing a
@@ -233,6 +270,21 @@ pub(crate) async fn deploy_dns_one(
         .await
 }
 
+/// Returns a copy of `zone` without any names under which ACME challenges are
+/// published (see [`ACME_CHALLENGE_LABEL`])
+fn without_acme_challenges(zone: &DnsConfigZone) -> DnsConfigZone {
+    let prefix = format!("{ACME_CHALLENGE_LABEL}.");
+    DnsConfigZone {
+        zone_name: zone.zone_name.clone(),
+        records: zone
+            .records
+            .iter()
+            .filter(|(name, _)| !name.starts_with(&prefix))
+            .map(|(name, records)| (name.clone(), records.clone()))
+            .collect(),
+    }
+}
+
 fn dns_compute_update(
     log: &slog::Logger,
     dns_group: DnsGroup,
@@ -291,6 +343,99 @@ fn dns_compute_update(
     Ok(Some(update))
 }
 
+/// Adds to `update` the changes needed to make the reverse zones in
+/// `current_config` match `new_zones`, returning whether there were any
+///
+/// Reverse zones that are in `new_zones` but not `current_config` are created.
+/// Reverse zones in `current_config` that are not in `new_zones` have all of
+/// their names removed.
+fn dns_compute_reverse_update(
+    log: &slog::Logger,
+    update: &mut DnsVersionUpdateBuilder,
+    current_config: &DnsConfigParams,
+    new_zones: &[DnsConfigZone],
+) -> Result<bool, Error> {
+    let current_zones: BTreeMap<_, _> = current_config
+        .zones
+        .iter()
+        .filter(|zone| is_reverse_zone(&zone.zone_name))
+        .map(|zone| (zone.zone_name.as_str(), zone))
+        .collect();
+    let new_zones: BTreeMap<_, _> =
+        new_zones.iter().map(|zone| (zone.zone_name.as_str(), zone)).collect();
+    let zone_names: BTreeSet<_> =
+        current_zones.keys().chain(new_zones.keys()).copied().collect();
+
+    let mut changed = false;
+    for zone_name in zone_names {
+        let empty_zone = DnsConfigZone {
+            zone_name: zone_name.to_string(),
+            records: HashMap::new(),
+        };
+        let current_zone =
+            current_zones.get(zone_name).copied().unwrap_or(&empty_zone);
+        let new_zone = new_zones.get(zone_name).copied().unwrap_or(&empty_zone);
+        let diff = DnsDiff::new(current_zone, new_zone)
+            .map_err(|e| Error::internal_error(&format!("{:#}", e)))?;
+        if diff.is_empty() {
+            continue;
+        }
+
+        changed = true;
+        if !current_zones.contains_key(zone_name) {
+            debug!(log, "adding reverse zone"; "dns_zone" => zone_name);
+            update.add_zone(zone_name.to_string())?;
+        }
+
+        for (name, new_records) in diff.names_added() {
+            debug!(
+                log,
+                "adding name";
+                "dns_zone" => zone_name,
+                "dns_name" => name,
+                "new_records" => ?new_records,
+            );
+            update.add_name_in_zone(
+                zone_name.to_string(),
+                name.to_string(),
+                new_records.into_iter().cloned().collect(),
+            )?;
+        }
+
+        for (name, old_records) in diff.names_removed() {
+            debug!(
+                log,
+                "removing name";
+                "dns_zone" => zone_name,
+                "dns_name" => name,
+                "old_records" => ?old_records,
+            );
+            update
+                .remove_name_in_zone(zone_name.to_string(), name.to_string())?;
+        }
+
+        for (name, old_records, new_records) in diff.names_changed() {
+            debug!(
+                log,
+                "updating name";
+                "dns_zone" => zone_name,
+                "dns_name" => name,
+                "old_records" => ?old_records,
+                "new_records" => ?new_records,
+            );
+            update
+                .remove_name_in_zone(zone_name.to_string(), name.to_string())?;
+            update.add_name_in_zone(
+                zone_name.to_string(),
+                name.to_string(),
+                new_records.into_iter().cloned().collect(),
+            )?;
+        }
+    }
+
+    Ok(changed)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -303,6 +448,8 @@ mod test {
     use internal_dns_types::names::BOUNDARY_NTP_DNS_NAME;
     use internal_dns_types::names::DNS_ZONE;
     use internal_dns_types::names::ServiceName;
+    use internal_dns_types::names::ipv6_reverse_name;
+    use internal_dns_types::names::ipv6_reverse_zone;
     use nexus_db_model::DnsGroup;
     use nexus_db_model::Silo;
     use nexus_db_queries::authn;
@@ -1036,6 +1183,51 @@ mod test {
             expected_boundary_ntp_srv_targets
         );
         assert!(expected_boundary_ntp_srv_targets.is_empty());
+
+        // Finally, every host name (for a sled, Omicron zone, or switch zone)
+        // with a AAAA record should have a PTR record pointing back at it in
+        // the reverse zone for its address, and there should be no other PTR
+        // records.
+        let reverse_zones = blueprint_internal_reverse_dns_config(
+            &blueprint,
+            &sleds_by_id,
+            blueprint.nexus_generation,
+            &Default::default(),
+        )
+        .unwrap();
+        let mut expected_ptrs = BTreeSet::new();
+        for (name, records) in &blueprint_dns_zone.records {
+            if !name.ends_with(".host") && !name.ends_with(".sled") {
+                continue;
+            }
+            for record in records {
+                let DnsRecord::Aaaa(addr) = record else {
+                    panic!("expected AAAA record for {name}; got {record:?}");
+                };
+                expected_ptrs.insert((
+                    ipv6_reverse_zone(*addr),
+                    ipv6_reverse_name(*addr),
+                    format!("{}.{}", name, blueprint_dns_zone.zone_name),
+                ));
+            }
+        }
+        let found_ptrs = reverse_zones
+            .iter()
+            .flat_map(|zone| {
+                zone.records.iter().flat_map(move |(name, records)| {
+                    records.iter().filter_map(move |record| match record {
+                        DnsRecord::Ptr(target) => Some((
+                            zone.zone_name.clone(),
+                            name.clone(),
+                            target.clone(),
+                        )),
+                        _ => None,
+                    })
+                })
+            })
+            .collect::<BTreeSet<_>>();
+        assert!(!expected_ptrs.is_empty());
+        assert_eq!(expected_ptrs, found_ptrs);
     }
 
     #[tokio::test]
@@ -1142,7 +1334,11 @@ mod test {
                 .map(|record| match record {
                     DnsRecord::A(v) => IpAddr::V4(*v),
                     DnsRecord::Aaaa(v) => IpAddr::V6(*v),
-                    other @ DnsRecord::Srv(_) | other @ DnsRecord::Ns(_) => {
+                    other @ (DnsRecord::Srv(_)
+                    | DnsRecord::Ns(_)
+                    | DnsRecord::Txt(_)
+                    | DnsRecord::Cname(_)
+                    | DnsRecord::Ptr(_)) => {
                         panic!("unexpected DNS record for silo: {other:?}")
                     }
                 })
@@ -1368,8 +1564,8 @@ mod test {
         left: &'a DnsConfigParams,
         right: &'a DnsConfigParams,
     ) -> DnsDiff<'a> {
-        let left_zone = left.sole_zone().unwrap();
-        let right_zone = right.sole_zone().unwrap();
+        let left_zone = sole_forward_zone(left).unwrap();
+        let right_zone = sole_forward_zone(right).unwrap();
         DnsDiff::new(left_zone, right_zone).unwrap()
     }
 
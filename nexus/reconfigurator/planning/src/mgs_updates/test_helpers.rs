@@ -1368,6 +1368,7 @@ impl<'a> TestBoardCollectionBuilder<'a> {
                             zpools: vec![],
                             datasets: vec![],
                             ledgered_sled_config: Some(fake_sled_config),
+                            sled_config_ledger_report: None,
                             reconciler_status:
                                 ConfigReconcilerInventoryStatus::NotYetRun,
                             file_source_resolver:
@@ -1491,6 +1491,7 @@ impl Sled {
                     .collect(),
                 datasets: vec![],
                 ledgered_sled_config: Some(sled_config.clone()),
+                sled_config_ledger_report: None,
                 reconciler_status: ConfigReconcilerInventoryStatus::Idle {
                     completed_at: Utc::now(),
                     ran_for: Duration::from_secs(5),
@@ -1682,6 +1683,7 @@ impl Sled {
             zpools: vec![],
             datasets: vec![],
             ledgered_sled_config: inv_sled_agent.ledgered_sled_config.clone(),
+            sled_config_ledger_report: None,
             reconciler_status: inv_sled_agent.reconciler_status.clone(),
             last_reconciliation: inv_sled_agent.last_reconciliation.clone(),
             file_source_resolver: inv_sled_agent.file_source_resolver.clone(),
@@ -0,0 +1,744 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! # ACME Certificate Issuance
+//!
+//! Rather than relying on operators to upload (and remember to replace) each
+//! Silo's TLS certificate, Nexus can obtain them from an [ACME] server such as
+//! Let's Encrypt.  The `acme_certificates` background task decides which Silos
+//! need a new certificate and stores what it obtains alongside uploaded
+//! certificates, where the `external_endpoints` task picks them up like any
+//! other.
+//!
+//! Nexus proves that it controls a Silo's DNS names using one of two kinds of
+//! challenge, chosen in its configuration:
+//!
+//! * HTTP-01 (the default): the ACME server fetches
+//!   `/.well-known/acme-challenge/<token>` from each name, and the external
+//!   API answers with the challenge's key authorization.  Outstanding
+//!   challenges are stored in the database, since the request may arrive at
+//!   any Nexus.  The ACME server makes that request over plain HTTP to port
+//!   80, so the external API must be reachable there.
+//! * DNS-01: Nexus publishes a digest of the key authorization as a TXT
+//!   record at `_acme-challenge.<name>` in the external DNS zone, and the ACME
+//!   server looks it up.  This requires that the zone be delegated to the
+//!   rack's external DNS servers, which is also what makes the Silo's names
+//!   resolve in the first place.
+//!
+//! [`AcmeClient`] implements only what Nexus needs from the protocol:
+//! registering an account with an ECDSA P-256 key, ordering a certificate,
+//! answering its challenges, and finalizing the order.
+//!
+//! [ACME]: https://www.rfc-editor.org/rfc/rfc8555
+
+use crate::Nexus;
+use crate::app::background::Activator;
+use anyhow::Context;
+use anyhow::anyhow;
+use anyhow::bail;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use nexus_config::AcmeChallengeType;
+use nexus_db_queries::context::OpContext;
+use nexus_db_queries::db::DataStore;
+use nexus_db_queries::db::model::AcmeHttp01Challenge;
+use nexus_types::silo::ACME_CHALLENGE_LABEL;
+use omicron_common::api::external::Error;
+use openssl::bn::BigNum;
+use openssl::bn::BigNumContext;
+use openssl::ec::EcGroup;
+use openssl::ec::EcKey;
+use openssl::ecdsa::EcdsaSig;
+use openssl::hash::MessageDigest;
+use openssl::hash::hash;
+use openssl::nid::Nid;
+use openssl::pkey::PKey;
+use openssl::pkey::Private;
+use openssl::stack::Stack;
+use openssl::x509::X509ReqBuilder;
+use openssl::x509::extension::SubjectAlternativeName;
+use serde::Deserialize;
+use serde_json::json;
+use slog_error_chain::InlineErrorChain;
+use std::time::Duration;
+
+/// How long we wait between checks of an authorization or order that the ACME
+/// server is still working on
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// How many times we check an authorization or order before giving up on it
+const POLL_ATTEMPTS: usize = 30;
+
+/// The problem type with which ACME servers reject a stale nonce
+const BAD_NONCE: &str = "urn:ietf:params:acme:error:badNonce";
+
+/// How long we give the external DNS servers to start serving a DNS-01
+/// challenge's TXT record before asking the ACME server to look it up
+///
+/// Propagation normally takes a few seconds.  If this turns out to be too
+/// short, the authorization fails and the next activation of the background
+/// task tries again.
+const DNS01_PROPAGATION_DELAY: Duration = Duration::from_secs(15);
+
+impl Nexus {
+    /// Returns the key authorization that answers an outstanding HTTP-01
+    /// challenge
+    pub(crate) async fn acme_http01_key_authorization(
+        &self,
+        token: &str,
+    ) -> Result<String, Error> {
+        self.db_datastore
+            .acme_http01_challenge_fetch(self.opctx_external_authn(), token)
+            .await?
+            .map(|challenge| challenge.key_authorization)
+            .ok_or_else(|| {
+                Error::non_resourcetype_not_found(format!(
+                    "ACME challenge {token:?}"
+                ))
+            })
+    }
+}
+
+/// Finishes building the HTTP client used to talk to ACME servers
+pub(super) fn http_client(
+    builder: reqwest::ClientBuilder,
+) -> Result<reqwest::Client, reqwest::Error> {
+    builder
+        .connect_timeout(Duration::from_secs(10))
+        .timeout(Duration::from_secs(30))
+        .build()
+}
+
+/// What [`AcmeClient`] needs to answer challenges of the configured kind
+pub(crate) struct ChallengeContext<'a> {
+    pub opctx: &'a OpContext,
+    pub datastore: &'a DataStore,
+    pub kind: AcmeChallengeType,
+    /// names of the external DNS zones, for DNS-01 challenges
+    pub dns_zones: &'a [String],
+    /// identifies this Nexus in the history of external DNS changes
+    pub dns_creator: &'a str,
+    /// activates the task that sends external DNS changes to the DNS servers
+    pub dns_config: &'a Activator,
+}
+
+/// A certificate chain and its private key, both in PEM format
+pub(crate) struct ObtainedCertificate {
+    pub cert: String,
+    pub key: String,
+}
+
+/// Client for an ACME server, acting as the account Nexus registered there
+pub(crate) struct AcmeClient {
+    log: slog::Logger,
+    http: reqwest::Client,
+    directory: Directory,
+    key: EcKey<Private>,
+    /// URL identifying the account, once registered
+    account_url: Option<String>,
+    /// nonce from the server's last response, to be used in the next request
+    nonce: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Directory {
+    new_nonce: String,
+    new_account: String,
+    new_order: String,
+}
+
+#[derive(Deserialize)]
+struct Order {
+    status: String,
+    authorizations: Vec<String>,
+    finalize: String,
+    certificate: Option<String>,
+    error: Option<Problem>,
+}
+
+#[derive(Deserialize)]
+struct Authorization {
+    status: String,
+    identifier: Identifier,
+    challenges: Vec<Challenge>,
+}
+
+#[derive(Deserialize)]
+struct Identifier {
+    value: String,
+}
+
+#[derive(Deserialize)]
+struct Challenge {
+    #[serde(rename = "type")]
+    kind: String,
+    url: String,
+    #[serde(default)]
+    token: Option<String>,
+    #[serde(default)]
+    error: Option<Problem>,
+}
+
+/// An error reported by the ACME server (RFC 7807 "problem details")
+#[derive(Deserialize)]
+struct Problem {
+    #[serde(rename = "type", default)]
+    kind: String,
+    #[serde(default)]
+    detail: Option<String>,
+}
+
+impl std::fmt::Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.detail {
+            Some(detail) => write!(f, "{} ({})", detail, self.kind),
+            None => write!(f, "{}", self.kind),
+        }
+    }
+}
+
+impl AcmeClient {
+    /// Registers a new account with the ACME server whose directory is at
+    /// `directory_url`
+    pub async fn register(
+        log: slog::Logger,
+        http: reqwest::Client,
+        directory_url: &str,
+        contact: &[String],
+    ) -> anyhow::Result<AcmeClient> {
+        let directory = fetch_directory(&http, directory_url).await?;
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1)?;
+        let key = EcKey::generate(&group).context("generating account key")?;
+        let mut client = AcmeClient {
+            log,
+            http,
+            directory,
+            key,
+            account_url: None,
+            nonce: None,
+        };
+
+        let new_account_url = client.directory.new_account.clone();
+        let response = client
+            .post(
+                &new_account_url,
+                Some(&json!({
+                    "termsOfServiceAgreed": true,
+                    "contact": contact,
+                })),
+            )
+            .await
+            .context("registering account")?;
+        let account_url = location(&response)?;
+        info!(client.log, "registered ACME account"; "url" => &account_url);
+        client.account_url = Some(account_url);
+        Ok(client)
+    }
+
+    /// Uses an account previously registered with the ACME server whose
+    /// directory is at `directory_url`
+    pub async fn existing(
+        log: slog::Logger,
+        http: reqwest::Client,
+        directory_url: &str,
+        key_pem: &str,
+        account_url: String,
+    ) -> anyhow::Result<AcmeClient> {
+        let directory = fetch_directory(&http, directory_url).await?;
+        let key = PKey::private_key_from_pem(key_pem.as_bytes())
+            .and_then(|key| key.ec_key())
+            .context("parsing account key")?;
+        Ok(AcmeClient {
+            log,
+            http,
+            directory,
+            key,
+            account_url: Some(account_url),
+            nonce: None,
+        })
+    }
+
+    /// Returns the URL identifying this client's account
+    pub fn account_url(&self) -> &str {
+        // Both constructors leave the account registered.
+        self.account_url.as_deref().expect("ACME account is registered")
+    }
+
+    /// Returns the account's private key in PEM format
+    pub fn key_pem(&self) -> anyhow::Result<String> {
+        let pem = PKey::from_ec_key(self.key.clone())?
+            .private_key_to_pem_pkcs8()
+            .context("serializing account key")?;
+        Ok(String::from_utf8(pem)?)
+    }
+
+    /// Obtains a certificate valid for all of `dns_names`
+    ///
+    /// Challenges are published while the ACME server validates them: HTTP-01
+    /// challenges in the database, so that whichever Nexus receives the
+    /// server's request can answer it, and DNS-01 challenges in the external
+    /// DNS zones.
+    pub async fn obtain_certificate(
+        &mut self,
+        challenges: &ChallengeContext<'_>,
+        dns_names: &[String],
+    ) -> anyhow::Result<ObtainedCertificate> {
+        let identifiers: Vec<_> = dns_names
+            .iter()
+            .map(|name| json!({ "type": "dns", "value": name }))
+            .collect();
+        let new_order_url = self.directory.new_order.clone();
+        let response = self
+            .post(&new_order_url, Some(&json!({ "identifiers": identifiers })))
+            .await
+            .context("creating order")?;
+        let order_url = location(&response)?;
+        let order: Order = response.json().await.context("parsing order")?;
+
+        for authorization_url in &order.authorizations {
+            self.authorize(challenges, authorization_url).await?;
+        }
+
+        // Once every authorization is valid, the order is ready to be
+        // finalized with a request for the certificate.
+        let order = self.poll_order(&order_url, "ready").await?;
+        let (csr, key) = certificate_request(dns_names)?;
+        self.post(
+            &order.finalize,
+            Some(&json!({ "csr": URL_SAFE_NO_PAD.encode(csr) })),
+        )
+        .await
+        .context("finalizing order")?;
+
+        let order = self.poll_order(&order_url, "valid").await?;
+        let certificate_url = order.certificate.ok_or_else(|| {
+            anyhow!("valid order {order_url} has no certificate")
+        })?;
+        let cert = self
+            .post(&certificate_url, None)
+            .await
+            .context("downloading certificate")?
+            .text()
+            .await
+            .context("reading certificate")?;
+        Ok(ObtainedCertificate { cert, key })
+    }
+
+    /// Completes the authorization at `url` by answering its challenge of the
+    /// configured kind, unless the server already considers it valid
+    async fn authorize(
+        &mut self,
+        challenges: &ChallengeContext<'_>,
+        url: &str,
+    ) -> anyhow::Result<()> {
+        let authorization: Authorization = self
+            .post(url, None)
+            .await
+            .context("fetching authorization")?
+            .json()
+            .await
+            .context("parsing authorization")?;
+        if authorization.status == "valid" {
+            return Ok(());
+        }
+
+        let dns_name = &authorization.identifier.value;
+        let kind = match challenges.kind {
+            AcmeChallengeType::Http01 => "http-01",
+            AcmeChallengeType::Dns01 => "dns-01",
+        };
+        let challenge = authorization
+            .challenges
+            .iter()
+            .find(|c| c.kind == kind)
+            .ok_or_else(|| {
+                anyhow!("no {kind} challenge offered for {dns_name}")
+            })?;
+        let token = challenge.token.clone().ok_or_else(|| {
+            anyhow!("{kind} challenge for {dns_name} has no token")
+        })?;
+        let key_authorization = format!("{token}.{}", self.thumbprint()?);
+
+        let published = match challenges.kind {
+            AcmeChallengeType::Http01 => {
+                PublishedChallenge::publish_http01(
+                    challenges,
+                    token,
+                    key_authorization,
+                )
+                .await?
+            }
+            AcmeChallengeType::Dns01 => {
+                PublishedChallenge::publish_dns01(
+                    challenges,
+                    dns_name,
+                    &key_authorization,
+                )
+                .await?
+            }
+        };
+        let result = self.complete_challenge(url, &challenge.url).await;
+        published.withdraw(&self.log, challenges).await;
+        result.with_context(|| format!("validating control of {dns_name}"))
+    }
+
+    /// Tells the server to validate the challenge at `challenge_url`, then
+    /// waits for the authorization at `authorization_url` to be decided
+    async fn complete_challenge(
+        &mut self,
+        authorization_url: &str,
+        challenge_url: &str,
+    ) -> anyhow::Result<()> {
+        // An empty object tells the server that the challenge is ready to be
+        // validated.
+        self.post(challenge_url, Some(&json!({}))).await?;
+        for _ in 0..POLL_ATTEMPTS {
+            let authorization: Authorization =
+                self.post(authorization_url, None).await?.json().await?;
+            match authorization.status.as_str() {
+                "valid" => return Ok(()),
+                "pending" => tokio::time::sleep(POLL_INTERVAL).await,
+                status => {
+                    let problem = authorization
+                        .challenges
+                        .iter()
+                        .find_map(|c| c.error.as_ref())
+                        .map(|p| format!(": {p}"))
+                        .unwrap_or_default();
+                    bail!("authorization is {status}{problem}");
+                }
+            }
+        }
+        bail!("timed out waiting for validation")
+    }
+
+    /// Waits for the order at `url` to reach the `wanted` state
+    async fn poll_order(
+        &mut self,
+        url: &str,
+        wanted: &str,
+    ) -> anyhow::Result<Order> {
+        for _ in 0..POLL_ATTEMPTS {
+            let order: Order = self
+                .post(url, None)
+                .await
+                .context("fetching order")?
+                .json()
+                .await
+                .context("parsing order")?;
+            if order.status == wanted {
+                return Ok(order);
+            }
+            if order.status == "invalid" {
+                let problem =
+                    order.error.map(|p| format!(": {p}")).unwrap_or_default();
+                bail!("order {url} is invalid{problem}");
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+        bail!("timed out waiting for order {url}")
+    }
+
+    /// Sends a signed request to the ACME server, returning its response if
+    /// it was successful
+    ///
+    /// Without a `payload`, this is a "POST-as-GET" request, which is how ACME
+    /// fetches resources on behalf of an account.
+    async fn post(
+        &mut self,
+        url: &str,
+        payload: Option<&serde_json::Value>,
+    ) -> anyhow::Result<reqwest::Response> {
+        // The server may reject our nonce as stale, in which case it provides a
+        // fresh one with which we should try again.
+        let mut retried = false;
+        loop {
+            let nonce = match self.nonce.take() {
+                Some(nonce) => nonce,
+                None => self.new_nonce().await?,
+            };
+            let body = self.sign(url, &nonce, payload)?;
+            let response = self
+                .http
+                .post(url)
+                .header(reqwest::header::CONTENT_TYPE, "application/jose+json")
+                .body(body)
+                .send()
+                .await
+                .with_context(|| format!("POST {url}"))?;
+            self.nonce = replay_nonce(&response);
+
+            let status = response.status();
+            if status.is_success() {
+                return Ok(response);
+            }
+            let problem = response.json::<Problem>().await.ok();
+            if let Some(problem) = &problem
+                && problem.kind == BAD_NONCE
+                && !retried
+            {
+                retried = true;
+                continue;
+            }
+            let problem = problem.map(|p| format!(": {p}")).unwrap_or_default();
+            bail!("POST {url}: {status}{problem}");
+        }
+    }
+
+    /// Fetches a fresh nonce from the server
+    async fn new_nonce(&self) -> anyhow::Result<String> {
+        let url = &self.directory.new_nonce;
+        let response = self
+            .http
+            .head(url)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .with_context(|| format!("HEAD {url}"))?;
+        replay_nonce(&response)
+            .ok_or_else(|| anyhow!("HEAD {url}: response has no nonce"))
+    }
+
+    /// Encodes a request body as a JSON Web Signature (RFC 7515) using the
+    /// account key
+    fn sign(
+        &self,
+        url: &str,
+        nonce: &str,
+        payload: Option<&serde_json::Value>,
+    ) -> anyhow::Result<String> {
+        let mut protected =
+            json!({ "alg": "ES256", "nonce": nonce, "url": url });
+        match &self.account_url {
+            Some(account_url) => protected["kid"] = json!(account_url),
+            // Until the account is registered, requests carry its public key.
+            None => protected["jwk"] = self.jwk()?,
+        }
+        let protected = URL_SAFE_NO_PAD.encode(serde_json::to_vec(&protected)?);
+        let payload = match payload {
+            Some(payload) => {
+                URL_SAFE_NO_PAD.encode(serde_json::to_vec(payload)?)
+            }
+            None => String::new(),
+        };
+
+        // ES256 signatures are the two 32-byte integers r and s, concatenated
+        // (rather than the DER structure OpenSSL produces).
+        let digest = hash(
+            MessageDigest::sha256(),
+            format!("{protected}.{payload}").as_bytes(),
+        )?;
+        let signature = EcdsaSig::sign(&digest, &self.key)?;
+        let mut raw_signature = signature.r().to_vec_padded(32)?;
+        raw_signature.extend(signature.s().to_vec_padded(32)?);
+
+        Ok(serde_json::to_string(&json!({
+            "protected": protected,
+            "payload": payload,
+            "signature": URL_SAFE_NO_PAD.encode(raw_signature),
+        }))?)
+    }
+
+    /// Returns the account's public key as a JSON Web Key (RFC 7517)
+    fn jwk(&self) -> anyhow::Result<serde_json::Value> {
+        let (x, y) = self.public_coordinates()?;
+        Ok(json!({ "crv": "P-256", "kty": "EC", "x": x, "y": y }))
+    }
+
+    /// Returns the thumbprint (RFC 7638) of the account's public key, which
+    /// forms part of each challenge's key authorization
+    fn thumbprint(&self) -> anyhow::Result<String> {
+        // The thumbprint is computed over the required members of the key,
+        // in lexicographic order and without whitespace.
+        let (x, y) = self.public_coordinates()?;
+        let jwk =
+            format!(r#"{{"crv":"P-256","kty":"EC","x":"{x}","y":"{y}"}}"#);
+        Ok(URL_SAFE_NO_PAD
+            .encode(hash(MessageDigest::sha256(), jwk.as_bytes())?))
+    }
+
+    /// Returns the base64url-encoded coordinates of the account's public key
+    fn public_coordinates(&self) -> anyhow::Result<(String, String)> {
+        let mut ctx = BigNumContext::new()?;
+        let mut x = BigNum::new()?;
+        let mut y = BigNum::new()?;
+        self.key.public_key().affine_coordinates(
+            self.key.group(),
+            &mut x,
+            &mut y,
+            &mut ctx,
+        )?;
+        Ok((
+            URL_SAFE_NO_PAD.encode(x.to_vec_padded(32)?),
+            URL_SAFE_NO_PAD.encode(y.to_vec_padded(32)?),
+        ))
+    }
+}
+
+/// A challenge answer that's available to the ACME server until withdrawn
+enum PublishedChallenge {
+    Http01 { token: String },
+    Dns01 { name: String, value: String },
+}
+
+impl PublishedChallenge {
+    async fn publish_http01(
+        challenges: &ChallengeContext<'_>,
+        token: String,
+        key_authorization: String,
+    ) -> anyhow::Result<PublishedChallenge> {
+        challenges
+            .datastore
+            .acme_http01_challenge_create(
+                challenges.opctx,
+                AcmeHttp01Challenge::new(token.clone(), key_authorization),
+            )
+            .await
+            .context("recording challenge")?;
+        Ok(PublishedChallenge::Http01 { token })
+    }
+
+    /// Publishes the TXT record answering a DNS-01 challenge for `dns_name`
+    /// and waits for the external DNS servers to pick it up
+    async fn publish_dns01(
+        challenges: &ChallengeContext<'_>,
+        dns_name: &str,
+        key_authorization: &str,
+    ) -> anyhow::Result<PublishedChallenge> {
+        // The name is published in every external DNS zone, so it's given
+        // relative to whichever of them `dns_name` is in.
+        let relative_name = challenges
+            .dns_zones
+            .iter()
+            .find_map(|zone| {
+                dns_name
+                    .strip_suffix(zone.as_str())
+                    .and_then(|name| name.strip_suffix('.'))
+            })
+            .ok_or_else(|| {
+                anyhow!("{dns_name} is not in any external DNS zone")
+            })?;
+        let name = format!("{ACME_CHALLENGE_LABEL}.{relative_name}");
+        let value = URL_SAFE_NO_PAD.encode(hash(
+            MessageDigest::sha256(),
+            key_authorization.as_bytes(),
+        )?);
+        challenges
+            .datastore
+            .acme_dns01_challenge_create(
+                challenges.opctx,
+                challenges.dns_creator,
+                &name,
+                &value,
+            )
+            .await
+            .context("publishing challenge in external DNS")?;
+        challenges.dns_config.activate();
+        tokio::time::sleep(DNS01_PROPAGATION_DELAY).await;
+        Ok(PublishedChallenge::Dns01 { name, value })
+    }
+
+    /// Removes the challenge once the ACME server has decided it
+    ///
+    /// Failures are only logged.  A leftover HTTP-01 challenge or TXT record
+    /// is harmless: it's only useful to whoever holds the account key.
+    async fn withdraw(
+        self,
+        log: &slog::Logger,
+        challenges: &ChallengeContext<'_>,
+    ) {
+        let result = match &self {
+            PublishedChallenge::Http01 { token } => {
+                challenges
+                    .datastore
+                    .acme_http01_challenge_delete(challenges.opctx, token)
+                    .await
+            }
+            PublishedChallenge::Dns01 { name, value } => {
+                let result = challenges
+                    .datastore
+                    .acme_dns01_challenge_delete(
+                        challenges.opctx,
+                        challenges.dns_creator,
+                        name,
+                        value,
+                    )
+                    .await;
+                challenges.dns_config.activate();
+                result
+            }
+        };
+        if let Err(error) = result {
+            let (PublishedChallenge::Http01 { token: id }
+            | PublishedChallenge::Dns01 { name: id, .. }) = &self;
+            warn!(
+                log,
+                "failed to remove ACME challenge";
+                "challenge" => id,
+                InlineErrorChain::new(&error),
+            );
+        }
+    }
+}
+
+async fn fetch_directory(
+    http: &reqwest::Client,
+    directory_url: &str,
+) -> anyhow::Result<Directory> {
+    http.get(directory_url)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .with_context(|| format!("GET {directory_url}"))?
+        .json()
+        .await
+        .with_context(|| format!("parsing ACME directory {directory_url}"))
+}
+
+fn replay_nonce(response: &reqwest::Response) -> Option<String> {
+    response
+        .headers()
+        .get("replay-nonce")
+        .and_then(|v| v.to_str().ok())
+        .map(String::from)
+}
+
+/// Returns the URL of the resource created by a request
+fn location(response: &reqwest::Response) -> anyhow::Result<String> {
+    response
+        .headers()
+        .get(reqwest::header::LOCATION)
+        .and_then(|v| v.to_str().ok())
+        .map(String::from)
+        .ok_or_else(|| anyhow!("{}: response has no Location", response.url()))
+}
+
+/// Generates a private key for a certificate valid for `dns_names`, returning
+/// a certificate signing request (in DER format) and the key (in PEM format)
+fn certificate_request(
+    dns_names: &[String],
+) -> anyhow::Result<(Vec<u8>, String)> {
+    let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1)?;
+    let key = PKey::from_ec_key(EcKey::generate(&group)?)?;
+
+    // The names go only in the subjectAltName extension.  That's where
+    // clients look for them, and a Silo's name may be too long for the
+    // subject's common name.
+    let mut builder = X509ReqBuilder::new()?;
+    builder.set_pubkey(&key)?;
+    let mut san = SubjectAlternativeName::new();
+    for dns_name in dns_names {
+        san.dns(dns_name);
+    }
+    let san = san.build(&builder.x509v3_context(None))?;
+    let mut extensions = Stack::new()?;
+    extensions.push(san)?;
+    builder.add_extensions(&extensions)?;
+    builder.sign(&key, MessageDigest::sha256())?;
+
+    let csr = builder.build().to_der().context("encoding CSR")?;
+    let key = String::from_utf8(key.private_key_to_pem_pkcs8()?)?;
+    Ok((csr, key))
+}
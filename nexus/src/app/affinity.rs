@@ -20,6 +20,7 @@ use omicron_common::api::external::Error;
 use omicron_common::api::external::ListResultVec;
 use omicron_common::api::external::LookupResult;
 use omicron_common::api::external::NameOrId;
+use omicron_common::api::external::ResourceType;
 use omicron_common::api::external::UpdateResult;
 use omicron_common::api::external::http_pagination::PaginatedBy;
 use omicron_uuid_kinds::GenericUuid;
@@ -150,10 +151,13 @@ impl super::Nexus {
 
         let affinity_group =
             AffinityGroup::new(authz_project.id(), affinity_group_params);
-        self.db_datastore
+        let group: affinity::AffinityGroup = self
+            .db_datastore
             .affinity_group_create(opctx, &authz_project, affinity_group)
-            .await
-            .map(Into::into)
+            .await?
+            .into();
+        opctx.set_audit_target(ResourceType::AffinityGroup, group.identity.id);
+        Ok(group)
     }
 
     pub(crate) async fn anti_affinity_group_create(
@@ -169,14 +173,20 @@ impl super::Nexus {
             authz_project.id(),
             anti_affinity_group_params,
         );
-        self.db_datastore
+        let group: affinity::AntiAffinityGroup = self
+            .db_datastore
             .anti_affinity_group_create(
                 opctx,
                 &authz_project,
                 anti_affinity_group,
             )
-            .await
-            .map(Into::into)
+            .await?
+            .into();
+        opctx.set_audit_target(
+            ResourceType::AntiAffinityGroup,
+            group.identity.id,
+        );
+        Ok(group)
     }
 
     pub(crate) async fn affinity_group_update(
@@ -187,6 +197,7 @@ impl super::Nexus {
     ) -> UpdateResult<affinity::AffinityGroup> {
         let (.., authz_group) =
             group_lookup.lookup_for(authz::Action::Modify).await?;
+        opctx.set_audit_target(ResourceType::AffinityGroup, authz_group.id());
         self.db_datastore
             .affinity_group_update(opctx, &authz_group, updates.clone().into())
             .await
@@ -201,6 +212,10 @@ impl super::Nexus {
     ) -> UpdateResult<affinity::AntiAffinityGroup> {
         let (.., authz_group) =
             group_lookup.lookup_for(authz::Action::Modify).await?;
+        opctx.set_audit_target(
+            ResourceType::AntiAffinityGroup,
+            authz_group.id(),
+        );
         self.db_datastore
             .anti_affinity_group_update(
                 opctx,
@@ -218,6 +233,7 @@ impl super::Nexus {
     ) -> DeleteResult {
         let (.., authz_group) =
             group_lookup.lookup_for(authz::Action::Delete).await?;
+        opctx.set_audit_target(ResourceType::AffinityGroup, authz_group.id());
         self.db_datastore.affinity_group_delete(opctx, &authz_group).await
     }
 
@@ -228,6 +244,10 @@ impl super::Nexus {
     ) -> DeleteResult {
         let (.., authz_group) =
             group_lookup.lookup_for(authz::Action::Delete).await?;
+        opctx.set_audit_target(
+            ResourceType::AntiAffinityGroup,
+            authz_group.id(),
+        );
         self.db_datastore.anti_affinity_group_delete(opctx, &authz_group).await
     }
 
@@ -16,8 +16,8 @@
 //!   API are children of the [`AlertReceiver`] API resource.
 //!
 //!   Various mechanisms for delivering alerts are represented by "subtypes" of
-//!   alert receivers.  At present, [webhooks](super::webhook) are the only
-//!   such subtype.  Different subtypes of alert receivers are created and
+//!   alert receivers: [webhooks](super::webhook), [SMTP](super::smtp), and
+//!   [syslog](super::syslog) receivers.  Different subtypes of alert receivers are created and
 //!   modified by separate APIs for that particular type of receiver, as
 //!   different configuration options exist based on the receiver type.
 //!   However, some operations, such as listing receivers, viewing or
@@ -27,7 +27,9 @@
 //! + **Alerts** represent events in the system for which an alert
 //!   notifications are generated and sent to receivers.  The control plane
 //!   calls the [`Nexus::alert_publish`] method to record a new event
-//!   and publish it to receivers.
+//!   and publish it to receivers.  Sagas and background tasks which observe
+//!   the state transitions that alerts describe use [`publish_alert`]
+//!   instead, which never fails the operation that observed the transition.
 //!
 //!   Alerts are categorized into [alert classes], as described in RFD
 //!   538.  Receivers *subscribe* to these classes, indicating that they wish to
@@ -43,16 +45,17 @@
 //!   alert, and creates a *delivery record* in appropriate delivery table,
 //!   indicating that the alert should be sent to that receiver.
 //!
-//! + The `webhook_deliverator`[^1] task reads webhook delivery records and
-//!   sends HTTP requests to webhook receiver endpoint for each webhook
-//!   delivery that is currently in flight.  The deliverator is responsible
-//!   for recording the status of each *delivery attempt*.  Retries and retry
-//!   backoff are the responsibility of the deliverator.
+//! + The `webhook_deliverator`[^1] task reads delivery records and sends each
+//!   delivery that is currently in flight to its receiver, using the
+//!   [`DeliveryClient`] for that receiver's kind.  Despite its name, the
+//!   deliverator is responsible for all kinds of receiver.  It records the
+//!   status of each *delivery attempt*.  Retries and retry backoff are the
+//!   responsibility of the deliverator.
 //!
-//!   In the future, other delivery mechanisms may be implemented by their own
-//!   deliverator background tasks, similarly to webhooks.  Each delivery
-//!   mechanism will operate on its own delivery table, as different delivery
-//!   mechanisms may need to record different state associated with a delivery.
+//!   All delivery mechanisms share the `webhook_delivery` and
+//!   `webhook_delivery_attempt` tables.  Where a mechanism's outcomes don't
+//!   map directly onto those of webhooks, the mapping is documented alongside
+//!   the conversion to that mechanism's API delivery attempt type.
 //!
 //! ## Alert Subscriptions
 //!
@@ -94,10 +97,11 @@
 //! A *delivery* represents state associated to sending an alert to a
 //! particular receiver.
 //!
-//! At present, the only currently existing delivery type are *webhook
-//! deliveries*, which represent the process of sending HTTP request(s)
-//! representing an alert to a receiver.  Failed HTTP requests are retried up
-//! to two times, so a delivery may consist of up to three *delivery attempts*.
+//! For webhooks, a delivery represents the process of sending HTTP request(s)
+//! representing an alert to a receiver; SMTP and syslog deliveries are
+//! analogous, sending an email message or syslog message respectively.
+//! Failed attempts are retried up to two times, so a delivery may consist of
+//! up to three *delivery attempts*.
 //! Each time the `webhook_deliverator` background task is activated, it
 //! searches for deliveries which have not yet succeeded or permanently failed,
 //! which are not presently being delivered by another Nexus, and for which the
@@ -126,9 +130,9 @@
 //!
 //! Re-delivery of an event can be requested either via the alert resend API
 //! endpoint, or by a *liveness probe* succeeding.  Liveness probes are
-//! synthetic delivery requests sent to a webhook receiver to check whether it's
+//! synthetic delivery requests sent to a receiver to check whether it's
 //! actually able to receive an event.  They are triggered via the
-//! [`Nexus::webhook_receiver_probe`] API endpoint.  A probe may optionally
+//! [`Nexus::alert_receiver_probe`] API endpoint.  A probe may optionally
 //! request that any events for which all past deliveries have failed be resent
 //! if it succeeds.  Delivery records are also created to represent the outcome
 //! of a probe.
@@ -143,21 +147,34 @@
 //!     with more restrictive permissions are implemented, please rememvber to
 //!     delete this footnote.
 
+use super::smtp::SmtpClient;
+use super::syslog::SyslogClient;
+use super::webhook::ReceiverClient;
 use crate::Nexus;
+use crate::app::background::Activator;
 use chrono::DateTime;
 use chrono::Utc;
 use nexus_db_lookup::LookupPath;
 use nexus_db_lookup::lookup;
 use nexus_db_queries::authz;
 use nexus_db_queries::context::OpContext;
+use nexus_db_queries::db::DataStore;
 use nexus_db_queries::db::model::Alert;
 use nexus_db_queries::db::model::AlertClass;
 use nexus_db_queries::db::model::AlertDeliveryState;
 use nexus_db_queries::db::model::AlertDeliveryTrigger;
+use nexus_db_queries::db::model::AlertReceiver;
+use nexus_db_queries::db::model::AlertRxKind;
 use nexus_db_queries::db::model::WebhookDelivery;
+use nexus_db_queries::db::model::WebhookDeliveryAttempt;
+use nexus_db_queries::db::model::WebhookDeliveryAttemptResult;
 use nexus_db_queries::db::model::WebhookReceiverConfig;
+use nexus_db_queries::db::model::WebhookSecret;
+use nexus_types::alert as alert_types;
+use nexus_types::alert::AlertPayload;
 use nexus_types::external_api::alert;
 use nexus_types::identity::Asset;
+use nexus_types::identity::Resource;
 use omicron_common::api::external::CreateResult;
 use omicron_common::api::external::DataPageParams;
 use omicron_common::api::external::DeleteResult;
@@ -165,11 +182,14 @@ use omicron_common::api::external::Error;
 use omicron_common::api::external::ListResultVec;
 use omicron_common::api::external::LookupResult;
 use omicron_common::api::external::NameOrId;
+use omicron_common::api::external::ResourceType;
 use omicron_common::api::external::http_pagination::PaginatedBy;
 use omicron_uuid_kinds::AlertReceiverUuid;
 use omicron_uuid_kinds::AlertUuid;
 use omicron_uuid_kinds::GenericUuid;
+use omicron_uuid_kinds::OmicronZoneUuid;
 use omicron_uuid_kinds::WebhookDeliveryUuid;
+use std::sync::LazyLock;
 use uuid::Uuid;
 
 impl Nexus {
@@ -354,7 +374,8 @@ impl Nexus {
         filter: alert::AlertDeliveryStateFilter,
         pagparams: &DataPageParams<'_, (DateTime<Utc>, Uuid)>,
     ) -> ListResultVec<alert::AlertDelivery> {
-        let (authz_rx,) = rx.lookup_for(authz::Action::ListChildren).await?;
+        let (authz_rx, db_rx) =
+            rx.fetch_for(authz::Action::ListChildren).await?;
         let only_states = if filter.include_all() {
             Vec::new()
         } else {
@@ -383,12 +404,159 @@ impl Nexus {
             .await?
             .into_iter()
             .map(|(delivery, class, attempts)| {
-                delivery.to_api_delivery(class, &attempts)
+                delivery.to_api_delivery(class, db_rx.kind, &attempts)
             })
             .collect();
         Ok(deliveries)
     }
 
+    //
+    // Receiver liveness probe API methods
+    //
+
+    pub async fn alert_receiver_probe(
+        &self,
+        opctx: &OpContext,
+        rx: lookup::AlertReceiver<'_>,
+        params: alert::AlertReceiverProbe,
+    ) -> Result<alert::AlertProbeResult, Error> {
+        let (authz_rx, rx) = rx.fetch_for(authz::Action::ListChildren).await?;
+        let rx_id = authz_rx.id();
+        let datastore = self.datastore();
+        let secrets = if rx.kind == AlertRxKind::Webhook {
+            datastore.webhook_rx_secret_list(opctx, &authz_rx).await?
+        } else {
+            Vec::new()
+        };
+        let mut client = DeliveryClient::new(
+            &self.webhook_delivery_client,
+            secrets,
+            &rx,
+            self.id,
+        )?;
+        let mut delivery = WebhookDelivery::new_probe(&rx_id, &self.id);
+
+        const CLASS: AlertClass = <alert_types::Probe as AlertPayload>::CLASS;
+        const VERSION: u32 = <alert_types::Probe as AlertPayload>::VERSION;
+        static DATA: LazyLock<serde_json::Value> = LazyLock::new(|| {
+            serde_json::to_value(&alert_types::Probe {}).expect(
+                "a struct with no fields should always serialize properly",
+            )
+        });
+
+        let attempt = match client
+            .send_delivery_request(opctx, &delivery, CLASS, VERSION, &DATA)
+            .await
+        {
+            Ok(attempt) => attempt,
+            Err(e) => {
+                slog::error!(
+                    &opctx.log,
+                    "failed to probe alert receiver";
+                    "rx_id" => %authz_rx.id(),
+                    "rx_name" => %rx.name(),
+                    "delivery_id" => %delivery.id,
+                    "error" => %e,
+                );
+                return Err(Error::InternalError {
+                    internal_message: e.to_string(),
+                });
+            }
+        };
+
+        // Update the delivery state based on the result of the probe attempt.
+        // Otherwise, it will still appear "pending", which is obviously wrong.
+        delivery.state = if attempt.result.is_failed() {
+            AlertDeliveryState::Failed
+        } else {
+            AlertDeliveryState::Delivered
+        };
+
+        let resends_started = if params.resend
+            && attempt.result == WebhookDeliveryAttemptResult::Succeeded
+        {
+            slog::debug!(
+                &opctx.log,
+                "liveness probe succeeded, resending failed \
+                 deliveries...";
+                "rx_id" => %authz_rx.id(),
+                "rx_name" => %rx.name(),
+                "delivery_id" => %delivery.id,
+            );
+
+            let deliveries = datastore
+                .webhook_rx_list_resendable_events(opctx, &rx_id)
+                .await
+                .map_err(|e| {
+                    e.internal_context("error listing events to resend")
+                })?
+                .into_iter()
+                .map(|event| {
+                    slog::trace!(
+                        &opctx.log,
+                        "will resend alert after probe success";
+                        "rx_id" => ?authz_rx.id(),
+                        "rx_name" => %rx.name(),
+                        "delivery_id" => ?delivery.id,
+                        "alert_id" => ?event.id(),
+                        "alert_class" => %event.class,
+                    );
+                    WebhookDelivery::new(
+                        &event.id(),
+                        &rx_id,
+                        AlertDeliveryTrigger::Resend,
+                    )
+                })
+                .collect::<Vec<_>>();
+            let events_found = deliveries.len();
+
+            let started = datastore
+                .webhook_delivery_create_batch(&opctx, deliveries)
+                .await
+                .map_err(|e| {
+                    e.internal_context(
+                        "error creating deliveries to resend failed events",
+                    )
+                })?;
+
+            if started > 0 {
+                slog::info!(
+                    &opctx.log,
+                    "liveness probe succeeded, created {started} \
+                     re-deliveries";
+                    "rx_id" => %authz_rx.id(),
+                    "rx_name" => %rx.name(),
+                    "delivery_id" => %delivery.id,
+                    "events_found" => events_found,
+                    "deliveries_started" => started,
+                );
+                // If new deliveries were created, activate the
+                // deliverator background task to start actually delivering
+                // them.
+                self.background_tasks.task_webhook_deliverator.activate();
+            } else {
+                slog::debug!(
+                    &opctx.log,
+                    "liveness probe succeeded, but no failed events \
+                     were re-delivered";
+                    "rx_id" => %authz_rx.id(),
+                    "rx_name" => %rx.name(),
+                    "delivery_id" => %delivery.id,
+                    "events_found" => events_found,
+                );
+            }
+
+            Some(started)
+        } else {
+            None
+        };
+
+        Ok(alert::AlertProbeResult {
+            probe: delivery.to_api_delivery(CLASS, rx.kind, &[attempt]),
+            resends_started,
+        })
+    }
+
     //
     // Receiver subscription API methods
     //
@@ -400,6 +568,10 @@ impl Nexus {
         alert::AlertSubscriptionCreate { subscription}: alert::AlertSubscriptionCreate,
     ) -> CreateResult<alert::AlertSubscriptionCreated> {
         let (authz_rx,) = rx.lookup_for(authz::Action::Modify).await?;
+        opctx.set_audit_target(
+            ResourceType::AlertReceiver,
+            authz_rx.id().into_untyped_uuid(),
+        );
         let db_subscription = nexus_db_model::AlertSubscriptionKind::try_from(
             subscription.clone(),
         )?;
@@ -417,6 +589,10 @@ impl Nexus {
         subscription: alert::AlertSubscription,
     ) -> DeleteResult {
         let (authz_rx,) = rx.lookup_for(authz::Action::Modify).await?;
+        opctx.set_audit_target(
+            ResourceType::AlertReceiver,
+            authz_rx.id().into_untyped_uuid(),
+        );
         let db_subscription =
             nexus_db_model::AlertSubscriptionKind::try_from(subscription)?;
         let _ = self
@@ -483,6 +659,65 @@ impl Nexus {
     }
 }
 
+/// Publishes an alert on behalf of a saga or background task.
+///
+/// The alerts published here describe state transitions which have already
+/// been durably recorded, so a failure to publish the alert should not cause
+/// the operation that observed the transition to fail or unwind.  Instead,
+/// errors are logged and otherwise ignored.
+///
+/// If an alert with the provided `id` already exists, it is assumed to have
+/// been published by a previous execution of the same saga action, and is not
+/// published again.  Saga actions should therefore generate `id` in a prior
+/// saga node, rather than in the action that calls this function.
+///
+/// Returns whether the alert was newly published.
+pub(crate) async fn publish_alert<A: AlertPayload>(
+    opctx: &OpContext,
+    datastore: &DataStore,
+    alert_dispatcher: &Activator,
+    id: AlertUuid,
+    alert: &A,
+) -> bool {
+    let result = async {
+        let alert = Alert::new(id, alert)?;
+        datastore.alert_create(opctx, alert).await
+    }
+    .await;
+    match result {
+        Ok(_) => {
+            slog::info!(
+                &opctx.log,
+                "published alert";
+                "alert_id" => %id,
+                "alert_class" => %A::CLASS,
+            );
+            alert_dispatcher.activate();
+            true
+        }
+        Err(Error::Conflict { .. }) => {
+            slog::debug!(
+                &opctx.log,
+                "alert was already published";
+                "alert_id" => %id,
+                "alert_class" => %A::CLASS,
+            );
+            false
+        }
+        Err(error) => {
+            slog::warn!(
+                &opctx.log,
+                "failed to publish alert";
+                "alert_id" => %id,
+                "alert_class" => %A::CLASS,
+                "alert" => ?alert,
+                "error" => %error,
+            );
+            false
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -543,3 +778,84 @@ mod tests {
         assert_eq!(classes, Vec::<String>::new());
     }
 }
+
+/// A client for delivering alerts to a receiver, of whatever kind it is.
+///
+/// This is used both by the `webhook_deliverator` background task and by
+/// the liveness probe API.
+pub(crate) enum DeliveryClient<'a> {
+    Webhook(ReceiverClient<'a>),
+    Smtp(SmtpClient<'a>),
+    Syslog(SyslogClient<'a>),
+}
+
+impl<'a> DeliveryClient<'a> {
+    /// Constructs a client for delivering alerts to `rx`.
+    ///
+    /// `secrets` are used only by webhook receivers.
+    pub(crate) fn new(
+        webhook_client: &'a reqwest::Client,
+        secrets: impl IntoIterator<Item = WebhookSecret>,
+        rx: &'a AlertReceiver,
+        nexus_id: OmicronZoneUuid,
+    ) -> Result<Self, Error> {
+        match rx.kind {
+            AlertRxKind::Webhook => Ok(Self::Webhook(ReceiverClient::new(
+                webhook_client,
+                secrets,
+                rx,
+                nexus_id,
+            )?)),
+            AlertRxKind::Smtp => Ok(Self::Smtp(SmtpClient::new(rx, nexus_id)?)),
+            AlertRxKind::Syslog => {
+                Ok(Self::Syslog(SyslogClient::new(rx, nexus_id)?))
+            }
+        }
+    }
+
+    pub(crate) async fn send_delivery_request(
+        &mut self,
+        opctx: &OpContext,
+        delivery: &WebhookDelivery,
+        alert_class: impl Into<nexus_types::alert::AlertClass>,
+        alert_version: u32,
+        data: &serde_json::Value,
+    ) -> Result<WebhookDeliveryAttempt, anyhow::Error> {
+        let alert_class = alert_class.into();
+        match self {
+            Self::Webhook(client) => {
+                client
+                    .send_delivery_request(
+                        opctx,
+                        delivery,
+                        alert_class,
+                        alert_version,
+                        data,
+                    )
+                    .await
+            }
+            Self::Smtp(client) => {
+                client
+                    .send_delivery_request(
+                        opctx,
+                        delivery,
+                        alert_class,
+                        alert_version,
+                        data,
+                    )
+                    .await
+            }
+            Self::Syslog(client) => {
+                client
+                    .send_delivery_request(
+                        opctx,
+                        delivery,
+                        alert_class,
+                        alert_version,
+                        data,
+                    )
+                    .await
+            }
+        }
+    }
+}
@@ -5,9 +5,10 @@
 use chrono::{DateTime, Utc};
 use dropshot::{HttpError, HttpResponse, RequestContext};
 use nexus_db_model::{
-    AuditLogActor, AuditLogCompletion, AuditLogEntry, AuditLogEntryInit,
-    AuditLogEntryInitParams,
+    AuditLogActor, AuditLogCompletion, AuditLogCompletionUpdate, AuditLogEntry,
+    AuditLogEntryInit, AuditLogEntryInitParams,
 };
+use nexus_db_queries::context::AuditTarget;
 use nexus_db_queries::context::OpContext;
 use omicron_common::api::external::{
     CreateResult, DataPageParams, ListResultVec, UpdateResult,
@@ -18,6 +19,70 @@ use uuid::Uuid;
 
 use crate::context::ApiContext;
 
+/// Request bodies larger than this (after redaction and serialization) are not
+/// recorded in the audit log
+const MAX_REQUEST_BODY_BYTES: usize = 16 * 1024;
+
+/// Replaces sensitive values in an audit log request body
+const REDACTED: &str = "<redacted>";
+
+/// Prepares a request body to be recorded in the audit log
+///
+/// Each entry in `redact` is a path to a value in the body that should be
+/// replaced with a placeholder, in the form of a JSON pointer (e.g.,
+/// `/signing_keypair/private_key`) where a `*` component matches every element
+/// of an array or every value in an object. Paths that don't exist in a
+/// particular body are ignored. Returns `None` if the body is too large to
+/// record.
+pub(crate) fn audit_request_body<T: serde::Serialize>(
+    body: &T,
+    redact: &[&str],
+) -> Option<serde_json::Value> {
+    let mut body = serde_json::to_value(body).ok()?;
+    for path in redact {
+        let components: Vec<&str> = path.split('/').skip(1).collect();
+        redact_path(&mut body, &components);
+    }
+    let len = serde_json::to_vec(&body).map(|b| b.len()).unwrap_or(usize::MAX);
+    (len <= MAX_REQUEST_BODY_BYTES).then_some(body)
+}
+
+fn redact_path(value: &mut serde_json::Value, path: &[&str]) {
+    let Some((first, rest)) = path.split_first() else {
+        // Leave nulls alone so that the log still shows an optional value
+        // was not provided.
+        if !value.is_null() {
+            *value = serde_json::Value::String(String::from(REDACTED));
+        }
+        return;
+    };
+    match (value, *first) {
+        (serde_json::Value::Array(items), "*") => {
+            for item in items {
+                redact_path(item, rest);
+            }
+        }
+        (serde_json::Value::Object(fields), "*") => {
+            for field in fields.values_mut() {
+                redact_path(field, rest);
+            }
+        }
+        (serde_json::Value::Object(fields), key) => {
+            if let Some(field) = fields.get_mut(key) {
+                redact_path(field, rest);
+            }
+        }
+        (serde_json::Value::Array(items), index) => {
+            if let Some(item) =
+                index.parse::<usize>().ok().and_then(|i| items.get_mut(i))
+            {
+                redact_path(item, rest);
+            }
+        }
+        _ => (),
+    }
+}
+
 /// Truncate a str to at most `max` bytes, but make sure not to cut any chars
 /// in half.
 fn safe_truncate(s: &str, max: usize) -> String {
@@ -35,14 +100,18 @@ impl super::Nexus {
         pagparams: &DataPageParams<'_, (DateTime<Utc>, Uuid)>,
         start_time: DateTime<Utc>,
         end_time: Option<DateTime<Utc>>,
+        resource_id: Option<Uuid>,
     ) -> ListResultVec<AuditLogEntry> {
         self.db_datastore
-            .audit_log_list(opctx, pagparams, start_time, end_time)
+            .audit_log_list(opctx, pagparams, start_time, end_time, resource_id)
             .await
     }
 
     /// Use for authenticated operations because we want to pull the actor from
     /// the opctx.
+    ///
+    /// `request_body` should already have been redacted with
+    /// [`audit_request_body`].
     pub(crate) async fn audit_log_entry_init(
         &self,
         opctx: &OpContext,
@@ -51,6 +120,7 @@ impl super::Nexus {
         // that, we could instead give this function a million arguments and
         // extract the relevant fields at the call site.
         rqctx: &RequestContext<ApiContext>,
+        request_body: Option<serde_json::Value>,
     ) -> CreateResult<AuditLogEntryInit> {
         // for now, this conversion is pretty much 1-1
         let actor = match opctx.authn.actor() {
@@ -67,10 +137,18 @@ impl super::Nexus {
             Some(nexus_auth::authn::Actor::Scim { silo_id }) => {
                 AuditLogActor::Scim { silo_id: *silo_id }
             }
+            Some(nexus_auth::authn::Actor::ServiceAccount {
+                service_account_id,
+                silo_id,
+            }) => AuditLogActor::ServiceAccount {
+                service_account_id: *service_account_id,
+                silo_id: *silo_id,
+            },
             None => AuditLogActor::Unauthenticated,
         };
 
-        self.audit_log_entry_init_inner(&opctx, actor, rqctx).await
+        self.audit_log_entry_init_inner(&opctx, actor, rqctx, request_body)
+            .await
     }
 
     /// For authenticated operations, we can pull the actor out of the opctx
@@ -83,8 +161,10 @@ impl super::Nexus {
         opctx: &OpContext,
         rqctx: &RequestContext<ApiContext>,
     ) -> CreateResult<AuditLogEntryInit> {
+        // The bodies of unauthenticated requests consist of credentials, so
+        // they're never recorded.
         let actor = AuditLogActor::Unauthenticated;
-        self.audit_log_entry_init_inner(&opctx, actor, rqctx).await
+        self.audit_log_entry_init_inner(&opctx, actor, rqctx, None).await
     }
 
     // A note on the handling of request URI: request.request.uri() is a
@@ -102,14 +182,14 @@ impl super::Nexus {
     // to fall back to, though in practice I don't think it's possible for it to
     // come back as `None` because every operation we audit log has a path.
     //
-    // We should also consider redacting query strings or at least building in
-    // some tooling to help us make sure we're not logging anything sensitive.
+    // We should also consider redacting query strings, as we do request bodies.
 
     async fn audit_log_entry_init_inner(
         &self,
         opctx: &OpContext,
         actor: AuditLogActor,
         rqctx: &RequestContext<ApiContext>,
+        request_body: Option<serde_json::Value>,
     ) -> CreateResult<AuditLogEntryInit> {
         // User agent is truncated for the DB because it can theoretically be
         // very long, but almost never contains useful info past the beginning.
@@ -125,7 +205,8 @@ impl super::Nexus {
             // cause this method to be called with a built-in user
             AuditLogActor::UserBuiltin { .. }
             | AuditLogActor::SiloUser { .. }
-            | AuditLogActor::Scim { .. } => {
+            | AuditLogActor::Scim { .. }
+            | AuditLogActor::ServiceAccount { .. } => {
                 opctx.authn.scheme_used().map(Into::into)
             }
             // if we tried to pull it off the opctx this would be None anyway,
@@ -142,20 +223,22 @@ impl super::Nexus {
             actor,
             auth_method,
             credential_id: opctx.authn.credential_id(),
+            request_body,
         };
         self.db_datastore.audit_log_entry_init(opctx, entry_params.into()).await
     }
 
     /// Complete an existing audit log entry with result info like end time,
-    /// HTTP status code, error message, etc. Note we retry write failures
-    /// because we really want this to go through, but the caller should
-    /// ignore error results because we do not want such a failure to fail the
-    /// operation.
+    /// HTTP status code, error message, and the resource the operation acted
+    /// on (`target`), if known. Note we retry write failures because we
+    /// really want this to go through, but the caller should ignore error
+    /// results because we do not want such a failure to fail the operation.
     pub(crate) async fn audit_log_entry_complete<R: HttpResponse>(
         &self,
         opctx: &OpContext,
         entry: &AuditLogEntryInit,
         result: &Result<R, HttpError>,
+        target: Option<AuditTarget>,
     ) -> UpdateResult<()> {
         let completion = match result {
             Ok(response) => AuditLogCompletion::Success {
@@ -167,6 +250,15 @@ impl super::Nexus {
                 error_message: error.external_message.clone(),
             },
         };
+        let update = |completion: AuditLogCompletion| {
+            let update = AuditLogCompletionUpdate::from(completion);
+            match target {
+                Some(AuditTarget { resource_type, id }) => {
+                    update.with_resource(resource_type.to_string(), id)
+                }
+                None => update,
+            }
+        };
 
         // Should retry at roughly 250ms, 750ms, 1750ms (plus however long the
         // tries take). We really want this write to go through.
@@ -181,7 +273,7 @@ impl super::Nexus {
             backoff,
             || async {
                 self.db_datastore
-                    .audit_log_entry_complete(opctx, &entry, completion.clone().into())
+                    .audit_log_entry_complete(opctx, &entry, update(completion.clone()))
                     .await
                     .map_err(backoff::BackoffError::transient)
             },
@@ -206,3 +298,54 @@ impl super::Nexus {
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_audit_request_body_redaction() {
+        let body = json!({
+            "name": "my-silo",
+            "tls_certificates": [
+                { "cert": "cert-1", "key": "key-1" },
+                { "cert": "cert-2", "key": "key-2" },
+            ],
+            "signing_keypair": null,
+            "secrets": ["a", "b"],
+        });
+        let redacted = audit_request_body(
+            &body,
+            &[
+                "/tls_certificates/*/key",
+                "/signing_keypair/private_key",
+                "/secrets/1",
+                "/does/not/exist",
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            redacted,
+            json!({
+                "name": "my-silo",
+                "tls_certificates": [
+                    { "cert": "cert-1", "key": REDACTED },
+                    { "cert": "cert-2", "key": REDACTED },
+                ],
+                "signing_keypair": null,
+                "secrets": ["a", REDACTED],
+            })
+        );
+
+        // Nulls are left alone so it's clear no value was provided.
+        let redacted =
+            audit_request_body(&json!({ "user_data": null }), &["/user_data"])
+                .unwrap();
+        assert_eq!(redacted, json!({ "user_data": null }));
+
+        // Bodies too large to record are dropped entirely.
+        let big = "x".repeat(MAX_REQUEST_BODY_BYTES + 1);
+        assert_eq!(audit_request_body(&json!({ "data": big }), &[]), None);
+    }
+}
@@ -0,0 +1,138 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Audit log sinks
+//!
+//! Entries are delivered to sinks by the `audit_log_export` background task.
+
+use nexus_db_lookup::LookupPath;
+use nexus_db_lookup::lookup;
+use nexus_db_queries::authz;
+use nexus_db_queries::context::OpContext;
+use nexus_db_queries::db;
+use nexus_db_queries::db::model::Name;
+use nexus_types::external_api::audit;
+use omicron_common::api::external::CreateResult;
+use omicron_common::api::external::DeleteResult;
+use omicron_common::api::external::Error;
+use omicron_common::api::external::ListResultVec;
+use omicron_common::api::external::LookupResult;
+use omicron_common::api::external::NameOrId;
+use omicron_common::api::external::http_pagination::PaginatedBy;
+use omicron_uuid_kinds::AuditLogSinkUuid;
+use omicron_uuid_kinds::GenericUuid;
+use ref_cast::RefCast;
+
+/// Splits a syslog collector address of the form "host:port"
+pub(crate) fn parse_syslog_address(
+    address: &str,
+) -> Result<(&str, u16), String> {
+    let (host, port) = address
+        .rsplit_once(':')
+        .ok_or_else(|| format!("expected \"host:port\", found {address:?}"))?;
+    // Allow bracketed IPv6 literals, as in "[::1]:6514".
+    let host = host
+        .strip_prefix('[')
+        .and_then(|h| h.strip_suffix(']'))
+        .unwrap_or(host);
+    if host.is_empty() {
+        return Err(format!("missing host in {address:?}"));
+    }
+    let port = port
+        .parse::<u16>()
+        .map_err(|e| format!("invalid port in {address:?}: {e}"))?;
+    Ok((host, port))
+}
+
+/// Parses PEM-encoded CA certificates used to verify a syslog collector
+pub(crate) fn parse_root_certs(
+    pem: &str,
+) -> Result<rustls::RootCertStore, String> {
+    let mut roots = rustls::RootCertStore::empty();
+    let mut cursor = std::io::Cursor::new(pem.as_bytes());
+    for cert in rustls_pemfile::certs(&mut cursor) {
+        let cert = cert.map_err(|e| format!("parsing PEM: {e}"))?;
+        roots.add(cert).map_err(|e| format!("invalid CA certificate: {e}"))?;
+    }
+    if roots.is_empty() {
+        return Err(String::from("no certificates found in PEM"));
+    }
+    Ok(roots)
+}
+
+impl super::Nexus {
+    pub fn audit_log_sink_lookup<'a>(
+        &'a self,
+        opctx: &'a OpContext,
+        sink: &'a NameOrId,
+    ) -> LookupResult<lookup::AuditLogSink<'a>> {
+        match sink {
+            NameOrId::Id(id) => {
+                let sink = LookupPath::new(opctx, &self.db_datastore)
+                    .audit_log_sink_id(AuditLogSinkUuid::from_untyped_uuid(
+                        *id,
+                    ));
+                Ok(sink)
+            }
+            NameOrId::Name(name) => {
+                let sink = LookupPath::new(opctx, &self.db_datastore)
+                    .audit_log_sink_name(Name::ref_cast(name));
+                Ok(sink)
+            }
+        }
+    }
+
+    pub(crate) async fn audit_log_sink_list(
+        &self,
+        opctx: &OpContext,
+        pagparams: &PaginatedBy<'_>,
+    ) -> ListResultVec<db::model::AuditLogSink> {
+        self.db_datastore.audit_log_sink_list(opctx, pagparams).await
+    }
+
+    pub(crate) async fn audit_log_sink_create(
+        &self,
+        opctx: &OpContext,
+        params: audit::AuditLogSinkCreate,
+    ) -> CreateResult<db::model::AuditLogSink> {
+        match &params.config {
+            audit::AuditLogSinkConfig::Syslog { address, tls_root_cert } => {
+                parse_syslog_address(address)
+                    .map_err(|e| Error::invalid_value("address", e))?;
+                if let Some(pem) = tls_root_cert {
+                    parse_root_certs(pem).map_err(|e| {
+                        Error::invalid_value("tls_root_cert", e)
+                    })?;
+                }
+            }
+            audit::AuditLogSinkConfig::Http { endpoint, secret } => {
+                if !matches!(endpoint.scheme(), "http" | "https") {
+                    return Err(Error::invalid_value(
+                        "endpoint",
+                        "URL scheme must be \"http\" or \"https\"",
+                    ));
+                }
+                if secret.is_empty() {
+                    return Err(Error::invalid_value(
+                        "secret",
+                        "secret must not be empty",
+                    ));
+                }
+            }
+        }
+
+        let sink = db::model::AuditLogSink::new(params);
+        self.db_datastore.audit_log_sink_create(opctx, sink).await
+    }
+
+    pub(crate) async fn audit_log_sink_delete(
+        &self,
+        opctx: &OpContext,
+        sink_lookup: &lookup::AuditLogSink<'_>,
+    ) -> DeleteResult {
+        let (.., authz_sink) =
+            sink_lookup.lookup_for(authz::Action::Delete).await?;
+        self.db_datastore.audit_log_sink_delete(opctx, &authz_sink).await
+    }
+}
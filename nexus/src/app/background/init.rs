@@ -90,9 +90,11 @@
 use super::Driver;
 use super::driver::TaskDefinition;
 use super::tasks::abandoned_vmm_reaper;
+use super::tasks::acme_certificates;
 use super::tasks::alert_dispatcher::AlertDispatcher;
 use super::tasks::attached_subnets;
 use super::tasks::audit_log_cleanup;
+use super::tasks::audit_log_export;
 use super::tasks::audit_log_timeout_incomplete;
 use super::tasks::bfd;
 use super::tasks::blueprint_execution;
@@ -100,11 +102,13 @@ use super::tasks::blueprint_load;
 use super::tasks::blueprint_load::LoadedTargetBlueprint;
 use super::tasks::blueprint_planner;
 use super::tasks::blueprint_rendezvous;
+use super::tasks::certificate_expiry;
 use super::tasks::crdb_node_id_collector;
 use super::tasks::decommissioned_disk_cleaner;
 use super::tasks::dns_config;
 use super::tasks::dns_propagation;
 use super::tasks::dns_servers;
+use super::tasks::dnssec_keys;
 use super::tasks::ereport_ingester;
 use super::tasks::external_endpoints;
 use super::tasks::fm_analysis::{self, FmAnalysis};
@@ -117,6 +121,7 @@ use super::tasks::instance_updater;
 use super::tasks::instance_watcher;
 use super::tasks::inventory_collection;
 use super::tasks::inventory_load;
+use super::tasks::load_balancer_manager;
 use super::tasks::lookup_region_port;
 use super::tasks::metrics_producer_gc;
 use super::tasks::multicast::MulticastGroupReconciler;
@@ -135,6 +140,7 @@ use super::tasks::region_snapshot_replacement_step::*;
 use super::tasks::saga_recovery;
 use super::tasks::service_firewall_rules;
 use super::tasks::session_cleanup;
+use super::tasks::sled_evacuator;
 use super::tasks::support_bundle_collector;
 use super::tasks::sync_service_zone_nat::ServiceZoneNatTracker;
 use super::tasks::sync_switch_configuration::SwitchPortSettingsManager;
@@ -142,6 +148,7 @@ use super::tasks::trust_quorum;
 use super::tasks::tuf_artifact_replication;
 use super::tasks::tuf_repo_pruner;
 use super::tasks::v2p_mappings::V2PManager;
+use super::tasks::vpc_dns;
 use super::tasks::vpc_routes;
 use super::tasks::webhook_deliverator;
 use crate::Nexus;
@@ -162,6 +169,7 @@ use nexus_types::inventory::Collection;
 use omicron_uuid_kinds::OmicronZoneUuid;
 use oximeter::types::ProducerRegistry;
 use std::collections::BTreeMap;
+use std::net::IpAddr;
 use std::sync::Arc;
 use tokio::sync::mpsc;
 use tokio::sync::watch;
@@ -253,6 +261,13 @@ impl BackgroundTasksInitializer {
             task_service_firewall_propagation: Activator::new(),
             task_abandoned_vmm_reaper: Activator::new(),
             task_audit_log_cleanup: Activator::new(),
+            task_audit_log_export: Activator::new(),
+            task_sled_evacuator: Activator::new(),
+            task_load_balancer_manager: Activator::new(),
+            task_vpc_dns: Activator::new(),
+            task_acme_certificates: Activator::new(),
+            task_certificate_expiry: Activator::new(),
+            task_dnssec_keys: Activator::new(),
             task_audit_log_timeout_incomplete: Activator::new(),
             task_vpc_route_manager: Activator::new(),
             task_saga_recovery: Activator::new(),
@@ -374,6 +389,13 @@ impl BackgroundTasksInitializer {
             task_session_cleanup,
             task_audit_log_timeout_incomplete,
             task_audit_log_cleanup,
+            task_audit_log_export,
+            task_sled_evacuator,
+            task_load_balancer_manager,
+            task_vpc_dns,
+            task_acme_certificates,
+            task_certificate_expiry,
+            task_dnssec_keys,
             task_populate_switch_ports,
             // Add new background tasks here.  Be sure to use this binding in a
             // call to `Driver::register()` below.  That's what actually wires
@@ -394,7 +416,7 @@ impl BackgroundTasksInitializer {
             task_internal_dns_propagation,
         );
 
-        init_dns(
+        let external_dns_servers = init_dns(
             &mut driver,
             opctx,
             datastore.clone(),
@@ -588,6 +610,7 @@ impl BackgroundTasksInitializer {
             reconfigurator_config_watcher.clone(),
             inventory_load_watcher.clone(),
             rx_blueprint.clone(),
+            task_alert_dispatcher.clone(),
         );
         let rx_planner = blueprint_planner.watcher();
         driver.register(TaskDefinition {
@@ -647,6 +670,7 @@ impl BackgroundTasksInitializer {
                     resolver.clone(),
                     config.support_bundle_collector.disable,
                     nexus_id,
+                    task_alert_dispatcher.clone(),
                 ),
             ),
             opctx: opctx.child(BTreeMap::new()),
@@ -836,6 +860,7 @@ impl BackgroundTasksInitializer {
                     sagas.clone(),
                     config.instance_reincarnation.disable,
                     task_multicast_reconciler.clone(),
+                    task_alert_dispatcher.clone(),
                 );
             driver.register(TaskDefinition {
                 name: "instance_reincarnation",
@@ -849,6 +874,117 @@ impl BackgroundTasksInitializer {
             });
         }
 
+        // Background task: migrate instances off of sleds being evacuated.
+        driver.register(TaskDefinition {
+            name: "sled_evacuator",
+            description: "migrates running instances off of sleds that are \
+                being evacuated",
+            period: config.sled_evacuator.period_secs,
+            task_impl: Box::new(sled_evacuator::SledEvacuator::new(
+                datastore.clone(),
+                sagas.clone(),
+                config.sled_evacuator.max_concurrent_migrations,
+                task_vpc_route_manager.clone(),
+                task_multicast_reconciler.clone(),
+            )),
+            opctx: opctx.child(BTreeMap::new()),
+            watchers: vec![],
+            activator: task_sled_evacuator,
+        });
+
+        // Background task: health check load balancer backends and keep the
+        // data plane pointed at the healthy ones.
+        driver.register(TaskDefinition {
+            name: "load_balancer_manager",
+            description: "health checks load balancer backends and programs \
+                NAT entries for load balancer frontends",
+            period: config.load_balancer_manager.period_secs,
+            task_impl: Box::new(
+                load_balancer_manager::LoadBalancerManager::new(
+                    datastore.clone(),
+                    resolver.clone(),
+                ),
+            ),
+            opctx: opctx.child(BTreeMap::new()),
+            watchers: vec![],
+            activator: task_load_balancer_manager,
+        });
+
+        // Background task: compute VPCs' private DNS zones and propagate them
+        // to the external DNS servers, which serve them to instances.
+        driver.register(TaskDefinition {
+            name: "vpc_dns",
+            description: "computes VPCs' private DNS zones from instances' \
+                network interfaces and propagates them to the external DNS \
+                servers",
+            period: config.vpc_dns.period_secs,
+            task_impl: Box::new(vpc_dns::VpcDnsManager::new(
+                datastore.clone(),
+                external_dns_servers.clone(),
+                args.upstream_dns_servers.clone(),
+            )),
+            opctx: opctx.child(BTreeMap::new()),
+            watchers: vec![Box::new(external_dns_servers.clone())],
+            activator: task_vpc_dns,
+        });
+
+        // Background task: roll over the external DNS zones' DNSSEC keys and
+        // propagate them to the external DNS servers, which sign the zones.
+        driver.register(TaskDefinition {
+            name: "dnssec_keys",
+            description: "rolls over the external DNS zones' DNSSEC keys and \
+                propagates them to the external DNS servers",
+            period: config.dnssec_keys.period_secs,
+            task_impl: Box::new(dnssec_keys::DnssecKeyManager::new(
+                datastore.clone(),
+                config.dnssec_keys.clone(),
+                external_dns_servers.clone(),
+            )),
+            opctx: opctx.child(BTreeMap::new()),
+            watchers: vec![Box::new(external_dns_servers)],
+            activator: task_dnssec_keys,
+        });
+
+        // Background task: obtain and renew Silos' TLS certificates from an
+        // ACME server, if one is configured.
+        driver.register(TaskDefinition {
+            name: "acme_certificates",
+            description: "obtains and renews silos' TLS certificates from an \
+                ACME server, if one is configured",
+            period: config.acme_certificates.period_secs,
+            task_impl: Box::new(acme_certificates::AcmeCertificates::new(
+                datastore.clone(),
+                config.acme_certificates.clone(),
+                args.acme_client,
+                task_external_endpoints.clone(),
+                task_external_dns_config.clone(),
+                nexus_id.to_string(),
+            )),
+            opctx: opctx.child(BTreeMap::new()),
+            watchers: vec![],
+            activator: task_acme_certificates,
+        });
+
+        // Background task: report when stored certificates (and those webhook
+        // receivers present) expire, and alert as they near expiry.
+        driver.register(TaskDefinition {
+            name: "certificate_expiry",
+            description: "reports when certificates stored by the control \
+                plane or presented by webhook receivers expire, publishing \
+                metrics and alerts",
+            period: config.certificate_expiry.period_secs,
+            task_impl: Box::new(certificate_expiry::CertificateExpiry::new(
+                datastore.clone(),
+                config.certificate_expiry.alert_before_days.clone(),
+                task_alert_dispatcher.clone(),
+                producer_registry,
+                nexus_id,
+            )),
+            opctx: opctx.child(BTreeMap::new()),
+            watchers: vec![],
+            activator: task_certificate_expiry,
+        });
+
         // Background task: service firewall rule propagation
         driver.register(TaskDefinition {
             name: "service_firewall_rule_propagation",
@@ -1083,7 +1219,7 @@ impl BackgroundTasksInitializer {
                         datastore.clone(),
                         cfg,
                         nexus_id,
-                        args.webhook_delivery_client,
+                        args.webhook_delivery_client.clone(),
                     ),
                 ),
                 opctx: opctx.child(BTreeMap::new()),
@@ -1284,6 +1420,22 @@ impl BackgroundTasksInitializer {
             activator: task_audit_log_cleanup,
         });
 
+        driver.register(TaskDefinition {
+            name: "audit_log_export",
+            description: "delivers completed audit log entries to \
+                 configured audit log sinks",
+            period: config.audit_log_export.period_secs,
+            task_impl: Box::new(audit_log_export::AuditLogExporter::new(
+                datastore.clone(),
+                args.webhook_delivery_client,
+                config.audit_log_export.settle_time_secs,
+                config.audit_log_export.max_entries_per_batch,
+            )),
+            opctx: opctx.child(BTreeMap::new()),
+            watchers: vec![],
+            activator: task_audit_log_export,
+        });
+
         driver.register(TaskDefinition {
             name: "populate_switch_ports",
             description: "one-time population of the `switch_port` table \
@@ -1336,6 +1488,9 @@ pub struct BackgroundTasksData {
     /// This is shared with the external API as it's also used when sending
     /// webhook liveness probe requests from the API.
     pub webhook_delivery_client: reqwest::Client,
+    /// `reqwest::Client` for requests to the ACME server from which Silos'
+    /// TLS certificates are obtained
+    pub acme_client: reqwest::Client,
     /// Channel for configuring pending MGS updates
     pub mgs_updates_tx: watch::Sender<PendingMgsUpdates>,
     /// handle for controlling Nexus quiesce
@@ -1345,10 +1500,15 @@ pub struct BackgroundTasksData {
     /// Console session absolute timeout, from
     /// `pkg.console.session_absolute_timeout_minutes`.
     pub console_session_absolute_timeout: chrono::TimeDelta,
+    /// DNS servers outside the rack, from `deployment.external_dns_servers`,
+    /// to which the VPC DNS zones' servers forward instances' other queries
+    pub upstream_dns_servers: Vec<IpAddr>,
 }
 
 /// Starts the three DNS-propagation-related background tasks for either
 /// internal or external DNS (depending on the arguments)
+///
+/// Returns a watcher for the list of DNS servers in the group.
 #[allow(clippy::too_many_arguments)]
 fn init_dns(
     driver: &mut Driver,
@@ -1360,7 +1520,7 @@ fn init_dns(
     task_config: &Activator,
     task_servers: &Activator,
     task_propagation: &Activator,
-) {
+) -> watch::Receiver<Option<dns_servers::DnsServersList>> {
     let dns_group_name = dns_group.to_string();
     let metadata = BTreeMap::from([("dns_group".to_string(), dns_group_name)]);
 
@@ -1418,10 +1578,12 @@ fn init_dns(
         opctx: opctx.child(metadata),
         watchers: vec![
             Box::new(dns_config_watcher),
-            Box::new(dns_servers_watcher),
+            Box::new(dns_servers_watcher.clone()),
         ],
         activator: task_propagation,
     });
+
+    dns_servers_watcher
 }
 
 #[cfg(test)]
@@ -1584,6 +1746,10 @@ pub mod test {
             store,
             &dns_server::dns_server::Config {
                 bind_address: "[::1]:0".parse().unwrap(),
+                transfer: Default::default(),
+                rate_limit: Default::default(),
+                query_log: Default::default(),
+                tcp: Default::default(),
             },
             &dropshot::ConfigDropshot {
                 bind_address: "[::1]:0".parse().unwrap(),
@@ -0,0 +1,354 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Background task for obtaining Silos' TLS certificates from an ACME server
+//!
+//! Each activation checks every Silo for a certificate that Nexus obtained
+//! earlier and that isn't due for renewal.  For each Silo without one, it
+//! obtains a certificate covering the Silo's DNS names in every external DNS
+//! zone, stores it, and deletes any certificates it supersedes.  See
+//! [`crate::app::acme`] for how certificates are obtained.
+//!
+//! Every Nexus runs this task.  If several find that the same Silo needs a
+//! certificate at once, each obtains one; the newest is kept.
+
+use crate::app::acme::AcmeClient;
+use crate::app::acme::ChallengeContext;
+use crate::app::background::Activator;
+use crate::app::background::BackgroundTask;
+use anyhow::Context;
+use anyhow::anyhow;
+use chrono::Utc;
+use futures::future::BoxFuture;
+use nexus_config::AcmeCertificatesConfig;
+use nexus_db_model::Certificate;
+use nexus_db_model::DnsGroup;
+use nexus_db_model::ServiceKind;
+use nexus_db_queries::context::OpContext;
+use nexus_db_queries::db::DataStore;
+use nexus_db_queries::db::datastore::Discoverability;
+use nexus_db_queries::db::model::AcmeAccount;
+use nexus_types::external_api::certificate::CertificateCreate;
+use nexus_types::external_api::certificate::ServiceUsingCertificate;
+use nexus_types::identity::Resource;
+use nexus_types::internal_api::background::AcmeCertificateIssued;
+use nexus_types::internal_api::background::AcmeCertificatesStatus;
+use nexus_types::silo::DEFAULT_SILO_ID;
+use nexus_types::silo::silo_dns_name;
+use omicron_common::api::external::IdentityMetadataCreateParams;
+use omicron_common::api::external::Name;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct AcmeCertificates {
+    datastore: Arc<DataStore>,
+    config: AcmeCertificatesConfig,
+    http: reqwest::Client,
+    external_endpoints: Activator,
+    external_dns_config: Activator,
+    /// identifies this Nexus in the history of external DNS changes
+    creator: String,
+    /// client for the configured ACME server, once we've set up an account
+    client: Option<AcmeClient>,
+}
+
+impl AcmeCertificates {
+    pub fn new(
+        datastore: Arc<DataStore>,
+        config: AcmeCertificatesConfig,
+        http: reqwest::Client,
+        external_endpoints: Activator,
+        external_dns_config: Activator,
+        creator: String,
+    ) -> Self {
+        Self {
+            datastore,
+            config,
+            http,
+            external_endpoints,
+            external_dns_config,
+            creator,
+            client: None,
+        }
+    }
+
+    async fn update_certificates(
+        &mut self,
+        opctx: &OpContext,
+        directory_url: &str,
+        status: &mut AcmeCertificatesStatus,
+    ) -> anyhow::Result<()> {
+        let silos = self
+            .datastore
+            .silo_list_all_batched(opctx, Discoverability::All)
+            .await
+            .context("listing silos")?;
+        let zones: Vec<String> = self
+            .datastore
+            .dns_zones_list_all(opctx, DnsGroup::External)
+            .await
+            .context("listing external DNS zones")?
+            .into_iter()
+            .map(|zone| zone.zone_name)
+            .collect();
+        let certificates = self
+            .datastore
+            .acme_certificate_list_all_batched(opctx)
+            .await
+            .context("listing certificates")?;
+
+        // For each Silo, find how many days remain on the longest-lived
+        // certificate we've obtained for it.
+        let mut days_remaining: BTreeMap<Uuid, i64> = BTreeMap::new();
+        for certificate in &certificates {
+            let days = match certificate_days_remaining(certificate) {
+                Ok(days) => days,
+                Err(error) => {
+                    warn!(
+                        opctx.log,
+                        "failed to parse ACME-issued certificate";
+                        "certificate_id" => %certificate.id(),
+                        "error" => format!("{error:#}"),
+                    );
+                    continue;
+                }
+            };
+            days_remaining
+                .entry(certificate.silo_id)
+                .and_modify(|d| *d = (*d).max(days))
+                .or_insert(days);
+        }
+
+        let renew_before_days = i64::from(self.config.renew_before_days);
+        for silo in silos {
+            // Nobody logs into the built-in default Silo.
+            if silo.id() == DEFAULT_SILO_ID {
+                continue;
+            }
+            if days_remaining
+                .get(&silo.id())
+                .is_some_and(|days| *days >= renew_before_days)
+            {
+                status.silos_current += 1;
+                continue;
+            }
+
+            let silo_name = silo.name().to_string();
+            let dns_names: Vec<String> = zones
+                .iter()
+                .map(|zone| format!("{}.{}", silo_dns_name(silo.name()), zone))
+                .collect();
+            match self
+                .issue_certificate(
+                    opctx,
+                    directory_url,
+                    &zones,
+                    silo.id(),
+                    &dns_names,
+                )
+                .await
+            {
+                Ok((certificate, superseded)) => {
+                    info!(
+                        opctx.log,
+                        "obtained certificate from ACME server";
+                        "silo" => &silo_name,
+                        "certificate_id" => %certificate.id(),
+                    );
+                    status.superseded_deleted += superseded;
+                    status.issued.push(AcmeCertificateIssued {
+                        silo_name,
+                        certificate_id: certificate.id(),
+                        certificate_name: certificate.name().to_string(),
+                    });
+                }
+                Err(error) => {
+                    let error = format!("{error:#}");
+                    warn!(
+                        opctx.log,
+                        "failed to obtain certificate from ACME server";
+                        "silo" => &silo_name,
+                        "error" => &error,
+                    );
+                    status.silo_errors.insert(silo_name, error);
+                }
+            }
+        }
+
+        if !status.issued.is_empty() {
+            self.external_endpoints.activate();
+        }
+        Ok(())
+    }
+
+    /// Obtains and stores a certificate for a Silo's `dns_names`, returning it
+    /// and the number of older certificates it replaced
+    async fn issue_certificate(
+        &mut self,
+        opctx: &OpContext,
+        directory_url: &str,
+        dns_zones: &[String],
+        silo_id: Uuid,
+        dns_names: &[String],
+    ) -> anyhow::Result<(Certificate, usize)> {
+        let datastore = self.datastore.clone();
+        let kind = self.config.challenge;
+        let dns_creator = self.creator.clone();
+        let dns_config = self.external_dns_config.clone();
+        let challenges = ChallengeContext {
+            opctx,
+            datastore: &datastore,
+            kind,
+            dns_zones,
+            dns_creator: &dns_creator,
+            dns_config: &dns_config,
+        };
+        let client = self.client(opctx, directory_url).await?;
+        let obtained =
+            client.obtain_certificate(&challenges, dns_names).await?;
+
+        let name = Utc::now()
+            .format("acme-%Y%m%d-%H%M%S")
+            .to_string()
+            .parse::<Name>()
+            .map_err(|e| anyhow!("naming certificate: {e}"))?;
+        let certificate = Certificate::new(
+            silo_id,
+            Uuid::new_v4(),
+            ServiceKind::Nexus,
+            CertificateCreate {
+                identity: IdentityMetadataCreateParams {
+                    name,
+                    description: format!("obtained from {directory_url}"),
+                },
+                cert: obtained.cert,
+                key: obtained.key,
+                service: ServiceUsingCertificate::ExternalApi,
+            },
+            dns_names,
+        )
+        .context("validating certificate")?;
+        let certificate = self
+            .datastore
+            .acme_certificate_create(opctx, certificate)
+            .await
+            .context("storing certificate")?;
+        let superseded = self
+            .datastore
+            .acme_certificate_delete_superseded(opctx, silo_id, &certificate)
+            .await
+            .context("deleting superseded certificates")?;
+        Ok((certificate, superseded))
+    }
+
+    /// Returns a client for the ACME server, using the account stored in the
+    /// database (registering one first if there isn't one)
+    async fn client(
+        &mut self,
+        opctx: &OpContext,
+        directory_url: &str,
+    ) -> anyhow::Result<&mut AcmeClient> {
+        if self.client.is_none() {
+            self.client = Some(self.connect(opctx, directory_url).await?);
+        }
+        Ok(self.client.as_mut().unwrap())
+    }
+
+    async fn connect(
+        &self,
+        opctx: &OpContext,
+        directory_url: &str,
+    ) -> anyhow::Result<AcmeClient> {
+        let log =
+            opctx.log.new(o!("acme_directory" => directory_url.to_string()));
+        let account = self
+            .datastore
+            .acme_account_fetch(opctx, directory_url)
+            .await
+            .context("fetching ACME account")?;
+        if let Some(account) = account {
+            return AcmeClient::existing(
+                log,
+                self.http.clone(),
+                directory_url,
+                &account.key_pem,
+                account.account_url,
+            )
+            .await;
+        }
+
+        let client = AcmeClient::register(
+            log.clone(),
+            self.http.clone(),
+            directory_url,
+            &self.config.contact,
+        )
+        .await?;
+        let account = self
+            .datastore
+            .acme_account_create(
+                opctx,
+                AcmeAccount::new(
+                    directory_url.to_string(),
+                    client.account_url().to_string(),
+                    client.key_pem()?,
+                ),
+            )
+            .await
+            .context("storing ACME account")?;
+
+        // If another Nexus registered an account first, use that one instead.
+        if account.account_url == client.account_url() {
+            Ok(client)
+        } else {
+            AcmeClient::existing(
+                log,
+                self.http.clone(),
+                directory_url,
+                &account.key_pem,
+                account.account_url,
+            )
+            .await
+        }
+    }
+}
+
+impl BackgroundTask for AcmeCertificates {
+    fn activate<'a>(
+        &'a mut self,
+        opctx: &'a OpContext,
+    ) -> BoxFuture<'a, serde_json::Value> {
+        Box::pin(async move {
+            let mut status = AcmeCertificatesStatus::default();
+            let Some(directory_url) = self.config.directory_url.clone() else {
+                return serde_json::json!(status);
+            };
+            status.enabled = true;
+
+            if let Err(error) = self
+                .update_certificates(opctx, &directory_url, &mut status)
+                .await
+            {
+                let error = format!("{error:#}");
+                error!(
+                    opctx.log,
+                    "failed to update ACME certificates";
+                    "error" => &error,
+                );
+                status.error = Some(error);
+            }
+            serde_json::json!(status)
+        })
+    }
+}
+
+/// Returns the number of whole days until the first certificate in
+/// `certificate`'s chain expires
+fn certificate_days_remaining(
+    certificate: &Certificate,
+) -> anyhow::Result<i64> {
+    let expiry = omicron_certificates::first_expiry(&certificate.cert)?;
+    Ok((expiry - Utc::now().timestamp()).div_euclid(86_400))
+}
@@ -400,6 +400,10 @@ mod test {
                 endpoint: "http://webhooks.elizas.website".parse().unwrap(),
                 secret_gen: db::model::Generation::new(),
                 subscription_gen: db::model::Generation::new(),
+                kind: db::model::AlertRxKind::Webhook,
+                smtp_from: None,
+                smtp_to: None,
+                syslog_facility: None,
             })
             .execute_async(&*conn)
             .await
@@ -164,6 +164,7 @@ mod tests {
             actor: AuditLogActor::Unauthenticated,
             auth_method: None,
             credential_id: None,
+            request_body: None,
         }
     }
 
@@ -0,0 +1,438 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Background task that delivers completed audit log entries to audit log
+//! sinks
+//!
+//! Each sink has a durable cursor: the `(time_completed, id)` of the last
+//! entry delivered to it.  On each activation, for every sink that isn't
+//! backing off after a failure, we read the entries after the cursor in the
+//! same order that `audit_log_list` returns them, deliver them in batches,
+//! and advance the cursor after each successful batch.  A failed batch is
+//! retried (from the same cursor) after an exponential backoff.
+//!
+//! Delivery is at-least-once: if we deliver a batch but fail to record that,
+//! or if two Nexus instances deliver the same batch concurrently, the sink
+//! will see those entries twice.  Consumers can deduplicate by entry ID.
+
+use crate::app::audit_log_sink::parse_root_certs;
+use crate::app::audit_log_sink::parse_syslog_address;
+use crate::app::background::BackgroundTask;
+use anyhow::Context;
+use anyhow::anyhow;
+use chrono::DateTime;
+use chrono::TimeDelta;
+use chrono::Utc;
+use futures::future::BoxFuture;
+use hmac::{Hmac, Mac};
+use http::HeaderName;
+use nexus_db_queries::context::OpContext;
+use nexus_db_queries::db::DataStore;
+use nexus_db_queries::db::model::AuditLogSink;
+use nexus_db_queries::db::model::AuditLogSinkKind;
+use nexus_types::external_api::audit;
+use nexus_types::identity::Resource;
+use nexus_types::internal_api::background::AuditLogExportStatus;
+use nexus_types::internal_api::background::AuditLogSinkExportStatus;
+use omicron_common::api::external::DataPageParams;
+use omicron_uuid_kinds::GenericUuid;
+use serde_json::json;
+use sha2::Sha256;
+use slog_error_chain::InlineErrorChain;
+use std::num::NonZeroU32;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::AsyncWrite;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpStream;
+use uuid::Uuid;
+
+/// Backoff after the first failed delivery to a sink
+const MIN_BACKOFF: TimeDelta = TimeDelta::seconds(10);
+/// Longest backoff between attempts to deliver to a failing sink
+const MAX_BACKOFF: TimeDelta = TimeDelta::hours(1);
+/// How long we'll wait to connect to a syslog collector and send a batch
+const SYSLOG_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// RFC 5424 facility 13, "log audit"
+const SYSLOG_FACILITY_LOG_AUDIT: u8 = 13;
+const SYSLOG_SEVERITY_WARNING: u8 = 4;
+const SYSLOG_SEVERITY_INFORMATIONAL: u8 = 6;
+
+pub struct AuditLogExporter {
+    datastore: Arc<DataStore>,
+    http_client: reqwest::Client,
+    settle_time: TimeDelta,
+    max_entries_per_batch: NonZeroU32,
+}
+
+impl AuditLogExporter {
+    pub fn new(
+        datastore: Arc<DataStore>,
+        http_client: reqwest::Client,
+        settle_time: Duration,
+        max_entries_per_batch: NonZeroU32,
+    ) -> Self {
+        let Ok(settle_time) = TimeDelta::from_std(settle_time) else {
+            panic!(
+                "invalid settle_time {settle_time:?} \
+                 (must be representable as a TimeDelta)"
+            );
+        };
+        Self { datastore, http_client, settle_time, max_entries_per_batch }
+    }
+
+    pub(crate) async fn actually_activate(
+        &mut self,
+        opctx: &OpContext,
+    ) -> AuditLogExportStatus {
+        let now = Utc::now();
+        let cutoff = now - self.settle_time;
+        let mut status =
+            AuditLogExportStatus { cutoff, sinks: Vec::new(), error: None };
+
+        let sinks = match self
+            .datastore
+            .audit_log_sink_list_due(opctx, now)
+            .await
+        {
+            Ok(sinks) => sinks,
+            Err(err) => {
+                slog::error!(&opctx.log, "failed to list audit log sinks"; &err);
+                status.error = Some(InlineErrorChain::new(&err).to_string());
+                return status;
+            }
+        };
+
+        for sink in sinks {
+            let sink_status = self.export_to_sink(opctx, sink, cutoff).await;
+            status.sinks.push(sink_status);
+        }
+        status
+    }
+
+    /// Delivers all settled entries after `sink`'s cursor, stopping at the
+    /// first failure
+    async fn export_to_sink(
+        &self,
+        opctx: &OpContext,
+        mut sink: AuditLogSink,
+        cutoff: DateTime<Utc>,
+    ) -> AuditLogSinkExportStatus {
+        let log = opctx.log.new(slog::o!(
+            "sink_id" => sink.id().to_string(),
+            "sink_name" => sink.name().to_string(),
+        ));
+        let mut status = AuditLogSinkExportStatus {
+            sink_id: sink.id(),
+            sink_name: sink.name().to_string(),
+            entries_delivered: 0,
+            error: None,
+        };
+
+        loop {
+            let result = self.export_batch(opctx, &sink, cutoff).await;
+            let (delivered, cursor) = match result {
+                Ok(Some(batch)) => batch,
+                // Caught up.
+                Ok(None) => break,
+                Err(err) => {
+                    let error = format!("{err:#}");
+                    slog::warn!(
+                        &log,
+                        "failed to deliver audit log entries";
+                        "error" => &error,
+                    );
+                    let next_attempt =
+                        Utc::now() + backoff(sink.consecutive_failures);
+                    if let Err(e) = self
+                        .datastore
+                        .audit_log_sink_delivery_failed(
+                            opctx,
+                            &sink,
+                            error.clone(),
+                            next_attempt,
+                        )
+                        .await
+                    {
+                        slog::error!(
+                            &log,
+                            "failed to record audit log delivery failure";
+                            &e,
+                        );
+                    }
+                    status.error = Some(error);
+                    break;
+                }
+            };
+
+            match self
+                .datastore
+                .audit_log_sink_advance(opctx, &sink, cursor)
+                .await
+            {
+                Ok(true) => {}
+                Ok(false) => {
+                    // Another Nexus advanced the cursor (or the sink was
+                    // deleted) while we were delivering. Leave the sink to it.
+                    slog::info!(
+                        &log,
+                        "audit log sink cursor moved concurrently; stopping"
+                    );
+                    status.entries_delivered += delivered;
+                    break;
+                }
+                Err(e) => {
+                    // The entries were delivered, but we'll deliver them again
+                    // next time. That's allowed, but not great.
+                    slog::error!(&log, "failed to advance audit log sink"; &e);
+                    status.error = Some(InlineErrorChain::new(&e).to_string());
+                    break;
+                }
+            }
+
+            status.entries_delivered += delivered;
+            (sink.cursor_time_completed, sink.cursor_id) = cursor;
+            sink.consecutive_failures = 0;
+            if delivered < self.max_entries_per_batch.get() as usize {
+                break;
+            }
+        }
+
+        if status.entries_delivered > 0 {
+            slog::info!(
+                &log,
+                "delivered audit log entries";
+                "count" => status.entries_delivered,
+            );
+        }
+        status
+    }
+
+    /// Delivers the next batch of entries after `sink`'s cursor, returning
+    /// how many were delivered and the position of the last one, or `None` if
+    /// there are no entries to deliver
+    async fn export_batch(
+        &self,
+        opctx: &OpContext,
+        sink: &AuditLogSink,
+        cutoff: DateTime<Utc>,
+    ) -> Result<Option<(usize, (DateTime<Utc>, Uuid))>, anyhow::Error> {
+        if cutoff <= sink.cursor_time_completed {
+            return Ok(None);
+        }
+        let marker = (sink.cursor_time_completed, sink.cursor_id);
+        let pagparams = DataPageParams {
+            marker: Some(&marker),
+            direction: dropshot::PaginationOrder::Ascending,
+            limit: self.max_entries_per_batch,
+        };
+        let entries = self
+            .datastore
+            .audit_log_list(
+                opctx,
+                &pagparams,
+                sink.cursor_time_completed,
+                Some(cutoff),
+                None,
+            )
+            .await
+            .context("listing audit log entries")?
+            .into_iter()
+            .map(audit::AuditLogEntry::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .context("converting audit log entries")?;
+        let Some(last) = entries.last() else {
+            return Ok(None);
+        };
+        let cursor = (last.time_completed, last.id);
+        let count = entries.len();
+
+        match sink.kind {
+            AuditLogSinkKind::Http => {
+                let batch = audit::AuditLogSinkBatch {
+                    sink_id: sink.id().into_untyped_uuid(),
+                    entries,
+                };
+                self.deliver_http(sink, &batch).await?
+            }
+            AuditLogSinkKind::Syslog => deliver_syslog(sink, &entries).await?,
+        }
+        Ok(Some((count, cursor)))
+    }
+
+    async fn deliver_http(
+        &self,
+        sink: &AuditLogSink,
+        batch: &audit::AuditLogSinkBatch,
+    ) -> Result<(), anyhow::Error> {
+        const HDR_SINK_ID: HeaderName =
+            HeaderName::from_static("x-oxide-audit-log-sink-id");
+        const HDR_SIG: HeaderName =
+            HeaderName::from_static("x-oxide-signature");
+        const HDR_TIMESTAMP: HeaderName =
+            HeaderName::from_static("x-oxide-timestamp");
+
+        let secret = sink
+            .secret
+            .as_ref()
+            .ok_or_else(|| anyhow!("HTTP audit log sink has no secret"))?;
+        // As with webhooks, we serialize the body ourselves so that we can
+        // sign it.
+        let body = serde_json::to_vec(batch)
+            .context("serializing audit log entries")?;
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+            .expect("HMAC key can be any size; this should never fail");
+        mac.update(&body);
+        let sig = hex::encode(mac.finalize().into_bytes());
+
+        self.http_client
+            .post(&sink.endpoint)
+            .header(HDR_SINK_ID, sink.id().to_string())
+            .header(HDR_TIMESTAMP, Utc::now().to_rfc3339())
+            .header(HDR_SIG, format!("a=sha256&id={}&s={sig}", sink.id()))
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .send()
+            .await
+            .context("sending request")?
+            .error_for_status()
+            .context("sink returned an error")?;
+        Ok(())
+    }
+}
+
+/// How long to wait before retrying a sink that has already failed
+/// `consecutive_failures` times before the current failure
+fn backoff(consecutive_failures: i32) -> TimeDelta {
+    let exp = u32::try_from(consecutive_failures).unwrap_or(0).min(16);
+    MIN_BACKOFF
+        .checked_mul(1 << exp)
+        .map_or(MAX_BACKOFF, |backoff| backoff.min(MAX_BACKOFF))
+}
+
+/// Formats an entry as an RFC 5424 syslog message whose body is the entry's
+/// JSON representation, framed with octet counting as described in RFC 6587
+fn syslog_frame(
+    entry: &audit::AuditLogEntry,
+) -> Result<Vec<u8>, serde_json::Error> {
+    let severity = match entry.result {
+        audit::AuditLogEntryResult::Success { .. } => {
+            SYSLOG_SEVERITY_INFORMATIONAL
+        }
+        audit::AuditLogEntryResult::Error { .. }
+        | audit::AuditLogEntryResult::Unknown => SYSLOG_SEVERITY_WARNING,
+    };
+    let pri = u16::from(SYSLOG_FACILITY_LOG_AUDIT) * 8 + u16::from(severity);
+    // HOSTNAME and PROCID are NILVALUE; MSGID is the operation so that
+    // collectors can filter on it without parsing the body.
+    let message = format!(
+        "<{pri}>1 {timestamp} - oxide-nexus - {msgid} - {body}",
+        timestamp = entry
+            .time_completed
+            .to_rfc3339_opts(chrono::SecondsFormat::Micros, true),
+        msgid = syslog_msgid(&entry.operation_id),
+        body = serde_json::to_string(entry)?,
+    );
+    Ok(format!("{} {message}", message.len()).into_bytes())
+}
+
+/// MSGID must be 1-32 printable ASCII characters
+fn syslog_msgid(operation_id: &str) -> String {
+    let msgid: String = operation_id
+        .chars()
+        .filter(|c| c.is_ascii_graphic())
+        .take(32)
+        .collect();
+    if msgid.is_empty() { String::from("-") } else { msgid }
+}
+
+async fn deliver_syslog(
+    sink: &AuditLogSink,
+    entries: &[audit::AuditLogEntry],
+) -> Result<(), anyhow::Error> {
+    let mut buf = Vec::new();
+    for entry in entries {
+        buf.extend(syslog_frame(entry).context("serializing audit log entry")?);
+    }
+
+    let (host, port) =
+        parse_syslog_address(&sink.endpoint).map_err(|e| anyhow!(e))?;
+    let send = async {
+        let stream = TcpStream::connect((host, port))
+            .await
+            .with_context(|| format!("connecting to {}", sink.endpoint))?;
+        match &sink.tls_root_cert {
+            None => write_all_and_close(stream, &buf).await,
+            Some(pem) => {
+                let roots = parse_root_certs(pem).map_err(|e| anyhow!(e))?;
+                let config = rustls::ClientConfig::builder()
+                    .with_root_certificates(roots)
+                    .with_no_client_auth();
+                let server_name =
+                    rustls::pki_types::ServerName::try_from(host.to_string())
+                        .with_context(|| {
+                        format!("invalid TLS server name {host:?}")
+                    })?;
+                let stream = tokio_rustls::TlsConnector::from(Arc::new(config))
+                    .connect(server_name, stream)
+                    .await
+                    .context("TLS handshake")?;
+                write_all_and_close(stream, &buf).await
+            }
+        }
+    };
+    tokio::time::timeout(SYSLOG_TIMEOUT, send)
+        .await
+        .context("timed out sending to syslog collector")?
+}
+
+async fn write_all_and_close(
+    mut stream: impl AsyncWrite + Unpin,
+    buf: &[u8],
+) -> Result<(), anyhow::Error> {
+    stream.write_all(buf).await.context("sending syslog messages")?;
+    stream.shutdown().await.context("closing syslog connection")?;
+    Ok(())
+}
+
+impl BackgroundTask for AuditLogExporter {
+    fn activate<'a>(
+        &'a mut self,
+        opctx: &'a OpContext,
+    ) -> BoxFuture<'a, serde_json::Value> {
+        Box::pin(async {
+            let status = self.actually_activate(opctx).await;
+            match serde_json::to_value(status) {
+                Ok(val) => val,
+                Err(err) => {
+                    json!({ "error": format!("failed to serialize status: {err}") })
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff() {
+        assert_eq!(backoff(0), TimeDelta::seconds(10));
+        assert_eq!(backoff(1), TimeDelta::seconds(20));
+        assert_eq!(backoff(5), TimeDelta::seconds(320));
+        assert_eq!(backoff(9), MAX_BACKOFF);
+        assert_eq!(backoff(i32::MAX), MAX_BACKOFF);
+        assert_eq!(backoff(-1), MIN_BACKOFF);
+    }
+
+    #[test]
+    fn test_syslog_msgid() {
+        assert_eq!(syslog_msgid("project_create"), "project_create");
+        assert_eq!(syslog_msgid(""), "-");
+        assert_eq!(syslog_msgid("a b"), "ab");
+        assert_eq!(syslog_msgid(&"x".repeat(40)).len(), 32);
+    }
+}
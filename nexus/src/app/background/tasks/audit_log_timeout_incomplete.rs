@@ -169,6 +169,7 @@ mod tests {
             actor: AuditLogActor::Unauthenticated,
             auth_method: None,
             credential_id: None,
+            request_body: None,
         }
     }
 
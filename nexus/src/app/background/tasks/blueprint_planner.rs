@@ -5,8 +5,11 @@
 //! Background task for automatic update planning.
 
 use super::reconfigurator_config::ReconfiguratorConfigLoaderState;
+use crate::app::alert::publish_alert;
+use crate::app::background::Activator;
 use crate::app::background::BackgroundTask;
 use crate::app::background::tasks::blueprint_load::LoadedTargetBlueprint;
+use crate::app::deployment::BlueprintTargetReleaseStatus;
 use chrono::Utc;
 use futures::future::BoxFuture;
 use nexus_auth::authz;
@@ -16,13 +19,18 @@ use nexus_db_queries::db::datastore::BlueprintLimitReachedOutput;
 use nexus_reconfigurator_planning::planner::Planner;
 use nexus_reconfigurator_planning::planner::PlannerRng;
 use nexus_reconfigurator_preparation::PlanningInputFromDb;
+use nexus_types::alert::update::UpdateProgress;
+use nexus_types::alert::update::UpdateStatusChanged;
+use nexus_types::deployment::Blueprint;
 use nexus_types::deployment::BlueprintSource;
 use nexus_types::deployment::BlueprintTarget;
+use nexus_types::deployment::PlanningInput;
 use nexus_types::deployment::PlanningReport;
 use nexus_types::internal_api::background::BlueprintPlannerStatus;
 use nexus_types::inventory::Collection;
 use omicron_common::api::external::Error;
 use omicron_common::api::external::LookupType;
+use omicron_uuid_kinds::AlertUuid;
 use omicron_uuid_kinds::BlueprintUuid;
 use omicron_uuid_kinds::GenericUuid as _;
 use serde_json::json;
@@ -62,6 +70,7 @@ pub struct BlueprintPlanner {
     rx_blueprint: Receiver<Option<LoadedTargetBlueprint>>,
     tx_planned: Sender<Option<BlueprintUuid>>,
     blueprint_limit: u64,
+    alert_dispatcher: Activator,
 }
 
 /// The default number of blueprints, beyond which the auto-planner will stop
@@ -86,6 +95,7 @@ impl BlueprintPlanner {
         rx_config: Receiver<ReconfiguratorConfigLoaderState>,
         rx_inventory: Receiver<Option<Arc<Collection>>>,
         rx_blueprint: Receiver<Option<LoadedTargetBlueprint>>,
+        alert_dispatcher: Activator,
     ) -> Self {
         let (tx_planned, _) = watch::channel(None);
         Self {
@@ -95,6 +105,7 @@ impl BlueprintPlanner {
             rx_blueprint,
             tx_planned,
             blueprint_limit: DEFAULT_BLUEPRINT_LIMIT,
+            alert_dispatcher,
         }
     }
 
@@ -329,6 +340,7 @@ impl BlueprintPlanner {
         // We have a new target!
 
         self.tx_planned.send_replace(Some(blueprint.id));
+        self.publish_update_completed(opctx, &input, &parent, &blueprint).await;
         Ok(BlueprintPlannerStatus::Targeted {
             parent_blueprint_id,
             blueprint_id,
@@ -339,6 +351,54 @@ impl BlueprintPlanner {
         })
     }
 
+    /// If `blueprint` is the first blueprint in which every component is on
+    /// the current target release, publish an alert announcing that the
+    /// update has completed.
+    async fn publish_update_completed(
+        &self,
+        opctx: &OpContext,
+        input: &PlanningInput,
+        parent: &Blueprint,
+        blueprint: &Blueprint,
+    ) {
+        let Some(repo) = input.tuf_repo().description().tuf_repo() else {
+            return;
+        };
+        let system_version = &repo.repo.system_version;
+        let version = system_version.to_string();
+        let was_in_progress = matches!(
+            BlueprintTargetReleaseStatus::new(parent, &version),
+            BlueprintTargetReleaseStatus::PreviousUpdateInProgress(_)
+        );
+        let is_complete = matches!(
+            BlueprintTargetReleaseStatus::new(blueprint, &version),
+            BlueprintTargetReleaseStatus::AllComponentsOnCurrentTargetRelease
+        );
+        if !(was_in_progress && is_complete) {
+            return;
+        }
+
+        info!(
+            &opctx.log,
+            "all components are now on the target release";
+            "system_version" => %system_version,
+            "blueprint_id" => %blueprint.id,
+        );
+        let alert = UpdateStatusChanged {
+            status: UpdateProgress::Completed,
+            system_version: system_version.clone(),
+            previous_system_version: None,
+        };
+        publish_alert(
+            opctx,
+            &self.datastore,
+            &self.alert_dispatcher,
+            AlertUuid::new_v4(),
+            &alert,
+        )
+        .await;
+    }
+
     async fn check_blueprint_limit_reached(
         &self,
         opctx: &OpContext,
@@ -517,6 +577,7 @@ mod test {
             rx_config_loader,
             rx_inventory,
             rx_loader.clone(),
+            Activator::new(),
         );
 
         // On activation, the planner should run successfully and generate
@@ -692,6 +753,7 @@ mod test {
             rx_config_loader,
             rx_inventory,
             rx_blueprint,
+            Activator::new(),
         );
 
         // This limit matches the loop above.
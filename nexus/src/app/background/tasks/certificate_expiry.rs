@@ -0,0 +1,467 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Background task for monitoring when stored certificates expire
+//!
+//! Each activation parses every certificate the control plane stores: Silos'
+//! TLS certificates, the certificates Silos' SAML identity providers use to
+//! verify requests from Nexus, and the CA certificates trusted for audit log
+//! sinks.  It also covers webhook receivers' certificates, which the control
+//! plane doesn't store, but records the expiry of each time a receiver presents
+//! one during a delivery attempt.  It reports how many days each has left as
+//! the `tls_certificate:days_until_expiry` timeseries, and publishes a
+//! `certificate.expiring` alert as each crosses one of the configured
+//! thresholds.
+//!
+//! A webhook receiver is only covered once an alert (or a liveness probe) has
+//! been delivered to it over HTTPS.  If it replaces its certificate, the
+//! replacement is only seen on the next delivery.  The same goes for SMTP
+//! receivers whose mail server offers STARTTLS, whose certificates are
+//! reported as those of webhook receivers.
+
+use crate::app::alert::publish_alert;
+use crate::app::background::Activator;
+use crate::app::background::BackgroundTask;
+use base64::Engine;
+use chrono::DateTime;
+use chrono::Utc;
+use futures::future::BoxFuture;
+use nexus_db_queries::context::OpContext;
+use nexus_db_queries::db::DataStore;
+use nexus_types::alert::certificate::CertificateExpiring;
+use nexus_types::alert::certificate::CertificateKind;
+use nexus_types::identity::Resource;
+use nexus_types::internal_api::background::CertificateExpiryStatus;
+use nexus_types::internal_api::background::StoredCertificateError;
+use nexus_types::internal_api::background::StoredCertificateExpiry;
+use omicron_uuid_kinds::AlertUuid;
+use omicron_uuid_kinds::GenericUuid;
+use omicron_uuid_kinds::OmicronZoneUuid;
+use openssl::x509::X509;
+use oximeter::types::ProducerRegistry;
+use slog_error_chain::InlineErrorChain;
+use std::sync::Arc;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+oximeter::use_timeseries!("tls-certificate.toml");
+use tls_certificate::DaysUntilExpiry;
+use tls_certificate::TlsCertificate;
+
+pub struct CertificateExpiry {
+    datastore: Arc<DataStore>,
+    alert_before_days: Vec<u32>,
+    alert_dispatcher: Activator,
+    nexus_id: Uuid,
+    /// days remaining on each certificate, as of the last activation
+    metrics: Arc<Mutex<Vec<(TlsCertificate, i64)>>>,
+}
+
+/// A certificate found in the database
+struct StoredCertificate {
+    kind: CertificateKind,
+    id: Uuid,
+    name: String,
+    silo_id: Option<Uuid>,
+    /// when the certificate expires, or why that couldn't be determined
+    expiry: Result<DateTime<Utc>, String>,
+}
+
+impl CertificateExpiry {
+    pub fn new(
+        datastore: Arc<DataStore>,
+        alert_before_days: Vec<u32>,
+        alert_dispatcher: Activator,
+        producer_registry: &ProducerRegistry,
+        nexus_id: OmicronZoneUuid,
+    ) -> Self {
+        let metrics = Arc::new(Mutex::new(Vec::new()));
+        producer_registry.register_producer(Producer(metrics.clone())).unwrap();
+        Self {
+            datastore,
+            alert_before_days,
+            alert_dispatcher,
+            nexus_id: nexus_id.into_untyped_uuid(),
+            metrics,
+        }
+    }
+
+    async fn list_certificates(
+        &self,
+        opctx: &OpContext,
+        status: &mut CertificateExpiryStatus,
+    ) -> Vec<StoredCertificate> {
+        let mut stored = Vec::new();
+
+        match self.datastore.certificate_list_all_batched(opctx).await {
+            Ok(certificates) => {
+                stored.extend(certificates.into_iter().map(|c| {
+                    StoredCertificate {
+                        kind: CertificateKind::SiloTls,
+                        id: c.id(),
+                        name: c.name().to_string(),
+                        silo_id: Some(c.silo_id),
+                        expiry: pem_expiry(&c.cert),
+                    }
+                }))
+            }
+            Err(error) => status.errors.push(format!(
+                "listing Silo certificates: {}",
+                InlineErrorChain::new(&error)
+            )),
+        }
+
+        match self
+            .datastore
+            .saml_identity_provider_list_all_batched(opctx)
+            .await
+        {
+            Ok(providers) => {
+                stored.extend(providers.into_iter().filter_map(|idp| {
+                    let cert = idp.public_cert.as_ref()?;
+                    Some(StoredCertificate {
+                        kind: CertificateKind::SamlServiceProvider,
+                        id: idp.id(),
+                        name: idp.name().to_string(),
+                        silo_id: Some(idp.silo_id),
+                        expiry: saml_certificate_pem(cert)
+                            .and_then(|pem| pem_expiry(&pem)),
+                    })
+                }))
+            }
+            Err(error) => status.errors.push(format!(
+                "listing SAML identity providers: {}",
+                InlineErrorChain::new(&error)
+            )),
+        }
+
+        match self.datastore.audit_log_sink_list_all(opctx).await {
+            Ok(sinks) => stored.extend(sinks.into_iter().filter_map(|sink| {
+                let cert = sink.tls_root_cert.as_ref()?;
+                Some(StoredCertificate {
+                    kind: CertificateKind::AuditLogSinkRoot,
+                    id: sink.id().into_untyped_uuid(),
+                    name: sink.name().to_string(),
+                    silo_id: None,
+                    expiry: pem_expiry(cert.as_bytes()),
+                })
+            })),
+            Err(error) => status.errors.push(format!(
+                "listing audit log sinks: {}",
+                InlineErrorChain::new(&error)
+            )),
+        }
+
+        match self.datastore.webhook_rx_peer_cert_expiry_list(opctx).await {
+            Ok(receivers) => stored.extend(receivers.into_iter().map(
+                |(rx, time_expires)| StoredCertificate {
+                    kind: CertificateKind::WebhookReceiver,
+                    id: rx.id().into_untyped_uuid(),
+                    name: rx.name().to_string(),
+                    silo_id: None,
+                    expiry: Ok(time_expires),
+                },
+            )),
+            Err(error) => status.errors.push(format!(
+                "listing webhook receivers' certificates: {}",
+                InlineErrorChain::new(&error)
+            )),
+        }
+
+        stored
+    }
+
+    /// Publishes an alert for each certificate that has crossed one of the
+    /// configured thresholds, returning how many were newly published
+    async fn publish_alerts(
+        &self,
+        opctx: &OpContext,
+        certificates: &[StoredCertificateExpiry],
+    ) -> usize {
+        let mut published = 0;
+        for certificate in certificates {
+            // Only the nearest threshold crossed matters.  If we missed
+            // farther ones (e.g., because the certificate was only just
+            // stored), there's no point in alerting about them now.
+            let Some(threshold) = self
+                .alert_before_days
+                .iter()
+                .copied()
+                .filter(|t| certificate.days_remaining < i64::from(*t))
+                .min()
+            else {
+                continue;
+            };
+            let alert = CertificateExpiring {
+                kind: certificate.kind,
+                id: certificate.id,
+                name: certificate.name.clone(),
+                silo_id: certificate.silo_id,
+                time_expires: certificate.time_expires,
+                days_remaining: certificate.days_remaining,
+            };
+            if publish_alert(
+                opctx,
+                &self.datastore,
+                &self.alert_dispatcher,
+                alert_id(certificate, threshold),
+                &alert,
+            )
+            .await
+            {
+                published += 1;
+            }
+        }
+        published
+    }
+}
+
+impl BackgroundTask for CertificateExpiry {
+    fn activate<'a>(
+        &'a mut self,
+        opctx: &'a OpContext,
+    ) -> BoxFuture<'a, serde_json::Value> {
+        Box::pin(async move {
+            let mut status = CertificateExpiryStatus::default();
+            let stored = self.list_certificates(opctx, &mut status).await;
+
+            let now = Utc::now();
+            for certificate in stored {
+                match certificate.expiry {
+                    Ok(time_expires) => {
+                        let days_remaining = (time_expires - now)
+                            .num_seconds()
+                            .div_euclid(86_400);
+                        status.certificates.push(StoredCertificateExpiry {
+                            kind: certificate.kind,
+                            id: certificate.id,
+                            name: certificate.name,
+                            silo_id: certificate.silo_id,
+                            time_expires,
+                            days_remaining,
+                        });
+                    }
+                    Err(error) => {
+                        warn!(
+                            opctx.log,
+                            "failed to parse stored certificate";
+                            "kind" => %certificate.kind,
+                            "id" => %certificate.id,
+                            "error" => &error,
+                        );
+                        status.parse_errors.push(StoredCertificateError {
+                            kind: certificate.kind,
+                            id: certificate.id,
+                            name: certificate.name,
+                            error,
+                        });
+                    }
+                }
+            }
+            status.certificates.sort_by_key(|c| (c.time_expires, c.id));
+
+            *self.metrics.lock().unwrap() = status
+                .certificates
+                .iter()
+                .map(|c| {
+                    let target = TlsCertificate {
+                        nexus_id: self.nexus_id,
+                        kind: c.kind.as_str().into(),
+                        id: c.id,
+                        name: c.name.clone().into(),
+                    };
+                    (target, c.days_remaining)
+                })
+                .collect();
+
+            status.alerts_published =
+                self.publish_alerts(opctx, &status.certificates).await;
+            serde_json::json!(status)
+        })
+    }
+}
+
+/// Returns when the first of the certificates in `pem` expires
+fn pem_expiry(pem: &[u8]) -> Result<DateTime<Utc>, String> {
+    let expiry = omicron_certificates::first_expiry(pem)
+        .map_err(|e| InlineErrorChain::new(&e).to_string())?;
+    DateTime::from_timestamp(expiry, 0)
+        .ok_or_else(|| format!("expiry time {expiry} is out of range"))
+}
+
+/// Converts a SAML identity provider's certificate (base64-encoded DER) to
+/// PEM format
+fn saml_certificate_pem(cert: &str) -> Result<Vec<u8>, String> {
+    let der = base64::engine::general_purpose::STANDARD
+        .decode(cert.as_bytes())
+        .map_err(|e| format!("decoding base64: {e}"))?;
+    X509::from_der(&der)
+        .and_then(|cert| cert.to_pem())
+        .map_err(|e| format!("parsing DER: {}", InlineErrorChain::new(&e)))
+}
+
+/// Returns the ID of the alert for `certificate` crossing the threshold
+/// `threshold_days` days before it expires
+///
+/// The ID is derived from the certificate and when it expires, like a version 5
+/// UUID, so that every activation (on every Nexus) publishes the same alert,
+/// and it's only published once.  A certificate that's replaced in place (like
+/// a SAML identity provider's or a webhook receiver's) gets new alerts when the
+/// replacement nears expiry.
+fn alert_id(
+    certificate: &StoredCertificateExpiry,
+    threshold_days: u32,
+) -> AlertUuid {
+    let name = format!(
+        "certificate.expiring/{}/{}/{}/{}",
+        certificate.kind,
+        certificate.id,
+        certificate.time_expires.timestamp(),
+        threshold_days
+    );
+    let digest = openssl::sha::sha1(name.as_bytes());
+    let mut bytes = [0; 16];
+    bytes.copy_from_slice(&digest[..16]);
+    AlertUuid::from_untyped_uuid(
+        uuid::Builder::from_sha1_bytes(bytes).into_uuid(),
+    )
+}
+
+/// Produces the days remaining on each certificate as of the last activation
+struct Producer(Arc<Mutex<Vec<(TlsCertificate, i64)>>>);
+
+impl oximeter::Producer for Producer {
+    fn produce(
+        &mut self,
+    ) -> Result<
+        Box<dyn Iterator<Item = oximeter::Sample>>,
+        oximeter::MetricsError,
+    > {
+        let metrics = self.0.lock().unwrap();
+        let samples = metrics
+            .iter()
+            .map(|(target, days)| {
+                oximeter::Sample::new(target, &DaysUntilExpiry { datum: *days })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Box::new(samples.into_iter()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use async_bb8_diesel::AsyncRunQueryDsl;
+    use chrono::Datelike;
+    use chrono::TimeDelta;
+    use diesel::prelude::*;
+    use nexus_db_model::AlertClass;
+    use nexus_db_model::Certificate;
+    use nexus_db_model::ServiceKind;
+    use nexus_test_utils_macros::nexus_test;
+    use nexus_types::external_api::certificate::CertificateCreate;
+    use nexus_types::external_api::certificate::ServiceUsingCertificate;
+    use nexus_types::silo::DEFAULT_SILO_ID;
+    use omicron_common::api::external::IdentityMetadataCreateParams;
+    use omicron_test_utils::certificates::CertificateChain;
+
+    type ControlPlaneTestContext =
+        nexus_test_utils::ControlPlaneTestContext<crate::Server>;
+
+    /// Returns a certificate for the default Silo that expires at the start of
+    /// the given day
+    fn certificate_expiring(
+        name: &str,
+        year: i32,
+        month: u32,
+        day: u32,
+    ) -> Certificate {
+        let mut params = rcgen::CertificateParams::new(Vec::<String>::new());
+        params.not_after = rcgen::date_time_ymd(
+            year,
+            u8::try_from(month).unwrap(),
+            u8::try_from(day).unwrap(),
+        );
+        let chain = CertificateChain::with_params(params);
+        Certificate::new_unvalidated(
+            DEFAULT_SILO_ID,
+            Uuid::new_v4(),
+            ServiceKind::Nexus,
+            CertificateCreate {
+                identity: IdentityMetadataCreateParams {
+                    name: name.parse().unwrap(),
+                    description: String::new(),
+                },
+                cert: chain.cert_chain_as_pem(),
+                key: chain.end_cert_private_key_as_pem(),
+                service: ServiceUsingCertificate::ExternalApi,
+            },
+        )
+    }
+
+    #[nexus_test(server = crate::Server)]
+    async fn test_certificate_expiry(cptestctx: &ControlPlaneTestContext) {
+        use nexus_db_schema::schema::alert::dsl as alert_dsl;
+        use nexus_db_schema::schema::certificate::dsl as certificate_dsl;
+
+        let nexus = &cptestctx.server.server_context().nexus;
+        let datastore = nexus.datastore();
+        let opctx = OpContext::for_tests(
+            cptestctx.logctx.log.clone(),
+            datastore.clone(),
+        );
+        let conn = datastore.pool_connection_for_tests().await.unwrap();
+
+        // One certificate expires in a few days, and another already has.
+        let soon = (Utc::now() + TimeDelta::days(5)).date_naive();
+        let expiring = certificate_expiring(
+            "expiring",
+            soon.year(),
+            soon.month(),
+            soon.day(),
+        );
+        let expired = certificate_expiring("expired", 2000, 1, 1);
+        diesel::insert_into(certificate_dsl::certificate)
+            .values(vec![expiring.clone(), expired.clone()])
+            .execute_async(&*conn)
+            .await
+            .unwrap();
+
+        let mut task = CertificateExpiry::new(
+            datastore.clone(),
+            vec![30, 7, 1],
+            Activator::new(),
+            &ProducerRegistry::new(),
+            OmicronZoneUuid::new_v4(),
+        );
+        let status = serde_json::from_value::<CertificateExpiryStatus>(
+            task.activate(&opctx).await,
+        )
+        .unwrap();
+        assert_eq!(status.errors, Vec::<String>::new());
+        assert_eq!(status.parse_errors, Vec::new());
+
+        // The expired certificate sorts first, followed by the one that's
+        // expiring.
+        let ids: Vec<_> = status.certificates.iter().map(|c| c.id).collect();
+        assert_eq!(ids, [expired.id(), expiring.id()]);
+        assert!(status.certificates[0].days_remaining < 0);
+        assert_eq!(status.certificates[1].days_remaining, 4);
+        assert_eq!(status.alerts_published, 2);
+
+        // The alerts aren't published again.
+        let status = serde_json::from_value::<CertificateExpiryStatus>(
+            task.activate(&opctx).await,
+        )
+        .unwrap();
+        assert_eq!(status.alerts_published, 0);
+        let alerts = alert_dsl::alert
+            .filter(alert_dsl::alert_class.eq(AlertClass::CertificateExpiring))
+            .select(alert_dsl::payload)
+            .load_async::<serde_json::Value>(&*conn)
+            .await
+            .unwrap();
+        assert_eq!(alerts.len(), 2);
+    }
+}
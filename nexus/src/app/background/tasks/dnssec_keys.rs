@@ -0,0 +1,503 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Background task for rolling over the external DNS zones' DNSSEC keys
+//!
+//! Each external DNS zone is signed with a key-signing key (KSK), which signs
+//! only the zone's DNSKEY records and is referred to by the DS record in the
+//! parent zone, and a zone-signing key (ZSK), which signs everything else.
+//! Each activation creates keys for zones that lack them and rolls over keys
+//! that have reached the end of their lifetime, then propagates the keys to
+//! the external DNS servers.
+//!
+//! A key is rolled over by pre-publication (RFC 6781 section 4.1): its
+//! successor is published for a while before it starts signing the zone, so
+//! that resolvers have it by the time they see its signatures, and the old key
+//! remains published for a while after it stops, so that resolvers can still
+//! validate signatures they cached earlier. Since the DNS servers sign the
+//! DNSKEY records with every published KSK, a new KSK can be used as soon as
+//! its DS record has been registered with the parent zone, which operators
+//! must do before the old KSK is deleted.
+//!
+//! Every Nexus runs this task. Changes are only applied if no other Nexus has
+//! changed the keys since they were read, so concurrent activations do not
+//! create duplicate keys.
+
+use super::dns_servers::DnsServersList;
+use crate::app::background::BackgroundTask;
+use anyhow::Context;
+use chrono::DateTime;
+use chrono::TimeDelta;
+use chrono::Utc;
+use futures::future::BoxFuture;
+use futures::future::join_all;
+use internal_dns_types::config::DnssecAlgorithm;
+use internal_dns_types::config::DnssecConfigParams;
+use internal_dns_types::dnssec::SigningKey;
+use internal_dns_types::dnssec::generate_private_key;
+use nexus_config::DnssecKeysConfig;
+use nexus_db_model::DnsGroup;
+use nexus_db_model::DnssecKey;
+use nexus_db_model::DnssecKeyRole;
+use nexus_db_model::DnssecKeyState;
+use nexus_db_queries::context::OpContext;
+use nexus_db_queries::db::DataStore;
+use nexus_db_queries::db::datastore::DnssecKeyChanges;
+use nexus_db_queries::db::datastore::dnssec_config_params;
+use nexus_types::internal_api::background::DnssecKeyStatus;
+use nexus_types::internal_api::background::DnssecKeysStatus;
+use slog_error_chain::InlineErrorChain;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::sync::watch;
+
+pub struct DnssecKeyManager {
+    datastore: Arc<DataStore>,
+    config: DnssecKeysConfig,
+    rx_servers: watch::Receiver<Option<DnsServersList>>,
+}
+
+impl DnssecKeyManager {
+    pub fn new(
+        datastore: Arc<DataStore>,
+        config: DnssecKeysConfig,
+        rx_servers: watch::Receiver<Option<DnsServersList>>,
+    ) -> Self {
+        Self { datastore, config, rx_servers }
+    }
+
+    /// Rolls over the keys as needed, returning them as they are sent to the
+    /// DNS servers
+    async fn update_keys(
+        &self,
+        opctx: &OpContext,
+        status: &mut DnssecKeysStatus,
+    ) -> anyhow::Result<DnssecConfigParams> {
+        // With signing disabled, there are no zones to sign, so any keys left
+        // over from when it was enabled are deleted.
+        let zone_names = if self.config.enabled {
+            self.datastore
+                .dns_zones_list_all(opctx, DnsGroup::External)
+                .await
+                .context("listing external DNS zones")?
+                .into_iter()
+                .map(|zone| zone.zone_name)
+                .collect()
+        } else {
+            BTreeSet::new()
+        };
+
+        let (generation, keys) = self
+            .datastore
+            .dnssec_key_list(opctx)
+            .await
+            .context("listing DNSSEC keys")?;
+        let changes =
+            plan_rollover(&self.config, &zone_names, &keys, Utc::now())?;
+        if !changes.is_empty() {
+            let counts = (
+                changes.create.len(),
+                changes.activate.len(),
+                changes.retire.len(),
+                changes.delete.len(),
+            );
+            let applied = self
+                .datastore
+                .dnssec_keys_update(opctx, generation, changes)
+                .await
+                .context("updating DNSSEC keys")?;
+            if applied {
+                (
+                    status.keys_created,
+                    status.keys_activated,
+                    status.keys_retired,
+                    status.keys_deleted,
+                ) = counts;
+            } else {
+                info!(
+                    opctx.log,
+                    "DNSSEC keys changed concurrently; \
+                     leaving rollover to the next activation";
+                    "generation" => %generation,
+                );
+            }
+        }
+
+        let (generation, keys) = self
+            .datastore
+            .dnssec_key_list(opctx)
+            .await
+            .context("listing DNSSEC keys")?;
+        status.generation = Some(generation);
+        for key in &keys {
+            let signing_key = SigningKey::new(&key.clone().into())
+                .with_context(|| format!("loading DNSSEC key {}", key.id))?;
+            let ds = match key.role {
+                DnssecKeyRole::Ksk => {
+                    Some(signing_key.ds_text(&key.zone_name)?)
+                }
+                DnssecKeyRole::Zsk => None,
+            };
+            status.keys.push(DnssecKeyStatus {
+                zone_name: key.zone_name.clone(),
+                role: key.role.to_string(),
+                state: key.state.to_string(),
+                key_tag: signing_key.key_tag(),
+                ds,
+                time_created: key.time_created,
+            });
+        }
+        Ok(dnssec_config_params(generation, keys))
+    }
+}
+
+impl BackgroundTask for DnssecKeyManager {
+    fn activate<'a>(
+        &'a mut self,
+        opctx: &'a OpContext,
+    ) -> BoxFuture<'a, serde_json::Value> {
+        Box::pin(async move {
+            let mut status = DnssecKeysStatus {
+                enabled: self.config.enabled,
+                ..Default::default()
+            };
+
+            let config = match self.update_keys(opctx, &mut status).await {
+                Ok(config) => config,
+                Err(error) => {
+                    let error = format!("{error:#}");
+                    error!(
+                        opctx.log,
+                        "failed to update DNSSEC keys";
+                        "error" => &error,
+                    );
+                    status.error = Some(error);
+                    return serde_json::json!(status);
+                }
+            };
+
+            // Clone the server list rather than hold the borrow, which would
+            // block the task that maintains it.
+            let Some(servers) = self.rx_servers.borrow().clone() else {
+                warn!(opctx.log, "DNSSEC key propagation skipped: no servers");
+                status.error = Some(String::from("no servers"));
+                return serde_json::json!(status);
+            };
+
+            let results = join_all(
+                servers
+                    .addresses
+                    .iter()
+                    .map(|addr| propagate_one(&opctx.log, &config, *addr)),
+            )
+            .await;
+            for (addr, result) in servers.addresses.iter().zip(results) {
+                if let Err(error) = &result {
+                    warn!(
+                        opctx.log,
+                        "failed to propagate DNSSEC keys";
+                        "server" => %addr,
+                        "error" => error,
+                    );
+                }
+                status.server_results.insert(addr.to_string(), result);
+            }
+
+            serde_json::json!(status)
+        })
+    }
+}
+
+/// Returns the changes needed at time `now` for each zone in `zone_names` to
+/// have an active KSK and ZSK, rolled over according to `config`, and for no
+/// other zone to have any keys
+fn plan_rollover(
+    config: &DnssecKeysConfig,
+    zone_names: &BTreeSet<String>,
+    keys: &[DnssecKey],
+    now: DateTime<Utc>,
+) -> anyhow::Result<DnssecKeyChanges> {
+    let mut changes = DnssecKeyChanges::default();
+
+    let mut by_zone_role: BTreeMap<(&str, DnssecKeyRole), Vec<&DnssecKey>> =
+        BTreeMap::new();
+    for key in keys {
+        if zone_names.contains(&key.zone_name) {
+            by_zone_role
+                .entry((key.zone_name.as_str(), key.role))
+                .or_default()
+                .push(key);
+        } else {
+            changes.delete.push(key.id);
+        }
+    }
+
+    for zone_name in zone_names {
+        for role in [DnssecKeyRole::Ksk, DnssecKeyRole::Zsk] {
+            let (lifetime, overlap) = match role {
+                DnssecKeyRole::Ksk => {
+                    (config.ksk_lifetime_days, config.ksk_overlap_days)
+                }
+                DnssecKeyRole::Zsk => {
+                    (config.zsk_lifetime_days, config.zsk_overlap_days)
+                }
+            };
+            let lifetime = TimeDelta::days(i64::from(lifetime));
+            let overlap = TimeDelta::days(i64::from(overlap));
+            let keys = by_zone_role
+                .remove(&(zone_name.as_str(), role))
+                .unwrap_or_default();
+
+            let mut active = Vec::new();
+            let mut published = Vec::new();
+            for key in keys {
+                match key.state {
+                    DnssecKeyState::Active => active.push(key),
+                    DnssecKeyState::Published => published.push(key),
+                    DnssecKeyState::Retired => {
+                        let retired =
+                            key.time_retired.unwrap_or(key.time_created);
+                        if now - retired >= overlap {
+                            changes.delete.push(key.id);
+                        }
+                    }
+                }
+            }
+            active.sort_by_key(|key| activated(key));
+            published.sort_by_key(|key| key.time_created);
+
+            // There should be at most one successor; keep the oldest.
+            let successor = published.first().copied();
+            changes.delete.extend(published.iter().skip(1).map(|key| key.id));
+
+            // There should be at most one active key; keep the newest.
+            let current = active.pop();
+            changes.retire.extend(active.iter().map(|key| key.id));
+
+            match (current, successor) {
+                (None, Some(successor)) => {
+                    changes.activate.push(successor.id);
+                }
+                (None, None) => {
+                    changes.create.push(new_key(
+                        zone_name,
+                        role,
+                        DnssecKeyState::Active,
+                    )?);
+                }
+                (Some(current), Some(successor)) => {
+                    if now - successor.time_created >= overlap {
+                        changes.activate.push(successor.id);
+                        changes.retire.push(current.id);
+                    }
+                }
+                (Some(current), None) => {
+                    let prepublish =
+                        (lifetime - overlap).max(TimeDelta::zero());
+                    if now - activated(current) >= prepublish {
+                        changes.create.push(new_key(
+                            zone_name,
+                            role,
+                            DnssecKeyState::Published,
+                        )?);
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(changes)
+}
+
+/// Returns when `key` started signing its zone
+fn activated(key: &DnssecKey) -> DateTime<Utc> {
+    key.time_activated.unwrap_or(key.time_created)
+}
+
+fn new_key(
+    zone_name: &str,
+    role: DnssecKeyRole,
+    state: DnssecKeyState,
+) -> anyhow::Result<DnssecKey> {
+    let private_key = generate_private_key(DnssecAlgorithm::EcdsaP256Sha256)
+        .context("generating DNSSEC key")?;
+    Ok(DnssecKey::new(zone_name.to_string(), role, state, private_key))
+}
+
+async fn propagate_one(
+    log: &slog::Logger,
+    config: &DnssecConfigParams,
+    server_addr: SocketAddr,
+) -> Result<(), String> {
+    let url = format!("http://{server_addr}");
+    let client = dns_service_client::Client::new(&url, log.clone());
+    client.dnssec_config_put(config).await.map(|_| ()).map_err(|e| {
+        format!(
+            "failed to propagate DNSSEC keys generation {}: {}",
+            config.generation,
+            InlineErrorChain::new(&e),
+        )
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    fn config(enabled: bool) -> DnssecKeysConfig {
+        DnssecKeysConfig {
+            period_secs: Duration::from_secs(3600),
+            enabled,
+            zsk_lifetime_days: 90,
+            zsk_overlap_days: 7,
+            ksk_lifetime_days: 365,
+            ksk_overlap_days: 30,
+        }
+    }
+
+    fn zones(names: &[&str]) -> BTreeSet<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    /// Returns `keys` with `changes` applied at time `now`, as the datastore
+    /// would apply them
+    fn apply(
+        keys: &[DnssecKey],
+        changes: &DnssecKeyChanges,
+        now: DateTime<Utc>,
+    ) -> Vec<DnssecKey> {
+        let mut keys: Vec<_> = keys
+            .iter()
+            .filter(|key| !changes.delete.contains(&key.id))
+            .cloned()
+            .collect();
+        for key in &mut keys {
+            if changes.activate.contains(&key.id) {
+                key.state = DnssecKeyState::Active;
+                key.time_activated = Some(now);
+            }
+            if changes.retire.contains(&key.id) {
+                key.state = DnssecKeyState::Retired;
+                key.time_retired = Some(now);
+            }
+        }
+        keys.extend(changes.create.iter().cloned().map(|mut key| {
+            key.time_created = now;
+            key.time_activated =
+                (key.state == DnssecKeyState::Active).then_some(now);
+            key
+        }));
+        keys
+    }
+
+    fn states(keys: &[DnssecKey], role: DnssecKeyRole) -> Vec<DnssecKeyState> {
+        let mut keys: Vec<_> = keys.iter().filter(|k| k.role == role).collect();
+        keys.sort_by_key(|k| k.time_created);
+        keys.iter().map(|k| k.state).collect()
+    }
+
+    #[test]
+    fn test_plan_initial_keys() {
+        let config = config(true);
+        let zone_names = zones(&["oxide.test"]);
+        let now = Utc::now();
+
+        let changes = plan_rollover(&config, &zone_names, &[], now).unwrap();
+        assert_eq!(changes.create.len(), 2);
+        assert!(changes.activate.is_empty());
+        assert!(changes.retire.is_empty());
+        assert!(changes.delete.is_empty());
+        for key in &changes.create {
+            assert_eq!(key.zone_name, "oxide.test");
+            assert_eq!(key.state, DnssecKeyState::Active);
+            SigningKey::new(&key.clone().into()).expect("valid key");
+        }
+
+        // Once created, the keys are left alone until they need replacing.
+        let keys = apply(&[], &changes, now);
+        let changes = plan_rollover(&config, &zone_names, &keys, now).unwrap();
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn test_plan_zsk_rollover() {
+        let config = config(true);
+        let zone_names = zones(&["oxide.test"]);
+        let start = Utc::now();
+        let day = TimeDelta::days(1);
+
+        let changes = plan_rollover(&config, &zone_names, &[], start).unwrap();
+        let mut keys = apply(&[], &changes, start);
+
+        // Nothing happens until the successor must be published, one overlap
+        // before the end of the ZSK's lifetime.
+        let now = start + day * 82;
+        let changes = plan_rollover(&config, &zone_names, &keys, now).unwrap();
+        assert!(changes.is_empty());
+
+        let now = start + day * 83;
+        let changes = plan_rollover(&config, &zone_names, &keys, now).unwrap();
+        assert_eq!(changes.create.len(), 1);
+        assert_eq!(changes.create[0].role, DnssecKeyRole::Zsk);
+        assert_eq!(changes.create[0].state, DnssecKeyState::Published);
+        keys = apply(&keys, &changes, now);
+
+        // Once the successor has been published for the overlap, it replaces
+        // the old key, which stays published for another overlap.
+        let now = start + day * 89;
+        let changes = plan_rollover(&config, &zone_names, &keys, now).unwrap();
+        assert!(changes.is_empty());
+
+        let now = start + day * 90;
+        let changes = plan_rollover(&config, &zone_names, &keys, now).unwrap();
+        assert_eq!(changes.activate.len(), 1);
+        assert_eq!(changes.retire.len(), 1);
+        keys = apply(&keys, &changes, now);
+        assert_eq!(
+            states(&keys, DnssecKeyRole::Zsk),
+            [DnssecKeyState::Retired, DnssecKeyState::Active]
+        );
+        assert_eq!(states(&keys, DnssecKeyRole::Ksk), [DnssecKeyState::Active]);
+
+        let now = start + day * 97;
+        let changes = plan_rollover(&config, &zone_names, &keys, now).unwrap();
+        assert_eq!(changes.delete.len(), 1);
+        assert!(changes.create.is_empty());
+        keys = apply(&keys, &changes, now);
+        assert_eq!(states(&keys, DnssecKeyRole::Zsk), [DnssecKeyState::Active]);
+    }
+
+    #[test]
+    fn test_plan_removed_zones() {
+        let zone_names = zones(&["oxide.test", "other.test"]);
+        let now = Utc::now();
+
+        let changes =
+            plan_rollover(&config(true), &zone_names, &[], now).unwrap();
+        let keys = apply(&[], &changes, now);
+        assert_eq!(keys.len(), 4);
+
+        // Keys are deleted along with their zones.
+        let changes =
+            plan_rollover(&config(true), &zones(&["oxide.test"]), &keys, now)
+                .unwrap();
+        let deleted: Vec<_> = keys
+            .iter()
+            .filter(|key| changes.delete.contains(&key.id))
+            .map(|key| key.zone_name.as_str())
+            .collect();
+        assert_eq!(deleted, ["other.test", "other.test"]);
+        assert!(changes.create.is_empty());
+
+        // Disabling signing deletes all of the keys.
+        let changes =
+            plan_rollover(&config(false), &BTreeSet::new(), &keys, now)
+                .unwrap();
+        assert_eq!(changes.delete.len(), 4);
+        assert!(changes.create.is_empty());
+    }
+}
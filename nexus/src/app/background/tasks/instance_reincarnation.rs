@@ -4,6 +4,7 @@
 
 //! Background task for automatically restarting failed instances.
 
+use crate::app::alert::publish_alert;
 use crate::app::background::Activator;
 use crate::app::background::BackgroundTask;
 use crate::app::saga::StartSaga;
@@ -14,11 +15,16 @@ use nexus_db_queries::authn;
 use nexus_db_queries::context::OpContext;
 use nexus_db_queries::db::DataStore;
 use nexus_db_queries::db::pagination::Paginator;
+use nexus_types::alert::instance::AutoRestartReason;
+use nexus_types::alert::instance::InstanceAutoRestarted;
 use nexus_types::identity::Resource;
 use nexus_types::internal_api::background::InstanceReincarnationStatus;
 use nexus_types::internal_api::background::ReincarnatableInstance;
 use nexus_types::internal_api::background::ReincarnationReason;
 use omicron_common::api::external::Error;
+use omicron_uuid_kinds::AlertUuid;
+use omicron_uuid_kinds::GenericUuid;
+use omicron_uuid_kinds::InstanceUuid;
 use std::num::NonZeroU32;
 use std::sync::Arc;
 use steno::SagaId;
@@ -34,6 +40,9 @@ pub struct InstanceReincarnation {
     /// Called after successful instance-start sagas to trigger member state
     /// transitions ("Joining" → "Joined") for instances with multicast memberships.
     task_multicast_reconciler: Activator,
+    /// Activator for the alert dispatcher background task, which is activated
+    /// when an alert is published for a reincarnated instance.
+    task_alert_dispatcher: Activator,
 }
 
 const DEFAULT_MAX_CONCURRENT_REINCARNATIONS: NonZeroU32 =
@@ -42,7 +51,14 @@ const DEFAULT_MAX_CONCURRENT_REINCARNATIONS: NonZeroU32 =
         None => unreachable!(), // 16 > 0
     };
 
-type RunningSaga = (Uuid, SagaId, BoxFuture<'static, Result<(), Error>>);
+/// A running instance-start saga, along with the alert to publish if it
+/// completes successfully.
+type RunningSaga = (
+    Uuid,
+    SagaId,
+    BoxFuture<'static, Result<(), Error>>,
+    InstanceAutoRestarted,
+);
 
 impl BackgroundTask for InstanceReincarnation {
     fn activate<'a>(
@@ -142,6 +158,7 @@ impl InstanceReincarnation {
         sagas: Arc<dyn StartSaga>,
         disabled: bool,
         task_multicast_reconciler: Activator,
+        task_alert_dispatcher: Activator,
     ) -> Self {
         Self {
             datastore,
@@ -149,6 +166,7 @@ impl InstanceReincarnation {
             concurrency_limit: DEFAULT_MAX_CONCURRENT_REINCARNATIONS,
             disabled,
             task_multicast_reconciler,
+            task_alert_dispatcher,
         }
     }
 
@@ -191,6 +209,19 @@ impl InstanceReincarnation {
             }
             for db_instance in batch {
                 let instance_id = db_instance.id();
+                let alert = InstanceAutoRestarted {
+                    instance_id: InstanceUuid::from_untyped_uuid(instance_id),
+                    instance_name: db_instance.name().clone(),
+                    project_id: db_instance.project_id,
+                    reason: match reason {
+                        ReincarnationReason::Failed => {
+                            AutoRestartReason::Failed
+                        }
+                        ReincarnationReason::SagaUnwound => {
+                            AutoRestartReason::StartSagaUnwound
+                        }
+                    },
+                };
                 info!(
                     opctx.log,
                     "attempting to reincarnate instance...";
@@ -216,7 +247,12 @@ impl InstanceReincarnation {
                 .await;
                 match running_saga {
                     Ok((saga_id, completed)) => {
-                        running_sagas.push((instance_id, saga_id, completed));
+                        running_sagas.push((
+                            instance_id,
+                            saga_id,
+                            completed,
+                            alert,
+                        ));
                     }
                     Err(error) => {
                         const ERR_MSG: &'static str =
@@ -259,7 +295,7 @@ impl InstanceReincarnation {
             // Otherwise, we may see some instances multiple times, because
             // their sagas completing is what changes the instance record's
             // state so that it no longer shows up in the query results.
-            for (instance_id, saga_id, saga) in running_sagas.drain(..) {
+            for (instance_id, saga_id, saga, alert) in running_sagas.drain(..) {
                 match saga.await {
                     // Start saga completed successfully
                     Ok(_) => {
@@ -274,6 +310,14 @@ impl InstanceReincarnation {
                         status.instances_reincarnated.push(
                             ReincarnatableInstance { instance_id, reason },
                         );
+                        publish_alert(
+                            opctx,
+                            &self.datastore,
+                            &self.task_alert_dispatcher,
+                            AlertUuid::new_v4(),
+                            &alert,
+                        )
+                        .await;
                     }
                     // The instance's state changed in the meantime, that's fine...
                     Err(err @ Error::Conflict { .. }) => {
@@ -623,6 +667,7 @@ mod test {
             nexus.sagas.clone(),
             false,
             Activator::new(),
+            Activator::new(),
         );
 
         // Noop test
@@ -676,6 +721,7 @@ mod test {
             nexus.sagas.clone(),
             false,
             Activator::new(),
+            Activator::new(),
         );
 
         // Create an instance in the `Failed` state that's eligible to be
@@ -723,6 +769,7 @@ mod test {
             nexus.sagas.clone(),
             false,
             Activator::new(),
+            Activator::new(),
         );
 
         // Create instances in the `Failed` state that are eligible to be
@@ -904,6 +951,7 @@ mod test {
             nexus.sagas.clone(),
             false,
             Activator::new(),
+            Activator::new(),
         );
 
         let instance1 = create_instance(
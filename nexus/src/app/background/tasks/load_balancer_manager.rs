@@ -0,0 +1,589 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Background task for maintaining the data plane configuration of load
+//! balancers.
+//!
+//! Each activation walks every load balancer and:
+//!
+//! - brings its tag-selected backends up to date with the instances carrying
+//!   its backend tag,
+//! - health checks its running backends, by opening a TCP connection to the
+//!   health check port on each backend's primary private address,
+//! - programs one NAT entry per listener port on the switches, pointing at one
+//!   of the healthy backends, and records which ports each backend serves,
+//!   and
+//! - adds the frontend address to the OPTE ports of the healthy backends, so
+//!   that they accept traffic forwarded to it, and withdraws it from the
+//!   others.
+//!
+//! The switches allow a single NAT target for each address and port, and
+//! there's no other place in the data path to spread the flows to one port
+//! across several instances. So this balances load per port, not per flow:
+//! each port is pinned to one backend, and all flows to it reach that backend
+//! until it fails its health check. The ports are divided evenly among the
+//! healthy backends, each port going to the backend ranked highest for it by
+//! rendezvous hashing that still has room, so every healthy backend serves as
+//! long as there are at least as many ports as backends, and most ports stay
+//! put as backends come and go. Healthy backends beyond the number of ports
+//! are idle standbys. The API documents this, and reports the ports assigned
+//! to each backend.
+
+use crate::app::background::BackgroundTask;
+use crate::app::dpd_clients;
+use crate::app::load_balancer::withdraw_frontend;
+use futures::future::BoxFuture;
+use futures::future::join_all;
+use internal_dns_resolver::Resolver;
+use nexus_db_model::ExternalIp;
+use nexus_db_model::LoadBalancer;
+use nexus_db_model::LoadBalancerBackend;
+use nexus_db_model::LoadBalancerBackendHealth;
+use nexus_db_model::NatEntryValues;
+use nexus_db_model::SqlU16;
+use nexus_db_queries::context::OpContext;
+use nexus_db_queries::db::DataStore;
+use nexus_db_queries::db::datastore::LoadBalancerBackendTarget;
+use nexus_types::external_api::load_balancer::LoadBalancerHealthCheck;
+use nexus_types::identity::Resource;
+use nexus_types::internal_api::background::LoadBalancerManagerStatus;
+use nexus_types::internal_api::background::LoadBalancerStatus;
+use omicron_uuid_kinds::GenericUuid;
+use oxnet::Ipv6Net;
+use sled_agent_client::types::InstanceExternalIpBody;
+use slog_error_chain::InlineErrorChain;
+use std::collections::BTreeMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// How long to wait for a backend to accept a health check connection
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+pub struct LoadBalancerManager {
+    datastore: Arc<DataStore>,
+    resolver: Resolver,
+}
+
+impl LoadBalancerManager {
+    pub fn new(datastore: Arc<DataStore>, resolver: Resolver) -> Self {
+        Self { datastore, resolver }
+    }
+
+    async fn reconcile(
+        &self,
+        opctx: &OpContext,
+        load_balancer: &LoadBalancer,
+        frontend: &ExternalIp,
+    ) -> LoadBalancerStatus {
+        let log = &opctx.log;
+        let load_balancer_id = load_balancer.id();
+        let frontend_ip = frontend.ip.ip();
+        let mut status = LoadBalancerStatus {
+            load_balancer_id,
+            ip: frontend_ip,
+            backends: 0,
+            serving: 0,
+            nat_changes: 0,
+            errors: Vec::new(),
+        };
+
+        match self
+            .datastore
+            .load_balancer_backends_sync(opctx, load_balancer)
+            .await
+        {
+            Ok(removed) => {
+                status.errors.extend(
+                    withdraw_frontend(
+                        &self.datastore,
+                        opctx,
+                        frontend_ip,
+                        &removed,
+                    )
+                    .await,
+                );
+            }
+            Err(e) => {
+                status.errors.push(format!(
+                    "failed to sync tagged backends: {}",
+                    InlineErrorChain::new(&e)
+                ));
+            }
+        }
+
+        let backends = match self
+            .datastore
+            .load_balancer_backends_list_all(opctx, load_balancer_id)
+            .await
+        {
+            Ok(backends) => backends,
+            Err(e) => {
+                status.errors.push(format!(
+                    "failed to list backends: {}",
+                    InlineErrorChain::new(&e)
+                ));
+                return status;
+            }
+        };
+        let targets = match self
+            .datastore
+            .load_balancer_backend_targets(opctx, load_balancer_id)
+            .await
+        {
+            Ok(targets) => targets
+                .into_iter()
+                .map(|t| (t.instance_id, t))
+                .collect::<BTreeMap<_, _>>(),
+            Err(e) => {
+                status.errors.push(format!(
+                    "failed to list running backends: {}",
+                    InlineErrorChain::new(&e)
+                ));
+                return status;
+            }
+        };
+        status.backends = backends.len();
+
+        // Check the health of every running backend concurrently.
+        let health_check = load_balancer.health_check();
+        let checks = join_all(backends.iter().map(|backend| {
+            let target = targets.get(&backend.instance_id);
+            async move {
+                match (target, &health_check) {
+                    (Some(target), Some(check)) => {
+                        Some(check_backend(target, check.port).await)
+                    }
+                    _ => None,
+                }
+            }
+        }))
+        .await;
+
+        let mut updated = Vec::with_capacity(backends.len());
+        for (mut backend, passed) in backends.into_iter().zip(checks) {
+            let original = backend.clone();
+            match (targets.contains_key(&backend.instance_id), &health_check) {
+                // A backend that is not running is not checked, and starts
+                // over once it is running again.
+                (false, _) => {
+                    backend.health = LoadBalancerBackendHealth::Unknown;
+                    backend.consecutive_successes = 0;
+                    backend.consecutive_failures = 0;
+                }
+                (true, Some(check)) => {
+                    record_health_check(
+                        &mut backend,
+                        check,
+                        passed.unwrap_or(false),
+                    );
+                }
+                (true, None) => {
+                    backend.health = LoadBalancerBackendHealth::Healthy;
+                }
+            }
+            updated.push((original, backend));
+        }
+
+        let serving = updated
+            .iter()
+            .filter(|(_, b)| b.health == LoadBalancerBackendHealth::Healthy)
+            .filter_map(|(_, b)| targets.get(&b.instance_id))
+            .collect::<Vec<_>>();
+        status.serving = serving.len();
+
+        // Divide the listener ports among the serving backends.
+        let ports = load_balancer.ports().collect::<Vec<_>>();
+        let assignments = assign_ports(&ports, &serving);
+        let nat_entries = assignments
+            .iter()
+            .map(|(port, target)| NatEntryValues {
+                external_address: frontend.ip.into(),
+                first_port: SqlU16::from(*port),
+                last_port: SqlU16::from(*port),
+                sled_address: Ipv6Net::host_net(
+                    *target.sled_agent_address.ip(),
+                )
+                .into(),
+                vni: target.vni,
+                mac: target.mac,
+            })
+            .collect::<Vec<_>>();
+        let nat_synced = match self
+            .datastore
+            .nat_sync_external_address(opctx, frontend.ip.into(), &nat_entries)
+            .await
+        {
+            Ok(0) => true,
+            Ok(changes) => {
+                status.nat_changes = changes;
+                self.notify_dendrite(opctx, &mut status.errors).await;
+                true
+            }
+            Err(e) => {
+                status.errors.push(format!(
+                    "failed to update NAT entries: {}",
+                    InlineErrorChain::new(&e)
+                ));
+                false
+            }
+        };
+
+        // Add the frontend address to the VMMs of serving backends, and
+        // withdraw it from the others.
+        for (original, mut backend) in updated {
+            let serving_vmm = (backend.health
+                == LoadBalancerBackendHealth::Healthy)
+                .then(|| targets.get(&backend.instance_id))
+                .flatten();
+            let wanted = serving_vmm.map(|t| t.propolis_id.into_untyped_uuid());
+            if backend.frontend_vmm_id != wanted {
+                if backend.frontend_vmm_id.is_some() {
+                    let errors = withdraw_frontend(
+                        &self.datastore,
+                        opctx,
+                        frontend_ip,
+                        std::slice::from_ref(&backend),
+                    )
+                    .await;
+                    if errors.is_empty() {
+                        backend.frontend_vmm_id = None;
+                    }
+                    status.errors.extend(errors);
+                }
+                if let (None, Some(target)) =
+                    (backend.frontend_vmm_id, serving_vmm)
+                {
+                    match push_frontend(log, target, frontend_ip).await {
+                        Ok(()) => backend.frontend_vmm_id = wanted,
+                        Err(e) => status.errors.push(e),
+                    }
+                }
+            }
+
+            // If the NAT entries weren't updated, the ports we recorded last
+            // time are still the ones the switches forward to this backend.
+            if nat_synced {
+                backend.ports = assignments
+                    .iter()
+                    .filter(|(_, target)| {
+                        target.instance_id == backend.instance_id
+                    })
+                    .map(|(port, _)| SqlU16::from(*port))
+                    .collect();
+            }
+
+            if backend_state_changed(&original, &backend) {
+                if let Err(e) = self
+                    .datastore
+                    .load_balancer_backend_update_state(opctx, &backend)
+                    .await
+                {
+                    status.errors.push(format!(
+                        "failed to record state of backend {}: {}",
+                        backend.instance_id,
+                        InlineErrorChain::new(&e)
+                    ));
+                }
+            }
+        }
+
+        status
+    }
+
+    /// Tell the switches that the set of NAT entries has changed.
+    async fn notify_dendrite(
+        &self,
+        opctx: &OpContext,
+        errors: &mut Vec<String>,
+    ) {
+        let log = &opctx.log;
+        let clients = match dpd_clients(&self.resolver, log).await {
+            Ok(clients) => clients,
+            Err(e) => {
+                errors.push(format!(
+                    "failed to resolve addresses for Dendrite services: {e}"
+                ));
+                return;
+            }
+        };
+        for (_location, client) in clients {
+            if let Err(e) = client.nat_trigger_update().await {
+                error!(
+                    log,
+                    "failed to trigger dpd rpw workflow";
+                    "error" => ?e
+                );
+            }
+        }
+    }
+}
+
+impl BackgroundTask for LoadBalancerManager {
+    fn activate<'a>(
+        &'a mut self,
+        opctx: &'a OpContext,
+    ) -> BoxFuture<'a, serde_json::Value> {
+        Box::pin(async move {
+            let mut status = LoadBalancerManagerStatus::default();
+
+            let load_balancers = match self
+                .datastore
+                .load_balancers_list_all_batched(opctx)
+                .await
+            {
+                Ok(load_balancers) => load_balancers,
+                Err(e) => {
+                    let error = InlineErrorChain::new(&e);
+                    error!(
+                        opctx.log,
+                        "failed to list load balancers";
+                        &error,
+                    );
+                    status.error =
+                        Some(format!("failed to list load balancers: {error}"));
+                    return serde_json::json!(status);
+                }
+            };
+
+            for (load_balancer, frontend) in &load_balancers {
+                let lb_status =
+                    self.reconcile(opctx, load_balancer, frontend).await;
+                for error in &lb_status.errors {
+                    warn!(
+                        opctx.log,
+                        "error reconciling load balancer";
+                        "load_balancer_id" => %lb_status.load_balancer_id,
+                        "error" => error,
+                    );
+                }
+                status.load_balancers.push(lb_status);
+            }
+
+            serde_json::json!(status)
+        })
+    }
+}
+
+/// Attempt to open a TCP connection to `port` on the backend's private
+/// address
+async fn check_backend(target: &LoadBalancerBackendTarget, port: u16) -> bool {
+    let addr = SocketAddr::new(target.private_ip, port);
+    matches!(
+        tokio::time::timeout(
+            HEALTH_CHECK_TIMEOUT,
+            tokio::net::TcpStream::connect(addr)
+        )
+        .await,
+        Ok(Ok(_))
+    )
+}
+
+/// Record the result of one health check of `backend`
+fn record_health_check(
+    backend: &mut LoadBalancerBackend,
+    check: &LoadBalancerHealthCheck,
+    passed: bool,
+) {
+    if passed {
+        backend.consecutive_successes =
+            backend.consecutive_successes.saturating_add(1);
+        backend.consecutive_failures = 0;
+        if backend.consecutive_successes >= i32::from(check.healthy_threshold) {
+            backend.health = LoadBalancerBackendHealth::Healthy;
+        }
+    } else {
+        backend.consecutive_failures =
+            backend.consecutive_failures.saturating_add(1);
+        backend.consecutive_successes = 0;
+        if backend.consecutive_failures >= i32::from(check.unhealthy_threshold)
+        {
+            backend.health = LoadBalancerBackendHealth::Unhealthy;
+        }
+    }
+}
+
+/// Assign each of `ports` to one of `backends`
+///
+/// The ports are divided evenly: every backend gets either `ports / backends`
+/// ports or one more, so that all backends serve when there are at least as
+/// many ports as backends. Within that bound, each port goes to the backend
+/// with the highest hash of the port and the backend's instance ID that still
+/// has room (rendezvous hashing with bounded loads), so that adding or
+/// removing a backend moves few of the other backends' ports.
+fn assign_ports<'a>(
+    ports: &[u16],
+    backends: &[&'a LoadBalancerBackendTarget],
+) -> Vec<(u16, &'a LoadBalancerBackendTarget)> {
+    if backends.is_empty() {
+        return Vec::new();
+    }
+    let share = ports.len() / backends.len();
+    let mut extra = ports.len() % backends.len();
+    let mut load = vec![0; backends.len()];
+    ports
+        .iter()
+        .map(|&port| {
+            let mut ranked = (0..backends.len()).collect::<Vec<_>>();
+            ranked.sort_by_key(|&i| {
+                let mut hasher = DefaultHasher::new();
+                port.hash(&mut hasher);
+                backends[i].instance_id.hash(&mut hasher);
+                std::cmp::Reverse((hasher.finish(), backends[i].instance_id))
+            });
+            // The total room left always equals the number of ports left, so
+            // some backend has room.
+            let i = ranked
+                .into_iter()
+                .find(|&i| load[i] < share || (load[i] == share && extra > 0))
+                .expect("some backend has room for the port");
+            if load[i] == share {
+                extra -= 1;
+            }
+            load[i] += 1;
+            (port, backends[i])
+        })
+        .collect()
+}
+
+/// Add the frontend address `ip` to the OPTE port of `target`'s VMM
+async fn push_frontend(
+    log: &slog::Logger,
+    target: &LoadBalancerBackendTarget,
+    ip: std::net::IpAddr,
+) -> Result<(), String> {
+    let client = nexus_networking::sled_client_from_address(
+        target.sled_id,
+        target.sled_agent_address,
+        log,
+    );
+    client
+        .vmm_put_external_ip(
+            &target.propolis_id,
+            &InstanceExternalIpBody::Floating(ip),
+        )
+        .await
+        .map(|_| ())
+        .map_err(|e| {
+            format!(
+                "failed to add load balancer address {ip} to VMM {}: {}",
+                target.propolis_id,
+                InlineErrorChain::new(&e)
+            )
+        })
+}
+
+fn backend_state_changed(
+    original: &LoadBalancerBackend,
+    backend: &LoadBalancerBackend,
+) -> bool {
+    original.health != backend.health
+        || original.consecutive_successes != backend.consecutive_successes
+        || original.consecutive_failures != backend.consecutive_failures
+        || original.frontend_vmm_id != backend.frontend_vmm_id
+        || original.ports != backend.ports
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use nexus_db_model::MacAddr;
+    use nexus_db_model::Vni;
+    use omicron_uuid_kinds::PropolisUuid;
+    use omicron_uuid_kinds::SledUuid;
+    use std::net::Ipv6Addr;
+    use std::net::SocketAddrV6;
+
+    fn target() -> LoadBalancerBackendTarget {
+        LoadBalancerBackendTarget {
+            instance_id: Uuid::new_v4(),
+            propolis_id: PropolisUuid::new_v4(),
+            sled_id: SledUuid::new_v4(),
+            sled_agent_address: SocketAddrV6::new(Ipv6Addr::LOCALHOST, 0, 0, 0),
+            private_ip: "172.30.0.5".parse().unwrap(),
+            mac: MacAddr(omicron_common::api::external::MacAddr::random_guest()),
+            vni: Vni(omicron_common::api::external::Vni::random()),
+        }
+    }
+
+    #[test]
+    fn test_record_health_check() {
+        let check = LoadBalancerHealthCheck {
+            port: 80,
+            healthy_threshold: 2,
+            unhealthy_threshold: 3,
+        };
+        let mut backend =
+            LoadBalancerBackend::new(Uuid::new_v4(), Uuid::new_v4(), true);
+
+        record_health_check(&mut backend, &check, true);
+        assert_eq!(backend.health, LoadBalancerBackendHealth::Unknown);
+        record_health_check(&mut backend, &check, true);
+        assert_eq!(backend.health, LoadBalancerBackendHealth::Healthy);
+
+        // A healthy backend tolerates failures up to the threshold.
+        record_health_check(&mut backend, &check, false);
+        record_health_check(&mut backend, &check, false);
+        assert_eq!(backend.health, LoadBalancerBackendHealth::Healthy);
+        record_health_check(&mut backend, &check, false);
+        assert_eq!(backend.health, LoadBalancerBackendHealth::Unhealthy);
+
+        // And must pass again enough times to be healthy again.
+        record_health_check(&mut backend, &check, true);
+        assert_eq!(backend.health, LoadBalancerBackendHealth::Unhealthy);
+        record_health_check(&mut backend, &check, true);
+        assert_eq!(backend.health, LoadBalancerBackendHealth::Healthy);
+    }
+
+    #[test]
+    fn test_assign_ports_spreads_ports() {
+        assert!(assign_ports(&[80, 443], &[]).is_empty());
+
+        let targets = (0..4).map(|_| target()).collect::<Vec<_>>();
+        let all = targets.iter().collect::<Vec<_>>();
+        let count = |assignments: &[(u16, &LoadBalancerBackendTarget)]| {
+            let mut counts = BTreeMap::new();
+            for (_, target) in assignments {
+                *counts.entry(target.instance_id).or_insert(0) += 1;
+            }
+            counts
+        };
+
+        // Every backend serves, and the ports are divided evenly.
+        let ports = (1000..1010).collect::<Vec<_>>();
+        let assignments = assign_ports(&ports, &all);
+        assert_eq!(
+            assignments.iter().map(|(port, _)| *port).collect::<Vec<_>>(),
+            ports
+        );
+        let counts = count(&assignments);
+        assert_eq!(counts.len(), 4);
+        assert!(counts.values().all(|&n| n == 2 || n == 3), "{counts:?}");
+
+        // With fewer ports than backends, each port gets its own backend.
+        let counts = count(&assign_ports(&[80, 443], &all));
+        assert_eq!(counts.len(), 2);
+        assert!(counts.values().all(|&n| n == 1));
+
+        // Removing a backend moves its ports to the others, which stay
+        // balanced.
+        let ports = (1000..1100).collect::<Vec<_>>();
+        let before = assign_ports(&ports, &all);
+        let removed = targets[0].instance_id;
+        let after = assign_ports(&ports, &all[1..]);
+        let counts = count(&after);
+        assert!(!counts.contains_key(&removed));
+        assert!(counts.values().all(|&n| n == 33 || n == 34), "{counts:?}");
+
+        // And the assignment is deterministic.
+        let again = assign_ports(&ports, &all);
+        for ((_, a), (_, b)) in before.iter().zip(&again) {
+            assert_eq!(a.instance_id, b.instance_id);
+        }
+    }
+}
@@ -5,20 +5,24 @@
 //! Implementations of specific background tasks
 
 pub mod abandoned_vmm_reaper;
+pub mod acme_certificates;
 pub mod alert_dispatcher;
 pub mod attached_subnets;
 pub mod audit_log_cleanup;
+pub mod audit_log_export;
 pub mod audit_log_timeout_incomplete;
 pub mod bfd;
 pub mod blueprint_execution;
 pub mod blueprint_load;
 pub mod blueprint_planner;
 pub mod blueprint_rendezvous;
+pub mod certificate_expiry;
 pub mod crdb_node_id_collector;
 pub mod decommissioned_disk_cleaner;
 pub mod dns_config;
 pub mod dns_propagation;
 pub mod dns_servers;
+pub mod dnssec_keys;
 pub mod ereport_ingester;
 pub mod external_endpoints;
 pub mod fm_analysis;
@@ -30,6 +34,7 @@ pub mod instance_updater;
 pub mod instance_watcher;
 pub mod inventory_collection;
 pub mod inventory_load;
+pub mod load_balancer_manager;
 pub mod lookup_region_port;
 pub mod metrics_producer_gc;
 pub mod multicast;
@@ -50,6 +55,7 @@ pub mod region_snapshot_replacement_step;
 pub mod saga_recovery;
 pub mod service_firewall_rules;
 pub mod session_cleanup;
+pub mod sled_evacuator;
 pub mod support_bundle_collector;
 pub mod sync_service_zone_nat;
 pub mod sync_switch_configuration;
@@ -57,5 +63,6 @@ pub mod trust_quorum;
 pub mod tuf_artifact_replication;
 pub mod tuf_repo_pruner;
 pub mod v2p_mappings;
+pub mod vpc_dns;
 pub mod vpc_routes;
 pub mod webhook_deliverator;
@@ -0,0 +1,509 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Background task for migrating instances off of sleds being evacuated.
+//!
+//! An operator starts an evacuation of a sled through the external API, which
+//! marks the sled non-provisionable and records the evacuation in the
+//! database. Each activation of this task then starts instance-migrate sagas
+//! for the running instances that remain on each sled being evacuated, up to
+//! a per-sled concurrency limit. The sagas choose each instance's destination
+//! through the usual sled reservation path, so affinity and anti-affinity
+//! groups and sled capacity are respected. Once no instances remain on the
+//! sled, the evacuation is marked completed.
+//!
+//! Instances that fail to migrate (e.g., because no other sled has room for
+//! them) are retried on subsequent activations until the evacuation is either
+//! completed or cancelled.
+
+use crate::app::background::Activator;
+use crate::app::background::BackgroundTask;
+use crate::app::instance::instance_migrate_saga_params;
+use crate::app::saga::SagaCompletionFuture;
+use crate::app::saga::StartSaga;
+use crate::app::sagas::NexusSaga;
+use crate::app::sagas::instance_migrate;
+use futures::future::BoxFuture;
+use nexus_db_lookup::LookupPath;
+use nexus_db_model::SledEvacuation;
+use nexus_db_model::SledInstance;
+use nexus_db_model::VmmState;
+use nexus_db_queries::authz;
+use nexus_db_queries::context::OpContext;
+use nexus_db_queries::db::DataStore;
+use nexus_db_queries::db::datastore::SQL_BATCH_SIZE;
+use nexus_db_queries::db::pagination::Paginator;
+use nexus_types::internal_api::background::SledEvacuationStatus;
+use nexus_types::internal_api::background::SledEvacuatorStatus;
+use omicron_common::api::external::Error;
+use omicron_common::api::external::LookupType;
+use std::num::NonZeroU32;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct SledEvacuator {
+    datastore: Arc<DataStore>,
+    sagas: Arc<dyn StartSaga>,
+    /// The maximum number of instances migrating off of each sled at once.
+    max_concurrent_migrations: NonZeroU32,
+    /// Activator for the VPC route manager background task, which is
+    /// activated when instances have moved to new sleds.
+    task_vpc_route_manager: Activator,
+    /// Activator for the multicast reconciler background task, which is
+    /// activated when instances have moved to new sleds.
+    task_multicast_reconciler: Activator,
+}
+
+impl BackgroundTask for SledEvacuator {
+    fn activate<'a>(
+        &'a mut self,
+        opctx: &'a OpContext,
+    ) -> BoxFuture<'a, serde_json::Value> {
+        Box::pin(async move {
+            let mut status = SledEvacuatorStatus::default();
+
+            let evacuations = match self
+                .datastore
+                .sled_evacuation_list_in_progress(opctx)
+                .await
+            {
+                Ok(evacuations) => evacuations,
+                Err(error) => {
+                    error!(
+                        opctx.log,
+                        "failed to list in-progress sled evacuations";
+                        "error" => %error,
+                    );
+                    status.error = Some(format!(
+                        "failed to list in-progress sled evacuations: {error}"
+                    ));
+                    return serde_json::json!(status);
+                }
+            };
+
+            for evacuation in evacuations {
+                let evacuation_status = self.evacuate(opctx, evacuation).await;
+                status.evacuations.push(evacuation_status);
+            }
+
+            if status
+                .evacuations
+                .iter()
+                .any(|evacuation| !evacuation.instances_migrated.is_empty())
+            {
+                self.task_vpc_route_manager.activate();
+                self.task_multicast_reconciler.activate();
+            }
+
+            serde_json::json!(status)
+        })
+    }
+}
+
+impl SledEvacuator {
+    pub(crate) fn new(
+        datastore: Arc<DataStore>,
+        sagas: Arc<dyn StartSaga>,
+        max_concurrent_migrations: NonZeroU32,
+        task_vpc_route_manager: Activator,
+        task_multicast_reconciler: Activator,
+    ) -> Self {
+        Self {
+            datastore,
+            sagas,
+            max_concurrent_migrations,
+            task_vpc_route_manager,
+            task_multicast_reconciler,
+        }
+    }
+
+    /// Makes progress on one evacuation, returning a summary of what was done
+    async fn evacuate(
+        &self,
+        opctx: &OpContext,
+        evacuation: SledEvacuation,
+    ) -> SledEvacuationStatus {
+        let sled_id = evacuation.sled_id();
+        let log = opctx.log.new(o!(
+            "evacuation_id" => evacuation.id.to_string(),
+            "sled_id" => sled_id.to_string(),
+        ));
+        let mut status = SledEvacuationStatus {
+            evacuation_id: evacuation.id,
+            sled_id,
+            instances_remaining: 0,
+            instances_migrating: 0,
+            instances_migrated: Vec::new(),
+            completed: false,
+            errors: Vec::new(),
+        };
+
+        let instances = match self.list_sled_instances(opctx, &evacuation).await
+        {
+            Ok(instances) => instances,
+            Err(error) => {
+                error!(
+                    &log,
+                    "failed to list instances on evacuated sled";
+                    "error" => %error,
+                );
+                status.errors.push(format!(
+                    "failed to list instances on sled {sled_id}: {error}"
+                ));
+                return status;
+            }
+        };
+        status.instances_remaining = instances.len();
+        status.instances_migrating = instances
+            .iter()
+            .filter(|instance| instance.migration_id.is_some())
+            .count();
+
+        if instances.is_empty() {
+            match self
+                .datastore
+                .sled_evacuation_complete(opctx, evacuation.id)
+                .await
+            {
+                Ok(completed) => {
+                    // If the evacuation wasn't updated, it was cancelled
+                    // after we listed it, which is fine.
+                    status.completed = completed;
+                    if completed {
+                        info!(&log, "sled evacuation completed");
+                    }
+                }
+                Err(error) => {
+                    error!(
+                        &log,
+                        "failed to mark sled evacuation completed";
+                        "error" => %error,
+                    );
+                    status.errors.push(format!(
+                        "failed to mark evacuation completed: {error}"
+                    ));
+                }
+            }
+            return status;
+        }
+
+        // Instances that are already migrating count against the limit, so
+        // that only a bounded number of migrations are ever in flight from
+        // the sled, even across activations.
+        let budget = usize::try_from(self.max_concurrent_migrations.get())
+            .unwrap_or(usize::MAX)
+            .saturating_sub(status.instances_migrating);
+        let candidates = instances
+            .iter()
+            .filter(|instance| {
+                instance.migration_id.is_none()
+                    && instance.state == VmmState::Running
+            })
+            .take(budget);
+
+        let mut running_sagas = Vec::new();
+        for instance in candidates {
+            let instance_id = instance.instance_id();
+            match self.start_migration(opctx, instance_id).await {
+                Ok(saga) => running_sagas.push((instance_id, saga)),
+                Err(error) => {
+                    warn!(
+                        &log,
+                        "failed to start migrating instance off of sled";
+                        "instance_id" => %instance_id,
+                        "error" => %error,
+                    );
+                    status.errors.push(format!(
+                        "failed to migrate instance {instance_id}: {error}"
+                    ));
+                }
+            }
+        }
+
+        let mut migrations_started = 0;
+        for (instance_id, saga) in running_sagas {
+            match saga.await {
+                Ok(()) => {
+                    info!(
+                        &log,
+                        "started migrating instance off of sled";
+                        "instance_id" => %instance_id,
+                    );
+                    migrations_started += 1;
+                    status.instances_migrated.push(instance_id);
+                }
+                Err(error) => {
+                    warn!(
+                        &log,
+                        "instance-migrate saga failed";
+                        "instance_id" => %instance_id,
+                        "error" => %error,
+                    );
+                    status.errors.push(format!(
+                        "failed to migrate instance {instance_id}: {error}"
+                    ));
+                }
+            }
+        }
+
+        if let Err(error) = self
+            .datastore
+            .sled_evacuation_record_progress(
+                opctx,
+                evacuation.id,
+                migrations_started,
+                status.errors.last().cloned(),
+            )
+            .await
+        {
+            error!(
+                &log,
+                "failed to record sled evacuation progress";
+                "error" => %error,
+            );
+            status
+                .errors
+                .push(format!("failed to record evacuation progress: {error}"));
+        }
+
+        status
+    }
+
+    /// Lists the instances whose active VMM is on the evacuated sled
+    async fn list_sled_instances(
+        &self,
+        opctx: &OpContext,
+        evacuation: &SledEvacuation,
+    ) -> Result<Vec<SledInstance>, Error> {
+        let sled_id = evacuation.sled_id();
+        let authz_sled =
+            authz::Sled::new(authz::FLEET, sled_id, LookupType::by_id(sled_id));
+
+        let mut instances = Vec::new();
+        let mut paginator = Paginator::new(
+            SQL_BATCH_SIZE,
+            dropshot::PaginationOrder::Ascending,
+        );
+        while let Some(p) = paginator.next() {
+            let batch = self
+                .datastore
+                .sled_instance_list(opctx, &authz_sled, &p.current_pagparams())
+                .await?;
+            paginator = p.found_batch(&batch, &|instance| instance.id);
+            instances.extend(batch);
+        }
+        Ok(instances)
+    }
+
+    /// Starts an instance-migrate saga that moves `instance_id` to whichever
+    /// sled the saga selects, returning a future that completes with the saga
+    async fn start_migration(
+        &self,
+        opctx: &OpContext,
+        instance_id: Uuid,
+    ) -> Result<SagaCompletionFuture, Error> {
+        let (.., authz_instance) = LookupPath::new(opctx, &self.datastore)
+            .instance_id(instance_id)
+            .lookup_for(authz::Action::Modify)
+            .await?;
+        let params = instance_migrate_saga_params(
+            opctx,
+            &self.datastore,
+            &authz_instance,
+            None,
+        )
+        .await?;
+        let dag = instance_migrate::SagaInstanceMigrate::prepare(&params)?;
+        let (_, completion) = self.sagas.saga_run(dag).await?;
+        Ok(completion)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::sagas::test_helpers;
+    use nexus_db_queries::db::model::SledEvacuationState;
+    use nexus_test_utils::resource_helpers::{
+        create_default_ip_pools, create_project, object_create,
+    };
+    use nexus_test_utils_macros::nexus_test;
+    use nexus_types::external_api::instance;
+    use nexus_types_versions::latest;
+    use omicron_common::api::external::ByteCount;
+    use omicron_common::api::external::IdentityMetadataCreateParams;
+    use omicron_common::api::external::InstanceCpuCount;
+    use omicron_uuid_kinds::GenericUuid;
+    use omicron_uuid_kinds::InstanceUuid;
+    use omicron_uuid_kinds::PropolisUuid;
+
+    type ControlPlaneTestContext =
+        nexus_test_utils::ControlPlaneTestContext<crate::Server>;
+
+    const PROJECT_NAME: &str = "evacuation-project";
+
+    async fn create_running_instance(
+        cptestctx: &ControlPlaneTestContext,
+        name: &str,
+    ) -> InstanceUuid {
+        let instance: latest::instance::Instance = object_create(
+            &cptestctx.external_client,
+            &format!("/v1/instances?project={PROJECT_NAME}"),
+            &instance::InstanceCreate {
+                identity: IdentityMetadataCreateParams {
+                    name: name.parse().unwrap(),
+                    description: format!("instance {name:?}"),
+                },
+                ncpus: InstanceCpuCount(2),
+                memory: ByteCount::from_gibibytes_u32(2),
+                hostname: name.parse().unwrap(),
+                user_data: Vec::new(),
+                ssh_public_keys: None,
+                network_interfaces:
+                    instance::InstanceNetworkInterfaceAttachment::None,
+                external_ips: vec![],
+                disks: vec![],
+                boot_disk: None,
+                cpu_platform: None,
+                start: true,
+                auto_restart_policy: Default::default(),
+                anti_affinity_groups: Vec::new(),
+                multicast_groups: Vec::new(),
+                enable_jumbo_frames: false,
+            },
+        )
+        .await;
+        let instance_id = InstanceUuid::from_untyped_uuid(instance.identity.id);
+        test_helpers::instance_simulate(cptestctx, &instance_id).await;
+        instance_id
+    }
+
+    #[nexus_test(server = crate::Server, extra_sled_agents = 1)]
+    async fn test_evacuates_running_instances(
+        cptestctx: &ControlPlaneTestContext,
+    ) {
+        let nexus = &cptestctx.server.server_context().nexus;
+        let datastore = nexus.datastore();
+        let opctx = test_helpers::test_opctx(cptestctx);
+        create_default_ip_pools(&cptestctx.external_client).await;
+        create_project(&cptestctx.external_client, PROJECT_NAME).await;
+
+        let instance_id = create_running_instance(cptestctx, "evacuee").await;
+        let state = test_helpers::instance_fetch(cptestctx, instance_id).await;
+        let src_sled_id = state.vmm().as_ref().unwrap().sled_id();
+
+        let sled_lookup = nexus
+            .sled_lookup(&opctx, &src_sled_id)
+            .expect("sled lookup should succeed");
+        let evacuation = nexus
+            .sled_evacuation_start(&opctx, &sled_lookup)
+            .await
+            .expect("should start evacuation");
+        assert_eq!(evacuation.instances_total, 1);
+
+        let mut task = SledEvacuator::new(
+            datastore.clone(),
+            nexus.sagas.clone(),
+            NonZeroU32::new(4).unwrap(),
+            Activator::new(),
+            Activator::new(),
+        );
+
+        // The first activation should migrate the instance to the other sled.
+        let status = serde_json::from_value::<SledEvacuatorStatus>(
+            task.activate(&opctx).await,
+        )
+        .expect("status should deserialize");
+        assert_eq!(status.error, None);
+        assert_eq!(status.evacuations.len(), 1);
+        let evacuation_status = &status.evacuations[0];
+        assert_eq!(evacuation_status.sled_id, src_sled_id);
+        assert_eq!(evacuation_status.errors, Vec::<String>::new());
+        assert_eq!(
+            evacuation_status.instances_migrated,
+            vec![instance_id.into_untyped_uuid()]
+        );
+        assert!(!evacuation_status.completed);
+
+        let state = test_helpers::instance_fetch(cptestctx, instance_id).await;
+        let dst_propolis_id = PropolisUuid::from_untyped_uuid(
+            state
+                .instance()
+                .runtime()
+                .dst_propolis_id
+                .expect("instance should be migrating"),
+        );
+        let dst_vmm = datastore
+            .vmm_fetch(&opctx, &dst_propolis_id)
+            .await
+            .expect("target VMM should exist");
+        assert_ne!(dst_vmm.sled_id(), src_sled_id);
+
+        // While the instance is migrating, another activation should leave it
+        // alone.
+        let status = serde_json::from_value::<SledEvacuatorStatus>(
+            task.activate(&opctx).await,
+        )
+        .expect("status should deserialize");
+        let evacuation_status = &status.evacuations[0];
+        assert_eq!(evacuation_status.instances_migrating, 1);
+        assert!(evacuation_status.instances_migrated.is_empty());
+        assert!(!evacuation_status.completed);
+
+        let evacuation = nexus
+            .sled_evacuation_view(&opctx, &sled_lookup)
+            .await
+            .expect("should fetch evacuation");
+        assert_eq!(evacuation.migrations_started, 1);
+        assert_eq!(evacuation.instances_migrating, 1);
+    }
+
+    #[nexus_test(server = crate::Server)]
+    async fn test_completes_empty_evacuation(
+        cptestctx: &ControlPlaneTestContext,
+    ) {
+        let nexus = &cptestctx.server.server_context().nexus;
+        let datastore = nexus.datastore();
+        let opctx = test_helpers::test_opctx(cptestctx);
+
+        let sled_lookup = nexus
+            .sled_lookup(&opctx, &cptestctx.first_sled_id())
+            .expect("sled lookup should succeed");
+        nexus
+            .sled_evacuation_start(&opctx, &sled_lookup)
+            .await
+            .expect("should start evacuation");
+
+        let mut task = SledEvacuator::new(
+            datastore.clone(),
+            nexus.sagas.clone(),
+            NonZeroU32::new(4).unwrap(),
+            Activator::new(),
+            Activator::new(),
+        );
+        let status = serde_json::from_value::<SledEvacuatorStatus>(
+            task.activate(&opctx).await,
+        )
+        .expect("status should deserialize");
+        assert_eq!(status.evacuations.len(), 1);
+        assert!(status.evacuations[0].completed);
+
+        let (.., authz_sled) = sled_lookup
+            .lookup_for(authz::Action::Read)
+            .await
+            .expect("sled should exist");
+        let evacuation = datastore
+            .sled_evacuation_fetch_latest(&opctx, &authz_sled)
+            .await
+            .expect("should fetch evacuation");
+        assert_eq!(evacuation.state, SledEvacuationState::Completed);
+
+        // Once completed, there is nothing left to do.
+        let status = serde_json::from_value::<SledEvacuatorStatus>(
+            task.activate(&opctx).await,
+        )
+        .expect("status should deserialize");
+        assert!(status.evacuations.is_empty());
+    }
+}
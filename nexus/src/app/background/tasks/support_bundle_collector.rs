@@ -4,6 +4,8 @@
 
 //! Background task for managing Support Bundles
 
+use crate::app::alert::publish_alert;
+use crate::app::background::Activator;
 use crate::app::background::BackgroundTask;
 use anyhow::Context;
 use camino::Utf8Path;
@@ -17,12 +19,14 @@ use nexus_db_model::SupportBundleState;
 use nexus_db_queries::authz;
 use nexus_db_queries::context::OpContext;
 use nexus_db_queries::db::DataStore;
+use nexus_types::alert::support_bundle::SupportBundleReady;
 use nexus_types::internal_api::background::SupportBundleCleanupReport;
 use nexus_types::internal_api::background::SupportBundleCollectionReport;
 use omicron_common::api::external::DataPageParams;
 use omicron_common::api::external::Error;
 use omicron_common::api::external::LookupType;
 use omicron_common::api::external::ResourceType;
+use omicron_uuid_kinds::AlertUuid;
 use omicron_uuid_kinds::DatasetUuid;
 use omicron_uuid_kinds::OmicronZoneUuid;
 use omicron_uuid_kinds::SledUuid;
@@ -78,6 +82,7 @@ pub struct SupportBundleCollector {
     disable: bool,
     nexus_id: OmicronZoneUuid,
     transfer_chunk_size: NonZeroU64,
+    alert_dispatcher: Activator,
 }
 
 impl SupportBundleCollector {
@@ -86,6 +91,7 @@ impl SupportBundleCollector {
         resolver: Resolver,
         disable: bool,
         nexus_id: OmicronZoneUuid,
+        alert_dispatcher: Activator,
     ) -> Self {
         SupportBundleCollector {
             datastore,
@@ -93,6 +99,7 @@ impl SupportBundleCollector {
             disable,
             nexus_id,
             transfer_chunk_size: CHUNK_SIZE,
+            alert_dispatcher,
         }
     }
 
@@ -470,6 +477,20 @@ impl SupportBundleCollector {
             }
         }
         report.activated_in_db_ok = true;
+
+        let alert = SupportBundleReady {
+            bundle_id: bundle.id.into(),
+            reason_for_creation: bundle.reason_for_creation.clone(),
+        };
+        publish_alert(
+            opctx,
+            &self.datastore,
+            &self.alert_dispatcher,
+            AlertUuid::new_v4(),
+            &alert,
+        )
+        .await;
+
         Ok(Some(report))
     }
 
@@ -757,6 +778,7 @@ mod test {
             resolver.clone(),
             false,
             nexus.id(),
+            Activator::new(),
         );
 
         let report = collector
@@ -783,6 +805,7 @@ mod test {
             resolver.clone(),
             false,
             nexus.id(),
+            Activator::new(),
         );
 
         let report = collector
@@ -1102,6 +1125,7 @@ mod test {
             resolver.clone(),
             false,
             nexus.id(),
+            Activator::new(),
         );
 
         // The bundle collection should complete successfully.
@@ -1182,6 +1206,7 @@ mod test {
             resolver.clone(),
             false,
             nexus.id(),
+            Activator::new(),
         );
 
         // Collect the bundle
@@ -1296,6 +1321,7 @@ mod test {
             resolver.clone(),
             false,
             nexus.id(),
+            Activator::new(),
         )
         .with_transfer_chunk_size(NonZeroU64::new(16).unwrap());
 
@@ -1403,6 +1429,7 @@ mod test {
             resolver.clone(),
             false,
             nexus.id(),
+            Activator::new(),
         );
 
         // Each time we call "collect_bundle", we collect a SINGLE bundle.
@@ -1519,6 +1546,7 @@ mod test {
             resolver.clone(),
             false,
             nexus.id(),
+            Activator::new(),
         );
 
         let report = collector
@@ -1577,6 +1605,7 @@ mod test {
             resolver.clone(),
             false,
             nexus.id(),
+            Activator::new(),
         );
         let report = collector
             .collect_bundle(&opctx)
@@ -1678,6 +1707,7 @@ mod test {
             resolver.clone(),
             false,
             nexus.id(),
+            Activator::new(),
         );
 
         let report = collector
@@ -1739,6 +1769,7 @@ mod test {
             resolver.clone(),
             false,
             nexus.id(),
+            Activator::new(),
         );
         let report = collector
             .collect_bundle(&opctx)
@@ -1827,6 +1858,7 @@ mod test {
             resolver.clone(),
             false,
             nexus.id(),
+            Activator::new(),
         );
         let report = collector
             .collect_bundle(&opctx)
@@ -1914,6 +1946,7 @@ mod test {
             resolver.clone(),
             false,
             nexus.id(),
+            Activator::new(),
         );
 
         // Collect the bundle
@@ -2020,6 +2053,7 @@ mod test {
             resolver.clone(),
             false,
             nexus.id(),
+            Activator::new(),
         );
 
         let report = collector
@@ -0,0 +1,136 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Background task for maintaining VPCs' private DNS zones
+//!
+//! Each activation computes the zones from the instances' network interfaces
+//! and external addresses, along with users' records, stores them (advancing
+//! their generation if they changed), and propagates them to the external DNS
+//! servers. Every Nexus does this, so the stored zones converge on the current
+//! state of the database even if a Nexus stores zones computed from a stale
+//! read.
+//!
+//! The zones are served by the external DNS servers rather than from within
+//! the VPC, so each zone is scoped to the external addresses its instances'
+//! queries come from. Instances use those servers as their resolver, so the
+//! servers forward their queries for other names to the rack's upstream DNS
+//! servers, which are stored and propagated along with the zones. (They only
+//! do so over TCP, where the source address can't be spoofed.)
+//!
+//! Instances reach the servers at their external addresses, so an instance
+//! without any external address can't use its VPC's zone at all until OPTE
+//! can deliver guest DNS traffic to a rack-internal address. Such instances
+//! aren't given the servers as resolvers.
+
+use super::dns_servers::DnsServersList;
+use crate::app::background::BackgroundTask;
+use futures::future::BoxFuture;
+use futures::future::join_all;
+use internal_dns_types::config::VpcDnsConfigParams;
+use nexus_db_queries::context::OpContext;
+use nexus_db_queries::db::DataStore;
+use nexus_types::internal_api::background::VpcDnsStatus;
+use slog_error_chain::InlineErrorChain;
+use std::net::IpAddr;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::sync::watch;
+
+pub struct VpcDnsManager {
+    datastore: Arc<DataStore>,
+    rx_servers: watch::Receiver<Option<DnsServersList>>,
+    forwarders: Vec<IpAddr>,
+}
+
+impl VpcDnsManager {
+    pub fn new(
+        datastore: Arc<DataStore>,
+        rx_servers: watch::Receiver<Option<DnsServersList>>,
+        forwarders: Vec<IpAddr>,
+    ) -> Self {
+        Self { datastore, rx_servers, forwarders }
+    }
+}
+
+impl BackgroundTask for VpcDnsManager {
+    fn activate<'a>(
+        &'a mut self,
+        opctx: &'a OpContext,
+    ) -> BoxFuture<'a, serde_json::Value> {
+        Box::pin(async move {
+            let mut status = VpcDnsStatus::default();
+
+            let config = match self.datastore.vpc_dns_zones(opctx).await {
+                Ok(zones) => {
+                    self.datastore
+                        .vpc_dns_config_update(opctx, zones, &self.forwarders)
+                        .await
+                }
+                Err(e) => Err(e),
+            };
+            let config = match config {
+                Ok(config) => config,
+                Err(e) => {
+                    let error = InlineErrorChain::new(&e);
+                    error!(
+                        opctx.log,
+                        "failed to update VPC DNS zones";
+                        &error,
+                    );
+                    status.error = Some(format!(
+                        "failed to update VPC DNS zones: {error}"
+                    ));
+                    return serde_json::json!(status);
+                }
+            };
+            status.generation = Some(config.generation);
+            status.zones = config.zones.len();
+
+            // Clone the server list rather than hold the borrow, which would
+            // block the task that maintains it.
+            let Some(servers) = self.rx_servers.borrow().clone() else {
+                warn!(opctx.log, "VPC DNS propagation skipped: no servers");
+                status.error = Some(String::from("no servers"));
+                return serde_json::json!(status);
+            };
+
+            let results = join_all(
+                servers
+                    .addresses
+                    .iter()
+                    .map(|addr| propagate_one(&opctx.log, &config, *addr)),
+            )
+            .await;
+            for (addr, result) in servers.addresses.iter().zip(results) {
+                if let Err(error) = &result {
+                    warn!(
+                        opctx.log,
+                        "failed to propagate VPC DNS zones";
+                        "server" => %addr,
+                        "error" => error,
+                    );
+                }
+                status.server_results.insert(addr.to_string(), result);
+            }
+
+            serde_json::json!(status)
+        })
+    }
+}
+
+async fn propagate_one(
+    log: &slog::Logger,
+    config: &VpcDnsConfigParams,
+    server_addr: SocketAddr,
+) -> Result<(), String> {
+    let url = format!("http://{server_addr}");
+    let client = dns_service_client::Client::new(&url, log.clone());
+    client.vpc_dns_config_put(config).await.map(|_| ()).map_err(|e| {
+        format!(
+            "failed to propagate VPC DNS generation {}: {}",
+            config.generation,
+            InlineErrorChain::new(&e),
+        )
+    })
+}
@@ -2,12 +2,14 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-//! Background task that sends HTTP requests to webhook receiver endpoints for
-//! active webhook deliveries.
+//! Background task that delivers alerts to receivers for active deliveries.
 //!
 //! This task reads [`WebhookDelivery`] records from the database (created by the
-//! [`alert_dispatcher`] task) and sends HTTP requests to the receivers for
-//! those records.  The deliverator is responsible for recording the status of
+//! [`alert_dispatcher`] task) and sends the alerts to the receivers for those
+//! records.  Despite the task's name, it handles every kind of receiver: HTTP
+//! requests are sent to webhook receivers, email messages to SMTP receivers,
+//! and syslog messages to syslog receivers, using the [`DeliveryClient`] for
+//! each receiver's kind.  The deliverator is responsible for recording the status of
 //! each of these attempts, and for retrying failed attempts as needed.  For
 //! an overview of all the components of the webhook subsystem, their roles, and
 //! how they fit together, refer to the comments in the [`app::webhook`] module.
@@ -29,9 +31,10 @@
 //! [`WebhookDelivery`]: nexus_db_model::WebhookDelivery
 //! [`alert_dispatcher`]: super::alert_dispatcher
 //! [`app::webhook`]: crate::app::webhook
+//! [`DeliveryClient`]: crate::app::alert::DeliveryClient
 
+use crate::app::alert::DeliveryClient;
 use crate::app::background::BackgroundTask;
-use crate::app::webhook::ReceiverClient;
 use futures::future::BoxFuture;
 use nexus_db_queries::context::OpContext;
 use nexus_db_queries::db::DataStore;
@@ -216,7 +219,7 @@ impl WebhookDeliverator {
         WebhookReceiverConfig { rx, secrets, .. }: WebhookReceiverConfig,
     ) -> Result<WebhookRxDeliveryStatus, anyhow::Error> {
         let mut client =
-            ReceiverClient::new(&self.client, secrets, &rx, self.nexus_id)?;
+            DeliveryClient::new(&self.client, secrets, &rx, self.nexus_id)?;
 
         let deliveries = self
             .datastore
@@ -9,6 +9,7 @@ use nexus_db_lookup::lookup;
 use nexus_db_queries::authz;
 use nexus_db_queries::context::OpContext;
 use nexus_db_queries::db;
+use nexus_db_queries::db::identity::Resource;
 use nexus_db_queries::db::model::Name;
 use nexus_db_queries::db::model::ServiceKind;
 use nexus_types::external_api::certificate;
@@ -17,6 +18,7 @@ use omicron_common::api::external::DeleteResult;
 use omicron_common::api::external::InternalContext;
 use omicron_common::api::external::ListResultVec;
 use omicron_common::api::external::NameOrId;
+use omicron_common::api::external::ResourceType;
 use omicron_common::api::external::http_pagination::PaginatedBy;
 use ref_cast::RefCast;
 use uuid::Uuid;
@@ -75,6 +77,7 @@ impl super::Nexus {
             .db_datastore
             .certificate_create(opctx, new_certificate)
             .await?;
+        opctx.set_audit_target(ResourceType::Certificate, cert.id());
 
         match kind {
             certificate::ServiceUsingCertificate::ExternalApi => {
@@ -106,6 +109,7 @@ impl super::Nexus {
     ) -> DeleteResult {
         let (.., authz_cert, db_cert) =
             certificate_lookup.fetch_for(authz::Action::Delete).await?;
+        opctx.set_audit_target(ResourceType::Certificate, authz_cert.id());
         self.db_datastore.certificate_delete(opctx, &authz_cert).await?;
         match db_cert.service {
             ServiceKind::Nexus => {
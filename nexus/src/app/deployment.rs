@@ -4,12 +4,15 @@
 
 //! Configuration of the deployment system
 
+use crate::app::alert::publish_alert;
 use nexus_db_model::TargetReleaseSource;
 use nexus_db_queries::authz;
 use nexus_db_queries::context::OpContext;
 use nexus_reconfigurator_planning::planner::Planner;
 use nexus_reconfigurator_planning::planner::PlannerRng;
 use nexus_reconfigurator_preparation::PlanningInputFromDb;
+use nexus_types::alert::update::UpdateProgress;
+use nexus_types::alert::update::UpdateStatusChanged;
 use nexus_types::deployment::Blueprint;
 use nexus_types::deployment::BlueprintArtifactVersion;
 use nexus_types::deployment::BlueprintHostPhase2DesiredContents;
@@ -32,6 +35,7 @@ use omicron_common::api::external::InternalContext;
 use omicron_common::api::external::ListResultVec;
 use omicron_common::api::external::LookupResult;
 use omicron_common::api::external::LookupType;
+use omicron_uuid_kinds::AlertUuid;
 use omicron_uuid_kinds::SledUuid;
 use slog::Logger;
 use slog::warn;
@@ -293,6 +297,7 @@ impl super::Nexus {
         let current_target_release_source = current_target_release
             .release_source()
             .map_err(|err| Error::internal_error(&format!("{err:#}")))?;
+        let mut previous_system_version = None;
 
         match current_target_release_source {
             TargetReleaseSource::Unspecified => {
@@ -338,6 +343,7 @@ impl super::Nexus {
                     .datastore()
                     .tuf_repo_get_version(&opctx, &tuf_repo_id)
                     .await?;
+                previous_system_version = Some(current_version.clone());
                 let validation_result = match intent {
                     SetTargetReleaseIntent::Update => {
                         validate_can_set_target_release_for_update(
@@ -368,7 +374,7 @@ impl super::Nexus {
         // Fetch the TUF repo metadata and update the target release.
         let tuf_repo_id = self
             .datastore()
-            .tuf_repo_get_by_version(&opctx, new_system_version.into())
+            .tuf_repo_get_by_version(&opctx, new_system_version.clone().into())
             .await?
             .id;
         let next_target_release =
@@ -379,6 +385,24 @@ impl super::Nexus {
         self.datastore()
             .target_release_insert(&opctx, next_target_release)
             .await?;
+
+        // Recovering from a mupdate doesn't start an update, so there's
+        // nothing to alert anyone about.
+        if let SetTargetReleaseIntent::Update = intent {
+            let alert = UpdateStatusChanged {
+                status: UpdateProgress::Started,
+                system_version: new_system_version,
+                previous_system_version,
+            };
+            publish_alert(
+                opctx,
+                self.datastore(),
+                &self.background_tasks.task_alert_dispatcher,
+                AlertUuid::new_v4(),
+                &alert,
+            )
+            .await;
+        }
         Ok(())
     }
 }
@@ -47,7 +47,7 @@
 use dropshot::{Body, HttpError};
 use http::{Response, StatusCode, header};
 use nexus_db_lookup::LookupPath;
-use nexus_db_queries::authn::{Actor, Reason};
+use nexus_db_queries::authn::{self, Actor, Reason};
 use nexus_db_queries::authz;
 use nexus_db_queries::context::OpContext;
 use nexus_db_queries::db::model::{DeviceAccessToken, DeviceAuthRequest};
@@ -239,22 +239,25 @@ impl super::Nexus {
 
     /// Look up the actor for which a token was granted.
     /// Corresponds to a request *after* completing the flow above.
-    /// Returns the actor, the token's expiration time (if any), and the token ID.
+    /// Device access tokens are checked first, then service account tokens.
     pub(crate) async fn authenticate_token(
         &self,
         opctx: &OpContext,
         token: String,
-    ) -> Result<(Actor, Option<chrono::DateTime<Utc>>, Uuid), Reason> {
-        let (.., db_access_token) = self
+    ) -> Result<authn::Details, Reason> {
+        let db_access_token = match self
             .db_datastore
-            .device_token_lookup_by_token(opctx, token)
+            .device_token_lookup_by_token(opctx, token.clone())
             .await
-            .map_err(|e| match e {
-                Error::ObjectNotFound { .. } => Reason::UnknownActor {
-                    actor: "from device access token".to_string(),
-                },
-                e => Reason::UnknownError { source: e },
-            })?;
+        {
+            Ok((.., db_access_token)) => db_access_token,
+            Err(Error::ObjectNotFound { .. }) => {
+                return self
+                    .authenticate_service_account_token(opctx, token)
+                    .await;
+            }
+            Err(e) => return Err(Reason::UnknownError { source: e }),
+        };
 
         let silo_user_id = db_access_token.silo_user_id();
         let (.., db_silo_user) = LookupPath::new(opctx, &self.db_datastore)
@@ -271,22 +274,52 @@ impl super::Nexus {
 
         let expiration = db_access_token.time_expires;
         let token_id = db_access_token.id().into_untyped_uuid();
+        let actor = Actor::SiloUser { silo_user_id, silo_id };
 
-        if let Some(time_expires) = expiration {
-            let now = Utc::now();
-            if time_expires < now {
-                return Err(Reason::BadCredentials {
-                    actor: Actor::SiloUser { silo_user_id, silo_id },
-                    source: anyhow!(
-                        "token expired at {} (current time: {})",
-                        time_expires,
-                        now
-                    ),
-                });
-            }
-        }
+        check_token_expiration(actor, expiration)?;
 
-        Ok((Actor::SiloUser { silo_user_id, silo_id }, expiration, token_id))
+        Ok(authn::Details {
+            actor,
+            device_token_expiration: expiration,
+            credential_id: Some(token_id),
+            scope: None,
+        })
+    }
+
+    async fn authenticate_service_account_token(
+        &self,
+        opctx: &OpContext,
+        token: String,
+    ) -> Result<authn::Details, Reason> {
+        let (_, db_token, db_service_account) = self
+            .db_datastore
+            .service_account_token_lookup_by_token(opctx, token)
+            .await
+            .map_err(|e| match e {
+                Error::ObjectNotFound { .. } => Reason::UnknownActor {
+                    actor: "from access token".to_string(),
+                },
+                e => Reason::UnknownError { source: e },
+            })?;
+
+        let actor = Actor::ServiceAccount {
+            service_account_id: db_service_account.id(),
+            silo_id: db_service_account.silo_id,
+        };
+
+        check_token_expiration(actor, db_token.time_expires)?;
+
+        Ok(authn::Details {
+            actor,
+            device_token_expiration: None,
+            credential_id: Some(db_token.id().into_untyped_uuid()),
+            scope: Some(authn::CredentialScope {
+                read_only: db_token.read_only,
+                project_ids: db_token
+                    .project_ids
+                    .map(|ids| ids.into_iter().collect()),
+            }),
+        })
     }
 
     pub(crate) async fn device_access_token(
@@ -378,3 +411,23 @@ impl super::Nexus {
         self.db_datastore.current_user_token_delete(opctx, token_id).await
     }
 }
+
+fn check_token_expiration(
+    actor: Actor,
+    time_expires: Option<chrono::DateTime<Utc>>,
+) -> Result<(), Reason> {
+    if let Some(time_expires) = time_expires {
+        let now = Utc::now();
+        if time_expires < now {
+            return Err(Reason::BadCredentials {
+                actor,
+                source: anyhow!(
+                    "token expired at {} (current time: {})",
+                    time_expires,
+                    now
+                ),
+            });
+        }
+    }
+    Ok(())
+}
@@ -25,6 +25,7 @@ use omicron_common::api::external::InternalContext;
 use omicron_common::api::external::ListResultVec;
 use omicron_common::api::external::LookupResult;
 use omicron_common::api::external::NameOrId;
+use omicron_common::api::external::ResourceType;
 use omicron_common::api::external::UpdateResult;
 use omicron_common::api::external::http_pagination::PaginatedBy;
 use std::sync::Arc;
@@ -339,6 +340,7 @@ impl super::Nexus {
                 internal_message: format!("{e:#}"),
             })
             .internal_context("looking up output from disk create saga")?;
+        opctx.set_audit_target(ResourceType::Disk, disk_created.id());
 
         Ok(disk_created)
     }
@@ -365,6 +367,7 @@ impl super::Nexus {
     ) -> DeleteResult {
         let (.., project, authz_disk) =
             disk_lookup.lookup_for(authz::Action::Delete).await?;
+        opctx.set_audit_target(ResourceType::Disk, authz_disk.id());
 
         let disk = self.datastore().disk_get(opctx, authz_disk.id()).await?;
 
@@ -24,6 +24,7 @@ use omicron_common::api::external::Error;
 use omicron_common::api::external::ListResultVec;
 use omicron_common::api::external::LookupResult;
 use omicron_common::api::external::NameOrId;
+use omicron_common::api::external::ResourceType;
 use omicron_common::api::external::UpdateResult;
 use omicron_common::api::external::http_pagination::PaginatedBy;
 use omicron_uuid_kinds::GenericUuid;
@@ -123,6 +124,31 @@ impl super::Nexus {
         let floating_ip::FloatingIpCreate { identity, address_allocator } =
             params;
 
+        let allocation =
+            self.floating_ip_allocation(opctx, address_allocator).await?;
+
+        let fip: floating_ip::FloatingIp = self
+            .db_datastore
+            .allocate_floating_ip(
+                opctx,
+                authz_project.id(),
+                identity,
+                allocation,
+            )
+            .await?
+            .try_into()
+            .unwrap();
+        opctx.set_audit_target(ResourceType::FloatingIp, fip.identity.id);
+        Ok(fip)
+    }
+
+    /// Resolve the pool named by `address_allocator`, if any, into a
+    /// [`FloatingIpAllocation`] for the datastore.
+    pub(crate) async fn floating_ip_allocation(
+        &self,
+        opctx: &OpContext,
+        address_allocator: floating_ip::AddressAllocator,
+    ) -> LookupResult<FloatingIpAllocation> {
         let allocation = match address_allocator {
             floating_ip::AddressAllocator::Explicit { ip } => {
                 FloatingIpAllocation::Explicit { ip }
@@ -149,18 +175,7 @@ impl super::Nexus {
                 }
             }
         };
-
-        Ok(self
-            .db_datastore
-            .allocate_floating_ip(
-                opctx,
-                authz_project.id(),
-                identity,
-                allocation,
-            )
-            .await?
-            .try_into()
-            .unwrap())
+        Ok(allocation)
     }
 
     pub(crate) async fn floating_ip_update(
@@ -171,6 +186,7 @@ impl super::Nexus {
     ) -> UpdateResult<floating_ip::FloatingIp> {
         let (.., authz_fip) =
             ip_lookup.lookup_for(authz::Action::Modify).await?;
+        opctx.set_audit_target(ResourceType::FloatingIp, authz_fip.id());
         Ok(self
             .db_datastore
             .floating_ip_update(opctx, &authz_fip, params.clone().into())
@@ -186,6 +202,7 @@ impl super::Nexus {
     ) -> DeleteResult {
         let (.., authz_fip) =
             ip_lookup.lookup_for(authz::Action::Delete).await?;
+        opctx.set_audit_target(ResourceType::FloatingIp, authz_fip.id());
 
         self.db_datastore.floating_ip_delete(opctx, &authz_fip).await
     }
@@ -199,6 +216,7 @@ impl super::Nexus {
         let fip_lookup = self.floating_ip_lookup(opctx, fip_selector)?;
         let (.., authz_project, authz_fip, db_fip) =
             fip_lookup.fetch_for(authz::Action::Modify).await?;
+        opctx.set_audit_target(ResourceType::FloatingIp, authz_fip.id());
 
         match target.kind {
             floating_ip::FloatingIpParentKind::Instance => {
@@ -246,6 +264,7 @@ impl super::Nexus {
         //      at this point).
         let (.., authz_fip, db_fip) =
             ip_lookup.fetch_for(authz::Action::Modify).await?;
+        opctx.set_audit_target(ResourceType::FloatingIp, authz_fip.id());
 
         let Some(parent_id) = db_fip.parent_id else {
             return Ok(db_fip.into());
@@ -14,6 +14,7 @@ use nexus_db_queries::context::OpContext;
 use nexus_db_queries::db;
 use nexus_types::external_api::image;
 use nexus_types::external_api::project;
+use nexus_types::identity::Resource;
 use omicron_common::api::external::CreateResult;
 use omicron_common::api::external::DeleteResult;
 use omicron_common::api::external::Error;
@@ -21,6 +22,7 @@ use omicron_common::api::external::InternalContext;
 use omicron_common::api::external::ListResultVec;
 use omicron_common::api::external::LookupResult;
 use omicron_common::api::external::NameOrId;
+use omicron_common::api::external::ResourceType;
 use omicron_common::api::external::UpdateResult;
 use omicron_common::api::external::http_pagination::PaginatedBy;
 use std::sync::Arc;
@@ -122,6 +124,7 @@ impl super::Nexus {
             .map_err(|e| Error::internal_error(&format!("{:#}", &e)))
             .internal_context("looking up output from image create saga")?;
 
+        opctx.set_audit_target(ResourceType::Image, created_image.id());
         Ok(created_image)
     }
 
@@ -158,11 +161,13 @@ impl super::Nexus {
             ImageLookup::ProjectImage(lookup) => {
                 let (_, _, authz_image, image) =
                     lookup.fetch_for(authz::Action::Delete).await?;
+                opctx.set_audit_target(ResourceType::Image, authz_image.id());
                 sagas::image_delete::ImageParam::Project { authz_image, image }
             }
             ImageLookup::SiloImage(lookup) => {
                 let (_, authz_image, image) =
                     lookup.fetch_for(authz::Action::Delete).await?;
+                opctx.set_audit_target(ResourceType::Image, authz_image.id());
                 sagas::image_delete::ImageParam::Silo { authz_image, image }
             }
         };
@@ -189,6 +194,10 @@ impl super::Nexus {
             ImageLookup::ProjectImage(lookup) => {
                 let (authz_silo, _, authz_project_image, project_image) =
                     lookup.fetch_for(authz::Action::Modify).await?;
+                opctx.set_audit_target(
+                    ResourceType::Image,
+                    authz_project_image.id(),
+                );
                 opctx
                     .authorize(authz::Action::CreateChild, &authz_silo)
                     .await?;
@@ -218,6 +227,10 @@ impl super::Nexus {
             ImageLookup::SiloImage(lookup) => {
                 let (_, authz_silo_image, silo_image) =
                     lookup.fetch_for(authz::Action::Modify).await?;
+                opctx.set_audit_target(
+                    ResourceType::Image,
+                    authz_silo_image.id(),
+                );
                 let (_, authz_project) =
                     project_lookup.lookup_for(authz::Action::Modify).await?;
                 self.db_datastore
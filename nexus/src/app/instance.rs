@@ -9,6 +9,7 @@ use super::MAX_EPHEMERAL_IPS_PER_INSTANCE;
 use super::MAX_EXTERNAL_IPS_PER_INSTANCE;
 use super::MAX_MEMORY_BYTES_PER_INSTANCE;
 use super::MAX_MULTICAST_GROUPS_PER_INSTANCE;
+use super::MAX_NETWORK_TAGS_PER_INSTANCE;
 use super::MAX_NICS_PER_INSTANCE;
 use super::MAX_SSH_KEYS_PER_INSTANCE;
 use super::MAX_VCPU_PER_INSTANCE;
@@ -59,7 +60,9 @@ use omicron_common::api::external::InternalContext;
 use omicron_common::api::external::IpVersion;
 use omicron_common::api::external::ListResultVec;
 use omicron_common::api::external::LookupResult;
+use omicron_common::api::external::Name;
 use omicron_common::api::external::NameOrId;
+use omicron_common::api::external::ResourceType;
 use omicron_common::api::external::UpdateResult;
 use omicron_common::api::external::http_pagination::PaginatedBy;
 use omicron_uuid_kinds::GenericUuid;
@@ -561,6 +564,10 @@ impl super::Nexus {
     ) -> UpdateResult<InstanceAndActiveVmm> {
         let (.., authz_project, authz_instance) =
             instance_lookup.lookup_for(authz::Action::Modify).await?;
+        opctx.set_audit_target(
+            ResourceType::Instance,
+            authz_instance.id().into_untyped_uuid(),
+        );
 
         let instance::InstanceUpdate {
             ncpus,
@@ -822,6 +829,7 @@ impl super::Nexus {
             .lookup_node_output::<Uuid>("instance_id")
             .map_err(|e| Error::internal_error(&format!("{:#}", &e)))
             .internal_context("looking up output from instance create saga")?;
+        opctx.set_audit_target(ResourceType::Instance, instance_id);
 
         // If the caller asked to start the instance, kick off that saga.
         // There's a window in which the instance is stopped and can be deleted,
@@ -891,6 +899,10 @@ impl super::Nexus {
         // not right away so that callers can see that they've been destroyed.
         let (.., authz_instance, instance) =
             instance_lookup.fetch_for(authz::Action::Delete).await?;
+        opctx.set_audit_target(
+            ResourceType::Instance,
+            authz_instance.id().into_untyped_uuid(),
+        );
 
         self.db_datastore
             .instance_set_intended_state(
@@ -926,81 +938,31 @@ impl super::Nexus {
         Ok(())
     }
 
-    /// Returns true if any of the attached disks are type LocalStorage
-    pub(crate) async fn instance_uses_local_storage(
-        self: &Arc<Self>,
-        opctx: &OpContext,
-        authz_instance: &authz::Instance,
-    ) -> Result<bool, Error> {
-        let disks = self
-            .db_datastore
-            .instance_list_disks(
-                opctx,
-                authz_instance,
-                &PaginatedBy::Name(DataPageParams {
-                    marker: None,
-                    direction: dropshot::PaginationOrder::Ascending,
-                    limit: std::num::NonZeroU32::new(MAX_DISKS_PER_INSTANCE)
-                        .unwrap(),
-                }),
-            )
-            .await?;
-
-        Ok(disks.into_iter().any(|disk| match disk {
-            db::datastore::Disk::LocalStorage(_) => true,
-            db::datastore::Disk::Crucible(_) => false,
-        }))
-    }
-
+    /// Live-migrate a running instance to another sled
+    ///
+    /// If `dst_sled_id` is `None`, the destination is chosen in the same way
+    /// as when starting an instance, excluding the instance's current sled.
+    /// Migration is only available to fleet operators.
     pub(crate) async fn instance_migrate(
         self: &Arc<Self>,
         opctx: &OpContext,
-        id: InstanceUuid,
-        params: nexus_types::internal_api::params::InstanceMigrateRequest,
+        instance_lookup: &lookup::Instance<'_>,
+        dst_sled_id: Option<SledUuid>,
     ) -> UpdateResult<InstanceAndActiveVmm> {
-        let (.., authz_instance) = LookupPath::new(&opctx, &self.db_datastore)
-            .instance_id(id.into_untyped_uuid())
-            .lookup_for(authz::Action::Modify)
-            .await?;
-
-        // Cannot migrate instance if it has local storage
-        if self.instance_uses_local_storage(opctx, &authz_instance).await? {
-            return Err(Error::invalid_request(format!(
-                "cannot migrate instance {} as it uses local storage",
-                authz_instance.id()
-            )));
-        }
-
-        let state = self
-            .db_datastore
-            .instance_fetch_with_vmm(opctx, &authz_instance)
-            .await?;
-        let (instance, vmm) = (state.instance(), state.vmm());
-
-        if vmm.is_none() || vmm.as_ref().unwrap().state != DbVmmState::Running {
-            return Err(Error::invalid_request(
-                "instance must be running before it can migrate",
-            ));
-        }
-
-        let vmm = vmm.as_ref().unwrap();
-        if vmm.sled_id() == params.dst_sled_id {
-            return Err(Error::invalid_request(
-                "instance is already running on destination sled",
-            ));
-        }
-
-        if instance.runtime().migration_id.is_some() {
-            return Err(Error::conflict("instance is already migrating"));
-        }
+        let (.., authz_instance) =
+            instance_lookup.lookup_for(authz::Action::Modify).await?;
+        // Choosing where an instance runs is reserved for fleet operators,
+        // even though silo users may otherwise modify their instances.
+        opctx.authorize(authz::Action::Modify, &authz::FLEET).await?;
 
         // Kick off the migration saga
-        let saga_params = sagas::instance_migrate::Params {
-            serialized_authn: authn::saga::Serialized::for_opctx(opctx),
-            instance: instance.clone(),
-            src_vmm: vmm.clone(),
-            migrate_params: params,
-        };
+        let saga_params = instance_migrate_saga_params(
+            opctx,
+            &self.db_datastore,
+            &authz_instance,
+            dst_sled_id,
+        )
+        .await?;
         self.sagas
             .saga_execute::<sagas::instance_migrate::SagaInstanceMigrate>(
                 saga_params,
@@ -1017,6 +979,74 @@ impl super::Nexus {
         self.db_datastore.instance_fetch_with_vmm(opctx, &authz_instance).await
     }
 
+    /// List the network tags carried by an instance
+    pub(crate) async fn instance_network_tags_list(
+        &self,
+        opctx: &OpContext,
+        instance_lookup: &lookup::Instance<'_>,
+    ) -> ListResultVec<Name> {
+        let (.., authz_instance) =
+            instance_lookup.lookup_for(authz::Action::Read).await?;
+        let tags = self
+            .db_datastore
+            .instance_network_tags_list(opctx, &authz_instance)
+            .await?;
+        Ok(tags.into_iter().map(|tag| tag.tag.into()).collect())
+    }
+
+    /// Replace the network tags carried by an instance
+    ///
+    /// Firewall rules may target or filter by tag, so if the instance has
+    /// network interfaces, its VPC's firewall rules are re-sent to sleds to
+    /// reflect the new tag membership.
+    pub(crate) async fn instance_network_tags_update(
+        &self,
+        opctx: &OpContext,
+        instance_lookup: &lookup::Instance<'_>,
+        tags: Vec<Name>,
+    ) -> UpdateResult<Vec<Name>> {
+        if tags.len() > MAX_NETWORK_TAGS_PER_INSTANCE {
+            return Err(Error::invalid_request(format!(
+                "an instance may carry at most {} network tags",
+                MAX_NETWORK_TAGS_PER_INSTANCE
+            )));
+        }
+        let (.., authz_instance) =
+            instance_lookup.lookup_for(authz::Action::Modify).await?;
+        let tags = self
+            .db_datastore
+            .instance_network_tags_replace(opctx, &authz_instance, tags)
+            .await?;
+
+        // All of an instance's NICs are in the same VPC, so the first one
+        // tells us which VPC's rules to update. (See the same lookup in
+        // instance_ensure_registered.)
+        let nics = self
+            .db_datastore
+            .derive_guest_network_interface_info(opctx, &authz_instance)
+            .await?;
+        if let Some(nic) = nics.first() {
+            let vpc = self
+                .db_datastore
+                .resolve_vni_to_vpc(opctx, db::model::Vni(nic.vni))
+                .await?;
+            let (.., authz_vpc) = LookupPath::new(opctx, &self.db_datastore)
+                .vpc_id(vpc.id())
+                .lookup_for(authz::Action::Read)
+                .await?;
+            let rules = self
+                .db_datastore
+                .vpc_list_firewall_rules(opctx, &authz_vpc)
+                .await?;
+            if rules.iter().any(|rule| rule.references_tags()) {
+                self.send_sled_agents_firewall_rules(opctx, &vpc, &rules, &[])
+                    .await?;
+            }
+        }
+
+        Ok(tags.into_iter().map(|tag| tag.tag.into()).collect())
+    }
+
     /// Reboot the specified instance.
     pub(crate) async fn instance_reboot(
         &self,
@@ -1565,7 +1595,7 @@ impl super::Nexus {
         // so we fetch it via the first interface's VNI. (It doesn't
         // matter which one we use because all NICs must be in the
         // same VPC; see the check in project_create_instance.)
-        let firewall_rules = if let Some(nic) = nics.first() {
+        let (firewall_rules, vpc_dns_zone) = if let Some(nic) = nics.first() {
             let vni = nic.vni;
             let vpc = self
                 .db_datastore
@@ -1579,12 +1609,57 @@ impl super::Nexus {
                 .db_datastore
                 .vpc_list_firewall_rules(opctx, &authz_vpc)
                 .await?;
-            self.resolve_firewall_rules_for_sled_agent(opctx, &vpc, &rules)
-                .await?
+            let rules = self
+                .resolve_firewall_rules_for_sled_agent(opctx, &vpc, &rules)
+                .await?;
+            (rules, Some(db::model::vpc_dns_zone_name(&vpc.dns_name)))
         } else {
-            vec![]
+            (vec![], None)
         };
 
+        // The VPC's private DNS zone is served by the external DNS servers to
+        // queries from the VPC's instances' external addresses, so instances
+        // with external addresses use those servers as their resolvers. The
+        // servers forward the instance's queries for other names (over TCP;
+        // queries over UDP are truncated so that the instance retries) to the
+        // upstream DNS servers, which are listed after them in case the
+        // external DNS servers can't be reached. Instances without external
+        // addresses have no path to the external DNS servers, so they only
+        // get the upstream servers and can't resolve their VPC's names.
+        let mut dns_servers = Vec::new();
+        let mut search_domains = Vec::new();
+        if let Some(zone) = vpc_dns_zone
+            && !all_external_ips.is_empty()
+        {
+            match self
+                .db_datastore
+                .external_dns_external_ips_specified_by_rack_setup(
+                    &self.opctx_alloc,
+                )
+                .await
+            {
+                Ok(ips) => {
+                    dns_servers.extend(ips);
+                    search_domains.push(zone);
+                }
+                Err(error) => {
+                    // The instance can still start, just without resolving
+                    // its VPC's names.
+                    warn!(
+                        opctx.log,
+                        "failed to look up VPC DNS servers";
+                        "instance_id" => %authz_instance.id(),
+                        "error" => &InlineErrorChain::new(&error),
+                    );
+                }
+            }
+        }
+        for ip in &self.external_dns_servers {
+            if !dns_servers.contains(ip) {
+                dns_servers.push(*ip);
+            }
+        }
+
         let ssh_keys = self
             .db_datastore
             .instance_ssh_keys_list(
@@ -1693,10 +1768,9 @@ impl super::Nexus {
             firewall_rules,
             multicast_groups,
             dhcp_config: sled_agent_client::types::DhcpConfig {
-                dns_servers: self.external_dns_servers.clone(),
-                // TODO: finish designing instance DNS
-                host_domain: None,
-                search_domains: Vec::new(),
+                dns_servers,
+                host_domain: search_domains.first().cloned(),
+                search_domains,
             },
             delegated_zvols,
             attached_subnets,
@@ -2682,6 +2756,78 @@ impl super::Nexus {
     }
 }
 
+/// Returns whether any of the instance's disks use local storage.
+async fn instance_uses_local_storage(
+    opctx: &OpContext,
+    datastore: &DataStore,
+    authz_instance: &authz::Instance,
+) -> Result<bool, Error> {
+    let disks = datastore
+        .instance_list_disks(
+            opctx,
+            authz_instance,
+            &PaginatedBy::Name(DataPageParams {
+                marker: None,
+                direction: dropshot::PaginationOrder::Ascending,
+                limit: std::num::NonZeroU32::new(MAX_DISKS_PER_INSTANCE)
+                    .unwrap(),
+            }),
+        )
+        .await?;
+
+    Ok(disks.into_iter().any(|disk| match disk {
+        db::datastore::Disk::LocalStorage(_) => true,
+        db::datastore::Disk::Crucible(_) => false,
+    }))
+}
+
+/// Checks that an instance can be live-migrated, and returns the parameters
+/// for an instance-migrate saga that will migrate it
+///
+/// If `dst_sled_id` is `None`, the saga selects the destination sled.
+pub(crate) async fn instance_migrate_saga_params(
+    opctx: &OpContext,
+    datastore: &DataStore,
+    authz_instance: &authz::Instance,
+    dst_sled_id: Option<SledUuid>,
+) -> Result<sagas::instance_migrate::Params, Error> {
+    // Cannot migrate instance if it has local storage
+    if instance_uses_local_storage(opctx, datastore, authz_instance).await? {
+        return Err(Error::invalid_request(format!(
+            "cannot migrate instance {} as it uses local storage",
+            authz_instance.id()
+        )));
+    }
+
+    let state =
+        datastore.instance_fetch_with_vmm(opctx, authz_instance).await?;
+    let (instance, vmm) = (state.instance(), state.vmm());
+
+    let Some(vmm) = vmm.as_ref().filter(|vmm| vmm.state == DbVmmState::Running)
+    else {
+        return Err(Error::invalid_request(
+            "instance must be running before it can migrate",
+        ));
+    };
+
+    if Some(vmm.sled_id()) == dst_sled_id {
+        return Err(Error::invalid_request(
+            "instance is already running on destination sled",
+        ));
+    }
+
+    if instance.runtime().migration_id.is_some() {
+        return Err(Error::conflict("instance is already migrating"));
+    }
+
+    Ok(sagas::instance_migrate::Params {
+        serialized_authn: authn::saga::Serialized::for_opctx(opctx),
+        instance: instance.clone(),
+        src_vmm: vmm.clone(),
+        dst_sled_id,
+    })
+}
+
 fn build_external_ip_config(
     ips: &[ExternalIp],
 ) -> Result<ExternalIpConfig, Error> {
@@ -2773,6 +2919,13 @@ where
             IpKind::Floating => {
                 floating_ips.insert(ip.ip);
             }
+            // Load balancer frontends are owned by the load balancer, and
+            // added to the OPTE ports of its backends separately.
+            IpKind::LoadBalancer => {
+                return Err(Error::internal_error(
+                    "Load balancer frontends are not instance external IPs",
+                ));
+            }
         }
     }
 
@@ -224,6 +224,21 @@ impl Nexus {
         .await
     }
 
+    /// Informs all available boundary switches that the set of NAT entries
+    /// has changed.
+    pub(crate) async fn notify_dendrite_nat_changed(
+        &self,
+    ) -> Result<(), Error> {
+        notify_dendrite_nat_state(
+            &self.db_datastore,
+            &self.log,
+            self.resolver(),
+            &self.opctx_alloc,
+            None,
+        )
+        .await
+    }
+
     /// Send a single attached subnet to Dendrite.
     pub(crate) async fn send_attached_subnet_to_dendrite(
         &self,
@@ -218,7 +218,9 @@ impl super::Nexus {
             ),
         };
 
-        self.db_datastore.ip_pool_create(opctx, pool).await
+        let pool = self.db_datastore.ip_pool_create(opctx, pool).await?;
+        opctx.set_audit_target(ResourceType::IpPool, pool.id());
+        Ok(pool)
     }
 
     /// List IP pools in current silo
@@ -313,6 +315,7 @@ impl super::Nexus {
     ) -> CreateResult<db::model::IpPoolResource> {
         let (authz_pool,) =
             pool_lookup.lookup_for(authz::Action::Modify).await?;
+        opctx.set_audit_target(ResourceType::IpPool, authz_pool.id());
 
         if self
             .db_datastore
@@ -347,6 +350,7 @@ impl super::Nexus {
     ) -> DeleteResult {
         let (.., authz_pool) =
             pool_lookup.lookup_for(authz::Action::Modify).await?;
+        opctx.set_audit_target(ResourceType::IpPool, authz_pool.id());
 
         if self
             .db_datastore
@@ -373,6 +377,7 @@ impl super::Nexus {
     ) -> CreateResult<db::model::IpPoolResource> {
         let (.., authz_pool) =
             pool_lookup.lookup_for(authz::Action::Modify).await?;
+        opctx.set_audit_target(ResourceType::IpPool, authz_pool.id());
 
         if self
             .db_datastore
@@ -410,6 +415,7 @@ impl super::Nexus {
     ) -> DeleteResult {
         let (.., authz_pool, db_pool) =
             pool_lookup.fetch_for(authz::Action::Delete).await?;
+        opctx.set_audit_target(ResourceType::IpPool, authz_pool.id());
 
         if self
             .db_datastore
@@ -430,6 +436,7 @@ impl super::Nexus {
     ) -> UpdateResult<db::model::IpPool> {
         let (.., authz_pool) =
             pool_lookup.lookup_for(authz::Action::Modify).await?;
+        opctx.set_audit_target(ResourceType::IpPool, authz_pool.id());
 
         if self
             .db_datastore
@@ -474,6 +481,7 @@ impl super::Nexus {
     ) -> UpdateResult<db::model::IpPoolRange> {
         let (.., authz_pool, db_pool) =
             pool_lookup.fetch_for(authz::Action::Modify).await?;
+        opctx.set_audit_target(ResourceType::IpPool, authz_pool.id());
 
         if self
             .db_datastore
@@ -565,6 +573,7 @@ impl super::Nexus {
     ) -> DeleteResult {
         let (.., authz_pool, _db_pool) =
             pool_lookup.fetch_for(authz::Action::Modify).await?;
+        opctx.set_audit_target(ResourceType::IpPool, authz_pool.id());
 
         if self
             .db_datastore
@@ -0,0 +1,358 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Layer-4 load balancers
+//!
+//! The API manages load balancers and their backends in the database. The
+//! data plane configuration, which consists of NAT entries on the switches
+//! and the frontend address on the OPTE ports of the backends, is maintained
+//! by the `load_balancer_manager` background task. Deleting a load balancer
+//! or removing a backend withdraws that configuration here, since the task
+//! no longer sees the removed records.
+
+use nexus_db_lookup::LookupPath;
+use nexus_db_lookup::lookup;
+use nexus_db_queries::authz;
+use nexus_db_queries::context::OpContext;
+use nexus_db_queries::db;
+use nexus_db_queries::db::DataStore;
+use nexus_types::external_api::instance;
+use nexus_types::external_api::load_balancer;
+use nexus_types::external_api::project;
+use nexus_types::identity::Resource;
+use omicron_common::api::external::CreateResult;
+use omicron_common::api::external::DataPageParams;
+use omicron_common::api::external::DeleteResult;
+use omicron_common::api::external::Error;
+use omicron_common::api::external::ListResultVec;
+use omicron_common::api::external::LookupResult;
+use omicron_common::api::external::NameOrId;
+use omicron_common::api::external::ResourceType;
+use omicron_common::api::external::http_pagination::PaginatedBy;
+use omicron_uuid_kinds::GenericUuid;
+use omicron_uuid_kinds::PropolisUuid;
+use sled_agent_client::types::InstanceExternalIpBody;
+use slog::warn;
+use slog_error_chain::InlineErrorChain;
+use std::collections::BTreeSet;
+use std::net::IpAddr;
+use uuid::Uuid;
+
+impl super::Nexus {
+    pub(crate) fn load_balancer_lookup<'a>(
+        &'a self,
+        opctx: &'a OpContext,
+        selector: load_balancer::LoadBalancerSelector,
+    ) -> LookupResult<lookup::LoadBalancer<'a>> {
+        match selector {
+            load_balancer::LoadBalancerSelector {
+                load_balancer: NameOrId::Id(id),
+                project: None,
+            } => {
+                Ok(LookupPath::new(opctx, &self.db_datastore)
+                    .load_balancer_id(id))
+            }
+            load_balancer::LoadBalancerSelector {
+                load_balancer: NameOrId::Name(name),
+                project: Some(project),
+            } => Ok(self
+                .project_lookup(opctx, project::ProjectSelector { project })?
+                .load_balancer_name_owned(name.into())),
+            load_balancer::LoadBalancerSelector {
+                load_balancer: NameOrId::Id(_),
+                ..
+            } => Err(Error::invalid_request(
+                "when providing load balancer as an ID project should not be \
+                specified",
+            )),
+            _ => Err(Error::invalid_request(
+                "load balancer should either be UUID or project should be \
+                specified",
+            )),
+        }
+    }
+
+    pub(crate) async fn load_balancers_list(
+        &self,
+        opctx: &OpContext,
+        project_lookup: &lookup::Project<'_>,
+        pagparams: &PaginatedBy<'_>,
+    ) -> ListResultVec<load_balancer::LoadBalancer> {
+        let (.., authz_project) =
+            project_lookup.lookup_for(authz::Action::ListChildren).await?;
+        Ok(self
+            .db_datastore
+            .load_balancers_list(opctx, &authz_project, pagparams)
+            .await?
+            .into_iter()
+            .map(|(lb, frontend)| lb.into_view(&frontend))
+            .collect())
+    }
+
+    pub(crate) async fn load_balancer_create(
+        &self,
+        opctx: &OpContext,
+        project_lookup: &lookup::Project<'_>,
+        params: load_balancer::LoadBalancerCreate,
+    ) -> CreateResult<load_balancer::LoadBalancer> {
+        let (.., authz_project) =
+            project_lookup.lookup_for(authz::Action::CreateChild).await?;
+
+        validate_ports(&params.ports)?;
+        if let Some(health_check) = &params.health_check {
+            if health_check.port == 0 {
+                return Err(Error::invalid_request(
+                    "health check port must not be 0",
+                ));
+            }
+            if health_check.healthy_threshold == 0
+                || health_check.unhealthy_threshold == 0
+            {
+                return Err(Error::invalid_request(
+                    "health check thresholds must be at least 1",
+                ));
+            }
+        }
+
+        let allocation = self
+            .floating_ip_allocation(opctx, params.address_allocator.clone())
+            .await?;
+        let (lb, frontend) = self
+            .db_datastore
+            .load_balancer_create(opctx, &authz_project, params, allocation)
+            .await?;
+        opctx.set_audit_target(ResourceType::LoadBalancer, lb.id());
+
+        self.background_tasks
+            .activate(&self.background_tasks.task_load_balancer_manager);
+        Ok(lb.into_view(&frontend))
+    }
+
+    pub(crate) async fn load_balancer_view(
+        &self,
+        opctx: &OpContext,
+        lb_lookup: &lookup::LoadBalancer<'_>,
+    ) -> LookupResult<load_balancer::LoadBalancer> {
+        let (.., db_lb) = lb_lookup.fetch().await?;
+        let frontend = self
+            .db_datastore
+            .load_balancer_frontend_fetch(opctx, &db_lb)
+            .await?;
+        Ok(db_lb.into_view(&frontend))
+    }
+
+    pub(crate) async fn load_balancer_delete(
+        &self,
+        opctx: &OpContext,
+        lb_lookup: &lookup::LoadBalancer<'_>,
+    ) -> DeleteResult {
+        let (.., authz_lb) =
+            lb_lookup.lookup_for(authz::Action::Delete).await?;
+        opctx.set_audit_target(ResourceType::LoadBalancer, authz_lb.id());
+
+        let (frontend, backends) =
+            self.db_datastore.load_balancer_delete(opctx, &authz_lb).await?;
+
+        // The load balancer is gone from the database, so the background task
+        // will not clean up after it: remove its NAT entries and withdraw its
+        // address from the backends serving it. Failing to notify the switches
+        // is not fatal, since they will catch up on their own.
+        self.db_datastore
+            .nat_sync_external_address(opctx, frontend.ip.into(), &[])
+            .await?;
+        if let Err(e) = self.notify_dendrite_nat_changed().await {
+            warn!(
+                self.log,
+                "failed to notify dendrite of load balancer removal";
+                "load_balancer_id" => %authz_lb.id(),
+                InlineErrorChain::new(&e),
+            );
+        }
+        withdraw_frontend(
+            &self.db_datastore,
+            opctx,
+            frontend.ip.ip(),
+            &backends,
+        )
+        .await;
+        Ok(())
+    }
+
+    pub(crate) async fn load_balancer_backends_list(
+        &self,
+        opctx: &OpContext,
+        lb_lookup: &lookup::LoadBalancer<'_>,
+        pagparams: &DataPageParams<'_, Uuid>,
+    ) -> ListResultVec<load_balancer::LoadBalancerBackend> {
+        let (.., authz_lb) = lb_lookup.lookup_for(authz::Action::Read).await?;
+        Ok(self
+            .db_datastore
+            .load_balancer_backends_list(opctx, &authz_lb, pagparams)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+
+    /// Add an instance in the load balancer's project as a backend
+    pub(crate) async fn load_balancer_backend_add(
+        &self,
+        opctx: &OpContext,
+        lb_lookup: &lookup::LoadBalancer<'_>,
+        params: load_balancer::LoadBalancerBackendCreate,
+    ) -> CreateResult<load_balancer::LoadBalancerBackend> {
+        let (.., authz_project, authz_lb) =
+            lb_lookup.lookup_for(authz::Action::Modify).await?;
+        opctx.set_audit_target(ResourceType::LoadBalancer, authz_lb.id());
+
+        // An instance named without a project is looked up in the project of
+        // the load balancer.
+        let project = match &params.instance {
+            NameOrId::Name(_) => Some(NameOrId::Id(authz_project.id())),
+            NameOrId::Id(_) => None,
+        };
+        let (.., authz_instance_project, authz_instance) = self
+            .instance_lookup(
+                opctx,
+                instance::InstanceSelector {
+                    project,
+                    instance: params.instance,
+                },
+            )?
+            .lookup_for(authz::Action::Read)
+            .await?;
+        if authz_instance_project.id() != authz_project.id() {
+            return Err(Error::invalid_request(
+                "backends must be in the same project as the load balancer",
+            ));
+        }
+
+        let backend = self
+            .db_datastore
+            .load_balancer_backend_add(opctx, &authz_lb, &authz_instance)
+            .await?;
+        self.background_tasks
+            .activate(&self.background_tasks.task_load_balancer_manager);
+        Ok(backend.into())
+    }
+
+    /// Remove an explicitly added backend from a load balancer
+    pub(crate) async fn load_balancer_backend_remove(
+        &self,
+        opctx: &OpContext,
+        lb_lookup: &lookup::LoadBalancer<'_>,
+        instance_id: Uuid,
+    ) -> DeleteResult {
+        let (.., authz_lb, db_lb) =
+            lb_lookup.fetch_for(authz::Action::Modify).await?;
+        opctx.set_audit_target(ResourceType::LoadBalancer, authz_lb.id());
+
+        let backend = self
+            .db_datastore
+            .load_balancer_backend_remove(opctx, &authz_lb, instance_id)
+            .await?;
+
+        // The background task picks a new target for the ports this backend
+        // was serving.
+        let frontend = self
+            .db_datastore
+            .load_balancer_frontend_fetch(opctx, &db_lb)
+            .await?;
+        withdraw_frontend(
+            &self.db_datastore,
+            opctx,
+            frontend.ip.ip(),
+            std::slice::from_ref(&backend),
+        )
+        .await;
+        self.background_tasks
+            .activate(&self.background_tasks.task_load_balancer_manager);
+        Ok(())
+    }
+}
+
+/// Check that the listener ports of a load balancer are non-empty, non-zero
+/// and distinct
+fn validate_ports(ports: &[u16]) -> Result<(), Error> {
+    if ports.is_empty() {
+        return Err(Error::invalid_request(
+            "a load balancer must listen on at least one port",
+        ));
+    }
+    if ports.contains(&0) {
+        return Err(Error::invalid_request(
+            "load balancer ports must not be 0",
+        ));
+    }
+    if ports.iter().collect::<BTreeSet<_>>().len() != ports.len() {
+        return Err(Error::invalid_request(
+            "load balancer ports must be distinct",
+        ));
+    }
+    Ok(())
+}
+
+/// Withdraw a load balancer's frontend address `ip` from the OPTE ports of
+/// the VMMs of `backends` that are serving it
+///
+/// This is best-effort: a VMM that has since been destroyed no longer has an
+/// OPTE port to withdraw the address from. Other failures are logged, and
+/// returned as messages for the caller to report.
+pub(crate) async fn withdraw_frontend(
+    datastore: &DataStore,
+    opctx: &OpContext,
+    ip: IpAddr,
+    backends: &[db::model::LoadBalancerBackend],
+) -> Vec<String> {
+    let log = &opctx.log;
+    let mut errors = Vec::new();
+    for backend in backends {
+        let Some(vmm_id) = backend.frontend_vmm_id else {
+            continue;
+        };
+        let propolis_id = PropolisUuid::from_untyped_uuid(vmm_id);
+        let vmm = match datastore.vmm_fetch(opctx, &propolis_id).await {
+            Ok(vmm) => vmm,
+            Err(Error::ObjectNotFound { .. }) => continue,
+            Err(e) => {
+                let message = format!(
+                    "failed to look up VMM {propolis_id} of backend {}: {}",
+                    backend.instance_id,
+                    InlineErrorChain::new(&e),
+                );
+                warn!(log, "{message}");
+                errors.push(message);
+                continue;
+            }
+        };
+
+        let result = match nexus_networking::sled_client(
+            datastore,
+            opctx,
+            vmm.sled_id.into(),
+            log,
+        )
+        .await
+        {
+            Ok(client) => client
+                .vmm_delete_external_ip(
+                    &propolis_id,
+                    &InstanceExternalIpBody::Floating(ip),
+                )
+                .await
+                .map(|_| ())
+                .map_err(|e| InlineErrorChain::new(&e).to_string()),
+            Err(e) => Err(InlineErrorChain::new(&e).to_string()),
+        };
+        if let Err(e) = result {
+            let message = format!(
+                "failed to withdraw load balancer address {ip} from VMM \
+                {propolis_id}: {e}",
+            );
+            warn!(log, "{message}");
+            errors.push(message);
+        }
+    }
+    errors
+}
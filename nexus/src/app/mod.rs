@@ -57,11 +57,13 @@ use uuid::Uuid;
 
 // The implementation of Nexus is large, and split into a number of submodules
 // by resource.
+pub(crate) mod acme;
 mod address_lot;
 mod affinity;
 mod alert;
 mod allow_list;
 mod audit_log;
+mod audit_log_sink;
 pub(crate) mod background;
 mod bfd;
 mod bgp;
@@ -82,6 +84,7 @@ mod instance_platform;
 mod internet_gateway;
 mod ip_pool;
 mod lldp;
+pub(crate) mod load_balancer;
 mod login;
 mod metrics;
 pub(crate) mod multicast;
@@ -92,12 +95,15 @@ mod project;
 mod quiesce;
 mod quota;
 mod rack;
+mod rate_limit;
 pub(crate) mod saga;
 mod scim;
+mod service_account;
 mod session;
 mod silo;
 mod sled;
 mod sled_instance;
+mod smtp;
 mod snapshot;
 mod ssh_key;
 mod subnet_pool;
@@ -105,6 +111,7 @@ pub(crate) mod support_bundles;
 mod switch;
 mod switch_interface;
 mod switch_port;
+mod syslog;
 mod system_networking;
 pub mod test_interfaces;
 mod trust_quorum;
@@ -123,6 +130,7 @@ pub(crate) mod sagas;
 // TODO: When referring to API types, we should try to include
 // the prefix unless it is unambiguous.
 
+pub(crate) use self::audit_log::audit_request_body;
 pub(crate) use self::deployment::SetTargetReleaseIntent;
 use crate::app::quiesce::NexusQuiesceHandle;
 pub(crate) use nexus_db_model::MAX_NICS_PER_INSTANCE;
@@ -173,6 +181,10 @@ pub const LOCAL_STORAGE_WORKERS: NonZeroU32 = NonZeroU32::new(30).unwrap();
 /// This value is aribtrary
 pub const MAX_SSH_KEYS_PER_INSTANCE: u32 = 100;
 
+/// This value is arbitrary, but bounds the number of tags each firewall rule
+/// resolution may need to look up per instance.
+pub const MAX_NETWORK_TAGS_PER_INSTANCE: usize = 32;
+
 /// The amount of disk space to reserve for non-Crucible / control plane
 /// storage. This amount represents a buffer that the region allocation query
 /// will not use for each U2.
@@ -249,6 +261,9 @@ pub struct Nexus {
     /// Operational context used for external request authentication
     opctx_external_authn: OpContext,
 
+    /// Per-principal rate limiting of external API requests
+    rate_limiter: rate_limit::RateLimiter,
+
     /// Max issue delay for samael crate - used only for testing
     // the samael crate has an extra check (beyond the check against the SAML
     // response NotOnOrAfter) that fails if the issue instant was too long ago.
@@ -496,6 +511,20 @@ impl Nexus {
             })?
         };
 
+        // Likewise, ACME servers are external to the rack.
+        let acme_client = {
+            let builder = external_http_client_builder(
+                &config.deployment.external_http_clients,
+                &external_resolver,
+            );
+            acme::http_client(builder).map_err(|e| {
+                format!(
+                    "failed to build ACME client: {}",
+                    InlineErrorChain::new(&e)
+                )
+            })?
+        };
+
         let mut mgs_resolver =
             qorb_resolver.for_service(ServiceName::ManagementGatewayService);
         let mut repo_depot_resolver =
@@ -556,6 +585,16 @@ impl Nexus {
                 Arc::clone(&db_datastore)
                     as Arc<dyn nexus_auth::storage::Storage>,
             ),
+            rate_limiter: rate_limit::RateLimiter::new(
+                config.pkg.rate_limit.clone(),
+                OpContext::for_background(
+                    log.new(o!("component" => "RateLimiter")),
+                    Arc::clone(&authz),
+                    authn::Context::internal_read(),
+                    Arc::clone(&db_datastore)
+                        as Arc<dyn nexus_auth::storage::Storage>,
+                ),
+            ),
             samael_max_issue_delay: std::sync::Mutex::new(None),
             pantry_connection_pool: make_pantry_connection_pool(&qorb_resolver),
             internal_resolver: resolver.clone(),
@@ -663,6 +702,7 @@ impl Nexus {
                     webhook_delivery_client: task_nexus
                         .webhook_delivery_client
                         .clone(),
+                    acme_client,
                     nexus_quiesce: task_nexus.quiesce.clone(),
 
                     saga_recovery: SagaRecoveryHelpers {
@@ -678,6 +718,9 @@ impl Nexus {
                     blueprint_load_tx,
                     sitrep_load_tx,
                     console_session_absolute_timeout,
+                    upstream_dns_servers: task_nexus
+                        .external_dns_servers
+                        .clone(),
                 },
             );
 
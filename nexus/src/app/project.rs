@@ -21,6 +21,7 @@ use omicron_common::api::external::InternalContext;
 use omicron_common::api::external::ListResultVec;
 use omicron_common::api::external::LookupResult;
 use omicron_common::api::external::NameOrId;
+use omicron_common::api::external::ResourceType;
 use omicron_common::api::external::UpdateResult;
 use omicron_common::api::external::http_pagination::PaginatedBy;
 use std::sync::Arc;
@@ -64,12 +65,13 @@ impl super::Nexus {
                 saga_params,
             )
             .await?;
-        let (_authz_project, db_project) = saga_outputs
+        let (authz_project, db_project) = saga_outputs
             .lookup_node_output::<(authz::Project, db::model::Project)>(
                 "project",
             )
             .map_err(|e| Error::internal_error(&format!("{:#}", &e)))
             .internal_context("looking up output from project create saga")?;
+        opctx.set_audit_target(ResourceType::Project, authz_project.id());
         Ok(db_project)
     }
 
@@ -89,6 +91,7 @@ impl super::Nexus {
     ) -> UpdateResult<db::model::Project> {
         let (.., authz_project) =
             project_lookup.lookup_for(authz::Action::Modify).await?;
+        opctx.set_audit_target(ResourceType::Project, authz_project.id());
         self.db_datastore
             .project_update(opctx, &authz_project, new_params.clone().into())
             .await
@@ -101,6 +104,7 @@ impl super::Nexus {
     ) -> DeleteResult {
         let (.., authz_project, db_project) =
             project_lookup.fetch_for(authz::Action::Delete).await?;
+        opctx.set_audit_target(ResourceType::Project, authz_project.id());
         self.db_datastore
             .project_delete(opctx, &authz_project, &db_project)
             .await
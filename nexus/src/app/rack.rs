@@ -7,6 +7,7 @@
 use crate::app::CONTROL_PLANE_STORAGE_BUFFER;
 use crate::internal_api::params::RackInitializationRequest;
 use internal_dns_types::names::DNS_ZONE;
+use internal_dns_types::names::is_reverse_zone;
 use ipnetwork::{IpNetwork, Ipv6Network};
 use nexus_db_lookup::LookupPath;
 use nexus_db_model::DnsGroup;
@@ -153,16 +154,16 @@ impl super::Nexus {
             })
             .collect();
 
-        let dns_zone = request
+        let (internal_zones, other_zones): (Vec<_>, Vec<_>) = request
             .internal_dns_zone_config
             .zones
             .into_iter()
-            .find(|z| z.zone_name == DNS_ZONE)
-            .ok_or_else(|| {
-                Error::invalid_request(
-                    "expected initial DNS config to include control plane zone",
-                )
-            })?;
+            .partition(|z| z.zone_name == DNS_ZONE);
+        let dns_zone = internal_zones.into_iter().next().ok_or_else(|| {
+            Error::invalid_request(
+                "expected initial DNS config to include control plane zone",
+            )
+        })?;
 
         // sled-agent, in service of RSS, has configured internal DNS. We record
         // its reported initial DNS config in this `InitialDnsGroup`. sled-agent
@@ -170,13 +171,20 @@ impl super::Nexus {
         // `InitialDnsGroup` and initialize it in accordance with the initial
         // system blueprint.
 
-        let internal_dns = InitialDnsGroup::new(
+        let mut internal_dns = InitialDnsGroup::new(
             DnsGroup::Internal,
             &dns_zone.zone_name,
             &self.id.to_string(),
             "rack setup",
             dns_zone.records,
         );
+        // RSS also sets up the reverse zones for the control plane's underlay
+        // addresses.  Any other zones are ignored.
+        for zone in other_zones {
+            if is_reverse_zone(&zone.zone_name) {
+                internal_dns.add_reverse_zone(&zone.zone_name, zone.records);
+            }
+        }
 
         let external_dns = InitialDnsGroup::new(
             DnsGroup::External,
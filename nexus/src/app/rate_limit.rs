@@ -0,0 +1,345 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Rate limiting of external API requests
+//!
+//! Every authenticated principal gets a token bucket that holds up to `burst`
+//! tokens and refills at `requests_per_second`.  Each request takes a token;
+//! requests that find the bucket empty are rejected.  Unauthenticated requests
+//! are limited the same way, keyed by the client's IP address.  Requests whose
+//! credentials fail to authenticate count against that same bucket, so that
+//! guessing credentials is limited too.
+//!
+//! Buckets live in memory, so each Nexus instance limits independently.
+
+use nexus_config::RateLimitConfig;
+use nexus_db_lookup::LookupPath;
+use nexus_db_lookup::lookup;
+use nexus_db_queries::authn;
+use nexus_db_queries::authn::Actor;
+use nexus_db_queries::authz;
+use nexus_db_queries::context::OpContext;
+use nexus_db_queries::db;
+use nexus_types::external_api::silo;
+use omicron_common::api::external::Error;
+use omicron_common::api::external::LookupResult;
+use omicron_common::api::external::UpdateResult;
+use slog::warn;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::num::NonZeroU32;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+use uuid::Uuid;
+
+/// How long a Silo's rate limit overrides are cached before being re-read
+const SILO_LIMIT_TTL: Duration = Duration::from_secs(30);
+
+/// Number of buckets beyond which we prune those that have refilled
+///
+/// A full bucket is indistinguishable from one that was never created, so
+/// pruning those loses nothing.
+const MAX_BUCKETS: usize = 10_000;
+
+/// What a token bucket is keyed on
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum BucketKey {
+    Actor(Actor),
+    RemoteAddr(IpAddr),
+}
+
+/// The limit applied to a single bucket
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Limit {
+    requests_per_second: NonZeroU32,
+    burst: NonZeroU32,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+    /// when the bucket will be full again if no more requests arrive
+    full_at: Instant,
+}
+
+impl TokenBucket {
+    fn new(limit: &Limit, now: Instant) -> TokenBucket {
+        TokenBucket {
+            tokens: f64::from(limit.burst.get()),
+            last_refill: now,
+            full_at: now,
+        }
+    }
+
+    /// Takes a token from the bucket, or returns how long the caller must
+    /// wait until one is available
+    fn try_acquire(
+        &mut self,
+        limit: &Limit,
+        now: Instant,
+    ) -> Result<(), Duration> {
+        let rate = f64::from(limit.requests_per_second.get());
+        let burst = f64::from(limit.burst.get());
+        let elapsed =
+            now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * rate).min(burst);
+        self.last_refill = now;
+
+        let result = if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - self.tokens) / rate))
+        };
+        self.full_at =
+            now + Duration::from_secs_f64((burst - self.tokens) / rate);
+        result
+    }
+}
+
+/// In-memory state for external API rate limiting
+pub(crate) struct RateLimiter {
+    config: RateLimitConfig,
+    /// context used to read Silo overrides, which must not depend on the
+    /// privileges of whoever happens to be making the request
+    opctx: OpContext,
+    buckets: Mutex<HashMap<BucketKey, TokenBucket>>,
+    silo_limits: Mutex<HashMap<Uuid, (Instant, Limit)>>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(config: RateLimitConfig, opctx: OpContext) -> Self {
+        RateLimiter {
+            config,
+            opctx,
+            buckets: Mutex::new(HashMap::new()),
+            silo_limits: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn default_limit(&self) -> Limit {
+        Limit {
+            requests_per_second: self.config.requests_per_second,
+            burst: self.config.burst,
+        }
+    }
+
+    fn unauthenticated_limit(&self) -> Limit {
+        Limit {
+            requests_per_second: self
+                .config
+                .unauthenticated_requests_per_second,
+            burst: self.config.unauthenticated_burst,
+        }
+    }
+
+    fn limit_with_overrides(
+        &self,
+        overrides: &db::model::SiloRateLimit,
+    ) -> Limit {
+        let default = self.default_limit();
+        Limit {
+            requests_per_second: overrides
+                .requests_per_second()
+                .unwrap_or(default.requests_per_second),
+            burst: overrides.burst().unwrap_or(default.burst),
+        }
+    }
+
+    fn try_acquire(
+        &self,
+        key: BucketKey,
+        limit: &Limit,
+        now: Instant,
+    ) -> Result<(), Duration> {
+        let mut buckets = self.buckets.lock().unwrap();
+        if buckets.len() >= MAX_BUCKETS && !buckets.contains_key(&key) {
+            buckets.retain(|_, bucket| bucket.full_at > now);
+        }
+        buckets
+            .entry(key)
+            .or_insert_with(|| TokenBucket::new(limit, now))
+            .try_acquire(limit, now)
+    }
+
+    fn forget_silo(&self, silo_id: Uuid) {
+        self.silo_limits.lock().unwrap().remove(&silo_id);
+    }
+}
+
+impl super::Nexus {
+    /// Checks whether the request described by `authn` and `remote_addr` is
+    /// within its rate limit
+    ///
+    /// On failure, returns how long the client should wait before trying
+    /// again.
+    pub(crate) async fn rate_limit_check(
+        &self,
+        authn: &authn::Context,
+        remote_addr: IpAddr,
+    ) -> Result<(), Duration> {
+        let limiter = &self.rate_limiter;
+        if !limiter.config.enabled {
+            return Ok(());
+        }
+
+        let (key, limit) = match authn.actor() {
+            None => (
+                BucketKey::RemoteAddr(remote_addr),
+                limiter.unauthenticated_limit(),
+            ),
+            // Built-in users are only used by the control plane itself.
+            Some(Actor::UserBuiltin { .. }) => return Ok(()),
+            Some(actor) => {
+                let limit = match actor.silo_id() {
+                    Some(silo_id) => self.rate_limit_for_silo(silo_id).await,
+                    None => limiter.default_limit(),
+                };
+                (BucketKey::Actor(*actor), limit)
+            }
+        };
+
+        limiter.try_acquire(key, &limit, Instant::now())
+    }
+
+    /// Counts a request whose credentials failed to authenticate against the
+    /// rate limit for unauthenticated requests from `remote_addr`
+    ///
+    /// On failure, returns how long the client should wait before trying
+    /// again.
+    pub(crate) fn rate_limit_failed_authn(
+        &self,
+        remote_addr: IpAddr,
+    ) -> Result<(), Duration> {
+        let limiter = &self.rate_limiter;
+        if !limiter.config.enabled {
+            return Ok(());
+        }
+        limiter.try_acquire(
+            BucketKey::RemoteAddr(remote_addr),
+            &limiter.unauthenticated_limit(),
+            Instant::now(),
+        )
+    }
+
+    /// Returns the limit for principals in `silo_id`, consulting the
+    /// database if our cached copy is missing or stale
+    async fn rate_limit_for_silo(&self, silo_id: Uuid) -> Limit {
+        let limiter = &self.rate_limiter;
+        let now = Instant::now();
+        let cached = limiter.silo_limits.lock().unwrap().get(&silo_id).copied();
+        if let Some((fetched, limit)) = cached {
+            if now.saturating_duration_since(fetched) < SILO_LIMIT_TTL {
+                return limit;
+            }
+        }
+
+        let opctx = &limiter.opctx;
+        let result: Result<_, Error> = async {
+            let (.., authz_silo) = LookupPath::new(opctx, &self.db_datastore)
+                .silo_id(silo_id)
+                .lookup_for(authz::Action::Read)
+                .await?;
+            self.db_datastore.silo_rate_limit_view(opctx, &authz_silo).await
+        }
+        .await;
+
+        match result {
+            Ok(overrides) => {
+                let limit = limiter.limit_with_overrides(&overrides);
+                limiter
+                    .silo_limits
+                    .lock()
+                    .unwrap()
+                    .insert(silo_id, (now, limit));
+                limit
+            }
+            Err(error) => {
+                // Rather than fail the request, fall back to the defaults.
+                // We'll try again on the next request.
+                warn!(
+                    opctx.log,
+                    "failed to load silo rate limit";
+                    "silo_id" => %silo_id,
+                    "error" => %error,
+                );
+                limiter.default_limit()
+            }
+        }
+    }
+
+    pub(crate) async fn silo_rate_limit_view(
+        &self,
+        opctx: &OpContext,
+        silo_lookup: &lookup::Silo<'_>,
+    ) -> LookupResult<db::model::SiloRateLimit> {
+        let (.., authz_silo) =
+            silo_lookup.lookup_for(authz::Action::Read).await?;
+        self.db_datastore.silo_rate_limit_view(opctx, &authz_silo).await
+    }
+
+    pub(crate) async fn silo_rate_limit_update(
+        &self,
+        opctx: &OpContext,
+        silo_lookup: &lookup::Silo<'_>,
+        params: &silo::SiloRateLimitUpdate,
+    ) -> UpdateResult<db::model::SiloRateLimit> {
+        let (.., authz_silo) =
+            silo_lookup.lookup_for(authz::Action::Modify).await?;
+        let rate_limit = db::model::SiloRateLimit::from_update(
+            authz_silo.id(),
+            params.clone(),
+        );
+        let result = self
+            .db_datastore
+            .silo_rate_limit_update(opctx, &authz_silo, rate_limit)
+            .await?;
+
+        // Other Nexus instances will pick up the change when their cached
+        // copy expires, but there's no reason for this one to wait.
+        self.rate_limiter.forget_silo(authz_silo.id());
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Limit;
+    use super::TokenBucket;
+    use std::num::NonZeroU32;
+    use std::time::Duration;
+    use std::time::Instant;
+
+    #[test]
+    fn test_token_bucket() {
+        let limit = Limit {
+            requests_per_second: NonZeroU32::new(2).unwrap(),
+            burst: NonZeroU32::new(3).unwrap(),
+        };
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(&limit, start);
+
+        // The whole burst is available immediately.
+        for _ in 0..3 {
+            bucket.try_acquire(&limit, start).unwrap();
+        }
+        let wait = bucket.try_acquire(&limit, start).unwrap_err();
+        assert_eq!(wait, Duration::from_millis(500));
+
+        // After half a second, one more token is available.
+        let later = start + Duration::from_millis(500);
+        bucket.try_acquire(&limit, later).unwrap();
+        bucket.try_acquire(&limit, later).unwrap_err();
+
+        // Waiting a long time refills the bucket, but only up to the burst.
+        let much_later = later + Duration::from_secs(60);
+        for _ in 0..3 {
+            bucket.try_acquire(&limit, much_later).unwrap();
+        }
+        bucket.try_acquire(&limit, much_later).unwrap_err();
+        assert_eq!(bucket.full_at, much_later + Duration::from_millis(1500));
+    }
+}
@@ -15,7 +15,6 @@ use nexus_db_model::VmmCpuPlatform;
 use nexus_db_queries::db::datastore::sled::SledReservationReason;
 use nexus_db_queries::db::identity::Resource;
 use nexus_db_queries::{authn, authz, db};
-use nexus_types::internal_api::params::InstanceMigrateRequest;
 use nexus_types::saga::saga_action_failed;
 use omicron_common::api::external::Error;
 use omicron_uuid_kinds::{GenericUuid, InstanceUuid, PropolisUuid, SledUuid};
@@ -35,7 +34,10 @@ pub struct Params {
     pub serialized_authn: authn::saga::Serialized,
     pub instance: db::model::Instance,
     pub src_vmm: db::model::Vmm,
-    pub migrate_params: InstanceMigrateRequest,
+    /// The sled to which to migrate the instance. If this is `None`, the saga
+    /// selects any suitable sled other than the one the instance is currently
+    /// running on.
+    pub dst_sled_id: Option<SledUuid>,
 }
 
 // The migration saga is similar to the instance start saga: get a destination
@@ -174,7 +176,8 @@ async fn sim_generate_propolis_id(
     Ok(PropolisUuid::new_v4())
 }
 
-/// Reserves resources for the destination on the specified target sled.
+/// Reserves resources for the destination on the specified target sled, or on
+/// any suitable sled other than the source sled if no target was specified.
 async fn sim_reserve_sled_resources(
     sagactx: NexusActionContext,
 ) -> Result<SledUuid, ActionError> {
@@ -223,17 +226,22 @@ async fn sim_reserve_sled_resources(
         ))));
     };
 
-    // Add a constraint that requires the allocator to reserve on the
-    // migration's destination sled instead of a random sled.
+    // If a destination sled was requested, add a constraint that requires the
+    // allocator to reserve on that sled instead of a random sled. Otherwise,
+    // let the allocator pick any sled (subject to the instance's affinity
+    // rules) except the one we're migrating away from.
     //
-    // The destination sled ID is arbitrary (from the internal API), so it's
-    // possible that we were told to migrate to a sled that is incompatible with
-    // the VMM's CPU platform. Constrain by that so we'll fail to pick the
+    // The destination sled ID is arbitrary (from the API), so it's possible
+    // that we were told to migrate to a sled that is incompatible with the
+    // VMM's CPU platform. Constrain by that so we'll fail to pick the
     // destination sled if it's truly incompatible.
     let constraints = db::model::SledReservationConstraintBuilder::new()
-        .must_select_from(&[params.migrate_params.dst_sled_id])
-        .cpu_families(compatible_sled_families)
-        .build();
+        .cpu_families(compatible_sled_families);
+    let constraints = match params.dst_sled_id {
+        Some(dst_sled_id) => constraints.must_select_from(&[dst_sled_id]),
+        None => constraints.must_not_select_from(&[params.src_vmm.sled_id()]),
+    }
+    .build();
 
     let resource = super::instance_common::reserve_vmm_resources(
         osagactx.nexus(),
@@ -268,12 +276,9 @@ async fn sim_allocate_propolis_ip(
         &sagactx,
         &params.serialized_authn,
     );
-    allocate_vmm_ipv6(
-        &opctx,
-        sagactx.user_data().datastore(),
-        params.migrate_params.dst_sled_id,
-    )
-    .await
+    let dst_sled_id = sagactx.lookup::<SledUuid>("dst_sled_id")?;
+    allocate_vmm_ipv6(&opctx, sagactx.user_data().datastore(), dst_sled_id)
+        .await
 }
 
 async fn sim_create_migration_record(
@@ -700,7 +705,7 @@ mod tests {
             serialized_authn: authn::saga::Serialized::for_opctx(&opctx),
             instance: state.instance().clone(),
             src_vmm: vmm.clone(),
-            migrate_params: InstanceMigrateRequest { dst_sled_id },
+            dst_sled_id: Some(dst_sled_id),
         };
 
         nexus
@@ -721,6 +726,55 @@ mod tests {
         );
     }
 
+    #[nexus_test(server = crate::Server, extra_sled_agents = 1)]
+    async fn test_saga_without_destination_picks_other_sled(
+        cptestctx: &ControlPlaneTestContext,
+    ) {
+        let client = &cptestctx.external_client;
+        let nexus = &cptestctx.server.server_context().nexus;
+        let _project_id = setup_test_project(&client).await;
+
+        let opctx = test_helpers::test_opctx(cptestctx);
+        let instance = create_instance(client).await;
+        let instance_id = InstanceUuid::from_untyped_uuid(instance.identity.id);
+
+        // Poke the instance to get it into the Running state.
+        test_helpers::instance_simulate(cptestctx, &instance_id).await;
+
+        let state = test_helpers::instance_fetch(cptestctx, instance_id).await;
+        let vmm = state.vmm().as_ref().unwrap();
+        let params = Params {
+            serialized_authn: authn::saga::Serialized::for_opctx(&opctx),
+            instance: state.instance().clone(),
+            src_vmm: vmm.clone(),
+            dst_sled_id: None,
+        };
+
+        nexus
+            .sagas
+            .saga_execute::<SagaInstanceMigrate>(params)
+            .await
+            .expect("Migration saga should succeed");
+
+        // With only two sleds, the saga must have chosen the one the instance
+        // isn't running on.
+        let new_state =
+            test_helpers::instance_fetch(cptestctx, instance_id).await;
+        let dst_propolis_id = PropolisUuid::from_untyped_uuid(
+            new_state
+                .instance()
+                .runtime()
+                .dst_propolis_id
+                .expect("instance should have a migration target"),
+        );
+        let dst_vmm = nexus
+            .datastore()
+            .vmm_fetch(&opctx, &dst_propolis_id)
+            .await
+            .expect("target VMM should exist");
+        assert_ne!(dst_vmm.sled_id(), vmm.sled_id());
+    }
+
     #[nexus_test(server = crate::Server, extra_sled_agents = 1)]
     async fn test_action_failure_can_unwind(
         cptestctx: &ControlPlaneTestContext,
@@ -765,7 +819,7 @@ mod tests {
                         ),
                         instance: old_instance.clone(),
                         src_vmm: old_vmm.clone(),
-                        migrate_params: InstanceMigrateRequest { dst_sled_id },
+                        dst_sled_id: Some(dst_sled_id),
                     }
                 }
             })
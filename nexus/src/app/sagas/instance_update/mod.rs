@@ -344,6 +344,7 @@ use super::{
     ACTION_GENERATE_ID, ActionRegistry, NexusActionContext, NexusSaga,
     SagaContext, SagaInitError,
 };
+use crate::app::alert::publish_alert;
 use crate::app::db::datastore;
 use crate::app::db::datastore::InstanceGestalt;
 use crate::app::db::datastore::VmmStateUpdateResult;
@@ -353,6 +354,7 @@ use crate::app::db::model::Generation;
 use crate::app::db::model::InstanceIntendedState;
 use crate::app::db::model::InstanceRuntimeState;
 use crate::app::db::model::InstanceState;
+use crate::app::db::model::Migration;
 use crate::app::db::model::MigrationState;
 use crate::app::db::model::Vmm;
 use crate::app::db::model::VmmState;
@@ -362,11 +364,13 @@ use anyhow::Context;
 use chrono::Utc;
 use nexus_db_lookup::LookupPath;
 use nexus_db_queries::{authn, authz};
+use nexus_types::alert::instance as instance_alerts;
 use nexus_types::identity::Resource;
 use nexus_types::instance::SledVmmState;
 use nexus_types::saga::saga_action_failed;
 use omicron_common::api::external::Error;
 use omicron_common::backoff;
+use omicron_uuid_kinds::AlertUuid;
 use omicron_uuid_kinds::GenericUuid;
 use omicron_uuid_kinds::InstanceUuid;
 use omicron_uuid_kinds::PropolisUuid;
@@ -505,6 +509,21 @@ struct UpdatesRequired {
     /// instance has moved to a new sled, or deleting them if it is no longer
     /// incarnated.
     network_config: Option<NetworkConfigUpdate>,
+
+    /// Alerts to publish once the new runtime state has been committed.
+    ///
+    /// The IDs of these alerts are chosen when the updates are determined, so
+    /// that re-executing the action that publishes them does not publish
+    /// duplicate alerts.
+    alerts: Vec<(AlertUuid, InstanceAlert)>,
+}
+
+/// An alert describing a state transition performed by an update saga.
+#[derive(Debug, Deserialize, Serialize)]
+enum InstanceAlert {
+    Failed(instance_alerts::InstanceFailed),
+    MigrationCompleted(instance_alerts::InstanceMigrationCompleted),
+    MigrationFailed(instance_alerts::InstanceMigrationFailed),
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -543,6 +562,7 @@ impl UpdatesRequired {
         let mut active_vmm_failed = false;
         let mut network_config = None;
         let mut update_sled_reservations_for_migration_success = None;
+        let mut alerts = Vec::new();
 
         // Has the active VMM been destroyed?
         let destroy_active_vmm =
@@ -616,6 +636,14 @@ impl UpdatesRequired {
                 new_runtime.migration_id = None;
                 new_runtime.dst_propolis_id = None;
                 update_required = true;
+                alerts.push((
+                    AlertUuid::new_v4(),
+                    InstanceAlert::MigrationFailed(
+                        instance_alerts::InstanceMigrationFailed(
+                            migration_alert(snapshot, migration),
+                        ),
+                    ),
+                ));
 
                 // If the active VMM was destroyed, the network config must be
                 // deleted (which was determined above). Otherwise, if the
@@ -715,6 +743,14 @@ impl UpdatesRequired {
                             active_vmm_id: migration.source_propolis_id,
                             target_vmm_id: migration.target_propolis_id,
                         });
+                    alerts.push((
+                        AlertUuid::new_v4(),
+                        InstanceAlert::MigrationCompleted(
+                            instance_alerts::InstanceMigrationCompleted(
+                                migration_alert(snapshot, migration),
+                            ),
+                        ),
+                    ));
 
                     update_required = true;
                 }
@@ -739,6 +775,24 @@ impl UpdatesRequired {
             } else {
                 InstanceState::NoVmm
             };
+            if let Some(failed_vmm) =
+                snapshot.active_vmm.as_ref().filter(|_| active_vmm_failed)
+            {
+                alerts.push((
+                    AlertUuid::new_v4(),
+                    InstanceAlert::Failed(instance_alerts::InstanceFailed {
+                        instance_id: InstanceUuid::from_untyped_uuid(
+                            instance_id,
+                        ),
+                        instance_name: snapshot.instance.name().clone(),
+                        project_id: snapshot.instance.project_id,
+                        propolis_id: PropolisUuid::from_untyped_uuid(
+                            failed_vmm.id,
+                        ),
+                        sled_id: failed_vmm.sled_id(),
+                    }),
+                ));
+            }
             // If the active VMM was destroyed and the instance has not migrated
             // out of it, we must delete the instance's network configuration.
             //
@@ -771,10 +825,41 @@ impl UpdatesRequired {
             destroy_target_vmm,
             deprovision,
             network_config,
+            alerts,
         })
     }
 }
 
+/// Returns the details of `migration` to include in an instance migration
+/// alert.
+fn migration_alert(
+    snapshot: &InstanceGestalt,
+    migration: &Migration,
+) -> instance_alerts::InstanceMigration {
+    let sled_for_vmm = |vmm_id: Uuid| {
+        snapshot
+            .active_vmm
+            .iter()
+            .chain(snapshot.target_vmm.iter())
+            .find(|vmm| vmm.id == vmm_id)
+            .map(|vmm| vmm.sled_id())
+    };
+    instance_alerts::InstanceMigration {
+        instance_id: InstanceUuid::from_untyped_uuid(snapshot.instance.id()),
+        instance_name: snapshot.instance.name().clone(),
+        project_id: snapshot.instance.project_id,
+        migration_id: migration.id,
+        source_propolis_id: PropolisUuid::from_untyped_uuid(
+            migration.source_propolis_id,
+        ),
+        source_sled_id: sled_for_vmm(migration.source_propolis_id),
+        target_propolis_id: PropolisUuid::from_untyped_uuid(
+            migration.target_propolis_id,
+        ),
+        target_sled_id: sled_for_vmm(migration.target_propolis_id),
+    }
+}
+
 impl NetworkConfigUpdate {
     fn to_vmm(vmm: &Vmm) -> Self {
         Self::Update {
@@ -860,6 +945,16 @@ declare_saga_actions! {
         + siu_commit_instance_updates
     }
 
+    // Publish any alerts describing the state transitions committed by this
+    // saga.
+    //
+    // This occurs only after the new runtime state has been committed, so
+    // that alerts are not published for updates which are never actually
+    // performed. Failing to publish an alert does not unwind the saga.
+    PUBLISH_ALERTS -> "publish_alerts" {
+        + siu_publish_alerts
+    }
+
     // If a migration was successfully completed, update the target VMM's
     // `sled_resource_vmm` record to be the active one, and tombstone the source
     // record.
@@ -926,6 +1021,7 @@ impl NexusSaga for SagaDoActualInstanceUpdate {
             destroy_target_vmm,
             deprovision,
             network_config,
+            alerts,
         } = &params.update;
 
         // If a network config update is required, do that.
@@ -945,6 +1041,10 @@ impl NexusSaga for SagaDoActualInstanceUpdate {
         // write back the updated state and release the instance lock.
         builder.append(commit_instance_updates_action());
 
+        if !alerts.is_empty() {
+            builder.append(publish_alerts_action());
+        }
+
         // If a migration succeeded, we need to set the destination propolis'
         // associated sled_resource_vmm record's state to active, and the
         // source's to tombstoned.
@@ -1356,6 +1456,39 @@ async fn siu_commit_instance_updates(
     Ok(())
 }
 
+async fn siu_publish_alerts(
+    sagactx: NexusActionContext,
+) -> Result<(), ActionError> {
+    let osagactx = sagactx.user_data();
+    let RealParams { serialized_authn, ref update, .. } =
+        sagactx.saga_params::<RealParams>()?;
+
+    let opctx =
+        crate::context::op_context_for_saga_action(&sagactx, &serialized_authn);
+    let datastore = osagactx.datastore();
+    let alert_dispatcher =
+        &osagactx.nexus().background_tasks.task_alert_dispatcher;
+
+    for (id, alert) in &update.alerts {
+        match alert {
+            InstanceAlert::Failed(alert) => {
+                publish_alert(&opctx, datastore, alert_dispatcher, *id, alert)
+                    .await
+            }
+            InstanceAlert::MigrationCompleted(alert) => {
+                publish_alert(&opctx, datastore, alert_dispatcher, *id, alert)
+                    .await
+            }
+            InstanceAlert::MigrationFailed(alert) => {
+                publish_alert(&opctx, datastore, alert_dispatcher, *id, alert)
+                    .await
+            }
+        };
+    }
+
+    Ok(())
+}
+
 async fn siu_update_sled_reservations_for_migration_success(
     sagactx: NexusActionContext,
 ) -> Result<(), ActionError> {
@@ -1696,7 +1829,6 @@ mod test {
     use nexus_types::instance::Migrations;
     use nexus_types::instance::VmmFailureReason;
     use nexus_types::instance::VmmState as NexusVmmState;
-    use nexus_types::internal_api::params::InstanceMigrateRequest;
     use omicron_common::api::external::{
         ByteCount, DataPageParams, IdentityMetadataCreateParams,
         InstanceCpuCount, Name,
@@ -3133,7 +3265,7 @@ mod test {
                 serialized_authn: authn::saga::Serialized::for_opctx(&opctx),
                 instance: state.instance().clone(),
                 src_vmm: vmm.clone(),
-                migrate_params: InstanceMigrateRequest { dst_sled_id },
+                dst_sled_id: Some(dst_sled_id),
             };
 
             nexus
@@ -29,15 +29,21 @@
 //! 2. Clear the operating saga id from the request record, and change the state
 //!    to Completed.
 //!
+//! 3. Publish a `storage.region_replacement.finished` alert.
+//!
 
 use super::{
     ACTION_GENERATE_ID, ActionRegistry, NexusActionContext, NexusSaga,
     SagaInitError,
 };
+use crate::app::alert::publish_alert;
 use crate::app::sagas::declare_saga_actions;
 use crate::app::sagas::volume_delete;
 use crate::app::{authn, db};
+use nexus_types::alert::storage::RegionReplacementFinished;
 use nexus_types::saga::saga_action_failed;
+use omicron_uuid_kinds::AlertUuid;
+use omicron_uuid_kinds::GenericUuid;
 use omicron_uuid_kinds::VolumeUuid;
 use serde::Deserialize;
 use serde::Serialize;
@@ -92,6 +98,12 @@ impl NexusSaga for SagaRegionReplacementFinish {
             ACTION_GENERATE_ID.as_ref(),
         ));
 
+        builder.append(Node::action(
+            "alert_id",
+            "GenerateAlertId",
+            ACTION_GENERATE_ID.as_ref(),
+        ));
+
         builder.append(set_saga_id_action());
 
         let subsaga_params = volume_delete::Params {
@@ -193,6 +205,14 @@ async fn srrf_update_request_record(
     );
 
     let saga_id = sagactx.lookup::<Uuid>("saga_id")?;
+    let alert_id = sagactx.lookup::<Uuid>("alert_id")?;
+
+    let alert = RegionReplacementFinished {
+        request_id: params.request.id,
+        volume_id: params.request.volume_id(),
+        old_region_id: params.request.old_region_id,
+        new_region_id: params.request.new_region_id,
+    };
 
     // Now that the region has been deleted, update the replacement request
     // record to 'Complete' and clear the operating saga id. There is no undo
@@ -202,6 +222,15 @@ async fn srrf_update_request_record(
         .await
         .map_err(saga_action_failed)?;
 
+    publish_alert(
+        &opctx,
+        datastore,
+        &osagactx.nexus().background_tasks.task_alert_dispatcher,
+        AlertUuid::from_untyped_uuid(alert_id),
+        &alert,
+    )
+    .await;
+
     Ok(())
 }
 
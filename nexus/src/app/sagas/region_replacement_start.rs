@@ -40,6 +40,8 @@
 //! 4. Update the region replacement request by clearing the operating saga id
 //!    and changing the state to "Running".
 //!
+//! 5. Publish a `storage.region_replacement.started` alert.
+//!
 //! Any unwind will place the state back into Requested.
 //!
 //! See the documentation for the "region replacement drive" saga for the next
@@ -50,13 +52,16 @@ use super::{
     SagaInitError,
 };
 use crate::app::RegionAllocationStrategy;
+use crate::app::alert::publish_alert;
 use crate::app::db::datastore::VolumeReplaceResult;
 use crate::app::sagas::common_storage::find_only_new_region;
 use crate::app::sagas::declare_saga_actions;
 use crate::app::{authn, db};
 use nexus_db_queries::db::datastore::REGION_REDUNDANCY_THRESHOLD;
+use nexus_types::alert::storage::RegionReplacementStarted;
 use nexus_types::saga::saga_action_failed;
 use omicron_common::api::external::Error;
+use omicron_uuid_kinds::AlertUuid;
 use omicron_uuid_kinds::GenericUuid;
 use omicron_uuid_kinds::VolumeUuid;
 use serde::Deserialize;
@@ -146,6 +151,12 @@ impl NexusSaga for SagaRegionReplacementStart {
             ACTION_GENERATE_ID.as_ref(),
         ));
 
+        builder.append(Node::action(
+            "alert_id",
+            "GenerateAlertId",
+            ACTION_GENERATE_ID.as_ref(),
+        ));
+
         builder.append(set_saga_id_action());
         builder.append(get_existing_datasets_and_regions_action());
         builder.append(alloc_new_region_action());
@@ -754,6 +765,21 @@ async fn srrs_update_request_record(
         .await
         .map_err(saga_action_failed)?;
 
+    let alert_id = sagactx.lookup::<Uuid>("alert_id")?;
+    publish_alert(
+        &opctx,
+        datastore,
+        &osagactx.nexus().background_tasks.task_alert_dispatcher,
+        AlertUuid::from_untyped_uuid(alert_id),
+        &RegionReplacementStarted {
+            request_id: params.request.id,
+            volume_id: params.request.volume_id(),
+            old_region_id: params.request.old_region_id,
+            new_region_id,
+        },
+    )
+    .await;
+
     Ok(())
 }
 
@@ -0,0 +1,400 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Service accounts and their API tokens
+
+use nexus_db_lookup::LookupPath;
+use nexus_db_lookup::lookup;
+use nexus_db_model::DatabaseString;
+use nexus_db_queries::authz;
+use nexus_db_queries::context::OpContext;
+use nexus_db_queries::db;
+use nexus_db_queries::db::model::Name;
+use nexus_types::external_api::policy::ProjectRole;
+use nexus_types::external_api::policy::SiloRole;
+use nexus_types::external_api::service_account;
+use nexus_types::external_api::service_account::ServiceAccountRoleAssignment;
+use nexus_types::identity::Resource;
+use omicron_common::api::external::CreateResult;
+use omicron_common::api::external::DeleteResult;
+use omicron_common::api::external::Error;
+use omicron_common::api::external::InternalContext;
+use omicron_common::api::external::ListResultVec;
+use omicron_common::api::external::LookupResult;
+use omicron_common::api::external::NameOrId;
+use omicron_common::api::external::ResourceType;
+use omicron_common::api::external::UpdateResult;
+use omicron_common::api::external::http_pagination::PaginatedBy;
+use omicron_uuid_kinds::GenericUuid;
+use omicron_uuid_kinds::ServiceAccountTokenUuid;
+use omicron_uuid_kinds::ServiceAccountUuid;
+use ref_cast::RefCast;
+use std::collections::BTreeSet;
+use uuid::Uuid;
+
+impl super::Nexus {
+    /// Look up a service account in the current silo
+    pub fn service_account_lookup<'a>(
+        &'a self,
+        opctx: &'a OpContext,
+        service_account: &'a NameOrId,
+    ) -> LookupResult<lookup::ServiceAccount<'a>> {
+        match service_account {
+            NameOrId::Id(id) => {
+                let sa = LookupPath::new(opctx, &self.db_datastore)
+                    .service_account_id(ServiceAccountUuid::from_untyped_uuid(
+                        *id,
+                    ));
+                Ok(sa)
+            }
+            NameOrId::Name(name) => {
+                let sa = LookupPath::new(opctx, &self.db_datastore)
+                    .service_account_name(Name::ref_cast(name));
+                Ok(sa)
+            }
+        }
+    }
+
+    pub(crate) async fn service_account_create(
+        &self,
+        opctx: &OpContext,
+        params: service_account::ServiceAccountCreate,
+    ) -> CreateResult<db::model::ServiceAccount> {
+        let authz_silo = opctx
+            .authn
+            .silo_required()
+            .internal_context("creating a service account")?;
+        let authz_sa_list = authz::SiloServiceAccountList::new(authz_silo);
+        let service_account =
+            db::model::ServiceAccount::new(authz_sa_list.silo().id(), params);
+        let service_account = self
+            .db_datastore
+            .service_account_create(opctx, &authz_sa_list, service_account)
+            .await?;
+        opctx.set_audit_target(
+            ResourceType::ServiceAccount,
+            service_account.id().into_untyped_uuid(),
+        );
+        Ok(service_account)
+    }
+
+    pub(crate) async fn service_accounts_list(
+        &self,
+        opctx: &OpContext,
+        pagparams: &PaginatedBy<'_>,
+    ) -> ListResultVec<db::model::ServiceAccount> {
+        let authz_silo = opctx
+            .authn
+            .silo_required()
+            .internal_context("listing service accounts")?;
+        let authz_sa_list = authz::SiloServiceAccountList::new(authz_silo);
+        self.db_datastore
+            .service_account_list(opctx, &authz_sa_list, pagparams)
+            .await
+    }
+
+    pub(crate) async fn service_account_delete(
+        &self,
+        opctx: &OpContext,
+        sa_lookup: &lookup::ServiceAccount<'_>,
+    ) -> DeleteResult {
+        let (.., authz_sa) =
+            sa_lookup.lookup_for(authz::Action::Delete).await?;
+        opctx.set_audit_target(
+            ResourceType::ServiceAccount,
+            authz_sa.id().into_untyped_uuid(),
+        );
+        self.db_datastore.service_account_delete(opctx, &authz_sa).await
+    }
+
+    pub(crate) async fn service_account_policy_view(
+        &self,
+        opctx: &OpContext,
+        sa_lookup: &lookup::ServiceAccount<'_>,
+    ) -> LookupResult<service_account::ServiceAccountPolicy> {
+        let (.., authz_sa) = sa_lookup.lookup_for(authz::Action::Read).await?;
+        let role_assignments = self
+            .db_datastore
+            .service_account_role_assignments_list(opctx, &authz_sa)
+            .await?
+            .into_iter()
+            .map(role_assignment_from_db)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(service_account::ServiceAccountPolicy { role_assignments })
+    }
+
+    /// Replace the set of roles granted to a service account
+    ///
+    /// Besides being able to modify the service account itself, the caller
+    /// must be allowed to modify the policy of every Silo or project whose
+    /// assignment is being added or removed. Otherwise, anybody who could
+    /// create a service account could use it to grant themselves any role.
+    pub(crate) async fn service_account_policy_update(
+        &self,
+        opctx: &OpContext,
+        sa_lookup: &lookup::ServiceAccount<'_>,
+        new_policy: &service_account::ServiceAccountPolicy,
+    ) -> UpdateResult<service_account::ServiceAccountPolicy> {
+        let (authz_silo, authz_sa) =
+            sa_lookup.lookup_for(authz::Action::Modify).await?;
+        opctx.set_audit_target(
+            ResourceType::ServiceAccount,
+            authz_sa.id().into_untyped_uuid(),
+        );
+
+        let old_assignments = self
+            .db_datastore
+            .service_account_role_assignments_list(opctx, &authz_sa)
+            .await?;
+
+        let mut new_assignments = Vec::new();
+        let mut silo_touched = false;
+        let mut new_projects = BTreeSet::new();
+        for assignment in &new_policy.role_assignments {
+            let db_assignment = match assignment {
+                ServiceAccountRoleAssignment::Silo { role_name } => {
+                    silo_touched = true;
+                    db::model::RoleAssignment::new_for_service_account(
+                        authz_sa.id(),
+                        ResourceType::Silo,
+                        authz_silo.id(),
+                        &role_name.to_database_string(),
+                    )
+                }
+                ServiceAccountRoleAssignment::Project {
+                    project_id,
+                    role_name,
+                } => {
+                    new_projects.insert(*project_id);
+                    db::model::RoleAssignment::new_for_service_account(
+                        authz_sa.id(),
+                        ResourceType::Project,
+                        *project_id,
+                        &role_name.to_database_string(),
+                    )
+                }
+            };
+            new_assignments.push(db_assignment);
+        }
+
+        let mut old_projects = BTreeSet::new();
+        for assignment in &old_assignments {
+            if assignment.resource_type == ResourceType::Silo.to_string() {
+                silo_touched = true;
+            } else if assignment.resource_type
+                == ResourceType::Project.to_string()
+            {
+                old_projects.insert(assignment.resource_id);
+            }
+        }
+
+        if silo_touched {
+            opctx.authorize(authz::Action::ModifyPolicy, &authz_silo).await?;
+        }
+
+        // Any project being granted a role must exist within the service
+        // account's Silo.
+        for project_id in &new_projects {
+            self.service_account_authorize_project(
+                opctx,
+                &authz_silo,
+                *project_id,
+            )
+            .await?;
+        }
+
+        // Projects whose roles are being revoked may since have been deleted,
+        // in which case there's nothing left to protect.
+        for project_id in old_projects.difference(&new_projects) {
+            match self
+                .service_account_authorize_project(
+                    opctx,
+                    &authz_silo,
+                    *project_id,
+                )
+                .await
+            {
+                Ok(()) | Err(Error::ObjectNotFound { .. }) => (),
+                Err(error) => return Err(error),
+            }
+        }
+
+        let role_assignments = self
+            .db_datastore
+            .service_account_role_assignments_replace(
+                opctx,
+                &authz_sa,
+                new_assignments,
+            )
+            .await?
+            .into_iter()
+            .map(role_assignment_from_db)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(service_account::ServiceAccountPolicy { role_assignments })
+    }
+
+    async fn service_account_authorize_project(
+        &self,
+        opctx: &OpContext,
+        authz_silo: &authz::Silo,
+        project_id: Uuid,
+    ) -> Result<(), Error> {
+        let (project_silo, _) = LookupPath::new(opctx, &self.db_datastore)
+            .project_id(project_id)
+            .lookup_for(authz::Action::ModifyPolicy)
+            .await?;
+        if project_silo.id() != authz_silo.id() {
+            return Err(Error::invalid_request(format!(
+                "project {} is not in the service account's silo",
+                project_id
+            )));
+        }
+        Ok(())
+    }
+
+    // Service account tokens
+
+    /// Looks up one of `service_account`'s tokens
+    ///
+    /// A token selected by ID is looked up directly, so it's checked against
+    /// `service_account` here: a token of some other service account is
+    /// reported as not found.
+    pub async fn service_account_token_lookup<'a>(
+        &'a self,
+        opctx: &'a OpContext,
+        service_account: &'a NameOrId,
+        token: &'a NameOrId,
+    ) -> LookupResult<lookup::ServiceAccountToken<'a>> {
+        match token {
+            NameOrId::Id(id) => {
+                let (.., authz_sa) = self
+                    .service_account_lookup(opctx, service_account)?
+                    .lookup_for(authz::Action::Read)
+                    .await?;
+                let token = LookupPath::new(opctx, &self.db_datastore)
+                    .service_account_token_id(
+                        ServiceAccountTokenUuid::from_untyped_uuid(*id),
+                    );
+                let (_, token_sa, _) =
+                    token.lookup_for(authz::Action::Read).await?;
+                if token_sa.id() != authz_sa.id() {
+                    return Err(Error::not_found_by_id(
+                        ResourceType::ServiceAccountToken,
+                        id,
+                    ));
+                }
+                Ok(token)
+            }
+            NameOrId::Name(name) => {
+                let token = self
+                    .service_account_lookup(opctx, service_account)?
+                    .service_account_token_name(Name::ref_cast(name));
+                Ok(token)
+            }
+        }
+    }
+
+    pub(crate) async fn service_account_token_create(
+        &self,
+        opctx: &OpContext,
+        sa_lookup: &lookup::ServiceAccount<'_>,
+        params: service_account::ServiceAccountTokenCreate,
+    ) -> CreateResult<db::model::ServiceAccountToken> {
+        if let Some(time_expires) = params.time_expires {
+            if time_expires <= chrono::Utc::now() {
+                return Err(Error::invalid_request(
+                    "token expiration must be in the future",
+                ));
+            }
+        }
+        let (.., authz_sa) =
+            sa_lookup.lookup_for(authz::Action::CreateChild).await?;
+        let token = db::model::ServiceAccountToken::new(authz_sa.id(), params);
+        let token = self
+            .db_datastore
+            .service_account_token_create(opctx, &authz_sa, token)
+            .await?;
+        opctx.set_audit_target(
+            ResourceType::ServiceAccountToken,
+            token.id().into_untyped_uuid(),
+        );
+        Ok(token)
+    }
+
+    pub(crate) async fn service_account_tokens_list(
+        &self,
+        opctx: &OpContext,
+        sa_lookup: &lookup::ServiceAccount<'_>,
+        pagparams: &PaginatedBy<'_>,
+    ) -> ListResultVec<db::model::ServiceAccountToken> {
+        let (.., authz_sa) =
+            sa_lookup.lookup_for(authz::Action::ListChildren).await?;
+        self.db_datastore
+            .service_account_token_list(opctx, &authz_sa, pagparams)
+            .await
+    }
+
+    pub(crate) async fn service_account_token_delete(
+        &self,
+        opctx: &OpContext,
+        token_lookup: &lookup::ServiceAccountToken<'_>,
+    ) -> DeleteResult {
+        let (.., authz_token) =
+            token_lookup.lookup_for(authz::Action::Delete).await?;
+        opctx.set_audit_target(
+            ResourceType::ServiceAccountToken,
+            authz_token.id().into_untyped_uuid(),
+        );
+        self.db_datastore
+            .service_account_token_delete(opctx, &authz_token)
+            .await
+    }
+
+    /// Replace a token with a new one having the same name, expiration, and
+    /// restrictions, but a new secret value
+    pub(crate) async fn service_account_token_rotate(
+        &self,
+        opctx: &OpContext,
+        token_lookup: &lookup::ServiceAccountToken<'_>,
+    ) -> UpdateResult<db::model::ServiceAccountToken> {
+        let (.., authz_token, db_token) =
+            token_lookup.fetch_for(authz::Action::Modify).await?;
+        opctx.set_audit_target(
+            ResourceType::ServiceAccountToken,
+            authz_token.id().into_untyped_uuid(),
+        );
+        self.db_datastore
+            .service_account_token_rotate(opctx, &authz_token, &db_token)
+            .await
+    }
+}
+
+fn role_assignment_from_db(
+    assignment: db::model::RoleAssignment,
+) -> Result<ServiceAccountRoleAssignment, Error> {
+    let parse_error = |error: anyhow::Error| {
+        Error::internal_error(&format!(
+            "parsing database role assignment: {:#}",
+            error
+        ))
+    };
+    if assignment.resource_type == ResourceType::Silo.to_string() {
+        let role_name = SiloRole::from_database_string(&assignment.role_name)
+            .map_err(parse_error)?;
+        Ok(ServiceAccountRoleAssignment::Silo { role_name })
+    } else if assignment.resource_type == ResourceType::Project.to_string() {
+        let role_name =
+            ProjectRole::from_database_string(&assignment.role_name)
+                .map_err(parse_error)?;
+        Ok(ServiceAccountRoleAssignment::Project {
+            project_id: assignment.resource_id,
+            role_name,
+        })
+    } else {
+        Err(Error::internal_error(&format!(
+            "unexpected resource type for service account role: {:?}",
+            assignment.resource_type
+        )))
+    }
+}
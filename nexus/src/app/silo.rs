@@ -39,6 +39,7 @@ use omicron_common::api::external::http_pagination::PaginatedBy;
 use omicron_common::api::external::{DataPageParams, ResourceType};
 use omicron_common::api::external::{DeleteResult, NameOrId};
 use omicron_common::api::external::{Error, InternalContext};
+use omicron_uuid_kinds::GenericUuid;
 use omicron_uuid_kinds::SiloGroupUuid;
 use omicron_uuid_kinds::SiloUserUuid;
 use slog_error_chain::InlineErrorChain;
@@ -158,6 +159,7 @@ impl super::Nexus {
                 dns_update,
             )
             .await?;
+        opctx.set_audit_target(ResourceType::Silo, silo.id());
         self.background_tasks
             .activate(&self.background_tasks.task_external_dns_config);
         self.background_tasks
@@ -184,6 +186,7 @@ impl super::Nexus {
         let datastore = self.datastore();
         let (.., authz_silo, db_silo) =
             silo_lookup.fetch_for(authz::Action::Delete).await?;
+        opctx.set_audit_target(ResourceType::Silo, authz_silo.id());
         let mut dns_update = DnsVersionUpdateBuilder::new(
             DnsGroup::External,
             format!("delete silo: {:?}", db_silo.name()),
@@ -481,6 +484,10 @@ impl super::Nexus {
             .datastore()
             .silo_user_create(&authz_silo, silo_user.clone().into())
             .await?;
+        opctx.set_audit_target(
+            ResourceType::SiloUser,
+            authz_silo_user.id().into_untyped_uuid(),
+        );
 
         self.silo_user_password_set_internal(
             opctx,
@@ -511,6 +518,10 @@ impl super::Nexus {
                 authz::Action::Delete,
             )
             .await?;
+        opctx.set_audit_target(
+            ResourceType::SiloUser,
+            authz_silo_user.id().into_untyped_uuid(),
+        );
         self.db_datastore.silo_user_delete(opctx, &authz_silo_user).await
     }
 
@@ -710,6 +721,10 @@ impl super::Nexus {
                 authz::Action::Modify,
             )
             .await?;
+        opctx.set_audit_target(
+            ResourceType::SiloUser,
+            authz_silo_user.id().into_untyped_uuid(),
+        );
 
         let silo_user = match &silo_user {
             SiloUser::ApiOnly(user) => user,
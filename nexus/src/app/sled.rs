@@ -4,6 +4,7 @@
 
 //! Sleds, and the hardware and services within them.
 
+use crate::app::alert::publish_alert;
 use crate::internal_api::params::{
     PhysicalDiskPutRequest, SledAgentInfo, ZpoolPutRequest,
 };
@@ -13,16 +14,22 @@ use nexus_db_queries::authz;
 use nexus_db_queries::context::OpContext;
 use nexus_db_queries::db;
 use nexus_db_queries::db::datastore::sled::SledReservationReason;
+use nexus_types::alert::hardware::PhysicalDiskExpunged;
+use nexus_types::alert::hardware::SledPolicyChanged;
 use nexus_types::deployment::DiskFilter;
 use nexus_types::deployment::SledFilter;
 use nexus_types::external_api::path_params;
 use nexus_types::external_api::physical_disk::PhysicalDiskPolicy;
+use nexus_types::external_api::sled;
 use nexus_types::external_api::sled::{SledPolicy, SledProvisionPolicy};
+use nexus_types::identity::Asset;
 use omicron_common::api::external::ByteCount;
 use omicron_common::api::external::DataPageParams;
 use omicron_common::api::external::Error;
 use omicron_common::api::external::ListResultVec;
 use omicron_common::api::external::LookupResult;
+use omicron_common::api::external::UpdateResult;
+use omicron_uuid_kinds::AlertUuid;
 use omicron_uuid_kinds::DatasetUuid;
 use omicron_uuid_kinds::GenericUuid;
 use omicron_uuid_kinds::InstanceUuid;
@@ -156,6 +163,15 @@ impl super::Nexus {
             .db_datastore
             .sled_set_policy_to_expunged(opctx, &authz_sled)
             .await?;
+        if prev_policy != SledPolicy::Expunged {
+            self.publish_sled_policy_changed(
+                opctx,
+                &sled,
+                prev_policy,
+                SledPolicy::Expunged,
+            )
+            .await;
+        }
 
         // The instance-watcher background task is responsible for marking any
         // VMMs running on `Expunged` sleds as `Failed`, so that their instances
@@ -245,11 +261,141 @@ impl super::Nexus {
         sled_lookup: &lookup::Sled<'_>,
         new_policy: SledProvisionPolicy,
     ) -> Result<SledProvisionPolicy, Error> {
-        let (authz_sled,) =
-            sled_lookup.lookup_for(authz::Action::Modify).await?;
-        self.db_datastore
+        let (authz_sled, sled) =
+            sled_lookup.fetch_for(authz::Action::Modify).await?;
+        let old_policy = self
+            .db_datastore
             .sled_set_provision_policy(opctx, &authz_sled, new_policy)
-            .await
+            .await?;
+        if old_policy != new_policy {
+            self.publish_sled_policy_changed(
+                opctx,
+                &sled,
+                SledPolicy::InService { provision_policy: old_policy },
+                SledPolicy::InService { provision_policy: new_policy },
+            )
+            .await;
+        }
+        Ok(old_policy)
+    }
+
+    async fn publish_sled_policy_changed(
+        &self,
+        opctx: &OpContext,
+        sled: &db::model::Sled,
+        old_policy: SledPolicy,
+        new_policy: SledPolicy,
+    ) {
+        let alert = SledPolicyChanged {
+            sled_id: sled.id(),
+            serial: sled.serial_number().to_string(),
+            old_policy,
+            new_policy,
+        };
+        publish_alert(
+            opctx,
+            &self.db_datastore,
+            &self.background_tasks.task_alert_dispatcher,
+            AlertUuid::new_v4(),
+            &alert,
+        )
+        .await;
+    }
+
+    // Sled evacuation
+
+    /// Start evacuating all running instances from a sled
+    ///
+    /// The sled is first marked non-provisionable, so that no new instances
+    /// are placed on it; the `sled_evacuator` background task then migrates
+    /// the instances that remain.
+    pub(crate) async fn sled_evacuation_start(
+        &self,
+        opctx: &OpContext,
+        sled_lookup: &lookup::Sled<'_>,
+    ) -> Result<sled::SledEvacuation, Error> {
+        let (authz_sled, sled) =
+            sled_lookup.fetch_for(authz::Action::Modify).await?;
+        if !matches!(sled.policy(), SledPolicy::InService { .. }) {
+            return Err(Error::invalid_request(format!(
+                "sled {} is not in service and cannot be evacuated",
+                authz_sled.id()
+            )));
+        }
+
+        self.sled_set_provision_policy(
+            opctx,
+            sled_lookup,
+            SledProvisionPolicy::NonProvisionable,
+        )
+        .await?;
+
+        let progress = self
+            .db_datastore
+            .sled_evacuation_progress(opctx, authz_sled.id())
+            .await?;
+        let evacuation = self
+            .db_datastore
+            .sled_evacuation_create(
+                opctx,
+                &authz_sled,
+                progress.instances_remaining,
+            )
+            .await?;
+        info!(
+            opctx.log,
+            "started sled evacuation";
+            "sled_id" => %authz_sled.id(),
+            "evacuation_id" => %evacuation.id,
+            "instances" => progress.instances_remaining,
+        );
+        self.background_tasks.task_sled_evacuator.activate();
+
+        Ok(evacuation.into_view(progress))
+    }
+
+    /// Fetch the most recent evacuation of a sled, with its progress
+    pub(crate) async fn sled_evacuation_view(
+        &self,
+        opctx: &OpContext,
+        sled_lookup: &lookup::Sled<'_>,
+    ) -> LookupResult<sled::SledEvacuation> {
+        let (.., authz_sled) =
+            sled_lookup.lookup_for(authz::Action::Read).await?;
+        let evacuation = self
+            .db_datastore
+            .sled_evacuation_fetch_latest(opctx, &authz_sled)
+            .await?;
+        let progress = self
+            .db_datastore
+            .sled_evacuation_progress(opctx, authz_sled.id())
+            .await?;
+        Ok(evacuation.into_view(progress))
+    }
+
+    /// Cancel the in-progress evacuation of a sled
+    pub(crate) async fn sled_evacuation_cancel(
+        &self,
+        opctx: &OpContext,
+        sled_lookup: &lookup::Sled<'_>,
+    ) -> UpdateResult<sled::SledEvacuation> {
+        let (.., authz_sled) =
+            sled_lookup.lookup_for(authz::Action::Modify).await?;
+        let evacuation = self
+            .db_datastore
+            .sled_evacuation_cancel(opctx, &authz_sled)
+            .await?;
+        info!(
+            opctx.log,
+            "cancelled sled evacuation";
+            "sled_id" => %authz_sled.id(),
+            "evacuation_id" => %evacuation.id,
+        );
+        let progress = self
+            .db_datastore
+            .sled_evacuation_progress(opctx, authz_sled.id())
+            .await?;
+        Ok(evacuation.into_view(progress))
     }
 
     // Physical disks
@@ -397,15 +543,34 @@ impl super::Nexus {
         disk: path_params::PhysicalDiskPath,
     ) -> Result<(), Error> {
         let physical_disk_lookup = self.physical_disk_lookup(opctx, &disk)?;
-        let (authz_disk,) =
-            physical_disk_lookup.lookup_for(authz::Action::Modify).await?;
+        let (authz_disk, db_disk) =
+            physical_disk_lookup.fetch_for(authz::Action::Modify).await?;
         self.db_datastore
             .physical_disk_update_policy(
                 opctx,
                 authz_disk.id(),
                 PhysicalDiskPolicy::Expunged.into(),
             )
-            .await
+            .await?;
+
+        if db_disk.disk_policy != db::model::PhysicalDiskPolicy::Expunged {
+            let alert = PhysicalDiskExpunged {
+                disk_id: db_disk.id(),
+                sled_id: db_disk.sled_id.into(),
+                vendor: db_disk.vendor,
+                serial: db_disk.serial,
+                model: db_disk.model,
+            };
+            publish_alert(
+                opctx,
+                &self.db_datastore,
+                &self.background_tasks.task_alert_dispatcher,
+                AlertUuid::new_v4(),
+                &alert,
+            )
+            .await;
+        }
+        Ok(())
     }
 
     // Zpools (contained within sleds)
@@ -0,0 +1,500 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! # SMTP Alert Receivers
+//!
+//! SMTP receivers deliver [alerts] as email messages, by relaying them through
+//! an operator-provided mail server.  Each delivery attempt is a single SMTP
+//! transaction with that server: the alert is considered delivered once the
+//! server has accepted the message for all of the receiver's recipients.
+//!
+//! Generic operations on all types of alert receivers are defined in the
+//! [`alert` module][alerts].  Creating and updating SMTP receiver
+//! configurations is defined here, along with [`SmtpClient`], which is used
+//! by both the liveness probe API and the `webhook_deliverator` background
+//! task to send messages.
+//!
+//! The client is intentionally minimal, and doesn't authenticate to the
+//! server.  It uses STARTTLS whenever the server offers it, verifying the
+//! server's certificate against the system's trusted roots, and records when
+//! that certificate expires, as for webhook receivers.  A server that offers
+//! STARTTLS but presents a certificate we can't verify is treated as
+//! unreachable: we never fall back to sending the message in the clear.  A
+//! server that doesn't offer STARTTLS at all receives the message over plain
+//! SMTP, so such a relay should be on a trusted network.
+//!
+//! [alerts]: super::alert
+
+use super::webhook::DeliveryPayload;
+use crate::Nexus;
+use chrono::DateTime;
+use chrono::TimeDelta;
+use chrono::Utc;
+use nexus_db_lookup::lookup;
+use nexus_db_queries::authz;
+use nexus_db_queries::context::OpContext;
+use nexus_db_queries::db::model::AlertReceiver;
+use nexus_db_queries::db::model::AlertRxKind;
+use nexus_db_queries::db::model::SqlU8;
+use nexus_db_queries::db::model::WebhookDelivery;
+use nexus_db_queries::db::model::WebhookDeliveryAttempt;
+use nexus_db_queries::db::model::WebhookDeliveryAttemptResult;
+use nexus_db_queries::db::model::WebhookReceiverConfig;
+use nexus_types::alert::AlertClass;
+use nexus_types::external_api::alert;
+use nexus_types::identity::Resource;
+use omicron_common::api::external::CreateResult;
+use omicron_common::api::external::Error;
+use omicron_common::api::external::ResourceType;
+use omicron_common::api::external::UpdateResult;
+use omicron_uuid_kinds::GenericUuid;
+use omicron_uuid_kinds::OmicronZoneUuid;
+use omicron_uuid_kinds::WebhookDeliveryAttemptUuid;
+use std::sync::Arc;
+use std::sync::LazyLock;
+use std::time::Duration;
+use std::time::Instant;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncWrite;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+use tokio::net::TcpStream;
+
+/// How long we wait to establish a connection to the mail server.
+///
+/// This is the same as the connect timeout for webhook receivers.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long the whole SMTP transaction may take, including connecting.
+///
+/// This is the same as the request timeout for webhook receivers.
+const TRANSACTION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The TLS configuration used with mail servers that offer STARTTLS.
+///
+/// Servers' certificates are verified against the system's trusted roots.
+/// Roots that fail to load are skipped; if none load, every handshake fails.
+static TLS_CONFIG: LazyLock<Arc<rustls::ClientConfig>> = LazyLock::new(|| {
+    let mut roots = rustls::RootCertStore::empty();
+    roots.add_parsable_certificates(
+        rustls_native_certs::load_native_certs().certs,
+    );
+    Arc::new(
+        rustls::ClientConfig::builder()
+            .with_root_certificates(roots)
+            .with_no_client_auth(),
+    )
+});
+
+impl Nexus {
+    pub async fn smtp_receiver_create(
+        &self,
+        opctx: &OpContext,
+        params: alert::SmtpCreate,
+    ) -> CreateResult<WebhookReceiverConfig> {
+        let rx = self.datastore().smtp_rx_create(&opctx, params).await?;
+        opctx.set_audit_target(
+            ResourceType::AlertReceiver,
+            rx.rx.id().into_untyped_uuid(),
+        );
+        Ok(rx)
+    }
+
+    pub async fn smtp_receiver_update(
+        &self,
+        opctx: &OpContext,
+        rx: lookup::AlertReceiver<'_>,
+        params: alert::SmtpReceiverUpdate,
+    ) -> UpdateResult<()> {
+        let (authz_rx, db_rx) = rx.fetch_for(authz::Action::Modify).await?;
+        opctx.set_audit_target(
+            ResourceType::AlertReceiver,
+            authz_rx.id().into_untyped_uuid(),
+        );
+        db_rx.ensure_kind(AlertRxKind::Smtp)?;
+        let _ = self
+            .datastore()
+            .smtp_rx_update(opctx, &authz_rx, &db_rx, params)
+            .await?;
+        Ok(())
+    }
+}
+
+/// Everything necessary to deliver an alert to an SMTP receiver.
+pub(crate) struct SmtpClient<'a> {
+    rx: &'a AlertReceiver,
+    config: alert::SmtpReceiverConfig,
+    nexus_id: OmicronZoneUuid,
+}
+
+/// The ways in which an SMTP transaction can fail.
+#[derive(Debug)]
+enum SmtpError {
+    /// The server replied with an unexpected reply code.
+    Rejected { code: u16, message: String },
+    /// We couldn't talk to the server.
+    Io(std::io::Error),
+    /// The server said something that isn't an SMTP reply.
+    Protocol(String),
+}
+
+impl From<std::io::Error> for SmtpError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl<'a> SmtpClient<'a> {
+    pub(crate) fn new(
+        rx: &'a AlertReceiver,
+        nexus_id: OmicronZoneUuid,
+    ) -> Result<Self, Error> {
+        let config = rx.smtp_config()?;
+        Ok(Self { rx, config, nexus_id })
+    }
+
+    pub(crate) async fn send_delivery_request(
+        &mut self,
+        opctx: &OpContext,
+        delivery: &WebhookDelivery,
+        alert_class: impl Into<AlertClass>,
+        alert_version: u32,
+        data: &serde_json::Value,
+    ) -> Result<WebhookDeliveryAttempt, anyhow::Error> {
+        let alert_class = alert_class.into();
+        let time_attempted = Utc::now();
+        let sent_at = time_attempted.to_rfc3339();
+        let payload = DeliveryPayload::new(
+            delivery,
+            self.rx,
+            alert_class,
+            alert_version,
+            data,
+            &sent_at,
+        );
+        let body = serde_json::to_string_pretty(&payload)?;
+        let message = self.format_message(delivery, alert_class, &body);
+
+        let t0 = Instant::now();
+        let mut time_peer_cert_expires = None;
+        let result = tokio::time::timeout(
+            TRANSACTION_TIMEOUT,
+            self.transaction(&message, &mut time_peer_cert_expires),
+        )
+        .await;
+        let duration = t0.elapsed();
+        let (delivery_result, reply_code) = match result {
+            Ok(Ok(code)) => {
+                slog::debug!(
+                    &opctx.log,
+                    "alert delivered to SMTP server successfully";
+                    "alert_id" => %delivery.alert_id,
+                    "alert_class" => %alert_class,
+                    "delivery_id" => %delivery.id,
+                    "delivery_trigger" => %delivery.triggered_by,
+                    "reply_code" => code,
+                    "duration" => ?duration,
+                );
+                (WebhookDeliveryAttemptResult::Succeeded, Some(code))
+            }
+            Ok(Err(SmtpError::Rejected { code, message })) => {
+                slog::warn!(
+                    &opctx.log,
+                    "SMTP server rejected alert delivery";
+                    "alert_id" => %delivery.alert_id,
+                    "alert_class" => %alert_class,
+                    "delivery_id" => %delivery.id,
+                    "delivery_trigger" => %delivery.triggered_by,
+                    "reply_code" => code,
+                    "reply" => %message,
+                );
+                // Rejections are recorded as HTTP errors, with the SMTP reply
+                // code in place of the HTTP status.
+                (WebhookDeliveryAttemptResult::FailedHttpError, Some(code))
+            }
+            Ok(Err(SmtpError::Protocol(message))) => {
+                slog::warn!(
+                    &opctx.log,
+                    "SMTP server sent a malformed reply";
+                    "alert_id" => %delivery.alert_id,
+                    "alert_class" => %alert_class,
+                    "delivery_id" => %delivery.id,
+                    "delivery_trigger" => %delivery.triggered_by,
+                    "reply" => %message,
+                );
+                (WebhookDeliveryAttemptResult::FailedHttpError, None)
+            }
+            Ok(Err(SmtpError::Io(e))) => {
+                let result = if e.kind() == std::io::ErrorKind::TimedOut {
+                    WebhookDeliveryAttemptResult::FailedTimeout
+                } else {
+                    WebhookDeliveryAttemptResult::FailedUnreachable
+                };
+                slog::warn!(
+                    &opctx.log,
+                    "failed to communicate with SMTP server";
+                    "alert_id" => %delivery.alert_id,
+                    "alert_class" => %alert_class,
+                    "delivery_id" => %delivery.id,
+                    "delivery_trigger" => %delivery.triggered_by,
+                    "error" => %e,
+                );
+                (result, None)
+            }
+            Err(_) => {
+                slog::warn!(
+                    &opctx.log,
+                    "SMTP transaction timed out";
+                    "alert_id" => %delivery.alert_id,
+                    "alert_class" => %alert_class,
+                    "delivery_id" => %delivery.id,
+                    "delivery_trigger" => %delivery.triggered_by,
+                    "timeout" => ?TRANSACTION_TIMEOUT,
+                );
+                (WebhookDeliveryAttemptResult::FailedTimeout, None)
+            }
+        };
+        // As with webhooks, only include a duration if the server replied.
+        let response_duration = reply_code.map(|_| {
+            TimeDelta::from_std(duration).expect(
+                "because we set a 30-second transaction timeout, there is no \
+                 way a duration could ever exceed the max representable \
+                 TimeDelta of `i64::MAX` milliseconds",
+            )
+        });
+
+        Ok(WebhookDeliveryAttempt {
+            id: WebhookDeliveryAttemptUuid::new_v4().into(),
+            delivery_id: delivery.id,
+            rx_id: delivery.rx_id,
+            attempt: SqlU8::new(delivery.attempts.0 + 1),
+            result: delivery_result,
+            response_status: reply_code.map(Into::into),
+            response_duration,
+            time_created: chrono::Utc::now(),
+            deliverator_id: self.nexus_id.into(),
+            time_peer_cert_expires,
+        })
+    }
+
+    /// Formats the message sent to the receiver, including its headers.
+    ///
+    /// Lines are terminated with CRLF, and any line beginning with a `.` is
+    /// dot-stuffed, so the result can be sent verbatim after the `DATA`
+    /// command.
+    fn format_message(
+        &self,
+        delivery: &WebhookDelivery,
+        alert_class: AlertClass,
+        body: &str,
+    ) -> String {
+        let alert::SmtpReceiverConfig { from, to, .. } = &self.config;
+        let mut message = String::new();
+        let mut line = |l: &str| {
+            if l.starts_with('.') {
+                message.push('.');
+            }
+            message.push_str(l);
+            message.push_str("\r\n");
+        };
+        line(&format!("From: <{from}>"));
+        line(&format!(
+            "To: {}",
+            to.iter()
+                .map(|addr| format!("<{addr}>"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+        line(&format!("Subject: Oxide alert: {alert_class}"));
+        line(&format!("Date: {}", Utc::now().to_rfc2822()));
+        line(&format!(
+            "Message-ID: <{}.{}@{}>",
+            delivery.id,
+            delivery.attempts.0 + 1,
+            self.nexus_id,
+        ));
+        line(&format!("X-Oxide-Receiver-Id: {}", self.rx.id()));
+        line(&format!("X-Oxide-Delivery-Id: {}", delivery.id));
+        line(&format!("X-Oxide-Alert-Id: {}", delivery.alert_id));
+        line(&format!("X-Oxide-Alert-Class: {alert_class}"));
+        line("MIME-Version: 1.0");
+        line("Content-Type: text/plain; charset=utf-8");
+        line("");
+        for l in body.lines() {
+            line(l);
+        }
+        message
+    }
+
+    /// Runs an SMTP transaction which sends `message`, returning the reply
+    /// code with which the server accepted it.
+    ///
+    /// If the server offers STARTTLS, the expiry of the certificate it
+    /// presents is stored in `time_peer_cert_expires`.
+    async fn transaction(
+        &self,
+        message: &str,
+        time_peer_cert_expires: &mut Option<DateTime<Utc>>,
+    ) -> Result<u16, SmtpError> {
+        let host = &self.config.host;
+        let stream = tokio::time::timeout(
+            CONNECT_TIMEOUT,
+            TcpStream::connect((host.as_str(), self.config.port)),
+        )
+        .await
+        .map_err(|_| {
+            std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                "timed out connecting to SMTP server",
+            )
+        })??;
+        // Identify ourselves by address literal, as we don't have a
+        // resolvable domain name of our own.
+        let ehlo = match stream.local_addr()?.ip() {
+            std::net::IpAddr::V4(ip) => format!("EHLO [{ip}]"),
+            std::net::IpAddr::V6(ip) => format!("EHLO [IPv6:{ip}]"),
+        };
+        let mut conn = SmtpConnection { stream: BufReader::new(stream) };
+
+        conn.expect_reply(&[220]).await?;
+        let (_, extensions) = conn.command(&ehlo, &[250]).await?;
+        if !offers_starttls(&extensions) {
+            return self.send_message(conn, message).await;
+        }
+
+        conn.command("STARTTLS", &[220]).await?;
+        // Anything the server sent after its reply to STARTTLS arrived in the
+        // clear, and must not be mistaken for part of the TLS session.
+        if !conn.stream.buffer().is_empty() {
+            return Err(SmtpError::Protocol(String::from(
+                "unexpected data after STARTTLS reply",
+            )));
+        }
+        let server_name = rustls::pki_types::ServerName::try_from(
+            host.to_string(),
+        )
+        .map_err(|e| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("invalid TLS server name {host:?}: {e}"),
+            )
+        })?;
+        let stream = tokio_rustls::TlsConnector::from(TLS_CONFIG.clone())
+            .connect(server_name, conn.stream.into_inner())
+            .await
+            .map_err(|e| {
+                std::io::Error::new(
+                    e.kind(),
+                    format!("TLS handshake with SMTP server failed: {e}"),
+                )
+            })?;
+        // As with webhook receivers, a certificate we can't parse doesn't
+        // affect the delivery: it was good enough to complete the handshake.
+        *time_peer_cert_expires = stream
+            .get_ref()
+            .1
+            .peer_certificates()
+            .and_then(|certs| certs.first())
+            .and_then(|cert| omicron_certificates::der_expiry(cert).ok())
+            .and_then(|expiry| DateTime::from_timestamp(expiry, 0));
+
+        // The server forgets everything it learned before STARTTLS, so we
+        // must introduce ourselves again.
+        let mut conn = SmtpConnection { stream: BufReader::new(stream) };
+        conn.command(&ehlo, &[250]).await?;
+        self.send_message(conn, message).await
+    }
+
+    /// Sends `message` over a connection on which we've already said `EHLO`,
+    /// returning the reply code with which the server accepted it.
+    async fn send_message<S: AsyncRead + AsyncWrite + Unpin>(
+        &self,
+        mut conn: SmtpConnection<S>,
+        message: &str,
+    ) -> Result<u16, SmtpError> {
+        let alert::SmtpReceiverConfig { from, to, .. } = &self.config;
+        conn.command(&format!("MAIL FROM:<{from}>"), &[250]).await?;
+        for addr in to {
+            conn.command(&format!("RCPT TO:<{addr}>"), &[250, 251]).await?;
+        }
+        conn.command("DATA", &[354]).await?;
+        conn.stream.write_all(message.as_bytes()).await?;
+        let (code, _) = conn.command(".", &[250]).await?;
+        // We don't really care whether the server says goodbye nicely; the
+        // message has already been accepted.
+        let _ = conn.command("QUIT", &[221]).await;
+        Ok(code)
+    }
+}
+
+/// Returns whether the reply to `EHLO` advertises the STARTTLS extension
+///
+/// `reply` is the text of the reply, without reply codes.  Its first line is
+/// the server's greeting, and each following line names one extension.
+fn offers_starttls(reply: &str) -> bool {
+    reply.lines().skip(1).any(|line| {
+        line.split_whitespace()
+            .next()
+            .is_some_and(|keyword| keyword.eq_ignore_ascii_case("STARTTLS"))
+    })
+}
+
+struct SmtpConnection<S> {
+    stream: BufReader<S>,
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> SmtpConnection<S> {
+    /// Sends `command` and reads the server's reply, which must have one of
+    /// the `expected` reply codes.
+    ///
+    /// Returns the reply code and the text of the reply.
+    async fn command(
+        &mut self,
+        command: &str,
+        expected: &[u16],
+    ) -> Result<(u16, String), SmtpError> {
+        self.stream.write_all(format!("{command}\r\n").as_bytes()).await?;
+        self.expect_reply(expected).await
+    }
+
+    /// Reads a (possibly multi-line) reply from the server, which must have
+    /// one of the `expected` reply codes.
+    ///
+    /// Returns the reply code and the text of the reply, with one line per
+    /// line of the reply.
+    async fn expect_reply(
+        &mut self,
+        expected: &[u16],
+    ) -> Result<(u16, String), SmtpError> {
+        let mut message = String::new();
+        loop {
+            let mut line = String::new();
+            if self.stream.read_line(&mut line).await? == 0 {
+                return Err(SmtpError::Io(std::io::Error::new(
+                    std::io::ErrorKind::UnexpectedEof,
+                    "SMTP server closed the connection",
+                )));
+            }
+            let line = line.trim_end();
+            let (code, rest) = match (line.get(..3), line.get(3..)) {
+                (Some(code), Some(rest)) => (code, rest),
+                _ => return Err(SmtpError::Protocol(line.to_string())),
+            };
+            let code: u16 = code
+                .parse()
+                .map_err(|_| SmtpError::Protocol(line.to_string()))?;
+            message.push_str(rest.get(1..).unwrap_or_default());
+            // A hyphen after the reply code indicates that more lines follow.
+            if rest.starts_with('-') {
+                message.push('\n');
+                continue;
+            }
+            if expected.contains(&code) {
+                return Ok((code, message));
+            }
+            return Err(SmtpError::Rejected { code, message });
+        }
+    }
+}
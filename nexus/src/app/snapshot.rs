@@ -16,12 +16,14 @@ use nexus_db_queries::db::datastore;
 use nexus_types::external_api::disk::DiskSelector;
 use nexus_types::external_api::project;
 use nexus_types::external_api::snapshot;
+use nexus_types::identity::Resource;
 use omicron_common::api::external::CreateResult;
 use omicron_common::api::external::DeleteResult;
 use omicron_common::api::external::Error;
 use omicron_common::api::external::ListResultVec;
 use omicron_common::api::external::LookupResult;
 use omicron_common::api::external::NameOrId;
+use omicron_common::api::external::ResourceType;
 use omicron_common::api::external::http_pagination::PaginatedBy;
 
 use super::sagas;
@@ -169,6 +171,7 @@ impl super::Nexus {
                 internal_message: e.to_string(),
             })?;
 
+        opctx.set_audit_target(ResourceType::Snapshot, snapshot_created.id());
         Ok(snapshot_created)
     }
 
@@ -191,6 +194,7 @@ impl super::Nexus {
     ) -> DeleteResult {
         let (.., authz_snapshot, db_snapshot) =
             snapshot_lookup.fetch_for(authz::Action::Delete).await?;
+        opctx.set_audit_target(ResourceType::Snapshot, authz_snapshot.id());
 
         let saga_params = sagas::snapshot_delete::Params {
             serialized_authn: authn::saga::Serialized::for_opctx(opctx),
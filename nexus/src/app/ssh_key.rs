@@ -6,11 +6,13 @@ use nexus_db_queries::db;
 use nexus_db_queries::db::model::Name;
 use nexus_db_queries::db::model::SshKey;
 use nexus_types::external_api::ssh_key;
+use nexus_types::identity::Resource;
 use omicron_common::api::external::CreateResult;
 use omicron_common::api::external::DeleteResult;
 use omicron_common::api::external::ListResultVec;
 use omicron_common::api::external::LookupResult;
 use omicron_common::api::external::NameOrId;
+use omicron_common::api::external::ResourceType;
 use omicron_common::api::external::http_pagination::PaginatedBy;
 use omicron_uuid_kinds::SiloUserUuid;
 use ref_cast::RefCast;
@@ -55,7 +57,12 @@ impl super::Nexus {
             .lookup_for(authz::Action::CreateChild)
             .await?;
         assert_eq!(authz_user.id(), silo_user_id);
-        self.db_datastore.ssh_key_create(opctx, &authz_user, ssh_key).await
+        let ssh_key = self
+            .db_datastore
+            .ssh_key_create(opctx, &authz_user, ssh_key)
+            .await?;
+        opctx.set_audit_target(ResourceType::SshKey, ssh_key.id());
+        Ok(ssh_key)
     }
 
     pub(crate) async fn ssh_keys_list(
@@ -93,6 +100,7 @@ impl super::Nexus {
     ) -> DeleteResult {
         let (.., authz_silo_user, authz_ssh_key) =
             ssh_key_lookup.lookup_for(authz::Action::Delete).await?;
+        opctx.set_audit_target(ResourceType::SshKey, authz_ssh_key.id());
         assert_eq!(authz_silo_user.id(), silo_user_id);
         self.db_datastore.ssh_key_delete(opctx, &authz_ssh_key).await
     }
@@ -0,0 +1,240 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! # Syslog Alert Receivers
+//!
+//! Syslog receivers deliver [alerts] as [RFC 5424] syslog messages, sent to
+//! an operator-provided collector over UDP or TCP.  The message body is the
+//! same JSON document that is sent as the body of a webhook request.
+//!
+//! Syslog has no notion of acknowledging a message, so a delivery attempt is
+//! considered successful once the message has been sent.  Over UDP, that only
+//! means that we were able to send a datagram; over TCP, it means that the
+//! collector accepted a connection and the message was written to it.  TCP
+//! messages are framed using octet counting, as described in [RFC 6587].
+//!
+//! Generic operations on all types of alert receivers are defined in the
+//! [`alert` module][alerts].  Creating and updating syslog receiver
+//! configurations is defined here, along with [`SyslogClient`].
+//!
+//! [alerts]: super::alert
+//! [RFC 5424]: https://www.rfc-editor.org/rfc/rfc5424
+//! [RFC 6587]: https://www.rfc-editor.org/rfc/rfc6587#section-3.4.1
+
+use super::webhook::DeliveryPayload;
+use crate::Nexus;
+use chrono::SecondsFormat;
+use chrono::Utc;
+use nexus_db_lookup::lookup;
+use nexus_db_queries::authz;
+use nexus_db_queries::context::OpContext;
+use nexus_db_queries::db::model::AlertReceiver;
+use nexus_db_queries::db::model::AlertRxKind;
+use nexus_db_queries::db::model::SqlU8;
+use nexus_db_queries::db::model::WebhookDelivery;
+use nexus_db_queries::db::model::WebhookDeliveryAttempt;
+use nexus_db_queries::db::model::WebhookDeliveryAttemptResult;
+use nexus_db_queries::db::model::WebhookReceiverConfig;
+use nexus_types::alert::AlertClass;
+use nexus_types::external_api::alert;
+use nexus_types::identity::Resource;
+use omicron_common::api::external::CreateResult;
+use omicron_common::api::external::Error;
+use omicron_common::api::external::ResourceType;
+use omicron_common::api::external::UpdateResult;
+use omicron_uuid_kinds::GenericUuid;
+use omicron_uuid_kinds::OmicronZoneUuid;
+use omicron_uuid_kinds::WebhookDeliveryAttemptUuid;
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpStream;
+use tokio::net::UdpSocket;
+
+/// How long sending a message may take, including resolving the collector's
+/// address and connecting to it.
+const SEND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The severity of alert messages: "notice", or "normal but significant
+/// condition".
+const SEVERITY_NOTICE: u8 = 5;
+
+/// The APP-NAME field of alert messages.
+const APP_NAME: &str = "oxide-nexus";
+
+/// The MSGID field of alert messages.
+const MSG_ID: &str = "alert";
+
+impl Nexus {
+    pub async fn syslog_receiver_create(
+        &self,
+        opctx: &OpContext,
+        params: alert::SyslogCreate,
+    ) -> CreateResult<WebhookReceiverConfig> {
+        let rx = self.datastore().syslog_rx_create(&opctx, params).await?;
+        opctx.set_audit_target(
+            ResourceType::AlertReceiver,
+            rx.rx.id().into_untyped_uuid(),
+        );
+        Ok(rx)
+    }
+
+    pub async fn syslog_receiver_update(
+        &self,
+        opctx: &OpContext,
+        rx: lookup::AlertReceiver<'_>,
+        params: alert::SyslogReceiverUpdate,
+    ) -> UpdateResult<()> {
+        let (authz_rx, db_rx) = rx.fetch_for(authz::Action::Modify).await?;
+        opctx.set_audit_target(
+            ResourceType::AlertReceiver,
+            authz_rx.id().into_untyped_uuid(),
+        );
+        db_rx.ensure_kind(AlertRxKind::Syslog)?;
+        let _ = self
+            .datastore()
+            .syslog_rx_update(opctx, &authz_rx, &db_rx, params)
+            .await?;
+        Ok(())
+    }
+}
+
+/// Everything necessary to deliver an alert to a syslog receiver.
+pub(crate) struct SyslogClient<'a> {
+    rx: &'a AlertReceiver,
+    config: alert::SyslogReceiverConfig,
+    nexus_id: OmicronZoneUuid,
+}
+
+impl<'a> SyslogClient<'a> {
+    pub(crate) fn new(
+        rx: &'a AlertReceiver,
+        nexus_id: OmicronZoneUuid,
+    ) -> Result<Self, Error> {
+        let config = rx.syslog_config()?;
+        Ok(Self { rx, config, nexus_id })
+    }
+
+    pub(crate) async fn send_delivery_request(
+        &mut self,
+        opctx: &OpContext,
+        delivery: &WebhookDelivery,
+        alert_class: impl Into<AlertClass>,
+        alert_version: u32,
+        data: &serde_json::Value,
+    ) -> Result<WebhookDeliveryAttempt, anyhow::Error> {
+        let alert_class = alert_class.into();
+        let time_attempted = Utc::now();
+        let timestamp =
+            time_attempted.to_rfc3339_opts(SecondsFormat::Micros, true);
+        let payload = DeliveryPayload::new(
+            delivery,
+            self.rx,
+            alert_class,
+            alert_version,
+            data,
+            &timestamp,
+        );
+        let body = serde_json::to_string(&payload)?;
+        let pri = self.config.facility.code() * 8 + SEVERITY_NOTICE;
+        // Our zone name is the closest thing we have to a hostname.
+        let message = format!(
+            "<{pri}>1 {timestamp} oxz_nexus_{} {APP_NAME} - {MSG_ID} - {body}",
+            self.nexus_id,
+        );
+
+        let result =
+            tokio::time::timeout(SEND_TIMEOUT, self.send(&message)).await;
+        let delivery_result = match result {
+            Ok(Ok(addr)) => {
+                slog::debug!(
+                    &opctx.log,
+                    "alert sent to syslog collector";
+                    "alert_id" => %delivery.alert_id,
+                    "alert_class" => %alert_class,
+                    "delivery_id" => %delivery.id,
+                    "delivery_trigger" => %delivery.triggered_by,
+                    "collector_addr" => %addr,
+                    "transport" => %self.config.transport,
+                );
+                WebhookDeliveryAttemptResult::Succeeded
+            }
+            Ok(Err(e)) => {
+                slog::warn!(
+                    &opctx.log,
+                    "failed to send alert to syslog collector";
+                    "alert_id" => %delivery.alert_id,
+                    "alert_class" => %alert_class,
+                    "delivery_id" => %delivery.id,
+                    "delivery_trigger" => %delivery.triggered_by,
+                    "transport" => %self.config.transport,
+                    "error" => %e,
+                );
+                WebhookDeliveryAttemptResult::FailedUnreachable
+            }
+            Err(_) => {
+                slog::warn!(
+                    &opctx.log,
+                    "sending alert to syslog collector timed out";
+                    "alert_id" => %delivery.alert_id,
+                    "alert_class" => %alert_class,
+                    "delivery_id" => %delivery.id,
+                    "delivery_trigger" => %delivery.triggered_by,
+                    "transport" => %self.config.transport,
+                    "timeout" => ?SEND_TIMEOUT,
+                );
+                WebhookDeliveryAttemptResult::FailedTimeout
+            }
+        };
+
+        Ok(WebhookDeliveryAttempt {
+            id: WebhookDeliveryAttemptUuid::new_v4().into(),
+            delivery_id: delivery.id,
+            rx_id: delivery.rx_id,
+            attempt: SqlU8::new(delivery.attempts.0 + 1),
+            result: delivery_result,
+            // Syslog collectors never respond.
+            response_status: None,
+            response_duration: None,
+            time_created: chrono::Utc::now(),
+            deliverator_id: self.nexus_id.into(),
+            // Nor are messages sent over TLS.
+            time_peer_cert_expires: None,
+        })
+    }
+
+    /// Sends `message` to the collector, returning the address it was sent
+    /// to.
+    async fn send(&self, message: &str) -> Result<SocketAddr, std::io::Error> {
+        let alert::SyslogReceiverConfig { host, port, transport, .. } =
+            &self.config;
+        let addr = tokio::net::lookup_host((host.as_str(), *port))
+            .await?
+            .next()
+            .ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("{host:?} did not resolve to any addresses"),
+                )
+            })?;
+        match transport {
+            alert::SyslogTransport::Udp => {
+                let bind_addr: SocketAddr = if addr.is_ipv4() {
+                    (std::net::Ipv4Addr::UNSPECIFIED, 0).into()
+                } else {
+                    (std::net::Ipv6Addr::UNSPECIFIED, 0).into()
+                };
+                let socket = UdpSocket::bind(bind_addr).await?;
+                socket.send_to(message.as_bytes(), addr).await?;
+            }
+            alert::SyslogTransport::Tcp => {
+                let mut stream = TcpStream::connect(addr).await?;
+                let frame = format!("{} {message}", message.len());
+                stream.write_all(frame.as_bytes()).await?;
+                stream.shutdown().await?;
+            }
+        }
+        Ok(addr)
+    }
+}
@@ -859,6 +859,7 @@ mod test {
             zpools,
             datasets: vec![],
             ledgered_sled_config: None,
+            sled_config_ledger_report: None,
             reconciler_status: ConfigReconcilerInventoryStatus::NotYetRun,
             last_reconciliation: None,
             file_source_resolver: OmicronFileSourceResolverInventory::new_fake(
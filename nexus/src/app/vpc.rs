@@ -2,7 +2,7 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-//! VPCs and firewall rules
+//! VPCs, firewall rules, and private DNS records
 
 use crate::app::sagas;
 use nexus_db_lookup::LookupPath;
@@ -11,20 +11,24 @@ use nexus_db_queries::authn;
 use nexus_db_queries::authz;
 use nexus_db_queries::context::OpContext;
 use nexus_db_queries::db;
+use nexus_db_queries::db::datastore::VpcFirewallRuleEdit;
 use nexus_db_queries::db::model::Name;
 use nexus_defaults as defaults;
 use nexus_networking::FirewallRulesError;
 use nexus_types::external_api::project;
 use nexus_types::external_api::vpc;
+use nexus_types::identity::Resource;
 use omicron_common::api::external;
 use omicron_common::api::external::CreateResult;
 use omicron_common::api::external::DeleteResult;
 use omicron_common::api::external::Error;
+use omicron_common::api::external::Generation;
 use omicron_common::api::external::InternalContext;
 use omicron_common::api::external::ListResultVec;
 use omicron_common::api::external::LookupResult;
 use omicron_common::api::external::LookupType;
 use omicron_common::api::external::NameOrId;
+use omicron_common::api::external::ResourceType;
 use omicron_common::api::external::ServiceIcmpConfig;
 use omicron_common::api::external::UpdateResult;
 use omicron_common::api::external::VpcFirewallRuleUpdateParams;
@@ -35,6 +39,9 @@ use slog_error_chain::InlineErrorChain;
 use std::sync::Arc;
 use uuid::Uuid;
 
+/// The maximum number of user-defined records in a VPC's private DNS zone
+const MAX_VPC_DNS_RECORDS: usize = 1024;
+
 impl super::Nexus {
     // VPCs
 
@@ -94,10 +101,11 @@ impl super::Nexus {
             .saga_execute::<sagas::vpc_create::SagaVpcCreate>(saga_params)
             .await?;
 
-        let (_, db_vpc) = saga_outputs
+        let (authz_vpc, db_vpc) = saga_outputs
             .lookup_node_output::<(authz::Vpc, db::model::Vpc)>("vpc")
             .map_err(|e| Error::internal_error(&format!("{:#}", &e)))
             .internal_context("looking up output from VPC create saga")?;
+        opctx.set_audit_target(ResourceType::Vpc, authz_vpc.id());
 
         Ok(db_vpc)
     }
@@ -121,6 +129,7 @@ impl super::Nexus {
     ) -> UpdateResult<db::model::Vpc> {
         let (.., authz_vpc) =
             vpc_lookup.lookup_for(authz::Action::Modify).await?;
+        opctx.set_audit_target(ResourceType::Vpc, authz_vpc.id());
         self.db_datastore
             .project_update_vpc(opctx, &authz_vpc, params.clone().into())
             .await
@@ -133,6 +142,7 @@ impl super::Nexus {
     ) -> DeleteResult {
         let (.., authz_vpc, db_vpc) =
             vpc_lookup.fetch_for(authz::Action::Delete).await?;
+        opctx.set_audit_target(ResourceType::Vpc, authz_vpc.id());
 
         let authz_vpc_router = authz::VpcRouter::new(
             authz_vpc.clone(),
@@ -157,26 +167,34 @@ impl super::Nexus {
             .await?;
         self.db_datastore.vpc_delete_router(&opctx, &authz_vpc_router).await?;
 
-        // Delete all firewall rules after deleting the VPC, to ensure no
-        // firewall rules get added between rules deletion and VPC deletion.
+        // Delete all firewall rules and DNS records after deleting the VPC, to
+        // ensure none get added between their deletion and VPC deletion.
         self.db_datastore
             .vpc_delete_all_firewall_rules(&opctx, &authz_vpc)
-            .await
+            .await?;
+        self.db_datastore.vpc_dns_record_delete_all(&opctx, &authz_vpc).await?;
+        self.background_tasks.activate(&self.background_tasks.task_vpc_dns);
+        Ok(())
     }
 
     // Firewall rules
 
+    /// List the firewall rules of a VPC, along with the generation of the
+    /// rule set
     pub(crate) async fn vpc_list_firewall_rules(
         &self,
         opctx: &OpContext,
         vpc_lookup: &lookup::Vpc<'_>,
-    ) -> ListResultVec<db::model::VpcFirewallRule> {
-        nexus_networking::vpc_list_firewall_rules(
-            &self.db_datastore,
-            opctx,
-            vpc_lookup,
-        )
-        .await
+    ) -> LookupResult<(Generation, Vec<db::model::VpcFirewallRule>)> {
+        // The generation is read before the rules. If the rules change in
+        // between, the caller sees newer rules with an older generation, so
+        // edits made against them fail instead of clobbering the change.
+        let (.., authz_vpc, db_vpc) = vpc_lookup.fetch().await?;
+        let rules = self
+            .db_datastore
+            .vpc_list_firewall_rules(opctx, &authz_vpc)
+            .await?;
+        Ok((*db_vpc.firewall_gen, rules))
     }
 
     pub(crate) async fn vpc_update_firewall_rules(
@@ -184,27 +202,247 @@ impl super::Nexus {
         opctx: &OpContext,
         vpc_lookup: &lookup::Vpc<'_>,
         params: &VpcFirewallRuleUpdateParams,
-    ) -> UpdateResult<Vec<db::model::VpcFirewallRule>> {
+    ) -> UpdateResult<(Generation, Vec<db::model::VpcFirewallRule>)> {
         let (.., authz_vpc, db_vpc) =
             vpc_lookup.fetch_for(authz::Action::Modify).await?;
+        // Firewall rules are stored as a set belonging to the VPC, so attribute
+        // changes to them to the VPC that owns them.
+        opctx.set_audit_target(ResourceType::Vpc, authz_vpc.id());
+
         let rules = db::model::VpcFirewallRule::vec_from_params(
             authz_vpc.id(),
             params.clone(),
         )?;
+        self.vpc_ensure_firewall_rules_valid(&db_vpc, &rules)?;
+
+        let (generation, rules) = self
+            .db_datastore
+            .vpc_update_firewall_rules_at_generation(
+                opctx, &authz_vpc, None, rules,
+            )
+            .await?;
+        self.send_sled_agents_firewall_rules(opctx, &db_vpc, &rules, &[])
+            .await?;
+        Ok((*generation, rules))
+    }
+
+    /// Add a single firewall rule to a VPC, provided its rule set is still at
+    /// the generation given in `params`
+    pub(crate) async fn vpc_firewall_rule_create(
+        &self,
+        opctx: &OpContext,
+        vpc_lookup: &lookup::Vpc<'_>,
+        params: &vpc::VpcFirewallRuleCreate,
+    ) -> CreateResult<(Generation, Vec<db::model::VpcFirewallRule>)> {
+        let rule = &params.rule;
+        self.vpc_edit_firewall_rules(
+            opctx,
+            vpc_lookup,
+            params.generation,
+            |vpc_id, rules| {
+                ensure_firewall_rule_name_free(rules, &rule.name)?;
+                let rule = db::model::VpcFirewallRule::new(
+                    Uuid::new_v4(),
+                    vpc_id,
+                    rule,
+                )?;
+                rules.push(rule.clone());
+                db::model::VpcFirewallRule::ensure_rule_count(rules.len())?;
+                Ok(VpcFirewallRuleEdit::Create(rule))
+            },
+        )
+        .await
+    }
+
+    /// Replace the firewall rule `rule_name` of a VPC, provided its rule set
+    /// is still at the generation given in `params`
+    ///
+    /// The rule keeps its ID, even if it is renamed.
+    pub(crate) async fn vpc_firewall_rule_update(
+        &self,
+        opctx: &OpContext,
+        vpc_lookup: &lookup::Vpc<'_>,
+        rule_name: &external::Name,
+        params: &vpc::VpcFirewallRuleReplace,
+    ) -> UpdateResult<(Generation, Vec<db::model::VpcFirewallRule>)> {
+        let rule = &params.rule;
+        self.vpc_edit_firewall_rules(
+            opctx,
+            vpc_lookup,
+            params.generation,
+            |vpc_id, rules| {
+                if &rule.name != rule_name {
+                    ensure_firewall_rule_name_free(rules, &rule.name)?;
+                }
+                let existing = rules
+                    .iter_mut()
+                    .find(|r| r.name() == rule_name)
+                    .ok_or_else(|| firewall_rule_not_found(rule_name))?;
+                *existing = db::model::VpcFirewallRule::new(
+                    existing.id(),
+                    vpc_id,
+                    rule,
+                )?;
+                Ok(VpcFirewallRuleEdit::Replace(existing.clone()))
+            },
+        )
+        .await
+    }
+
+    /// Remove the firewall rule `rule_name` from a VPC, provided its rule set
+    /// is still at `generation`
+    pub(crate) async fn vpc_firewall_rule_delete(
+        &self,
+        opctx: &OpContext,
+        vpc_lookup: &lookup::Vpc<'_>,
+        rule_name: &external::Name,
+        generation: Generation,
+    ) -> DeleteResult {
+        self.vpc_edit_firewall_rules(
+            opctx,
+            vpc_lookup,
+            generation,
+            |_, rules| {
+                let index = rules
+                    .iter()
+                    .position(|r| r.name() == rule_name)
+                    .ok_or_else(|| firewall_rule_not_found(rule_name))?;
+                let rule = rules.remove(index);
+                Ok(VpcFirewallRuleEdit::Delete(rule.id()))
+            },
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Apply `edit` to the current firewall rules of a VPC and write back the
+    /// one rule it changed, provided the rule set is still at `generation`
+    ///
+    /// `edit` is given the VPC's ID and its current rules, which it updates to
+    /// reflect the change it returns. The resulting rule set is validated and
+    /// propagated to sleds exactly like [`Self::vpc_update_firewall_rules`],
+    /// but only the edited rule is written, so the others keep their IDs.
+    async fn vpc_edit_firewall_rules(
+        &self,
+        opctx: &OpContext,
+        vpc_lookup: &lookup::Vpc<'_>,
+        generation: Generation,
+        edit: impl FnOnce(
+            Uuid,
+            &mut Vec<db::model::VpcFirewallRule>,
+        ) -> Result<VpcFirewallRuleEdit, Error>,
+    ) -> UpdateResult<(Generation, Vec<db::model::VpcFirewallRule>)> {
+        let (.., authz_vpc, db_vpc) =
+            vpc_lookup.fetch_for(authz::Action::Modify).await?;
+        // Firewall rules are stored as a set belonging to the VPC, so attribute
+        // changes to them to the VPC that owns them.
+        opctx.set_audit_target(ResourceType::Vpc, authz_vpc.id());
+
+        // If the rules listed here are newer than `generation`, writing the
+        // edit below fails, so it never applies to a rule set the caller has
+        // not seen.
+        let mut rules = self
+            .db_datastore
+            .vpc_list_firewall_rules(opctx, &authz_vpc)
+            .await?;
+        let edit = edit(authz_vpc.id(), &mut rules)?;
+        self.vpc_ensure_firewall_rules_valid(&db_vpc, &rules)?;
+
+        let (generation, rules) = self
+            .db_datastore
+            .vpc_edit_firewall_rule_at_generation(
+                opctx,
+                &authz_vpc,
+                generation.into(),
+                edit,
+            )
+            .await?;
+        self.send_sled_agents_firewall_rules(opctx, &db_vpc, &rules, &[])
+            .await?;
+        Ok((*generation, rules))
+    }
 
+    /// Reject `rules` if they refer to another VPC's resources
+    fn vpc_ensure_firewall_rules_valid(
+        &self,
+        db_vpc: &db::model::Vpc,
+        rules: &[db::model::VpcFirewallRule],
+    ) -> Result<(), Error> {
         // Reject cross-VPC references before writing anything. The same check
         // happens again when resolving rules for sled-agents, but that runs
         // after the write, so without this the rules would be persisted even
         // though the request fails (omicron#10561).
-        nexus_networking::ensure_no_cross_vpc_references(&db_vpc, &rules)?;
+        nexus_networking::ensure_no_cross_vpc_references(db_vpc, rules)
+    }
 
-        let rules = self
+    // Private DNS
+
+    /// List the user-defined records in a VPC's private DNS zone
+    pub(crate) async fn vpc_dns_records_view(
+        &self,
+        opctx: &OpContext,
+        vpc_lookup: &lookup::Vpc<'_>,
+    ) -> LookupResult<vpc::VpcDnsRecords> {
+        let (.., authz_vpc, db_vpc) = vpc_lookup.fetch().await?;
+        let records = self
             .db_datastore
-            .vpc_update_firewall_rules(opctx, &authz_vpc, rules)
-            .await?;
-        self.send_sled_agents_firewall_rules(opctx, &db_vpc, &rules, &[])
-            .await?;
-        Ok(rules)
+            .vpc_dns_record_list(opctx, &authz_vpc)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+        Ok(vpc::VpcDnsRecords {
+            zone_name: db::model::vpc_dns_zone_name(&db_vpc.dns_name),
+            records,
+        })
+    }
+
+    /// Replace the user-defined records in a VPC's private DNS zone
+    pub(crate) async fn vpc_dns_records_update(
+        &self,
+        opctx: &OpContext,
+        vpc_lookup: &lookup::Vpc<'_>,
+        params: vpc::VpcDnsRecordsUpdate,
+    ) -> UpdateResult<vpc::VpcDnsRecords> {
+        let (.., authz_vpc, db_vpc) =
+            vpc_lookup.fetch_for(authz::Action::Modify).await?;
+        // Records are stored as a set belonging to the VPC, so attribute
+        // changes to them to the VPC that owns them.
+        opctx.set_audit_target(ResourceType::Vpc, authz_vpc.id());
+
+        if params.records.len() > MAX_VPC_DNS_RECORDS {
+            return Err(Error::invalid_request(format!(
+                "a VPC may have at most {MAX_VPC_DNS_RECORDS} DNS records"
+            )));
+        }
+        // A name may have several addresses, but each only once.
+        let mut seen = std::collections::BTreeSet::new();
+        for record in &params.records {
+            if !seen.insert((&record.name, record.address)) {
+                return Err(Error::invalid_request(format!(
+                    "duplicate DNS record \"{}\" for {}",
+                    record.name, record.address,
+                )));
+            }
+        }
+
+        let records = params
+            .records
+            .into_iter()
+            .map(|record| db::model::VpcDnsRecord::new(authz_vpc.id(), record))
+            .collect();
+        let records = self
+            .db_datastore
+            .vpc_dns_record_replace(opctx, &authz_vpc, records)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+        self.background_tasks.activate(&self.background_tasks.task_vpc_dns);
+        Ok(vpc::VpcDnsRecords {
+            zone_name: db::model::vpc_dns_zone_name(&db_vpc.dns_name),
+            records,
+        })
     }
 
     /// Customize the default firewall rules for a particular VPC
@@ -367,3 +605,20 @@ impl super::Nexus {
         }
     }
 }
+
+fn firewall_rule_not_found(name: &external::Name) -> Error {
+    Error::not_found_by_name(ResourceType::VpcFirewallRule, name)
+}
+
+fn ensure_firewall_rule_name_free(
+    rules: &[db::model::VpcFirewallRule],
+    name: &external::Name,
+) -> Result<(), Error> {
+    if rules.iter().any(|r| r.name() == name) {
+        return Err(Error::ObjectAlreadyExists {
+            type_name: ResourceType::VpcFirewallRule,
+            object_name: name.to_string(),
+        });
+    }
+    Ok(())
+}
@@ -13,12 +13,14 @@ use nexus_db_queries::db::model::RouterRoute;
 use nexus_db_queries::db::model::VpcRouter;
 use nexus_db_queries::db::model::VpcRouterKind;
 use nexus_types::external_api::vpc;
+use nexus_types::identity::Resource;
 use omicron_common::api::external::CreateResult;
 use omicron_common::api::external::DeleteResult;
 use omicron_common::api::external::Error;
 use omicron_common::api::external::ListResultVec;
 use omicron_common::api::external::LookupResult;
 use omicron_common::api::external::NameOrId;
+use omicron_common::api::external::ResourceType;
 use omicron_common::api::external::RouteDestination;
 use omicron_common::api::external::RouteTarget;
 use omicron_common::api::external::RouterRouteKind;
@@ -126,6 +128,8 @@ impl super::Nexus {
             .vpc_create_router(&opctx, &authz_vpc, router)
             .await?;
 
+        opctx.set_audit_target(ResourceType::VpcRouter, router.id());
+
         // Note: we don't trigger the route RPW here as it's impossible
         //       for the router to be bound to a subnet at this point.
 
@@ -155,6 +159,7 @@ impl super::Nexus {
     ) -> UpdateResult<VpcRouter> {
         let (.., authz_router) =
             vpc_router_lookup.lookup_for(authz::Action::Modify).await?;
+        opctx.set_audit_target(ResourceType::VpcRouter, authz_router.id());
         self.db_datastore
             .vpc_update_router(opctx, &authz_router, params.clone().into())
             .await
@@ -167,6 +172,7 @@ impl super::Nexus {
     ) -> DeleteResult {
         let (.., authz_router, db_router) =
             vpc_router_lookup.fetch_for(authz::Action::Delete).await?;
+        opctx.set_audit_target(ResourceType::VpcRouter, authz_router.id());
         // TODO-performance shouldn't this check be part of the "update"
         // database query?  This shouldn't affect correctness, assuming that a
         // router kind cannot be changed, but it might be able to save us a
@@ -259,6 +265,7 @@ impl super::Nexus {
             .router_create_route(&opctx, &authz_router, route)
             .await?;
 
+        opctx.set_audit_target(ResourceType::RouterRoute, route.id());
         self.vpc_router_increment_rpw_version(opctx, &authz_router).await?;
 
         Ok(route)
@@ -285,6 +292,7 @@ impl super::Nexus {
     ) -> UpdateResult<RouterRoute> {
         let (.., authz_router, authz_route, db_route) =
             route_lookup.fetch_for(authz::Action::Modify).await?;
+        opctx.set_audit_target(ResourceType::RouterRoute, authz_route.id());
 
         match db_route.kind.0 {
             // Default routes allow a constrained form of modification:
@@ -333,6 +341,7 @@ impl super::Nexus {
     ) -> DeleteResult {
         let (.., authz_router, authz_route, db_route) =
             route_lookup.fetch_for(authz::Action::Delete).await?;
+        opctx.set_audit_target(ResourceType::RouterRoute, authz_route.id());
 
         // Only custom routes can be deleted
         // TODO Shouldn't this constraint be checked by the database query?
@@ -23,6 +23,7 @@ use omicron_common::api::external::Ipv6NetExt;
 use omicron_common::api::external::ListResultVec;
 use omicron_common::api::external::LookupResult;
 use omicron_common::api::external::NameOrId;
+use omicron_common::api::external::ResourceType;
 use omicron_common::api::external::UpdateResult;
 use omicron_common::api::external::http_pagination::PaginatedBy;
 
@@ -164,6 +165,7 @@ impl super::Nexus {
             .map_err(|e| Error::internal_error(&format!("{:#}", &e)))
             .internal_context("looking up output from vpc create saga")?;
 
+        opctx.set_audit_target(ResourceType::VpcSubnet, out.id());
         self.vpc_needed_notify_sleds();
 
         Ok(out)
@@ -188,6 +190,7 @@ impl super::Nexus {
     ) -> UpdateResult<VpcSubnet> {
         let (.., authz_vpc, authz_subnet) =
             vpc_subnet_lookup.lookup_for(authz::Action::Modify).await?;
+        opctx.set_audit_target(ResourceType::VpcSubnet, authz_subnet.id());
 
         let custom_router = match &params.custom_router {
             Some(k) => Some(
@@ -228,6 +231,7 @@ impl super::Nexus {
     ) -> DeleteResult {
         let (.., authz_vpc, authz_subnet, db_subnet) =
             vpc_subnet_lookup.fetch_for(authz::Action::Delete).await?;
+        opctx.set_audit_target(ResourceType::VpcSubnet, authz_subnet.id());
 
         let saga_params = sagas::vpc_subnet_delete::Params {
             serialized_authn: authn::saga::Serialized::for_opctx(opctx),
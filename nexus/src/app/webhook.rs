@@ -29,6 +29,7 @@
 
 use crate::Nexus;
 use anyhow::Context;
+use chrono::DateTime;
 use chrono::TimeDelta;
 use chrono::Utc;
 use hmac::{Hmac, Mac};
@@ -38,26 +39,23 @@ use nexus_db_lookup::LookupPath;
 use nexus_db_lookup::lookup;
 use nexus_db_queries::authz;
 use nexus_db_queries::context::OpContext;
-use nexus_db_queries::db::model::AlertDeliveryState;
-use nexus_db_queries::db::model::AlertDeliveryTrigger;
 use nexus_db_queries::db::model::AlertReceiver;
+use nexus_db_queries::db::model::AlertRxKind;
 use nexus_db_queries::db::model::SqlU8;
 use nexus_db_queries::db::model::WebhookDelivery;
 use nexus_db_queries::db::model::WebhookDeliveryAttempt;
 use nexus_db_queries::db::model::WebhookDeliveryAttemptResult;
 use nexus_db_queries::db::model::WebhookReceiverConfig;
 use nexus_db_queries::db::model::WebhookSecret;
-use nexus_types::alert as alert_types;
 use nexus_types::alert::AlertClass;
-use nexus_types::alert::AlertPayload;
 use nexus_types::external_api::alert;
-use nexus_types::identity::Asset;
 use nexus_types::identity::Resource;
 use omicron_common::api::external::CreateResult;
 use omicron_common::api::external::DeleteResult;
 use omicron_common::api::external::Error;
 use omicron_common::api::external::ListResultVec;
 use omicron_common::api::external::LookupResult;
+use omicron_common::api::external::ResourceType;
 use omicron_common::api::external::UpdateResult;
 use omicron_uuid_kinds::AlertReceiverUuid;
 use omicron_uuid_kinds::AlertUuid;
@@ -68,7 +66,6 @@ use omicron_uuid_kinds::WebhookDeliveryUuid;
 use omicron_uuid_kinds::WebhookSecretUuid;
 use sha2::Sha256;
 use slog_error_chain::InlineErrorChain;
-use std::sync::LazyLock;
 use std::time::Duration;
 use std::time::Instant;
 
@@ -90,7 +87,12 @@ impl Nexus {
         opctx: &OpContext,
         params: alert::WebhookCreate,
     ) -> CreateResult<WebhookReceiverConfig> {
-        self.datastore().webhook_rx_create(&opctx, params).await
+        let rx = self.datastore().webhook_rx_create(&opctx, params).await?;
+        opctx.set_audit_target(
+            ResourceType::AlertReceiver,
+            rx.rx.id().into_untyped_uuid(),
+        );
+        Ok(rx)
     }
 
     pub async fn webhook_receiver_update(
@@ -99,7 +101,12 @@ impl Nexus {
         rx: lookup::AlertReceiver<'_>,
         params: alert::WebhookReceiverUpdate,
     ) -> UpdateResult<()> {
-        let (authz_rx,) = rx.lookup_for(authz::Action::Modify).await?;
+        let (authz_rx, db_rx) = rx.fetch_for(authz::Action::Modify).await?;
+        opctx.set_audit_target(
+            ResourceType::AlertReceiver,
+            authz_rx.id().into_untyped_uuid(),
+        );
+        db_rx.ensure_kind(AlertRxKind::Webhook)?;
         let _ = self
             .datastore()
             .webhook_rx_update(opctx, &authz_rx, params)
@@ -113,6 +120,10 @@ impl Nexus {
         rx: lookup::AlertReceiver<'_>,
     ) -> DeleteResult {
         let (authz_rx, db_rx) = rx.fetch_for(authz::Action::Delete).await?;
+        opctx.set_audit_target(
+            ResourceType::AlertReceiver,
+            authz_rx.id().into_untyped_uuid(),
+        );
         self.datastore().webhook_rx_delete(&opctx, &authz_rx, &db_rx).await
     }
 
@@ -135,7 +146,13 @@ impl Nexus {
         rx: lookup::AlertReceiver<'_>,
         secret: String,
     ) -> Result<alert::WebhookSecret, Error> {
-        let (authz_rx,) = rx.lookup_for(authz::Action::CreateChild).await?;
+        let (authz_rx, db_rx) =
+            rx.fetch_for(authz::Action::CreateChild).await?;
+        opctx.set_audit_target(
+            ResourceType::AlertReceiver,
+            authz_rx.id().into_untyped_uuid(),
+        );
+        db_rx.ensure_kind(AlertRxKind::Webhook)?;
         let secret = WebhookSecret::new(authz_rx.id(), secret);
         let secret = self
             .datastore()
@@ -158,6 +175,10 @@ impl Nexus {
     ) -> DeleteResult {
         let (authz_rx, authz_secret) =
             secret.lookup_for(authz::Action::Delete).await?;
+        opctx.set_audit_target(
+            ResourceType::AlertReceiver,
+            authz_rx.id().into_untyped_uuid(),
+        );
         self.datastore()
             .webhook_rx_secret_delete(&opctx, &authz_rx, &authz_secret)
             .await?;
@@ -169,150 +190,6 @@ impl Nexus {
         );
         Ok(())
     }
-
-    //
-    // Receiver event delivery API methods
-    //
-
-    pub async fn webhook_receiver_probe(
-        &self,
-        opctx: &OpContext,
-        rx: lookup::AlertReceiver<'_>,
-        params: alert::AlertReceiverProbe,
-    ) -> Result<alert::AlertProbeResult, Error> {
-        let (authz_rx, rx) = rx.fetch_for(authz::Action::ListChildren).await?;
-        let rx_id = authz_rx.id();
-        let datastore = self.datastore();
-        let secrets =
-            datastore.webhook_rx_secret_list(opctx, &authz_rx).await?;
-        let mut client = ReceiverClient::new(
-            &self.webhook_delivery_client,
-            secrets,
-            &rx,
-            self.id,
-        )?;
-        let mut delivery = WebhookDelivery::new_probe(&rx_id, &self.id);
-
-        const CLASS: AlertClass = <alert_types::Probe as AlertPayload>::CLASS;
-        const VERSION: u32 = <alert_types::Probe as AlertPayload>::VERSION;
-        static DATA: LazyLock<serde_json::Value> = LazyLock::new(|| {
-            serde_json::to_value(&alert_types::Probe {}).expect(
-                "a struct with no fields should always serialize properly",
-            )
-        });
-
-        let attempt = match client
-            .send_delivery_request(opctx, &delivery, CLASS, VERSION, &DATA)
-            .await
-        {
-            Ok(attempt) => attempt,
-            Err(e) => {
-                slog::error!(
-                    &opctx.log,
-                    "failed to probe webhook receiver";
-                    "rx_id" => %authz_rx.id(),
-                    "rx_name" => %rx.name(),
-                    "delivery_id" => %delivery.id,
-                    "error" => %e,
-                );
-                return Err(Error::InternalError {
-                    internal_message: e.to_string(),
-                });
-            }
-        };
-
-        // Update the delivery state based on the result of the probe attempt.
-        // Otherwise, it will still appear "pending", which is obviously wrong.
-        delivery.state = if attempt.result.is_failed() {
-            AlertDeliveryState::Failed
-        } else {
-            AlertDeliveryState::Delivered
-        };
-
-        let resends_started = if params.resend
-            && attempt.result == WebhookDeliveryAttemptResult::Succeeded
-        {
-            slog::debug!(
-                &opctx.log,
-                "webhook liveness probe succeeded, resending failed \
-                 deliveries...";
-                "rx_id" => %authz_rx.id(),
-                "rx_name" => %rx.name(),
-                "delivery_id" => %delivery.id,
-            );
-
-            let deliveries = datastore
-                .webhook_rx_list_resendable_events(opctx, &rx_id)
-                .await
-                .map_err(|e| {
-                    e.internal_context("error listing events to resend")
-                })?
-                .into_iter()
-                .map(|event| {
-                    slog::trace!(
-                        &opctx.log,
-                        "will resend webhook event after probe success";
-                        "rx_id" => ?authz_rx.id(),
-                        "rx_name" => %rx.name(),
-                        "delivery_id" => ?delivery.id,
-                        "alert_id" => ?event.id(),
-                        "alert_class" => %event.class,
-                    );
-                    WebhookDelivery::new(
-                        &event.id(),
-                        &rx_id,
-                        AlertDeliveryTrigger::Resend,
-                    )
-                })
-                .collect::<Vec<_>>();
-            let events_found = deliveries.len();
-
-            let started = datastore
-                .webhook_delivery_create_batch(&opctx, deliveries)
-                .await
-                .map_err(|e| {
-                    e.internal_context(
-                        "error creating deliveries to resend failed events",
-                    )
-                })?;
-
-            if started > 0 {
-                slog::info!(
-                    &opctx.log,
-                    "webhook liveness probe succeeded, created {started} \
-                     re-deliveries";
-                    "rx_id" => %authz_rx.id(),
-                    "rx_name" => %rx.name(),
-                    "delivery_id" => %delivery.id,
-                    "events_found" => events_found,
-                    "deliveries_started" => started,
-                );
-                // If new deliveries were created, activate the webhook
-                // deliverator background task to start actually delivering
-                // them.
-                self.background_tasks.task_webhook_deliverator.activate();
-            } else {
-                slog::debug!(
-                    &opctx.log,
-                    "webhook liveness probe succeeded, but no failed events \
-                     were re-delivered";
-                    "rx_id" => %authz_rx.id(),
-                    "rx_name" => %rx.name(),
-                    "delivery_id" => %delivery.id,
-                    "events_found" => events_found,
-                );
-            }
-
-            Some(started)
-        } else {
-            None
-        };
-
-        Ok(alert::AlertProbeResult {
-            probe: delivery.to_api_delivery(CLASS, &[attempt]),
-            resends_started,
-        })
-    }
 }
 
 /// Construct a [`reqwest::Client`] configured for webhook delivery requests.
@@ -336,9 +213,57 @@ pub(super) fn delivery_client(
         //
         // [1]: https://rfd.shared.oxide.computer/rfd/538#delivery-failure
         .timeout(Duration::from_secs(30))
+        // Record the certificate each receiver presents, so that we can
+        // report when it expires.
+        .tls_info(true)
         .build()
 }
 
+/// The JSON representation of an alert delivery.
+///
+/// This is the body of webhook delivery requests, and is also used as the
+/// message body by receivers which don't speak HTTP.
+#[derive(serde::Serialize, Debug)]
+pub(super) struct DeliveryPayload<'a> {
+    pub(super) alert_class: AlertClass,
+    alert_version: u32,
+    alert_id: AlertUuid,
+    data: &'a serde_json::Value,
+    delivery: DeliveryMetadata<'a>,
+}
+
+#[derive(serde::Serialize, Debug)]
+struct DeliveryMetadata<'a> {
+    id: WebhookDeliveryUuid,
+    receiver_id: AlertReceiverUuid,
+    sent_at: &'a str,
+    trigger: alert::AlertDeliveryTrigger,
+}
+
+impl<'a> DeliveryPayload<'a> {
+    pub(super) fn new(
+        delivery: &WebhookDelivery,
+        rx: &AlertReceiver,
+        alert_class: AlertClass,
+        alert_version: u32,
+        data: &'a serde_json::Value,
+        sent_at: &'a str,
+    ) -> Self {
+        Self {
+            alert_class,
+            alert_version,
+            alert_id: delivery.alert_id.into(),
+            data,
+            delivery: DeliveryMetadata {
+                id: delivery.id.into(),
+                receiver_id: rx.id(),
+                sent_at,
+                trigger: delivery.triggered_by.into(),
+            },
+        }
+    }
+}
+
 /// Everything necessary to send a delivery request to a webhook receiver.
 ///
 /// This is its' own thing, rather than part of the `webhook_deliverator`
@@ -400,39 +325,18 @@ impl<'a> ReceiverClient<'a> {
         const HDR_TIMESTAMP: HeaderName =
             HeaderName::from_static("x-oxide-timestamp");
 
-        #[derive(serde::Serialize, Debug)]
-        struct Payload<'a> {
-            alert_class: AlertClass,
-            alert_version: u32,
-            alert_id: AlertUuid,
-            data: &'a serde_json::Value,
-            delivery: DeliveryMetadata<'a>,
-        }
-
-        #[derive(serde::Serialize, Debug)]
-        struct DeliveryMetadata<'a> {
-            id: WebhookDeliveryUuid,
-            receiver_id: AlertReceiverUuid,
-            sent_at: &'a str,
-            trigger: alert::AlertDeliveryTrigger,
-        }
-
         // okay, actually do the thing...
         let alert_class = alert_class.into();
         let time_attempted = Utc::now();
         let sent_at = time_attempted.to_rfc3339();
-        let payload = Payload {
+        let payload = DeliveryPayload::new(
+            delivery,
+            self.rx,
             alert_class,
             alert_version,
-            alert_id: delivery.alert_id.into(),
             data,
-            delivery: DeliveryMetadata {
-                id: delivery.id.into(),
-                receiver_id: self.rx.id(),
-                sent_at: &sent_at,
-                trigger: delivery.triggered_by.into(),
-            },
-        };
+            &sent_at,
+        );
         // N.B. that we serialize the body "ourselves" rather than just
         // passing it to `RequestBuilder::json` because we must access
         // the serialized body in order to calculate HMAC signatures.
@@ -509,6 +413,7 @@ impl<'a> ReceiverClient<'a> {
         let t0 = Instant::now();
         let result = self.client.execute(request).await;
         let duration = t0.elapsed();
+        let mut time_peer_cert_expires = None;
         let (delivery_result, status) = match result {
             // Builder errors are our fault, that's weird!
             Err(e) if e.is_builder() => {
@@ -565,6 +470,7 @@ impl<'a> ReceiverClient<'a> {
             }
             Ok(rsp) => {
                 let status = rsp.status();
+                time_peer_cert_expires = peer_cert_expiry(opctx, &rsp);
                 if status.is_success() {
                     slog::debug!(
                         &opctx.log,
@@ -614,6 +520,32 @@ impl<'a> ReceiverClient<'a> {
             response_duration,
             time_created: chrono::Utc::now(),
             deliverator_id: self.nexus_id.into(),
+            time_peer_cert_expires,
         })
     }
 }
+
+/// Returns when the certificate presented by the server that sent `rsp`
+/// expires, if the request was made over TLS
+fn peer_cert_expiry(
+    opctx: &OpContext,
+    rsp: &reqwest::Response,
+) -> Option<DateTime<Utc>> {
+    let cert =
+        rsp.extensions().get::<reqwest::tls::TlsInfo>()?.peer_certificate()?;
+    let expiry = match omicron_certificates::der_expiry(cert) {
+        Ok(expiry) => expiry,
+        Err(e) => {
+            // This would be surprising, as the certificate was good enough to
+            // complete the handshake.  It doesn't affect the delivery, though.
+            slog::warn!(
+                &opctx.log,
+                "failed to parse webhook receiver's certificate";
+                "url" => %rsp.url(),
+                "error" => InlineErrorChain::new(&e),
+            );
+            return None;
+        }
+    };
+    DateTime::from_timestamp(expiry, 0)
+}
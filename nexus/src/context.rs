@@ -344,12 +344,54 @@ impl ServerContext {
 /// 1. Creates an OpContext via authentication
 /// 2. Initializes an audit log entry
 /// 3. Runs the handler
-/// 4. Completes the audit log entry with result info
+/// 4. Completes the audit log entry with result info, including the resource
+///    the handler recorded with `OpContext::set_audit_target()`, if any
 /// 5. Wraps everything in latency instrumentation
+///
+/// For endpoints that accept a request body, use [`audit_and_time_with_body`]
+/// so that the body is recorded too.
 pub async fn audit_and_time<F, Fut, R>(
     rqctx: &dropshot::RequestContext<ApiContext>,
     handler: F,
 ) -> Result<R, HttpError>
+where
+    F: FnOnce(Arc<OpContext>, Arc<Nexus>) -> Fut,
+    Fut: Future<Output = Result<R, HttpError>>,
+    R: HttpResponse,
+{
+    audit_and_time_inner(rqctx, None, handler).await
+}
+
+/// Like [`audit_and_time`], but also records the request body `body` in the
+/// audit log entry, then passes it along to the handler
+///
+/// `redact` is the endpoint's redaction policy: the paths of values in the
+/// body, like passwords and secrets, that must not be written to the audit
+/// log. See [`crate::app::audit_request_body`] for the path syntax.
+pub async fn audit_and_time_with_body<T, F, Fut, R>(
+    rqctx: &dropshot::RequestContext<ApiContext>,
+    body: T,
+    redact: &[&str],
+    handler: F,
+) -> Result<R, HttpError>
+where
+    T: serde::Serialize,
+    F: FnOnce(Arc<OpContext>, Arc<Nexus>, T) -> Fut,
+    Fut: Future<Output = Result<R, HttpError>>,
+    R: HttpResponse,
+{
+    let request_body = crate::app::audit_request_body(&body, redact);
+    audit_and_time_inner(rqctx, request_body, move |opctx, nexus| {
+        handler(opctx, nexus, body)
+    })
+    .await
+}
+
+async fn audit_and_time_inner<F, Fut, R>(
+    rqctx: &dropshot::RequestContext<ApiContext>,
+    request_body: Option<serde_json::Value>,
+    handler: F,
+) -> Result<R, HttpError>
 where
     F: FnOnce(Arc<OpContext>, Arc<Nexus>) -> Fut,
     Fut: Future<Output = Result<R, HttpError>>,
@@ -359,12 +401,20 @@ where
     let nexus = Arc::clone(&apictx.context.nexus);
     let handler = async {
         let opctx = Arc::new(op_context_for_external_api(rqctx).await?);
-        let audit = nexus.audit_log_entry_init(&opctx, rqctx).await?;
+        let audit =
+            nexus.audit_log_entry_init(&opctx, rqctx, request_body).await?;
         let result = handler(Arc::clone(&opctx), Arc::clone(&nexus)).await;
         // Ignore error: unlike the init line, audit log failures cannot cause
         // the request to fail because the primary operation has already taken
         // place. The complete function retries internally and logs on failure.
-        let _ = nexus.audit_log_entry_complete(&opctx, &audit, &result).await;
+        let _ = nexus
+            .audit_log_entry_complete(
+                &opctx,
+                &audit,
+                &result,
+                opctx.audit_target(),
+            )
+            .await;
         result
     };
     apictx
@@ -383,9 +433,41 @@ pub(crate) async fn op_context_for_external_api(
     OpContext::new_async(
         &rqctx.log,
         async {
-            let authn = Arc::new(
-                apictx.context.external_authn.authn_request(rqctx).await?,
-            );
+            let remote_addr = rqctx.request.remote_addr().ip();
+            let authn = match apictx
+                .context
+                .external_authn
+                .authn_request(rqctx)
+                .await
+            {
+                Ok(authn) => Arc::new(authn),
+                Err(error) => {
+                    // Failed attempts count against the client's address,
+                    // like unauthenticated requests do.  Otherwise, guessing
+                    // credentials would never be limited at all.
+                    if let Err(retry_after) = apictx
+                        .context
+                        .nexus
+                        .rate_limit_failed_authn(remote_addr)
+                    {
+                        apictx
+                            .context
+                            .external_latencies
+                            .record_rate_limited(&rqctx.endpoint.operation_id);
+                        return Err(too_many_requests(retry_after));
+                    }
+                    return Err(error.into());
+                }
+            };
+            if let Err(retry_after) =
+                apictx.context.nexus.rate_limit_check(&authn, remote_addr).await
+            {
+                apictx
+                    .context
+                    .external_latencies
+                    .record_rate_limited(&rqctx.endpoint.operation_id);
+                return Err(too_many_requests(retry_after));
+            }
             let datastore = Arc::clone(apictx.context.nexus.datastore());
             let authz = authz::Context::new(
                 Arc::clone(&authn),
@@ -400,6 +482,25 @@ pub(crate) async fn op_context_for_external_api(
     .await
 }
 
+/// Builds the error returned for a request that exceeded its rate limit
+///
+/// `retry_after` is rounded up to whole seconds for the `Retry-After` header.
+fn too_many_requests(retry_after: std::time::Duration) -> HttpError {
+    let retry_after_secs = retry_after.as_secs_f64().ceil().max(1.0) as u64;
+    let mut headers = http::HeaderMap::new();
+    headers.insert(http::header::RETRY_AFTER, retry_after_secs.into());
+    let message = String::from("too many requests");
+    HttpError {
+        status_code: dropshot::ErrorStatusCode::TOO_MANY_REQUESTS,
+        error_code: Some(String::from("TooManyRequests")),
+        external_message: message.clone(),
+        internal_message: format!(
+            "{message} (retry after {retry_after_secs}s)"
+        ),
+        headers: Some(Box::new(headers)),
+    }
+}
+
 pub(crate) async fn op_context_for_internal_api(
     rqctx: &dropshot::RequestContext<ApiContext>,
 ) -> OpContext {
@@ -501,10 +602,7 @@ impl authn::external::token::TokenContext for ServerContext {
     async fn authenticate_token(
         &self,
         token: String,
-    ) -> Result<
-        (authn::Actor, Option<chrono::DateTime<chrono::Utc>>, Uuid),
-        authn::Reason,
-    > {
+    ) -> Result<authn::Details, authn::Reason> {
         let opctx = self.nexus.opctx_external_authn();
         self.nexus.authenticate_token(opctx, token).await
     }
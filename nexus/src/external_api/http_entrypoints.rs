@@ -8,7 +8,7 @@ use super::console_api;
 use crate::app::SetTargetReleaseIntent;
 use crate::app::external_endpoints::authority_for_request;
 use crate::app::support_bundles::SupportBundleQueryType;
-use crate::context::{ApiContext, audit_and_time};
+use crate::context::{ApiContext, audit_and_time, audit_and_time_with_body};
 use dropshot::Body;
 use dropshot::EmptyScanParams;
 use dropshot::Header;
@@ -43,10 +43,10 @@ use nexus_types::authn::cookies::Cookies;
 use nexus_types::external_api::{
     affinity, alert, audit, certificate, console, device, disk, external_ip,
     external_subnet, floating_ip, hardware, identity_provider, image, instance,
-    internet_gateway, ip_pool, metrics, multicast, networking, oxql,
-    path_params, policy, probe, project, rack, scim, silo, sled, snapshot,
-    ssh_key, subnet_pool, support_bundle, switch, system, system_networking,
-    timeseries, update, user, vpc,
+    internet_gateway, ip_pool, load_balancer, metrics, multicast, networking,
+    oxql, path_params, policy, probe, project, rack, scim, service_account,
+    silo, sled, snapshot, ssh_key, subnet_pool, support_bundle, switch, system,
+    system_networking, timeseries, update, user, vpc,
 };
 // Type imports for API implementations (per RFD 619)
 use nexus_types::external_api::bfd::BfdStatus;
@@ -64,7 +64,7 @@ use nexus_types::external_api::probe::ProbeInfo;
 use nexus_types::external_api::project::Project;
 use nexus_types::external_api::rack::{Rack, RackMembershipStatus};
 use nexus_types::external_api::silo::{
-    Silo, SiloQuotas, SiloUtilization, Utilization,
+    Silo, SiloQuotas, SiloRateLimit, SiloUtilization, Utilization,
 };
 use nexus_types::external_api::sled::Sled;
 use nexus_types::external_api::snapshot::Snapshot;
@@ -73,6 +73,7 @@ use nexus_types::external_api::user::{Group, User, UserBuiltin};
 use nexus_types::external_api::vpc::{Vpc, VpcRouter, VpcSubnet};
 use nexus_types_versions::latest::headers::RangeRequest;
 use nexus_types_versions::v2025_11_20_00;
+use nexus_types_versions::v2026_10_19_00;
 use omicron_common::address::IpRange;
 use omicron_common::api::external::AddressLot;
 use omicron_common::api::external::AddressLotBlock;
@@ -158,14 +159,21 @@ impl NexusExternalApi for NexusExternalApiImpl {
         new_policy: TypedBody<policy::Policy<policy::FleetRole>>,
     ) -> Result<HttpResponseOk<policy::Policy<policy::FleetRole>>, HttpError>
     {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let new_policy = new_policy.into_inner();
-            let nasgns = new_policy.role_assignments.len();
-            // This should have been validated during parsing.
-            bail_unless!(nasgns <= policy::MAX_ROLE_ASSIGNMENTS_PER_RESOURCE);
-            let policy = nexus.fleet_update_policy(&opctx, &new_policy).await?;
-            Ok(HttpResponseOk(policy))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            new_policy.into_inner(),
+            &[],
+            |opctx, nexus, new_policy| async move {
+                let nasgns = new_policy.role_assignments.len();
+                // This should have been validated during parsing.
+                bail_unless!(
+                    nasgns <= policy::MAX_ROLE_ASSIGNMENTS_PER_RESOURCE
+                );
+                let policy =
+                    nexus.fleet_update_policy(&opctx, &new_policy).await?;
+                Ok(HttpResponseOk(policy))
+            },
+        )
         .await
     }
 
@@ -242,23 +250,29 @@ impl NexusExternalApi for NexusExternalApiImpl {
         new_policy: TypedBody<policy::Policy<policy::SiloRole>>,
     ) -> Result<HttpResponseOk<policy::Policy<policy::SiloRole>>, HttpError>
     {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let new_policy = new_policy.into_inner();
-            let nasgns = new_policy.role_assignments.len();
-            // This should have been validated during parsing.
-            bail_unless!(nasgns <= policy::MAX_ROLE_ASSIGNMENTS_PER_RESOURCE);
-            let silo: NameOrId = opctx
-                .authn
-                .silo_required()
-                .internal_context("loading current silo")?
-                .id()
-                .into();
-            let silo_lookup = nexus.silo_lookup(&opctx, silo)?;
-            let policy = nexus
-                .silo_update_policy(&opctx, &silo_lookup, &new_policy)
-                .await?;
-            Ok(HttpResponseOk(policy))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            new_policy.into_inner(),
+            &[],
+            |opctx, nexus, new_policy| async move {
+                let nasgns = new_policy.role_assignments.len();
+                // This should have been validated during parsing.
+                bail_unless!(
+                    nasgns <= policy::MAX_ROLE_ASSIGNMENTS_PER_RESOURCE
+                );
+                let silo: NameOrId = opctx
+                    .authn
+                    .silo_required()
+                    .internal_context("loading current silo")?
+                    .id()
+                    .into();
+                let silo_lookup = nexus.silo_lookup(&opctx, silo)?;
+                let policy = nexus
+                    .silo_update_policy(&opctx, &silo_lookup, &new_policy)
+                    .await?;
+                Ok(HttpResponseOk(policy))
+            },
+        )
         .await
     }
 
@@ -293,20 +307,28 @@ impl NexusExternalApi for NexusExternalApiImpl {
         rqctx: RequestContext<Self::Context>,
         new_settings: TypedBody<silo::SiloAuthSettingsUpdate>,
     ) -> Result<HttpResponseOk<silo::SiloAuthSettings>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let new_settings = new_settings.into_inner();
-            let silo: NameOrId = opctx
-                .authn
-                .silo_required()
-                .internal_context("loading current silo")?
-                .id()
-                .into();
-            let silo_lookup = nexus.silo_lookup(&opctx, silo)?;
-            let settings = nexus
-                .silo_update_auth_settings(&opctx, &silo_lookup, &new_settings)
-                .await?;
-            Ok(HttpResponseOk(settings.into()))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            new_settings.into_inner(),
+            &[],
+            |opctx, nexus, new_settings| async move {
+                let silo: NameOrId = opctx
+                    .authn
+                    .silo_required()
+                    .internal_context("loading current silo")?
+                    .id()
+                    .into();
+                let silo_lookup = nexus.silo_lookup(&opctx, silo)?;
+                let settings = nexus
+                    .silo_update_auth_settings(
+                        &opctx,
+                        &silo_lookup,
+                        &new_settings,
+                    )
+                    .await?;
+                Ok(HttpResponseOk(settings.into()))
+            },
+        )
         .await
     }
 
@@ -449,15 +471,67 @@ impl NexusExternalApi for NexusExternalApiImpl {
         path_params: Path<path_params::SiloPath>,
         new_quota: TypedBody<silo::SiloQuotasUpdate>,
     ) -> Result<HttpResponseOk<SiloQuotas>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let path = path_params.into_inner();
-            let new_quota = new_quota.into_inner();
-            let silo_lookup = nexus.silo_lookup(&opctx, path.silo)?;
-            let quota = nexus
-                .silo_update_quota(&opctx, &silo_lookup, &new_quota)
-                .await?;
-            Ok(HttpResponseOk(quota.into()))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            new_quota.into_inner(),
+            &[],
+            |opctx, nexus, new_quota| async move {
+                let path = path_params.into_inner();
+                let silo_lookup = nexus.silo_lookup(&opctx, path.silo)?;
+                let quota = nexus
+                    .silo_update_quota(&opctx, &silo_lookup, &new_quota)
+                    .await?;
+                Ok(HttpResponseOk(quota.into()))
+            },
+        )
+        .await
+    }
+
+    async fn silo_rate_limit_view(
+        rqctx: RequestContext<ApiContext>,
+        path_params: Path<path_params::SiloPath>,
+    ) -> Result<HttpResponseOk<SiloRateLimit>, HttpError> {
+        let apictx = rqctx.context();
+        let handler = async {
+            let nexus = &apictx.context.nexus;
+
+            let opctx =
+                crate::context::op_context_for_external_api(&rqctx).await?;
+            let silo_lookup =
+                nexus.silo_lookup(&opctx, path_params.into_inner().silo)?;
+            let rate_limit =
+                nexus.silo_rate_limit_view(&opctx, &silo_lookup).await?;
+            Ok(HttpResponseOk(rate_limit.into()))
+        };
+        apictx
+            .context
+            .external_latencies
+            .instrument_dropshot_handler(&rqctx, handler)
+            .await
+    }
+
+    async fn silo_rate_limit_update(
+        rqctx: RequestContext<ApiContext>,
+        path_params: Path<path_params::SiloPath>,
+        new_rate_limit: TypedBody<silo::SiloRateLimitUpdate>,
+    ) -> Result<HttpResponseOk<SiloRateLimit>, HttpError> {
+        audit_and_time_with_body(
+            &rqctx,
+            new_rate_limit.into_inner(),
+            &[],
+            |opctx, nexus, new_rate_limit| async move {
+                let path = path_params.into_inner();
+                let silo_lookup = nexus.silo_lookup(&opctx, path.silo)?;
+                let rate_limit = nexus
+                    .silo_rate_limit_update(
+                        &opctx,
+                        &silo_lookup,
+                        &new_rate_limit,
+                    )
+                    .await?;
+                Ok(HttpResponseOk(rate_limit.into()))
+            },
+        )
         .await
     }
 
@@ -497,11 +571,15 @@ impl NexusExternalApi for NexusExternalApiImpl {
         rqctx: RequestContext<ApiContext>,
         new_silo_params: TypedBody<silo::SiloCreate>,
     ) -> Result<HttpResponseCreated<Silo>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let new_silo_params = new_silo_params.into_inner();
-            let silo = nexus.silo_create(&opctx, new_silo_params).await?;
-            Ok(HttpResponseCreated(silo.try_into()?))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            new_silo_params.into_inner(),
+            &["/tls_certificates/*/key"],
+            |opctx, nexus, new_silo_params| async move {
+                let silo = nexus.silo_create(&opctx, new_silo_params).await?;
+                Ok(HttpResponseCreated(silo.try_into()?))
+            },
+        )
         .await
     }
 
@@ -611,18 +689,24 @@ impl NexusExternalApi for NexusExternalApiImpl {
         new_policy: TypedBody<policy::Policy<policy::SiloRole>>,
     ) -> Result<HttpResponseOk<policy::Policy<policy::SiloRole>>, HttpError>
     {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let path = path_params.into_inner();
-            let new_policy = new_policy.into_inner();
-            let nasgns = new_policy.role_assignments.len();
-            // This should have been validated during parsing.
-            bail_unless!(nasgns <= policy::MAX_ROLE_ASSIGNMENTS_PER_RESOURCE);
-            let silo_lookup = nexus.silo_lookup(&opctx, path.silo)?;
-            let policy = nexus
-                .silo_update_policy(&opctx, &silo_lookup, &new_policy)
-                .await?;
-            Ok(HttpResponseOk(policy))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            new_policy.into_inner(),
+            &[],
+            |opctx, nexus, new_policy| async move {
+                let path = path_params.into_inner();
+                let nasgns = new_policy.role_assignments.len();
+                // This should have been validated during parsing.
+                bail_unless!(
+                    nasgns <= policy::MAX_ROLE_ASSIGNMENTS_PER_RESOURCE
+                );
+                let silo_lookup = nexus.silo_lookup(&opctx, path.silo)?;
+                let policy = nexus
+                    .silo_update_policy(&opctx, &silo_lookup, &new_policy)
+                    .await?;
+                Ok(HttpResponseOk(policy))
+            },
+        )
         .await
     }
 
@@ -732,19 +816,23 @@ impl NexusExternalApi for NexusExternalApiImpl {
         HttpResponseCreated<identity_provider::SamlIdentityProvider>,
         HttpError,
     > {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let query = query_params.into_inner();
-            let new_provider = new_provider.into_inner();
-            let silo_lookup = nexus.silo_lookup(&opctx, query.silo)?;
-            let provider = nexus
-                .saml_identity_provider_create(
-                    &opctx,
-                    &silo_lookup,
-                    new_provider,
-                )
-                .await?;
-            Ok(HttpResponseCreated(provider.into()))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            new_provider.into_inner(),
+            &["/signing_keypair/private_key"],
+            |opctx, nexus, new_provider| async move {
+                let query = query_params.into_inner();
+                let silo_lookup = nexus.silo_lookup(&opctx, query.silo)?;
+                let provider = nexus
+                    .saml_identity_provider_create(
+                        &opctx,
+                        &silo_lookup,
+                        new_provider,
+                    )
+                    .await?;
+                Ok(HttpResponseCreated(provider.into()))
+            },
+        )
         .await
     }
 
@@ -793,15 +881,23 @@ impl NexusExternalApi for NexusExternalApiImpl {
         query_params: Query<silo::SiloSelector>,
         new_user_params: TypedBody<user::UserCreate>,
     ) -> Result<HttpResponseCreated<User>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let query = query_params.into_inner();
-            let new_user_params = new_user_params.into_inner();
-            let silo_lookup = nexus.silo_lookup(&opctx, query.silo)?;
-            let user = nexus
-                .local_idp_create_user(&opctx, &silo_lookup, new_user_params)
-                .await?;
-            Ok(HttpResponseCreated(user.into()))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            new_user_params.into_inner(),
+            &["/password/value"],
+            |opctx, nexus, new_user_params| async move {
+                let query = query_params.into_inner();
+                let silo_lookup = nexus.silo_lookup(&opctx, query.silo)?;
+                let user = nexus
+                    .local_idp_create_user(
+                        &opctx,
+                        &silo_lookup,
+                        new_user_params,
+                    )
+                    .await?;
+                Ok(HttpResponseCreated(user.into()))
+            },
+        )
         .await
     }
 
@@ -828,21 +924,25 @@ impl NexusExternalApi for NexusExternalApiImpl {
         query_params: Query<path_params::SiloPath>,
         update: TypedBody<user::UserPassword>,
     ) -> Result<HttpResponseUpdatedNoContent, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let path = path_params.into_inner();
-            let query = query_params.into_inner();
-            let update = update.into_inner();
-            let silo_lookup = nexus.silo_lookup(&opctx, query.silo)?;
-            nexus
-                .local_idp_user_set_password(
-                    &opctx,
-                    &silo_lookup,
-                    path.user_id,
-                    update,
-                )
-                .await?;
-            Ok(HttpResponseUpdatedNoContent())
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            update.into_inner(),
+            &["/value"],
+            |opctx, nexus, update| async move {
+                let path = path_params.into_inner();
+                let query = query_params.into_inner();
+                let silo_lookup = nexus.silo_lookup(&opctx, query.silo)?;
+                nexus
+                    .local_idp_user_set_password(
+                        &opctx,
+                        &silo_lookup,
+                        path.user_id,
+                        update,
+                    )
+                    .await?;
+                Ok(HttpResponseUpdatedNoContent())
+            },
+        )
         .await
     }
 
@@ -1142,11 +1242,16 @@ impl NexusExternalApi for NexusExternalApiImpl {
         rqctx: RequestContext<ApiContext>,
         new_project: TypedBody<project::ProjectCreate>,
     ) -> Result<HttpResponseCreated<Project>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let new_project = new_project.into_inner();
-            let project = nexus.project_create(&opctx, &new_project).await?;
-            Ok(HttpResponseCreated(project.into()))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            new_project.into_inner(),
+            &[],
+            |opctx, nexus, new_project| async move {
+                let project =
+                    nexus.project_create(&opctx, &new_project).await?;
+                Ok(HttpResponseCreated(project.into()))
+            },
+        )
         .await
     }
 
@@ -1199,18 +1304,22 @@ impl NexusExternalApi for NexusExternalApiImpl {
         path_params: Path<path_params::ProjectPath>,
         updated_project: TypedBody<project::ProjectUpdate>,
     ) -> Result<HttpResponseOk<Project>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let path = path_params.into_inner();
-            let updated_project = updated_project.into_inner();
-            let project_selector =
-                project::ProjectSelector { project: path.project };
-            let project_lookup =
-                nexus.project_lookup(&opctx, project_selector)?;
-            let project = nexus
-                .project_update(&opctx, &project_lookup, &updated_project)
-                .await?;
-            Ok(HttpResponseOk(project.into()))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            updated_project.into_inner(),
+            &[],
+            |opctx, nexus, updated_project| async move {
+                let path = path_params.into_inner();
+                let project_selector =
+                    project::ProjectSelector { project: path.project };
+                let project_lookup =
+                    nexus.project_lookup(&opctx, project_selector)?;
+                let project = nexus
+                    .project_update(&opctx, &project_lookup, &updated_project)
+                    .await?;
+                Ok(HttpResponseOk(project.into()))
+            },
+        )
         .await
     }
 
@@ -1246,18 +1355,22 @@ impl NexusExternalApi for NexusExternalApiImpl {
         new_policy: TypedBody<policy::Policy<policy::ProjectRole>>,
     ) -> Result<HttpResponseOk<policy::Policy<policy::ProjectRole>>, HttpError>
     {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let path = path_params.into_inner();
-            let new_policy = new_policy.into_inner();
-            let project_selector =
-                project::ProjectSelector { project: path.project };
-            let project_lookup =
-                nexus.project_lookup(&opctx, project_selector)?;
-            let policy = nexus
-                .project_update_policy(&opctx, &project_lookup, &new_policy)
-                .await?;
-            Ok(HttpResponseOk(policy))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            new_policy.into_inner(),
+            &[],
+            |opctx, nexus, new_policy| async move {
+                let path = path_params.into_inner();
+                let project_selector =
+                    project::ProjectSelector { project: path.project };
+                let project_lookup =
+                    nexus.project_lookup(&opctx, project_selector)?;
+                let policy = nexus
+                    .project_update_policy(&opctx, &project_lookup, &new_policy)
+                    .await?;
+                Ok(HttpResponseOk(policy))
+            },
+        )
         .await
     }
 
@@ -1363,11 +1476,15 @@ impl NexusExternalApi for NexusExternalApiImpl {
         rqctx: RequestContext<ApiContext>,
         pool_params: TypedBody<ip_pool::IpPoolCreate>,
     ) -> Result<HttpResponseCreated<IpPool>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let pool_params = pool_params.into_inner();
-            let pool = nexus.ip_pool_create(&opctx, &pool_params).await?;
-            Ok(HttpResponseCreated(pool.into()))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            pool_params.into_inner(),
+            &[],
+            |opctx, nexus, pool_params| async move {
+                let pool = nexus.ip_pool_create(&opctx, &pool_params).await?;
+                Ok(HttpResponseCreated(pool.into()))
+            },
+        )
         .await
     }
 
@@ -1412,14 +1529,19 @@ impl NexusExternalApi for NexusExternalApiImpl {
         path_params: Path<path_params::IpPoolPath>,
         updates: TypedBody<ip_pool::IpPoolUpdate>,
     ) -> Result<HttpResponseOk<IpPool>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let path = path_params.into_inner();
-            let updates = updates.into_inner();
-            let pool_lookup = nexus.ip_pool_lookup(&opctx, &path.pool)?;
-            let pool =
-                nexus.ip_pool_update(&opctx, &pool_lookup, &updates).await?;
-            Ok(HttpResponseOk(pool.into()))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            updates.into_inner(),
+            &[],
+            |opctx, nexus, updates| async move {
+                let path = path_params.into_inner();
+                let pool_lookup = nexus.ip_pool_lookup(&opctx, &path.pool)?;
+                let pool = nexus
+                    .ip_pool_update(&opctx, &pool_lookup, &updates)
+                    .await?;
+                Ok(HttpResponseOk(pool.into()))
+            },
+        )
         .await
     }
 
@@ -1500,15 +1622,19 @@ impl NexusExternalApi for NexusExternalApiImpl {
         path_params: Path<path_params::IpPoolPath>,
         resource_assoc: TypedBody<ip_pool::IpPoolLinkSilo>,
     ) -> Result<HttpResponseCreated<ip_pool::IpPoolSiloLink>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let path = path_params.into_inner();
-            let resource_assoc = resource_assoc.into_inner();
-            let pool_lookup = nexus.ip_pool_lookup(&opctx, &path.pool)?;
-            let assoc = nexus
-                .ip_pool_link_silo(&opctx, &pool_lookup, &resource_assoc)
-                .await?;
-            Ok(HttpResponseCreated(assoc.into()))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            resource_assoc.into_inner(),
+            &[],
+            |opctx, nexus, resource_assoc| async move {
+                let path = path_params.into_inner();
+                let pool_lookup = nexus.ip_pool_lookup(&opctx, &path.pool)?;
+                let assoc = nexus
+                    .ip_pool_link_silo(&opctx, &pool_lookup, &resource_assoc)
+                    .await?;
+                Ok(HttpResponseCreated(assoc.into()))
+            },
+        )
         .await
     }
 
@@ -1533,21 +1659,25 @@ impl NexusExternalApi for NexusExternalApiImpl {
         path_params: Path<ip_pool::IpPoolSiloPath>,
         update: TypedBody<ip_pool::IpPoolSiloUpdate>,
     ) -> Result<HttpResponseOk<ip_pool::IpPoolSiloLink>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let path = path_params.into_inner();
-            let update = update.into_inner();
-            let pool_lookup = nexus.ip_pool_lookup(&opctx, &path.pool)?;
-            let silo_lookup = nexus.silo_lookup(&opctx, path.silo)?;
-            let assoc = nexus
-                .ip_pool_silo_update(
-                    &opctx,
-                    &pool_lookup,
-                    &silo_lookup,
-                    &update,
-                )
-                .await?;
-            Ok(HttpResponseOk(assoc.into()))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            update.into_inner(),
+            &[],
+            |opctx, nexus, update| async move {
+                let path = path_params.into_inner();
+                let pool_lookup = nexus.ip_pool_lookup(&opctx, &path.pool)?;
+                let silo_lookup = nexus.silo_lookup(&opctx, path.silo)?;
+                let assoc = nexus
+                    .ip_pool_silo_update(
+                        &opctx,
+                        &pool_lookup,
+                        &silo_lookup,
+                        &update,
+                    )
+                    .await?;
+                Ok(HttpResponseOk(assoc.into()))
+            },
+        )
         .await
     }
 
@@ -1617,14 +1747,19 @@ impl NexusExternalApi for NexusExternalApiImpl {
         path_params: Path<path_params::IpPoolPath>,
         range_params: TypedBody<IpRange>,
     ) -> Result<HttpResponseCreated<IpPoolRange>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let path = path_params.into_inner();
-            let range = range_params.into_inner();
-            let pool_lookup = nexus.ip_pool_lookup(&opctx, &path.pool)?;
-            let out =
-                nexus.ip_pool_add_range(&opctx, &pool_lookup, &range).await?;
-            Ok(HttpResponseCreated(out.try_into()?))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            range_params.into_inner(),
+            &[],
+            |opctx, nexus, range| async move {
+                let path = path_params.into_inner();
+                let pool_lookup = nexus.ip_pool_lookup(&opctx, &path.pool)?;
+                let out = nexus
+                    .ip_pool_add_range(&opctx, &pool_lookup, &range)
+                    .await?;
+                Ok(HttpResponseCreated(out.try_into()?))
+            },
+        )
         .await
     }
 
@@ -1633,13 +1768,19 @@ impl NexusExternalApi for NexusExternalApiImpl {
         path_params: Path<path_params::IpPoolPath>,
         range_params: TypedBody<IpRange>,
     ) -> Result<HttpResponseUpdatedNoContent, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let path = path_params.into_inner();
-            let range = range_params.into_inner();
-            let pool_lookup = nexus.ip_pool_lookup(&opctx, &path.pool)?;
-            nexus.ip_pool_delete_range(&opctx, &pool_lookup, &range).await?;
-            Ok(HttpResponseUpdatedNoContent())
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            range_params.into_inner(),
+            &[],
+            |opctx, nexus, range| async move {
+                let path = path_params.into_inner();
+                let pool_lookup = nexus.ip_pool_lookup(&opctx, &path.pool)?;
+                nexus
+                    .ip_pool_delete_range(&opctx, &pool_lookup, &range)
+                    .await?;
+                Ok(HttpResponseUpdatedNoContent())
+            },
+        )
         .await
     }
 
@@ -1687,11 +1828,16 @@ impl NexusExternalApi for NexusExternalApiImpl {
         rqctx: RequestContext<ApiContext>,
         range_params: TypedBody<IpRange>,
     ) -> Result<HttpResponseCreated<IpPoolRange>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let range = range_params.into_inner();
-            let out = nexus.ip_pool_service_add_range(&opctx, &range).await?;
-            Ok(HttpResponseCreated(out.try_into()?))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            range_params.into_inner(),
+            &[],
+            |opctx, nexus, range| async move {
+                let out =
+                    nexus.ip_pool_service_add_range(&opctx, &range).await?;
+                Ok(HttpResponseCreated(out.try_into()?))
+            },
+        )
         .await
     }
 
@@ -1699,11 +1845,15 @@ impl NexusExternalApi for NexusExternalApiImpl {
         rqctx: RequestContext<ApiContext>,
         range_params: TypedBody<IpRange>,
     ) -> Result<HttpResponseUpdatedNoContent, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let range = range_params.into_inner();
-            nexus.ip_pool_service_delete_range(&opctx, &range).await?;
-            Ok(HttpResponseUpdatedNoContent())
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            range_params.into_inner(),
+            &[],
+            |opctx, nexus, range| async move {
+                nexus.ip_pool_service_delete_range(&opctx, &range).await?;
+                Ok(HttpResponseUpdatedNoContent())
+            },
+        )
         .await
     }
 
@@ -1741,11 +1891,16 @@ impl NexusExternalApi for NexusExternalApiImpl {
         rqctx: RequestContext<ApiContext>,
         pool_params: TypedBody<subnet_pool::SubnetPoolCreate>,
     ) -> Result<HttpResponseCreated<subnet_pool::SubnetPool>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let pool_params = pool_params.into_inner();
-            let pool = nexus.subnet_pool_create(&opctx, pool_params).await?;
-            Ok(HttpResponseCreated(pool))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            pool_params.into_inner(),
+            &[],
+            |opctx, nexus, pool_params| async move {
+                let pool =
+                    nexus.subnet_pool_create(&opctx, pool_params).await?;
+                Ok(HttpResponseCreated(pool))
+            },
+        )
         .await
     }
 
@@ -1774,13 +1929,18 @@ impl NexusExternalApi for NexusExternalApiImpl {
         path_params: Path<subnet_pool::SubnetPoolPath>,
         updates: TypedBody<subnet_pool::SubnetPoolUpdate>,
     ) -> Result<HttpResponseOk<subnet_pool::SubnetPool>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let path = path_params.into_inner();
-            let updates = updates.into_inner();
-            let pool =
-                nexus.subnet_pool_update(&opctx, &path.pool, updates).await?;
-            Ok(HttpResponseOk(pool))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            updates.into_inner(),
+            &[],
+            |opctx, nexus, updates| async move {
+                let path = path_params.into_inner();
+                let pool = nexus
+                    .subnet_pool_update(&opctx, &path.pool, updates)
+                    .await?;
+                Ok(HttpResponseOk(pool))
+            },
+        )
         .await
     }
 
@@ -1848,14 +2008,18 @@ impl NexusExternalApi for NexusExternalApiImpl {
         subnet_params: TypedBody<subnet_pool::SubnetPoolMemberAdd>,
     ) -> Result<HttpResponseCreated<subnet_pool::SubnetPoolMember>, HttpError>
     {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let path = path_params.into_inner();
-            let subnet_params = subnet_params.into_inner();
-            let member = nexus
-                .subnet_pool_member_add(&opctx, &path.pool, &subnet_params)
-                .await?;
-            Ok(HttpResponseCreated(member))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            subnet_params.into_inner(),
+            &[],
+            |opctx, nexus, subnet_params| async move {
+                let path = path_params.into_inner();
+                let member = nexus
+                    .subnet_pool_member_add(&opctx, &path.pool, &subnet_params)
+                    .await?;
+                Ok(HttpResponseCreated(member))
+            },
+        )
         .await
     }
 
@@ -1864,14 +2028,22 @@ impl NexusExternalApi for NexusExternalApiImpl {
         path_params: Path<subnet_pool::SubnetPoolPath>,
         subnet_params: TypedBody<subnet_pool::SubnetPoolMemberRemove>,
     ) -> Result<HttpResponseUpdatedNoContent, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let path = path_params.into_inner();
-            let subnet_params = subnet_params.into_inner();
-            nexus
-                .subnet_pool_member_remove(&opctx, &path.pool, &subnet_params)
-                .await?;
-            Ok(HttpResponseUpdatedNoContent())
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            subnet_params.into_inner(),
+            &[],
+            |opctx, nexus, subnet_params| async move {
+                let path = path_params.into_inner();
+                nexus
+                    .subnet_pool_member_remove(
+                        &opctx,
+                        &path.pool,
+                        &subnet_params,
+                    )
+                    .await?;
+                Ok(HttpResponseUpdatedNoContent())
+            },
+        )
         .await
     }
 
@@ -2021,14 +2193,18 @@ impl NexusExternalApi for NexusExternalApiImpl {
         silo_link: TypedBody<subnet_pool::SubnetPoolLinkSilo>,
     ) -> Result<HttpResponseCreated<subnet_pool::SubnetPoolSiloLink>, HttpError>
     {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let path = path_params.into_inner();
-            let silo_link = silo_link.into_inner();
-            let link = nexus
-                .subnet_pool_silo_link(&opctx, &path.pool, silo_link)
-                .await?;
-            Ok(HttpResponseCreated(link))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            silo_link.into_inner(),
+            &[],
+            |opctx, nexus, silo_link| async move {
+                let path = path_params.into_inner();
+                let link = nexus
+                    .subnet_pool_silo_link(&opctx, &path.pool, silo_link)
+                    .await?;
+                Ok(HttpResponseCreated(link))
+            },
+        )
         .await
     }
 
@@ -2038,14 +2214,20 @@ impl NexusExternalApi for NexusExternalApiImpl {
         update: TypedBody<subnet_pool::SubnetPoolSiloUpdate>,
     ) -> Result<HttpResponseOk<subnet_pool::SubnetPoolSiloLink>, HttpError>
     {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let path = path_params.into_inner();
-            let update = update.into_inner();
-            let link = nexus
-                .subnet_pool_silo_update(&opctx, path.pool, path.silo, update)
-                .await?;
-            Ok(HttpResponseOk(link))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            update.into_inner(),
+            &[],
+            |opctx, nexus, update| async move {
+                let path = path_params.into_inner();
+                let link = nexus
+                    .subnet_pool_silo_update(
+                        &opctx, path.pool, path.silo, update,
+                    )
+                    .await?;
+                Ok(HttpResponseOk(link))
+            },
+        )
         .await
     }
 
@@ -2127,15 +2309,19 @@ impl NexusExternalApi for NexusExternalApiImpl {
         subnet_params: TypedBody<external_subnet::ExternalSubnetCreate>,
     ) -> Result<HttpResponseCreated<external_subnet::ExternalSubnet>, HttpError>
     {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let query = query_params.into_inner();
-            let params = subnet_params.into_inner();
-            let project_lookup = nexus.project_lookup(&opctx, query)?;
-            let subnet = nexus
-                .external_subnet_create(&opctx, &project_lookup, params)
-                .await?;
-            Ok(HttpResponseCreated(subnet))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            subnet_params.into_inner(),
+            &[],
+            |opctx, nexus, params| async move {
+                let query = query_params.into_inner();
+                let project_lookup = nexus.project_lookup(&opctx, query)?;
+                let subnet = nexus
+                    .external_subnet_create(&opctx, &project_lookup, params)
+                    .await?;
+                Ok(HttpResponseCreated(subnet))
+            },
+        )
         .await
     }
 
@@ -2173,18 +2359,23 @@ impl NexusExternalApi for NexusExternalApiImpl {
         subnet_params: TypedBody<external_subnet::ExternalSubnetUpdate>,
     ) -> Result<HttpResponseOk<external_subnet::ExternalSubnet>, HttpError>
     {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let path = path_params.into_inner();
-            let query = query_params.into_inner();
-            let params = subnet_params.into_inner();
-            let selector = external_subnet::ExternalSubnetSelector {
-                external_subnet: path.external_subnet,
-                project: query.project,
-            };
-            let subnet =
-                nexus.external_subnet_update(&opctx, selector, params).await?;
-            Ok(HttpResponseOk(subnet))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            subnet_params.into_inner(),
+            &[],
+            |opctx, nexus, params| async move {
+                let path = path_params.into_inner();
+                let query = query_params.into_inner();
+                let selector = external_subnet::ExternalSubnetSelector {
+                    external_subnet: path.external_subnet,
+                    project: query.project,
+                };
+                let subnet = nexus
+                    .external_subnet_update(&opctx, selector, params)
+                    .await?;
+                Ok(HttpResponseOk(subnet))
+            },
+        )
         .await
     }
 
@@ -2213,18 +2404,23 @@ impl NexusExternalApi for NexusExternalApiImpl {
         attach_params: TypedBody<external_subnet::ExternalSubnetAttach>,
     ) -> Result<HttpResponseAccepted<external_subnet::ExternalSubnet>, HttpError>
     {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let path = path_params.into_inner();
-            let query = query_params.into_inner();
-            let attach = attach_params.into_inner();
-            let selector = external_subnet::ExternalSubnetSelector {
-                external_subnet: path.external_subnet,
-                project: query.project,
-            };
-            let subnet =
-                nexus.external_subnet_attach(&opctx, selector, attach).await?;
-            Ok(HttpResponseAccepted(subnet))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            attach_params.into_inner(),
+            &[],
+            |opctx, nexus, attach| async move {
+                let path = path_params.into_inner();
+                let query = query_params.into_inner();
+                let selector = external_subnet::ExternalSubnetSelector {
+                    external_subnet: path.external_subnet,
+                    project: query.project,
+                };
+                let subnet = nexus
+                    .external_subnet_attach(&opctx, selector, attach)
+                    .await?;
+                Ok(HttpResponseAccepted(subnet))
+            },
+        )
         .await
     }
 
@@ -2286,16 +2482,24 @@ impl NexusExternalApi for NexusExternalApiImpl {
         query_params: Query<project::ProjectSelector>,
         floating_params: TypedBody<floating_ip::FloatingIpCreate>,
     ) -> Result<HttpResponseCreated<FloatingIp>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let project_selector = query_params.into_inner();
-            let floating_params = floating_params.into_inner();
-            let project_lookup =
-                nexus.project_lookup(&opctx, project_selector)?;
-            let ip = nexus
-                .floating_ip_create(&opctx, &project_lookup, floating_params)
-                .await?;
-            Ok(HttpResponseCreated(ip))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            floating_params.into_inner(),
+            &[],
+            |opctx, nexus, floating_params| async move {
+                let project_selector = query_params.into_inner();
+                let project_lookup =
+                    nexus.project_lookup(&opctx, project_selector)?;
+                let ip = nexus
+                    .floating_ip_create(
+                        &opctx,
+                        &project_lookup,
+                        floating_params,
+                    )
+                    .await?;
+                Ok(HttpResponseCreated(ip))
+            },
+        )
         .await
     }
 
@@ -2305,25 +2509,29 @@ impl NexusExternalApi for NexusExternalApiImpl {
         query_params: Query<project::OptionalProjectSelector>,
         updated_floating_ip: TypedBody<floating_ip::FloatingIpUpdate>,
     ) -> Result<HttpResponseOk<FloatingIp>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let path = path_params.into_inner();
-            let query = query_params.into_inner();
-            let updated_floating_ip_params = updated_floating_ip.into_inner();
-            let floating_ip_selector = floating_ip::FloatingIpSelector {
-                project: query.project,
-                floating_ip: path.floating_ip,
-            };
-            let floating_ip_lookup =
-                nexus.floating_ip_lookup(&opctx, floating_ip_selector)?;
-            let floating_ip = nexus
-                .floating_ip_update(
-                    &opctx,
-                    floating_ip_lookup,
-                    updated_floating_ip_params,
-                )
-                .await?;
-            Ok(HttpResponseOk(floating_ip))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            updated_floating_ip.into_inner(),
+            &[],
+            |opctx, nexus, updated_floating_ip_params| async move {
+                let path = path_params.into_inner();
+                let query = query_params.into_inner();
+                let floating_ip_selector = floating_ip::FloatingIpSelector {
+                    project: query.project,
+                    floating_ip: path.floating_ip,
+                };
+                let floating_ip_lookup =
+                    nexus.floating_ip_lookup(&opctx, floating_ip_selector)?;
+                let floating_ip = nexus
+                    .floating_ip_update(
+                        &opctx,
+                        floating_ip_lookup,
+                        updated_floating_ip_params,
+                    )
+                    .await?;
+                Ok(HttpResponseOk(floating_ip))
+            },
+        )
         .await
     }
 
@@ -2382,19 +2590,23 @@ impl NexusExternalApi for NexusExternalApiImpl {
         query_params: Query<project::OptionalProjectSelector>,
         target: TypedBody<floating_ip::FloatingIpAttach>,
     ) -> Result<HttpResponseAccepted<FloatingIp>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let path = path_params.into_inner();
-            let query = query_params.into_inner();
-            let target = target.into_inner();
-            let floating_ip_selector = floating_ip::FloatingIpSelector {
-                floating_ip: path.floating_ip,
-                project: query.project,
-            };
-            let ip = nexus
-                .floating_ip_attach(&opctx, floating_ip_selector, target)
-                .await?;
-            Ok(HttpResponseAccepted(ip))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            target.into_inner(),
+            &[],
+            |opctx, nexus, target| async move {
+                let path = path_params.into_inner();
+                let query = query_params.into_inner();
+                let floating_ip_selector = floating_ip::FloatingIpSelector {
+                    floating_ip: path.floating_ip,
+                    project: query.project,
+                };
+                let ip = nexus
+                    .floating_ip_attach(&opctx, floating_ip_selector, target)
+                    .await?;
+                Ok(HttpResponseAccepted(ip))
+            },
+        )
         .await
     }
 
@@ -2418,6 +2630,207 @@ impl NexusExternalApi for NexusExternalApiImpl {
         .await
     }
 
+    // Load Balancers
+
+    async fn load_balancer_list(
+        rqctx: RequestContext<ApiContext>,
+        query_params: Query<PaginatedByNameOrId<project::ProjectSelector>>,
+    ) -> Result<
+        HttpResponseOk<ResultsPage<load_balancer::LoadBalancer>>,
+        HttpError,
+    > {
+        let apictx = rqctx.context();
+        let handler = async {
+            let nexus = &apictx.context.nexus;
+            let opctx =
+                crate::context::op_context_for_external_api(&rqctx).await?;
+            let query = query_params.into_inner();
+            let pag_params = data_page_params_for(&rqctx, &query)?;
+            let scan_params = ScanByNameOrId::from_query(&query)?;
+            let paginated_by = name_or_id_pagination(&pag_params, scan_params)?;
+            let project_lookup =
+                nexus.project_lookup(&opctx, scan_params.selector.clone())?;
+            let load_balancers = nexus
+                .load_balancers_list(&opctx, &project_lookup, &paginated_by)
+                .await?;
+            Ok(HttpResponseOk(ScanByNameOrId::results_page(
+                &query,
+                load_balancers,
+                &marker_for_name_or_id,
+            )?))
+        };
+        apictx
+            .context
+            .external_latencies
+            .instrument_dropshot_handler(&rqctx, handler)
+            .await
+    }
+
+    async fn load_balancer_create(
+        rqctx: RequestContext<ApiContext>,
+        query_params: Query<project::ProjectSelector>,
+        new_load_balancer: TypedBody<load_balancer::LoadBalancerCreate>,
+    ) -> Result<HttpResponseCreated<load_balancer::LoadBalancer>, HttpError>
+    {
+        audit_and_time_with_body(
+            &rqctx,
+            new_load_balancer.into_inner(),
+            &[],
+            |opctx, nexus, new_load_balancer| async move {
+                let project_selector = query_params.into_inner();
+                let project_lookup =
+                    nexus.project_lookup(&opctx, project_selector)?;
+                let load_balancer = nexus
+                    .load_balancer_create(
+                        &opctx,
+                        &project_lookup,
+                        new_load_balancer,
+                    )
+                    .await?;
+                Ok(HttpResponseCreated(load_balancer))
+            },
+        )
+        .await
+    }
+
+    async fn load_balancer_view(
+        rqctx: RequestContext<ApiContext>,
+        path_params: Path<load_balancer::LoadBalancerPath>,
+        query_params: Query<project::OptionalProjectSelector>,
+    ) -> Result<HttpResponseOk<load_balancer::LoadBalancer>, HttpError> {
+        let apictx = rqctx.context();
+        let handler = async {
+            let opctx =
+                crate::context::op_context_for_external_api(&rqctx).await?;
+            let nexus = &apictx.context.nexus;
+            let path = path_params.into_inner();
+            let query = query_params.into_inner();
+            let selector = load_balancer::LoadBalancerSelector {
+                load_balancer: path.load_balancer,
+                project: query.project,
+            };
+            let lb_lookup = nexus.load_balancer_lookup(&opctx, selector)?;
+            let load_balancer =
+                nexus.load_balancer_view(&opctx, &lb_lookup).await?;
+            Ok(HttpResponseOk(load_balancer))
+        };
+        apictx
+            .context
+            .external_latencies
+            .instrument_dropshot_handler(&rqctx, handler)
+            .await
+    }
+
+    async fn load_balancer_delete(
+        rqctx: RequestContext<ApiContext>,
+        path_params: Path<load_balancer::LoadBalancerPath>,
+        query_params: Query<project::OptionalProjectSelector>,
+    ) -> Result<HttpResponseDeleted, HttpError> {
+        audit_and_time(&rqctx, |opctx, nexus| async move {
+            let path = path_params.into_inner();
+            let query = query_params.into_inner();
+            let selector = load_balancer::LoadBalancerSelector {
+                load_balancer: path.load_balancer,
+                project: query.project,
+            };
+            let lb_lookup = nexus.load_balancer_lookup(&opctx, selector)?;
+            nexus.load_balancer_delete(&opctx, &lb_lookup).await?;
+            Ok(HttpResponseDeleted())
+        })
+        .await
+    }
+
+    async fn load_balancer_backend_list(
+        rqctx: RequestContext<ApiContext>,
+        path_params: Path<load_balancer::LoadBalancerPath>,
+        query_params: Query<PaginatedById<project::OptionalProjectSelector>>,
+    ) -> Result<
+        HttpResponseOk<ResultsPage<load_balancer::LoadBalancerBackend>>,
+        HttpError,
+    > {
+        let apictx = rqctx.context();
+        let handler = async {
+            let opctx =
+                crate::context::op_context_for_external_api(&rqctx).await?;
+            let nexus = &apictx.context.nexus;
+            let path = path_params.into_inner();
+            let query = query_params.into_inner();
+            let pag_params = data_page_params_for(&rqctx, &query)?;
+            let scan_params = ScanById::from_query(&query)?;
+            let selector = load_balancer::LoadBalancerSelector {
+                load_balancer: path.load_balancer,
+                project: scan_params.selector.project.clone(),
+            };
+            let lb_lookup = nexus.load_balancer_lookup(&opctx, selector)?;
+            let backends = nexus
+                .load_balancer_backends_list(&opctx, &lb_lookup, &pag_params)
+                .await?;
+            Ok(HttpResponseOk(ScanById::results_page(
+                &query,
+                backends,
+                &|_, backend: &load_balancer::LoadBalancerBackend| {
+                    backend.instance_id
+                },
+            )?))
+        };
+        apictx
+            .context
+            .external_latencies
+            .instrument_dropshot_handler(&rqctx, handler)
+            .await
+    }
+
+    async fn load_balancer_backend_add(
+        rqctx: RequestContext<ApiContext>,
+        path_params: Path<load_balancer::LoadBalancerPath>,
+        query_params: Query<project::OptionalProjectSelector>,
+        backend: TypedBody<load_balancer::LoadBalancerBackendCreate>,
+    ) -> Result<
+        HttpResponseCreated<load_balancer::LoadBalancerBackend>,
+        HttpError,
+    > {
+        audit_and_time_with_body(
+            &rqctx,
+            backend.into_inner(),
+            &[],
+            |opctx, nexus, backend| async move {
+                let path = path_params.into_inner();
+                let query = query_params.into_inner();
+                let selector = load_balancer::LoadBalancerSelector {
+                    load_balancer: path.load_balancer,
+                    project: query.project,
+                };
+                let lb_lookup = nexus.load_balancer_lookup(&opctx, selector)?;
+                let backend = nexus
+                    .load_balancer_backend_add(&opctx, &lb_lookup, backend)
+                    .await?;
+                Ok(HttpResponseCreated(backend))
+            },
+        )
+        .await
+    }
+
+    async fn load_balancer_backend_remove(
+        rqctx: RequestContext<ApiContext>,
+        path_params: Path<load_balancer::LoadBalancerBackendPath>,
+        query_params: Query<project::OptionalProjectSelector>,
+    ) -> Result<HttpResponseDeleted, HttpError> {
+        audit_and_time(&rqctx, |opctx, nexus| async move {
+            let path = path_params.into_inner();
+            let query = query_params.into_inner();
+            let selector = load_balancer::LoadBalancerSelector {
+                load_balancer: path.load_balancer,
+                project: query.project,
+            };
+            let lb_lookup = nexus.load_balancer_lookup(&opctx, selector)?;
+            nexus
+                .load_balancer_backend_remove(&opctx, &lb_lookup, path.instance)
+                .await?;
+            Ok(HttpResponseDeleted())
+        })
+        .await
+    }
+
     // Multicast Groups
 
     async fn multicast_group_list(
@@ -2570,15 +2983,19 @@ impl NexusExternalApi for NexusExternalApiImpl {
         query_params: Query<project::ProjectSelector>,
         new_disk: TypedBody<disk::DiskCreate>,
     ) -> Result<HttpResponseCreated<Disk>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let query = query_params.into_inner();
-            let params = new_disk.into_inner();
-            let project_lookup = nexus.project_lookup(&opctx, query)?;
-            let disk = nexus
-                .project_create_disk(&opctx, &project_lookup, &params)
-                .await?;
-            Ok(HttpResponseCreated(disk.into()))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            new_disk.into_inner(),
+            &[],
+            |opctx, nexus, params| async move {
+                let query = query_params.into_inner();
+                let project_lookup = nexus.project_lookup(&opctx, query)?;
+                let disk = nexus
+                    .project_create_disk(&opctx, &project_lookup, &params)
+                    .await?;
+                Ok(HttpResponseCreated(disk.into()))
+            },
+        )
         .await
     }
 
@@ -2698,18 +3115,28 @@ impl NexusExternalApi for NexusExternalApiImpl {
         query_params: Query<project::OptionalProjectSelector>,
         finalize_params: TypedBody<disk::FinalizeDisk>,
     ) -> Result<HttpResponseUpdatedNoContent, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let path = path_params.into_inner();
-            let query = query_params.into_inner();
-            let finalize_params = finalize_params.into_inner();
-            let disk_selector =
-                disk::DiskSelector { disk: path.disk, project: query.project };
-            let disk_lookup = nexus.disk_lookup(&opctx, disk_selector)?;
-            nexus
-                .disk_finalize_import(&opctx, &disk_lookup, &finalize_params)
-                .await?;
-            Ok(HttpResponseUpdatedNoContent())
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            finalize_params.into_inner(),
+            &[],
+            |opctx, nexus, finalize_params| async move {
+                let path = path_params.into_inner();
+                let query = query_params.into_inner();
+                let disk_selector = disk::DiskSelector {
+                    disk: path.disk,
+                    project: query.project,
+                };
+                let disk_lookup = nexus.disk_lookup(&opctx, disk_selector)?;
+                nexus
+                    .disk_finalize_import(
+                        &opctx,
+                        &disk_lookup,
+                        &finalize_params,
+                    )
+                    .await?;
+                Ok(HttpResponseUpdatedNoContent())
+            },
+        )
         .await
     }
 
@@ -2755,20 +3182,24 @@ impl NexusExternalApi for NexusExternalApiImpl {
         query_params: Query<project::ProjectSelector>,
         new_instance: TypedBody<instance::InstanceCreate>,
     ) -> Result<HttpResponseCreated<instance::Instance>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let project_selector = query_params.into_inner();
-            let new_instance_params = new_instance.into_inner();
-            let project_lookup =
-                nexus.project_lookup(&opctx, project_selector)?;
-            let instance = nexus
-                .project_create_instance(
-                    &opctx,
-                    &project_lookup,
-                    &new_instance_params,
-                )
-                .await?;
-            Ok(HttpResponseCreated(instance.into()))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            new_instance.into_inner(),
+            &["/user_data"],
+            |opctx, nexus, new_instance_params| async move {
+                let project_selector = query_params.into_inner();
+                let project_lookup =
+                    nexus.project_lookup(&opctx, project_selector)?;
+                let instance = nexus
+                    .project_create_instance(
+                        &opctx,
+                        &project_lookup,
+                        &new_instance_params,
+                    )
+                    .await?;
+                Ok(HttpResponseCreated(instance.into()))
+            },
+        )
         .await
     }
 
@@ -2833,23 +3264,27 @@ impl NexusExternalApi for NexusExternalApiImpl {
     ) -> Result<HttpResponseOk<instance::Instance>, HttpError> {
         let query = query_params.into_inner();
         let path = path_params.into_inner();
-        let instance_config = instance_config.into_inner();
         let instance_selector = instance::InstanceSelector {
             project: query.project,
             instance: path.instance,
         };
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let instance_lookup =
-                nexus.instance_lookup(&opctx, instance_selector)?;
-            let instance = nexus
-                .instance_reconfigure(
-                    &opctx,
-                    &instance_lookup,
-                    &instance_config,
-                )
-                .await?;
-            Ok(HttpResponseOk(instance.into()))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            instance_config.into_inner(),
+            &[],
+            |opctx, nexus, instance_config| async move {
+                let instance_lookup =
+                    nexus.instance_lookup(&opctx, instance_selector)?;
+                let instance = nexus
+                    .instance_reconfigure(
+                        &opctx,
+                        &instance_lookup,
+                        &instance_config,
+                    )
+                    .await?;
+                Ok(HttpResponseOk(instance.into()))
+            },
+        )
         .await
     }
 
@@ -2874,6 +3309,38 @@ impl NexusExternalApi for NexusExternalApiImpl {
         .await
     }
 
+    async fn instance_migrate(
+        rqctx: RequestContext<ApiContext>,
+        query_params: Query<project::OptionalProjectSelector>,
+        path_params: Path<path_params::InstancePath>,
+        migrate_params: TypedBody<instance::InstanceMigrate>,
+    ) -> Result<HttpResponseAccepted<instance::Instance>, HttpError> {
+        let path = path_params.into_inner();
+        let query = query_params.into_inner();
+        let instance_selector = instance::InstanceSelector {
+            project: query.project,
+            instance: path.instance,
+        };
+        audit_and_time_with_body(
+            &rqctx,
+            migrate_params.into_inner(),
+            &[],
+            |opctx, nexus, migrate| async move {
+                let instance_lookup =
+                    nexus.instance_lookup(&opctx, instance_selector)?;
+                let instance = nexus
+                    .instance_migrate(
+                        &opctx,
+                        &instance_lookup,
+                        migrate.dst_sled_id,
+                    )
+                    .await?;
+                Ok(HttpResponseAccepted(instance.into()))
+            },
+        )
+        .await
+    }
+
     async fn instance_start(
         rqctx: RequestContext<ApiContext>,
         query_params: Query<project::OptionalProjectSelector>,
@@ -3040,6 +3507,68 @@ impl NexusExternalApi for NexusExternalApiImpl {
             .await
     }
 
+    async fn instance_network_tags_view(
+        rqctx: RequestContext<ApiContext>,
+        query_params: Query<project::OptionalProjectSelector>,
+        path_params: Path<path_params::InstancePath>,
+    ) -> Result<HttpResponseOk<instance::InstanceNetworkTags>, HttpError> {
+        let apictx = rqctx.context();
+        let handler = async {
+            let nexus = &apictx.context.nexus;
+            let path = path_params.into_inner();
+            let query = query_params.into_inner();
+            let opctx =
+                crate::context::op_context_for_external_api(&rqctx).await?;
+            let instance_selector = instance::InstanceSelector {
+                project: query.project,
+                instance: path.instance,
+            };
+            let instance_lookup =
+                nexus.instance_lookup(&opctx, instance_selector)?;
+            let tags = nexus
+                .instance_network_tags_list(&opctx, &instance_lookup)
+                .await?;
+            Ok(HttpResponseOk(instance::InstanceNetworkTags { tags }))
+        };
+        apictx
+            .context
+            .external_latencies
+            .instrument_dropshot_handler(&rqctx, handler)
+            .await
+    }
+
+    async fn instance_network_tags_update(
+        rqctx: RequestContext<ApiContext>,
+        query_params: Query<project::OptionalProjectSelector>,
+        path_params: Path<path_params::InstancePath>,
+        tags: TypedBody<instance::InstanceNetworkTagsUpdate>,
+    ) -> Result<HttpResponseOk<instance::InstanceNetworkTags>, HttpError> {
+        let path = path_params.into_inner();
+        let query = query_params.into_inner();
+        let instance_selector = instance::InstanceSelector {
+            project: query.project,
+            instance: path.instance,
+        };
+        audit_and_time_with_body(
+            &rqctx,
+            tags.into_inner(),
+            &[],
+            |opctx, nexus, update| async move {
+                let instance_lookup =
+                    nexus.instance_lookup(&opctx, instance_selector)?;
+                let tags = nexus
+                    .instance_network_tags_update(
+                        &opctx,
+                        &instance_lookup,
+                        update.tags,
+                    )
+                    .await?;
+                Ok(HttpResponseOk(instance::InstanceNetworkTags { tags }))
+            },
+        )
+        .await
+    }
+
     async fn instance_disk_list(
         rqctx: RequestContext<ApiContext>,
         query_params: Query<
@@ -3088,21 +3617,26 @@ impl NexusExternalApi for NexusExternalApiImpl {
         query_params: Query<project::OptionalProjectSelector>,
         disk_to_attach: TypedBody<path_params::DiskPath>,
     ) -> Result<HttpResponseAccepted<Disk>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let path = path_params.into_inner();
-            let query = query_params.into_inner();
-            let disk = disk_to_attach.into_inner().disk;
-            let instance_selector = instance::InstanceSelector {
-                project: query.project,
-                instance: path.instance,
-            };
-            let instance_lookup =
-                nexus.instance_lookup(&opctx, instance_selector)?;
-            let disk = nexus
-                .instance_attach_disk(&opctx, &instance_lookup, disk)
-                .await?;
-            Ok(HttpResponseAccepted(disk.into()))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            disk_to_attach.into_inner(),
+            &[],
+            |opctx, nexus, disk_to_attach| async move {
+                let path = path_params.into_inner();
+                let query = query_params.into_inner();
+                let disk = disk_to_attach.disk;
+                let instance_selector = instance::InstanceSelector {
+                    project: query.project,
+                    instance: path.instance,
+                };
+                let instance_lookup =
+                    nexus.instance_lookup(&opctx, instance_selector)?;
+                let disk = nexus
+                    .instance_attach_disk(&opctx, &instance_lookup, disk)
+                    .await?;
+                Ok(HttpResponseAccepted(disk.into()))
+            },
+        )
         .await
     }
 
@@ -3112,21 +3646,26 @@ impl NexusExternalApi for NexusExternalApiImpl {
         query_params: Query<project::OptionalProjectSelector>,
         disk_to_detach: TypedBody<path_params::DiskPath>,
     ) -> Result<HttpResponseAccepted<Disk>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let path = path_params.into_inner();
-            let query = query_params.into_inner();
-            let disk = disk_to_detach.into_inner().disk;
-            let instance_selector = instance::InstanceSelector {
-                project: query.project,
-                instance: path.instance,
-            };
-            let instance_lookup =
-                nexus.instance_lookup(&opctx, instance_selector)?;
-            let disk = nexus
-                .instance_detach_disk(&opctx, &instance_lookup, disk)
-                .await?;
-            Ok(HttpResponseAccepted(disk.into()))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            disk_to_detach.into_inner(),
+            &[],
+            |opctx, nexus, disk_to_detach| async move {
+                let path = path_params.into_inner();
+                let query = query_params.into_inner();
+                let disk = disk_to_detach.disk;
+                let instance_selector = instance::InstanceSelector {
+                    project: query.project,
+                    instance: path.instance,
+                };
+                let instance_lookup =
+                    nexus.instance_lookup(&opctx, instance_selector)?;
+                let disk = nexus
+                    .instance_detach_disk(&opctx, &instance_lookup, disk)
+                    .await?;
+                Ok(HttpResponseAccepted(disk.into()))
+            },
+        )
         .await
     }
 
@@ -3455,19 +3994,23 @@ impl NexusExternalApi for NexusExternalApiImpl {
         query_params: Query<project::ProjectSelector>,
         new_affinity_group_params: TypedBody<affinity::AffinityGroupCreate>,
     ) -> Result<HttpResponseCreated<affinity::AffinityGroup>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let query = query_params.into_inner();
-            let new_affinity_group = new_affinity_group_params.into_inner();
-            let project_lookup = nexus.project_lookup(&opctx, query)?;
-            let affinity_group = nexus
-                .affinity_group_create(
-                    &opctx,
-                    &project_lookup,
-                    new_affinity_group,
-                )
-                .await?;
-            Ok(HttpResponseCreated(affinity_group))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            new_affinity_group_params.into_inner(),
+            &[],
+            |opctx, nexus, new_affinity_group| async move {
+                let query = query_params.into_inner();
+                let project_lookup = nexus.project_lookup(&opctx, query)?;
+                let affinity_group = nexus
+                    .affinity_group_create(
+                        &opctx,
+                        &project_lookup,
+                        new_affinity_group,
+                    )
+                    .await?;
+                Ok(HttpResponseCreated(affinity_group))
+            },
+        )
         .await
     }
 
@@ -3477,21 +4020,25 @@ impl NexusExternalApi for NexusExternalApiImpl {
         path_params: Path<path_params::AffinityGroupPath>,
         updated_group: TypedBody<affinity::AffinityGroupUpdate>,
     ) -> Result<HttpResponseOk<affinity::AffinityGroup>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let path = path_params.into_inner();
-            let query = query_params.into_inner();
-            let updates = updated_group.into_inner();
-            let group_selector = affinity::AffinityGroupSelector {
-                project: query.project,
-                affinity_group: path.affinity_group,
-            };
-            let group_lookup =
-                nexus.affinity_group_lookup(&opctx, group_selector)?;
-            let affinity_group = nexus
-                .affinity_group_update(&opctx, &group_lookup, &updates)
-                .await?;
-            Ok(HttpResponseOk(affinity_group))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            updated_group.into_inner(),
+            &[],
+            |opctx, nexus, updates| async move {
+                let path = path_params.into_inner();
+                let query = query_params.into_inner();
+                let group_selector = affinity::AffinityGroupSelector {
+                    project: query.project,
+                    affinity_group: path.affinity_group,
+                };
+                let group_lookup =
+                    nexus.affinity_group_lookup(&opctx, group_selector)?;
+                let affinity_group = nexus
+                    .affinity_group_update(&opctx, &group_lookup, &updates)
+                    .await?;
+                Ok(HttpResponseOk(affinity_group))
+            },
+        )
         .await
     }
 
@@ -3775,21 +4322,25 @@ impl NexusExternalApi for NexusExternalApiImpl {
         path_params: Path<path_params::AntiAffinityGroupPath>,
         updated_group: TypedBody<affinity::AntiAffinityGroupUpdate>,
     ) -> Result<HttpResponseOk<affinity::AntiAffinityGroup>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let path = path_params.into_inner();
-            let query = query_params.into_inner();
-            let updates = updated_group.into_inner();
-            let group_selector = affinity::AntiAffinityGroupSelector {
-                project: query.project,
-                anti_affinity_group: path.anti_affinity_group,
-            };
-            let group_lookup =
-                nexus.anti_affinity_group_lookup(&opctx, group_selector)?;
-            let anti_affinity_group = nexus
-                .anti_affinity_group_update(&opctx, &group_lookup, &updates)
-                .await?;
-            Ok(HttpResponseOk(anti_affinity_group))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            updated_group.into_inner(),
+            &[],
+            |opctx, nexus, updates| async move {
+                let path = path_params.into_inner();
+                let query = query_params.into_inner();
+                let group_selector = affinity::AntiAffinityGroupSelector {
+                    project: query.project,
+                    anti_affinity_group: path.anti_affinity_group,
+                };
+                let group_lookup =
+                    nexus.anti_affinity_group_lookup(&opctx, group_selector)?;
+                let anti_affinity_group = nexus
+                    .anti_affinity_group_update(&opctx, &group_lookup, &updates)
+                    .await?;
+                Ok(HttpResponseOk(anti_affinity_group))
+            },
+        )
         .await
     }
 
@@ -3851,12 +4402,16 @@ impl NexusExternalApi for NexusExternalApiImpl {
         rqctx: RequestContext<ApiContext>,
         new_cert: TypedBody<certificate::CertificateCreate>,
     ) -> Result<HttpResponseCreated<Certificate>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let new_cert_params = new_cert.into_inner();
-            let cert =
-                nexus.certificate_create(&opctx, new_cert_params).await?;
-            Ok(HttpResponseCreated(cert.try_into()?))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            new_cert.into_inner(),
+            &["/key"],
+            |opctx, nexus, new_cert_params| async move {
+                let cert =
+                    nexus.certificate_create(&opctx, new_cert_params).await?;
+                Ok(HttpResponseCreated(cert.try_into()?))
+            },
+        )
         .await
     }
 
@@ -3904,14 +4459,21 @@ impl NexusExternalApi for NexusExternalApiImpl {
         rqctx: RequestContext<ApiContext>,
         new_address_lot: TypedBody<networking::AddressLotCreate>,
     ) -> Result<HttpResponseCreated<AddressLotCreateResponse>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let params = new_address_lot.into_inner();
-            let result = nexus.address_lot_create(&opctx, params).await?;
-            let lot: AddressLot = result.lot.into();
-            let blocks: Vec<AddressLotBlock> =
-                result.blocks.iter().map(|b| b.clone().into()).collect();
-            Ok(HttpResponseCreated(AddressLotCreateResponse { lot, blocks }))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            new_address_lot.into_inner(),
+            &[],
+            |opctx, nexus, params| async move {
+                let result = nexus.address_lot_create(&opctx, params).await?;
+                let lot: AddressLot = result.lot.into();
+                let blocks: Vec<AddressLotBlock> =
+                    result.blocks.iter().map(|b| b.clone().into()).collect();
+                Ok(HttpResponseCreated(AddressLotCreateResponse {
+                    lot,
+                    blocks,
+                }))
+            },
+        )
         .await
     }
 
@@ -4036,12 +4598,17 @@ impl NexusExternalApi for NexusExternalApiImpl {
         new_loopback_address: TypedBody<networking::LoopbackAddressCreate>,
     ) -> Result<HttpResponseCreated<networking::LoopbackAddress>, HttpError>
     {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let params = new_loopback_address.into_inner();
-            let result = nexus.loopback_address_create(&opctx, params).await?;
-            let addr: networking::LoopbackAddress = result.into();
-            Ok(HttpResponseCreated(addr))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            new_loopback_address.into_inner(),
+            &[],
+            |opctx, nexus, params| async move {
+                let result =
+                    nexus.loopback_address_create(&opctx, params).await?;
+                let addr: networking::LoopbackAddress = result.into();
+                Ok(HttpResponseCreated(addr))
+            },
+        )
         .await
     }
 
@@ -4110,13 +4677,18 @@ impl NexusExternalApi for NexusExternalApiImpl {
         new_settings: TypedBody<networking::SwitchPortSettingsCreate>,
     ) -> Result<HttpResponseCreated<networking::SwitchPortSettings>, HttpError>
     {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let params = new_settings.into_inner();
-            let result =
-                nexus.switch_port_settings_post(&opctx, params).await?;
-            let settings: networking::SwitchPortSettings = result.try_into()?;
-            Ok(HttpResponseCreated(settings))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            new_settings.into_inner(),
+            &["/bgp_peers/*/peers/*/md5_auth_key"],
+            |opctx, nexus, params| async move {
+                let result =
+                    nexus.switch_port_settings_post(&opctx, params).await?;
+                let settings: networking::SwitchPortSettings =
+                    result.try_into()?;
+                Ok(HttpResponseCreated(settings))
+            },
+        )
         .await
     }
 
@@ -4255,14 +4827,20 @@ impl NexusExternalApi for NexusExternalApiImpl {
         settings_body: TypedBody<networking::SwitchPortApplySettings>,
     ) -> Result<HttpResponseUpdatedNoContent, HttpError> {
         let port = path_params.into_inner().port;
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let query = query_params.into_inner();
-            let settings = settings_body.into_inner();
-            nexus
-                .switch_port_apply_settings(&opctx, &port, &query, &settings)
-                .await?;
-            Ok(HttpResponseUpdatedNoContent {})
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            settings_body.into_inner(),
+            &[],
+            |opctx, nexus, settings| async move {
+                let query = query_params.into_inner();
+                nexus
+                    .switch_port_apply_settings(
+                        &opctx, &port, &query, &settings,
+                    )
+                    .await?;
+                Ok(HttpResponseUpdatedNoContent {})
+            },
+        )
         .await
     }
 
@@ -4315,21 +4893,25 @@ impl NexusExternalApi for NexusExternalApiImpl {
         query_params: Query<networking::SwitchPortSelector>,
         config: TypedBody<networking::LldpLinkConfig>,
     ) -> Result<HttpResponseUpdatedNoContent, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let query = query_params.into_inner();
-            let path = path_params.into_inner();
-            let config = config.into_inner();
-            nexus
-                .lldp_config_update(
-                    &opctx,
-                    query.rack_id,
-                    query.switch_slot,
-                    path.port,
-                    config,
-                )
-                .await?;
-            Ok(HttpResponseUpdatedNoContent {})
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            config.into_inner(),
+            &[],
+            |opctx, nexus, config| async move {
+                let query = query_params.into_inner();
+                let path = path_params.into_inner();
+                nexus
+                    .lldp_config_update(
+                        &opctx,
+                        query.rack_id,
+                        query.switch_slot,
+                        path.port,
+                        config,
+                    )
+                    .await?;
+                Ok(HttpResponseUpdatedNoContent {})
+            },
+        )
         .await
     }
 
@@ -4377,11 +4959,17 @@ impl NexusExternalApi for NexusExternalApiImpl {
         rqctx: RequestContext<ApiContext>,
         config: TypedBody<networking::BgpConfigCreate>,
     ) -> Result<HttpResponseCreated<networking::BgpConfig>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let config = config.into_inner();
-            let result = nexus.bgp_config_create(&opctx, &config).await?;
-            Ok(HttpResponseCreated::<networking::BgpConfig>(result.try_into()?))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            config.into_inner(),
+            &[],
+            |opctx, nexus, config| async move {
+                let result = nexus.bgp_config_create(&opctx, &config).await?;
+                Ok(HttpResponseCreated::<networking::BgpConfig>(
+                    result.try_into()?,
+                ))
+            },
+        )
         .await
     }
 
@@ -4536,11 +5124,18 @@ impl NexusExternalApi for NexusExternalApiImpl {
         rqctx: RequestContext<ApiContext>,
         config: TypedBody<networking::BgpAnnounceSetCreate>,
     ) -> Result<HttpResponseOk<networking::BgpAnnounceSet>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let config = config.into_inner();
-            let result = nexus.bgp_update_announce_set(&opctx, &config).await?;
-            Ok(HttpResponseOk::<networking::BgpAnnounceSet>(result.0.into()))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            config.into_inner(),
+            &[],
+            |opctx, nexus, config| async move {
+                let result =
+                    nexus.bgp_update_announce_set(&opctx, &config).await?;
+                Ok(HttpResponseOk::<networking::BgpAnnounceSet>(
+                    result.0.into(),
+                ))
+            },
+        )
         .await
     }
 
@@ -4617,12 +5212,18 @@ impl NexusExternalApi for NexusExternalApiImpl {
         rqctx: RequestContext<ApiContext>,
         session: TypedBody<networking::BfdSessionEnable>,
     ) -> Result<HttpResponseUpdatedNoContent, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let session = session.into_inner();
-            opctx.authorize(authz::Action::ListChildren, &authz::FLEET).await?;
-            nexus.bfd_enable(&opctx, session).await?;
-            Ok(HttpResponseUpdatedNoContent {})
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            session.into_inner(),
+            &[],
+            |opctx, nexus, session| async move {
+                opctx
+                    .authorize(authz::Action::ListChildren, &authz::FLEET)
+                    .await?;
+                nexus.bfd_enable(&opctx, session).await?;
+                Ok(HttpResponseUpdatedNoContent {})
+            },
+        )
         .await
     }
 
@@ -4630,12 +5231,18 @@ impl NexusExternalApi for NexusExternalApiImpl {
         rqctx: RequestContext<ApiContext>,
         session: TypedBody<networking::BfdSessionDisable>,
     ) -> Result<HttpResponseUpdatedNoContent, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let session = session.into_inner();
-            opctx.authorize(authz::Action::ListChildren, &authz::FLEET).await?;
-            nexus.bfd_disable(&opctx, session).await?;
-            Ok(HttpResponseUpdatedNoContent {})
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            session.into_inner(),
+            &[],
+            |opctx, nexus, session| async move {
+                opctx
+                    .authorize(authz::Action::ListChildren, &authz::FLEET)
+                    .await?;
+                nexus.bfd_disable(&opctx, session).await?;
+                Ok(HttpResponseUpdatedNoContent {})
+            },
+        )
         .await
     }
 
@@ -4685,14 +5292,18 @@ impl NexusExternalApi for NexusExternalApiImpl {
     ) -> Result<HttpResponseOk<system::AllowList>, HttpError> {
         let server_kind = rqctx.context().kind;
         let remote_addr = rqctx.request.remote_addr().ip();
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let params = params.into_inner();
-            nexus
-                .allow_list_upsert(&opctx, remote_addr, server_kind, params)
-                .await
-                .map(HttpResponseOk)
-                .map_err(HttpError::from)
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            params.into_inner(),
+            &[],
+            |opctx, nexus, params| async move {
+                nexus
+                    .allow_list_upsert(&opctx, remote_addr, server_kind, params)
+                    .await
+                    .map(HttpResponseOk)
+                    .map_err(HttpError::from)
+            },
+        )
         .await
     }
 
@@ -4721,14 +5332,18 @@ impl NexusExternalApi for NexusExternalApiImpl {
         rqctx: RequestContext<Self::Context>,
         params: TypedBody<ServiceIcmpConfig>,
     ) -> Result<HttpResponseUpdatedNoContent, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let params = params.into_inner();
-            nexus
-                .nexus_firewall_inbound_icmp_update(&opctx, params)
-                .await
-                .map(|_| HttpResponseUpdatedNoContent())
-                .map_err(HttpError::from)
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            params.into_inner(),
+            &[],
+            |opctx, nexus, params| async move {
+                nexus
+                    .nexus_firewall_inbound_icmp_update(&opctx, params)
+                    .await
+                    .map(|_| HttpResponseUpdatedNoContent())
+                    .map_err(HttpError::from)
+            },
+        )
         .await
     }
 
@@ -4781,31 +5396,35 @@ impl NexusExternalApi for NexusExternalApiImpl {
             .await
     }
 
-    async fn image_create(
-        rqctx: RequestContext<ApiContext>,
-        query_params: Query<project::OptionalProjectSelector>,
-        new_image: TypedBody<image::ImageCreate>,
-    ) -> Result<HttpResponseCreated<Image>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let query = query_params.into_inner();
-            let params = new_image.into_inner();
-            let parent_lookup = match query.project.clone() {
-                Some(project) => {
-                    let project_lookup = nexus.project_lookup(
-                        &opctx,
-                        project::ProjectSelector { project },
-                    )?;
-                    ImageParentLookup::Project(project_lookup)
-                }
-                None => {
-                    let silo_lookup = nexus.current_silo_lookup(&opctx)?;
-                    ImageParentLookup::Silo(silo_lookup)
-                }
-            };
-            let image =
-                nexus.image_create(&opctx, &parent_lookup, &params).await?;
-            Ok(HttpResponseCreated(image.into()))
-        })
+    async fn image_create(
+        rqctx: RequestContext<ApiContext>,
+        query_params: Query<project::OptionalProjectSelector>,
+        new_image: TypedBody<image::ImageCreate>,
+    ) -> Result<HttpResponseCreated<Image>, HttpError> {
+        audit_and_time_with_body(
+            &rqctx,
+            new_image.into_inner(),
+            &[],
+            |opctx, nexus, params| async move {
+                let query = query_params.into_inner();
+                let parent_lookup = match query.project.clone() {
+                    Some(project) => {
+                        let project_lookup = nexus.project_lookup(
+                            &opctx,
+                            project::ProjectSelector { project },
+                        )?;
+                        ImageParentLookup::Project(project_lookup)
+                    }
+                    None => {
+                        let silo_lookup = nexus.current_silo_lookup(&opctx)?;
+                        ImageParentLookup::Silo(silo_lookup)
+                    }
+                };
+                let image =
+                    nexus.image_create(&opctx, &parent_lookup, &params).await?;
+                Ok(HttpResponseCreated(image.into()))
+            },
+        )
         .await
     }
 
@@ -4962,19 +5581,26 @@ impl NexusExternalApi for NexusExternalApiImpl {
         query_params: Query<instance::InstanceSelector>,
         interface_params: TypedBody<instance::InstanceNetworkInterfaceCreate>,
     ) -> Result<HttpResponseCreated<InstanceNetworkInterface>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let query = query_params.into_inner();
-            let interface_params = interface_params.into_inner();
-            let instance_lookup = nexus.instance_lookup(&opctx, query)?;
-            let iface = nexus
-                .network_interface_create(
-                    &opctx,
-                    &instance_lookup,
-                    &interface_params,
-                )
-                .await?;
-            iface.try_into().map(HttpResponseCreated).map_err(HttpError::from)
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            interface_params.into_inner(),
+            &[],
+            |opctx, nexus, interface_params| async move {
+                let query = query_params.into_inner();
+                let instance_lookup = nexus.instance_lookup(&opctx, query)?;
+                let iface = nexus
+                    .network_interface_create(
+                        &opctx,
+                        &instance_lookup,
+                        &interface_params,
+                    )
+                    .await?;
+                iface
+                    .try_into()
+                    .map(HttpResponseCreated)
+                    .map_err(HttpError::from)
+            },
+        )
         .await
     }
 
@@ -5041,30 +5667,37 @@ impl NexusExternalApi for NexusExternalApiImpl {
         query_params: Query<instance::OptionalInstanceSelector>,
         updated_iface: TypedBody<instance::InstanceNetworkInterfaceUpdate>,
     ) -> Result<HttpResponseOk<InstanceNetworkInterface>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let path = path_params.into_inner();
-            let query = query_params.into_inner();
-            let updated_iface = updated_iface.into_inner();
-            let network_interface_selector =
-                instance::InstanceNetworkInterfaceSelector {
-                    project: query.project,
-                    instance: query.instance,
-                    network_interface: path.interface,
-                };
-            let network_interface_lookup = nexus
-                .instance_network_interface_lookup(
-                    &opctx,
-                    network_interface_selector,
-                )?;
-            let interface = nexus
-                .instance_network_interface_update(
-                    &opctx,
-                    &network_interface_lookup,
-                    updated_iface,
-                )
-                .await?;
-            interface.try_into().map(HttpResponseOk).map_err(HttpError::from)
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            updated_iface.into_inner(),
+            &[],
+            |opctx, nexus, updated_iface| async move {
+                let path = path_params.into_inner();
+                let query = query_params.into_inner();
+                let network_interface_selector =
+                    instance::InstanceNetworkInterfaceSelector {
+                        project: query.project,
+                        instance: query.instance,
+                        network_interface: path.interface,
+                    };
+                let network_interface_lookup = nexus
+                    .instance_network_interface_lookup(
+                        &opctx,
+                        network_interface_selector,
+                    )?;
+                let interface = nexus
+                    .instance_network_interface_update(
+                        &opctx,
+                        &network_interface_lookup,
+                        updated_iface,
+                    )
+                    .await?;
+                interface
+                    .try_into()
+                    .map(HttpResponseOk)
+                    .map_err(HttpError::from)
+            },
+        )
         .await
     }
 
@@ -5267,32 +5900,36 @@ impl NexusExternalApi for NexusExternalApiImpl {
         body_params: TypedBody<multicast::InstanceMulticastGroupJoin>,
     ) -> Result<HttpResponseCreated<multicast::MulticastGroupMember>, HttpError>
     {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let path = path_params.into_inner();
-            let query = query_params.into_inner();
-            let body = body_params.into_inner();
-            let instance_selector = instance::InstanceSelector {
-                project: match &path.instance {
-                    NameOrId::Name(_) => query.project.clone(),
-                    NameOrId::Id(_) => None,
-                },
-                instance: path.instance.clone(),
-            };
-            let instance_lookup =
-                nexus.instance_lookup(&opctx, instance_selector)?;
-            let result = nexus
-                .instance_join_multicast_group(
-                    &opctx,
-                    &path.multicast_group,
-                    &instance_lookup,
-                    body.source_ips.as_deref(),
-                    body.ip_version,
-                )
-                .await?;
-            Ok(HttpResponseCreated(multicast::MulticastGroupMember::try_from(
-                result,
-            )?))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            body_params.into_inner(),
+            &[],
+            |opctx, nexus, body| async move {
+                let path = path_params.into_inner();
+                let query = query_params.into_inner();
+                let instance_selector = instance::InstanceSelector {
+                    project: match &path.instance {
+                        NameOrId::Name(_) => query.project.clone(),
+                        NameOrId::Id(_) => None,
+                    },
+                    instance: path.instance.clone(),
+                };
+                let instance_lookup =
+                    nexus.instance_lookup(&opctx, instance_selector)?;
+                let result = nexus
+                    .instance_join_multicast_group(
+                        &opctx,
+                        &path.multicast_group,
+                        &instance_lookup,
+                        body.source_ips.as_deref(),
+                        body.ip_version,
+                    )
+                    .await?;
+                Ok(HttpResponseCreated(
+                    multicast::MulticastGroupMember::try_from(result)?,
+                ))
+            },
+        )
         .await
     }
 
@@ -5423,15 +6060,23 @@ impl NexusExternalApi for NexusExternalApiImpl {
         query_params: Query<project::ProjectSelector>,
         new_snapshot: TypedBody<snapshot::SnapshotCreate>,
     ) -> Result<HttpResponseCreated<Snapshot>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let query = query_params.into_inner();
-            let new_snapshot_params = new_snapshot.into_inner();
-            let project_lookup = nexus.project_lookup(&opctx, query)?;
-            let snapshot = nexus
-                .snapshot_create(&opctx, project_lookup, &new_snapshot_params)
-                .await?;
-            Ok(HttpResponseCreated(snapshot.into()))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            new_snapshot.into_inner(),
+            &[],
+            |opctx, nexus, new_snapshot_params| async move {
+                let query = query_params.into_inner();
+                let project_lookup = nexus.project_lookup(&opctx, query)?;
+                let snapshot = nexus
+                    .snapshot_create(
+                        &opctx,
+                        project_lookup,
+                        &new_snapshot_params,
+                    )
+                    .await?;
+                Ok(HttpResponseCreated(snapshot.into()))
+            },
+        )
         .await
     }
 
@@ -5526,15 +6171,23 @@ impl NexusExternalApi for NexusExternalApiImpl {
         query_params: Query<project::ProjectSelector>,
         body: TypedBody<vpc::VpcCreate>,
     ) -> Result<HttpResponseCreated<Vpc>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let query = query_params.into_inner();
-            let new_vpc_params = body.into_inner();
-            let project_lookup = nexus.project_lookup(&opctx, query)?;
-            let vpc = nexus
-                .project_create_vpc(&opctx, &project_lookup, &new_vpc_params)
-                .await?;
-            Ok(HttpResponseCreated(vpc.into()))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            body.into_inner(),
+            &[],
+            |opctx, nexus, new_vpc_params| async move {
+                let query = query_params.into_inner();
+                let project_lookup = nexus.project_lookup(&opctx, query)?;
+                let vpc = nexus
+                    .project_create_vpc(
+                        &opctx,
+                        &project_lookup,
+                        &new_vpc_params,
+                    )
+                    .await?;
+                Ok(HttpResponseCreated(vpc.into()))
+            },
+        )
         .await
     }
 
@@ -5569,18 +6222,26 @@ impl NexusExternalApi for NexusExternalApiImpl {
         query_params: Query<project::OptionalProjectSelector>,
         updated_vpc: TypedBody<vpc::VpcUpdate>,
     ) -> Result<HttpResponseOk<Vpc>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let path = path_params.into_inner();
-            let query = query_params.into_inner();
-            let updated_vpc_params = updated_vpc.into_inner();
-            let vpc_selector =
-                vpc::VpcSelector { project: query.project, vpc: path.vpc };
-            let vpc_lookup = nexus.vpc_lookup(&opctx, vpc_selector)?;
-            let vpc = nexus
-                .project_update_vpc(&opctx, &vpc_lookup, &updated_vpc_params)
-                .await?;
-            Ok(HttpResponseOk(vpc.into()))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            updated_vpc.into_inner(),
+            &[],
+            |opctx, nexus, updated_vpc_params| async move {
+                let path = path_params.into_inner();
+                let query = query_params.into_inner();
+                let vpc_selector =
+                    vpc::VpcSelector { project: query.project, vpc: path.vpc };
+                let vpc_lookup = nexus.vpc_lookup(&opctx, vpc_selector)?;
+                let vpc = nexus
+                    .project_update_vpc(
+                        &opctx,
+                        &vpc_lookup,
+                        &updated_vpc_params,
+                    )
+                    .await?;
+                Ok(HttpResponseOk(vpc.into()))
+            },
+        )
         .await
     }
 
@@ -5640,14 +6301,19 @@ impl NexusExternalApi for NexusExternalApiImpl {
         query_params: Query<vpc::VpcSelector>,
         create_params: TypedBody<vpc::VpcSubnetCreate>,
     ) -> Result<HttpResponseCreated<VpcSubnet>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let query = query_params.into_inner();
-            let create = create_params.into_inner();
-            let vpc_lookup = nexus.vpc_lookup(&opctx, query)?;
-            let subnet =
-                nexus.vpc_create_subnet(&opctx, &vpc_lookup, &create).await?;
-            Ok(HttpResponseCreated(subnet.into()))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            create_params.into_inner(),
+            &[],
+            |opctx, nexus, create| async move {
+                let query = query_params.into_inner();
+                let vpc_lookup = nexus.vpc_lookup(&opctx, query)?;
+                let subnet = nexus
+                    .vpc_create_subnet(&opctx, &vpc_lookup, &create)
+                    .await?;
+                Ok(HttpResponseCreated(subnet.into()))
+            },
+        )
         .await
     }
 
@@ -5708,22 +6374,26 @@ impl NexusExternalApi for NexusExternalApiImpl {
         query_params: Query<vpc::OptionalVpcSelector>,
         subnet_params: TypedBody<vpc::VpcSubnetUpdate>,
     ) -> Result<HttpResponseOk<VpcSubnet>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let path = path_params.into_inner();
-            let query = query_params.into_inner();
-            let subnet_params = subnet_params.into_inner();
-            let subnet_selector = vpc::SubnetSelector {
-                project: query.project,
-                vpc: query.vpc,
-                subnet: path.subnet,
-            };
-            let subnet_lookup =
-                nexus.vpc_subnet_lookup(&opctx, subnet_selector)?;
-            let subnet = nexus
-                .vpc_update_subnet(&opctx, &subnet_lookup, &subnet_params)
-                .await?;
-            Ok(HttpResponseOk(subnet.into()))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            subnet_params.into_inner(),
+            &[],
+            |opctx, nexus, subnet_params| async move {
+                let path = path_params.into_inner();
+                let query = query_params.into_inner();
+                let subnet_selector = vpc::SubnetSelector {
+                    project: query.project,
+                    vpc: query.vpc,
+                    subnet: path.subnet,
+                };
+                let subnet_lookup =
+                    nexus.vpc_subnet_lookup(&opctx, subnet_selector)?;
+                let subnet = nexus
+                    .vpc_update_subnet(&opctx, &subnet_lookup, &subnet_params)
+                    .await?;
+                Ok(HttpResponseOk(subnet.into()))
+            },
+        )
         .await
     }
 
@@ -5783,9 +6453,6 @@ impl NexusExternalApi for NexusExternalApiImpl {
         rqctx: RequestContext<ApiContext>,
         query_params: Query<vpc::VpcSelector>,
     ) -> Result<HttpResponseOk<VpcFirewallRules>, HttpError> {
-        // TODO: Check If-Match and fail if the ETag doesn't match anymore.
-        // Without this check, if firewall rules change while someone is listing
-        // the rules, they will see a mix of the old and new rules.
         let apictx = rqctx.context();
         let nexus = &apictx.context.nexus;
         let query = query_params.into_inner();
@@ -5793,10 +6460,11 @@ impl NexusExternalApi for NexusExternalApiImpl {
             let opctx =
                 crate::context::op_context_for_external_api(&rqctx).await?;
             let vpc_lookup = nexus.vpc_lookup(&opctx, query)?;
-            let rules =
+            let (generation, rules) =
                 nexus.vpc_list_firewall_rules(&opctx, &vpc_lookup).await?;
             Ok(HttpResponseOk(VpcFirewallRules {
                 rules: rules.into_iter().map(|rule| rule.into()).collect(),
+                generation,
             }))
         };
         apictx
@@ -5816,20 +6484,153 @@ impl NexusExternalApi for NexusExternalApiImpl {
     ) -> Result<HttpResponseOk<VpcFirewallRules>, HttpError> {
         // TODO: Check If-Match and fail if the ETag doesn't match anymore.
         // TODO: limit size of the ruleset because the GET endpoint is not paginated
+        audit_and_time_with_body(
+            &rqctx,
+            router_params.into_inner(),
+            &[],
+            |opctx, nexus, router_params| async move {
+                let query = query_params.into_inner();
+                let vpc_lookup = nexus.vpc_lookup(&opctx, query)?;
+                let (generation, rules) = nexus
+                    .vpc_update_firewall_rules(
+                        &opctx,
+                        &vpc_lookup,
+                        &router_params,
+                    )
+                    .await?;
+                Ok(HttpResponseOk(VpcFirewallRules {
+                    rules: rules.into_iter().map(|rule| rule.into()).collect(),
+                    generation,
+                }))
+            },
+        )
+        .await
+    }
+
+    async fn vpc_firewall_rule_create(
+        rqctx: RequestContext<ApiContext>,
+        query_params: Query<vpc::VpcSelector>,
+        create: TypedBody<vpc::VpcFirewallRuleCreate>,
+    ) -> Result<HttpResponseCreated<VpcFirewallRules>, HttpError> {
+        audit_and_time_with_body(
+            &rqctx,
+            create.into_inner(),
+            &[],
+            |opctx, nexus, create| async move {
+                let query = query_params.into_inner();
+                let vpc_lookup = nexus.vpc_lookup(&opctx, query)?;
+                let (generation, rules) = nexus
+                    .vpc_firewall_rule_create(&opctx, &vpc_lookup, &create)
+                    .await?;
+                Ok(HttpResponseCreated(VpcFirewallRules {
+                    rules: rules.into_iter().map(|rule| rule.into()).collect(),
+                    generation,
+                }))
+            },
+        )
+        .await
+    }
+
+    async fn vpc_firewall_rule_update(
+        rqctx: RequestContext<ApiContext>,
+        path_params: Path<vpc::VpcFirewallRulePath>,
+        query_params: Query<vpc::VpcSelector>,
+        update: TypedBody<vpc::VpcFirewallRuleReplace>,
+    ) -> Result<HttpResponseOk<VpcFirewallRules>, HttpError> {
+        audit_and_time_with_body(
+            &rqctx,
+            update.into_inner(),
+            &[],
+            |opctx, nexus, update| async move {
+                let path = path_params.into_inner();
+                let query = query_params.into_inner();
+                let vpc_lookup = nexus.vpc_lookup(&opctx, query)?;
+                let (generation, rules) = nexus
+                    .vpc_firewall_rule_update(
+                        &opctx,
+                        &vpc_lookup,
+                        &path.rule,
+                        &update,
+                    )
+                    .await?;
+                Ok(HttpResponseOk(VpcFirewallRules {
+                    rules: rules.into_iter().map(|rule| rule.into()).collect(),
+                    generation,
+                }))
+            },
+        )
+        .await
+    }
+
+    async fn vpc_firewall_rule_delete(
+        rqctx: RequestContext<ApiContext>,
+        path_params: Path<vpc::VpcFirewallRulePath>,
+        query_params: Query<vpc::VpcFirewallRuleDeleteSelector>,
+    ) -> Result<HttpResponseDeleted, HttpError> {
         audit_and_time(&rqctx, |opctx, nexus| async move {
+            let path = path_params.into_inner();
             let query = query_params.into_inner();
-            let router_params = router_params.into_inner();
-            let vpc_lookup = nexus.vpc_lookup(&opctx, query)?;
-            let rules = nexus
-                .vpc_update_firewall_rules(&opctx, &vpc_lookup, &router_params)
+            let vpc_selector =
+                vpc::VpcSelector { project: query.project, vpc: query.vpc };
+            let vpc_lookup = nexus.vpc_lookup(&opctx, vpc_selector)?;
+            nexus
+                .vpc_firewall_rule_delete(
+                    &opctx,
+                    &vpc_lookup,
+                    &path.rule,
+                    query.generation,
+                )
                 .await?;
-            Ok(HttpResponseOk(VpcFirewallRules {
-                rules: rules.into_iter().map(|rule| rule.into()).collect(),
-            }))
+            Ok(HttpResponseDeleted())
         })
         .await
     }
 
+    // VPC DNS
+
+    async fn vpc_dns_records_view(
+        rqctx: RequestContext<ApiContext>,
+        query_params: Query<vpc::VpcSelector>,
+    ) -> Result<HttpResponseOk<vpc::VpcDnsRecords>, HttpError> {
+        let apictx = rqctx.context();
+        let nexus = &apictx.context.nexus;
+        let query = query_params.into_inner();
+        let handler = async {
+            let opctx =
+                crate::context::op_context_for_external_api(&rqctx).await?;
+            let vpc_lookup = nexus.vpc_lookup(&opctx, query)?;
+            let records =
+                nexus.vpc_dns_records_view(&opctx, &vpc_lookup).await?;
+            Ok(HttpResponseOk(records))
+        };
+        apictx
+            .context
+            .external_latencies
+            .instrument_dropshot_handler(&rqctx, handler)
+            .await
+    }
+
+    async fn vpc_dns_records_update(
+        rqctx: RequestContext<ApiContext>,
+        query_params: Query<vpc::VpcSelector>,
+        update: TypedBody<vpc::VpcDnsRecordsUpdate>,
+    ) -> Result<HttpResponseOk<vpc::VpcDnsRecords>, HttpError> {
+        audit_and_time_with_body(
+            &rqctx,
+            update.into_inner(),
+            &[],
+            |opctx, nexus, update| async move {
+                let query = query_params.into_inner();
+                let vpc_lookup = nexus.vpc_lookup(&opctx, query)?;
+                let records = nexus
+                    .vpc_dns_records_update(&opctx, &vpc_lookup, update)
+                    .await?;
+                Ok(HttpResponseOk(records))
+            },
+        )
+        .await
+    }
+
     // VPC Routers
 
     async fn vpc_router_list(
@@ -5901,20 +6702,24 @@ impl NexusExternalApi for NexusExternalApiImpl {
         query_params: Query<vpc::VpcSelector>,
         create_params: TypedBody<vpc::VpcRouterCreate>,
     ) -> Result<HttpResponseCreated<VpcRouter>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let query = query_params.into_inner();
-            let create = create_params.into_inner();
-            let vpc_lookup = nexus.vpc_lookup(&opctx, query)?;
-            let router = nexus
-                .vpc_create_router(
-                    &opctx,
-                    &vpc_lookup,
-                    &db::model::VpcRouterKind::Custom,
-                    &create,
-                )
-                .await?;
-            Ok(HttpResponseCreated(router.into()))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            create_params.into_inner(),
+            &[],
+            |opctx, nexus, create| async move {
+                let query = query_params.into_inner();
+                let vpc_lookup = nexus.vpc_lookup(&opctx, query)?;
+                let router = nexus
+                    .vpc_create_router(
+                        &opctx,
+                        &vpc_lookup,
+                        &db::model::VpcRouterKind::Custom,
+                        &create,
+                    )
+                    .await?;
+                Ok(HttpResponseCreated(router.into()))
+            },
+        )
         .await
     }
 
@@ -5945,22 +6750,26 @@ impl NexusExternalApi for NexusExternalApiImpl {
         query_params: Query<vpc::OptionalVpcSelector>,
         router_params: TypedBody<vpc::VpcRouterUpdate>,
     ) -> Result<HttpResponseOk<VpcRouter>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let path = path_params.into_inner();
-            let query = query_params.into_inner();
-            let router_params = router_params.into_inner();
-            let router_selector = vpc::RouterSelector {
-                project: query.project,
-                vpc: query.vpc,
-                router: path.router,
-            };
-            let router_lookup =
-                nexus.vpc_router_lookup(&opctx, router_selector)?;
-            let router = nexus
-                .vpc_update_router(&opctx, &router_lookup, &router_params)
-                .await?;
-            Ok(HttpResponseOk(router.into()))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            router_params.into_inner(),
+            &[],
+            |opctx, nexus, router_params| async move {
+                let path = path_params.into_inner();
+                let query = query_params.into_inner();
+                let router_selector = vpc::RouterSelector {
+                    project: query.project,
+                    vpc: query.vpc,
+                    router: path.router,
+                };
+                let router_lookup =
+                    nexus.vpc_router_lookup(&opctx, router_selector)?;
+                let router = nexus
+                    .vpc_update_router(&opctx, &router_lookup, &router_params)
+                    .await?;
+                Ok(HttpResponseOk(router.into()))
+            },
+        )
         .await
     }
 
@@ -6036,20 +6845,24 @@ impl NexusExternalApi for NexusExternalApiImpl {
         query_params: Query<vpc::RouterSelector>,
         create_params: TypedBody<vpc::RouterRouteCreate>,
     ) -> Result<HttpResponseCreated<RouterRoute>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let query = query_params.into_inner();
-            let create = create_params.into_inner();
-            let router_lookup = nexus.vpc_router_lookup(&opctx, query)?;
-            let route = nexus
-                .router_create_route(
-                    &opctx,
-                    &router_lookup,
-                    &RouterRouteKind::Custom,
-                    &create,
-                )
-                .await?;
-            Ok(HttpResponseCreated(route.into()))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            create_params.into_inner(),
+            &[],
+            |opctx, nexus, create| async move {
+                let query = query_params.into_inner();
+                let router_lookup = nexus.vpc_router_lookup(&opctx, query)?;
+                let route = nexus
+                    .router_create_route(
+                        &opctx,
+                        &router_lookup,
+                        &RouterRouteKind::Custom,
+                        &create,
+                    )
+                    .await?;
+                Ok(HttpResponseCreated(route.into()))
+            },
+        )
         .await
     }
 
@@ -6081,23 +6894,27 @@ impl NexusExternalApi for NexusExternalApiImpl {
         query_params: Query<vpc::OptionalRouterSelector>,
         router_params: TypedBody<vpc::RouterRouteUpdate>,
     ) -> Result<HttpResponseOk<RouterRoute>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let path = path_params.into_inner();
-            let query = query_params.into_inner();
-            let router_params = router_params.into_inner();
-            let route_selector = vpc::RouteSelector {
-                project: query.project,
-                vpc: query.vpc,
-                router: query.router,
-                route: path.route,
-            };
-            let route_lookup =
-                nexus.vpc_router_route_lookup(&opctx, route_selector)?;
-            let route = nexus
-                .router_update_route(&opctx, &route_lookup, &router_params)
-                .await?;
-            Ok(HttpResponseOk(route.into()))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            router_params.into_inner(),
+            &[],
+            |opctx, nexus, router_params| async move {
+                let path = path_params.into_inner();
+                let query = query_params.into_inner();
+                let route_selector = vpc::RouteSelector {
+                    project: query.project,
+                    vpc: query.vpc,
+                    router: query.router,
+                    route: path.route,
+                };
+                let route_lookup =
+                    nexus.vpc_router_route_lookup(&opctx, route_selector)?;
+                let route = nexus
+                    .router_update_route(&opctx, &route_lookup, &router_params)
+                    .await?;
+                Ok(HttpResponseOk(route.into()))
+            },
+        )
         .await
     }
 
@@ -6180,15 +6997,19 @@ impl NexusExternalApi for NexusExternalApiImpl {
         create_params: TypedBody<internet_gateway::InternetGatewayCreate>,
     ) -> Result<HttpResponseCreated<internet_gateway::InternetGateway>, HttpError>
     {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let query = query_params.into_inner();
-            let create = create_params.into_inner();
-            let vpc_lookup = nexus.vpc_lookup(&opctx, query)?;
-            let result = nexus
-                .internet_gateway_create(&opctx, &vpc_lookup, &create)
-                .await?;
-            Ok(HttpResponseCreated(result.into()))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            create_params.into_inner(),
+            &[],
+            |opctx, nexus, create| async move {
+                let query = query_params.into_inner();
+                let vpc_lookup = nexus.vpc_lookup(&opctx, query)?;
+                let result = nexus
+                    .internet_gateway_create(&opctx, &vpc_lookup, &create)
+                    .await?;
+                Ok(HttpResponseCreated(result.into()))
+            },
+        )
         .await
     }
 
@@ -6266,15 +7087,19 @@ impl NexusExternalApi for NexusExternalApiImpl {
         HttpResponseCreated<internet_gateway::InternetGatewayIpPool>,
         HttpError,
     > {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let query = query_params.into_inner();
-            let create = create_params.into_inner();
-            let lookup = nexus.internet_gateway_lookup(&opctx, query)?;
-            let result = nexus
-                .internet_gateway_ip_pool_attach(&opctx, &lookup, &create)
-                .await?;
-            Ok(HttpResponseCreated(result.into()))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            create_params.into_inner(),
+            &[],
+            |opctx, nexus, create| async move {
+                let query = query_params.into_inner();
+                let lookup = nexus.internet_gateway_lookup(&opctx, query)?;
+                let result = nexus
+                    .internet_gateway_ip_pool_attach(&opctx, &lookup, &create)
+                    .await?;
+                Ok(HttpResponseCreated(result.into()))
+            },
+        )
         .await
     }
 
@@ -6414,16 +7239,20 @@ impl NexusExternalApi for NexusExternalApiImpl {
         path_params: Path<path_params::RackPath>,
         req: TypedBody<rack::RackMembershipAddSledsRequest>,
     ) -> Result<HttpResponseOk<RackMembershipStatus>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let req = req.into_inner();
-            let rack_id = path_params.into_inner().rack_id;
-            let authz_tq = authz::TrustQuorumConfig::for_rack_id(
-                RackUuid::from_untyped_uuid(rack_id),
-            );
-            let status =
-                nexus.tq_add_sleds(&opctx, authz_tq, req.sled_ids).await?;
-            Ok(HttpResponseOk(status.into()))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            req.into_inner(),
+            &[],
+            |opctx, nexus, req| async move {
+                let rack_id = path_params.into_inner().rack_id;
+                let authz_tq = authz::TrustQuorumConfig::for_rack_id(
+                    RackUuid::from_untyped_uuid(rack_id),
+                );
+                let status =
+                    nexus.tq_add_sleds(&opctx, authz_tq, req.sled_ids).await?;
+                Ok(HttpResponseOk(status.into()))
+            },
+        )
         .await
     }
 
@@ -6569,11 +7398,15 @@ impl NexusExternalApi for NexusExternalApiImpl {
         rqctx: RequestContext<ApiContext>,
         sled: TypedBody<hardware::UninitializedSledId>,
     ) -> Result<HttpResponseCreated<sled::SledId>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let sled = sled.into_inner();
-            let id = nexus.sled_add(&opctx, sled).await?;
-            Ok(HttpResponseCreated(sled::SledId { id }))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            sled.into_inner(),
+            &[],
+            |opctx, nexus, sled| async move {
+                let id = nexus.sled_add(&opctx, sled).await?;
+                Ok(HttpResponseCreated(sled::SledId { id }))
+            },
+        )
         .await
     }
 
@@ -6649,6 +7482,56 @@ impl NexusExternalApi for NexusExternalApiImpl {
         .await
     }
 
+    async fn sled_evacuation_start(
+        rqctx: RequestContext<ApiContext>,
+        path_params: Path<path_params::SledPath>,
+    ) -> Result<HttpResponseCreated<sled::SledEvacuation>, HttpError> {
+        audit_and_time(&rqctx, |opctx, nexus| async move {
+            let path = path_params.into_inner();
+            let sled_lookup = nexus.sled_lookup(&opctx, &path.sled_id)?;
+            let evacuation =
+                nexus.sled_evacuation_start(&opctx, &sled_lookup).await?;
+            Ok(HttpResponseCreated(evacuation))
+        })
+        .await
+    }
+
+    async fn sled_evacuation_view(
+        rqctx: RequestContext<ApiContext>,
+        path_params: Path<path_params::SledPath>,
+    ) -> Result<HttpResponseOk<sled::SledEvacuation>, HttpError> {
+        let apictx = rqctx.context();
+        let handler = async {
+            let nexus = &apictx.context.nexus;
+            let path = path_params.into_inner();
+            let opctx =
+                crate::context::op_context_for_external_api(&rqctx).await?;
+            let sled_lookup = nexus.sled_lookup(&opctx, &path.sled_id)?;
+            let evacuation =
+                nexus.sled_evacuation_view(&opctx, &sled_lookup).await?;
+            Ok(HttpResponseOk(evacuation))
+        };
+        apictx
+            .context
+            .external_latencies
+            .instrument_dropshot_handler(&rqctx, handler)
+            .await
+    }
+
+    async fn sled_evacuation_cancel(
+        rqctx: RequestContext<ApiContext>,
+        path_params: Path<path_params::SledPath>,
+    ) -> Result<HttpResponseOk<sled::SledEvacuation>, HttpError> {
+        audit_and_time(&rqctx, |opctx, nexus| async move {
+            let path = path_params.into_inner();
+            let sled_lookup = nexus.sled_lookup(&opctx, &path.sled_id)?;
+            let evacuation =
+                nexus.sled_evacuation_cancel(&opctx, &sled_lookup).await?;
+            Ok(HttpResponseOk(evacuation))
+        })
+        .await
+    }
+
     async fn sled_instance_list(
         rqctx: RequestContext<ApiContext>,
         path_params: Path<path_params::SledPath>,
@@ -7267,12 +8150,16 @@ impl NexusExternalApi for NexusExternalApiImpl {
         rqctx: RequestContext<Self::Context>,
         body: TypedBody<update::TufSignedRootRole>,
     ) -> Result<HttpResponseCreated<update::UpdatesTrustRoot>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let body = body.into_inner();
-            Ok(HttpResponseCreated(
-                nexus.updates_add_trust_root(&opctx, body).await?.into(),
-            ))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            body.into_inner(),
+            &[],
+            |opctx, nexus, body| async move {
+                Ok(HttpResponseCreated(
+                    nexus.updates_add_trust_root(&opctx, body).await?.into(),
+                ))
+            },
+        )
         .await
     }
 
@@ -7752,26 +8639,30 @@ impl NexusExternalApi for NexusExternalApiImpl {
         rqctx: RequestContext<ApiContext>,
         new_key: TypedBody<ssh_key::SshKeyCreate>,
     ) -> Result<HttpResponseCreated<SshKey>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let new_key = new_key.into_inner();
-            let &actor = opctx
-                .authn
-                .actor_required()
-                .internal_context("creating ssh key for current user")?;
-
-            let silo_user_id = match actor.silo_user_id() {
-                Some(silo_user_id) => silo_user_id,
-                None => {
-                    return Err(Error::non_resourcetype_not_found(
-                        "could not find silo user",
-                    ))?;
-                }
-            };
+        audit_and_time_with_body(
+            &rqctx,
+            new_key.into_inner(),
+            &[],
+            |opctx, nexus, new_key| async move {
+                let &actor = opctx
+                    .authn
+                    .actor_required()
+                    .internal_context("creating ssh key for current user")?;
+
+                let silo_user_id = match actor.silo_user_id() {
+                    Some(silo_user_id) => silo_user_id,
+                    None => {
+                        return Err(Error::non_resourcetype_not_found(
+                            "could not find silo user",
+                        ))?;
+                    }
+                };
 
-            let ssh_key =
-                nexus.ssh_key_create(&opctx, silo_user_id, new_key).await?;
-            Ok(HttpResponseCreated(ssh_key.into()))
-        })
+                let ssh_key =
+                    nexus.ssh_key_create(&opctx, silo_user_id, new_key).await?;
+                Ok(HttpResponseCreated(ssh_key.into()))
+            },
+        )
         .await
     }
 
@@ -7861,14 +8752,263 @@ impl NexusExternalApi for NexusExternalApiImpl {
             let tokens = nexus
                 .current_user_token_list(&opctx, &pag_params)
                 .await?
-                .into_iter()
-                .map(device::DeviceAccessToken::from)
-                .collect();
-            Ok(HttpResponseOk(ScanById::results_page(
-                &query,
-                tokens,
-                &marker_for_id,
-            )?))
+                .into_iter()
+                .map(device::DeviceAccessToken::from)
+                .collect();
+            Ok(HttpResponseOk(ScanById::results_page(
+                &query,
+                tokens,
+                &marker_for_id,
+            )?))
+        };
+        apictx
+            .context
+            .external_latencies
+            .instrument_dropshot_handler(&rqctx, handler)
+            .await
+    }
+
+    async fn current_user_access_token_delete(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<path_params::TokenPath>,
+    ) -> Result<HttpResponseDeleted, HttpError> {
+        audit_and_time(&rqctx, |opctx, nexus| async move {
+            let path = path_params.into_inner();
+            nexus.current_user_token_delete(&opctx, path.token_id).await?;
+            Ok(HttpResponseDeleted())
+        })
+        .await
+    }
+
+    // Service accounts
+
+    async fn service_account_list(
+        rqctx: RequestContext<Self::Context>,
+        query_params: Query<PaginatedByNameOrId>,
+    ) -> Result<
+        HttpResponseOk<ResultsPage<service_account::ServiceAccount>>,
+        HttpError,
+    > {
+        let apictx = rqctx.context();
+        let handler = async {
+            let nexus = &apictx.context.nexus;
+            let query = query_params.into_inner();
+            let pag_params = data_page_params_for(&rqctx, &query)?;
+            let scan_params = ScanByNameOrId::from_query(&query)?;
+            let paginated_by = name_or_id_pagination(&pag_params, scan_params)?;
+            let opctx =
+                crate::context::op_context_for_external_api(&rqctx).await?;
+            let service_accounts = nexus
+                .service_accounts_list(&opctx, &paginated_by)
+                .await?
+                .into_iter()
+                .map(Into::into)
+                .collect();
+            Ok(HttpResponseOk(ScanByNameOrId::results_page(
+                &query,
+                service_accounts,
+                &marker_for_name_or_id,
+            )?))
+        };
+        apictx
+            .context
+            .external_latencies
+            .instrument_dropshot_handler(&rqctx, handler)
+            .await
+    }
+
+    async fn service_account_create(
+        rqctx: RequestContext<Self::Context>,
+        new_service_account: TypedBody<service_account::ServiceAccountCreate>,
+    ) -> Result<HttpResponseCreated<service_account::ServiceAccount>, HttpError>
+    {
+        audit_and_time_with_body(
+            &rqctx,
+            new_service_account.into_inner(),
+            &[],
+            |opctx, nexus, params| async move {
+                let service_account =
+                    nexus.service_account_create(&opctx, params).await?;
+                Ok(HttpResponseCreated(service_account.into()))
+            },
+        )
+        .await
+    }
+
+    async fn service_account_view(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<service_account::ServiceAccountPath>,
+    ) -> Result<HttpResponseOk<service_account::ServiceAccount>, HttpError>
+    {
+        let apictx = rqctx.context();
+        let handler = async {
+            let nexus = &apictx.context.nexus;
+            let path = path_params.into_inner();
+            let opctx =
+                crate::context::op_context_for_external_api(&rqctx).await?;
+            let (.., service_account) = nexus
+                .service_account_lookup(&opctx, &path.service_account)?
+                .fetch()
+                .await?;
+            Ok(HttpResponseOk(service_account.into()))
+        };
+        apictx
+            .context
+            .external_latencies
+            .instrument_dropshot_handler(&rqctx, handler)
+            .await
+    }
+
+    async fn service_account_delete(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<service_account::ServiceAccountPath>,
+    ) -> Result<HttpResponseDeleted, HttpError> {
+        audit_and_time(&rqctx, |opctx, nexus| async move {
+            let path = path_params.into_inner();
+            let sa_lookup =
+                nexus.service_account_lookup(&opctx, &path.service_account)?;
+            nexus.service_account_delete(&opctx, &sa_lookup).await?;
+            Ok(HttpResponseDeleted())
+        })
+        .await
+    }
+
+    async fn service_account_policy_view(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<service_account::ServiceAccountPath>,
+    ) -> Result<HttpResponseOk<service_account::ServiceAccountPolicy>, HttpError>
+    {
+        let apictx = rqctx.context();
+        let handler = async {
+            let nexus = &apictx.context.nexus;
+            let path = path_params.into_inner();
+            let opctx =
+                crate::context::op_context_for_external_api(&rqctx).await?;
+            let sa_lookup =
+                nexus.service_account_lookup(&opctx, &path.service_account)?;
+            let policy =
+                nexus.service_account_policy_view(&opctx, &sa_lookup).await?;
+            Ok(HttpResponseOk(policy))
+        };
+        apictx
+            .context
+            .external_latencies
+            .instrument_dropshot_handler(&rqctx, handler)
+            .await
+    }
+
+    async fn service_account_policy_update(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<service_account::ServiceAccountPath>,
+        new_policy: TypedBody<service_account::ServiceAccountPolicy>,
+    ) -> Result<HttpResponseOk<service_account::ServiceAccountPolicy>, HttpError>
+    {
+        audit_and_time_with_body(
+            &rqctx,
+            new_policy.into_inner(),
+            &[],
+            |opctx, nexus, new_policy| async move {
+                let path = path_params.into_inner();
+                let sa_lookup = nexus
+                    .service_account_lookup(&opctx, &path.service_account)?;
+                let policy = nexus
+                    .service_account_policy_update(
+                        &opctx,
+                        &sa_lookup,
+                        &new_policy,
+                    )
+                    .await?;
+                Ok(HttpResponseOk(policy))
+            },
+        )
+        .await
+    }
+
+    async fn service_account_token_list(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<service_account::ServiceAccountPath>,
+        query_params: Query<PaginatedByNameOrId>,
+    ) -> Result<
+        HttpResponseOk<ResultsPage<service_account::ServiceAccountToken>>,
+        HttpError,
+    > {
+        let apictx = rqctx.context();
+        let handler = async {
+            let nexus = &apictx.context.nexus;
+            let path = path_params.into_inner();
+            let query = query_params.into_inner();
+            let pag_params = data_page_params_for(&rqctx, &query)?;
+            let scan_params = ScanByNameOrId::from_query(&query)?;
+            let paginated_by = name_or_id_pagination(&pag_params, scan_params)?;
+            let opctx =
+                crate::context::op_context_for_external_api(&rqctx).await?;
+            let sa_lookup =
+                nexus.service_account_lookup(&opctx, &path.service_account)?;
+            let tokens = nexus
+                .service_account_tokens_list(&opctx, &sa_lookup, &paginated_by)
+                .await?
+                .into_iter()
+                .map(Into::into)
+                .collect();
+            Ok(HttpResponseOk(ScanByNameOrId::results_page(
+                &query,
+                tokens,
+                &marker_for_name_or_id,
+            )?))
+        };
+        apictx
+            .context
+            .external_latencies
+            .instrument_dropshot_handler(&rqctx, handler)
+            .await
+    }
+
+    async fn service_account_token_create(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<service_account::ServiceAccountPath>,
+        new_token: TypedBody<service_account::ServiceAccountTokenCreate>,
+    ) -> Result<
+        HttpResponseCreated<service_account::ServiceAccountTokenValue>,
+        HttpError,
+    > {
+        audit_and_time_with_body(
+            &rqctx,
+            new_token.into_inner(),
+            &[],
+            |opctx, nexus, params| async move {
+                let path = path_params.into_inner();
+                let sa_lookup = nexus
+                    .service_account_lookup(&opctx, &path.service_account)?;
+                let token = nexus
+                    .service_account_token_create(&opctx, &sa_lookup, params)
+                    .await?;
+                Ok(HttpResponseCreated(token.into()))
+            },
+        )
+        .await
+    }
+
+    async fn service_account_token_view(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<service_account::ServiceAccountTokenPath>,
+    ) -> Result<HttpResponseOk<service_account::ServiceAccountToken>, HttpError>
+    {
+        let apictx = rqctx.context();
+        let handler = async {
+            let nexus = &apictx.context.nexus;
+            let path = path_params.into_inner();
+            let opctx =
+                crate::context::op_context_for_external_api(&rqctx).await?;
+            let (.., token) = nexus
+                .service_account_token_lookup(
+                    &opctx,
+                    &path.service_account,
+                    &path.token,
+                )
+                .await?
+                .fetch()
+                .await?;
+            Ok(HttpResponseOk(token.into()))
         };
         apictx
             .context
@@ -7877,18 +9017,49 @@ impl NexusExternalApi for NexusExternalApiImpl {
             .await
     }
 
-    async fn current_user_access_token_delete(
+    async fn service_account_token_delete(
         rqctx: RequestContext<Self::Context>,
-        path_params: Path<path_params::TokenPath>,
+        path_params: Path<service_account::ServiceAccountTokenPath>,
     ) -> Result<HttpResponseDeleted, HttpError> {
         audit_and_time(&rqctx, |opctx, nexus| async move {
             let path = path_params.into_inner();
-            nexus.current_user_token_delete(&opctx, path.token_id).await?;
+            let token_lookup = nexus
+                .service_account_token_lookup(
+                    &opctx,
+                    &path.service_account,
+                    &path.token,
+                )
+                .await?;
+            nexus.service_account_token_delete(&opctx, &token_lookup).await?;
             Ok(HttpResponseDeleted())
         })
         .await
     }
 
+    async fn service_account_token_rotate(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<service_account::ServiceAccountTokenPath>,
+    ) -> Result<
+        HttpResponseOk<service_account::ServiceAccountTokenValue>,
+        HttpError,
+    > {
+        audit_and_time(&rqctx, |opctx, nexus| async move {
+            let path = path_params.into_inner();
+            let token_lookup = nexus
+                .service_account_token_lookup(
+                    &opctx,
+                    &path.service_account,
+                    &path.token,
+                )
+                .await?;
+            let token = nexus
+                .service_account_token_rotate(&opctx, &token_lookup)
+                .await?;
+            Ok(HttpResponseOk(token.into()))
+        })
+        .await
+    }
+
     async fn support_bundle_list(
         rqctx: RequestContext<ApiContext>,
         query_params: Query<PaginatedByTimeAndId>,
@@ -8139,17 +9310,21 @@ impl NexusExternalApi for NexusExternalApiImpl {
         body: TypedBody<support_bundle::SupportBundleCreate>,
     ) -> Result<HttpResponseCreated<support_bundle::SupportBundleInfo>, HttpError>
     {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let create_params = body.into_inner();
-            let bundle = nexus
-                .support_bundle_create(
-                    &opctx,
-                    "Created by external API",
-                    create_params.user_comment,
-                )
-                .await?;
-            Ok(HttpResponseCreated(bundle.into()))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            body.into_inner(),
+            &[],
+            |opctx, nexus, create_params| async move {
+                let bundle = nexus
+                    .support_bundle_create(
+                        &opctx,
+                        "Created by external API",
+                        create_params.user_comment,
+                    )
+                    .await?;
+                Ok(HttpResponseCreated(bundle.into()))
+            },
+        )
         .await
     }
 
@@ -8176,18 +9351,22 @@ impl NexusExternalApi for NexusExternalApiImpl {
         body: TypedBody<support_bundle::SupportBundleUpdate>,
     ) -> Result<HttpResponseOk<support_bundle::SupportBundleInfo>, HttpError>
     {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let path = path_params.into_inner();
-            let update = body.into_inner();
-            let bundle = nexus
-                .support_bundle_update_user_comment(
-                    &opctx,
-                    SupportBundleUuid::from_untyped_uuid(path.bundle_id),
-                    update.user_comment,
-                )
-                .await?;
-            Ok(HttpResponseOk(bundle.into()))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            body.into_inner(),
+            &[],
+            |opctx, nexus, update| async move {
+                let path = path_params.into_inner();
+                let bundle = nexus
+                    .support_bundle_update_user_comment(
+                        &opctx,
+                        SupportBundleUuid::from_untyped_uuid(path.bundle_id),
+                        update.user_comment,
+                    )
+                    .await?;
+                Ok(HttpResponseOk(bundle.into()))
+            },
+        )
         .await
     }
 
@@ -8261,17 +9440,21 @@ impl NexusExternalApi for NexusExternalApiImpl {
         query_params: Query<project::ProjectSelector>,
         new_probe: TypedBody<probe::ProbeCreate>,
     ) -> Result<HttpResponseCreated<Probe>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            opctx.authorize(authz::Action::Modify, &authz::FLEET).await?;
-            let new_probe_params = new_probe.into_inner();
-            let project_selector = query_params.into_inner();
-            let project_lookup =
-                nexus.project_lookup(&opctx, project_selector)?;
-            let probe = nexus
-                .probe_create(&opctx, &project_lookup, &new_probe_params)
-                .await?;
-            Ok(HttpResponseCreated(probe.into()))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            new_probe.into_inner(),
+            &[],
+            |opctx, nexus, new_probe_params| async move {
+                opctx.authorize(authz::Action::Modify, &authz::FLEET).await?;
+                let project_selector = query_params.into_inner();
+                let project_lookup =
+                    nexus.project_lookup(&opctx, project_selector)?;
+                let probe = nexus
+                    .probe_create(&opctx, &project_lookup, &new_probe_params)
+                    .await?;
+                Ok(HttpResponseCreated(probe.into()))
+            },
+        )
         .await
     }
 
@@ -8313,6 +9496,7 @@ impl NexusExternalApi for NexusExternalApiImpl {
                     &pag_params,
                     scan_params.selector.start_time,
                     scan_params.selector.end_time,
+                    scan_params.selector.resource_id,
                 )
                 .await?;
             Ok(HttpResponseOk(ScanByTimeAndId::results_page(
@@ -8333,6 +9517,142 @@ impl NexusExternalApi for NexusExternalApiImpl {
             .await
     }
 
+    // Cannot delegate to lib.rs: the pagination parameters (including page
+    // tokens) of the old version embed the old `AuditLogParams`, and there's
+    // no way to construct the newer version's pagination parameters from
+    // them.
+    async fn audit_log_list_v2026_10_19_00(
+        rqctx: RequestContext<Self::Context>,
+        query_params: Query<
+            PaginatedByTimeAndId<v2025_11_20_00::audit::AuditLogParams>,
+        >,
+    ) -> Result<
+        HttpResponseOk<ResultsPage<v2026_10_19_00::audit::AuditLogEntry>>,
+        HttpError,
+    > {
+        let apictx = rqctx.context();
+        let handler = async {
+            let opctx =
+                crate::context::op_context_for_external_api(&rqctx).await?;
+
+            let nexus = &apictx.context.nexus;
+            let query = query_params.into_inner();
+            let scan_params = ScanByTimeAndId::from_query(&query)?;
+            let pag_params = data_page_params_for(&rqctx, &query)?;
+
+            let log_entries = nexus
+                .audit_log_list(
+                    &opctx,
+                    &pag_params,
+                    scan_params.selector.start_time,
+                    scan_params.selector.end_time,
+                    None,
+                )
+                .await?;
+            Ok(HttpResponseOk(ScanByTimeAndId::results_page(
+                &query,
+                log_entries
+                    .into_iter()
+                    .map(|e| audit::AuditLogEntry::try_from(e).map(Into::into))
+                    .collect::<Result<Vec<_>, _>>()?,
+                &|_, entry: &v2026_10_19_00::audit::AuditLogEntry| {
+                    (entry.time_completed, entry.id)
+                },
+            )?))
+        };
+        apictx
+            .context
+            .external_latencies
+            .instrument_dropshot_handler(&rqctx, handler)
+            .await
+    }
+
+    async fn audit_log_sink_list(
+        rqctx: RequestContext<Self::Context>,
+        query_params: Query<PaginatedByNameOrId>,
+    ) -> Result<HttpResponseOk<ResultsPage<audit::AuditLogSink>>, HttpError>
+    {
+        let apictx = rqctx.context();
+        let handler = async {
+            let nexus = &apictx.context.nexus;
+            let query = query_params.into_inner();
+            let pag_params = data_page_params_for(&rqctx, &query)?;
+            let scan_params = ScanByNameOrId::from_query(&query)?;
+            let paginated_by = name_or_id_pagination(&pag_params, scan_params)?;
+            let opctx =
+                crate::context::op_context_for_external_api(&rqctx).await?;
+            let sinks = nexus
+                .audit_log_sink_list(&opctx, &paginated_by)
+                .await?
+                .into_iter()
+                .map(Into::into)
+                .collect();
+            Ok(HttpResponseOk(ScanByNameOrId::results_page(
+                &query,
+                sinks,
+                &marker_for_name_or_id,
+            )?))
+        };
+        apictx
+            .context
+            .external_latencies
+            .instrument_dropshot_handler(&rqctx, handler)
+            .await
+    }
+
+    async fn audit_log_sink_create(
+        rqctx: RequestContext<Self::Context>,
+        new_sink: TypedBody<audit::AuditLogSinkCreate>,
+    ) -> Result<HttpResponseCreated<audit::AuditLogSink>, HttpError> {
+        audit_and_time_with_body(
+            &rqctx,
+            new_sink.into_inner(),
+            &["/config/secret"],
+            |opctx, nexus, params| async move {
+                let sink = nexus.audit_log_sink_create(&opctx, params).await?;
+                Ok(HttpResponseCreated(sink.into()))
+            },
+        )
+        .await
+    }
+
+    async fn audit_log_sink_view(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<audit::AuditLogSinkPath>,
+    ) -> Result<HttpResponseOk<audit::AuditLogSink>, HttpError> {
+        let apictx = rqctx.context();
+        let handler = async {
+            let nexus = &apictx.context.nexus;
+            let path = path_params.into_inner();
+            let opctx =
+                crate::context::op_context_for_external_api(&rqctx).await?;
+            let (.., sink) = nexus
+                .audit_log_sink_lookup(&opctx, &path.sink)?
+                .fetch()
+                .await?;
+            Ok(HttpResponseOk(sink.into()))
+        };
+        apictx
+            .context
+            .external_latencies
+            .instrument_dropshot_handler(&rqctx, handler)
+            .await
+    }
+
+    async fn audit_log_sink_delete(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<audit::AuditLogSinkPath>,
+    ) -> Result<HttpResponseDeleted, HttpError> {
+        audit_and_time(&rqctx, |opctx, nexus| async move {
+            let path = path_params.into_inner();
+            let sink_lookup =
+                nexus.audit_log_sink_lookup(&opctx, &path.sink)?;
+            nexus.audit_log_sink_delete(&opctx, &sink_lookup).await?;
+            Ok(HttpResponseDeleted())
+        })
+        .await
+    }
+
     async fn login_saml_begin(
         rqctx: RequestContext<Self::Context>,
         _path_params: Path<console::LoginToProviderPathParam>,
@@ -8423,8 +9743,11 @@ impl NexusExternalApi for NexusExternalApiImpl {
             }
             .await;
 
-            let _ =
-                nexus.audit_log_entry_complete(opctx, &audit, &result).await;
+            // `opctx` is shared by all unauthenticated requests, so it can't
+            // record which resource this request acted on.
+            let _ = nexus
+                .audit_log_entry_complete(opctx, &audit, &result, None)
+                .await;
             result
         };
         apictx
@@ -8496,8 +9819,11 @@ impl NexusExternalApi for NexusExternalApiImpl {
             }
             .await;
 
-            let _ =
-                nexus.audit_log_entry_complete(opctx, &audit, &result).await;
+            // `opctx` is shared by all unauthenticated requests, so it can't
+            // record which resource this request acted on.
+            let _ = nexus
+                .audit_log_entry_complete(opctx, &audit, &result, None)
+                .await;
             result
         };
         apictx
@@ -8651,6 +9977,35 @@ impl NexusExternalApi for NexusExternalApiImpl {
             .await
     }
 
+    async fn acme_http01_challenge(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<console::RestPathParam>,
+    ) -> Result<Response<Body>, HttpError> {
+        let apictx = rqctx.context();
+        let handler = async {
+            let nexus = &apictx.context.nexus;
+            // Challenge tokens are a single path component.
+            let path = path_params.into_inner().path;
+            let [token] = path.as_slice() else {
+                return Err(HttpError::for_not_found(
+                    None,
+                    format!("not an ACME challenge: {path:?}"),
+                ));
+            };
+            let key_authorization =
+                nexus.acme_http01_key_authorization(token).await?;
+            Ok(Response::builder()
+                .status(StatusCode::OK)
+                .header(header::CONTENT_TYPE, "application/octet-stream")
+                .body(key_authorization.into())?)
+        };
+        apictx
+            .context
+            .external_latencies
+            .instrument_dropshot_handler(&rqctx, handler)
+            .await
+    }
+
     // Entrypoints for the OAuth 2.0 Device Authorization Grant flow.
     //
     // These are endpoints used by the API client per se (e.g., the CLI),
@@ -8732,31 +10087,35 @@ impl NexusExternalApi for NexusExternalApiImpl {
         rqctx: RequestContext<Self::Context>,
         params: TypedBody<device::DeviceAuthVerify>,
     ) -> Result<HttpResponseUpdatedNoContent, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let params = params.into_inner();
-            let &actor = opctx.authn.actor_required().internal_context(
-                "creating new device auth session for current user",
-            )?;
+        audit_and_time_with_body(
+            &rqctx,
+            params.into_inner(),
+            &["/user_code"],
+            |opctx, nexus, params| async move {
+                let &actor = opctx.authn.actor_required().internal_context(
+                    "creating new device auth session for current user",
+                )?;
 
-            let silo_user_id = match actor.silo_user_id() {
-                Some(silo_user_id) => silo_user_id,
-                None => {
-                    return Err(Error::non_resourcetype_not_found(
-                        "could not find silo user",
-                    ))?;
-                }
-            };
+                let silo_user_id = match actor.silo_user_id() {
+                    Some(silo_user_id) => silo_user_id,
+                    None => {
+                        return Err(Error::non_resourcetype_not_found(
+                            "could not find silo user",
+                        ))?;
+                    }
+                };
 
-            let _token = nexus
-                .device_auth_request_verify(
-                    &opctx,
-                    params.user_code,
-                    silo_user_id,
-                )
-                .await?;
+                let _token = nexus
+                    .device_auth_request_verify(
+                        &opctx,
+                        params.user_code,
+                        silo_user_id,
+                    )
+                    .await?;
 
-            Ok(HttpResponseUpdatedNoContent())
-        })
+                Ok(HttpResponseUpdatedNoContent())
+            },
+        )
         .await
     }
 
@@ -8840,10 +10199,7 @@ impl NexusExternalApi for NexusExternalApiImpl {
                 .alert_receiver_list(&opctx, &paginated_by)
                 .await?
                 .into_iter()
-                .map(|webhook| {
-                    alert::WebhookReceiver::try_from(webhook)
-                        .map(alert::AlertReceiver::from)
-                })
+                .map(alert::AlertReceiver::try_from)
                 .collect::<Result<Vec<_>, _>>()?;
 
             Ok(HttpResponseOk(ScanByNameOrId::results_page(
@@ -8872,10 +10228,8 @@ impl NexusExternalApi for NexusExternalApiImpl {
                 crate::context::op_context_for_external_api(&rqctx).await?;
             let webhook_selector = path_params.into_inner();
             let rx = nexus.alert_receiver_lookup(&opctx, webhook_selector)?;
-            let webhook = nexus.alert_receiver_config_fetch(&opctx, rx).await?;
-            Ok(HttpResponseOk(
-                alert::WebhookReceiver::try_from(webhook)?.into(),
-            ))
+            let config = nexus.alert_receiver_config_fetch(&opctx, rx).await?;
+            Ok(HttpResponseOk(alert::AlertReceiver::try_from(config)?))
         };
         apictx
             .context
@@ -8888,12 +10242,18 @@ impl NexusExternalApi for NexusExternalApiImpl {
         rqctx: RequestContext<Self::Context>,
         params: TypedBody<alert::WebhookCreate>,
     ) -> Result<HttpResponseCreated<alert::WebhookReceiver>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let params = params.into_inner();
-            let receiver =
-                nexus.webhook_receiver_create(&opctx, params).await?;
-            Ok(HttpResponseCreated(alert::WebhookReceiver::try_from(receiver)?))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            params.into_inner(),
+            &["/secrets/*"],
+            |opctx, nexus, params| async move {
+                let receiver =
+                    nexus.webhook_receiver_create(&opctx, params).await?;
+                Ok(HttpResponseCreated(alert::WebhookReceiver::try_from(
+                    receiver,
+                )?))
+            },
+        )
         .await
     }
 
@@ -8902,13 +10262,18 @@ impl NexusExternalApi for NexusExternalApiImpl {
         path_params: Path<alert::AlertReceiverSelector>,
         params: TypedBody<alert::WebhookReceiverUpdate>,
     ) -> Result<HttpResponseUpdatedNoContent, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let webhook_selector = path_params.into_inner();
-            let params = params.into_inner();
-            let rx = nexus.alert_receiver_lookup(&opctx, webhook_selector)?;
-            nexus.webhook_receiver_update(&opctx, rx, params).await?;
-            Ok(HttpResponseUpdatedNoContent())
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            params.into_inner(),
+            &[],
+            |opctx, nexus, params| async move {
+                let webhook_selector = path_params.into_inner();
+                let rx =
+                    nexus.alert_receiver_lookup(&opctx, webhook_selector)?;
+                nexus.webhook_receiver_update(&opctx, rx, params).await?;
+                Ok(HttpResponseUpdatedNoContent())
+            },
+        )
         .await
     }
 
@@ -8931,15 +10296,20 @@ impl NexusExternalApi for NexusExternalApiImpl {
         params: TypedBody<alert::AlertSubscriptionCreate>,
     ) -> Result<HttpResponseCreated<alert::AlertSubscriptionCreated>, HttpError>
     {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let webhook_selector = path_params.into_inner();
-            let subscription = params.into_inner();
-            let rx = nexus.alert_receiver_lookup(&opctx, webhook_selector)?;
-            let subscription = nexus
-                .alert_receiver_subscription_add(&opctx, rx, subscription)
-                .await?;
-            Ok(HttpResponseCreated(subscription))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            params.into_inner(),
+            &[],
+            |opctx, nexus, subscription| async move {
+                let webhook_selector = path_params.into_inner();
+                let rx =
+                    nexus.alert_receiver_lookup(&opctx, webhook_selector)?;
+                let subscription = nexus
+                    .alert_receiver_subscription_add(&opctx, rx, subscription)
+                    .await?;
+                Ok(HttpResponseCreated(subscription))
+            },
+        )
         .await
     }
 
@@ -8969,7 +10339,7 @@ impl NexusExternalApi for NexusExternalApiImpl {
             let probe_params = query_params.into_inner();
             let rx = nexus.alert_receiver_lookup(&opctx, webhook_selector)?;
             let result =
-                nexus.webhook_receiver_probe(&opctx, rx, probe_params).await?;
+                nexus.alert_receiver_probe(&opctx, rx, probe_params).await?;
             Ok(HttpResponseOk(result))
         })
         .await
@@ -9010,14 +10380,21 @@ impl NexusExternalApi for NexusExternalApiImpl {
         query_params: Query<alert::AlertReceiverSelector>,
         params: TypedBody<alert::WebhookSecretCreate>,
     ) -> Result<HttpResponseCreated<alert::WebhookSecret>, HttpError> {
-        audit_and_time(&rqctx, |opctx, nexus| async move {
-            let alert::WebhookSecretCreate { secret } = params.into_inner();
-            let webhook_selector = query_params.into_inner();
-            let rx = nexus.alert_receiver_lookup(&opctx, webhook_selector)?;
-            let secret =
-                nexus.webhook_receiver_secret_add(&opctx, rx, secret).await?;
-            Ok(HttpResponseCreated(secret))
-        })
+        audit_and_time_with_body(
+            &rqctx,
+            params.into_inner(),
+            &["/secret"],
+            |opctx, nexus, params| async move {
+                let alert::WebhookSecretCreate { secret } = params;
+                let webhook_selector = query_params.into_inner();
+                let rx =
+                    nexus.alert_receiver_lookup(&opctx, webhook_selector)?;
+                let secret = nexus
+                    .webhook_receiver_secret_add(&opctx, rx, secret)
+                    .await?;
+                Ok(HttpResponseCreated(secret))
+            },
+        )
         .await
     }
 
@@ -9036,6 +10413,82 @@ impl NexusExternalApi for NexusExternalApiImpl {
         .await
     }
 
+    async fn smtp_receiver_create(
+        rqctx: RequestContext<Self::Context>,
+        params: TypedBody<alert::SmtpCreate>,
+    ) -> Result<HttpResponseCreated<alert::SmtpReceiver>, HttpError> {
+        audit_and_time_with_body(
+            &rqctx,
+            params.into_inner(),
+            &[],
+            |opctx, nexus, params| async move {
+                let receiver =
+                    nexus.smtp_receiver_create(&opctx, params).await?;
+                Ok(HttpResponseCreated(alert::SmtpReceiver::try_from(
+                    receiver,
+                )?))
+            },
+        )
+        .await
+    }
+
+    async fn smtp_receiver_update(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<alert::AlertReceiverSelector>,
+        params: TypedBody<alert::SmtpReceiverUpdate>,
+    ) -> Result<HttpResponseUpdatedNoContent, HttpError> {
+        audit_and_time_with_body(
+            &rqctx,
+            params.into_inner(),
+            &[],
+            |opctx, nexus, params| async move {
+                let rx_selector = path_params.into_inner();
+                let rx = nexus.alert_receiver_lookup(&opctx, rx_selector)?;
+                nexus.smtp_receiver_update(&opctx, rx, params).await?;
+                Ok(HttpResponseUpdatedNoContent())
+            },
+        )
+        .await
+    }
+
+    async fn syslog_receiver_create(
+        rqctx: RequestContext<Self::Context>,
+        params: TypedBody<alert::SyslogCreate>,
+    ) -> Result<HttpResponseCreated<alert::SyslogReceiver>, HttpError> {
+        audit_and_time_with_body(
+            &rqctx,
+            params.into_inner(),
+            &[],
+            |opctx, nexus, params| async move {
+                let receiver =
+                    nexus.syslog_receiver_create(&opctx, params).await?;
+                Ok(HttpResponseCreated(alert::SyslogReceiver::try_from(
+                    receiver,
+                )?))
+            },
+        )
+        .await
+    }
+
+    async fn syslog_receiver_update(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<alert::AlertReceiverSelector>,
+        params: TypedBody<alert::SyslogReceiverUpdate>,
+    ) -> Result<HttpResponseUpdatedNoContent, HttpError> {
+        audit_and_time_with_body(
+            &rqctx,
+            params.into_inner(),
+            &[],
+            |opctx, nexus, params| async move {
+                let rx_selector = path_params.into_inner();
+                let rx = nexus.alert_receiver_lookup(&opctx, rx_selector)?;
+                nexus.syslog_receiver_update(&opctx, rx, params).await?;
+                Ok(HttpResponseUpdatedNoContent())
+            },
+        )
+        .await
+    }
+
     async fn alert_delivery_list(
         rqctx: RequestContext<Self::Context>,
         receiver: Path<alert::AlertReceiverSelector>,
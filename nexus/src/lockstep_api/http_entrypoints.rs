@@ -34,6 +34,7 @@ use nexus_types::deployment::OximeterReadPolicy;
 use nexus_types::deployment::ReconfiguratorConfigParam;
 use nexus_types::deployment::ReconfiguratorConfigView;
 use nexus_types::external_api::hardware::UninitializedSled;
+use nexus_types::external_api::instance::InstanceSelector;
 use nexus_types::external_api::path_params::{BlueprintPath, PhysicalDiskPath};
 use nexus_types::external_api::rack::RackMembershipConfigPathParams;
 use nexus_types::external_api::sled::{SledPolicy, SledSelector};
@@ -55,6 +56,7 @@ use nexus_types::trust_quorum::TrustQuorumConfig;
 use nexus_types_versions::latest::headers::RangeRequest;
 use nexus_types_versions::latest::instance::Instance;
 use omicron_common::api::external::Error;
+use omicron_common::api::external::NameOrId;
 use omicron_common::api::external::http_pagination::PaginatedById;
 use omicron_common::api::external::http_pagination::PaginatedByTimeAndId;
 use omicron_common::api::external::http_pagination::ScanById;
@@ -148,8 +150,21 @@ impl NexusLockstepApi for NexusLockstepApiImpl {
         let handler = async {
             let opctx =
                 crate::context::op_context_for_internal_api(&rqctx).await;
+            let instance_lookup = nexus.instance_lookup(
+                &opctx,
+                InstanceSelector {
+                    instance: NameOrId::Id(
+                        path.instance_id.into_untyped_uuid(),
+                    ),
+                    project: None,
+                },
+            )?;
             let instance = nexus
-                .instance_migrate(&opctx, path.instance_id, migrate)
+                .instance_migrate(
+                    &opctx,
+                    &instance_lookup,
+                    Some(migrate.dst_sled_id),
+                )
                 .await?;
             Ok(HttpResponseOk(instance.into()))
         };
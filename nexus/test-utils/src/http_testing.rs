@@ -310,6 +310,16 @@ impl<'a> RequestBuilder<'a> {
         self.expect_status(Some(http::StatusCode::OK))
     }
 
+    /// Expect the request to be rejected because the client exceeded its rate
+    /// limit.
+    pub fn expect_rate_limited(mut self) -> Self {
+        self.allowed_headers.as_mut().unwrap().push(http::header::RETRY_AFTER);
+        self.expected_response_headers
+            .entry(http::header::RETRY_AFTER)
+            .or_insert(None);
+        self.expect_status(Some(http::StatusCode::TOO_MANY_REQUESTS))
+    }
+
     /// Allow non-dropshot error responses, i.e., errors that are not compatible
     /// with `dropshot::HttpErrorResponseBody`.
     pub fn allow_non_dropshot_errors(mut self) -> Self {
@@ -0,0 +1,987 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Tests for obtaining Silo certificates from an ACME server
+//!
+//! These run Nexus against a small stand-in ACME server that implements just
+//! the parts of RFC 8555 that Nexus uses.  It checks request signatures,
+//! validates HTTP-01 challenges by fetching them from Nexus's external API and
+//! DNS-01 challenges by looking them up in the external DNS server, and signs
+//! the resulting certificates with its own CA.
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use dropshot::ApiDescription;
+use dropshot::Body;
+use dropshot::HttpError;
+use dropshot::Path;
+use dropshot::RequestContext;
+use dropshot::UntypedBody;
+use dropshot::endpoint;
+use hickory_resolver::TokioResolver;
+use hickory_resolver::config::NameServerConfig;
+use hickory_resolver::config::ResolverConfig;
+use hickory_resolver::name_server::TokioConnectionProvider;
+use hickory_resolver::proto::xfer::Protocol;
+use http::Response;
+use http::StatusCode;
+use http::header;
+use nexus_config::AcmeChallengeType;
+use nexus_db_queries::context::OpContext;
+use nexus_lockstep_client::types::LastResult;
+use nexus_test_utils::background::activate_background_task;
+use nexus_test_utils::http_testing::RequestBuilder;
+use nexus_types::identity::Resource;
+use nexus_types::internal_api::background::AcmeCertificatesStatus;
+use omicron_test_utils::dev::poll::CondCheckError;
+use omicron_test_utils::dev::poll::wait_for_condition;
+use openssl::asn1::Asn1Time;
+use openssl::bn::BigNum;
+use openssl::ec::EcGroup;
+use openssl::ec::EcKey;
+use openssl::ecdsa::EcdsaSig;
+use openssl::hash::MessageDigest;
+use openssl::hash::hash;
+use openssl::nid::Nid;
+use openssl::pkey::PKey;
+use openssl::pkey::Private;
+use openssl::x509::X509;
+use openssl::x509::X509NameBuilder;
+use openssl::x509::X509Req;
+use openssl::x509::extension::BasicConstraints;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::json;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use uuid::Uuid;
+
+type ControlPlaneTestContext =
+    nexus_test_utils::ControlPlaneTestContext<omicron_nexus::Server>;
+
+/// State of the stand-in ACME server
+struct AcmeServer {
+    ca_key: PKey<Private>,
+    ca_cert: X509,
+    /// Nexus's external API, where HTTP-01 challenges are validated.  This
+    /// isn't known until Nexus has started, which may be after its first
+    /// requests.
+    nexus_address: Mutex<Option<SocketAddr>>,
+    /// the external DNS server, where DNS-01 challenges are validated.  Like
+    /// `nexus_address`, this is filled in once Nexus has started.
+    external_dns_address: Mutex<Option<SocketAddr>>,
+    inner: Mutex<AcmeServerInner>,
+}
+
+#[derive(Default)]
+struct AcmeServerInner {
+    next_nonce: u64,
+    nonces: Vec<String>,
+    /// each account's public key, as (x, y) coordinates
+    accounts: Vec<(String, String)>,
+    orders: Vec<AcmeOrder>,
+    authorizations: Vec<AcmeAuthorization>,
+    certificates: Vec<String>,
+}
+
+struct AcmeOrder {
+    account: usize,
+    status: &'static str,
+    identifiers: Vec<String>,
+    authorizations: Vec<usize>,
+    certificate: Option<usize>,
+}
+
+struct AcmeAuthorization {
+    account: usize,
+    identifier: String,
+    token: String,
+    status: &'static str,
+    /// kind of challenge with which the authorization was validated
+    validated_with: Option<&'static str>,
+}
+
+/// A request whose signature has been checked
+struct Verified {
+    account: Option<usize>,
+    jwk: (String, String),
+    payload: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct IdPath {
+    id: usize,
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct ChallengePath {
+    id: usize,
+    kind: String,
+}
+
+impl AcmeServer {
+    fn new() -> AcmeServer {
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+        let ca_key =
+            PKey::from_ec_key(EcKey::generate(&group).unwrap()).unwrap();
+        let mut name = X509NameBuilder::new().unwrap();
+        name.append_entry_by_text("CN", "ACME stand-in CA").unwrap();
+        let name = name.build();
+        let mut builder = X509::builder().unwrap();
+        builder.set_version(2).unwrap();
+        builder.set_subject_name(&name).unwrap();
+        builder.set_issuer_name(&name).unwrap();
+        builder.set_pubkey(&ca_key).unwrap();
+        builder.set_not_before(&Asn1Time::days_from_now(0).unwrap()).unwrap();
+        builder.set_not_after(&Asn1Time::days_from_now(365).unwrap()).unwrap();
+        builder
+            .append_extension(BasicConstraints::new().ca().build().unwrap())
+            .unwrap();
+        builder.sign(&ca_key, MessageDigest::sha256()).unwrap();
+        AcmeServer {
+            ca_key,
+            ca_cert: builder.build(),
+            nexus_address: Mutex::new(None),
+            external_dns_address: Mutex::new(None),
+            inner: Mutex::new(AcmeServerInner::default()),
+        }
+    }
+
+    fn base_url(rqctx: &RequestContext<Arc<AcmeServer>>) -> String {
+        format!("http://{}", rqctx.server.local_addr)
+    }
+
+    /// Builds a response carrying a fresh nonce
+    fn response(
+        &self,
+        status: StatusCode,
+        location: Option<String>,
+        content_type: &str,
+        body: String,
+    ) -> Response<Body> {
+        let nonce = {
+            let mut inner = self.inner.lock().unwrap();
+            let nonce = format!("nonce-{}", inner.next_nonce);
+            inner.next_nonce += 1;
+            inner.nonces.push(nonce.clone());
+            nonce
+        };
+        let mut builder = Response::builder()
+            .status(status)
+            .header("replay-nonce", nonce)
+            .header(header::CONTENT_TYPE, content_type);
+        if let Some(location) = location {
+            builder = builder.header(header::LOCATION, location);
+        }
+        builder.body(body.into()).unwrap()
+    }
+
+    fn json(
+        &self,
+        location: Option<String>,
+        body: serde_json::Value,
+    ) -> Response<Body> {
+        self.response(
+            StatusCode::OK,
+            location,
+            "application/json",
+            body.to_string(),
+        )
+    }
+
+    /// Checks the nonce, URL, and signature of a JWS-wrapped request
+    fn verify(
+        &self,
+        rqctx: &RequestContext<Arc<AcmeServer>>,
+        body: UntypedBody,
+    ) -> Result<Verified, HttpError> {
+        let bad = |message: &str| {
+            HttpError::for_bad_request(None, format!("bad JWS: {message}"))
+        };
+        let jws: serde_json::Value =
+            serde_json::from_slice(body.as_bytes()).map_err(|_| bad("json"))?;
+        let part = |name: &str| {
+            jws[name].as_str().map(String::from).ok_or_else(|| bad(name))
+        };
+        let (protected, payload, signature) =
+            (part("protected")?, part("payload")?, part("signature")?);
+        let header: serde_json::Value = URL_SAFE_NO_PAD
+            .decode(&protected)
+            .ok()
+            .and_then(|h| serde_json::from_slice(&h).ok())
+            .ok_or_else(|| bad("protected header"))?;
+
+        let url = header["url"].as_str().ok_or_else(|| bad("url"))?;
+        if url != format!("{}{}", Self::base_url(rqctx), rqctx.request.uri()) {
+            return Err(bad("url"));
+        }
+        let nonce = header["nonce"].as_str().ok_or_else(|| bad("nonce"))?;
+        let mut inner = self.inner.lock().unwrap();
+        let Some(index) = inner.nonces.iter().position(|n| n == nonce) else {
+            return Err(bad("nonce"));
+        };
+        inner.nonces.remove(index);
+
+        let (account, jwk) = if let Some(kid) = header["kid"].as_str() {
+            let account = kid
+                .rsplit_once('/')
+                .and_then(|(_, id)| id.parse::<usize>().ok())
+                .filter(|id| *id < inner.accounts.len())
+                .ok_or_else(|| bad("kid"))?;
+            (Some(account), inner.accounts[account].clone())
+        } else {
+            let jwk = &header["jwk"];
+            let coordinate = |c: &str| {
+                jwk[c].as_str().map(String::from).ok_or_else(|| bad("jwk"))
+            };
+            (None, (coordinate("x")?, coordinate("y")?))
+        };
+        drop(inner);
+
+        let decode = |s: &str| URL_SAFE_NO_PAD.decode(s).ok();
+        let coordinate =
+            |c: &str| decode(c).and_then(|c| BigNum::from_slice(&c).ok());
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+        let key = coordinate(&jwk.0)
+            .zip(coordinate(&jwk.1))
+            .and_then(|(x, y)| {
+                EcKey::from_public_key_affine_coordinates(&group, &x, &y).ok()
+            })
+            .ok_or_else(|| bad("jwk"))?;
+        let signature = decode(&signature)
+            .filter(|s| s.len() == 64)
+            .and_then(|s| {
+                let r = BigNum::from_slice(&s[..32]).ok()?;
+                let s = BigNum::from_slice(&s[32..]).ok()?;
+                EcdsaSig::from_private_components(r, s).ok()
+            })
+            .ok_or_else(|| bad("signature"))?;
+        let digest = hash(
+            MessageDigest::sha256(),
+            format!("{protected}.{payload}").as_bytes(),
+        )
+        .unwrap();
+        if !signature.verify(&digest, &key).unwrap_or(false) {
+            return Err(bad("signature"));
+        }
+
+        let payload = if payload.is_empty() {
+            None
+        } else {
+            Some(
+                decode(&payload)
+                    .and_then(|p| serde_json::from_slice(&p).ok())
+                    .ok_or_else(|| bad("payload"))?,
+            )
+        };
+        Ok(Verified { account, jwk, payload })
+    }
+
+    /// Checks that a request was signed by an account, returning it
+    fn account(verified: &Verified) -> Result<usize, HttpError> {
+        verified.account.ok_or_else(|| {
+            HttpError::for_bad_request(None, String::from("no account"))
+        })
+    }
+
+    fn order_json(
+        rqctx: &RequestContext<Arc<AcmeServer>>,
+        inner: &AcmeServerInner,
+        id: usize,
+    ) -> serde_json::Value {
+        let base = Self::base_url(rqctx);
+        let order = &inner.orders[id];
+        let mut json = json!({
+            "status": order.status,
+            "identifiers": order.identifiers.iter().map(|name| {
+                json!({ "type": "dns", "value": name })
+            }).collect::<Vec<_>>(),
+            "authorizations": order.authorizations.iter().map(|a| {
+                format!("{base}/authz/{a}")
+            }).collect::<Vec<_>>(),
+            "finalize": format!("{base}/finalize/{id}"),
+        });
+        if let Some(certificate) = order.certificate {
+            json["certificate"] = json!(format!("{base}/cert/{certificate}"));
+        }
+        json
+    }
+
+    fn authorization_json(
+        rqctx: &RequestContext<Arc<AcmeServer>>,
+        inner: &AcmeServerInner,
+        id: usize,
+    ) -> serde_json::Value {
+        let authorization = &inner.authorizations[id];
+        json!({
+            "status": authorization.status,
+            "identifier": { "type": "dns", "value": authorization.identifier },
+            "challenges": ["http-01", "dns-01"].map(|kind| {
+                Self::challenge_json(rqctx, inner, id, kind)
+            }),
+        })
+    }
+
+    fn challenge_json(
+        rqctx: &RequestContext<Arc<AcmeServer>>,
+        inner: &AcmeServerInner,
+        id: usize,
+        kind: &str,
+    ) -> serde_json::Value {
+        let authorization = &inner.authorizations[id];
+        json!({
+            "type": kind,
+            "url": format!("{}/challenge/{id}/{kind}", Self::base_url(rqctx)),
+            "token": authorization.token,
+            "status": authorization.status,
+        })
+    }
+
+    fn not_found() -> HttpError {
+        HttpError::for_not_found(None, String::from("no such resource"))
+    }
+}
+
+/// Returns the RFC 7638 thumbprint of a P-256 key
+fn thumbprint((x, y): &(String, String)) -> String {
+    let jwk = format!(r#"{{"crv":"P-256","kty":"EC","x":"{x}","y":"{y}"}}"#);
+    URL_SAFE_NO_PAD
+        .encode(hash(MessageDigest::sha256(), jwk.as_bytes()).unwrap())
+}
+
+#[endpoint {
+    method = GET,
+    path = "/directory",
+}]
+async fn acme_directory(
+    rqctx: RequestContext<Arc<AcmeServer>>,
+) -> Result<Response<Body>, HttpError> {
+    let base = AcmeServer::base_url(&rqctx);
+    Ok(rqctx.context().json(
+        None,
+        json!({
+            "newNonce": format!("{base}/new-nonce"),
+            "newAccount": format!("{base}/new-account"),
+            "newOrder": format!("{base}/new-order"),
+        }),
+    ))
+}
+
+#[endpoint {
+    method = HEAD,
+    path = "/new-nonce",
+}]
+async fn acme_new_nonce(
+    rqctx: RequestContext<Arc<AcmeServer>>,
+) -> Result<Response<Body>, HttpError> {
+    Ok(rqctx.context().response(
+        StatusCode::OK,
+        None,
+        "application/octet-stream",
+        String::new(),
+    ))
+}
+
+#[endpoint {
+    method = POST,
+    path = "/new-account",
+}]
+async fn acme_new_account(
+    rqctx: RequestContext<Arc<AcmeServer>>,
+    body: UntypedBody,
+) -> Result<Response<Body>, HttpError> {
+    let server = rqctx.context();
+    let verified = server.verify(&rqctx, body)?;
+    let id = {
+        let mut inner = server.inner.lock().unwrap();
+        inner.accounts.push(verified.jwk);
+        inner.accounts.len() - 1
+    };
+    Ok(server.response(
+        StatusCode::CREATED,
+        Some(format!("{}/account/{id}", AcmeServer::base_url(&rqctx))),
+        "application/json",
+        json!({ "status": "valid" }).to_string(),
+    ))
+}
+
+#[endpoint {
+    method = POST,
+    path = "/new-order",
+}]
+async fn acme_new_order(
+    rqctx: RequestContext<Arc<AcmeServer>>,
+    body: UntypedBody,
+) -> Result<Response<Body>, HttpError> {
+    let server = rqctx.context();
+    let verified = server.verify(&rqctx, body)?;
+    let account = AcmeServer::account(&verified)?;
+    let identifiers: Vec<String> = verified
+        .payload
+        .as_ref()
+        .and_then(|p| p["identifiers"].as_array())
+        .map(|identifiers| {
+            identifiers
+                .iter()
+                .filter_map(|i| i["value"].as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default();
+    if identifiers.is_empty() {
+        return Err(HttpError::for_bad_request(
+            None,
+            String::from("no identifiers"),
+        ));
+    }
+
+    let mut inner = server.inner.lock().unwrap();
+    let authorizations = identifiers
+        .iter()
+        .map(|identifier| {
+            inner.authorizations.push(AcmeAuthorization {
+                account,
+                identifier: identifier.clone(),
+                token: Uuid::new_v4().simple().to_string(),
+                status: "pending",
+                validated_with: None,
+            });
+            inner.authorizations.len() - 1
+        })
+        .collect();
+    inner.orders.push(AcmeOrder {
+        account,
+        status: "pending",
+        identifiers,
+        authorizations,
+        certificate: None,
+    });
+    let id = inner.orders.len() - 1;
+    let order = AcmeServer::order_json(&rqctx, &inner, id);
+    drop(inner);
+    Ok(server.response(
+        StatusCode::CREATED,
+        Some(format!("{}/order/{id}", AcmeServer::base_url(&rqctx))),
+        "application/json",
+        order.to_string(),
+    ))
+}
+
+#[endpoint {
+    method = POST,
+    path = "/authz/{id}",
+}]
+async fn acme_authorization(
+    rqctx: RequestContext<Arc<AcmeServer>>,
+    path: Path<IdPath>,
+    body: UntypedBody,
+) -> Result<Response<Body>, HttpError> {
+    let server = rqctx.context();
+    let verified = server.verify(&rqctx, body)?;
+    let account = AcmeServer::account(&verified)?;
+    let id = path.into_inner().id;
+    let inner = server.inner.lock().unwrap();
+    if inner.authorizations.get(id).is_none_or(|a| a.account != account) {
+        return Err(AcmeServer::not_found());
+    }
+    let authorization = AcmeServer::authorization_json(&rqctx, &inner, id);
+    drop(inner);
+    Ok(server.json(None, authorization))
+}
+
+/// Validates a challenge, fetching it from Nexus (HTTP-01) or looking it up in
+/// the external DNS server (DNS-01), before replying
+#[endpoint {
+    method = POST,
+    path = "/challenge/{id}/{kind}",
+}]
+async fn acme_challenge(
+    rqctx: RequestContext<Arc<AcmeServer>>,
+    path: Path<ChallengePath>,
+    body: UntypedBody,
+) -> Result<Response<Body>, HttpError> {
+    let server = rqctx.context();
+    let verified = server.verify(&rqctx, body)?;
+    let account = AcmeServer::account(&verified)?;
+    let ChallengePath { id, kind } = path.into_inner();
+    let kind = match kind.as_str() {
+        "http-01" => "http-01",
+        "dns-01" => "dns-01",
+        _ => return Err(AcmeServer::not_found()),
+    };
+    let (identifier, token) = {
+        let inner = server.inner.lock().unwrap();
+        match inner.authorizations.get(id) {
+            Some(a) if a.account == account => {
+                (a.identifier.clone(), a.token.clone())
+            }
+            _ => return Err(AcmeServer::not_found()),
+        }
+    };
+
+    let expected = format!("{token}.{}", thumbprint(&verified.jwk));
+    let valid = if kind == "http-01" {
+        match wait_for_address(&server.nexus_address).await {
+            Some(address) => {
+                let response = reqwest::Client::new()
+                    .get(format!(
+                        "http://{address}/.well-known/acme-challenge/{token}"
+                    ))
+                    .header(header::HOST, &identifier)
+                    .send()
+                    .await;
+                match response {
+                    Ok(response) if response.status() == StatusCode::OK => {
+                        response.text().await.is_ok_and(|body| body == expected)
+                    }
+                    _ => false,
+                }
+            }
+            None => false,
+        }
+    } else {
+        let expected = URL_SAFE_NO_PAD.encode(
+            hash(MessageDigest::sha256(), expected.as_bytes()).unwrap(),
+        );
+        match wait_for_address(&server.external_dns_address).await {
+            Some(address) => {
+                txt_lookup(address, &format!("_acme-challenge.{identifier}"))
+                    .await
+                    .contains(&expected)
+            }
+            None => false,
+        }
+    };
+
+    let mut inner = server.inner.lock().unwrap();
+    inner.authorizations[id].status = if valid { "valid" } else { "invalid" };
+    if valid {
+        inner.authorizations[id].validated_with = Some(kind);
+    }
+    for order in &mut inner.orders {
+        if order.status == "pending" && order.authorizations.contains(&id) {
+            order.status = if valid { "pending" } else { "invalid" };
+        }
+    }
+    let orders: Vec<_> = inner
+        .orders
+        .iter()
+        .enumerate()
+        .filter(|(_, o)| o.status == "pending")
+        .map(|(i, o)| (i, o.authorizations.clone()))
+        .collect();
+    for (order, authorizations) in orders {
+        if authorizations
+            .iter()
+            .all(|a| inner.authorizations[*a].status == "valid")
+        {
+            inner.orders[order].status = "ready";
+        }
+    }
+    let challenge = AcmeServer::challenge_json(&rqctx, &inner, id, kind);
+    drop(inner);
+    Ok(server.json(None, challenge))
+}
+
+/// Waits for Nexus to have started and `address` to have been filled in
+async fn wait_for_address(
+    address: &Mutex<Option<SocketAddr>>,
+) -> Option<SocketAddr> {
+    for _ in 0..600 {
+        if let Some(address) = *address.lock().unwrap() {
+            return Some(address);
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    None
+}
+
+/// Returns the TXT records of `name` served by the DNS server at `address`
+async fn txt_lookup(address: SocketAddr, name: &str) -> Vec<String> {
+    let mut config = ResolverConfig::new();
+    config.add_name_server(NameServerConfig::new(address, Protocol::Udp));
+    let resolver = TokioResolver::builder_with_config(
+        config,
+        TokioConnectionProvider::default(),
+    )
+    .build();
+    match resolver.txt_lookup(name).await {
+        Ok(lookup) => lookup
+            .iter()
+            .map(|txt| {
+                txt.txt_data()
+                    .iter()
+                    .map(|data| String::from_utf8_lossy(data))
+                    .collect()
+            })
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Issues the certificate for a ready order, copying the CSR's extensions
+#[endpoint {
+    method = POST,
+    path = "/finalize/{id}",
+}]
+async fn acme_finalize(
+    rqctx: RequestContext<Arc<AcmeServer>>,
+    path: Path<IdPath>,
+    body: UntypedBody,
+) -> Result<Response<Body>, HttpError> {
+    let server = rqctx.context();
+    let verified = server.verify(&rqctx, body)?;
+    let account = AcmeServer::account(&verified)?;
+    let id = path.into_inner().id;
+    let csr = verified
+        .payload
+        .as_ref()
+        .and_then(|p| p["csr"].as_str())
+        .and_then(|csr| URL_SAFE_NO_PAD.decode(csr).ok())
+        .and_then(|csr| X509Req::from_der(&csr).ok())
+        .ok_or_else(|| {
+            HttpError::for_bad_request(None, String::from("bad CSR"))
+        })?;
+    let public_key = csr.public_key().unwrap();
+    assert!(csr.verify(&public_key).unwrap(), "CSR signature is invalid");
+
+    let mut inner = server.inner.lock().unwrap();
+    match inner.orders.get(id) {
+        Some(o) if o.account == account && o.status == "ready" => (),
+        _ => return Err(AcmeServer::not_found()),
+    }
+    let serial = u32::try_from(inner.certificates.len() + 1).unwrap();
+    let mut builder = X509::builder().unwrap();
+    builder.set_version(2).unwrap();
+    builder
+        .set_serial_number(
+            &BigNum::from_u32(serial).unwrap().to_asn1_integer().unwrap(),
+        )
+        .unwrap();
+    builder.set_subject_name(csr.subject_name()).unwrap();
+    builder.set_issuer_name(server.ca_cert.subject_name()).unwrap();
+    builder.set_pubkey(&public_key).unwrap();
+    builder.set_not_before(&Asn1Time::days_from_now(0).unwrap()).unwrap();
+    builder.set_not_after(&Asn1Time::days_from_now(90).unwrap()).unwrap();
+    for extension in csr.extensions().unwrap() {
+        builder.append_extension(extension).unwrap();
+    }
+    builder.sign(&server.ca_key, MessageDigest::sha256()).unwrap();
+    let certificate = builder.build();
+
+    // The certificate must cover exactly the names that were authorized.
+    let mut names: Vec<String> = certificate
+        .subject_alt_names()
+        .unwrap()
+        .iter()
+        .filter_map(|n| n.dnsname().map(String::from))
+        .collect();
+    names.sort();
+    let mut identifiers = inner.orders[id].identifiers.clone();
+    identifiers.sort();
+    assert_eq!(names, identifiers);
+
+    let mut chain = certificate.to_pem().unwrap();
+    chain.extend(server.ca_cert.to_pem().unwrap());
+    inner.certificates.push(String::from_utf8(chain).unwrap());
+    inner.orders[id].certificate = Some(inner.certificates.len() - 1);
+    inner.orders[id].status = "valid";
+    let order = AcmeServer::order_json(&rqctx, &inner, id);
+    drop(inner);
+    Ok(server.json(None, order))
+}
+
+#[endpoint {
+    method = POST,
+    path = "/order/{id}",
+}]
+async fn acme_order(
+    rqctx: RequestContext<Arc<AcmeServer>>,
+    path: Path<IdPath>,
+    body: UntypedBody,
+) -> Result<Response<Body>, HttpError> {
+    let server = rqctx.context();
+    let verified = server.verify(&rqctx, body)?;
+    let account = AcmeServer::account(&verified)?;
+    let id = path.into_inner().id;
+    let inner = server.inner.lock().unwrap();
+    if inner.orders.get(id).is_none_or(|o| o.account != account) {
+        return Err(AcmeServer::not_found());
+    }
+    let order = AcmeServer::order_json(&rqctx, &inner, id);
+    drop(inner);
+    Ok(server.json(None, order))
+}
+
+#[endpoint {
+    method = POST,
+    path = "/cert/{id}",
+}]
+async fn acme_certificate(
+    rqctx: RequestContext<Arc<AcmeServer>>,
+    path: Path<IdPath>,
+    body: UntypedBody,
+) -> Result<Response<Body>, HttpError> {
+    let server = rqctx.context();
+    server.verify(&rqctx, body)?;
+    let id = path.into_inner().id;
+    let chain = server
+        .inner
+        .lock()
+        .unwrap()
+        .certificates
+        .get(id)
+        .cloned()
+        .ok_or_else(AcmeServer::not_found)?;
+    Ok(server.response(
+        StatusCode::OK,
+        None,
+        "application/pem-certificate-chain",
+        chain,
+    ))
+}
+
+fn start_acme_server(
+    log: &slog::Logger,
+) -> dropshot::HttpServer<Arc<AcmeServer>> {
+    let mut api = ApiDescription::new();
+    api.register(acme_directory).unwrap();
+    api.register(acme_new_nonce).unwrap();
+    api.register(acme_new_account).unwrap();
+    api.register(acme_new_order).unwrap();
+    api.register(acme_authorization).unwrap();
+    api.register(acme_challenge).unwrap();
+    api.register(acme_finalize).unwrap();
+    api.register(acme_order).unwrap();
+    api.register(acme_certificate).unwrap();
+    let config = dropshot::ConfigDropshot {
+        bind_address: "127.0.0.1:0".parse().unwrap(),
+        default_request_body_max_bytes: 1024 * 1024,
+        ..Default::default()
+    };
+    dropshot::ServerBuilder::new(
+        api,
+        Arc::new(AcmeServer::new()),
+        log.new(o!("component" => "acme-server")),
+    )
+    .config(config)
+    .start()
+    .unwrap()
+}
+
+async fn run_acme_certificates(
+    cptestctx: &ControlPlaneTestContext,
+) -> AcmeCertificatesStatus {
+    let task = activate_background_task(
+        &cptestctx.lockstep_client,
+        "acme_certificates",
+    )
+    .await;
+    let LastResult::Completed(last) = task.last else {
+        panic!(
+            "unexpected {:?} returned from acme_certificates task",
+            task.last
+        );
+    };
+    serde_json::from_value(last.details).unwrap()
+}
+
+/// Returns the IDs of certificates that Nexus has obtained from the ACME
+/// server and not yet deleted
+async fn acme_certificate_ids(
+    cptestctx: &ControlPlaneTestContext,
+) -> Vec<Uuid> {
+    let nexus = &cptestctx.server.server_context().nexus;
+    let datastore = nexus.datastore();
+    let opctx =
+        OpContext::for_tests(cptestctx.logctx.log.new(o!()), datastore.clone());
+    datastore
+        .acme_certificate_list_all_batched(&opctx)
+        .await
+        .unwrap()
+        .iter()
+        .map(|c| c.id())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_acme_certificates() {
+    let logctx =
+        omicron_test_utils::dev::test_setup_log("test_acme_certificates");
+    let acme_server = start_acme_server(&logctx.log);
+    let directory_url =
+        format!("http://{}/directory", acme_server.local_addr());
+
+    let cptestctx =
+        nexus_test_utils::ControlPlaneBuilder::new("test_acme_certificates")
+            .customize_nexus_config(&|config| {
+                let acme = &mut config.pkg.background_tasks.acme_certificates;
+                acme.directory_url = Some(directory_url.clone());
+                acme.contact = vec![String::from("mailto:ops@example.com")];
+                // The stand-in issues certificates valid for 90 days, so
+                // this makes every activation renew them.
+                acme.renew_before_days = 120;
+            })
+            .start::<omicron_nexus::Server>()
+            .await;
+    *acme_server.app_private().nexus_address.lock().unwrap() =
+        Some(cptestctx.external_client.bind_address);
+
+    // Nexus obtains a certificate for the test Silo, covering its name in the
+    // external DNS zone.  (It may already have done so when the task first
+    // ran at startup, in which case this replaces that certificate.)
+    let status = run_acme_certificates(&cptestctx).await;
+    assert!(status.enabled);
+    assert_eq!(status.error, None);
+    assert!(status.silo_errors.is_empty(), "{:?}", status.silo_errors);
+    assert_eq!(status.issued.len(), 1);
+    let first = &status.issued[0];
+    assert_eq!(first.silo_name, cptestctx.silo_name.to_string());
+    assert!(first.certificate_name.starts_with("acme-"));
+    assert_eq!(
+        acme_certificate_ids(&cptestctx).await,
+        vec![first.certificate_id]
+    );
+
+    let chain = acme_server
+        .app_private()
+        .inner
+        .lock()
+        .unwrap()
+        .certificates
+        .last()
+        .cloned()
+        .unwrap();
+    let leaf = X509::stack_from_pem(chain.as_bytes()).unwrap().remove(0);
+    let names: Vec<String> = leaf
+        .subject_alt_names()
+        .unwrap()
+        .iter()
+        .filter_map(|n| n.dnsname().map(String::from))
+        .collect();
+    assert_eq!(
+        names,
+        vec![format!(
+            "{}.sys.{}",
+            cptestctx.silo_name, cptestctx.external_dns_zone_name
+        )]
+    );
+
+    // The next activation renews the certificate, deleting the old one, and
+    // reuses the account registered the first time.
+    let status = run_acme_certificates(&cptestctx).await;
+    assert_eq!(status.error, None);
+    assert!(status.silo_errors.is_empty(), "{:?}", status.silo_errors);
+    assert_eq!(status.issued.len(), 1);
+    assert_eq!(status.superseded_deleted, 1);
+    let second = &status.issued[0];
+    assert_ne!(second.certificate_id, first.certificate_id);
+    assert_eq!(
+        acme_certificate_ids(&cptestctx).await,
+        vec![second.certificate_id]
+    );
+    assert_eq!(
+        acme_server.app_private().inner.lock().unwrap().accounts.len(),
+        1
+    );
+    assert!(
+        acme_server
+            .app_private()
+            .inner
+            .lock()
+            .unwrap()
+            .authorizations
+            .iter()
+            .all(|a| a.validated_with == Some("http-01")),
+        "Nexus answered a challenge other than HTTP-01"
+    );
+
+    // Challenges are removed once they've been validated, and unknown tokens
+    // aren't found.
+    let token = acme_server.app_private().inner.lock().unwrap().authorizations
+        [0]
+    .token
+    .clone();
+    for token in [token.as_str(), "bogus"] {
+        RequestBuilder::new(
+            &cptestctx.external_client,
+            http::Method::GET,
+            &format!("/.well-known/acme-challenge/{token}"),
+        )
+        .expect_status(Some(StatusCode::NOT_FOUND))
+        .execute()
+        .await
+        .unwrap();
+    }
+
+    cptestctx.teardown().await;
+    acme_server.close().await.unwrap();
+    logctx.cleanup_successful();
+}
+
+#[tokio::test]
+async fn test_acme_certificates_dns01() {
+    let logctx =
+        omicron_test_utils::dev::test_setup_log("test_acme_certificates_dns01");
+    let acme_server = start_acme_server(&logctx.log);
+    let directory_url =
+        format!("http://{}/directory", acme_server.local_addr());
+
+    let cptestctx = nexus_test_utils::ControlPlaneBuilder::new(
+        "test_acme_certificates_dns01",
+    )
+    .customize_nexus_config(&|config| {
+        let acme = &mut config.pkg.background_tasks.acme_certificates;
+        acme.directory_url = Some(directory_url.clone());
+        acme.renew_before_days = 120;
+        acme.challenge = AcmeChallengeType::Dns01;
+    })
+    .start::<omicron_nexus::Server>()
+    .await;
+    let external_dns_address =
+        cptestctx.external_dns.dns_server.local_address();
+    *acme_server.app_private().external_dns_address.lock().unwrap() =
+        Some(external_dns_address);
+
+    // Nexus obtains a certificate for the test Silo, proving control of its
+    // name with a TXT record in the external DNS zone.
+    let status = run_acme_certificates(&cptestctx).await;
+    assert_eq!(status.error, None);
+    assert!(status.silo_errors.is_empty(), "{:?}", status.silo_errors);
+    assert_eq!(status.issued.len(), 1);
+    assert_eq!(
+        acme_certificate_ids(&cptestctx).await,
+        vec![status.issued[0].certificate_id]
+    );
+    {
+        let inner = acme_server.app_private().inner.lock().unwrap();
+        let authorization = inner.authorizations.last().unwrap();
+        assert_eq!(authorization.status, "valid");
+        assert_eq!(authorization.validated_with, Some("dns-01"));
+    }
+
+    // The TXT record is withdrawn once the challenge has been validated.
+    let name = format!(
+        "_acme-challenge.{}.sys.{}",
+        cptestctx.silo_name, cptestctx.external_dns_zone_name
+    );
+    wait_for_condition(
+        || async {
+            if txt_lookup(external_dns_address, &name).await.is_empty() {
+                Ok(())
+            } else {
+                Err(CondCheckError::<()>::NotYet { status: None })
+            }
+        },
+        &Duration::from_millis(50),
+        &Duration::from_secs(30),
+    )
+    .await
+    .expect("ACME challenge was not removed from external DNS");
+
+    cptestctx.teardown().await;
+    acme_server.close().await.unwrap();
+    logctx.cleanup_successful();
+}
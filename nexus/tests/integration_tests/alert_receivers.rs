@@ -0,0 +1,571 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! SMTP and syslog alert receivers
+//!
+//! Webhook receivers, and operations common to all alert receivers, are
+//! tested in the `webhooks` module.
+
+use dropshot::test_util::ClientTestContext;
+use nexus_db_queries::context::OpContext;
+use nexus_test_utils::background::activate_background_task;
+use nexus_test_utils::http_testing::AuthnMode;
+use nexus_test_utils::http_testing::NexusRequest;
+use nexus_test_utils::http_testing::RequestBuilder;
+use nexus_test_utils::resource_helpers;
+use nexus_test_utils_macros::nexus_test;
+use nexus_types::alert::test_alerts;
+use nexus_types::external_api::alert::{
+    AlertDelivery, AlertDeliveryAttempts, AlertProbeResult, AlertReceiver,
+    AlertReceiverKind, SmtpCreate, SmtpDeliveryAttemptResult, SmtpReceiver,
+    SmtpReceiverUpdate, SyslogCreate, SyslogFacility, SyslogReceiver,
+    SyslogTransport, WebhookCreate, WebhookReceiver,
+};
+use omicron_common::api::external::IdentityMetadataCreateParams;
+use omicron_common::api::external::IdentityMetadataUpdateParams;
+use omicron_uuid_kinds::AlertUuid;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+use tokio::net::TcpListener;
+use tokio::net::UdpSocket;
+use tokio::sync::mpsc;
+use tokio::sync::oneshot;
+
+type ControlPlaneTestContext =
+    nexus_test_utils::ControlPlaneTestContext<omicron_nexus::Server>;
+
+const ALERT_RECEIVERS_BASE_PATH: &str = "/v1/alert-receivers";
+const SMTP_RECEIVERS_BASE_PATH: &str = "/v1/smtp-receivers";
+const SYSLOG_RECEIVERS_BASE_PATH: &str = "/v1/syslog-receivers";
+const WEBHOOK_RECEIVERS_BASE_PATH: &str = "/v1/webhook-receivers";
+
+/// How long to wait for a fake server to receive a message.
+const RECV_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A message received by [`fake_smtp_server`].
+#[derive(Debug)]
+struct ReceivedMail {
+    mail_from: String,
+    rcpt_to: Vec<String>,
+    data: String,
+}
+
+/// Starts a fake SMTP server, which handles transactions until the test ends.
+///
+/// The server accepts every message, unless `reject_rcpt` is set, in which
+/// case it rejects every recipient with that reply code.
+async fn fake_smtp_server(
+    reject_rcpt: Option<u16>,
+) -> (u16, mpsc::UnboundedReceiver<ReceivedMail>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let (tx, rx) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        loop {
+            let Ok((stream, _)) = listener.accept().await else {
+                return;
+            };
+            let tx = tx.clone();
+            tokio::spawn(async move {
+                let mut stream = BufReader::new(stream);
+                let mut mail = ReceivedMail {
+                    mail_from: String::new(),
+                    rcpt_to: Vec::new(),
+                    data: String::new(),
+                };
+                stream.write_all(b"220 fake ESMTP\r\n").await.unwrap();
+                let mut line = String::new();
+                loop {
+                    line.clear();
+                    if stream.read_line(&mut line).await.unwrap() == 0 {
+                        return;
+                    }
+                    let command = line.trim_end();
+                    let reply = if command.starts_with("EHLO ") {
+                        "250-fake greets you\r\n250 8BITMIME\r\n".to_string()
+                    } else if let Some(from) =
+                        command.strip_prefix("MAIL FROM:")
+                    {
+                        mail.mail_from = from.to_string();
+                        "250 OK\r\n".to_string()
+                    } else if let Some(to) = command.strip_prefix("RCPT TO:") {
+                        if let Some(code) = reject_rcpt {
+                            format!("{code} no such user\r\n")
+                        } else {
+                            mail.rcpt_to.push(to.to_string());
+                            "250 OK\r\n".to_string()
+                        }
+                    } else if command == "DATA" {
+                        stream.write_all(b"354 go ahead\r\n").await.unwrap();
+                        loop {
+                            line.clear();
+                            stream.read_line(&mut line).await.unwrap();
+                            if line == ".\r\n" {
+                                break;
+                            }
+                            mail.data.push_str(&line);
+                        }
+                        "250 queued\r\n".to_string()
+                    } else if command == "QUIT" {
+                        stream.write_all(b"221 bye\r\n").await.unwrap();
+                        let _ = tx.send(mail);
+                        return;
+                    } else {
+                        "502 unrecognized\r\n".to_string()
+                    };
+                    stream.write_all(reply.as_bytes()).await.unwrap();
+                }
+            });
+        }
+    });
+    (port, rx)
+}
+
+/// What [`fake_starttls_server`] saw of a transaction.
+#[derive(Debug)]
+struct StartTlsTranscript {
+    /// The commands the client sent before the TLS handshake.
+    plaintext_commands: Vec<String>,
+    /// Whether the TLS handshake completed.
+    handshake_completed: bool,
+}
+
+/// Starts a fake SMTP server which offers STARTTLS, but presents a
+/// self-signed certificate that clients can't verify, and handles a single
+/// transaction.
+async fn fake_starttls_server() -> (u16, oneshot::Receiver<StartTlsTranscript>)
+{
+    let cert =
+        rcgen::generate_simple_self_signed(vec!["127.0.0.1".to_string()])
+            .unwrap();
+    let config = rustls::ServerConfig::builder()
+        .with_no_client_auth()
+        .with_single_cert(
+            vec![rustls::pki_types::CertificateDer::from(
+                cert.serialize_der().unwrap(),
+            )],
+            rustls::pki_types::PrivateKeyDer::Pkcs8(
+                cert.serialize_private_key_der().into(),
+            ),
+        )
+        .unwrap();
+    let acceptor = tokio_rustls::TlsAcceptor::from(Arc::new(config));
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let (tx, rx) = oneshot::channel();
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut stream = BufReader::new(stream);
+        let mut transcript = StartTlsTranscript {
+            plaintext_commands: Vec::new(),
+            handshake_completed: false,
+        };
+        stream.write_all(b"220 fake ESMTP\r\n").await.unwrap();
+        let mut line = String::new();
+        loop {
+            line.clear();
+            match stream.read_line(&mut line).await {
+                Ok(0) | Err(_) => break,
+                Ok(_) => (),
+            }
+            let command = line.trim_end().to_string();
+            transcript.plaintext_commands.push(command.clone());
+            let reply: &[u8] = if command.starts_with("EHLO ") {
+                b"250-fake greets you\r\n250 STARTTLS\r\n"
+            } else if command == "STARTTLS" {
+                stream.write_all(b"220 go ahead\r\n").await.unwrap();
+                transcript.handshake_completed =
+                    acceptor.accept(stream.into_inner()).await.is_ok();
+                break;
+            } else {
+                b"530 must issue STARTTLS first\r\n"
+            };
+            stream.write_all(reply).await.unwrap();
+        }
+        let _ = tx.send(transcript);
+    });
+    (port, rx)
+}
+
+fn smtp_params(port: u16) -> SmtpCreate {
+    SmtpCreate {
+        identity: IdentityMetadataCreateParams {
+            name: "my-great-mailbox".parse().unwrap(),
+            description: String::from("my great mailbox"),
+        },
+        host: "127.0.0.1".to_string(),
+        port,
+        from: "rack@example.com".to_string(),
+        to: vec![
+            "oncall@example.com".to_string(),
+            "noc@example.com".to_string(),
+        ],
+        subscriptions: vec!["test.foo".parse().unwrap()],
+    }
+}
+
+async fn smtp_create(
+    ctx: &ControlPlaneTestContext,
+    params: &SmtpCreate,
+) -> SmtpReceiver {
+    resource_helpers::object_create::<SmtpCreate, SmtpReceiver>(
+        &ctx.external_client,
+        SMTP_RECEIVERS_BASE_PATH,
+        params,
+    )
+    .await
+}
+
+async fn syslog_create(
+    ctx: &ControlPlaneTestContext,
+    params: &SyslogCreate,
+) -> SyslogReceiver {
+    resource_helpers::object_create::<SyslogCreate, SyslogReceiver>(
+        &ctx.external_client,
+        SYSLOG_RECEIVERS_BASE_PATH,
+        params,
+    )
+    .await
+}
+
+async fn alert_deliveries_list(
+    client: &ClientTestContext,
+    rx: &str,
+) -> Vec<AlertDelivery> {
+    NexusRequest::iter_collection_authn(
+        client,
+        &format!("{ALERT_RECEIVERS_BASE_PATH}/{rx}/deliveries"),
+        "",
+        None,
+    )
+    .await
+    .unwrap()
+    .all_items
+}
+
+async fn publish_test_foo(ctx: &ControlPlaneTestContext) -> AlertUuid {
+    let nexus = ctx.server.server_context().nexus.clone();
+    let datastore = nexus.datastore();
+    let opctx =
+        OpContext::for_tests(ctx.logctx.log.new(o!()), datastore.clone());
+    let id = AlertUuid::new_v4();
+    nexus
+        .alert_publish(
+            &opctx,
+            id,
+            &test_alerts::Foo(serde_json::json!({"hello_world": true})),
+        )
+        .await
+        .expect("event should be published successfully");
+    dbg!(
+        activate_background_task(&ctx.lockstep_client, "alert_dispatcher")
+            .await
+    );
+    dbg!(
+        activate_background_task(&ctx.lockstep_client, "webhook_deliverator")
+            .await
+    );
+    id
+}
+
+/// Returns a client which does not send an `api-version` header, so that
+/// tests can pick their own.
+fn unversioned_client(ctx: &ControlPlaneTestContext) -> ClientTestContext {
+    ClientTestContext::new(
+        ctx.server.get_http_server_external_address(),
+        ctx.logctx.log.clone(),
+    )
+}
+
+#[nexus_test]
+async fn test_smtp_receiver_crud(cptestctx: &ControlPlaneTestContext) {
+    let client = &cptestctx.external_client;
+
+    let created = smtp_create(&cptestctx, &smtp_params(2525)).await;
+    assert_eq!(created.config.host, "127.0.0.1");
+    assert_eq!(created.config.port, 2525);
+
+    // The receiver is included in the list of all receivers, with its
+    // SMTP-specific configuration.
+    let rxs = resource_helpers::objects_list_page_authz::<AlertReceiver>(
+        client,
+        ALERT_RECEIVERS_BASE_PATH,
+    )
+    .await
+    .items;
+    assert_eq!(rxs, vec![AlertReceiver::from(created.clone())]);
+
+    // Update it.
+    NexusRequest::new(
+        RequestBuilder::new(
+            client,
+            http::Method::PUT,
+            &format!("{SMTP_RECEIVERS_BASE_PATH}/my-great-mailbox"),
+        )
+        .body(Some(&SmtpReceiverUpdate {
+            identity: IdentityMetadataUpdateParams {
+                name: None,
+                description: None,
+            },
+            host: Some("mail.example.com".to_string()),
+            port: None,
+            from: None,
+            to: Some(vec!["boss@example.com".to_string()]),
+        }))
+        .expect_status(Some(http::StatusCode::NO_CONTENT)),
+    )
+    .authn_as(AuthnMode::PrivilegedUser)
+    .execute()
+    .await
+    .unwrap();
+    let rx: AlertReceiver = NexusRequest::object_get(
+        client,
+        &format!("{ALERT_RECEIVERS_BASE_PATH}/my-great-mailbox"),
+    )
+    .authn_as(AuthnMode::PrivilegedUser)
+    .execute_and_parse_unwrap()
+    .await;
+    let AlertReceiverKind::Smtp(config) = rx.kind else {
+        panic!("expected an SMTP receiver, found {rx:?}");
+    };
+    assert_eq!(config.host, "mail.example.com");
+    assert_eq!(config.port, 2525);
+    assert_eq!(config.to, vec!["boss@example.com".to_string()]);
+
+    // Malformed addresses are rejected.
+    let mut params = smtp_params(25);
+    params.identity.name = "bad-mailbox".parse().unwrap();
+    params.to = vec!["oncall@example.com\r\nBcc: evil@example.com".to_string()];
+    let error = resource_helpers::object_create_error(
+        client,
+        SMTP_RECEIVERS_BASE_PATH,
+        &params,
+        http::StatusCode::BAD_REQUEST,
+    )
+    .await;
+    assert!(
+        dbg!(&error).message.contains("is not a valid email address"),
+        "unexpected error message: {}",
+        error.message,
+    );
+
+    // SMTP receivers can't be updated as webhooks, and vice versa.
+    let error = NexusRequest::expect_failure_with_body(
+        client,
+        http::StatusCode::BAD_REQUEST,
+        http::Method::PUT,
+        &format!("{WEBHOOK_RECEIVERS_BASE_PATH}/my-great-mailbox"),
+        &serde_json::json!({}),
+    )
+    .authn_as(AuthnMode::PrivilegedUser)
+    .execute()
+    .await
+    .unwrap()
+    .parsed_body::<dropshot::HttpErrorResponseBody>()
+    .unwrap();
+    assert!(
+        error.message.contains("is a smtp receiver, not a webhook receiver"),
+        "unexpected error message: {}",
+        error.message,
+    );
+}
+
+#[nexus_test]
+async fn test_smtp_event_delivery(cptestctx: &ControlPlaneTestContext) {
+    let client = &cptestctx.external_client;
+    let (port, mut mail) = fake_smtp_server(None).await;
+    let rx = smtp_create(&cptestctx, &smtp_params(port)).await;
+
+    let alert_id = publish_test_foo(&cptestctx).await;
+
+    let mail = tokio::time::timeout(RECV_TIMEOUT, mail.recv())
+        .await
+        .expect("timed out waiting for alert mail")
+        .expect("fake SMTP server should still be running");
+    dbg!(&mail);
+    assert_eq!(mail.mail_from, "<rack@example.com>");
+    assert_eq!(mail.rcpt_to, vec!["<oncall@example.com>", "<noc@example.com>"]);
+    assert!(mail.data.contains("From: <rack@example.com>\r\n"));
+    assert!(
+        mail.data.contains("To: <oncall@example.com>, <noc@example.com>\r\n")
+    );
+    assert!(mail.data.contains("Subject: Oxide alert: test.foo\r\n"));
+    assert!(mail.data.contains(&format!("X-Oxide-Alert-Id: {alert_id}\r\n")));
+    assert!(mail.data.contains("\"hello_world\": true"));
+
+    let deliveries =
+        alert_deliveries_list(client, &rx.identity.id.to_string()).await;
+    assert_eq!(deliveries.len(), 1, "{deliveries:#?}");
+    let AlertDeliveryAttempts::Smtp(attempts) = &deliveries[0].attempts else {
+        panic!("expected SMTP delivery attempts, found {deliveries:#?}");
+    };
+    assert_eq!(attempts.len(), 1);
+    assert_eq!(attempts[0].result, SmtpDeliveryAttemptResult::Succeeded);
+    assert_eq!(attempts[0].reply_code, Some(250));
+}
+
+#[nexus_test]
+async fn test_smtp_probe_rejected(cptestctx: &ControlPlaneTestContext) {
+    let (port, _mail) = fake_smtp_server(Some(550)).await;
+    let rx = smtp_create(&cptestctx, &smtp_params(port)).await;
+
+    let probe: AlertProbeResult = NexusRequest::new(
+        RequestBuilder::new(
+            &cptestctx.external_client,
+            http::Method::POST,
+            &format!("{ALERT_RECEIVERS_BASE_PATH}/{}/probe", rx.identity.id),
+        )
+        .expect_status(Some(http::StatusCode::OK)),
+    )
+    .authn_as(AuthnMode::PrivilegedUser)
+    .execute_and_parse_unwrap()
+    .await;
+    dbg!(&probe);
+    let AlertDeliveryAttempts::Smtp(attempts) = &probe.probe.attempts else {
+        panic!("expected SMTP delivery attempts, found {probe:#?}");
+    };
+    assert_eq!(attempts.len(), 1);
+    assert_eq!(attempts[0].result, SmtpDeliveryAttemptResult::FailedRejected);
+    assert_eq!(attempts[0].reply_code, Some(550));
+}
+
+#[nexus_test]
+async fn test_smtp_starttls_unverified(cptestctx: &ControlPlaneTestContext) {
+    let (port, transcript) = fake_starttls_server().await;
+    let rx = smtp_create(&cptestctx, &smtp_params(port)).await;
+
+    let probe: AlertProbeResult = NexusRequest::new(
+        RequestBuilder::new(
+            &cptestctx.external_client,
+            http::Method::POST,
+            &format!("{ALERT_RECEIVERS_BASE_PATH}/{}/probe", rx.identity.id),
+        )
+        .expect_status(Some(http::StatusCode::OK)),
+    )
+    .authn_as(AuthnMode::PrivilegedUser)
+    .execute_and_parse_unwrap()
+    .await;
+    dbg!(&probe);
+    let AlertDeliveryAttempts::Smtp(attempts) = &probe.probe.attempts else {
+        panic!("expected SMTP delivery attempts, found {probe:#?}");
+    };
+    assert_eq!(attempts.len(), 1);
+    assert_eq!(
+        attempts[0].result,
+        SmtpDeliveryAttemptResult::FailedUnreachable
+    );
+    assert_eq!(attempts[0].reply_code, None);
+
+    // Nexus upgraded the connection, and when it couldn't verify the server's
+    // certificate, gave up rather than sending the message in the clear.
+    let transcript = tokio::time::timeout(RECV_TIMEOUT, transcript)
+        .await
+        .expect("timed out waiting for SMTP transaction")
+        .expect("fake SMTP server should report the transaction");
+    dbg!(&transcript);
+    assert_eq!(transcript.plaintext_commands.len(), 2);
+    assert!(transcript.plaintext_commands[0].starts_with("EHLO "));
+    assert_eq!(transcript.plaintext_commands[1], "STARTTLS");
+    assert!(!transcript.handshake_completed);
+}
+
+#[nexus_test]
+async fn test_syslog_event_delivery(cptestctx: &ControlPlaneTestContext) {
+    let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let port = socket.local_addr().unwrap().port();
+    let rx = syslog_create(
+        &cptestctx,
+        &SyslogCreate {
+            identity: IdentityMetadataCreateParams {
+                name: "my-great-syslog".parse().unwrap(),
+                description: String::from("my great syslog"),
+            },
+            host: "127.0.0.1".to_string(),
+            port,
+            transport: SyslogTransport::Udp,
+            facility: SyslogFacility::Local3,
+            subscriptions: vec!["test.foo".parse().unwrap()],
+        },
+    )
+    .await;
+    assert_eq!(rx.config.facility, SyslogFacility::Local3);
+
+    let alert_id = publish_test_foo(&cptestctx).await;
+
+    let mut buf = vec![0u8; 64 * 1024];
+    let (len, _) =
+        tokio::time::timeout(RECV_TIMEOUT, socket.recv_from(&mut buf))
+            .await
+            .expect("timed out waiting for syslog message")
+            .unwrap();
+    let message = std::str::from_utf8(&buf[..len]).unwrap();
+    dbg!(message);
+    // local3 (19) * 8 + notice (5)
+    assert!(message.starts_with("<157>1 "), "{message}");
+    assert!(message.contains(" oxide-nexus - alert - {"), "{message}");
+    assert!(message.contains(&alert_id.to_string()), "{message}");
+}
+
+#[nexus_test]
+async fn test_alert_receiver_kinds_old_api_versions(
+    cptestctx: &ControlPlaneTestContext,
+) {
+    let client = unversioned_client(&cptestctx);
+    let old_version =
+        nexus_external_api::VERSION_AUDIT_LOG_RESOURCE.to_string();
+
+    let webhook = resource_helpers::object_create::<_, WebhookReceiver>(
+        &cptestctx.external_client,
+        WEBHOOK_RECEIVERS_BASE_PATH,
+        &WebhookCreate {
+            identity: IdentityMetadataCreateParams {
+                name: "my-great-webhook".parse().unwrap(),
+                description: String::from("my great webhook"),
+            },
+            endpoint: "https://example.com/webhooks".parse().unwrap(),
+            secrets: vec!["my cool secret".to_string()],
+            subscriptions: vec!["test.foo".parse().unwrap()],
+        },
+    )
+    .await;
+    let smtp = smtp_create(&cptestctx, &smtp_params(25)).await;
+
+    // Older versions only list webhook receivers.
+    let list: dropshot::ResultsPage<serde_json::Value> = NexusRequest::new(
+        RequestBuilder::new(
+            &client,
+            http::Method::GET,
+            ALERT_RECEIVERS_BASE_PATH,
+        )
+        .header(omicron_common::api::VERSION_HEADER, old_version.as_str())
+        .expect_status(Some(http::StatusCode::OK)),
+    )
+    .authn_as(AuthnMode::PrivilegedUser)
+    .execute_and_parse_unwrap()
+    .await;
+    let ids = list
+        .items
+        .iter()
+        .map(|rx| rx["id"].as_str().unwrap().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec![webhook.identity.id.to_string()]);
+
+    // ...and can't view other kinds of receiver.
+    NexusRequest::new(
+        RequestBuilder::new(
+            &client,
+            http::Method::GET,
+            &format!("{ALERT_RECEIVERS_BASE_PATH}/{}", smtp.identity.id),
+        )
+        .header(omicron_common::api::VERSION_HEADER, old_version.as_str())
+        .expect_status(Some(http::StatusCode::BAD_REQUEST)),
+    )
+    .authn_as(AuthnMode::PrivilegedUser)
+    .execute()
+    .await
+    .unwrap();
+}
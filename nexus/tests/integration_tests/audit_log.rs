@@ -11,10 +11,11 @@ use nexus_test_utils::http_testing::{AuthnMode, NexusRequest, RequestBuilder};
 use nexus_test_utils::resource_helpers::{
     DiskTest, create_console_session, create_default_ip_pools, create_disk,
     create_instance_with, create_local_user, create_project, create_silo,
-    get_device_token, grant_iam, object_create_error, object_create_no_body,
-    object_delete, objects_list_page_authz, test_params,
+    get_device_token, grant_iam, object_create, object_create_error,
+    object_create_no_body, object_delete, objects_list_page_authz, test_params,
 };
 use nexus_test_utils_macros::nexus_test;
+use nexus_types::external_api::alert::{WebhookCreate, WebhookReceiver};
 use nexus_types::external_api::audit::{
     self, AuditLogEntry, AuditLogEntryActor, AuditLogEntryResult,
 };
@@ -32,6 +33,7 @@ use nexus_types::{identity::Asset, silo::DEFAULT_SILO_ID};
 use omicron_common::api::external::{
     IdentityMetadataCreateParams, InstanceAutoRestartPolicy, Name, UserId,
 };
+use omicron_uuid_kinds::GenericUuid;
 use std::str::FromStr;
 
 type ControlPlaneTestContext =
@@ -234,6 +236,7 @@ async fn test_audit_log_list(ctx: &ControlPlaneTestContext) {
         actor: nexus_db_model::AuditLogActor::Unauthenticated,
         auth_method: None,
         credential_id: None,
+        request_body: None,
     };
     let entry = datastore
         .audit_log_entry_init(&opctx, params.into())
@@ -459,6 +462,108 @@ async fn test_audit_log_create_delete_ops(ctx: &ControlPlaneTestContext) {
     verify_entry(&items[7], "project_delete", project_del_url, 204, t2, t3);
 }
 
+#[nexus_test]
+async fn test_audit_log_resource_and_body(ctx: &ControlPlaneTestContext) {
+    let client = &ctx.external_client;
+    let t0 = Utc::now();
+
+    let project = create_project(client, "resource-project").await;
+    let _other = create_project(client, "other-project").await;
+
+    // Secrets in the request body should never make it into the log.
+    let webhook_params = WebhookCreate {
+        identity: IdentityMetadataCreateParams {
+            name: "audited-webhook".parse().unwrap(),
+            description: String::from("a webhook with a secret"),
+        },
+        endpoint: "http://127.0.0.1:1/webhooks".parse().unwrap(),
+        secrets: vec![String::from("hunter2")],
+        subscriptions: vec!["test.foo".parse().unwrap()],
+    };
+    let _webhook: WebhookReceiver =
+        object_create(client, "/v1/webhook-receivers", &webhook_params).await;
+
+    let log = fetch_log(client, t0, None).await;
+    assert_eq!(log.items.len(), 3);
+
+    // The project create records the new project and its request body.
+    let entry = &log.items[0];
+    assert_eq!(entry.operation_id, "project_create");
+    assert_eq!(entry.resource_type.as_deref(), Some("project"));
+    assert_eq!(entry.resource_id, Some(project.identity.id));
+    let body = entry.request_body.as_ref().expect("project body recorded");
+    assert_eq!(body["name"], "resource-project");
+
+    let entry = &log.items[2];
+    assert_eq!(entry.operation_id, "webhook_receiver_create");
+    let body = entry.request_body.as_ref().expect("webhook body recorded");
+    assert_eq!(body["name"], "audited-webhook");
+    assert_eq!(body["secrets"], serde_json::json!(["<redacted>"]));
+    assert!(!body.to_string().contains("hunter2"));
+
+    // Filtering by resource ID only returns entries for that resource.
+    let url = format!(
+        "/v1/system/audit-log?start_time={}&resource_id={}",
+        to_q(t0),
+        project.identity.id,
+    );
+    let filtered = objects_list_page_authz::<AuditLogEntry>(client, &url).await;
+    assert_eq!(filtered.items.len(), 1);
+    assert_eq!(filtered.items[0].id, log.items[0].id);
+}
+
+#[nexus_test]
+async fn test_audit_log_local_user_password(ctx: &ControlPlaneTestContext) {
+    let client = &ctx.external_client;
+
+    let silo_name = "password-silo";
+    let local = SiloIdentityMode::LocalOnly;
+    let silo = create_silo(client, silo_name, true, local).await;
+
+    let t0 = Utc::now();
+    let username = UserId::from_str("password-user").unwrap();
+    let params = test_params::UserPassword::Password("hunter2".into());
+    let user = create_local_user(client, &silo, &username, params).await;
+
+    let set_password_url = format!(
+        "/v1/system/identity-providers/local/users/{}/set-password?silo={}",
+        user.id, silo_name,
+    );
+    NexusRequest::new(
+        RequestBuilder::new(client, Method::POST, &set_password_url)
+            .body(Some(&test_params::UserPassword::Password(
+                "correct horse battery staple".into(),
+            )))
+            .expect_status(Some(StatusCode::NO_CONTENT)),
+    )
+    .authn_as(AuthnMode::PrivilegedUser)
+    .execute()
+    .await
+    .unwrap();
+
+    let log = fetch_log(client, t0, None).await;
+    assert_eq!(log.items.len(), 2);
+
+    // The user create records the new user and its body, but never the
+    // password itself.
+    let entry = &log.items[0];
+    assert_eq!(entry.operation_id, "local_idp_user_create");
+    assert_eq!(entry.resource_type.as_deref(), Some("silo-user"));
+    assert_eq!(entry.resource_id, Some(user.id.into_untyped_uuid()));
+    let body = entry.request_body.as_ref().expect("user body recorded");
+    assert_eq!(body["external_id"], "password-user");
+    assert_eq!(body["password"]["mode"], "password");
+    assert_eq!(body["password"]["value"], "<redacted>");
+    assert!(!body.to_string().contains("hunter2"));
+
+    let entry = &log.items[1];
+    assert_eq!(entry.operation_id, "local_idp_user_set_password");
+    assert_eq!(entry.resource_id, Some(user.id.into_untyped_uuid()));
+    let body = entry.request_body.as_ref().expect("password body recorded");
+    assert_eq!(body["value"], "<redacted>");
+    assert!(!body.to_string().contains("correct horse"));
+}
+
 /// Test that mutating endpoints in VERIFY_ENDPOINTS create audit log entries.
 /// This is a coverage test to catch endpoints that forget to add audit logging.
 /// The snapshot file lists endpoints that are known to not have audit logging.
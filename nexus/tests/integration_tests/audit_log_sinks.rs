@@ -0,0 +1,308 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Tests for delivering the audit log to audit log sinks
+
+use dropshot::test_util::ClientTestContext;
+use hmac::{Hmac, Mac};
+use http::StatusCode;
+use httpmock::prelude::*;
+use nexus_lockstep_client::types::LastResult;
+use nexus_test_utils::background::activate_background_task;
+use nexus_test_utils::resource_helpers::{
+    create_project, object_create, object_create_error, object_delete,
+    object_get, objects_list_page_authz,
+};
+use nexus_test_utils_macros::nexus_test;
+use nexus_types::external_api::audit::{
+    AuditLogEntry, AuditLogSink, AuditLogSinkBatch, AuditLogSinkConfig,
+    AuditLogSinkCreate, AuditLogSinkKind,
+};
+use nexus_types::internal_api::background::AuditLogExportStatus;
+use omicron_common::api::external::IdentityMetadataCreateParams;
+use omicron_uuid_kinds::GenericUuid;
+use sha2::Sha256;
+use tokio::io::AsyncReadExt;
+use tokio::net::TcpListener;
+
+type ControlPlaneTestContext =
+    nexus_test_utils::ControlPlaneTestContext<omicron_nexus::Server>;
+
+const SINKS_URL: &str = "/v1/system/audit-log/sinks";
+const SECRET: &str = "audit log secret";
+
+fn sink_url(name: &str) -> String {
+    format!("{SINKS_URL}/{name}")
+}
+
+fn sink_create(name: &str, config: AuditLogSinkConfig) -> AuditLogSinkCreate {
+    AuditLogSinkCreate {
+        identity: IdentityMetadataCreateParams {
+            name: name.parse().unwrap(),
+            description: String::new(),
+        },
+        config,
+        start_time: None,
+    }
+}
+
+async fn run_audit_log_export(
+    lockstep_client: &ClientTestContext,
+) -> AuditLogExportStatus {
+    let task =
+        activate_background_task(lockstep_client, "audit_log_export").await;
+    let LastResult::Completed(last) = task.last else {
+        panic!(
+            "unexpected {:?} returned from audit_log_export task",
+            task.last
+        );
+    };
+    serde_json::from_value(last.details).unwrap()
+}
+
+#[nexus_test]
+async fn test_audit_log_sink_crud(cptestctx: &ControlPlaneTestContext) {
+    let client = &cptestctx.external_client;
+
+    let sinks =
+        objects_list_page_authz::<AuditLogSink>(client, SINKS_URL).await;
+    assert!(sinks.items.is_empty());
+
+    let params = sink_create(
+        "collector",
+        AuditLogSinkConfig::Syslog {
+            address: String::from("collector.example.com:6514"),
+            tls_root_cert: None,
+        },
+    );
+    let sink: AuditLogSink = object_create(client, SINKS_URL, &params).await;
+    assert_eq!(sink.kind, AuditLogSinkKind::Syslog);
+    assert_eq!(sink.endpoint, "collector.example.com:6514");
+    assert!(!sink.tls);
+    assert_eq!(sink.consecutive_failures, 0);
+    assert_eq!(sink.time_last_delivered, None);
+
+    let fetched: AuditLogSink =
+        object_get(client, &sink_url("collector")).await;
+    assert_eq!(fetched.identity.id, sink.identity.id);
+    let sinks =
+        objects_list_page_authz::<AuditLogSink>(client, SINKS_URL).await;
+    assert_eq!(sinks.items.len(), 1);
+
+    // Names must be unique.
+    let error = object_create_error(
+        client,
+        SINKS_URL,
+        &params,
+        StatusCode::BAD_REQUEST,
+    )
+    .await;
+    assert_eq!(error.message, "already exists: audit-log-sink \"collector\"");
+
+    // Bad configurations are rejected.
+    for config in [
+        AuditLogSinkConfig::Syslog {
+            address: String::from("no-port.example.com"),
+            tls_root_cert: None,
+        },
+        AuditLogSinkConfig::Syslog {
+            address: String::from("collector.example.com:6514"),
+            tls_root_cert: Some(String::from("not a certificate")),
+        },
+        AuditLogSinkConfig::Http {
+            endpoint: "https://example.com/audit".parse().unwrap(),
+            secret: String::new(),
+        },
+    ] {
+        object_create_error(
+            client,
+            SINKS_URL,
+            &sink_create("bad", config),
+            StatusCode::BAD_REQUEST,
+        )
+        .await;
+    }
+
+    object_delete(client, &sink_url("collector")).await;
+    let sinks =
+        objects_list_page_authz::<AuditLogSink>(client, SINKS_URL).await;
+    assert!(sinks.items.is_empty());
+}
+
+#[nexus_test]
+async fn test_audit_log_sink_syslog(cptestctx: &ControlPlaneTestContext) {
+    let client = &cptestctx.external_client;
+    let lockstep_client = &cptestctx.lockstep_client;
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    let _: AuditLogSink = object_create(
+        client,
+        SINKS_URL,
+        &sink_create(
+            "collector",
+            AuditLogSinkConfig::Syslog { address, tls_root_cert: None },
+        ),
+    )
+    .await;
+
+    create_project(client, "audited").await;
+
+    // The task sends each batch over its own connection, closing it when
+    // done.
+    let received = tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut buf = Vec::new();
+        stream.read_to_end(&mut buf).await.unwrap();
+        String::from_utf8(buf).unwrap()
+    });
+    let status = run_audit_log_export(lockstep_client).await;
+    assert_eq!(status.sinks.len(), 1);
+    assert_eq!(status.sinks[0].error, None);
+    let received = received.await.unwrap();
+
+    // Messages are framed with octet counting.
+    let mut messages = Vec::new();
+    let mut rest = received.as_str();
+    while !rest.is_empty() {
+        let (len, tail) = rest.split_once(' ').unwrap();
+        let len: usize = len.parse().unwrap();
+        messages.push(&tail[..len]);
+        rest = &tail[len..];
+    }
+    assert_eq!(messages.len(), status.sinks[0].entries_delivered);
+
+    let entries: Vec<AuditLogEntry> = messages
+        .iter()
+        .map(|message| {
+            // <110>1 TIMESTAMP - oxide-nexus - MSGID - JSON
+            let fields: Vec<_> = message.splitn(8, ' ').collect();
+            // Facility "log audit"; informational for successful requests
+            // and warning otherwise
+            assert!(matches!(fields[0], "<110>1" | "<108>1"));
+            assert_eq!(fields[2], "-");
+            assert_eq!(fields[3], "oxide-nexus");
+            let entry: AuditLogEntry = serde_json::from_str(fields[7]).unwrap();
+            assert_eq!(fields[5], entry.operation_id);
+            entry
+        })
+        .collect();
+    assert!(entries.iter().any(|e| e.operation_id == "project_create"));
+    // Entries are delivered in order.
+    assert!(entries.is_sorted_by_key(|e| (e.time_completed, e.id)));
+
+    let sink: AuditLogSink = object_get(client, &sink_url("collector")).await;
+    assert!(sink.time_last_delivered.is_some());
+    assert_eq!(sink.cursor, entries.last().unwrap().time_completed);
+
+    // Nothing has been audited since then: the request we just made to check
+    // on the sink is a read, and reads aren't audited.
+    let status = run_audit_log_export(lockstep_client).await;
+    assert_eq!(status.sinks[0].entries_delivered, 0);
+}
+
+#[nexus_test]
+async fn test_audit_log_sink_http(cptestctx: &ControlPlaneTestContext) {
+    let client = &cptestctx.external_client;
+    let lockstep_client = &cptestctx.lockstep_client;
+    let server = httpmock::MockServer::start_async().await;
+
+    let sink: AuditLogSink = object_create(
+        client,
+        SINKS_URL,
+        &sink_create(
+            "receiver",
+            AuditLogSinkConfig::Http {
+                endpoint: server.url("/audit").parse().unwrap(),
+                secret: String::from(SECRET),
+            },
+        ),
+    )
+    .await;
+    let broken: AuditLogSink = object_create(
+        client,
+        SINKS_URL,
+        &sink_create(
+            "broken",
+            AuditLogSinkConfig::Http {
+                endpoint: server.url("/broken").parse().unwrap(),
+                secret: String::from(SECRET),
+            },
+        ),
+    )
+    .await;
+
+    create_project(client, "audited").await;
+
+    let sink_id = sink.identity.id;
+    let mock = server
+        .mock_async(move |when, then| {
+            when.method(POST)
+                .path("/audit")
+                .header("x-oxide-audit-log-sink-id", sink_id.to_string())
+                .header("content-type", "application/json")
+                .is_true(move |req| {
+                    let sig = format!("a=sha256&id={sink_id}&s=");
+                    let Some(sig) =
+                        req.headers_vec().iter().find_map(|(name, value)| {
+                            (name == "x-oxide-signature")
+                                .then(|| value.strip_prefix(&sig))
+                                .flatten()
+                                .map(|s| hex::decode(s).unwrap())
+                        })
+                    else {
+                        return false;
+                    };
+                    let mut mac =
+                        Hmac::<Sha256>::new_from_slice(SECRET.as_bytes())
+                            .unwrap();
+                    mac.update(req.body().as_ref());
+                    mac.verify_slice(&sig).is_ok()
+                })
+                .is_true(move |req| {
+                    let batch: AuditLogSinkBatch =
+                        serde_json::from_slice(req.body().as_ref()).unwrap();
+                    batch.sink_id == sink_id
+                        && batch
+                            .entries
+                            .iter()
+                            .any(|e| e.operation_id == "project_create")
+                });
+            then.status(204);
+        })
+        .await;
+    let broken_mock = server
+        .mock_async(|when, then| {
+            when.method(POST).path("/broken");
+            then.status(500);
+        })
+        .await;
+
+    let status = run_audit_log_export(lockstep_client).await;
+    assert_eq!(status.sinks.len(), 2);
+    mock.assert_async().await;
+    broken_mock.assert_async().await;
+
+    let sink: AuditLogSink = object_get(client, &sink_url("receiver")).await;
+    assert!(sink.time_last_delivered.is_some());
+    assert_eq!(sink.consecutive_failures, 0);
+
+    // The broken sink's cursor doesn't move, and it backs off, so it isn't
+    // retried right away.
+    let broken_after: AuditLogSink =
+        object_get(client, &sink_url("broken")).await;
+    assert_eq!(broken_after.cursor, broken.cursor);
+    assert_eq!(broken_after.time_last_delivered, None);
+    assert_eq!(broken_after.consecutive_failures, 1);
+    assert!(broken_after.last_error.is_some());
+
+    let status = run_audit_log_export(lockstep_client).await;
+    assert!(
+        status
+            .sinks
+            .iter()
+            .all(|s| s.sink_id.into_untyped_uuid() != broken.identity.id)
+    );
+    broken_mock.assert_calls_async(1).await;
+}
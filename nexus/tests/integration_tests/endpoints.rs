@@ -22,6 +22,7 @@ use nexus_test_utils::SWITCH_UUID;
 use nexus_test_utils::resource_helpers::test_params;
 use nexus_types::external_api::affinity;
 use nexus_types::external_api::alert;
+use nexus_types::external_api::audit;
 use nexus_types::external_api::certificate;
 use nexus_types::external_api::disk;
 use nexus_types::external_api::external_subnet;
@@ -32,6 +33,7 @@ use nexus_types::external_api::instance;
 use nexus_types::external_api::instance::PrivateIpStackCreate;
 use nexus_types::external_api::internet_gateway;
 use nexus_types::external_api::ip_pool;
+use nexus_types::external_api::load_balancer;
 use nexus_types::external_api::multicast;
 use nexus_types::external_api::networking;
 use nexus_types::external_api::path_params;
@@ -39,6 +41,7 @@ use nexus_types::external_api::physical_disk;
 use nexus_types::external_api::policy;
 use nexus_types::external_api::project;
 use nexus_types::external_api::rack;
+use nexus_types::external_api::service_account;
 use nexus_types::external_api::silo;
 use nexus_types::external_api::sled;
 use nexus_types::external_api::snapshot;
@@ -56,6 +59,7 @@ use omicron_common::api::external::AffinityPolicy;
 use omicron_common::api::external::AllowedSourceIps;
 use omicron_common::api::external::ByteCount;
 use omicron_common::api::external::FailureDomain;
+use omicron_common::api::external::Generation;
 use omicron_common::api::external::IdentityMetadataCreateParams;
 use omicron_common::api::external::IdentityMetadataUpdateParams;
 use omicron_common::api::external::InstanceCpuCount;
@@ -66,6 +70,7 @@ use omicron_common::api::external::RouteDestination;
 use omicron_common::api::external::RouteTarget;
 use omicron_common::api::external::ServiceIcmpConfig;
 use omicron_common::api::external::UserId;
+use omicron_common::api::external::VpcFirewallRuleUpdate;
 use omicron_common::api::external::VpcFirewallRuleUpdateParams;
 use omicron_test_utils::certificates::CertificateChain;
 use semver::Version;
@@ -94,6 +99,17 @@ pub static HARDWARE_SLED_PROVISION_POLICY_URL: LazyLock<String> =
             SLED_AGENT_UUID
         )
     });
+pub static HARDWARE_SLED_EVACUATION_URL: LazyLock<String> =
+    LazyLock::new(|| {
+        format!("/v1/system/hardware/sleds/{}/evacuation", SLED_AGENT_UUID)
+    });
+pub static HARDWARE_SLED_EVACUATION_CANCEL_URL: LazyLock<String> =
+    LazyLock::new(|| {
+        format!(
+            "/v1/system/hardware/sleds/{}/evacuation/cancel",
+            SLED_AGENT_UUID
+        )
+    });
 pub static HARDWARE_RACK_MEMBERSHIP_URL: LazyLock<String> =
     LazyLock::new(|| {
         format!("/v1/system/hardware/racks/{}/membership", RACK_UUID)
@@ -230,6 +246,14 @@ pub static DEMO_SILO_POLICY_URL: LazyLock<String> =
     LazyLock::new(|| format!("/v1/system/silos/{}/policy", *DEMO_SILO_NAME));
 pub static DEMO_SILO_QUOTAS_URL: LazyLock<String> =
     LazyLock::new(|| format!("/v1/system/silos/{}/quotas", *DEMO_SILO_NAME));
+pub static DEMO_SILO_RATE_LIMIT_URL: LazyLock<String> = LazyLock::new(|| {
+    format!("/v1/system/silos/{}/rate-limit", *DEMO_SILO_NAME)
+});
+pub static DEMO_SILO_RATE_LIMIT_UPDATE: LazyLock<silo::SiloRateLimitUpdate> =
+    LazyLock::new(|| silo::SiloRateLimitUpdate {
+        requests_per_second: Nullable(None),
+        burst: Nullable(None),
+    });
 pub static DEMO_SILO_CREATE: LazyLock<silo::SiloCreate> =
     LazyLock::new(|| silo::SiloCreate {
         identity: IdentityMetadataCreateParams {
@@ -333,6 +357,23 @@ pub static DEMO_VPC_SELECTOR: LazyLock<String> = LazyLock::new(|| {
 });
 pub static DEMO_VPC_URL_FIREWALL_RULES: LazyLock<String> =
     LazyLock::new(|| format!("/v1/vpc-firewall-rules?{}", *DEMO_VPC_SELECTOR));
+pub static DEMO_VPC_FIREWALL_RULE: LazyLock<VpcFirewallRuleUpdate> =
+    LazyLock::new(|| {
+        nexus_defaults::DEFAULT_FIREWALL_RULES
+            .rules
+            .iter()
+            .find(|rule| rule.name.as_str() == "allow-icmp")
+            .unwrap()
+            .clone()
+    });
+pub static DEMO_VPC_FIREWALL_RULE_URL: LazyLock<String> = LazyLock::new(|| {
+    format!(
+        "/v1/vpc-firewall-rules/{}?{}&generation=1",
+        DEMO_VPC_FIREWALL_RULE.name, *DEMO_VPC_SELECTOR
+    )
+});
+pub static DEMO_VPC_URL_DNS_RECORDS: LazyLock<String> =
+    LazyLock::new(|| format!("/v1/vpc-dns-records?{}", *DEMO_VPC_SELECTOR));
 pub static DEMO_VPC_URL_ROUTERS: LazyLock<String> =
     LazyLock::new(|| format!("/v1/vpc-routers?{}", *DEMO_VPC_SELECTOR));
 pub static DEMO_VPC_URL_SUBNETS: LazyLock<String> =
@@ -692,6 +733,14 @@ pub static DEMO_INSTANCE_REBOOT_URL: LazyLock<String> = LazyLock::new(|| {
         *DEMO_INSTANCE_NAME, *DEMO_PROJECT_SELECTOR
     )
 });
+pub static DEMO_INSTANCE_MIGRATE_URL: LazyLock<String> = LazyLock::new(|| {
+    format!(
+        "/v1/instances/{}/migrate?{}",
+        *DEMO_INSTANCE_NAME, *DEMO_PROJECT_SELECTOR
+    )
+});
+pub static DEMO_INSTANCE_MIGRATE: LazyLock<instance::InstanceMigrate> =
+    LazyLock::new(|| instance::InstanceMigrate { dst_sled_id: None });
 pub static DEMO_INSTANCE_SERIAL_URL: LazyLock<String> = LazyLock::new(|| {
     format!(
         "/v1/instances/{}/serial-console?{}",
@@ -752,6 +801,18 @@ pub static DEMO_INSTANCE_SSH_KEYS_URL: LazyLock<String> = LazyLock::new(|| {
         *DEMO_INSTANCE_NAME, *DEMO_PROJECT_SELECTOR
     )
 });
+pub static DEMO_INSTANCE_NETWORK_TAGS_URL: LazyLock<String> =
+    LazyLock::new(|| {
+        format!(
+            "/v1/instances/{}/network-tags?{}",
+            *DEMO_INSTANCE_NAME, *DEMO_PROJECT_SELECTOR
+        )
+    });
+pub static DEMO_INSTANCE_NETWORK_TAGS_UPDATE: LazyLock<
+    instance::InstanceNetworkTagsUpdate,
+> = LazyLock::new(|| instance::InstanceNetworkTagsUpdate {
+    tags: vec!["web".parse().unwrap()],
+});
 pub static DEMO_INSTANCE_NICS_URL: LazyLock<String> = LazyLock::new(|| {
     format!(
         "/v1/network-interfaces?project={}&instance={}",
@@ -887,6 +948,44 @@ pub static DEMO_CERTIFICATE_CREATE: LazyLock<certificate::CertificateCreate> =
         service: certificate::ServiceUsingCertificate::ExternalApi,
     });
 
+pub static DEMO_SERVICE_ACCOUNT_NAME: LazyLock<Name> =
+    LazyLock::new(|| "demo-service-account".parse().unwrap());
+pub const DEMO_SERVICE_ACCOUNTS_URL: &'static str = "/v1/service-accounts";
+pub const DEMO_SERVICE_ACCOUNT_URL: &'static str =
+    "/v1/service-accounts/demo-service-account";
+pub const DEMO_SERVICE_ACCOUNT_POLICY_URL: &'static str =
+    "/v1/service-accounts/demo-service-account/policy";
+pub const DEMO_SERVICE_ACCOUNT_TOKENS_URL: &'static str =
+    "/v1/service-accounts/demo-service-account/tokens";
+pub const DEMO_SERVICE_ACCOUNT_TOKEN_URL: &'static str =
+    "/v1/service-accounts/demo-service-account/tokens/demo-token";
+pub const DEMO_SERVICE_ACCOUNT_TOKEN_ROTATE_URL: &'static str =
+    "/v1/service-accounts/demo-service-account/tokens/demo-token/rotate";
+pub static DEMO_SERVICE_ACCOUNT_CREATE: LazyLock<
+    service_account::ServiceAccountCreate,
+> = LazyLock::new(|| service_account::ServiceAccountCreate {
+    identity: IdentityMetadataCreateParams {
+        name: DEMO_SERVICE_ACCOUNT_NAME.clone(),
+        description: String::from(""),
+    },
+});
+pub static DEMO_SERVICE_ACCOUNT_TOKEN_CREATE: LazyLock<
+    service_account::ServiceAccountTokenCreate,
+> = LazyLock::new(|| service_account::ServiceAccountTokenCreate {
+    identity: IdentityMetadataCreateParams {
+        name: "demo-token".parse().unwrap(),
+        description: String::from(""),
+    },
+    time_expires: None,
+    read_only: false,
+    project_ids: None,
+});
+pub static DEMO_SERVICE_ACCOUNT_POLICY: LazyLock<
+    service_account::ServiceAccountPolicy,
+> = LazyLock::new(|| service_account::ServiceAccountPolicy {
+    role_assignments: vec![],
+});
+
 // Multicast groups and members
 // Multicast groups are fleet-scoped (like IP pools), not project-scoped
 pub static DEMO_MULTICAST_GROUP_NAME: LazyLock<Name> =
@@ -1411,6 +1510,53 @@ pub static DEMO_FLOAT_IP_ATTACH: LazyLock<floating_ip::FloatingIpAttach> =
         kind: floating_ip::FloatingIpParentKind::Instance,
         parent: DEMO_FLOAT_IP_NAME.clone().into(),
     });
+// Project Load Balancers
+pub static DEMO_LOAD_BALANCER_NAME: LazyLock<Name> =
+    LazyLock::new(|| "demo-load-balancer".parse().unwrap());
+pub static DEMO_PROJECT_URL_LOAD_BALANCERS: LazyLock<String> =
+    LazyLock::new(|| {
+        format!("/v1/load-balancers?project={}", *DEMO_PROJECT_NAME)
+    });
+pub static DEMO_LOAD_BALANCER_URL: LazyLock<String> = LazyLock::new(|| {
+    format!(
+        "/v1/load-balancers/{}?{}",
+        *DEMO_LOAD_BALANCER_NAME, *DEMO_PROJECT_SELECTOR
+    )
+});
+pub static DEMO_LOAD_BALANCER_BACKENDS_URL: LazyLock<String> =
+    LazyLock::new(|| {
+        format!(
+            "/v1/load-balancers/{}/backends?{}",
+            *DEMO_LOAD_BALANCER_NAME, *DEMO_PROJECT_SELECTOR
+        )
+    });
+pub static DEMO_LOAD_BALANCER_BACKEND_URL: LazyLock<String> =
+    LazyLock::new(|| {
+        format!(
+            "/v1/load-balancers/{}/backends/{{id}}?{}",
+            *DEMO_LOAD_BALANCER_NAME, *DEMO_PROJECT_SELECTOR
+        )
+    });
+pub static DEMO_LOAD_BALANCER_CREATE: LazyLock<
+    load_balancer::LoadBalancerCreate,
+> = LazyLock::new(|| load_balancer::LoadBalancerCreate {
+    identity: IdentityMetadataCreateParams {
+        name: DEMO_LOAD_BALANCER_NAME.clone(),
+        description: String::from("a demo load balancer"),
+    },
+    address_allocator: floating_ip::AddressAllocator::Explicit {
+        ip: Ipv4Addr::new(10, 0, 0, 142).into(),
+    },
+    ports: vec![80, 443],
+    backend_tag: None,
+    health_check: None,
+});
+pub static DEMO_LOAD_BALANCER_BACKEND_CREATE: LazyLock<
+    load_balancer::LoadBalancerBackendCreate,
+> = LazyLock::new(|| load_balancer::LoadBalancerBackendCreate {
+    instance: DEMO_INSTANCE_NAME.clone().into(),
+});
+
 pub static DEMO_EPHEMERAL_IP_ATTACH: LazyLock<instance::EphemeralIpCreate> =
     LazyLock::new(|| instance::EphemeralIpCreate {
         pool_selector: ip_pool::PoolSelector::Auto { ip_version: None },
@@ -1511,6 +1657,8 @@ pub static DEMO_TARGET_RELEASE: LazyLock<update::SetTargetReleaseParams> =
 pub static ALERT_CLASSES_URL: &'static str = "/v1/alert-classes";
 pub static ALERT_RECEIVERS_URL: &'static str = "/v1/alert-receivers";
 pub static WEBHOOK_RECEIVERS_URL: &'static str = "/v1/webhook-receivers";
+pub static SMTP_RECEIVERS_URL: &'static str = "/v1/smtp-receivers";
+pub static SYSLOG_RECEIVERS_URL: &'static str = "/v1/syslog-receivers";
 
 pub static DEMO_WEBHOOK_RECEIVER_NAME: LazyLock<Name> =
     LazyLock::new(|| "my-great-webhook".parse().unwrap());
@@ -1545,6 +1693,64 @@ pub static DEMO_WEBHOOK_RECEIVER_URL: LazyLock<String> = LazyLock::new(|| {
     format!("{WEBHOOK_RECEIVERS_URL}/{}", *DEMO_WEBHOOK_RECEIVER_NAME)
 });
 
+pub static DEMO_SMTP_RECEIVER_NAME: LazyLock<Name> =
+    LazyLock::new(|| "my-great-mailbox".parse().unwrap());
+pub static DEMO_SMTP_RECEIVER_CREATE: LazyLock<alert::SmtpCreate> =
+    LazyLock::new(|| alert::SmtpCreate {
+        identity: IdentityMetadataCreateParams {
+            name: DEMO_SMTP_RECEIVER_NAME.clone(),
+            description: "you've got mail".to_string(),
+        },
+        host: "mail.example.com".to_string(),
+        port: 25,
+        from: "rack@example.com".to_string(),
+        to: vec!["oncall@example.com".to_string()],
+        subscriptions: vec!["test.*".parse().unwrap()],
+    });
+pub static DEMO_SMTP_RECEIVER_UPDATE: LazyLock<alert::SmtpReceiverUpdate> =
+    LazyLock::new(|| alert::SmtpReceiverUpdate {
+        identity: IdentityMetadataUpdateParams {
+            name: None,
+            description: Some("return to sender".to_string()),
+        },
+        host: None,
+        port: Some(2525),
+        from: None,
+        to: None,
+    });
+pub static DEMO_SMTP_RECEIVER_URL: LazyLock<String> = LazyLock::new(|| {
+    format!("{SMTP_RECEIVERS_URL}/{}", *DEMO_SMTP_RECEIVER_NAME)
+});
+
+pub static DEMO_SYSLOG_RECEIVER_NAME: LazyLock<Name> =
+    LazyLock::new(|| "my-great-syslog".parse().unwrap());
+pub static DEMO_SYSLOG_RECEIVER_CREATE: LazyLock<alert::SyslogCreate> =
+    LazyLock::new(|| alert::SyslogCreate {
+        identity: IdentityMetadataCreateParams {
+            name: DEMO_SYSLOG_RECEIVER_NAME.clone(),
+            description: "dear diary".to_string(),
+        },
+        host: "logs.example.com".to_string(),
+        port: 514,
+        transport: alert::SyslogTransport::Udp,
+        facility: alert::SyslogFacility::Local0,
+        subscriptions: vec!["test.*".parse().unwrap()],
+    });
+pub static DEMO_SYSLOG_RECEIVER_UPDATE: LazyLock<alert::SyslogReceiverUpdate> =
+    LazyLock::new(|| alert::SyslogReceiverUpdate {
+        identity: IdentityMetadataUpdateParams {
+            name: None,
+            description: Some("dear diary, again".to_string()),
+        },
+        host: None,
+        port: None,
+        transport: Some(alert::SyslogTransport::Tcp),
+        facility: None,
+    });
+pub static DEMO_SYSLOG_RECEIVER_URL: LazyLock<String> = LazyLock::new(|| {
+    format!("{SYSLOG_RECEIVERS_URL}/{}", *DEMO_SYSLOG_RECEIVER_NAME)
+});
+
 pub static DEMO_ALERT_RECEIVER_PROBE_URL: LazyLock<String> =
     LazyLock::new(|| format!("{}/probe", *DEMO_ALERT_RECEIVER_URL));
 
@@ -1610,6 +1816,25 @@ pub static AUDIT_LOG_URL: LazyLock<String> = LazyLock::new(|| {
     String::from("/v1/system/audit-log?start_time=2025-01-01T00:00:00Z")
 });
 
+pub static AUDIT_LOG_SINKS_URL: &'static str = "/v1/system/audit-log/sinks";
+pub static DEMO_AUDIT_LOG_SINK_NAME: LazyLock<Name> =
+    LazyLock::new(|| "demo-audit-log-sink".parse().unwrap());
+pub static DEMO_AUDIT_LOG_SINK_URL: LazyLock<String> = LazyLock::new(|| {
+    format!("{AUDIT_LOG_SINKS_URL}/{}", *DEMO_AUDIT_LOG_SINK_NAME)
+});
+pub static DEMO_AUDIT_LOG_SINK_CREATE: LazyLock<audit::AuditLogSinkCreate> =
+    LazyLock::new(|| audit::AuditLogSinkCreate {
+        identity: IdentityMetadataCreateParams {
+            name: DEMO_AUDIT_LOG_SINK_NAME.clone(),
+            description: String::from("where the audit log goes"),
+        },
+        config: audit::AuditLogSinkConfig::Http {
+            endpoint: "https://example.com/audit-log".parse().unwrap(),
+            secret: String::from("my cool secret"),
+        },
+        start_time: None,
+    });
+
 pub static SCIM_TOKENS_URL: LazyLock<String> = LazyLock::new(|| {
     format!("/v1/system/scim/tokens?silo={}", DEFAULT_SILO.identity().name,)
 });
@@ -2158,6 +2383,18 @@ pub static VERIFY_ENDPOINTS: LazyLock<Vec<VerifyEndpoint>> = LazyLock::new(
                     ),
                 ],
             },
+            VerifyEndpoint {
+                url: &DEMO_SILO_RATE_LIMIT_URL,
+                visibility: Visibility::Protected,
+                unprivileged_access: UnprivilegedAccess::None,
+                allowed_methods: vec![
+                    AllowedMethod::Get,
+                    AllowedMethod::Put(
+                        serde_json::to_value(&*DEMO_SILO_RATE_LIMIT_UPDATE)
+                            .unwrap(),
+                    ),
+                ],
+            },
             VerifyEndpoint {
                 url: "/v1/system/silo-quotas",
                 visibility: Visibility::Public,
@@ -2395,6 +2632,43 @@ pub static VERIFY_ENDPOINTS: LazyLock<Vec<VerifyEndpoint>> = LazyLock::new(
                         })
                         .unwrap(),
                     ),
+                    AllowedMethod::Post(
+                        serde_json::to_value(vpc::VpcFirewallRuleCreate {
+                            generation: Generation::new(),
+                            rule: DEMO_VPC_FIREWALL_RULE.clone(),
+                        })
+                        .unwrap(),
+                    ),
+                ],
+            },
+            /* VPC DNS records */
+            VerifyEndpoint {
+                url: &DEMO_VPC_URL_DNS_RECORDS,
+                visibility: Visibility::Protected,
+                unprivileged_access: UnprivilegedAccess::None,
+                allowed_methods: vec![
+                    AllowedMethod::Get,
+                    AllowedMethod::Put(
+                        serde_json::to_value(vpc::VpcDnsRecordsUpdate {
+                            records: vec![],
+                        })
+                        .unwrap(),
+                    ),
+                ],
+            },
+            VerifyEndpoint {
+                url: &DEMO_VPC_FIREWALL_RULE_URL,
+                visibility: Visibility::Protected,
+                unprivileged_access: UnprivilegedAccess::None,
+                allowed_methods: vec![
+                    AllowedMethod::Put(
+                        serde_json::to_value(vpc::VpcFirewallRuleReplace {
+                            generation: Generation::new(),
+                            rule: DEMO_VPC_FIREWALL_RULE.clone(),
+                        })
+                        .unwrap(),
+                    ),
+                    AllowedMethod::Delete,
                 ],
             },
             /* VPC Subnets */
@@ -2863,6 +3137,14 @@ pub static VERIFY_ENDPOINTS: LazyLock<Vec<VerifyEndpoint>> = LazyLock::new(
                     serde_json::Value::Null,
                 )],
             },
+            VerifyEndpoint {
+                url: &DEMO_INSTANCE_MIGRATE_URL,
+                visibility: Visibility::Protected,
+                unprivileged_access: UnprivilegedAccess::None,
+                allowed_methods: vec![AllowedMethod::Post(
+                    serde_json::to_value(&*DEMO_INSTANCE_MIGRATE).unwrap(),
+                )],
+            },
             VerifyEndpoint {
                 url: &DEMO_INSTANCE_SERIAL_URL,
                 visibility: Visibility::Protected,
@@ -2934,6 +3216,20 @@ pub static VERIFY_ENDPOINTS: LazyLock<Vec<VerifyEndpoint>> = LazyLock::new(
                 unprivileged_access: UnprivilegedAccess::None,
                 allowed_methods: vec![AllowedMethod::Get],
             },
+            VerifyEndpoint {
+                url: &DEMO_INSTANCE_NETWORK_TAGS_URL,
+                visibility: Visibility::Protected,
+                unprivileged_access: UnprivilegedAccess::None,
+                allowed_methods: vec![
+                    AllowedMethod::Get,
+                    AllowedMethod::Put(
+                        serde_json::to_value(
+                            &*DEMO_INSTANCE_NETWORK_TAGS_UPDATE,
+                        )
+                        .unwrap(),
+                    ),
+                ],
+            },
             /* IAM */
             VerifyEndpoint {
                 url: "/v1/system/users-builtin",
@@ -3015,6 +3311,25 @@ pub static VERIFY_ENDPOINTS: LazyLock<Vec<VerifyEndpoint>> = LazyLock::new(
                     serde_json::to_value(&*DEMO_SLED_PROVISION_POLICY).unwrap(),
                 )],
             },
+            VerifyEndpoint {
+                url: &HARDWARE_SLED_EVACUATION_URL,
+                visibility: Visibility::Protected,
+                unprivileged_access: UnprivilegedAccess::None,
+                allowed_methods: vec![
+                    // The evacuation's progress is computed when it is
+                    // fetched.
+                    AllowedMethod::GetVolatile,
+                    AllowedMethod::Post(serde_json::Value::Null),
+                ],
+            },
+            VerifyEndpoint {
+                url: &HARDWARE_SLED_EVACUATION_CANCEL_URL,
+                visibility: Visibility::Protected,
+                unprivileged_access: UnprivilegedAccess::None,
+                allowed_methods: vec![AllowedMethod::Post(
+                    serde_json::Value::Null,
+                )],
+            },
             VerifyEndpoint {
                 url: "/v1/system/hardware/switches",
                 visibility: Visibility::Public,
@@ -3336,6 +3651,71 @@ pub static VERIFY_ENDPOINTS: LazyLock<Vec<VerifyEndpoint>> = LazyLock::new(
                     AllowedMethod::Delete,
                 ],
             },
+            /* Service accounts */
+            VerifyEndpoint {
+                url: &DEMO_SERVICE_ACCOUNTS_URL,
+                visibility: Visibility::Public,
+                unprivileged_access: UnprivilegedAccess::None,
+                allowed_methods: vec![
+                    AllowedMethod::Get,
+                    AllowedMethod::Post(
+                        serde_json::to_value(&*DEMO_SERVICE_ACCOUNT_CREATE)
+                            .unwrap(),
+                    ),
+                ],
+            },
+            VerifyEndpoint {
+                url: &DEMO_SERVICE_ACCOUNT_URL,
+                visibility: Visibility::Protected,
+                unprivileged_access: UnprivilegedAccess::None,
+                allowed_methods: vec![
+                    AllowedMethod::Get,
+                    AllowedMethod::Delete,
+                ],
+            },
+            VerifyEndpoint {
+                url: &DEMO_SERVICE_ACCOUNT_POLICY_URL,
+                visibility: Visibility::Protected,
+                unprivileged_access: UnprivilegedAccess::None,
+                allowed_methods: vec![
+                    AllowedMethod::Get,
+                    AllowedMethod::Put(
+                        serde_json::to_value(&*DEMO_SERVICE_ACCOUNT_POLICY)
+                            .unwrap(),
+                    ),
+                ],
+            },
+            VerifyEndpoint {
+                url: &DEMO_SERVICE_ACCOUNT_TOKENS_URL,
+                visibility: Visibility::Protected,
+                unprivileged_access: UnprivilegedAccess::None,
+                allowed_methods: vec![
+                    AllowedMethod::Get,
+                    AllowedMethod::Post(
+                        serde_json::to_value(
+                            &*DEMO_SERVICE_ACCOUNT_TOKEN_CREATE,
+                        )
+                        .unwrap(),
+                    ),
+                ],
+            },
+            VerifyEndpoint {
+                url: &DEMO_SERVICE_ACCOUNT_TOKEN_URL,
+                visibility: Visibility::Protected,
+                unprivileged_access: UnprivilegedAccess::None,
+                allowed_methods: vec![
+                    AllowedMethod::Get,
+                    AllowedMethod::Delete,
+                ],
+            },
+            VerifyEndpoint {
+                url: &DEMO_SERVICE_ACCOUNT_TOKEN_ROTATE_URL,
+                visibility: Visibility::Protected,
+                unprivileged_access: UnprivilegedAccess::None,
+                allowed_methods: vec![AllowedMethod::Post(
+                    serde_json::Value::Null,
+                )],
+            },
             /* External Networking */
             VerifyEndpoint {
                 url: &DEMO_SWITCH_PORT_URL,
@@ -3551,6 +3931,48 @@ pub static VERIFY_ENDPOINTS: LazyLock<Vec<VerifyEndpoint>> = LazyLock::new(
                     serde_json::to_value(&()).unwrap(),
                 )],
             },
+            // Load balancers
+            VerifyEndpoint {
+                url: &DEMO_PROJECT_URL_LOAD_BALANCERS,
+                visibility: Visibility::Protected,
+                unprivileged_access: UnprivilegedAccess::None,
+                allowed_methods: vec![
+                    AllowedMethod::Post(
+                        serde_json::to_value(&*DEMO_LOAD_BALANCER_CREATE)
+                            .unwrap(),
+                    ),
+                    AllowedMethod::Get,
+                ],
+            },
+            VerifyEndpoint {
+                url: &DEMO_LOAD_BALANCER_URL,
+                visibility: Visibility::Protected,
+                unprivileged_access: UnprivilegedAccess::None,
+                allowed_methods: vec![
+                    AllowedMethod::Get,
+                    AllowedMethod::Delete,
+                ],
+            },
+            VerifyEndpoint {
+                url: &DEMO_LOAD_BALANCER_BACKENDS_URL,
+                visibility: Visibility::Protected,
+                unprivileged_access: UnprivilegedAccess::None,
+                allowed_methods: vec![
+                    AllowedMethod::Get,
+                    AllowedMethod::Post(
+                        serde_json::to_value(
+                            &*DEMO_LOAD_BALANCER_BACKEND_CREATE,
+                        )
+                        .unwrap(),
+                    ),
+                ],
+            },
+            VerifyEndpoint {
+                url: &DEMO_LOAD_BALANCER_BACKEND_URL,
+                visibility: Visibility::Protected,
+                unprivileged_access: UnprivilegedAccess::None,
+                allowed_methods: vec![AllowedMethod::Delete],
+            },
             // User-facing services IP allowlist
             VerifyEndpoint {
                 url: &ALLOW_LIST_URL,
@@ -3586,6 +4008,40 @@ pub static VERIFY_ENDPOINTS: LazyLock<Vec<VerifyEndpoint>> = LazyLock::new(
                         .unwrap(),
                 )],
             },
+            VerifyEndpoint {
+                url: &SMTP_RECEIVERS_URL,
+                visibility: Visibility::Public,
+                unprivileged_access: UnprivilegedAccess::None,
+                allowed_methods: vec![AllowedMethod::Post(
+                    serde_json::to_value(&*DEMO_SMTP_RECEIVER_CREATE).unwrap(),
+                )],
+            },
+            VerifyEndpoint {
+                url: &DEMO_SMTP_RECEIVER_URL,
+                visibility: Visibility::Protected,
+                unprivileged_access: UnprivilegedAccess::None,
+                allowed_methods: vec![AllowedMethod::Put(
+                    serde_json::to_value(&*DEMO_SMTP_RECEIVER_UPDATE).unwrap(),
+                )],
+            },
+            VerifyEndpoint {
+                url: &SYSLOG_RECEIVERS_URL,
+                visibility: Visibility::Public,
+                unprivileged_access: UnprivilegedAccess::None,
+                allowed_methods: vec![AllowedMethod::Post(
+                    serde_json::to_value(&*DEMO_SYSLOG_RECEIVER_CREATE)
+                        .unwrap(),
+                )],
+            },
+            VerifyEndpoint {
+                url: &DEMO_SYSLOG_RECEIVER_URL,
+                visibility: Visibility::Protected,
+                unprivileged_access: UnprivilegedAccess::None,
+                allowed_methods: vec![AllowedMethod::Put(
+                    serde_json::to_value(&*DEMO_SYSLOG_RECEIVER_UPDATE)
+                        .unwrap(),
+                )],
+            },
             VerifyEndpoint {
                 url: &ALERT_RECEIVERS_URL,
                 visibility: Visibility::Public,
@@ -3714,6 +4170,27 @@ pub static VERIFY_ENDPOINTS: LazyLock<Vec<VerifyEndpoint>> = LazyLock::new(
                 unprivileged_access: UnprivilegedAccess::None,
                 allowed_methods: vec![AllowedMethod::Get],
             },
+            VerifyEndpoint {
+                url: &AUDIT_LOG_SINKS_URL,
+                visibility: Visibility::Public,
+                unprivileged_access: UnprivilegedAccess::None,
+                allowed_methods: vec![
+                    AllowedMethod::Get,
+                    AllowedMethod::Post(
+                        serde_json::to_value(&*DEMO_AUDIT_LOG_SINK_CREATE)
+                            .unwrap(),
+                    ),
+                ],
+            },
+            VerifyEndpoint {
+                url: &DEMO_AUDIT_LOG_SINK_URL,
+                visibility: Visibility::Protected,
+                unprivileged_access: UnprivilegedAccess::None,
+                allowed_methods: vec![
+                    AllowedMethod::Get,
+                    AllowedMethod::Delete,
+                ],
+            },
             // SCIM client tokens
             VerifyEndpoint {
                 url: &SCIM_TOKENS_URL,
@@ -1623,6 +1623,207 @@ async fn test_instance_migrate_v2p_and_routes(
     }
 }
 
+/// Creates and starts an instance with no disks (the simulated sled agent
+/// assumes that disks are co-located with their instances), returning its ID
+/// and the sled it's running on.
+async fn create_running_migratable_instance(
+    cptestctx: &ControlPlaneTestContext,
+    instance_name: &str,
+) -> (InstanceUuid, SledUuid) {
+    let client = &cptestctx.external_client;
+    let nexus = &cptestctx.server.server_context().nexus;
+    let instance = nexus_test_utils::resource_helpers::create_instance_with(
+        client,
+        PROJECT_NAME,
+        instance_name,
+        &instance::InstanceNetworkInterfaceAttachment::DefaultIpv4,
+        Vec::<instance::InstanceDiskAttachment>::new(),
+        Vec::<ExternalIpCreate>::new(),
+        true,
+        Default::default(),
+        None,
+        Vec::new(),
+    )
+    .await;
+    let instance_id = InstanceUuid::from_untyped_uuid(instance.identity.id);
+    instance_simulate(nexus, &instance_id).await;
+    instance_wait_for_state(client, instance_id, InstanceState::Running).await;
+
+    let sled_id = nexus
+        .active_instance_info(&instance_id, None)
+        .await
+        .unwrap()
+        .expect("running instance should have a sled")
+        .sled_id;
+    (instance_id, sled_id)
+}
+
+#[nexus_test(extra_sled_agents = 1)]
+async fn test_instance_migrate_external(cptestctx: &ControlPlaneTestContext) {
+    let client = &cptestctx.external_client;
+    let nexus = &cptestctx.server.server_context().nexus;
+    let instance_name = "wandering-albatross";
+    create_project_and_pool(client).await;
+    let (instance_id, original_sled) =
+        create_running_migratable_instance(cptestctx, instance_name).await;
+    let migrate_url = format!(
+        "/v1/instances/{instance_name}/migrate?{}",
+        get_project_selector()
+    );
+
+    // An instance can't be migrated to the sled it's already running on.
+    let error: HttpErrorResponseBody = NexusRequest::expect_failure_with_body(
+        client,
+        StatusCode::BAD_REQUEST,
+        Method::POST,
+        &migrate_url,
+        &instance::InstanceMigrate { dst_sled_id: Some(original_sled) },
+    )
+    .authn_as(AuthnMode::PrivilegedUser)
+    .execute_and_parse_unwrap()
+    .await;
+    assert_eq!(
+        error.message,
+        "instance is already running on destination sled"
+    );
+
+    // Without a destination, Nexus picks one, which must be the other sled.
+    let _: Instance = NexusRequest::new(
+        RequestBuilder::new(client, Method::POST, &migrate_url)
+            .body(Some(&instance::InstanceMigrate { dst_sled_id: None }))
+            .expect_status(Some(StatusCode::ACCEPTED)),
+    )
+    .authn_as(AuthnMode::PrivilegedUser)
+    .execute_and_parse_unwrap()
+    .await;
+
+    let info = nexus
+        .active_instance_info(&instance_id, None)
+        .await
+        .unwrap()
+        .expect("instance should be on a sled");
+    assert_eq!(info.sled_id, original_sled);
+    assert!(info.dst_propolis_id.is_some());
+
+    // A second migration can't start while the first is underway.
+    NexusRequest::expect_failure_with_body(
+        client,
+        StatusCode::CONFLICT,
+        Method::POST,
+        &migrate_url,
+        &instance::InstanceMigrate { dst_sled_id: None },
+    )
+    .authn_as(AuthnMode::PrivilegedUser)
+    .execute()
+    .await
+    .unwrap();
+}
+
+#[nexus_test(extra_sled_agents = 1)]
+async fn test_sled_evacuation_migrates_instances(
+    cptestctx: &ControlPlaneTestContext,
+) {
+    let client = &cptestctx.external_client;
+    let lockstep_client = &cptestctx.lockstep_client;
+    let nexus = &cptestctx.server.server_context().nexus;
+    create_project_and_pool(client).await;
+    let (instance_id, original_sled) =
+        create_running_migratable_instance(cptestctx, "homeward-swallow").await;
+    let evacuation_url =
+        format!("/v1/system/hardware/sleds/{original_sled}/evacuation");
+
+    let evacuation: sled::SledEvacuation =
+        NexusRequest::objects_post_no_body(client, &evacuation_url)
+            .authn_as(AuthnMode::PrivilegedUser)
+            .execute_and_parse_unwrap()
+            .await;
+    assert_eq!(evacuation.instances_total, 1);
+    assert_eq!(evacuation.instances_remaining, 1);
+
+    // The evacuator should start migrating the instance to the other sled.
+    nexus_test_utils::background::activate_background_task(
+        lockstep_client,
+        "sled_evacuator",
+    )
+    .await;
+    let evacuation: sled::SledEvacuation =
+        NexusRequest::object_get(client, &evacuation_url)
+            .authn_as(AuthnMode::PrivilegedUser)
+            .execute_and_parse_unwrap()
+            .await;
+    assert_eq!(evacuation.state, sled::SledEvacuationState::InProgress);
+    assert_eq!(evacuation.migrations_started, 1);
+    assert_eq!(evacuation.instances_migrating, 1);
+    assert_eq!(evacuation.last_error, None);
+
+    let info = nexus
+        .active_instance_info(&instance_id, None)
+        .await
+        .unwrap()
+        .expect("instance should be on a sled");
+    let src_propolis_id = info.propolis_id;
+    let dst_propolis_id =
+        info.dst_propolis_id.expect("instance should have a migration target");
+    let dst_sled_id = if original_sled == cptestctx.first_sled_id() {
+        cptestctx.second_sled_id()
+    } else {
+        cptestctx.first_sled_id()
+    };
+    let migration_id = {
+        let datastore = nexus.datastore();
+        let opctx = OpContext::for_tests(
+            cptestctx.logctx.log.new(o!()),
+            datastore.clone(),
+        );
+        let (.., authz_instance) = LookupPath::new(&opctx, datastore)
+            .instance_id(instance_id.into_untyped_uuid())
+            .lookup_for(nexus_db_queries::authz::Action::Read)
+            .await
+            .unwrap();
+        datastore
+            .instance_refetch(&opctx, &authz_instance)
+            .await
+            .unwrap()
+            .migration_id
+            .expect("the evacuator should have started a migration")
+    };
+
+    // Finish the migration, after which the evacuation can complete.
+    instance_simulate_migration_source(
+        cptestctx,
+        nexus,
+        original_sled,
+        src_propolis_id,
+        migration_id,
+    )
+    .await;
+    vmm_simulate_on_sled(cptestctx, nexus, original_sled, src_propolis_id)
+        .await;
+    vmm_simulate_on_sled(cptestctx, nexus, dst_sled_id, dst_propolis_id).await;
+    instance_wait_for_state(client, instance_id, InstanceState::Running).await;
+
+    nexus_test_utils::background::activate_background_task(
+        lockstep_client,
+        "sled_evacuator",
+    )
+    .await;
+    let evacuation: sled::SledEvacuation =
+        NexusRequest::object_get(client, &evacuation_url)
+            .authn_as(AuthnMode::PrivilegedUser)
+            .execute_and_parse_unwrap()
+            .await;
+    assert_eq!(evacuation.state, sled::SledEvacuationState::Completed);
+    assert_eq!(evacuation.instances_remaining, 0);
+
+    let current_sled = nexus
+        .active_instance_info(&instance_id, None)
+        .await
+        .unwrap()
+        .expect("migrated instance should have a sled")
+        .sled_id;
+    assert_eq!(current_sled, dst_sled_id);
+}
+
 #[nexus_test]
 async fn test_instance_migration_compatible_cpu_platforms(
     cptestctx: &ControlPlaneTestContext,
@@ -0,0 +1,265 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use http::StatusCode;
+use nexus_test_utils::resource_helpers::create_default_ip_pools;
+use nexus_test_utils::resource_helpers::create_instance;
+use nexus_test_utils::resource_helpers::create_project;
+use nexus_test_utils::resource_helpers::object_create;
+use nexus_test_utils::resource_helpers::object_create_error;
+use nexus_test_utils::resource_helpers::object_delete;
+use nexus_test_utils::resource_helpers::object_delete_error;
+use nexus_test_utils::resource_helpers::object_get;
+use nexus_test_utils::resource_helpers::object_get_error;
+use nexus_test_utils::resource_helpers::objects_list_page_authz;
+use nexus_test_utils_macros::nexus_test;
+use nexus_types::external_api::floating_ip;
+use nexus_types::external_api::ip_pool;
+use nexus_types::external_api::load_balancer::LoadBalancer;
+use nexus_types::external_api::load_balancer::LoadBalancerBackend;
+use nexus_types::external_api::load_balancer::LoadBalancerBackendCreate;
+use nexus_types::external_api::load_balancer::LoadBalancerBackendHealth;
+use nexus_types::external_api::load_balancer::LoadBalancerCreate;
+use nexus_types::external_api::load_balancer::LoadBalancerHealthCheck;
+use nexus_types::identity::Resource;
+use omicron_common::api::external::IdentityMetadataCreateParams;
+use omicron_common::api::external::NameOrId;
+use std::net::IpAddr;
+use std::net::Ipv4Addr;
+
+type ControlPlaneTestContext =
+    nexus_test_utils::ControlPlaneTestContext<omicron_nexus::Server>;
+
+const PROJECT_NAME: &str = "lb-project";
+const LB_NAME: &str = "web";
+
+fn load_balancers_url(project: &str) -> String {
+    format!("/v1/load-balancers?project={project}")
+}
+
+fn load_balancer_url(project: &str, name: &str) -> String {
+    format!("/v1/load-balancers/{name}?project={project}")
+}
+
+fn backends_url(project: &str, name: &str) -> String {
+    format!("/v1/load-balancers/{name}/backends?project={project}")
+}
+
+fn load_balancer_create(
+    name: &str,
+    pool: &ip_pool::IpPool,
+    ports: Vec<u16>,
+) -> LoadBalancerCreate {
+    LoadBalancerCreate {
+        identity: IdentityMetadataCreateParams {
+            name: name.parse().unwrap(),
+            description: String::from("a load balancer"),
+        },
+        address_allocator: floating_ip::AddressAllocator::Auto {
+            pool_selector: ip_pool::PoolSelector::Explicit {
+                pool: pool.identity.name.clone().into(),
+            },
+        },
+        ports,
+        backend_tag: None,
+        health_check: None,
+    }
+}
+
+#[nexus_test]
+async fn test_load_balancer_lifecycle(cptestctx: &ControlPlaneTestContext) {
+    let client = &cptestctx.external_client;
+
+    let (pool, _) = create_default_ip_pools(client).await;
+    let project = create_project(client, PROJECT_NAME).await;
+
+    // Listener ports must be non-empty and distinct.
+    for (ports, message) in [
+        (vec![], "a load balancer must listen on at least one port"),
+        (vec![80, 80], "load balancer ports must be distinct"),
+        (vec![0], "load balancer ports must not be 0"),
+    ] {
+        let error = object_create_error(
+            client,
+            &load_balancers_url(PROJECT_NAME),
+            &load_balancer_create(LB_NAME, &pool, ports),
+            StatusCode::BAD_REQUEST,
+        )
+        .await;
+        assert_eq!(error.message, message);
+    }
+
+    // As must the health check thresholds be positive.
+    let error = object_create_error(
+        client,
+        &load_balancers_url(PROJECT_NAME),
+        &LoadBalancerCreate {
+            health_check: Some(LoadBalancerHealthCheck {
+                port: 8080,
+                healthy_threshold: 0,
+                unhealthy_threshold: 3,
+            }),
+            ..load_balancer_create(LB_NAME, &pool, vec![80])
+        },
+        StatusCode::BAD_REQUEST,
+    )
+    .await;
+    assert_eq!(error.message, "health check thresholds must be at least 1");
+
+    // The load balancer's address comes from the pool, like a floating IP's.
+    let lb: LoadBalancer = object_create(
+        client,
+        &load_balancers_url(PROJECT_NAME),
+        &load_balancer_create(LB_NAME, &pool, vec![80, 443]),
+    )
+    .await;
+    assert_eq!(lb.identity.name.as_str(), LB_NAME);
+    assert_eq!(lb.project_id, project.identity.id);
+    assert_eq!(lb.ip_pool_id, pool.identity.id);
+    assert_eq!(lb.ip, IpAddr::from(Ipv4Addr::new(10, 0, 0, 0)));
+    assert_eq!(lb.ports, vec![80, 443]);
+    assert_eq!(lb.health_check, None);
+
+    let error = object_create_error(
+        client,
+        &load_balancers_url(PROJECT_NAME),
+        &load_balancer_create(LB_NAME, &pool, vec![80]),
+        StatusCode::BAD_REQUEST,
+    )
+    .await;
+    assert_eq!(
+        error.message,
+        format!("already exists: load-balancer \"{LB_NAME}\"")
+    );
+
+    let lbs = objects_list_page_authz::<LoadBalancer>(
+        client,
+        &load_balancers_url(PROJECT_NAME),
+    )
+    .await
+    .items;
+    assert_eq!(lbs, vec![lb.clone()]);
+    let fetched: LoadBalancer =
+        object_get(client, &load_balancer_url(PROJECT_NAME, LB_NAME)).await;
+    assert_eq!(fetched, lb);
+    let fetched: LoadBalancer =
+        object_get(client, &format!("/v1/load-balancers/{}", lb.identity.id))
+            .await;
+    assert_eq!(fetched, lb);
+
+    // Add an instance as a backend, by name.
+    let instance = create_instance(client, PROJECT_NAME, "backend").await;
+    let backend: LoadBalancerBackend = object_create(
+        client,
+        &backends_url(PROJECT_NAME, LB_NAME),
+        &LoadBalancerBackendCreate {
+            instance: NameOrId::Name("backend".parse().unwrap()),
+        },
+    )
+    .await;
+    assert_eq!(backend.load_balancer_id, lb.identity.id);
+    assert_eq!(backend.instance_id, instance.identity.id);
+    assert!(backend.explicit);
+    assert_eq!(backend.health, LoadBalancerBackendHealth::Unknown);
+    assert!(!backend.serving);
+    assert!(backend.ports.is_empty());
+
+    let backends = objects_list_page_authz::<LoadBalancerBackend>(
+        client,
+        &backends_url(PROJECT_NAME, LB_NAME),
+    )
+    .await
+    .items;
+    assert_eq!(backends.len(), 1);
+    assert_eq!(backends[0].instance_id, instance.identity.id);
+
+    // Remove the backend, which can only be done once.
+    let backend_url = format!(
+        "/v1/load-balancers/{LB_NAME}/backends/{}?project={PROJECT_NAME}",
+        instance.identity.id
+    );
+    object_delete(client, &backend_url).await;
+    object_delete_error(client, &backend_url, StatusCode::NOT_FOUND).await;
+    let backends = objects_list_page_authz::<LoadBalancerBackend>(
+        client,
+        &backends_url(PROJECT_NAME, LB_NAME),
+    )
+    .await
+    .items;
+    assert!(backends.is_empty());
+
+    // Deleting the load balancer releases its address for reuse.
+    object_delete(client, &load_balancer_url(PROJECT_NAME, LB_NAME)).await;
+    object_get_error(
+        client,
+        &load_balancer_url(PROJECT_NAME, LB_NAME),
+        StatusCode::NOT_FOUND,
+    )
+    .await;
+    let lb: LoadBalancer = object_create(
+        client,
+        &load_balancers_url(PROJECT_NAME),
+        &load_balancer_create(LB_NAME, &pool, vec![80]),
+    )
+    .await;
+    assert_eq!(lb.ip, IpAddr::from(Ipv4Addr::new(10, 0, 0, 0)));
+}
+
+#[nexus_test]
+async fn test_load_balancer_backends_in_project(
+    cptestctx: &ControlPlaneTestContext,
+) {
+    let client = &cptestctx.external_client;
+
+    let (pool, _) = create_default_ip_pools(client).await;
+    create_project(client, PROJECT_NAME).await;
+    create_project(client, "other-project").await;
+    let other = create_instance(client, "other-project", "other").await;
+
+    let _: LoadBalancer = object_create(
+        client,
+        &load_balancers_url(PROJECT_NAME),
+        &load_balancer_create(LB_NAME, &pool, vec![80]),
+    )
+    .await;
+
+    // Backends must be in the load balancer's project.
+    let error = object_create_error(
+        client,
+        &backends_url(PROJECT_NAME, LB_NAME),
+        &LoadBalancerBackendCreate {
+            instance: NameOrId::Id(other.identity.id),
+        },
+        StatusCode::BAD_REQUEST,
+    )
+    .await;
+    assert_eq!(
+        error.message,
+        "backends must be in the same project as the load balancer"
+    );
+
+    // An instance named without a project is looked up in the load
+    // balancer's project.
+    object_create_error(
+        client,
+        &backends_url(PROJECT_NAME, LB_NAME),
+        &LoadBalancerBackendCreate {
+            instance: NameOrId::Name("other".parse().unwrap()),
+        },
+        StatusCode::NOT_FOUND,
+    )
+    .await;
+
+    // A project cannot be deleted while it has load balancers.
+    let error = object_delete_error(
+        client,
+        &format!("/v1/projects/{PROJECT_NAME}"),
+        StatusCode::BAD_REQUEST,
+    )
+    .await;
+    assert_eq!(
+        error.message,
+        format!("project to be deleted contains a load balancer: {LB_NAME}")
+    );
+}
@@ -7,10 +7,13 @@
 //! See the driver in the parent directory for how and why this is structured
 //! the way it is.
 
+mod acme;
 mod address_lots;
 mod affinity;
+mod alert_receivers;
 mod allow_list;
 mod audit_log;
+mod audit_log_sinks;
 mod authn_http;
 mod authz;
 mod basic;
@@ -32,6 +35,7 @@ mod instances;
 mod internet_gateway;
 mod inventory_matching;
 mod ip_pools;
+mod load_balancers;
 mod local_storage;
 mod metrics;
 mod metrics_querier;
@@ -45,11 +49,13 @@ mod projects;
 mod quiesce;
 mod quotas;
 mod rack;
+mod rate_limit;
 mod role_assignments;
 mod router_routes;
 mod saml;
 mod schema;
 mod scim;
+mod service_accounts;
 mod silo_users;
 mod silos;
 mod sleds;
@@ -66,6 +72,7 @@ mod updates;
 mod users_builtin;
 mod utilization;
 mod volume_management;
+mod vpc_dns;
 mod vpc_firewall;
 mod vpc_routers;
 mod vpc_subnets;
@@ -0,0 +1,196 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Tests for rate limiting of the external API
+
+use dropshot::HttpErrorResponseBody;
+use dropshot::test_util::ClientTestContext;
+use http::{StatusCode, header, method::Method};
+use nexus_db_queries::db::fixed_data::silo::DEFAULT_SILO;
+use nexus_db_queries::db::identity::Resource;
+use nexus_test_utils::http_testing::{
+    AuthnMode, NexusRequest, RequestBuilder, TestResponse,
+};
+use nexus_test_utils::resource_helpers::{
+    create_local_user, create_silo, grant_iam, object_get, object_put,
+    test_params,
+};
+use nexus_test_utils_macros::nexus_test;
+use nexus_types::external_api::policy::SiloRole;
+use nexus_types::external_api::silo::{
+    SiloIdentityMode, SiloRateLimit, SiloRateLimitUpdate,
+};
+use omicron_common::api::external::Nullable;
+use std::num::NonZeroU32;
+
+type ControlPlaneTestContext =
+    nexus_test_utils::ControlPlaneTestContext<omicron_nexus::Server>;
+
+async fn get_me(
+    client: &ClientTestContext,
+    authn_mode: AuthnMode,
+) -> TestResponse {
+    NexusRequest::new(
+        RequestBuilder::new(client, Method::GET, "/v1/me")
+            .expect_status(Some(StatusCode::OK)),
+    )
+    .authn_as(authn_mode)
+    .execute()
+    .await
+    .unwrap()
+}
+
+#[nexus_test]
+async fn test_silo_rate_limit(cptestctx: &ControlPlaneTestContext) {
+    let client = &cptestctx.external_client;
+    let url =
+        format!("/v1/system/silos/{}/rate-limit", DEFAULT_SILO.identity().name);
+
+    // By default, a Silo doesn't override anything.
+    let rate_limit: SiloRateLimit = object_get(client, &url).await;
+    assert_eq!(rate_limit.silo_id, DEFAULT_SILO.id());
+    assert_eq!(rate_limit.requests_per_second, None);
+    assert_eq!(rate_limit.burst, None);
+
+    // Allow each principal a burst of a few requests, refilling slowly enough
+    // that the test can't race with it.
+    let burst = NonZeroU32::new(3).unwrap();
+    let rate_limit: SiloRateLimit = object_put(
+        client,
+        &url,
+        &SiloRateLimitUpdate {
+            requests_per_second: Nullable(Some(NonZeroU32::new(1).unwrap())),
+            burst: Nullable(Some(burst)),
+        },
+    )
+    .await;
+    assert_eq!(rate_limit.requests_per_second, NonZeroU32::new(1));
+    assert_eq!(rate_limit.burst, Some(burst));
+
+    for _ in 0..burst.get() {
+        get_me(client, AuthnMode::PrivilegedUser).await;
+    }
+    let response = NexusRequest::new(
+        RequestBuilder::new(client, Method::GET, "/v1/me")
+            .expect_rate_limited(),
+    )
+    .authn_as(AuthnMode::PrivilegedUser)
+    .execute()
+    .await
+    .unwrap();
+    let retry_after: u64 = response
+        .headers
+        .get(header::RETRY_AFTER)
+        .expect("rate limited response has Retry-After")
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(retry_after >= 1);
+    let error: HttpErrorResponseBody =
+        serde_json::from_slice(&response.body).unwrap();
+    assert_eq!(error.error_code, Some(String::from("TooManyRequests")));
+
+    // Each principal has its own bucket, so other users in the same Silo are
+    // unaffected.
+    get_me(client, AuthnMode::UnprivilegedUser).await;
+}
+
+#[nexus_test]
+async fn test_silo_rate_limit_requires_fleet_admin(
+    cptestctx: &ControlPlaneTestContext,
+) {
+    let client = &cptestctx.external_client;
+    let silo =
+        create_silo(client, "rate-limited", true, SiloIdentityMode::LocalOnly)
+            .await;
+    let admin = create_local_user(
+        client,
+        &silo,
+        &"admin".parse().unwrap(),
+        test_params::UserPassword::LoginDisallowed,
+    )
+    .await;
+    grant_iam(
+        client,
+        "/v1/system/silos/rate-limited",
+        SiloRole::Admin,
+        admin.id,
+        AuthnMode::PrivilegedUser,
+    )
+    .await;
+
+    // A Silo admin can see their Silo's rate limit, but not change it.
+    let url = "/v1/system/silos/rate-limited/rate-limit";
+    let rate_limit: SiloRateLimit = NexusRequest::object_get(client, url)
+        .authn_as(AuthnMode::SiloUser(admin.id))
+        .execute_and_parse_unwrap()
+        .await;
+    assert_eq!(rate_limit.requests_per_second, None);
+
+    let update = SiloRateLimitUpdate {
+        requests_per_second: Nullable(Some(NonZeroU32::new(1000).unwrap())),
+        burst: Nullable(None),
+    };
+    NexusRequest::expect_failure_with_body(
+        client,
+        StatusCode::FORBIDDEN,
+        Method::PUT,
+        url,
+        &update,
+    )
+    .authn_as(AuthnMode::SiloUser(admin.id))
+    .execute()
+    .await
+    .unwrap();
+
+    let rate_limit: SiloRateLimit = object_get(client, url).await;
+    assert_eq!(rate_limit.requests_per_second, None);
+}
+
+#[tokio::test]
+async fn test_failed_authn_rate_limit() {
+    // Allow each client address a burst of a few unauthenticated requests,
+    // refilling slowly enough that the test can't race with it.
+    let burst = NonZeroU32::new(3).unwrap();
+    let cptestctx = nexus_test_utils::ControlPlaneBuilder::new(
+        "test_failed_authn_rate_limit",
+    )
+    .customize_nexus_config(&|config| {
+        let rate_limit = &mut config.pkg.rate_limit;
+        rate_limit.unauthenticated_requests_per_second =
+            NonZeroU32::new(1).unwrap();
+        rate_limit.unauthenticated_burst = burst;
+    })
+    .start::<omicron_nexus::Server>()
+    .await;
+    let client = &cptestctx.external_client;
+    let bogus_token = "Bearer oxide-token-not-a-real-token";
+
+    // Requests with bad credentials fail to authenticate until the client's
+    // bucket is empty, after which they're rate limited.
+    for _ in 0..burst.get() {
+        RequestBuilder::new(client, Method::GET, "/v1/me")
+            .header(header::AUTHORIZATION, bogus_token)
+            .expect_status(Some(StatusCode::UNAUTHORIZED))
+            .execute()
+            .await
+            .unwrap();
+    }
+    let response = RequestBuilder::new(client, Method::GET, "/v1/me")
+        .header(header::AUTHORIZATION, bogus_token)
+        .expect_rate_limited()
+        .execute()
+        .await
+        .unwrap();
+    assert!(response.headers.contains_key(header::RETRY_AFTER));
+    let error: HttpErrorResponseBody =
+        serde_json::from_slice(&response.body).unwrap();
+    assert_eq!(error.error_code, Some(String::from("TooManyRequests")));
+
+    // Authenticated principals have their own buckets, so they're unaffected.
+    get_me(client, AuthnMode::PrivilegedUser).await;
+
+    cptestctx.teardown().await;
+}
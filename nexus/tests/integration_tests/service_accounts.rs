@@ -0,0 +1,359 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Tests for service accounts and their API tokens
+
+use chrono::Utc;
+use dropshot::test_util::ClientTestContext;
+use http::{StatusCode, header, method::Method};
+use nexus_test_utils::http_testing::{AuthnMode, NexusRequest, RequestBuilder};
+use nexus_test_utils::resource_helpers::{
+    create_project, object_create, object_create_error, object_delete,
+    object_get, object_put,
+};
+use nexus_test_utils_macros::nexus_test;
+use nexus_types::external_api::policy::{ProjectRole, SiloRole};
+use nexus_types::external_api::project::{Project, ProjectUpdate};
+use nexus_types::external_api::service_account::{
+    ServiceAccount, ServiceAccountCreate, ServiceAccountPolicy,
+    ServiceAccountRoleAssignment, ServiceAccountToken,
+    ServiceAccountTokenCreate, ServiceAccountTokenValue,
+};
+use omicron_common::api::external::{
+    IdentityMetadataCreateParams, IdentityMetadataUpdateParams,
+};
+use omicron_uuid_kinds::GenericUuid;
+use uuid::Uuid;
+
+type ControlPlaneTestContext =
+    nexus_test_utils::ControlPlaneTestContext<omicron_nexus::Server>;
+
+const SERVICE_ACCOUNTS_URL: &str = "/v1/service-accounts";
+const SERVICE_ACCOUNT_URL: &str = "/v1/service-accounts/ci-bot";
+const SERVICE_ACCOUNT_TOKENS_URL: &str = "/v1/service-accounts/ci-bot/tokens";
+
+async fn create_service_account(client: &ClientTestContext) -> ServiceAccount {
+    object_create(
+        client,
+        SERVICE_ACCOUNTS_URL,
+        &ServiceAccountCreate {
+            identity: IdentityMetadataCreateParams {
+                name: "ci-bot".parse().unwrap(),
+                description: String::from("builds things"),
+            },
+        },
+    )
+    .await
+}
+
+async fn create_token(
+    client: &ClientTestContext,
+    name: &str,
+    read_only: bool,
+    project_ids: Option<Vec<Uuid>>,
+) -> ServiceAccountTokenValue {
+    object_create(
+        client,
+        SERVICE_ACCOUNT_TOKENS_URL,
+        &ServiceAccountTokenCreate {
+            identity: IdentityMetadataCreateParams {
+                name: name.parse().unwrap(),
+                description: String::new(),
+            },
+            time_expires: None,
+            read_only,
+            project_ids,
+        },
+    )
+    .await
+}
+
+async fn request_with_token(
+    client: &ClientTestContext,
+    method: Method,
+    path: &str,
+    body: Option<&ProjectUpdate>,
+    token: &str,
+    status: StatusCode,
+) {
+    RequestBuilder::new(client, method, path)
+        .body(body)
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .expect_status(Some(status))
+        .execute()
+        .await
+        .unwrap_or_else(|e| panic!("unexpected response for {path}: {e}"));
+}
+
+fn project_update() -> ProjectUpdate {
+    ProjectUpdate {
+        identity: IdentityMetadataUpdateParams {
+            name: None,
+            description: Some(String::from("updated by a service account")),
+        },
+    }
+}
+
+#[nexus_test]
+async fn test_service_account_token_scopes(
+    cptestctx: &ControlPlaneTestContext,
+) {
+    let client = &cptestctx.external_client;
+
+    let p1: Project = create_project(client, "p1").await;
+    let _p2: Project = create_project(client, "p2").await;
+
+    let service_account = create_service_account(client).await;
+    let policy = ServiceAccountPolicy {
+        role_assignments: vec![
+            ServiceAccountRoleAssignment::Silo { role_name: SiloRole::Viewer },
+            ServiceAccountRoleAssignment::Project {
+                project_id: p1.identity.id,
+                role_name: ProjectRole::Admin,
+            },
+        ],
+    };
+    let updated: ServiceAccountPolicy =
+        object_put(client, &format!("{SERVICE_ACCOUNT_URL}/policy"), &policy)
+            .await;
+    assert_eq!(updated, policy);
+
+    // An unrestricted token can do whatever the service account's roles
+    // allow.
+    let full = create_token(client, "full", false, None).await;
+    assert!(full.bearer_token.starts_with("oxide-token-"));
+    assert_eq!(
+        full.token.service_account_id.into_untyped_uuid(),
+        service_account.identity.id
+    );
+    request_with_token(
+        client,
+        Method::GET,
+        "/v1/projects/p2",
+        None,
+        &full.bearer_token,
+        StatusCode::OK,
+    )
+    .await;
+    request_with_token(
+        client,
+        Method::PUT,
+        "/v1/projects/p1",
+        Some(&project_update()),
+        &full.bearer_token,
+        StatusCode::OK,
+    )
+    .await;
+
+    // A read-only token can still read, but cannot modify anything.
+    let read_only = create_token(client, "read-only", true, None).await;
+    request_with_token(
+        client,
+        Method::GET,
+        "/v1/projects/p1",
+        None,
+        &read_only.bearer_token,
+        StatusCode::OK,
+    )
+    .await;
+    request_with_token(
+        client,
+        Method::PUT,
+        "/v1/projects/p1",
+        Some(&project_update()),
+        &read_only.bearer_token,
+        StatusCode::FORBIDDEN,
+    )
+    .await;
+
+    // A project-scoped token ignores the Silo-level role, so the other
+    // project isn't even visible.
+    let scoped =
+        create_token(client, "scoped", false, Some(vec![p1.identity.id])).await;
+    request_with_token(
+        client,
+        Method::PUT,
+        "/v1/projects/p1",
+        Some(&project_update()),
+        &scoped.bearer_token,
+        StatusCode::OK,
+    )
+    .await;
+    request_with_token(
+        client,
+        Method::GET,
+        "/v1/projects/p2",
+        None,
+        &scoped.bearer_token,
+        StatusCode::NOT_FOUND,
+    )
+    .await;
+
+    // Tokens can't manage service accounts, since the service account has
+    // no Silo admin role.
+    request_with_token(
+        client,
+        Method::GET,
+        SERVICE_ACCOUNTS_URL,
+        None,
+        &full.bearer_token,
+        StatusCode::FORBIDDEN,
+    )
+    .await;
+}
+
+#[nexus_test]
+async fn test_service_account_token_lifecycle(
+    cptestctx: &ControlPlaneTestContext,
+) {
+    let client = &cptestctx.external_client;
+
+    create_service_account(client).await;
+    object_put::<_, ServiceAccountPolicy>(
+        client,
+        &format!("{SERVICE_ACCOUNT_URL}/policy"),
+        &ServiceAccountPolicy {
+            role_assignments: vec![ServiceAccountRoleAssignment::Silo {
+                role_name: SiloRole::Viewer,
+            }],
+        },
+    )
+    .await;
+
+    // Tokens can't be created already expired.
+    object_create_error(
+        client,
+        SERVICE_ACCOUNT_TOKENS_URL,
+        &ServiceAccountTokenCreate {
+            identity: IdentityMetadataCreateParams {
+                name: "stale".parse().unwrap(),
+                description: String::new(),
+            },
+            time_expires: Some(Utc::now() - chrono::Duration::hours(1)),
+            read_only: false,
+            project_ids: None,
+        },
+        StatusCode::BAD_REQUEST,
+    )
+    .await;
+
+    let original = create_token(client, "deploy", false, None).await;
+    request_with_token(
+        client,
+        Method::GET,
+        "/v1/projects",
+        None,
+        &original.bearer_token,
+        StatusCode::OK,
+    )
+    .await;
+
+    // Rotating a token keeps its name but invalidates the old value.
+    let rotated: ServiceAccountTokenValue = NexusRequest::new(
+        RequestBuilder::new(
+            client,
+            Method::POST,
+            &format!("{SERVICE_ACCOUNT_TOKENS_URL}/deploy/rotate"),
+        )
+        .expect_status(Some(StatusCode::OK)),
+    )
+    .authn_as(AuthnMode::PrivilegedUser)
+    .execute_and_parse_unwrap()
+    .await;
+    assert_eq!(rotated.token.identity.name, original.token.identity.name);
+    assert_ne!(rotated.token.identity.id, original.token.identity.id);
+    assert_ne!(rotated.bearer_token, original.bearer_token);
+    request_with_token(
+        client,
+        Method::GET,
+        "/v1/projects",
+        None,
+        &original.bearer_token,
+        StatusCode::UNAUTHORIZED,
+    )
+    .await;
+    request_with_token(
+        client,
+        Method::GET,
+        "/v1/projects",
+        None,
+        &rotated.bearer_token,
+        StatusCode::OK,
+    )
+    .await;
+
+    // Deleting the service account revokes all of its tokens.
+    object_delete(client, SERVICE_ACCOUNT_URL).await;
+    request_with_token(
+        client,
+        Method::GET,
+        "/v1/projects",
+        None,
+        &rotated.bearer_token,
+        StatusCode::UNAUTHORIZED,
+    )
+    .await;
+}
+
+#[nexus_test]
+async fn test_service_account_token_belongs_to_path(
+    cptestctx: &ControlPlaneTestContext,
+) {
+    let client = &cptestctx.external_client;
+
+    create_service_account(client).await;
+    let other: ServiceAccount = object_create(
+        client,
+        SERVICE_ACCOUNTS_URL,
+        &ServiceAccountCreate {
+            identity: IdentityMetadataCreateParams {
+                name: "other-bot".parse().unwrap(),
+                description: String::new(),
+            },
+        },
+    )
+    .await;
+    let other_tokens_url = format!("{SERVICE_ACCOUNTS_URL}/other-bot/tokens");
+    let other_token: ServiceAccountTokenValue = object_create(
+        client,
+        &other_tokens_url,
+        &ServiceAccountTokenCreate {
+            identity: IdentityMetadataCreateParams {
+                name: "deploy".parse().unwrap(),
+                description: String::new(),
+            },
+            time_expires: None,
+            read_only: false,
+            project_ids: None,
+        },
+    )
+    .await;
+    let token_id = other_token.token.identity.id;
+
+    // The other service account's token can't be reached, by ID, through
+    // this service account's path.
+    let wrong_url = format!("{SERVICE_ACCOUNT_TOKENS_URL}/{token_id}");
+    for (method, path) in [
+        (Method::GET, wrong_url.clone()),
+        (Method::POST, format!("{wrong_url}/rotate")),
+        (Method::DELETE, wrong_url.clone()),
+    ] {
+        NexusRequest::expect_failure(
+            client,
+            StatusCode::NOT_FOUND,
+            method,
+            &path,
+        )
+        .authn_as(AuthnMode::PrivilegedUser)
+        .execute()
+        .await
+        .unwrap_or_else(|e| panic!("unexpected response for {path}: {e}"));
+    }
+
+    // The token was neither rotated nor deleted.
+    let token: ServiceAccountToken =
+        object_get(client, &format!("{other_tokens_url}/{token_id}")).await;
+    assert_eq!(token.identity.id, token_id);
+    assert_eq!(token.service_account_id.into_untyped_uuid(), other.identity.id);
+}
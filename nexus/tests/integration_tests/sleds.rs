@@ -6,11 +6,17 @@
 
 use camino::Utf8Path;
 use dropshot::test_util::ClientTestContext;
+use http::StatusCode;
+use http::method::Method;
 use nexus_db_model::PhysicalDisk as DbPhysicalDisk;
 use nexus_db_model::PhysicalDiskKind as DbPhysicalDiskKind;
 use nexus_db_queries::context::OpContext;
 use nexus_test_interface::NexusServer;
 use nexus_test_utils::SLED_AGENT_UUID;
+use nexus_test_utils::background::activate_background_task;
+use nexus_test_utils::http_testing::AuthnMode;
+use nexus_test_utils::http_testing::NexusRequest;
+use nexus_test_utils::http_testing::RequestBuilder;
 use nexus_test_utils::resource_helpers::create_default_ip_pools;
 use nexus_test_utils::resource_helpers::create_instance;
 use nexus_test_utils::resource_helpers::create_project;
@@ -18,7 +24,10 @@ use nexus_test_utils::resource_helpers::objects_list_page_authz;
 use nexus_test_utils::start_sled_agent;
 use nexus_test_utils_macros::nexus_test;
 use nexus_types::external_api::physical_disk::PhysicalDisk;
+use nexus_types::external_api::sled;
 use nexus_types::external_api::sled::Sled;
+use nexus_types::external_api::sled::SledEvacuation;
+use nexus_types::external_api::sled::SledEvacuationState;
 use nexus_types::external_api::sled::SledInstance;
 use omicron_sled_agent::sim;
 use omicron_test_utils::dev::poll::{CondCheckError, wait_for_condition};
@@ -227,3 +236,108 @@ async fn test_sled_instance_list(cptestctx: &ControlPlaneTestContext) {
     assert_eq!(project.identity.name, sled_instances[0].project_name);
     assert_eq!(instance.identity.name, sled_instances[0].name);
 }
+
+#[nexus_test]
+async fn test_sled_evacuation_lifecycle(cptestctx: &ControlPlaneTestContext) {
+    let client = &cptestctx.external_client;
+    let sled_url = format!("/v1/system/hardware/sleds/{SLED_AGENT_UUID}");
+    let evacuation_url = format!("{sled_url}/evacuation");
+    let cancel_url = format!("{evacuation_url}/cancel");
+
+    // The sled has never been evacuated.
+    NexusRequest::expect_failure(
+        client,
+        StatusCode::NOT_FOUND,
+        Method::GET,
+        &evacuation_url,
+    )
+    .authn_as(AuthnMode::PrivilegedUser)
+    .execute()
+    .await
+    .unwrap();
+
+    // Start an evacuation.  There are no instances on the sled, but it should
+    // become non-provisionable anyway.
+    let evacuation: SledEvacuation =
+        NexusRequest::objects_post_no_body(client, &evacuation_url)
+            .authn_as(AuthnMode::PrivilegedUser)
+            .execute_and_parse_unwrap()
+            .await;
+    assert_eq!(evacuation.sled_id.to_string(), SLED_AGENT_UUID);
+    assert_eq!(evacuation.state, SledEvacuationState::InProgress);
+    assert_eq!(evacuation.instances_total, 0);
+    assert_eq!(evacuation.time_finished, None);
+
+    let sled: Sled = NexusRequest::object_get(client, &sled_url)
+        .authn_as(AuthnMode::PrivilegedUser)
+        .execute_and_parse_unwrap()
+        .await;
+    assert_eq!(
+        sled.policy,
+        sled::SledPolicy::InService {
+            provision_policy: sled::SledProvisionPolicy::NonProvisionable
+        }
+    );
+
+    // Only one evacuation of the sled may be in progress at a time.
+    NexusRequest::expect_failure(
+        client,
+        StatusCode::CONFLICT,
+        Method::POST,
+        &evacuation_url,
+    )
+    .authn_as(AuthnMode::PrivilegedUser)
+    .execute()
+    .await
+    .unwrap();
+
+    // With nothing left to migrate, the evacuator completes the evacuation.
+    activate_background_task(&cptestctx.lockstep_client, "sled_evacuator")
+        .await;
+    let completed: SledEvacuation =
+        NexusRequest::object_get(client, &evacuation_url)
+            .authn_as(AuthnMode::PrivilegedUser)
+            .execute_and_parse_unwrap()
+            .await;
+    assert_eq!(completed.id, evacuation.id);
+    assert_eq!(completed.state, SledEvacuationState::Completed);
+    assert!(completed.time_finished.is_some());
+    assert_eq!(completed.instances_remaining, 0);
+
+    // A completed evacuation can't be cancelled.
+    NexusRequest::expect_failure(
+        client,
+        StatusCode::BAD_REQUEST,
+        Method::POST,
+        &cancel_url,
+    )
+    .authn_as(AuthnMode::PrivilegedUser)
+    .execute()
+    .await
+    .unwrap();
+
+    // Start another evacuation and cancel it before the evacuator runs.
+    let second: SledEvacuation =
+        NexusRequest::objects_post_no_body(client, &evacuation_url)
+            .authn_as(AuthnMode::PrivilegedUser)
+            .execute_and_parse_unwrap()
+            .await;
+    assert_ne!(second.id, evacuation.id);
+    let cancelled: SledEvacuation = NexusRequest::new(
+        RequestBuilder::new(client, Method::POST, &cancel_url)
+            .expect_status(Some(StatusCode::OK)),
+    )
+    .authn_as(AuthnMode::PrivilegedUser)
+    .execute_and_parse_unwrap()
+    .await;
+    assert_eq!(cancelled.id, second.id);
+    assert_eq!(cancelled.state, SledEvacuationState::Cancelled);
+
+    let latest: SledEvacuation =
+        NexusRequest::object_get(client, &evacuation_url)
+            .authn_as(AuthnMode::PrivilegedUser)
+            .execute_and_parse_unwrap()
+            .await;
+    assert_eq!(latest.id, second.id);
+    assert_eq!(latest.state, SledEvacuationState::Cancelled);
+}
@@ -507,6 +507,12 @@ static SETUP_REQUESTS: LazyLock<Vec<SetupReq>> = LazyLock::new(|| {
             body: serde_json::to_value(&*DEMO_FLOAT_IP_CREATE).unwrap(),
             id_routes: vec!["/v1/floating-ips/{id}"],
         },
+        // Create a Load Balancer in the project
+        SetupReq::Post {
+            url: &DEMO_PROJECT_URL_LOAD_BALANCERS,
+            body: serde_json::to_value(&*DEMO_LOAD_BALANCER_CREATE).unwrap(),
+            id_routes: vec![],
+        },
         // Create a SAML identity provider
         SetupReq::Post {
             url: &SAML_IDENTITY_PROVIDERS_URL,
@@ -525,6 +531,18 @@ static SETUP_REQUESTS: LazyLock<Vec<SetupReq>> = LazyLock::new(|| {
             body: serde_json::to_value(&*DEMO_CERTIFICATE_CREATE).unwrap(),
             id_routes: vec![],
         },
+        // Create a service account and a token for it
+        SetupReq::Post {
+            url: &DEMO_SERVICE_ACCOUNTS_URL,
+            body: serde_json::to_value(&*DEMO_SERVICE_ACCOUNT_CREATE).unwrap(),
+            id_routes: vec![],
+        },
+        SetupReq::Post {
+            url: &DEMO_SERVICE_ACCOUNT_TOKENS_URL,
+            body: serde_json::to_value(&*DEMO_SERVICE_ACCOUNT_TOKEN_CREATE)
+                .unwrap(),
+            id_routes: vec![],
+        },
         // Create a Support Bundle
         SetupReq::Post {
             url: &SUPPORT_BUNDLES_URL,
@@ -559,6 +577,30 @@ static SETUP_REQUESTS: LazyLock<Vec<SetupReq>> = LazyLock::new(|| {
             body: serde_json::to_value(&*DEMO_WEBHOOK_SECRET_CREATE).unwrap(),
             id_routes: vec![&*DEMO_WEBHOOK_SECRET_DELETE_URL],
         },
+        // Create SMTP and syslog receivers
+        SetupReq::Post {
+            url: &SMTP_RECEIVERS_URL,
+            body: serde_json::to_value(&*DEMO_SMTP_RECEIVER_CREATE).unwrap(),
+            id_routes: vec![],
+        },
+        SetupReq::Post {
+            url: &SYSLOG_RECEIVERS_URL,
+            body: serde_json::to_value(&*DEMO_SYSLOG_RECEIVER_CREATE).unwrap(),
+            id_routes: vec![],
+        },
+        // Create an audit log sink
+        SetupReq::Post {
+            url: &AUDIT_LOG_SINKS_URL,
+            body: serde_json::to_value(&*DEMO_AUDIT_LOG_SINK_CREATE).unwrap(),
+            id_routes: vec![],
+        },
+        // Start evacuating the sled.  This marks the sled non-provisionable,
+        // so it must come after any setup that creates instances.
+        SetupReq::Post {
+            url: &HARDWARE_SLED_EVACUATION_URL,
+            body: serde_json::Value::Null,
+            id_routes: vec![],
+        },
     ]
 });
 
@@ -0,0 +1,141 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Tests for VPCs' private DNS zones
+
+use http::StatusCode;
+use internal_dns_types::config::DnsRecord;
+use nexus_lockstep_client::types::LastResult;
+use nexus_test_utils::background::activate_background_task;
+use nexus_test_utils::resource_helpers::create_default_ip_pools;
+use nexus_test_utils::resource_helpers::create_instance;
+use nexus_test_utils::resource_helpers::create_project;
+use nexus_test_utils::resource_helpers::object_get;
+use nexus_test_utils::resource_helpers::object_put;
+use nexus_test_utils::resource_helpers::object_put_error;
+use nexus_test_utils::resource_helpers::objects_list_page_authz;
+use nexus_test_utils_macros::nexus_test;
+use nexus_types::external_api::vpc::VpcDnsRecord;
+use nexus_types::external_api::vpc::VpcDnsRecords;
+use nexus_types::external_api::vpc::VpcDnsRecordsUpdate;
+use nexus_types::internal_api::background::VpcDnsStatus;
+use omicron_common::api::external::InstanceNetworkInterface;
+use std::net::IpAddr;
+use std::net::Ipv4Addr;
+use std::net::Ipv6Addr;
+
+type ControlPlaneTestContext =
+    nexus_test_utils::ControlPlaneTestContext<omicron_nexus::Server>;
+
+const PROJECT_NAME: &str = "dns-project";
+
+fn dns_records_url() -> String {
+    format!("/v1/vpc-dns-records?project={PROJECT_NAME}&vpc=default")
+}
+
+fn record(name: &str, address: IpAddr) -> VpcDnsRecord {
+    VpcDnsRecord { name: name.parse().unwrap(), address }
+}
+
+#[nexus_test]
+async fn test_vpc_dns_records(cptestctx: &ControlPlaneTestContext) {
+    let client = &cptestctx.external_client;
+    create_project(client, PROJECT_NAME).await;
+
+    let records: VpcDnsRecords = object_get(client, &dns_records_url()).await;
+    assert_eq!(records.zone_name, "default.internal");
+    assert!(records.records.is_empty());
+
+    // A name may not be given the same address twice.
+    let web = record("web", IpAddr::from(Ipv4Addr::new(172, 30, 0, 10)));
+    let error = object_put_error(
+        client,
+        &dns_records_url(),
+        &VpcDnsRecordsUpdate { records: vec![web.clone(), web.clone()] },
+        StatusCode::BAD_REQUEST,
+    )
+    .await;
+    assert_eq!(error.message, "duplicate DNS record \"web\" for 172.30.0.10");
+
+    // Records are replaced wholesale, and listed by name.
+    let db =
+        record("db", IpAddr::from(Ipv6Addr::new(0xfd00, 0, 0, 0, 0, 0, 0, 5)));
+    let records: VpcDnsRecords = object_put(
+        client,
+        &dns_records_url(),
+        &VpcDnsRecordsUpdate { records: vec![web.clone(), db.clone()] },
+    )
+    .await;
+    assert_eq!(records.records, vec![db.clone(), web.clone()]);
+    let records: VpcDnsRecords = object_get(client, &dns_records_url()).await;
+    assert_eq!(records.records, vec![db, web.clone()]);
+
+    let records: VpcDnsRecords = object_put(
+        client,
+        &dns_records_url(),
+        &VpcDnsRecordsUpdate { records: vec![web.clone()] },
+    )
+    .await;
+    assert_eq!(records.records, vec![web]);
+}
+
+#[nexus_test]
+async fn test_vpc_dns_zone_propagation(cptestctx: &ControlPlaneTestContext) {
+    let client = &cptestctx.external_client;
+    create_default_ip_pools(client).await;
+    create_project(client, PROJECT_NAME).await;
+    let web = record("web", IpAddr::from(Ipv4Addr::new(172, 30, 0, 10)));
+    let _: VpcDnsRecords = object_put(
+        client,
+        &dns_records_url(),
+        &VpcDnsRecordsUpdate { records: vec![web] },
+    )
+    .await;
+
+    // The instance's SNAT address makes it a client of the VPC's zone.
+    create_instance(client, PROJECT_NAME, "inst").await;
+    let nics = objects_list_page_authz::<InstanceNetworkInterface>(
+        client,
+        &format!("/v1/network-interfaces?project={PROJECT_NAME}&instance=inst"),
+    )
+    .await
+    .items;
+    assert_eq!(nics.len(), 1);
+    let nic = &nics[0];
+    let nic_ip = *nic.ip_stack.ipv4_addr().expect("NIC has an IPv4 address");
+
+    let task =
+        activate_background_task(&cptestctx.lockstep_client, "vpc_dns").await;
+    let LastResult::Completed(last) = task.last else {
+        panic!("unexpected {:?} returned from vpc_dns task", task.last);
+    };
+    let status: VpcDnsStatus = serde_json::from_value(last.details).unwrap();
+    assert_eq!(status.error, None);
+    assert_eq!(status.zones, 1);
+    assert!(status.server_results.values().all(|r| r.is_ok()), "{status:?}");
+
+    // The zone has reached the external DNS server.
+    let dns_client = dns_service_client::Client::new(
+        &format!(
+            "http://{}",
+            cptestctx.external_dns.dropshot_server.local_addr()
+        ),
+        cptestctx.logctx.log.clone(),
+    );
+    let config = dns_client.vpc_dns_config_get().await.unwrap().into_inner();
+    assert_eq!(Some(config.generation), status.generation);
+    assert_eq!(config.zones.len(), 1);
+    let zone = &config.zones[0];
+    assert_eq!(zone.zone_name, "default.internal");
+    assert!(!zone.clients.is_empty());
+    assert_eq!(zone.records["inst"], vec![DnsRecord::A(nic_ip)]);
+    assert_eq!(
+        zone.records[&format!("{}.inst", nic.identity.name)],
+        vec![DnsRecord::A(nic_ip)]
+    );
+    assert_eq!(
+        zone.records["web"],
+        vec![DnsRecord::A(Ipv4Addr::new(172, 30, 0, 10))]
+    );
+}
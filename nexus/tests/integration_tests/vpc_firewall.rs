@@ -7,25 +7,39 @@ use http::method::Method;
 use nexus_auth::authn;
 use nexus_auth::context::OpContext;
 use nexus_db_lookup::LookupPath;
+use nexus_db_queries::authz;
 use nexus_db_queries::db::fixed_data::vpc_firewall_rule::NEXUS_ICMP_FW_RULE_NAME;
 use nexus_db_queries::db::{self, DataStore};
+use nexus_networking::resolve_firewall_rules_for_sled_agent;
 use nexus_networking::vpc_list_firewall_rules;
 use nexus_test_utils::http_testing::{AuthnMode, NexusRequest, RequestBuilder};
 use nexus_test_utils::resource_helpers::{
-    create_project, create_vpc, object_get, object_put, object_put_error,
+    create_instance, create_project, create_vpc, object_create,
+    object_create_error, object_delete, object_delete_error, object_get,
+    object_put, object_put_error,
 };
 use nexus_test_utils_macros::nexus_test;
-use nexus_types::external_api::vpc::Vpc;
+use nexus_types::external_api::instance::{
+    InstanceNetworkTags, InstanceNetworkTagsUpdate,
+};
+use nexus_types::external_api::vpc::{
+    Vpc, VpcFirewallRuleCreate, VpcFirewallRuleReplace,
+};
 use omicron_common::api::external::{
-    IcmpParamRange, IdentityMetadata, L4Port, L4PortRange, ServiceIcmpConfig,
-    VpcFirewallIcmpFilter, VpcFirewallRule, VpcFirewallRuleAction,
-    VpcFirewallRuleDirection, VpcFirewallRuleFilter, VpcFirewallRuleHostFilter,
-    VpcFirewallRulePriority, VpcFirewallRuleProtocol, VpcFirewallRuleStatus,
-    VpcFirewallRuleTarget, VpcFirewallRuleUpdate, VpcFirewallRuleUpdateParams,
-    VpcFirewallRules,
+    Generation, IcmpParamRange, IdentityMetadata, L4Port, L4PortRange, Name,
+    ServiceIcmpConfig, VpcFirewallIcmpFilter, VpcFirewallRule,
+    VpcFirewallRuleAction, VpcFirewallRuleDirection, VpcFirewallRuleFilter,
+    VpcFirewallRuleHostFilter, VpcFirewallRulePriority,
+    VpcFirewallRuleProtocol, VpcFirewallRuleStatus, VpcFirewallRuleTarget,
+    VpcFirewallRuleUpdate, VpcFirewallRuleUpdateParams, VpcFirewallRules,
 };
+use omicron_common::api::internal::nexus::HostIdentifier;
 use omicron_nexus::Nexus;
+use oxnet::IpNet;
+use sled_agent_types::inventory::NetworkInterfaceKind;
+use std::collections::HashSet;
 use std::convert::TryFrom;
+use std::net::IpAddr;
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -795,3 +809,322 @@ async fn test_nexus_firewall_icmp(cptestctx: &ControlPlaneTestContext) {
     .unwrap();
     assert!(icmp_rule_is_enabled(true, datastore, nexus, &opctx).await);
 }
+
+#[nexus_test]
+async fn test_firewall_rules_network_tags(cptestctx: &ControlPlaneTestContext) {
+    let client = &cptestctx.external_client;
+    let nexus = &cptestctx.server.server_context().nexus;
+    let datastore = nexus.datastore();
+    let opctx =
+        OpContext::for_tests(cptestctx.logctx.log.new(o!()), datastore.clone());
+
+    let project_name = "tagged";
+    create_project(&client, &project_name).await;
+    let web = create_instance(client, project_name, "web1").await;
+    let db_instance = create_instance(client, project_name, "db1").await;
+    let tags_url = |instance: &str| {
+        format!("/v1/instances/{instance}/network-tags?project={project_name}")
+    };
+    let tag = |s: &str| s.parse::<Name>().unwrap();
+
+    // Instances start out without tags; duplicates are collapsed.
+    let tags: InstanceNetworkTags = object_get(client, &tags_url("web1")).await;
+    assert!(tags.tags.is_empty());
+    let tags: InstanceNetworkTags = object_put(
+        client,
+        &tags_url("web1"),
+        &InstanceNetworkTagsUpdate { tags: vec![tag("web"), tag("web")] },
+    )
+    .await;
+    assert_eq!(tags.tags, vec![tag("web")]);
+    let tags: InstanceNetworkTags = object_get(client, &tags_url("web1")).await;
+    assert_eq!(tags.tags, vec![tag("web")]);
+
+    // There's a limit on the number of tags an instance can carry.
+    let error = object_put_error(
+        client,
+        &tags_url("db1"),
+        &InstanceNetworkTagsUpdate {
+            tags: (0..33).map(|i| tag(&format!("tag-{i}"))).collect(),
+        },
+        StatusCode::BAD_REQUEST,
+    )
+    .await;
+    assert_eq!(error.message, "an instance may carry at most 32 network tags");
+
+    // Allow traffic from instances tagged "db" to those tagged "web".
+    let rules: VpcFirewallRules = object_put(
+        client,
+        &format!("/v1/vpc-firewall-rules?vpc=default&project={project_name}"),
+        &VpcFirewallRuleUpdateParams {
+            rules: vec![VpcFirewallRuleUpdate {
+                name: "allow-db-to-web".parse().unwrap(),
+                description: "".to_string(),
+                status: VpcFirewallRuleStatus::Enabled,
+                direction: VpcFirewallRuleDirection::Inbound,
+                targets: vec![VpcFirewallRuleTarget::Tag(tag("web"))],
+                filters: VpcFirewallRuleFilter {
+                    hosts: Some(vec![VpcFirewallRuleHostFilter::Tag(tag(
+                        "db",
+                    ))]),
+                    protocols: None,
+                    ports: None,
+                },
+                action: VpcFirewallRuleAction::Allow,
+                priority: VpcFirewallRulePriority(100),
+            }],
+        },
+    )
+    .await;
+    assert_eq!(
+        rules.rules[0].targets,
+        vec![VpcFirewallRuleTarget::Tag(tag("web"))]
+    );
+
+    let resolve = || async {
+        let (.., authz_vpc, db_vpc) = LookupPath::new(&opctx, datastore)
+            .project_name(&db::model::Name(tag(project_name)))
+            .vpc_name(&db::model::Name(tag("default")))
+            .fetch()
+            .await
+            .unwrap();
+        let rules = datastore
+            .vpc_list_firewall_rules(&opctx, &authz_vpc)
+            .await
+            .unwrap();
+        resolve_firewall_rules_for_sled_agent(
+            datastore, &opctx, &db_vpc, &rules, &opctx.log,
+        )
+        .await
+        .unwrap()
+    };
+
+    // No instance carries the "db" tag yet, so the rule's host filter matches
+    // nothing and the rule is skipped.
+    assert!(resolve().await.is_empty());
+
+    // Once one does, the rule targets the web instance's NIC and admits
+    // traffic from the db instance's address.
+    let _: InstanceNetworkTags = object_put(
+        client,
+        &tags_url("db1"),
+        &InstanceNetworkTagsUpdate { tags: vec![tag("db")] },
+    )
+    .await;
+    let resolved = resolve().await;
+    assert_eq!(resolved.len(), 1);
+    let rule = &resolved[0];
+    assert_eq!(rule.targets.len(), 1);
+    assert_eq!(
+        rule.targets[0].kind,
+        NetworkInterfaceKind::Instance { id: web.identity.id }
+    );
+    let (.., authz_db_instance) = LookupPath::new(&opctx, datastore)
+        .instance_id(db_instance.identity.id)
+        .lookup_for(authz::Action::Read)
+        .await
+        .unwrap();
+    let db_nics = datastore
+        .derive_guest_network_interface_info(&opctx, &authz_db_instance)
+        .await
+        .unwrap();
+    let db_addr = *db_nics[0].ip_config.ipv4_addr().unwrap();
+    assert_eq!(
+        rule.filter_hosts,
+        Some(HashSet::from([HostIdentifier::Ip(IpNet::host_net(IpAddr::V4(
+            db_addr
+        )))]))
+    );
+
+    // Removing the web instance's tag leaves the rule without targets.
+    let _: InstanceNetworkTags = object_put(
+        client,
+        &tags_url("web1"),
+        &InstanceNetworkTagsUpdate { tags: vec![] },
+    )
+    .await;
+    assert!(resolve().await.is_empty());
+}
+
+#[nexus_test]
+async fn test_firewall_rules_per_rule_edits(
+    cptestctx: &ControlPlaneTestContext,
+) {
+    let client = &cptestctx.external_client;
+
+    let project_name = "per-rule-edits";
+    create_project(&client, &project_name).await;
+    let vpc_selector = format!("project={}&vpc=default", project_name);
+    let rules_url = format!("/v1/vpc-firewall-rules?{}", vpc_selector);
+    let rule_url = |name: &str, generation: Option<Generation>| {
+        let mut url =
+            format!("/v1/vpc-firewall-rules/{}?{}", name, vpc_selector);
+        if let Some(generation) = generation {
+            url.push_str(&format!("&generation={}", generation));
+        }
+        url
+    };
+
+    let initial = object_get::<VpcFirewallRules>(client, &rules_url).await;
+    assert!(is_default_firewall_rules("default", &initial.rules));
+
+    let deny_all = VpcFirewallRuleUpdate {
+        name: "deny-all".parse().unwrap(),
+        action: VpcFirewallRuleAction::Deny,
+        description: "deny everything".to_string(),
+        status: VpcFirewallRuleStatus::Enabled,
+        targets: vec![VpcFirewallRuleTarget::Vpc("default".parse().unwrap())],
+        filters: VpcFirewallRuleFilter {
+            hosts: None,
+            ports: None,
+            protocols: None,
+        },
+        direction: VpcFirewallRuleDirection::Inbound,
+        priority: VpcFirewallRulePriority(65535),
+    };
+
+    // Adding a rule leaves the others alone and advances the generation.
+    let created = object_create::<_, VpcFirewallRules>(
+        client,
+        &rules_url,
+        &VpcFirewallRuleCreate {
+            generation: initial.generation,
+            rule: deny_all.clone(),
+        },
+    )
+    .await;
+    assert_eq!(created.rules.len(), initial.rules.len() + 1);
+    assert!(created.generation > initial.generation);
+    let deny_all_id = created
+        .rules
+        .iter()
+        .find(|r| r.identity.name == "deny-all")
+        .expect("created rule should exist")
+        .identity
+        .id;
+    let rule_ids = |rules: &VpcFirewallRules| {
+        rules
+            .rules
+            .iter()
+            .filter(|r| r.identity.id != deny_all_id)
+            .map(|r| (r.identity.name.clone(), r.identity.id))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(rule_ids(&created), rule_ids(&initial));
+
+    // A second tool that still holds the initial generation is turned away,
+    // and the rules are unchanged.
+    let error = object_create_error(
+        client,
+        &rules_url,
+        &VpcFirewallRuleCreate {
+            generation: initial.generation,
+            rule: VpcFirewallRuleUpdate {
+                name: "deny-more".parse().unwrap(),
+                ..deny_all.clone()
+            },
+        },
+        StatusCode::CONFLICT,
+    )
+    .await;
+    assert!(error.message.contains("generation"), "{}", error.message);
+    let rules = object_get::<VpcFirewallRules>(client, &rules_url).await;
+    assert_eq!(rules.generation, created.generation);
+    assert_eq!(rules.rules.len(), created.rules.len());
+
+    // Rule names must stay unique.
+    object_create_error(
+        client,
+        &rules_url,
+        &VpcFirewallRuleCreate {
+            generation: created.generation,
+            rule: deny_all.clone(),
+        },
+        StatusCode::BAD_REQUEST,
+    )
+    .await;
+
+    // Replacing a rule can rename it.
+    let updated = object_put::<_, VpcFirewallRules>(
+        client,
+        &rule_url("deny-all", None),
+        &VpcFirewallRuleReplace {
+            generation: created.generation,
+            rule: VpcFirewallRuleUpdate {
+                name: "deny-most".parse().unwrap(),
+                priority: VpcFirewallRulePriority(65000),
+                ..deny_all.clone()
+            },
+        },
+    )
+    .await;
+    assert!(updated.generation > created.generation);
+    assert!(updated.rules.iter().all(|r| r.identity.name != "deny-all"));
+    let rule = updated
+        .rules
+        .iter()
+        .find(|r| r.identity.name == "deny-most")
+        .expect("renamed rule should exist");
+    assert_eq!(rule.priority, VpcFirewallRulePriority(65000));
+    // Neither the edited rule nor the others get new IDs.
+    assert_eq!(rule.identity.id, deny_all_id);
+    assert_eq!(rule_ids(&updated), rule_ids(&initial));
+
+    // Nor can a rule be renamed over another one.
+    object_put_error(
+        client,
+        &rule_url("deny-most", None),
+        &VpcFirewallRuleReplace {
+            generation: updated.generation,
+            rule: VpcFirewallRuleUpdate {
+                name: initial.rules[0].identity.name.clone(),
+                ..deny_all.clone()
+            },
+        },
+        StatusCode::BAD_REQUEST,
+    )
+    .await;
+
+    object_put_error(
+        client,
+        &rule_url("deny-all", None),
+        &VpcFirewallRuleReplace {
+            generation: updated.generation,
+            rule: deny_all.clone(),
+        },
+        StatusCode::NOT_FOUND,
+    )
+    .await;
+
+    // Deleting a rule requires the current generation too.
+    object_delete_error(
+        client,
+        &rule_url("deny-most", Some(created.generation)),
+        StatusCode::CONFLICT,
+    )
+    .await;
+    object_delete(client, &rule_url("deny-most", Some(updated.generation)))
+        .await;
+    let rules = object_get::<VpcFirewallRules>(client, &rules_url).await;
+    assert!(is_default_firewall_rules("default", &rules.rules));
+    assert!(rules.generation > updated.generation);
+    assert_eq!(rule_ids(&rules), rule_ids(&initial));
+
+    // Replacing the whole rule set also advances the generation, so per-rule
+    // edits made against the previous one fail.
+    let replaced = object_put::<_, VpcFirewallRules>(
+        client,
+        &rules_url,
+        &VpcFirewallRuleUpdateParams { rules: vec![] },
+    )
+    .await;
+    assert!(replaced.rules.is_empty());
+    assert!(replaced.generation > rules.generation);
+    object_create_error(
+        client,
+        &rules_url,
+        &VpcFirewallRuleCreate { generation: rules.generation, rule: deny_all },
+        StatusCode::CONFLICT,
+    )
+    .await;
+}
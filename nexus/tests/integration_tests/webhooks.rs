@@ -8,6 +8,7 @@ use dropshot::test_util::ClientTestContext;
 use hmac::{Hmac, Mac};
 use httpmock::prelude::*;
 use nexus_db_queries::context::OpContext;
+use nexus_test_utils::SLED_AGENT_UUID;
 use nexus_test_utils::background::activate_background_task;
 use nexus_test_utils::http_testing::AuthnMode;
 use nexus_test_utils::http_testing::Collection;
@@ -23,6 +24,7 @@ use nexus_types::external_api::alert::{
     WebhookDeliveryAttemptResult, WebhookReceiver, WebhookSecret,
     WebhookSecretCreate, WebhookSecrets,
 };
+use nexus_types::external_api::sled;
 use omicron_common::api::external::IdentityMetadataCreateParams;
 use omicron_common::api::external::NameOrId;
 use omicron_uuid_kinds::AlertReceiverUuid;
@@ -334,7 +336,9 @@ fn expect_delivery_attempts(
     actual: &AlertDeliveryAttempts,
     expected: &[ExpectAttempt],
 ) {
-    let AlertDeliveryAttempts::Webhook(actual) = actual;
+    let AlertDeliveryAttempts::Webhook(actual) = actual else {
+        panic!("expected webhook delivery attempts, found: {actual:?}");
+    };
     assert_eq!(
         actual.len(),
         expected.len(),
@@ -548,6 +552,77 @@ async fn test_event_delivery(cptestctx: &ControlPlaneTestContext) {
     mock.assert_async().await;
 }
 
+#[nexus_test]
+async fn test_sled_policy_changed_alert(cptestctx: &ControlPlaneTestContext) {
+    let lockstep_client = &cptestctx.lockstep_client;
+
+    let server = httpmock::MockServer::start_async().await;
+
+    // Create a webhook receiver subscribed to sled policy changes.
+    let webhook = webhook_create(
+        &cptestctx,
+        &WebhookCreate {
+            subscriptions: vec![
+                "hardware.sled.policy_changed".parse().unwrap(),
+            ],
+            ..my_great_webhook_params(&server)
+        },
+    )
+    .await;
+    dbg!(&webhook);
+
+    let mock = {
+        let webhook = webhook.clone();
+        server
+            .mock_async(move |when, then| {
+                let body = serde_json::json!({
+                    "alert_class": "hardware.sled.policy_changed",
+                    "alert_version": 0,
+                    "data": {
+                        "sled_id": SLED_AGENT_UUID,
+                        "old_policy": {
+                            "kind": "in_service",
+                            "provision_policy": "provisionable",
+                        },
+                        "new_policy": {
+                            "kind": "in_service",
+                            "provision_policy": "non_provisionable",
+                        },
+                    }
+                })
+                .to_string();
+                when.method(POST)
+                    .header(
+                        "x-oxide-alert-class",
+                        "hardware.sled.policy_changed",
+                    )
+                    .and(is_valid_for_webhook(&webhook))
+                    .json_body_includes(body);
+                then.status(200);
+            })
+            .await
+    };
+
+    // Mark the sled as non-provisionable. This should publish an alert.
+    let _: sled::SledProvisionPolicyResponse = resource_helpers::object_put(
+        &cptestctx.external_client,
+        &format!(
+            "/v1/system/hardware/sleds/{SLED_AGENT_UUID}/provision-policy"
+        ),
+        &sled::SledProvisionPolicyParams {
+            state: sled::SledProvisionPolicy::NonProvisionable,
+        },
+    )
+    .await;
+
+    dbg!(activate_background_task(lockstep_client, "alert_dispatcher").await);
+    dbg!(
+        activate_background_task(lockstep_client, "webhook_deliverator").await
+    );
+
+    mock.assert_async().await;
+}
+
 #[nexus_test]
 async fn test_multiple_secrets(cptestctx: &ControlPlaneTestContext) {
     let nexus = cptestctx.server.server_context().nexus.clone();
@@ -8,6 +8,13 @@ use schemars::JsonSchema;
 use serde::Serialize;
 use std::fmt;
 
+pub mod certificate;
+pub mod hardware;
+pub mod instance;
+pub mod storage;
+pub mod support_bundle;
+pub mod update;
+
 /// Trait implemented by alerts.
 pub trait AlertPayload: Serialize + JsonSchema + std::fmt::Debug {
     const CLASS: AlertClass;
@@ -72,6 +79,28 @@ pub enum AlertClass {
     TestQuuxBar,
     #[strum(serialize = "test.quux.bar.baz")]
     TestQuuxBarBaz,
+    #[strum(serialize = "instance.failed")]
+    InstanceFailed,
+    #[strum(serialize = "instance.auto_restarted")]
+    InstanceAutoRestarted,
+    #[strum(serialize = "instance.migration.completed")]
+    InstanceMigrationCompleted,
+    #[strum(serialize = "instance.migration.failed")]
+    InstanceMigrationFailed,
+    #[strum(serialize = "storage.region_replacement.started")]
+    RegionReplacementStarted,
+    #[strum(serialize = "storage.region_replacement.finished")]
+    RegionReplacementFinished,
+    #[strum(serialize = "hardware.physical_disk.expunged")]
+    PhysicalDiskExpunged,
+    #[strum(serialize = "hardware.sled.policy_changed")]
+    SledPolicyChanged,
+    #[strum(serialize = "support_bundle.ready")]
+    SupportBundleReady,
+    #[strum(serialize = "update.status_changed")]
+    UpdateStatusChanged,
+    #[strum(serialize = "certificate.expiring")]
+    CertificateExpiring,
 }
 
 impl AlertClass {
@@ -102,6 +131,44 @@ impl AlertClass {
                  but they should NOT be treated as notifications of an actual \
                  event in the system."
             }
+            Self::InstanceFailed => {
+                "An instance's active VMM has failed, and the instance has \
+                 transitioned to the `failed` state."
+            }
+            Self::InstanceAutoRestarted => {
+                "An instance was automatically restarted in accordance with \
+                 its auto-restart policy."
+            }
+            Self::InstanceMigrationCompleted => {
+                "An instance finished migrating to a new VMM."
+            }
+            Self::InstanceMigrationFailed => {
+                "An attempt to migrate an instance to a new VMM failed."
+            }
+            Self::RegionReplacementStarted => {
+                "The control plane has begun replacing one of the regions \
+                 backing a volume."
+            }
+            Self::RegionReplacementFinished => {
+                "The control plane has finished replacing one of the regions \
+                 backing a volume."
+            }
+            Self::PhysicalDiskExpunged => {
+                "An operator has expunged a physical disk."
+            }
+            Self::SledPolicyChanged => {
+                "An operator has changed a sled's policy."
+            }
+            Self::SupportBundleReady => {
+                "A support bundle has been collected and is ready to be \
+                 downloaded."
+            }
+            Self::UpdateStatusChanged => {
+                "A system software update has started or completed."
+            }
+            Self::CertificateExpiring => {
+                "A certificate stored by the control plane will expire soon."
+            }
             Self::TestFoo
             | Self::TestFooBar
             | Self::TestFooBaz
@@ -216,4 +283,37 @@ mod tests {
              variant(s) are: {problematic_variants:?}",
         );
     }
+
+    // Every alert class that is neither a probe nor a test class should have a
+    // payload type, so that it can actually be published.
+    #[test]
+    fn test_all_classes_have_payloads() {
+        let payload_classes = [
+            instance::InstanceFailed::CLASS,
+            instance::InstanceAutoRestarted::CLASS,
+            instance::InstanceMigrationCompleted::CLASS,
+            instance::InstanceMigrationFailed::CLASS,
+            storage::RegionReplacementStarted::CLASS,
+            storage::RegionReplacementFinished::CLASS,
+            hardware::PhysicalDiskExpunged::CLASS,
+            hardware::SledPolicyChanged::CLASS,
+            support_bundle::SupportBundleReady::CLASS,
+            update::UpdateStatusChanged::CLASS,
+            certificate::CertificateExpiring::CLASS,
+        ];
+        let missing = AlertClass::ALL_CLASSES
+            .iter()
+            .copied()
+            .filter(|class| {
+                *class != AlertClass::Probe
+                    && !class.is_test()
+                    && !payload_classes.contains(class)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            missing,
+            Vec::<AlertClass>::new(),
+            "the alert class(es) {missing:?} have no payload type",
+        );
+    }
 }
@@ -0,0 +1,86 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Alerts describing certificates stored by the control plane.
+
+use super::AlertClass;
+use super::AlertPayload;
+use chrono::DateTime;
+use chrono::Utc;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+use std::fmt;
+use uuid::Uuid;
+
+/// What a stored certificate is used for.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum CertificateKind {
+    /// A Silo's certificate for its external API and console endpoints.
+    SiloTls,
+    /// The certificate a Silo's SAML identity provider uses to verify
+    /// requests from the control plane.
+    SamlServiceProvider,
+    /// CA certificates trusted when sending the audit log to a syslog
+    /// collector over TLS.
+    AuditLogSinkRoot,
+    /// The certificate a webhook receiver's endpoint most recently presented
+    /// when an alert was delivered to it.
+    WebhookReceiver,
+}
+
+impl CertificateKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CertificateKind::SiloTls => "silo_tls",
+            CertificateKind::SamlServiceProvider => "saml_service_provider",
+            CertificateKind::AuditLogSinkRoot => "audit_log_sink_root",
+            CertificateKind::WebhookReceiver => "webhook_receiver",
+        }
+    }
+}
+
+impl fmt::Display for CertificateKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A stored certificate will expire soon.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct CertificateExpiring {
+    /// What the certificate is used for.
+    pub kind: CertificateKind,
+    /// The ID of the object holding the certificate: the certificate itself,
+    /// the identity provider, the audit log sink, or the webhook receiver.
+    pub id: Uuid,
+    /// The name of the object holding the certificate.
+    pub name: String,
+    /// The Silo the certificate belongs to, if any.
+    pub silo_id: Option<Uuid>,
+    /// When the certificate expires.
+    ///
+    /// For a chain or bundle of certificates, this is when the first of them
+    /// expires.
+    pub time_expires: DateTime<Utc>,
+    /// The number of whole days remaining before the certificate expires.
+    pub days_remaining: i64,
+}
+
+impl AlertPayload for CertificateExpiring {
+    const CLASS: AlertClass = AlertClass::CertificateExpiring;
+    const VERSION: u32 = 0;
+}
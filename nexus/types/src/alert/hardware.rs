@@ -0,0 +1,55 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Alerts describing changes to the rack's hardware.
+
+use super::AlertClass;
+use super::AlertPayload;
+use crate::external_api::sled::SledPolicy;
+use omicron_uuid_kinds::PhysicalDiskUuid;
+use omicron_uuid_kinds::SledUuid;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// An operator has expunged a physical disk.
+///
+/// Expungement is permanent: the control plane will no longer use the disk,
+/// and will replace any data that was stored on it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct PhysicalDiskExpunged {
+    /// The ID of the physical disk.
+    pub disk_id: PhysicalDiskUuid,
+    /// The sled containing the physical disk.
+    pub sled_id: SledUuid,
+    /// The disk's vendor.
+    pub vendor: String,
+    /// The disk's serial number.
+    pub serial: String,
+    /// The disk's model.
+    pub model: String,
+}
+
+impl AlertPayload for PhysicalDiskExpunged {
+    const CLASS: AlertClass = AlertClass::PhysicalDiskExpunged;
+    const VERSION: u32 = 0;
+}
+
+/// An operator has changed a sled's policy.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct SledPolicyChanged {
+    /// The ID of the sled.
+    pub sled_id: SledUuid,
+    /// The sled's serial number.
+    pub serial: String,
+    /// The sled's policy before the change.
+    pub old_policy: SledPolicy,
+    /// The sled's policy after the change.
+    pub new_policy: SledPolicy,
+}
+
+impl AlertPayload for SledPolicyChanged {
+    const CLASS: AlertClass = AlertClass::SledPolicyChanged;
+    const VERSION: u32 = 0;
+}
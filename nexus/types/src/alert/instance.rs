@@ -0,0 +1,116 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Alerts describing instance lifecycle events.
+
+use super::AlertClass;
+use super::AlertPayload;
+use omicron_common::api::external::Name;
+use omicron_uuid_kinds::InstanceUuid;
+use omicron_uuid_kinds::PropolisUuid;
+use omicron_uuid_kinds::SledUuid;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// An instance's active VMM has failed, and the instance has transitioned to
+/// the `failed` state.
+///
+/// If the instance's auto-restart policy permits it, the control plane will
+/// attempt to restart the instance, in which case an
+/// [`InstanceAutoRestarted`] alert will follow.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct InstanceFailed {
+    /// The ID of the instance.
+    pub instance_id: InstanceUuid,
+    /// The name of the instance.
+    pub instance_name: Name,
+    /// The ID of the project containing the instance.
+    pub project_id: Uuid,
+    /// The ID of the VMM that failed.
+    pub propolis_id: PropolisUuid,
+    /// The sled on which the failed VMM was running.
+    pub sled_id: SledUuid,
+}
+
+impl AlertPayload for InstanceFailed {
+    const CLASS: AlertClass = AlertClass::InstanceFailed;
+    const VERSION: u32 = 0;
+}
+
+/// Why an instance was automatically restarted.
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoRestartReason {
+    /// The instance had transitioned to the `failed` state.
+    Failed,
+    /// A previous attempt to start the instance did not complete.
+    StartSagaUnwound,
+}
+
+/// An instance was automatically restarted by the control plane, in
+/// accordance with its auto-restart policy.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct InstanceAutoRestarted {
+    /// The ID of the instance.
+    pub instance_id: InstanceUuid,
+    /// The name of the instance.
+    pub instance_name: Name,
+    /// The ID of the project containing the instance.
+    pub project_id: Uuid,
+    /// Why the instance was restarted.
+    pub reason: AutoRestartReason,
+}
+
+impl AlertPayload for InstanceAutoRestarted {
+    const CLASS: AlertClass = AlertClass::InstanceAutoRestarted;
+    const VERSION: u32 = 0;
+}
+
+/// Details of an instance migration, shared by the
+/// [`InstanceMigrationCompleted`] and [`InstanceMigrationFailed`] alerts.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct InstanceMigration {
+    /// The ID of the instance.
+    pub instance_id: InstanceUuid,
+    /// The name of the instance.
+    pub instance_name: Name,
+    /// The ID of the project containing the instance.
+    pub project_id: Uuid,
+    /// The ID of the migration.
+    pub migration_id: Uuid,
+    /// The ID of the VMM the instance was migrating out of.
+    pub source_propolis_id: PropolisUuid,
+    /// The sled on which the source VMM was running, if it still exists.
+    pub source_sled_id: Option<SledUuid>,
+    /// The ID of the VMM the instance was migrating into.
+    pub target_propolis_id: PropolisUuid,
+    /// The sled on which the target VMM was running, if it still exists.
+    pub target_sled_id: Option<SledUuid>,
+}
+
+/// An instance finished migrating to a new VMM.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(transparent)]
+pub struct InstanceMigrationCompleted(pub InstanceMigration);
+
+impl AlertPayload for InstanceMigrationCompleted {
+    const CLASS: AlertClass = AlertClass::InstanceMigrationCompleted;
+    const VERSION: u32 = 0;
+}
+
+/// An attempt to migrate an instance to a new VMM failed.
+///
+/// If the source VMM is still running, the instance continues to run there.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(transparent)]
+pub struct InstanceMigrationFailed(pub InstanceMigration);
+
+impl AlertPayload for InstanceMigrationFailed {
+    const CLASS: AlertClass = AlertClass::InstanceMigrationFailed;
+    const VERSION: u32 = 0;
+}
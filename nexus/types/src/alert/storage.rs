@@ -0,0 +1,54 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Alerts describing storage repair operations.
+
+use super::AlertClass;
+use super::AlertPayload;
+use omicron_uuid_kinds::VolumeUuid;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// The control plane has begun replacing one of the regions backing a
+/// volume.
+///
+/// While the replacement is in progress, the volume continues to operate with
+/// reduced redundancy.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct RegionReplacementStarted {
+    /// The ID of the region replacement request.
+    pub request_id: Uuid,
+    /// The ID of the volume whose region is being replaced.
+    pub volume_id: VolumeUuid,
+    /// The ID of the region being replaced.
+    pub old_region_id: Uuid,
+    /// The ID of the region replacing it.
+    pub new_region_id: Uuid,
+}
+
+impl AlertPayload for RegionReplacementStarted {
+    const CLASS: AlertClass = AlertClass::RegionReplacementStarted;
+    const VERSION: u32 = 0;
+}
+
+/// The control plane has finished replacing one of the regions backing a
+/// volume, and the old region has been deleted.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct RegionReplacementFinished {
+    /// The ID of the region replacement request.
+    pub request_id: Uuid,
+    /// The ID of the volume whose region was replaced.
+    pub volume_id: VolumeUuid,
+    /// The ID of the region that was replaced.
+    pub old_region_id: Uuid,
+    /// The ID of the region that replaced it, if known.
+    pub new_region_id: Option<Uuid>,
+}
+
+impl AlertPayload for RegionReplacementFinished {
+    const CLASS: AlertClass = AlertClass::RegionReplacementFinished;
+    const VERSION: u32 = 0;
+}
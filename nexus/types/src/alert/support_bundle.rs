@@ -0,0 +1,26 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Alerts describing support bundles.
+
+use super::AlertClass;
+use super::AlertPayload;
+use omicron_uuid_kinds::SupportBundleUuid;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// A support bundle has been collected and is ready to be downloaded.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct SupportBundleReady {
+    /// The ID of the support bundle.
+    pub bundle_id: SupportBundleUuid,
+    /// The reason the bundle was requested.
+    pub reason_for_creation: String,
+}
+
+impl AlertPayload for SupportBundleReady {
+    const CLASS: AlertClass = AlertClass::SupportBundleReady;
+    const VERSION: u32 = 0;
+}
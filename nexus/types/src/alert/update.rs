@@ -0,0 +1,42 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Alerts describing system software updates.
+
+use super::AlertClass;
+use super::AlertPayload;
+use schemars::JsonSchema;
+use semver::Version;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// How far a system software update has progressed.
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateProgress {
+    /// A new target release was set, and the system has begun updating to it.
+    Started,
+    /// All components of the system are running the target release.
+    Completed,
+}
+
+/// The status of a system software update has changed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct UpdateStatusChanged {
+    /// The new status of the update.
+    pub status: UpdateProgress,
+    /// The target release of the update.
+    pub system_version: Version,
+    /// The previous target release, if one was set.
+    ///
+    /// This is only present when the update has started.
+    pub previous_system_version: Option<Version>,
+}
+
+impl AlertPayload for UpdateStatusChanged {
+    const CLASS: AlertClass = AlertClass::UpdateStatusChanged;
+    const VERSION: u32 = 0;
+}
@@ -31,6 +31,39 @@ pub fn blueprint_internal_dns_config(
     active_nexus_generation: Generation,
     overrides: &Overridables,
 ) -> anyhow::Result<DnsConfigZone> {
+    let dns_builder = blueprint_internal_dns_builder(
+        blueprint,
+        sleds_by_id,
+        active_nexus_generation,
+        overrides,
+    )?;
+    Ok(dns_builder.build_zone())
+}
+
+/// Returns the expected reverse DNS zones (mapping underlay addresses back to
+/// the names of sleds and zones) served by internal DNS based on the given
+/// blueprint
+pub fn blueprint_internal_reverse_dns_config(
+    blueprint: &Blueprint,
+    sleds_by_id: &IdOrdMap<Sled>,
+    active_nexus_generation: Generation,
+    overrides: &Overridables,
+) -> anyhow::Result<Vec<DnsConfigZone>> {
+    let dns_builder = blueprint_internal_dns_builder(
+        blueprint,
+        sleds_by_id,
+        active_nexus_generation,
+        overrides,
+    )?;
+    Ok(dns_builder.build_reverse_zones())
+}
+
+fn blueprint_internal_dns_builder(
+    blueprint: &Blueprint,
+    sleds_by_id: &IdOrdMap<Sled>,
+    active_nexus_generation: Generation,
+    overrides: &Overridables,
+) -> anyhow::Result<DnsConfigBuilder> {
     // The DNS names configured here should match what RSS configures for the
     // same zones.  It's tricky to have RSS share the same code because it uses
     // Sled Agent's _internal_ `OmicronZoneConfig` (and friends), whereas we're
@@ -191,7 +224,7 @@ pub fn blueprint_internal_dns_config(
         }
     }
 
-    Ok(dns_builder.build_zone())
+    Ok(dns_builder)
 }
 
 pub fn blueprint_external_dns_config<'a>(
@@ -26,6 +26,7 @@ pub mod image;
 pub mod instance;
 pub mod internet_gateway;
 pub mod ip_pool;
+pub mod load_balancer;
 pub mod metrics;
 pub mod multicast;
 pub mod networking;
@@ -38,6 +39,7 @@ pub mod project;
 pub mod rack;
 pub mod saml;
 pub mod scim;
+pub mod service_account;
 pub mod silo;
 pub mod sled;
 pub mod snapshot;
@@ -2,6 +2,7 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+use crate::alert::certificate::CertificateKind;
 use crate::deployment::PlanningReport;
 use crate::external_api::alert;
 use chrono::DateTime;
@@ -13,6 +14,7 @@ use iddqd::id_upcast;
 use omicron_common::api::external::Generation;
 use omicron_uuid_kinds::AlertReceiverUuid;
 use omicron_uuid_kinds::AlertUuid;
+use omicron_uuid_kinds::AuditLogSinkUuid;
 use omicron_uuid_kinds::BlueprintUuid;
 use omicron_uuid_kinds::CollectionUuid;
 use omicron_uuid_kinds::SitrepUuid;
@@ -27,6 +29,7 @@ use sled_agent_types::early_networking::SwitchSlot;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::VecDeque;
+use std::net::IpAddr;
 use std::net::Ipv6Addr;
 use std::sync::Arc;
 use swrite::SWrite;
@@ -1161,6 +1164,198 @@ pub struct AuditLogCleanupStatus {
     pub error: Option<String>,
 }
 
+/// The status of an `audit_log_export` background task activation.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct AuditLogExportStatus {
+    /// Entries completed at or after this time were not yet eligible for
+    /// delivery, to allow in-flight entries to settle.
+    pub cutoff: DateTime<Utc>,
+    /// Results for each sink that was due for delivery.
+    pub sinks: Vec<AuditLogSinkExportStatus>,
+    /// Error listing sinks, if any.
+    pub error: Option<String>,
+}
+
+/// The results of delivering entries to one audit log sink.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct AuditLogSinkExportStatus {
+    pub sink_id: AuditLogSinkUuid,
+    pub sink_name: String,
+    /// Number of entries delivered to the sink in this activation.
+    pub entries_delivered: usize,
+    /// Error delivering to the sink, if any. The sink will back off before
+    /// it is retried.
+    pub error: Option<String>,
+}
+
+/// The status of a `sled_evacuator` background task activation.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct SledEvacuatorStatus {
+    /// Results for each evacuation that was in progress.
+    pub evacuations: Vec<SledEvacuationStatus>,
+    /// Error listing in-progress evacuations, if any.
+    pub error: Option<String>,
+}
+
+/// The progress made on one sled evacuation in an activation of the
+/// `sled_evacuator` background task.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct SledEvacuationStatus {
+    pub evacuation_id: Uuid,
+    pub sled_id: SledUuid,
+    /// Instances still running on the sled when the activation began.
+    pub instances_remaining: usize,
+    /// Instances that were already migrating when the activation began.
+    pub instances_migrating: usize,
+    /// Instances successfully migrated off the sled in this activation.
+    pub instances_migrated: Vec<Uuid>,
+    /// Whether the evacuation was marked completed in this activation.
+    pub completed: bool,
+    /// Errors migrating individual instances, or updating the evacuation.
+    pub errors: Vec<String>,
+}
+
+/// The status of a `load_balancer_manager` background task activation.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct LoadBalancerManagerStatus {
+    /// Results for each load balancer.
+    pub load_balancers: Vec<LoadBalancerStatus>,
+    /// Error listing load balancers, if any.
+    pub error: Option<String>,
+}
+
+/// The outcome of reconciling one load balancer in an activation of the
+/// `load_balancer_manager` background task.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct LoadBalancerStatus {
+    pub load_balancer_id: Uuid,
+    /// The frontend address of the load balancer.
+    pub ip: IpAddr,
+    /// Number of backends, running or not.
+    pub backends: usize,
+    /// Number of running, healthy backends receiving traffic.
+    pub serving: usize,
+    /// Number of NAT entries added or removed.
+    pub nat_changes: usize,
+    /// Errors checking backends or updating the data plane.
+    pub errors: Vec<String>,
+}
+
+/// The status of a `vpc_dns` background task activation.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct VpcDnsStatus {
+    /// Generation of the VPC DNS zones, if they were computed and stored.
+    pub generation: Option<Generation>,
+    /// Number of VPC DNS zones.
+    pub zones: usize,
+    /// Result of propagating the zones to each DNS server.
+    pub server_results: BTreeMap<String, Result<(), String>>,
+    /// Error computing or storing the zones, if any.
+    pub error: Option<String>,
+}
+
+/// The status of a `dnssec_keys` background task activation.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct DnssecKeysStatus {
+    /// Whether signing the external DNS zones is enabled.  If not, the task
+    /// deletes any keys left from when it was.
+    pub enabled: bool,
+    /// Generation of the keys, if they were read.
+    pub generation: Option<Generation>,
+    /// Number of keys created during this activation.
+    pub keys_created: usize,
+    /// Number of keys that started signing their zone.
+    pub keys_activated: usize,
+    /// Number of keys that stopped signing their zone.
+    pub keys_retired: usize,
+    /// Number of keys that stopped being published.
+    pub keys_deleted: usize,
+    /// The keys of each zone, after any changes.
+    pub keys: Vec<DnssecKeyStatus>,
+    /// Result of propagating the keys to each DNS server.
+    pub server_results: BTreeMap<String, Result<(), String>>,
+    /// Error rolling over or reading the keys, if any.
+    pub error: Option<String>,
+}
+
+/// A key reported by the `dnssec_keys` background task.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct DnssecKeyStatus {
+    pub zone_name: String,
+    /// "KSK" or "ZSK"
+    pub role: String,
+    /// "published", "active" or "retired"
+    pub state: String,
+    pub key_tag: u16,
+    /// For KSKs, the DS record to register with the parent zone.
+    pub ds: Option<String>,
+    pub time_created: DateTime<Utc>,
+}
+
+/// The status of an `acme_certificates` background task activation.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct AcmeCertificatesStatus {
+    /// Whether an ACME server is configured.  If not, the task does nothing.
+    pub enabled: bool,
+    /// Number of Silos whose certificates are not yet due for renewal.
+    pub silos_current: usize,
+    /// Certificates obtained during this activation.
+    pub issued: Vec<AcmeCertificateIssued>,
+    /// Number of older certificates deleted because they were superseded.
+    pub superseded_deleted: usize,
+    /// Errors obtaining certificates, by Silo name.
+    pub silo_errors: BTreeMap<String, String>,
+    /// Error that prevented checking any Silo, if any.
+    pub error: Option<String>,
+}
+
+/// A certificate obtained by the `acme_certificates` background task.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct AcmeCertificateIssued {
+    pub silo_name: String,
+    pub certificate_id: Uuid,
+    pub certificate_name: String,
+}
+
+/// The status of a `certificate_expiry` background task activation.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct CertificateExpiryStatus {
+    /// Every certificate that was inspected, soonest to expire first.
+    pub certificates: Vec<StoredCertificateExpiry>,
+    /// Certificates that could not be parsed.
+    pub parse_errors: Vec<StoredCertificateError>,
+    /// Number of `certificate.expiring` alerts published by this activation.
+    ///
+    /// Each alert is published once per certificate and threshold, so this
+    /// doesn't count certificates that were already alerted on.
+    pub alerts_published: usize,
+    /// Errors listing stored certificates.
+    pub errors: Vec<String>,
+}
+
+/// When a certificate stored by the control plane expires.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct StoredCertificateExpiry {
+    pub kind: CertificateKind,
+    /// ID of the object holding the certificate
+    pub id: Uuid,
+    /// name of the object holding the certificate
+    pub name: String,
+    pub silo_id: Option<Uuid>,
+    /// when the certificate (or the first in its chain or bundle) expires
+    pub time_expires: DateTime<Utc>,
+    pub days_remaining: i64,
+}
+
+/// A certificate stored by the control plane that could not be parsed.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct StoredCertificateError {
+    pub kind: CertificateKind,
+    pub id: Uuid,
+    pub name: String,
+    pub error: String,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
 #[serde(tag = "kind", rename_all = "snake_case")]
 pub enum SwitchPortPopulatorStatusKind {
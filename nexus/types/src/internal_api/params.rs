@@ -214,7 +214,16 @@ pub struct RackInitializationRequest {
 pub type DnsConfigParams = internal_dns_types::config::DnsConfigParams;
 pub type DnsConfigZone = internal_dns_types::config::DnsConfigZone;
 pub type DnsRecord = internal_dns_types::config::DnsRecord;
+pub type DnssecAlgorithm = internal_dns_types::config::DnssecAlgorithm;
+pub type DnssecConfigParams = internal_dns_types::config::DnssecConfigParams;
+pub type DnssecKey = internal_dns_types::config::DnssecKey;
+pub type DnssecKeyRole = internal_dns_types::config::DnssecKeyRole;
+pub type DnssecKeyState = internal_dns_types::config::DnssecKeyState;
+pub type DnssecZone = internal_dns_types::config::DnssecZone;
 pub type Srv = internal_dns_types::config::Srv;
+pub type VpcDnsClient = internal_dns_types::config::VpcDnsClient;
+pub type VpcDnsConfigParams = internal_dns_types::config::VpcDnsConfigParams;
+pub type VpcDnsZone = internal_dns_types::config::VpcDnsZone;
 
 /// Message used to notify Nexus that this oximeter instance is up and running.
 #[derive(Debug, Clone, Copy, JsonSchema, Serialize, Deserialize)]
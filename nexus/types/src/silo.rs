@@ -42,3 +42,11 @@ pub fn silo_dns_name(name: &omicron_common::api::external::Name) -> String {
     // resource into the DNS name rather than doing any kind of escaping.
     format!("{}.sys", name)
 }
+
+/// Label under which ACME DNS-01 challenges for a DNS name are published
+/// (e.g., `_acme-challenge.my-silo.sys`)
+///
+/// Nexus adds and removes these names in the external DNS zone while it
+/// obtains Silos' certificates.  They're not part of any blueprint, so
+/// blueprint execution must leave them alone.
+pub const ACME_CHALLENGE_LABEL: &str = "_acme-challenge";
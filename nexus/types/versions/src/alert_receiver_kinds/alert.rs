@@ -0,0 +1,526 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Alert types for version `ALERT_RECEIVER_KINDS`.
+
+use crate::v2025_11_20_00::alert::{
+    AlertDeliveryState, AlertDeliveryTrigger, AlertSubscription,
+    WebhookDeliveryAttempt, WebhookReceiver, WebhookReceiverConfig,
+};
+use api_identity::ObjectIdentity;
+use chrono::{DateTime, Utc};
+use omicron_common::api::external::{
+    IdentityMetadata, IdentityMetadataCreateParams,
+    IdentityMetadataUpdateParams, ObjectIdentity,
+};
+use omicron_uuid_kinds::{AlertReceiverUuid, AlertUuid};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Error returned when an alert receiver, or a delivery to one, cannot be
+/// represented in an older version of the API, because its receiver kind did
+/// not exist yet.
+#[derive(Debug)]
+pub struct ReceiverKindNotRepresentable;
+
+/// The configuration for an alert receiver.
+#[derive(
+    ObjectIdentity, Clone, Debug, Deserialize, Serialize, JsonSchema, PartialEq,
+)]
+pub struct AlertReceiver {
+    #[serde(flatten)]
+    pub identity: IdentityMetadata,
+
+    /// The list of alert classes to which this receiver is subscribed.
+    pub subscriptions: Vec<AlertSubscription>,
+
+    /// Configuration specific to the kind of alert receiver that this is.
+    pub kind: AlertReceiverKind,
+}
+
+impl TryFrom<AlertReceiver> for crate::v2025_11_20_00::alert::AlertReceiver {
+    type Error = ReceiverKindNotRepresentable;
+
+    fn try_from(new: AlertReceiver) -> Result<Self, Self::Error> {
+        let AlertReceiverKind::Webhook(config) = new.kind else {
+            return Err(ReceiverKindNotRepresentable);
+        };
+        Ok(Self {
+            identity: new.identity,
+            subscriptions: new.subscriptions,
+            kind: crate::v2025_11_20_00::alert::AlertReceiverKind::Webhook(
+                config,
+            ),
+        })
+    }
+}
+
+/// The possible alert delivery mechanisms for an alert receiver.
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum AlertReceiverKind {
+    Webhook(WebhookReceiverConfig),
+    Smtp(SmtpReceiverConfig),
+    Syslog(SyslogReceiverConfig),
+}
+
+impl From<WebhookReceiver> for AlertReceiver {
+    fn from(
+        WebhookReceiver { identity, subscriptions, config }: WebhookReceiver,
+    ) -> Self {
+        Self {
+            identity,
+            subscriptions,
+            kind: AlertReceiverKind::Webhook(config),
+        }
+    }
+}
+
+impl PartialEq<WebhookReceiver> for AlertReceiver {
+    fn eq(&self, other: &WebhookReceiver) -> bool {
+        let AlertReceiverKind::Webhook(ref config) = self.kind else {
+            return false;
+        };
+        self.identity == other.identity
+            && self.subscriptions == other.subscriptions
+            && config == &other.config
+    }
+}
+
+impl PartialEq<AlertReceiver> for WebhookReceiver {
+    fn eq(&self, other: &AlertReceiver) -> bool {
+        other == self
+    }
+}
+
+/// The configuration for an SMTP alert receiver.
+#[derive(
+    ObjectIdentity, Clone, Debug, Deserialize, Serialize, JsonSchema, PartialEq,
+)]
+pub struct SmtpReceiver {
+    #[serde(flatten)]
+    pub identity: IdentityMetadata,
+
+    /// The list of alert classes to which this receiver is subscribed.
+    pub subscriptions: Vec<AlertSubscription>,
+
+    #[serde(flatten)]
+    pub config: SmtpReceiverConfig,
+}
+
+impl From<SmtpReceiver> for AlertReceiver {
+    fn from(
+        SmtpReceiver { identity, subscriptions, config }: SmtpReceiver,
+    ) -> Self {
+        Self { identity, subscriptions, kind: AlertReceiverKind::Smtp(config) }
+    }
+}
+
+/// SMTP-specific alert receiver configuration.
+///
+/// Alerts are delivered to SMTP receivers as email messages whose body is the
+/// JSON alert payload.
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema, PartialEq)]
+pub struct SmtpReceiverConfig {
+    /// The hostname or IP address of the mail server that alert messages are
+    /// sent to.
+    pub host: String,
+    /// The TCP port on which the mail server accepts SMTP connections.
+    pub port: u16,
+    /// The address that alert messages are sent from.
+    pub from: String,
+    /// The addresses that alert messages are sent to.
+    pub to: Vec<String>,
+}
+
+/// The configuration for a syslog alert receiver.
+#[derive(
+    ObjectIdentity, Clone, Debug, Deserialize, Serialize, JsonSchema, PartialEq,
+)]
+pub struct SyslogReceiver {
+    #[serde(flatten)]
+    pub identity: IdentityMetadata,
+
+    /// The list of alert classes to which this receiver is subscribed.
+    pub subscriptions: Vec<AlertSubscription>,
+
+    #[serde(flatten)]
+    pub config: SyslogReceiverConfig,
+}
+
+impl From<SyslogReceiver> for AlertReceiver {
+    fn from(
+        SyslogReceiver { identity, subscriptions, config }: SyslogReceiver,
+    ) -> Self {
+        Self {
+            identity,
+            subscriptions,
+            kind: AlertReceiverKind::Syslog(config),
+        }
+    }
+}
+
+/// Syslog-specific alert receiver configuration.
+///
+/// Alerts are delivered to syslog receivers as RFC 5424 messages whose
+/// message body is the JSON alert payload.
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema, PartialEq)]
+pub struct SyslogReceiverConfig {
+    /// The hostname or IP address of the syslog collector that alert messages
+    /// are sent to.
+    pub host: String,
+    /// The port on which the syslog collector accepts messages.
+    pub port: u16,
+    /// The transport protocol used to send messages to the collector.
+    pub transport: SyslogTransport,
+    /// The syslog facility that alert messages are logged to.
+    pub facility: SyslogFacility,
+}
+
+/// The transport protocol used to send messages to a syslog collector.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    Deserialize,
+    Serialize,
+    JsonSchema,
+    strum::VariantArray,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum SyslogTransport {
+    /// Messages are sent as individual UDP datagrams (RFC 5426).
+    #[default]
+    Udp,
+    /// Messages are sent over a TCP connection, using octet-counting framing
+    /// (RFC 6587).
+    Tcp,
+}
+
+/// A syslog facility that alert messages may be logged to.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    Deserialize,
+    Serialize,
+    JsonSchema,
+    strum::VariantArray,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum SyslogFacility {
+    User,
+    Daemon,
+    Auth,
+    Syslog,
+    #[default]
+    Local0,
+    Local1,
+    Local2,
+    Local3,
+    Local4,
+    Local5,
+    Local6,
+    Local7,
+}
+
+/// A delivery of an alert.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub struct AlertDelivery {
+    /// The UUID of this delivery attempt.
+    pub id: Uuid,
+
+    /// The UUID of the alert receiver that this event was delivered to.
+    #[schemars(with = "Uuid")]
+    pub receiver_id: AlertReceiverUuid,
+
+    /// The event class.
+    pub alert_class: String,
+
+    /// The UUID of the event.
+    #[schemars(with = "Uuid")]
+    pub alert_id: AlertUuid,
+
+    /// The state of this delivery.
+    pub state: AlertDeliveryState,
+
+    /// Why this delivery was performed.
+    pub trigger: AlertDeliveryTrigger,
+
+    /// Individual attempts to deliver this alert, and their outcomes.
+    pub attempts: AlertDeliveryAttempts,
+
+    /// The time at which this delivery began (i.e. the event was dispatched to
+    /// the receiver).
+    pub time_started: DateTime<Utc>,
+}
+
+impl TryFrom<AlertDelivery> for crate::v2025_11_20_00::alert::AlertDelivery {
+    type Error = ReceiverKindNotRepresentable;
+
+    fn try_from(new: AlertDelivery) -> Result<Self, Self::Error> {
+        let AlertDeliveryAttempts::Webhook(attempts) = new.attempts else {
+            return Err(ReceiverKindNotRepresentable);
+        };
+        Ok(Self {
+            id: new.id,
+            receiver_id: new.receiver_id,
+            alert_class: new.alert_class,
+            alert_id: new.alert_id,
+            state: new.state,
+            trigger: new.trigger,
+            attempts:
+                crate::v2025_11_20_00::alert::AlertDeliveryAttempts::Webhook(
+                    attempts,
+                ),
+            time_started: new.time_started,
+        })
+    }
+}
+
+/// A list of attempts to deliver an alert to a receiver.
+///
+/// The type of the delivery attempt model depends on the receiver type, as it
+/// may contain information specific to that delivery mechanism. For example,
+/// webhook delivery attempts contain the HTTP status code of the webhook
+/// request, and SMTP delivery attempts contain the mail server's reply code.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertDeliveryAttempts {
+    /// A list of attempts to deliver an alert to a webhook receiver.
+    Webhook(Vec<WebhookDeliveryAttempt>),
+    /// A list of attempts to deliver an alert to an SMTP receiver.
+    Smtp(Vec<SmtpDeliveryAttempt>),
+    /// A list of attempts to deliver an alert to a syslog receiver.
+    Syslog(Vec<SyslogDeliveryAttempt>),
+}
+
+/// An individual delivery attempt for an alert sent to an SMTP receiver.
+///
+/// This represents a single SMTP transaction with the receiver's mail server,
+/// and its outcome.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub struct SmtpDeliveryAttempt {
+    /// The time at which the delivery was attempted.
+    pub time_sent: DateTime<Utc>,
+
+    /// The attempt number.
+    pub attempt: usize,
+
+    /// The outcome of this delivery attempt.
+    pub result: SmtpDeliveryAttemptResult,
+
+    /// The final reply code returned by the mail server, if one was received.
+    pub reply_code: Option<u16>,
+}
+
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Deserialize,
+    Serialize,
+    JsonSchema,
+    strum::VariantArray,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum SmtpDeliveryAttemptResult {
+    /// The mail server accepted the message.
+    Succeeded,
+    /// The mail server rejected the message, or a command in the SMTP
+    /// transaction that delivers it.
+    FailedRejected,
+    /// A connection to the mail server could not be established.
+    FailedUnreachable,
+    /// The mail server did not respond within the delivery timeout.
+    FailedTimeout,
+}
+
+/// An individual delivery attempt for an alert sent to a syslog receiver.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub struct SyslogDeliveryAttempt {
+    /// The time at which the delivery was attempted.
+    pub time_sent: DateTime<Utc>,
+
+    /// The attempt number.
+    pub attempt: usize,
+
+    /// The outcome of this delivery attempt.
+    ///
+    /// Note that syslog provides no acknowledgement of receipt, so a
+    /// successful attempt indicates only that the message was sent.
+    pub result: SyslogDeliveryAttemptResult,
+}
+
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Deserialize,
+    Serialize,
+    JsonSchema,
+    strum::VariantArray,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum SyslogDeliveryAttemptResult {
+    /// The message was sent to the syslog collector.
+    Succeeded,
+    /// The message could not be sent to the syslog collector.
+    FailedUnreachable,
+    /// Sending the message did not complete within the delivery timeout.
+    FailedTimeout,
+}
+
+/// Data describing the result of an alert receiver liveness probe attempt.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct AlertProbeResult {
+    /// The outcome of the probe delivery.
+    pub probe: AlertDelivery,
+    /// If the probe request succeeded, and resending failed deliveries on
+    /// success was requested, the number of new delivery attempts started.
+    /// Otherwise, if the probe did not succeed, or resending failed deliveries
+    /// was not requested, this is null.
+    ///
+    /// Note that this may be 0, if there were no events found which had not
+    /// been delivered successfully to this receiver.
+    pub resends_started: Option<usize>,
+}
+
+impl TryFrom<AlertProbeResult>
+    for crate::v2025_11_20_00::alert::AlertProbeResult
+{
+    type Error = ReceiverKindNotRepresentable;
+
+    fn try_from(new: AlertProbeResult) -> Result<Self, Self::Error> {
+        Ok(Self {
+            probe: new.probe.try_into()?,
+            resends_started: new.resends_started,
+        })
+    }
+}
+
+// ALERT PARAMS
+
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct SmtpCreate {
+    #[serde(flatten)]
+    pub identity: IdentityMetadataCreateParams,
+
+    /// The hostname or IP address of the mail server that alert messages
+    /// should be sent to.
+    pub host: String,
+
+    /// The TCP port on which the mail server accepts SMTP connections.
+    ///
+    /// Defaults to 25 if not provided.
+    #[serde(default = "default_smtp_port")]
+    pub port: u16,
+
+    /// The address that alert messages should be sent from.
+    pub from: String,
+
+    /// A non-empty list of addresses that alert messages should be sent to.
+    pub to: Vec<String>,
+
+    /// A list of alert class subscriptions.
+    ///
+    /// If this list is empty or is not included in the request body, the
+    /// receiver will not be subscribed to any alerts.
+    #[serde(default)]
+    pub subscriptions: Vec<AlertSubscription>,
+}
+
+fn default_smtp_port() -> u16 {
+    25
+}
+
+/// Parameters to update an SMTP receiver configuration.
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct SmtpReceiverUpdate {
+    #[serde(flatten)]
+    pub identity: IdentityMetadataUpdateParams,
+
+    /// The hostname or IP address of the mail server that alert messages
+    /// should be sent to.
+    pub host: Option<String>,
+
+    /// The TCP port on which the mail server accepts SMTP connections.
+    pub port: Option<u16>,
+
+    /// The address that alert messages should be sent from.
+    pub from: Option<String>,
+
+    /// A non-empty list of addresses that alert messages should be sent to.
+    pub to: Option<Vec<String>>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct SyslogCreate {
+    #[serde(flatten)]
+    pub identity: IdentityMetadataCreateParams,
+
+    /// The hostname or IP address of the syslog collector that alert messages
+    /// should be sent to.
+    pub host: String,
+
+    /// The port on which the syslog collector accepts messages.
+    ///
+    /// Defaults to 514 if not provided.
+    #[serde(default = "default_syslog_port")]
+    pub port: u16,
+
+    /// The transport protocol used to send messages to the collector.
+    ///
+    /// Defaults to UDP if not provided.
+    #[serde(default)]
+    pub transport: SyslogTransport,
+
+    /// The syslog facility that alert messages are logged to.
+    ///
+    /// Defaults to `local0` if not provided.
+    #[serde(default)]
+    pub facility: SyslogFacility,
+
+    /// A list of alert class subscriptions.
+    ///
+    /// If this list is empty or is not included in the request body, the
+    /// receiver will not be subscribed to any alerts.
+    #[serde(default)]
+    pub subscriptions: Vec<AlertSubscription>,
+}
+
+fn default_syslog_port() -> u16 {
+    514
+}
+
+/// Parameters to update a syslog receiver configuration.
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct SyslogReceiverUpdate {
+    #[serde(flatten)]
+    pub identity: IdentityMetadataUpdateParams,
+
+    /// The hostname or IP address of the syslog collector that alert messages
+    /// should be sent to.
+    pub host: Option<String>,
+
+    /// The port on which the syslog collector accepts messages.
+    pub port: Option<u16>,
+
+    /// The transport protocol used to send messages to the collector.
+    pub transport: Option<SyslogTransport>,
+
+    /// The syslog facility that alert messages are logged to.
+    pub facility: Option<SyslogFacility>,
+}
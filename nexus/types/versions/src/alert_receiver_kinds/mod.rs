@@ -0,0 +1,10 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Version `ALERT_RECEIVER_KINDS` of the Nexus external API.
+//!
+//! Adds SMTP and syslog alert receivers alongside webhooks, along with
+//! receiver-kind-specific delivery attempt records.
+
+pub mod alert;
@@ -0,0 +1,118 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Audit log types for version AUDIT_LOG_RESOURCE.
+
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use uuid::Uuid;
+
+use crate::v2025_11_20_00::audit::AuditLogEntryResult;
+use crate::v2026_01_15_00::audit::AuthMethod;
+use crate::v2026_10_19_00::audit::AuditLogEntryActor;
+
+/// Audit log has its own pagination scheme because it paginates by timestamp.
+#[derive(Deserialize, JsonSchema, Serialize, PartialEq, Debug, Clone)]
+pub struct AuditLogParams {
+    /// Required, inclusive
+    pub start_time: DateTime<Utc>,
+    /// Exclusive
+    pub end_time: Option<DateTime<Utc>>,
+    /// Only list entries for operations on the resource with this ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_id: Option<Uuid>,
+}
+
+impl From<crate::v2025_11_20_00::audit::AuditLogParams> for AuditLogParams {
+    fn from(old: crate::v2025_11_20_00::audit::AuditLogParams) -> Self {
+        Self {
+            start_time: old.start_time,
+            end_time: old.end_time,
+            resource_id: None,
+        }
+    }
+}
+
+/// Audit log entry
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct AuditLogEntry {
+    /// Unique identifier for the audit log entry
+    pub id: Uuid,
+
+    /// When the request was received
+    pub time_started: DateTime<Utc>,
+
+    /// Request ID for tracing requests through the system
+    pub request_id: String,
+    /// URI of the request, truncated to 512 characters. Will only include
+    /// host and scheme for HTTP/2 requests. For HTTP/1.1, the URI will
+    /// consist of only the path and query.
+    pub request_uri: String,
+    /// API endpoint ID, e.g., `project_create`
+    pub operation_id: String,
+    /// IP address that made the request
+    pub source_ip: IpAddr,
+    /// User agent string from the request, truncated to 256 characters.
+    pub user_agent: Option<String>,
+
+    pub actor: AuditLogEntryActor,
+
+    /// How the user authenticated the request (access token, session, or SCIM
+    /// token). Null for unauthenticated requests like login attempts.
+    pub auth_method: Option<AuthMethod>,
+
+    /// ID of the credential used for authentication. Null for unauthenticated
+    /// requests. The value of `auth_method` indicates what kind of credential
+    /// it is (access token, session, or SCIM token).
+    pub credential_id: Option<Uuid>,
+
+    /// Type of the resource the operation acted on, e.g., `project`. Null if
+    /// the operation does not act on a single resource, or if it failed
+    /// before the resource could be identified.
+    pub resource_type: Option<String>,
+    /// ID of the resource the operation acted on. Null whenever
+    /// `resource_type` is null.
+    pub resource_id: Option<Uuid>,
+    /// Body of the request, with sensitive values like passwords and secrets
+    /// replaced by the string `"<redacted>"`. Null if the request had no body,
+    /// if the body was too large to record, or if the operation's body is
+    /// never recorded because it consists mostly of sensitive values.
+    pub request_body: Option<serde_json::Value>,
+
+    /// Time operation completed
+    pub time_completed: DateTime<Utc>,
+
+    /// Result of the operation
+    pub result: AuditLogEntryResult,
+}
+
+impl From<AuditLogEntry> for crate::v2026_10_19_00::audit::AuditLogEntry {
+    fn from(new: AuditLogEntry) -> Self {
+        Self {
+            id: new.id,
+            time_started: new.time_started,
+            request_id: new.request_id,
+            request_uri: new.request_uri,
+            operation_id: new.operation_id,
+            source_ip: new.source_ip,
+            user_agent: new.user_agent,
+            actor: new.actor,
+            auth_method: new.auth_method,
+            credential_id: new.credential_id,
+            time_completed: new.time_completed,
+            result: new.result,
+        }
+    }
+}
+
+/// The body of a request delivering audit log entries to an HTTP sink
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct AuditLogSinkBatch {
+    /// ID of the sink the entries are delivered to
+    pub sink_id: Uuid,
+    /// Entries, in the order returned by `audit_log_list`
+    pub entries: Vec<AuditLogEntry>,
+}
@@ -0,0 +1,10 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Version `AUDIT_LOG_RESOURCE` of the Nexus external API.
+//!
+//! Adds the affected resource and a redacted copy of the request body to audit
+//! log entries, and allows filtering the audit log by resource.
+
+pub mod audit;
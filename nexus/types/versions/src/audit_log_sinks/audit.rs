@@ -0,0 +1,105 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Audit log sink types for version AUDIT_LOG_SINKS.
+
+use api_identity::ObjectIdentity;
+use chrono::{DateTime, Utc};
+use omicron_common::api::external::{
+    IdentityMetadata, IdentityMetadataCreateParams, NameOrId, ObjectIdentity,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use url::Url;
+use uuid::Uuid;
+
+/// The protocol used to deliver entries to an audit log sink
+#[derive(
+    Clone, Copy, Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditLogSinkKind {
+    /// Entries are sent to a syslog collector as RFC 5424 messages, framed
+    /// with octet counting (RFC 6587) over TCP or TLS.
+    Syslog,
+    /// Entries are POSTed in batches to an HTTP endpoint as JSON, signed
+    /// with a shared secret.
+    Http,
+}
+
+/// View of an audit log sink
+///
+/// Nexus delivers each completed audit log entry to every sink, in the order
+/// that `audit_log_list` returns them. Delivery is at-least-once: after a
+/// failure, entries may be delivered again.
+#[derive(ObjectIdentity, Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct AuditLogSink {
+    #[serde(flatten)]
+    pub identity: IdentityMetadata,
+    pub kind: AuditLogSinkKind,
+    /// For syslog sinks, the collector's address. For HTTP sinks, the URL
+    /// that entries are POSTed to.
+    pub endpoint: String,
+    /// Whether syslog messages are sent over TLS. Always false for HTTP
+    /// sinks, which use TLS if the endpoint URL does.
+    pub tls: bool,
+    /// Completion time of the last entry delivered to the sink. Entries
+    /// completed after this time have not yet been delivered.
+    pub cursor: DateTime<Utc>,
+    /// Time of the last successful delivery, if any
+    pub time_last_delivered: Option<DateTime<Utc>>,
+    /// Number of delivery attempts that have failed since the last success
+    pub consecutive_failures: u32,
+    /// Error from the most recent failed delivery attempt, if it has not
+    /// since succeeded
+    pub last_error: Option<String>,
+}
+
+/// Delivery configuration for an audit log sink
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AuditLogSinkConfig {
+    Syslog {
+        /// The collector's address, as "host:port"
+        address: String,
+        /// PEM-encoded CA certificates used to verify the collector. If
+        /// provided, messages are sent over TLS; otherwise, plain TCP is
+        /// used.
+        #[serde(default)]
+        tls_root_cert: Option<String>,
+    },
+    Http {
+        /// The URL that batches of entries should be POSTed to
+        endpoint: Url,
+        /// A secret key used to sign requests, as for webhook receivers
+        secret: String,
+    },
+}
+
+/// Create-time parameters for an `AuditLogSink`
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct AuditLogSinkCreate {
+    #[serde(flatten)]
+    pub identity: IdentityMetadataCreateParams,
+    pub config: AuditLogSinkConfig,
+    /// Deliver entries completed at or after this time. Defaults to the time
+    /// the sink is created, so that no existing entries are delivered.
+    #[serde(default)]
+    pub start_time: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct AuditLogSinkPath {
+    /// Name or ID of the audit log sink
+    pub sink: NameOrId,
+}
+
+/// The body of a request delivering audit log entries to an HTTP sink
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct AuditLogSinkBatch {
+    /// ID of the sink the entries are delivered to
+    pub sink_id: Uuid,
+    /// Entries, in the order returned by `audit_log_list`
+    pub entries: Vec<crate::v2026_10_19_00::audit::AuditLogEntry>,
+}
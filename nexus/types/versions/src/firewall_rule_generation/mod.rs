@@ -0,0 +1,10 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Version `FIREWALL_RULE_GENERATION` of the Nexus external API.
+//!
+//! Exposes the generation of a VPC's firewall rule set and adds endpoints to
+//! create, replace, and delete individual rules against that generation.
+
+pub mod vpc;
@@ -0,0 +1,50 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! VPC types for version FIREWALL_RULE_GENERATION.
+
+use omicron_common::api::external::Generation;
+use omicron_common::api::external::Name;
+use omicron_common::api::external::NameOrId;
+use omicron_common::api::external::VpcFirewallRuleUpdate;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Path parameters for operations on a single VPC firewall rule
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct VpcFirewallRulePath {
+    /// Name of the firewall rule
+    pub rule: Name,
+}
+
+/// Selector for deleting a single VPC firewall rule
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct VpcFirewallRuleDeleteSelector {
+    /// Name or ID of the project, only required if `vpc` is provided as a `Name`
+    pub project: Option<NameOrId>,
+    /// Name or ID of the VPC
+    pub vpc: NameOrId,
+    /// Generation of the VPC's rule set that this deletion was made against
+    pub generation: Generation,
+}
+
+/// Create-time parameters for a single VPC firewall rule
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct VpcFirewallRuleCreate {
+    /// Generation of the VPC's rule set that this rule was written against.
+    /// The request fails if the rule set has changed since.
+    pub generation: Generation,
+    /// The rule to add. Its name must not already be in use in this VPC.
+    pub rule: VpcFirewallRuleUpdate,
+}
+
+/// Replacement for a single VPC firewall rule
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct VpcFirewallRuleReplace {
+    /// Generation of the VPC's rule set that this rule was written against.
+    /// The request fails if the rule set has changed since.
+    pub generation: Generation,
+    /// The new contents of the rule. A different name renames the rule.
+    pub rule: VpcFirewallRuleUpdate,
+}
@@ -0,0 +1,27 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Instance types for version FIREWALL_TAGS.
+
+use omicron_common::api::external::Name;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The network tags carried by an instance
+///
+/// VPC firewall rules can target, or filter traffic by, the instances
+/// carrying a tag.
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema, PartialEq)]
+pub struct InstanceNetworkTags {
+    /// The instance's tags, in lexicographic order
+    pub tags: Vec<Name>,
+}
+
+/// Parameters for replacing the network tags carried by an instance
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct InstanceNetworkTagsUpdate {
+    /// The new set of tags. Duplicate tags are ignored.
+    #[schemars(length(max = 32))]
+    pub tags: Vec<Name>,
+}
@@ -0,0 +1,10 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Version `FIREWALL_TAGS` of the Nexus external API.
+//!
+//! Adds network tags on instances, which VPC firewall rules may use as
+//! targets and host filters.
+
+pub mod instance;
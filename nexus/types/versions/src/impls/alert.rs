@@ -6,12 +6,25 @@
 
 use crate::latest::alert::{
     AlertDeliveryState, AlertDeliveryStateFilter, AlertDeliveryTrigger,
-    AlertSubscription, WebhookDeliveryAttemptResult,
+    AlertSubscription, ReceiverKindNotRepresentable, SmtpDeliveryAttemptResult,
+    SyslogDeliveryAttemptResult, SyslogFacility, SyslogTransport,
+    WebhookDeliveryAttemptResult,
 };
 use omicron_common::api::external::Error;
 use std::fmt;
 use std::sync::LazyLock;
 
+impl From<ReceiverKindNotRepresentable> for dropshot::HttpError {
+    fn from(_: ReceiverKindNotRepresentable) -> Self {
+        dropshot::HttpError::for_bad_request(
+            None,
+            "this alert receiver's kind is not supported by the requested \
+             API version"
+                .to_string(),
+        )
+    }
+}
+
 impl AlertSubscription {
     pub(crate) fn is_valid(s: &str) -> Result<(), anyhow::Error> {
         static REGEX: std::sync::LazyLock<regex::Regex> =
@@ -160,6 +173,102 @@ impl fmt::Display for WebhookDeliveryAttemptResult {
     }
 }
 
+impl SmtpDeliveryAttemptResult {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Succeeded => "succeeded",
+            Self::FailedRejected => "failed_rejected",
+            Self::FailedUnreachable => "failed_unreachable",
+            Self::FailedTimeout => "failed_timeout",
+        }
+    }
+
+    /// Returns `true` if this `SmtpDeliveryAttemptResult` represents a failure
+    pub fn is_failed(&self) -> bool {
+        *self != Self::Succeeded
+    }
+}
+
+impl fmt::Display for SmtpDeliveryAttemptResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl SyslogDeliveryAttemptResult {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Succeeded => "succeeded",
+            Self::FailedUnreachable => "failed_unreachable",
+            Self::FailedTimeout => "failed_timeout",
+        }
+    }
+
+    /// Returns `true` if this `SyslogDeliveryAttemptResult` represents a
+    /// failure
+    pub fn is_failed(&self) -> bool {
+        *self != Self::Succeeded
+    }
+}
+
+impl fmt::Display for SyslogDeliveryAttemptResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl SyslogTransport {
+    /// The URL scheme used to represent a syslog collector endpoint using
+    /// this transport.
+    pub fn scheme(&self) -> &'static str {
+        match self {
+            Self::Udp => "udp",
+            Self::Tcp => "tcp",
+        }
+    }
+
+    pub fn from_scheme(scheme: &str) -> Option<Self> {
+        <Self as strum::VariantArray>::VARIANTS
+            .iter()
+            .find(|t| t.scheme() == scheme)
+            .copied()
+    }
+}
+
+impl fmt::Display for SyslogTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.scheme())
+    }
+}
+
+impl SyslogFacility {
+    /// Returns the numeric facility code for this facility, as defined in
+    /// [RFC 5424 § 6.2.1](https://www.rfc-editor.org/rfc/rfc5424#section-6.2.1).
+    pub fn code(&self) -> u8 {
+        match self {
+            Self::User => 1,
+            Self::Daemon => 3,
+            Self::Auth => 4,
+            Self::Syslog => 5,
+            Self::Local0 => 16,
+            Self::Local1 => 17,
+            Self::Local2 => 18,
+            Self::Local3 => 19,
+            Self::Local4 => 20,
+            Self::Local5 => 21,
+            Self::Local6 => 22,
+            Self::Local7 => 23,
+        }
+    }
+
+    pub fn from_code(code: u8) -> Option<Self> {
+        <Self as strum::VariantArray>::VARIANTS
+            .iter()
+            .find(|f| f.code() == code)
+            .copied()
+    }
+}
+
 impl Default for AlertDeliveryStateFilter {
     fn default() -> Self {
         Self::ALL
@@ -214,6 +323,17 @@ fn expected_one_of<T: strum::VariantArray + fmt::Display>() -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_syslog_facility_codes_round_trip() {
+        for facility in <SyslogFacility as strum::VariantArray>::VARIANTS {
+            assert_eq!(
+                SyslogFacility::from_code(facility.code()),
+                Some(*facility)
+            );
+        }
+        assert_eq!(SyslogFacility::from_code(0), None);
+    }
+
     #[test]
     fn test_webhook_subscription_validation() {
         let successes = [
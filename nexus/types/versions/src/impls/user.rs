@@ -28,3 +28,15 @@ impl AsRef<omicron_passwords::Password> for Password {
         &self.0
     }
 }
+
+/// Passwords never serialize their contents, only a placeholder. This lets
+/// request bodies containing them be recorded (e.g., in the audit log) without
+/// any way to leak the password itself.
+impl serde::Serialize for Password {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str("********")
+    }
+}
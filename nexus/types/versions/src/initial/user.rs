@@ -85,7 +85,7 @@ pub struct OptionalGroupSelector {
 }
 
 /// Create-time parameters for a `User`
-#[derive(Clone, Deserialize, JsonSchema)]
+#[derive(Clone, Deserialize, Serialize, JsonSchema)]
 pub struct UserCreate {
     /// Username used to log in
     pub external_id: UserId,
@@ -133,7 +133,7 @@ impl schemars::JsonSchema for Password {
 }
 
 /// Parameters for setting a user's password
-#[derive(Clone, Deserialize, JsonSchema)]
+#[derive(Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[serde(tag = "mode", content = "value")]
 pub enum UserPassword {
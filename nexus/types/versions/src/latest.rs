@@ -25,15 +25,10 @@ pub mod alert {
     pub use crate::v2025_11_20_00::alert::AlertClass;
     pub use crate::v2025_11_20_00::alert::AlertClassFilter;
     pub use crate::v2025_11_20_00::alert::AlertClassPage;
-    pub use crate::v2025_11_20_00::alert::AlertDelivery;
-    pub use crate::v2025_11_20_00::alert::AlertDeliveryAttempts;
     pub use crate::v2025_11_20_00::alert::AlertDeliveryId;
     pub use crate::v2025_11_20_00::alert::AlertDeliveryState;
     pub use crate::v2025_11_20_00::alert::AlertDeliveryStateFilter;
     pub use crate::v2025_11_20_00::alert::AlertDeliveryTrigger;
-    pub use crate::v2025_11_20_00::alert::AlertProbeResult;
-    pub use crate::v2025_11_20_00::alert::AlertReceiver;
-    pub use crate::v2025_11_20_00::alert::AlertReceiverKind;
     pub use crate::v2025_11_20_00::alert::AlertReceiverProbe;
     pub use crate::v2025_11_20_00::alert::AlertReceiverSelector;
     pub use crate::v2025_11_20_00::alert::AlertSelector;
@@ -52,16 +47,45 @@ pub mod alert {
     pub use crate::v2025_11_20_00::alert::WebhookSecretCreate;
     pub use crate::v2025_11_20_00::alert::WebhookSecretSelector;
     pub use crate::v2025_11_20_00::alert::WebhookSecrets;
+
+    pub use crate::v2026_10_19_04::alert::AlertDelivery;
+    pub use crate::v2026_10_19_04::alert::AlertDeliveryAttempts;
+    pub use crate::v2026_10_19_04::alert::AlertProbeResult;
+    pub use crate::v2026_10_19_04::alert::AlertReceiver;
+    pub use crate::v2026_10_19_04::alert::AlertReceiverKind;
+    pub use crate::v2026_10_19_04::alert::ReceiverKindNotRepresentable;
+    pub use crate::v2026_10_19_04::alert::SmtpCreate;
+    pub use crate::v2026_10_19_04::alert::SmtpDeliveryAttempt;
+    pub use crate::v2026_10_19_04::alert::SmtpDeliveryAttemptResult;
+    pub use crate::v2026_10_19_04::alert::SmtpReceiver;
+    pub use crate::v2026_10_19_04::alert::SmtpReceiverConfig;
+    pub use crate::v2026_10_19_04::alert::SmtpReceiverUpdate;
+    pub use crate::v2026_10_19_04::alert::SyslogCreate;
+    pub use crate::v2026_10_19_04::alert::SyslogDeliveryAttempt;
+    pub use crate::v2026_10_19_04::alert::SyslogDeliveryAttemptResult;
+    pub use crate::v2026_10_19_04::alert::SyslogFacility;
+    pub use crate::v2026_10_19_04::alert::SyslogReceiver;
+    pub use crate::v2026_10_19_04::alert::SyslogReceiverConfig;
+    pub use crate::v2026_10_19_04::alert::SyslogReceiverUpdate;
+    pub use crate::v2026_10_19_04::alert::SyslogTransport;
 }
 
 pub mod audit {
-    pub use crate::v2025_11_20_00::audit::AuditLogEntryActor;
     pub use crate::v2025_11_20_00::audit::AuditLogEntryResult;
-    pub use crate::v2025_11_20_00::audit::AuditLogParams;
 
     pub use crate::v2026_01_15_00::audit::AuthMethod;
 
-    pub use crate::v2026_01_15_01::audit::AuditLogEntry;
+    pub use crate::v2026_10_19_00::audit::AuditLogEntryActor;
+
+    pub use crate::v2026_10_19_02::audit::AuditLogSink;
+    pub use crate::v2026_10_19_02::audit::AuditLogSinkConfig;
+    pub use crate::v2026_10_19_02::audit::AuditLogSinkCreate;
+    pub use crate::v2026_10_19_02::audit::AuditLogSinkKind;
+    pub use crate::v2026_10_19_02::audit::AuditLogSinkPath;
+
+    pub use crate::v2026_10_19_03::audit::AuditLogEntry;
+    pub use crate::v2026_10_19_03::audit::AuditLogParams;
+    pub use crate::v2026_10_19_03::audit::AuditLogSinkBatch;
 }
 
 pub mod bfd {
@@ -183,6 +207,11 @@ pub mod instance {
     pub use crate::v2026_06_08_00::instance::InstanceCpuPlatform;
     pub use crate::v2026_06_08_00::instance::InstanceCreate;
     pub use crate::v2026_06_08_00::instance::InstanceUpdate;
+
+    pub use crate::v2026_10_19_05::instance::InstanceMigrate;
+
+    pub use crate::v2026_10_19_06::instance::InstanceNetworkTags;
+    pub use crate::v2026_10_19_06::instance::InstanceNetworkTagsUpdate;
 }
 
 pub mod internet_gateway {
@@ -217,6 +246,19 @@ pub mod ip_pool {
     pub use crate::v2026_01_05_00::ip_pool::PoolSelector;
 }
 
+pub mod load_balancer {
+    pub use crate::v2026_10_19_08::load_balancer::LoadBalancerBackendCreate;
+    pub use crate::v2026_10_19_08::load_balancer::LoadBalancerBackendHealth;
+    pub use crate::v2026_10_19_08::load_balancer::LoadBalancerBackendPath;
+    pub use crate::v2026_10_19_08::load_balancer::LoadBalancerCreate;
+    pub use crate::v2026_10_19_08::load_balancer::LoadBalancerHealthCheck;
+    pub use crate::v2026_10_19_08::load_balancer::LoadBalancerPath;
+    pub use crate::v2026_10_19_08::load_balancer::LoadBalancerSelector;
+
+    pub use crate::v2026_10_19_10::load_balancer::LoadBalancer;
+    pub use crate::v2026_10_19_10::load_balancer::LoadBalancerBackend;
+}
+
 pub mod metrics {
     pub use crate::v2025_11_20_00::metrics::ResourceMetrics;
     pub use crate::v2025_11_20_00::metrics::SystemMetricName;
@@ -359,6 +401,18 @@ pub mod scim {
     pub use crate::v2025_11_20_00::scim::ScimV2UserPathParam;
 }
 
+pub mod service_account {
+    pub use crate::v2026_10_19_00::service_account::ServiceAccount;
+    pub use crate::v2026_10_19_00::service_account::ServiceAccountCreate;
+    pub use crate::v2026_10_19_00::service_account::ServiceAccountPath;
+    pub use crate::v2026_10_19_00::service_account::ServiceAccountPolicy;
+    pub use crate::v2026_10_19_00::service_account::ServiceAccountRoleAssignment;
+    pub use crate::v2026_10_19_00::service_account::ServiceAccountToken;
+    pub use crate::v2026_10_19_00::service_account::ServiceAccountTokenCreate;
+    pub use crate::v2026_10_19_00::service_account::ServiceAccountTokenPath;
+    pub use crate::v2026_10_19_00::service_account::ServiceAccountTokenValue;
+}
+
 pub mod silo {
     pub use crate::v2025_11_20_00::silo::AuthenticationMode;
     pub use crate::v2025_11_20_00::silo::OptionalSiloSelector;
@@ -375,6 +429,8 @@ pub mod silo {
     pub use crate::v2025_11_20_00::silo::UserProvisionType;
     pub use crate::v2025_11_20_00::silo::Utilization;
     pub use crate::v2025_11_20_00::silo::VirtualResourceCounts;
+    pub use crate::v2026_10_19_01::silo::SiloRateLimit;
+    pub use crate::v2026_10_19_01::silo::SiloRateLimitUpdate;
 }
 
 pub mod snapshot {
@@ -447,6 +503,15 @@ pub mod vpc {
     pub use crate::v2025_11_20_00::vpc::VpcSubnetCreate;
     pub use crate::v2025_11_20_00::vpc::VpcSubnetUpdate;
     pub use crate::v2025_11_20_00::vpc::VpcUpdate;
+
+    pub use crate::v2026_10_19_07::vpc::VpcFirewallRuleCreate;
+    pub use crate::v2026_10_19_07::vpc::VpcFirewallRuleDeleteSelector;
+    pub use crate::v2026_10_19_07::vpc::VpcFirewallRulePath;
+    pub use crate::v2026_10_19_07::vpc::VpcFirewallRuleReplace;
+
+    pub use crate::v2026_10_19_09::vpc::VpcDnsRecord;
+    pub use crate::v2026_10_19_09::vpc::VpcDnsRecords;
+    pub use crate::v2026_10_19_09::vpc::VpcDnsRecordsUpdate;
 }
 
 pub mod asset {
@@ -532,6 +597,9 @@ pub mod sled {
     pub use crate::v2025_11_20_00::sled::SledSelector;
     pub use crate::v2025_11_20_00::sled::SledState;
     pub use crate::v2025_11_20_00::sled::SwitchSelector;
+
+    pub use crate::v2026_10_19_05::sled::SledEvacuation;
+    pub use crate::v2026_10_19_05::sled::SledEvacuationState;
 }
 
 pub mod ssh_key {
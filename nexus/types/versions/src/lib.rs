@@ -93,3 +93,25 @@ pub mod v2026_06_04_00;
 pub mod v2026_06_05_00;
 #[path = "instance_cpu_type_turin_v2/mod.rs"]
 pub mod v2026_06_08_00;
+#[path = "service_accounts/mod.rs"]
+pub mod v2026_10_19_00;
+#[path = "silo_rate_limit/mod.rs"]
+pub mod v2026_10_19_01;
+#[path = "audit_log_sinks/mod.rs"]
+pub mod v2026_10_19_02;
+#[path = "audit_log_resource/mod.rs"]
+pub mod v2026_10_19_03;
+#[path = "alert_receiver_kinds/mod.rs"]
+pub mod v2026_10_19_04;
+#[path = "sled_evacuation/mod.rs"]
+pub mod v2026_10_19_05;
+#[path = "firewall_tags/mod.rs"]
+pub mod v2026_10_19_06;
+#[path = "firewall_rule_generation/mod.rs"]
+pub mod v2026_10_19_07;
+#[path = "load_balancers/mod.rs"]
+pub mod v2026_10_19_08;
+#[path = "vpc_dns/mod.rs"]
+pub mod v2026_10_19_09;
+#[path = "load_balancer_port_assignments/mod.rs"]
+pub mod v2026_10_19_10;
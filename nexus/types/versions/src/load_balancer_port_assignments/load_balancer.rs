@@ -0,0 +1,102 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Load balancer types for version LOAD_BALANCER_PORT_ASSIGNMENTS.
+
+use api_identity::ObjectIdentity;
+use chrono::{DateTime, Utc};
+use omicron_common::api::external::{IdentityMetadata, Name, ObjectIdentity};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use uuid::Uuid;
+
+use crate::v2026_10_19_08;
+use crate::v2026_10_19_08::load_balancer::{
+    LoadBalancerBackendHealth, LoadBalancerHealthCheck,
+};
+
+/// A per-port load balancer
+///
+/// A load balancer holds an external IP address, and forwards traffic sent
+/// to that address on each of its ports to one of its healthy backend
+/// instances, on the same port.
+///
+/// Load is balanced per port, not per flow: each port is served by a single
+/// backend at a time, and all traffic to a port, from every client, goes to
+/// that backend. The ports are divided evenly among the healthy backends, so
+/// every healthy backend serves as long as there are at least as many ports
+/// as backends. Backends beyond the number of ports act as standbys. When a
+/// backend stops serving, its ports move to the others.
+#[derive(
+    ObjectIdentity, Debug, PartialEq, Clone, Deserialize, Serialize, JsonSchema,
+)]
+pub struct LoadBalancer {
+    #[serde(flatten)]
+    pub identity: IdentityMetadata,
+    /// The external IP address on which the load balancer accepts traffic.
+    pub ip: IpAddr,
+    /// The ID of the IP pool `ip` was allocated from.
+    pub ip_pool_id: Uuid,
+    /// The project this resource exists within.
+    pub project_id: Uuid,
+    /// The ports on which the load balancer accepts traffic.
+    pub ports: Vec<u16>,
+    /// If set, instances in the project carrying this network tag are
+    /// backends of the load balancer, in addition to those added explicitly.
+    pub backend_tag: Option<Name>,
+    /// The health check applied to backends, if any.
+    pub health_check: Option<LoadBalancerHealthCheck>,
+}
+
+impl From<LoadBalancer> for v2026_10_19_08::load_balancer::LoadBalancer {
+    fn from(value: LoadBalancer) -> Self {
+        Self {
+            identity: value.identity,
+            ip: value.ip,
+            ip_pool_id: value.ip_pool_id,
+            project_id: value.project_id,
+            ports: value.ports,
+            backend_tag: value.backend_tag,
+            health_check: value.health_check,
+        }
+    }
+}
+
+/// An instance receiving traffic from a load balancer
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct LoadBalancerBackend {
+    /// The load balancer this backend belongs to
+    pub load_balancer_id: Uuid,
+    /// The backend instance
+    pub instance_id: Uuid,
+    /// True if the backend was added explicitly, false if it was selected by
+    /// the load balancer's backend tag
+    pub explicit: bool,
+    pub health: LoadBalancerBackendHealth,
+    /// True if the backend is currently receiving traffic
+    pub serving: bool,
+    /// The load balancer ports whose traffic currently goes to this backend
+    ///
+    /// A healthy backend with no ports is a standby: it takes over ports
+    /// from backends that stop serving.
+    pub ports: Vec<u16>,
+    /// When the backend was added
+    pub time_created: DateTime<Utc>,
+}
+
+impl From<LoadBalancerBackend>
+    for v2026_10_19_08::load_balancer::LoadBalancerBackend
+{
+    fn from(value: LoadBalancerBackend) -> Self {
+        Self {
+            load_balancer_id: value.load_balancer_id,
+            instance_id: value.instance_id,
+            explicit: value.explicit,
+            health: value.health,
+            serving: value.serving,
+            time_created: value.time_created,
+        }
+    }
+}
@@ -0,0 +1,10 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Version `LOAD_BALANCER_PORT_ASSIGNMENTS` of the Nexus external API.
+//!
+//! Reports which of a load balancer's ports each backend serves, and
+//! documents that each port is served by a single backend at a time.
+
+pub mod load_balancer;
@@ -0,0 +1,160 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Load balancer types for version LOAD_BALANCERS.
+
+use api_identity::ObjectIdentity;
+use chrono::{DateTime, Utc};
+use omicron_common::api::external::{
+    IdentityMetadata, IdentityMetadataCreateParams, Name, NameOrId,
+    ObjectIdentity,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use uuid::Uuid;
+
+use crate::v2026_01_22_00::floating_ip::AddressAllocator;
+
+/// A per-port load balancer
+///
+/// A load balancer holds an external IP address, and forwards traffic sent
+/// to that address on each of its ports to one of its healthy backend
+/// instances, on the same port.
+#[derive(
+    ObjectIdentity, Debug, PartialEq, Clone, Deserialize, Serialize, JsonSchema,
+)]
+pub struct LoadBalancer {
+    #[serde(flatten)]
+    pub identity: IdentityMetadata,
+    /// The external IP address on which the load balancer accepts traffic.
+    pub ip: IpAddr,
+    /// The ID of the IP pool `ip` was allocated from.
+    pub ip_pool_id: Uuid,
+    /// The project this resource exists within.
+    pub project_id: Uuid,
+    /// The ports on which the load balancer accepts traffic.
+    pub ports: Vec<u16>,
+    /// If set, instances in the project carrying this network tag are
+    /// backends of the load balancer, in addition to those added explicitly.
+    pub backend_tag: Option<Name>,
+    /// The health check applied to backends, if any.
+    pub health_check: Option<LoadBalancerHealthCheck>,
+}
+
+/// A TCP health check applied to the backends of a load balancer
+///
+/// A backend is healthy once `healthy_threshold` consecutive connection
+/// attempts to `port` on its primary private IP address succeed, and
+/// unhealthy once `unhealthy_threshold` consecutive attempts fail. Without a
+/// health check, every running backend is considered healthy.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize, JsonSchema,
+)]
+pub struct LoadBalancerHealthCheck {
+    /// The TCP port to connect to.
+    pub port: u16,
+    /// Consecutive successful checks before a backend is healthy.
+    #[serde(default = "default_healthy_threshold")]
+    pub healthy_threshold: u8,
+    /// Consecutive failed checks before a backend is unhealthy.
+    #[serde(default = "default_unhealthy_threshold")]
+    pub unhealthy_threshold: u8,
+}
+
+fn default_healthy_threshold() -> u8 {
+    2
+}
+
+fn default_unhealthy_threshold() -> u8 {
+    3
+}
+
+/// Create-time parameters for a load balancer
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct LoadBalancerCreate {
+    #[serde(flatten)]
+    pub identity: IdentityMetadataCreateParams,
+
+    /// How to allocate the load balancer's external IP address.
+    #[serde(default)]
+    pub address_allocator: AddressAllocator,
+
+    /// The ports on which the load balancer accepts traffic. Traffic is
+    /// forwarded to the same port on a backend.
+    pub ports: Vec<u16>,
+
+    /// Network tag selecting additional backends among the instances of the
+    /// project.
+    #[serde(default)]
+    pub backend_tag: Option<Name>,
+
+    /// Health check applied to backends.
+    #[serde(default)]
+    pub health_check: Option<LoadBalancerHealthCheck>,
+}
+
+#[derive(Deserialize, JsonSchema, Clone)]
+pub struct LoadBalancerSelector {
+    /// Name or ID of the project, only required if `load_balancer` is
+    /// provided as a `Name`
+    pub project: Option<NameOrId>,
+    /// Name or ID of the load balancer
+    pub load_balancer: NameOrId,
+}
+
+/// Path parameters for operations on a single load balancer
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct LoadBalancerPath {
+    /// Name or ID of the load balancer
+    pub load_balancer: NameOrId,
+}
+
+/// Path parameters for operations on a single load balancer backend
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct LoadBalancerBackendPath {
+    /// Name or ID of the load balancer
+    pub load_balancer: NameOrId,
+    /// ID of the backend instance
+    pub instance: Uuid,
+}
+
+/// The health of a load balancer backend
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize, JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum LoadBalancerBackendHealth {
+    /// The backend has not been checked yet.
+    Unknown,
+    /// The backend passed its most recent health checks.
+    Healthy,
+    /// The backend failed its most recent health checks, and does not
+    /// receive traffic.
+    Unhealthy,
+}
+
+/// An instance receiving traffic from a load balancer
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct LoadBalancerBackend {
+    /// The load balancer this backend belongs to
+    pub load_balancer_id: Uuid,
+    /// The backend instance
+    pub instance_id: Uuid,
+    /// True if the backend was added explicitly, false if it was selected by
+    /// the load balancer's backend tag
+    pub explicit: bool,
+    pub health: LoadBalancerBackendHealth,
+    /// True if the backend is currently receiving traffic
+    pub serving: bool,
+    /// When the backend was added
+    pub time_created: DateTime<Utc>,
+}
+
+/// Parameters for adding a backend to a load balancer
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct LoadBalancerBackendCreate {
+    /// Name or ID of the instance, in the load balancer's project
+    pub instance: NameOrId,
+}
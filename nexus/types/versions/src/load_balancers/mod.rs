@@ -0,0 +1,10 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Version `LOAD_BALANCERS` of the Nexus external API.
+//!
+//! Adds load balancers, which forward traffic sent to an external address
+//! across a set of backend instances, one backend per port.
+
+pub mod load_balancer;
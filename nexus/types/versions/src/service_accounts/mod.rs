@@ -0,0 +1,12 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Version `SERVICE_ACCOUNTS` of the Nexus external API.
+//!
+//! Adds Silo service accounts and their API tokens. Audit log entries gain a
+//! `service_account` actor kind, so the audit log types come along for the
+//! change.
+
+pub mod audit;
+pub mod service_account;
@@ -0,0 +1,128 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Service account types for version SERVICE_ACCOUNTS.
+
+use api_identity::ObjectIdentity;
+use chrono::{DateTime, Utc};
+use omicron_common::api::external::{
+    IdentityMetadata, IdentityMetadataCreateParams, NameOrId, ObjectIdentity,
+};
+use omicron_uuid_kinds::ServiceAccountUuid;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::v2025_11_20_00::policy::{ProjectRole, SiloRole};
+
+/// View of a service account
+///
+/// A service account is a non-human principal within a Silo. It is granted
+/// roles like a user, but it can only authenticate with API tokens.
+#[derive(ObjectIdentity, Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct ServiceAccount {
+    #[serde(flatten)]
+    pub identity: IdentityMetadata,
+    /// The Silo in which this service account lives
+    pub silo_id: Uuid,
+}
+
+/// Create-time parameters for a `ServiceAccount`
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct ServiceAccountCreate {
+    #[serde(flatten)]
+    pub identity: IdentityMetadataCreateParams,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ServiceAccountPath {
+    /// Name or ID of the service account
+    pub service_account: NameOrId,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ServiceAccountTokenPath {
+    /// Name or ID of the service account
+    pub service_account: NameOrId,
+    /// Name or ID of the token
+    pub token: NameOrId,
+}
+
+/// View of a service account's API token
+///
+/// The token value itself is only shown once, when the token is created or
+/// rotated.
+#[derive(ObjectIdentity, Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct ServiceAccountToken {
+    #[serde(flatten)]
+    pub identity: IdentityMetadata,
+    /// The service account this token authenticates as
+    #[schemars(with = "Uuid")]
+    pub service_account_id: ServiceAccountUuid,
+    /// Expiration timestamp. A null value means the token does not
+    /// automatically expire.
+    pub time_expires: Option<DateTime<Utc>>,
+    /// If true, requests made with this token may only read resources
+    pub read_only: bool,
+    /// If present, requests made with this token may only act on these
+    /// projects (and resources within them), regardless of any Silo-level
+    /// roles held by the service account
+    pub project_ids: Option<Vec<Uuid>>,
+}
+
+/// Create-time parameters for a `ServiceAccountToken`
+///
+/// The `read_only` and `project_ids` restrictions only ever narrow what the
+/// service account's own role assignments allow.
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct ServiceAccountTokenCreate {
+    #[serde(flatten)]
+    pub identity: IdentityMetadataCreateParams,
+    /// Expiration timestamp. If omitted, the token does not automatically
+    /// expire.
+    #[serde(default)]
+    pub time_expires: Option<DateTime<Utc>>,
+    /// Restrict the token to read-only operations
+    #[serde(default)]
+    pub read_only: bool,
+    /// Restrict the token to these projects. If omitted, the token may act
+    /// anywhere the service account's role assignments allow.
+    #[serde(default)]
+    pub project_ids: Option<Vec<Uuid>>,
+}
+
+/// A newly-created or rotated service account token, including its value
+#[derive(Clone, Deserialize, Serialize, JsonSchema)]
+pub struct ServiceAccountTokenValue {
+    #[serde(flatten)]
+    pub token: ServiceAccountToken,
+    /// The bearer token to present in the `Authorization` header. This is the
+    /// only time the value is shown.
+    pub bearer_token: String,
+}
+
+impl std::fmt::Debug for ServiceAccountTokenValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ServiceAccountTokenValue")
+            .field("token", &self.token)
+            .field("bearer_token", &"<redacted>")
+            .finish()
+    }
+}
+
+/// A role granted to a service account
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(tag = "resource_type", rename_all = "snake_case")]
+pub enum ServiceAccountRoleAssignment {
+    /// A role on the service account's own Silo
+    Silo { role_name: SiloRole },
+    /// A role on a project within the service account's Silo
+    Project { project_id: Uuid, role_name: ProjectRole },
+}
+
+/// The complete set of roles granted to a service account
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+pub struct ServiceAccountPolicy {
+    pub role_assignments: Vec<ServiceAccountRoleAssignment>,
+}
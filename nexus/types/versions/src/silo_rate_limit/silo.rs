@@ -0,0 +1,38 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Silo rate limit types for version SILO_RATE_LIMIT.
+
+use omicron_common::api::external::Nullable;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::num::NonZeroU32;
+use uuid::Uuid;
+
+/// View of a silo's external API rate limit
+///
+/// Requests are limited separately for each principal (user, service account,
+/// or SCIM client) in the silo. Each principal may make up to `burst` requests
+/// at once, after which requests are admitted at `requests_per_second`.
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct SiloRateLimit {
+    pub silo_id: Uuid,
+    /// Sustained number of requests per second allowed for each principal.
+    /// If null, the fleet-wide default applies.
+    pub requests_per_second: Option<NonZeroU32>,
+    /// Number of requests a principal may make in a burst. If null, the
+    /// fleet-wide default applies.
+    pub burst: Option<NonZeroU32>,
+}
+
+/// Updateable properties of a silo's external API rate limit
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct SiloRateLimitUpdate {
+    /// Sustained number of requests per second allowed for each principal.
+    /// If set to null, the fleet-wide default applies.
+    pub requests_per_second: Nullable<NonZeroU32>,
+    /// Number of requests a principal may make in a burst. If set to null,
+    /// the fleet-wide default applies.
+    pub burst: Nullable<NonZeroU32>,
+}
@@ -0,0 +1,23 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Instance types for version SLED_EVACUATION.
+
+use omicron_uuid_kinds::SledUuid;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Parameters for `instance_migrate`
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct InstanceMigrate {
+    /// The sled to which the instance should be migrated.
+    ///
+    /// If this is not provided, a sled is chosen in the same way as when an
+    /// instance is started, honoring the instance's affinity and
+    /// anti-affinity groups, except that the sled on which the instance is
+    /// currently running is never chosen.
+    #[schemars(with = "Option<Uuid>")]
+    pub dst_sled_id: Option<SledUuid>,
+}
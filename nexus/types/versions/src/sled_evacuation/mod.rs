@@ -0,0 +1,11 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Version `SLED_EVACUATION` of the Nexus external API.
+//!
+//! Adds operator-initiated instance live migration, and sled evacuations,
+//! which migrate every running instance off of a sled.
+
+pub mod instance;
+pub mod sled;
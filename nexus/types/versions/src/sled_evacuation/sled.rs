@@ -0,0 +1,63 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Sled types for version SLED_EVACUATION.
+
+use chrono::{DateTime, Utc};
+use omicron_uuid_kinds::SledUuid;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// The state of a sled evacuation
+#[derive(
+    Clone, Copy, Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum SledEvacuationState {
+    /// Instances are being migrated off the sled.
+    InProgress,
+    /// No instances remain on the sled.
+    Completed,
+    /// The evacuation was cancelled by an operator. Migrations that were
+    /// already underway when the evacuation was cancelled are not affected.
+    Cancelled,
+}
+
+/// View of a sled evacuation
+///
+/// An evacuation live-migrates every running instance off of a sled, so that
+/// the sled can be serviced. Instances are placed on other sleds in the same
+/// way as when they are started, honoring affinity and anti-affinity groups
+/// and the capacity of the remaining sleds.
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct SledEvacuation {
+    /// Unique ID of this evacuation
+    pub id: Uuid,
+    /// The sled being evacuated
+    #[schemars(with = "Uuid")]
+    pub sled_id: SledUuid,
+    pub state: SledEvacuationState,
+    /// When the evacuation was started
+    pub time_started: DateTime<Utc>,
+    /// When the evacuation completed or was cancelled
+    pub time_finished: Option<DateTime<Utc>>,
+    /// Number of instances that were running on the sled when the
+    /// evacuation started
+    pub instances_total: u64,
+    /// Number of instances that are still running on the sled, including
+    /// those that are currently migrating away from it
+    pub instances_remaining: u64,
+    /// Number of instances currently migrating away from the sled
+    pub instances_migrating: u64,
+    /// Number of migrations started by this evacuation
+    pub migrations_started: u64,
+    /// The most recent error encountered while trying to migrate an instance
+    /// off of the sled, if any
+    ///
+    /// Instances which could not be migrated (for example, because no other
+    /// sled has capacity for them, or because they use local storage) are
+    /// retried periodically until the evacuation is cancelled.
+    pub last_error: Option<String>,
+}
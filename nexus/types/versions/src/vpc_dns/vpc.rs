@@ -0,0 +1,39 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! VPC types for version VPC_DNS.
+
+use omicron_common::api::external::Name;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+
+/// A user-defined record in a VPC's private DNS zone
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+pub struct VpcDnsRecord {
+    /// The name of the record, relative to the VPC's zone
+    pub name: Name,
+    /// The address the name resolves to. IPv4 addresses are served as A
+    /// records, and IPv6 addresses as AAAA records.
+    pub address: IpAddr,
+}
+
+/// The user-defined records in a VPC's private DNS zone
+///
+/// Instances in the VPC can resolve each other by name in this zone: each
+/// instance's name resolves to the addresses of its primary network
+/// interface, and `<interface>.<instance>` to the addresses of that
+/// interface. User-defined records are served alongside these.
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct VpcDnsRecords {
+    /// The name of the VPC's zone, `<vpc dns_name>.internal`
+    pub zone_name: String,
+    pub records: Vec<VpcDnsRecord>,
+}
+
+/// Updated list of the user-defined records in a VPC's private DNS zone
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct VpcDnsRecordsUpdate {
+    pub records: Vec<VpcDnsRecord>,
+}
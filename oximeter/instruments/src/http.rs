@@ -13,6 +13,7 @@ use futures::Future;
 use http::StatusCode;
 use oximeter::{
     MetricsError, Producer, Sample, histogram::Histogram, histogram::Record,
+    types::Cumulative,
 };
 use std::collections::HashMap;
 use std::hash::{DefaultHasher, Hash as _, Hasher};
@@ -21,6 +22,7 @@ use std::time::{Duration, Instant};
 
 oximeter::use_timeseries!("http-service.toml");
 pub use http_service::HttpService;
+pub use http_service::RateLimitedRequests;
 pub use http_service::RequestLatencyHistogram;
 
 impl RequestLatencyHistogram {
@@ -95,6 +97,8 @@ pub struct LatencyTracker {
     /// `RequestLatencyHistogram` when handling a request that we already have
     /// one for. Instead, we use this key to get the existing entry.
     latencies: Arc<Mutex<HashMap<u64, RequestLatencyHistogram>>>,
+    /// The number of requests rejected by rate limiting, keyed by operation.
+    rate_limited: Arc<Mutex<HashMap<String, RateLimitedRequests>>>,
     /// The histogram used to track each request.
     ///
     /// We store it here to clone as we see new requests.
@@ -109,6 +113,7 @@ impl LatencyTracker {
         Self {
             service,
             latencies: Arc::new(Mutex::new(HashMap::new())),
+            rate_limited: Arc::new(Mutex::new(HashMap::new())),
             histogram,
         }
     }
@@ -150,6 +155,23 @@ impl LatencyTracker {
         entry.datum.sample(latency.as_nanos() as _).map_err(MetricsError::from)
     }
 
+    /// Count a request to `operation_id` that was rejected because the client
+    /// exceeded its rate limit.
+    ///
+    /// Such requests never reach their handler, so they're not otherwise
+    /// recorded by [`LatencyTracker::instrument_dropshot_handler`].
+    pub fn record_rate_limited(&self, operation_id: &str) {
+        let mut rate_limited = self.rate_limited.lock().unwrap();
+        rate_limited
+            .entry(operation_id.to_string())
+            .or_insert_with(|| RateLimitedRequests {
+                operation_id: operation_id.to_string().into(),
+                datum: Cumulative::new(0),
+            })
+            .datum
+            .increment();
+    }
+
     /// Instrument the given Dropshot endpoint handler function.
     ///
     /// This method is intended as a semi-convenient way to instrument the handler for a `dropshot`
@@ -205,10 +227,18 @@ impl Producer for LatencyTracker {
         #[allow(clippy::needless_collect)]
         let latencies: Vec<_> =
             self.latencies.lock().unwrap().values().cloned().collect();
+        #[allow(clippy::needless_collect)]
+        let rate_limited: Vec<_> =
+            self.rate_limited.lock().unwrap().values().cloned().collect();
         let service = self.service.clone();
         let samples = latencies
             .into_iter()
             .map(|latency| Sample::new(&service, &latency))
+            .chain(
+                rate_limited
+                    .into_iter()
+                    .map(|count| Sample::new(&service, &count)),
+            )
             .collect::<Result<Vec<_>, _>>()?;
         Ok(Box::new(samples.into_iter()))
     }
@@ -246,4 +276,22 @@ mod tests {
             assert_eq!(bins[1].count, 1);
         }
     }
+
+    #[test]
+    fn test_rate_limited_requests() {
+        let service =
+            HttpService { name: "my-service".into(), id: ID.parse().unwrap() };
+        let hist = Histogram::new(&[100, 1000]).unwrap();
+        let mut tracker = LatencyTracker::new(service, hist);
+        tracker.record_rate_limited("op0");
+        tracker.record_rate_limited("op0");
+        tracker.record_rate_limited("op1");
+        {
+            let rate_limited = tracker.rate_limited.lock().unwrap();
+            assert_eq!(rate_limited.len(), 2);
+            assert_eq!(rate_limited["op0"].datum.value(), 2);
+            assert_eq!(rate_limited["op1"].datum.value(), 1);
+        }
+        assert_eq!(tracker.produce().unwrap().count(), 2);
+    }
 }
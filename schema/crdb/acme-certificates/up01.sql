@@ -0,0 +1,2 @@
+ALTER TABLE omicron.public.certificate
+    ADD COLUMN IF NOT EXISTS acme_issued BOOL NOT NULL DEFAULT false;
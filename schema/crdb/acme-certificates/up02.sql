@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS omicron.public.acme_account (
+    directory_url STRING(512) PRIMARY KEY,
+    time_created TIMESTAMPTZ NOT NULL,
+    account_url STRING(512) NOT NULL,
+    key_pem STRING NOT NULL
+);
@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS omicron.public.acme_http01_challenge (
+    token STRING(128) PRIMARY KEY,
+    time_created TIMESTAMPTZ NOT NULL,
+    key_authorization STRING(256) NOT NULL
+);
@@ -0,0 +1,5 @@
+CREATE TYPE IF NOT EXISTS omicron.public.alert_receiver_kind AS ENUM (
+    'webhook',
+    'smtp',
+    'syslog'
+);
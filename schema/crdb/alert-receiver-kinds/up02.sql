@@ -0,0 +1,3 @@
+ALTER TABLE omicron.public.alert_receiver
+    ADD COLUMN IF NOT EXISTS kind omicron.public.alert_receiver_kind
+    NOT NULL DEFAULT 'webhook';
@@ -0,0 +1 @@
+ALTER TABLE omicron.public.alert_receiver ALTER COLUMN kind DROP DEFAULT;
@@ -0,0 +1,2 @@
+ALTER TABLE omicron.public.alert_receiver
+    ADD COLUMN IF NOT EXISTS smtp_from STRING(512);
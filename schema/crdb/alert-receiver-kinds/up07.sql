@@ -0,0 +1,4 @@
+ALTER TABLE omicron.public.alert_receiver
+    ADD CONSTRAINT IF NOT EXISTS smtp_config_iff_smtp CHECK (
+        (kind = 'smtp') = (smtp_from IS NOT NULL AND smtp_to IS NOT NULL)
+    );
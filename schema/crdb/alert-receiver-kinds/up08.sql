@@ -0,0 +1,4 @@
+ALTER TABLE omicron.public.alert_receiver
+    ADD CONSTRAINT IF NOT EXISTS syslog_config_iff_syslog CHECK (
+        (kind = 'syslog') = (syslog_facility IS NOT NULL)
+    );
@@ -0,0 +1,2 @@
+ALTER TABLE omicron.public.audit_log
+ADD COLUMN IF NOT EXISTS resource_id UUID;
@@ -0,0 +1,4 @@
+ALTER TABLE omicron.public.audit_log
+ADD CONSTRAINT IF NOT EXISTS resource_type_and_id_consistent CHECK (
+    (resource_type IS NULL) = (resource_id IS NULL)
+);
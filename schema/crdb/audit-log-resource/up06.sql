@@ -0,0 +1,3 @@
+CREATE UNIQUE INDEX IF NOT EXISTS audit_log_by_resource_id
+    ON omicron.public.audit_log (resource_id, time_completed, id)
+    WHERE resource_id IS NOT NULL AND time_completed IS NOT NULL;
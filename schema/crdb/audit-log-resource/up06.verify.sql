@@ -0,0 +1,2 @@
+-- DO NOT EDIT. Generated by test_migration_verification_files.
+SELECT CAST(IF((SELECT true WHERE EXISTS (SELECT index_name FROM omicron.crdb_internal.table_indexes WHERE descriptor_name = 'audit_log' AND index_name = 'audit_log_by_resource_id')),'true','Schema change verification failed: index audit_log_by_resource_id on table audit_log does not exist') AS BOOL);
@@ -0,0 +1,4 @@
+CREATE TYPE IF NOT EXISTS omicron.public.audit_log_sink_kind AS ENUM (
+    'syslog',
+    'http'
+);
@@ -0,0 +1,35 @@
+CREATE TABLE IF NOT EXISTS omicron.public.audit_log_sink (
+    /* Identity metadata (resource) */
+    id UUID PRIMARY KEY,
+    name STRING(63) NOT NULL,
+    description STRING(512) NOT NULL,
+    time_created TIMESTAMPTZ NOT NULL,
+    time_modified TIMESTAMPTZ NOT NULL,
+    time_deleted TIMESTAMPTZ,
+
+    kind omicron.public.audit_log_sink_kind NOT NULL,
+    -- For syslog sinks, the collector's "host:port". For HTTP sinks, the URL
+    -- that batches of entries are POSTed to.
+    endpoint STRING(512) NOT NULL,
+    -- PEM-encoded CA certificates used to verify a syslog collector over TLS.
+    -- If null, syslog messages are sent over plain TCP.
+    tls_root_cert STRING,
+    -- Secret used to sign requests to HTTP sinks.
+    secret STRING(512),
+
+    -- The (time_completed, id) of the last audit log entry delivered to this
+    -- sink. Entries sorting after this are delivered next.
+    cursor_time_completed TIMESTAMPTZ NOT NULL,
+    cursor_id UUID NOT NULL,
+    time_last_delivered TIMESTAMPTZ,
+
+    -- Delivery failures since the last success, used to back off.
+    consecutive_failures INT4 NOT NULL,
+    time_next_attempt TIMESTAMPTZ,
+    last_error STRING,
+
+    CONSTRAINT kind_config_consistent CHECK (
+        (kind = 'syslog' AND secret IS NULL)
+        OR (kind = 'http' AND secret IS NOT NULL AND tls_root_cert IS NULL)
+    )
+);
@@ -0,0 +1,5 @@
+CREATE UNIQUE INDEX IF NOT EXISTS lookup_audit_log_sink_by_name
+ON omicron.public.audit_log_sink (
+    name
+) WHERE
+    time_deleted IS NULL;
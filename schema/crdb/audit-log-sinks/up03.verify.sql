@@ -0,0 +1,2 @@
+-- DO NOT EDIT. Generated by test_migration_verification_files.
+SELECT CAST(IF((SELECT true WHERE EXISTS (SELECT index_name FROM omicron.crdb_internal.table_indexes WHERE descriptor_name = 'audit_log_sink' AND index_name = 'lookup_audit_log_sink_by_name')),'true','Schema change verification failed: index lookup_audit_log_sink_by_name on table audit_log_sink does not exist') AS BOOL);
@@ -579,7 +579,12 @@ CREATE TABLE IF NOT EXISTS omicron.public.certificate (
     cert BYTES NOT NULL,
 
     -- key.pem file (private key in PEM format) as a binary blob
-    key BYTES NOT NULL
+    key BYTES NOT NULL,
+
+    -- whether Nexus obtained this certificate from an ACME server (rather
+    -- than it being uploaded by an operator), in which case Nexus also
+    -- renews and removes it
+    acme_issued BOOL NOT NULL DEFAULT false
 );
 
 -- Add an index which lets us look up certificates for a particular service
@@ -598,6 +603,25 @@ CREATE UNIQUE INDEX IF NOT EXISTS lookup_certificate_by_silo ON omicron.public.c
 ) WHERE
     time_deleted IS NULL;
 
+-- The account Nexus uses with each ACME server it obtains certificates from.
+-- Every Nexus shares the account, so only the first to register it creates
+-- one.
+CREATE TABLE IF NOT EXISTS omicron.public.acme_account (
+    directory_url STRING(512) PRIMARY KEY,
+    time_created TIMESTAMPTZ NOT NULL,
+    -- URL identifying the account to the ACME server
+    account_url STRING(512) NOT NULL,
+    -- the account's private key, in PEM format
+    key_pem STRING NOT NULL
+);
+
+-- Outstanding ACME HTTP-01 challenges, which any Nexus may be asked to answer
+CREATE TABLE IF NOT EXISTS omicron.public.acme_http01_challenge (
+    token STRING(128) PRIMARY KEY,
+    time_created TIMESTAMPTZ NOT NULL,
+    key_authorization STRING(256) NOT NULL
+);
+
 -- A table describing virtual resource provisioning which may be associated
 -- with a collection of objects, including:
 -- - Projects
@@ -1197,6 +1221,23 @@ CREATE TABLE IF NOT EXISTS omicron.public.instance_ssh_key (
     PRIMARY KEY (instance_id, ssh_key_id)
 );
 
+/*
+ * Network tags carried by an instance.
+ *
+ * VPC firewall rules may target, or filter traffic by, the instances carrying
+ * a tag. Entries are removed when the instance is destroyed.
+ */
+CREATE TABLE IF NOT EXISTS omicron.public.instance_network_tag (
+    instance_id UUID NOT NULL,
+    tag STRING(63) NOT NULL,
+    time_created TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (instance_id, tag)
+);
+
+/* Lookup the instances carrying a tag */
+CREATE INDEX IF NOT EXISTS lookup_instance_network_tag_by_tag
+    ON omicron.public.instance_network_tag (tag, instance_id);
+
 CREATE TABLE IF NOT EXISTS omicron.public.silo_quotas (
     silo_id UUID PRIMARY KEY,
     time_created TIMESTAMPTZ NOT NULL,
@@ -1245,6 +1286,21 @@ CREATE TABLE IF NOT EXISTS omicron.public.silo_auth_settings (
     device_token_max_ttl_seconds INT8 CHECK (device_token_max_ttl_seconds > 0)
 );
 
+/*
+ * Per-silo overrides of the external API rate limit. Requests are limited per
+ * principal (user, service account, or SCIM token). A silo without a row
+ * here uses the fleet-wide defaults from Nexus configuration.
+ */
+CREATE TABLE IF NOT EXISTS omicron.public.silo_rate_limit (
+    silo_id UUID PRIMARY KEY,
+    time_created TIMESTAMPTZ NOT NULL,
+    time_modified TIMESTAMPTZ NOT NULL,
+
+    -- null means use the fleet-wide default from Nexus configuration
+    requests_per_second INT8 CHECK (requests_per_second > 0),
+    burst INT8 CHECK (burst > 0)
+);
+
 /*
  * Fleet-wide networking settings. Singleton row; see `db_metadata` for an
  * explanation of the singleton pattern.
@@ -2178,6 +2234,24 @@ CREATE UNIQUE INDEX IF NOT EXISTS lookup_firewall_by_vpc ON omicron.public.vpc_f
 ) WHERE
     time_deleted IS NULL;
 
+/*
+ * User-defined records in a VPC's private DNS zone. A name may have several
+ * records, one per address.
+ */
+CREATE TABLE IF NOT EXISTS omicron.public.vpc_dns_record (
+    id UUID PRIMARY KEY,
+    time_created TIMESTAMPTZ NOT NULL,
+    time_modified TIMESTAMPTZ NOT NULL,
+    time_deleted TIMESTAMPTZ,
+    vpc_id UUID NOT NULL,
+    name STRING(63) NOT NULL,
+    address INET NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS lookup_vpc_dns_record_by_vpc
+    ON omicron.public.vpc_dns_record (vpc_id, name)
+    WHERE time_deleted IS NULL;
+
 CREATE TYPE IF NOT EXISTS omicron.public.vpc_router_kind AS ENUM (
     'system',
     'custom'
@@ -2497,7 +2571,13 @@ CREATE TYPE IF NOT EXISTS omicron.public.ip_kind AS ENUM (
      * A floating IP is an independent, named API resource that can be assigned
      * to an instance or service.
      */
-    'floating'
+    'floating',
+
+    /*
+     * The frontend address of a load balancer, whose lifetime is the same as
+     * the load balancer's. Its parent is the load balancer.
+     */
+    'load_balancer'
 );
 
 CREATE TYPE IF NOT EXISTS omicron.public.ip_attach_state AS ENUM (
@@ -2682,6 +2762,90 @@ WHERE
     omicron.public.external_ip.kind = 'floating' AND
     project_id IS NOT NULL;
 
+/*
+ * Layer-4 load balancers, which distribute traffic arriving at a frontend
+ * address allocated from an IP pool across a set of backend instances.
+ */
+CREATE TABLE IF NOT EXISTS omicron.public.load_balancer (
+    /* Identity metadata (resource) */
+    id UUID PRIMARY KEY,
+    name STRING(63) NOT NULL,
+    description STRING(512) NOT NULL,
+    time_created TIMESTAMPTZ NOT NULL,
+    time_modified TIMESTAMPTZ NOT NULL,
+    time_deleted TIMESTAMPTZ,
+
+    /* FK to the `project` table. */
+    project_id UUID NOT NULL,
+
+    /* FK to the `external_ip` table, for the 'load_balancer' frontend IP. */
+    external_ip_id UUID NOT NULL,
+
+    /* The ports on which traffic is accepted and forwarded to backends. */
+    ports INT4[] NOT NULL,
+
+    /* If set, instances in the project carrying this network tag are
+     * backends, in addition to those added explicitly. */
+    backend_tag STRING(63),
+
+    /* TCP health check of backends. All NULL if backends are not checked. */
+    health_check_port INT4,
+    healthy_threshold INT4,
+    unhealthy_threshold INT4,
+
+    CONSTRAINT health_check_all_or_nothing CHECK (
+        (health_check_port IS NULL) = (healthy_threshold IS NULL) AND
+        (health_check_port IS NULL) = (unhealthy_threshold IS NULL)
+    )
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS lookup_load_balancer_by_project ON omicron.public.load_balancer (
+    project_id,
+    name
+) WHERE
+    time_deleted IS NULL;
+
+CREATE TYPE IF NOT EXISTS omicron.public.load_balancer_backend_health AS ENUM (
+    'unknown',
+    'healthy',
+    'unhealthy'
+);
+
+/*
+ * The instances behind a load balancer, along with their health.
+ */
+CREATE TABLE IF NOT EXISTS omicron.public.load_balancer_backend (
+    /* FK to the `load_balancer` table. */
+    load_balancer_id UUID NOT NULL,
+
+    /* FK to the `instance` table. */
+    instance_id UUID NOT NULL,
+
+    time_created TIMESTAMPTZ NOT NULL,
+    time_modified TIMESTAMPTZ NOT NULL,
+
+    /* True if added through the API, false if selected by `backend_tag`. */
+    explicit BOOL NOT NULL,
+
+    health omicron.public.load_balancer_backend_health NOT NULL,
+    consecutive_successes INT4 NOT NULL,
+    consecutive_failures INT4 NOT NULL,
+
+    /* The VMM whose OPTE port the frontend address was last added to, if
+     * this backend is serving any of the load balancer's ports. */
+    frontend_vmm_id UUID,
+
+    /* The load balancer ports the switches last forwarded to this backend.
+     * Each port is forwarded to a single backend. */
+    ports INT4[] NOT NULL DEFAULT ARRAY[],
+
+    PRIMARY KEY (load_balancer_id, instance_id)
+);
+
+CREATE INDEX IF NOT EXISTS lookup_load_balancer_backend_by_instance ON omicron.public.load_balancer_backend (
+    instance_id
+);
+
 /*******************************************************************/
 
 /*
@@ -3404,6 +3568,73 @@ CREATE UNIQUE INDEX IF NOT EXISTS lookup_dns_name_by_zone ON omicron.public.dns_
     dns_zone_id, name
 ) WHERE version_removed IS NULL;
 
+/*
+ * The DNS zones private to VPCs, as last computed from instances' network
+ * interfaces and users' records. Unlike the DNS groups above, only the latest
+ * generation is kept: the zones are entirely derived from other tables.
+ */
+CREATE TABLE IF NOT EXISTS omicron.public.vpc_dns_config (
+    singleton BOOL NOT NULL PRIMARY KEY,
+    generation INT8 NOT NULL,
+    time_created TIMESTAMPTZ NOT NULL,
+    zones JSONB NOT NULL,
+    -- Recursive resolvers that the DNS servers forward the VPCs' instances'
+    -- queries for other names to
+    forwarders INET[] NOT NULL DEFAULT ARRAY[],
+
+    CHECK (singleton = true)
+);
+
+/*
+ * DNSSEC keys for the external DNS zones.  Keys are created, activated, and
+ * retired by a background task in Nexus that rolls them over on a schedule.
+ * A key's private half is sent to the external DNS servers, which sign their
+ * answers with it.
+ */
+CREATE TYPE IF NOT EXISTS omicron.public.dnssec_key_role AS ENUM (
+    'ksk',
+    'zsk'
+);
+
+CREATE TYPE IF NOT EXISTS omicron.public.dnssec_key_state AS ENUM (
+    /* Published in the zone, but not yet used to sign it */
+    'published',
+    /* Published and used to sign the zone */
+    'active',
+    /* Still published, but no longer used to sign the zone */
+    'retired'
+);
+
+CREATE TABLE IF NOT EXISTS omicron.public.dnssec_key (
+    id UUID PRIMARY KEY,
+    time_created TIMESTAMPTZ NOT NULL,
+    time_activated TIMESTAMPTZ,
+    time_retired TIMESTAMPTZ,
+    time_deleted TIMESTAMPTZ,
+    zone_name TEXT NOT NULL,
+    role omicron.public.dnssec_key_role NOT NULL,
+    state omicron.public.dnssec_key_state NOT NULL,
+    /* base64-encoded PKCS#8 document */
+    private_key TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS lookup_dnssec_key_by_zone
+    ON omicron.public.dnssec_key (zone_name, role)
+    WHERE time_deleted IS NULL;
+
+/*
+ * The generation of the DNSSEC keys as a whole, which advances whenever any
+ * key is created, changes state, or is deleted.  DNS servers are sent all of
+ * the keys at once, at this generation.
+ */
+CREATE TABLE IF NOT EXISTS omicron.public.dnssec_config (
+    singleton BOOL NOT NULL PRIMARY KEY,
+    generation INT8 NOT NULL,
+    time_modified TIMESTAMPTZ NOT NULL,
+
+    CHECK (singleton = true)
+);
+
 /*******************************************************************/
 
 /*
@@ -3498,6 +3729,60 @@ CREATE UNIQUE INDEX IF NOT EXISTS device_access_token_unique
 CREATE INDEX IF NOT EXISTS lookup_device_access_token_by_silo_user
     ON omicron.public.device_access_token (silo_user_id);
 
+/*
+ * Service accounts
+ *
+ * A service account is a non-human principal within a Silo.  It holds role
+ * assignments like a Silo user does, but it authenticates only with the
+ * long-lived API tokens in "service_account_token".
+ */
+CREATE TABLE IF NOT EXISTS omicron.public.service_account (
+    /* Identity metadata (resource) */
+    id UUID PRIMARY KEY,
+    name STRING(63) NOT NULL,
+    description STRING(512) NOT NULL,
+    time_created TIMESTAMPTZ NOT NULL,
+    time_modified TIMESTAMPTZ NOT NULL,
+    time_deleted TIMESTAMPTZ,
+
+    silo_id UUID NOT NULL
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS lookup_service_account_by_silo
+    ON omicron.public.service_account (silo_id, name)
+    WHERE time_deleted IS NULL;
+
+CREATE TABLE IF NOT EXISTS omicron.public.service_account_token (
+    /* Identity metadata (resource) */
+    id UUID PRIMARY KEY,
+    name STRING(63) NOT NULL,
+    description STRING(512) NOT NULL,
+    time_created TIMESTAMPTZ NOT NULL,
+    time_modified TIMESTAMPTZ NOT NULL,
+    time_deleted TIMESTAMPTZ,
+
+    service_account_id UUID NOT NULL,
+    token STRING(40) NOT NULL,
+    time_expires TIMESTAMPTZ,
+
+    /*
+     * Restrictions on what this token may be used for.  These only ever narrow
+     * the privileges conferred by the service account's role assignments.
+     * A NULL "project_ids" means the token is not restricted to any particular
+     * set of projects.
+     */
+    read_only BOOL NOT NULL,
+    project_ids UUID[]
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS lookup_service_account_token_by_service_account
+    ON omicron.public.service_account_token (service_account_id, name)
+    WHERE time_deleted IS NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS service_account_token_unique
+    ON omicron.public.service_account_token (token)
+    WHERE time_deleted IS NULL;
+
 
 /*
  * Assignments between users, roles, and resources
@@ -3512,7 +3797,8 @@ CREATE INDEX IF NOT EXISTS lookup_device_access_token_by_silo_user
 CREATE TYPE IF NOT EXISTS omicron.public.identity_type AS ENUM (
   'user_builtin',
   'silo_user',
-  'silo_group'
+  'silo_group',
+  'service_account'
 );
 
 CREATE TABLE IF NOT EXISTS omicron.public.role_assignment (
@@ -6743,6 +7029,55 @@ CREATE INDEX IF NOT EXISTS migrations_by_time_created ON omicron.public.migratio
     time_created
 );
 
+CREATE TYPE IF NOT EXISTS omicron.public.sled_evacuation_state AS ENUM (
+    'in_progress',
+    'completed',
+    'cancelled'
+);
+
+/*
+ * Operator-requested evacuations of all running instances from a sled.
+ *
+ * The `sled_evacuator` background task starts migrations for the instances
+ * remaining on each sled with an in-progress evacuation, and marks the
+ * evacuation completed once none remain.
+ */
+CREATE TABLE IF NOT EXISTS omicron.public.sled_evacuation (
+    id UUID PRIMARY KEY,
+
+    /* The sled being evacuated */
+    sled_id UUID NOT NULL,
+
+    time_started TIMESTAMPTZ NOT NULL,
+
+    /* When the evacuation completed or was cancelled */
+    time_finished TIMESTAMPTZ,
+
+    state omicron.public.sled_evacuation_state NOT NULL,
+
+    /* The number of instances on the sled when the evacuation started */
+    instances_total INT8 NOT NULL,
+
+    /* The number of migrations started by the evacuation so far */
+    migrations_started INT8 NOT NULL DEFAULT 0,
+
+    /* The most recent error encountered while migrating an instance */
+    last_error TEXT,
+
+    CONSTRAINT time_finished_iff_not_in_progress CHECK (
+        (state = 'in_progress') = (time_finished IS NULL)
+    )
+);
+
+/* Only one evacuation may be in progress for a sled at a time */
+CREATE UNIQUE INDEX IF NOT EXISTS one_in_progress_evacuation_per_sled
+    ON omicron.public.sled_evacuation (sled_id)
+    WHERE state = 'in_progress';
+
+/* Lookup the most recent evacuations of a sled */
+CREATE INDEX IF NOT EXISTS lookup_sled_evacuation_by_sled
+    ON omicron.public.sled_evacuation (sled_id, time_started);
+
 /* Lookup region snapshot by snapshot id */
 CREATE INDEX IF NOT EXISTS lookup_region_snapshot_by_snapshot_id on omicron.public.region_snapshot (
     snapshot_id
@@ -6830,7 +7165,8 @@ CREATE TYPE IF NOT EXISTS omicron.public.audit_log_actor_kind AS ENUM (
     'user_builtin',
     'silo_user',
     'unauthenticated',
-    'scim'
+    'scim',
+    'service_account'
 );
 
 CREATE TYPE IF NOT EXISTS omicron.public.audit_log_result_kind AS ENUM (
@@ -6886,6 +7222,15 @@ CREATE TABLE IF NOT EXISTS omicron.public.audit_log (
     -- or SCIM token ID.
     credential_id UUID,
 
+    -- The resource the operation acted on, if known. resource_type is the
+    -- kebab-case name of the resource type, e.g., 'project'.
+    resource_type STRING(63),
+    resource_id UUID,
+
+    -- The request body, with sensitive values redacted according to the
+    -- endpoint's policy. Null if there was no body or it was not recorded.
+    request_body JSONB,
+
     -- make sure time_completed and result_kind are either both null or both not
     CONSTRAINT time_completed_and_result_kind CHECK (
         (time_completed IS NULL AND result_kind IS NULL)
@@ -6924,6 +7269,10 @@ CREATE TABLE IF NOT EXISTS omicron.public.audit_log (
         OR
         -- For unauthenticated: must not have actor_id or actor_silo_id
         (actor_kind = 'unauthenticated' AND actor_id IS NULL AND actor_silo_id IS NULL)
+    ),
+
+    CONSTRAINT resource_type_and_id_consistent CHECK (
+        (resource_type IS NULL) = (resource_id IS NULL)
     )
 );
 
@@ -6949,6 +7298,11 @@ CREATE UNIQUE INDEX IF NOT EXISTS audit_log_by_time_completed
     ON omicron.public.audit_log (time_completed, id)
     WHERE time_completed IS NOT NULL;
 
+-- Supports listing the audit log entries for a particular resource.
+CREATE UNIQUE INDEX IF NOT EXISTS audit_log_by_resource_id
+    ON omicron.public.audit_log (resource_id, time_completed, id)
+    WHERE resource_id IS NOT NULL AND time_completed IS NOT NULL;
+
 -- Supports "find stale incomplete rows ordered by time_started".
 CREATE INDEX IF NOT EXISTS audit_log_incomplete_by_time_started
     ON omicron.public.audit_log (time_started, id)
@@ -6982,12 +7336,67 @@ SELECT
     error_message,
     result_kind,
     auth_method,
-    credential_id
+    credential_id,
+    resource_type,
+    resource_id,
+    request_body
 FROM omicron.public.audit_log
 WHERE
     time_completed IS NOT NULL
     AND result_kind IS NOT NULL;
 
+/*
+ * Audit log sinks: external destinations that audit log entries are streamed
+ * to by the `audit_log_export` background task.
+ */
+
+CREATE TYPE IF NOT EXISTS omicron.public.audit_log_sink_kind AS ENUM (
+    'syslog',
+    'http'
+);
+
+CREATE TABLE IF NOT EXISTS omicron.public.audit_log_sink (
+    /* Identity metadata (resource) */
+    id UUID PRIMARY KEY,
+    name STRING(63) NOT NULL,
+    description STRING(512) NOT NULL,
+    time_created TIMESTAMPTZ NOT NULL,
+    time_modified TIMESTAMPTZ NOT NULL,
+    time_deleted TIMESTAMPTZ,
+
+    kind omicron.public.audit_log_sink_kind NOT NULL,
+    -- For syslog sinks, the collector's "host:port". For HTTP sinks, the URL
+    -- that batches of entries are POSTed to.
+    endpoint STRING(512) NOT NULL,
+    -- PEM-encoded CA certificates used to verify a syslog collector over TLS.
+    -- If null, syslog messages are sent over plain TCP.
+    tls_root_cert STRING,
+    -- Secret used to sign requests to HTTP sinks.
+    secret STRING(512),
+
+    -- The (time_completed, id) of the last audit log entry delivered to this
+    -- sink. Entries sorting after this are delivered next.
+    cursor_time_completed TIMESTAMPTZ NOT NULL,
+    cursor_id UUID NOT NULL,
+    time_last_delivered TIMESTAMPTZ,
+
+    -- Delivery failures since the last success, used to back off.
+    consecutive_failures INT4 NOT NULL,
+    time_next_attempt TIMESTAMPTZ,
+    last_error STRING,
+
+    CONSTRAINT kind_config_consistent CHECK (
+        (kind = 'syslog' AND secret IS NULL)
+        OR (kind = 'http' AND secret IS NOT NULL AND tls_root_cert IS NULL)
+    )
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS lookup_audit_log_sink_by_name
+ON omicron.public.audit_log_sink (
+    name
+) WHERE
+    time_deleted IS NULL;
+
 /*
  * Alerts
  */
@@ -6997,6 +7406,13 @@ WHERE
  * Alert webhook receivers, receiver secrets, and receiver subscriptions.
  */
 
+-- The mechanism by which alerts are delivered to a receiver.
+CREATE TYPE IF NOT EXISTS omicron.public.alert_receiver_kind AS ENUM (
+    'webhook',
+    'smtp',
+    'syslog'
+);
+
 CREATE TABLE IF NOT EXISTS omicron.public.alert_receiver (
     /* Identity metadata (resource) */
     id UUID PRIMARY KEY,
@@ -7012,8 +7428,29 @@ CREATE TABLE IF NOT EXISTS omicron.public.alert_receiver (
     -- `secret_gen`, as updating secrets and updating subscriptions are separate
     -- operations which don't conflict with each other.
     subscription_gen INT NOT NULL,
-    -- URL of the endpoint webhooks are delivered to.
-    endpoint STRING(512) NOT NULL
+    -- URL of the endpoint alerts are delivered to. For webhooks, this is the
+    -- HTTP(S) URL requests are sent to; for SMTP receivers, an `smtp://` URL
+    -- naming the mail server; and for syslog receivers, a `udp://` or
+    -- `tcp://` URL naming the syslog collector.
+    endpoint STRING(512) NOT NULL,
+
+    kind omicron.public.alert_receiver_kind NOT NULL,
+
+    -- SMTP receiver configuration: the envelope and header sender address,
+    -- and the list of recipient addresses.
+    smtp_from STRING(512),
+    smtp_to STRING(512)[],
+
+    -- Syslog receiver configuration: the numeric syslog facility code used
+    -- when computing the priority of each message.
+    syslog_facility INT2,
+
+    CONSTRAINT smtp_config_iff_smtp CHECK (
+        (kind = 'smtp') = (smtp_from IS NOT NULL AND smtp_to IS NOT NULL)
+    ),
+    CONSTRAINT syslog_config_iff_syslog CHECK (
+        (kind = 'syslog') = (syslog_facility IS NOT NULL)
+    )
 );
 
 CREATE UNIQUE INDEX IF NOT EXISTS lookup_alert_rx_by_id
@@ -7061,7 +7498,24 @@ CREATE TYPE IF NOT EXISTS omicron.public.alert_class AS ENUM (
     'test.foo.bar',
     'test.foo.baz',
     'test.quux.bar',
-    'test.quux.bar.baz'
+    'test.quux.bar.baz',
+    -- Instance lifecycle alerts.
+    'instance.failed',
+    'instance.auto_restarted',
+    'instance.migration.completed',
+    'instance.migration.failed',
+    -- Storage repair alerts.
+    'storage.region_replacement.started',
+    'storage.region_replacement.finished',
+    -- Hardware alerts.
+    'hardware.physical_disk.expunged',
+    'hardware.sled.policy_changed',
+    -- Support bundle alerts.
+    'support_bundle.ready',
+    -- System update alerts.
+    'update.status_changed',
+    -- Certificate alerts.
+    'certificate.expiring'
     -- Add new alert classes here!
 );
 
@@ -7338,6 +7792,9 @@ CREATE TABLE IF NOT EXISTS omicron.public.webhook_delivery_attempt (
     time_created TIMESTAMPTZ NOT NULL,
     -- UUID of the Nexus who did this delivery attempt.
     deliverator_id UUID NOT NULL,
+    -- When the TLS certificate presented by the receiver's endpoint expires,
+    -- if the attempt was made over HTTPS and got far enough to see one.
+    time_peer_cert_expires TIMESTAMPTZ,
 
     -- Attempt numbers start at 1
     CONSTRAINT attempts_start_at_1 CHECK (attempt >= 1),
@@ -7380,6 +7837,13 @@ ON omicron.public.webhook_delivery_attempt (
     rx_id
 );
 
+-- Used to find the most recently seen certificate for each receiver.
+CREATE INDEX IF NOT EXISTS lookup_webhook_peer_cert_expiry
+ON omicron.public.webhook_delivery_attempt (
+    rx_id, time_created DESC
+) WHERE
+    time_peer_cert_expires IS NOT NULL;
+
 CREATE TYPE IF NOT EXISTS omicron.public.user_data_export_resource_type AS ENUM (
   'snapshot',
   'image'
@@ -8742,7 +9206,7 @@ INSERT INTO omicron.public.db_metadata (
     version,
     target_version
 ) VALUES
-    (TRUE, NOW(), NOW(), '268.0.0', NULL)
+    (TRUE, NOW(), NOW(), '285.0.0', NULL)
 ON CONFLICT DO NOTHING;
 
 COMMIT;
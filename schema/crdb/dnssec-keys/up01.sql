@@ -0,0 +1,4 @@
+CREATE TYPE IF NOT EXISTS omicron.public.dnssec_key_role AS ENUM (
+    'ksk',
+    'zsk'
+);
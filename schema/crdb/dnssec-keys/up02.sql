@@ -0,0 +1,5 @@
+CREATE TYPE IF NOT EXISTS omicron.public.dnssec_key_state AS ENUM (
+    'published',
+    'active',
+    'retired'
+);
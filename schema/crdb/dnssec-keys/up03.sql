@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS omicron.public.dnssec_key (
+    id UUID PRIMARY KEY,
+    time_created TIMESTAMPTZ NOT NULL,
+    time_activated TIMESTAMPTZ,
+    time_retired TIMESTAMPTZ,
+    time_deleted TIMESTAMPTZ,
+    zone_name TEXT NOT NULL,
+    role omicron.public.dnssec_key_role NOT NULL,
+    state omicron.public.dnssec_key_state NOT NULL,
+    private_key TEXT NOT NULL
+);
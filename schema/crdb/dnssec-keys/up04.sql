@@ -0,0 +1,3 @@
+CREATE INDEX IF NOT EXISTS lookup_dnssec_key_by_zone
+    ON omicron.public.dnssec_key (zone_name, role)
+    WHERE time_deleted IS NULL;
@@ -0,0 +1,2 @@
+-- DO NOT EDIT. Generated by test_migration_verification_files.
+SELECT CAST(IF((SELECT true WHERE EXISTS (SELECT index_name FROM omicron.crdb_internal.table_indexes WHERE descriptor_name = 'dnssec_key' AND index_name = 'lookup_dnssec_key_by_zone')),'true','Schema change verification failed: index lookup_dnssec_key_by_zone on table dnssec_key does not exist') AS BOOL);
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS omicron.public.dnssec_config (
+    singleton BOOL NOT NULL PRIMARY KEY,
+    generation INT8 NOT NULL,
+    time_modified TIMESTAMPTZ NOT NULL,
+
+    CHECK (singleton = true)
+);
@@ -0,0 +1,12 @@
+-- Allow inbound DNS over TCP (as well as UDP) to the External DNS zones, so that
+-- clients can retry there when a UDP response is truncated.  See
+-- nexus/db-fixed-data/src/vpc_firewall_rule.rs.
+UPDATE omicron.public.vpc_firewall_rule
+SET
+  filter_protocols = ARRAY['udp','tcp'],
+  time_modified = NOW()
+WHERE
+  vpc_id = '001de000-074c-4000-8000-000000000000'
+  AND name = 'external-dns-inbound'
+  AND time_deleted IS NULL
+  AND filter_protocols = ARRAY['udp'];
@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS omicron.public.instance_network_tag (
+    instance_id UUID NOT NULL,
+    tag STRING(63) NOT NULL,
+    time_created TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (instance_id, tag)
+);
@@ -0,0 +1,2 @@
+CREATE INDEX IF NOT EXISTS lookup_instance_network_tag_by_tag
+    ON omicron.public.instance_network_tag (tag, instance_id);
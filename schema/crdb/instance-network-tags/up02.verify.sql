@@ -0,0 +1,2 @@
+-- DO NOT EDIT. Generated by test_migration_verification_files.
+SELECT CAST(IF((SELECT true WHERE EXISTS (SELECT index_name FROM omicron.crdb_internal.table_indexes WHERE descriptor_name = 'instance_network_tag' AND index_name = 'lookup_instance_network_tag_by_tag')),'true','Schema change verification failed: index lookup_instance_network_tag_by_tag on table instance_network_tag does not exist') AS BOOL);
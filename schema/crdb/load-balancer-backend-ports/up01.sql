@@ -0,0 +1,2 @@
+ALTER TABLE omicron.public.load_balancer_backend
+    ADD COLUMN IF NOT EXISTS ports INT4[] NOT NULL DEFAULT ARRAY[];
@@ -0,0 +1,6 @@
+ALTER TYPE
+ omicron.public.ip_kind
+ADD VALUE IF NOT EXISTS
+ 'load_balancer'
+AFTER
+ 'floating'
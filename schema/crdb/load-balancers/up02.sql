@@ -0,0 +1,5 @@
+CREATE TYPE IF NOT EXISTS omicron.public.load_balancer_backend_health AS ENUM (
+    'unknown',
+    'healthy',
+    'unhealthy'
+);
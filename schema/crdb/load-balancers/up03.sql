@@ -0,0 +1,20 @@
+CREATE TABLE IF NOT EXISTS omicron.public.load_balancer (
+    id UUID PRIMARY KEY,
+    name STRING(63) NOT NULL,
+    description STRING(512) NOT NULL,
+    time_created TIMESTAMPTZ NOT NULL,
+    time_modified TIMESTAMPTZ NOT NULL,
+    time_deleted TIMESTAMPTZ,
+    project_id UUID NOT NULL,
+    external_ip_id UUID NOT NULL,
+    ports INT4[] NOT NULL,
+    backend_tag STRING(63),
+    health_check_port INT4,
+    healthy_threshold INT4,
+    unhealthy_threshold INT4,
+
+    CONSTRAINT health_check_all_or_nothing CHECK (
+        (health_check_port IS NULL) = (healthy_threshold IS NULL) AND
+        (health_check_port IS NULL) = (unhealthy_threshold IS NULL)
+    )
+);
@@ -0,0 +1,5 @@
+CREATE UNIQUE INDEX IF NOT EXISTS lookup_load_balancer_by_project ON omicron.public.load_balancer (
+    project_id,
+    name
+) WHERE
+    time_deleted IS NULL;
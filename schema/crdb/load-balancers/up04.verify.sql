@@ -0,0 +1,2 @@
+-- DO NOT EDIT. Generated by test_migration_verification_files.
+SELECT CAST(IF((SELECT true WHERE EXISTS (SELECT index_name FROM omicron.crdb_internal.table_indexes WHERE descriptor_name = 'load_balancer' AND index_name = 'lookup_load_balancer_by_project')),'true','Schema change verification failed: index lookup_load_balancer_by_project on table load_balancer does not exist') AS BOOL);
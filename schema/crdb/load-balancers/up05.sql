@@ -0,0 +1,13 @@
+CREATE TABLE IF NOT EXISTS omicron.public.load_balancer_backend (
+    load_balancer_id UUID NOT NULL,
+    instance_id UUID NOT NULL,
+    time_created TIMESTAMPTZ NOT NULL,
+    time_modified TIMESTAMPTZ NOT NULL,
+    explicit BOOL NOT NULL,
+    health omicron.public.load_balancer_backend_health NOT NULL,
+    consecutive_successes INT4 NOT NULL,
+    consecutive_failures INT4 NOT NULL,
+    frontend_vmm_id UUID,
+
+    PRIMARY KEY (load_balancer_id, instance_id)
+);
@@ -0,0 +1,3 @@
+CREATE INDEX IF NOT EXISTS lookup_load_balancer_backend_by_instance ON omicron.public.load_balancer_backend (
+    instance_id
+);
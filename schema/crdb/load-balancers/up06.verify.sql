@@ -0,0 +1,2 @@
+-- DO NOT EDIT. Generated by test_migration_verification_files.
+SELECT CAST(IF((SELECT true WHERE EXISTS (SELECT index_name FROM omicron.crdb_internal.table_indexes WHERE descriptor_name = 'load_balancer_backend' AND index_name = 'lookup_load_balancer_backend_by_instance')),'true','Schema change verification failed: index lookup_load_balancer_backend_by_instance on table load_balancer_backend does not exist') AS BOOL);
@@ -0,0 +1,6 @@
+ALTER TYPE
+ omicron.public.alert_class
+ADD VALUE IF NOT EXISTS
+ 'instance.failed'
+AFTER
+ 'test.quux.bar.baz'
@@ -0,0 +1,6 @@
+ALTER TYPE
+ omicron.public.alert_class
+ADD VALUE IF NOT EXISTS
+ 'instance.migration.completed'
+AFTER
+ 'instance.auto_restarted'
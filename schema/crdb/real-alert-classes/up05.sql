@@ -0,0 +1,6 @@
+ALTER TYPE
+ omicron.public.alert_class
+ADD VALUE IF NOT EXISTS
+ 'storage.region_replacement.started'
+AFTER
+ 'instance.migration.failed'
@@ -0,0 +1,6 @@
+ALTER TYPE
+ omicron.public.alert_class
+ADD VALUE IF NOT EXISTS
+ 'hardware.physical_disk.expunged'
+AFTER
+ 'storage.region_replacement.finished'
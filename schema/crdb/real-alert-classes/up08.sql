@@ -0,0 +1,6 @@
+ALTER TYPE
+ omicron.public.alert_class
+ADD VALUE IF NOT EXISTS
+ 'hardware.sled.policy_changed'
+AFTER
+ 'hardware.physical_disk.expunged'
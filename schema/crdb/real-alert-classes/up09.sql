@@ -0,0 +1,6 @@
+ALTER TYPE
+ omicron.public.alert_class
+ADD VALUE IF NOT EXISTS
+ 'support_bundle.ready'
+AFTER
+ 'hardware.sled.policy_changed'
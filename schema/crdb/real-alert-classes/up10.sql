@@ -0,0 +1,6 @@
+ALTER TYPE
+ omicron.public.alert_class
+ADD VALUE IF NOT EXISTS
+ 'update.status_changed'
+AFTER
+ 'support_bundle.ready'
@@ -0,0 +1,2 @@
+-- DO NOT EDIT. Generated by test_migration_verification_files.
+SELECT CAST(IF((SELECT true WHERE EXISTS (SELECT index_name FROM omicron.crdb_internal.table_indexes WHERE descriptor_name = 'service_account' AND index_name = 'lookup_service_account_by_silo')),'true','Schema change verification failed: index lookup_service_account_by_silo on table service_account does not exist') AS BOOL);
@@ -0,0 +1,15 @@
+CREATE TABLE IF NOT EXISTS omicron.public.service_account_token (
+    /* Identity metadata (resource) */
+    id UUID PRIMARY KEY,
+    name STRING(63) NOT NULL,
+    description STRING(512) NOT NULL,
+    time_created TIMESTAMPTZ NOT NULL,
+    time_modified TIMESTAMPTZ NOT NULL,
+    time_deleted TIMESTAMPTZ,
+
+    service_account_id UUID NOT NULL,
+    token STRING(40) NOT NULL,
+    time_expires TIMESTAMPTZ,
+    read_only BOOL NOT NULL,
+    project_ids UUID[]
+);
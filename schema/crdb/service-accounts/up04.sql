@@ -0,0 +1,3 @@
+CREATE UNIQUE INDEX IF NOT EXISTS lookup_service_account_token_by_service_account
+    ON omicron.public.service_account_token (service_account_id, name)
+    WHERE time_deleted IS NULL;
@@ -0,0 +1,2 @@
+-- DO NOT EDIT. Generated by test_migration_verification_files.
+SELECT CAST(IF((SELECT true WHERE EXISTS (SELECT index_name FROM omicron.crdb_internal.table_indexes WHERE descriptor_name = 'service_account_token' AND index_name = 'service_account_token_unique')),'true','Schema change verification failed: index service_account_token_unique on table service_account_token does not exist') AS BOOL);
@@ -0,0 +1,6 @@
+ALTER TYPE
+ omicron.public.audit_log_actor_kind
+ADD VALUE IF NOT EXISTS
+ 'service_account'
+AFTER
+ 'scim';
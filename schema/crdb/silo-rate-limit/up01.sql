@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS omicron.public.silo_rate_limit (
+    silo_id UUID PRIMARY KEY,
+    time_created TIMESTAMPTZ NOT NULL,
+    time_modified TIMESTAMPTZ NOT NULL,
+
+    -- null means use the fleet-wide default from Nexus configuration
+    requests_per_second INT8 CHECK (requests_per_second > 0),
+    burst INT8 CHECK (burst > 0)
+);
@@ -0,0 +1,5 @@
+CREATE TYPE IF NOT EXISTS omicron.public.sled_evacuation_state AS ENUM (
+    'in_progress',
+    'completed',
+    'cancelled'
+);
@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS omicron.public.sled_evacuation (
+    id UUID PRIMARY KEY,
+    sled_id UUID NOT NULL,
+    time_started TIMESTAMPTZ NOT NULL,
+    time_finished TIMESTAMPTZ,
+    state omicron.public.sled_evacuation_state NOT NULL,
+    instances_total INT8 NOT NULL,
+    migrations_started INT8 NOT NULL DEFAULT 0,
+    last_error TEXT,
+
+    CONSTRAINT time_finished_iff_not_in_progress CHECK (
+        (state = 'in_progress') = (time_finished IS NULL)
+    )
+);
@@ -0,0 +1,3 @@
+CREATE UNIQUE INDEX IF NOT EXISTS one_in_progress_evacuation_per_sled
+    ON omicron.public.sled_evacuation (sled_id)
+    WHERE state = 'in_progress';
@@ -0,0 +1,2 @@
+CREATE INDEX IF NOT EXISTS lookup_sled_evacuation_by_sled
+    ON omicron.public.sled_evacuation (sled_id, time_started);
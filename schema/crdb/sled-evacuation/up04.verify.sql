@@ -0,0 +1,2 @@
+-- DO NOT EDIT. Generated by test_migration_verification_files.
+SELECT CAST(IF((SELECT true WHERE EXISTS (SELECT index_name FROM omicron.crdb_internal.table_indexes WHERE descriptor_name = 'sled_evacuation' AND index_name = 'lookup_sled_evacuation_by_sled')),'true','Schema change verification failed: index lookup_sled_evacuation_by_sled on table sled_evacuation does not exist') AS BOOL);
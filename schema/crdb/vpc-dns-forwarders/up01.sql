@@ -0,0 +1,2 @@
+ALTER TABLE omicron.public.vpc_dns_config
+    ADD COLUMN IF NOT EXISTS forwarders INET[] NOT NULL DEFAULT ARRAY[];
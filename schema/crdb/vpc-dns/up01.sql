@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS omicron.public.vpc_dns_record (
+    id UUID PRIMARY KEY,
+    time_created TIMESTAMPTZ NOT NULL,
+    time_modified TIMESTAMPTZ NOT NULL,
+    time_deleted TIMESTAMPTZ,
+    vpc_id UUID NOT NULL,
+    name STRING(63) NOT NULL,
+    address INET NOT NULL
+);
@@ -0,0 +1,3 @@
+CREATE INDEX IF NOT EXISTS lookup_vpc_dns_record_by_vpc
+    ON omicron.public.vpc_dns_record (vpc_id, name)
+    WHERE time_deleted IS NULL;
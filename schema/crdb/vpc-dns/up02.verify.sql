@@ -0,0 +1,2 @@
+-- DO NOT EDIT. Generated by test_migration_verification_files.
+SELECT CAST(IF((SELECT true WHERE EXISTS (SELECT index_name FROM omicron.crdb_internal.table_indexes WHERE descriptor_name = 'vpc_dns_record' AND index_name = 'lookup_vpc_dns_record_by_vpc')),'true','Schema change verification failed: index lookup_vpc_dns_record_by_vpc on table vpc_dns_record does not exist') AS BOOL);
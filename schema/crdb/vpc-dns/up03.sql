@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS omicron.public.vpc_dns_config (
+    singleton BOOL NOT NULL PRIMARY KEY,
+    generation INT8 NOT NULL,
+    time_created TIMESTAMPTZ NOT NULL,
+    zones JSONB NOT NULL,
+
+    CHECK (singleton = true)
+);
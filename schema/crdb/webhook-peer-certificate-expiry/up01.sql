@@ -0,0 +1,2 @@
+ALTER TABLE omicron.public.webhook_delivery_attempt
+    ADD COLUMN IF NOT EXISTS time_peer_cert_expires TIMESTAMPTZ;
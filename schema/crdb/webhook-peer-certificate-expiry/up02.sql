@@ -0,0 +1,5 @@
+CREATE INDEX IF NOT EXISTS lookup_webhook_peer_cert_expiry
+ON omicron.public.webhook_delivery_attempt (
+    rx_id, time_created DESC
+) WHERE
+    time_peer_cert_expires IS NOT NULL;
@@ -21,7 +21,7 @@ use omicron_common::api::internal::{
 };
 use sled_agent_types_versions::{
     latest, v1, v4, v6, v7, v9, v10, v11, v12, v14, v16, v17, v18, v20, v22,
-    v24, v25, v26, v28, v29, v30, v31, v32, v33, v34, v37, v39,
+    v24, v25, v26, v28, v29, v30, v31, v32, v33, v34, v37, v39, v40, v41,
 };
 use sled_diagnostics::SledDiagnosticsQueryOutput;
 use slog_error_chain::InlineErrorChain;
@@ -38,6 +38,8 @@ api_versions!([
     // |  example for the next person.
     // v
     // (next_int, IDENT),
+    (43, ADD_LEDGER_REPORT_TO_INVENTORY),
+    (42, ADD_FIREWALL_RULE_IDS),
     (41, ADD_INSTANCE_PRIMARY_NIC_MTU),
     (40, ADD_FMD_TO_INVENTORY),
     (39, BOOTSTORE_SERVICE_NAT_GENERATION),
@@ -445,7 +447,7 @@ pub trait SledAgentApi {
         operation_id = "vmm_register",
         method = PUT,
         path = "/vmms/{propolis_id}",
-        versions = VERSION_ADD_INSTANCE_PRIMARY_NIC_MTU..
+        versions = VERSION_ADD_FIREWALL_RULE_IDS..
     }]
     async fn vmm_register(
         rqctx: RequestContext<Self::Context>,
@@ -453,6 +455,20 @@ pub trait SledAgentApi {
         body: TypedBody<latest::instance::InstanceEnsureBody>,
     ) -> Result<HttpResponseOk<latest::instance::SledVmmState>, HttpError>;
 
+    #[endpoint {
+        operation_id = "vmm_register",
+        method = PUT,
+        path = "/vmms/{propolis_id}",
+        versions = VERSION_ADD_INSTANCE_PRIMARY_NIC_MTU..VERSION_ADD_FIREWALL_RULE_IDS
+    }]
+    async fn vmm_register_v41(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<latest::instance::VmmPathParam>,
+        body: TypedBody<v41::instance::InstanceEnsureBody>,
+    ) -> Result<HttpResponseOk<latest::instance::SledVmmState>, HttpError> {
+        Self::vmm_register(rqctx, path_params, body.map(Into::into)).await
+    }
+
     #[endpoint {
         operation_id = "vmm_register",
         method = PUT,
@@ -464,7 +480,7 @@ pub trait SledAgentApi {
         path_params: Path<latest::instance::VmmPathParam>,
         body: TypedBody<v32::instance::InstanceEnsureBody>,
     ) -> Result<HttpResponseOk<latest::instance::SledVmmState>, HttpError> {
-        Self::vmm_register(rqctx, path_params, body.map(Into::into)).await
+        Self::vmm_register_v41(rqctx, path_params, body.map(Into::into)).await
     }
 
     #[endpoint {
@@ -753,7 +769,7 @@ pub trait SledAgentApi {
     #[endpoint {
         method = PUT,
         path = "/vpc/{vpc_id}/firewall/rules",
-        versions = VERSION_ADD_ICMPV6_FIREWALL_SUPPORT..,
+        versions = VERSION_ADD_FIREWALL_RULE_IDS..,
     }]
     async fn vpc_firewall_rules_put(
         rqctx: RequestContext<Self::Context>,
@@ -761,6 +777,21 @@ pub trait SledAgentApi {
         body: TypedBody<latest::firewall_rules::VpcFirewallRulesEnsureBody>,
     ) -> Result<HttpResponseUpdatedNoContent, HttpError>;
 
+    #[endpoint {
+        operation_id = "vpc_firewall_rules_put",
+        method = PUT,
+        path = "/vpc/{vpc_id}/firewall/rules",
+        versions = VERSION_ADD_ICMPV6_FIREWALL_SUPPORT..VERSION_ADD_FIREWALL_RULE_IDS,
+    }]
+    async fn vpc_firewall_rules_put_v31(
+        rqctx: RequestContext<Self::Context>,
+        path_params: Path<latest::instance::VpcPathParam>,
+        body: TypedBody<v31::firewall_rules::VpcFirewallRulesEnsureBody>,
+    ) -> Result<HttpResponseUpdatedNoContent, HttpError> {
+        Self::vpc_firewall_rules_put(rqctx, path_params, body.map(Into::into))
+            .await
+    }
+
     #[endpoint {
         operation_id = "vpc_firewall_rules_put",
         method = PUT,
@@ -774,7 +805,7 @@ pub trait SledAgentApi {
     ) -> Result<HttpResponseUpdatedNoContent, HttpError> {
         let body =
             body.map(v31::firewall_rules::VpcFirewallRulesEnsureBody::from);
-        Self::vpc_firewall_rules_put(rqctx, path_params, body).await
+        Self::vpc_firewall_rules_put_v31(rqctx, path_params, body).await
     }
 
     #[endpoint {
@@ -1053,12 +1084,27 @@ pub trait SledAgentApi {
     #[endpoint {
         method = GET,
         path = "/inventory",
-        versions = VERSION_ADD_FMD_TO_INVENTORY..,
+        versions = VERSION_ADD_LEDGER_REPORT_TO_INVENTORY..,
     }]
     async fn inventory(
         rqctx: RequestContext<Self::Context>,
     ) -> Result<HttpResponseOk<latest::inventory::Inventory>, HttpError>;
 
+    /// Fetch basic information about this sled
+    #[endpoint {
+        operation_id = "inventory",
+        method = GET,
+        path = "/inventory",
+        versions = VERSION_ADD_FMD_TO_INVENTORY..VERSION_ADD_LEDGER_REPORT_TO_INVENTORY,
+    }]
+    async fn inventory_v40(
+        rqctx: RequestContext<Self::Context>,
+    ) -> Result<HttpResponseOk<v40::inventory::Inventory>, HttpError> {
+        Self::inventory(rqctx).await.map(|HttpResponseOk(inv)| {
+            HttpResponseOk(v40::inventory::Inventory::from(inv))
+        })
+    }
+
     /// Fetch basic information about this sled
     #[endpoint {
         operation_id = "inventory",
@@ -1069,7 +1115,7 @@ pub trait SledAgentApi {
     async fn inventory_v37(
         rqctx: RequestContext<Self::Context>,
     ) -> Result<HttpResponseOk<v37::inventory::Inventory>, HttpError> {
-        Self::inventory(rqctx).await.map(|HttpResponseOk(inv)| {
+        Self::inventory_v40(rqctx).await.map(|HttpResponseOk(inv)| {
             HttpResponseOk(v37::inventory::Inventory::from(inv))
         })
     }
@@ -6,6 +6,7 @@ use camino::Utf8PathBuf;
 use illumos_utils::zpool::PathInPool;
 use key_manager::StorageKeyRequester;
 use omicron_common::disk::DatasetName;
+use omicron_ledger::LedgerReport;
 use sled_agent_types::artifact::ArtifactConfig;
 use sled_agent_types::inventory::ConfigReconcilerInventory;
 use sled_agent_types::inventory::ConfigReconcilerInventoryStatus;
@@ -437,6 +438,8 @@ impl ConfigReconcilerHandle {
         log: &Logger,
     ) -> Result<ReconcilerInventory, InventoryError> {
         let ledgered_sled_config = self.ledgered_sled_config()?;
+        let sled_config_ledger_report =
+            self.ledger_task.get().and_then(LedgerTaskHandle::ledger_report);
         let zpools = self.currently_managed_zpools_rx.to_inventory(log).await;
 
         let datasets = self
@@ -459,6 +462,7 @@ impl ConfigReconcilerHandle {
                 .collect(),
             datasets,
             ledgered_sled_config,
+            sled_config_ledger_report,
             reconciler_status,
             last_reconciliation,
         })
@@ -477,6 +481,7 @@ pub struct ReconcilerInventory {
     pub zpools: Vec<InventoryZpool>,
     pub datasets: Vec<InventoryDataset>,
     pub ledgered_sled_config: Option<OmicronSledConfig>,
+    pub sled_config_ledger_report: Option<LedgerReport>,
     pub reconciler_status: ConfigReconcilerInventoryStatus,
     pub last_reconciliation: Option<ConfigReconcilerInventory>,
 }
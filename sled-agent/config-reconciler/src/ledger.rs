@@ -9,6 +9,7 @@ use dropshot::HttpError;
 use omicron_common::api::external::Generation;
 use omicron_ledger as ledger;
 use omicron_ledger::Ledger;
+use omicron_ledger::LedgerReport;
 use sled_agent_types::artifact::ArtifactConfig;
 use sled_agent_types::inventory::HostPhase2DesiredSlots;
 use sled_agent_types::inventory::OmicronSledConfig;
@@ -120,6 +121,7 @@ pub(crate) enum CurrentSledConfig {
 pub(crate) struct LedgerTaskHandle {
     request_tx: mpsc::Sender<LedgerTaskRequest>,
     current_config_rx: watch::Receiver<CurrentSledConfig>,
+    ledger_report_rx: watch::Receiver<Option<LedgerReport>>,
 }
 
 impl LedgerTaskHandle {
@@ -152,6 +154,10 @@ impl LedgerTaskHandle {
         let (current_config_tx, current_config_rx) =
             watch::channel(CurrentSledConfig::WaitingForInternalDisks);
 
+        // We don't have a report on the ledger's copies until we've found
+        // internal disks and tried to load it.
+        let (ledger_report_tx, ledger_report_rx) = watch::channel(None);
+
         // The measurement handler relies on the ledger task running.
         // Give a channel to wait for that to happen instead of relying
         // on polling.
@@ -163,13 +169,18 @@ impl LedgerTaskHandle {
                 request_rx,
                 internal_disks_rx,
                 current_config_tx,
+                ledger_report_tx,
                 log,
             }
             .run(ledger_run_tx),
         );
 
         (
-            Self { request_tx, current_config_rx: current_config_rx.clone() },
+            Self {
+                request_tx,
+                current_config_rx: current_config_rx.clone(),
+                ledger_report_rx,
+            },
             current_config_rx,
             ledger_run_rx,
         )
@@ -179,6 +190,12 @@ impl LedgerTaskHandle {
         self.current_config_rx.borrow().clone()
     }
 
+    /// Returns the condition of each copy of the sled config ledger, as found
+    /// when it was loaded, or `None` if it hasn't been loaded yet.
+    pub(crate) fn ledger_report(&self) -> Option<LedgerReport> {
+        self.ledger_report_rx.borrow().clone()
+    }
+
     pub async fn set_new_config(
         &self,
         new_config: OmicronSledConfig,
@@ -257,6 +274,7 @@ struct LedgerTask<T> {
     request_rx: mpsc::Receiver<LedgerTaskRequest>,
     internal_disks_rx: InternalDisksReceiver,
     current_config_tx: watch::Sender<CurrentSledConfig>,
+    ledger_report_tx: watch::Sender<Option<LedgerReport>>,
     log: Logger,
 }
 
@@ -566,11 +584,12 @@ impl<T: SledAgentArtifactStore> LedgerTask<T> {
             .await
             {
                 // If we're still waiting, fall through to the `select!` below.
-                CurrentSledConfig::WaitingForInternalDisks => (),
+                (CurrentSledConfig::WaitingForInternalDisks, _) => (),
                 // Otherwise, we're done waiting: set our loaded config (which
                 // might be `WaitingForInitialConfig` if we have disks but they
                 // have no ledger contents!).
-                config => {
+                (config, report) => {
+                    self.ledger_report_tx.send_modify(|r| *r = report);
                     self.current_config_tx.send_modify(|c| *c = config);
                     return Ok(());
                 }
@@ -635,9 +654,9 @@ fn config_artifact_hashes(
 async fn load_sled_config(
     config_datasets: &[Utf8PathBuf],
     log: &Logger,
-) -> CurrentSledConfig {
+) -> (CurrentSledConfig, Option<LedgerReport>) {
     if config_datasets.is_empty() {
-        return CurrentSledConfig::WaitingForInternalDisks;
+        return (CurrentSledConfig::WaitingForInternalDisks, None);
     }
 
     // First try to load the ledger from our expected path(s).
@@ -649,7 +668,8 @@ async fn load_sled_config(
         log, "Attempting to load sled config from ledger";
         "paths" => ?paths,
     );
-    match read_ledgered_sled_config(log, paths).await {
+    let (config, report) = read_ledgered_sled_config(log, paths).await;
+    let config = match config {
         Some(config) => CurrentSledConfig::Ledgered(Box::new(config)),
         None => {
             // We have no ledger; we must be waiting for RSS (if we're
@@ -658,7 +678,8 @@ async fn load_sled_config(
             info!(log, "No sled config ledger exists");
             CurrentSledConfig::WaitingForInitialConfig
         }
-    }
+    };
+    (config, Some(report))
 }
 
 // `LedgerTask` should not exit in production, but may exit during tests
@@ -6,6 +6,7 @@
 
 use camino::Utf8PathBuf;
 use omicron_ledger::Ledger;
+use omicron_ledger::LedgerReport;
 use omicron_ledger::Ledgerable;
 use serde::Deserialize;
 use serde::Serialize;
@@ -75,6 +76,9 @@ version_conversion_chain!(
 /// Read the ledgered [`OmicronSledConfig`], converting from older versions if
 /// needed.
 ///
+/// Also returns a [`LedgerReport`] describing the condition of each copy of
+/// the ledger, as found before any conversion.
+///
 /// # Panics
 ///
 /// This panics if we're able to read a config (of any known older version) but
@@ -90,21 +94,27 @@ version_conversion_chain!(
 pub(super) async fn read_ledgered_sled_config(
     log: &Logger,
     paths: Vec<Utf8PathBuf>,
-) -> Option<OmicronSledConfig> {
+) -> (Option<OmicronSledConfig>, LedgerReport) {
     // Attempt to read the ledger as the current version; if this succeeds,
     // we're done.
-    if let Some(config) = Ledger::new(log, paths.clone()).await {
+    let (ledger, report) = Ledger::new_with_report(log, paths.clone()).await;
+    if let Some(config) = ledger {
         info!(log, "Ledger of sled config exists");
-        return Some(config.into_inner());
+        return (Some(config.into_inner()), report);
     }
 
     // Try to read the config as the previous version; if we have an older
     // version on disk, this will recurse until we get to it, but then convert
-    // it up through our previous version before returning.
-    let prev_version = try_ledgered_config_versions_chain::<
+    // it up through our previous version before returning. In that case, the
+    // report from the version we found describes the copies on disk better
+    // than the one above (which can only say that they aren't current).
+    let Some((prev_version, report)) = try_ledgered_config_versions_chain::<
         <OmicronSledConfig as VersionConversionChain>::Previous,
     >(log, paths.clone())
-    .await?;
+    .await
+    else {
+        return (None, report);
+    };
 
     let current_version = prev_version.try_into().unwrap_or_else(|e| {
         panic!(
@@ -114,7 +124,7 @@ pub(super) async fn read_ledgered_sled_config(
         );
     });
 
-    Some(write_converted_ledger(log, paths, current_version).await)
+    (Some(write_converted_ledger(log, paths, current_version).await), report)
 }
 
 /// Reading old ledgers from disk in the face of multiple version changes is
@@ -152,7 +162,7 @@ pub(super) async fn read_ledgered_sled_config(
 async fn try_ledgered_config_versions_chain<T>(
     log: &Logger,
     paths: Vec<Utf8PathBuf>,
-) -> Option<T>
+) -> Option<(T, LedgerReport)>
 where
     T: VersionConversionChain,
 {
@@ -160,16 +170,18 @@ where
         return None;
     }
 
-    if let Some(config) = Ledger::<T>::new(log, paths.clone()).await {
+    let (ledger, report) =
+        Ledger::<T>::new_with_report(log, paths.clone()).await;
+    if let Some(config) = ledger {
         info!(
             log,
             "successfully read ledgered config as version {}",
             T::DESCRIPTION
         );
-        return Some(config.into_inner());
+        return Some((config.into_inner(), report));
     }
 
-    let old_config =
+    let (old_config, report) =
         try_ledgered_config_versions_chain::<T::Previous>(log, paths).await?;
 
     match old_config.try_into() {
@@ -179,7 +191,7 @@ where
                 "converted config read from ledger to version {}",
                 T::DESCRIPTION
             );
-            Some(config)
+            Some((config, report))
         }
         Err(err) => {
             panic!(
@@ -336,9 +348,10 @@ pub(super) mod tests {
         .expect("read v14 config");
 
         // Reading old configs should rewrite the file to match the newest
-        // version.
+        // version. The ledger adds a checksum when writing, so we compare
+        // the rest of the rewritten file against this.
         let expected_rewritten =
-            serde_json::to_string(&expected_config).expect("serialized config");
+            serde_json::to_value(&expected_config).expect("serialized config");
 
         // If no conversion was necessary, we should keep the same contents.
         // This is semantically equivalent to `expected_rewritten` but may be
@@ -365,12 +378,13 @@ pub(super) mod tests {
             // Attempt to read `my-ledger.json`; this should give us back a
             // current-version `OmicronSledConfig` and also have rewritten the
             // config.
-            let converted_config = read_ledgered_sled_config(
+            let (converted_config, _report) = read_ledgered_sled_config(
                 log,
                 vec![dst_ledger_path.to_path_buf()],
             )
-            .await
-            .expect("read and converted ledger");
+            .await;
+            let converted_config =
+                converted_config.expect("read and converted ledger");
             assert_eq!(expected_config, converted_config);
 
             // We should only rewrite the file if we converted it.
@@ -379,8 +393,25 @@ pub(super) mod tests {
                 .expect("read tempdir ledger");
             if data != expected_unchanged {
                 // The data changed - we must have done a conversion. Assert it
-                // matches what we expect.
-                assert_eq!(data, expected_rewritten);
+                // matches what we expect, and that it was written with a
+                // checksum.
+                let mut rewritten: serde_json::Value =
+                    serde_json::from_str(&data)
+                        .expect("parsed rewritten ledger");
+                let checksum = rewritten
+                    .as_object_mut()
+                    .and_then(|obj| obj.shift_remove("ledger_checksum"));
+                assert!(checksum.is_some(), "rewritten ledger has no checksum");
+                assert_eq!(rewritten, expected_rewritten);
+
+                // The checksum must also be valid.
+                let reread = v14::inventory::OmicronSledConfig::read_from(
+                    log,
+                    &dst_ledger_path.to_path_buf(),
+                )
+                .await
+                .expect("read rewritten ledger");
+                assert_eq!(expected_config, reread);
             }
         }
 
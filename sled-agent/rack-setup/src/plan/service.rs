@@ -1532,6 +1532,7 @@ mod tests {
                 zpools: vec![],
                 datasets: vec![],
                 ledgered_sled_config: None,
+                sled_config_ledger_report: None,
                 reconciler_status: ConfigReconcilerInventoryStatus::NotYetRun,
                 last_reconciliation: None,
                 file_source_resolver:
@@ -1941,6 +1941,7 @@ mod test {
                 zpools: vec![],
                 datasets: vec![],
                 ledgered_sled_config: None,
+                sled_config_ledger_report: None,
                 reconciler_status: ConfigReconcilerInventoryStatus::NotYetRun,
                 last_reconciliation: None,
                 file_source_resolver:
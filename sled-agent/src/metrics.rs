@@ -4,11 +4,15 @@
 
 //! Metrics produced by the sled-agent for collection by oximeter.
 
+use illumos_utils::opte::PortManager;
 use illumos_utils::running_zone::RunningZone;
 use omicron_common::api::internal::nexus::ProducerEndpoint;
 use omicron_common::api::internal::nexus::ProducerKind;
 use omicron_common::api::internal::shared::SledIdentifiers;
 use omicron_uuid_kinds::GenericUuid;
+use oximeter::MetricsError;
+use oximeter::Sample;
+use oximeter::types::Cumulative;
 use oximeter_instruments::http::HttpService;
 use oximeter_instruments::http::LatencyTracker;
 use oximeter_instruments::kstat;
@@ -25,6 +29,7 @@ use oximeter_instruments::kstat::zone::ZoneTarget;
 use oximeter_instruments::zfs::usage::ZfsUsageProducer;
 use oximeter_producer::LogConfig;
 use oximeter_producer::Server as ProducerServer;
+use sled_agent_types::inventory::NetworkInterfaceKind;
 use slog::Logger;
 use std::collections::HashMap;
 use std::collections::hash_map::Entry;
@@ -35,6 +40,8 @@ use tokio::sync::mpsc;
 use tokio::sync::mpsc::error::TrySendError;
 use uuid::Uuid;
 
+oximeter::use_timeseries!("vpc-firewall-rule.toml");
+
 type TrackedLinks = HashMap<String, Target>;
 
 /// The interval on which we ask `oximeter` to poll us for metric data.
@@ -162,11 +169,56 @@ fn get_collection_details(kind: &str) -> CollectionDetails {
     }
 }
 
+/// Produces the hit counters of the VPC firewall rules installed on this
+/// sled's OPTE ports.
+#[derive(Clone, Debug)]
+struct FirewallRuleHitsProducer {
+    sled_id: Uuid,
+    port_manager: PortManager,
+}
+
+impl oximeter::Producer for FirewallRuleHitsProducer {
+    fn produce(
+        &mut self,
+    ) -> Result<Box<dyn Iterator<Item = Sample>>, MetricsError> {
+        let hits = self
+            .port_manager
+            .firewall_rule_hits()
+            .map_err(|e| MetricsError::DatumError(e.to_string()))?;
+        let samples = hits
+            .into_iter()
+            .map(|hits| {
+                let (interface_kind, parent_id) = match hits.kind {
+                    NetworkInterfaceKind::Instance { id } => ("instance", id),
+                    NetworkInterfaceKind::Service { id } => ("service", id),
+                    NetworkInterfaceKind::Probe { id } => ("probe", id),
+                };
+                let target = vpc_firewall_rule::VpcFirewallRule {
+                    rule_id: hits.rule_id,
+                    interface_id: hits.interface_id,
+                    interface_kind: interface_kind.into(),
+                    parent_id,
+                    sled_id: self.sled_id,
+                };
+                let metric = vpc_firewall_rule::Hits {
+                    datum: Cumulative::with_start_time(
+                        hits.start_time,
+                        hits.hits,
+                    ),
+                };
+                Sample::new(&target, &metric)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Box::new(samples.into_iter()))
+    }
+}
+
 /// The main task used to collect and publish sled-agent metrics.
 async fn metrics_task(
     sled_identifiers: SledIdentifiers,
     kstat_sampler: KstatSampler,
     server: ProducerServer,
+    port_manager: PortManager,
     log: Logger,
     mut rx: mpsc::Receiver<Message>,
 ) {
@@ -279,6 +331,15 @@ async fn metrics_task(
                         .registry()
                         .register_producer(zfs_producer.clone())
                         .expect("actually infallible");
+
+                    let firewall_producer = FirewallRuleHitsProducer {
+                        sled_id,
+                        port_manager: port_manager.clone(),
+                    };
+                    server
+                        .registry()
+                        .register_producer(firewall_producer)
+                        .expect("actually infallible");
                 }
             }
         }
@@ -569,6 +630,7 @@ impl MetricsManager {
         log: &Logger,
         identifiers: SledIdentifiers,
         address: Ipv6Addr,
+        port_manager: PortManager,
     ) -> Result<Self, Error> {
         let sampler = KstatSampler::new(log).map_err(Error::Kstat)?;
         let server = start_producer_server(&log, identifiers.sled_id, address)?;
@@ -601,6 +663,7 @@ impl MetricsManager {
             identifiers,
             sampler,
             server,
+            port_manager,
             task_log,
             rx,
         ));
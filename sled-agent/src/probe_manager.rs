@@ -368,6 +368,7 @@ impl ProbeManagerInner {
             nic,
             external_ips: &external_ips,
             firewall_rules: &[ResolvedVpcFirewallRule {
+                id: None,
                 status: VpcFirewallRuleStatus::Enabled,
                 direction: VpcFirewallRuleDirection::Inbound,
                 targets: vec![nic.clone()],
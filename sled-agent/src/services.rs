@@ -2003,6 +2003,7 @@ impl ServiceManager {
                 RunningZone::boot(installed_zone).await?
             }
             OmicronZoneConfig {
+                id: zone_id,
                 zone_type:
                     OmicronZoneType::ExternalDns {
                         http_address,
@@ -2048,11 +2049,8 @@ impl ServiceManager {
                         "astring",
                         http_address.to_string(),
                     )
-                    .add_property(
-                        "dns_address",
-                        "astring",
-                        private_dns_address,
-                    );
+                    .add_property("dns_address", "astring", private_dns_address)
+                    .add_property("zone_id", "astring", zone_id.to_string());
                 let external_dns_service =
                     ServiceBuilder::new("oxide/external_dns").add_instance(
                         ServiceInstanceBuilder::new("default")
@@ -2253,6 +2251,7 @@ impl ServiceManager {
                 RunningZone::boot(installed_zone).await?
             }
             OmicronZoneConfig {
+                id: zone_id,
                 zone_type:
                     OmicronZoneType::InternalDns {
                         http_address,
@@ -2323,7 +2322,8 @@ impl ServiceManager {
                         "dns_address",
                         "astring",
                         dns_address.to_string(),
-                    );
+                    )
+                    .add_property("zone_id", "astring", zone_id.to_string());
                 let internal_dns_service =
                     ServiceBuilder::new("oxide/internal_dns").add_instance(
                         ServiceInstanceBuilder::new("default")
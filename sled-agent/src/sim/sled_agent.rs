@@ -982,6 +982,7 @@ impl SledAgent {
                 })
                 .unwrap_or_else(|_| vec![]),
             ledgered_sled_config: Some(sled_config.clone()),
+            sled_config_ledger_report: None,
             reconciler_status: ConfigReconcilerInventoryStatus::Idle {
                 completed_at: Utc::now() - Duration::from_secs(10),
                 ran_for: Duration::from_secs(3),
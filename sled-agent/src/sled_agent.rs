@@ -548,8 +548,18 @@ impl SledAgent {
             revision: baseboard.revision(),
             serial: baseboard.identifier().to_string(),
         };
-        let metrics_manager =
-            MetricsManager::new(&log, identifiers.clone(), *sled_address.ip())?;
+        // Create the PortManager to manage all the OPTE ports on the sled.
+        let port_manager = PortManager::new(
+            parent_log.new(o!("component" => "PortManager")),
+            *sled_address.ip(),
+        );
+
+        let metrics_manager = MetricsManager::new(
+            &log,
+            identifiers.clone(),
+            *sled_address.ip(),
+            port_manager.clone(),
+        )?;
 
         // Start tracking the underlay physical links.
         for link in underlay::find_chelsio_links(&config.data_links).await? {
@@ -580,12 +590,6 @@ impl SledAgent {
             ),
         }
 
-        // Create the PortManager to manage all the OPTE ports on the sled.
-        let port_manager = PortManager::new(
-            parent_log.new(o!("component" => "PortManager")),
-            *sled_address.ip(),
-        );
-
         // The VMM reservoir is configured with respect to what's left after
         // accounting for relatively fixed and predictable uses.
         // We expect certain amounts of memory to be set aside for kernel,
@@ -1286,6 +1290,7 @@ impl SledAgent {
             zpools,
             datasets,
             ledgered_sled_config,
+            sled_config_ledger_report,
             reconciler_status,
             last_reconciliation,
         } = self.inner.config_reconciler.inventory(&self.log).await?;
@@ -1303,6 +1308,7 @@ impl SledAgent {
             zpools,
             datasets,
             ledgered_sled_config,
+            sled_config_ledger_report,
             reconciler_status,
             last_reconciliation,
             file_source_resolver,
@@ -0,0 +1,11 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Version `ADD_FIREWALL_RULE_IDS` of the Sled Agent API.
+//!
+//! Resolved firewall rules now carry the ID of the VPC firewall rule they were
+//! derived from, so the sled agent can report per-rule hit counters.
+
+pub mod firewall_rules;
+pub mod instance;
@@ -0,0 +1,107 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use iddqd::IdOrdMap;
+use omicron_common::api::external::ByteCount;
+use omicron_common::snake_case_result;
+use omicron_common::snake_case_result::SnakeCaseResult;
+use omicron_ledger::LedgerReport;
+use omicron_uuid_kinds::SledUuid;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sled_hardware_types::{Baseboard, SledCpuFamily};
+use std::net::SocketAddrV6;
+
+use crate::v1::inventory::InventoryDataset;
+use crate::v1::inventory::InventoryDisk;
+use crate::v1::inventory::SledRole;
+use crate::v14::inventory::ConfigReconcilerInventoryStatus;
+use crate::v14::inventory::OmicronFileSourceResolverInventory;
+use crate::v14::inventory::OmicronSledConfig;
+use crate::v16::inventory::ConfigReconcilerInventory;
+use crate::v16::inventory::SingleMeasurementInventory;
+use crate::v24::inventory::InventoryZpool;
+use crate::v37;
+use crate::v40;
+use crate::v40::inventory::FmdInventory;
+use crate::v40::inventory::FmdInventoryError;
+
+/// Identity and basic status information about this sled agent
+#[derive(Clone, Debug, Deserialize, JsonSchema, Serialize)]
+pub struct Inventory {
+    pub sled_id: SledUuid,
+    pub sled_agent_address: SocketAddrV6,
+    pub sled_role: SledRole,
+    pub baseboard: Baseboard,
+    pub usable_hardware_threads: u32,
+    pub usable_physical_ram: ByteCount,
+    pub cpu_family: SledCpuFamily,
+    pub reservoir_size: ByteCount,
+    pub disks: Vec<InventoryDisk>,
+    pub zpools: Vec<InventoryZpool>,
+    pub datasets: Vec<InventoryDataset>,
+    pub ledgered_sled_config: Option<OmicronSledConfig>,
+    /// The condition of each copy of the sled config ledger, as found when
+    /// the sled agent loaded it at startup, and the outcome of any repair.
+    ///
+    /// `None` if the sled agent has not yet loaded the ledger.
+    pub sled_config_ledger_report: Option<LedgerReport>,
+    pub reconciler_status: ConfigReconcilerInventoryStatus,
+    pub last_reconciliation: Option<ConfigReconcilerInventory>,
+    pub file_source_resolver: OmicronFileSourceResolverInventory,
+    pub smf_services_enabled_not_online:
+        v37::inventory::SvcsEnabledNotOnlineResult,
+    pub reference_measurements: IdOrdMap<SingleMeasurementInventory>,
+    #[serde(with = "snake_case_result")]
+    #[schemars(
+        schema_with = "SnakeCaseResult::<FmdInventory, FmdInventoryError>::json_schema"
+    )]
+    pub fmd: Result<FmdInventory, FmdInventoryError>,
+}
+
+impl From<Inventory> for v40::inventory::Inventory {
+    fn from(value: Inventory) -> Self {
+        let Inventory {
+            sled_id,
+            sled_agent_address,
+            sled_role,
+            baseboard,
+            usable_hardware_threads,
+            usable_physical_ram,
+            cpu_family,
+            reservoir_size,
+            disks,
+            zpools,
+            datasets,
+            ledgered_sled_config,
+            sled_config_ledger_report: _,
+            reconciler_status,
+            last_reconciliation,
+            file_source_resolver,
+            smf_services_enabled_not_online,
+            reference_measurements,
+            fmd,
+        } = value;
+        Self {
+            sled_id,
+            sled_agent_address,
+            sled_role,
+            baseboard,
+            usable_hardware_threads,
+            usable_physical_ram,
+            cpu_family,
+            reservoir_size,
+            disks,
+            zpools,
+            datasets,
+            ledgered_sled_config,
+            reconciler_status,
+            last_reconciliation,
+            file_source_resolver,
+            smf_services_enabled_not_online,
+            reference_measurements,
+            fmd,
+        }
+    }
+}
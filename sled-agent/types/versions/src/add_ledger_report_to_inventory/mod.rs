@@ -0,0 +1,11 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Version `ADD_LEDGER_REPORT_TO_INVENTORY` of the Sled Agent API.
+//!
+//! This version adds the condition of each copy of the sled config ledger to
+//! the sled inventory response, so that a damaged copy on one M.2 is noticed
+//! before the other copy is lost too.
+
+pub mod inventory;
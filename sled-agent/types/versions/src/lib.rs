@@ -89,6 +89,10 @@ pub mod v4;
 pub mod v40;
 #[path = "add_instance_primary_nic_mtu/mod.rs"]
 pub mod v41;
+#[path = "add_firewall_rule_ids/mod.rs"]
+pub mod v42;
+#[path = "add_ledger_report_to_inventory/mod.rs"]
+pub mod v43;
 #[path = "add_probe_put_endpoint/mod.rs"]
 pub mod v6;
 #[path = "multicast_support/mod.rs"]
@@ -43,6 +43,7 @@ impl_typed_uuid_kinds! {
         Alert = {},
         AlertReceiver = {},
         AntiAffinityGroup = {},
+        AuditLogSink = {},
         BgpPeerConfigAllowExport = {},
         BgpPeerConfigAllowImport = {},
         BgpPeerConfigCommunity = {},
@@ -85,6 +86,8 @@ impl_typed_uuid_kinds! {
         ReconfiguratorSimOp = {},
         ReconfiguratorSimState = {},
         Region = {},
+        ServiceAccount = {},
+        ServiceAccountToken = {},
         SiloGroup = {},
         SiloUser = {},
         Sitrep = {},